@@ -0,0 +1,5 @@
+use multiversx_sc_meta::cli_main;
+
+fn main() {
+    cli_main::<wine_airdrop::AbiProvider>();
+}
\ No newline at end of file
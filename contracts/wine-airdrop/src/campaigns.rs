@@ -0,0 +1,223 @@
+use multiversx_sc::derive_imports::*;
+use multiversx_sc::imports::*;
+use wine_common::errors::*;
+use wine_roles::Role;
+
+// Deep enough for trees of over a million recipients
+pub const MAX_PROOF_LENGTH: usize = 24;
+
+#[derive(TopEncode, TopDecode, TypeAbi, PartialEq, Debug)]
+pub struct Campaign<M: ManagedTypeApi> {
+    // Fungible reward token, or the wine collection with `token_nonce` for NFTs
+    pub token: EgldOrEsdtTokenIdentifier<M>,
+    pub token_nonce: u64,
+    pub merkle_root: ManagedByteArray<M, 32>,
+    pub total_amount: BigUint<M>,
+    pub claimed_amount: BigUint<M>,
+    // No claims after this timestamp
+    pub expiry_timestamp: u64,
+    pub clawed_back: bool,
+}
+
+// Airdrops to addresses committed to in a Merkle tree, e.g. early registry producers or top
+// raters. Each leaf is keccak256(address | amount as big-endian bytes) and parent nodes hash
+// their two children in ascending order, so proofs carry no left/right flags. Admins fund
+// a campaign with its root, and take back what is left unclaimed once it expires.
+#[multiversx_sc::module]
+pub trait CampaignsModule: wine_roles::RolesModule {
+    // Funded with the payment: any fungible token, or units of one NFT/SFT nonce
+    #[payable("*")]
+    #[endpoint(createCampaign)]
+    fn create_campaign(
+        &self,
+        merkle_root: ManagedByteArray<Self::Api, 32>,
+        expiry_timestamp: u64,
+    ) -> u32 {
+        self.require_role(Role::Admin);
+        require!(
+            expiry_timestamp > self.blockchain().get_block_timestamp(),
+            ERR_EXPIRY_MUST_BE_IN_THE_FUTURE
+        );
+        let payment = self.call_value().egld_or_single_esdt();
+        require!(payment.amount > 0, ERR_CAMPAIGN_MUST_BE_FUNDED);
+
+        let campaign_id = self.campaign_count().get() + 1;
+        self.campaign_count().set(campaign_id);
+        self.campaigns(campaign_id).set(&Campaign {
+            token: payment.token_identifier.clone(),
+            token_nonce: payment.token_nonce,
+            merkle_root,
+            total_amount: payment.amount.clone(),
+            claimed_amount: BigUint::zero(),
+            expiry_timestamp,
+            clawed_back: false,
+        });
+
+        self.campaign_created_event(
+            campaign_id,
+            &payment.token_identifier,
+            payment.token_nonce,
+            &payment.amount,
+            expiry_timestamp,
+        );
+
+        campaign_id
+    }
+
+    #[endpoint(claimAirdrop)]
+    fn claim_airdrop(
+        &self,
+        campaign_id: u32,
+        amount: BigUint,
+        proof: MultiValueEncoded<ManagedByteArray<Self::Api, 32>>,
+    ) {
+        let mut campaign = self.require_campaign(campaign_id);
+        require!(
+            self.blockchain().get_block_timestamp() <= campaign.expiry_timestamp,
+            ERR_CAMPAIGN_HAS_EXPIRED
+        );
+        let caller = self.blockchain().get_caller();
+        require!(!self.has_claimed(campaign_id, &caller).get(), ERR_ALREADY_CLAIMED);
+        require!(amount > 0, ERR_AMOUNT_MUST_BE_GREATER_THAN_0);
+        require!(proof.len() <= MAX_PROOF_LENGTH, ERR_PROOF_TOO_LONG);
+        require!(
+            self.verify_proof(&campaign.merkle_root, &caller, &amount, proof),
+            ERR_INVALID_MERKLE_PROOF
+        );
+        require!(
+            &campaign.claimed_amount + &amount <= campaign.total_amount,
+            ERR_CAMPAIGN_IS_EXHAUSTED
+        );
+
+        campaign.claimed_amount += &amount;
+        self.campaigns(campaign_id).set(&campaign);
+        self.has_claimed(campaign_id, &caller).set(true);
+        self.send().direct(&caller, &campaign.token, campaign.token_nonce, &amount);
+
+        self.airdrop_claimed_event(campaign_id, &caller, &amount);
+    }
+
+    // Return the unclaimed remainder of an expired campaign to the calling admin
+    #[endpoint(clawBackCampaign)]
+    fn claw_back_campaign(&self, campaign_id: u32) -> BigUint {
+        self.require_role(Role::Admin);
+        let mut campaign = self.require_campaign(campaign_id);
+        require!(
+            self.blockchain().get_block_timestamp() > campaign.expiry_timestamp,
+            ERR_CAMPAIGN_HAS_NOT_EXPIRED
+        );
+        require!(!campaign.clawed_back, ERR_CAMPAIGN_ALREADY_CLAWED_BACK);
+
+        let remaining = &campaign.total_amount - &campaign.claimed_amount;
+        campaign.clawed_back = true;
+        self.campaigns(campaign_id).set(&campaign);
+        let caller = self.blockchain().get_caller();
+        if remaining > 0 {
+            self.send().direct(&caller, &campaign.token, campaign.token_nonce, &remaining);
+        }
+
+        self.campaign_clawed_back_event(campaign_id, &caller, &remaining);
+
+        remaining
+    }
+
+    // Internal helpers
+    fn require_campaign(&self, campaign_id: u32) -> Campaign<Self::Api> {
+        require!(!self.campaigns(campaign_id).is_empty(), ERR_CAMPAIGN_DOES_NOT_EXIST);
+        self.campaigns(campaign_id).get()
+    }
+
+    fn verify_proof(
+        &self,
+        merkle_root: &ManagedByteArray<Self::Api, 32>,
+        address: &ManagedAddress,
+        amount: &BigUint,
+        proof: MultiValueEncoded<ManagedByteArray<Self::Api, 32>>,
+    ) -> bool {
+        let mut leaf = address.as_managed_buffer().clone();
+        leaf.append(&amount.to_bytes_be_buffer());
+        let mut node = self.crypto().keccak256(&leaf);
+
+        for sibling in proof {
+            let mut pair = ManagedBuffer::new();
+            if node.to_byte_array() <= sibling.to_byte_array() {
+                pair.append(node.as_managed_buffer());
+                pair.append(sibling.as_managed_buffer());
+            } else {
+                pair.append(sibling.as_managed_buffer());
+                pair.append(node.as_managed_buffer());
+            }
+            node = self.crypto().keccak256(&pair);
+        }
+
+        node == *merkle_root
+    }
+
+    // View functions
+    #[view(getCampaign)]
+    fn get_campaign(&self, campaign_id: u32) -> Campaign<Self::Api> {
+        self.require_campaign(campaign_id)
+    }
+
+    #[view(getCampaignCount)]
+    fn get_campaign_count(&self) -> u32 {
+        self.campaign_count().get()
+    }
+
+    #[view(hasClaimed)]
+    fn has_claimed_view(&self, campaign_id: u32, address: ManagedAddress) -> bool {
+        self.has_claimed(campaign_id, &address).get()
+    }
+
+    // Lets front ends check a proof before sending the claim
+    #[view(isClaimValid)]
+    fn is_claim_valid(
+        &self,
+        campaign_id: u32,
+        address: ManagedAddress,
+        amount: BigUint,
+        proof: MultiValueEncoded<ManagedByteArray<Self::Api, 32>>,
+    ) -> bool {
+        let campaign = self.require_campaign(campaign_id);
+        proof.len() <= MAX_PROOF_LENGTH
+            && !self.has_claimed(campaign_id, &address).get()
+            && self.verify_proof(&campaign.merkle_root, &address, &amount, proof)
+    }
+
+    // Storage mappers
+    #[storage_mapper("campaignCount")]
+    fn campaign_count(&self) -> SingleValueMapper<u32>;
+
+    #[storage_mapper("campaigns")]
+    fn campaigns(&self, campaign_id: u32) -> SingleValueMapper<Campaign<Self::Api>>;
+
+    #[storage_mapper("hasClaimed")]
+    fn has_claimed(&self, campaign_id: u32, address: &ManagedAddress) -> SingleValueMapper<bool>;
+
+    // Events
+    #[event("campaignCreated")]
+    fn campaign_created_event(
+        &self,
+        #[indexed] campaign_id: u32,
+        #[indexed] token: &EgldOrEsdtTokenIdentifier,
+        #[indexed] token_nonce: u64,
+        total_amount: &BigUint,
+        expiry_timestamp: u64,
+    );
+
+    #[event("airdropClaimed")]
+    fn airdrop_claimed_event(
+        &self,
+        #[indexed] campaign_id: u32,
+        #[indexed] address: &ManagedAddress,
+        amount: &BigUint,
+    );
+
+    #[event("campaignClawedBack")]
+    fn campaign_clawed_back_event(
+        &self,
+        #[indexed] campaign_id: u32,
+        #[indexed] recipient: &ManagedAddress,
+        amount: &BigUint,
+    );
+}
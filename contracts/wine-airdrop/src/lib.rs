@@ -0,0 +1,18 @@
+#![no_std]
+
+use multiversx_sc::imports::*;
+
+pub mod campaigns;
+
+pub use campaigns::Campaign;
+
+// Merkle airdrops of wine NFTs and reward tokens. Each campaign commits to its recipients
+// with a Merkle root; recipients claim once with a proof until the campaign expires.
+#[multiversx_sc::contract]
+pub trait WineAirdrop: campaigns::CampaignsModule + wine_roles::RolesModule {
+    #[init]
+    fn init(&self) {}
+
+    #[upgrade]
+    fn upgrade(&self) {}
+}
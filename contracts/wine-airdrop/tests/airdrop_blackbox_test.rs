@@ -0,0 +1,199 @@
+use multiversx_sc_scenario::api::StaticApi;
+use multiversx_sc_scenario::imports::*;
+use multiversx_sc_scenario::multiversx_chain_vm::crypto_functions::keccak256;
+use wine_airdrop::campaigns::ProxyTrait as _;
+use wine_airdrop::Campaign;
+use wine_airdrop::ProxyTrait as _;
+use wine_common::errors::*;
+
+const AIRDROP_PATH_EXPR: &str = "mxsc:output/wine-airdrop.mxsc.json";
+const AIRDROP_ADDRESS_EXPR: &str = "sc:wine-airdrop";
+const OWNER_ADDRESS_EXPR: &str = "address:owner";
+const ALICE_ADDRESS_EXPR: &str = "address:alice";
+const BOB_ADDRESS_EXPR: &str = "address:bob";
+
+const REWARD_TOKEN_ID_EXPR: &str = "str:REWARD-abcdef";
+
+const START_TIMESTAMP: u64 = 1_000;
+const EXPIRY_TIMESTAMP: u64 = 10_000;
+const ALICE_AMOUNT: u64 = 300;
+const BOB_AMOUNT: u64 = 200;
+
+type AirdropContract = ContractInfo<wine_airdrop::Proxy<StaticApi>>;
+
+fn user_error(message: &str) -> TxExpect {
+    TxExpect::user_error(format!("str:{message}"))
+}
+
+// keccak256(address | amount as minimal big-endian bytes), as the contract hashes its leaves
+fn leaf(address_expr: &str, amount: u64) -> [u8; 32] {
+    let mut data = AddressValue::from(address_expr).to_address().to_vec();
+    let amount_bytes = amount.to_be_bytes();
+    let first_significant = amount_bytes.iter().position(|byte| *byte != 0).unwrap_or(8);
+    data.extend_from_slice(&amount_bytes[first_significant..]);
+    keccak256(&data)
+}
+
+fn parent(first: &[u8; 32], second: &[u8; 32]) -> [u8; 32] {
+    let (low, high) = if first <= second { (first, second) } else { (second, first) };
+    let mut pair = low.to_vec();
+    pair.extend_from_slice(high);
+    keccak256(&pair)
+}
+
+fn proof(sibling: &[u8; 32]) -> MultiValueVec<ManagedByteArray<StaticApi, 32>> {
+    MultiValueVec::from(vec![ManagedByteArray::from(sibling)])
+}
+
+struct AirdropTestState {
+    world: ScenarioWorld,
+    airdrop: AirdropContract,
+}
+
+impl AirdropTestState {
+    // Campaign 1 holds 600 reward tokens for a tree of two leaves, 300 for Alice and 200
+    // for Bob, claimable until timestamp 10000
+    fn new() -> Self {
+        let mut world = ScenarioWorld::new();
+        world.register_contract(AIRDROP_PATH_EXPR, wine_airdrop::ContractBuilder);
+
+        world.set_state_step(
+            SetStateStep::new()
+                .block_timestamp(START_TIMESTAMP)
+                .put_account(
+                    OWNER_ADDRESS_EXPR,
+                    Account::new().nonce(1).esdt_balance(REWARD_TOKEN_ID_EXPR, "600"),
+                )
+                .put_account(
+                    ALICE_ADDRESS_EXPR,
+                    Account::new().nonce(1).esdt_balance(REWARD_TOKEN_ID_EXPR, "100"),
+                )
+                .put_account(BOB_ADDRESS_EXPR, Account::new().nonce(1))
+                .new_address(OWNER_ADDRESS_EXPR, 1, AIRDROP_ADDRESS_EXPR),
+        );
+
+        let mut airdrop = AirdropContract::new(AIRDROP_ADDRESS_EXPR);
+        let airdrop_code = world.code_expression(AIRDROP_PATH_EXPR);
+        world.sc_deploy(
+            ScDeployStep::new()
+                .from(OWNER_ADDRESS_EXPR)
+                .code(airdrop_code)
+                .call(airdrop.init()),
+        );
+
+        // Only admins fund campaigns
+        let root = parent(&leaf(ALICE_ADDRESS_EXPR, ALICE_AMOUNT), &leaf(BOB_ADDRESS_EXPR, BOB_AMOUNT));
+        world.sc_call(
+            ScCallStep::new()
+                .from(ALICE_ADDRESS_EXPR)
+                .esdt_transfer(REWARD_TOKEN_ID_EXPR, 0, "100")
+                .call(airdrop.create_campaign(ManagedByteArray::from(&root), EXPIRY_TIMESTAMP))
+                .expect(user_error(ERR_CALLER_DOES_NOT_HAVE_THE_REQUIRED_ROLE)),
+        );
+        world.sc_call(
+            ScCallStep::new()
+                .from(OWNER_ADDRESS_EXPR)
+                .esdt_transfer(REWARD_TOKEN_ID_EXPR, 0, "600")
+                .call(airdrop.create_campaign(ManagedByteArray::from(&root), EXPIRY_TIMESTAMP))
+                .expect_value(1u32),
+        );
+
+        AirdropTestState { world, airdrop }
+    }
+
+    fn set_block_timestamp(&mut self, timestamp: u64) {
+        self.world.set_state_step(SetStateStep::new().block_timestamp(timestamp));
+    }
+
+    fn claim_airdrop(&mut self, claimer: &str, amount: u64, sibling: &[u8; 32]) -> TypedScCall<()> {
+        ScCallStep::new()
+            .from(claimer)
+            .call(self.airdrop.claim_airdrop(1u32, amount, proof(sibling)))
+    }
+
+    fn campaign(&mut self) -> Campaign<StaticApi> {
+        self.world.quick_query(self.airdrop.get_campaign(1u32))
+    }
+}
+
+#[test]
+fn recipients_claim_their_leaf_once() {
+    let mut state = AirdropTestState::new();
+    let alice_leaf = leaf(ALICE_ADDRESS_EXPR, ALICE_AMOUNT);
+    let bob_leaf = leaf(BOB_ADDRESS_EXPR, BOB_AMOUNT);
+    let alice = AddressValue::from(ALICE_ADDRESS_EXPR).to_address();
+
+    let valid: bool = state.world.quick_query(state.airdrop.is_claim_valid(
+        1u32,
+        managed_address!(&alice),
+        ALICE_AMOUNT,
+        proof(&bob_leaf),
+    ));
+    assert!(valid);
+
+    // Proofs are bound to both the caller and the amount
+    let step = state.claim_airdrop(ALICE_ADDRESS_EXPR, ALICE_AMOUNT + 1, &bob_leaf);
+    state.world.sc_call(step.expect(user_error(ERR_INVALID_MERKLE_PROOF)));
+    let step = state.claim_airdrop(BOB_ADDRESS_EXPR, ALICE_AMOUNT, &bob_leaf);
+    state.world.sc_call(step.expect(user_error(ERR_INVALID_MERKLE_PROOF)));
+
+    let step = state.claim_airdrop(ALICE_ADDRESS_EXPR, ALICE_AMOUNT, &bob_leaf);
+    state.world.sc_call(step);
+    let step = state.claim_airdrop(ALICE_ADDRESS_EXPR, ALICE_AMOUNT, &bob_leaf);
+    state.world.sc_call(step.expect(user_error(ERR_ALREADY_CLAIMED)));
+    let step = state.claim_airdrop(BOB_ADDRESS_EXPR, BOB_AMOUNT, &alice_leaf);
+    state.world.sc_call(step);
+
+    assert_eq!(state.campaign().claimed_amount, 500u64);
+    state.world.check_state_step(
+        CheckStateStep::new()
+            .put_account(ALICE_ADDRESS_EXPR, CheckAccount::new().esdt_balance(REWARD_TOKEN_ID_EXPR, "400"))
+            .put_account(BOB_ADDRESS_EXPR, CheckAccount::new().esdt_balance(REWARD_TOKEN_ID_EXPR, "200"))
+            .put_account(AIRDROP_ADDRESS_EXPR, CheckAccount::new().esdt_balance(REWARD_TOKEN_ID_EXPR, "100")),
+    );
+}
+
+#[test]
+fn admin_claws_back_the_unclaimed_rest_after_expiry() {
+    let mut state = AirdropTestState::new();
+    let bob_leaf = leaf(BOB_ADDRESS_EXPR, BOB_AMOUNT);
+    let step = state.claim_airdrop(ALICE_ADDRESS_EXPR, ALICE_AMOUNT, &bob_leaf);
+    state.world.sc_call(step);
+
+    state.world.sc_call(
+        ScCallStep::new()
+            .from(OWNER_ADDRESS_EXPR)
+            .call(state.airdrop.claw_back_campaign(1u32))
+            .expect(user_error(ERR_CAMPAIGN_HAS_NOT_EXPIRED)),
+    );
+
+    state.set_block_timestamp(EXPIRY_TIMESTAMP + 1);
+    let alice_leaf = leaf(ALICE_ADDRESS_EXPR, ALICE_AMOUNT);
+    let step = state.claim_airdrop(BOB_ADDRESS_EXPR, BOB_AMOUNT, &alice_leaf);
+    state.world.sc_call(step.expect(user_error(ERR_CAMPAIGN_HAS_EXPIRED)));
+    state.world.sc_call(
+        ScCallStep::new()
+            .from(ALICE_ADDRESS_EXPR)
+            .call(state.airdrop.claw_back_campaign(1u32))
+            .expect(user_error(ERR_CALLER_DOES_NOT_HAVE_THE_REQUIRED_ROLE)),
+    );
+    state.world.sc_call(
+        ScCallStep::new()
+            .from(OWNER_ADDRESS_EXPR)
+            .call(state.airdrop.claw_back_campaign(1u32))
+            .expect_value(BigUint::<StaticApi>::from(300u64)),
+    );
+    state.world.sc_call(
+        ScCallStep::new()
+            .from(OWNER_ADDRESS_EXPR)
+            .call(state.airdrop.claw_back_campaign(1u32))
+            .expect(user_error(ERR_CAMPAIGN_ALREADY_CLAWED_BACK)),
+    );
+
+    assert!(state.campaign().clawed_back);
+    state.world.check_state_step(
+        CheckStateStep::new()
+            .put_account(OWNER_ADDRESS_EXPR, CheckAccount::new().esdt_balance(REWARD_TOKEN_ID_EXPR, "300"))
+            .put_account(AIRDROP_ADDRESS_EXPR, CheckAccount::new().esdt_balance(REWARD_TOKEN_ID_EXPR, "0")),
+    );
+}
@@ -0,0 +1,391 @@
+use multiversx_sc::derive_imports::*;
+use multiversx_sc::imports::*;
+use wine_common::errors::*;
+use wine_roles::Role;
+
+// Bounds the juror loops of drawing and ruling
+pub const MAX_JURORS_PER_CASE: u32 = 9;
+// Gas for delivering a ruling to the contract the dispute came from
+pub const RULING_GAS: u64 = 30_000_000;
+
+// How a ruling is delivered to the contract a dispute came from. Both are called through
+// `resolveDispute(id, bool)`, with the flag meaning opposite things.
+#[derive(TopEncode, TopDecode, NestedEncode, NestedDecode, TypeAbi, PartialEq, Debug, Clone, Copy)]
+pub enum DisputeSourceKind {
+    // Marketplace protected sales: `refund` is true when the buyer wins. The marketplace must
+    // have this contract set as its arbiter.
+    ProtectedSale,
+    // Redemption orders: `delivered` is true when the producer wins. The redemption contract
+    // must grant this contract its Admin role.
+    Redemption,
+}
+
+#[derive(TopEncode, TopDecode, NestedEncode, NestedDecode, TypeAbi, PartialEq, Debug, Clone, Copy)]
+pub enum CaseStatus {
+    // Waiting out the draw delay, during which parties can commit entropy for the draw
+    AwaitingJurors,
+    Voting,
+    Ruled,
+}
+
+#[derive(TopEncode, TopDecode, TypeAbi, PartialEq, Debug)]
+pub struct ArbitrationConfig {
+    // Odd, so that a full vote has a majority
+    pub jurors_per_case: u32,
+    pub draw_delay: u64,
+    pub voting_period: u64,
+    // Part of a juror's stake slashed for voting with the minority or not voting
+    pub slash_bps: u64,
+}
+
+#[derive(TopEncode, TopDecode, TypeAbi, PartialEq, Debug)]
+pub struct Case<M: ManagedTypeApi> {
+    pub source: ManagedAddress<M>,
+    pub source_kind: DisputeSourceKind,
+    // Listing or order id in the source contract
+    pub source_id: u64,
+    pub opened_timestamp: u64,
+    pub jurors: ManagedVec<M, ManagedAddress<M>>,
+    pub voting_deadline: u64,
+    // Votes for the party that opened the dispute (the buyer or holder) and against
+    pub votes_for_claimant: u32,
+    pub votes_against_claimant: u32,
+    pub status: CaseStatus,
+    pub claimant_wins: bool,
+}
+
+// Disputes are opened by registered source contracts and decided by jurors drawn at random
+// from the staked pool. Once every juror has voted or voting closes, anyone can execute the
+// ruling: the majority decides, a tie or an empty vote goes against the claimant. Jurors in
+// the minority or who did not vote are slashed, and the slashed stake is split among the
+// majority.
+#[multiversx_sc::module]
+pub trait CasesModule:
+    wine_roles::RolesModule + wine_randomness::RandomnessModule + crate::jurors::JurorsModule
+{
+    #[endpoint(setArbitrationConfig)]
+    fn set_arbitration_config(
+        &self,
+        jurors_per_case: u32,
+        draw_delay: u64,
+        voting_period: u64,
+        slash_bps: u64,
+    ) {
+        self.require_role(Role::Admin);
+        self.store_arbitration_config(jurors_per_case, draw_delay, voting_period, slash_bps);
+    }
+
+    #[endpoint(addDisputeSource)]
+    fn add_dispute_source(&self, source: ManagedAddress, kind: DisputeSourceKind) {
+        self.require_role(Role::Admin);
+        require!(self.blockchain().is_smart_contract(&source), ERR_SOURCE_MUST_BE_A_SMART_CONTRACT);
+        self.dispute_sources().insert(source.clone(), kind);
+
+        self.dispute_source_added_event(&source, kind);
+    }
+
+    #[endpoint(removeDisputeSource)]
+    fn remove_dispute_source(&self, source: ManagedAddress) {
+        self.require_role(Role::Admin);
+        require!(self.dispute_sources().remove(&source).is_some(), ERR_NOT_A_DISPUTE_SOURCE);
+    }
+
+    // Called by a dispute source when one of its disputes opens
+    #[endpoint(openCase)]
+    fn open_case(&self, source_id: u64) -> u64 {
+        self.require_not_paused();
+        let source = self.blockchain().get_caller();
+        let source_kind = self
+            .dispute_sources()
+            .get(&source)
+            .unwrap_or_else(|| sc_panic!(ERR_NOT_A_DISPUTE_SOURCE));
+        require!(self.case_by_source(&source, source_id).is_empty(), ERR_CASE_ALREADY_OPEN);
+
+        let case_id = self.case_count().get() + 1;
+        self.case_count().set(case_id);
+        self.cases(case_id).set(&Case {
+            source: source.clone(),
+            source_kind,
+            source_id,
+            opened_timestamp: self.blockchain().get_block_timestamp(),
+            jurors: ManagedVec::new(),
+            voting_deadline: 0,
+            votes_for_claimant: 0,
+            votes_against_claimant: 0,
+            status: CaseStatus::AwaitingJurors,
+            claimant_wins: false,
+        });
+        self.case_by_source(&source, source_id).set(case_id);
+
+        self.case_opened_event(case_id, &source, source_id);
+
+        case_id
+    }
+
+    // Draw the jurors once the draw delay has passed. Anyone can call it.
+    #[endpoint(drawJurors)]
+    fn draw_jurors(&self, case_id: u64) {
+        let mut case = self.require_case(case_id);
+        require!(case.status == CaseStatus::AwaitingJurors, ERR_JURORS_ALREADY_DRAWN);
+        let config = self.arbitration_config().get();
+        let now = self.blockchain().get_block_timestamp();
+        require!(now >= case.opened_timestamp + config.draw_delay, ERR_DRAW_DELAY_HAS_NOT_PASSED);
+
+        let mut pool: ManagedVec<ManagedAddress> = self.jurors().iter().collect();
+        require!(
+            pool.len() >= config.jurors_per_case as usize,
+            ERR_NOT_ENOUGH_JURORS_IN_THE_POOL
+        );
+        let domain = ManagedBuffer::from(wine_randomness::ARBITRATION_DOMAIN);
+        for _ in 0..config.jurors_per_case {
+            let index =
+                self.draw_random_u64_in_range(&domain, case_id, 0, pool.len() as u64) as usize;
+            let juror = pool.get(index).clone_value();
+            pool.remove(index);
+
+            let stake_at_risk = self.juror_stake(&juror).get() * config.slash_bps / 10_000u64;
+            self.juror_stake_at_risk(case_id, &juror).set(&stake_at_risk);
+            self.juror_active_cases(&juror).update(|cases| *cases += 1);
+            case.jurors.push(juror);
+        }
+        case.status = CaseStatus::Voting;
+        case.voting_deadline = now + config.voting_period;
+        self.cases(case_id).set(&case);
+
+        self.jurors_drawn_event(case_id, &case.jurors);
+    }
+
+    #[endpoint(vote)]
+    fn vote(&self, case_id: u64, claimant_wins: bool) {
+        let mut case = self.require_case(case_id);
+        require!(case.status == CaseStatus::Voting, ERR_CASE_IS_NOT_VOTING);
+        require!(
+            self.blockchain().get_block_timestamp() <= case.voting_deadline,
+            ERR_VOTING_HAS_CLOSED
+        );
+        let juror = self.blockchain().get_caller();
+        require!(case.jurors.contains(&juror), ERR_NOT_A_JUROR_OF_THIS_CASE);
+        require!(self.juror_votes(case_id, &juror).get().is_none(), ERR_ALREADY_VOTED);
+
+        self.juror_votes(case_id, &juror).set(Some(claimant_wins));
+        if claimant_wins {
+            case.votes_for_claimant += 1;
+        } else {
+            case.votes_against_claimant += 1;
+        }
+        self.cases(case_id).set(&case);
+
+        self.vote_cast_event(case_id, &juror, claimant_wins);
+    }
+
+    // Decide the case, settle juror stakes and deliver the ruling to the source contract
+    #[endpoint(executeRuling)]
+    fn execute_ruling(&self, case_id: u64) {
+        let mut case = self.require_case(case_id);
+        require!(case.status == CaseStatus::Voting, ERR_CASE_IS_NOT_VOTING);
+        let votes_cast = case.votes_for_claimant + case.votes_against_claimant;
+        require!(
+            votes_cast as usize == case.jurors.len()
+                || self.blockchain().get_block_timestamp() > case.voting_deadline,
+            ERR_VOTING_IS_STILL_OPEN
+        );
+        case.claimant_wins = case.votes_for_claimant > case.votes_against_claimant;
+        case.status = CaseStatus::Ruled;
+        self.cases(case_id).set(&case);
+
+        self.settle_juror_stakes(case_id, &case);
+        let ruling_flag = match case.source_kind {
+            DisputeSourceKind::ProtectedSale => case.claimant_wins,
+            DisputeSourceKind::Redemption => !case.claimant_wins,
+        };
+        self.tx()
+            .to(&case.source)
+            .gas(RULING_GAS)
+            .raw_call("resolveDispute")
+            .argument(&(case.source_id as u32))
+            .argument(&ruling_flag)
+            .transfer_execute();
+
+        self.case_ruled_event(
+            case_id,
+            case.claimant_wins,
+            case.votes_for_claimant,
+            case.votes_against_claimant,
+        );
+    }
+
+    // Internal helpers
+    fn store_arbitration_config(
+        &self,
+        jurors_per_case: u32,
+        draw_delay: u64,
+        voting_period: u64,
+        slash_bps: u64,
+    ) {
+        require!(
+            jurors_per_case % 2 == 1 && jurors_per_case <= MAX_JURORS_PER_CASE,
+            ERR_JURORS_PER_CASE_MUST_BE_ODD_AND_AT_MOST_9
+        );
+        require!(voting_period > 0, ERR_VOTING_PERIOD_MUST_BE_GREATER_THAN_0);
+        require!(slash_bps <= 10_000, ERR_SLASH_CANNOT_EXCEED_100_PERCENT);
+        self.arbitration_config().set(&ArbitrationConfig {
+            jurors_per_case,
+            draw_delay,
+            voting_period,
+            slash_bps,
+        });
+    }
+
+    fn require_case(&self, case_id: u64) -> Case<Self::Api> {
+        require!(!self.cases(case_id).is_empty(), ERR_CASE_DOES_NOT_EXIST);
+        self.cases(case_id).get()
+    }
+
+    // Slash the minority and absent jurors and split the slashed stake among the majority.
+    // Without a majority the slashed stake goes to the owner.
+    fn settle_juror_stakes(&self, case_id: u64, case: &Case<Self::Api>) {
+        let mut slashed = BigUint::zero();
+        let mut majority = ManagedVec::<Self::Api, ManagedAddress>::new();
+        for juror in case.jurors.iter() {
+            let juror = juror.clone_value();
+            self.juror_active_cases(&juror).update(|cases| *cases -= 1);
+            let stake_at_risk = self.juror_stake_at_risk(case_id, &juror).take();
+            if self.juror_votes(case_id, &juror).get() == Some(case.claimant_wins) {
+                majority.push(juror);
+                continue;
+            }
+            // The stake may have shrunk through slashing on another case
+            let stake = self.juror_stake(&juror).get();
+            let slash = if stake_at_risk > stake { stake.clone() } else { stake_at_risk };
+            self.juror_stake(&juror).set(&(stake - &slash));
+            self.update_juror_pool(&juror);
+            slashed += &slash;
+
+            self.juror_slashed_event(case_id, &juror, &slash);
+        }
+        if slashed == 0 {
+            return;
+        }
+        if majority.is_empty() {
+            let owner = self.blockchain().get_owner_address();
+            self.send().direct_esdt(&owner, &self.staking_token().get(), 0, &slashed);
+            return;
+        }
+        let reward = &slashed / majority.len() as u64;
+        let remainder = &slashed - &(&reward * majority.len() as u64);
+        for (index, juror) in majority.iter().enumerate() {
+            let juror_reward = if index == 0 { &reward + &remainder } else { reward.clone() };
+            self.juror_stake(&juror).update(|stake| *stake += &juror_reward);
+            self.update_juror_pool(&juror);
+
+            self.juror_rewarded_event(case_id, &juror, &juror_reward);
+        }
+    }
+
+    // View functions
+    #[view(getCase)]
+    fn get_case(&self, case_id: u64) -> Case<Self::Api> {
+        self.require_case(case_id)
+    }
+
+    #[view(getCaseCount)]
+    fn get_case_count(&self) -> u64 {
+        self.case_count().get()
+    }
+
+    #[view(getCaseBySource)]
+    fn get_case_by_source(&self, source: ManagedAddress, source_id: u64) -> OptionalValue<u64> {
+        if self.case_by_source(&source, source_id).is_empty() {
+            OptionalValue::None
+        } else {
+            OptionalValue::Some(self.case_by_source(&source, source_id).get())
+        }
+    }
+
+    #[view(getArbitrationConfig)]
+    fn get_arbitration_config(&self) -> ArbitrationConfig {
+        self.arbitration_config().get()
+    }
+
+    #[view(getDisputeSources)]
+    fn get_dispute_sources(
+        &self,
+    ) -> MultiValueEncoded<MultiValue2<ManagedAddress, DisputeSourceKind>> {
+        let mut result = MultiValueEncoded::new();
+        for (source, kind) in self.dispute_sources().iter() {
+            result.push((source, kind).into());
+        }
+        result
+    }
+
+    // Storage mappers
+    #[storage_mapper("arbitrationConfig")]
+    fn arbitration_config(&self) -> SingleValueMapper<ArbitrationConfig>;
+
+    #[storage_mapper("disputeSources")]
+    fn dispute_sources(&self) -> MapMapper<ManagedAddress, DisputeSourceKind>;
+
+    #[storage_mapper("caseCount")]
+    fn case_count(&self) -> SingleValueMapper<u64>;
+
+    #[storage_mapper("cases")]
+    fn cases(&self, case_id: u64) -> SingleValueMapper<Case<Self::Api>>;
+
+    #[storage_mapper("caseBySource")]
+    fn case_by_source(&self, source: &ManagedAddress, source_id: u64) -> SingleValueMapper<u64>;
+
+    #[storage_mapper("jurorStakeAtRisk")]
+    fn juror_stake_at_risk(&self, case_id: u64, juror: &ManagedAddress) -> SingleValueMapper<BigUint>;
+
+    // An Option, since a plain `false` would read as no vote
+    #[storage_mapper("jurorVotes")]
+    fn juror_votes(&self, case_id: u64, juror: &ManagedAddress) -> SingleValueMapper<Option<bool>>;
+
+    // Events
+    #[event("disputeSourceAdded")]
+    fn dispute_source_added_event(&self, #[indexed] source: &ManagedAddress, kind: DisputeSourceKind);
+
+    #[event("caseOpened")]
+    fn case_opened_event(
+        &self,
+        #[indexed] case_id: u64,
+        #[indexed] source: &ManagedAddress,
+        source_id: u64,
+    );
+
+    #[event("jurorsDrawn")]
+    fn jurors_drawn_event(&self, #[indexed] case_id: u64, jurors: &ManagedVec<ManagedAddress>);
+
+    #[event("voteCast")]
+    fn vote_cast_event(
+        &self,
+        #[indexed] case_id: u64,
+        #[indexed] juror: &ManagedAddress,
+        claimant_wins: bool,
+    );
+
+    #[event("caseRuled")]
+    fn case_ruled_event(
+        &self,
+        #[indexed] case_id: u64,
+        #[indexed] claimant_wins: bool,
+        votes_for_claimant: u32,
+        votes_against_claimant: u32,
+    );
+
+    #[event("jurorSlashed")]
+    fn juror_slashed_event(
+        &self,
+        #[indexed] case_id: u64,
+        #[indexed] juror: &ManagedAddress,
+        amount: &BigUint,
+    );
+
+    #[event("jurorRewarded")]
+    fn juror_rewarded_event(
+        &self,
+        #[indexed] case_id: u64,
+        #[indexed] juror: &ManagedAddress,
+        amount: &BigUint,
+    );
+}
@@ -0,0 +1,101 @@
+use multiversx_sc::imports::*;
+use wine_common::errors::*;
+use wine_roles::Role;
+
+// Cases draw from the whole pool in one transaction, so it stays bounded
+pub const MAX_JURORS: usize = 100;
+
+// Jurors stake the staking token to join the pool cases are drawn from. Stake can only be
+// withdrawn while the juror sits on no open case, since it backs their votes.
+#[multiversx_sc::module]
+pub trait JurorsModule: wine_roles::RolesModule {
+    // Stake below the minimum leaves the juror out of the pool
+    #[endpoint(setMinJurorStake)]
+    fn set_min_juror_stake(&self, min_juror_stake: BigUint) {
+        self.require_role(Role::Admin);
+        require!(min_juror_stake > 0, ERR_MINIMUM_STAKE_MUST_BE_GREATER_THAN_0);
+        self.min_juror_stake().set(&min_juror_stake);
+    }
+
+    #[payable("*")]
+    #[endpoint(stakeAsJuror)]
+    fn stake_as_juror(&self) {
+        self.require_not_paused();
+        let payment = self.call_value().single_esdt();
+        require!(payment.token_identifier == self.staking_token().get(), ERR_INVALID_STAKING_TOKEN);
+        require!(payment.amount > 0, ERR_AMOUNT_MUST_BE_GREATER_THAN_0);
+
+        let juror = self.blockchain().get_caller();
+        self.juror_stake(&juror).update(|stake| *stake += &payment.amount);
+        self.update_juror_pool(&juror);
+
+        self.juror_staked_event(&juror, &payment.amount);
+    }
+
+    #[endpoint(unstakeJuror)]
+    fn unstake_juror(&self, amount: BigUint) {
+        let juror = self.blockchain().get_caller();
+        require!(self.juror_active_cases(&juror).get() == 0, ERR_JUROR_SITS_ON_AN_OPEN_CASE);
+        require!(amount > 0, ERR_AMOUNT_MUST_BE_GREATER_THAN_0);
+        require!(amount <= self.juror_stake(&juror).get(), ERR_NOT_ENOUGH_STAKE);
+
+        self.juror_stake(&juror).update(|stake| *stake -= &amount);
+        self.update_juror_pool(&juror);
+        self.send().direct_esdt(&juror, &self.staking_token().get(), 0, &amount);
+
+        self.juror_unstaked_event(&juror, &amount);
+    }
+
+    // Internal helpers
+    // Keep the pool in line with the juror's stake
+    fn update_juror_pool(&self, juror: &ManagedAddress) {
+        if self.juror_stake(juror).get() >= self.min_juror_stake().get() {
+            if self.jurors().insert(juror.clone()) {
+                require!(self.jurors().len() <= MAX_JURORS, ERR_JUROR_POOL_IS_FULL);
+            }
+        } else {
+            self.jurors().swap_remove(juror);
+        }
+    }
+
+    // View functions
+    #[view(getJurors)]
+    fn get_jurors(&self) -> MultiValueEncoded<ManagedAddress> {
+        self.jurors().iter().collect()
+    }
+
+    #[view(getJurorStake)]
+    fn get_juror_stake(&self, juror: ManagedAddress) -> BigUint {
+        self.juror_stake(&juror).get()
+    }
+
+    #[view(getJurorActiveCases)]
+    fn get_juror_active_cases(&self, juror: ManagedAddress) -> u32 {
+        self.juror_active_cases(&juror).get()
+    }
+
+    // Storage mappers
+    #[view(getStakingToken)]
+    #[storage_mapper("stakingToken")]
+    fn staking_token(&self) -> SingleValueMapper<TokenIdentifier>;
+
+    #[view(getMinJurorStake)]
+    #[storage_mapper("minJurorStake")]
+    fn min_juror_stake(&self) -> SingleValueMapper<BigUint>;
+
+    #[storage_mapper("jurors")]
+    fn jurors(&self) -> UnorderedSetMapper<ManagedAddress>;
+
+    #[storage_mapper("jurorStake")]
+    fn juror_stake(&self, juror: &ManagedAddress) -> SingleValueMapper<BigUint>;
+
+    #[storage_mapper("jurorActiveCases")]
+    fn juror_active_cases(&self, juror: &ManagedAddress) -> SingleValueMapper<u32>;
+
+    // Events
+    #[event("jurorStaked")]
+    fn juror_staked_event(&self, #[indexed] juror: &ManagedAddress, amount: &BigUint);
+
+    #[event("jurorUnstaked")]
+    fn juror_unstaked_event(&self, #[indexed] juror: &ManagedAddress, amount: &BigUint);
+}
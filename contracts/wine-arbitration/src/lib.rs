@@ -0,0 +1,39 @@
+#![no_std]
+
+use multiversx_sc::imports::*;
+use wine_common::errors::*;
+
+pub mod cases;
+pub mod jurors;
+
+pub use cases::{ArbitrationConfig, Case, CaseStatus, DisputeSourceKind};
+
+// Dispute resolution for the marketplace's protected sales and for redemption orders, by
+// jurors who stake tokens and are drawn at random for each case
+#[multiversx_sc::contract]
+pub trait WineArbitration:
+    cases::CasesModule
+    + jurors::JurorsModule
+    + wine_randomness::RandomnessModule
+    + wine_roles::RolesModule
+{
+    #[init]
+    fn init(
+        &self,
+        staking_token: TokenIdentifier,
+        min_juror_stake: BigUint,
+        jurors_per_case: u32,
+        draw_delay: u64,
+        voting_period: u64,
+        slash_bps: u64,
+    ) {
+        require!(staking_token.is_valid_esdt_identifier(), ERR_INVALID_STAKING_TOKEN);
+        require!(min_juror_stake > 0, ERR_MINIMUM_STAKE_MUST_BE_GREATER_THAN_0);
+        self.staking_token().set(&staking_token);
+        self.min_juror_stake().set(&min_juror_stake);
+        self.store_arbitration_config(jurors_per_case, draw_delay, voting_period, slash_bps);
+    }
+
+    #[upgrade]
+    fn upgrade(&self) {}
+}
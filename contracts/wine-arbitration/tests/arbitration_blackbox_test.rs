@@ -0,0 +1,379 @@
+use multiversx_sc::codec::top_encode_to_vec_u8_or_panic;
+use multiversx_sc_scenario::api::StaticApi;
+use multiversx_sc_scenario::imports::*;
+use wine_arbitration::cases::ProxyTrait as _;
+use wine_arbitration::jurors::ProxyTrait as _;
+use wine_arbitration::ProxyTrait as _;
+use wine_arbitration::{Case, CaseStatus, DisputeSourceKind};
+use wine_common::errors::*;
+use wine_marketplace::listing::ProxyTrait as _;
+use wine_marketplace::protected_sale::ProxyTrait as _;
+use wine_marketplace::registry_cache::ProxyTrait as _;
+use wine_marketplace::ProxyTrait as _;
+use wine_marketplace::{ProtectedSale, ProtectedSaleStatus, WineNftAttributes, WineReadModel, WineStatus};
+use wine_roles::ProxyTrait as _;
+
+const ARBITRATION_PATH_EXPR: &str = "mxsc:output/wine-arbitration.mxsc.json";
+const MARKETPLACE_PATH_EXPR: &str = "mxsc:../wine-marketplace/output/wine-marketplace.mxsc.json";
+const ARBITRATION_ADDRESS_EXPR: &str = "sc:wine-arbitration";
+const MARKETPLACE_ADDRESS_EXPR: &str = "sc:wine-marketplace";
+const OWNER_ADDRESS_EXPR: &str = "address:owner";
+const REGISTRY_ADDRESS_EXPR: &str = "address:registry";
+const SELLER_ADDRESS_EXPR: &str = "address:seller";
+const BUYER_ADDRESS_EXPR: &str = "address:buyer";
+const ALICE_ADDRESS_EXPR: &str = "address:alice";
+const BOB_ADDRESS_EXPR: &str = "address:bob";
+const CAROL_ADDRESS_EXPR: &str = "address:carol";
+
+const WINE_NFT_TOKEN_ID_EXPR: &str = "str:WINE-abcdef";
+const STAKE_TOKEN_ID_EXPR: &str = "str:STAKE-abcdef";
+
+const START_TIMESTAMP: u64 = 1_000;
+const DRAW_DELAY: u64 = 10;
+const VOTING_PERIOD: u64 = 100;
+const PRICE: u64 = 1_000;
+const JURORS: [&str; 3] = [ALICE_ADDRESS_EXPR, BOB_ADDRESS_EXPR, CAROL_ADDRESS_EXPR];
+
+type ArbitrationContract = ContractInfo<wine_arbitration::Proxy<StaticApi>>;
+type MarketplaceContract = ContractInfo<wine_marketplace::Proxy<StaticApi>>;
+
+fn user_error(message: &str) -> TxExpect {
+    TxExpect::user_error(format!("str:{message}"))
+}
+
+fn encoded_wine_attributes() -> Option<Vec<u8>> {
+    Some(top_encode_to_vec_u8_or_panic(&WineNftAttributes::<StaticApi> {
+        wine_id: 1,
+        vineyard: ManagedBuffer::from("Chateau Test"),
+        variety: ManagedBuffer::from("Merlot"),
+        vintage_year: 2020,
+        region: ManagedBuffer::from("Bordeaux"),
+        quality_grade: 8,
+        ipfs_hash: ManagedBuffer::new(),
+    }))
+}
+
+struct ArbitrationTestState {
+    world: ScenarioWorld,
+    arbitration: ArbitrationContract,
+    marketplace: MarketplaceContract,
+}
+
+impl ArbitrationTestState {
+    // Arbitration for the marketplace's protected sales, with three jurors per case. Alice,
+    // Bob and Carol each stake 100 STAKE, with half of it at risk on every case, so all three
+    // are drawn for every case. The buyer has bought the seller's wine NFT 1 as a protected
+    // sale for 1000 EGLD.
+    fn new() -> Self {
+        let mut world = ScenarioWorld::new();
+        world.register_contract(ARBITRATION_PATH_EXPR, wine_arbitration::ContractBuilder);
+        world.register_contract(MARKETPLACE_PATH_EXPR, wine_marketplace::ContractBuilder);
+
+        let mut set_state = SetStateStep::new()
+            .block_timestamp(START_TIMESTAMP)
+            .put_account(OWNER_ADDRESS_EXPR, Account::new().nonce(1))
+            .put_account(REGISTRY_ADDRESS_EXPR, Account::new().nonce(1))
+            .put_account(
+                SELLER_ADDRESS_EXPR,
+                Account::new()
+                    .nonce(1)
+                    .esdt_nft_balance(WINE_NFT_TOKEN_ID_EXPR, 1, "1", encoded_wine_attributes()),
+            )
+            .put_account(BUYER_ADDRESS_EXPR, Account::new().nonce(1).balance("1000"))
+            .new_address(OWNER_ADDRESS_EXPR, 1, ARBITRATION_ADDRESS_EXPR)
+            .new_address(OWNER_ADDRESS_EXPR, 2, MARKETPLACE_ADDRESS_EXPR);
+        for juror in JURORS {
+            set_state = set_state.put_account(juror, Account::new().nonce(1).esdt_balance(STAKE_TOKEN_ID_EXPR, "100"));
+        }
+        world.set_state_step(set_state);
+
+        let mut arbitration = ArbitrationContract::new(ARBITRATION_ADDRESS_EXPR);
+        let arbitration_code = world.code_expression(ARBITRATION_PATH_EXPR);
+        world.sc_deploy(
+            ScDeployStep::new()
+                .from(OWNER_ADDRESS_EXPR)
+                .code(arbitration_code)
+                .call(arbitration.init(
+                    TokenIdentifier::from("STAKE-abcdef"),
+                    100u64,
+                    3u32,
+                    DRAW_DELAY,
+                    VOTING_PERIOD,
+                    5_000u64,
+                )),
+        );
+        let mut marketplace = MarketplaceContract::new(MARKETPLACE_ADDRESS_EXPR);
+        let marketplace_code = world.code_expression(MARKETPLACE_PATH_EXPR);
+        let registry = AddressValue::from(REGISTRY_ADDRESS_EXPR).to_address();
+        world.sc_deploy(
+            ScDeployStep::new()
+                .from(OWNER_ADDRESS_EXPR)
+                .code(marketplace_code)
+                .call(marketplace.init(250u64, managed_address!(&registry))),
+        );
+
+        // Dispute sources are registered by an admin
+        let marketplace_address = AddressValue::from(MARKETPLACE_ADDRESS_EXPR).to_address();
+        let seller = AddressValue::from(SELLER_ADDRESS_EXPR).to_address();
+        world.sc_call(
+            ScCallStep::new()
+                .from(ALICE_ADDRESS_EXPR)
+                .call(arbitration.add_dispute_source(
+                    managed_address!(&marketplace_address),
+                    DisputeSourceKind::ProtectedSale,
+                ))
+                .expect(user_error(ERR_CALLER_DOES_NOT_HAVE_THE_REQUIRED_ROLE)),
+        );
+        world.sc_call(
+            ScCallStep::new()
+                .from(OWNER_ADDRESS_EXPR)
+                .call(arbitration.add_dispute_source(managed_address!(&seller), DisputeSourceKind::ProtectedSale))
+                .expect(user_error(ERR_SOURCE_MUST_BE_A_SMART_CONTRACT)),
+        );
+        world.sc_call(
+            ScCallStep::new()
+                .from(OWNER_ADDRESS_EXPR)
+                .call(arbitration.add_dispute_source(
+                    managed_address!(&marketplace_address),
+                    DisputeSourceKind::ProtectedSale,
+                )),
+        );
+        for juror in JURORS {
+            world.sc_call(
+                ScCallStep::new()
+                    .from(juror)
+                    .esdt_transfer(STAKE_TOKEN_ID_EXPR, 0, "100")
+                    .call(arbitration.stake_as_juror()),
+            );
+        }
+
+        // The protected sale, with the arbitration contract as the marketplace's arbiter
+        let arbitration_address = AddressValue::from(ARBITRATION_ADDRESS_EXPR).to_address();
+        world.sc_call(
+            ScCallStep::new()
+                .from(OWNER_ADDRESS_EXPR)
+                .call(marketplace.set_wine_collection(TokenIdentifier::from("WINE-abcdef"))),
+        );
+        world.sc_call(
+            ScCallStep::new()
+                .from(OWNER_ADDRESS_EXPR)
+                .call(marketplace.set_arbiter(managed_address!(&arbitration_address))),
+        );
+        world.sc_call(ScCallStep::new().from(REGISTRY_ADDRESS_EXPR).call(marketplace.sync_wine_read_model(
+            1u32,
+            WineReadModel {
+                price_per_bottle: BigUint::from(PRICE),
+                quality_grade: 8,
+                certified: false,
+                royalty_bps: 0,
+                status: WineStatus::Released,
+                producer: managed_address!(&seller),
+                counterfeit: false,
+                certified_until: 0,
+            },
+        )));
+        world.sc_call(
+            ScCallStep::new()
+                .from(SELLER_ADDRESS_EXPR)
+                .esdt_transfer(WINE_NFT_TOKEN_ID_EXPR, 1, "1")
+                .call(marketplace.create_listing(
+                    1u32,
+                    PRICE,
+                    EgldOrEsdtTokenIdentifier::egld(),
+                    3_600u64,
+                    OptionalValue::Some(true),
+                )),
+        );
+        world.sc_call(
+            ScCallStep::new()
+                .from(BUYER_ADDRESS_EXPR)
+                .egld_value(PRICE)
+                .call(marketplace.buy_wine(1u32, OptionalValue::<ManagedAddress<StaticApi>>::None)),
+        );
+
+        ArbitrationTestState {
+            world,
+            arbitration,
+            marketplace,
+        }
+    }
+
+    fn set_block_timestamp(&mut self, timestamp: u64) {
+        self.world.set_state_step(SetStateStep::new().block_timestamp(timestamp));
+    }
+
+    // The buyer disputes the sale, which opens case 1 for listing 1; its jurors are drawn
+    // once the draw delay has passed
+    fn dispute_sale(&mut self) {
+        self.world.sc_call(
+            ScCallStep::new()
+                .from(BUYER_ADDRESS_EXPR)
+                .call(self.marketplace.open_dispute(1u32, ManagedBuffer::from("Cork taint"))),
+        );
+        self.world.sc_call(
+            ScCallStep::new()
+                .from(ALICE_ADDRESS_EXPR)
+                .call(self.arbitration.draw_jurors(1u64))
+                .expect(user_error(ERR_DRAW_DELAY_HAS_NOT_PASSED)),
+        );
+        self.set_block_timestamp(START_TIMESTAMP + DRAW_DELAY);
+        self.world.sc_call(
+            ScCallStep::new()
+                .from(ALICE_ADDRESS_EXPR)
+                .call(self.arbitration.draw_jurors(1u64)),
+        );
+    }
+
+    fn vote(&mut self, juror: &str, claimant_wins: bool) -> TypedScCall<()> {
+        ScCallStep::new()
+            .from(juror)
+            .call(self.arbitration.vote(1u64, claimant_wins))
+    }
+
+    fn execute_ruling(&mut self) -> TypedScCall<()> {
+        ScCallStep::new()
+            .from(OWNER_ADDRESS_EXPR)
+            .call(self.arbitration.execute_ruling(1u64))
+    }
+
+    fn juror_stake(&mut self, juror: &str) -> BigUint<StaticApi> {
+        let juror = AddressValue::from(juror).to_address();
+        self.world.quick_query(self.arbitration.get_juror_stake(managed_address!(&juror)))
+    }
+
+    fn case(&mut self) -> Case<StaticApi> {
+        self.world.quick_query(self.arbitration.get_case(1u64))
+    }
+
+    fn protected_sale_status(&mut self) -> ProtectedSaleStatus {
+        let sale: OptionalValue<ProtectedSale<StaticApi>> =
+            self.world.quick_query(self.marketplace.get_protected_sale(1u32));
+        sale.into_option().unwrap().status
+    }
+}
+
+#[test]
+fn majority_ruling_refunds_the_buyer_and_rewards_the_majority() {
+    let mut state = ArbitrationTestState::new();
+    state.world.sc_call(
+        ScCallStep::new()
+            .from(BUYER_ADDRESS_EXPR)
+            .call(state.arbitration.open_case(1u64))
+            .expect(user_error(ERR_NOT_A_DISPUTE_SOURCE)),
+    );
+    state.dispute_sale();
+    assert_eq!(state.case().status, CaseStatus::Voting);
+    assert_eq!(state.case().jurors.len(), 3);
+
+    let step = state.vote(ALICE_ADDRESS_EXPR, true);
+    state.world.sc_call(step);
+    let step = state.vote(ALICE_ADDRESS_EXPR, true);
+    state.world.sc_call(step.expect(user_error(ERR_ALREADY_VOTED)));
+    let step = state.vote(BUYER_ADDRESS_EXPR, true);
+    state.world.sc_call(step.expect(user_error(ERR_NOT_A_JUROR_OF_THIS_CASE)));
+    let step = state.execute_ruling();
+    state.world.sc_call(step.expect(user_error(ERR_VOTING_IS_STILL_OPEN)));
+
+    // Stake backs the votes until the case is ruled
+    state.world.sc_call(
+        ScCallStep::new()
+            .from(CAROL_ADDRESS_EXPR)
+            .call(state.arbitration.unstake_juror(100u64))
+            .expect(user_error(ERR_JUROR_SITS_ON_AN_OPEN_CASE)),
+    );
+    let step = state.vote(BOB_ADDRESS_EXPR, true);
+    state.world.sc_call(step);
+    let step = state.vote(CAROL_ADDRESS_EXPR, false);
+    state.world.sc_call(step);
+    let step = state.execute_ruling();
+    state.world.sc_call(step);
+
+    let case = state.case();
+    assert_eq!(case.status, CaseStatus::Ruled);
+    assert!(case.claimant_wins);
+    assert_eq!(state.protected_sale_status(), ProtectedSaleStatus::Refunded);
+    assert_eq!(state.juror_stake(ALICE_ADDRESS_EXPR), 125u64);
+    assert_eq!(state.juror_stake(BOB_ADDRESS_EXPR), 125u64);
+    assert_eq!(state.juror_stake(CAROL_ADDRESS_EXPR), 50u64);
+
+    // Below the minimum stake, the slashed juror leaves the pool
+    let jurors: MultiValueVec<ManagedAddress<StaticApi>> = state.world.quick_query(state.arbitration.get_jurors());
+    assert_eq!(jurors.into_vec().len(), 2);
+    state.world.sc_call(
+        ScCallStep::new()
+            .from(CAROL_ADDRESS_EXPR)
+            .call(state.arbitration.unstake_juror(50u64)),
+    );
+    state.world.check_state_step(
+        CheckStateStep::new()
+            .put_account(BUYER_ADDRESS_EXPR, CheckAccount::new().balance("1000"))
+            .put_account(
+                SELLER_ADDRESS_EXPR,
+                CheckAccount::new().esdt_nft_balance_and_attributes(
+                    WINE_NFT_TOKEN_ID_EXPR,
+                    1,
+                    "1",
+                    encoded_wine_attributes(),
+                ),
+            )
+            .put_account(CAROL_ADDRESS_EXPR, CheckAccount::new().esdt_balance(STAKE_TOKEN_ID_EXPR, "50"))
+            .put_account(ARBITRATION_ADDRESS_EXPR, CheckAccount::new().esdt_balance(STAKE_TOKEN_ID_EXPR, "250")),
+    );
+}
+
+#[test]
+fn empty_vote_completes_the_sale_and_slashes_to_the_owner() {
+    let mut state = ArbitrationTestState::new();
+    state.dispute_sale();
+
+    state.set_block_timestamp(START_TIMESTAMP + DRAW_DELAY + VOTING_PERIOD + 1);
+    let step = state.vote(ALICE_ADDRESS_EXPR, true);
+    state.world.sc_call(step.expect(user_error(ERR_VOTING_HAS_CLOSED)));
+    let step = state.execute_ruling();
+    state.world.sc_call(step);
+    let step = state.execute_ruling();
+    state.world.sc_call(step.expect(user_error(ERR_CASE_IS_NOT_VOTING)));
+
+    assert!(!state.case().claimant_wins);
+    assert_eq!(state.protected_sale_status(), ProtectedSaleStatus::Released);
+    for juror in JURORS {
+        assert_eq!(state.juror_stake(juror), 50u64);
+    }
+    state.world.check_state_step(
+        CheckStateStep::new()
+            .put_account(
+                BUYER_ADDRESS_EXPR,
+                CheckAccount::new().balance("0").esdt_nft_balance_and_attributes(
+                    WINE_NFT_TOKEN_ID_EXPR,
+                    1,
+                    "1",
+                    encoded_wine_attributes(),
+                ),
+            )
+            .put_account(OWNER_ADDRESS_EXPR, CheckAccount::new().esdt_balance(STAKE_TOKEN_ID_EXPR, "150"))
+            .put_account(ARBITRATION_ADDRESS_EXPR, CheckAccount::new().esdt_balance(STAKE_TOKEN_ID_EXPR, "150")),
+    );
+}
+
+#[test]
+fn no_cases_open_while_paused() {
+    let mut state = ArbitrationTestState::new();
+
+    state.world.sc_call(
+        ScCallStep::new()
+            .from(ALICE_ADDRESS_EXPR)
+            .call(state.arbitration.pause())
+            .expect(user_error(ERR_CALLER_DOES_NOT_HAVE_THE_REQUIRED_ROLE)),
+    );
+    state.world.sc_call(ScCallStep::new().from(OWNER_ADDRESS_EXPR).call(state.arbitration.pause()));
+    state.world.sc_call(
+        ScCallStep::new()
+            .from(MARKETPLACE_ADDRESS_EXPR)
+            .call(state.arbitration.open_case(1u64))
+            .expect(user_error(ERR_CONTRACT_IS_PAUSED)),
+    );
+
+    state.world.sc_call(ScCallStep::new().from(OWNER_ADDRESS_EXPR).call(state.arbitration.unpause()));
+    state.dispute_sale();
+    assert_eq!(state.case().status, CaseStatus::Voting);
+}
@@ -0,0 +1,185 @@
+use multiversx_sc::derive_imports::*;
+use multiversx_sc::imports::*;
+use wine_common::errors::*;
+use wine_roles::Role;
+
+#[derive(TopEncode, TopDecode, TypeAbi, PartialEq, Debug)]
+pub struct Attestation<M: ManagedTypeApi> {
+    pub issuer: ManagedAddress<M>,
+    // Key the attestation was signed with, kept for verification after key rotations
+    pub issuer_public_key: ManagedByteArray<M, 32>,
+    pub schema_id: u32,
+    // Registry wine the claim is about
+    pub wine_id: u32,
+    pub claim_hash: ManagedByteArray<M, 32>,
+    // 0 for attestations that do not expire
+    pub expiry_timestamp: u64,
+    pub issued_timestamp: u64,
+    pub signature: ManagedBuffer<M>,
+    pub revoked: bool,
+}
+
+// Attestations are signed off-chain by the issuer and can be published by anyone, e.g. a
+// relayer. The signature covers this contract's address, the schema, the wine, the claim hash
+// and the expiry, and is verified on publication; it is kept so third-party tooling can check
+// it again against `getAttestationSigningMessage`.
+#[multiversx_sc::module]
+pub trait AttestationsModule: crate::issuers::IssuersModule + wine_roles::RolesModule {
+    #[endpoint(publishAttestation)]
+    fn publish_attestation(
+        &self,
+        issuer: ManagedAddress,
+        schema_id: u32,
+        wine_id: u32,
+        claim_hash: ManagedByteArray<Self::Api, 32>,
+        expiry_timestamp: u64,
+        signature: ManagedBuffer,
+    ) -> u64 {
+        self.require_not_paused();
+        self.require_issuer(&issuer);
+        require!(self.require_schema(schema_id).active, ERR_SCHEMA_IS_INACTIVE);
+        require!(
+            expiry_timestamp == 0 || expiry_timestamp > self.blockchain().get_block_timestamp(),
+            ERR_EXPIRY_MUST_BE_IN_THE_FUTURE
+        );
+
+        let message =
+            self.attestation_signing_message(schema_id, wine_id, &claim_hash, expiry_timestamp);
+        let digest = self.crypto().keccak256(&message);
+        require!(self.attestation_by_digest(&digest).is_empty(), ERR_ATTESTATION_ALREADY_PUBLISHED);
+        require!(signature.len() == 64, ERR_INVALID_ATTESTATION_SIGNATURE);
+        let issuer_public_key = self.issuer_public_key(&issuer).get();
+        self.crypto().verify_ed25519(issuer_public_key.as_managed_buffer(), &message, &signature);
+
+        let attestation_id = self.attestation_count().get() + 1;
+        self.attestation_count().set(attestation_id);
+        self.attestations(attestation_id).set(&Attestation {
+            issuer: issuer.clone(),
+            issuer_public_key,
+            schema_id,
+            wine_id,
+            claim_hash: claim_hash.clone(),
+            expiry_timestamp,
+            issued_timestamp: self.blockchain().get_block_timestamp(),
+            signature,
+            revoked: false,
+        });
+        self.attestation_by_digest(&digest).set(attestation_id);
+        self.wine_attestations(wine_id).push(&attestation_id);
+
+        self.attestation_published_event(attestation_id, &issuer, schema_id, wine_id, &claim_hash);
+
+        attestation_id
+    }
+
+    // By the issuer, or by an admin e.g. for a compromised issuer key
+    #[endpoint(revokeAttestation)]
+    fn revoke_attestation(&self, attestation_id: u64, reason: ManagedBuffer) {
+        let mut attestation = self.require_attestation(attestation_id);
+        let caller = self.blockchain().get_caller();
+        require!(
+            caller == attestation.issuer || self.has_role(Role::Admin, &caller),
+            ERR_ONLY_THE_ISSUER_OR_AN_ADMIN_CAN_REVOKE
+        );
+        require!(!attestation.revoked, ERR_ATTESTATION_ALREADY_REVOKED);
+        attestation.revoked = true;
+        self.attestations(attestation_id).set(&attestation);
+
+        self.attestation_revoked_event(attestation_id, &caller, &reason);
+    }
+
+    // Internal helpers
+    fn require_attestation(&self, attestation_id: u64) -> Attestation<Self::Api> {
+        require!(!self.attestations(attestation_id).is_empty(), ERR_ATTESTATION_DOES_NOT_EXIST);
+        self.attestations(attestation_id).get()
+    }
+
+    fn attestation_signing_message(
+        &self,
+        schema_id: u32,
+        wine_id: u32,
+        claim_hash: &ManagedByteArray<Self::Api, 32>,
+        expiry_timestamp: u64,
+    ) -> ManagedBuffer {
+        self.serializer().top_encode_to_managed_buffer(&(
+            self.blockchain().get_sc_address(),
+            schema_id,
+            wine_id,
+            claim_hash.clone(),
+            expiry_timestamp,
+        ))
+    }
+
+    // View functions
+    #[view(getAttestation)]
+    fn get_attestation(&self, attestation_id: u64) -> Attestation<Self::Api> {
+        self.require_attestation(attestation_id)
+    }
+
+    #[view(getAttestationCount)]
+    fn get_attestation_count(&self) -> u64 {
+        self.attestation_count().get()
+    }
+
+    // Published, not revoked and not expired
+    #[view(isAttestationValid)]
+    fn is_attestation_valid(&self, attestation_id: u64) -> bool {
+        if self.attestations(attestation_id).is_empty() {
+            return false;
+        }
+        let attestation = self.attestations(attestation_id).get();
+        !attestation.revoked
+            && (attestation.expiry_timestamp == 0
+                || self.blockchain().get_block_timestamp() <= attestation.expiry_timestamp)
+    }
+
+    // Bytes the issuer signs for an attestation
+    #[view(getAttestationSigningMessage)]
+    fn get_attestation_signing_message(
+        &self,
+        schema_id: u32,
+        wine_id: u32,
+        claim_hash: ManagedByteArray<Self::Api, 32>,
+        expiry_timestamp: u64,
+    ) -> ManagedBuffer {
+        self.attestation_signing_message(schema_id, wine_id, &claim_hash, expiry_timestamp)
+    }
+
+    #[view(getWineAttestations)]
+    fn get_wine_attestations(&self, wine_id: u32) -> MultiValueEncoded<u64> {
+        self.wine_attestations(wine_id).iter().collect()
+    }
+
+    // Storage mappers
+    #[storage_mapper("attestationCount")]
+    fn attestation_count(&self) -> SingleValueMapper<u64>;
+
+    #[storage_mapper("attestations")]
+    fn attestations(&self, attestation_id: u64) -> SingleValueMapper<Attestation<Self::Api>>;
+
+    // Keyed by the keccak256 of the signing message, so an attestation is published once
+    #[storage_mapper("attestationByDigest")]
+    fn attestation_by_digest(&self, digest: &ManagedByteArray<Self::Api, 32>) -> SingleValueMapper<u64>;
+
+    #[storage_mapper("wineAttestations")]
+    fn wine_attestations(&self, wine_id: u32) -> VecMapper<u64>;
+
+    // Events
+    #[event("attestationPublished")]
+    fn attestation_published_event(
+        &self,
+        #[indexed] attestation_id: u64,
+        #[indexed] issuer: &ManagedAddress,
+        #[indexed] schema_id: u32,
+        #[indexed] wine_id: u32,
+        claim_hash: &ManagedByteArray<Self::Api, 32>,
+    );
+
+    #[event("attestationRevoked")]
+    fn attestation_revoked_event(
+        &self,
+        #[indexed] attestation_id: u64,
+        #[indexed] revoked_by: &ManagedAddress,
+        reason: &ManagedBuffer,
+    );
+}
@@ -0,0 +1,142 @@
+use multiversx_sc::derive_imports::*;
+use multiversx_sc::imports::*;
+use wine_common::errors::*;
+use wine_roles::Role;
+
+// What kind of claim an attestation makes, e.g. "organic-2024" with the hash of its JSON schema
+#[derive(TopEncode, TopDecode, TypeAbi, PartialEq, Debug)]
+pub struct AttestationSchema<M: ManagedTypeApi> {
+    pub name: ManagedBuffer<M>,
+    pub definition_hash: ManagedBuffer<M>,
+    // Inactive schemas take no new attestations; existing ones stay valid
+    pub active: bool,
+}
+
+// Issuers are certification authorities with an ed25519 key; schemas define the claims they
+// can attest to. Both are curated by admins.
+#[multiversx_sc::module]
+pub trait IssuersModule: wine_roles::RolesModule {
+    #[endpoint(addAttestationIssuer)]
+    fn add_attestation_issuer(
+        &self,
+        issuer: ManagedAddress,
+        public_key: ManagedByteArray<Self::Api, 32>,
+    ) {
+        self.require_role(Role::Admin);
+        require!(self.issuer_public_key(&issuer).is_empty(), ERR_ALREADY_AN_ATTESTATION_ISSUER);
+        self.issuer_public_key(&issuer).set(&public_key);
+
+        self.attestation_issuer_set_event(&issuer, &public_key);
+    }
+
+    // Attestations published before the rotation keep the key they were signed with
+    #[endpoint(rotateIssuerKey)]
+    fn rotate_issuer_key(&self, public_key: ManagedByteArray<Self::Api, 32>) {
+        let issuer = self.blockchain().get_caller();
+        self.require_issuer(&issuer);
+        self.issuer_public_key(&issuer).set(&public_key);
+
+        self.attestation_issuer_set_event(&issuer, &public_key);
+    }
+
+    #[endpoint(removeAttestationIssuer)]
+    fn remove_attestation_issuer(&self, issuer: ManagedAddress) {
+        self.require_role(Role::Admin);
+        self.require_issuer(&issuer);
+        self.issuer_public_key(&issuer).clear();
+
+        self.attestation_issuer_removed_event(&issuer);
+    }
+
+    #[endpoint(registerSchema)]
+    fn register_schema(&self, name: ManagedBuffer, definition_hash: ManagedBuffer) -> u32 {
+        self.require_role(Role::Admin);
+        require!(!name.is_empty(), ERR_SCHEMA_NAME_CANNOT_BE_EMPTY);
+
+        let schema_id = self.schema_count().get() + 1;
+        self.schema_count().set(schema_id);
+        self.schemas(schema_id).set(&AttestationSchema {
+            name,
+            definition_hash,
+            active: true,
+        });
+
+        self.schema_registered_event(schema_id, &self.schemas(schema_id).get());
+
+        schema_id
+    }
+
+    #[endpoint(deactivateSchema)]
+    fn deactivate_schema(&self, schema_id: u32) {
+        self.require_role(Role::Admin);
+        let mut schema = self.require_schema(schema_id);
+        require!(schema.active, ERR_SCHEMA_IS_ALREADY_INACTIVE);
+        schema.active = false;
+        self.schemas(schema_id).set(&schema);
+
+        self.schema_deactivated_event(schema_id);
+    }
+
+    // Internal helpers
+    fn require_issuer(&self, issuer: &ManagedAddress) {
+        require!(!self.issuer_public_key(issuer).is_empty(), ERR_NOT_AN_ATTESTATION_ISSUER);
+    }
+
+    fn require_schema(&self, schema_id: u32) -> AttestationSchema<Self::Api> {
+        require!(!self.schemas(schema_id).is_empty(), ERR_SCHEMA_DOES_NOT_EXIST);
+        self.schemas(schema_id).get()
+    }
+
+    // View functions
+    #[view(getIssuerPublicKey)]
+    fn get_issuer_public_key(
+        &self,
+        issuer: ManagedAddress,
+    ) -> OptionalValue<ManagedByteArray<Self::Api, 32>> {
+        if self.issuer_public_key(&issuer).is_empty() {
+            OptionalValue::None
+        } else {
+            OptionalValue::Some(self.issuer_public_key(&issuer).get())
+        }
+    }
+
+    #[view(getSchema)]
+    fn get_schema(&self, schema_id: u32) -> AttestationSchema<Self::Api> {
+        self.require_schema(schema_id)
+    }
+
+    #[view(getSchemaCount)]
+    fn get_schema_count(&self) -> u32 {
+        self.schema_count().get()
+    }
+
+    // Storage mappers
+    #[storage_mapper("issuerPublicKey")]
+    fn issuer_public_key(
+        &self,
+        issuer: &ManagedAddress,
+    ) -> SingleValueMapper<ManagedByteArray<Self::Api, 32>>;
+
+    #[storage_mapper("schemaCount")]
+    fn schema_count(&self) -> SingleValueMapper<u32>;
+
+    #[storage_mapper("schemas")]
+    fn schemas(&self, schema_id: u32) -> SingleValueMapper<AttestationSchema<Self::Api>>;
+
+    // Events
+    #[event("attestationIssuerSet")]
+    fn attestation_issuer_set_event(
+        &self,
+        #[indexed] issuer: &ManagedAddress,
+        public_key: &ManagedByteArray<Self::Api, 32>,
+    );
+
+    #[event("attestationIssuerRemoved")]
+    fn attestation_issuer_removed_event(&self, #[indexed] issuer: &ManagedAddress);
+
+    #[event("schemaRegistered")]
+    fn schema_registered_event(&self, #[indexed] schema_id: u32, schema: &AttestationSchema<Self::Api>);
+
+    #[event("schemaDeactivated")]
+    fn schema_deactivated_event(&self, #[indexed] schema_id: u32);
+}
@@ -0,0 +1,23 @@
+#![no_std]
+
+use multiversx_sc::imports::*;
+
+pub mod attestations;
+pub mod issuers;
+
+pub use attestations::Attestation;
+pub use issuers::AttestationSchema;
+
+// Verifiable credentials for wine certifications. Certification authorities sign attestations
+// about registry wines with their ed25519 keys; the registry's certifications can reference
+// them by id.
+#[multiversx_sc::contract]
+pub trait WineAttestations:
+    attestations::AttestationsModule + issuers::IssuersModule + wine_roles::RolesModule
+{
+    #[init]
+    fn init(&self) {}
+
+    #[upgrade]
+    fn upgrade(&self) {}
+}
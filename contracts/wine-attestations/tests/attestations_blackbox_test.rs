@@ -0,0 +1,240 @@
+use ed25519_dalek::{Signer, SigningKey};
+use multiversx_sc::codec::top_encode_to_vec_u8_or_panic;
+use multiversx_sc_scenario::api::StaticApi;
+use multiversx_sc_scenario::imports::*;
+use wine_attestations::attestations::ProxyTrait as _;
+use wine_attestations::issuers::ProxyTrait as _;
+use wine_attestations::Attestation;
+use wine_attestations::ProxyTrait as _;
+use wine_common::errors::*;
+
+const ATTESTATIONS_PATH_EXPR: &str = "mxsc:output/wine-attestations.mxsc.json";
+const ATTESTATIONS_ADDRESS_EXPR: &str = "sc:wine-attestations";
+const OWNER_ADDRESS_EXPR: &str = "address:owner";
+const ISSUER_ADDRESS_EXPR: &str = "address:issuer";
+const RELAYER_ADDRESS_EXPR: &str = "address:relayer";
+
+const START_TIMESTAMP: u64 = 1_000;
+const WINE_ID: u32 = 7;
+
+type AttestationsContract = ContractInfo<wine_attestations::Proxy<StaticApi>>;
+
+fn user_error(message: &str) -> TxExpect {
+    TxExpect::user_error(format!("str:{message}"))
+}
+
+fn issuer_key() -> SigningKey {
+    SigningKey::from_bytes(&[1u8; 32])
+}
+
+fn public_key(signing_key: &SigningKey) -> ManagedByteArray<StaticApi, 32> {
+    ManagedByteArray::from(&signing_key.verifying_key().to_bytes())
+}
+
+fn claim_hash(claim: u8) -> ManagedByteArray<StaticApi, 32> {
+    ManagedByteArray::from(&[claim; 32])
+}
+
+// Signature by `signer` over the message the contract rebuilds for an organic claim on the wine
+fn sign_attestation(signer: &SigningKey, claim: u8, expiry_timestamp: u64) -> ManagedBuffer<StaticApi> {
+    let attestations = AddressValue::from(ATTESTATIONS_ADDRESS_EXPR).to_address();
+    let message = top_encode_to_vec_u8_or_panic(&(
+        ManagedAddress::<StaticApi>::from(&attestations),
+        1u32,
+        WINE_ID,
+        claim_hash(claim),
+        expiry_timestamp,
+    ));
+    ManagedBuffer::from(&signer.sign(&message).to_bytes()[..])
+}
+
+struct AttestationsTestState {
+    world: ScenarioWorld,
+    attestations: AttestationsContract,
+}
+
+impl AttestationsTestState {
+    // One certification authority with an ed25519 key and an "organic-2024" schema; attestations
+    // are published by a relayer on the issuer's behalf
+    fn new() -> Self {
+        let mut world = ScenarioWorld::new();
+        world.register_contract(ATTESTATIONS_PATH_EXPR, wine_attestations::ContractBuilder);
+
+        world.set_state_step(
+            SetStateStep::new()
+                .block_timestamp(START_TIMESTAMP)
+                .put_account(OWNER_ADDRESS_EXPR, Account::new().nonce(1))
+                .put_account(ISSUER_ADDRESS_EXPR, Account::new().nonce(1))
+                .put_account(RELAYER_ADDRESS_EXPR, Account::new().nonce(1))
+                .new_address(OWNER_ADDRESS_EXPR, 1, ATTESTATIONS_ADDRESS_EXPR),
+        );
+
+        let mut attestations = AttestationsContract::new(ATTESTATIONS_ADDRESS_EXPR);
+        let attestations_code = world.code_expression(ATTESTATIONS_PATH_EXPR);
+        world.sc_deploy(
+            ScDeployStep::new()
+                .from(OWNER_ADDRESS_EXPR)
+                .code(attestations_code)
+                .call(attestations.init()),
+        );
+
+        // Issuers and schemas are curated by admins
+        let issuer = AddressValue::from(ISSUER_ADDRESS_EXPR).to_address();
+        world.sc_call(
+            ScCallStep::new()
+                .from(ISSUER_ADDRESS_EXPR)
+                .call(attestations.add_attestation_issuer(managed_address!(&issuer), public_key(&issuer_key())))
+                .expect(user_error(ERR_CALLER_DOES_NOT_HAVE_THE_REQUIRED_ROLE)),
+        );
+        world.sc_call(
+            ScCallStep::new()
+                .from(OWNER_ADDRESS_EXPR)
+                .call(attestations.add_attestation_issuer(managed_address!(&issuer), public_key(&issuer_key()))),
+        );
+        world.sc_call(
+            ScCallStep::new()
+                .from(ISSUER_ADDRESS_EXPR)
+                .call(attestations.register_schema(ManagedBuffer::from("organic-2024"), ManagedBuffer::from("0xdef")))
+                .expect(user_error(ERR_CALLER_DOES_NOT_HAVE_THE_REQUIRED_ROLE)),
+        );
+        world.sc_call(
+            ScCallStep::new()
+                .from(OWNER_ADDRESS_EXPR)
+                .call(attestations.register_schema(ManagedBuffer::from("organic-2024"), ManagedBuffer::from("0xdef")))
+                .expect_value(1u32),
+        );
+
+        AttestationsTestState { world, attestations }
+    }
+
+    fn set_block_timestamp(&mut self, timestamp: u64) {
+        self.world.set_state_step(SetStateStep::new().block_timestamp(timestamp));
+    }
+
+    fn publish_attestation(
+        &mut self,
+        claim: u8,
+        expiry_timestamp: u64,
+        signature: ManagedBuffer<StaticApi>,
+    ) -> TypedScCall<u64> {
+        let issuer = AddressValue::from(ISSUER_ADDRESS_EXPR).to_address();
+        ScCallStep::new().from(RELAYER_ADDRESS_EXPR).call(self.attestations.publish_attestation(
+            managed_address!(&issuer),
+            1u32,
+            WINE_ID,
+            claim_hash(claim),
+            expiry_timestamp,
+            signature,
+        ))
+    }
+
+    fn revoke_attestation(&mut self, caller: &str, attestation_id: u64) -> TypedScCall<()> {
+        ScCallStep::new()
+            .from(caller)
+            .call(self.attestations.revoke_attestation(attestation_id, ManagedBuffer::from("lab error")))
+    }
+
+    fn is_attestation_valid(&mut self, attestation_id: u64) -> bool {
+        self.world.quick_query(self.attestations.is_attestation_valid(attestation_id))
+    }
+}
+
+#[test]
+fn signed_attestations_are_published_once_and_expire() {
+    let mut state = AttestationsTestState::new();
+    let expiry = START_TIMESTAMP + 100;
+
+    let step = state.publish_attestation(1, START_TIMESTAMP, sign_attestation(&issuer_key(), 1, START_TIMESTAMP));
+    state.world.sc_call(step.expect(user_error(ERR_EXPIRY_MUST_BE_IN_THE_FUTURE)));
+    let step = state.publish_attestation(1, expiry, ManagedBuffer::from("too short"));
+    state.world.sc_call(step.expect(user_error(ERR_INVALID_ATTESTATION_SIGNATURE)));
+    let outsider = SigningKey::from_bytes(&[9u8; 32]);
+    let step = state.publish_attestation(1, expiry, sign_attestation(&outsider, 1, expiry));
+    state.world.sc_call(step.expect(TxExpect::err(10, "str:invalid signature")));
+
+    let step = state.publish_attestation(1, expiry, sign_attestation(&issuer_key(), 1, expiry));
+    state.world.sc_call(step.expect_value(1u64));
+    let step = state.publish_attestation(1, expiry, sign_attestation(&issuer_key(), 1, expiry));
+    state.world.sc_call(step.expect(user_error(ERR_ATTESTATION_ALREADY_PUBLISHED)));
+    let step = state.publish_attestation(2, 0, sign_attestation(&issuer_key(), 2, 0));
+    state.world.sc_call(step.expect_value(2u64));
+
+    let attestation: Attestation<StaticApi> = state.world.quick_query(state.attestations.get_attestation(1u64));
+    assert_eq!(attestation.issuer_public_key, public_key(&issuer_key()));
+    assert_eq!(attestation.issued_timestamp, START_TIMESTAMP);
+    let wine_attestations: MultiValueVec<u64> =
+        state.world.quick_query(state.attestations.get_wine_attestations(WINE_ID));
+    assert_eq!(wine_attestations.into_vec(), vec![1, 2]);
+
+    // Only the attestation with an expiry lapses
+    state.set_block_timestamp(expiry + 1);
+    assert!(!state.is_attestation_valid(1));
+    assert!(state.is_attestation_valid(2));
+}
+
+#[test]
+fn attestations_are_revoked_by_the_issuer_or_an_admin() {
+    let mut state = AttestationsTestState::new();
+    for claim in [1u8, 2] {
+        let step = state.publish_attestation(claim, 0, sign_attestation(&issuer_key(), claim, 0));
+        state.world.sc_call(step);
+    }
+
+    let step = state.revoke_attestation(RELAYER_ADDRESS_EXPR, 1);
+    state.world.sc_call(step.expect(user_error(ERR_ONLY_THE_ISSUER_OR_AN_ADMIN_CAN_REVOKE)));
+    let step = state.revoke_attestation(ISSUER_ADDRESS_EXPR, 1);
+    state.world.sc_call(step);
+    let step = state.revoke_attestation(OWNER_ADDRESS_EXPR, 1);
+    state.world.sc_call(step.expect(user_error(ERR_ATTESTATION_ALREADY_REVOKED)));
+    let step = state.revoke_attestation(OWNER_ADDRESS_EXPR, 2);
+    state.world.sc_call(step);
+
+    assert!(!state.is_attestation_valid(1));
+    assert!(!state.is_attestation_valid(2));
+}
+
+#[test]
+fn rotated_and_removed_issuers_can_no_longer_sign() {
+    let mut state = AttestationsTestState::new();
+    let step = state.publish_attestation(1, 0, sign_attestation(&issuer_key(), 1, 0));
+    state.world.sc_call(step);
+
+    let rotated_key = SigningKey::from_bytes(&[2u8; 32]);
+    state.world.sc_call(
+        ScCallStep::new()
+            .from(RELAYER_ADDRESS_EXPR)
+            .call(state.attestations.rotate_issuer_key(public_key(&rotated_key)))
+            .expect(user_error(ERR_NOT_AN_ATTESTATION_ISSUER)),
+    );
+    state.world.sc_call(
+        ScCallStep::new()
+            .from(ISSUER_ADDRESS_EXPR)
+            .call(state.attestations.rotate_issuer_key(public_key(&rotated_key))),
+    );
+    let step = state.publish_attestation(2, 0, sign_attestation(&issuer_key(), 2, 0));
+    state.world.sc_call(step.expect(TxExpect::err(10, "str:invalid signature")));
+    let step = state.publish_attestation(2, 0, sign_attestation(&rotated_key, 2, 0));
+    state.world.sc_call(step.expect_value(2u64));
+
+    // Earlier attestations keep the key they were signed with
+    let attestation: Attestation<StaticApi> = state.world.quick_query(state.attestations.get_attestation(1u64));
+    assert_eq!(attestation.issuer_public_key, public_key(&issuer_key()));
+    assert!(state.is_attestation_valid(1));
+
+    state.world.sc_call(
+        ScCallStep::new()
+            .from(OWNER_ADDRESS_EXPR)
+            .call(state.attestations.deactivate_schema(1u32)),
+    );
+    let step = state.publish_attestation(3, 0, sign_attestation(&rotated_key, 3, 0));
+    state.world.sc_call(step.expect(user_error(ERR_SCHEMA_IS_INACTIVE)));
+
+    let issuer = AddressValue::from(ISSUER_ADDRESS_EXPR).to_address();
+    state.world.sc_call(
+        ScCallStep::new()
+            .from(OWNER_ADDRESS_EXPR)
+            .call(state.attestations.remove_attestation_issuer(managed_address!(&issuer))),
+    );
+    let step = state.publish_attestation(3, 0, sign_attestation(&rotated_key, 3, 0));
+    state.world.sc_call(step.expect(user_error(ERR_NOT_AN_ATTESTATION_ISSUER)));
+}
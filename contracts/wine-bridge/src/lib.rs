@@ -0,0 +1,26 @@
+#![no_std]
+
+use multiversx_sc::imports::*;
+use wine_common::errors::*;
+
+pub mod relayers;
+pub mod transfers;
+
+pub use transfers::{OutboundTransfer, WineNftAttributes, WineSnapshot};
+
+// Bridge adapter for mirroring wines on other chains. Outgoing bottles are locked here with
+// a snapshot of their registry metadata; returning ones are released on a quorum of relayer
+// signatures.
+#[multiversx_sc::contract]
+pub trait WineBridge:
+    relayers::RelayersModule + transfers::TransfersModule + wine_roles::RolesModule
+{
+    #[init]
+    fn init(&self, wine_collection: TokenIdentifier) {
+        require!(wine_collection.is_valid_esdt_identifier(), ERR_INVALID_WINE_COLLECTION);
+        self.wine_collection().set(&wine_collection);
+    }
+
+    #[upgrade]
+    fn upgrade(&self) {}
+}
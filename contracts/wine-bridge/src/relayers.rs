@@ -0,0 +1,116 @@
+use multiversx_sc::imports::*;
+use wine_common::errors::*;
+use wine_roles::Role;
+
+pub const MAX_RELAYERS: usize = 10;
+
+// Relayers watch the other chains and co-sign wines coming back. A return is released once
+// at least `relayerQuorum` distinct relayers have signed it with their ed25519 keys.
+#[multiversx_sc::module]
+pub trait RelayersModule: wine_roles::RolesModule {
+    #[endpoint(addSupportedChain)]
+    fn add_supported_chain(&self, chain: ManagedBuffer) {
+        self.require_role(Role::Admin);
+        require!(!chain.is_empty(), ERR_CHAIN_NAME_CANNOT_BE_EMPTY);
+        require!(self.supported_chains().insert(chain.clone()), ERR_CHAIN_ALREADY_SUPPORTED);
+        self.supported_chain_added_event(&chain);
+    }
+
+    // Wines already on a removed chain can still come back
+    #[endpoint(removeSupportedChain)]
+    fn remove_supported_chain(&self, chain: ManagedBuffer) {
+        self.require_role(Role::Admin);
+        require!(self.supported_chains().swap_remove(&chain), ERR_CHAIN_NOT_SUPPORTED);
+        self.supported_chain_removed_event(&chain);
+    }
+
+    #[endpoint(addRelayer)]
+    fn add_relayer(&self, public_key: ManagedByteArray<Self::Api, 32>) {
+        self.require_role(Role::Admin);
+        require!(self.relayers().len() < MAX_RELAYERS, ERR_MAXIMUM_NUMBER_OF_RELAYERS_REACHED);
+        require!(self.relayers().insert(public_key.clone()), ERR_BRIDGE_ALREADY_A_RELAYER);
+        self.relayer_added_event(&public_key);
+    }
+
+    #[endpoint(removeRelayer)]
+    fn remove_relayer(&self, public_key: ManagedByteArray<Self::Api, 32>) {
+        self.require_role(Role::Admin);
+        require!(self.relayers().swap_remove(&public_key), ERR_BRIDGE_NOT_A_RELAYER);
+        require!(
+            self.relayers().len() >= self.relayer_quorum().get(),
+            ERR_REMOVAL_WOULD_LEAVE_FEWER_RELAYERS_THAN_THE_QUORUM
+        );
+        self.relayer_removed_event(&public_key);
+    }
+
+    #[endpoint(setRelayerQuorum)]
+    fn set_relayer_quorum(&self, quorum: usize) {
+        self.require_role(Role::Admin);
+        require!(quorum > 0, ERR_QUORUM_MUST_BE_GREATER_THAN_0);
+        require!(quorum <= self.relayers().len(), ERR_QUORUM_EXCEEDS_THE_NUMBER_OF_RELAYERS);
+        self.relayer_quorum().set(quorum);
+        self.relayer_quorum_updated_event(quorum);
+    }
+
+    // Internal helpers
+    // Fails the transaction unless enough distinct relayers signed the message; an invalid
+    // signature fails it too
+    fn require_relayer_quorum(
+        &self,
+        message: &ManagedBuffer,
+        signatures: MultiValueEncoded<MultiValue2<ManagedByteArray<Self::Api, 32>, ManagedBuffer>>,
+    ) {
+        let quorum = self.relayer_quorum().get();
+        require!(quorum > 0, ERR_RELAYER_QUORUM_NOT_SET);
+        require!(signatures.len() <= MAX_RELAYERS, ERR_TOO_MANY_SIGNATURES);
+
+        let mut signers = ManagedVec::<Self::Api, ManagedByteArray<Self::Api, 32>>::new();
+        for signature_pair in signatures {
+            let (public_key, signature) = signature_pair.into_tuple();
+            require!(self.relayers().contains(&public_key), ERR_SIGNER_IS_NOT_A_RELAYER);
+            require!(!signers.contains(&public_key), ERR_DUPLICATE_RELAYER_SIGNATURE);
+            require!(signature.len() == 64, ERR_INVALID_RELAYER_SIGNATURE);
+            self.crypto().verify_ed25519(public_key.as_managed_buffer(), message, &signature);
+            signers.push(public_key);
+        }
+        require!(signers.len() >= quorum, ERR_NOT_ENOUGH_RELAYER_SIGNATURES);
+    }
+
+    // View functions
+    #[view(getSupportedChains)]
+    fn get_supported_chains(&self) -> MultiValueEncoded<ManagedBuffer> {
+        self.supported_chains().iter().collect()
+    }
+
+    #[view(getRelayers)]
+    fn get_relayers(&self) -> MultiValueEncoded<ManagedByteArray<Self::Api, 32>> {
+        self.relayers().iter().collect()
+    }
+
+    // Storage mappers
+    #[storage_mapper("supportedChains")]
+    fn supported_chains(&self) -> UnorderedSetMapper<ManagedBuffer>;
+
+    #[storage_mapper("relayers")]
+    fn relayers(&self) -> UnorderedSetMapper<ManagedByteArray<Self::Api, 32>>;
+
+    #[view(getRelayerQuorum)]
+    #[storage_mapper("relayerQuorum")]
+    fn relayer_quorum(&self) -> SingleValueMapper<usize>;
+
+    // Events
+    #[event("supportedChainAdded")]
+    fn supported_chain_added_event(&self, #[indexed] chain: &ManagedBuffer);
+
+    #[event("supportedChainRemoved")]
+    fn supported_chain_removed_event(&self, #[indexed] chain: &ManagedBuffer);
+
+    #[event("relayerAdded")]
+    fn relayer_added_event(&self, #[indexed] public_key: &ManagedByteArray<Self::Api, 32>);
+
+    #[event("relayerRemoved")]
+    fn relayer_removed_event(&self, #[indexed] public_key: &ManagedByteArray<Self::Api, 32>);
+
+    #[event("relayerQuorumUpdated")]
+    fn relayer_quorum_updated_event(&self, quorum: usize);
+}
@@ -0,0 +1,224 @@
+use multiversx_sc::derive_imports::*;
+use multiversx_sc::imports::*;
+pub use wine_common::WineNftAttributes;
+use wine_common::errors::*;
+
+// Registry metadata of a wine as it was when first bridged, for the mirror on the other chain
+#[derive(TopEncode, TopDecode, NestedEncode, NestedDecode, TypeAbi, PartialEq, Debug)]
+pub struct WineSnapshot<M: ManagedTypeApi> {
+    pub name: ManagedBuffer<M>,
+    pub royalties: BigUint<M>,
+    pub uris: ManagedVec<M, ManagedBuffer<M>>,
+    pub attributes: WineNftAttributes<M>,
+}
+
+#[derive(TopEncode, TopDecode, TypeAbi, PartialEq, Debug)]
+pub struct OutboundTransfer<M: ManagedTypeApi> {
+    pub sender: ManagedAddress<M>,
+    pub wine_nonce: u64,
+    pub bottles: BigUint<M>,
+    pub destination_chain: ManagedBuffer<M>,
+    // Address on the destination chain, in that chain's own encoding
+    pub recipient: ManagedBuffer<M>,
+    pub timestamp: u64,
+}
+
+// Wines leave by being locked here, and the `wineBridgedOut` event carries everything the
+// relayers need to mint the mirror. Mirrors are burned on the other chain to come back; the
+// relayers sign the return and the original bottles are released, so the registry's
+// provenance of each nonce is never broken.
+#[multiversx_sc::module]
+pub trait TransfersModule: crate::relayers::RelayersModule + wine_roles::RolesModule {
+    // Lock the wine sent along for mirroring to `recipient` on `destination_chain`
+    #[payable("*")]
+    #[endpoint(bridgeOut)]
+    fn bridge_out(&self, destination_chain: ManagedBuffer, recipient: ManagedBuffer) -> u64 {
+        self.require_not_paused();
+        require!(
+            self.supported_chains().contains(&destination_chain),
+            ERR_DESTINATION_CHAIN_NOT_SUPPORTED
+        );
+        require!(!recipient.is_empty(), ERR_RECIPIENT_CANNOT_BE_EMPTY);
+        let payment = self.call_value().single_esdt();
+        require!(payment.token_identifier == self.wine_collection().get(), ERR_INVALID_WINE_TOKEN);
+
+        if self.wine_snapshots(payment.token_nonce).is_empty() {
+            let token_data = self.blockchain().get_esdt_token_data(
+                &self.blockchain().get_sc_address(),
+                &payment.token_identifier,
+                payment.token_nonce,
+            );
+            let attributes: WineNftAttributes<Self::Api> = token_data.decode_attributes();
+            self.wine_snapshots(payment.token_nonce).set(&WineSnapshot {
+                name: token_data.name,
+                royalties: token_data.royalties,
+                uris: token_data.uris,
+                attributes,
+            });
+        }
+        self.locked_bottles(payment.token_nonce).update(|locked| *locked += &payment.amount);
+
+        let sender = self.blockchain().get_caller();
+        let transfer_id = self.outbound_count().get() + 1;
+        self.outbound_count().set(transfer_id);
+        let transfer = OutboundTransfer {
+            sender,
+            wine_nonce: payment.token_nonce,
+            bottles: payment.amount,
+            destination_chain,
+            recipient,
+            timestamp: self.blockchain().get_block_timestamp(),
+        };
+        self.outbound_transfers(transfer_id).set(&transfer);
+
+        self.wine_bridged_out_event(
+            transfer_id,
+            &transfer.destination_chain,
+            &transfer.sender,
+            &transfer,
+            &self.wine_snapshots(transfer.wine_nonce).get(),
+        );
+
+        transfer_id
+    }
+
+    // Release bottles returning from `source_chain`. `source_transfer_id` identifies the
+    // burn there and can be released only once.
+    #[endpoint(bridgeIn)]
+    fn bridge_in(
+        &self,
+        source_chain: ManagedBuffer,
+        source_transfer_id: ManagedBuffer,
+        wine_nonce: u64,
+        bottles: BigUint,
+        recipient: ManagedAddress,
+        signatures: MultiValueEncoded<MultiValue2<ManagedByteArray<Self::Api, 32>, ManagedBuffer>>,
+    ) {
+        self.require_not_paused();
+        require!(
+            !self.processed_inbound(&source_chain, &source_transfer_id).get(),
+            ERR_TRANSFER_ALREADY_PROCESSED
+        );
+        require!(bottles > 0, ERR_BOTTLES_MUST_BE_GREATER_THAN_0);
+        require!(
+            bottles <= self.locked_bottles(wine_nonce).get(),
+            ERR_NOT_ENOUGH_BOTTLES_LOCKED_FOR_THIS_WINE
+        );
+
+        let message = self.inbound_signing_message(
+            &source_chain,
+            &source_transfer_id,
+            wine_nonce,
+            &bottles,
+            &recipient,
+        );
+        self.require_relayer_quorum(&message, signatures);
+
+        self.processed_inbound(&source_chain, &source_transfer_id).set(true);
+        self.locked_bottles(wine_nonce).update(|locked| *locked -= &bottles);
+        self.send().direct_esdt(&recipient, &self.wine_collection().get(), wine_nonce, &bottles);
+
+        self.wine_bridged_in_event(&source_chain, &source_transfer_id, &recipient, wine_nonce, &bottles);
+    }
+
+    // Internal helpers
+    // Relayers sign this bridge's address and every release argument, so a signature cannot
+    // be replayed on another bridge or for another release
+    fn inbound_signing_message(
+        &self,
+        source_chain: &ManagedBuffer,
+        source_transfer_id: &ManagedBuffer,
+        wine_nonce: u64,
+        bottles: &BigUint,
+        recipient: &ManagedAddress,
+    ) -> ManagedBuffer {
+        self.serializer().top_encode_to_managed_buffer(&(
+            self.blockchain().get_sc_address(),
+            source_chain.clone(),
+            source_transfer_id.clone(),
+            wine_nonce,
+            bottles.clone(),
+            recipient.clone(),
+        ))
+    }
+
+    // View functions
+    #[view(getOutboundTransfer)]
+    fn get_outbound_transfer(&self, transfer_id: u64) -> OutboundTransfer<Self::Api> {
+        require!(!self.outbound_transfers(transfer_id).is_empty(), ERR_TRANSFER_DOES_NOT_EXIST);
+        self.outbound_transfers(transfer_id).get()
+    }
+
+    #[view(getOutboundTransferCount)]
+    fn get_outbound_transfer_count(&self) -> u64 {
+        self.outbound_count().get()
+    }
+
+    #[view(getWineSnapshot)]
+    fn get_wine_snapshot(&self, wine_nonce: u64) -> OptionalValue<WineSnapshot<Self::Api>> {
+        if self.wine_snapshots(wine_nonce).is_empty() {
+            OptionalValue::None
+        } else {
+            OptionalValue::Some(self.wine_snapshots(wine_nonce).get())
+        }
+    }
+
+    #[view(getLockedBottles)]
+    fn get_locked_bottles(&self, wine_nonce: u64) -> BigUint {
+        self.locked_bottles(wine_nonce).get()
+    }
+
+    #[view(isInboundProcessed)]
+    fn is_inbound_processed(
+        &self,
+        source_chain: ManagedBuffer,
+        source_transfer_id: ManagedBuffer,
+    ) -> bool {
+        self.processed_inbound(&source_chain, &source_transfer_id).get()
+    }
+
+    // Storage mappers
+    #[view(getWineCollection)]
+    #[storage_mapper("wineCollection")]
+    fn wine_collection(&self) -> SingleValueMapper<TokenIdentifier>;
+
+    #[storage_mapper("outboundCount")]
+    fn outbound_count(&self) -> SingleValueMapper<u64>;
+
+    #[storage_mapper("outboundTransfers")]
+    fn outbound_transfers(&self, transfer_id: u64) -> SingleValueMapper<OutboundTransfer<Self::Api>>;
+
+    #[storage_mapper("wineSnapshots")]
+    fn wine_snapshots(&self, wine_nonce: u64) -> SingleValueMapper<WineSnapshot<Self::Api>>;
+
+    #[storage_mapper("lockedBottles")]
+    fn locked_bottles(&self, wine_nonce: u64) -> SingleValueMapper<BigUint>;
+
+    #[storage_mapper("processedInbound")]
+    fn processed_inbound(
+        &self,
+        source_chain: &ManagedBuffer,
+        source_transfer_id: &ManagedBuffer,
+    ) -> SingleValueMapper<bool>;
+
+    // Events
+    #[event("wineBridgedOut")]
+    fn wine_bridged_out_event(
+        &self,
+        #[indexed] transfer_id: u64,
+        #[indexed] destination_chain: &ManagedBuffer,
+        #[indexed] sender: &ManagedAddress,
+        transfer: &OutboundTransfer<Self::Api>,
+        snapshot: &WineSnapshot<Self::Api>,
+    );
+
+    #[event("wineBridgedIn")]
+    fn wine_bridged_in_event(
+        &self,
+        #[indexed] source_chain: &ManagedBuffer,
+        #[indexed] source_transfer_id: &ManagedBuffer,
+        #[indexed] recipient: &ManagedAddress,
+        #[indexed] wine_nonce: u64,
+        bottles: &BigUint,
+    );
+}
@@ -0,0 +1,264 @@
+use ed25519_dalek::{Signer, SigningKey};
+use multiversx_sc::codec::top_encode_to_vec_u8_or_panic;
+use multiversx_sc_scenario::api::StaticApi;
+use multiversx_sc_scenario::imports::*;
+use wine_bridge::relayers::ProxyTrait as _;
+use wine_bridge::transfers::ProxyTrait as _;
+use wine_bridge::ProxyTrait as _;
+use wine_bridge::{OutboundTransfer, WineNftAttributes, WineSnapshot};
+use wine_common::errors::*;
+
+const BRIDGE_PATH_EXPR: &str = "mxsc:output/wine-bridge.mxsc.json";
+const BRIDGE_ADDRESS_EXPR: &str = "sc:wine-bridge";
+const OWNER_ADDRESS_EXPR: &str = "address:owner";
+const HOLDER_ADDRESS_EXPR: &str = "address:holder";
+
+const WINE_NFT_TOKEN_ID_EXPR: &str = "str:WINE-abcdef";
+
+const START_TIMESTAMP: u64 = 1_000;
+const CHAIN: &str = "ethereum";
+const RELAYER_QUORUM: usize = 2;
+
+type BridgeContract = ContractInfo<wine_bridge::Proxy<StaticApi>>;
+type RelayerSignatures = MultiValueVec<MultiValue2<ManagedByteArray<StaticApi, 32>, ManagedBuffer<StaticApi>>>;
+
+fn user_error(message: &str) -> TxExpect {
+    TxExpect::user_error(format!("str:{message}"))
+}
+
+fn wine_attributes() -> WineNftAttributes<StaticApi> {
+    WineNftAttributes {
+        wine_id: 7,
+        vineyard: ManagedBuffer::from("Chateau Test"),
+        variety: ManagedBuffer::from("Merlot"),
+        vintage_year: 2015,
+        region: ManagedBuffer::from("Bordeaux"),
+        quality_grade: 9,
+        ipfs_hash: ManagedBuffer::new(),
+    }
+}
+
+fn encoded_wine_attributes() -> Option<Vec<u8>> {
+    Some(top_encode_to_vec_u8_or_panic(&wine_attributes()))
+}
+
+fn relayer_keys() -> [SigningKey; 3] {
+    [1u8, 2, 3].map(|seed| SigningKey::from_bytes(&[seed; 32]))
+}
+
+fn public_key(signing_key: &SigningKey) -> ManagedByteArray<StaticApi, 32> {
+    ManagedByteArray::from(&signing_key.verifying_key().to_bytes())
+}
+
+// A return of `bottles` bottles of wine NFT 1 to the holder, burned as `transfer_id` on the
+// other chain, signed by each of `signers` over the message the bridge rebuilds
+fn sign_bridge_in(transfer_id: &str, bottles: u64, signers: &[&SigningKey]) -> RelayerSignatures {
+    let bridge = AddressValue::from(BRIDGE_ADDRESS_EXPR).to_address();
+    let holder = AddressValue::from(HOLDER_ADDRESS_EXPR).to_address();
+    let message = top_encode_to_vec_u8_or_panic(&(
+        ManagedAddress::<StaticApi>::from(&bridge),
+        ManagedBuffer::<StaticApi>::from(CHAIN),
+        ManagedBuffer::<StaticApi>::from(transfer_id),
+        1u64,
+        BigUint::<StaticApi>::from(bottles),
+        ManagedAddress::<StaticApi>::from(&holder),
+    ));
+    let signatures = signers
+        .iter()
+        .map(|signer| {
+            let signature = signer.sign(&message).to_bytes();
+            MultiValue2::from((public_key(signer), ManagedBuffer::from(&signature[..])))
+        })
+        .collect::<Vec<_>>();
+    MultiValueVec::from(signatures)
+}
+
+struct BridgeTestState {
+    world: ScenarioWorld,
+    bridge: BridgeContract,
+}
+
+impl BridgeTestState {
+    // Bridges to Ethereum with three relayers, two of whom must sign a return. The holder
+    // has three bottles of wine NFT 1.
+    fn new() -> Self {
+        let mut world = ScenarioWorld::new();
+        world.register_contract(BRIDGE_PATH_EXPR, wine_bridge::ContractBuilder);
+
+        world.set_state_step(
+            SetStateStep::new()
+                .block_timestamp(START_TIMESTAMP)
+                .put_account(OWNER_ADDRESS_EXPR, Account::new().nonce(1))
+                .put_account(
+                    HOLDER_ADDRESS_EXPR,
+                    Account::new()
+                        .nonce(1)
+                        .esdt_nft_balance(WINE_NFT_TOKEN_ID_EXPR, 1, "3", encoded_wine_attributes()),
+                )
+                .new_address(OWNER_ADDRESS_EXPR, 1, BRIDGE_ADDRESS_EXPR),
+        );
+
+        let mut bridge = BridgeContract::new(BRIDGE_ADDRESS_EXPR);
+        let bridge_code = world.code_expression(BRIDGE_PATH_EXPR);
+        world.sc_deploy(
+            ScDeployStep::new()
+                .from(OWNER_ADDRESS_EXPR)
+                .code(bridge_code)
+                .call(bridge.init(TokenIdentifier::from("WINE-abcdef"))),
+        );
+
+        // Chains and relayers are managed by an admin
+        world.sc_call(
+            ScCallStep::new()
+                .from(HOLDER_ADDRESS_EXPR)
+                .call(bridge.add_supported_chain(ManagedBuffer::from(CHAIN)))
+                .expect(user_error(ERR_CALLER_DOES_NOT_HAVE_THE_REQUIRED_ROLE)),
+        );
+        world.sc_call(
+            ScCallStep::new()
+                .from(OWNER_ADDRESS_EXPR)
+                .call(bridge.add_supported_chain(ManagedBuffer::from(CHAIN))),
+        );
+        for relayer in relayer_keys().iter() {
+            world.sc_call(
+                ScCallStep::new()
+                    .from(OWNER_ADDRESS_EXPR)
+                    .call(bridge.add_relayer(public_key(relayer))),
+            );
+        }
+        world.sc_call(
+            ScCallStep::new()
+                .from(OWNER_ADDRESS_EXPR)
+                .call(bridge.set_relayer_quorum(RELAYER_QUORUM)),
+        );
+
+        BridgeTestState { world, bridge }
+    }
+
+    fn bridge_out(&mut self, chain: &str, bottles: &str) -> TypedScCall<u64> {
+        ScCallStep::new()
+            .from(HOLDER_ADDRESS_EXPR)
+            .esdt_transfer(WINE_NFT_TOKEN_ID_EXPR, 1, bottles)
+            .call(self.bridge.bridge_out(ManagedBuffer::from(chain), ManagedBuffer::from("0xholder")))
+    }
+
+    fn bridge_in(&mut self, transfer_id: &str, bottles: u64, signatures: RelayerSignatures) -> TypedScCall<()> {
+        let holder = AddressValue::from(HOLDER_ADDRESS_EXPR).to_address();
+        ScCallStep::new().from(OWNER_ADDRESS_EXPR).call(self.bridge.bridge_in(
+            ManagedBuffer::from(CHAIN),
+            ManagedBuffer::from(transfer_id),
+            1u64,
+            bottles,
+            managed_address!(&holder),
+            signatures,
+        ))
+    }
+
+    fn locked_bottles(&mut self) -> BigUint<StaticApi> {
+        self.world.quick_query(self.bridge.get_locked_bottles(1u64))
+    }
+}
+
+#[test]
+fn bottles_are_locked_on_the_way_out_and_released_on_a_quorum() {
+    let mut state = BridgeTestState::new();
+
+    let step = state.bridge_out("solana", "2");
+    state.world.sc_call(step.expect(user_error(ERR_DESTINATION_CHAIN_NOT_SUPPORTED)));
+    let step = state.bridge_out(CHAIN, "2");
+    state.world.sc_call(step.expect_value(1u64));
+
+    // The registry metadata travels with the first transfer of each nonce
+    assert_eq!(state.locked_bottles(), 2u64);
+    let transfer: OutboundTransfer<StaticApi> = state.world.quick_query(state.bridge.get_outbound_transfer(1u64));
+    assert_eq!(transfer.bottles, 2u64);
+    assert_eq!(transfer.destination_chain, ManagedBuffer::from(CHAIN));
+    let snapshot: OptionalValue<WineSnapshot<StaticApi>> =
+        state.world.quick_query(state.bridge.get_wine_snapshot(1u64));
+    assert_eq!(snapshot.into_option().unwrap().attributes, wine_attributes());
+
+    let [first, _, third] = relayer_keys();
+    let step = state.bridge_in("burn-1", 1, sign_bridge_in("burn-1", 1, &[&first, &third]));
+    state.world.sc_call(step);
+    let step = state.bridge_in("burn-1", 1, sign_bridge_in("burn-1", 1, &[&first, &third]));
+    state.world.sc_call(step.expect(user_error(ERR_TRANSFER_ALREADY_PROCESSED)));
+
+    assert_eq!(state.locked_bottles(), 1u64);
+    state.world.check_state_step(
+        CheckStateStep::new()
+            .put_account(
+                HOLDER_ADDRESS_EXPR,
+                CheckAccount::new().esdt_nft_balance_and_attributes(
+                    WINE_NFT_TOKEN_ID_EXPR,
+                    1,
+                    "2",
+                    encoded_wine_attributes(),
+                ),
+            )
+            .put_account(
+                BRIDGE_ADDRESS_EXPR,
+                CheckAccount::new().esdt_nft_balance_and_attributes(
+                    WINE_NFT_TOKEN_ID_EXPR,
+                    1,
+                    "1",
+                    encoded_wine_attributes(),
+                ),
+            ),
+    );
+}
+
+#[test]
+fn returns_without_a_valid_quorum_are_rejected() {
+    let mut state = BridgeTestState::new();
+    let step = state.bridge_out(CHAIN, "2");
+    state.world.sc_call(step);
+    let [first, second, _] = relayer_keys();
+    let outsider = SigningKey::from_bytes(&[9u8; 32]);
+
+    let step = state.bridge_in("burn-1", 1, sign_bridge_in("burn-1", 1, &[&first]));
+    state.world.sc_call(step.expect(user_error(ERR_NOT_ENOUGH_RELAYER_SIGNATURES)));
+    let step = state.bridge_in("burn-1", 1, sign_bridge_in("burn-1", 1, &[&first, &outsider]));
+    state.world.sc_call(step.expect(user_error(ERR_SIGNER_IS_NOT_A_RELAYER)));
+    let step = state.bridge_in("burn-1", 1, sign_bridge_in("burn-1", 1, &[&first, &first]));
+    state.world.sc_call(step.expect(user_error(ERR_DUPLICATE_RELAYER_SIGNATURE)));
+    let step = state.bridge_in("burn-1", 3, sign_bridge_in("burn-1", 3, &[&first, &second]));
+    state.world.sc_call(step.expect(user_error(ERR_NOT_ENOUGH_BOTTLES_LOCKED_FOR_THIS_WINE)));
+
+    // Signatures over other release arguments do not verify
+    let step = state.bridge_in("burn-1", 2, sign_bridge_in("burn-1", 1, &[&first, &second]));
+    state.world.sc_call(step.expect(TxExpect::err(10, "str:invalid signature")));
+    assert_eq!(state.locked_bottles(), 2u64);
+}
+
+#[test]
+fn relayers_cannot_drop_below_the_quorum() {
+    let mut state = BridgeTestState::new();
+    let [first, second, _] = relayer_keys();
+
+    state.world.sc_call(
+        ScCallStep::new()
+            .from(HOLDER_ADDRESS_EXPR)
+            .call(state.bridge.remove_relayer(public_key(&first)))
+            .expect(user_error(ERR_CALLER_DOES_NOT_HAVE_THE_REQUIRED_ROLE)),
+    );
+    state.world.sc_call(
+        ScCallStep::new()
+            .from(OWNER_ADDRESS_EXPR)
+            .call(state.bridge.set_relayer_quorum(4usize))
+            .expect(user_error(ERR_QUORUM_EXCEEDS_THE_NUMBER_OF_RELAYERS)),
+    );
+    state.world.sc_call(
+        ScCallStep::new()
+            .from(OWNER_ADDRESS_EXPR)
+            .call(state.bridge.remove_relayer(public_key(&first))),
+    );
+    state.world.sc_call(
+        ScCallStep::new()
+            .from(OWNER_ADDRESS_EXPR)
+            .call(state.bridge.remove_relayer(public_key(&second)))
+            .expect(user_error(ERR_REMOVAL_WOULD_LEAVE_FEWER_RELAYERS_THAN_THE_QUORUM)),
+    );
+
+    let relayers: MultiValueVec<ManagedByteArray<StaticApi, 32>> = state.world.quick_query(state.bridge.get_relayers());
+    assert_eq!(relayers.into_vec().len(), 2);
+}
@@ -0,0 +1,164 @@
+use multiversx_sc::derive_imports::*;
+use multiversx_sc::imports::*;
+use wine_roles::Role;
+pub use wine_common::MAX_HUMIDITY_X100;
+
+use crate::deposits::DepositStatus;
+use wine_common::errors::*;
+
+pub const REGISTRY_LOG_GAS: u64 = 10_000_000;
+
+// Summary of the conditions a deposit was kept in, as reported by the cellar's keepers
+#[derive(TopEncode, TopDecode, TypeAbi, PartialEq, Debug)]
+pub struct StorageCertificate {
+    pub readings: u64,
+    pub min_temperature_x100: i32,
+    pub max_temperature_x100: i32,
+    pub min_humidity_x100: u32,
+    pub max_humidity_x100: u32,
+    pub first_timestamp: u64,
+    pub last_timestamp: u64,
+}
+
+// Keepers certify the conditions of stored deposits. When a registry is set, every reading
+// is also logged there against the wine, so it shows in the wine's storage history; this
+// contract must be one of the registry's storage loggers.
+#[multiversx_sc::module]
+pub trait CertificationModule: wine_roles::RolesModule + crate::deposits::DepositsModule {
+    #[endpoint(addCellarKeeper)]
+    fn add_cellar_keeper(&self, keeper: ManagedAddress) {
+        self.require_role(Role::Admin);
+        require!(self.cellar_keepers().insert(keeper.clone()), ERR_ALREADY_A_CELLAR_KEEPER);
+
+        self.cellar_keeper_added_event(&keeper);
+    }
+
+    #[endpoint(removeCellarKeeper)]
+    fn remove_cellar_keeper(&self, keeper: ManagedAddress) {
+        self.require_role(Role::Admin);
+        require!(self.cellar_keepers().swap_remove(&keeper), ERR_NOT_A_CELLAR_KEEPER);
+
+        self.cellar_keeper_removed_event(&keeper);
+    }
+
+    // Registry that receives the readings; an empty address stops forwarding
+    #[endpoint(setWineRegistry)]
+    fn set_wine_registry(&self, registry: ManagedAddress) {
+        self.require_role(Role::Admin);
+        if registry.is_zero() {
+            self.wine_registry_address().clear();
+        } else {
+            self.wine_registry_address().set(&registry);
+        }
+    }
+
+    // Hash of the cellar's location, reported with every reading
+    #[endpoint(setCellarLocationHash)]
+    fn set_cellar_location_hash(&self, location_hash: ManagedByteArray<Self::Api, 32>) {
+        self.require_role(Role::Admin);
+        self.cellar_location_hash().set(&location_hash);
+    }
+
+    #[endpoint(recordStorageCondition)]
+    fn record_storage_condition(&self, deposit_id: u32, temperature_x100: i32, humidity_x100: u32) {
+        let keeper = self.blockchain().get_caller();
+        require!(self.cellar_keepers().contains(&keeper), ERR_NOT_A_CELLAR_KEEPER);
+        require!(humidity_x100 <= MAX_HUMIDITY_X100, ERR_HUMIDITY_CANNOT_EXCEED_100_PERCENT);
+        let deposit = self.require_deposit(deposit_id);
+        require!(deposit.status == DepositStatus::Stored, ERR_WINE_IS_NO_LONGER_STORED);
+
+        let timestamp = self.blockchain().get_block_timestamp();
+        let certificate_mapper = self.storage_certificate(deposit_id);
+        let certificate = if certificate_mapper.is_empty() {
+            StorageCertificate {
+                readings: 1,
+                min_temperature_x100: temperature_x100,
+                max_temperature_x100: temperature_x100,
+                min_humidity_x100: humidity_x100,
+                max_humidity_x100: humidity_x100,
+                first_timestamp: timestamp,
+                last_timestamp: timestamp,
+            }
+        } else {
+            let certificate = certificate_mapper.get();
+            StorageCertificate {
+                readings: certificate.readings + 1,
+                min_temperature_x100: certificate.min_temperature_x100.min(temperature_x100),
+                max_temperature_x100: certificate.max_temperature_x100.max(temperature_x100),
+                min_humidity_x100: certificate.min_humidity_x100.min(humidity_x100),
+                max_humidity_x100: certificate.max_humidity_x100.max(humidity_x100),
+                first_timestamp: certificate.first_timestamp,
+                last_timestamp: timestamp,
+            }
+        };
+        certificate_mapper.set(&certificate);
+
+        if !self.wine_registry_address().is_empty() {
+            self.tx()
+                .to(&self.wine_registry_address().get())
+                .gas(REGISTRY_LOG_GAS)
+                .raw_call("logStorageCondition")
+                .argument(&deposit.wine_id)
+                .argument(&temperature_x100)
+                .argument(&humidity_x100)
+                .argument(&self.cellar_location_hash().get())
+                .transfer_execute();
+        }
+
+        self.storage_condition_recorded_event(deposit_id, &keeper, temperature_x100, humidity_x100);
+    }
+
+    // View functions
+    #[view(isCellarKeeper)]
+    fn is_cellar_keeper(&self, address: ManagedAddress) -> bool {
+        self.cellar_keepers().contains(&address)
+    }
+
+    #[view(getWineRegistry)]
+    fn get_wine_registry(&self) -> OptionalValue<ManagedAddress> {
+        if self.wine_registry_address().is_empty() {
+            OptionalValue::None
+        } else {
+            OptionalValue::Some(self.wine_registry_address().get())
+        }
+    }
+
+    // Kept after withdrawal, as a record of the stay
+    #[view(getStorageCertificate)]
+    fn get_storage_certificate(&self, deposit_id: u32) -> OptionalValue<StorageCertificate> {
+        if self.storage_certificate(deposit_id).is_empty() {
+            OptionalValue::None
+        } else {
+            OptionalValue::Some(self.storage_certificate(deposit_id).get())
+        }
+    }
+
+    // Storage mappers
+    #[storage_mapper("cellarKeepers")]
+    fn cellar_keepers(&self) -> UnorderedSetMapper<ManagedAddress>;
+
+    #[storage_mapper("wineRegistryAddress")]
+    fn wine_registry_address(&self) -> SingleValueMapper<ManagedAddress>;
+
+    #[storage_mapper("cellarLocationHash")]
+    fn cellar_location_hash(&self) -> SingleValueMapper<ManagedByteArray<Self::Api, 32>>;
+
+    #[storage_mapper("storageCertificate")]
+    fn storage_certificate(&self, deposit_id: u32) -> SingleValueMapper<StorageCertificate>;
+
+    // Events
+    #[event("cellarKeeperAdded")]
+    fn cellar_keeper_added_event(&self, #[indexed] keeper: &ManagedAddress);
+
+    #[event("cellarKeeperRemoved")]
+    fn cellar_keeper_removed_event(&self, #[indexed] keeper: &ManagedAddress);
+
+    #[event("storageConditionRecorded")]
+    fn storage_condition_recorded_event(
+        &self,
+        #[indexed] deposit_id: u32,
+        #[indexed] keeper: &ManagedAddress,
+        #[indexed] temperature_x100: i32,
+        humidity_x100: u32,
+    );
+}
@@ -0,0 +1,343 @@
+use multiversx_sc::derive_imports::*;
+use multiversx_sc::imports::*;
+use wine_roles::Role;
+pub use wine_common::WineNftAttributes;
+use wine_common::errors::*;
+
+#[derive(TopEncode, TopDecode, NestedEncode, NestedDecode, TypeAbi, PartialEq, Debug, Clone, Copy)]
+pub enum DepositStatus {
+    Stored,
+    Withdrawn,
+    // Seized for unpaid fees
+    Liquidated,
+}
+
+#[derive(TopEncode, TopDecode, TypeAbi, PartialEq, Debug)]
+pub struct CellarDeposit<M: ManagedTypeApi> {
+    pub owner: ManagedAddress<M>,
+    pub wine_id: u32,
+    pub wine_nonce: u64,
+    pub bottles: u32,
+    // Fee rate at the time of deposit, kept for the whole stay
+    pub fee_per_bottle_epoch: BigUint<M>,
+    pub deposit_epoch: u64,
+    // Fees paid so far, including any prepaid balance
+    pub fees_paid: BigUint<M>,
+    pub status: DepositStatus,
+    // Epoch of withdrawal or liquidation, 0 while stored
+    pub closed_epoch: u64,
+}
+
+// Collectors deposit wine NFTs for storage and pay a fee per bottle for every epoch started
+// in the cellar. Fees can be prepaid at any time and must be settled on withdrawal. Deposits
+// owing more than the liquidation grace period's worth of fees can be seized by admins.
+#[multiversx_sc::module]
+pub trait DepositsModule: wine_roles::RolesModule {
+    // Applies to deposits made from now on
+    #[endpoint(setStorageFee)]
+    fn set_storage_fee(&self, fee_per_bottle_epoch: BigUint) {
+        self.require_role(Role::FeeManager);
+        self.fee_per_bottle_epoch().set(&fee_per_bottle_epoch);
+
+        self.storage_fee_updated_event(&fee_per_bottle_epoch);
+    }
+
+    #[endpoint(setLiquidationGraceEpochs)]
+    fn set_liquidation_grace_epochs(&self, grace_epochs: u64) {
+        self.require_role(Role::Admin);
+        require!(grace_epochs > 0, ERR_GRACE_PERIOD_MUST_BE_GREATER_THAN_0);
+        self.liquidation_grace_epochs().set(grace_epochs);
+    }
+
+    #[payable("*")]
+    #[endpoint(depositWine)]
+    fn deposit_wine(&self) -> u32 {
+        self.require_not_paused();
+        let payment = self.call_value().single_esdt();
+        require!(payment.token_identifier == self.wine_collection().get(), ERR_INVALID_WINE_TOKEN);
+        let bottles = payment.amount.to_u64().unwrap_or(u64::MAX);
+        require!(bottles > 0 && bottles <= u32::MAX as u64, ERR_INVALID_BOTTLE_AMOUNT);
+
+        let token_data = self.blockchain().get_esdt_token_data(
+            &self.blockchain().get_sc_address(),
+            &payment.token_identifier,
+            payment.token_nonce,
+        );
+        let attributes: WineNftAttributes<Self::Api> = token_data.decode_attributes();
+
+        let owner = self.blockchain().get_caller();
+        let deposit_id = self.deposit_count().get() + 1;
+        self.deposit_count().set(deposit_id);
+        self.deposits(deposit_id).set(&CellarDeposit {
+            owner: owner.clone(),
+            wine_id: attributes.wine_id,
+            wine_nonce: payment.token_nonce,
+            bottles: bottles as u32,
+            fee_per_bottle_epoch: self.fee_per_bottle_epoch().get(),
+            deposit_epoch: self.blockchain().get_block_epoch(),
+            fees_paid: BigUint::zero(),
+            status: DepositStatus::Stored,
+            closed_epoch: 0,
+        });
+        self.owner_deposits(&owner).insert(deposit_id);
+
+        self.wine_deposited_event(deposit_id, &owner, attributes.wine_id, bottles as u32);
+
+        deposit_id
+    }
+
+    // Anyone can pay, e.g. a buyer-to-be of the stored wine
+    #[payable("*")]
+    #[endpoint(payStorageFees)]
+    fn pay_storage_fees(&self, deposit_id: u32) {
+        let mut deposit = self.require_stored_deposit(deposit_id);
+        let payment = self.call_value().egld_or_single_esdt();
+        require!(payment.token_identifier == self.fee_token().get(), ERR_INVALID_FEE_TOKEN);
+        require!(payment.amount > 0, ERR_AMOUNT_MUST_BE_GREATER_THAN_0);
+        deposit.fees_paid += &payment.amount;
+        self.deposits(deposit_id).set(&deposit);
+
+        self.storage_fees_paid_event(deposit_id, &self.blockchain().get_caller(), &payment.amount);
+    }
+
+    // Settle outstanding fees, optionally paid along with this call, and take the wine back.
+    // Prepaid fees beyond the stay are refunded.
+    #[payable("*")]
+    #[endpoint(withdrawWine)]
+    fn withdraw_wine(&self, deposit_id: u32) {
+        let mut deposit = self.require_stored_deposit(deposit_id);
+        require!(self.blockchain().get_caller() == deposit.owner, ERR_ONLY_OWNER_CAN_WITHDRAW);
+
+        let payment = self.call_value().egld_or_single_esdt();
+        if payment.amount > 0 {
+            require!(payment.token_identifier == self.fee_token().get(), ERR_INVALID_FEE_TOKEN);
+            deposit.fees_paid += &payment.amount;
+        }
+        let fees_due = self.fees_due(&deposit);
+        require!(deposit.fees_paid >= fees_due, ERR_STORAGE_FEES_OUTSTANDING);
+
+        let refund = &deposit.fees_paid - &fees_due;
+        self.collected_fees().update(|collected| *collected += &fees_due);
+        deposit.fees_paid = fees_due.clone();
+        self.close_deposit(deposit_id, &mut deposit, DepositStatus::Withdrawn);
+
+        self.send().direct_esdt(
+            &deposit.owner,
+            &self.wine_collection().get(),
+            deposit.wine_nonce,
+            &BigUint::from(deposit.bottles),
+        );
+        if refund > 0 {
+            self.send().direct(&deposit.owner, &self.fee_token().get(), 0, &refund);
+        }
+
+        self.wine_withdrawn_event(deposit_id, &deposit.owner, &fees_due);
+    }
+
+    // Seize a deposit whose unpaid fees exceed the grace period. The wine goes to the
+    // liquidating admin, to be sold off-chain or on the marketplace to cover the debt.
+    #[endpoint(liquidateDeposit)]
+    fn liquidate_deposit(&self, deposit_id: u32) {
+        self.require_role(Role::Admin);
+        let mut deposit = self.require_stored_deposit(deposit_id);
+        require!(self.is_liquidatable(&deposit), ERR_DEPOSIT_IS_NOT_LIQUIDATABLE);
+
+        let outstanding = self.fees_due(&deposit) - &deposit.fees_paid;
+        self.collected_fees().update(|collected| *collected += &deposit.fees_paid);
+        self.close_deposit(deposit_id, &mut deposit, DepositStatus::Liquidated);
+
+        let caller = self.blockchain().get_caller();
+        self.send().direct_esdt(
+            &caller,
+            &self.wine_collection().get(),
+            deposit.wine_nonce,
+            &BigUint::from(deposit.bottles),
+        );
+
+        self.deposit_liquidated_event(deposit_id, &deposit.owner, &caller, &outstanding);
+    }
+
+    #[endpoint(claimStorageFees)]
+    fn claim_storage_fees(&self) -> BigUint {
+        self.require_role(Role::FeeManager);
+        let amount = self.collected_fees().take();
+        require!(amount > 0, ERR_NO_STORAGE_FEES_TO_CLAIM);
+        let caller = self.blockchain().get_caller();
+        self.send().direct(&caller, &self.fee_token().get(), 0, &amount);
+
+        self.storage_fees_claimed_event(&caller, &amount);
+
+        amount
+    }
+
+    // Internal helpers
+    fn require_deposit(&self, deposit_id: u32) -> CellarDeposit<Self::Api> {
+        require!(!self.deposits(deposit_id).is_empty(), ERR_DEPOSIT_DOES_NOT_EXIST);
+        self.deposits(deposit_id).get()
+    }
+
+    fn require_stored_deposit(&self, deposit_id: u32) -> CellarDeposit<Self::Api> {
+        let deposit = self.require_deposit(deposit_id);
+        require!(deposit.status == DepositStatus::Stored, ERR_WINE_IS_NO_LONGER_STORED);
+        deposit
+    }
+
+    // Every epoch started in the cellar is charged, the deposit epoch included
+    fn fees_due(&self, deposit: &CellarDeposit<Self::Api>) -> BigUint {
+        let end_epoch = if deposit.status == DepositStatus::Stored {
+            self.blockchain().get_block_epoch()
+        } else {
+            deposit.closed_epoch
+        };
+        let epochs = end_epoch - deposit.deposit_epoch + 1;
+        &deposit.fee_per_bottle_epoch * deposit.bottles * epochs
+    }
+
+    fn outstanding_fees(&self, deposit: &CellarDeposit<Self::Api>) -> BigUint {
+        let fees_due = self.fees_due(deposit);
+        if fees_due > deposit.fees_paid {
+            fees_due - &deposit.fees_paid
+        } else {
+            BigUint::zero()
+        }
+    }
+
+    fn is_liquidatable(&self, deposit: &CellarDeposit<Self::Api>) -> bool {
+        let grace_fees = &deposit.fee_per_bottle_epoch
+            * deposit.bottles
+            * self.liquidation_grace_epochs().get();
+        deposit.status == DepositStatus::Stored && self.outstanding_fees(deposit) > grace_fees
+    }
+
+    fn close_deposit(
+        &self,
+        deposit_id: u32,
+        deposit: &mut CellarDeposit<Self::Api>,
+        status: DepositStatus,
+    ) {
+        deposit.status = status;
+        deposit.closed_epoch = self.blockchain().get_block_epoch();
+        self.deposits(deposit_id).set(&*deposit);
+        self.owner_deposits(&deposit.owner).swap_remove(&deposit_id);
+    }
+
+    // View functions
+    #[view(getWineCollection)]
+    fn get_wine_collection(&self) -> TokenIdentifier {
+        self.wine_collection().get()
+    }
+
+    #[view(getFeeToken)]
+    fn get_fee_token(&self) -> EgldOrEsdtTokenIdentifier {
+        self.fee_token().get()
+    }
+
+    #[view(getStorageFee)]
+    fn get_storage_fee(&self) -> BigUint {
+        self.fee_per_bottle_epoch().get()
+    }
+
+    #[view(getLiquidationGraceEpochs)]
+    fn get_liquidation_grace_epochs(&self) -> u64 {
+        self.liquidation_grace_epochs().get()
+    }
+
+    #[view(getDeposit)]
+    fn get_deposit(&self, deposit_id: u32) -> CellarDeposit<Self::Api> {
+        self.require_deposit(deposit_id)
+    }
+
+    #[view(getDepositCount)]
+    fn get_deposit_count(&self) -> u32 {
+        self.deposit_count().get()
+    }
+
+    // Deposits currently stored for `owner`
+    #[view(getOwnerDeposits)]
+    fn get_owner_deposits(&self, owner: ManagedAddress) -> MultiValueEncoded<u32> {
+        self.owner_deposits(&owner).iter().collect()
+    }
+
+    // Amount to pay for withdrawing in the current epoch
+    #[view(getOutstandingFees)]
+    fn get_outstanding_fees(&self, deposit_id: u32) -> BigUint {
+        self.outstanding_fees(&self.require_deposit(deposit_id))
+    }
+
+    #[view(isLiquidatable)]
+    fn is_liquidatable_view(&self, deposit_id: u32) -> bool {
+        self.is_liquidatable(&self.require_deposit(deposit_id))
+    }
+
+    // Settled fees not yet claimed by fee managers
+    #[view(getCollectedFees)]
+    fn get_collected_fees(&self) -> BigUint {
+        self.collected_fees().get()
+    }
+
+    // Storage mappers
+    #[storage_mapper("wineCollection")]
+    fn wine_collection(&self) -> SingleValueMapper<TokenIdentifier>;
+
+    #[storage_mapper("feeToken")]
+    fn fee_token(&self) -> SingleValueMapper<EgldOrEsdtTokenIdentifier>;
+
+    #[storage_mapper("feePerBottleEpoch")]
+    fn fee_per_bottle_epoch(&self) -> SingleValueMapper<BigUint>;
+
+    #[storage_mapper("liquidationGraceEpochs")]
+    fn liquidation_grace_epochs(&self) -> SingleValueMapper<u64>;
+
+    #[storage_mapper("depositCount")]
+    fn deposit_count(&self) -> SingleValueMapper<u32>;
+
+    #[storage_mapper("deposits")]
+    fn deposits(&self, deposit_id: u32) -> SingleValueMapper<CellarDeposit<Self::Api>>;
+
+    #[storage_mapper("ownerDeposits")]
+    fn owner_deposits(&self, owner: &ManagedAddress) -> UnorderedSetMapper<u32>;
+
+    #[storage_mapper("collectedFees")]
+    fn collected_fees(&self) -> SingleValueMapper<BigUint>;
+
+    // Events
+    #[event("storageFeeUpdated")]
+    fn storage_fee_updated_event(&self, fee_per_bottle_epoch: &BigUint);
+
+    #[event("wineDeposited")]
+    fn wine_deposited_event(
+        &self,
+        #[indexed] deposit_id: u32,
+        #[indexed] owner: &ManagedAddress,
+        #[indexed] wine_id: u32,
+        bottles: u32,
+    );
+
+    #[event("storageFeesPaid")]
+    fn storage_fees_paid_event(
+        &self,
+        #[indexed] deposit_id: u32,
+        #[indexed] payer: &ManagedAddress,
+        amount: &BigUint,
+    );
+
+    #[event("wineWithdrawn")]
+    fn wine_withdrawn_event(
+        &self,
+        #[indexed] deposit_id: u32,
+        #[indexed] owner: &ManagedAddress,
+        fees_charged: &BigUint,
+    );
+
+    #[event("depositLiquidated")]
+    fn deposit_liquidated_event(
+        &self,
+        #[indexed] deposit_id: u32,
+        #[indexed] owner: &ManagedAddress,
+        #[indexed] liquidator: &ManagedAddress,
+        unpaid_fees: &BigUint,
+    );
+
+    #[event("storageFeesClaimed")]
+    fn storage_fees_claimed_event(&self, #[indexed] claimer: &ManagedAddress, amount: &BigUint);
+}
@@ -0,0 +1,38 @@
+#![no_std]
+
+use multiversx_sc::imports::*;
+use wine_common::errors::*;
+
+pub mod certification;
+pub mod deposits;
+
+pub use certification::StorageCertificate;
+pub use deposits::{CellarDeposit, DepositStatus, WineNftAttributes};
+
+// Professional cellar storage for wine NFTs. Collectors deposit bottles and pay storage fees
+// per epoch; keepers certify the storage conditions, which feed the registry's storage
+// history. Wine is released once fees are settled, and long-unpaid deposits are liquidated.
+#[multiversx_sc::contract]
+pub trait WineCellar:
+    certification::CertificationModule + deposits::DepositsModule + wine_roles::RolesModule
+{
+    #[init]
+    fn init(
+        &self,
+        wine_collection: TokenIdentifier,
+        fee_token: EgldOrEsdtTokenIdentifier,
+        fee_per_bottle_epoch: BigUint,
+        liquidation_grace_epochs: u64,
+    ) {
+        require!(wine_collection.is_valid_esdt_identifier(), ERR_INVALID_WINE_COLLECTION);
+        require!(fee_token.is_valid(), ERR_INVALID_FEE_TOKEN);
+        require!(liquidation_grace_epochs > 0, ERR_GRACE_PERIOD_MUST_BE_GREATER_THAN_0);
+        self.wine_collection().set(&wine_collection);
+        self.fee_token().set(&fee_token);
+        self.fee_per_bottle_epoch().set(&fee_per_bottle_epoch);
+        self.liquidation_grace_epochs().set(liquidation_grace_epochs);
+    }
+
+    #[upgrade]
+    fn upgrade(&self) {}
+}
@@ -0,0 +1,252 @@
+use multiversx_sc::codec::top_encode_to_vec_u8_or_panic;
+use multiversx_sc_scenario::api::StaticApi;
+use multiversx_sc_scenario::imports::*;
+use wine_cellar::certification::ProxyTrait as _;
+use wine_cellar::certification::MAX_HUMIDITY_X100;
+use wine_cellar::deposits::ProxyTrait as _;
+use wine_cellar::ProxyTrait as _;
+use wine_cellar::{CellarDeposit, DepositStatus, StorageCertificate, WineNftAttributes};
+use wine_common::errors::*;
+
+const CELLAR_PATH_EXPR: &str = "mxsc:output/wine-cellar.mxsc.json";
+const CELLAR_ADDRESS_EXPR: &str = "sc:wine-cellar";
+const OWNER_ADDRESS_EXPR: &str = "address:owner";
+const COLLECTOR_ADDRESS_EXPR: &str = "address:collector";
+const KEEPER_ADDRESS_EXPR: &str = "address:keeper";
+
+const WINE_NFT_TOKEN_ID_EXPR: &str = "str:WINE-abcdef";
+
+const DEPOSIT_EPOCH: u64 = 5;
+const FEE_PER_BOTTLE_EPOCH: u64 = 10;
+const GRACE_EPOCHS: u64 = 3;
+const WINE_ID: u32 = 7;
+
+type CellarContract = ContractInfo<wine_cellar::Proxy<StaticApi>>;
+
+fn user_error(message: &str) -> TxExpect {
+    TxExpect::user_error(format!("str:{message}"))
+}
+
+fn encoded_wine_attributes() -> Option<Vec<u8>> {
+    Some(top_encode_to_vec_u8_or_panic(&WineNftAttributes::<StaticApi> {
+        wine_id: WINE_ID,
+        vineyard: ManagedBuffer::from("Chateau Test"),
+        variety: ManagedBuffer::from("Merlot"),
+        vintage_year: 2015,
+        region: ManagedBuffer::from("Bordeaux"),
+        quality_grade: 9,
+        ipfs_hash: ManagedBuffer::new(),
+    }))
+}
+
+struct CellarTestState {
+    world: ScenarioWorld,
+    cellar: CellarContract,
+}
+
+impl CellarTestState {
+    // Deposit 1 holds the collector's two bottles of wine 7, stored since epoch 5 at 10 EGLD
+    // per bottle and epoch
+    fn new() -> Self {
+        let mut world = ScenarioWorld::new();
+        world.register_contract(CELLAR_PATH_EXPR, wine_cellar::ContractBuilder);
+
+        world.set_state_step(
+            SetStateStep::new()
+                .block_epoch(DEPOSIT_EPOCH)
+                .put_account(OWNER_ADDRESS_EXPR, Account::new().nonce(1))
+                .put_account(
+                    COLLECTOR_ADDRESS_EXPR,
+                    Account::new()
+                        .nonce(1)
+                        .balance("1000")
+                        .esdt_nft_balance(WINE_NFT_TOKEN_ID_EXPR, 1, "2", encoded_wine_attributes()),
+                )
+                .put_account(KEEPER_ADDRESS_EXPR, Account::new().nonce(1))
+                .new_address(OWNER_ADDRESS_EXPR, 1, CELLAR_ADDRESS_EXPR),
+        );
+
+        let mut cellar = CellarContract::new(CELLAR_ADDRESS_EXPR);
+        let cellar_code = world.code_expression(CELLAR_PATH_EXPR);
+        world.sc_deploy(
+            ScDeployStep::new()
+                .from(OWNER_ADDRESS_EXPR)
+                .code(cellar_code)
+                .call(cellar.init(
+                    TokenIdentifier::from("WINE-abcdef"),
+                    EgldOrEsdtTokenIdentifier::egld(),
+                    FEE_PER_BOTTLE_EPOCH,
+                    GRACE_EPOCHS,
+                )),
+        );
+        world.sc_call(
+            ScCallStep::new()
+                .from(COLLECTOR_ADDRESS_EXPR)
+                .esdt_transfer(WINE_NFT_TOKEN_ID_EXPR, 1, "2")
+                .call(cellar.deposit_wine())
+                .expect_value(1u32),
+        );
+
+        CellarTestState { world, cellar }
+    }
+
+    fn set_block_epoch(&mut self, epoch: u64) {
+        self.world.set_state_step(SetStateStep::new().block_epoch(epoch));
+    }
+
+    fn withdraw_wine(&mut self) -> TypedScCall<()> {
+        ScCallStep::new()
+            .from(COLLECTOR_ADDRESS_EXPR)
+            .call(self.cellar.withdraw_wine(1u32))
+    }
+
+    fn deposit(&mut self) -> CellarDeposit<StaticApi> {
+        self.world.quick_query(self.cellar.get_deposit(1u32))
+    }
+}
+
+#[test]
+fn withdrawal_settles_the_fees_and_refunds_the_prepayment() {
+    let mut state = CellarTestState::new();
+    state.world.sc_call(
+        ScCallStep::new()
+            .from(COLLECTOR_ADDRESS_EXPR)
+            .egld_value(100u64)
+            .call(state.cellar.pay_storage_fees(1u32)),
+    );
+
+    // Epochs 5 to 7 are charged for both bottles
+    state.set_block_epoch(DEPOSIT_EPOCH + 2);
+    state.world.sc_call(
+        ScCallStep::new()
+            .from(OWNER_ADDRESS_EXPR)
+            .call(state.cellar.withdraw_wine(1u32))
+            .expect(user_error(ERR_ONLY_OWNER_CAN_WITHDRAW)),
+    );
+    let step = state.withdraw_wine();
+    state.world.sc_call(step);
+
+    let deposit = state.deposit();
+    assert_eq!(deposit.status, DepositStatus::Withdrawn);
+    assert_eq!(deposit.fees_paid, 60u64);
+    state.world.check_state_step(
+        CheckStateStep::new().put_account(
+            COLLECTOR_ADDRESS_EXPR,
+            CheckAccount::new().balance("940").esdt_nft_balance_and_attributes(
+                WINE_NFT_TOKEN_ID_EXPR,
+                1,
+                "2",
+                encoded_wine_attributes(),
+            ),
+        ),
+    );
+
+    state.world.sc_call(
+        ScCallStep::new()
+            .from(COLLECTOR_ADDRESS_EXPR)
+            .call(state.cellar.claim_storage_fees())
+            .expect(user_error(ERR_CALLER_DOES_NOT_HAVE_THE_REQUIRED_ROLE)),
+    );
+    state.world.sc_call(
+        ScCallStep::new()
+            .from(OWNER_ADDRESS_EXPR)
+            .call(state.cellar.claim_storage_fees())
+            .expect_value(BigUint::<StaticApi>::from(60u64)),
+    );
+    state.world.check_state_step(
+        CheckStateStep::new()
+            .put_account(OWNER_ADDRESS_EXPR, CheckAccount::new().balance("60"))
+            .put_account(CELLAR_ADDRESS_EXPR, CheckAccount::new().balance("0")),
+    );
+}
+
+#[test]
+fn unpaid_deposit_is_liquidated_after_the_grace_period() {
+    let mut state = CellarTestState::new();
+
+    // 60 outstanding is still within three epochs' worth of fees
+    state.set_block_epoch(DEPOSIT_EPOCH + 2);
+    let step = state.withdraw_wine();
+    state.world.sc_call(step.expect(user_error(ERR_STORAGE_FEES_OUTSTANDING)));
+    state.world.sc_call(
+        ScCallStep::new()
+            .from(OWNER_ADDRESS_EXPR)
+            .call(state.cellar.liquidate_deposit(1u32))
+            .expect(user_error(ERR_DEPOSIT_IS_NOT_LIQUIDATABLE)),
+    );
+
+    state.set_block_epoch(DEPOSIT_EPOCH + 3);
+    state.world.sc_call(
+        ScCallStep::new()
+            .from(COLLECTOR_ADDRESS_EXPR)
+            .call(state.cellar.liquidate_deposit(1u32))
+            .expect(user_error(ERR_CALLER_DOES_NOT_HAVE_THE_REQUIRED_ROLE)),
+    );
+    state.world.sc_call(
+        ScCallStep::new()
+            .from(OWNER_ADDRESS_EXPR)
+            .call(state.cellar.liquidate_deposit(1u32)),
+    );
+
+    assert_eq!(state.deposit().status, DepositStatus::Liquidated);
+    state.world.check_state_step(
+        CheckStateStep::new().put_account(
+            OWNER_ADDRESS_EXPR,
+            CheckAccount::new().esdt_nft_balance_and_attributes(
+                WINE_NFT_TOKEN_ID_EXPR,
+                1,
+                "2",
+                encoded_wine_attributes(),
+            ),
+        ),
+    );
+    let step = state.withdraw_wine();
+    state.world.sc_call(step.expect(user_error(ERR_WINE_IS_NO_LONGER_STORED)));
+}
+
+#[test]
+fn keepers_certify_the_storage_conditions() {
+    let mut state = CellarTestState::new();
+    let keeper = AddressValue::from(KEEPER_ADDRESS_EXPR).to_address();
+
+    state.world.sc_call(
+        ScCallStep::new()
+            .from(KEEPER_ADDRESS_EXPR)
+            .call(state.cellar.add_cellar_keeper(managed_address!(&keeper)))
+            .expect(user_error(ERR_CALLER_DOES_NOT_HAVE_THE_REQUIRED_ROLE)),
+    );
+    state.world.sc_call(
+        ScCallStep::new()
+            .from(KEEPER_ADDRESS_EXPR)
+            .call(state.cellar.record_storage_condition(1u32, 1_300i32, 7_000u32))
+            .expect(user_error(ERR_NOT_A_CELLAR_KEEPER)),
+    );
+    state.world.sc_call(
+        ScCallStep::new()
+            .from(OWNER_ADDRESS_EXPR)
+            .call(state.cellar.add_cellar_keeper(managed_address!(&keeper))),
+    );
+
+    state.world.sc_call(
+        ScCallStep::new()
+            .from(KEEPER_ADDRESS_EXPR)
+            .call(state.cellar.record_storage_condition(1u32, 1_300i32, MAX_HUMIDITY_X100 + 1))
+            .expect(user_error(ERR_HUMIDITY_CANNOT_EXCEED_100_PERCENT)),
+    );
+    for (temperature_x100, humidity_x100) in [(1_300i32, 7_000u32), (1_150, 7_400)] {
+        state.world.sc_call(
+            ScCallStep::new()
+                .from(KEEPER_ADDRESS_EXPR)
+                .call(state.cellar.record_storage_condition(1u32, temperature_x100, humidity_x100)),
+        );
+    }
+
+    let certificate: OptionalValue<StorageCertificate> =
+        state.world.quick_query(state.cellar.get_storage_certificate(1u32));
+    let certificate = certificate.into_option().unwrap();
+    assert_eq!(certificate.readings, 2);
+    assert_eq!(certificate.min_temperature_x100, 1_150);
+    assert_eq!(certificate.max_temperature_x100, 1_300);
+    assert_eq!(certificate.min_humidity_x100, 7_000);
+    assert_eq!(certificate.max_humidity_x100, 7_400);
+}
@@ -0,0 +1,275 @@
+use multiversx_sc::derive_imports::*;
+use multiversx_sc::imports::*;
+use wine_common::errors::*;
+use wine_roles::Role;
+
+// Units of one wine NFT/SFT nonce shared among the members who enrol
+#[derive(TopEncode, TopDecode, TypeAbi, PartialEq, Debug)]
+pub struct WineDrop<M: ManagedTypeApi> {
+    pub producer: ManagedAddress<M>,
+    pub wine_nonce: u64,
+    pub total_bottles: BigUint<M>,
+    pub claimed_bottles: BigUint<M>,
+    // Members enrol before this epoch and claim from it on
+    pub enrollment_end_epoch: u64,
+    // Unclaimed bottles return to the producer from this epoch on
+    pub claim_end_epoch: u64,
+    // Sum of the tier weights of every enrolled membership
+    pub total_weight: u64,
+    pub closed: bool,
+}
+
+// Producers deposit periodic drops of wine for the club. Members in good standing enrol
+// during the enrollment window with their tier's weight, and each then claims bottles in
+// proportion to their weight. Rounding remainders and unclaimed bottles go back to the
+// producer once the claim window ends.
+#[multiversx_sc::module]
+pub trait DropsModule: crate::memberships::MembershipsModule + wine_roles::RolesModule {
+    #[endpoint(addClubProducer)]
+    fn add_club_producer(&self, producer: ManagedAddress) {
+        self.require_role(Role::Admin);
+        require!(self.club_producers().insert(producer.clone()), ERR_ALREADY_A_CLUB_PRODUCER);
+        self.club_producer_added_event(&producer);
+    }
+
+    #[endpoint(removeClubProducer)]
+    fn remove_club_producer(&self, producer: ManagedAddress) {
+        self.require_role(Role::Admin);
+        require!(self.club_producers().swap_remove(&producer), ERR_NOT_A_CLUB_PRODUCER);
+        self.club_producer_removed_event(&producer);
+    }
+
+    // Deposit units of one wine nonce as a drop
+    #[payable("*")]
+    #[endpoint(depositDrop)]
+    fn deposit_drop(&self, enrollment_end_epoch: u64, claim_end_epoch: u64) -> u32 {
+        self.require_not_paused();
+        let producer = self.blockchain().get_caller();
+        require!(
+            self.club_producers().contains(&producer),
+            ERR_ONLY_CLUB_PRODUCERS_CAN_DEPOSIT_DROPS
+        );
+        require!(
+            enrollment_end_epoch > self.blockchain().get_block_epoch(),
+            ERR_ENROLLMENT_END_MUST_BE_IN_THE_FUTURE
+        );
+        require!(
+            claim_end_epoch > enrollment_end_epoch,
+            ERR_CLAIM_END_MUST_FOLLOW_THE_ENROLLMENT_END
+        );
+        let payment = self.call_value().single_esdt();
+        require!(
+            payment.token_identifier == self.wine_collection().get(),
+            ERR_ONLY_WINE_NFTS_CAN_BE_DROPPED
+        );
+
+        let drop_id = self.drop_count().get() + 1;
+        self.drop_count().set(drop_id);
+        self.drops(drop_id).set(&WineDrop {
+            producer: producer.clone(),
+            wine_nonce: payment.token_nonce,
+            total_bottles: payment.amount.clone(),
+            claimed_bottles: BigUint::zero(),
+            enrollment_end_epoch,
+            claim_end_epoch,
+            total_weight: 0,
+            closed: false,
+        });
+
+        self.drop_deposited_event(
+            drop_id,
+            &producer,
+            payment.token_nonce,
+            &payment.amount,
+            enrollment_end_epoch,
+        );
+
+        drop_id
+    }
+
+    // Enrol the membership sent along in a drop; the NFT is returned
+    #[payable("*")]
+    #[endpoint(enterDrop)]
+    fn enter_drop(&self, drop_id: u32) {
+        self.require_not_paused();
+        let membership_nonce = self.take_membership_nft();
+        let mut drop = self.require_drop(drop_id);
+        require!(
+            self.blockchain().get_block_epoch() < drop.enrollment_end_epoch,
+            ERR_ENROLLMENT_HAS_ENDED
+        );
+        require!(
+            self.drop_enrollment(drop_id, membership_nonce).is_empty(),
+            ERR_MEMBERSHIP_ALREADY_ENROLLED
+        );
+        let membership = self.require_membership(membership_nonce);
+        require!(!self.is_lapsed(&membership), ERR_MEMBERSHIP_IS_NOT_IN_GOOD_STANDING);
+
+        let weight = self.tiers(membership.tier_id).get().drop_weight;
+        drop.total_weight += weight as u64;
+        self.drops(drop_id).set(&drop);
+        self.drop_enrollment(drop_id, membership_nonce).set(weight);
+        self.return_membership_nft(membership_nonce);
+
+        self.drop_entered_event(drop_id, membership_nonce, weight);
+    }
+
+    // Claim the share of an enrolled membership, sent along and returned with the bottles
+    #[payable("*")]
+    #[endpoint(claimDrop)]
+    fn claim_drop(&self, drop_id: u32) -> BigUint {
+        let membership_nonce = self.take_membership_nft();
+        let mut drop = self.require_drop(drop_id);
+        let current_epoch = self.blockchain().get_block_epoch();
+        require!(current_epoch >= drop.enrollment_end_epoch, ERR_ENROLLMENT_HAS_NOT_ENDED);
+        require!(current_epoch < drop.claim_end_epoch, ERR_CLAIM_WINDOW_HAS_ENDED);
+        require!(
+            !self.drop_enrollment(drop_id, membership_nonce).is_empty(),
+            ERR_MEMBERSHIP_IS_NOT_ENROLLED
+        );
+        require!(!self.drop_claimed(drop_id, membership_nonce).get(), ERR_DROP_ALREADY_CLAIMED);
+
+        let share = self.drop_share(&drop, self.drop_enrollment(drop_id, membership_nonce).get());
+        drop.claimed_bottles += &share;
+        self.drops(drop_id).set(&drop);
+        self.drop_claimed(drop_id, membership_nonce).set(true);
+        self.return_membership_nft(membership_nonce);
+
+        let caller = self.blockchain().get_caller();
+        if share > 0 {
+            self.send().direct_esdt(&caller, &self.wine_collection().get(), drop.wine_nonce, &share);
+        }
+
+        self.drop_claimed_event(drop_id, membership_nonce, &caller, &share);
+
+        share
+    }
+
+    // Return what was not claimed to the producer, once the claim window ends or straight
+    // after enrollment if nobody enrolled
+    #[endpoint(closeDrop)]
+    fn close_drop(&self, drop_id: u32) -> BigUint {
+        let mut drop = self.require_drop(drop_id);
+        require!(
+            self.blockchain().get_caller() == drop.producer,
+            ERR_ONLY_PRODUCER_CAN_CLOSE_THE_DROP
+        );
+        require!(!drop.closed, ERR_DROP_ALREADY_CLOSED);
+        let current_epoch = self.blockchain().get_block_epoch();
+        let nobody_enrolled = drop.total_weight == 0 && current_epoch >= drop.enrollment_end_epoch;
+        require!(
+            nobody_enrolled || current_epoch >= drop.claim_end_epoch,
+            ERR_CLAIM_WINDOW_HAS_NOT_ENDED
+        );
+
+        let remaining = &drop.total_bottles - &drop.claimed_bottles;
+        drop.closed = true;
+        self.drops(drop_id).set(&drop);
+        if remaining > 0 {
+            self.send().direct_esdt(
+                &drop.producer,
+                &self.wine_collection().get(),
+                drop.wine_nonce,
+                &remaining,
+            );
+        }
+
+        self.drop_closed_event(drop_id, &remaining);
+
+        remaining
+    }
+
+    // Internal helpers
+    fn require_drop(&self, drop_id: u32) -> WineDrop<Self::Api> {
+        require!(!self.drops(drop_id).is_empty(), ERR_DROP_DOES_NOT_EXIST);
+        self.drops(drop_id).get()
+    }
+
+    fn drop_share(&self, drop: &WineDrop<Self::Api>, weight: u32) -> BigUint {
+        if drop.total_weight == 0 {
+            return BigUint::zero();
+        }
+        &drop.total_bottles * weight / drop.total_weight
+    }
+
+    // View functions
+    #[view(getDrop)]
+    fn get_drop(&self, drop_id: u32) -> WineDrop<Self::Api> {
+        self.require_drop(drop_id)
+    }
+
+    #[view(getDropCount)]
+    fn get_drop_count(&self) -> u32 {
+        self.drop_count().get()
+    }
+
+    #[view(getClubProducers)]
+    fn get_club_producers(&self) -> MultiValueEncoded<ManagedAddress> {
+        self.club_producers().iter().collect()
+    }
+
+    // Bottles the membership can claim so far; final once enrollment ends
+    #[view(getDropShare)]
+    fn get_drop_share(&self, drop_id: u32, membership_nonce: u64) -> BigUint {
+        let drop = self.require_drop(drop_id);
+        if self.drop_enrollment(drop_id, membership_nonce).is_empty()
+            || self.drop_claimed(drop_id, membership_nonce).get()
+        {
+            return BigUint::zero();
+        }
+        self.drop_share(&drop, self.drop_enrollment(drop_id, membership_nonce).get())
+    }
+
+    // Storage mappers
+    #[view(getWineCollection)]
+    #[storage_mapper("wineCollection")]
+    fn wine_collection(&self) -> SingleValueMapper<TokenIdentifier>;
+
+    #[storage_mapper("clubProducers")]
+    fn club_producers(&self) -> UnorderedSetMapper<ManagedAddress>;
+
+    #[storage_mapper("dropCount")]
+    fn drop_count(&self) -> SingleValueMapper<u32>;
+
+    #[storage_mapper("drops")]
+    fn drops(&self, drop_id: u32) -> SingleValueMapper<WineDrop<Self::Api>>;
+
+    // Tier weight of each enrolled membership
+    #[storage_mapper("dropEnrollment")]
+    fn drop_enrollment(&self, drop_id: u32, membership_nonce: u64) -> SingleValueMapper<u32>;
+
+    #[storage_mapper("dropClaimed")]
+    fn drop_claimed(&self, drop_id: u32, membership_nonce: u64) -> SingleValueMapper<bool>;
+
+    // Events
+    #[event("clubProducerAdded")]
+    fn club_producer_added_event(&self, #[indexed] producer: &ManagedAddress);
+
+    #[event("clubProducerRemoved")]
+    fn club_producer_removed_event(&self, #[indexed] producer: &ManagedAddress);
+
+    #[event("dropDeposited")]
+    fn drop_deposited_event(
+        &self,
+        #[indexed] drop_id: u32,
+        #[indexed] producer: &ManagedAddress,
+        #[indexed] wine_nonce: u64,
+        total_bottles: &BigUint,
+        enrollment_end_epoch: u64,
+    );
+
+    #[event("dropEntered")]
+    fn drop_entered_event(&self, #[indexed] drop_id: u32, #[indexed] membership_nonce: u64, weight: u32);
+
+    #[event("dropClaimed")]
+    fn drop_claimed_event(
+        &self,
+        #[indexed] drop_id: u32,
+        #[indexed] membership_nonce: u64,
+        #[indexed] member: &ManagedAddress,
+        bottles: &BigUint,
+    );
+
+    #[event("dropClosed")]
+    fn drop_closed_event(&self, #[indexed] drop_id: u32, returned_bottles: &BigUint);
+}
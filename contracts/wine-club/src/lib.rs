@@ -0,0 +1,35 @@
+#![no_std]
+
+use multiversx_sc::imports::*;
+use wine_common::errors::*;
+
+pub mod drops;
+pub mod memberships;
+
+pub use drops::WineDrop;
+pub use memberships::{Membership, MembershipAttributes, MembershipTier};
+
+// A wine club. Members hold a membership NFT in one of several tiers and pay a recurring fee
+// per epoch; producers deposit periodic drops of wine NFTs that members in good standing
+// share in proportion to their tier's weight.
+#[multiversx_sc::contract]
+pub trait WineClub:
+    drops::DropsModule + memberships::MembershipsModule + wine_roles::RolesModule
+{
+    #[init]
+    fn init(
+        &self,
+        wine_collection: TokenIdentifier,
+        fee_token: EgldOrEsdtTokenIdentifier,
+        grace_period_epochs: u64,
+    ) {
+        require!(wine_collection.is_valid_esdt_identifier(), ERR_INVALID_WINE_COLLECTION);
+        require!(fee_token.is_valid(), ERR_INVALID_FEE_TOKEN);
+        self.wine_collection().set(&wine_collection);
+        self.fee_token().set(&fee_token);
+        self.grace_period_epochs().set(grace_period_epochs);
+    }
+
+    #[upgrade]
+    fn upgrade(&self) {}
+}
@@ -0,0 +1,393 @@
+use multiversx_sc::derive_imports::*;
+use multiversx_sc::imports::*;
+use wine_common::errors::*;
+use wine_roles::Role;
+
+pub const MAX_MEMBERSHIP_TIERS: usize = 10;
+// About ten years of daily epochs per payment
+pub const MAX_EPOCHS_PER_PAYMENT: u64 = 3650;
+
+#[derive(TopEncode, TopDecode, NestedEncode, NestedDecode, TypeAbi, PartialEq, Debug)]
+pub struct MembershipTier<M: ManagedTypeApi> {
+    pub name: ManagedBuffer<M>,
+    pub fee_per_epoch: BigUint<M>,
+    // Share of each drop relative to the other enrolled members
+    pub drop_weight: u32,
+    // Closed tiers take no new members; existing ones keep their tier
+    pub open: bool,
+}
+
+// Membership state, keyed by the membership NFT nonce
+#[derive(TopEncode, TopDecode, TypeAbi, PartialEq, Debug)]
+pub struct Membership {
+    pub tier_id: u32,
+    pub joined_epoch: u64,
+    // Fees are paid for every epoch before this one
+    pub paid_until_epoch: u64,
+}
+
+// On-chain attributes of a membership NFT
+#[derive(TopEncode, TopDecode, TypeAbi, PartialEq, Debug)]
+pub struct MembershipAttributes {
+    pub member_since_epoch: u64,
+}
+
+// Memberships are NFTs of one collection, so they can be gifted or sold with the club state
+// attached. Fees are prepaid in whole epochs. A member who falls behind stays in good
+// standing for the grace period and must pay the arrears to continue; after that the
+// membership lapses, and renewing starts afresh from the current epoch.
+#[multiversx_sc::module]
+pub trait MembershipsModule: wine_roles::RolesModule {
+    // Issue the membership NFT collection with all roles assigned to this contract. Requires
+    // the issue cost in EGLD.
+    #[payable("EGLD")]
+    #[endpoint(issueMembershipCollection)]
+    fn issue_membership_collection(
+        &self,
+        token_display_name: ManagedBuffer,
+        token_ticker: ManagedBuffer,
+    ) {
+        self.require_role(Role::Admin);
+        let issue_cost = self.call_value().egld_value().clone_value();
+        self.membership_collection().issue_and_set_all_roles(
+            EsdtTokenType::NonFungible,
+            issue_cost,
+            token_display_name,
+            token_ticker,
+            0,
+            Some(self.callbacks().membership_collection_issued()),
+        );
+    }
+
+    #[endpoint(addMembershipTier)]
+    fn add_membership_tier(
+        &self,
+        name: ManagedBuffer,
+        fee_per_epoch: BigUint,
+        drop_weight: u32,
+    ) -> u32 {
+        self.require_role(Role::Admin);
+        require!(
+            self.tier_count().get() < MAX_MEMBERSHIP_TIERS as u32,
+            ERR_MAXIMUM_NUMBER_OF_TIERS_REACHED
+        );
+        require!(!name.is_empty(), ERR_TIER_NAME_CANNOT_BE_EMPTY);
+        require!(fee_per_epoch > 0, ERR_FEE_PER_EPOCH_MUST_BE_GREATER_THAN_0);
+        require!(drop_weight > 0, ERR_DROP_WEIGHT_MUST_BE_GREATER_THAN_0);
+
+        let tier_id = self.tier_count().get() + 1;
+        self.tier_count().set(tier_id);
+        self.tiers(tier_id).set(&MembershipTier {
+            name,
+            fee_per_epoch,
+            drop_weight,
+            open: true,
+        });
+
+        self.membership_tier_updated_event(tier_id, &self.tiers(tier_id).get());
+
+        tier_id
+    }
+
+    // A new fee applies from each member's next payment
+    #[endpoint(updateMembershipTier)]
+    fn update_membership_tier(
+        &self,
+        tier_id: u32,
+        fee_per_epoch: BigUint,
+        drop_weight: u32,
+        open: bool,
+    ) {
+        self.require_role(Role::Admin);
+        let mut tier = self.require_tier(tier_id);
+        require!(fee_per_epoch > 0, ERR_FEE_PER_EPOCH_MUST_BE_GREATER_THAN_0);
+        require!(drop_weight > 0, ERR_DROP_WEIGHT_MUST_BE_GREATER_THAN_0);
+
+        tier.fee_per_epoch = fee_per_epoch;
+        tier.drop_weight = drop_weight;
+        tier.open = open;
+        self.tiers(tier_id).set(&tier);
+
+        self.membership_tier_updated_event(tier_id, &tier);
+    }
+
+    #[endpoint(setGracePeriodEpochs)]
+    fn set_grace_period_epochs(&self, grace_period_epochs: u64) {
+        self.require_role(Role::Admin);
+        self.grace_period_epochs().set(grace_period_epochs);
+    }
+
+    // Join a tier, prepaying its fee for one or more whole epochs
+    #[payable("*")]
+    #[endpoint(joinClub)]
+    fn join_club(&self, tier_id: u32) -> u64 {
+        self.require_not_paused();
+        let tier = self.require_tier(tier_id);
+        require!(tier.open, ERR_TIER_IS_CLOSED_TO_NEW_MEMBERS);
+        require!(!self.membership_collection().is_empty(), ERR_MEMBERSHIP_COLLECTION_NOT_ISSUED);
+
+        let epochs = self.require_fee_payment(&tier.fee_per_epoch);
+        let current_epoch = self.blockchain().get_block_epoch();
+        let token_id = self.membership_collection().get_token_id();
+        let nonce = self.send().esdt_nft_create(
+            &token_id,
+            &BigUint::from(1u32),
+            &sc_format!("{} membership", tier.name),
+            &BigUint::zero(),
+            &ManagedBuffer::new(),
+            &MembershipAttributes {
+                member_since_epoch: current_epoch,
+            },
+            &ManagedVec::new(),
+        );
+        self.memberships(nonce).set(&Membership {
+            tier_id,
+            joined_epoch: current_epoch,
+            paid_until_epoch: current_epoch + epochs,
+        });
+
+        let caller = self.blockchain().get_caller();
+        self.send().direct_esdt(&caller, &token_id, nonce, &BigUint::from(1u32));
+
+        self.club_joined_event(nonce, &caller, tier_id, current_epoch + epochs);
+
+        nonce
+    }
+
+    // Anyone can pay for a membership. Arrears within the grace period are settled first;
+    // a lapsed membership restarts from the current epoch.
+    #[payable("*")]
+    #[endpoint(payMembership)]
+    fn pay_membership(&self, membership_nonce: u64) -> u64 {
+        self.require_not_paused();
+        let mut membership = self.require_membership(membership_nonce);
+        let tier = self.tiers(membership.tier_id).get();
+        let epochs = self.require_fee_payment(&tier.fee_per_epoch);
+
+        if self.is_lapsed(&membership) {
+            membership.paid_until_epoch = self.blockchain().get_block_epoch();
+        }
+        membership.paid_until_epoch += epochs;
+        self.memberships(membership_nonce).set(&membership);
+
+        self.membership_paid_event(
+            membership_nonce,
+            &self.blockchain().get_caller(),
+            membership.paid_until_epoch,
+        );
+
+        membership.paid_until_epoch
+    }
+
+    // Move to another tier, sent with the membership NFT. Prepaid epochs are converted at
+    // the new tier's fee, rounding down.
+    #[payable("*")]
+    #[endpoint(changeMembershipTier)]
+    fn change_membership_tier(&self, new_tier_id: u32) -> u64 {
+        self.require_not_paused();
+        let membership_nonce = self.take_membership_nft();
+        let mut membership = self.require_membership(membership_nonce);
+        require!(membership.tier_id != new_tier_id, ERR_MEMBERSHIP_IS_ALREADY_IN_THIS_TIER);
+        let new_tier = self.require_tier(new_tier_id);
+        require!(new_tier.open, ERR_TIER_IS_CLOSED_TO_NEW_MEMBERS);
+
+        let current_epoch = self.blockchain().get_block_epoch();
+        require!(membership.paid_until_epoch > current_epoch, ERR_MEMBERSHIP_FEES_ARE_OVERDUE);
+        let old_tier = self.tiers(membership.tier_id).get();
+        let credit = old_tier.fee_per_epoch * (membership.paid_until_epoch - current_epoch);
+        let converted_epochs = (credit / &new_tier.fee_per_epoch).to_u64().unwrap_or_default();
+
+        let old_tier_id = membership.tier_id;
+        membership.tier_id = new_tier_id;
+        membership.paid_until_epoch = current_epoch + converted_epochs;
+        self.memberships(membership_nonce).set(&membership);
+        self.return_membership_nft(membership_nonce);
+
+        self.membership_tier_changed_event(
+            membership_nonce,
+            old_tier_id,
+            new_tier_id,
+            membership.paid_until_epoch,
+        );
+
+        membership.paid_until_epoch
+    }
+
+    #[endpoint(claimClubFees)]
+    fn claim_club_fees(&self) -> BigUint {
+        self.require_role(Role::FeeManager);
+        let amount = self.collected_fees().take();
+        require!(amount > 0, ERR_NO_CLUB_FEES_TO_CLAIM);
+        let caller = self.blockchain().get_caller();
+        self.send().direct(&caller, &self.fee_token().get(), 0, &amount);
+
+        self.club_fees_claimed_event(&caller, &amount);
+
+        amount
+    }
+
+    // Internal helpers
+    fn require_tier(&self, tier_id: u32) -> MembershipTier<Self::Api> {
+        require!(!self.tiers(tier_id).is_empty(), ERR_TIER_DOES_NOT_EXIST);
+        self.tiers(tier_id).get()
+    }
+
+    fn require_membership(&self, membership_nonce: u64) -> Membership {
+        require!(!self.memberships(membership_nonce).is_empty(), ERR_MEMBERSHIP_DOES_NOT_EXIST);
+        self.memberships(membership_nonce).get()
+    }
+
+    // Returns the number of epochs paid for, which must be whole
+    fn require_fee_payment(&self, fee_per_epoch: &BigUint) -> u64 {
+        let payment = self.call_value().egld_or_single_esdt();
+        require!(payment.token_identifier == self.fee_token().get(), ERR_INVALID_PAYMENT_TOKEN);
+        require!(payment.amount >= *fee_per_epoch, ERR_PAYMENT_MUST_COVER_AT_LEAST_ONE_EPOCH);
+        require!(
+            &payment.amount % fee_per_epoch == 0,
+            ERR_PAYMENT_MUST_COVER_A_WHOLE_NUMBER_OF_EPOCHS
+        );
+        let epochs = (&payment.amount / fee_per_epoch).to_u64();
+        require!(
+            epochs.is_some_and(|epochs| epochs <= MAX_EPOCHS_PER_PAYMENT),
+            ERR_PAYMENT_COVERS_TOO_MANY_EPOCHS
+        );
+        self.collected_fees().update(|fees| *fees += &payment.amount);
+
+        epochs.unwrap_or_default()
+    }
+
+    // The caller proves they hold a membership by sending its NFT along; the endpoint must
+    // hand it back with `return_membership_nft`
+    fn take_membership_nft(&self) -> u64 {
+        let payment = self.call_value().single_esdt();
+        require!(!self.membership_collection().is_empty(), ERR_MEMBERSHIP_COLLECTION_NOT_ISSUED);
+        require!(
+            payment.token_identifier == self.membership_collection().get_token_id(),
+            ERR_INVALID_MEMBERSHIP_TOKEN
+        );
+        payment.token_nonce
+    }
+
+    fn return_membership_nft(&self, membership_nonce: u64) {
+        self.send().direct_esdt(
+            &self.blockchain().get_caller(),
+            &self.membership_collection().get_token_id(),
+            membership_nonce,
+            &BigUint::from(1u32),
+        );
+    }
+
+    // Behind on fees for longer than the grace period
+    fn is_lapsed(&self, membership: &Membership) -> bool {
+        self.blockchain().get_block_epoch()
+            >= membership.paid_until_epoch + self.grace_period_epochs().get()
+    }
+
+    #[callback]
+    fn membership_collection_issued(
+        &self,
+        #[call_result] result: ManagedAsyncCallResult<TokenIdentifier>,
+    ) {
+        match result {
+            ManagedAsyncCallResult::Ok(token_id) => {
+                self.membership_collection().set_token_id(token_id);
+            },
+            ManagedAsyncCallResult::Err(_) => {
+                self.membership_collection().clear();
+            },
+        }
+    }
+
+    // View functions
+    #[view(getMembershipCollection)]
+    fn get_membership_collection(&self) -> OptionalValue<TokenIdentifier> {
+        if self.membership_collection().get_token_state().is_set() {
+            OptionalValue::Some(self.membership_collection().get_token_id())
+        } else {
+            OptionalValue::None
+        }
+    }
+
+    #[view(getMembershipTier)]
+    fn get_membership_tier(&self, tier_id: u32) -> MembershipTier<Self::Api> {
+        self.require_tier(tier_id)
+    }
+
+    #[view(getMembershipTierCount)]
+    fn get_membership_tier_count(&self) -> u32 {
+        self.tier_count().get()
+    }
+
+    #[view(getMembership)]
+    fn get_membership(&self, membership_nonce: u64) -> Membership {
+        self.require_membership(membership_nonce)
+    }
+
+    // Paid up, or behind by no more than the grace period
+    #[view(isInGoodStanding)]
+    fn is_in_good_standing(&self, membership_nonce: u64) -> bool {
+        !self.is_lapsed(&self.require_membership(membership_nonce))
+    }
+
+    #[view(getCollectedClubFees)]
+    fn get_collected_club_fees(&self) -> BigUint {
+        self.collected_fees().get()
+    }
+
+    // Storage mappers
+    #[storage_mapper("membershipCollection")]
+    fn membership_collection(&self) -> NonFungibleTokenMapper;
+
+    #[view(getFeeToken)]
+    #[storage_mapper("feeToken")]
+    fn fee_token(&self) -> SingleValueMapper<EgldOrEsdtTokenIdentifier>;
+
+    #[view(getGracePeriodEpochs)]
+    #[storage_mapper("gracePeriodEpochs")]
+    fn grace_period_epochs(&self) -> SingleValueMapper<u64>;
+
+    #[storage_mapper("tierCount")]
+    fn tier_count(&self) -> SingleValueMapper<u32>;
+
+    #[storage_mapper("tiers")]
+    fn tiers(&self, tier_id: u32) -> SingleValueMapper<MembershipTier<Self::Api>>;
+
+    #[storage_mapper("memberships")]
+    fn memberships(&self, membership_nonce: u64) -> SingleValueMapper<Membership>;
+
+    #[storage_mapper("collectedFees")]
+    fn collected_fees(&self) -> SingleValueMapper<BigUint>;
+
+    // Events
+    #[event("membershipTierUpdated")]
+    fn membership_tier_updated_event(&self, #[indexed] tier_id: u32, tier: &MembershipTier<Self::Api>);
+
+    #[event("clubJoined")]
+    fn club_joined_event(
+        &self,
+        #[indexed] membership_nonce: u64,
+        #[indexed] member: &ManagedAddress,
+        #[indexed] tier_id: u32,
+        paid_until_epoch: u64,
+    );
+
+    #[event("membershipPaid")]
+    fn membership_paid_event(
+        &self,
+        #[indexed] membership_nonce: u64,
+        #[indexed] payer: &ManagedAddress,
+        paid_until_epoch: u64,
+    );
+
+    #[event("membershipTierChanged")]
+    fn membership_tier_changed_event(
+        &self,
+        #[indexed] membership_nonce: u64,
+        #[indexed] old_tier_id: u32,
+        #[indexed] new_tier_id: u32,
+        paid_until_epoch: u64,
+    );
+
+    #[event("clubFeesClaimed")]
+    fn club_fees_claimed_event(&self, #[indexed] recipient: &ManagedAddress, amount: &BigUint);
+}
@@ -0,0 +1,352 @@
+use multiversx_sc_scenario::api::StaticApi;
+use multiversx_sc_scenario::imports::*;
+use wine_club::drops::ProxyTrait as _;
+use wine_club::memberships::ProxyTrait as _;
+use wine_club::ProxyTrait as _;
+use wine_club::{Membership, MembershipAttributes, WineDrop};
+use wine_common::errors::*;
+
+const CLUB_PATH_EXPR: &str = "mxsc:output/wine-club.mxsc.json";
+const CLUB_ADDRESS_EXPR: &str = "sc:wine-club";
+const OWNER_ADDRESS_EXPR: &str = "address:owner";
+const PRODUCER_ADDRESS_EXPR: &str = "address:producer";
+const ALICE_ADDRESS_EXPR: &str = "address:alice";
+const BOB_ADDRESS_EXPR: &str = "address:bob";
+// Issues the membership collection. The VM runs its own mock of it, the account only has to exist.
+const ESDT_SYSTEM_SC_ADDRESS_EXPR: &str = "0x000000000000000000010000000000000000000000000000000000000002ffff";
+
+const WINE_NFT_TOKEN_ID_EXPR: &str = "str:WINE-abcdef";
+const MEMBER_TOKEN_ID: &str = "MEMBER-abcdef";
+const MEMBER_TOKEN_ID_EXPR: &str = "str:MEMBER-abcdef";
+
+const START_EPOCH: u64 = 10;
+const GRACE_PERIOD_EPOCHS: u64 = 2;
+const ENROLLMENT_END_EPOCH: u64 = 12;
+const CLAIM_END_EPOCH: u64 = 20;
+
+type ClubContract = ContractInfo<wine_club::Proxy<StaticApi>>;
+
+fn user_error(message: &str) -> TxExpect {
+    TxExpect::user_error(format!("str:{message}"))
+}
+
+fn membership_attributes() -> Option<MembershipAttributes> {
+    Some(MembershipAttributes {
+        member_since_epoch: START_EPOCH,
+    })
+}
+
+struct ClubTestState {
+    world: ScenarioWorld,
+    club: ClubContract,
+}
+
+impl ClubTestState {
+    // Tier 1 costs 10 EGLD per epoch with a drop weight of 1, tier 2 costs 30 with a weight
+    // of 3. Alice holds membership 1 in tier 1, paid for 5 epochs, and Bob membership 2 in
+    // tier 2, paid for 3. Starts in epoch 10.
+    fn new() -> Self {
+        let mut world = ScenarioWorld::new();
+        world.register_contract(CLUB_PATH_EXPR, wine_club::ContractBuilder);
+
+        let club_code = world.code_expression(CLUB_PATH_EXPR);
+        world.set_state_step(
+            SetStateStep::new()
+                .block_epoch(START_EPOCH)
+                .put_account(OWNER_ADDRESS_EXPR, Account::new().nonce(1).balance("50000"))
+                .put_account(
+                    PRODUCER_ADDRESS_EXPR,
+                    Account::new()
+                        .nonce(1)
+                        .esdt_nft_balance(WINE_NFT_TOKEN_ID_EXPR, 1, "10", Option::<&str>::None),
+                )
+                .put_account(ALICE_ADDRESS_EXPR, Account::new().nonce(1).balance("1000"))
+                .put_account(BOB_ADDRESS_EXPR, Account::new().nonce(1).balance("1000"))
+                .put_account(ESDT_SYSTEM_SC_ADDRESS_EXPR, Account::new().code(club_code.clone()))
+                .new_address(OWNER_ADDRESS_EXPR, 1, CLUB_ADDRESS_EXPR)
+                .new_token_identifier(MEMBER_TOKEN_ID),
+        );
+
+        let mut club = ClubContract::new(CLUB_ADDRESS_EXPR);
+        world.sc_deploy(
+            ScDeployStep::new()
+                .from(OWNER_ADDRESS_EXPR)
+                .code(club_code)
+                .call(club.init(
+                    TokenIdentifier::from("WINE-abcdef"),
+                    EgldOrEsdtTokenIdentifier::egld(),
+                    GRACE_PERIOD_EPOCHS,
+                )),
+        );
+        world.sc_call(
+            ScCallStep::new()
+                .from(OWNER_ADDRESS_EXPR)
+                .egld_value("50000")
+                .call(club.issue_membership_collection(ManagedBuffer::from("WineClub"), ManagedBuffer::from("MEMBER"))),
+        );
+
+        // Tiers and producers are managed by an admin
+        world.sc_call(
+            ScCallStep::new()
+                .from(ALICE_ADDRESS_EXPR)
+                .call(club.add_membership_tier(ManagedBuffer::from("Cellar"), 1u64, 100u32))
+                .expect(user_error(ERR_CALLER_DOES_NOT_HAVE_THE_REQUIRED_ROLE)),
+        );
+        for (name, fee_per_epoch, drop_weight) in [("Cellar", 10u64, 1u32), ("Grand Cru", 30, 3)] {
+            world.sc_call(
+                ScCallStep::new()
+                    .from(OWNER_ADDRESS_EXPR)
+                    .call(club.add_membership_tier(ManagedBuffer::from(name), fee_per_epoch, drop_weight)),
+            );
+        }
+        let producer = AddressValue::from(PRODUCER_ADDRESS_EXPR).to_address();
+        world.sc_call(
+            ScCallStep::new()
+                .from(OWNER_ADDRESS_EXPR)
+                .call(club.add_club_producer(managed_address!(&producer))),
+        );
+
+        for (member, tier_id, payment, nonce) in [(ALICE_ADDRESS_EXPR, 1u32, 50u64, 1u64), (BOB_ADDRESS_EXPR, 2, 90, 2)] {
+            world.sc_call(
+                ScCallStep::new()
+                    .from(member)
+                    .egld_value(payment)
+                    .call(club.join_club(tier_id))
+                    .expect_value(nonce),
+            );
+        }
+
+        ClubTestState { world, club }
+    }
+
+    fn set_block_epoch(&mut self, epoch: u64) {
+        self.world.set_state_step(SetStateStep::new().block_epoch(epoch));
+    }
+
+    fn deposit_drop(&mut self, enrollment_end_epoch: u64, claim_end_epoch: u64) {
+        self.world.sc_call(
+            ScCallStep::new()
+                .from(PRODUCER_ADDRESS_EXPR)
+                .esdt_transfer(WINE_NFT_TOKEN_ID_EXPR, 1, "10")
+                .call(self.club.deposit_drop(enrollment_end_epoch, claim_end_epoch))
+                .expect_value(1u32),
+        );
+    }
+
+    fn enter_drop(&mut self, member: &str, membership_nonce: u64) -> TypedScCall<()> {
+        ScCallStep::new()
+            .from(member)
+            .esdt_transfer(MEMBER_TOKEN_ID_EXPR, membership_nonce, "1")
+            .call(self.club.enter_drop(1u32))
+    }
+
+    fn claim_drop(&mut self, member: &str, membership_nonce: u64) -> TypedScCall<BigUint<StaticApi>> {
+        ScCallStep::new()
+            .from(member)
+            .esdt_transfer(MEMBER_TOKEN_ID_EXPR, membership_nonce, "1")
+            .call(self.club.claim_drop(1u32))
+    }
+
+    fn membership(&mut self, membership_nonce: u64) -> Membership {
+        self.world.quick_query(self.club.get_membership(membership_nonce))
+    }
+}
+
+#[test]
+fn drops_are_shared_by_tier_weight_and_the_rest_goes_back() {
+    let mut state = ClubTestState::new();
+
+    state.world.sc_call(
+        ScCallStep::new()
+            .from(ALICE_ADDRESS_EXPR)
+            .call(state.club.deposit_drop(ENROLLMENT_END_EPOCH, CLAIM_END_EPOCH))
+            .expect(user_error(ERR_ONLY_CLUB_PRODUCERS_CAN_DEPOSIT_DROPS)),
+    );
+    state.deposit_drop(ENROLLMENT_END_EPOCH, CLAIM_END_EPOCH);
+    let step = state.enter_drop(ALICE_ADDRESS_EXPR, 1);
+    state.world.sc_call(step);
+    let step = state.enter_drop(ALICE_ADDRESS_EXPR, 1);
+    state.world.sc_call(step.expect(user_error(ERR_MEMBERSHIP_ALREADY_ENROLLED)));
+    let step = state.enter_drop(BOB_ADDRESS_EXPR, 2);
+    state.world.sc_call(step);
+    let step = state.claim_drop(ALICE_ADDRESS_EXPR, 1);
+    state.world.sc_call(step.expect(user_error(ERR_ENROLLMENT_HAS_NOT_ENDED)));
+
+    // Out of a total weight of 4, Alice gets a quarter and Bob three quarters, rounded down
+    state.set_block_epoch(ENROLLMENT_END_EPOCH);
+    let step = state.claim_drop(ALICE_ADDRESS_EXPR, 1);
+    state.world.sc_call(step.expect_value(BigUint::from(2u64)));
+    let step = state.claim_drop(ALICE_ADDRESS_EXPR, 1);
+    state.world.sc_call(step.expect(user_error(ERR_DROP_ALREADY_CLAIMED)));
+    let step = state.claim_drop(BOB_ADDRESS_EXPR, 2);
+    state.world.sc_call(step.expect_value(BigUint::from(7u64)));
+    state.world.check_state_step(
+        CheckStateStep::new()
+            .put_account(
+                ALICE_ADDRESS_EXPR,
+                CheckAccount::new()
+                    .esdt_nft_balance_and_attributes(WINE_NFT_TOKEN_ID_EXPR, 1, "2", Some(""))
+                    .esdt_nft_balance_and_attributes(MEMBER_TOKEN_ID_EXPR, 1, "1", membership_attributes()),
+            )
+            .put_account(
+                BOB_ADDRESS_EXPR,
+                CheckAccount::new()
+                    .esdt_nft_balance_and_attributes(WINE_NFT_TOKEN_ID_EXPR, 1, "7", Some(""))
+                    .esdt_nft_balance_and_attributes(MEMBER_TOKEN_ID_EXPR, 2, "1", membership_attributes()),
+            ),
+    );
+
+    state.world.sc_call(
+        ScCallStep::new()
+            .from(PRODUCER_ADDRESS_EXPR)
+            .call(state.club.close_drop(1u32))
+            .expect(user_error(ERR_CLAIM_WINDOW_HAS_NOT_ENDED)),
+    );
+    state.set_block_epoch(CLAIM_END_EPOCH);
+    state.world.sc_call(
+        ScCallStep::new()
+            .from(ALICE_ADDRESS_EXPR)
+            .call(state.club.close_drop(1u32))
+            .expect(user_error(ERR_ONLY_PRODUCER_CAN_CLOSE_THE_DROP)),
+    );
+    state.world.sc_call(
+        ScCallStep::new()
+            .from(PRODUCER_ADDRESS_EXPR)
+            .call(state.club.close_drop(1u32))
+            .expect_value(BigUint::<StaticApi>::from(1u64)),
+    );
+
+    let drop: WineDrop<StaticApi> = state.world.quick_query(state.club.get_drop(1u32));
+    assert!(drop.closed);
+    assert_eq!(drop.claimed_bottles, 9u64);
+    state.world.check_state_step(
+        CheckStateStep::new().put_account(
+            PRODUCER_ADDRESS_EXPR,
+            CheckAccount::new().esdt_nft_balance_and_attributes(WINE_NFT_TOKEN_ID_EXPR, 1, "1", Some("")),
+        ),
+    );
+}
+
+#[test]
+fn lapsed_memberships_restart_from_the_current_epoch() {
+    let mut state = ClubTestState::new();
+
+    // Alice paid up to epoch 15 and stays in good standing for two more epochs
+    state.set_block_epoch(16);
+    let in_good_standing: bool = state.world.quick_query(state.club.is_in_good_standing(1u64));
+    assert!(in_good_standing);
+    state.world.sc_call(
+        ScCallStep::new()
+            .from(ALICE_ADDRESS_EXPR)
+            .egld_value(15u64)
+            .call(state.club.pay_membership(1u64))
+            .expect(user_error(ERR_PAYMENT_MUST_COVER_A_WHOLE_NUMBER_OF_EPOCHS)),
+    );
+    state.world.sc_call(
+        ScCallStep::new()
+            .from(ALICE_ADDRESS_EXPR)
+            .egld_value(10u64)
+            .call(state.club.pay_membership(1u64))
+            .expect_value(16u64),
+    );
+
+    state.set_block_epoch(18);
+    let in_good_standing: bool = state.world.quick_query(state.club.is_in_good_standing(1u64));
+    assert!(!in_good_standing);
+    state.deposit_drop(20, 30);
+    let step = state.enter_drop(ALICE_ADDRESS_EXPR, 1);
+    state.world.sc_call(step.expect(user_error(ERR_MEMBERSHIP_IS_NOT_IN_GOOD_STANDING)));
+    state.world.sc_call(
+        ScCallStep::new()
+            .from(ALICE_ADDRESS_EXPR)
+            .egld_value(20u64)
+            .call(state.club.pay_membership(1u64))
+            .expect_value(20u64),
+    );
+
+    // Every fee paid so far goes to the fee manager
+    state.world.sc_call(
+        ScCallStep::new()
+            .from(ALICE_ADDRESS_EXPR)
+            .call(state.club.claim_club_fees())
+            .expect(user_error(ERR_CALLER_DOES_NOT_HAVE_THE_REQUIRED_ROLE)),
+    );
+    state.world.sc_call(
+        ScCallStep::new()
+            .from(OWNER_ADDRESS_EXPR)
+            .call(state.club.claim_club_fees())
+            .expect_value(BigUint::<StaticApi>::from(170u64)),
+    );
+    state.world.check_state_step(
+        CheckStateStep::new()
+            .put_account(OWNER_ADDRESS_EXPR, CheckAccount::new().balance("170"))
+            .put_account(ALICE_ADDRESS_EXPR, CheckAccount::new().balance("920"))
+            .put_account(CLUB_ADDRESS_EXPR, CheckAccount::new().balance("0")),
+    );
+}
+
+#[test]
+fn payments_are_capped_in_epochs() {
+    let mut state = ClubTestState::new();
+    state.world.set_state_step(
+        SetStateStep::new().put_account(BOB_ADDRESS_EXPR, Account::new().nonce(1).balance("100000")),
+    );
+
+    // Alice's tier costs 10 per epoch
+    state.world.sc_call(
+        ScCallStep::new()
+            .from(BOB_ADDRESS_EXPR)
+            .egld_value(36_510u64)
+            .call(state.club.pay_membership(1u64))
+            .expect(user_error(ERR_PAYMENT_COVERS_TOO_MANY_EPOCHS)),
+    );
+    state.world.sc_call(
+        ScCallStep::new()
+            .from(BOB_ADDRESS_EXPR)
+            .egld_value(36_500u64)
+            .call(state.club.pay_membership(1u64))
+            .expect_value(START_EPOCH + 5 + 3650),
+    );
+}
+
+#[test]
+fn changing_tier_converts_the_prepaid_epochs() {
+    let mut state = ClubTestState::new();
+
+    // Alice's five epochs at 10 buy one epoch and change at 30
+    state.world.sc_call(
+        ScCallStep::new()
+            .from(ALICE_ADDRESS_EXPR)
+            .esdt_transfer(MEMBER_TOKEN_ID_EXPR, 1, "1")
+            .call(state.club.change_membership_tier(1u32))
+            .expect(user_error(ERR_MEMBERSHIP_IS_ALREADY_IN_THIS_TIER)),
+    );
+    state.world.sc_call(
+        ScCallStep::new()
+            .from(ALICE_ADDRESS_EXPR)
+            .esdt_transfer(MEMBER_TOKEN_ID_EXPR, 1, "1")
+            .call(state.club.change_membership_tier(2u32))
+            .expect_value(START_EPOCH + 1),
+    );
+    assert_eq!(state.membership(1).tier_id, 2);
+
+    // Closed tiers take no one new
+    state.world.sc_call(
+        ScCallStep::new()
+            .from(OWNER_ADDRESS_EXPR)
+            .call(state.club.update_membership_tier(1u32, 10u64, 1u32, false)),
+    );
+    state.world.sc_call(
+        ScCallStep::new()
+            .from(BOB_ADDRESS_EXPR)
+            .esdt_transfer(MEMBER_TOKEN_ID_EXPR, 2, "1")
+            .call(state.club.change_membership_tier(1u32))
+            .expect(user_error(ERR_TIER_IS_CLOSED_TO_NEW_MEMBERS)),
+    );
+    state.world.check_state_step(
+        CheckStateStep::new().put_account(
+            ALICE_ADDRESS_EXPR,
+            CheckAccount::new().esdt_nft_balance_and_attributes(MEMBER_TOKEN_ID_EXPR, 1, "1", membership_attributes()),
+        ),
+    );
+}
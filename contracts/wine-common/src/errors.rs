@@ -0,0 +1,826 @@
+// Failure messages of the wine contracts. Each starts with a code that is never reused or
+// renumbered, so SDKs and the frontend can match a failure on its code while the wording
+// stays free to change. Shared messages, including those of the roles and randomness
+// modules, are E0xxx, registry E1xxx, marketplace E2xxx. The other contracts get a hundred
+// codes each from E3000 on, in the order of their blocks below.
+
+// Shared by both contracts
+pub const ERR_BATCH_SIZE_MUST_BE_GREATER_THAN_ZERO: &str = "E0001: Batch size must be greater than zero";
+pub const ERR_UNKNOWN_STORAGE_VERSION: &str = "E0002: Unknown storage version";
+pub const ERR_PRODUCER_IS_NOT_VERIFIED: &str = "E0003: Producer is not verified";
+pub const ERR_AMOUNT_MUST_BE_GREATER_THAN_0: &str = "E0004: Amount must be greater than 0";
+pub const ERR_WINE_HAS_BEEN_RECALLED: &str = "E0005: Wine has been recalled";
+
+// Roles module
+pub const ERR_ADDRESS_ALREADY_HAS_THE_ROLE: &str = "E0006: Address already has the role";
+pub const ERR_ADDRESS_DOES_NOT_HAVE_THE_ROLE: &str = "E0007: Address does not have the role";
+pub const ERR_CONTRACT_IS_ALREADY_PAUSED: &str = "E0008: Contract is already paused";
+pub const ERR_CONTRACT_IS_NOT_PAUSED: &str = "E0009: Contract is not paused";
+pub const ERR_CALLER_DOES_NOT_HAVE_THE_REQUIRED_ROLE: &str =
+    "E0010: Caller does not have the required role";
+pub const ERR_ONLY_OWNER_CAN_MANAGE_ADMINS: &str = "E0011: Only owner can manage admins";
+pub const ERR_ONLY_ADMINS_CAN_MANAGE_ROLES: &str = "E0012: Only admins can manage roles";
+pub const ERR_CONTRACT_IS_PAUSED: &str = "E0013: Contract is paused";
+
+// Randomness module
+pub const ERR_DOMAIN_CANNOT_BE_EMPTY: &str = "E0014: Domain cannot be empty";
+pub const ERR_ENTROPY_IS_SEALED_FOR_THIS_CONTEXT: &str = "E0015: Entropy is sealed for this context";
+pub const ERR_ENTROPY_ALREADY_COMMITTED: &str = "E0016: Entropy already committed";
+pub const ERR_NO_ENTROPY_COMMITMENT_FOUND: &str = "E0017: No entropy commitment found";
+pub const ERR_ENTROPY_ALREADY_REVEALED: &str = "E0018: Entropy already revealed";
+pub const ERR_PREIMAGE_DOES_NOT_MATCH_COMMITMENT: &str = "E0019: Preimage does not match commitment";
+pub const ERR_INVALID_RANDOM_RANGE: &str = "E0020: Invalid random range";
+
+// Registry
+pub const ERR_INTEGRATION_NOT_REGISTERED: &str = "E1001: Integration not registered";
+pub const ERR_A_BLEND_NEEDS_AT_LEAST_TWO_SOURCE_WINES: &str =
+    "E1002: A blend needs at least two source wines";
+pub const ERR_TOO_MANY_SOURCE_WINES: &str = "E1003: Too many source wines";
+pub const ERR_ONLY_THE_OWNER_OF_A_SOURCE_WINE_CAN_BLEND_IT: &str =
+    "E1004: Only the owner of a source wine can blend it";
+pub const ERR_DUPLICATE_SOURCE_WINE: &str = "E1005: Duplicate source wine";
+pub const ERR_SHARE_MUST_BE_GREATER_THAN_0: &str = "E1006: Share must be greater than 0";
+pub const ERR_SHARES_MUST_ADD_UP_TO_100_PERCENT: &str = "E1007: Shares must add up to 100%";
+pub const ERR_AUTHORITY_IS_ALREADY_INACTIVE: &str = "E1008: Authority is already inactive";
+pub const ERR_AUTHORITY_IS_ALREADY_ACTIVE: &str = "E1009: Authority is already active";
+pub const ERR_CLASS_ALREADY_GRANTED: &str = "E1010: Class already granted";
+pub const ERR_CLASS_NOT_GRANTED: &str = "E1011: Class not granted";
+pub const ERR_ATTESTATION_CONTRACT_MUST_BE_A_SMART_CONTRACT: &str =
+    "E1012: Attestation contract must be a smart contract";
+pub const ERR_NOT_A_CERTIFICATION_AUTHORITY: &str = "E1013: Not a certification authority";
+pub const ERR_AUTHORITY_NOT_ALLOWED_TO_ISSUE_THIS_CERTIFICATION_TYPE: &str =
+    "E1014: Authority not allowed to issue this certification type";
+pub const ERR_ATTESTATION_CONTRACT_NOT_SET: &str = "E1015: Attestation contract not set";
+pub const ERR_CERTIFICATION_DOES_NOT_EXIST: &str = "E1016: Certification does not exist";
+pub const ERR_CERTIFICATION_ALREADY_REVOKED: &str = "E1017: Certification already revoked";
+pub const ERR_ONLY_ISSUING_AUTHORITY_OR_ADMIN_CAN_REVOKE: &str =
+    "E1018: Only issuing authority or admin can revoke";
+pub const ERR_AUTHORITY_DOES_NOT_EXIST: &str = "E1019: Authority does not exist";
+pub const ERR_EVIDENCE_IS_REQUIRED: &str = "E1020: Evidence is required";
+pub const ERR_EVIDENCE_HASH_IS_TOO_LONG: &str = "E1021: Evidence hash is too long";
+pub const ERR_WINE_IS_NOT_FLAGGED: &str = "E1022: Wine is not flagged";
+pub const ERR_REPORT_IS_NOT_OPEN: &str = "E1023: Report is not open";
+pub const ERR_REPORT_DOES_NOT_EXIST: &str = "E1024: Report does not exist";
+pub const ERR_ONLY_PRODUCER_CAN_RETIRE_THE_WINE: &str = "E1025: Only producer can retire the wine";
+pub const ERR_WINE_HAS_MINTED_BOTTLES: &str = "E1026: Wine has minted bottles";
+pub const ERR_ONLY_PRODUCER_OR_CERTIFICATION_AUTHORITY_CAN_CHANGE_STATUS: &str =
+    "E1027: Only producer or certification authority can change status";
+pub const ERR_INVALID_STATUS_TRANSITION: &str = "E1028: Invalid status transition";
+pub const ERR_ONLY_WINE_OWNER_CAN_MINT: &str = "E1029: Only wine owner can mint";
+pub const ERR_QUANTITY_MUST_BE_GREATER_THAN_0: &str = "E1030: Quantity must be greater than 0";
+pub const ERR_MINT_BATCH_TOO_LARGE: &str = "E1031: Mint batch too large";
+pub const ERR_EXPECTED_ONE_SECRET_HASH_PER_BOTTLE: &str = "E1032: Expected one secret hash per bottle";
+pub const ERR_WINE_COLLECTION_NOT_ISSUED: &str = "E1033: Wine collection not issued";
+pub const ERR_CANNOT_MINT_MORE_THAN_TOTAL_BOTTLES: &str = "E1034: Cannot mint more than total bottles";
+pub const ERR_BOTTLE_HAS_NO_AUTHENTICATION_SECRET: &str = "E1035: Bottle has no authentication secret";
+pub const ERR_BOTTLE_DOES_NOT_EXIST: &str = "E1036: Bottle does not exist";
+pub const ERR_PRODUCER_ALREADY_VERIFIED: &str = "E1037: Producer already verified";
+pub const ERR_WINE_DOES_NOT_EXIST: &str = "E1038: Wine does not exist";
+pub const ERR_INVALID_NEW_OWNER: &str = "E1039: Invalid new owner";
+pub const ERR_WINE_ALREADY_BELONGS_TO_THIS_ADDRESS: &str = "E1040: Wine already belongs to this address";
+pub const ERR_ONLY_WINE_OWNER_CAN_TRANSFER_OWNERSHIP: &str =
+    "E1041: Only wine owner can transfer ownership";
+pub const ERR_WINE_IS_LOCKED_BY_A_MARKETPLACE: &str = "E1042: Wine is locked by a marketplace";
+pub const ERR_NOT_AUTHORIZED_TO_RECORD_SALES: &str = "E1043: Not authorized to record sales";
+pub const ERR_WINE_IS_NOT_LOCKED_BY_CALLER: &str = "E1044: Wine is not locked by caller";
+pub const ERR_INVALID_TRANSFER_REASON: &str = "E1045: Invalid transfer reason";
+pub const ERR_RATING_MUST_BE_BETWEEN_1_10: &str = "E1046: Rating must be between 1-10";
+pub const ERR_REVIEW_IS_TOO_LONG: &str = "E1047: Review is too long";
+pub const ERR_YOU_HAVE_ALREADY_RATED_THIS_WINE: &str = "E1048: You have already rated this wine";
+pub const ERR_YOU_HAVE_NOT_RATED_THIS_WINE: &str = "E1049: You have not rated this wine";
+pub const ERR_RATING_CAN_NO_LONGER_BE_CHANGED: &str = "E1050: Rating can no longer be changed";
+pub const ERR_RATING_NOT_FOUND: &str = "E1051: Rating not found";
+pub const ERR_SUBSCRIBER_MUST_BE_A_SMART_CONTRACT: &str = "E1052: Subscriber must be a smart contract";
+pub const ERR_TOO_MANY_READ_MODEL_SUBSCRIBERS: &str = "E1053: Too many read model subscribers";
+pub const ERR_ONLY_WINE_OWNER_CAN_SET_ROYALTY: &str = "E1054: Only wine owner can set royalty";
+pub const ERR_ROYALTY_CANNOT_EXCEED_10_PERCENT: &str = "E1055: Royalty cannot exceed 10%";
+pub const ERR_ONLY_ADMIN_OR_WINE_OWNER_CAN_RESYNC: &str = "E1056: Only admin or wine owner can resync";
+pub const ERR_REGION_NAME_IS_REQUIRED: &str = "E1057: Region name is required";
+pub const ERR_REGION_NAME_IS_TOO_LONG: &str = "E1058: Region name is too long";
+pub const ERR_OTHER_IS_ALWAYS_ACCEPTED: &str = "E1059: Other is always accepted";
+pub const ERR_REGION_ALREADY_LISTED: &str = "E1060: Region already listed";
+pub const ERR_REGION_NOT_LISTED: &str = "E1061: Region not listed";
+pub const ERR_ONLY_ADMIN_OR_CERTIFICATION_AUTHORITY_CAN_CURATE_REGIONS: &str =
+    "E1062: Only admin or certification authority can curate regions";
+pub const ERR_INVALID_FEE_TOKEN: &str = "E1063: Invalid fee token";
+pub const ERR_ONLY_FEE_MANAGERS_OR_TREASURY_CAN_WITHDRAW_FEES: &str =
+    "E1064: Only fee managers or treasury can withdraw fees";
+pub const ERR_INSUFFICIENT_BALANCE: &str = "E1065: Insufficient balance";
+pub const ERR_NO_WINES_TO_REGISTER: &str = "E1066: No wines to register";
+pub const ERR_TOO_MANY_WINES_IN_ONE_BATCH: &str = "E1067: Too many wines in one batch";
+pub const ERR_ONLY_PRODUCER_CAN_UPDATE_WINE_DETAILS: &str =
+    "E1068: Only producer can update wine details";
+pub const ERR_IPFS_HASH_IS_REQUIRED: &str = "E1069: IPFS hash is required";
+pub const ERR_ONLY_PRODUCER_CAN_UPDATE_THE_PRICE: &str = "E1070: Only producer can update the price";
+pub const ERR_PRICE_PER_BOTTLE_MUST_BE_GREATER_THAN_0: &str =
+    "E1071: Price per bottle must be greater than 0";
+pub const ERR_PRICE_IS_UNCHANGED: &str = "E1072: Price is unchanged";
+pub const ERR_ONLY_PRODUCER_CAN_RESTOCK_THE_WINE: &str = "E1073: Only producer can restock the wine";
+pub const ERR_ADDITIONAL_BOTTLES_MUST_BE_GREATER_THAN_0: &str =
+    "E1074: Additional bottles must be greater than 0";
+pub const ERR_TOTAL_BOTTLES_WOULD_OVERFLOW: &str = "E1075: Total bottles would overflow";
+pub const ERR_NOT_AUTHORIZED_TO_UPDATE_AVAILABILITY: &str =
+    "E1076: Not authorized to update availability";
+pub const ERR_INSUFFICIENT_BOTTLES_AVAILABLE: &str = "E1077: Insufficient bottles available";
+pub const ERR_INSUFFICIENT_REGISTRATION_FEE: &str = "E1078: Insufficient registration fee";
+pub const ERR_PRODUCER_PUBLIC_KEY_NOT_REGISTERED: &str = "E1079: Producer public key not registered";
+pub const ERR_INVALID_PRODUCER_SIGNATURE: &str = "E1080: Invalid producer signature";
+pub const ERR_TOKEN_NOT_ACCEPTED_FOR_REGISTRATION_FEES: &str =
+    "E1081: Token not accepted for registration fees";
+pub const ERR_QUALITY_GRADE_MUST_BE_BETWEEN_1_10: &str = "E1082: Quality grade must be between 1-10";
+pub const ERR_ALCOHOL_CONTENT_CANNOT_EXCEED_20_PERCENT: &str =
+    "E1083: Alcohol content cannot exceed 20%";
+pub const ERR_INVALID_VINTAGE_YEAR: &str = "E1084: Invalid vintage year";
+pub const ERR_TOTAL_BOTTLES_MUST_BE_GREATER_THAN_0: &str = "E1085: Total bottles must be greater than 0";
+pub const ERR_UNKNOWN_REGION: &str = "E1086: Unknown region";
+pub const ERR_REWARD_TOKEN_MUST_BE_FUNGIBLE: &str = "E1087: Reward token must be fungible";
+pub const ERR_AMOUNT_PER_POINT_MUST_BE_GREATER_THAN_0: &str =
+    "E1088: Amount per point must be greater than 0";
+pub const ERR_TOO_MANY_HOLDERS: &str = "E1089: Too many holders";
+pub const ERR_INSUFFICIENT_REWARD_POOL: &str = "E1090: Insufficient reward pool";
+pub const ERR_REINDEX_BATCH_TOO_LARGE: &str = "E1091: Reindex batch too large";
+pub const ERR_ALREADY_A_SOMMELIER: &str = "E1092: Already a sommelier";
+pub const ERR_NOT_A_SOMMELIER: &str = "E1093: Not a sommelier";
+pub const ERR_ONLY_ADMIN_OR_CERTIFICATION_AUTHORITY_CAN_MANAGE_SOMMELIERS: &str =
+    "E1094: Only admin or certification authority can manage sommeliers";
+pub const ERR_ALREADY_A_STORAGE_LOGGER: &str = "E1095: Already a storage logger";
+pub const ERR_NOT_A_STORAGE_LOGGER: &str = "E1096: Not a storage logger";
+pub const ERR_HUMIDITY_CANNOT_EXCEED_100_PERCENT: &str = "E1097: Humidity cannot exceed 100%";
+pub const ERR_VINEYARD_NAME_IS_REQUIRED: &str = "E1098: Vineyard name is required";
+pub const ERR_VINEYARD_REGION_IS_REQUIRED: &str = "E1099: Vineyard region is required";
+pub const ERR_VINEYARD_SIZE_MUST_BE_GREATER_THAN_0: &str = "E1100: Vineyard size must be greater than 0";
+pub const ERR_ONLY_PRODUCER_CAN_SET_THE_VINEYARD: &str = "E1101: Only producer can set the vineyard";
+pub const ERR_ONLY_VINEYARD_OWNER_CAN_LINK_WINES: &str = "E1102: Only vineyard owner can link wines";
+pub const ERR_WINE_ALREADY_LINKED_TO_A_VINEYARD: &str = "E1103: Wine already linked to a vineyard";
+pub const ERR_VINEYARD_DOES_NOT_EXIST: &str = "E1104: Vineyard does not exist";
+pub const ERR_WINE_IS_NOT_LINKED_TO_A_VINEYARD: &str = "E1105: Wine is not linked to a vineyard";
+pub const ERR_ONLY_PRODUCER_OR_CERTIFICATION_AUTHORITY_CAN_ADD_VINTAGE_REPORTS: &str =
+    "E1106: Only producer or certification authority can add vintage reports";
+pub const ERR_IPFS_HASH_IS_TOO_LONG: &str = "E1107: IPFS hash is too long";
+pub const ERR_ONLY_ADMIN_OR_CERTIFICATION_AUTHORITY_CAN_SET_PRICE_FLOORS: &str =
+    "E1108: Only admin or certification authority can set price floors";
+pub const ERR_REGISTRATION_RATE_LIMIT_EXCEEDED: &str =
+    "E1109: Registration rate limit exceeded for this epoch";
+pub const ERR_INVALID_IPFS_CID: &str = "E1110: Invalid IPFS CID";
+pub const ERR_TAG_IS_REQUIRED: &str = "E1111: Tag is required";
+pub const ERR_TAG_IS_TOO_LONG: &str = "E1112: Tag is too long";
+pub const ERR_TAG_ALREADY_LISTED: &str = "E1113: Tag already listed";
+pub const ERR_TAG_NOT_LISTED: &str = "E1114: Tag not listed";
+pub const ERR_TOO_MANY_TAG_OPTIONS: &str = "E1115: Too many tags in this category";
+pub const ERR_TOO_MANY_WINE_TAGS: &str = "E1116: Too many tags on the wine in this category";
+pub const ERR_ONLY_PRODUCER_CAN_TAG_THE_WINE: &str = "E1117: Only producer can tag the wine";
+pub const ERR_ONLY_WINE_OWNER_CAN_ADD_CO_OWNERS: &str = "E1118: Only wine owner can add co-owners";
+pub const ERR_INVALID_CO_OWNER: &str = "E1119: Invalid co-owner";
+pub const ERR_ALREADY_A_CO_OWNER: &str = "E1120: Already a co-owner";
+pub const ERR_NOT_A_CO_OWNER: &str = "E1121: Not a co-owner";
+pub const ERR_CO_OWNER_SHARE_MUST_BE_GREATER_THAN_0: &str =
+    "E1122: Co-owner share must be greater than 0";
+pub const ERR_CO_OWNER_SHARE_EXCEEDS_OWNER_SHARE: &str =
+    "E1123: Co-owner share must be less than the owner's share";
+pub const ERR_TOO_MANY_CO_OWNERS: &str = "E1124: Too many co-owners";
+pub const ERR_ONLY_THE_CO_OWNER_CAN_GIVE_UP_THEIR_SHARE: &str =
+    "E1125: Only the co-owner can give up their share";
+pub const ERR_ONLY_WINE_OWNER_CAN_SET_APPROVAL_THRESHOLDS: &str =
+    "E1126: Only wine owner can set approval thresholds";
+pub const ERR_APPROVAL_THRESHOLD_CANNOT_EXCEED_100_PERCENT: &str =
+    "E1127: Approval threshold cannot exceed 100%";
+pub const ERR_APPROVAL_THRESHOLD_CANNOT_BE_LOWERED_WITH_CO_OWNERS: &str =
+    "E1128: Approval threshold cannot be lowered while the wine has co-owners";
+pub const ERR_CALLER_HOLDS_NO_SHARE_OF_THE_WINE: &str = "E1129: Caller holds no share of the wine";
+pub const ERR_WINE_ACTION_ALREADY_APPROVED: &str = "E1130: Wine action already approved";
+pub const ERR_WINE_ACTION_NOT_APPROVED: &str = "E1131: Wine action not approved";
+pub const ERR_CO_OWNER_APPROVALS_BELOW_THRESHOLD: &str =
+    "E1132: Co-owner approvals below threshold";
+pub const ERR_ATTESTATION_HAS_EXPIRED: &str = "E1133: Attestation has expired";
+
+// Marketplace
+pub const ERR_INVALID_TOKEN_IDENTIFIER: &str = "E2001: Invalid token identifier";
+pub const ERR_PAYMENT_TOKEN_NOT_SUPPORTED: &str = "E2002: Payment token not supported";
+pub const ERR_MARKETPLACE_LACKS_TRANSFER_ROLE_FOR_PAYMENT_TOKEN: &str =
+    "E2003: Marketplace lacks transfer role for payment token";
+pub const ERR_INVALID_PAYMENT_TOKEN: &str = "E2004: Invalid payment token";
+pub const ERR_PAYMENT_MUST_BE_A_FUNGIBLE_TOKEN: &str = "E2005: Payment must be a fungible token";
+pub const ERR_AUCTION_DOES_NOT_EXIST: &str = "E2006: Auction does not exist";
+pub const ERR_STARTING_PRICE_MUST_BE_GREATER_THAN_ZERO: &str =
+    "E2007: Starting price must be greater than zero";
+pub const ERR_MINIMUM_AUCTION_DURATION_IS_1_HOUR: &str = "E2008: Minimum auction duration is 1 hour";
+pub const ERR_MAXIMUM_AUCTION_DURATION_IS_7_DAYS: &str = "E2009: Maximum auction duration is 7 days";
+pub const ERR_MIN_BID_INCREMENT_MUST_BE_GREATER_THAN_ZERO: &str =
+    "E2010: Min bid increment must be greater than zero";
+pub const ERR_MUST_SEND_EXACTLY_1_NFT: &str = "E2011: Must send exactly 1 NFT";
+pub const ERR_AUCTION_IS_NOT_ACTIVE: &str = "E2012: Auction is not active";
+pub const ERR_AUCTION_HAS_ENDED: &str = "E2013: Auction has ended";
+pub const ERR_CANNOT_BID_ON_YOUR_OWN_AUCTION: &str = "E2014: Cannot bid on your own auction";
+pub const ERR_BID_TOO_LOW: &str = "E2015: Bid too low";
+pub const ERR_AUCTION_HAS_NOT_ENDED_YET: &str = "E2016: Auction has not ended yet";
+pub const ERR_CANCELLATION_REASON_IS_REQUIRED: &str = "E2018: Cancellation reason is required";
+pub const ERR_NOTHING_TO_WITHDRAW: &str = "E2019: Nothing to withdraw";
+pub const ERR_EXTENSION_WINDOW_CANNOT_EXCEED_1_HOUR: &str =
+    "E2020: Extension window cannot exceed 1 hour";
+pub const ERR_EXTENSION_DURATION_CANNOT_EXCEED_1_HOUR: &str =
+    "E2021: Extension duration cannot exceed 1 hour";
+pub const ERR_EXTENSION_DURATION_MUST_BE_GREATER_THAN_ZERO: &str =
+    "E2022: Extension duration must be greater than zero";
+pub const ERR_AUCTION_RESULT_DOES_NOT_EXIST: &str = "E2023: Auction result does not exist";
+pub const ERR_ADDRESS_IS_BLACKLISTED: &str = "E2024: Address is blacklisted";
+pub const ERR_MINIMUM_CLOSING_WINDOW_IS_10_MINUTES: &str = "E2025: Minimum closing window is 10 minutes";
+pub const ERR_CLOSING_WINDOW_CANNOT_EXCEED_AUCTION_DURATION: &str =
+    "E2026: Closing window cannot exceed auction duration";
+pub const ERR_AUCTION_IS_NOT_SETTLED_YET: &str = "E2027: Auction is not settled yet";
+pub const ERR_GOLD_THRESHOLDS_MUST_NOT_BE_BELOW_SILVER: &str =
+    "E2028: Gold thresholds must not be below silver";
+pub const ERR_TOKENS_MUST_DIFFER: &str = "E2029: Tokens must differ";
+pub const ERR_RATE_MUST_BE_GREATER_THAN_ZERO: &str = "E2030: Rate must be greater than zero";
+pub const ERR_ONLY_ADMIN_OR_RATE_ORACLE_CAN_UPDATE_RATES: &str =
+    "E2031: Only admin or rate oracle can update rates";
+pub const ERR_NO_EXCHANGE_RATE_FOR_PAYMENT_TOKEN: &str = "E2032: No exchange rate for payment token";
+pub const ERR_EXCHANGE_RATE_IS_STALE: &str = "E2033: Exchange rate is stale";
+pub const ERR_ALREADY_A_FEE_DISCOUNT_ISSUER: &str = "E2034: Already a fee discount issuer";
+pub const ERR_NOT_A_FEE_DISCOUNT_ISSUER: &str = "E2035: Not a fee discount issuer";
+pub const ERR_INVALID_FEE_DISCOUNT: &str = "E2036: Invalid fee discount";
+pub const ERR_EXPIRY_MUST_BE_IN_THE_FUTURE: &str = "E2037: Expiry must be in the future";
+pub const ERR_INVALID_MINIMUM_DEPOSIT: &str = "E2038: Invalid minimum deposit";
+pub const ERR_PENALTY_CANNOT_EXCEED_MINIMUM_DEPOSIT: &str =
+    "E2039: Penalty cannot exceed minimum deposit";
+pub const ERR_INVALID_INSTALLMENT_DURATION: &str = "E2040: Invalid installment duration";
+pub const ERR_LISTING_IS_NOT_ACTIVE: &str = "E2041: Listing is not active";
+pub const ERR_INSTALLMENTS_ARE_NOT_AVAILABLE_FOR_PROTECTED_LISTINGS: &str =
+    "E2042: Installments are not available for protected listings";
+pub const ERR_LISTING_HAS_EXPIRED: &str = "E2043: Listing has expired";
+pub const ERR_DEPOSIT_TOO_LOW: &str = "E2044: Deposit too low";
+pub const ERR_DEPOSIT_COVERS_THE_FULL_PRICE_USE_BUYWINE: &str =
+    "E2045: Deposit covers the full price, use buyWine";
+pub const ERR_CANNOT_BUY_YOUR_OWN_LISTING: &str = "E2046: Cannot buy your own listing";
+pub const ERR_ONLY_THE_PLAN_BUYER_CAN_PAY_INSTALLMENTS: &str =
+    "E2047: Only the plan buyer can pay installments";
+pub const ERR_INSTALLMENT_DEADLINE_HAS_PASSED: &str = "E2048: Installment deadline has passed";
+pub const ERR_PAYMENT_MUST_BE_GREATER_THAN_ZERO: &str = "E2049: Payment must be greater than zero";
+pub const ERR_INSTALLMENT_DEADLINE_HAS_NOT_PASSED: &str = "E2050: Installment deadline has not passed";
+pub const ERR_NO_INSTALLMENT_PLAN_FOR_LISTING: &str = "E2051: No installment plan for listing";
+pub const ERR_FEE_CANNOT_EXCEED_10_PERCENT: &str = "E2052: Fee cannot exceed 10%";
+pub const ERR_PRICE_MUST_BE_GREATER_THAN_ZERO: &str = "E2053: Price must be greater than zero";
+pub const ERR_MINIMUM_LISTING_DURATION_IS_1_HOUR: &str = "E2054: Minimum listing duration is 1 hour";
+pub const ERR_MAXIMUM_LISTING_DURATION_IS_30_DAYS: &str = "E2055: Maximum listing duration is 30 days";
+pub const ERR_INSUFFICIENT_PAYMENT: &str = "E2056: Insufficient payment";
+pub const ERR_ONLY_SELLER_CAN_CANCEL_LISTING: &str = "E2057: Only seller can cancel listing";
+pub const ERR_WINE_HAS_NOT_BEEN_RECALLED: &str = "E2058: Wine has not been recalled";
+pub const ERR_ONLY_SELLER_CAN_CHANGE_PAYMENT_TOKENS: &str =
+    "E2059: Only seller can change payment tokens";
+pub const ERR_LISTING_IS_ALREADY_PRICED_IN_THIS_TOKEN: &str =
+    "E2060: Listing is already priced in this token";
+pub const ERR_TOO_MANY_PAYMENT_TOKENS: &str = "E2061: Too many payment tokens";
+pub const ERR_ONLY_SELLER_CAN_SET_AUTO_RELIST: &str = "E2062: Only seller can set auto relist";
+pub const ERR_RELIST_LIMIT_TOO_HIGH: &str = "E2063: Relist limit too high";
+pub const ERR_LISTING_HAS_NOT_EXPIRED: &str = "E2064: Listing has not expired";
+pub const ERR_RELIST_LIMIT_REACHED: &str = "E2065: Relist limit reached";
+pub const ERR_AUTO_RELIST_NOT_AVAILABLE: &str = "E2066: Auto relist not available";
+pub const ERR_LOYALTY_TOKEN_ALREADY_SET: &str = "E2067: Loyalty token already set";
+pub const ERR_INVALID_LOYALTY_TOKEN: &str = "E2068: Invalid loyalty token";
+pub const ERR_LOYALTY_TOKEN_NOT_SET: &str = "E2069: Loyalty token not set";
+pub const ERR_NO_LOYALTY_REWARDS_TO_CLAIM: &str = "E2070: No loyalty rewards to claim";
+pub const ERR_OFFER_AMOUNT_MUST_BE_GREATER_THAN_ZERO: &str =
+    "E2071: Offer amount must be greater than zero";
+pub const ERR_MINIMUM_OFFER_DURATION_IS_1_HOUR: &str = "E2072: Minimum offer duration is 1 hour";
+pub const ERR_MAXIMUM_OFFER_DURATION_IS_30_DAYS: &str = "E2073: Maximum offer duration is 30 days";
+pub const ERR_OFFER_IS_NOT_ACTIVE: &str = "E2074: Offer is not active";
+pub const ERR_OFFER_HAS_EXPIRED: &str = "E2075: Offer has expired";
+pub const ERR_NFT_DOES_NOT_MATCH_OFFER: &str = "E2076: NFT does not match offer";
+pub const ERR_CANNOT_ACCEPT_YOUR_OWN_OFFER: &str = "E2077: Cannot accept your own offer";
+pub const ERR_ONLY_BUYER_CAN_CANCEL_OFFER: &str = "E2078: Only buyer can cancel offer";
+pub const ERR_INVALID_ARBITER_ADDRESS: &str = "E2079: Invalid arbiter address";
+pub const ERR_DISPUTE_WINDOW_MUST_BE_BETWEEN_1_AND_60_DAYS: &str =
+    "E2080: Dispute window must be between 1 and 60 days";
+pub const ERR_SALE_IS_NOT_IN_ESCROW: &str = "E2081: Sale is not in escrow";
+pub const ERR_ONLY_BUYER_CAN_OPEN_A_DISPUTE: &str = "E2082: Only buyer can open a dispute";
+pub const ERR_DISPUTE_WINDOW_HAS_PASSED: &str = "E2083: Dispute window has passed";
+pub const ERR_DISPUTE_WINDOW_HAS_NOT_PASSED: &str = "E2084: Dispute window has not passed";
+pub const ERR_SALE_IS_NOT_DISPUTED: &str = "E2085: Sale is not disputed";
+pub const ERR_ONLY_ARBITER_CAN_RESOLVE_DISPUTES: &str = "E2086: Only arbiter can resolve disputes";
+pub const ERR_PROTECTED_SALE_DOES_NOT_EXIST: &str = "E2087: Protected sale does not exist";
+pub const ERR_NO_ARBITER_CONFIGURED: &str = "E2088: No arbiter configured";
+pub const ERR_ONLY_THE_WINE_REGISTRY_CAN_SYNC_WINE_DATA: &str =
+    "E2089: Only the wine registry can sync wine data";
+pub const ERR_WINE_HAS_BEEN_RETIRED: &str = "E2090: Wine has been retired";
+pub const ERR_WINE_HAS_BEEN_FLAGGED_AS_COUNTERFEIT: &str = "E2091: Wine has been flagged as counterfeit";
+pub const ERR_VERIFIED_LIMIT_MUST_NOT_BE_BELOW_THE_DEFAULT_LIMIT: &str =
+    "E2092: Verified limit must not be below the default limit";
+pub const ERR_TOO_MANY_ACTIVE_ITEMS_FOR_THIS_SELLER: &str =
+    "E2093: Too many active items for this seller";
+pub const ERR_PROMOTIONAL_FEE_MUST_BE_BELOW_THE_MARKETPLACE_FEE: &str =
+    "E2094: Promotional fee must be below the marketplace fee";
+pub const ERR_PROMOTION_NEEDS_A_DURATION_OR_A_SALES_LIMIT: &str =
+    "E2095: Promotion needs a duration or a sales limit";
+pub const ERR_TOO_MANY_FEE_PROMOTIONS: &str = "E2096: Too many fee promotions";
+pub const ERR_CONSIGNMENT_CONTRACT_MUST_BE_A_SMART_CONTRACT: &str =
+    "E2097: Consignment contract must be a smart contract";
+pub const ERR_ALREADY_A_CONSIGNMENT_CONTRACT: &str = "E2098: Already a consignment contract";
+pub const ERR_NOT_A_CONSIGNMENT_CONTRACT: &str = "E2099: Not a consignment contract";
+pub const ERR_ONLY_OWNER_CAN_SET_THE_FEE_RECIPIENT: &str = "E2100: Only owner can set the fee recipient";
+pub const ERR_INVALID_FEE_RECIPIENT: &str = "E2101: Invalid fee recipient";
+pub const ERR_ONLY_OWNER_CAN_SET_THE_FEE_ROUTER: &str = "E2102: Only owner can set the fee router";
+pub const ERR_FEE_ROUTER_MUST_BE_A_SMART_CONTRACT: &str = "E2103: Fee router must be a smart contract";
+pub const ERR_ONLY_OWNER_CAN_SET_THE_FEE_SHARE: &str = "E2104: Only owner can set the fee share";
+pub const ERR_FEE_SHARE_CANNOT_EXCEED_100_PERCENT: &str = "E2105: Fee share cannot exceed 100%";
+pub const ERR_INVALID_FEE_SHARE_RECIPIENT: &str = "E2106: Invalid fee share recipient";
+pub const ERR_INVALID_RECIPIENT_ADDRESS: &str = "E2107: Invalid recipient address";
+pub const ERR_PROTECTED_LISTINGS_TAKE_A_SINGLE_PAYMENT_TOKEN: &str =
+    "E2108: Protected listings take a single payment token";
+pub const ERR_DUPLICATE_PAYMENT_TOKEN: &str = "E2109: Duplicate payment token";
+pub const ERR_CLEANUP_BATCH_TOO_LARGE: &str = "E2110: Cleanup batch too large";
+pub const ERR_STORAGE_MIGRATION_PENDING: &str = "E2111: Storage migration pending";
+pub const ERR_SYNC_BATCH_TOO_LARGE: &str = "E2112: State sync batch too large";
+pub const ERR_PRICE_BELOW_GRADE_FLOOR: &str = "E2113: Price below the floor for the wine's grade";
+pub const ERR_ONLY_CERTIFIED_WINES_CAN_BE_AUCTIONED: &str =
+    "E2114: Only certified wines can be auctioned";
+pub const ERR_KEEPER_BOUNTY_TOO_HIGH: &str = "E2115: Keeper bounty cannot exceed 20% of the fee";
+pub const ERR_MIXED_SETTLEMENT_NOT_ALLOWED: &str =
+    "E2116: Listing does not take EGLD and ESDT payments together";
+pub const ERR_ONLY_SELLER_CAN_SET_MIXED_SETTLEMENT: &str =
+    "E2117: Only seller can set mixed settlement";
+pub const ERR_ALREADY_A_RELAYER: &str = "E2118: Address is already a relayer";
+pub const ERR_NOT_A_RELAYER: &str = "E2119: Address is not a relayer";
+pub const ERR_CALLER_IS_NOT_A_RELAYER: &str = "E2120: Caller is not a whitelisted relayer";
+pub const ERR_INTENT_HAS_EXPIRED: &str = "E2121: Intent has expired";
+pub const ERR_INVALID_INTENT_NONCE: &str = "E2122: Invalid intent nonce";
+pub const ERR_INVALID_INTENT_SIGNATURE: &str = "E2123: Invalid intent signature";
+pub const ERR_PRICE_EXCEEDS_INTENT_MAXIMUM: &str = "E2124: Price exceeds the intent maximum";
+pub const ERR_INSUFFICIENT_RELAY_BALANCE: &str = "E2125: Insufficient relay balance";
+pub const ERR_WINE_COLLECTION_NOT_SET: &str = "E2126: Wine collection not set";
+pub const ERR_NOT_A_WINE_NFT: &str = "E2127: Token is not a wine NFT";
+pub const ERR_WINE_ID_DOES_NOT_MATCH_NFT: &str = "E2128: Wine id does not match the NFT";
+pub const ERR_WINE_NOT_SYNCED_FROM_REGISTRY: &str = "E2129: Wine has not been synced from the registry";
+
+// Shared by the satellite contracts
+pub const ERR_CAMPAIGN_DOES_NOT_EXIST: &str = "E0021: Campaign does not exist";
+pub const ERR_ALREADY_VOTED: &str = "E0022: Already voted";
+pub const ERR_VOTING_PERIOD_MUST_BE_GREATER_THAN_0: &str =
+    "E0023: Voting period must be greater than 0";
+pub const ERR_INVALID_STAKING_TOKEN: &str = "E0024: Invalid staking token";
+pub const ERR_NOT_ENOUGH_STAKE: &str = "E0025: Not enough stake";
+pub const ERR_INVALID_WINE_COLLECTION: &str = "E0026: Invalid wine collection";
+pub const ERR_INVALID_WINE_TOKEN: &str = "E0027: Invalid wine token";
+pub const ERR_BOTTLES_MUST_BE_GREATER_THAN_0: &str = "E0028: Bottles must be greater than 0";
+pub const ERR_INVALID_BOTTLE_AMOUNT: &str = "E0029: Invalid bottle amount";
+pub const ERR_DURATION_MUST_BE_GREATER_THAN_0: &str = "E0030: Duration must be greater than 0";
+pub const ERR_NOT_ENOUGH_BOTTLES_LEFT: &str = "E0031: Not enough bottles left";
+pub const ERR_INCORRECT_PAYMENT_AMOUNT: &str = "E0032: Incorrect payment amount";
+pub const ERR_INVALID_WINE_NONCE: &str = "E0033: Invalid wine nonce";
+pub const ERR_SHARE_TOKEN_NOT_ISSUED: &str = "E0034: Share token not issued";
+pub const ERR_INVALID_WINE_PAYMENT: &str = "E0035: Invalid wine payment";
+pub const ERR_NOT_ENOUGH_SHARES: &str = "E0036: Not enough shares";
+pub const ERR_MAXIMUM_PRICE_AGE_MUST_BE_GREATER_THAN_0: &str =
+    "E0037: Maximum price age must be greater than 0";
+pub const ERR_PRICE_MUST_BE_GREATER_THAN_0: &str = "E0038: Price must be greater than 0";
+pub const ERR_NOT_ENOUGH_TICKETS_LEFT: &str = "E0039: Not enough tickets left";
+
+// Airdrop
+pub const ERR_CAMPAIGN_MUST_BE_FUNDED: &str = "E3001: Campaign must be funded";
+pub const ERR_CAMPAIGN_HAS_EXPIRED: &str = "E3002: Campaign has expired";
+pub const ERR_ALREADY_CLAIMED: &str = "E3003: Already claimed";
+pub const ERR_PROOF_TOO_LONG: &str = "E3004: Proof too long";
+pub const ERR_INVALID_MERKLE_PROOF: &str = "E3005: Invalid Merkle proof";
+pub const ERR_CAMPAIGN_IS_EXHAUSTED: &str = "E3006: Campaign is exhausted";
+pub const ERR_CAMPAIGN_HAS_NOT_EXPIRED: &str = "E3007: Campaign has not expired";
+pub const ERR_CAMPAIGN_ALREADY_CLAWED_BACK: &str = "E3008: Campaign already clawed back";
+
+// Arbitration
+pub const ERR_SOURCE_MUST_BE_A_SMART_CONTRACT: &str = "E3101: Source must be a smart contract";
+pub const ERR_NOT_A_DISPUTE_SOURCE: &str = "E3102: Not a dispute source";
+pub const ERR_CASE_ALREADY_OPEN: &str = "E3103: Case already open";
+pub const ERR_JURORS_ALREADY_DRAWN: &str = "E3104: Jurors already drawn";
+pub const ERR_DRAW_DELAY_HAS_NOT_PASSED: &str = "E3105: Draw delay has not passed";
+pub const ERR_NOT_ENOUGH_JURORS_IN_THE_POOL: &str = "E3106: Not enough jurors in the pool";
+pub const ERR_CASE_IS_NOT_VOTING: &str = "E3107: Case is not voting";
+pub const ERR_VOTING_HAS_CLOSED: &str = "E3108: Voting has closed";
+pub const ERR_NOT_A_JUROR_OF_THIS_CASE: &str = "E3109: Not a juror of this case";
+pub const ERR_VOTING_IS_STILL_OPEN: &str = "E3110: Voting is still open";
+pub const ERR_JURORS_PER_CASE_MUST_BE_ODD_AND_AT_MOST_9: &str =
+    "E3111: Jurors per case must be odd and at most 9";
+pub const ERR_SLASH_CANNOT_EXCEED_100_PERCENT: &str = "E3112: Slash cannot exceed 100%";
+pub const ERR_CASE_DOES_NOT_EXIST: &str = "E3113: Case does not exist";
+pub const ERR_MINIMUM_STAKE_MUST_BE_GREATER_THAN_0: &str =
+    "E3114: Minimum stake must be greater than 0";
+pub const ERR_JUROR_SITS_ON_AN_OPEN_CASE: &str = "E3115: Juror sits on an open case";
+pub const ERR_JUROR_POOL_IS_FULL: &str = "E3116: Juror pool is full";
+
+// Attestations
+pub const ERR_SCHEMA_IS_INACTIVE: &str = "E3201: Schema is inactive";
+pub const ERR_ATTESTATION_ALREADY_PUBLISHED: &str = "E3202: Attestation already published";
+pub const ERR_INVALID_ATTESTATION_SIGNATURE: &str = "E3203: Invalid attestation signature";
+pub const ERR_ONLY_THE_ISSUER_OR_AN_ADMIN_CAN_REVOKE: &str =
+    "E3204: Only the issuer or an admin can revoke";
+pub const ERR_ATTESTATION_ALREADY_REVOKED: &str = "E3205: Attestation already revoked";
+pub const ERR_ATTESTATION_DOES_NOT_EXIST: &str = "E3206: Attestation does not exist";
+pub const ERR_ALREADY_AN_ATTESTATION_ISSUER: &str = "E3207: Already an attestation issuer";
+pub const ERR_SCHEMA_NAME_CANNOT_BE_EMPTY: &str = "E3208: Schema name cannot be empty";
+pub const ERR_SCHEMA_IS_ALREADY_INACTIVE: &str = "E3209: Schema is already inactive";
+pub const ERR_NOT_AN_ATTESTATION_ISSUER: &str = "E3210: Not an attestation issuer";
+pub const ERR_SCHEMA_DOES_NOT_EXIST: &str = "E3211: Schema does not exist";
+
+// Bridge
+pub const ERR_CHAIN_NAME_CANNOT_BE_EMPTY: &str = "E3301: Chain name cannot be empty";
+pub const ERR_CHAIN_ALREADY_SUPPORTED: &str = "E3302: Chain already supported";
+pub const ERR_CHAIN_NOT_SUPPORTED: &str = "E3303: Chain not supported";
+pub const ERR_MAXIMUM_NUMBER_OF_RELAYERS_REACHED: &str =
+    "E3304: Maximum number of relayers reached";
+pub const ERR_BRIDGE_ALREADY_A_RELAYER: &str = "E3305: Already a relayer";
+pub const ERR_BRIDGE_NOT_A_RELAYER: &str = "E3306: Not a relayer";
+pub const ERR_REMOVAL_WOULD_LEAVE_FEWER_RELAYERS_THAN_THE_QUORUM: &str =
+    "E3307: Removal would leave fewer relayers than the quorum";
+pub const ERR_QUORUM_MUST_BE_GREATER_THAN_0: &str = "E3308: Quorum must be greater than 0";
+pub const ERR_QUORUM_EXCEEDS_THE_NUMBER_OF_RELAYERS: &str =
+    "E3309: Quorum exceeds the number of relayers";
+pub const ERR_RELAYER_QUORUM_NOT_SET: &str = "E3310: Relayer quorum not set";
+pub const ERR_TOO_MANY_SIGNATURES: &str = "E3311: Too many signatures";
+pub const ERR_SIGNER_IS_NOT_A_RELAYER: &str = "E3312: Signer is not a relayer";
+pub const ERR_DUPLICATE_RELAYER_SIGNATURE: &str = "E3313: Duplicate relayer signature";
+pub const ERR_INVALID_RELAYER_SIGNATURE: &str = "E3314: Invalid relayer signature";
+pub const ERR_NOT_ENOUGH_RELAYER_SIGNATURES: &str = "E3315: Not enough relayer signatures";
+pub const ERR_DESTINATION_CHAIN_NOT_SUPPORTED: &str = "E3316: Destination chain not supported";
+pub const ERR_RECIPIENT_CANNOT_BE_EMPTY: &str = "E3317: Recipient cannot be empty";
+pub const ERR_TRANSFER_ALREADY_PROCESSED: &str = "E3318: Transfer already processed";
+pub const ERR_NOT_ENOUGH_BOTTLES_LOCKED_FOR_THIS_WINE: &str =
+    "E3319: Not enough bottles locked for this wine";
+pub const ERR_TRANSFER_DOES_NOT_EXIST: &str = "E3320: Transfer does not exist";
+
+// Cellar
+pub const ERR_ALREADY_A_CELLAR_KEEPER: &str = "E3401: Already a cellar keeper";
+pub const ERR_NOT_A_CELLAR_KEEPER: &str = "E3402: Not a cellar keeper";
+pub const ERR_WINE_IS_NO_LONGER_STORED: &str = "E3403: Wine is no longer stored";
+pub const ERR_GRACE_PERIOD_MUST_BE_GREATER_THAN_0: &str =
+    "E3404: Grace period must be greater than 0";
+pub const ERR_ONLY_OWNER_CAN_WITHDRAW: &str = "E3405: Only owner can withdraw";
+pub const ERR_STORAGE_FEES_OUTSTANDING: &str = "E3406: Storage fees outstanding";
+pub const ERR_DEPOSIT_IS_NOT_LIQUIDATABLE: &str = "E3407: Deposit is not liquidatable";
+pub const ERR_NO_STORAGE_FEES_TO_CLAIM: &str = "E3408: No storage fees to claim";
+pub const ERR_DEPOSIT_DOES_NOT_EXIST: &str = "E3409: Deposit does not exist";
+
+// Club
+pub const ERR_ALREADY_A_CLUB_PRODUCER: &str = "E3501: Already a club producer";
+pub const ERR_NOT_A_CLUB_PRODUCER: &str = "E3502: Not a club producer";
+pub const ERR_ONLY_CLUB_PRODUCERS_CAN_DEPOSIT_DROPS: &str =
+    "E3503: Only club producers can deposit drops";
+pub const ERR_ENROLLMENT_END_MUST_BE_IN_THE_FUTURE: &str =
+    "E3504: Enrollment end must be in the future";
+pub const ERR_CLAIM_END_MUST_FOLLOW_THE_ENROLLMENT_END: &str =
+    "E3505: Claim end must follow the enrollment end";
+pub const ERR_ONLY_WINE_NFTS_CAN_BE_DROPPED: &str = "E3506: Only wine NFTs can be dropped";
+pub const ERR_ENROLLMENT_HAS_ENDED: &str = "E3507: Enrollment has ended";
+pub const ERR_MEMBERSHIP_ALREADY_ENROLLED: &str = "E3508: Membership already enrolled";
+pub const ERR_MEMBERSHIP_IS_NOT_IN_GOOD_STANDING: &str =
+    "E3509: Membership is not in good standing";
+pub const ERR_ENROLLMENT_HAS_NOT_ENDED: &str = "E3510: Enrollment has not ended";
+pub const ERR_CLAIM_WINDOW_HAS_ENDED: &str = "E3511: Claim window has ended";
+pub const ERR_MEMBERSHIP_IS_NOT_ENROLLED: &str = "E3512: Membership is not enrolled";
+pub const ERR_DROP_ALREADY_CLAIMED: &str = "E3513: Drop already claimed";
+pub const ERR_ONLY_PRODUCER_CAN_CLOSE_THE_DROP: &str = "E3514: Only producer can close the drop";
+pub const ERR_DROP_ALREADY_CLOSED: &str = "E3515: Drop already closed";
+pub const ERR_CLAIM_WINDOW_HAS_NOT_ENDED: &str = "E3516: Claim window has not ended";
+pub const ERR_DROP_DOES_NOT_EXIST: &str = "E3517: Drop does not exist";
+pub const ERR_MAXIMUM_NUMBER_OF_TIERS_REACHED: &str = "E3518: Maximum number of tiers reached";
+pub const ERR_TIER_NAME_CANNOT_BE_EMPTY: &str = "E3519: Tier name cannot be empty";
+pub const ERR_FEE_PER_EPOCH_MUST_BE_GREATER_THAN_0: &str =
+    "E3520: Fee per epoch must be greater than 0";
+pub const ERR_DROP_WEIGHT_MUST_BE_GREATER_THAN_0: &str =
+    "E3521: Drop weight must be greater than 0";
+pub const ERR_TIER_IS_CLOSED_TO_NEW_MEMBERS: &str = "E3522: Tier is closed to new members";
+pub const ERR_MEMBERSHIP_COLLECTION_NOT_ISSUED: &str = "E3523: Membership collection not issued";
+pub const ERR_MEMBERSHIP_IS_ALREADY_IN_THIS_TIER: &str =
+    "E3524: Membership is already in this tier";
+pub const ERR_MEMBERSHIP_FEES_ARE_OVERDUE: &str = "E3525: Membership fees are overdue";
+pub const ERR_NO_CLUB_FEES_TO_CLAIM: &str = "E3526: No club fees to claim";
+pub const ERR_TIER_DOES_NOT_EXIST: &str = "E3527: Tier does not exist";
+pub const ERR_MEMBERSHIP_DOES_NOT_EXIST: &str = "E3528: Membership does not exist";
+pub const ERR_PAYMENT_MUST_COVER_AT_LEAST_ONE_EPOCH: &str =
+    "E3529: Payment must cover at least one epoch";
+pub const ERR_PAYMENT_MUST_COVER_A_WHOLE_NUMBER_OF_EPOCHS: &str =
+    "E3530: Payment must cover a whole number of epochs";
+pub const ERR_PAYMENT_COVERS_TOO_MANY_EPOCHS: &str = "E3531: Payment covers too many epochs";
+pub const ERR_INVALID_MEMBERSHIP_TOKEN: &str = "E3532: Invalid membership token";
+
+// Consignment
+pub const ERR_MARKETPLACE_MUST_BE_A_SMART_CONTRACT: &str =
+    "E3601: Marketplace must be a smart contract";
+pub const ERR_WINE_IS_ALREADY_ON_CONSIGNMENT: &str = "E3602: Wine is already on consignment";
+pub const ERR_MINIMUM_PRICE_MUST_BE_GREATER_THAN_0: &str =
+    "E3603: Minimum price must be greater than 0";
+pub const ERR_COMMISSION_CANNOT_EXCEED_100_PERCENT: &str = "E3604: Commission cannot exceed 100%";
+pub const ERR_MERCHANT_MUST_DIFFER_FROM_PRODUCER: &str =
+    "E3605: Merchant must differ from producer";
+pub const ERR_ONLY_MERCHANT_CAN_LIST: &str = "E3606: Only merchant can list";
+pub const ERR_PRICE_BELOW_THE_CONSIGNMENT_MINIMUM: &str =
+    "E3607: Price below the consignment minimum";
+pub const ERR_LISTING_WOULD_OUTLAST_THE_CONSIGNMENT: &str =
+    "E3608: Listing would outlast the consignment";
+pub const ERR_NO_BOTTLES_LEFT_TO_LIST: &str = "E3609: No bottles left to list";
+pub const ERR_NOT_ALLOWED_TO_DELIST: &str = "E3610: Not allowed to delist";
+pub const ERR_ONLY_THE_MARKETPLACE_CAN_PAY_PROCEEDS: &str =
+    "E3611: Only the marketplace can pay proceeds";
+pub const ERR_WINE_IS_NOT_ON_CONSIGNMENT: &str = "E3612: Wine is not on consignment";
+pub const ERR_NOT_ALLOWED_TO_CLOSE: &str = "E3613: Not allowed to close";
+pub const ERR_BOTTLES_STILL_LISTED_ON_THE_MARKETPLACE: &str =
+    "E3614: Bottles still listed on the marketplace";
+pub const ERR_CONSIGNMENT_DOES_NOT_EXIST: &str = "E3615: Consignment does not exist";
+pub const ERR_CONSIGNMENT_IS_CLOSED: &str = "E3616: Consignment is closed";
+
+// Crowdfunding
+pub const ERR_ALREADY_A_CAMPAIGN_PRODUCER: &str = "E3701: Already a campaign producer";
+pub const ERR_NOT_A_CAMPAIGN_PRODUCER: &str = "E3702: Not a campaign producer";
+pub const ERR_ALREADY_A_MILESTONE_AUTHORITY: &str = "E3703: Already a milestone authority";
+pub const ERR_NOT_A_MILESTONE_AUTHORITY: &str = "E3704: Not a milestone authority";
+pub const ERR_ONLY_CAMPAIGN_PRODUCERS_CAN_CREATE_CAMPAIGNS: &str =
+    "E3705: Only campaign producers can create campaigns";
+pub const ERR_GOAL_MUST_BE_GREATER_THAN_0: &str = "E3706: Goal must be greater than 0";
+pub const ERR_MAXIMUM_BOTTLES_MUST_NOT_BE_BELOW_THE_GOAL: &str =
+    "E3707: Maximum bottles must not be below the goal";
+pub const ERR_DEADLINE_MUST_BE_IN_THE_FUTURE: &str = "E3708: Deadline must be in the future";
+pub const ERR_ONLY_PRODUCER_CAN_SET_MILESTONES: &str = "E3709: Only producer can set milestones";
+pub const ERR_CAMPAIGN_ALREADY_HAS_BACKERS: &str = "E3710: Campaign already has backers";
+pub const ERR_INVALID_NUMBER_OF_MILESTONES: &str = "E3711: Invalid number of milestones";
+pub const ERR_MILESTONES_MUST_ADD_UP_TO_100_PERCENT: &str = "E3712: Milestones must add up to 100%";
+pub const ERR_CAMPAIGN_IS_NOT_FUNDING: &str = "E3713: Campaign is not funding";
+pub const ERR_CAMPAIGN_HAS_ENDED: &str = "E3714: Campaign has ended";
+pub const ERR_ALLOCATION_COLLECTION_NOT_ISSUED: &str = "E3715: Allocation collection not issued";
+pub const ERR_CAMPAIGN_IS_STILL_FUNDING: &str = "E3716: Campaign is still funding";
+pub const ERR_ONLY_PRODUCER_CAN_CANCEL_THE_CAMPAIGN: &str =
+    "E3717: Only producer can cancel the campaign";
+pub const ERR_CAMPAIGN_HAS_NOT_SUCCEEDED: &str = "E3718: Campaign has not succeeded";
+pub const ERR_ALL_MILESTONES_CONFIRMED: &str = "E3719: All milestones confirmed";
+pub const ERR_VINTAGE_DELIVERY_HAS_STARTED: &str = "E3720: Vintage delivery has started";
+pub const ERR_ONLY_PRODUCER_CAN_RELEASE_FUNDS: &str = "E3721: Only producer can release funds";
+pub const ERR_NOTHING_TO_RELEASE: &str = "E3722: Nothing to release";
+pub const ERR_ONLY_PRODUCER_CAN_DELIVER_THE_VINTAGE: &str =
+    "E3723: Only producer can deliver the vintage";
+pub const ERR_DELIVERED_VINTAGE_DOES_NOT_MATCH: &str = "E3724: Delivered vintage does not match";
+pub const ERR_DELIVERY_EXCEEDS_THE_BOTTLES_BACKED: &str =
+    "E3725: Delivery exceeds the bottles backed";
+pub const ERR_NOT_ENOUGH_WINE_DELIVERED: &str = "E3726: Not enough wine delivered";
+pub const ERR_CAMPAIGN_HAS_NOT_FAILED: &str = "E3727: Campaign has not failed";
+pub const ERR_ONLY_THE_CAMPAIGN_S_MILESTONE_AUTHORITY_CAN_DO_THIS: &str =
+    "E3728: Only the campaign's milestone authority can do this";
+pub const ERR_NO_ALLOCATIONS_WERE_SOLD: &str = "E3729: No allocations were sold";
+pub const ERR_INVALID_ALLOCATION_TOKEN: &str = "E3730: Invalid allocation token";
+pub const ERR_NO_ALLOCATIONS_PAID: &str = "E3731: No allocations paid";
+
+// Fee Router
+pub const ERR_INVALID_DESTINATION_ADDRESS: &str = "E3801: Invalid destination address";
+pub const ERR_DESTINATION_MUST_BE_A_SMART_CONTRACT: &str =
+    "E3802: Destination must be a smart contract";
+pub const ERR_AT_LEAST_ONE_WEIGHT_MUST_BE_SET: &str = "E3803: At least one weight must be set";
+pub const ERR_DESTINATION_ADDRESS_NOT_SET: &str = "E3804: Destination address not set";
+pub const ERR_FEES_MUST_BE_FUNGIBLE: &str = "E3805: Fees must be fungible";
+pub const ERR_NO_UNACCOUNTED_FEES: &str = "E3806: No unaccounted fees";
+pub const ERR_NO_PENDING_FEES: &str = "E3807: No pending fees";
+pub const ERR_FEE_WEIGHTS_NOT_SET: &str = "E3808: Fee weights not set";
+pub const ERR_TOO_MANY_FEE_TOKENS: &str = "E3809: Too many fee tokens";
+pub const ERR_INVALID_TREASURY_ADDRESS: &str = "E3810: Invalid treasury address";
+
+// Futures
+pub const ERR_SALE_END_MUST_BE_IN_THE_FUTURE: &str = "E3901: Sale end must be in the future";
+pub const ERR_DELIVERY_DEADLINE_MUST_NOT_PRECEDE_THE_SALE_END: &str =
+    "E3902: Delivery deadline must not precede the sale end";
+pub const ERR_FUTURE_IS_NOT_OPEN: &str = "E3903: Future is not open";
+pub const ERR_SALE_HAS_ENDED: &str = "E3904: Sale has ended";
+pub const ERR_CLAIM_COLLECTION_NOT_ISSUED: &str = "E3905: Claim collection not issued";
+pub const ERR_ONLY_PRODUCER_CAN_CANCEL_THE_FUTURE: &str =
+    "E3906: Only producer can cancel the future";
+pub const ERR_FUTURE_DOES_NOT_EXIST: &str = "E3907: Future does not exist";
+pub const ERR_ONLY_PRODUCER_CAN_DELIVER_THE_WINE: &str =
+    "E3908: Only producer can deliver the wine";
+pub const ERR_DELIVERY_DEADLINE_HAS_PASSED: &str = "E3909: Delivery deadline has passed";
+pub const ERR_DELIVERED_WINE_DOES_NOT_MATCH: &str = "E3910: Delivered wine does not match";
+pub const ERR_DELIVERY_EXCEEDS_THE_BOTTLES_SOLD: &str = "E3911: Delivery exceeds the bottles sold";
+pub const ERR_WINE_HAS_NOT_BEEN_DELIVERED: &str = "E3912: Wine has not been delivered";
+pub const ERR_DELIVERY_HAS_NOT_FAILED: &str = "E3913: Delivery has not failed";
+pub const ERR_ONLY_PRODUCER_CAN_RECLAIM_THE_DELIVERY: &str =
+    "E3914: Only producer can reclaim the delivery";
+pub const ERR_NOTHING_WAS_DELIVERED: &str = "E3915: Nothing was delivered";
+pub const ERR_NO_CLAIMS_WERE_SOLD: &str = "E3916: No claims were sold";
+pub const ERR_INVALID_CLAIM_TOKEN: &str = "E3917: Invalid claim token";
+pub const ERR_NO_CLAIMS_PAID: &str = "E3918: No claims paid";
+
+// Governance
+pub const ERR_INVALID_GOVERNANCE_TOKEN: &str = "E4001: Invalid governance token";
+pub const ERR_TARGET_IS_NOT_GOVERNED: &str = "E4002: Target is not governed";
+pub const ERR_ENDPOINT_IS_REQUIRED: &str = "E4003: Endpoint is required";
+pub const ERR_INVALID_GAS_LIMIT: &str = "E4004: Invalid gas limit";
+pub const ERR_DESCRIPTION_TOO_LONG: &str = "E4005: Description too long";
+pub const ERR_TOO_MANY_ARGUMENTS: &str = "E4006: Too many arguments";
+pub const ERR_NOT_ENOUGH_VOTING_POWER_TO_PROPOSE: &str =
+    "E4007: Not enough voting power to propose";
+pub const ERR_PROPOSAL_IS_NOT_ACTIVE: &str = "E4008: Proposal is not active";
+pub const ERR_NO_VOTING_POWER_AT_THE_SNAPSHOT: &str = "E4009: No voting power at the snapshot";
+pub const ERR_PROPOSAL_HAS_NOT_SUCCEEDED: &str = "E4010: Proposal has not succeeded";
+pub const ERR_ONLY_PROPOSER_CAN_CANCEL: &str = "E4011: Only proposer can cancel";
+pub const ERR_CONTRACT_IS_NOT_GOVERNED: &str = "E4012: Contract is not governed";
+pub const ERR_ONLY_CALLABLE_THROUGH_A_PROPOSAL: &str = "E4013: Only callable through a proposal";
+pub const ERR_QUORUM_CANNOT_EXCEED_100_PERCENT: &str = "E4014: Quorum cannot exceed 100%";
+pub const ERR_GOVERNED_CONTRACT_MUST_BE_A_SMART_CONTRACT: &str =
+    "E4015: Governed contract must be a smart contract";
+pub const ERR_PROPOSAL_DOES_NOT_EXIST: &str = "E4016: Proposal does not exist";
+pub const ERR_NOT_ENOUGH_DEPOSITED: &str = "E4017: Not enough deposited";
+
+// Index Fund
+pub const ERR_INVALID_COLLECTION: &str = "E4101: Invalid collection";
+pub const ERR_ALREADY_A_BASKET_COLLECTION: &str = "E4102: Already a basket collection";
+pub const ERR_TOO_MANY_BASKET_COLLECTIONS: &str = "E4103: Too many basket collections";
+pub const ERR_NOT_A_BASKET_COLLECTION: &str = "E4104: Not a basket collection";
+pub const ERR_DEPOSIT_HAS_NO_VALUE: &str = "E4105: Deposit has no value";
+pub const ERR_FUND_HAS_NO_VALUE: &str = "E4106: Fund has no value";
+pub const ERR_DEPOSIT_TOO_SMALL: &str = "E4107: Deposit too small";
+pub const ERR_NOT_ENOUGH_WINE_IN_BASKET: &str = "E4108: Not enough wine in basket";
+pub const ERR_NOTHING_TO_REDEEM: &str = "E4109: Nothing to redeem";
+pub const ERR_NOT_ENOUGH_PROCEEDS: &str = "E4110: Not enough proceeds";
+pub const ERR_WINE_NOT_IN_BASKET: &str = "E4111: Wine not in basket";
+pub const ERR_WINE_IS_NOT_FOR_SALE: &str = "E4112: Wine is not for sale";
+pub const ERR_INVALID_PAYMENT_AMOUNT: &str = "E4113: Invalid payment amount";
+pub const ERR_BASKET_IS_FULL: &str = "E4114: Basket is full";
+pub const ERR_NO_SHARES_ISSUED: &str = "E4115: No shares issued";
+pub const ERR_SHARE_TOKEN_ALREADY_ISSUED: &str = "E4116: Share token already issued";
+pub const ERR_ORACLE_MUST_BE_A_SMART_CONTRACT: &str = "E4117: Oracle must be a smart contract";
+pub const ERR_ONLY_ADMIN_OR_PRICE_ORACLE_CAN_SET_PRICES: &str =
+    "E4118: Only admin or price oracle can set prices";
+pub const ERR_MANAGEMENT_FEE_TOO_HIGH: &str = "E4119: Management fee too high";
+pub const ERR_NO_FLOOR_PRICE_FOR_COLLECTION: &str = "E4120: No floor price for collection";
+pub const ERR_FLOOR_PRICE_IS_STALE: &str = "E4121: Floor price is stale";
+pub const ERR_INVALID_QUOTE_TOKEN: &str = "E4122: Invalid quote token";
+
+// Insurance
+pub const ERR_INVALID_POOL_TOKEN: &str = "E4201: Invalid pool token";
+pub const ERR_ALREADY_A_CLAIM_CERTIFIER: &str = "E4202: Already a claim certifier";
+pub const ERR_NOT_A_CLAIM_CERTIFIER: &str = "E4203: Not a claim certifier";
+pub const ERR_COVERAGE_MUST_BE_GREATER_THAN_0: &str = "E4204: Coverage must be greater than 0";
+pub const ERR_INVALID_DURATION: &str = "E4205: Invalid duration";
+pub const ERR_NOT_ENOUGH_CAPITAL_IN_THE_POOL: &str = "E4206: Not enough capital in the pool";
+pub const ERR_PREMIUM_NOT_COVERED: &str = "E4207: Premium not covered";
+pub const ERR_ONLY_HOLDER_CAN_FILE_A_CLAIM: &str = "E4208: Only holder can file a claim";
+pub const ERR_POLICY_IS_NOT_ACTIVE: &str = "E4209: Policy is not active";
+pub const ERR_POLICY_HAS_EXPIRED: &str = "E4210: Policy has expired";
+pub const ERR_NO_PENDING_CLAIM: &str = "E4211: No pending claim";
+pub const ERR_POLICY_HAS_NOT_EXPIRED: &str = "E4212: Policy has not expired";
+pub const ERR_PREMIUM_RATE_MUST_BE_GREATER_THAN_0: &str =
+    "E4213: Premium rate must be greater than 0";
+pub const ERR_MAXIMUM_DURATION_MUST_BE_GREATER_THAN_0: &str =
+    "E4214: Maximum duration must be greater than 0";
+pub const ERR_POLICY_DOES_NOT_EXIST: &str = "E4215: Policy does not exist";
+pub const ERR_POOL_IS_INSOLVENT: &str = "E4216: Pool is insolvent";
+pub const ERR_STAKE_TOO_SMALL: &str = "E4217: Stake too small";
+pub const ERR_SHARES_MUST_BE_GREATER_THAN_0: &str = "E4218: Shares must be greater than 0";
+pub const ERR_CAPITAL_IS_BACKING_ACTIVE_COVERAGE: &str =
+    "E4219: Capital is backing active coverage";
+
+// Marketplace Factory
+pub const ERR_TEMPLATE_MUST_BE_A_SMART_CONTRACT: &str = "E4301: Template must be a smart contract";
+pub const ERR_INVALID_MAIN_TREASURY: &str = "E4302: Invalid main treasury";
+pub const ERR_INVALID_PRODUCER: &str = "E4303: Invalid producer";
+pub const ERR_PRODUCER_ALREADY_HAS_A_STOREFRONT: &str = "E4304: Producer already has a storefront";
+pub const ERR_NOT_A_STOREFRONT: &str = "E4305: Not a storefront";
+
+// Oracle
+pub const ERR_ALREADY_A_PRICE_FEEDER: &str = "E4401: Already a price feeder";
+pub const ERR_TOO_MANY_PRICE_FEEDERS: &str = "E4402: Too many price feeders";
+pub const ERR_NOT_A_PRICE_FEEDER: &str = "E4403: Not a price feeder";
+pub const ERR_TOO_MANY_PRICE_SUBSCRIBERS: &str = "E4404: Too many price subscribers";
+pub const ERR_INVALID_MINIMUM_SUBMISSIONS: &str = "E4405: Invalid minimum submissions";
+pub const ERR_RANK_OUT_OF_RANGE: &str = "E4406: Rank out of range";
+pub const ERR_NO_PRICE_FOR_PAIR: &str = "E4407: No price for pair";
+pub const ERR_PRICE_IS_STALE: &str = "E4408: Price is stale";
+
+// Otc
+pub const ERR_CANNOT_DEAL_WITH_YOURSELF: &str = "E4501: Cannot deal with yourself";
+pub const ERR_INVALID_COUNTERPARTY: &str = "E4502: Invalid counterparty";
+pub const ERR_PAYMENT_AMOUNT_MUST_BE_GREATER_THAN_0: &str =
+    "E4503: Payment amount must be greater than 0";
+pub const ERR_DEAL_MUST_INCLUDE_TOKENS: &str = "E4504: Deal must include tokens";
+pub const ERR_TOO_MANY_TOKENS_IN_ONE_DEAL: &str = "E4505: Too many tokens in one deal";
+pub const ERR_ONLY_THE_COUNTERPARTY_CAN_FUND_THE_PAYMENT: &str =
+    "E4506: Only the counterparty can fund the payment";
+pub const ERR_PAYMENT_ALREADY_FUNDED: &str = "E4507: Payment already funded";
+pub const ERR_ONLY_A_PARTY_TO_THE_DEAL_CAN_CANCEL_IT: &str =
+    "E4508: Only a party to the deal can cancel it";
+pub const ERR_BOTH_LEGS_ARE_FUNDED_THE_DEAL_CAN_ONLY_BE_SETTLED: &str =
+    "E4509: Both legs are funded; the deal can only be settled";
+pub const ERR_ONLY_A_PARTY_TO_THE_DEAL_CAN_SETTLE_IT: &str =
+    "E4510: Only a party to the deal can settle it";
+pub const ERR_DEAL_IS_NOT_FULLY_FUNDED: &str = "E4511: Deal is not fully funded";
+pub const ERR_DEAL_DOES_NOT_EXIST: &str = "E4512: Deal does not exist";
+pub const ERR_DEAL_IS_NOT_OPEN: &str = "E4513: Deal is not open";
+
+// Raffle
+pub const ERR_PAYMENT_TOKEN_ALREADY_SUPPORTED: &str = "E4601: Payment token already supported";
+pub const ERR_TICKET_PRICE_MUST_BE_GREATER_THAN_0: &str =
+    "E4602: Ticket price must be greater than 0";
+pub const ERR_INVALID_TICKET_CAP: &str = "E4603: Invalid ticket cap";
+pub const ERR_INVALID_RAFFLE_DURATION: &str = "E4604: Invalid raffle duration";
+pub const ERR_RAFFLE_IS_NOT_OPEN: &str = "E4605: Raffle is not open";
+pub const ERR_RAFFLE_HAS_ENDED: &str = "E4606: Raffle has ended";
+pub const ERR_COUNT_MUST_BE_GREATER_THAN_0: &str = "E4607: Count must be greater than 0";
+pub const ERR_CANNOT_ENTER_YOUR_OWN_RAFFLE: &str = "E4608: Cannot enter your own raffle";
+pub const ERR_PAYMENT_MUST_MATCH_THE_TICKET_PRICE: &str =
+    "E4609: Payment must match the ticket price";
+pub const ERR_ONLY_SELLER_CAN_CANCEL: &str = "E4610: Only seller can cancel";
+pub const ERR_TICKETS_HAVE_BEEN_SOLD: &str = "E4611: Tickets have been sold";
+pub const ERR_RAFFLE_HAS_NOT_ENDED_YET: &str = "E4612: Raffle has not ended yet";
+pub const ERR_RAFFLE_WAS_NOT_CANCELLED: &str = "E4613: Raffle was not cancelled";
+pub const ERR_NO_TICKETS_TO_REFUND: &str = "E4614: No tickets to refund";
+pub const ERR_RAFFLE_DOES_NOT_EXIST: &str = "E4615: Raffle does not exist";
+
+// Redemption
+pub const ERR_WINE_HAS_NO_PRODUCER: &str = "E4701: Wine has no producer";
+pub const ERR_INVALID_BOND_TOKEN: &str = "E4702: Invalid bond token";
+pub const ERR_NOT_ENOUGH_FREE_BOND: &str = "E4703: Not enough free bond";
+pub const ERR_SHIPPING_TIMEOUT_MUST_BE_GREATER_THAN_0: &str =
+    "E4704: Shipping timeout must be greater than 0";
+pub const ERR_CONFIRMATION_TIMEOUT_MUST_BE_GREATER_THAN_0: &str =
+    "E4705: Confirmation timeout must be greater than 0";
+pub const ERR_PRODUCER_BOND_TOO_LOW: &str = "E4706: Producer bond too low";
+pub const ERR_WINE_IS_NOT_REDEEMABLE: &str = "E4707: Wine is not redeemable";
+pub const ERR_ONLY_HOLDER_CAN_CANCEL: &str = "E4708: Only holder can cancel";
+pub const ERR_ORDER_IS_NOT_PENDING_SHIPMENT: &str = "E4709: Order is not pending shipment";
+pub const ERR_ONLY_PRODUCER_CAN_CONFIRM_SHIPMENT: &str =
+    "E4710: Only producer can confirm shipment";
+pub const ERR_SHIPPING_TIMEOUT_HAS_PASSED: &str = "E4711: Shipping timeout has passed";
+pub const ERR_ONLY_HOLDER_CAN_CONFIRM_RECEIPT: &str = "E4712: Only holder can confirm receipt";
+pub const ERR_ORDER_IS_NOT_SHIPPED: &str = "E4713: Order is not shipped";
+pub const ERR_ONLY_HOLDER_CAN_CLAIM: &str = "E4714: Only holder can claim";
+pub const ERR_SHIPPING_TIMEOUT_HAS_NOT_PASSED: &str = "E4715: Shipping timeout has not passed";
+pub const ERR_ONLY_PRODUCER_CAN_CLAIM: &str = "E4716: Only producer can claim";
+pub const ERR_CONFIRMATION_TIMEOUT_HAS_NOT_PASSED: &str =
+    "E4717: Confirmation timeout has not passed";
+pub const ERR_ONLY_HOLDER_CAN_DISPUTE: &str = "E4718: Only holder can dispute";
+pub const ERR_CONFIRMATION_TIMEOUT_HAS_PASSED: &str = "E4719: Confirmation timeout has passed";
+pub const ERR_ORDER_IS_NOT_DISPUTED: &str = "E4720: Order is not disputed";
+pub const ERR_ORDER_DOES_NOT_EXIST: &str = "E4721: Order does not exist";
+
+// Shares
+pub const ERR_VAULT_IS_NOT_ACTIVE: &str = "E4801: Vault is not active";
+pub const ERR_BUYOUT_AMOUNT_MUST_BE_GREATER_THAN_0: &str =
+    "E4803: Buyout amount must be greater than 0";
+pub const ERR_NO_OPEN_BUYOUT: &str = "E4804: No open buyout";
+pub const ERR_VOTING_PERIOD_HAS_ENDED: &str = "E4805: Voting period has ended";
+pub const ERR_NO_SHARES_TO_VOTE_WITH: &str = "E4806: No shares to vote with";
+pub const ERR_VAULT_BOUGHT_OUT_CLAIM_THE_PROCEEDS: &str =
+    "E4807: Vault bought out, claim the proceeds";
+pub const ERR_NO_LOCKED_SHARES: &str = "E4808: No locked shares";
+pub const ERR_ONLY_BIDDER_CAN_WITHDRAW: &str = "E4809: Only bidder can withdraw";
+pub const ERR_VOTING_PERIOD_HAS_NOT_ENDED: &str = "E4810: Voting period has not ended";
+pub const ERR_VAULT_HAS_NOT_BEEN_BOUGHT_OUT: &str = "E4811: Vault has not been bought out";
+pub const ERR_NO_SHARES_TO_CLAIM_WITH: &str = "E4812: No shares to claim with";
+pub const ERR_THE_WHOLE_SHARE_SUPPLY_IS_REQUIRED: &str =
+    "E4814: The whole share supply is required";
+pub const ERR_SHARE_SUPPLY_MUST_BE_GREATER_THAN_0: &str =
+    "E4815: Share supply must be greater than 0";
+pub const ERR_RESERVE_PRICE_MUST_BE_GREATER_THAN_0: &str =
+    "E4816: Reserve price must be greater than 0";
+pub const ERR_ONLY_CURATOR_CAN_DEPOSIT_THE_WINE: &str = "E4817: Only curator can deposit the wine";
+pub const ERR_VAULT_IS_NOT_PENDING: &str = "E4818: Vault is not pending";
+pub const ERR_VAULT_DOES_NOT_EXIST: &str = "E4819: Vault does not exist";
+pub const ERR_OFFER_MUST_EXCEED_THE_OPEN_BUYOUT: &str = "E4820: Offer must exceed the open buyout";
+
+// Staking
+pub const ERR_INVALID_REWARD_TOKEN: &str = "E4901: Invalid reward token";
+pub const ERR_ALREADY_A_REWARD_FEEDER: &str = "E4902: Already a reward feeder";
+pub const ERR_NOT_A_REWARD_FEEDER: &str = "E4903: Not a reward feeder";
+pub const ERR_NOT_AUTHORIZED_TO_FEED_REWARDS: &str = "E4904: Not authorized to feed rewards";
+pub const ERR_NO_REWARDS_TO_CLAIM: &str = "E4905: No rewards to claim";
+pub const ERR_TOO_MANY_UNBONDING_ENTRIES: &str = "E4906: Too many unbonding entries";
+
+// Tastings
+pub const ERR_INVALID_MARKETPLACE_ADDRESS: &str = "E5001: Invalid marketplace address";
+pub const ERR_DISCOUNT_TOO_HIGH: &str = "E5002: Discount too high";
+pub const ERR_VALIDITY_MUST_BE_GREATER_THAN_0: &str = "E5003: Validity must be greater than 0";
+pub const ERR_MARKETPLACE_NOT_SET: &str = "E5004: Marketplace not set";
+pub const ERR_EVENT_DOES_NOT_USE_TICKET_BURN: &str = "E5005: Event does not use ticket burn";
+pub const ERR_EVENT_DOES_NOT_USE_TICKET_FLAGS: &str = "E5006: Event does not use ticket flags";
+pub const ERR_TICKET_ALREADY_CHECKED_IN: &str = "E5007: Ticket already checked in";
+pub const ERR_INVALID_TICKET: &str = "E5008: Invalid ticket";
+pub const ERR_CHECK_IN_IS_NOT_OPEN: &str = "E5009: Check-in is not open";
+pub const ERR_TICKET_DOES_NOT_EXIST: &str = "E5010: Ticket does not exist";
+pub const ERR_ALREADY_AN_EVENT_ORGANIZER: &str = "E5011: Already an event organizer";
+pub const ERR_NOT_AN_EVENT_ORGANIZER: &str = "E5012: Not an event organizer";
+pub const ERR_EVENT_NAME_CANNOT_BE_EMPTY: &str = "E5013: Event name cannot be empty";
+pub const ERR_EVENT_MUST_BE_IN_THE_FUTURE: &str = "E5014: Event must be in the future";
+pub const ERR_MAX_TICKETS_MUST_BE_GREATER_THAN_0: &str =
+    "E5015: Max tickets must be greater than 0";
+pub const ERR_PER_WALLET_CAP_MUST_BE_GREATER_THAN_0: &str =
+    "E5016: Per-wallet cap must be greater than 0";
+pub const ERR_TICKET_SALES_HAVE_ENDED: &str = "E5017: Ticket sales have ended";
+pub const ERR_INVALID_TICKET_QUANTITY: &str = "E5018: Invalid ticket quantity";
+pub const ERR_PER_WALLET_TICKET_CAP_REACHED: &str = "E5019: Per-wallet ticket cap reached";
+pub const ERR_TICKET_COLLECTION_NOT_ISSUED: &str = "E5020: Ticket collection not issued";
+pub const ERR_TICKETS_ARE_NOT_SOLD_IN_THIS_TOKEN: &str =
+    "E5021: Tickets are not sold in this token";
+pub const ERR_EVENT_DOES_NOT_EXIST: &str = "E5022: Event does not exist";
+pub const ERR_ONLY_THE_ORGANIZER_CAN_MANAGE_THE_EVENT: &str =
+    "E5023: Only the organizer can manage the event";
+
+// Vesting
+pub const ERR_INVALID_VESTING_TOKEN: &str = "E5101: Invalid vesting token";
+pub const ERR_INVALID_BENEFICIARY: &str = "E5102: Invalid beneficiary";
+pub const ERR_CLIFF_CANNOT_EXCEED_THE_DURATION: &str = "E5103: Cliff cannot exceed the duration";
+pub const ERR_TOO_MANY_SCHEDULES_FOR_BENEFICIARY: &str =
+    "E5104: Too many schedules for beneficiary";
+pub const ERR_NOTHING_TO_CLAIM: &str = "E5105: Nothing to claim";
+pub const ERR_SCHEDULE_IS_NOT_REVOCABLE: &str = "E5106: Schedule is not revocable";
+pub const ERR_SCHEDULE_ALREADY_REVOKED: &str = "E5107: Schedule already revoked";
+pub const ERR_SCHEDULE_DOES_NOT_EXIST: &str = "E5108: Schedule does not exist";
@@ -0,0 +1,52 @@
+use multiversx_sc::api::ManagedTypeApi;
+use multiversx_sc::types::ManagedBuffer;
+
+// Longest CID accepted; also bounds the stack copy made while validating
+pub const MAX_CID_LENGTH: usize = 128;
+// CIDv0: base58btc of a 34-byte sha2-256 multihash, which always starts with "Qm"
+pub const CID_V0_LENGTH: usize = 46;
+// Multibase prefix plus version and codec, plus the shortest multihash (code, length, 1 byte)
+const MIN_CID_V1_LENGTH: usize = 8;
+
+const BASE58_ALPHABET: &[u8] = b"123456789ABCDEFGHJKLMNPQRSTUVWXYZabcdefghijkmnopqrstuvwxyz";
+
+// Structural check of a bare IPFS CID, as stored in metadata hash fields: a CIDv0 ("Qm...")
+// or a CIDv1 in base32 ("b..."/"B...") or base58btc ("z..."). The content behind it is not
+// checked, and "ipfs://" URIs or paths are rejected; the registry adds the scheme itself.
+pub fn is_valid_ipfs_cid<M: ManagedTypeApi>(cid: &ManagedBuffer<M>) -> bool {
+    let len = cid.len();
+    if len > MAX_CID_LENGTH {
+        return false;
+    }
+    let mut buffer = [0u8; MAX_CID_LENGTH];
+    let bytes = cid.load_to_byte_array(&mut buffer);
+
+    if len == CID_V0_LENGTH && bytes.starts_with(b"Qm") {
+        return bytes.iter().all(is_base58_char);
+    }
+    if len < MIN_CID_V1_LENGTH {
+        return false;
+    }
+
+    let payload = &bytes[1..];
+    match bytes[0] {
+        b'b' => is_cid_v1_base32(payload, b'a', u8::is_ascii_lowercase),
+        b'B' => is_cid_v1_base32(payload, b'A', u8::is_ascii_uppercase),
+        b'z' => payload.iter().all(is_base58_char),
+        _ => false,
+    }
+}
+
+fn is_base58_char(byte: &u8) -> bool {
+    BASE58_ALPHABET.contains(byte)
+}
+
+// Unpadded RFC 4648 base32 in one letter case. The first character encodes the top bits of
+// the version byte 0x01, so it is always 'a'.
+fn is_cid_v1_base32(payload: &[u8], version_char: u8, is_letter: fn(&u8) -> bool) -> bool {
+    // Remainders of 1, 3 and 6 characters do not occur in unpadded base32
+    if matches!(payload.len() % 8, 1 | 3 | 6) || payload[0] != version_char {
+        return false;
+    }
+    payload.iter().all(|byte| is_letter(byte) || (b'2'..=b'7').contains(byte))
+}
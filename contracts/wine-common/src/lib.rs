@@ -0,0 +1,22 @@
+#![no_std]
+
+// Types that cross contract boundaries live here, so every contract encodes them the same
+// way. Changing a field changes the encoding for all of them, and stored records need a
+// migration in each contract that keeps them.
+
+pub mod errors;
+pub mod ipfs;
+pub mod marketplace;
+pub mod registry;
+
+pub use marketplace::{Auction, Listing};
+pub use registry::{WineDetails, WineNftAttributes, WineRating, WineReadModel, WineStatus};
+
+// Budget for the return data of a single list view. The gateway base64-encodes return data,
+// so this leaves room under its response limit; page size caps are derived from the
+// worst-case encoded size of one item so that a full page always fits.
+pub const MAX_VIEW_RETURN_BYTES: usize = 32 * 1024;
+// Relative humidity cannot exceed 100.00%
+pub const MAX_HUMIDITY_X100: u32 = 10_000;
+// Largest discount a fee discount issuer can grant, in basis points of the marketplace fee
+pub const MAX_FEE_DISCOUNT_BPS: u64 = 5_000;
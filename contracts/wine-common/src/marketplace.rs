@@ -0,0 +1,33 @@
+use multiversx_sc::derive_imports::*;
+use multiversx_sc::imports::*;
+
+#[derive(TopEncode, TopDecode, TypeAbi, PartialEq, Debug, Clone)]
+pub struct Listing<M: ManagedTypeApi> {
+    pub wine_nft_id: u32,
+    pub nft_token_id: TokenIdentifier<M>,
+    pub nft_nonce: u64,
+    pub seller: ManagedAddress<M>,
+    pub price: BigUint<M>,
+    pub payment_token: EgldOrEsdtTokenIdentifier<M>,
+    pub deadline: u64,
+    pub active: bool,
+    pub created_timestamp: u64,
+    // Protected sale: payment and NFT stay in escrow for the dispute window after purchase
+    pub protected: bool,
+}
+
+#[derive(TopEncode, TopDecode, TypeAbi, PartialEq, Debug)]
+pub struct Auction<M: ManagedTypeApi> {
+    pub wine_nft_id: u32,
+    pub nft_token_id: TokenIdentifier<M>,
+    pub nft_nonce: u64,
+    pub seller: ManagedAddress<M>,
+    pub starting_price: BigUint<M>,
+    pub current_bid: BigUint<M>,
+    pub highest_bidder: ManagedAddress<M>,
+    pub payment_token: EgldOrEsdtTokenIdentifier<M>,
+    pub end_timestamp: u64,
+    pub active: bool,
+    pub min_bid_increment: BigUint<M>,
+    pub bid_count: u32,
+}
@@ -0,0 +1,79 @@
+use multiversx_sc::derive_imports::*;
+use multiversx_sc::imports::*;
+
+// Enhanced Wine Details with additional fields
+#[derive(TopEncode, TopDecode, TypeAbi, PartialEq, Debug)]
+pub struct WineDetails<M: ManagedTypeApi> {
+    pub vineyard: ManagedBuffer<M>,
+    pub variety: ManagedBuffer<M>,
+    pub vintage_year: u32,
+    pub production_date: u64,
+    pub quality_grade: u8, // 1-10 scale
+    pub alcohol_content: u16, // x100 for precision (e.g., 1250 = 12.50%)
+    pub region: ManagedBuffer<M>,
+    pub certification: ManagedBuffer<M>,
+    pub producer_signature: ManagedBuffer<M>,
+    pub ipfs_hash: ManagedBuffer<M>, // IPFS hash for additional metadata
+    pub total_bottles: u32,
+    pub available_bottles: u32,
+    pub price_per_bottle: BigUint<M>,
+    pub is_organic: bool,
+    pub harvest_date: u64,
+    pub aging_process: ManagedBuffer<M>,
+    pub tasting_notes: ManagedBuffer<M>,
+    pub producer: ManagedAddress<M>,
+}
+
+#[derive(TopEncode, TopDecode, NestedEncode, NestedDecode, ManagedVecItem, TypeAbi, PartialEq, Debug)]
+pub struct WineRating<M: ManagedTypeApi> {
+    pub rater: ManagedAddress<M>,
+    pub rating: u8, // 1-10
+    pub review: ManagedBuffer<M>,
+    pub timestamp: u64,
+}
+
+// On-chain attributes of a wine SFT; the full metadata lives behind the IPFS URI. Contracts
+// holding wines decode these to tell which wine they were sent.
+#[derive(TopEncode, TopDecode, NestedEncode, NestedDecode, TypeAbi, PartialEq, Debug)]
+pub struct WineNftAttributes<M: ManagedTypeApi> {
+    pub wine_id: u32,
+    pub vineyard: ManagedBuffer<M>,
+    pub variety: ManagedBuffer<M>,
+    pub vintage_year: u32,
+    pub region: ManagedBuffer<M>,
+    pub quality_grade: u8,
+    pub ipfs_hash: ManagedBuffer<M>,
+}
+
+// Production stages in order, followed by the terminal states. Retired is only reached
+// through retireWine.
+#[derive(
+    TopEncode, TopDecode, NestedEncode, NestedDecode, TypeAbi,
+    PartialEq, Eq, PartialOrd, Ord, Debug, Clone, Copy,
+)]
+pub enum WineStatus {
+    Harvested,
+    Fermenting,
+    Aging,
+    Bottled,
+    Released,
+    Recalled,
+    Consumed,
+    Retired,
+}
+
+// Compact view of a wine that marketplaces keep locally, so their buy paths can check it
+// in the same transaction instead of reading the registry cross-shard
+#[derive(TopEncode, TopDecode, NestedEncode, NestedDecode, TypeAbi, PartialEq, Debug, Clone)]
+pub struct WineReadModel<M: ManagedTypeApi> {
+    pub price_per_bottle: BigUint<M>,
+    pub quality_grade: u8,
+    pub certified: bool,
+    pub royalty_bps: u32,
+    pub status: WineStatus,
+    pub producer: ManagedAddress<M>,
+    // Set once a counterfeit report is confirmed
+    pub counterfeit: bool,
+    // Latest expiry of the valid certifications; 0 while one of them does not expire
+    pub certified_until: u64,
+}
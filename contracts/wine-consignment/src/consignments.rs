@@ -0,0 +1,334 @@
+use multiversx_sc::derive_imports::*;
+use multiversx_sc::imports::*;
+use wine_roles::Role;
+pub use wine_common::WineNftAttributes;
+use wine_common::errors::*;
+
+pub const MARKETPLACE_CALL_GAS: u64 = 20_000_000;
+
+#[derive(TopEncode, TopDecode, NestedEncode, NestedDecode, TypeAbi, PartialEq, Debug, Clone, Copy)]
+pub enum ConsignmentStatus {
+    Open,
+    Closed,
+}
+
+#[derive(TopEncode, TopDecode, TypeAbi, PartialEq, Debug)]
+pub struct Consignment<M: ManagedTypeApi> {
+    pub producer: ManagedAddress<M>,
+    pub merchant: ManagedAddress<M>,
+    pub wine_id: u32,
+    pub wine_nonce: u64,
+    pub bottles: u32,
+    pub sold: u32,
+    // Terms agreed with the merchant
+    pub payment_token: EgldOrEsdtTokenIdentifier<M>,
+    pub min_price: BigUint<M>,
+    pub commission_bps: u64,
+    pub end_timestamp: u64,
+    // Proceeds paid out so far
+    pub producer_proceeds: BigUint<M>,
+    pub merchant_commission: BigUint<M>,
+    pub status: ConsignmentStatus,
+}
+
+// Producers hand bottles to a merchant, who lists them on the marketplace one by one with
+// this contract as the seller. The marketplace pays each sale in here, and the proceeds are
+// split right away between merchant and producer. There is at most one open consignment
+// per wine nonce, which is how proceeds are matched to consignments.
+//
+// Cancelled and expired listings return their NFT with a plain transfer, so the contract
+// must be deployed payable, and added as a consignment contract on the marketplace.
+#[multiversx_sc::module]
+pub trait ConsignmentsModule: wine_roles::RolesModule {
+    #[endpoint(setMarketplace)]
+    fn set_marketplace(&self, marketplace: ManagedAddress) {
+        self.require_role(Role::Admin);
+        require!(
+            self.blockchain().is_smart_contract(&marketplace),
+            ERR_MARKETPLACE_MUST_BE_A_SMART_CONTRACT
+        );
+        self.marketplace_address().set(&marketplace);
+    }
+
+    // Consign the sent bottles to `merchant`, who may list them at `min_price` or more in
+    // `payment_token` for `duration_seconds` and keeps `commission_bps` of every sale
+    #[payable("*")]
+    #[endpoint(consignWine)]
+    fn consign_wine(
+        &self,
+        merchant: ManagedAddress,
+        payment_token: EgldOrEsdtTokenIdentifier,
+        min_price: BigUint,
+        commission_bps: u64,
+        duration_seconds: u64,
+    ) -> u32 {
+        self.require_not_paused();
+        let payment = self.call_value().single_esdt();
+        require!(payment.token_identifier == self.wine_collection().get(), ERR_INVALID_WINE_TOKEN);
+        let bottles = payment.amount.to_u64().unwrap_or(u64::MAX);
+        require!(bottles > 0 && bottles <= u32::MAX as u64, ERR_INVALID_BOTTLE_AMOUNT);
+        require!(
+            self.open_consignment(payment.token_nonce).is_empty(),
+            ERR_WINE_IS_ALREADY_ON_CONSIGNMENT
+        );
+        require!(payment_token.is_valid(), ERR_INVALID_PAYMENT_TOKEN);
+        require!(min_price > 0, ERR_MINIMUM_PRICE_MUST_BE_GREATER_THAN_0);
+        require!(commission_bps <= 10_000, ERR_COMMISSION_CANNOT_EXCEED_100_PERCENT);
+        require!(duration_seconds > 0, ERR_DURATION_MUST_BE_GREATER_THAN_0);
+
+        let producer = self.blockchain().get_caller();
+        require!(merchant != producer, ERR_MERCHANT_MUST_DIFFER_FROM_PRODUCER);
+        let token_data = self.blockchain().get_esdt_token_data(
+            &self.blockchain().get_sc_address(),
+            &payment.token_identifier,
+            payment.token_nonce,
+        );
+        let attributes: WineNftAttributes<Self::Api> = token_data.decode_attributes();
+
+        let consignment_id = self.consignment_count().get() + 1;
+        self.consignment_count().set(consignment_id);
+        self.consignments(consignment_id).set(&Consignment {
+            producer: producer.clone(),
+            merchant: merchant.clone(),
+            wine_id: attributes.wine_id,
+            wine_nonce: payment.token_nonce,
+            bottles: bottles as u32,
+            sold: 0,
+            payment_token,
+            min_price,
+            commission_bps,
+            end_timestamp: self.blockchain().get_block_timestamp() + duration_seconds,
+            producer_proceeds: BigUint::zero(),
+            merchant_commission: BigUint::zero(),
+            status: ConsignmentStatus::Open,
+        });
+        self.open_consignment(payment.token_nonce).set(consignment_id);
+
+        self.wine_consigned_event(consignment_id, &producer, &merchant, bottles as u32);
+
+        consignment_id
+    }
+
+    // List one bottle on the marketplace. The listing must end within the consignment.
+    #[endpoint(listConsignedWine)]
+    fn list_consigned_wine(&self, consignment_id: u32, price: BigUint, duration_seconds: u64) {
+        self.require_not_paused();
+        let consignment = self.require_open_consignment(consignment_id);
+        require!(
+            self.blockchain().get_caller() == consignment.merchant,
+            ERR_ONLY_MERCHANT_CAN_LIST
+        );
+        require!(price >= consignment.min_price, ERR_PRICE_BELOW_THE_CONSIGNMENT_MINIMUM);
+        require!(
+            self.blockchain().get_block_timestamp() + duration_seconds <= consignment.end_timestamp,
+            ERR_LISTING_WOULD_OUTLAST_THE_CONSIGNMENT
+        );
+        require!(self.escrowed_bottles(&consignment) > 0, ERR_NO_BOTTLES_LEFT_TO_LIST);
+
+        self.tx()
+            .to(&self.marketplace_address().get())
+            .gas(MARKETPLACE_CALL_GAS)
+            .single_esdt(&self.wine_collection().get(), consignment.wine_nonce, &BigUint::from(1u32))
+            .raw_call("createListing")
+            .argument(&consignment.wine_id)
+            .argument(&price)
+            .argument(&consignment.payment_token)
+            .argument(&duration_seconds)
+            .transfer_execute();
+
+        self.consigned_wine_listed_event(consignment_id, &price);
+    }
+
+    // Withdraw a listing of the consignment's wine, returning the bottle into escrow. The
+    // merchant can delist at any time, the producer once the consignment has ended.
+    #[endpoint(delistConsignedWine)]
+    fn delist_consigned_wine(&self, consignment_id: u32, listing_id: u32) {
+        let consignment = self.require_open_consignment(consignment_id);
+        let caller = self.blockchain().get_caller();
+        require!(
+            caller == consignment.merchant
+                || (caller == consignment.producer && self.is_consignment_ended(&consignment)),
+            ERR_NOT_ALLOWED_TO_DELIST
+        );
+
+        self.tx()
+            .to(&self.marketplace_address().get())
+            .gas(MARKETPLACE_CALL_GAS)
+            .raw_call("cancelListing")
+            .argument(&listing_id)
+            .transfer_execute();
+    }
+
+    // Marketplace payout hook: split the proceeds of a sold bottle
+    #[payable("*")]
+    #[endpoint(consignmentSaleProceeds)]
+    fn consignment_sale_proceeds(&self, collection: TokenIdentifier, nonce: u64) {
+        require!(
+            self.blockchain().get_caller() == self.marketplace_address().get(),
+            ERR_ONLY_THE_MARKETPLACE_CAN_PAY_PROCEEDS
+        );
+        require!(collection == self.wine_collection().get(), ERR_INVALID_WINE_TOKEN);
+        require!(!self.open_consignment(nonce).is_empty(), ERR_WINE_IS_NOT_ON_CONSIGNMENT);
+        let consignment_id = self.open_consignment(nonce).get();
+        let mut consignment = self.consignments(consignment_id).get();
+
+        let payment = self.call_value().egld_or_single_esdt();
+        let commission = &payment.amount * consignment.commission_bps / 10_000u64;
+        let producer_share = &payment.amount - &commission;
+        consignment.sold += 1;
+        consignment.merchant_commission += &commission;
+        consignment.producer_proceeds += &producer_share;
+        self.consignments(consignment_id).set(&consignment);
+
+        self.send_proceeds(&consignment.merchant, &payment.token_identifier, &commission);
+        self.send_proceeds(&consignment.producer, &payment.token_identifier, &producer_share);
+
+        self.consigned_wine_sold_event(consignment_id, &producer_share, &commission);
+    }
+
+    // Return the unsold bottles to the producer. Bottles still listed must be delisted first.
+    #[endpoint(closeConsignment)]
+    fn close_consignment(&self, consignment_id: u32) {
+        let mut consignment = self.require_open_consignment(consignment_id);
+        let caller = self.blockchain().get_caller();
+        require!(
+            caller == consignment.merchant
+                || (caller == consignment.producer && self.is_consignment_ended(&consignment)),
+            ERR_NOT_ALLOWED_TO_CLOSE
+        );
+        let escrowed = self.escrowed_bottles(&consignment);
+        require!(
+            consignment.sold as u64 + escrowed == consignment.bottles as u64,
+            ERR_BOTTLES_STILL_LISTED_ON_THE_MARKETPLACE
+        );
+
+        consignment.status = ConsignmentStatus::Closed;
+        self.consignments(consignment_id).set(&consignment);
+        self.open_consignment(consignment.wine_nonce).clear();
+        if escrowed > 0 {
+            self.send().direct_esdt(
+                &consignment.producer,
+                &self.wine_collection().get(),
+                consignment.wine_nonce,
+                &BigUint::from(escrowed),
+            );
+        }
+
+        self.consignment_closed_event(consignment_id, consignment.sold, escrowed as u32);
+    }
+
+    // Internal helpers
+    fn require_consignment(&self, consignment_id: u32) -> Consignment<Self::Api> {
+        require!(!self.consignments(consignment_id).is_empty(), ERR_CONSIGNMENT_DOES_NOT_EXIST);
+        self.consignments(consignment_id).get()
+    }
+
+    fn require_open_consignment(&self, consignment_id: u32) -> Consignment<Self::Api> {
+        let consignment = self.require_consignment(consignment_id);
+        require!(consignment.status == ConsignmentStatus::Open, ERR_CONSIGNMENT_IS_CLOSED);
+        consignment
+    }
+
+    fn is_consignment_ended(&self, consignment: &Consignment<Self::Api>) -> bool {
+        self.blockchain().get_block_timestamp() > consignment.end_timestamp
+    }
+
+    // Bottles held here rather than listed or sold
+    fn escrowed_bottles(&self, consignment: &Consignment<Self::Api>) -> u64 {
+        self.blockchain()
+            .get_esdt_balance(
+                &self.blockchain().get_sc_address(),
+                &self.wine_collection().get(),
+                consignment.wine_nonce,
+            )
+            .to_u64()
+            .unwrap_or(0)
+    }
+
+    fn send_proceeds(&self, to: &ManagedAddress, token: &EgldOrEsdtTokenIdentifier, amount: &BigUint) {
+        if *amount > 0 {
+            self.send().direct(to, token, 0, amount);
+        }
+    }
+
+    // View functions
+    #[view(getMarketplace)]
+    fn get_marketplace(&self) -> ManagedAddress {
+        self.marketplace_address().get()
+    }
+
+    #[view(getWineCollection)]
+    fn get_wine_collection(&self) -> TokenIdentifier {
+        self.wine_collection().get()
+    }
+
+    #[view(getConsignment)]
+    fn get_consignment(&self, consignment_id: u32) -> Consignment<Self::Api> {
+        self.require_consignment(consignment_id)
+    }
+
+    #[view(getConsignmentCount)]
+    fn get_consignment_count(&self) -> u32 {
+        self.consignment_count().get()
+    }
+
+    #[view(getOpenConsignment)]
+    fn get_open_consignment(&self, wine_nonce: u64) -> OptionalValue<u32> {
+        if self.open_consignment(wine_nonce).is_empty() {
+            OptionalValue::None
+        } else {
+            OptionalValue::Some(self.open_consignment(wine_nonce).get())
+        }
+    }
+
+    #[view(getEscrowedBottles)]
+    fn get_escrowed_bottles(&self, consignment_id: u32) -> u64 {
+        let consignment = self.require_open_consignment(consignment_id);
+        self.escrowed_bottles(&consignment)
+    }
+
+    // Storage mappers
+    #[storage_mapper("marketplaceAddress")]
+    fn marketplace_address(&self) -> SingleValueMapper<ManagedAddress>;
+
+    #[storage_mapper("wineCollection")]
+    fn wine_collection(&self) -> SingleValueMapper<TokenIdentifier>;
+
+    #[storage_mapper("consignmentCount")]
+    fn consignment_count(&self) -> SingleValueMapper<u32>;
+
+    #[storage_mapper("consignments")]
+    fn consignments(&self, consignment_id: u32) -> SingleValueMapper<Consignment<Self::Api>>;
+
+    #[storage_mapper("openConsignment")]
+    fn open_consignment(&self, wine_nonce: u64) -> SingleValueMapper<u32>;
+
+    // Events
+    #[event("wineConsigned")]
+    fn wine_consigned_event(
+        &self,
+        #[indexed] consignment_id: u32,
+        #[indexed] producer: &ManagedAddress,
+        #[indexed] merchant: &ManagedAddress,
+        bottles: u32,
+    );
+
+    #[event("consignedWineListed")]
+    fn consigned_wine_listed_event(&self, #[indexed] consignment_id: u32, price: &BigUint);
+
+    #[event("consignedWineSold")]
+    fn consigned_wine_sold_event(
+        &self,
+        #[indexed] consignment_id: u32,
+        producer_share: &BigUint,
+        commission: &BigUint,
+    );
+
+    #[event("consignmentClosed")]
+    fn consignment_closed_event(
+        &self,
+        #[indexed] consignment_id: u32,
+        sold: u32,
+        returned: u32,
+    );
+}
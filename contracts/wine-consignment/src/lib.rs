@@ -0,0 +1,28 @@
+#![no_std]
+
+use multiversx_sc::imports::*;
+use wine_common::errors::*;
+
+pub mod consignments;
+
+pub use consignments::{Consignment, ConsignmentStatus, WineNftAttributes};
+
+// Consignment of wine to merchants. Producers deposit bottles under agreed terms, merchants
+// sell them on the marketplace on the producer's behalf, and every sale is split between
+// them per those terms.
+#[multiversx_sc::contract]
+pub trait WineConsignment: consignments::ConsignmentsModule + wine_roles::RolesModule {
+    #[init]
+    fn init(&self, marketplace: ManagedAddress, wine_collection: TokenIdentifier) {
+        require!(wine_collection.is_valid_esdt_identifier(), ERR_INVALID_WINE_COLLECTION);
+        require!(
+            self.blockchain().is_smart_contract(&marketplace),
+            ERR_MARKETPLACE_MUST_BE_A_SMART_CONTRACT
+        );
+        self.marketplace_address().set(&marketplace);
+        self.wine_collection().set(&wine_collection);
+    }
+
+    #[upgrade]
+    fn upgrade(&self) {}
+}
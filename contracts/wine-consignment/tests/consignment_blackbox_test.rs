@@ -0,0 +1,305 @@
+use multiversx_sc::codec::top_encode_to_vec_u8_or_panic;
+use multiversx_sc_scenario::api::StaticApi;
+use multiversx_sc_scenario::imports::*;
+use wine_common::errors::*;
+use wine_common::{WineReadModel, WineStatus};
+use wine_consignment::consignments::ProxyTrait as _;
+use wine_consignment::ProxyTrait as _;
+use wine_consignment::{Consignment, ConsignmentStatus, WineNftAttributes};
+use wine_marketplace::listing::ProxyTrait as _;
+use wine_marketplace::registry_cache::ProxyTrait as _;
+use wine_marketplace::treasury::ProxyTrait as _;
+use wine_marketplace::ProxyTrait as _;
+
+const CONSIGNMENT_PATH_EXPR: &str = "mxsc:output/wine-consignment.mxsc.json";
+const MARKETPLACE_PATH_EXPR: &str = "mxsc:../wine-marketplace/output/wine-marketplace.mxsc.json";
+const CONSIGNMENT_ADDRESS_EXPR: &str = "sc:wine-consignment";
+const MARKETPLACE_ADDRESS_EXPR: &str = "sc:wine-marketplace";
+const OWNER_ADDRESS_EXPR: &str = "address:owner";
+const REGISTRY_ADDRESS_EXPR: &str = "address:registry";
+const PRODUCER_ADDRESS_EXPR: &str = "address:producer";
+const MERCHANT_ADDRESS_EXPR: &str = "address:merchant";
+const BUYER_ADDRESS_EXPR: &str = "address:buyer";
+
+const WINE_NFT_TOKEN_ID_EXPR: &str = "str:WINE-abcdef";
+
+const START_TIMESTAMP: u64 = 1_000;
+const CONSIGNMENT_DURATION: u64 = 10 * 86_400;
+const LISTING_DURATION: u64 = 86_400;
+const MIN_PRICE: u64 = 500;
+const PRICE: u64 = 1_000;
+const COMMISSION_BPS: u64 = 1_000;
+const WINE_ID: u32 = 7;
+
+type ConsignmentContract = ContractInfo<wine_consignment::Proxy<StaticApi>>;
+type MarketplaceContract = ContractInfo<wine_marketplace::Proxy<StaticApi>>;
+
+fn user_error(message: &str) -> TxExpect {
+    TxExpect::user_error(format!("str:{message}"))
+}
+
+fn encoded_wine_attributes() -> Option<Vec<u8>> {
+    Some(top_encode_to_vec_u8_or_panic(&WineNftAttributes::<StaticApi> {
+        wine_id: WINE_ID,
+        vineyard: ManagedBuffer::from("Chateau Test"),
+        variety: ManagedBuffer::from("Merlot"),
+        vintage_year: 2020,
+        region: ManagedBuffer::from("Bordeaux"),
+        quality_grade: 8,
+        ipfs_hash: ManagedBuffer::new(),
+    }))
+}
+
+struct ConsignmentTestState {
+    world: ScenarioWorld,
+    consignment: ConsignmentContract,
+    marketplace: MarketplaceContract,
+}
+
+impl ConsignmentTestState {
+    // The producer consigned 3 bottles of wine 7 to the merchant at a 10% commission, on a
+    // marketplace charging 2.5%
+    fn new() -> Self {
+        let mut world = ScenarioWorld::new();
+        world.register_contract(CONSIGNMENT_PATH_EXPR, wine_consignment::ContractBuilder);
+        world.register_contract(MARKETPLACE_PATH_EXPR, wine_marketplace::ContractBuilder);
+
+        world.set_state_step(
+            SetStateStep::new()
+                .block_timestamp(START_TIMESTAMP)
+                .put_account(OWNER_ADDRESS_EXPR, Account::new().nonce(1))
+                // Receives the lock/unlock calls made as bottles enter and leave escrow
+                .put_account(REGISTRY_ADDRESS_EXPR, Account::new().nonce(1))
+                .put_account(
+                    PRODUCER_ADDRESS_EXPR,
+                    Account::new()
+                        .nonce(1)
+                        .esdt_nft_balance(WINE_NFT_TOKEN_ID_EXPR, 1, "4", encoded_wine_attributes()),
+                )
+                .put_account(MERCHANT_ADDRESS_EXPR, Account::new().nonce(1))
+                .put_account(BUYER_ADDRESS_EXPR, Account::new().nonce(1).balance("10000"))
+                .new_address(OWNER_ADDRESS_EXPR, 1, MARKETPLACE_ADDRESS_EXPR)
+                .new_address(OWNER_ADDRESS_EXPR, 2, CONSIGNMENT_ADDRESS_EXPR),
+        );
+
+        let mut marketplace = MarketplaceContract::new(MARKETPLACE_ADDRESS_EXPR);
+        let marketplace_code = world.code_expression(MARKETPLACE_PATH_EXPR);
+        let registry = AddressValue::from(REGISTRY_ADDRESS_EXPR).to_address();
+        world.sc_deploy(
+            ScDeployStep::new()
+                .from(OWNER_ADDRESS_EXPR)
+                .code(marketplace_code)
+                .call(marketplace.init(250u64, managed_address!(&registry))),
+        );
+        let mut consignment = ConsignmentContract::new(CONSIGNMENT_ADDRESS_EXPR);
+        let consignment_code = world.code_expression(CONSIGNMENT_PATH_EXPR);
+        let marketplace_address = AddressValue::from(MARKETPLACE_ADDRESS_EXPR).to_address();
+        world.sc_deploy(
+            ScDeployStep::new()
+                .from(OWNER_ADDRESS_EXPR)
+                .code(consignment_code)
+                .call(consignment.init(managed_address!(&marketplace_address), TokenIdentifier::from("WINE-abcdef"))),
+        );
+
+        let consignment_address = AddressValue::from(CONSIGNMENT_ADDRESS_EXPR).to_address();
+        let producer = AddressValue::from(PRODUCER_ADDRESS_EXPR).to_address();
+        world
+            .sc_call(
+                ScCallStep::new()
+                    .from(OWNER_ADDRESS_EXPR)
+                    .call(marketplace.set_wine_collection(TokenIdentifier::from("WINE-abcdef"))),
+            )
+            .sc_call(
+                ScCallStep::new()
+                    .from(OWNER_ADDRESS_EXPR)
+                    .call(marketplace.add_consignment_contract(managed_address!(&consignment_address))),
+            )
+            .sc_call(
+                ScCallStep::new().from(REGISTRY_ADDRESS_EXPR).call(marketplace.sync_wine_read_model(
+                    WINE_ID,
+                    WineReadModel::<StaticApi> {
+                        price_per_bottle: BigUint::from(PRICE),
+                        quality_grade: 8,
+                        certified: false,
+                        royalty_bps: 0,
+                        status: WineStatus::Released,
+                        producer: managed_address!(&producer),
+                        counterfeit: false,
+                        certified_until: 0,
+                    },
+                )),
+            );
+
+        let mut state = ConsignmentTestState { world, consignment, marketplace };
+        let step = state.consign_wine_step("3");
+        state.world.sc_call(step.expect_value(1u32));
+        state
+    }
+
+    fn set_block_timestamp(&mut self, timestamp: u64) {
+        self.world.set_state_step(SetStateStep::new().block_timestamp(timestamp));
+    }
+
+    fn consign_wine_step(&mut self, bottles: &str) -> TypedScCall<u32> {
+        let merchant = AddressValue::from(MERCHANT_ADDRESS_EXPR).to_address();
+        ScCallStep::new()
+            .from(PRODUCER_ADDRESS_EXPR)
+            .esdt_transfer(WINE_NFT_TOKEN_ID_EXPR, 1, bottles)
+            .call(self.consignment.consign_wine(
+                managed_address!(&merchant),
+                EgldOrEsdtTokenIdentifier::egld(),
+                MIN_PRICE,
+                COMMISSION_BPS,
+                CONSIGNMENT_DURATION,
+            ))
+    }
+
+    fn list_consigned_wine(&mut self) {
+        self.world.sc_call(
+            ScCallStep::new()
+                .from(MERCHANT_ADDRESS_EXPR)
+                .call(self.consignment.list_consigned_wine(1u32, PRICE, LISTING_DURATION)),
+        );
+    }
+
+    fn close_consignment(&mut self, caller: &str) -> TypedScCall<()> {
+        ScCallStep::new()
+            .from(caller)
+            .call(self.consignment.close_consignment(1u32))
+    }
+
+    fn consignment(&mut self) -> Consignment<StaticApi> {
+        self.world.quick_query(self.consignment.get_consignment(1u32))
+    }
+}
+
+#[test]
+fn sold_bottle_proceeds_are_split_between_merchant_and_producer() {
+    let mut state = ConsignmentTestState::new();
+
+    state.world.sc_call(
+        ScCallStep::new()
+            .from(PRODUCER_ADDRESS_EXPR)
+            .call(state.consignment.list_consigned_wine(1u32, PRICE, LISTING_DURATION))
+            .expect(user_error(ERR_ONLY_MERCHANT_CAN_LIST)),
+    );
+    state.world.sc_call(
+        ScCallStep::new()
+            .from(MERCHANT_ADDRESS_EXPR)
+            .call(state.consignment.list_consigned_wine(1u32, MIN_PRICE - 1, LISTING_DURATION))
+            .expect(user_error(ERR_PRICE_BELOW_THE_CONSIGNMENT_MINIMUM)),
+    );
+    state.list_consigned_wine();
+
+    state.world.sc_call(
+        ScCallStep::new()
+            .from(BUYER_ADDRESS_EXPR)
+            .egld_value(PRICE)
+            .call(state.marketplace.buy_wine(1u32, OptionalValue::<ManagedAddress<StaticApi>>::None)),
+    );
+
+    // 975 reaches the consignment after the marketplace fee, of which the merchant keeps 10%
+    let consignment = state.consignment();
+    assert_eq!(consignment.sold, 1);
+    assert_eq!(consignment.merchant_commission, 97u64);
+    assert_eq!(consignment.producer_proceeds, 878u64);
+    state.world.check_state_step(
+        CheckStateStep::new()
+            .put_account(MERCHANT_ADDRESS_EXPR, CheckAccount::new().balance("97"))
+            .put_account(
+                PRODUCER_ADDRESS_EXPR,
+                CheckAccount::new().balance("878").esdt_nft_balance_and_attributes(
+                    WINE_NFT_TOKEN_ID_EXPR,
+                    1,
+                    "1",
+                    encoded_wine_attributes(),
+                ),
+            )
+            .put_account(CONSIGNMENT_ADDRESS_EXPR, CheckAccount::new().balance("0")),
+    );
+
+    // Proceeds are only taken from the marketplace
+    state.world.sc_call(
+        ScCallStep::new()
+            .from(BUYER_ADDRESS_EXPR)
+            .egld_value(100u64)
+            .call(state.consignment.consignment_sale_proceeds(TokenIdentifier::from("WINE-abcdef"), 1u64))
+            .expect(user_error(ERR_ONLY_THE_MARKETPLACE_CAN_PAY_PROCEEDS)),
+    );
+
+    let step = state.close_consignment(MERCHANT_ADDRESS_EXPR);
+    state.world.sc_call(step);
+    assert_eq!(state.consignment().status, ConsignmentStatus::Closed);
+    state.world.check_state_step(
+        CheckStateStep::new().put_account(
+            PRODUCER_ADDRESS_EXPR,
+            CheckAccount::new().esdt_nft_balance_and_attributes(
+                WINE_NFT_TOKEN_ID_EXPR,
+                1,
+                "3",
+                encoded_wine_attributes(),
+            ),
+        ),
+    );
+}
+
+#[test]
+fn listed_bottles_are_delisted_before_the_consignment_closes() {
+    let mut state = ConsignmentTestState::new();
+    state.list_consigned_wine();
+
+    let step = state.close_consignment(MERCHANT_ADDRESS_EXPR);
+    state.world.sc_call(step.expect(user_error(ERR_BOTTLES_STILL_LISTED_ON_THE_MARKETPLACE)));
+    state.world.sc_call(
+        ScCallStep::new()
+            .from(PRODUCER_ADDRESS_EXPR)
+            .call(state.consignment.delist_consigned_wine(1u32, 1u32))
+            .expect(user_error(ERR_NOT_ALLOWED_TO_DELIST)),
+    );
+    state.world.sc_call(
+        ScCallStep::new()
+            .from(MERCHANT_ADDRESS_EXPR)
+            .call(state.consignment.delist_consigned_wine(1u32, 1u32)),
+    );
+    let escrowed: u64 = state.world.quick_query(state.consignment.get_escrowed_bottles(1u32));
+    assert_eq!(escrowed, 3);
+
+    // The producer takes the bottles back only once the consignment has ended
+    let step = state.close_consignment(PRODUCER_ADDRESS_EXPR);
+    state.world.sc_call(step.expect(user_error(ERR_NOT_ALLOWED_TO_CLOSE)));
+    state.set_block_timestamp(START_TIMESTAMP + CONSIGNMENT_DURATION + 1);
+    let step = state.close_consignment(PRODUCER_ADDRESS_EXPR);
+    state.world.sc_call(step);
+
+    state.world.check_state_step(
+        CheckStateStep::new().put_account(
+            PRODUCER_ADDRESS_EXPR,
+            CheckAccount::new().esdt_nft_balance_and_attributes(
+                WINE_NFT_TOKEN_ID_EXPR,
+                1,
+                "4",
+                encoded_wine_attributes(),
+            ),
+        ),
+    );
+}
+
+#[test]
+fn one_open_consignment_per_wine_nonce() {
+    let mut state = ConsignmentTestState::new();
+
+    let merchant = AddressValue::from(MERCHANT_ADDRESS_EXPR).to_address();
+    let step = state.consign_wine_step("1");
+    state.world.sc_call(step.expect(user_error(ERR_WINE_IS_ALREADY_ON_CONSIGNMENT)));
+    state.world.sc_call(
+        ScCallStep::new()
+            .from(PRODUCER_ADDRESS_EXPR)
+            .call(state.consignment.set_marketplace(managed_address!(&merchant)))
+            .expect(user_error(ERR_CALLER_DOES_NOT_HAVE_THE_REQUIRED_ROLE)),
+    );
+
+    let step = state.close_consignment(MERCHANT_ADDRESS_EXPR);
+    state.world.sc_call(step);
+    let step = state.consign_wine_step("1");
+    state.world.sc_call(step.expect_value(2u32));
+}
@@ -0,0 +1,401 @@
+use multiversx_sc::derive_imports::*;
+use multiversx_sc::imports::*;
+use wine_common::errors::*;
+use wine_roles::Role;
+
+pub const MAX_MILESTONES: usize = 10;
+
+#[derive(TopEncode, TopDecode, NestedEncode, NestedDecode, TypeAbi, PartialEq, Debug, Clone, Copy)]
+pub enum CampaignStatus {
+    // Taking contributions until the deadline
+    Funding,
+    // Goal reached; funds release to the producer and the vintage is delivered
+    Succeeded,
+    // Goal missed, cancelled, or failed by the authority; allocations are refunded
+    Failed,
+}
+
+#[derive(TopEncode, TopDecode, TypeAbi, PartialEq, Debug)]
+pub struct Campaign<M: ManagedTypeApi> {
+    pub producer: ManagedAddress<M>,
+    // Delivered wine must be of this vintage
+    pub vintage_year: u32,
+    pub payment_token: EgldOrEsdtTokenIdentifier<M>,
+    pub price_per_bottle: BigUint<M>,
+    // Bottles that must be backed for the campaign to succeed
+    pub goal_bottles: u32,
+    pub max_bottles: u32,
+    pub backed_bottles: u32,
+    pub deadline: u64,
+    // Allocation token nonce, 0 until the first contribution
+    pub allocation_nonce: u64,
+    // Milestone releases in basis points of the funds raised, confirmed by the authority. No
+    // milestones release everything at once on success.
+    pub milestone_authority: Option<ManagedAddress<M>>,
+    pub milestone_bps: ManagedVec<M, u64>,
+    pub confirmed_milestones: u32,
+    pub released_amount: BigUint<M>,
+    // Wine NFT nonce, 0 until the first delivery
+    pub wine_nonce: u64,
+    pub delivered_bottles: u32,
+    pub claimed_bottles: u32,
+    pub status: CampaignStatus,
+}
+
+// On-chain attributes of an allocation token; one unit is the right to one bottle
+#[derive(TopEncode, TopDecode, TypeAbi, PartialEq, Debug)]
+pub struct AllocationAttributes {
+    pub campaign_id: u32,
+    pub vintage_year: u32,
+}
+
+// Producers raise capital for a future vintage by selling bottle allocations at a fixed
+// price. Backers receive allocations as units of one SFT collection, one nonce per campaign.
+// Campaigns are all-or-nothing: below the goal at the deadline, allocations are refunded.
+#[multiversx_sc::module]
+pub trait CampaignsModule: wine_roles::RolesModule {
+    // Issue the allocation SFT collection with all roles assigned to this contract. Requires
+    // the issue cost in EGLD.
+    #[payable("EGLD")]
+    #[endpoint(issueAllocationCollection)]
+    fn issue_allocation_collection(
+        &self,
+        token_display_name: ManagedBuffer,
+        token_ticker: ManagedBuffer,
+    ) {
+        self.require_role(Role::Admin);
+        let issue_cost = self.call_value().egld_value().clone_value();
+        self.allocation_collection().issue_and_set_all_roles(
+            EsdtTokenType::SemiFungible,
+            issue_cost,
+            token_display_name,
+            token_ticker,
+            0,
+            Some(self.callbacks().allocation_collection_issued()),
+        );
+    }
+
+    // Registered producers allowed to raise funds
+    #[endpoint(addCampaignProducer)]
+    fn add_campaign_producer(&self, producer: ManagedAddress) {
+        self.require_role(Role::Admin);
+        require!(
+            self.campaign_producers().insert(producer.clone()),
+            ERR_ALREADY_A_CAMPAIGN_PRODUCER
+        );
+        self.campaign_producer_added_event(&producer);
+    }
+
+    #[endpoint(removeCampaignProducer)]
+    fn remove_campaign_producer(&self, producer: ManagedAddress) {
+        self.require_role(Role::Admin);
+        require!(self.campaign_producers().swap_remove(&producer), ERR_NOT_A_CAMPAIGN_PRODUCER);
+        self.campaign_producer_removed_event(&producer);
+    }
+
+    // Certification authorities and similar parties that can confirm milestones
+    #[endpoint(addMilestoneAuthority)]
+    fn add_milestone_authority(&self, authority: ManagedAddress) {
+        self.require_role(Role::Admin);
+        require!(self.milestone_authorities().insert(authority), ERR_ALREADY_A_MILESTONE_AUTHORITY);
+    }
+
+    #[endpoint(removeMilestoneAuthority)]
+    fn remove_milestone_authority(&self, authority: ManagedAddress) {
+        self.require_role(Role::Admin);
+        require!(
+            self.milestone_authorities().swap_remove(&authority),
+            ERR_NOT_A_MILESTONE_AUTHORITY
+        );
+    }
+
+    #[endpoint(createCampaign)]
+    fn create_campaign(
+        &self,
+        vintage_year: u32,
+        payment_token: EgldOrEsdtTokenIdentifier,
+        price_per_bottle: BigUint,
+        goal_bottles: u32,
+        max_bottles: u32,
+        deadline: u64,
+    ) -> u32 {
+        self.require_not_paused();
+        let producer = self.blockchain().get_caller();
+        require!(
+            self.campaign_producers().contains(&producer),
+            ERR_ONLY_CAMPAIGN_PRODUCERS_CAN_CREATE_CAMPAIGNS
+        );
+        require!(payment_token.is_valid(), ERR_INVALID_PAYMENT_TOKEN);
+        require!(price_per_bottle > 0, ERR_PRICE_PER_BOTTLE_MUST_BE_GREATER_THAN_0);
+        require!(goal_bottles > 0, ERR_GOAL_MUST_BE_GREATER_THAN_0);
+        require!(max_bottles >= goal_bottles, ERR_MAXIMUM_BOTTLES_MUST_NOT_BE_BELOW_THE_GOAL);
+        require!(
+            deadline > self.blockchain().get_block_timestamp(),
+            ERR_DEADLINE_MUST_BE_IN_THE_FUTURE
+        );
+
+        let campaign_id = self.campaign_counter().get();
+        self.campaign_counter().set(campaign_id + 1);
+        self.campaigns(campaign_id).set(&Campaign {
+            producer: producer.clone(),
+            vintage_year,
+            payment_token,
+            price_per_bottle,
+            goal_bottles,
+            max_bottles,
+            backed_bottles: 0,
+            deadline,
+            allocation_nonce: 0,
+            milestone_authority: None,
+            milestone_bps: ManagedVec::new(),
+            confirmed_milestones: 0,
+            released_amount: BigUint::zero(),
+            wine_nonce: 0,
+            delivered_bottles: 0,
+            claimed_bottles: 0,
+            status: CampaignStatus::Funding,
+        });
+
+        self.campaign_created_event(campaign_id, &producer, vintage_year, goal_bottles);
+
+        campaign_id
+    }
+
+    // Release funds in milestones confirmed by `authority` instead of all at once. Milestone
+    // shares are in basis points and must add up to 100%. Only before the first contribution,
+    // so backers know the terms they back.
+    #[endpoint(setCampaignMilestones)]
+    fn set_campaign_milestones(
+        &self,
+        campaign_id: u32,
+        authority: ManagedAddress,
+        milestone_bps: MultiValueEncoded<u64>,
+    ) {
+        let mut campaign = self.require_campaign(campaign_id);
+        require!(
+            self.blockchain().get_caller() == campaign.producer,
+            ERR_ONLY_PRODUCER_CAN_SET_MILESTONES
+        );
+        require!(campaign.backed_bottles == 0, ERR_CAMPAIGN_ALREADY_HAS_BACKERS);
+        require!(
+            self.milestone_authorities().contains(&authority),
+            ERR_NOT_A_MILESTONE_AUTHORITY
+        );
+        let milestone_bps = milestone_bps.to_vec();
+        require!(
+            !milestone_bps.is_empty() && milestone_bps.len() <= MAX_MILESTONES,
+            ERR_INVALID_NUMBER_OF_MILESTONES
+        );
+        let total_bps: u64 = milestone_bps.iter().sum();
+        require!(total_bps == 10_000, ERR_MILESTONES_MUST_ADD_UP_TO_100_PERCENT);
+
+        campaign.milestone_authority = Some(authority.clone());
+        campaign.milestone_bps = milestone_bps;
+        self.campaigns(campaign_id).set(&campaign);
+
+        let milestone_count = campaign.milestone_bps.len() as u32;
+        self.campaign_milestones_set_event(campaign_id, &authority, milestone_count);
+    }
+
+    // Back `bottles` bottles, paying exactly their price, for the same number of allocations
+    #[payable("*")]
+    #[endpoint(contribute)]
+    fn contribute(&self, campaign_id: u32, bottles: u32) {
+        self.require_not_paused();
+        let mut campaign = self.require_campaign(campaign_id);
+        require!(campaign.status == CampaignStatus::Funding, ERR_CAMPAIGN_IS_NOT_FUNDING);
+        require!(
+            self.blockchain().get_block_timestamp() <= campaign.deadline,
+            ERR_CAMPAIGN_HAS_ENDED
+        );
+        require!(bottles > 0, ERR_BOTTLES_MUST_BE_GREATER_THAN_0);
+        require!(
+            bottles <= campaign.max_bottles - campaign.backed_bottles,
+            ERR_NOT_ENOUGH_BOTTLES_LEFT
+        );
+        require!(!self.allocation_collection().is_empty(), ERR_ALLOCATION_COLLECTION_NOT_ISSUED);
+
+        let payment = self.call_value().egld_or_single_esdt();
+        require!(payment.token_identifier == campaign.payment_token, ERR_INVALID_PAYMENT_TOKEN);
+        require!(
+            payment.amount == &campaign.price_per_bottle * bottles,
+            ERR_INCORRECT_PAYMENT_AMOUNT
+        );
+
+        let token_id = self.allocation_collection().get_token_id();
+        let amount = BigUint::from(bottles);
+        if campaign.allocation_nonce == 0 {
+            let attributes = AllocationAttributes {
+                campaign_id,
+                vintage_year: campaign.vintage_year,
+            };
+            campaign.allocation_nonce = self.send().esdt_nft_create(
+                &token_id,
+                &amount,
+                &sc_format!("Vintage {} allocation", campaign.vintage_year),
+                &BigUint::zero(),
+                &ManagedBuffer::new(),
+                &attributes,
+                &ManagedVec::new(),
+            );
+        } else {
+            self.send().esdt_local_mint(&token_id, campaign.allocation_nonce, &amount);
+        }
+        campaign.backed_bottles += bottles;
+        self.campaigns(campaign_id).set(&campaign);
+
+        let backer = self.blockchain().get_caller();
+        self.send().direct_esdt(&backer, &token_id, campaign.allocation_nonce, &amount);
+
+        self.contributed_event(campaign_id, &backer, bottles);
+    }
+
+    // Settle the funding round: anyone can call it past the deadline, or once the campaign is
+    // fully backed
+    #[endpoint(finalizeCampaign)]
+    fn finalize_campaign(&self, campaign_id: u32) {
+        let mut campaign = self.require_campaign(campaign_id);
+        require!(campaign.status == CampaignStatus::Funding, ERR_CAMPAIGN_IS_NOT_FUNDING);
+        require!(
+            self.blockchain().get_block_timestamp() > campaign.deadline
+                || campaign.backed_bottles == campaign.max_bottles,
+            ERR_CAMPAIGN_IS_STILL_FUNDING
+        );
+
+        campaign.status = if campaign.backed_bottles >= campaign.goal_bottles {
+            CampaignStatus::Succeeded
+        } else {
+            CampaignStatus::Failed
+        };
+        self.campaigns(campaign_id).set(&campaign);
+
+        self.campaign_finalized_event(campaign_id, campaign.status, campaign.backed_bottles);
+    }
+
+    // Withdraw a campaign that is still funding; every allocation becomes refundable
+    #[endpoint(cancelCampaign)]
+    fn cancel_campaign(&self, campaign_id: u32) {
+        let mut campaign = self.require_campaign(campaign_id);
+        require!(
+            self.blockchain().get_caller() == campaign.producer,
+            ERR_ONLY_PRODUCER_CAN_CANCEL_THE_CAMPAIGN
+        );
+        require!(campaign.status == CampaignStatus::Funding, ERR_CAMPAIGN_IS_NOT_FUNDING);
+
+        campaign.status = CampaignStatus::Failed;
+        self.campaigns(campaign_id).set(&campaign);
+
+        self.campaign_finalized_event(campaign_id, campaign.status, campaign.backed_bottles);
+    }
+
+    // Internal helpers
+    fn require_campaign(&self, campaign_id: u32) -> Campaign<Self::Api> {
+        require!(!self.campaigns(campaign_id).is_empty(), ERR_CAMPAIGN_DOES_NOT_EXIST);
+        self.campaigns(campaign_id).get()
+    }
+
+    #[callback]
+    fn allocation_collection_issued(
+        &self,
+        #[call_result] result: ManagedAsyncCallResult<TokenIdentifier>,
+    ) {
+        match result {
+            ManagedAsyncCallResult::Ok(token_id) => {
+                self.allocation_collection().set_token_id(token_id);
+            },
+            ManagedAsyncCallResult::Err(_) => {
+                self.allocation_collection().clear();
+            },
+        }
+    }
+
+    // View functions
+    #[view(getAllocationCollection)]
+    fn get_allocation_collection(&self) -> OptionalValue<TokenIdentifier> {
+        if self.allocation_collection().get_token_state().is_set() {
+            OptionalValue::Some(self.allocation_collection().get_token_id())
+        } else {
+            OptionalValue::None
+        }
+    }
+
+    #[view(getCampaign)]
+    fn get_campaign(&self, campaign_id: u32) -> Campaign<Self::Api> {
+        self.require_campaign(campaign_id)
+    }
+
+    #[view(getCampaignCount)]
+    fn get_campaign_count(&self) -> u32 {
+        self.campaign_counter().get() - 1
+    }
+
+    #[view(getCampaignProducers)]
+    fn get_campaign_producers(&self) -> MultiValueEncoded<ManagedAddress> {
+        self.campaign_producers().iter().collect()
+    }
+
+    #[view(getMilestoneAuthorities)]
+    fn get_milestone_authorities(&self) -> MultiValueEncoded<ManagedAddress> {
+        self.milestone_authorities().iter().collect()
+    }
+
+    // Storage mappers
+    #[storage_mapper("allocationCollection")]
+    fn allocation_collection(&self) -> NonFungibleTokenMapper;
+
+    #[view(getWineCollection)]
+    #[storage_mapper("wineCollection")]
+    fn wine_collection(&self) -> SingleValueMapper<TokenIdentifier>;
+
+    #[storage_mapper("campaignCounter")]
+    fn campaign_counter(&self) -> SingleValueMapper<u32>;
+
+    #[storage_mapper("campaigns")]
+    fn campaigns(&self, campaign_id: u32) -> SingleValueMapper<Campaign<Self::Api>>;
+
+    #[storage_mapper("campaignProducers")]
+    fn campaign_producers(&self) -> UnorderedSetMapper<ManagedAddress>;
+
+    #[storage_mapper("milestoneAuthorities")]
+    fn milestone_authorities(&self) -> UnorderedSetMapper<ManagedAddress>;
+
+    // Events
+    #[event("campaignProducerAdded")]
+    fn campaign_producer_added_event(&self, #[indexed] producer: &ManagedAddress);
+
+    #[event("campaignProducerRemoved")]
+    fn campaign_producer_removed_event(&self, #[indexed] producer: &ManagedAddress);
+
+    #[event("campaignCreated")]
+    fn campaign_created_event(
+        &self,
+        #[indexed] campaign_id: u32,
+        #[indexed] producer: &ManagedAddress,
+        #[indexed] vintage_year: u32,
+        goal_bottles: u32,
+    );
+
+    #[event("campaignMilestonesSet")]
+    fn campaign_milestones_set_event(
+        &self,
+        #[indexed] campaign_id: u32,
+        #[indexed] authority: &ManagedAddress,
+        milestone_count: u32,
+    );
+
+    #[event("contributed")]
+    fn contributed_event(
+        &self,
+        #[indexed] campaign_id: u32,
+        #[indexed] backer: &ManagedAddress,
+        bottles: u32,
+    );
+
+    #[event("campaignFinalized")]
+    fn campaign_finalized_event(
+        &self,
+        #[indexed] campaign_id: u32,
+        #[indexed] status: CampaignStatus,
+        backed_bottles: u32,
+    );
+}
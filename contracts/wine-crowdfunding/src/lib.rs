@@ -0,0 +1,29 @@
+#![no_std]
+
+use multiversx_sc::imports::*;
+use wine_common::errors::*;
+
+pub mod campaigns;
+pub mod settlement;
+
+pub use campaigns::{AllocationAttributes, Campaign, CampaignStatus};
+pub use settlement::WineNftAttributes;
+
+// Vintage crowdfunding. Registered producers raise capital for a future vintage; backers
+// receive transferable allocations that redeem for the wine NFTs once the vintage is
+// delivered. Funds release on success, optionally in milestones confirmed by an authority,
+// and are refunded if the goal is missed.
+#[multiversx_sc::contract]
+pub trait WineCrowdfunding:
+    campaigns::CampaignsModule + settlement::SettlementModule + wine_roles::RolesModule
+{
+    #[init]
+    fn init(&self, wine_collection: TokenIdentifier) {
+        require!(wine_collection.is_valid_esdt_identifier(), ERR_INVALID_WINE_COLLECTION);
+        self.wine_collection().set(&wine_collection);
+        self.campaign_counter().set(1u32);
+    }
+
+    #[upgrade]
+    fn upgrade(&self) {}
+}
@@ -0,0 +1,229 @@
+use multiversx_sc::imports::*;
+pub use wine_common::WineNftAttributes;
+
+use crate::campaigns::{Campaign, CampaignStatus};
+use wine_common::errors::*;
+
+// Releasing funds to the producer, delivering the vintage against allocations, and refunds.
+// A failed campaign refunds every allocation an equal part of the funds not yet released.
+#[multiversx_sc::module]
+pub trait SettlementModule: wine_roles::RolesModule + crate::campaigns::CampaignsModule {
+    // Confirm the next milestone of a successful campaign
+    #[endpoint(confirmMilestone)]
+    fn confirm_milestone(&self, campaign_id: u32) {
+        let mut campaign = self.require_campaign(campaign_id);
+        self.require_milestone_authority(&campaign);
+        require!(campaign.status == CampaignStatus::Succeeded, ERR_CAMPAIGN_HAS_NOT_SUCCEEDED);
+        require!(
+            (campaign.confirmed_milestones as usize) < campaign.milestone_bps.len(),
+            ERR_ALL_MILESTONES_CONFIRMED
+        );
+        campaign.confirmed_milestones += 1;
+        self.campaigns(campaign_id).set(&campaign);
+
+        self.milestone_confirmed_event(campaign_id, campaign.confirmed_milestones);
+    }
+
+    // Stop a successful campaign whose vintage will not be delivered, e.g. a lost harvest.
+    // Funds not yet released become refundable.
+    #[endpoint(failCampaign)]
+    fn fail_campaign(&self, campaign_id: u32) {
+        let mut campaign = self.require_campaign(campaign_id);
+        self.require_milestone_authority(&campaign);
+        require!(campaign.status == CampaignStatus::Succeeded, ERR_CAMPAIGN_HAS_NOT_SUCCEEDED);
+        require!(campaign.delivered_bottles == 0, ERR_VINTAGE_DELIVERY_HAS_STARTED);
+
+        campaign.status = CampaignStatus::Failed;
+        self.campaigns(campaign_id).set(&campaign);
+
+        self.campaign_finalized_event(campaign_id, campaign.status, campaign.backed_bottles);
+    }
+
+    // Pay out what the confirmed milestones unlock, or everything without milestones
+    #[endpoint(releaseFunds)]
+    fn release_funds(&self, campaign_id: u32) -> BigUint {
+        let mut campaign = self.require_campaign(campaign_id);
+        require!(
+            self.blockchain().get_caller() == campaign.producer,
+            ERR_ONLY_PRODUCER_CAN_RELEASE_FUNDS
+        );
+        require!(campaign.status == CampaignStatus::Succeeded, ERR_CAMPAIGN_HAS_NOT_SUCCEEDED);
+
+        let releasable = self.unlocked_amount(&campaign) - &campaign.released_amount;
+        require!(releasable > 0, ERR_NOTHING_TO_RELEASE);
+        campaign.released_amount += &releasable;
+        self.campaigns(campaign_id).set(&campaign);
+        self.send().direct(&campaign.producer, &campaign.payment_token, 0, &releasable);
+
+        self.funds_released_event(campaign_id, &releasable);
+
+        releasable
+    }
+
+    // Deposit NFTs of the campaign's vintage, possibly over several calls and up to the
+    // bottles backed. Allocations redeem against what has been delivered.
+    #[payable("*")]
+    #[endpoint(deliverVintage)]
+    fn deliver_vintage(&self, campaign_id: u32) {
+        let mut campaign = self.require_campaign(campaign_id);
+        require!(
+            self.blockchain().get_caller() == campaign.producer,
+            ERR_ONLY_PRODUCER_CAN_DELIVER_THE_VINTAGE
+        );
+        require!(campaign.status == CampaignStatus::Succeeded, ERR_CAMPAIGN_HAS_NOT_SUCCEEDED);
+
+        let payment = self.call_value().single_esdt();
+        require!(payment.token_identifier == self.wine_collection().get(), ERR_INVALID_WINE_TOKEN);
+        require!(
+            campaign.wine_nonce == 0 || payment.token_nonce == campaign.wine_nonce,
+            ERR_INVALID_WINE_NONCE
+        );
+        let token_data = self.blockchain().get_esdt_token_data(
+            &self.blockchain().get_sc_address(),
+            &payment.token_identifier,
+            payment.token_nonce,
+        );
+        let attributes: WineNftAttributes<Self::Api> = token_data.decode_attributes();
+        require!(
+            attributes.vintage_year == campaign.vintage_year,
+            ERR_DELIVERED_VINTAGE_DOES_NOT_MATCH
+        );
+
+        let bottles = payment.amount.to_u64().unwrap_or(u64::MAX);
+        require!(
+            bottles <= (campaign.backed_bottles - campaign.delivered_bottles) as u64,
+            ERR_DELIVERY_EXCEEDS_THE_BOTTLES_BACKED
+        );
+        campaign.wine_nonce = payment.token_nonce;
+        campaign.delivered_bottles += bottles as u32;
+        self.campaigns(campaign_id).set(&campaign);
+
+        self.vintage_delivered_event(campaign_id, bottles as u32, campaign.delivered_bottles);
+    }
+
+    // Redeem allocations for the same number of delivered wine NFTs
+    #[payable("*")]
+    #[endpoint(claimWine)]
+    fn claim_wine(&self, campaign_id: u32) {
+        let mut campaign = self.require_campaign(campaign_id);
+        require!(campaign.status == CampaignStatus::Succeeded, ERR_CAMPAIGN_HAS_NOT_SUCCEEDED);
+        let amount = self.burn_allocations(campaign.allocation_nonce);
+        let bottles = amount.to_u64().unwrap_or(u64::MAX);
+        require!(
+            bottles <= (campaign.delivered_bottles - campaign.claimed_bottles) as u64,
+            ERR_NOT_ENOUGH_WINE_DELIVERED
+        );
+        campaign.claimed_bottles += bottles as u32;
+        self.campaigns(campaign_id).set(&campaign);
+
+        let holder = self.blockchain().get_caller();
+        let wine_token = self.wine_collection().get();
+        self.send().direct_esdt(&holder, &wine_token, campaign.wine_nonce, &amount);
+
+        self.wine_claimed_event(campaign_id, &holder, &amount);
+    }
+
+    // Redeem allocations of a failed campaign for their part of the unreleased funds
+    #[payable("*")]
+    #[endpoint(refundAllocations)]
+    fn refund_allocations(&self, campaign_id: u32) -> BigUint {
+        let campaign = self.require_campaign(campaign_id);
+        require!(campaign.status == CampaignStatus::Failed, ERR_CAMPAIGN_HAS_NOT_FAILED);
+        let amount = self.burn_allocations(campaign.allocation_nonce);
+
+        let raised = &campaign.price_per_bottle * campaign.backed_bottles;
+        let refund = &amount * &(raised - &campaign.released_amount) / campaign.backed_bottles;
+        let holder = self.blockchain().get_caller();
+        if refund > 0 {
+            self.send().direct(&holder, &campaign.payment_token, 0, &refund);
+        }
+
+        self.allocations_refunded_event(campaign_id, &holder, &amount, &refund);
+
+        refund
+    }
+
+    // Internal helpers
+    fn require_milestone_authority(&self, campaign: &Campaign<Self::Api>) {
+        let caller = self.blockchain().get_caller();
+        require!(
+            campaign.milestone_authority.as_ref() == Some(&caller)
+                && self.milestone_authorities().contains(&caller),
+            ERR_ONLY_THE_CAMPAIGN_S_MILESTONE_AUTHORITY_CAN_DO_THIS
+        );
+    }
+
+    // Funds unlocked so far; the last milestone unlocks the rounding remainder too
+    fn unlocked_amount(&self, campaign: &Campaign<Self::Api>) -> BigUint {
+        let raised = &campaign.price_per_bottle * campaign.backed_bottles;
+        if campaign.milestone_bps.len() == campaign.confirmed_milestones as usize {
+            return raised;
+        }
+        let mut unlocked_bps = 0u64;
+        for index in 0..campaign.confirmed_milestones as usize {
+            unlocked_bps += campaign.milestone_bps.get(index);
+        }
+        raised * unlocked_bps / 10_000u64
+    }
+
+    // Burn the allocation tokens paid to the current call and return their amount
+    fn burn_allocations(&self, allocation_nonce: u64) -> BigUint {
+        require!(allocation_nonce != 0, ERR_NO_ALLOCATIONS_WERE_SOLD);
+        let allocation_token = self.allocation_collection().get_token_id();
+        let mut amount = BigUint::zero();
+        for payment in self.call_value().all_esdt_transfers().iter() {
+            require!(
+                payment.token_identifier == allocation_token && payment.token_nonce == allocation_nonce,
+                ERR_INVALID_ALLOCATION_TOKEN
+            );
+            amount += &payment.amount;
+        }
+        require!(amount > 0, ERR_NO_ALLOCATIONS_PAID);
+        self.send().esdt_local_burn(&allocation_token, allocation_nonce, &amount);
+
+        amount
+    }
+
+    // View functions
+    // What `releaseFunds` would pay out now
+    #[view(getReleasableFunds)]
+    fn get_releasable_funds(&self, campaign_id: u32) -> BigUint {
+        let campaign = self.require_campaign(campaign_id);
+        if campaign.status != CampaignStatus::Succeeded {
+            return BigUint::zero();
+        }
+        self.unlocked_amount(&campaign) - &campaign.released_amount
+    }
+
+    // Events
+    #[event("milestoneConfirmed")]
+    fn milestone_confirmed_event(&self, #[indexed] campaign_id: u32, confirmed_milestones: u32);
+
+    #[event("fundsReleased")]
+    fn funds_released_event(&self, #[indexed] campaign_id: u32, amount: &BigUint);
+
+    #[event("vintageDelivered")]
+    fn vintage_delivered_event(
+        &self,
+        #[indexed] campaign_id: u32,
+        bottles: u32,
+        delivered_bottles: u32,
+    );
+
+    #[event("wineClaimed")]
+    fn wine_claimed_event(
+        &self,
+        #[indexed] campaign_id: u32,
+        #[indexed] holder: &ManagedAddress,
+        bottles: &BigUint,
+    );
+
+    #[event("allocationsRefunded")]
+    fn allocations_refunded_event(
+        &self,
+        #[indexed] campaign_id: u32,
+        #[indexed] holder: &ManagedAddress,
+        bottles: &BigUint,
+        refund: &BigUint,
+    );
+}
@@ -0,0 +1,340 @@
+use multiversx_sc::codec::top_encode_to_vec_u8_or_panic;
+use multiversx_sc_scenario::api::StaticApi;
+use multiversx_sc_scenario::imports::*;
+use wine_common::errors::*;
+use wine_crowdfunding::campaigns::ProxyTrait as _;
+use wine_crowdfunding::settlement::ProxyTrait as _;
+use wine_crowdfunding::ProxyTrait as _;
+use wine_crowdfunding::{Campaign, CampaignStatus, WineNftAttributes};
+
+const CROWDFUNDING_PATH_EXPR: &str = "mxsc:output/wine-crowdfunding.mxsc.json";
+const CROWDFUNDING_ADDRESS_EXPR: &str = "sc:wine-crowdfunding";
+const ESDT_SYSTEM_SC_ADDRESS_EXPR: &str = "0x000000000000000000010000000000000000000000000000000000000002ffff";
+const OWNER_ADDRESS_EXPR: &str = "address:owner";
+const PRODUCER_ADDRESS_EXPR: &str = "address:producer";
+const AUTHORITY_ADDRESS_EXPR: &str = "address:authority";
+const ALICE_ADDRESS_EXPR: &str = "address:alice";
+const BOB_ADDRESS_EXPR: &str = "address:bob";
+
+const WINE_NFT_TOKEN_ID_EXPR: &str = "str:WINE-abcdef";
+const ALLOCATION_TOKEN_ID: &str = "ALLOC-abcdef";
+const ALLOCATION_TOKEN_ID_EXPR: &str = "str:ALLOC-abcdef";
+
+const START_TIMESTAMP: u64 = 1_000;
+const DEADLINE: u64 = 2_000;
+const VINTAGE_YEAR: u32 = 2024;
+const PRICE_PER_BOTTLE: u64 = 10;
+
+type CrowdfundingContract = ContractInfo<wine_crowdfunding::Proxy<StaticApi>>;
+
+fn user_error(message: &str) -> TxExpect {
+    TxExpect::user_error(format!("str:{message}"))
+}
+
+fn encoded_wine_attributes(vintage_year: u32) -> Option<Vec<u8>> {
+    Some(top_encode_to_vec_u8_or_panic(&WineNftAttributes::<StaticApi> {
+        wine_id: 7,
+        vineyard: ManagedBuffer::from("Chateau Test"),
+        variety: ManagedBuffer::from("Merlot"),
+        vintage_year,
+        region: ManagedBuffer::from("Bordeaux"),
+        quality_grade: 9,
+        ipfs_hash: ManagedBuffer::new(),
+    }))
+}
+
+struct CrowdfundingTestState {
+    world: ScenarioWorld,
+    crowdfunding: CrowdfundingContract,
+}
+
+impl CrowdfundingTestState {
+    // A registered producer raising EGLD for the 2024 vintage at 10 per bottle, with a goal of
+    // three bottles out of five. The producer holds five bottles of the 2024 vintage as wine
+    // NFT 1 and one of 2023 as NFT 2; Alice and Bob have 1000 EGLD each.
+    fn new() -> Self {
+        let mut world = ScenarioWorld::new();
+        world.register_contract(CROWDFUNDING_PATH_EXPR, wine_crowdfunding::ContractBuilder);
+
+        let crowdfunding_code = world.code_expression(CROWDFUNDING_PATH_EXPR);
+        world.set_state_step(
+            SetStateStep::new()
+                .block_timestamp(START_TIMESTAMP)
+                .put_account(OWNER_ADDRESS_EXPR, Account::new().nonce(1).balance("50000"))
+                .put_account(
+                    PRODUCER_ADDRESS_EXPR,
+                    Account::new()
+                        .nonce(1)
+                        .esdt_nft_balance(WINE_NFT_TOKEN_ID_EXPR, 1, "5", encoded_wine_attributes(VINTAGE_YEAR))
+                        .esdt_nft_balance(WINE_NFT_TOKEN_ID_EXPR, 2, "1", encoded_wine_attributes(2023)),
+                )
+                .put_account(AUTHORITY_ADDRESS_EXPR, Account::new().nonce(1))
+                .put_account(ALICE_ADDRESS_EXPR, Account::new().nonce(1).balance("1000"))
+                .put_account(BOB_ADDRESS_EXPR, Account::new().nonce(1).balance("1000"))
+                .put_account(ESDT_SYSTEM_SC_ADDRESS_EXPR, Account::new().code(crowdfunding_code.clone()))
+                .new_address(OWNER_ADDRESS_EXPR, 1, CROWDFUNDING_ADDRESS_EXPR)
+                .new_token_identifier(ALLOCATION_TOKEN_ID),
+        );
+
+        let mut crowdfunding = CrowdfundingContract::new(CROWDFUNDING_ADDRESS_EXPR);
+        world.sc_deploy(
+            ScDeployStep::new()
+                .from(OWNER_ADDRESS_EXPR)
+                .code(crowdfunding_code)
+                .call(crowdfunding.init(TokenIdentifier::from("WINE-abcdef"))),
+        );
+        world.sc_call(
+            ScCallStep::new()
+                .from(OWNER_ADDRESS_EXPR)
+                .egld_value("50000")
+                .call(crowdfunding.issue_allocation_collection(
+                    ManagedBuffer::from("WineAllocation"),
+                    ManagedBuffer::from("ALLOC"),
+                )),
+        );
+
+        // Producers and milestone authorities are registered by an admin
+        let producer = AddressValue::from(PRODUCER_ADDRESS_EXPR).to_address();
+        let authority = AddressValue::from(AUTHORITY_ADDRESS_EXPR).to_address();
+        world.sc_call(
+            ScCallStep::new()
+                .from(PRODUCER_ADDRESS_EXPR)
+                .call(crowdfunding.add_campaign_producer(managed_address!(&producer)))
+                .expect(user_error(ERR_CALLER_DOES_NOT_HAVE_THE_REQUIRED_ROLE)),
+        );
+        world.sc_call(
+            ScCallStep::new()
+                .from(OWNER_ADDRESS_EXPR)
+                .call(crowdfunding.add_campaign_producer(managed_address!(&producer))),
+        );
+        world.sc_call(
+            ScCallStep::new()
+                .from(OWNER_ADDRESS_EXPR)
+                .call(crowdfunding.add_milestone_authority(managed_address!(&authority))),
+        );
+
+        let mut state = CrowdfundingTestState { world, crowdfunding };
+        let step = state.create_campaign(ALICE_ADDRESS_EXPR);
+        state
+            .world
+            .sc_call(step.expect(user_error(ERR_ONLY_CAMPAIGN_PRODUCERS_CAN_CREATE_CAMPAIGNS)));
+        let step = state.create_campaign(PRODUCER_ADDRESS_EXPR);
+        state.world.sc_call(step.expect_value(1u32));
+        state
+    }
+
+    fn set_block_timestamp(&mut self, timestamp: u64) {
+        self.world.set_state_step(SetStateStep::new().block_timestamp(timestamp));
+    }
+
+    fn create_campaign(&mut self, caller: &str) -> TypedScCall<u32> {
+        ScCallStep::new().from(caller).call(self.crowdfunding.create_campaign(
+            VINTAGE_YEAR,
+            EgldOrEsdtTokenIdentifier::egld(),
+            PRICE_PER_BOTTLE,
+            3u32,
+            5u32,
+            DEADLINE,
+        ))
+    }
+
+    // 40% of the funds on the first milestone, the rest on the second
+    fn set_milestones(&mut self) {
+        let authority = AddressValue::from(AUTHORITY_ADDRESS_EXPR).to_address();
+        self.world.sc_call(
+            ScCallStep::new()
+                .from(PRODUCER_ADDRESS_EXPR)
+                .call(self.crowdfunding.set_campaign_milestones(
+                    1u32,
+                    managed_address!(&authority),
+                    MultiValueVec::from(vec![4_000u64, 6_000]),
+                )),
+        );
+    }
+
+    fn contribute(&mut self, backer: &str, bottles: u32) -> TypedScCall<()> {
+        ScCallStep::new()
+            .from(backer)
+            .egld_value(PRICE_PER_BOTTLE * bottles as u64)
+            .call(self.crowdfunding.contribute(1u32, bottles))
+    }
+
+    fn release_funds(&mut self) -> TypedScCall<BigUint<StaticApi>> {
+        ScCallStep::new()
+            .from(PRODUCER_ADDRESS_EXPR)
+            .call(self.crowdfunding.release_funds(1u32))
+    }
+
+    fn refund_allocations(&mut self, holder: &str, bottles: &str) -> TypedScCall<BigUint<StaticApi>> {
+        ScCallStep::new()
+            .from(holder)
+            .esdt_transfer(ALLOCATION_TOKEN_ID_EXPR, 1, bottles)
+            .call(self.crowdfunding.refund_allocations(1u32))
+    }
+
+    fn campaign(&mut self) -> Campaign<StaticApi> {
+        self.world.quick_query(self.crowdfunding.get_campaign(1u32))
+    }
+}
+
+#[test]
+fn backed_vintage_releases_funds_by_milestone_and_delivers_the_wine() {
+    let mut state = CrowdfundingTestState::new();
+    state.set_milestones();
+
+    let step = state.contribute(ALICE_ADDRESS_EXPR, 2);
+    state.world.sc_call(step);
+    let step = state.contribute(BOB_ADDRESS_EXPR, 4);
+    state.world.sc_call(step.expect(user_error(ERR_NOT_ENOUGH_BOTTLES_LEFT)));
+    let step = state.contribute(BOB_ADDRESS_EXPR, 3);
+    state.world.sc_call(step);
+
+    // Fully backed, so the round settles before the deadline
+    state.world.sc_call(
+        ScCallStep::new()
+            .from(BOB_ADDRESS_EXPR)
+            .call(state.crowdfunding.finalize_campaign(1u32)),
+    );
+    assert_eq!(state.campaign().status, CampaignStatus::Succeeded);
+
+    let step = state.release_funds();
+    state.world.sc_call(step.expect(user_error(ERR_NOTHING_TO_RELEASE)));
+    state.world.sc_call(
+        ScCallStep::new()
+            .from(PRODUCER_ADDRESS_EXPR)
+            .call(state.crowdfunding.confirm_milestone(1u32))
+            .expect(user_error(ERR_ONLY_THE_CAMPAIGN_S_MILESTONE_AUTHORITY_CAN_DO_THIS)),
+    );
+    state.world.sc_call(
+        ScCallStep::new()
+            .from(AUTHORITY_ADDRESS_EXPR)
+            .call(state.crowdfunding.confirm_milestone(1u32)),
+    );
+    let step = state.release_funds();
+    state.world.sc_call(step.expect_value(BigUint::from(20u64)));
+
+    // Only wine of the backed vintage is delivered
+    state.world.sc_call(
+        ScCallStep::new()
+            .from(PRODUCER_ADDRESS_EXPR)
+            .esdt_transfer(WINE_NFT_TOKEN_ID_EXPR, 2, "1")
+            .call(state.crowdfunding.deliver_vintage(1u32))
+            .expect(user_error(ERR_DELIVERED_VINTAGE_DOES_NOT_MATCH)),
+    );
+    state.world.sc_call(
+        ScCallStep::new()
+            .from(PRODUCER_ADDRESS_EXPR)
+            .esdt_transfer(WINE_NFT_TOKEN_ID_EXPR, 1, "5")
+            .call(state.crowdfunding.deliver_vintage(1u32)),
+    );
+    state.world.sc_call(
+        ScCallStep::new()
+            .from(ALICE_ADDRESS_EXPR)
+            .esdt_transfer(ALLOCATION_TOKEN_ID_EXPR, 1, "2")
+            .call(state.crowdfunding.claim_wine(1u32)),
+    );
+
+    state.world.check_state_step(
+        CheckStateStep::new()
+            .put_account(PRODUCER_ADDRESS_EXPR, CheckAccount::new().balance("20"))
+            .put_account(
+                ALICE_ADDRESS_EXPR,
+                CheckAccount::new().balance("980").esdt_nft_balance_and_attributes(
+                    WINE_NFT_TOKEN_ID_EXPR,
+                    1,
+                    "2",
+                    encoded_wine_attributes(VINTAGE_YEAR),
+                ),
+            )
+            .put_account(
+                CROWDFUNDING_ADDRESS_EXPR,
+                CheckAccount::new().balance("30").esdt_nft_balance_and_attributes(
+                    WINE_NFT_TOKEN_ID_EXPR,
+                    1,
+                    "3",
+                    encoded_wine_attributes(VINTAGE_YEAR),
+                ),
+            ),
+    );
+}
+
+#[test]
+fn missed_goal_refunds_every_allocation() {
+    let mut state = CrowdfundingTestState::new();
+    let step = state.contribute(ALICE_ADDRESS_EXPR, 2);
+    state.world.sc_call(step);
+
+    state.world.sc_call(
+        ScCallStep::new()
+            .from(ALICE_ADDRESS_EXPR)
+            .call(state.crowdfunding.finalize_campaign(1u32))
+            .expect(user_error(ERR_CAMPAIGN_IS_STILL_FUNDING)),
+    );
+    state.set_block_timestamp(DEADLINE + 1);
+    let step = state.contribute(BOB_ADDRESS_EXPR, 1);
+    state.world.sc_call(step.expect(user_error(ERR_CAMPAIGN_HAS_ENDED)));
+    state.world.sc_call(
+        ScCallStep::new()
+            .from(ALICE_ADDRESS_EXPR)
+            .call(state.crowdfunding.finalize_campaign(1u32)),
+    );
+    assert_eq!(state.campaign().status, CampaignStatus::Failed);
+
+    let step = state.release_funds();
+    state.world.sc_call(step.expect(user_error(ERR_CAMPAIGN_HAS_NOT_SUCCEEDED)));
+    let step = state.refund_allocations(ALICE_ADDRESS_EXPR, "2");
+    state.world.sc_call(step.expect_value(BigUint::from(20u64)));
+    state.world.check_state_step(
+        CheckStateStep::new()
+            .put_account(ALICE_ADDRESS_EXPR, CheckAccount::new().balance("1000"))
+            .put_account(CROWDFUNDING_ADDRESS_EXPR, CheckAccount::new().balance("0")),
+    );
+}
+
+#[test]
+fn failed_vintage_refunds_what_was_not_released() {
+    let mut state = CrowdfundingTestState::new();
+    state.set_milestones();
+    let step = state.contribute(ALICE_ADDRESS_EXPR, 2);
+    state.world.sc_call(step);
+    let step = state.contribute(BOB_ADDRESS_EXPR, 3);
+    state.world.sc_call(step);
+    state.world.sc_call(
+        ScCallStep::new()
+            .from(ALICE_ADDRESS_EXPR)
+            .call(state.crowdfunding.finalize_campaign(1u32)),
+    );
+    state.world.sc_call(
+        ScCallStep::new()
+            .from(AUTHORITY_ADDRESS_EXPR)
+            .call(state.crowdfunding.confirm_milestone(1u32)),
+    );
+    let step = state.release_funds();
+    state.world.sc_call(step);
+
+    // A lost harvest; only the authority can call the vintage off
+    state.world.sc_call(
+        ScCallStep::new()
+            .from(PRODUCER_ADDRESS_EXPR)
+            .call(state.crowdfunding.fail_campaign(1u32))
+            .expect(user_error(ERR_ONLY_THE_CAMPAIGN_S_MILESTONE_AUTHORITY_CAN_DO_THIS)),
+    );
+    state.world.sc_call(
+        ScCallStep::new()
+            .from(AUTHORITY_ADDRESS_EXPR)
+            .call(state.crowdfunding.fail_campaign(1u32)),
+    );
+
+    // The 30 EGLD left are shared per allocation
+    let step = state.refund_allocations(ALICE_ADDRESS_EXPR, "2");
+    state.world.sc_call(step.expect_value(BigUint::from(12u64)));
+    let step = state.refund_allocations(BOB_ADDRESS_EXPR, "3");
+    state.world.sc_call(step.expect_value(BigUint::from(18u64)));
+    state.world.check_state_step(
+        CheckStateStep::new()
+            .put_account(PRODUCER_ADDRESS_EXPR, CheckAccount::new().balance("20"))
+            .put_account(ALICE_ADDRESS_EXPR, CheckAccount::new().balance("992"))
+            .put_account(BOB_ADDRESS_EXPR, CheckAccount::new().balance("988"))
+            .put_account(CROWDFUNDING_ADDRESS_EXPR, CheckAccount::new().balance("0")),
+    );
+}
@@ -0,0 +1,300 @@
+use multiversx_sc::derive_imports::*;
+use multiversx_sc::imports::*;
+use wine_common::errors::*;
+
+// Keeps the accounting views bounded
+pub const MAX_FEE_TOKENS: usize = 20;
+// Gas for feeding the staking and registry contracts
+pub const FEED_GAS: u64 = 15_000_000;
+
+#[derive(TopEncode, TopDecode, NestedEncode, NestedDecode, TypeAbi, PartialEq, Debug, Clone, Copy)]
+pub enum FeeDestination {
+    // Plain transfer to the protocol treasury
+    Treasury,
+    // `depositRewards` on the staking contract, which must list the router as a reward feeder
+    Staking,
+    // `fundRewardPool` on the registry, which must grant the router the FeeManager role
+    Registry,
+    // Plain transfer to a burn address
+    Burn,
+}
+
+pub const FEE_DESTINATIONS: [FeeDestination; 4] = [
+    FeeDestination::Treasury,
+    FeeDestination::Staking,
+    FeeDestination::Registry,
+    FeeDestination::Burn,
+];
+
+// Fees come in through `depositFees` and are split right away into per-destination pending
+// balances, per token. `distributeFees` pays them out; it can be called by anyone.
+#[multiversx_sc::module]
+pub trait DistributionModule: wine_roles::RolesModule {
+    #[endpoint(setDestinationAddress)]
+    fn set_destination_address(&self, destination: FeeDestination, address: ManagedAddress) {
+        self.require_owner("Only owner can set fee destinations");
+        require!(!address.is_zero(), ERR_INVALID_DESTINATION_ADDRESS);
+        if destination == FeeDestination::Staking || destination == FeeDestination::Registry {
+            require!(
+                self.blockchain().is_smart_contract(&address),
+                ERR_DESTINATION_MUST_BE_A_SMART_CONTRACT
+            );
+        }
+        self.destination_address(destination).set(&address);
+
+        self.destination_address_set_event(destination, &address);
+    }
+
+    // Relative weights; a destination with a weight needs an address. Only affects fees
+    // deposited from now on.
+    #[endpoint(setDestinationWeights)]
+    fn set_destination_weights(
+        &self,
+        treasury_weight: u64,
+        staking_weight: u64,
+        registry_weight: u64,
+        burn_weight: u64,
+    ) {
+        self.require_owner("Only owner can set fee weights");
+        let weights = [treasury_weight, staking_weight, registry_weight, burn_weight];
+        require!(weights.iter().any(|weight| *weight > 0), ERR_AT_LEAST_ONE_WEIGHT_MUST_BE_SET);
+        for (destination, weight) in FEE_DESTINATIONS.iter().zip(weights.iter()) {
+            require!(
+                *weight == 0 || !self.destination_address(*destination).is_empty(),
+                ERR_DESTINATION_ADDRESS_NOT_SET
+            );
+            self.destination_weight(*destination).set(*weight);
+        }
+
+        self.destination_weights_set_event(
+            treasury_weight,
+            staking_weight,
+            registry_weight,
+            burn_weight,
+        );
+    }
+
+    // Fungible fees in EGLD or any ESDT, e.g. from the marketplace
+    #[payable("*")]
+    #[endpoint(depositFees)]
+    fn deposit_fees(&self) {
+        self.require_not_paused();
+        let payment = self.call_value().egld_or_single_esdt();
+        require!(payment.token_nonce == 0, ERR_FEES_MUST_BE_FUNGIBLE);
+        require!(payment.amount > 0, ERR_AMOUNT_MUST_BE_GREATER_THAN_0);
+
+        self.credit_fees(&payment.token_identifier, &payment.amount);
+
+        let caller = self.blockchain().get_caller();
+        self.fees_deposited_event(&caller, &payment.token_identifier, &payment.amount);
+    }
+
+    // Split balance that arrived outside `depositFees`, e.g. plain transfers or a feed the
+    // staking contract or registry rejected, as if it had been deposited
+    #[endpoint(creditUnaccountedFees)]
+    fn credit_unaccounted_fees(&self, token: EgldOrEsdtTokenIdentifier) -> BigUint {
+        self.require_not_paused();
+        let mut accounted = BigUint::zero();
+        for destination in FEE_DESTINATIONS.iter() {
+            accounted += self.pending_fees(*destination, &token).get();
+        }
+        let balance = self.blockchain().get_sc_balance(&token, 0);
+        require!(balance > accounted, ERR_NO_UNACCOUNTED_FEES);
+        let unaccounted = balance - accounted;
+
+        self.credit_fees(&token, &unaccounted);
+
+        self.fees_deposited_event(&self.blockchain().get_caller(), &token, &unaccounted);
+
+        unaccounted
+    }
+
+    #[endpoint(distributeFees)]
+    fn distribute_fees(&self, token: EgldOrEsdtTokenIdentifier) {
+        self.require_not_paused();
+        let mut distributed_any = false;
+        for destination in FEE_DESTINATIONS.iter() {
+            let amount = self.pending_fees(*destination, &token).take();
+            if amount == 0 {
+                continue;
+            }
+            distributed_any = true;
+            let address = self.destination_address(*destination).get();
+            match destination {
+                FeeDestination::Staking => {
+                    self.feed_destination(&address, &token, &amount, "depositRewards")
+                },
+                FeeDestination::Registry => {
+                    self.feed_destination(&address, &token, &amount, "fundRewardPool")
+                },
+                FeeDestination::Treasury | FeeDestination::Burn => {
+                    self.send().direct(&address, &token, 0, &amount)
+                },
+            }
+            self.total_distributed(*destination, &token).update(|total| *total += &amount);
+
+            self.fees_distributed_event(*destination, &token, &address, &amount);
+        }
+        require!(distributed_any, ERR_NO_PENDING_FEES);
+    }
+
+    // Internal helpers
+    fn require_owner(&self, error: &str) {
+        require!(self.blockchain().get_caller() == self.blockchain().get_owner_address(), error);
+    }
+
+    // Split by weight; the rounding remainder goes to the last weighted destination
+    fn credit_fees(&self, token: &EgldOrEsdtTokenIdentifier, amount: &BigUint) {
+        let total_weight: u64 = FEE_DESTINATIONS
+            .iter()
+            .map(|destination| self.destination_weight(*destination).get())
+            .sum();
+        require!(total_weight > 0, ERR_FEE_WEIGHTS_NOT_SET);
+        if !self.fee_tokens().contains(token) {
+            require!(self.fee_tokens().len() < MAX_FEE_TOKENS, ERR_TOO_MANY_FEE_TOKENS);
+            self.fee_tokens().insert(token.clone());
+        }
+
+        let mut remaining = amount.clone();
+        let mut remaining_weight = total_weight;
+        for destination in FEE_DESTINATIONS.iter() {
+            let weight = self.destination_weight(*destination).get();
+            if weight == 0 {
+                continue;
+            }
+            remaining_weight -= weight;
+            let share = if remaining_weight == 0 {
+                remaining.clone()
+            } else {
+                amount * weight / total_weight
+            };
+            remaining -= &share;
+            self.pending_fees(*destination, token).update(|pending| *pending += share);
+        }
+        self.total_received(token).update(|total| *total += amount);
+    }
+
+    // Fire-and-forget; a rejected feed comes back as unaccounted balance
+    fn feed_destination(
+        &self,
+        address: &ManagedAddress,
+        token: &EgldOrEsdtTokenIdentifier,
+        amount: &BigUint,
+        endpoint: &str,
+    ) {
+        self.tx()
+            .to(address)
+            .gas(FEED_GAS)
+            .egld_or_single_esdt(token, 0, amount)
+            .raw_call(endpoint)
+            .transfer_execute();
+    }
+
+    // View functions
+    // (destination, address, weight) for every destination with an address
+    #[view(getFeeDestinations)]
+    fn get_fee_destinations(
+        &self,
+    ) -> MultiValueEncoded<MultiValue3<FeeDestination, ManagedAddress, u64>> {
+        let mut result = MultiValueEncoded::new();
+        for destination in FEE_DESTINATIONS.iter() {
+            if !self.destination_address(*destination).is_empty() {
+                let address = self.destination_address(*destination).get();
+                let weight = self.destination_weight(*destination).get();
+                result.push((*destination, address, weight).into());
+            }
+        }
+        result
+    }
+
+    #[view(getPendingFees)]
+    fn get_pending_fees(
+        &self,
+        token: EgldOrEsdtTokenIdentifier,
+    ) -> MultiValueEncoded<MultiValue2<FeeDestination, BigUint>> {
+        let mut result = MultiValueEncoded::new();
+        for destination in FEE_DESTINATIONS.iter() {
+            result.push((*destination, self.pending_fees(*destination, &token).get()).into());
+        }
+        result
+    }
+
+    #[view(getTotalDistributed)]
+    fn get_total_distributed(
+        &self,
+        token: EgldOrEsdtTokenIdentifier,
+    ) -> MultiValueEncoded<MultiValue2<FeeDestination, BigUint>> {
+        let mut result = MultiValueEncoded::new();
+        for destination in FEE_DESTINATIONS.iter() {
+            result.push((*destination, self.total_distributed(*destination, &token).get()).into());
+        }
+        result
+    }
+
+    #[view(getFeeTokens)]
+    fn get_fee_tokens(&self) -> MultiValueEncoded<EgldOrEsdtTokenIdentifier> {
+        self.fee_tokens().iter().collect()
+    }
+
+    // Storage mappers
+    #[storage_mapper("destinationAddress")]
+    fn destination_address(&self, destination: FeeDestination) -> SingleValueMapper<ManagedAddress>;
+
+    #[storage_mapper("destinationWeight")]
+    fn destination_weight(&self, destination: FeeDestination) -> SingleValueMapper<u64>;
+
+    #[storage_mapper("pendingFees")]
+    fn pending_fees(
+        &self,
+        destination: FeeDestination,
+        token: &EgldOrEsdtTokenIdentifier,
+    ) -> SingleValueMapper<BigUint>;
+
+    #[view(getTotalReceived)]
+    #[storage_mapper("totalReceived")]
+    fn total_received(&self, token: &EgldOrEsdtTokenIdentifier) -> SingleValueMapper<BigUint>;
+
+    #[storage_mapper("totalDistributed")]
+    fn total_distributed(
+        &self,
+        destination: FeeDestination,
+        token: &EgldOrEsdtTokenIdentifier,
+    ) -> SingleValueMapper<BigUint>;
+
+    #[storage_mapper("feeTokens")]
+    fn fee_tokens(&self) -> UnorderedSetMapper<EgldOrEsdtTokenIdentifier>;
+
+    // Events
+    #[event("destinationAddressSet")]
+    fn destination_address_set_event(
+        &self,
+        #[indexed] destination: FeeDestination,
+        #[indexed] address: &ManagedAddress,
+    );
+
+    #[event("destinationWeightsSet")]
+    fn destination_weights_set_event(
+        &self,
+        #[indexed] treasury_weight: u64,
+        #[indexed] staking_weight: u64,
+        #[indexed] registry_weight: u64,
+        #[indexed] burn_weight: u64,
+    );
+
+    #[event("feesDeposited")]
+    fn fees_deposited_event(
+        &self,
+        #[indexed] depositor: &ManagedAddress,
+        #[indexed] token: &EgldOrEsdtTokenIdentifier,
+        amount: &BigUint,
+    );
+
+    #[event("feesDistributed")]
+    fn fees_distributed_event(
+        &self,
+        #[indexed] destination: FeeDestination,
+        #[indexed] token: &EgldOrEsdtTokenIdentifier,
+        #[indexed] recipient: &ManagedAddress,
+        amount: &BigUint,
+    );
+}
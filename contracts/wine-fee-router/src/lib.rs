@@ -0,0 +1,23 @@
+#![no_std]
+
+use multiversx_sc::imports::*;
+use wine_common::errors::*;
+
+pub mod distribution;
+
+pub use distribution::FeeDestination;
+
+// Receives marketplace fees and splits them between the treasury, staking rewards, registry
+// reward funding and a burn address, by owner-configured weights
+#[multiversx_sc::contract]
+pub trait WineFeeRouter: distribution::DistributionModule + wine_roles::RolesModule {
+    #[init]
+    fn init(&self, treasury: ManagedAddress) {
+        require!(!treasury.is_zero(), ERR_INVALID_TREASURY_ADDRESS);
+        self.destination_address(FeeDestination::Treasury).set(&treasury);
+        self.destination_weight(FeeDestination::Treasury).set(1u64);
+    }
+
+    #[upgrade]
+    fn upgrade(&self) {}
+}
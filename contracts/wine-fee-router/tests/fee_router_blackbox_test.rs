@@ -0,0 +1,250 @@
+use multiversx_sc_scenario::api::StaticApi;
+use multiversx_sc_scenario::imports::*;
+use wine_common::errors::*;
+use wine_fee_router::distribution::ProxyTrait as _;
+use wine_fee_router::FeeDestination;
+use wine_fee_router::ProxyTrait as _;
+use wine_roles::ProxyTrait as _;
+use wine_staking::rewards::ProxyTrait as _;
+use wine_staking::ProxyTrait as _;
+
+const FEE_ROUTER_PATH_EXPR: &str = "mxsc:output/wine-fee-router.mxsc.json";
+const STAKING_PATH_EXPR: &str = "mxsc:../wine-staking/output/wine-staking.mxsc.json";
+const FEE_ROUTER_ADDRESS_EXPR: &str = "sc:wine-fee-router";
+const STAKING_ADDRESS_EXPR: &str = "sc:wine-staking";
+const OWNER_ADDRESS_EXPR: &str = "address:owner";
+const TREASURY_ADDRESS_EXPR: &str = "address:treasury";
+const BURN_ADDRESS_EXPR: &str = "address:burn";
+const MARKETPLACE_ADDRESS_EXPR: &str = "address:marketplace";
+
+const WINE_NFT_TOKEN_ID_EXPR: &str = "str:WINE-abcdef";
+
+type FeeRouterContract = ContractInfo<wine_fee_router::Proxy<StaticApi>>;
+type StakingContract = ContractInfo<wine_staking::Proxy<StaticApi>>;
+
+fn user_error(message: &str) -> TxExpect {
+    TxExpect::user_error(format!("str:{message}"))
+}
+
+struct FeeRouterTestState {
+    world: ScenarioWorld,
+    fee_router: FeeRouterContract,
+    staking: StakingContract,
+}
+
+impl FeeRouterTestState {
+    // A router splitting fees 50/30/20 between the treasury, a staking contract paying EGLD
+    // rewards and a burn address. The marketplace has 5000 EGLD of fees to deposit.
+    fn new() -> Self {
+        let mut world = ScenarioWorld::new();
+        world.register_contract(FEE_ROUTER_PATH_EXPR, wine_fee_router::ContractBuilder);
+        world.register_contract(STAKING_PATH_EXPR, wine_staking::ContractBuilder);
+
+        world.set_state_step(
+            SetStateStep::new()
+                .block_epoch(1)
+                .put_account(OWNER_ADDRESS_EXPR, Account::new().nonce(1))
+                .put_account(TREASURY_ADDRESS_EXPR, Account::new().nonce(1))
+                .put_account(BURN_ADDRESS_EXPR, Account::new().nonce(1))
+                .put_account(
+                    MARKETPLACE_ADDRESS_EXPR,
+                    Account::new()
+                        .nonce(1)
+                        .balance("5000")
+                        .esdt_nft_balance(WINE_NFT_TOKEN_ID_EXPR, 1, "1", Option::<&str>::None),
+                )
+                .new_address(OWNER_ADDRESS_EXPR, 1, FEE_ROUTER_ADDRESS_EXPR)
+                .new_address(OWNER_ADDRESS_EXPR, 2, STAKING_ADDRESS_EXPR),
+        );
+
+        let mut fee_router = FeeRouterContract::new(FEE_ROUTER_ADDRESS_EXPR);
+        let fee_router_code = world.code_expression(FEE_ROUTER_PATH_EXPR);
+        let treasury = AddressValue::from(TREASURY_ADDRESS_EXPR).to_address();
+        world.sc_deploy(
+            ScDeployStep::new()
+                .from(OWNER_ADDRESS_EXPR)
+                .code(fee_router_code)
+                .call(fee_router.init(managed_address!(&treasury))),
+        );
+        let mut staking = StakingContract::new(STAKING_ADDRESS_EXPR);
+        let staking_code = world.code_expression(STAKING_PATH_EXPR);
+        world.sc_deploy(
+            ScDeployStep::new()
+                .from(OWNER_ADDRESS_EXPR)
+                .code(staking_code)
+                .call(staking.init(
+                    TokenIdentifier::from("STAKE-abcdef"),
+                    EgldOrEsdtTokenIdentifier::egld(),
+                    2u64,
+                )),
+        );
+        let fee_router_address = AddressValue::from(FEE_ROUTER_ADDRESS_EXPR).to_address();
+        world.sc_call(
+            ScCallStep::new()
+                .from(OWNER_ADDRESS_EXPR)
+                .call(staking.add_reward_feeder(managed_address!(&fee_router_address))),
+        );
+
+        // Destinations and weights are set by the owner only
+        let staking_address = AddressValue::from(STAKING_ADDRESS_EXPR).to_address();
+        let burn = AddressValue::from(BURN_ADDRESS_EXPR).to_address();
+        world.sc_call(
+            ScCallStep::new()
+                .from(MARKETPLACE_ADDRESS_EXPR)
+                .call(fee_router.set_destination_address(FeeDestination::Burn, managed_address!(&burn)))
+                .expect(user_error("Only owner can set fee destinations")),
+        );
+        world.sc_call(
+            ScCallStep::new()
+                .from(OWNER_ADDRESS_EXPR)
+                .call(fee_router.set_destination_address(FeeDestination::Staking, managed_address!(&burn)))
+                .expect(user_error(ERR_DESTINATION_MUST_BE_A_SMART_CONTRACT)),
+        );
+        let destinations = [(FeeDestination::Staking, &staking_address), (FeeDestination::Burn, &burn)];
+        for (destination, address) in destinations {
+            world.sc_call(
+                ScCallStep::new()
+                    .from(OWNER_ADDRESS_EXPR)
+                    .call(fee_router.set_destination_address(destination, managed_address!(address))),
+            );
+        }
+        world.sc_call(
+            ScCallStep::new()
+                .from(MARKETPLACE_ADDRESS_EXPR)
+                .call(fee_router.set_destination_weights(50u64, 30u64, 0u64, 20u64))
+                .expect(user_error("Only owner can set fee weights")),
+        );
+        world.sc_call(
+            ScCallStep::new()
+                .from(OWNER_ADDRESS_EXPR)
+                .call(fee_router.set_destination_weights(50u64, 30u64, 10u64, 20u64))
+                .expect(user_error(ERR_DESTINATION_ADDRESS_NOT_SET)),
+        );
+        world.sc_call(
+            ScCallStep::new()
+                .from(OWNER_ADDRESS_EXPR)
+                .call(fee_router.set_destination_weights(50u64, 30u64, 0u64, 20u64)),
+        );
+
+        FeeRouterTestState { world, fee_router, staking }
+    }
+
+    fn deposit_fees(&mut self, amount: u64) -> TypedScCall<()> {
+        ScCallStep::new()
+            .from(MARKETPLACE_ADDRESS_EXPR)
+            .egld_value(amount)
+            .call(self.fee_router.deposit_fees())
+    }
+
+    fn distribute_fees(&mut self) -> TypedScCall<()> {
+        ScCallStep::new()
+            .from(TREASURY_ADDRESS_EXPR)
+            .call(self.fee_router.distribute_fees(EgldOrEsdtTokenIdentifier::egld()))
+    }
+
+    fn pending_fees(&mut self) -> Vec<(FeeDestination, BigUint<StaticApi>)> {
+        let pending: MultiValueVec<MultiValue2<FeeDestination, BigUint<StaticApi>>> =
+            self.world.quick_query(self.fee_router.get_pending_fees(EgldOrEsdtTokenIdentifier::egld()));
+        pending.into_vec().into_iter().map(|entry| entry.into_tuple()).collect()
+    }
+}
+
+#[test]
+fn fees_are_split_by_weight_and_paid_out_by_anyone() {
+    let mut state = FeeRouterTestState::new();
+
+    state.world.sc_call(
+        ScCallStep::new()
+            .from(MARKETPLACE_ADDRESS_EXPR)
+            .esdt_transfer(WINE_NFT_TOKEN_ID_EXPR, 1, "1")
+            .call(state.fee_router.deposit_fees())
+            .expect(user_error(ERR_FEES_MUST_BE_FUNGIBLE)),
+    );
+    let step = state.distribute_fees();
+    state.world.sc_call(step.expect(user_error(ERR_NO_PENDING_FEES)));
+
+    // The rounding remainder goes to the burn address, the last weighted destination
+    let step = state.deposit_fees(1_001);
+    state.world.sc_call(step);
+    assert_eq!(
+        state.pending_fees(),
+        vec![
+            (FeeDestination::Treasury, BigUint::from(500u64)),
+            (FeeDestination::Staking, BigUint::from(300u64)),
+            (FeeDestination::Registry, BigUint::zero()),
+            (FeeDestination::Burn, BigUint::from(201u64)),
+        ]
+    );
+
+    let step = state.distribute_fees();
+    state.world.sc_call(step);
+    let step = state.distribute_fees();
+    state.world.sc_call(step.expect(user_error(ERR_NO_PENDING_FEES)));
+
+    // The staking contract was fed through `depositRewards`
+    let epoch_rewards: BigUint<StaticApi> = state.world.quick_query(state.staking.get_epoch_rewards(1u64));
+    assert_eq!(epoch_rewards, 300u64);
+    state.world.check_state_step(
+        CheckStateStep::new()
+            .put_account(TREASURY_ADDRESS_EXPR, CheckAccount::new().balance("500"))
+            .put_account(STAKING_ADDRESS_EXPR, CheckAccount::new().balance("300"))
+            .put_account(BURN_ADDRESS_EXPR, CheckAccount::new().balance("201"))
+            .put_account(FEE_ROUTER_ADDRESS_EXPR, CheckAccount::new().balance("0")),
+    );
+}
+
+#[test]
+fn weight_changes_only_affect_later_deposits() {
+    let mut state = FeeRouterTestState::new();
+    let step = state.deposit_fees(1_000);
+    state.world.sc_call(step);
+
+    state.world.sc_call(
+        ScCallStep::new()
+            .from(OWNER_ADDRESS_EXPR)
+            .call(state.fee_router.set_destination_weights(0u64, 0u64, 0u64, 0u64))
+            .expect(user_error(ERR_AT_LEAST_ONE_WEIGHT_MUST_BE_SET)),
+    );
+    state.world.sc_call(
+        ScCallStep::new()
+            .from(OWNER_ADDRESS_EXPR)
+            .call(state.fee_router.set_destination_weights(1u64, 0u64, 0u64, 0u64)),
+    );
+    let step = state.deposit_fees(1_000);
+    state.world.sc_call(step);
+    let step = state.distribute_fees();
+    state.world.sc_call(step);
+
+    state.world.check_state_step(
+        CheckStateStep::new()
+            .put_account(TREASURY_ADDRESS_EXPR, CheckAccount::new().balance("1500"))
+            .put_account(STAKING_ADDRESS_EXPR, CheckAccount::new().balance("300"))
+            .put_account(BURN_ADDRESS_EXPR, CheckAccount::new().balance("200")),
+    );
+}
+
+#[test]
+fn nothing_moves_while_paused() {
+    let mut state = FeeRouterTestState::new();
+    let step = state.deposit_fees(1_000);
+    state.world.sc_call(step);
+
+    state.world.sc_call(
+        ScCallStep::new()
+            .from(MARKETPLACE_ADDRESS_EXPR)
+            .call(state.fee_router.pause())
+            .expect(user_error(ERR_CALLER_DOES_NOT_HAVE_THE_REQUIRED_ROLE)),
+    );
+    state.world.sc_call(ScCallStep::new().from(OWNER_ADDRESS_EXPR).call(state.fee_router.pause()));
+    let step = state.deposit_fees(1_000);
+    state.world.sc_call(step.expect(user_error(ERR_CONTRACT_IS_PAUSED)));
+    let step = state.distribute_fees();
+    state.world.sc_call(step.expect(user_error(ERR_CONTRACT_IS_PAUSED)));
+
+    state.world.sc_call(ScCallStep::new().from(OWNER_ADDRESS_EXPR).call(state.fee_router.unpause()));
+    let step = state.distribute_fees();
+    state.world.sc_call(step);
+    state.world.check_state_step(
+        CheckStateStep::new().put_account(FEE_ROUTER_ADDRESS_EXPR, CheckAccount::new().balance("0")),
+    );
+}
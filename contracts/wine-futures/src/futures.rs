@@ -0,0 +1,277 @@
+use multiversx_sc::derive_imports::*;
+use multiversx_sc::imports::*;
+use wine_common::errors::*;
+use wine_roles::Role;
+
+#[derive(TopEncode, TopDecode, NestedEncode, NestedDecode, TypeAbi, PartialEq, Debug, Clone, Copy)]
+pub enum FutureStatus {
+    // Selling claims and awaiting delivery
+    Open,
+    // Every sold bottle was delivered and the producer paid
+    Delivered,
+    // Withdrawn by the producer; claims are refunded
+    Cancelled,
+}
+
+// An en primeur offering of one registered wine
+#[derive(TopEncode, TopDecode, TypeAbi, PartialEq, Debug)]
+pub struct WineFuture<M: ManagedTypeApi> {
+    pub producer: ManagedAddress<M>,
+    pub wine_id: u32,
+    pub payment_token: EgldOrEsdtTokenIdentifier<M>,
+    pub price_per_bottle: BigUint<M>,
+    pub total_bottles: u32,
+    pub sold_bottles: u32,
+    // No claims are sold after this timestamp
+    pub sale_end: u64,
+    // Sold bottles must all be delivered by this timestamp
+    pub delivery_deadline: u64,
+    // Claim token nonce, 0 until the first sale
+    pub claim_nonce: u64,
+    // Wine NFT nonce, 0 until the first delivery
+    pub wine_nonce: u64,
+    pub delivered_bottles: u32,
+    pub status: FutureStatus,
+}
+
+// On-chain attributes of a claim token; one unit is a claim on one bottle
+#[derive(TopEncode, TopDecode, TypeAbi, PartialEq, Debug)]
+pub struct FutureClaimAttributes {
+    pub future_id: u32,
+    pub wine_id: u32,
+}
+
+// Claims are units of one SFT collection, one nonce per future
+#[multiversx_sc::module]
+pub trait FuturesModule: wine_roles::RolesModule {
+    // Issue the claim SFT collection with all roles assigned to this contract. Requires the
+    // issue cost in EGLD.
+    #[payable("EGLD")]
+    #[endpoint(issueClaimCollection)]
+    fn issue_claim_collection(&self, token_display_name: ManagedBuffer, token_ticker: ManagedBuffer) {
+        self.require_role(Role::Admin);
+        let issue_cost = self.call_value().egld_value().clone_value();
+        self.claim_collection().issue_and_set_all_roles(
+            EsdtTokenType::SemiFungible,
+            issue_cost,
+            token_display_name,
+            token_ticker,
+            0,
+            Some(self.callbacks().claim_collection_issued()),
+        );
+    }
+
+    // Only wines of this collection can be delivered
+    #[endpoint(setWineCollection)]
+    fn set_wine_collection(&self, wine_collection: TokenIdentifier) {
+        self.require_role(Role::Admin);
+        require!(wine_collection.is_valid_esdt_identifier(), ERR_INVALID_WINE_COLLECTION);
+        self.wine_collection().set(&wine_collection);
+    }
+
+    // Offer claims on up to `total_bottles` bottles of a registered wine. Delivery must be
+    // in NFTs of that wine, so only its owner can fulfil the future.
+    #[endpoint(createFuture)]
+    fn create_future(
+        &self,
+        wine_id: u32,
+        payment_token: EgldOrEsdtTokenIdentifier,
+        price_per_bottle: BigUint,
+        total_bottles: u32,
+        sale_end: u64,
+        delivery_deadline: u64,
+    ) -> u32 {
+        self.require_not_paused();
+        require!(payment_token.is_valid(), ERR_INVALID_PAYMENT_TOKEN);
+        require!(price_per_bottle > 0, ERR_PRICE_PER_BOTTLE_MUST_BE_GREATER_THAN_0);
+        require!(total_bottles > 0, ERR_TOTAL_BOTTLES_MUST_BE_GREATER_THAN_0);
+        require!(
+            sale_end > self.blockchain().get_block_timestamp(),
+            ERR_SALE_END_MUST_BE_IN_THE_FUTURE
+        );
+        require!(
+            delivery_deadline >= sale_end,
+            ERR_DELIVERY_DEADLINE_MUST_NOT_PRECEDE_THE_SALE_END
+        );
+
+        let producer = self.blockchain().get_caller();
+        let future_id = self.future_counter().get();
+        self.future_counter().set(future_id + 1);
+        self.futures(future_id).set(&WineFuture {
+            producer: producer.clone(),
+            wine_id,
+            payment_token,
+            price_per_bottle,
+            total_bottles,
+            sold_bottles: 0,
+            sale_end,
+            delivery_deadline,
+            claim_nonce: 0,
+            wine_nonce: 0,
+            delivered_bottles: 0,
+            status: FutureStatus::Open,
+        });
+
+        self.future_created_event(future_id, &producer, wine_id, total_bottles);
+
+        future_id
+    }
+
+    // Buy claims on `quantity` bottles, paying exactly their price
+    #[payable("*")]
+    #[endpoint(buyFuture)]
+    fn buy_future(&self, future_id: u32, quantity: u32) {
+        self.require_not_paused();
+        let mut future = self.require_future(future_id);
+        require!(future.status == FutureStatus::Open, ERR_FUTURE_IS_NOT_OPEN);
+        require!(self.blockchain().get_block_timestamp() <= future.sale_end, ERR_SALE_HAS_ENDED);
+        require!(quantity > 0, ERR_QUANTITY_MUST_BE_GREATER_THAN_0);
+        require!(
+            quantity <= future.total_bottles - future.sold_bottles,
+            ERR_NOT_ENOUGH_BOTTLES_LEFT
+        );
+        require!(!self.claim_collection().is_empty(), ERR_CLAIM_COLLECTION_NOT_ISSUED);
+
+        let payment = self.call_value().egld_or_single_esdt();
+        require!(payment.token_identifier == future.payment_token, ERR_INVALID_PAYMENT_TOKEN);
+        require!(
+            payment.amount == &future.price_per_bottle * quantity,
+            ERR_INCORRECT_PAYMENT_AMOUNT
+        );
+
+        let token_id = self.claim_collection().get_token_id();
+        let amount = BigUint::from(quantity);
+        if future.claim_nonce == 0 {
+            let attributes = FutureClaimAttributes {
+                future_id,
+                wine_id: future.wine_id,
+            };
+            future.claim_nonce = self.send().esdt_nft_create(
+                &token_id,
+                &amount,
+                &sc_format!("Wine {} en primeur", future.wine_id),
+                &BigUint::zero(),
+                &ManagedBuffer::new(),
+                &attributes,
+                &ManagedVec::new(),
+            );
+        } else {
+            self.send().esdt_local_mint(&token_id, future.claim_nonce, &amount);
+        }
+        future.sold_bottles += quantity;
+        self.futures(future_id).set(&future);
+
+        let buyer = self.blockchain().get_caller();
+        self.send().direct_esdt(&buyer, &token_id, future.claim_nonce, &amount);
+
+        self.future_bought_event(future_id, &buyer, quantity);
+    }
+
+    // Withdraw an undelivered future; every claim becomes refundable
+    #[endpoint(cancelFuture)]
+    fn cancel_future(&self, future_id: u32) {
+        let mut future = self.require_future(future_id);
+        require!(
+            self.blockchain().get_caller() == future.producer,
+            ERR_ONLY_PRODUCER_CAN_CANCEL_THE_FUTURE
+        );
+        require!(future.status == FutureStatus::Open, ERR_FUTURE_IS_NOT_OPEN);
+
+        future.status = FutureStatus::Cancelled;
+        self.futures(future_id).set(&future);
+
+        self.future_cancelled_event(future_id);
+    }
+
+    // Internal helpers
+    fn require_future(&self, future_id: u32) -> WineFuture<Self::Api> {
+        require!(!self.futures(future_id).is_empty(), ERR_FUTURE_DOES_NOT_EXIST);
+        self.futures(future_id).get()
+    }
+
+    // Cancelled, or still open past the delivery deadline
+    fn is_delivery_failed(&self, future: &WineFuture<Self::Api>) -> bool {
+        match future.status {
+            FutureStatus::Open => self.blockchain().get_block_timestamp() > future.delivery_deadline,
+            FutureStatus::Delivered => false,
+            FutureStatus::Cancelled => true,
+        }
+    }
+
+    #[callback]
+    fn claim_collection_issued(&self, #[call_result] result: ManagedAsyncCallResult<TokenIdentifier>) {
+        match result {
+            ManagedAsyncCallResult::Ok(token_id) => {
+                self.claim_collection().set_token_id(token_id);
+            },
+            ManagedAsyncCallResult::Err(_) => {
+                self.claim_collection().clear();
+            },
+        }
+    }
+
+    // View functions
+    #[view(getClaimCollection)]
+    fn get_claim_collection(&self) -> OptionalValue<TokenIdentifier> {
+        if self.claim_collection().get_token_state().is_set() {
+            OptionalValue::Some(self.claim_collection().get_token_id())
+        } else {
+            OptionalValue::None
+        }
+    }
+
+    #[view(getWineCollection)]
+    fn get_wine_collection(&self) -> TokenIdentifier {
+        self.wine_collection().get()
+    }
+
+    #[view(getFuture)]
+    fn get_future(&self, future_id: u32) -> WineFuture<Self::Api> {
+        self.require_future(future_id)
+    }
+
+    #[view(getFutureCount)]
+    fn get_future_count(&self) -> u32 {
+        self.future_counter().get() - 1
+    }
+
+    // Claims of a failed future are refunded at the purchase price
+    #[view(isDeliveryFailed)]
+    fn is_delivery_failed_view(&self, future_id: u32) -> bool {
+        self.is_delivery_failed(&self.require_future(future_id))
+    }
+
+    // Storage mappers
+    #[storage_mapper("claimCollection")]
+    fn claim_collection(&self) -> NonFungibleTokenMapper;
+
+    #[storage_mapper("wineCollection")]
+    fn wine_collection(&self) -> SingleValueMapper<TokenIdentifier>;
+
+    #[storage_mapper("futureCounter")]
+    fn future_counter(&self) -> SingleValueMapper<u32>;
+
+    #[storage_mapper("futures")]
+    fn futures(&self, future_id: u32) -> SingleValueMapper<WineFuture<Self::Api>>;
+
+    // Events
+    #[event("futureCreated")]
+    fn future_created_event(
+        &self,
+        #[indexed] future_id: u32,
+        #[indexed] producer: &ManagedAddress,
+        #[indexed] wine_id: u32,
+        total_bottles: u32,
+    );
+
+    #[event("futureBought")]
+    fn future_bought_event(
+        &self,
+        #[indexed] future_id: u32,
+        #[indexed] buyer: &ManagedAddress,
+        quantity: u32,
+    );
+
+    #[event("futureCancelled")]
+    fn future_cancelled_event(&self, #[indexed] future_id: u32);
+}
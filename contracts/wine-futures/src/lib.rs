@@ -0,0 +1,29 @@
+#![no_std]
+
+use multiversx_sc::imports::*;
+use wine_common::errors::*;
+
+pub mod futures;
+pub mod settlement;
+
+pub use futures::{FutureClaimAttributes, FutureStatus, WineFuture};
+pub use settlement::WineNftAttributes;
+
+// En primeur sales. Producers sell claims on a registered wine before it is bottled; buyers
+// pay into escrow and receive transferable claim tokens. The producer is paid once the wine
+// NFTs are delivered, and claims then redeem bottle for bottle. If delivery misses its
+// deadline, claims are refunded at the purchase price.
+#[multiversx_sc::contract]
+pub trait WineFutures:
+    futures::FuturesModule + settlement::SettlementModule + wine_roles::RolesModule
+{
+    #[init]
+    fn init(&self, wine_collection: TokenIdentifier) {
+        require!(wine_collection.is_valid_esdt_identifier(), ERR_INVALID_WINE_COLLECTION);
+        self.wine_collection().set(&wine_collection);
+        self.future_counter().set(1u32);
+    }
+
+    #[upgrade]
+    fn upgrade(&self) {}
+}
@@ -0,0 +1,162 @@
+use multiversx_sc::imports::*;
+pub use wine_common::WineNftAttributes;
+
+use crate::futures::FutureStatus;
+use wine_common::errors::*;
+
+// Delivery by the producer and redemption of claims, either for the wine or, when delivery
+// failed, for a refund
+#[multiversx_sc::module]
+pub trait SettlementModule: wine_roles::RolesModule + crate::futures::FuturesModule {
+    // Deposit NFTs of the future's wine, possibly over several calls. Once every sold bottle
+    // is delivered the sale closes, the escrowed payments go to the producer and claims
+    // become redeemable.
+    #[payable("*")]
+    #[endpoint(deliverWine)]
+    fn deliver_wine(&self, future_id: u32) {
+        let mut future = self.require_future(future_id);
+        require!(
+            self.blockchain().get_caller() == future.producer,
+            ERR_ONLY_PRODUCER_CAN_DELIVER_THE_WINE
+        );
+        require!(future.status == FutureStatus::Open, ERR_FUTURE_IS_NOT_OPEN);
+        require!(!self.is_delivery_failed(&future), ERR_DELIVERY_DEADLINE_HAS_PASSED);
+
+        let payment = self.call_value().single_esdt();
+        require!(payment.token_identifier == self.wine_collection().get(), ERR_INVALID_WINE_TOKEN);
+        require!(
+            future.wine_nonce == 0 || payment.token_nonce == future.wine_nonce,
+            ERR_INVALID_WINE_NONCE
+        );
+        let token_data = self.blockchain().get_esdt_token_data(
+            &self.blockchain().get_sc_address(),
+            &payment.token_identifier,
+            payment.token_nonce,
+        );
+        let attributes: WineNftAttributes<Self::Api> = token_data.decode_attributes();
+        require!(attributes.wine_id == future.wine_id, ERR_DELIVERED_WINE_DOES_NOT_MATCH);
+
+        let bottles = payment.amount.to_u64().unwrap_or(u64::MAX);
+        require!(
+            bottles <= (future.sold_bottles - future.delivered_bottles) as u64,
+            ERR_DELIVERY_EXCEEDS_THE_BOTTLES_SOLD
+        );
+        future.wine_nonce = payment.token_nonce;
+        future.delivered_bottles += bottles as u32;
+
+        let complete = future.delivered_bottles == future.sold_bottles;
+        if complete {
+            future.status = FutureStatus::Delivered;
+        }
+        self.futures(future_id).set(&future);
+
+        self.wine_delivered_event(future_id, bottles as u32, future.delivered_bottles);
+
+        if complete {
+            let proceeds = &future.price_per_bottle * future.sold_bottles;
+            self.send().direct(&future.producer, &future.payment_token, 0, &proceeds);
+            self.future_settled_event(future_id, &proceeds);
+        }
+    }
+
+    // Redeem claim tokens of a delivered future for the same number of wine NFTs
+    #[payable("*")]
+    #[endpoint(claimWine)]
+    fn claim_wine(&self, future_id: u32) {
+        let future = self.require_future(future_id);
+        require!(future.status == FutureStatus::Delivered, ERR_WINE_HAS_NOT_BEEN_DELIVERED);
+        let amount = self.burn_claims(future.claim_nonce);
+
+        let holder = self.blockchain().get_caller();
+        let wine_token = self.wine_collection().get();
+        self.send().direct_esdt(&holder, &wine_token, future.wine_nonce, &amount);
+
+        self.wine_claimed_event(future_id, &holder, &amount);
+    }
+
+    // Redeem claim tokens of a failed future for their purchase price
+    #[payable("*")]
+    #[endpoint(refundClaims)]
+    fn refund_claims(&self, future_id: u32) {
+        let future = self.require_future(future_id);
+        require!(self.is_delivery_failed(&future), ERR_DELIVERY_HAS_NOT_FAILED);
+        let amount = self.burn_claims(future.claim_nonce);
+
+        let holder = self.blockchain().get_caller();
+        let refund = &future.price_per_bottle * &amount;
+        self.send().direct(&holder, &future.payment_token, 0, &refund);
+
+        self.claims_refunded_event(future_id, &holder, &amount, &refund);
+    }
+
+    // Take back a partial delivery once the future has failed
+    #[endpoint(reclaimDelivery)]
+    fn reclaim_delivery(&self, future_id: u32) {
+        let mut future = self.require_future(future_id);
+        require!(
+            self.blockchain().get_caller() == future.producer,
+            ERR_ONLY_PRODUCER_CAN_RECLAIM_THE_DELIVERY
+        );
+        require!(self.is_delivery_failed(&future), ERR_DELIVERY_HAS_NOT_FAILED);
+        require!(future.delivered_bottles > 0, ERR_NOTHING_WAS_DELIVERED);
+
+        let amount = BigUint::from(future.delivered_bottles);
+        future.delivered_bottles = 0;
+        self.futures(future_id).set(&future);
+        let wine_token = self.wine_collection().get();
+        self.send().direct_esdt(&future.producer, &wine_token, future.wine_nonce, &amount);
+
+        self.delivery_reclaimed_event(future_id, &amount);
+    }
+
+    // Internal helpers
+    // Burn the claim tokens paid to the current call and return their amount
+    fn burn_claims(&self, claim_nonce: u64) -> BigUint {
+        require!(claim_nonce != 0, ERR_NO_CLAIMS_WERE_SOLD);
+        let claim_token = self.claim_collection().get_token_id();
+        let mut amount = BigUint::zero();
+        for payment in self.call_value().all_esdt_transfers().iter() {
+            require!(
+                payment.token_identifier == claim_token && payment.token_nonce == claim_nonce,
+                ERR_INVALID_CLAIM_TOKEN
+            );
+            amount += &payment.amount;
+        }
+        require!(amount > 0, ERR_NO_CLAIMS_PAID);
+        self.send().esdt_local_burn(&claim_token, claim_nonce, &amount);
+
+        amount
+    }
+
+    // Events
+    #[event("wineDelivered")]
+    fn wine_delivered_event(
+        &self,
+        #[indexed] future_id: u32,
+        bottles: u32,
+        delivered_bottles: u32,
+    );
+
+    #[event("futureSettled")]
+    fn future_settled_event(&self, #[indexed] future_id: u32, proceeds: &BigUint);
+
+    #[event("wineClaimed")]
+    fn wine_claimed_event(
+        &self,
+        #[indexed] future_id: u32,
+        #[indexed] holder: &ManagedAddress,
+        bottles: &BigUint,
+    );
+
+    #[event("claimsRefunded")]
+    fn claims_refunded_event(
+        &self,
+        #[indexed] future_id: u32,
+        #[indexed] holder: &ManagedAddress,
+        bottles: &BigUint,
+        refund: &BigUint,
+    );
+
+    #[event("deliveryReclaimed")]
+    fn delivery_reclaimed_event(&self, #[indexed] future_id: u32, bottles: &BigUint);
+}
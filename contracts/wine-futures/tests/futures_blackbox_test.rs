@@ -0,0 +1,304 @@
+use multiversx_sc::codec::top_encode_to_vec_u8_or_panic;
+use multiversx_sc_scenario::api::StaticApi;
+use multiversx_sc_scenario::imports::*;
+use wine_common::errors::*;
+use wine_futures::futures::ProxyTrait as _;
+use wine_futures::settlement::ProxyTrait as _;
+use wine_futures::ProxyTrait as _;
+use wine_futures::{FutureClaimAttributes, FutureStatus, WineFuture, WineNftAttributes};
+
+const FUTURES_PATH_EXPR: &str = "mxsc:output/wine-futures.mxsc.json";
+const FUTURES_ADDRESS_EXPR: &str = "sc:wine-futures";
+const OWNER_ADDRESS_EXPR: &str = "address:owner";
+const PRODUCER_ADDRESS_EXPR: &str = "address:producer";
+const BUYER_ADDRESS_EXPR: &str = "address:buyer";
+const OTHER_BUYER_ADDRESS_EXPR: &str = "address:other-buyer";
+// Issues the claim collection. The VM runs its own mock of it, the account only has to exist.
+const ESDT_SYSTEM_SC_ADDRESS_EXPR: &str = "0x000000000000000000010000000000000000000000000000000000000002ffff";
+
+const WINE_NFT_TOKEN_ID_EXPR: &str = "str:WINE-abcdef";
+const CLAIM_TOKEN_ID: &str = "CLAIM-abcdef";
+const CLAIM_TOKEN_ID_EXPR: &str = "str:CLAIM-abcdef";
+
+const START_TIMESTAMP: u64 = 1_000;
+const SALE_END: u64 = START_TIMESTAMP + 86_400;
+const DELIVERY_DEADLINE: u64 = START_TIMESTAMP + 30 * 86_400;
+const PRICE_PER_BOTTLE: u64 = 100;
+const WINE_ID: u32 = 7;
+
+type FuturesContract = ContractInfo<wine_futures::Proxy<StaticApi>>;
+
+fn user_error(message: &str) -> TxExpect {
+    TxExpect::user_error(format!("str:{message}"))
+}
+
+fn encoded_wine_attributes(wine_id: u32) -> Option<Vec<u8>> {
+    Some(top_encode_to_vec_u8_or_panic(&WineNftAttributes::<StaticApi> {
+        wine_id,
+        vineyard: ManagedBuffer::from("Chateau Test"),
+        variety: ManagedBuffer::from("Merlot"),
+        vintage_year: 2024,
+        region: ManagedBuffer::from("Bordeaux"),
+        quality_grade: 8,
+        ipfs_hash: ManagedBuffer::new(),
+    }))
+}
+
+struct FuturesTestState {
+    world: ScenarioWorld,
+    futures: FuturesContract,
+}
+
+impl FuturesTestState {
+    // Future 1 sells 10 bottles of wine 7 at 100 EGLD each. The producer holds 5 bottles of
+    // wine 7 (NFT 1) and one of wine 8 (NFT 2).
+    fn new() -> Self {
+        let mut world = ScenarioWorld::new();
+        world.register_contract(FUTURES_PATH_EXPR, wine_futures::ContractBuilder);
+
+        let futures_code = world.code_expression(FUTURES_PATH_EXPR);
+        world.set_state_step(
+            SetStateStep::new()
+                .block_timestamp(START_TIMESTAMP)
+                .put_account(OWNER_ADDRESS_EXPR, Account::new().nonce(1).balance("50000"))
+                .put_account(
+                    PRODUCER_ADDRESS_EXPR,
+                    Account::new()
+                        .nonce(1)
+                        .esdt_nft_balance(WINE_NFT_TOKEN_ID_EXPR, 1, "5", encoded_wine_attributes(WINE_ID))
+                        .esdt_nft_balance(WINE_NFT_TOKEN_ID_EXPR, 2, "1", encoded_wine_attributes(8)),
+                )
+                .put_account(BUYER_ADDRESS_EXPR, Account::new().nonce(1).balance("1000"))
+                .put_account(OTHER_BUYER_ADDRESS_EXPR, Account::new().nonce(1).balance("1000"))
+                .put_account(ESDT_SYSTEM_SC_ADDRESS_EXPR, Account::new().code(futures_code.clone()))
+                .new_address(OWNER_ADDRESS_EXPR, 1, FUTURES_ADDRESS_EXPR)
+                .new_token_identifier(CLAIM_TOKEN_ID),
+        );
+
+        let mut futures = FuturesContract::new(FUTURES_ADDRESS_EXPR);
+        world.sc_deploy(
+            ScDeployStep::new()
+                .from(OWNER_ADDRESS_EXPR)
+                .code(futures_code)
+                .call(futures.init(TokenIdentifier::from("WINE-abcdef"))),
+        );
+
+        // The claim collection is issued by an admin
+        world.sc_call(
+            ScCallStep::new()
+                .from(PRODUCER_ADDRESS_EXPR)
+                .call(futures.issue_claim_collection(ManagedBuffer::from("WineClaims"), ManagedBuffer::from("CLAIM")))
+                .expect(user_error(ERR_CALLER_DOES_NOT_HAVE_THE_REQUIRED_ROLE)),
+        );
+        world.sc_call(
+            ScCallStep::new()
+                .from(OWNER_ADDRESS_EXPR)
+                .egld_value("50000")
+                .call(futures.issue_claim_collection(ManagedBuffer::from("WineClaims"), ManagedBuffer::from("CLAIM"))),
+        );
+        world.sc_call(
+            ScCallStep::new()
+                .from(PRODUCER_ADDRESS_EXPR)
+                .call(futures.create_future(
+                    WINE_ID,
+                    EgldOrEsdtTokenIdentifier::egld(),
+                    PRICE_PER_BOTTLE,
+                    10u32,
+                    SALE_END,
+                    DELIVERY_DEADLINE,
+                ))
+                .expect_value(1u32),
+        );
+
+        FuturesTestState { world, futures }
+    }
+
+    fn set_block_timestamp(&mut self, timestamp: u64) {
+        self.world.set_state_step(SetStateStep::new().block_timestamp(timestamp));
+    }
+
+    fn buy_future(&mut self, buyer: &str, quantity: u32) {
+        self.world.sc_call(
+            ScCallStep::new()
+                .from(buyer)
+                .egld_value(PRICE_PER_BOTTLE * quantity as u64)
+                .call(self.futures.buy_future(1u32, quantity)),
+        );
+    }
+
+    fn deliver_wine(&mut self, wine_nonce: u64, bottles: &str) {
+        self.world.sc_call(
+            ScCallStep::new()
+                .from(PRODUCER_ADDRESS_EXPR)
+                .esdt_transfer(WINE_NFT_TOKEN_ID_EXPR, wine_nonce, bottles)
+                .call(self.futures.deliver_wine(1u32)),
+        );
+    }
+
+    fn future(&mut self) -> WineFuture<StaticApi> {
+        self.world.quick_query(self.futures.get_future(1u32))
+    }
+}
+
+#[test]
+fn claims_are_paid_into_escrow_and_redeemed_for_the_delivered_wine() {
+    let mut state = FuturesTestState::new();
+
+    state.world.sc_call(
+        ScCallStep::new()
+            .from(BUYER_ADDRESS_EXPR)
+            .egld_value(250u64)
+            .call(state.futures.buy_future(1u32, 3u32))
+            .expect(user_error(ERR_INCORRECT_PAYMENT_AMOUNT)),
+    );
+    state.buy_future(BUYER_ADDRESS_EXPR, 3);
+    state.buy_future(OTHER_BUYER_ADDRESS_EXPR, 2);
+    state.world.check_state_step(
+        CheckStateStep::new()
+            .put_account(FUTURES_ADDRESS_EXPR, CheckAccount::new().balance("500"))
+            .put_account(
+                BUYER_ADDRESS_EXPR,
+                CheckAccount::new().balance("700").esdt_nft_balance_and_attributes(
+                    CLAIM_TOKEN_ID_EXPR,
+                    1,
+                    "3",
+                    Some(FutureClaimAttributes { future_id: 1, wine_id: WINE_ID }),
+                ),
+            ),
+    );
+    state.world.sc_call(
+        ScCallStep::new()
+            .from(BUYER_ADDRESS_EXPR)
+            .esdt_transfer(CLAIM_TOKEN_ID_EXPR, 1, "3")
+            .call(state.futures.claim_wine(1u32))
+            .expect(user_error(ERR_WINE_HAS_NOT_BEEN_DELIVERED)),
+    );
+
+    // Only the producer delivers, and only bottles of the future's wine
+    state.world.sc_call(
+        ScCallStep::new()
+            .from(BUYER_ADDRESS_EXPR)
+            .call(state.futures.deliver_wine(1u32))
+            .expect(user_error(ERR_ONLY_PRODUCER_CAN_DELIVER_THE_WINE)),
+    );
+    state.world.sc_call(
+        ScCallStep::new()
+            .from(PRODUCER_ADDRESS_EXPR)
+            .esdt_transfer(WINE_NFT_TOKEN_ID_EXPR, 2, "1")
+            .call(state.futures.deliver_wine(1u32))
+            .expect(user_error(ERR_DELIVERED_WINE_DOES_NOT_MATCH)),
+    );
+
+    // The producer is paid once every sold bottle is in
+    state.deliver_wine(1, "3");
+    assert_eq!(state.future().status, FutureStatus::Open);
+    state.deliver_wine(1, "2");
+    assert_eq!(state.future().status, FutureStatus::Delivered);
+    state.world.check_state_step(
+        CheckStateStep::new()
+            .put_account(PRODUCER_ADDRESS_EXPR, CheckAccount::new().balance("500"))
+            .put_account(FUTURES_ADDRESS_EXPR, CheckAccount::new().balance("0")),
+    );
+
+    state.world.sc_call(
+        ScCallStep::new()
+            .from(BUYER_ADDRESS_EXPR)
+            .esdt_transfer(CLAIM_TOKEN_ID_EXPR, 1, "3")
+            .call(state.futures.claim_wine(1u32)),
+    );
+    state.world.check_state_step(
+        CheckStateStep::new().put_account(
+            BUYER_ADDRESS_EXPR,
+            CheckAccount::new()
+                .esdt_nft_balance_and_attributes(
+                    CLAIM_TOKEN_ID_EXPR,
+                    1,
+                    "0",
+                    Some(FutureClaimAttributes { future_id: 1, wine_id: WINE_ID }),
+                )
+                .esdt_nft_balance_and_attributes(WINE_NFT_TOKEN_ID_EXPR, 1, "3", encoded_wine_attributes(WINE_ID)),
+        ),
+    );
+}
+
+#[test]
+fn failed_delivery_refunds_claims_and_returns_the_partial_delivery() {
+    let mut state = FuturesTestState::new();
+    state.buy_future(BUYER_ADDRESS_EXPR, 3);
+    state.deliver_wine(1, "1");
+
+    state.set_block_timestamp(DELIVERY_DEADLINE + 1);
+    state.world.sc_call(
+        ScCallStep::new()
+            .from(PRODUCER_ADDRESS_EXPR)
+            .esdt_transfer(WINE_NFT_TOKEN_ID_EXPR, 1, "2")
+            .call(state.futures.deliver_wine(1u32))
+            .expect(user_error(ERR_DELIVERY_DEADLINE_HAS_PASSED)),
+    );
+
+    state.world.sc_call(
+        ScCallStep::new()
+            .from(BUYER_ADDRESS_EXPR)
+            .esdt_transfer(CLAIM_TOKEN_ID_EXPR, 1, "3")
+            .call(state.futures.refund_claims(1u32)),
+    );
+    state.world.sc_call(
+        ScCallStep::new()
+            .from(BUYER_ADDRESS_EXPR)
+            .call(state.futures.reclaim_delivery(1u32))
+            .expect(user_error(ERR_ONLY_PRODUCER_CAN_RECLAIM_THE_DELIVERY)),
+    );
+    state.world.sc_call(
+        ScCallStep::new()
+            .from(PRODUCER_ADDRESS_EXPR)
+            .call(state.futures.reclaim_delivery(1u32)),
+    );
+
+    state.world.check_state_step(
+        CheckStateStep::new()
+            .put_account(BUYER_ADDRESS_EXPR, CheckAccount::new().balance("1000"))
+            .put_account(
+                PRODUCER_ADDRESS_EXPR,
+                CheckAccount::new()
+                    .balance("0")
+                    .esdt_nft_balance_and_attributes(WINE_NFT_TOKEN_ID_EXPR, 1, "5", encoded_wine_attributes(WINE_ID)),
+            )
+            .put_account(FUTURES_ADDRESS_EXPR, CheckAccount::new().balance("0")),
+    );
+}
+
+#[test]
+fn cancelled_future_stops_selling_and_refunds_claims() {
+    let mut state = FuturesTestState::new();
+    state.buy_future(BUYER_ADDRESS_EXPR, 2);
+
+    state.world.sc_call(
+        ScCallStep::new()
+            .from(BUYER_ADDRESS_EXPR)
+            .call(state.futures.cancel_future(1u32))
+            .expect(user_error(ERR_ONLY_PRODUCER_CAN_CANCEL_THE_FUTURE)),
+    );
+    state.world.sc_call(
+        ScCallStep::new()
+            .from(PRODUCER_ADDRESS_EXPR)
+            .call(state.futures.cancel_future(1u32)),
+    );
+    assert_eq!(state.future().status, FutureStatus::Cancelled);
+
+    state.world.sc_call(
+        ScCallStep::new()
+            .from(OTHER_BUYER_ADDRESS_EXPR)
+            .egld_value(PRICE_PER_BOTTLE)
+            .call(state.futures.buy_future(1u32, 1u32))
+            .expect(user_error(ERR_FUTURE_IS_NOT_OPEN)),
+    );
+    state.world.sc_call(
+        ScCallStep::new()
+            .from(BUYER_ADDRESS_EXPR)
+            .esdt_transfer(CLAIM_TOKEN_ID_EXPR, 1, "2")
+            .call(state.futures.refund_claims(1u32)),
+    );
+    state.world.check_state_step(
+        CheckStateStep::new().put_account(BUYER_ADDRESS_EXPR, CheckAccount::new().balance("1000")),
+    );
+}
@@ -0,0 +1,36 @@
+#![no_std]
+
+use multiversx_sc::imports::*;
+use wine_common::errors::*;
+
+pub mod proposals;
+pub mod voting_power;
+
+pub use proposals::{GovernanceConfig, Proposal, ProposalCall, ProposalStatus, VoteReceipt, VoteType};
+pub use voting_power::Checkpoint;
+
+// DAO for the marketplace and registry parameters. WINE holders deposit tokens for voting
+// power, create proposals that call endpoints of the governed contracts, and vote with
+// their balance as of each proposal's creation.
+#[multiversx_sc::contract]
+pub trait WineGovernance: voting_power::VotingPowerModule + proposals::ProposalsModule {
+    #[init]
+    fn init(
+        &self,
+        governance_token: TokenIdentifier,
+        voting_period: u64,
+        quorum_bps: u64,
+        proposal_threshold: BigUint,
+        governed_contracts: MultiValueEncoded<ManagedAddress>,
+    ) {
+        require!(governance_token.is_valid_esdt_identifier(), ERR_INVALID_GOVERNANCE_TOKEN);
+        self.governance_token().set(&governance_token);
+        self.store_governance_config(voting_period, quorum_bps, proposal_threshold);
+        for address in governed_contracts {
+            self.add_governed(address);
+        }
+    }
+
+    #[upgrade]
+    fn upgrade(&self) {}
+}
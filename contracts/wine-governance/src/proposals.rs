@@ -0,0 +1,345 @@
+use multiversx_sc::derive_imports::*;
+use multiversx_sc::imports::*;
+use wine_common::errors::*;
+
+// Keep proposals bounded in storage
+pub const MAX_PROPOSAL_ARGUMENTS: usize = 10;
+pub const MAX_DESCRIPTION_LENGTH: usize = 256;
+pub const MAX_PROPOSAL_GAS: u64 = 50_000_000;
+
+#[derive(TopEncode, TopDecode, NestedEncode, NestedDecode, TypeAbi, PartialEq, Debug, Clone, Copy)]
+pub enum VoteType {
+    For,
+    Against,
+    Abstain,
+}
+
+#[derive(TopEncode, TopDecode, NestedEncode, NestedDecode, TypeAbi, PartialEq, Debug, Clone, Copy)]
+pub enum ProposalStatus {
+    Active,
+    Defeated,
+    Succeeded,
+    Executed,
+    Cancelled,
+}
+
+// The endpoint call a proposal makes once passed, e.g. setMarketplaceFeePercent,
+// addSupportedToken or pause on the marketplace
+#[derive(TopEncode, TopDecode, NestedEncode, NestedDecode, TypeAbi, PartialEq, Debug, Clone)]
+pub struct ProposalCall<M: ManagedTypeApi> {
+    pub target: ManagedAddress<M>,
+    pub endpoint: ManagedBuffer<M>,
+    // Top-encoded arguments
+    pub arguments: ManagedVec<M, ManagedBuffer<M>>,
+    pub gas_limit: u64,
+}
+
+#[derive(TopEncode, TopDecode, TypeAbi, PartialEq, Debug)]
+pub struct Proposal<M: ManagedTypeApi> {
+    pub proposer: ManagedAddress<M>,
+    pub description: ManagedBuffer<M>,
+    pub call: ProposalCall<M>,
+    // Votes are weighed by deposits made before this block
+    pub snapshot_nonce: u64,
+    pub end_timestamp: u64,
+    pub votes_for: BigUint<M>,
+    pub votes_against: BigUint<M>,
+    pub votes_abstain: BigUint<M>,
+    pub executed: bool,
+    pub cancelled: bool,
+}
+
+#[derive(TopEncode, TopDecode, TypeAbi, PartialEq, Debug)]
+pub struct VoteReceipt<M: ManagedTypeApi> {
+    pub vote: VoteType,
+    pub weight: BigUint<M>,
+}
+
+#[derive(TopEncode, TopDecode, TypeAbi, PartialEq, Debug)]
+pub struct GovernanceConfig<M: ManagedTypeApi> {
+    pub voting_period: u64,
+    // Share of the snapshot's total deposits that must vote, in basis points
+    pub quorum_bps: u64,
+    // Deposit required to create a proposal
+    pub proposal_threshold: BigUint<M>,
+}
+
+// Proposals call endpoints of the governed contracts, or of this contract to change its own
+// settings. A proposal passes with more votes for than against once the quorum is reached,
+// and anyone can execute it after its voting period. The governed contracts must grant this
+// contract the roles their endpoints require.
+#[multiversx_sc::module]
+pub trait ProposalsModule: crate::voting_power::VotingPowerModule {
+    #[endpoint(propose)]
+    fn propose(
+        &self,
+        target: ManagedAddress,
+        endpoint: ManagedBuffer,
+        gas_limit: u64,
+        description: ManagedBuffer,
+        arguments: MultiValueEncoded<ManagedBuffer>,
+    ) -> u32 {
+        let own_address = self.blockchain().get_sc_address();
+        require!(
+            target == own_address || self.governed_contracts().contains(&target),
+            ERR_TARGET_IS_NOT_GOVERNED
+        );
+        require!(!endpoint.is_empty(), ERR_ENDPOINT_IS_REQUIRED);
+        require!(gas_limit > 0 && gas_limit <= MAX_PROPOSAL_GAS, ERR_INVALID_GAS_LIMIT);
+        require!(description.len() <= MAX_DESCRIPTION_LENGTH, ERR_DESCRIPTION_TOO_LONG);
+        require!(arguments.len() <= MAX_PROPOSAL_ARGUMENTS, ERR_TOO_MANY_ARGUMENTS);
+
+        let proposer = self.blockchain().get_caller();
+        let config = self.governance_config().get();
+        require!(
+            self.latest_balance(&self.voting_checkpoints(&proposer)) >= config.proposal_threshold,
+            ERR_NOT_ENOUGH_VOTING_POWER_TO_PROPOSE
+        );
+
+        let proposal_id = self.proposal_count().get() + 1;
+        self.proposal_count().set(proposal_id);
+        let call = ProposalCall {
+            target,
+            endpoint,
+            arguments: arguments.to_vec(),
+            gas_limit,
+        };
+        self.proposal_created_event(proposal_id, &proposer, &call.target, &call.endpoint);
+        self.proposals(proposal_id).set(&Proposal {
+            proposer,
+            description,
+            call,
+            snapshot_nonce: self.blockchain().get_block_nonce(),
+            end_timestamp: self.blockchain().get_block_timestamp() + config.voting_period,
+            votes_for: BigUint::zero(),
+            votes_against: BigUint::zero(),
+            votes_abstain: BigUint::zero(),
+            executed: false,
+            cancelled: false,
+        });
+
+        proposal_id
+    }
+
+    #[endpoint(castVote)]
+    fn cast_vote(&self, proposal_id: u32, vote: VoteType) {
+        let mut proposal = self.require_proposal(proposal_id);
+        require!(
+            self.proposal_status(&proposal) == ProposalStatus::Active,
+            ERR_PROPOSAL_IS_NOT_ACTIVE
+        );
+        let voter = self.blockchain().get_caller();
+        require!(self.vote_receipts(proposal_id, &voter).is_empty(), ERR_ALREADY_VOTED);
+        let weight = self.balance_before(&self.voting_checkpoints(&voter), proposal.snapshot_nonce);
+        require!(weight > 0, ERR_NO_VOTING_POWER_AT_THE_SNAPSHOT);
+
+        match vote {
+            VoteType::For => proposal.votes_for += &weight,
+            VoteType::Against => proposal.votes_against += &weight,
+            VoteType::Abstain => proposal.votes_abstain += &weight,
+        }
+        self.proposals(proposal_id).set(&proposal);
+        self.vote_receipts(proposal_id, &voter).set(&VoteReceipt {
+            vote,
+            weight: weight.clone(),
+        });
+
+        self.vote_cast_event(proposal_id, &voter, vote, &weight);
+    }
+
+    // Fire-and-forget: a failing call does not revert the execution
+    #[endpoint(executeProposal)]
+    fn execute_proposal(&self, proposal_id: u32) {
+        let mut proposal = self.require_proposal(proposal_id);
+        require!(
+            self.proposal_status(&proposal) == ProposalStatus::Succeeded,
+            ERR_PROPOSAL_HAS_NOT_SUCCEEDED
+        );
+        proposal.executed = true;
+        self.proposals(proposal_id).set(&proposal);
+
+        let call = proposal.call;
+        self.tx()
+            .to(&call.target)
+            .gas(call.gas_limit)
+            .raw_call(call.endpoint)
+            .arguments_raw(ManagedArgBuffer::from(call.arguments))
+            .transfer_execute();
+
+        self.proposal_executed_event(proposal_id);
+    }
+
+    #[endpoint(cancelProposal)]
+    fn cancel_proposal(&self, proposal_id: u32) {
+        let mut proposal = self.require_proposal(proposal_id);
+        require!(
+            self.blockchain().get_caller() == proposal.proposer,
+            ERR_ONLY_PROPOSER_CAN_CANCEL
+        );
+        require!(
+            self.proposal_status(&proposal) == ProposalStatus::Active,
+            ERR_PROPOSAL_IS_NOT_ACTIVE
+        );
+        proposal.cancelled = true;
+        self.proposals(proposal_id).set(&proposal);
+
+        self.proposal_cancelled_event(proposal_id);
+    }
+
+    // Settings below are changed through proposals targeting this contract
+    #[endpoint(setGovernanceConfig)]
+    fn set_governance_config(&self, voting_period: u64, quorum_bps: u64, proposal_threshold: BigUint) {
+        self.require_self_call();
+        self.store_governance_config(voting_period, quorum_bps, proposal_threshold);
+    }
+
+    #[endpoint(addGovernedContract)]
+    fn add_governed_contract(&self, address: ManagedAddress) {
+        self.require_self_call();
+        self.add_governed(address);
+    }
+
+    #[endpoint(removeGovernedContract)]
+    fn remove_governed_contract(&self, address: ManagedAddress) {
+        self.require_self_call();
+        require!(self.governed_contracts().swap_remove(&address), ERR_CONTRACT_IS_NOT_GOVERNED);
+    }
+
+    // Internal helpers
+    fn require_self_call(&self) {
+        require!(
+            self.blockchain().get_caller() == self.blockchain().get_sc_address(),
+            ERR_ONLY_CALLABLE_THROUGH_A_PROPOSAL
+        );
+    }
+
+    fn store_governance_config(&self, voting_period: u64, quorum_bps: u64, proposal_threshold: BigUint) {
+        require!(voting_period > 0, ERR_VOTING_PERIOD_MUST_BE_GREATER_THAN_0);
+        require!(quorum_bps <= 10_000, ERR_QUORUM_CANNOT_EXCEED_100_PERCENT);
+        self.governance_config().set(&GovernanceConfig {
+            voting_period,
+            quorum_bps,
+            proposal_threshold,
+        });
+    }
+
+    fn add_governed(&self, address: ManagedAddress) {
+        require!(
+            self.blockchain().is_smart_contract(&address),
+            ERR_GOVERNED_CONTRACT_MUST_BE_A_SMART_CONTRACT
+        );
+        self.governed_contracts().insert(address);
+    }
+
+    fn require_proposal(&self, proposal_id: u32) -> Proposal<Self::Api> {
+        require!(!self.proposals(proposal_id).is_empty(), ERR_PROPOSAL_DOES_NOT_EXIST);
+        self.proposals(proposal_id).get()
+    }
+
+    fn proposal_status(&self, proposal: &Proposal<Self::Api>) -> ProposalStatus {
+        if proposal.cancelled {
+            ProposalStatus::Cancelled
+        } else if proposal.executed {
+            ProposalStatus::Executed
+        } else if self.blockchain().get_block_timestamp() <= proposal.end_timestamp {
+            ProposalStatus::Active
+        } else if self.is_proposal_passed(proposal) {
+            ProposalStatus::Succeeded
+        } else {
+            ProposalStatus::Defeated
+        }
+    }
+
+    fn is_proposal_passed(&self, proposal: &Proposal<Self::Api>) -> bool {
+        let total_votes = &proposal.votes_for + &proposal.votes_against + &proposal.votes_abstain;
+        let snapshot_total = self.balance_before(&self.total_checkpoints(), proposal.snapshot_nonce);
+        let quorum_bps = self.governance_config().get().quorum_bps;
+        proposal.votes_for > proposal.votes_against
+            && total_votes * 10_000u64 >= snapshot_total * quorum_bps
+    }
+
+    // View functions
+    #[view(getProposal)]
+    fn get_proposal(&self, proposal_id: u32) -> Proposal<Self::Api> {
+        self.require_proposal(proposal_id)
+    }
+
+    #[view(getProposalStatus)]
+    fn get_proposal_status(&self, proposal_id: u32) -> ProposalStatus {
+        self.proposal_status(&self.require_proposal(proposal_id))
+    }
+
+    #[view(getProposalCount)]
+    fn get_proposal_count(&self) -> u32 {
+        self.proposal_count().get()
+    }
+
+    #[view(getVoteReceipt)]
+    fn get_vote_receipt(
+        &self,
+        proposal_id: u32,
+        voter: ManagedAddress,
+    ) -> OptionalValue<VoteReceipt<Self::Api>> {
+        if self.vote_receipts(proposal_id, &voter).is_empty() {
+            OptionalValue::None
+        } else {
+            OptionalValue::Some(self.vote_receipts(proposal_id, &voter).get())
+        }
+    }
+
+    #[view(getGovernanceConfig)]
+    fn get_governance_config(&self) -> GovernanceConfig<Self::Api> {
+        self.governance_config().get()
+    }
+
+    // Kept short by proposals, so returned whole
+    #[view(getGovernedContracts)]
+    fn get_governed_contracts(&self) -> MultiValueEncoded<ManagedAddress> {
+        self.governed_contracts().iter().collect()
+    }
+
+    // Storage mappers
+    #[storage_mapper("governanceConfig")]
+    fn governance_config(&self) -> SingleValueMapper<GovernanceConfig<Self::Api>>;
+
+    #[storage_mapper("governedContracts")]
+    fn governed_contracts(&self) -> UnorderedSetMapper<ManagedAddress>;
+
+    #[storage_mapper("proposalCount")]
+    fn proposal_count(&self) -> SingleValueMapper<u32>;
+
+    #[storage_mapper("proposals")]
+    fn proposals(&self, proposal_id: u32) -> SingleValueMapper<Proposal<Self::Api>>;
+
+    #[storage_mapper("voteReceipts")]
+    fn vote_receipts(
+        &self,
+        proposal_id: u32,
+        voter: &ManagedAddress,
+    ) -> SingleValueMapper<VoteReceipt<Self::Api>>;
+
+    // Events
+    #[event("proposalCreated")]
+    fn proposal_created_event(
+        &self,
+        #[indexed] proposal_id: u32,
+        #[indexed] proposer: &ManagedAddress,
+        #[indexed] target: &ManagedAddress,
+        endpoint: &ManagedBuffer,
+    );
+
+    #[event("voteCast")]
+    fn vote_cast_event(
+        &self,
+        #[indexed] proposal_id: u32,
+        #[indexed] voter: &ManagedAddress,
+        #[indexed] vote: VoteType,
+        weight: &BigUint,
+    );
+
+    #[event("proposalExecuted")]
+    fn proposal_executed_event(&self, #[indexed] proposal_id: u32);
+
+    #[event("proposalCancelled")]
+    fn proposal_cancelled_event(&self, #[indexed] proposal_id: u32);
+}
@@ -0,0 +1,129 @@
+use multiversx_sc::derive_imports::*;
+use multiversx_sc::imports::*;
+use wine_common::errors::*;
+
+// A deposited balance, in force from block `nonce` on
+#[derive(TopEncode, TopDecode, NestedEncode, NestedDecode, TypeAbi, PartialEq, Debug)]
+pub struct Checkpoint<M: ManagedTypeApi> {
+    pub nonce: u64,
+    pub balance: BigUint<M>,
+}
+
+// Voting power is the governance token deposited here. Every balance change is checkpointed
+// by block nonce, so a proposal weighs votes by the balances before its creation and tokens
+// cannot be moved around to vote twice.
+#[multiversx_sc::module]
+pub trait VotingPowerModule {
+    #[payable("*")]
+    #[endpoint(depositVotingTokens)]
+    fn deposit_voting_tokens(&self) {
+        let payment = self.call_value().single_esdt();
+        require!(
+            payment.token_identifier == self.governance_token().get(),
+            ERR_INVALID_GOVERNANCE_TOKEN
+        );
+        require!(payment.amount > 0, ERR_AMOUNT_MUST_BE_GREATER_THAN_0);
+
+        let caller = self.blockchain().get_caller();
+        let balance = self.latest_balance(&self.voting_checkpoints(&caller)) + &payment.amount;
+        self.write_checkpoint(&mut self.voting_checkpoints(&caller), balance);
+        let total = self.latest_balance(&self.total_checkpoints()) + &payment.amount;
+        self.write_checkpoint(&mut self.total_checkpoints(), total);
+
+        self.voting_tokens_deposited_event(&caller, &payment.amount);
+    }
+
+    // Votes already cast keep their weight
+    #[endpoint(withdrawVotingTokens)]
+    fn withdraw_voting_tokens(&self, amount: BigUint) {
+        require!(amount > 0, ERR_AMOUNT_MUST_BE_GREATER_THAN_0);
+        let caller = self.blockchain().get_caller();
+        let balance = self.latest_balance(&self.voting_checkpoints(&caller));
+        require!(amount <= balance, ERR_NOT_ENOUGH_DEPOSITED);
+
+        self.write_checkpoint(&mut self.voting_checkpoints(&caller), balance - &amount);
+        let total = self.latest_balance(&self.total_checkpoints()) - &amount;
+        self.write_checkpoint(&mut self.total_checkpoints(), total);
+        self.send().direct_esdt(&caller, &self.governance_token().get(), 0, &amount);
+
+        self.voting_tokens_withdrawn_event(&caller, &amount);
+    }
+
+    // Internal helpers
+    fn latest_balance(&self, checkpoints: &VecMapper<Checkpoint<Self::Api>>) -> BigUint {
+        if checkpoints.is_empty() {
+            BigUint::zero()
+        } else {
+            checkpoints.get(checkpoints.len()).balance
+        }
+    }
+
+    // Changes within one block overwrite that block's checkpoint
+    fn write_checkpoint(&self, checkpoints: &mut VecMapper<Checkpoint<Self::Api>>, balance: BigUint) {
+        let nonce = self.blockchain().get_block_nonce();
+        let len = checkpoints.len();
+        let checkpoint = Checkpoint { nonce, balance };
+        if len > 0 && checkpoints.get(len).nonce == nonce {
+            checkpoints.set(len, &checkpoint);
+        } else {
+            checkpoints.push(&checkpoint);
+        }
+    }
+
+    // Balance at the end of the block before `nonce`, by binary search over the checkpoints
+    fn balance_before(&self, checkpoints: &VecMapper<Checkpoint<Self::Api>>, nonce: u64) -> BigUint {
+        let mut balance = BigUint::zero();
+        let mut low = 1;
+        let mut high = checkpoints.len();
+        while low <= high {
+            let middle = (low + high) / 2;
+            let checkpoint = checkpoints.get(middle);
+            if checkpoint.nonce < nonce {
+                balance = checkpoint.balance;
+                low = middle + 1;
+            } else {
+                high = middle - 1;
+            }
+        }
+        balance
+    }
+
+    // View functions
+    #[view(getGovernanceToken)]
+    fn get_governance_token(&self) -> TokenIdentifier {
+        self.governance_token().get()
+    }
+
+    #[view(getVotingPower)]
+    fn get_voting_power(&self, address: ManagedAddress) -> BigUint {
+        self.latest_balance(&self.voting_checkpoints(&address))
+    }
+
+    // Power as counted by proposals created in block `nonce`
+    #[view(getVotingPowerAt)]
+    fn get_voting_power_at(&self, address: ManagedAddress, nonce: u64) -> BigUint {
+        self.balance_before(&self.voting_checkpoints(&address), nonce)
+    }
+
+    #[view(getTotalVotingPower)]
+    fn get_total_voting_power(&self) -> BigUint {
+        self.latest_balance(&self.total_checkpoints())
+    }
+
+    // Storage mappers
+    #[storage_mapper("governanceToken")]
+    fn governance_token(&self) -> SingleValueMapper<TokenIdentifier>;
+
+    #[storage_mapper("votingCheckpoints")]
+    fn voting_checkpoints(&self, address: &ManagedAddress) -> VecMapper<Checkpoint<Self::Api>>;
+
+    #[storage_mapper("totalCheckpoints")]
+    fn total_checkpoints(&self) -> VecMapper<Checkpoint<Self::Api>>;
+
+    // Events
+    #[event("votingTokensDeposited")]
+    fn voting_tokens_deposited_event(&self, #[indexed] address: &ManagedAddress, amount: &BigUint);
+
+    #[event("votingTokensWithdrawn")]
+    fn voting_tokens_withdrawn_event(&self, #[indexed] address: &ManagedAddress, amount: &BigUint);
+}
@@ -0,0 +1,257 @@
+use multiversx_sc::codec::top_encode_to_vec_u8_or_panic;
+use multiversx_sc_scenario::api::StaticApi;
+use multiversx_sc_scenario::imports::*;
+use wine_common::errors::*;
+use wine_governance::proposals::ProxyTrait as _;
+use wine_governance::voting_power::ProxyTrait as _;
+use wine_governance::ProxyTrait as _;
+use wine_governance::{GovernanceConfig, ProposalStatus, VoteType};
+
+const GOVERNANCE_PATH_EXPR: &str = "mxsc:output/wine-governance.mxsc.json";
+const GOVERNANCE_ADDRESS_EXPR: &str = "sc:wine-governance";
+const OWNER_ADDRESS_EXPR: &str = "address:owner";
+const ALICE_ADDRESS_EXPR: &str = "address:alice";
+const BOB_ADDRESS_EXPR: &str = "address:bob";
+const CAROL_ADDRESS_EXPR: &str = "address:carol";
+
+const GOV_TOKEN_ID_EXPR: &str = "str:GOV-abcdef";
+
+const START_TIMESTAMP: u64 = 1_000;
+const VOTING_PERIOD: u64 = 3 * 24 * 60 * 60;
+const QUORUM_BPS: u64 = 4_000;
+const PROPOSAL_THRESHOLD: u64 = 100;
+const GAS_LIMIT: u64 = 10_000_000;
+
+type GovernanceContract = ContractInfo<wine_governance::Proxy<StaticApi>>;
+
+fn user_error(message: &str) -> TxExpect {
+    TxExpect::user_error(format!("str:{message}"))
+}
+
+struct GovernanceTestState {
+    world: ScenarioWorld,
+    governance: GovernanceContract,
+}
+
+impl GovernanceTestState {
+    // Alice deposited 600 and Bob 400 voting tokens in block 1; Carol holds 500 undeposited
+    fn new() -> Self {
+        let mut world = ScenarioWorld::new();
+        world.register_contract(GOVERNANCE_PATH_EXPR, wine_governance::ContractBuilder);
+
+        world.set_state_step(
+            SetStateStep::new()
+                .block_nonce(1)
+                .block_timestamp(START_TIMESTAMP)
+                .put_account(OWNER_ADDRESS_EXPR, Account::new().nonce(1))
+                .put_account(ALICE_ADDRESS_EXPR, Account::new().nonce(1).esdt_balance(GOV_TOKEN_ID_EXPR, "600"))
+                .put_account(BOB_ADDRESS_EXPR, Account::new().nonce(1).esdt_balance(GOV_TOKEN_ID_EXPR, "400"))
+                .put_account(CAROL_ADDRESS_EXPR, Account::new().nonce(1).esdt_balance(GOV_TOKEN_ID_EXPR, "500"))
+                .new_address(OWNER_ADDRESS_EXPR, 1, GOVERNANCE_ADDRESS_EXPR),
+        );
+
+        let mut governance = GovernanceContract::new(GOVERNANCE_ADDRESS_EXPR);
+        let governance_code = world.code_expression(GOVERNANCE_PATH_EXPR);
+        world.sc_deploy(
+            ScDeployStep::new()
+                .from(OWNER_ADDRESS_EXPR)
+                .code(governance_code)
+                .call(governance.init(
+                    TokenIdentifier::from("GOV-abcdef"),
+                    VOTING_PERIOD,
+                    QUORUM_BPS,
+                    PROPOSAL_THRESHOLD,
+                    MultiValueVec::<ManagedAddress<StaticApi>>::new(),
+                )),
+        );
+
+        let mut state = GovernanceTestState { world, governance };
+        state.deposit(ALICE_ADDRESS_EXPR, "600");
+        state.deposit(BOB_ADDRESS_EXPR, "400");
+        state.world.set_state_step(SetStateStep::new().block_nonce(2));
+        state
+    }
+
+    fn set_block_timestamp(&mut self, timestamp: u64) {
+        self.world.set_state_step(SetStateStep::new().block_timestamp(timestamp));
+    }
+
+    fn deposit(&mut self, voter: &str, amount: &str) {
+        self.world.sc_call(
+            ScCallStep::new()
+                .from(voter)
+                .esdt_transfer(GOV_TOKEN_ID_EXPR, 0, amount)
+                .call(self.governance.deposit_voting_tokens()),
+        );
+    }
+
+    // Proposal to change this contract's own settings
+    fn propose_config(&mut self, proposer: &str, voting_period: u64) -> TypedScCall<u32> {
+        let arguments = vec![
+            ManagedBuffer::<StaticApi>::from(top_encode_to_vec_u8_or_panic(&voting_period)),
+            ManagedBuffer::from(top_encode_to_vec_u8_or_panic(&QUORUM_BPS)),
+            ManagedBuffer::from(top_encode_to_vec_u8_or_panic(&PROPOSAL_THRESHOLD)),
+        ];
+        ScCallStep::new().from(proposer).call(self.governance.propose(
+            AddressValue::from(GOVERNANCE_ADDRESS_EXPR).to_address(),
+            ManagedBuffer::from("setGovernanceConfig"),
+            GAS_LIMIT,
+            ManagedBuffer::from("Shorter voting period"),
+            MultiValueVec::from(arguments),
+        ))
+    }
+
+    fn cast_vote(&mut self, voter: &str, vote: VoteType) {
+        self.world.sc_call(
+            ScCallStep::new()
+                .from(voter)
+                .call(self.governance.cast_vote(1u32, vote)),
+        );
+    }
+
+    fn proposal_status(&mut self) -> ProposalStatus {
+        self.world.quick_query(self.governance.get_proposal_status(1u32))
+    }
+}
+
+#[test]
+fn passed_proposal_is_executed_after_the_voting_period() {
+    let mut state = GovernanceTestState::new();
+
+    let step = state.propose_config(CAROL_ADDRESS_EXPR, VOTING_PERIOD / 3);
+    state.world.sc_call(step.expect(user_error(ERR_NOT_ENOUGH_VOTING_POWER_TO_PROPOSE)));
+    let step = state.propose_config(ALICE_ADDRESS_EXPR, VOTING_PERIOD / 3);
+    state.world.sc_call(step.expect_value(1u32));
+
+    state.cast_vote(ALICE_ADDRESS_EXPR, VoteType::For);
+    state.cast_vote(BOB_ADDRESS_EXPR, VoteType::Against);
+    state.world.sc_call(
+        ScCallStep::new()
+            .from(ALICE_ADDRESS_EXPR)
+            .call(state.governance.cast_vote(1u32, VoteType::For))
+            .expect(user_error(ERR_ALREADY_VOTED)),
+    );
+    state.world.sc_call(
+        ScCallStep::new()
+            .from(ALICE_ADDRESS_EXPR)
+            .call(state.governance.execute_proposal(1u32))
+            .expect(user_error(ERR_PROPOSAL_HAS_NOT_SUCCEEDED)),
+    );
+
+    state.set_block_timestamp(START_TIMESTAMP + VOTING_PERIOD + 1);
+    assert_eq!(state.proposal_status(), ProposalStatus::Succeeded);
+    state.world.sc_call(
+        ScCallStep::new()
+            .from(CAROL_ADDRESS_EXPR)
+            .call(state.governance.execute_proposal(1u32)),
+    );
+
+    assert_eq!(state.proposal_status(), ProposalStatus::Executed);
+    let config: GovernanceConfig<StaticApi> = state.world.quick_query(state.governance.get_governance_config());
+    assert_eq!(config.voting_period, VOTING_PERIOD / 3);
+    state.world.sc_call(
+        ScCallStep::new()
+            .from(CAROL_ADDRESS_EXPR)
+            .call(state.governance.execute_proposal(1u32))
+            .expect(user_error(ERR_PROPOSAL_HAS_NOT_SUCCEEDED)),
+    );
+}
+
+#[test]
+fn settings_only_change_through_proposals() {
+    let mut state = GovernanceTestState::new();
+
+    state.world.sc_call(
+        ScCallStep::new()
+            .from(OWNER_ADDRESS_EXPR)
+            .call(state.governance.set_governance_config(VOTING_PERIOD, 10_000u64, 0u64))
+            .expect(user_error(ERR_ONLY_CALLABLE_THROUGH_A_PROPOSAL)),
+    );
+    let bob = AddressValue::from(BOB_ADDRESS_EXPR).to_address();
+    state.world.sc_call(
+        ScCallStep::new()
+            .from(ALICE_ADDRESS_EXPR)
+            .call(state.governance.propose(
+                managed_address!(&bob),
+                ManagedBuffer::from("pause"),
+                GAS_LIMIT,
+                ManagedBuffer::from("Not governed"),
+                MultiValueVec::<ManagedBuffer<StaticApi>>::new(),
+            ))
+            .expect(user_error(ERR_TARGET_IS_NOT_GOVERNED)),
+    );
+}
+
+#[test]
+fn deposits_after_the_snapshot_do_not_vote() {
+    let mut state = GovernanceTestState::new();
+    let step = state.propose_config(ALICE_ADDRESS_EXPR, VOTING_PERIOD / 3);
+    state.world.sc_call(step);
+
+    state.world.set_state_step(SetStateStep::new().block_nonce(3));
+    state.deposit(CAROL_ADDRESS_EXPR, "500");
+    state.world.sc_call(
+        ScCallStep::new()
+            .from(CAROL_ADDRESS_EXPR)
+            .call(state.governance.cast_vote(1u32, VoteType::Against))
+            .expect(user_error(ERR_NO_VOTING_POWER_AT_THE_SNAPSHOT)),
+    );
+
+    // Bob withdraws after voting and the vote keeps its weight
+    state.cast_vote(BOB_ADDRESS_EXPR, VoteType::For);
+    state.world.sc_call(
+        ScCallStep::new()
+            .from(BOB_ADDRESS_EXPR)
+            .call(state.governance.withdraw_voting_tokens(401u64))
+            .expect(user_error(ERR_NOT_ENOUGH_DEPOSITED)),
+    );
+    state.world.sc_call(
+        ScCallStep::new()
+            .from(BOB_ADDRESS_EXPR)
+            .call(state.governance.withdraw_voting_tokens(400u64)),
+    );
+    state.world.check_state_step(
+        CheckStateStep::new()
+            .put_account(BOB_ADDRESS_EXPR, CheckAccount::new().esdt_balance(GOV_TOKEN_ID_EXPR, "400"))
+            .put_account(GOVERNANCE_ADDRESS_EXPR, CheckAccount::new().esdt_balance(GOV_TOKEN_ID_EXPR, "1100")),
+    );
+
+    // 400 of the 1000 deposited at the snapshot meets the 40% quorum
+    state.set_block_timestamp(START_TIMESTAMP + VOTING_PERIOD + 1);
+    assert_eq!(state.proposal_status(), ProposalStatus::Succeeded);
+}
+
+#[test]
+fn unvoted_proposal_is_defeated_and_only_the_proposer_cancels() {
+    let mut state = GovernanceTestState::new();
+    let step = state.propose_config(BOB_ADDRESS_EXPR, VOTING_PERIOD / 3);
+    state.world.sc_call(step);
+    let step = state.propose_config(BOB_ADDRESS_EXPR, VOTING_PERIOD / 2);
+    state.world.sc_call(step.expect_value(2u32));
+
+    state.world.sc_call(
+        ScCallStep::new()
+            .from(ALICE_ADDRESS_EXPR)
+            .call(state.governance.cancel_proposal(1u32))
+            .expect(user_error(ERR_ONLY_PROPOSER_CAN_CANCEL)),
+    );
+    state.world.sc_call(ScCallStep::new().from(BOB_ADDRESS_EXPR).call(state.governance.cancel_proposal(1u32)));
+    assert_eq!(state.proposal_status(), ProposalStatus::Cancelled);
+    state.world.sc_call(
+        ScCallStep::new()
+            .from(ALICE_ADDRESS_EXPR)
+            .call(state.governance.cast_vote(1u32, VoteType::For))
+            .expect(user_error(ERR_PROPOSAL_IS_NOT_ACTIVE)),
+    );
+
+    // Nobody voted on the second one, so it misses the quorum
+    state.set_block_timestamp(START_TIMESTAMP + VOTING_PERIOD + 1);
+    let status: ProposalStatus = state.world.quick_query(state.governance.get_proposal_status(2u32));
+    assert_eq!(status, ProposalStatus::Defeated);
+    state.world.sc_call(
+        ScCallStep::new()
+            .from(BOB_ADDRESS_EXPR)
+            .call(state.governance.execute_proposal(2u32))
+            .expect(user_error(ERR_PROPOSAL_HAS_NOT_SUCCEEDED)),
+    );
+}
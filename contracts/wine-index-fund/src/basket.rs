@@ -0,0 +1,315 @@
+use multiversx_sc::derive_imports::*;
+use multiversx_sc::imports::*;
+use wine_common::errors::*;
+use wine_roles::Role;
+
+// The basket is valued on every deposit and redemption, so both stay bounded
+pub const MAX_BASKET_COLLECTIONS: usize = 20;
+pub const MAX_BASKET_WINES: usize = 100;
+
+#[derive(TopEncode, TopDecode, NestedEncode, NestedDecode, TypeAbi, PartialEq, Debug, Clone)]
+pub struct BasketWine<M: ManagedTypeApi> {
+    pub collection: TokenIdentifier<M>,
+    pub nonce: u64,
+}
+
+// Wine NFTs held by the fund plus the proceeds of basket sales. Depositing wine of a curated
+// collection mints shares at its floor value against the fund's net asset value; shares are
+// redeemed at that value for a basket wine or for proceeds. The first deposit mints one share
+// per quote token unit of value.
+#[multiversx_sc::module]
+pub trait BasketModule: wine_roles::RolesModule + crate::fund::FundModule {
+    #[endpoint(addBasketCollection)]
+    fn add_basket_collection(&self, collection: TokenIdentifier) {
+        self.require_role(Role::Admin);
+        require!(collection.is_valid_esdt_identifier(), ERR_INVALID_COLLECTION);
+        require!(
+            self.basket_collections().insert(collection.clone()),
+            ERR_ALREADY_A_BASKET_COLLECTION
+        );
+        require!(
+            self.basket_collections().len() <= MAX_BASKET_COLLECTIONS,
+            ERR_TOO_MANY_BASKET_COLLECTIONS
+        );
+
+        self.basket_collection_added_event(&collection);
+    }
+
+    // Wine already held stays in the basket and keeps being valued at its floor price
+    #[endpoint(removeBasketCollection)]
+    fn remove_basket_collection(&self, collection: TokenIdentifier) {
+        self.require_role(Role::Admin);
+        require!(self.basket_collections().swap_remove(&collection), ERR_NOT_A_BASKET_COLLECTION);
+
+        self.basket_collection_removed_event(&collection);
+    }
+
+    // Deposit wine NFTs of basket collections for index shares
+    #[payable("*")]
+    #[endpoint(depositWine)]
+    fn deposit_wine(&self) -> BigUint {
+        self.require_not_paused();
+        require!(self.share_token().get_token_state().is_set(), ERR_SHARE_TOKEN_NOT_ISSUED);
+        self.accrue_management_fee();
+        // Valued before the deposit lands in the basket
+        let net_asset_value = self.net_asset_value();
+
+        let mut deposit_value = BigUint::zero();
+        for payment in self.call_value().all_esdt_transfers().iter() {
+            require!(
+                self.basket_collections().contains(&payment.token_identifier),
+                ERR_NOT_A_BASKET_COLLECTION
+            );
+            require!(payment.token_nonce > 0 && payment.amount > 0, ERR_INVALID_WINE_PAYMENT);
+            deposit_value += self.wine_value(&payment.token_identifier, &payment.amount);
+            self.add_to_basket(&payment.token_identifier, payment.token_nonce, &payment.amount);
+        }
+        require!(deposit_value > 0, ERR_DEPOSIT_HAS_NO_VALUE);
+
+        let supply = self.share_supply().get();
+        let shares = if supply == 0 {
+            deposit_value.clone()
+        } else {
+            require!(net_asset_value > 0, ERR_FUND_HAS_NO_VALUE);
+            &deposit_value * &supply / &net_asset_value
+        };
+        require!(shares > 0, ERR_DEPOSIT_TOO_SMALL);
+        self.share_supply().set(&(supply + &shares));
+        let depositor = self.blockchain().get_caller();
+        self.share_token().mint_and_send(&depositor, shares.clone());
+
+        self.wine_deposited_event(&depositor, &deposit_value, &shares);
+
+        shares
+    }
+
+    // Redeem shares for bottles of a basket wine, at its floor value. Shares beyond what the
+    // bottles are worth are returned.
+    #[payable("*")]
+    #[endpoint(redeemForWine)]
+    fn redeem_for_wine(&self, collection: TokenIdentifier, nonce: u64, bottles: BigUint) {
+        self.require_not_paused();
+        let paid_shares = self.collect_share_payment();
+        require!(bottles > 0, ERR_BOTTLES_MUST_BE_GREATER_THAN_0);
+        require!(
+            self.basket_amount(&collection, nonce).get() >= bottles,
+            ERR_NOT_ENOUGH_WINE_IN_BASKET
+        );
+        self.accrue_management_fee();
+
+        let net_asset_value = self.net_asset_value();
+        require!(net_asset_value > 0, ERR_FUND_HAS_NO_VALUE);
+        let supply = self.share_supply().get();
+        let value = self.wine_value(&collection, &bottles);
+        // Rounded up, in the fund's favour
+        let shares = (&value * &supply + &net_asset_value - 1u32) / &net_asset_value;
+        require!(paid_shares >= shares, ERR_NOT_ENOUGH_SHARES);
+
+        self.burn_shares(&shares);
+        self.remove_from_basket(&collection, nonce, &bottles);
+        let caller = self.blockchain().get_caller();
+        self.send().direct_esdt(&caller, &collection, nonce, &bottles);
+        let excess = paid_shares - &shares;
+        if excess > 0 {
+            self.send().direct_esdt(&caller, &self.share_token().get_token_id(), 0, &excess);
+        }
+
+        self.redeemed_for_wine_event(&caller, &collection, nonce, &bottles, &shares);
+    }
+
+    // Redeem shares for their value in sale proceeds
+    #[payable("*")]
+    #[endpoint(redeemForProceeds)]
+    fn redeem_for_proceeds(&self) -> BigUint {
+        self.require_not_paused();
+        let shares = self.collect_share_payment();
+        self.accrue_management_fee();
+
+        let payout = &shares * &self.net_asset_value() / &self.share_supply().get();
+        require!(payout > 0, ERR_NOTHING_TO_REDEEM);
+        require!(payout <= self.proceeds().get(), ERR_NOT_ENOUGH_PROCEEDS);
+        self.proceeds().update(|proceeds| *proceeds -= &payout);
+        self.burn_shares(&shares);
+        let caller = self.blockchain().get_caller();
+        self.send().direct(&caller, &self.quote_token().get(), 0, &payout);
+
+        self.redeemed_for_proceeds_event(&caller, &shares, &payout);
+
+        payout
+    }
+
+    // Offer bottles of a basket wine for sale at a fixed price per bottle, in the quote token.
+    // A price of 0 withdraws the offer.
+    #[endpoint(setBasketWineSalePrice)]
+    fn set_basket_wine_sale_price(&self, collection: TokenIdentifier, nonce: u64, price: BigUint) {
+        self.require_role(Role::Admin);
+        require!(self.basket_amount(&collection, nonce).get() > 0, ERR_WINE_NOT_IN_BASKET);
+        if price == 0 {
+            self.sale_prices(&collection, nonce).clear();
+        } else {
+            self.sale_prices(&collection, nonce).set(&price);
+        }
+
+        self.basket_wine_sale_price_set_event(&collection, nonce, &price);
+    }
+
+    // Buy bottles offered for sale; the payment adds to the proceeds shareholders can redeem
+    #[payable("*")]
+    #[endpoint(buyBasketWine)]
+    fn buy_basket_wine(&self, collection: TokenIdentifier, nonce: u64, bottles: BigUint) {
+        self.require_not_paused();
+        require!(!self.sale_prices(&collection, nonce).is_empty(), ERR_WINE_IS_NOT_FOR_SALE);
+        require!(bottles > 0, ERR_BOTTLES_MUST_BE_GREATER_THAN_0);
+        require!(
+            self.basket_amount(&collection, nonce).get() >= bottles,
+            ERR_NOT_ENOUGH_WINE_IN_BASKET
+        );
+        let payment = self.call_value().egld_or_single_esdt();
+        require!(payment.token_identifier == self.quote_token().get(), ERR_INVALID_PAYMENT_TOKEN);
+        let price = self.sale_prices(&collection, nonce).get() * &bottles;
+        require!(payment.amount == price, ERR_INVALID_PAYMENT_AMOUNT);
+
+        self.remove_from_basket(&collection, nonce, &bottles);
+        self.proceeds().update(|proceeds| *proceeds += &price);
+        let buyer = self.blockchain().get_caller();
+        self.send().direct_esdt(&buyer, &collection, nonce, &bottles);
+
+        self.basket_wine_sold_event(&buyer, &collection, nonce, &bottles, &price);
+    }
+
+    // Internal helpers
+    // Proceeds plus every basket wine at its floor value; fails on a stale floor price
+    fn net_asset_value(&self) -> BigUint {
+        let mut value = self.proceeds().get();
+        for wine in self.basket_wines().iter() {
+            let bottles = self.basket_amount(&wine.collection, wine.nonce).get();
+            value += self.wine_value(&wine.collection, &bottles);
+        }
+        value
+    }
+
+    fn add_to_basket(&self, collection: &TokenIdentifier, nonce: u64, amount: &BigUint) {
+        self.basket_wines().insert(BasketWine {
+            collection: collection.clone(),
+            nonce,
+        });
+        require!(self.basket_wines().len() <= MAX_BASKET_WINES, ERR_BASKET_IS_FULL);
+        self.basket_amount(collection, nonce).update(|held| *held += amount);
+    }
+
+    fn remove_from_basket(&self, collection: &TokenIdentifier, nonce: u64, amount: &BigUint) {
+        let remaining = self.basket_amount(collection, nonce).get() - amount;
+        if remaining == 0 {
+            self.basket_amount(collection, nonce).clear();
+            self.sale_prices(collection, nonce).clear();
+            self.basket_wines().swap_remove(&BasketWine {
+                collection: collection.clone(),
+                nonce,
+            });
+        } else {
+            self.basket_amount(collection, nonce).set(&remaining);
+        }
+    }
+
+    fn burn_shares(&self, shares: &BigUint) {
+        self.share_supply().update(|supply| *supply -= shares);
+        self.share_token().burn(shares);
+    }
+
+    // View functions
+    #[view(getNetAssetValue)]
+    fn get_net_asset_value(&self) -> BigUint {
+        self.net_asset_value()
+    }
+
+    // Quote token value of one whole share (10^18 units)
+    #[view(getSharePrice)]
+    fn get_share_price(&self) -> BigUint {
+        let supply = self.share_supply().get();
+        require!(supply > 0, ERR_NO_SHARES_ISSUED);
+        self.net_asset_value() * crate::fund::PRICE_PRECISION / supply
+    }
+
+    // (wine, bottles held, sale price or 0)
+    #[view(getBasket)]
+    fn get_basket(&self) -> MultiValueEncoded<MultiValue3<BasketWine<Self::Api>, BigUint, BigUint>> {
+        let mut result = MultiValueEncoded::new();
+        for wine in self.basket_wines().iter() {
+            let amount = self.basket_amount(&wine.collection, wine.nonce).get();
+            let sale_price = self.sale_prices(&wine.collection, wine.nonce).get();
+            result.push((wine, amount, sale_price).into());
+        }
+        result
+    }
+
+    #[view(getBasketCollections)]
+    fn get_basket_collections(&self) -> MultiValueEncoded<TokenIdentifier> {
+        self.basket_collections().iter().collect()
+    }
+
+    // Storage mappers
+    #[storage_mapper("basketWines")]
+    fn basket_wines(&self) -> UnorderedSetMapper<BasketWine<Self::Api>>;
+
+    #[storage_mapper("basketAmount")]
+    fn basket_amount(&self, collection: &TokenIdentifier, nonce: u64) -> SingleValueMapper<BigUint>;
+
+    #[storage_mapper("salePrices")]
+    fn sale_prices(&self, collection: &TokenIdentifier, nonce: u64) -> SingleValueMapper<BigUint>;
+
+    // Quote token from basket sales, not yet redeemed
+    #[view(getProceeds)]
+    #[storage_mapper("proceeds")]
+    fn proceeds(&self) -> SingleValueMapper<BigUint>;
+
+    // Events
+    #[event("basketCollectionAdded")]
+    fn basket_collection_added_event(&self, #[indexed] collection: &TokenIdentifier);
+
+    #[event("basketCollectionRemoved")]
+    fn basket_collection_removed_event(&self, #[indexed] collection: &TokenIdentifier);
+
+    #[event("wineDeposited")]
+    fn wine_deposited_event(
+        &self,
+        #[indexed] depositor: &ManagedAddress,
+        #[indexed] value: &BigUint,
+        shares: &BigUint,
+    );
+
+    #[event("redeemedForWine")]
+    fn redeemed_for_wine_event(
+        &self,
+        #[indexed] holder: &ManagedAddress,
+        #[indexed] collection: &TokenIdentifier,
+        #[indexed] nonce: u64,
+        #[indexed] bottles: &BigUint,
+        shares: &BigUint,
+    );
+
+    #[event("redeemedForProceeds")]
+    fn redeemed_for_proceeds_event(
+        &self,
+        #[indexed] holder: &ManagedAddress,
+        #[indexed] shares: &BigUint,
+        payout: &BigUint,
+    );
+
+    #[event("basketWineSalePriceSet")]
+    fn basket_wine_sale_price_set_event(
+        &self,
+        #[indexed] collection: &TokenIdentifier,
+        #[indexed] nonce: u64,
+        price: &BigUint,
+    );
+
+    #[event("basketWineSold")]
+    fn basket_wine_sold_event(
+        &self,
+        #[indexed] buyer: &ManagedAddress,
+        #[indexed] collection: &TokenIdentifier,
+        #[indexed] nonce: u64,
+        #[indexed] bottles: &BigUint,
+        price: &BigUint,
+    );
+}
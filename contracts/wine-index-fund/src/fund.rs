@@ -0,0 +1,247 @@
+use multiversx_sc::derive_imports::*;
+use multiversx_sc::imports::*;
+use wine_common::errors::*;
+use wine_roles::Role;
+
+// Index shares use the same precision as oracle prices
+pub const SHARE_DECIMALS: usize = 18;
+// Oracle prices: 1 unit of the base token is worth price / 10^18 units of the quote token
+pub const PRICE_PRECISION: u64 = 1_000_000_000_000_000_000;
+pub const SECONDS_PER_YEAR: u64 = 365 * 24 * 60 * 60;
+// Management fee cap, in basis points per year
+pub const MAX_MANAGEMENT_FEE_BPS: u64 = 500;
+
+#[derive(TopEncode, TopDecode, NestedEncode, NestedDecode, TypeAbi, PartialEq, Debug)]
+pub struct FloorPrice<M: ManagedTypeApi> {
+    // Quote token per bottle of the collection, scaled by PRICE_PRECISION
+    pub price: BigUint<M>,
+    pub timestamp: u64,
+}
+
+#[derive(TopEncode, TopDecode, TypeAbi, PartialEq, Debug)]
+pub struct ManagementFee<M: ManagedTypeApi> {
+    pub recipient: ManagedAddress<M>,
+    pub fee_bps: u64, // per year
+    pub last_accrual_timestamp: u64,
+}
+
+// The index share token, the floor prices the basket is valued at and the management fee.
+// Floor prices are pushed by the wine-oracle through `setExchangeRate`, with a basket
+// collection as the base token and the fund's quote token as the quote. The management fee
+// accrues as newly minted shares, diluting holders over time.
+#[multiversx_sc::module]
+pub trait FundModule: wine_roles::RolesModule {
+    // Requires the issue cost in EGLD
+    #[payable("EGLD")]
+    #[endpoint(issueShareToken)]
+    fn issue_share_token(&self, display_name: ManagedBuffer, ticker: ManagedBuffer) {
+        self.require_role(Role::Admin);
+        require!(self.share_token().is_empty(), ERR_SHARE_TOKEN_ALREADY_ISSUED);
+
+        let issue_cost = self.call_value().egld_value().clone_value();
+        self.share_token().issue_and_set_all_roles(
+            issue_cost,
+            display_name,
+            ticker,
+            SHARE_DECIMALS,
+            Some(self.callbacks().share_token_issued()),
+        );
+    }
+
+    #[endpoint(setPriceOracle)]
+    fn set_price_oracle(&self, oracle: ManagedAddress) {
+        self.require_role(Role::Admin);
+        require!(self.blockchain().is_smart_contract(&oracle), ERR_ORACLE_MUST_BE_A_SMART_CONTRACT);
+        self.price_oracle().set(&oracle);
+    }
+
+    // Floor prices older than this are not used to value the basket
+    #[endpoint(setMaxPriceAge)]
+    fn set_max_price_age(&self, max_price_age: u64) {
+        self.require_role(Role::Admin);
+        require!(max_price_age > 0, ERR_MAXIMUM_PRICE_AGE_MUST_BE_GREATER_THAN_0);
+        self.max_price_age().set(max_price_age);
+    }
+
+    // Pushed by the price oracle, or set by an admin. Pairs the fund does not value are ignored,
+    // so the oracle can push all its aggregates here.
+    #[endpoint(setExchangeRate)]
+    fn set_exchange_rate(
+        &self,
+        base_token: EgldOrEsdtTokenIdentifier,
+        quote_token: EgldOrEsdtTokenIdentifier,
+        price: BigUint,
+    ) {
+        let caller = self.blockchain().get_caller();
+        let is_oracle = !self.price_oracle().is_empty() && caller == self.price_oracle().get();
+        require!(
+            self.has_role(Role::Admin, &caller) || is_oracle,
+            ERR_ONLY_ADMIN_OR_PRICE_ORACLE_CAN_SET_PRICES
+        );
+        if quote_token != self.quote_token().get() || !base_token.is_esdt() {
+            return;
+        }
+        let collection = base_token.unwrap_esdt();
+        if !self.basket_collections().contains(&collection) {
+            return;
+        }
+        require!(price > 0, ERR_PRICE_MUST_BE_GREATER_THAN_0);
+        self.floor_prices(&collection).set(&FloorPrice {
+            price: price.clone(),
+            timestamp: self.blockchain().get_block_timestamp(),
+        });
+
+        self.floor_price_updated_event(&collection, &price);
+    }
+
+    // Setting the fee accrues what is due at the previous rate first
+    #[endpoint(setManagementFee)]
+    fn set_management_fee(&self, recipient: ManagedAddress, fee_bps: u64) {
+        self.require_role(Role::FeeManager);
+        require!(!recipient.is_zero(), ERR_INVALID_FEE_RECIPIENT);
+        require!(fee_bps <= MAX_MANAGEMENT_FEE_BPS, ERR_MANAGEMENT_FEE_TOO_HIGH);
+        self.accrue_management_fee();
+        self.management_fee().set(&ManagementFee {
+            recipient: recipient.clone(),
+            fee_bps,
+            last_accrual_timestamp: self.blockchain().get_block_timestamp(),
+        });
+
+        self.management_fee_set_event(&recipient, fee_bps);
+    }
+
+    // Anyone can trigger an accrual; deposits and redemptions accrue first as well
+    #[endpoint(accrueManagementFee)]
+    fn accrue_management_fee_endpoint(&self) -> BigUint {
+        self.accrue_management_fee()
+    }
+
+    // Internal helpers
+    // Mints the fee shares due since the last accrual to the fee recipient
+    fn accrue_management_fee(&self) -> BigUint {
+        if self.management_fee().is_empty() {
+            return BigUint::zero();
+        }
+        let mut fee = self.management_fee().get();
+        let now = self.blockchain().get_block_timestamp();
+        let elapsed = now - fee.last_accrual_timestamp;
+        fee.last_accrual_timestamp = now;
+        self.management_fee().set(&fee);
+
+        let supply = self.share_supply().get();
+        let fee_shares = &supply * fee.fee_bps * elapsed / (10_000u64 * SECONDS_PER_YEAR);
+        if fee_shares > 0 {
+            self.share_supply().set(&(supply + &fee_shares));
+            self.share_token().mint_and_send(&fee.recipient, fee_shares.clone());
+
+            self.management_fee_accrued_event(&fee.recipient, &fee_shares);
+        }
+        fee_shares
+    }
+
+    // Fresh floor price of a basket collection
+    fn require_floor_price(&self, collection: &TokenIdentifier) -> BigUint {
+        require!(!self.floor_prices(collection).is_empty(), ERR_NO_FLOOR_PRICE_FOR_COLLECTION);
+        let floor_price = self.floor_prices(collection).get();
+        let max_price_age = self.max_price_age().get();
+        require!(
+            self.blockchain().get_block_timestamp() <= floor_price.timestamp + max_price_age,
+            ERR_FLOOR_PRICE_IS_STALE
+        );
+        floor_price.price
+    }
+
+    fn wine_value(&self, collection: &TokenIdentifier, bottles: &BigUint) -> BigUint {
+        bottles * &self.require_floor_price(collection) / PRICE_PRECISION
+    }
+
+    // Collects the share tokens paid to the current call
+    fn collect_share_payment(&self) -> BigUint {
+        let payment = self.call_value().single_esdt();
+        self.share_token().require_same_token(&payment.token_identifier);
+        require!(payment.amount > 0, ERR_AMOUNT_MUST_BE_GREATER_THAN_0);
+        payment.amount
+    }
+
+    #[callback]
+    fn share_token_issued(&self, #[call_result] result: ManagedAsyncCallResult<TokenIdentifier>) {
+        match result {
+            ManagedAsyncCallResult::Ok(token_id) => {
+                self.share_token().set_token_id(token_id);
+            },
+            ManagedAsyncCallResult::Err(_) => {
+                self.share_token().clear();
+            },
+        }
+    }
+
+    // View functions
+    // Empty until the share token is issued
+    #[view(getShareToken)]
+    fn get_share_token(&self) -> OptionalValue<TokenIdentifier> {
+        if self.share_token().get_token_state().is_set() {
+            OptionalValue::Some(self.share_token().get_token_id())
+        } else {
+            OptionalValue::None
+        }
+    }
+
+    #[view(getFloorPrice)]
+    fn get_floor_price(&self, collection: TokenIdentifier) -> OptionalValue<FloorPrice<Self::Api>> {
+        if self.floor_prices(&collection).is_empty() {
+            OptionalValue::None
+        } else {
+            OptionalValue::Some(self.floor_prices(&collection).get())
+        }
+    }
+
+    #[view(getManagementFee)]
+    fn get_management_fee(&self) -> OptionalValue<ManagementFee<Self::Api>> {
+        if self.management_fee().is_empty() {
+            OptionalValue::None
+        } else {
+            OptionalValue::Some(self.management_fee().get())
+        }
+    }
+
+    // Storage mappers
+    #[storage_mapper("shareToken")]
+    fn share_token(&self) -> FungibleTokenMapper;
+
+    // Shares minted minus shares burned
+    #[view(getShareSupply)]
+    #[storage_mapper("shareSupply")]
+    fn share_supply(&self) -> SingleValueMapper<BigUint>;
+
+    // Token floor prices are quoted in, sales are paid in and redemptions pay out
+    #[view(getQuoteToken)]
+    #[storage_mapper("quoteToken")]
+    fn quote_token(&self) -> SingleValueMapper<EgldOrEsdtTokenIdentifier>;
+
+    #[view(getPriceOracle)]
+    #[storage_mapper("priceOracle")]
+    fn price_oracle(&self) -> SingleValueMapper<ManagedAddress>;
+
+    #[view(getMaxPriceAge)]
+    #[storage_mapper("maxPriceAge")]
+    fn max_price_age(&self) -> SingleValueMapper<u64>;
+
+    #[storage_mapper("floorPrices")]
+    fn floor_prices(&self, collection: &TokenIdentifier) -> SingleValueMapper<FloorPrice<Self::Api>>;
+
+    #[storage_mapper("managementFee")]
+    fn management_fee(&self) -> SingleValueMapper<ManagementFee<Self::Api>>;
+
+    #[storage_mapper("basketCollections")]
+    fn basket_collections(&self) -> UnorderedSetMapper<TokenIdentifier>;
+
+    // Events
+    #[event("floorPriceUpdated")]
+    fn floor_price_updated_event(&self, #[indexed] collection: &TokenIdentifier, price: &BigUint);
+
+    #[event("managementFeeSet")]
+    fn management_fee_set_event(&self, #[indexed] recipient: &ManagedAddress, fee_bps: u64);
+
+    #[event("managementFeeAccrued")]
+    fn management_fee_accrued_event(&self, #[indexed] recipient: &ManagedAddress, shares: &BigUint);
+}
@@ -0,0 +1,26 @@
+#![no_std]
+
+use multiversx_sc::imports::*;
+use wine_common::errors::*;
+
+pub mod basket;
+pub mod fund;
+
+pub use basket::BasketWine;
+pub use fund::{FloorPrice, ManagementFee};
+
+// A curated basket of wine NFTs held against fungible index shares, priced at collection floor
+// prices from the wine-oracle
+#[multiversx_sc::contract]
+pub trait WineIndexFund: basket::BasketModule + fund::FundModule + wine_roles::RolesModule {
+    #[init]
+    fn init(&self, quote_token: EgldOrEsdtTokenIdentifier, max_price_age: u64) {
+        require!(quote_token.is_valid(), ERR_INVALID_QUOTE_TOKEN);
+        require!(max_price_age > 0, ERR_MAXIMUM_PRICE_AGE_MUST_BE_GREATER_THAN_0);
+        self.quote_token().set(&quote_token);
+        self.max_price_age().set(max_price_age);
+    }
+
+    #[upgrade]
+    fn upgrade(&self) {}
+}
@@ -0,0 +1,280 @@
+use multiversx_sc_scenario::api::StaticApi;
+use multiversx_sc_scenario::imports::*;
+use wine_common::errors::*;
+use wine_index_fund::basket::ProxyTrait as _;
+use wine_index_fund::fund::{ProxyTrait as _, PRICE_PRECISION, SECONDS_PER_YEAR};
+use wine_index_fund::ProxyTrait as _;
+
+const INDEX_FUND_PATH_EXPR: &str = "mxsc:output/wine-index-fund.mxsc.json";
+const INDEX_FUND_ADDRESS_EXPR: &str = "sc:wine-index-fund";
+const ESDT_SYSTEM_SC_ADDRESS_EXPR: &str = "0x000000000000000000010000000000000000000000000000000000000002ffff";
+const OWNER_ADDRESS_EXPR: &str = "address:owner";
+const ALICE_ADDRESS_EXPR: &str = "address:alice";
+const BOB_ADDRESS_EXPR: &str = "address:bob";
+
+const WINE_NFT_TOKEN_ID: &str = "WINE-abcdef";
+const WINE_NFT_TOKEN_ID_EXPR: &str = "str:WINE-abcdef";
+const OTHER_NFT_TOKEN_ID_EXPR: &str = "str:OTHER-abcdef";
+const SHARE_TOKEN_ID: &str = "SHARE-abcdef";
+const SHARE_TOKEN_ID_EXPR: &str = "str:SHARE-abcdef";
+
+const START_TIMESTAMP: u64 = 1_000;
+const MAX_PRICE_AGE: u64 = 100;
+
+type IndexFundContract = ContractInfo<wine_index_fund::Proxy<StaticApi>>;
+
+fn user_error(message: &str) -> TxExpect {
+    TxExpect::user_error(format!("str:{message}"))
+}
+
+struct IndexFundTestState {
+    world: ScenarioWorld,
+    index_fund: IndexFundContract,
+}
+
+impl IndexFundTestState {
+    // An EGLD-quoted fund over the WINE collection, valued at 100 EGLD per bottle. Alice holds
+    // three bottles of wine NFT 1, Bob two of NFT 2 and 1000 EGLD.
+    fn new() -> Self {
+        let mut world = ScenarioWorld::new();
+        world.register_contract(INDEX_FUND_PATH_EXPR, wine_index_fund::ContractBuilder);
+
+        let index_fund_code = world.code_expression(INDEX_FUND_PATH_EXPR);
+        world.set_state_step(
+            SetStateStep::new()
+                .block_timestamp(START_TIMESTAMP)
+                .put_account(OWNER_ADDRESS_EXPR, Account::new().nonce(1).balance("50000"))
+                .put_account(
+                    ALICE_ADDRESS_EXPR,
+                    Account::new()
+                        .nonce(1)
+                        .esdt_nft_balance(WINE_NFT_TOKEN_ID_EXPR, 1, "3", Option::<&str>::None)
+                        .esdt_nft_balance(OTHER_NFT_TOKEN_ID_EXPR, 1, "1", Option::<&str>::None),
+                )
+                .put_account(
+                    BOB_ADDRESS_EXPR,
+                    Account::new()
+                        .nonce(1)
+                        .balance("1000")
+                        .esdt_nft_balance(WINE_NFT_TOKEN_ID_EXPR, 2, "2", Option::<&str>::None),
+                )
+                .put_account(ESDT_SYSTEM_SC_ADDRESS_EXPR, Account::new().code(index_fund_code.clone()))
+                .new_address(OWNER_ADDRESS_EXPR, 1, INDEX_FUND_ADDRESS_EXPR)
+                .new_token_identifier(SHARE_TOKEN_ID),
+        );
+
+        let mut index_fund = IndexFundContract::new(INDEX_FUND_ADDRESS_EXPR);
+        world.sc_deploy(
+            ScDeployStep::new()
+                .from(OWNER_ADDRESS_EXPR)
+                .code(index_fund_code)
+                .call(index_fund.init(EgldOrEsdtTokenIdentifier::egld(), MAX_PRICE_AGE)),
+        );
+        world.sc_call(
+            ScCallStep::new()
+                .from(OWNER_ADDRESS_EXPR)
+                .egld_value("50000")
+                .call(index_fund.issue_share_token(ManagedBuffer::from("WineIndex"), ManagedBuffer::from("SHARE"))),
+        );
+
+        // The basket is curated by admins
+        world.sc_call(
+            ScCallStep::new()
+                .from(ALICE_ADDRESS_EXPR)
+                .call(index_fund.add_basket_collection(TokenIdentifier::from(WINE_NFT_TOKEN_ID)))
+                .expect(user_error(ERR_CALLER_DOES_NOT_HAVE_THE_REQUIRED_ROLE)),
+        );
+        world.sc_call(
+            ScCallStep::new()
+                .from(OWNER_ADDRESS_EXPR)
+                .call(index_fund.add_basket_collection(TokenIdentifier::from(WINE_NFT_TOKEN_ID))),
+        );
+
+        let mut state = IndexFundTestState { world, index_fund };
+        let step = state.set_floor_price(OWNER_ADDRESS_EXPR, 100);
+        state.world.sc_call(step);
+        state
+    }
+
+    fn set_block_timestamp(&mut self, timestamp: u64) {
+        self.world.set_state_step(SetStateStep::new().block_timestamp(timestamp));
+    }
+
+    // In EGLD per bottle
+    fn set_floor_price(&mut self, caller: &str, price: u64) -> TypedScCall<()> {
+        ScCallStep::new().from(caller).call(self.index_fund.set_exchange_rate(
+            EgldOrEsdtTokenIdentifier::esdt(WINE_NFT_TOKEN_ID),
+            EgldOrEsdtTokenIdentifier::egld(),
+            BigUint::from(price) * PRICE_PRECISION,
+        ))
+    }
+
+    fn deposit_wine(&mut self, depositor: &str, nonce: u64, bottles: &str) -> TypedScCall<BigUint<StaticApi>> {
+        ScCallStep::new()
+            .from(depositor)
+            .esdt_transfer(WINE_NFT_TOKEN_ID_EXPR, nonce, bottles)
+            .call(self.index_fund.deposit_wine())
+    }
+
+    fn redeem_for_proceeds(&mut self, holder: &str, shares: &str) -> TypedScCall<BigUint<StaticApi>> {
+        ScCallStep::new()
+            .from(holder)
+            .esdt_transfer(SHARE_TOKEN_ID_EXPR, 0, shares)
+            .call(self.index_fund.redeem_for_proceeds())
+    }
+}
+
+#[test]
+fn deposits_and_redemptions_are_priced_at_net_asset_value() {
+    let mut state = IndexFundTestState::new();
+
+    state.world.sc_call(
+        ScCallStep::new()
+            .from(ALICE_ADDRESS_EXPR)
+            .esdt_transfer(OTHER_NFT_TOKEN_ID_EXPR, 1, "1")
+            .call(state.index_fund.deposit_wine())
+            .expect(user_error(ERR_NOT_A_BASKET_COLLECTION)),
+    );
+    // The first deposit mints one share per EGLD of value
+    let step = state.deposit_wine(ALICE_ADDRESS_EXPR, 1, "2");
+    state.world.sc_call(step.expect_value(BigUint::from(200u64)));
+
+    // Doubling the floor price doubles the value of each share
+    let step = state.set_floor_price(ALICE_ADDRESS_EXPR, 200);
+    state.world.sc_call(step.expect(user_error(ERR_ONLY_ADMIN_OR_PRICE_ORACLE_CAN_SET_PRICES)));
+    let step = state.set_floor_price(OWNER_ADDRESS_EXPR, 200);
+    state.world.sc_call(step);
+    let step = state.deposit_wine(BOB_ADDRESS_EXPR, 2, "1");
+    state.world.sc_call(step.expect_value(BigUint::from(100u64)));
+    let net_asset_value: BigUint<StaticApi> = state.world.quick_query(state.index_fund.get_net_asset_value());
+    assert_eq!(net_asset_value, 600u64);
+
+    // A bottle of NFT 2 costs Alice 100 shares; the rest of what she paid comes back
+    state.world.sc_call(
+        ScCallStep::new()
+            .from(ALICE_ADDRESS_EXPR)
+            .esdt_transfer(SHARE_TOKEN_ID_EXPR, 0, "99")
+            .call(state.index_fund.redeem_for_wine(TokenIdentifier::from(WINE_NFT_TOKEN_ID), 2u64, 1u64))
+            .expect(user_error(ERR_NOT_ENOUGH_SHARES)),
+    );
+    state.world.sc_call(
+        ScCallStep::new()
+            .from(ALICE_ADDRESS_EXPR)
+            .esdt_transfer(SHARE_TOKEN_ID_EXPR, 0, "150")
+            .call(state.index_fund.redeem_for_wine(TokenIdentifier::from(WINE_NFT_TOKEN_ID), 2u64, 1u64)),
+    );
+
+    // Without a fresh floor price the basket cannot be valued
+    state.set_block_timestamp(START_TIMESTAMP + MAX_PRICE_AGE + 1);
+    let step = state.deposit_wine(ALICE_ADDRESS_EXPR, 1, "1");
+    state.world.sc_call(step.expect(user_error(ERR_FLOOR_PRICE_IS_STALE)));
+
+    state.world.check_state_step(
+        CheckStateStep::new()
+            .put_account(
+                ALICE_ADDRESS_EXPR,
+                CheckAccount::new()
+                    .esdt_balance(SHARE_TOKEN_ID_EXPR, "100")
+                    .esdt_nft_balance_and_attributes(WINE_NFT_TOKEN_ID_EXPR, 1, "1", Some(""))
+                    .esdt_nft_balance_and_attributes(WINE_NFT_TOKEN_ID_EXPR, 2, "1", Some("")),
+            )
+            .put_account(BOB_ADDRESS_EXPR, CheckAccount::new().esdt_balance(SHARE_TOKEN_ID_EXPR, "100"))
+            .put_account(
+                INDEX_FUND_ADDRESS_EXPR,
+                CheckAccount::new().esdt_nft_balance_and_attributes(WINE_NFT_TOKEN_ID_EXPR, 1, "2", Some("")),
+            ),
+    );
+}
+
+#[test]
+fn basket_sales_are_redeemed_as_proceeds() {
+    let mut state = IndexFundTestState::new();
+    let step = state.deposit_wine(ALICE_ADDRESS_EXPR, 1, "2");
+    state.world.sc_call(step);
+
+    let wine = TokenIdentifier::from(WINE_NFT_TOKEN_ID);
+    state.world.sc_call(
+        ScCallStep::new()
+            .from(ALICE_ADDRESS_EXPR)
+            .call(state.index_fund.set_basket_wine_sale_price(wine.clone(), 1u64, 150u64))
+            .expect(user_error(ERR_CALLER_DOES_NOT_HAVE_THE_REQUIRED_ROLE)),
+    );
+    state.world.sc_call(
+        ScCallStep::new()
+            .from(OWNER_ADDRESS_EXPR)
+            .call(state.index_fund.set_basket_wine_sale_price(wine.clone(), 1u64, 150u64)),
+    );
+    state.world.sc_call(
+        ScCallStep::new()
+            .from(BOB_ADDRESS_EXPR)
+            .egld_value(100u64)
+            .call(state.index_fund.buy_basket_wine(wine.clone(), 1u64, 1u64))
+            .expect(user_error(ERR_INVALID_PAYMENT_AMOUNT)),
+    );
+    state.world.sc_call(
+        ScCallStep::new()
+            .from(BOB_ADDRESS_EXPR)
+            .egld_value(150u64)
+            .call(state.index_fund.buy_basket_wine(wine, 1u64, 1u64)),
+    );
+
+    // 150 EGLD of proceeds plus a bottle at 100 EGLD, over 200 shares
+    let step = state.redeem_for_proceeds(ALICE_ADDRESS_EXPR, "200");
+    state.world.sc_call(step.expect(user_error(ERR_NOT_ENOUGH_PROCEEDS)));
+    let step = state.redeem_for_proceeds(ALICE_ADDRESS_EXPR, "100");
+    state.world.sc_call(step.expect_value(BigUint::from(125u64)));
+
+    state.world.check_state_step(
+        CheckStateStep::new()
+            .put_account(
+                ALICE_ADDRESS_EXPR,
+                CheckAccount::new().balance("125").esdt_balance(SHARE_TOKEN_ID_EXPR, "100"),
+            )
+            .put_account(
+                BOB_ADDRESS_EXPR,
+                CheckAccount::new()
+                    .balance("850")
+                    .esdt_nft_balance_and_attributes(WINE_NFT_TOKEN_ID_EXPR, 1, "1", Some("")),
+            )
+            .put_account(INDEX_FUND_ADDRESS_EXPR, CheckAccount::new().balance("25")),
+    );
+}
+
+#[test]
+fn management_fee_dilutes_holders_over_time() {
+    let mut state = IndexFundTestState::new();
+    let step = state.deposit_wine(ALICE_ADDRESS_EXPR, 1, "2");
+    state.world.sc_call(step);
+    let owner = AddressValue::from(OWNER_ADDRESS_EXPR).to_address();
+
+    state.world.sc_call(
+        ScCallStep::new()
+            .from(ALICE_ADDRESS_EXPR)
+            .call(state.index_fund.set_management_fee(managed_address!(&owner), 500u64))
+            .expect(user_error(ERR_CALLER_DOES_NOT_HAVE_THE_REQUIRED_ROLE)),
+    );
+    state.world.sc_call(
+        ScCallStep::new()
+            .from(OWNER_ADDRESS_EXPR)
+            .call(state.index_fund.set_management_fee(managed_address!(&owner), 501u64))
+            .expect(user_error(ERR_MANAGEMENT_FEE_TOO_HIGH)),
+    );
+    state.world.sc_call(
+        ScCallStep::new()
+            .from(OWNER_ADDRESS_EXPR)
+            .call(state.index_fund.set_management_fee(managed_address!(&owner), 500u64)),
+    );
+
+    // 5% of the supply over a year, minted to the fee recipient on accrual
+    state.set_block_timestamp(START_TIMESTAMP + SECONDS_PER_YEAR);
+    state.world.sc_call(
+        ScCallStep::new()
+            .from(BOB_ADDRESS_EXPR)
+            .call(state.index_fund.accrue_management_fee_endpoint())
+            .expect_value(BigUint::from(10u64)),
+    );
+    state.world.check_state_step(
+        CheckStateStep::new()
+            .put_account(OWNER_ADDRESS_EXPR, CheckAccount::new().esdt_balance(SHARE_TOKEN_ID_EXPR, "10")),
+    );
+}
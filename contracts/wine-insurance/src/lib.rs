@@ -0,0 +1,32 @@
+#![no_std]
+
+use multiversx_sc::imports::*;
+use wine_common::errors::*;
+
+pub mod policies;
+pub mod underwriting;
+
+pub use policies::{ClaimReason, Policy, PolicyClaim, PolicyStatus};
+
+// Insurance pool for stored wine. Underwriters stake capital, holders pay premiums to cover
+// bottles of a wine NFT, and claims confirmed by a certification authority are paid from
+// the pool, with the loss shared by underwriters in proportion to their shares.
+#[multiversx_sc::contract]
+pub trait WineInsurance:
+    policies::PoliciesModule + underwriting::UnderwritingModule + wine_roles::RolesModule
+{
+    #[init]
+    fn init(
+        &self,
+        pool_token: EgldOrEsdtTokenIdentifier,
+        premium_bps_per_epoch: u64,
+        max_duration_epochs: u64,
+    ) {
+        require!(pool_token.is_valid(), ERR_INVALID_POOL_TOKEN);
+        self.pool_token().set(&pool_token);
+        self.store_premium_rate(premium_bps_per_epoch, max_duration_epochs);
+    }
+
+    #[upgrade]
+    fn upgrade(&self) {}
+}
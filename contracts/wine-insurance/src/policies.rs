@@ -0,0 +1,291 @@
+use multiversx_sc::derive_imports::*;
+use multiversx_sc::imports::*;
+use wine_common::errors::*;
+use wine_roles::Role;
+
+#[derive(TopEncode, TopDecode, NestedEncode, NestedDecode, TypeAbi, PartialEq, Debug, Clone, Copy)]
+pub enum PolicyStatus {
+    Active,
+    // A claim awaits a certification authority
+    ClaimPending,
+    PaidOut,
+    Expired,
+}
+
+#[derive(TopEncode, TopDecode, NestedEncode, NestedDecode, TypeAbi, PartialEq, Debug, Clone, Copy)]
+pub enum ClaimReason {
+    Breakage,
+    Spoilage,
+}
+
+// Coverage of bottles of one wine NFT nonce, paid out in full on a confirmed claim
+#[derive(TopEncode, TopDecode, TypeAbi, PartialEq, Debug)]
+pub struct Policy<M: ManagedTypeApi> {
+    pub holder: ManagedAddress<M>,
+    pub wine_nonce: u64,
+    pub bottles: u32,
+    pub coverage: BigUint<M>,
+    pub premium: BigUint<M>,
+    pub start_epoch: u64,
+    // Last covered epoch
+    pub end_epoch: u64,
+    pub status: PolicyStatus,
+}
+
+#[derive(TopEncode, TopDecode, TypeAbi, PartialEq, Debug)]
+pub struct PolicyClaim<M: ManagedTypeApi> {
+    pub reason: ClaimReason,
+    // Hash of the damage report and photos, kept off-chain
+    pub evidence_hash: ManagedByteArray<M, 32>,
+    pub filed_timestamp: u64,
+}
+
+// Holders buy coverage for their bottles against breakage and spoilage. Claims are filed
+// during coverage and paid from the pool once a certification authority confirms the loss.
+#[multiversx_sc::module]
+pub trait PoliciesModule: wine_roles::RolesModule + crate::underwriting::UnderwritingModule {
+    // Premium per epoch of coverage, in basis points of the insured value
+    #[endpoint(setPremiumRate)]
+    fn set_premium_rate(&self, premium_bps_per_epoch: u64, max_duration_epochs: u64) {
+        self.require_role(Role::Admin);
+        self.store_premium_rate(premium_bps_per_epoch, max_duration_epochs);
+    }
+
+    #[endpoint(addClaimCertifier)]
+    fn add_claim_certifier(&self, certifier: ManagedAddress) {
+        self.require_role(Role::Admin);
+        require!(self.claim_certifiers().insert(certifier.clone()), ERR_ALREADY_A_CLAIM_CERTIFIER);
+
+        self.claim_certifier_added_event(&certifier);
+    }
+
+    #[endpoint(removeClaimCertifier)]
+    fn remove_claim_certifier(&self, certifier: ManagedAddress) {
+        self.require_role(Role::Admin);
+        require!(self.claim_certifiers().swap_remove(&certifier), ERR_NOT_A_CLAIM_CERTIFIER);
+
+        self.claim_certifier_removed_event(&certifier);
+    }
+
+    // Insure `bottles` of a wine NFT nonce for `coverage` over `duration_epochs`, starting
+    // this epoch. The premium is paid with the call and any excess refunded.
+    #[payable("*")]
+    #[endpoint(buyPolicy)]
+    fn buy_policy(
+        &self,
+        wine_nonce: u64,
+        bottles: u32,
+        coverage: BigUint,
+        duration_epochs: u64,
+    ) -> u32 {
+        self.require_not_paused();
+        require!(wine_nonce > 0, ERR_INVALID_WINE_NONCE);
+        require!(bottles > 0, ERR_BOTTLES_MUST_BE_GREATER_THAN_0);
+        require!(coverage > 0, ERR_COVERAGE_MUST_BE_GREATER_THAN_0);
+        require!(
+            duration_epochs > 0 && duration_epochs <= self.max_duration_epochs().get(),
+            ERR_INVALID_DURATION
+        );
+        require!(coverage <= self.free_capital(), ERR_NOT_ENOUGH_CAPITAL_IN_THE_POOL);
+
+        let premium = &coverage * self.premium_bps_per_epoch().get() * duration_epochs / 10_000u64;
+        let payment = self.call_value().egld_or_single_esdt();
+        require!(payment.token_identifier == self.pool_token().get(), ERR_INVALID_POOL_TOKEN);
+        require!(payment.amount >= premium, ERR_PREMIUM_NOT_COVERED);
+
+        let holder = self.blockchain().get_caller();
+        let start_epoch = self.blockchain().get_block_epoch();
+        let policy_id = self.policy_count().get() + 1;
+        self.policy_count().set(policy_id);
+        self.policies(policy_id).set(&Policy {
+            holder: holder.clone(),
+            wine_nonce,
+            bottles,
+            coverage: coverage.clone(),
+            premium: premium.clone(),
+            start_epoch,
+            end_epoch: start_epoch + duration_epochs - 1,
+            status: PolicyStatus::Active,
+        });
+        self.active_coverage().update(|active| *active += &coverage);
+        self.pool_capital().update(|capital| *capital += &premium);
+
+        let refund = &payment.amount - &premium;
+        if refund > 0 {
+            self.send().direct(&holder, &payment.token_identifier, 0, &refund);
+        }
+
+        self.policy_bought_event(policy_id, &holder, wine_nonce, &coverage, &premium);
+
+        policy_id
+    }
+
+    #[endpoint(fileClaim)]
+    fn file_claim(
+        &self,
+        policy_id: u32,
+        reason: ClaimReason,
+        evidence_hash: ManagedByteArray<Self::Api, 32>,
+    ) {
+        let mut policy = self.require_policy(policy_id);
+        require!(self.blockchain().get_caller() == policy.holder, ERR_ONLY_HOLDER_CAN_FILE_A_CLAIM);
+        require!(policy.status == PolicyStatus::Active, ERR_POLICY_IS_NOT_ACTIVE);
+        require!(
+            self.blockchain().get_block_epoch() <= policy.end_epoch,
+            ERR_POLICY_HAS_EXPIRED
+        );
+
+        policy.status = PolicyStatus::ClaimPending;
+        self.policies(policy_id).set(&policy);
+        self.policy_claims(policy_id).set(&PolicyClaim {
+            reason,
+            evidence_hash,
+            filed_timestamp: self.blockchain().get_block_timestamp(),
+        });
+
+        self.claim_filed_event(policy_id, &policy.holder, reason);
+    }
+
+    // Certification authorities only. A confirmed claim pays the full coverage; a rejected
+    // one puts the policy back in force.
+    #[endpoint(settleClaim)]
+    fn settle_claim(&self, policy_id: u32, confirmed: bool) {
+        let certifier = self.blockchain().get_caller();
+        require!(self.claim_certifiers().contains(&certifier), ERR_NOT_A_CLAIM_CERTIFIER);
+        let mut policy = self.require_policy(policy_id);
+        require!(policy.status == PolicyStatus::ClaimPending, ERR_NO_PENDING_CLAIM);
+
+        if confirmed {
+            policy.status = PolicyStatus::PaidOut;
+            self.active_coverage().update(|active| *active -= &policy.coverage);
+            self.pool_capital().update(|capital| *capital -= &policy.coverage);
+            self.send().direct(&policy.holder, &self.pool_token().get(), 0, &policy.coverage);
+        } else {
+            policy.status = PolicyStatus::Active;
+        }
+        self.policies(policy_id).set(&policy);
+
+        self.claim_settled_event(policy_id, &certifier, confirmed);
+    }
+
+    // Release the coverage of a policy past its end; anyone can call
+    #[endpoint(expirePolicy)]
+    fn expire_policy(&self, policy_id: u32) {
+        let mut policy = self.require_policy(policy_id);
+        require!(policy.status == PolicyStatus::Active, ERR_POLICY_IS_NOT_ACTIVE);
+        require!(
+            self.blockchain().get_block_epoch() > policy.end_epoch,
+            ERR_POLICY_HAS_NOT_EXPIRED
+        );
+
+        policy.status = PolicyStatus::Expired;
+        self.policies(policy_id).set(&policy);
+        self.active_coverage().update(|active| *active -= &policy.coverage);
+
+        self.policy_expired_event(policy_id);
+    }
+
+    // Internal helpers
+    fn store_premium_rate(&self, premium_bps_per_epoch: u64, max_duration_epochs: u64) {
+        require!(premium_bps_per_epoch > 0, ERR_PREMIUM_RATE_MUST_BE_GREATER_THAN_0);
+        require!(max_duration_epochs > 0, ERR_MAXIMUM_DURATION_MUST_BE_GREATER_THAN_0);
+        self.premium_bps_per_epoch().set(premium_bps_per_epoch);
+        self.max_duration_epochs().set(max_duration_epochs);
+    }
+
+    fn require_policy(&self, policy_id: u32) -> Policy<Self::Api> {
+        require!(!self.policies(policy_id).is_empty(), ERR_POLICY_DOES_NOT_EXIST);
+        self.policies(policy_id).get()
+    }
+
+    // View functions
+    #[view(getPremiumRate)]
+    fn get_premium_rate(&self) -> MultiValue2<u64, u64> {
+        (self.premium_bps_per_epoch().get(), self.max_duration_epochs().get()).into()
+    }
+
+    #[view(quotePremium)]
+    fn quote_premium(&self, coverage: BigUint, duration_epochs: u64) -> BigUint {
+        coverage * self.premium_bps_per_epoch().get() * duration_epochs / 10_000u64
+    }
+
+    #[view(isClaimCertifier)]
+    fn is_claim_certifier(&self, address: ManagedAddress) -> bool {
+        self.claim_certifiers().contains(&address)
+    }
+
+    #[view(getPolicy)]
+    fn get_policy(&self, policy_id: u32) -> Policy<Self::Api> {
+        self.require_policy(policy_id)
+    }
+
+    #[view(getPolicyCount)]
+    fn get_policy_count(&self) -> u32 {
+        self.policy_count().get()
+    }
+
+    // Last claim filed against the policy
+    #[view(getPolicyClaim)]
+    fn get_policy_claim(&self, policy_id: u32) -> OptionalValue<PolicyClaim<Self::Api>> {
+        if self.policy_claims(policy_id).is_empty() {
+            OptionalValue::None
+        } else {
+            OptionalValue::Some(self.policy_claims(policy_id).get())
+        }
+    }
+
+    // Storage mappers
+    #[storage_mapper("premiumBpsPerEpoch")]
+    fn premium_bps_per_epoch(&self) -> SingleValueMapper<u64>;
+
+    #[storage_mapper("maxDurationEpochs")]
+    fn max_duration_epochs(&self) -> SingleValueMapper<u64>;
+
+    #[storage_mapper("claimCertifiers")]
+    fn claim_certifiers(&self) -> UnorderedSetMapper<ManagedAddress>;
+
+    #[storage_mapper("policyCount")]
+    fn policy_count(&self) -> SingleValueMapper<u32>;
+
+    #[storage_mapper("policies")]
+    fn policies(&self, policy_id: u32) -> SingleValueMapper<Policy<Self::Api>>;
+
+    #[storage_mapper("policyClaims")]
+    fn policy_claims(&self, policy_id: u32) -> SingleValueMapper<PolicyClaim<Self::Api>>;
+
+    // Events
+    #[event("claimCertifierAdded")]
+    fn claim_certifier_added_event(&self, #[indexed] certifier: &ManagedAddress);
+
+    #[event("claimCertifierRemoved")]
+    fn claim_certifier_removed_event(&self, #[indexed] certifier: &ManagedAddress);
+
+    #[event("policyBought")]
+    fn policy_bought_event(
+        &self,
+        #[indexed] policy_id: u32,
+        #[indexed] holder: &ManagedAddress,
+        #[indexed] wine_nonce: u64,
+        coverage: &BigUint,
+        premium: &BigUint,
+    );
+
+    #[event("claimFiled")]
+    fn claim_filed_event(
+        &self,
+        #[indexed] policy_id: u32,
+        #[indexed] holder: &ManagedAddress,
+        #[indexed] reason: ClaimReason,
+    );
+
+    #[event("claimSettled")]
+    fn claim_settled_event(
+        &self,
+        #[indexed] policy_id: u32,
+        #[indexed] certifier: &ManagedAddress,
+        #[indexed] confirmed: bool,
+    );
+
+    #[event("policyExpired")]
+    fn policy_expired_event(&self, #[indexed] policy_id: u32);
+}
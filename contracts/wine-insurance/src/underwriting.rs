@@ -0,0 +1,150 @@
+use multiversx_sc::imports::*;
+use wine_common::errors::*;
+
+// Underwriters own the pool in shares. Premiums raise the value of every share and paid
+// claims lower it, so gains and losses are split pro rata. Capital backing active coverage
+// cannot be withdrawn.
+#[multiversx_sc::module]
+pub trait UnderwritingModule: wine_roles::RolesModule {
+    #[payable("*")]
+    #[endpoint(stakeCapital)]
+    fn stake_capital(&self) -> BigUint {
+        self.require_not_paused();
+        let payment = self.call_value().egld_or_single_esdt();
+        require!(payment.token_identifier == self.pool_token().get(), ERR_INVALID_POOL_TOKEN);
+        require!(payment.amount > 0, ERR_AMOUNT_MUST_BE_GREATER_THAN_0);
+
+        let pool_capital = self.pool_capital().get();
+        let total_shares = self.total_shares().get();
+        let shares = if total_shares == 0 {
+            payment.amount.clone()
+        } else {
+            require!(pool_capital > 0, ERR_POOL_IS_INSOLVENT);
+            &payment.amount * &total_shares / &pool_capital
+        };
+        require!(shares > 0, ERR_STAKE_TOO_SMALL);
+
+        let underwriter = self.blockchain().get_caller();
+        self.underwriter_shares(&underwriter).update(|owned| *owned += &shares);
+        self.total_shares().set(&total_shares + &shares);
+        self.pool_capital().set(&pool_capital + &payment.amount);
+
+        self.capital_staked_event(&underwriter, &payment.amount, &shares);
+
+        shares
+    }
+
+    #[endpoint(withdrawCapital)]
+    fn withdraw_capital(&self, shares: BigUint) -> BigUint {
+        require!(shares > 0, ERR_SHARES_MUST_BE_GREATER_THAN_0);
+        let underwriter = self.blockchain().get_caller();
+        let owned = self.underwriter_shares(&underwriter).get();
+        require!(shares <= owned, ERR_NOT_ENOUGH_SHARES);
+
+        let pool_capital = self.pool_capital().get();
+        let total_shares = self.total_shares().get();
+        let amount = &shares * &pool_capital / &total_shares;
+        require!(
+            &pool_capital - &amount >= self.active_coverage().get(),
+            ERR_CAPITAL_IS_BACKING_ACTIVE_COVERAGE
+        );
+
+        self.underwriter_shares(&underwriter).set(&owned - &shares);
+        self.total_shares().set(&total_shares - &shares);
+        self.pool_capital().set(&pool_capital - &amount);
+        if amount > 0 {
+            self.send().direct(&underwriter, &self.pool_token().get(), 0, &amount);
+        }
+
+        self.capital_withdrawn_event(&underwriter, &amount, &shares);
+
+        amount
+    }
+
+    // Internal helpers
+    fn free_capital(&self) -> BigUint {
+        let pool_capital = self.pool_capital().get();
+        let active_coverage = self.active_coverage().get();
+        if pool_capital > active_coverage {
+            pool_capital - active_coverage
+        } else {
+            BigUint::zero()
+        }
+    }
+
+    // View functions
+    #[view(getPoolToken)]
+    fn get_pool_token(&self) -> EgldOrEsdtTokenIdentifier {
+        self.pool_token().get()
+    }
+
+    #[view(getPoolCapital)]
+    fn get_pool_capital(&self) -> BigUint {
+        self.pool_capital().get()
+    }
+
+    #[view(getActiveCoverage)]
+    fn get_active_coverage(&self) -> BigUint {
+        self.active_coverage().get()
+    }
+
+    // Capital available for new coverage or withdrawals
+    #[view(getFreeCapital)]
+    fn get_free_capital(&self) -> BigUint {
+        self.free_capital()
+    }
+
+    #[view(getTotalShares)]
+    fn get_total_shares(&self) -> BigUint {
+        self.total_shares().get()
+    }
+
+    #[view(getUnderwriterShares)]
+    fn get_underwriter_shares(&self, underwriter: ManagedAddress) -> BigUint {
+        self.underwriter_shares(&underwriter).get()
+    }
+
+    // Current value of the underwriter's shares
+    #[view(getUnderwriterCapital)]
+    fn get_underwriter_capital(&self, underwriter: ManagedAddress) -> BigUint {
+        let total_shares = self.total_shares().get();
+        if total_shares == 0 {
+            return BigUint::zero();
+        }
+        self.underwriter_shares(&underwriter).get() * self.pool_capital().get() / total_shares
+    }
+
+    // Storage mappers
+    #[storage_mapper("poolToken")]
+    fn pool_token(&self) -> SingleValueMapper<EgldOrEsdtTokenIdentifier>;
+
+    #[storage_mapper("poolCapital")]
+    fn pool_capital(&self) -> SingleValueMapper<BigUint>;
+
+    // Sum of the coverage of policies not yet expired or paid out
+    #[storage_mapper("activeCoverage")]
+    fn active_coverage(&self) -> SingleValueMapper<BigUint>;
+
+    #[storage_mapper("totalShares")]
+    fn total_shares(&self) -> SingleValueMapper<BigUint>;
+
+    #[storage_mapper("underwriterShares")]
+    fn underwriter_shares(&self, underwriter: &ManagedAddress) -> SingleValueMapper<BigUint>;
+
+    // Events
+    #[event("capitalStaked")]
+    fn capital_staked_event(
+        &self,
+        #[indexed] underwriter: &ManagedAddress,
+        amount: &BigUint,
+        shares: &BigUint,
+    );
+
+    #[event("capitalWithdrawn")]
+    fn capital_withdrawn_event(
+        &self,
+        #[indexed] underwriter: &ManagedAddress,
+        amount: &BigUint,
+        shares: &BigUint,
+    );
+}
@@ -0,0 +1,241 @@
+use multiversx_sc_scenario::api::StaticApi;
+use multiversx_sc_scenario::imports::*;
+use wine_common::errors::*;
+use wine_insurance::policies::ProxyTrait as _;
+use wine_insurance::underwriting::ProxyTrait as _;
+use wine_insurance::ProxyTrait as _;
+use wine_insurance::{ClaimReason, Policy, PolicyStatus};
+
+const INSURANCE_PATH_EXPR: &str = "mxsc:output/wine-insurance.mxsc.json";
+const INSURANCE_ADDRESS_EXPR: &str = "sc:wine-insurance";
+const OWNER_ADDRESS_EXPR: &str = "address:owner";
+const UNDERWRITER_ADDRESS_EXPR: &str = "address:underwriter";
+const OTHER_UNDERWRITER_ADDRESS_EXPR: &str = "address:other-underwriter";
+const HOLDER_ADDRESS_EXPR: &str = "address:holder";
+const CERTIFIER_ADDRESS_EXPR: &str = "address:certifier";
+
+const PREMIUM_BPS_PER_EPOCH: u64 = 100;
+const MAX_DURATION_EPOCHS: u64 = 12;
+
+type InsuranceContract = ContractInfo<wine_insurance::Proxy<StaticApi>>;
+
+fn user_error(message: &str) -> TxExpect {
+    TxExpect::user_error(format!("str:{message}"))
+}
+
+struct InsuranceTestState {
+    world: ScenarioWorld,
+    insurance: InsuranceContract,
+}
+
+impl InsuranceTestState {
+    // A pool of 10000 EGLD, 6000 from the underwriter and 4000 from the other one, with the
+    // certifier confirming claims. Starts in epoch 1.
+    fn new() -> Self {
+        let mut world = ScenarioWorld::new();
+        world.register_contract(INSURANCE_PATH_EXPR, wine_insurance::ContractBuilder);
+
+        world.set_state_step(
+            SetStateStep::new()
+                .block_epoch(1)
+                .put_account(OWNER_ADDRESS_EXPR, Account::new().nonce(1))
+                .put_account(UNDERWRITER_ADDRESS_EXPR, Account::new().nonce(1).balance("6000"))
+                .put_account(OTHER_UNDERWRITER_ADDRESS_EXPR, Account::new().nonce(1).balance("4000"))
+                .put_account(HOLDER_ADDRESS_EXPR, Account::new().nonce(1).balance("1000"))
+                .put_account(CERTIFIER_ADDRESS_EXPR, Account::new().nonce(1))
+                .new_address(OWNER_ADDRESS_EXPR, 1, INSURANCE_ADDRESS_EXPR),
+        );
+
+        let mut insurance = InsuranceContract::new(INSURANCE_ADDRESS_EXPR);
+        let insurance_code = world.code_expression(INSURANCE_PATH_EXPR);
+        world.sc_deploy(
+            ScDeployStep::new()
+                .from(OWNER_ADDRESS_EXPR)
+                .code(insurance_code)
+                .call(insurance.init(
+                    EgldOrEsdtTokenIdentifier::egld(),
+                    PREMIUM_BPS_PER_EPOCH,
+                    MAX_DURATION_EPOCHS,
+                )),
+        );
+        for (underwriter, amount) in [(UNDERWRITER_ADDRESS_EXPR, 6_000u64), (OTHER_UNDERWRITER_ADDRESS_EXPR, 4_000)] {
+            world.sc_call(
+                ScCallStep::new()
+                    .from(underwriter)
+                    .egld_value(amount)
+                    .call(insurance.stake_capital())
+                    .expect_value(BigUint::<StaticApi>::from(amount)),
+            );
+        }
+
+        // Claims are certified by addresses an admin trusts
+        let certifier = AddressValue::from(CERTIFIER_ADDRESS_EXPR).to_address();
+        world.sc_call(
+            ScCallStep::new()
+                .from(CERTIFIER_ADDRESS_EXPR)
+                .call(insurance.add_claim_certifier(managed_address!(&certifier)))
+                .expect(user_error(ERR_CALLER_DOES_NOT_HAVE_THE_REQUIRED_ROLE)),
+        );
+        world.sc_call(
+            ScCallStep::new()
+                .from(OWNER_ADDRESS_EXPR)
+                .call(insurance.add_claim_certifier(managed_address!(&certifier))),
+        );
+
+        InsuranceTestState { world, insurance }
+    }
+
+    fn set_block_epoch(&mut self, epoch: u64) {
+        self.world.set_state_step(SetStateStep::new().block_epoch(epoch));
+    }
+
+    // Covers two bottles of wine NFT 1 from this epoch on
+    fn buy_policy(&mut self, coverage: u64, duration_epochs: u64, payment: u64) {
+        self.world.sc_call(
+            ScCallStep::new()
+                .from(HOLDER_ADDRESS_EXPR)
+                .egld_value(payment)
+                .call(self.insurance.buy_policy(1u64, 2u32, coverage, duration_epochs)),
+        );
+    }
+
+    fn file_claim(&mut self) {
+        self.world.sc_call(
+            ScCallStep::new()
+                .from(HOLDER_ADDRESS_EXPR)
+                .call(self.insurance.file_claim(1u32, ClaimReason::Breakage, ManagedByteArray::from(&[7u8; 32]))),
+        );
+    }
+
+    fn policy_status(&mut self) -> PolicyStatus {
+        let policy: Policy<StaticApi> = self.world.quick_query(self.insurance.get_policy(1u32));
+        policy.status
+    }
+}
+
+#[test]
+fn confirmed_claim_is_paid_from_the_pool_and_shared_by_underwriters() {
+    let mut state = InsuranceTestState::new();
+
+    state.world.sc_call(
+        ScCallStep::new()
+            .from(HOLDER_ADDRESS_EXPR)
+            .egld_value(1_000u64)
+            .call(state.insurance.buy_policy(1u64, 2u32, 10_001u64, 2u64))
+            .expect(user_error(ERR_NOT_ENOUGH_CAPITAL_IN_THE_POOL)),
+    );
+    // The premium is 1% of the coverage per epoch, the excess comes back
+    state.buy_policy(5_000, 2, 150);
+    state.world.check_state_step(
+        CheckStateStep::new().put_account(HOLDER_ADDRESS_EXPR, CheckAccount::new().balance("900")),
+    );
+
+    state.world.sc_call(
+        ScCallStep::new()
+            .from(CERTIFIER_ADDRESS_EXPR)
+            .call(state.insurance.file_claim(1u32, ClaimReason::Breakage, ManagedByteArray::from(&[7u8; 32])))
+            .expect(user_error(ERR_ONLY_HOLDER_CAN_FILE_A_CLAIM)),
+    );
+    state.file_claim();
+    assert_eq!(state.policy_status(), PolicyStatus::ClaimPending);
+
+    state.world.sc_call(
+        ScCallStep::new()
+            .from(HOLDER_ADDRESS_EXPR)
+            .call(state.insurance.settle_claim(1u32, true))
+            .expect(user_error(ERR_NOT_A_CLAIM_CERTIFIER)),
+    );
+    state.world.sc_call(
+        ScCallStep::new()
+            .from(CERTIFIER_ADDRESS_EXPR)
+            .call(state.insurance.settle_claim(1u32, true)),
+    );
+
+    assert_eq!(state.policy_status(), PolicyStatus::PaidOut);
+    let pool_capital: BigUint<StaticApi> = state.world.quick_query(state.insurance.get_pool_capital());
+    assert_eq!(pool_capital, 5_100u64);
+
+    // The underwriter owns 60% of the shares, so bears 60% of the net loss
+    state.world.sc_call(
+        ScCallStep::new()
+            .from(UNDERWRITER_ADDRESS_EXPR)
+            .call(state.insurance.withdraw_capital(6_000u64))
+            .expect_value(BigUint::<StaticApi>::from(3_060u64)),
+    );
+    state.world.check_state_step(
+        CheckStateStep::new()
+            .put_account(HOLDER_ADDRESS_EXPR, CheckAccount::new().balance("5900"))
+            .put_account(UNDERWRITER_ADDRESS_EXPR, CheckAccount::new().balance("3060"))
+            .put_account(INSURANCE_ADDRESS_EXPR, CheckAccount::new().balance("2040")),
+    );
+}
+
+#[test]
+fn capital_backing_coverage_is_released_when_the_policy_expires() {
+    let mut state = InsuranceTestState::new();
+    state.buy_policy(8_000, 2, 160);
+
+    state.world.sc_call(
+        ScCallStep::new()
+            .from(UNDERWRITER_ADDRESS_EXPR)
+            .call(state.insurance.withdraw_capital(6_000u64))
+            .expect(user_error(ERR_CAPITAL_IS_BACKING_ACTIVE_COVERAGE)),
+    );
+    state.set_block_epoch(2);
+    state.world.sc_call(
+        ScCallStep::new()
+            .from(UNDERWRITER_ADDRESS_EXPR)
+            .call(state.insurance.expire_policy(1u32))
+            .expect(user_error(ERR_POLICY_HAS_NOT_EXPIRED)),
+    );
+
+    state.set_block_epoch(3);
+    state.world.sc_call(
+        ScCallStep::new()
+            .from(HOLDER_ADDRESS_EXPR)
+            .call(state.insurance.file_claim(1u32, ClaimReason::Spoilage, ManagedByteArray::from(&[7u8; 32])))
+            .expect(user_error(ERR_POLICY_HAS_EXPIRED)),
+    );
+    state.world.sc_call(
+        ScCallStep::new()
+            .from(UNDERWRITER_ADDRESS_EXPR)
+            .call(state.insurance.expire_policy(1u32)),
+    );
+    assert_eq!(state.policy_status(), PolicyStatus::Expired);
+
+    // The premium stays in the pool as the underwriters' gain
+    state.world.sc_call(
+        ScCallStep::new()
+            .from(UNDERWRITER_ADDRESS_EXPR)
+            .call(state.insurance.withdraw_capital(6_000u64))
+            .expect_value(BigUint::<StaticApi>::from(6_096u64)),
+    );
+    state.world.check_state_step(
+        CheckStateStep::new().put_account(UNDERWRITER_ADDRESS_EXPR, CheckAccount::new().balance("6096")),
+    );
+}
+
+#[test]
+fn rejected_claim_puts_the_policy_back_in_force() {
+    let mut state = InsuranceTestState::new();
+    state.buy_policy(5_000, 2, 100);
+    state.file_claim();
+
+    state.world.sc_call(
+        ScCallStep::new()
+            .from(CERTIFIER_ADDRESS_EXPR)
+            .call(state.insurance.settle_claim(1u32, false)),
+    );
+    assert_eq!(state.policy_status(), PolicyStatus::Active);
+    state.world.sc_call(
+        ScCallStep::new()
+            .from(CERTIFIER_ADDRESS_EXPR)
+            .call(state.insurance.settle_claim(1u32, true))
+            .expect(user_error(ERR_NO_PENDING_CLAIM)),
+    );
+    state.world.check_state_step(
+        CheckStateStep::new()
+            .put_account(HOLDER_ADDRESS_EXPR, CheckAccount::new().balance("900"))
+            .put_account(INSURANCE_ADDRESS_EXPR, CheckAccount::new().balance("10100")),
+    );
+}
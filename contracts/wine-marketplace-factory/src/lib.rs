@@ -0,0 +1,36 @@
+#![no_std]
+
+use multiversx_sc::imports::*;
+use wine_common::errors::*;
+
+pub mod storefronts;
+
+pub use storefronts::Storefront;
+
+// Deploys and keeps track of per-winery marketplace storefronts, each forwarding a share of
+// its fees to the main marketplace treasury.
+#[multiversx_sc::contract]
+pub trait WineMarketplaceFactory: storefronts::StorefrontsModule + wine_roles::RolesModule {
+    #[init]
+    fn init(
+        &self,
+        marketplace_template: ManagedAddress,
+        wine_registry: ManagedAddress,
+        main_treasury: ManagedAddress,
+        treasury_fee_share_percent: u64,
+    ) {
+        require!(
+            self.blockchain().is_smart_contract(&marketplace_template),
+            ERR_TEMPLATE_MUST_BE_A_SMART_CONTRACT
+        );
+        require!(!main_treasury.is_zero(), ERR_INVALID_MAIN_TREASURY);
+        require!(treasury_fee_share_percent <= 10000, ERR_FEE_SHARE_CANNOT_EXCEED_100_PERCENT);
+        self.marketplace_template().set(&marketplace_template);
+        self.wine_registry().set(&wine_registry);
+        self.main_treasury().set(&main_treasury);
+        self.treasury_fee_share_percent().set(treasury_fee_share_percent);
+    }
+
+    #[upgrade]
+    fn upgrade(&self) {}
+}
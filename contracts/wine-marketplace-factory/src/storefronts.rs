@@ -0,0 +1,278 @@
+use multiversx_sc::derive_imports::*;
+use multiversx_sc::imports::*;
+use wine_common::errors::*;
+use wine_roles::Role;
+
+// Gas for deploying a storefront from the template; the marketplace is a large contract
+pub const STOREFRONT_DEPLOY_GAS: u64 = 200_000_000;
+// Gas for each configuration call on a freshly deployed storefront
+pub const STOREFRONT_SETUP_GAS: u64 = 10_000_000;
+// Gas for each call registering a storefront with the wine registry
+pub const REGISTRY_SETUP_GAS: u64 = 10_000_000;
+// address 32 + producer 32 + timestamp 8 = 72 bytes
+pub const MAX_STOREFRONTS_PAGE_SIZE: u32 = 400;
+
+#[derive(TopEncode, TopDecode, NestedEncode, NestedDecode, TypeAbi, PartialEq, Debug)]
+pub struct Storefront<M: ManagedTypeApi> {
+    pub address: ManagedAddress<M>,
+    pub producer: ManagedAddress<M>,
+    pub deployed_timestamp: u64,
+}
+
+// Branded storefronts for wineries: marketplace instances deployed from a template contract,
+// one per producer. The factory stays the owner of each storefront so it can upgrade them and
+// keep the main treasury's share of their fees in place; the producer becomes its admin and
+// receives the rest of its fees. Storefronts sell the registry's wines, so the factory holds
+// the registry's Admin role and sits on its shard, as storefronts lock wines there with
+// synchronous calls.
+#[multiversx_sc::module]
+pub trait StorefrontsModule: wine_roles::RolesModule {
+    // Deployed marketplace whose code new storefronts copy
+    #[endpoint(setMarketplaceTemplate)]
+    fn set_marketplace_template(&self, template: ManagedAddress) {
+        self.require_role(Role::Admin);
+        require!(
+            self.blockchain().is_smart_contract(&template),
+            ERR_TEMPLATE_MUST_BE_A_SMART_CONTRACT
+        );
+        self.marketplace_template().set(&template);
+
+        self.marketplace_template_updated_event(&template);
+    }
+
+    // Applies to storefronts deployed from now on; existing ones are moved over with
+    // `updateStorefrontFeeShare`
+    #[endpoint(setTreasuryFeeShare)]
+    fn set_treasury_fee_share(&self, main_treasury: ManagedAddress, fee_share_percent: u64) {
+        self.require_role(Role::FeeManager);
+        require!(!main_treasury.is_zero(), ERR_INVALID_MAIN_TREASURY);
+        require!(fee_share_percent <= 10000, ERR_FEE_SHARE_CANNOT_EXCEED_100_PERCENT);
+        self.main_treasury().set(&main_treasury);
+        self.treasury_fee_share_percent().set(fee_share_percent);
+
+        self.treasury_fee_share_updated_event(&main_treasury, fee_share_percent);
+    }
+
+    // Deploy a storefront for `producer`, who becomes its admin and fee recipient
+    #[endpoint(deployStorefront)]
+    fn deploy_storefront(
+        &self,
+        producer: ManagedAddress,
+        marketplace_fee_percent: u64,
+    ) -> ManagedAddress {
+        self.require_role(Role::Admin);
+        self.require_not_paused();
+        require!(!producer.is_zero(), ERR_INVALID_PRODUCER);
+        require!(
+            self.producer_storefront(&producer).is_empty(),
+            ERR_PRODUCER_ALREADY_HAS_A_STOREFRONT
+        );
+
+        let storefront = self
+            .tx()
+            .raw_deploy()
+            .from_source(self.marketplace_template().get())
+            .code_metadata(CodeMetadata::UPGRADEABLE | CodeMetadata::READABLE)
+            .argument(&marketplace_fee_percent)
+            .argument(&self.wine_registry().get())
+            .gas(STOREFRONT_DEPLOY_GAS)
+            .returns(ReturnsNewManagedAddress)
+            .sync_call();
+
+        self.tx()
+            .to(&storefront)
+            .gas(STOREFRONT_SETUP_GAS)
+            .raw_call("setFeeRecipient")
+            .argument(&producer)
+            .transfer_execute();
+        self.push_fee_share(&storefront);
+        self.tx()
+            .to(&storefront)
+            .gas(STOREFRONT_SETUP_GAS)
+            .raw_call("grantRole")
+            .argument(&Role::Admin)
+            .argument(&producer)
+            .transfer_execute();
+        self.connect_to_registry(&storefront);
+
+        self.storefronts().push(&Storefront {
+            address: storefront.clone(),
+            producer: producer.clone(),
+            deployed_timestamp: self.blockchain().get_block_timestamp(),
+        });
+        self.producer_storefront(&producer).set(&storefront);
+        self.storefront_producer(&storefront).set(&producer);
+
+        self.storefront_deployed_event(&storefront, &producer, marketplace_fee_percent);
+
+        storefront
+    }
+
+    // Bring a storefront's code up to date with the template
+    #[endpoint(upgradeStorefront)]
+    fn upgrade_storefront(&self, storefront: ManagedAddress) {
+        self.require_role(Role::Admin);
+        self.require_storefront(&storefront);
+
+        self.storefront_upgraded_event(&storefront);
+
+        self.tx()
+            .to(&storefront)
+            .raw_upgrade()
+            .from_source(self.marketplace_template().get())
+            .code_metadata(CodeMetadata::UPGRADEABLE | CodeMetadata::READABLE)
+            .upgrade_async_call_and_exit();
+    }
+
+    // Apply the current treasury fee share to an existing storefront
+    #[endpoint(updateStorefrontFeeShare)]
+    fn update_storefront_fee_share(&self, storefront: ManagedAddress) {
+        self.require_role(Role::FeeManager);
+        self.require_storefront(&storefront);
+        self.push_fee_share(&storefront);
+    }
+
+    // Internal helpers
+    fn require_storefront(&self, storefront: &ManagedAddress) {
+        require!(!self.storefront_producer(storefront).is_empty(), ERR_NOT_A_STOREFRONT);
+    }
+
+    // The storefront receives the registry's read models, may lock wines and record sales
+    // there, and only accepts bottles of the registry's collection. The registry calls are
+    // synchronous, so a factory the registry does not accept fails the deployment instead of
+    // leaving a storefront that cannot list.
+    fn connect_to_registry(&self, storefront: &ManagedAddress) {
+        let registry = self.wine_registry().get();
+        let results = self
+            .tx()
+            .to(&registry)
+            .gas(REGISTRY_SETUP_GAS)
+            .raw_call("getWineCollection")
+            .returns(ReturnsRawResult)
+            .sync_call();
+        require!(!results.is_empty(), ERR_WINE_COLLECTION_NOT_ISSUED);
+        let collection = results.get(0).clone_value();
+
+        self.tx()
+            .to(&registry)
+            .gas(REGISTRY_SETUP_GAS)
+            .raw_call("addReadModelSubscriber")
+            .argument(storefront)
+            .sync_call();
+        self.tx()
+            .to(&registry)
+            .gas(REGISTRY_SETUP_GAS)
+            .raw_call("setIntegrationPermissions")
+            .argument(storefront)
+            .argument(&false)
+            .argument(&true)
+            .argument(&false)
+            .sync_call();
+        self.tx()
+            .to(storefront)
+            .gas(STOREFRONT_SETUP_GAS)
+            .raw_call("setWineCollection")
+            .argument(&collection)
+            .transfer_execute();
+    }
+
+    fn push_fee_share(&self, storefront: &ManagedAddress) {
+        self.tx()
+            .to(storefront)
+            .gas(STOREFRONT_SETUP_GAS)
+            .raw_call("setFeeShare")
+            .argument(&self.main_treasury().get())
+            .argument(&self.treasury_fee_share_percent().get())
+            .transfer_execute();
+    }
+
+    // View functions
+    // Storefronts in deployment order
+    #[view(getStorefronts)]
+    fn get_storefronts(
+        &self,
+        cursor: u64,
+        page_size: u32,
+    ) -> MultiValue2<u64, MultiValueEncoded<Storefront<Self::Api>>> {
+        let storefronts = self.storefronts();
+        let size = if page_size == 0 || page_size > MAX_STOREFRONTS_PAGE_SIZE {
+            MAX_STOREFRONTS_PAGE_SIZE
+        } else {
+            page_size
+        };
+        let total = storefronts.len();
+        let start = core::cmp::min(usize::try_from(cursor).unwrap_or(usize::MAX), total);
+        let end = core::cmp::min(start.saturating_add(size as usize), total);
+        let next_cursor = if end < total { end as u64 } else { 0 };
+
+        let mut result = MultiValueEncoded::new();
+        for index in start..end {
+            result.push(storefronts.get(index + 1));
+        }
+        (next_cursor, result).into()
+    }
+
+    #[view(getStorefrontCount)]
+    fn get_storefront_count(&self) -> usize {
+        self.storefronts().len()
+    }
+
+    #[view(getProducerStorefront)]
+    fn get_producer_storefront(&self, producer: ManagedAddress) -> OptionalValue<ManagedAddress> {
+        if self.producer_storefront(&producer).is_empty() {
+            OptionalValue::None
+        } else {
+            OptionalValue::Some(self.producer_storefront(&producer).get())
+        }
+    }
+
+    // Storage mappers
+    #[view(getMarketplaceTemplate)]
+    #[storage_mapper("marketplaceTemplate")]
+    fn marketplace_template(&self) -> SingleValueMapper<ManagedAddress>;
+
+    // Passed to every storefront's init
+    #[view(getWineRegistry)]
+    #[storage_mapper("wineRegistry")]
+    fn wine_registry(&self) -> SingleValueMapper<ManagedAddress>;
+
+    #[view(getMainTreasury)]
+    #[storage_mapper("mainTreasury")]
+    fn main_treasury(&self) -> SingleValueMapper<ManagedAddress>;
+
+    // Basis points of each storefront fee forwarded to the main treasury
+    #[view(getTreasuryFeeSharePercent)]
+    #[storage_mapper("treasuryFeeSharePercent")]
+    fn treasury_fee_share_percent(&self) -> SingleValueMapper<u64>;
+
+    #[storage_mapper("storefronts")]
+    fn storefronts(&self) -> VecMapper<Storefront<Self::Api>>;
+
+    #[storage_mapper("producerStorefront")]
+    fn producer_storefront(&self, producer: &ManagedAddress) -> SingleValueMapper<ManagedAddress>;
+
+    #[storage_mapper("storefrontProducer")]
+    fn storefront_producer(&self, storefront: &ManagedAddress) -> SingleValueMapper<ManagedAddress>;
+
+    // Events
+    #[event("marketplaceTemplateUpdated")]
+    fn marketplace_template_updated_event(&self, #[indexed] template: &ManagedAddress);
+
+    #[event("treasuryFeeShareUpdated")]
+    fn treasury_fee_share_updated_event(
+        &self,
+        #[indexed] main_treasury: &ManagedAddress,
+        fee_share_percent: u64,
+    );
+
+    #[event("storefrontDeployed")]
+    fn storefront_deployed_event(
+        &self,
+        #[indexed] storefront: &ManagedAddress,
+        #[indexed] producer: &ManagedAddress,
+        marketplace_fee_percent: u64,
+    );
+
+    #[event("storefrontUpgraded")]
+    fn storefront_upgraded_event(&self, #[indexed] storefront: &ManagedAddress);
+}
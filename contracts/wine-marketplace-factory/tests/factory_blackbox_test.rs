@@ -0,0 +1,292 @@
+use multiversx_sc::codec::top_encode_to_vec_u8_or_panic;
+use multiversx_sc_scenario::api::StaticApi;
+use multiversx_sc_scenario::imports::*;
+use wine_common::errors::*;
+use wine_marketplace::listing::ProxyTrait as _;
+use wine_marketplace::treasury::ProxyTrait as _;
+use wine_marketplace::ProxyTrait as _;
+use wine_marketplace::{FeeShare, WineNftAttributes};
+use wine_marketplace_factory::storefronts::ProxyTrait as _;
+use wine_marketplace_factory::ProxyTrait as _;
+use wine_registry::minting::MintingModule;
+use wine_registry::provenance::ProxyTrait as _;
+use wine_registry::registration::ProxyTrait as _;
+use wine_registry::ProxyTrait as _;
+use wine_roles::ProxyTrait as _;
+use wine_roles::Role;
+
+const FACTORY_PATH_EXPR: &str = "mxsc:output/wine-marketplace-factory.mxsc.json";
+const MARKETPLACE_PATH_EXPR: &str = "mxsc:../wine-marketplace/output/wine-marketplace.mxsc.json";
+const REGISTRY_PATH_EXPR: &str = "mxsc:../wine-registry/output/wine-registry.mxsc.json";
+const FACTORY_ADDRESS_EXPR: &str = "sc:wine-marketplace-factory";
+const TEMPLATE_ADDRESS_EXPR: &str = "sc:marketplace-template";
+const STOREFRONT_ADDRESS_EXPR: &str = "sc:storefront";
+const OWNER_ADDRESS_EXPR: &str = "address:owner";
+const PRODUCER_ADDRESS_EXPR: &str = "address:producer";
+const TREASURY_ADDRESS_EXPR: &str = "address:treasury";
+const REGISTRY_ADDRESS_EXPR: &str = "sc:wine-registry";
+
+const WINE_NFT_TOKEN_ID: &[u8] = b"WINE-abcdef";
+const WINE_NFT_TOKEN_ID_EXPR: &str = "str:WINE-abcdef";
+
+const TREASURY_FEE_SHARE_PERCENT: u64 = 2_000;
+const STOREFRONT_FEE_PERCENT: u64 = 300;
+const REGISTRATION_FEE: u64 = 1_000;
+const PRICE: u64 = 1_000;
+
+type FactoryContract = ContractInfo<wine_marketplace_factory::Proxy<StaticApi>>;
+type MarketplaceContract = ContractInfo<wine_marketplace::Proxy<StaticApi>>;
+type RegistryContract = ContractInfo<wine_registry::Proxy<StaticApi>>;
+
+fn user_error(message: &str) -> TxExpect {
+    TxExpect::user_error(format!("str:{message}"))
+}
+
+struct FactoryTestState {
+    world: ScenarioWorld,
+    factory: FactoryContract,
+    storefront: MarketplaceContract,
+    registry: RegistryContract,
+}
+
+impl FactoryTestState {
+    // A registry with an issued wine collection and a factory copying a deployed marketplace,
+    // forwarding 20% of every storefront's fees to the treasury. The factory administers the
+    // registry. The producer's storefront, once deployed, is `storefront`.
+    fn new() -> Self {
+        let mut world = ScenarioWorld::new();
+        world.register_contract(FACTORY_PATH_EXPR, wine_marketplace_factory::ContractBuilder);
+        world.register_contract(MARKETPLACE_PATH_EXPR, wine_marketplace::ContractBuilder);
+        world.register_contract(REGISTRY_PATH_EXPR, wine_registry::ContractBuilder);
+
+        let attributes = WineNftAttributes::<StaticApi> {
+            wine_id: 1,
+            vineyard: ManagedBuffer::from("Domaine de Test"),
+            variety: ManagedBuffer::from("Pinot Noir"),
+            vintage_year: 2020,
+            region: ManagedBuffer::from("Burgundy"),
+            quality_grade: 8,
+            ipfs_hash: ManagedBuffer::new(),
+        };
+        world.set_state_step(
+            SetStateStep::new()
+                .put_account(OWNER_ADDRESS_EXPR, Account::new().nonce(1))
+                .put_account(
+                    PRODUCER_ADDRESS_EXPR,
+                    Account::new().nonce(1).balance(REGISTRATION_FEE).esdt_nft_balance(
+                        WINE_NFT_TOKEN_ID_EXPR,
+                        1,
+                        "1",
+                        Some(top_encode_to_vec_u8_or_panic(&attributes)),
+                    ),
+                )
+                .new_address(OWNER_ADDRESS_EXPR, 1, REGISTRY_ADDRESS_EXPR)
+                .new_address(OWNER_ADDRESS_EXPR, 2, TEMPLATE_ADDRESS_EXPR)
+                .new_address(OWNER_ADDRESS_EXPR, 3, FACTORY_ADDRESS_EXPR)
+                .new_address(FACTORY_ADDRESS_EXPR, 0, STOREFRONT_ADDRESS_EXPR),
+        );
+
+        let mut registry = RegistryContract::new(REGISTRY_ADDRESS_EXPR);
+        let registry_code = world.code_expression(REGISTRY_PATH_EXPR);
+        world.sc_deploy(
+            ScDeployStep::new()
+                .from(OWNER_ADDRESS_EXPR)
+                .code(registry_code)
+                .call(registry.init(REGISTRATION_FEE)),
+        );
+
+        let mut template = MarketplaceContract::new(TEMPLATE_ADDRESS_EXPR);
+        let marketplace_code = world.code_expression(MARKETPLACE_PATH_EXPR);
+        let registry_address = AddressValue::from(REGISTRY_ADDRESS_EXPR).to_address();
+        world.sc_deploy(
+            ScDeployStep::new()
+                .from(OWNER_ADDRESS_EXPR)
+                .code(marketplace_code)
+                .call(template.init(250u64, managed_address!(&registry_address))),
+        );
+        let mut factory = FactoryContract::new(FACTORY_ADDRESS_EXPR);
+        let factory_code = world.code_expression(FACTORY_PATH_EXPR);
+        let template_address = AddressValue::from(TEMPLATE_ADDRESS_EXPR).to_address();
+        let treasury = AddressValue::from(TREASURY_ADDRESS_EXPR).to_address();
+        world.sc_deploy(
+            ScDeployStep::new()
+                .from(OWNER_ADDRESS_EXPR)
+                .code(factory_code)
+                .call(factory.init(
+                    managed_address!(&template_address),
+                    managed_address!(&registry_address),
+                    managed_address!(&treasury),
+                    TREASURY_FEE_SHARE_PERCENT,
+                )),
+        );
+
+        // Issuing goes through the ESDT system contract, so the collection is set directly
+        let registry_whitebox = WhiteboxContract::new(REGISTRY_ADDRESS_EXPR, wine_registry::contract_obj);
+        world.whitebox_call(&registry_whitebox, ScCallStep::new().from(OWNER_ADDRESS_EXPR), |sc| {
+            sc.wine_collection().set_token_id(managed_token_id!(WINE_NFT_TOKEN_ID));
+        });
+
+        let factory_address = AddressValue::from(FACTORY_ADDRESS_EXPR).to_address();
+        world.sc_call(
+            ScCallStep::new()
+                .from(OWNER_ADDRESS_EXPR)
+                .call(registry.grant_role(Role::Admin, managed_address!(&factory_address))),
+        );
+
+        let storefront = MarketplaceContract::new(STOREFRONT_ADDRESS_EXPR);
+        FactoryTestState { world, factory, storefront, registry }
+    }
+
+    fn deploy_storefront(&mut self) -> TypedScCall<ManagedAddress<StaticApi>> {
+        let producer = AddressValue::from(PRODUCER_ADDRESS_EXPR).to_address();
+        ScCallStep::new()
+            .from(OWNER_ADDRESS_EXPR)
+            .call(self.factory.deploy_storefront(managed_address!(&producer), STOREFRONT_FEE_PERCENT))
+    }
+
+    fn storefront_fee_share(&mut self) -> FeeShare<StaticApi> {
+        let fee_share: OptionalValue<FeeShare<StaticApi>> = self.world.quick_query(self.storefront.get_fee_share());
+        fee_share.into_option().unwrap()
+    }
+}
+
+#[test]
+fn storefront_is_run_by_the_producer_and_owned_by_the_factory() {
+    let mut state = FactoryTestState::new();
+    let producer = AddressValue::from(PRODUCER_ADDRESS_EXPR).to_address();
+    let storefront_address = AddressValue::from(STOREFRONT_ADDRESS_EXPR).to_address();
+    let treasury = AddressValue::from(TREASURY_ADDRESS_EXPR).to_address();
+
+    state.world.sc_call(
+        ScCallStep::new()
+            .from(PRODUCER_ADDRESS_EXPR)
+            .call(state.factory.deploy_storefront(managed_address!(&producer), STOREFRONT_FEE_PERCENT))
+            .expect(user_error(ERR_CALLER_DOES_NOT_HAVE_THE_REQUIRED_ROLE)),
+    );
+    let step = state.deploy_storefront();
+    state.world.sc_call(step.expect_value(managed_address!(&storefront_address)));
+    let step = state.deploy_storefront();
+    state.world.sc_call(step.expect(user_error(ERR_PRODUCER_ALREADY_HAS_A_STOREFRONT)));
+
+    let producer_storefront: OptionalValue<ManagedAddress<StaticApi>> =
+        state.world.quick_query(state.factory.get_producer_storefront(managed_address!(&producer)));
+    assert_eq!(producer_storefront.into_option(), Some(managed_address!(&storefront_address)));
+
+    // Fees go to the producer, less the treasury's share
+    let fee_recipient: ManagedAddress<StaticApi> = state.world.quick_query(state.storefront.get_fee_recipient());
+    assert_eq!(fee_recipient, managed_address!(&producer));
+    let fee_percent: u64 = state.world.quick_query(state.storefront.get_marketplace_fee_percent());
+    assert_eq!(fee_percent, STOREFRONT_FEE_PERCENT);
+    let fee_share = state.storefront_fee_share();
+    assert_eq!(fee_share.recipient, managed_address!(&treasury));
+    assert_eq!(fee_share.share_percent, TREASURY_FEE_SHARE_PERCENT);
+
+    // The producer administers the storefront but cannot redirect its fees
+    let is_admin: bool = state.world.quick_query(state.storefront.has_role(Role::Admin, managed_address!(&producer)));
+    assert!(is_admin);
+    state.world.sc_call(
+        ScCallStep::new()
+            .from(PRODUCER_ADDRESS_EXPR)
+            .call(state.storefront.set_fee_share(managed_address!(&producer), 0u64))
+            .expect(user_error(ERR_ONLY_OWNER_CAN_SET_THE_FEE_SHARE)),
+    );
+}
+
+#[test]
+fn fee_share_changes_are_pushed_to_existing_storefronts() {
+    let mut state = FactoryTestState::new();
+    let step = state.deploy_storefront();
+    state.world.sc_call(step);
+    let treasury = AddressValue::from(TREASURY_ADDRESS_EXPR).to_address();
+    let producer = AddressValue::from(PRODUCER_ADDRESS_EXPR).to_address();
+    let storefront_address = AddressValue::from(STOREFRONT_ADDRESS_EXPR).to_address();
+
+    state.world.sc_call(
+        ScCallStep::new()
+            .from(PRODUCER_ADDRESS_EXPR)
+            .call(state.factory.set_treasury_fee_share(managed_address!(&treasury), 1_000u64))
+            .expect(user_error(ERR_CALLER_DOES_NOT_HAVE_THE_REQUIRED_ROLE)),
+    );
+    state.world.sc_call(
+        ScCallStep::new()
+            .from(OWNER_ADDRESS_EXPR)
+            .call(state.factory.set_treasury_fee_share(managed_address!(&treasury), 1_000u64)),
+    );
+    assert_eq!(state.storefront_fee_share().share_percent, TREASURY_FEE_SHARE_PERCENT);
+
+    state.world.sc_call(
+        ScCallStep::new()
+            .from(OWNER_ADDRESS_EXPR)
+            .call(state.factory.update_storefront_fee_share(managed_address!(&producer)))
+            .expect(user_error(ERR_NOT_A_STOREFRONT)),
+    );
+    state.world.sc_call(
+        ScCallStep::new()
+            .from(OWNER_ADDRESS_EXPR)
+            .call(state.factory.update_storefront_fee_share(managed_address!(&storefront_address))),
+    );
+    assert_eq!(state.storefront_fee_share().share_percent, 1_000);
+}
+
+#[test]
+fn storefront_sells_the_registry_wines() {
+    let mut state = FactoryTestState::new();
+    let factory_address = AddressValue::from(FACTORY_ADDRESS_EXPR).to_address();
+
+    // A factory the registry does not accept cannot deploy storefronts that could not list
+    state.world.sc_call(
+        ScCallStep::new()
+            .from(OWNER_ADDRESS_EXPR)
+            .call(state.registry.revoke_role(Role::Admin, managed_address!(&factory_address))),
+    );
+    let step = state.deploy_storefront();
+    state.world.sc_call(step.expect(user_error(ERR_CALLER_DOES_NOT_HAVE_THE_REQUIRED_ROLE)));
+    state.world.sc_call(
+        ScCallStep::new()
+            .from(OWNER_ADDRESS_EXPR)
+            .call(state.registry.grant_role(Role::Admin, managed_address!(&factory_address))),
+    );
+    let step = state.deploy_storefront();
+    state.world.sc_call(step);
+
+    // Registered after the storefront subscribed, so the wine is pushed to it
+    state.world.sc_call(
+        ScCallStep::new()
+            .from(PRODUCER_ADDRESS_EXPR)
+            .egld_value(REGISTRATION_FEE)
+            .call(state.registry.register_wine(
+                "Domaine de Test",
+                "Pinot Noir",
+                2020u32,
+                8u8,
+                1300u16,
+                "Burgundy",
+                "AOC",
+                "producer-signature",
+                "QmYwAPJzv5CZsnA625s3Xf2nemtYgPpHdWEz79ojWnPbdG",
+                100u32,
+                50_000u64,
+                false,
+                1_600_000_000u64,
+                "18 months in French oak",
+                "Cherry, forest floor",
+            ))
+            .expect_value(1u32),
+    );
+
+    state.world.sc_call(
+        ScCallStep::new()
+            .from(PRODUCER_ADDRESS_EXPR)
+            .esdt_transfer(WINE_NFT_TOKEN_ID_EXPR, 1, "1")
+            .call(state.storefront.create_listing(
+                1u32,
+                PRICE,
+                EgldOrEsdtTokenIdentifier::egld(),
+                3600u64,
+                OptionalValue::<bool>::None,
+            ))
+            .expect_value(1u32),
+    );
+    let locked: bool = state.world.quick_query(state.registry.is_wine_locked(1u32));
+    assert!(locked);
+}
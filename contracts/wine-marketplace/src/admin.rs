@@ -0,0 +1,91 @@
+use multiversx_sc::imports::*;
+use wine_common::errors::*;
+use wine_roles::Role;
+
+#[multiversx_sc::module]
+pub trait AdminModule: wine_roles::RolesModule {
+    // Add supported payment token
+    #[endpoint(addSupportedToken)]
+    fn add_supported_token(&self, token_id: EgldOrEsdtTokenIdentifier) {
+        self.require_role(Role::Admin);
+        self.supported_payment_tokens(&token_id).set(true);
+        self.token_added_event(&token_id);
+    }
+
+    // Flag an ESDT whose transfers are limited to holders of the transfer role. Such tokens
+    // are only accepted while the marketplace holds the role, so escrowed funds can be paid out.
+    #[endpoint(setTransferRoleRestricted)]
+    fn set_transfer_role_restricted(&self, token_id: TokenIdentifier, restricted: bool) {
+        self.require_role(Role::Admin);
+        require!(token_id.is_valid_esdt_identifier(), ERR_INVALID_TOKEN_IDENTIFIER);
+        self.transfer_role_restricted(&token_id).set(restricted);
+    }
+
+    // Internal helpers
+    fn require_supported_payment_token(&self, token_id: &EgldOrEsdtTokenIdentifier) {
+        require!(
+            self.supported_payment_tokens(token_id).get(),
+            ERR_PAYMENT_TOKEN_NOT_SUPPORTED
+        );
+        self.require_transferable_payment_token(token_id);
+    }
+
+    fn require_transferable_payment_token(&self, token_id: &EgldOrEsdtTokenIdentifier) {
+        if let Some(esdt_token_id) = token_id.as_esdt_option() {
+            if self.transfer_role_restricted(&esdt_token_id).get() {
+                let roles = self.blockchain().get_esdt_local_roles(&esdt_token_id);
+                require!(
+                    roles.has_role(&EsdtLocalRole::Transfer),
+                    ERR_MARKETPLACE_LACKS_TRANSFER_ROLE_FOR_PAYMENT_TOKEN
+                );
+            }
+        }
+    }
+
+    // Validate an incoming payment in `expected_token`: payment tokens are fungible, so
+    // any nonce other than 0 means an NFT/SFT of the same collection was sent instead.
+    // Returns the paid amount.
+    fn require_fungible_payment(&self, expected_token: &EgldOrEsdtTokenIdentifier) -> BigUint {
+        let payment = self.call_value().egld_or_single_esdt();
+        require!(
+            payment.token_identifier == *expected_token,
+            ERR_INVALID_PAYMENT_TOKEN
+        );
+        self.require_fungible(&payment);
+
+        payment.amount
+    }
+
+    fn require_fungible(&self, payment: &EgldOrEsdtTokenPayment) {
+        require!(payment.token_nonce == 0, ERR_PAYMENT_MUST_BE_A_FUNGIBLE_TOKEN);
+        self.require_transferable_payment_token(&payment.token_identifier);
+    }
+
+    // View functions
+    #[view(isSupportedPaymentToken)]
+    fn is_supported_payment_token(&self, token_id: &EgldOrEsdtTokenIdentifier) -> bool {
+        self.supported_payment_tokens(token_id).get()
+    }
+
+    #[view(isTransferRoleRestricted)]
+    fn is_transfer_role_restricted(&self, token_id: &TokenIdentifier) -> bool {
+        self.transfer_role_restricted(token_id).get()
+    }
+
+    // Storage mappers
+    #[storage_mapper("supportedPaymentTokens")]
+    fn supported_payment_tokens(&self, token_id: &EgldOrEsdtTokenIdentifier) -> SingleValueMapper<bool>;
+
+    #[storage_mapper("transferRoleRestricted")]
+    fn transfer_role_restricted(&self, token_id: &TokenIdentifier) -> SingleValueMapper<bool>;
+
+    #[storage_mapper("wineRegistryAddress")]
+    fn wine_registry_address(&self) -> SingleValueMapper<ManagedAddress>;
+
+    // Events
+    #[event("tokenAdded")]
+    fn token_added_event(
+        &self,
+        token_id: &EgldOrEsdtTokenIdentifier,
+    );
+}
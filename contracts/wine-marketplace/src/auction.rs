@@ -0,0 +1,637 @@
+use multiversx_sc::derive_imports::*;
+use multiversx_sc::imports::*;
+use wine_common::errors::*;
+use wine_roles::Role;
+pub use wine_common::Auction;
+
+use crate::auction_archive::{AuctionKind, AuctionResult};
+use crate::pagination::{Page, MAX_IDS_PAGE_SIZE};
+use crate::treasury::SaleSettlement;
+
+// Anti-snipe settings: bids placed within `extension_window` seconds of the end
+// push the end back by `extension_duration`, at most `max_extensions` times (0 = unlimited)
+#[derive(TopEncode, TopDecode, TypeAbi, PartialEq, Debug, Clone)]
+pub struct AntiSnipeConfig {
+    pub extension_window: u64,
+    pub extension_duration: u64,
+    pub max_extensions: u32,
+}
+
+// Auction state with the timing clients would otherwise work out from timestamps
+#[derive(TopEncode, TopDecode, TypeAbi, PartialEq, Debug)]
+pub struct AuctionView<M: ManagedTypeApi> {
+    pub auction_id: u32,
+    pub wine_nft_id: u32,
+    pub seller: ManagedAddress<M>,
+    pub payment_token: EgldOrEsdtTokenIdentifier<M>,
+    pub current_bid: BigUint<M>,
+    pub highest_bidder: ManagedAddress<M>,
+    pub bid_count: u32,
+    pub min_next_bid: BigUint<M>,
+    pub end_timestamp: u64,
+    pub time_left: u64,
+    pub extension_count: u32,
+    // A bid placed now would push the end back
+    pub anti_snipe_active: bool,
+    pub active: bool,
+}
+
+#[multiversx_sc::module]
+pub trait AuctionModule:
+    wine_roles::RolesModule
+    + crate::admin::AdminModule
+    + crate::collector_tiers::CollectorTiersModule
+    + crate::loyalty::LoyaltyModule
+    + crate::fee_discounts::FeeDiscountsModule
+    + crate::stats_snapshots::StatsSnapshotsModule
+    + crate::treasury::TreasuryModule
+    + crate::blacklist::BlacklistModule
+    + crate::seller_limits::SellerLimitsModule
+    + crate::registry_cache::RegistryCacheModule
+    + crate::market_summary::MarketSummaryModule
+    + crate::auction_archive::AuctionArchiveModule
+{
+    // Toggle pushing refunds to outbid bidders inside placeBid instead of escrowing them
+    #[endpoint(setDirectOutbidRefunds)]
+    fn set_direct_outbid_refunds(&self, enabled: bool) {
+        self.require_role(Role::Admin);
+        self.direct_outbid_refunds().set(enabled);
+    }
+
+    // Configure marketplace-wide anti-snipe parameters
+    #[endpoint(setAntiSnipeConfig)]
+    fn set_anti_snipe_config(&self, extension_window: u64, extension_duration: u64, max_extensions: u32) {
+        self.require_role(Role::Admin);
+        let config = self.validate_anti_snipe_config(extension_window, extension_duration, max_extensions);
+        self.anti_snipe_config().set(&config);
+        self.anti_snipe_config_updated_event(0u32, extension_window, extension_duration, max_extensions);
+    }
+
+    // Override anti-snipe parameters for a single auction
+    #[endpoint(setAuctionAntiSnipeConfig)]
+    fn set_auction_anti_snipe_config(
+        &self,
+        auction_id: u32,
+        extension_window: u64,
+        extension_duration: u64,
+        max_extensions: u32,
+    ) {
+        self.require_role(Role::Admin);
+        require!(!self.auctions(auction_id).is_empty(), ERR_AUCTION_DOES_NOT_EXIST);
+        let config = self.validate_anti_snipe_config(extension_window, extension_duration, max_extensions);
+        self.auction_anti_snipe_config(auction_id).set(&config);
+        self.anti_snipe_config_updated_event(auction_id, extension_window, extension_duration, max_extensions);
+    }
+
+    // Drop a per-auction override, falling back to the marketplace config
+    #[endpoint(clearAuctionAntiSnipeConfig)]
+    fn clear_auction_anti_snipe_config(&self, auction_id: u32) {
+        self.require_role(Role::Admin);
+        self.auction_anti_snipe_config(auction_id).clear();
+    }
+
+    // Create auction
+    #[payable("*")]
+    #[endpoint(createAuction)]
+    fn create_auction(
+        &self,
+        wine_nft_id: u32,
+        starting_price: BigUint,
+        payment_token: EgldOrEsdtTokenIdentifier,
+        duration_seconds: u64,
+        min_bid_increment: BigUint,
+    ) -> u32 {
+        self.require_not_paused();
+        self.require_supported_payment_token(&payment_token);
+        require!(!starting_price.is_zero(), ERR_STARTING_PRICE_MUST_BE_GREATER_THAN_ZERO);
+        require!(duration_seconds >= 3600, ERR_MINIMUM_AUCTION_DURATION_IS_1_HOUR);
+        require!(duration_seconds <= 604800, ERR_MAXIMUM_AUCTION_DURATION_IS_7_DAYS);
+        require!(!min_bid_increment.is_zero(), ERR_MIN_BID_INCREMENT_MUST_BE_GREATER_THAN_ZERO);
+
+        // Receive NFT in escrow; registry gates below apply to the wine it is a bottle of
+        let payment = self.call_value().single_esdt();
+        let nft_token_id = payment.token_identifier.clone();
+        let nft_nonce = payment.token_nonce;
+
+        require!(payment.amount == BigUint::from(1u32), ERR_MUST_SEND_EXACTLY_1_NFT);
+        self.require_escrowed_wine(wine_nft_id, &nft_token_id, nft_nonce);
+
+        let caller = self.blockchain().get_caller();
+        self.require_not_blacklisted(&caller);
+        self.require_wine_listable(wine_nft_id, &caller);
+        self.require_certified_for_auction(wine_nft_id, &nft_token_id);
+        let end_timestamp = self.blockchain().get_block_timestamp() + duration_seconds;
+
+        let auction_id = self.auction_counter().get();
+        let auction = Auction {
+            wine_nft_id,
+            nft_token_id,
+            nft_nonce,
+            seller: caller.clone(),
+            starting_price: starting_price.clone(),
+            current_bid: starting_price,
+            highest_bidder: caller.clone(),
+            payment_token,
+            end_timestamp,
+            active: true,
+            min_bid_increment,
+            bid_count: 0u32,
+        };
+
+        self.auctions(auction_id).set(&auction);
+        self.auction_counter().set(auction_id + 1);
+        self.seller_auctions(&caller).push(&auction_id);
+
+        // Update stats
+        self.open_seller_item(&caller);
+        self.lock_wine_in_registry(wine_nft_id, &caller);
+        self.update_marketplace_stats(|stats| {
+            stats.active_auctions += 1;
+        });
+
+        self.auction_created_event(auction_id, wine_nft_id, &caller, &auction.starting_price);
+
+        auction_id
+    }
+
+    // Place bid on auction, optionally naming a gift recipient for the NFT if the bid wins
+    #[payable("*")]
+    #[endpoint(placeBid)]
+    fn place_bid(&self, auction_id: u32, opt_recipient: OptionalValue<ManagedAddress>) {
+        self.require_not_paused();
+        let auction = self.require_open_auction(auction_id);
+        let payment_amount = self.require_fungible_payment(&auction.payment_token);
+        let bidder = self.blockchain().get_caller();
+        self.record_bid(auction_id, auction, bidder, payment_amount, opt_recipient);
+    }
+
+    // Anyone can finalize an ended auction. Callers other than the seller and the highest
+    // bidder are keepers and earn the keeper bounty out of the marketplace fee.
+    #[endpoint(finalizeAuction)]
+    fn finalize_auction(&self, auction_id: u32) {
+        let mut auction = self.auctions(auction_id).get();
+        require!(auction.active, ERR_AUCTION_IS_NOT_ACTIVE);
+        require!(
+            self.blockchain().get_block_timestamp() >= auction.end_timestamp,
+            ERR_AUCTION_HAS_NOT_ENDED_YET
+        );
+
+        let caller = self.blockchain().get_caller();
+        let keeper = if caller == auction.seller || caller == auction.highest_bidder {
+            None
+        } else {
+            Some(&caller)
+        };
+
+        auction.active = false;
+        self.auctions(auction_id).set(&auction);
+
+        // Update stats
+        self.close_seller_item(&auction.seller);
+        self.unlock_wine_in_registry(auction.wine_nft_id, &auction.seller);
+        self.update_marketplace_stats(|stats| {
+            stats.active_auctions -= 1;
+        });
+
+        if auction.bid_count > 0 && auction.highest_bidder != auction.seller {
+            let recipient = self.get_auction_recipient(auction_id, &auction.highest_bidder);
+
+            // Transfer NFT to winner (or the recipient named in the winning bid)
+            self.send_nft(&recipient, &auction.nft_token_id, auction.nft_nonce);
+            self.record_verified_buyer_in_registry(auction.wine_nft_id, &auction.highest_bidder);
+
+            // Pay seller and collect marketplace fee
+            let settlement = self.pay_out_sale_with_keeper(
+                &auction.seller,
+                &auction.highest_bidder,
+                &auction.nft_token_id,
+                auction.nft_nonce,
+                &auction.payment_token,
+                &auction.current_bid,
+                keeper,
+            );
+            self.accrue_loyalty_rewards(
+                &auction.highest_bidder,
+                &auction.payment_token,
+                &auction.current_bid,
+            );
+
+            self.auction_finalized_event(
+                auction_id,
+                &auction.highest_bidder,
+                &recipient,
+                &settlement
+            );
+            self.archive_auction_result(AuctionResult {
+                kind: AuctionKind::English,
+                auction_id,
+                wine_nft_id: auction.wine_nft_id,
+                seller: auction.seller,
+                winner: auction.highest_bidder,
+                payment_token: auction.payment_token,
+                final_price: auction.current_bid,
+                timestamp: self.blockchain().get_block_timestamp(),
+            });
+        } else {
+            // No bids, return NFT to seller
+            self.send_nft(&auction.seller, &auction.nft_token_id, auction.nft_nonce);
+
+            self.auction_cancelled_event(auction_id);
+        }
+    }
+
+    // Unwind a running auction before its end, e.g. for legal or compliance reasons: the
+    // highest bid is refunded, the NFT goes back to the seller and the reason is recorded
+    #[endpoint(emergencyCancelAuction)]
+    fn emergency_cancel_auction(&self, auction_id: u32, reason: ManagedBuffer) {
+        self.require_role(Role::Admin);
+        let mut auction = self.auctions(auction_id).get();
+        require!(auction.active, ERR_AUCTION_IS_NOT_ACTIVE);
+        require!(!reason.is_empty(), ERR_CANCELLATION_REASON_IS_REQUIRED);
+
+        auction.active = false;
+        self.auctions(auction_id).set(&auction);
+
+        self.close_seller_item(&auction.seller);
+        self.unlock_wine_in_registry(auction.wine_nft_id, &auction.seller);
+        self.update_marketplace_stats(|stats| {
+            stats.active_auctions -= 1;
+        });
+
+        if auction.bid_count > 0 && auction.highest_bidder != auction.seller {
+            self.send_payment(&auction.highest_bidder, &auction.payment_token, &auction.current_bid);
+        }
+        self.send_nft(&auction.seller, &auction.nft_token_id, auction.nft_nonce);
+
+        self.auction_cancellation_reason(auction_id).set(&reason);
+
+        let caller = self.blockchain().get_caller();
+        self.auction_emergency_cancelled_event(auction_id, &caller, &reason);
+    }
+
+    // Withdraw bids that were outbid on a regular auction
+    #[endpoint(withdrawOutbidFunds)]
+    fn withdraw_outbid_funds(&self, auction_id: u32) {
+        let caller = self.blockchain().get_caller();
+        let amount = self.outbid_funds(auction_id, &caller).take();
+        require!(!amount.is_zero(), ERR_NOTHING_TO_WITHDRAW);
+
+        // Outbid funds outlive the auction entry when it has been cleaned up
+        let payment_token = if self.auctions(auction_id).is_empty() {
+            self.cleaned_auction_payment_token(auction_id).get()
+        } else {
+            self.auctions(auction_id).get().payment_token
+        };
+        self.send_payment(&caller, &payment_token, &amount);
+
+        self.outbid_funds_withdrawn_event(auction_id, &caller, &amount);
+    }
+
+    fn require_open_auction(&self, auction_id: u32) -> Auction<Self::Api> {
+        let auction = self.auctions(auction_id).get();
+        require!(auction.active, ERR_AUCTION_IS_NOT_ACTIVE);
+        require!(
+            self.blockchain().get_block_timestamp() < auction.end_timestamp,
+            ERR_AUCTION_HAS_ENDED
+        );
+        auction
+    }
+
+    // Bid `payment_amount`, already received from `bidder` or on their behalf by a relayer
+    fn record_bid(
+        &self,
+        auction_id: u32,
+        mut auction: Auction<Self::Api>,
+        bidder: ManagedAddress,
+        payment_amount: BigUint,
+        opt_recipient: OptionalValue<ManagedAddress>,
+    ) {
+        self.require_not_blacklisted(&bidder);
+        require!(bidder != auction.seller, ERR_CANNOT_BID_ON_YOUR_OWN_AUCTION);
+        let recipient = self.resolve_recipient(&bidder, opt_recipient);
+
+        require!(payment_amount >= self.min_next_bid(&auction), ERR_BID_TOO_LOW);
+
+        // Release previous highest bid: escrowed for withdrawal, or pushed back if direct refunds are enabled
+        if auction.highest_bidder != auction.seller && auction.bid_count > 0 {
+            if self.direct_outbid_refunds().get() {
+                self.send_payment(&auction.highest_bidder, &auction.payment_token, &auction.current_bid);
+            } else {
+                self.outbid_funds(auction_id, &auction.highest_bidder)
+                    .update(|amount| *amount += &auction.current_bid);
+            }
+        }
+
+        // Update auction with new bid
+        auction.current_bid = payment_amount.clone();
+        auction.highest_bidder = bidder.clone();
+        auction.bid_count += 1;
+        self.auction_recipient(auction_id).set(&recipient);
+
+        // Extend auction if bid placed inside the anti-snipe window
+        if self.is_anti_snipe_active(auction_id, &auction) {
+            let config = self.get_auction_anti_snipe_config(auction_id);
+            let extension_count = self.auction_extension_count(auction_id).get() + 1;
+            auction.end_timestamp += config.extension_duration;
+            self.auction_extension_count(auction_id).set(extension_count);
+            self.auction_extended_event(auction_id, auction.end_timestamp, extension_count);
+        }
+
+        self.auctions(auction_id).set(&auction);
+
+        self.bid_placed_event(auction_id, &bidder, &auction.payment_token, &payment_amount);
+    }
+
+    // Current bid plus the increment; before the first bid the current bid is the starting price
+    fn min_next_bid(&self, auction: &Auction<Self::Api>) -> BigUint {
+        &auction.current_bid + &auction.min_bid_increment
+    }
+
+    // Anti-snipe helpers
+    // Whether a bid placed now lands inside the window, with extensions left
+    fn is_anti_snipe_active(&self, auction_id: u32, auction: &Auction<Self::Api>) -> bool {
+        let time_left = self.auction_time_left(auction);
+        if time_left == 0 {
+            return false;
+        }
+        let config = self.get_auction_anti_snipe_config(auction_id);
+        let extension_count = self.auction_extension_count(auction_id).get();
+        time_left < config.extension_window
+            && (config.max_extensions == 0 || extension_count < config.max_extensions)
+    }
+
+    fn auction_time_left(&self, auction: &Auction<Self::Api>) -> u64 {
+        auction.end_timestamp.saturating_sub(self.blockchain().get_block_timestamp())
+    }
+
+    fn validate_anti_snipe_config(
+        &self,
+        extension_window: u64,
+        extension_duration: u64,
+        max_extensions: u32,
+    ) -> AntiSnipeConfig {
+        require!(extension_window <= 3600, ERR_EXTENSION_WINDOW_CANNOT_EXCEED_1_HOUR);
+        require!(extension_duration <= 3600, ERR_EXTENSION_DURATION_CANNOT_EXCEED_1_HOUR);
+        require!(
+            extension_window == 0 || extension_duration > 0,
+            ERR_EXTENSION_DURATION_MUST_BE_GREATER_THAN_ZERO
+        );
+
+        AntiSnipeConfig {
+            extension_window,
+            extension_duration,
+            max_extensions,
+        }
+    }
+
+    // Gift recipient helpers
+    fn get_auction_recipient(&self, auction_id: u32, highest_bidder: &ManagedAddress) -> ManagedAddress {
+        let recipient_mapper = self.auction_recipient(auction_id);
+        if recipient_mapper.is_empty() {
+            highest_bidder.clone()
+        } else {
+            recipient_mapper.get()
+        }
+    }
+
+    // View functions
+    #[view(getAuction)]
+    fn get_auction(&self, auction_id: u32) -> Auction<Self::Api> {
+        self.auctions(auction_id).get()
+    }
+
+    // Bids at or above this amount are accepted while the auction runs
+    #[view(getMinNextBid)]
+    fn get_min_next_bid(&self, auction_id: u32) -> BigUint {
+        let auction = self.auctions(auction_id).get();
+        self.min_next_bid(&auction)
+    }
+
+    // Seconds until bidding closes, 0 once it has; extensions are included
+    #[view(getTimeLeft)]
+    fn get_time_left(&self, auction_id: u32) -> u64 {
+        let auction = self.auctions(auction_id).get();
+        if !auction.active {
+            return 0;
+        }
+        self.auction_time_left(&auction)
+    }
+
+    #[view(getAuctionView)]
+    fn get_auction_view(&self, auction_id: u32) -> AuctionView<Self::Api> {
+        let auction = self.auctions(auction_id).get();
+        let time_left = if auction.active {
+            self.auction_time_left(&auction)
+        } else {
+            0
+        };
+
+        AuctionView {
+            auction_id,
+            wine_nft_id: auction.wine_nft_id,
+            seller: auction.seller.clone(),
+            payment_token: auction.payment_token.clone(),
+            current_bid: auction.current_bid.clone(),
+            highest_bidder: auction.highest_bidder.clone(),
+            bid_count: auction.bid_count,
+            min_next_bid: self.min_next_bid(&auction),
+            end_timestamp: auction.end_timestamp,
+            time_left,
+            extension_count: self.auction_extension_count(auction_id).get(),
+            anti_snipe_active: auction.active && self.is_anti_snipe_active(auction_id, &auction),
+            active: auction.active,
+        }
+    }
+
+    #[view(getAuctionRecipient)]
+    fn get_auction_recipient_view(&self, auction_id: u32) -> ManagedAddress {
+        let auction = self.auctions(auction_id).get();
+        self.get_auction_recipient(auction_id, &auction.highest_bidder)
+    }
+
+    #[view(getAuctionCancellationReason)]
+    fn get_auction_cancellation_reason(&self, auction_id: u32) -> ManagedBuffer {
+        self.auction_cancellation_reason(auction_id).get()
+    }
+
+    #[view(getOutbidFunds)]
+    fn get_outbid_funds(&self, auction_id: u32, bidder: &ManagedAddress) -> BigUint {
+        self.outbid_funds(auction_id, bidder).get()
+    }
+
+    #[view(isDirectOutbidRefundsEnabled)]
+    fn is_direct_outbid_refunds_enabled(&self) -> bool {
+        self.direct_outbid_refunds().get()
+    }
+
+    #[view(getAntiSnipeConfig)]
+    fn get_anti_snipe_config(&self) -> AntiSnipeConfig {
+        self.anti_snipe_config().get()
+    }
+
+    #[view(getAuctionAntiSnipeConfig)]
+    fn get_auction_anti_snipe_config(&self, auction_id: u32) -> AntiSnipeConfig {
+        let override_mapper = self.auction_anti_snipe_config(auction_id);
+        if override_mapper.is_empty() {
+            self.anti_snipe_config().get()
+        } else {
+            override_mapper.get()
+        }
+    }
+
+    #[view(getAuctionExtensionCount)]
+    fn get_auction_extension_count(&self, auction_id: u32) -> u32 {
+        self.auction_extension_count(auction_id).get()
+    }
+
+    // Paginated: returns the next cursor (0 when done) followed by at most `page_size` auction ids
+    #[view(getSellerAuctions)]
+    fn get_seller_auctions(
+        &self,
+        seller: &ManagedAddress,
+        cursor: u64,
+        page_size: u32,
+    ) -> MultiValue2<u64, MultiValueEncoded<u32>> {
+        let ids = self.seller_auctions(seller);
+        let page = Page::new(cursor, page_size, MAX_IDS_PAGE_SIZE, ids.len());
+
+        let mut result = MultiValueEncoded::new();
+        for index in page.start..page.end {
+            result.push(ids.get(index + 1));
+        }
+        (page.next_cursor, result).into()
+    }
+
+    // Paginated over auction ids: returns the next cursor (0 when done) followed by the ids in
+    // the page of active auctions whose end has passed, ready for finalizeAuction
+    #[view(getFinalizableAuctions)]
+    fn get_finalizable_auctions(
+        &self,
+        cursor: u64,
+        page_size: u32,
+    ) -> MultiValue2<u64, MultiValueEncoded<u32>> {
+        // The counter holds the next id, and ids start at 1
+        let auction_count = self.auction_counter().get().saturating_sub(1) as usize;
+        let page = Page::new(cursor, page_size, MAX_IDS_PAGE_SIZE, auction_count);
+        let now = self.blockchain().get_block_timestamp();
+
+        let mut result = MultiValueEncoded::new();
+        for index in page.start..page.end {
+            let auction_id = (index + 1) as u32;
+            let auction_mapper = self.auctions(auction_id);
+            if auction_mapper.is_empty() {
+                continue;
+            }
+            let auction = auction_mapper.get();
+            if auction.active && now >= auction.end_timestamp {
+                result.push(auction_id);
+            }
+        }
+        (page.next_cursor, result).into()
+    }
+
+    // Storage mappers
+    #[storage_mapper("auctions")]
+    fn auctions(&self, auction_id: u32) -> SingleValueMapper<Auction<Self::Api>>;
+
+    #[storage_mapper("auctionRecipient")]
+    fn auction_recipient(&self, auction_id: u32) -> SingleValueMapper<ManagedAddress>;
+
+    #[storage_mapper("outbidFunds")]
+    fn outbid_funds(&self, auction_id: u32, bidder: &ManagedAddress) -> SingleValueMapper<BigUint>;
+
+    #[storage_mapper("directOutbidRefunds")]
+    fn direct_outbid_refunds(&self) -> SingleValueMapper<bool>;
+
+    #[storage_mapper("antiSnipeConfig")]
+    fn anti_snipe_config(&self) -> SingleValueMapper<AntiSnipeConfig>;
+
+    #[storage_mapper("auctionAntiSnipeConfig")]
+    fn auction_anti_snipe_config(&self, auction_id: u32) -> SingleValueMapper<AntiSnipeConfig>;
+
+    #[storage_mapper("auctionExtensionCount")]
+    fn auction_extension_count(&self, auction_id: u32) -> SingleValueMapper<u32>;
+
+    // Tombstone of an auction entry removed by cleanupAuctions
+    #[storage_mapper("cleanedAuctionPaymentToken")]
+    fn cleaned_auction_payment_token(
+        &self,
+        auction_id: u32,
+    ) -> SingleValueMapper<EgldOrEsdtTokenIdentifier>;
+
+    #[storage_mapper("auctionCancellationReason")]
+    fn auction_cancellation_reason(&self, auction_id: u32) -> SingleValueMapper<ManagedBuffer>;
+
+    #[storage_mapper("sellerAuctions")]
+    fn seller_auctions(&self, seller: &ManagedAddress) -> VecMapper<u32>;
+
+    #[storage_mapper("auctionCounter")]
+    fn auction_counter(&self) -> SingleValueMapper<u32>;
+
+    // Events
+    #[event("auctionCreated")]
+    fn auction_created_event(
+        &self,
+        #[indexed] auction_id: u32,
+        #[indexed] wine_nft_id: u32,
+        #[indexed] seller: &ManagedAddress,
+        starting_price: &BigUint,
+    );
+
+    #[event("bidPlaced")]
+    fn bid_placed_event(
+        &self,
+        #[indexed] auction_id: u32,
+        #[indexed] bidder: &ManagedAddress,
+        #[indexed] payment_token: &EgldOrEsdtTokenIdentifier,
+        bid_amount: &BigUint,
+    );
+
+    #[event("auctionExtended")]
+    fn auction_extended_event(
+        &self,
+        #[indexed] auction_id: u32,
+        new_end_timestamp: u64,
+        extension_count: u32,
+    );
+
+    #[event("antiSnipeConfigUpdated")]
+    fn anti_snipe_config_updated_event(
+        &self,
+        #[indexed] auction_id: u32, // 0 for the marketplace-wide config
+        extension_window: u64,
+        extension_duration: u64,
+        max_extensions: u32,
+    );
+
+    #[event("auctionFinalized")]
+    fn auction_finalized_event(
+        &self,
+        #[indexed] auction_id: u32,
+        #[indexed] winner: &ManagedAddress,
+        #[indexed] recipient: &ManagedAddress,
+        settlement: &SaleSettlement<Self::Api>,
+    );
+
+    #[event("outbidFundsWithdrawn")]
+    fn outbid_funds_withdrawn_event(
+        &self,
+        #[indexed] auction_id: u32,
+        #[indexed] bidder: &ManagedAddress,
+        amount: &BigUint,
+    );
+
+    #[event("auctionCancelled")]
+    fn auction_cancelled_event(
+        &self,
+        #[indexed] auction_id: u32,
+    );
+
+    #[event("auctionEmergencyCancelled")]
+    fn auction_emergency_cancelled_event(
+        &self,
+        #[indexed] auction_id: u32,
+        #[indexed] cancelled_by: &ManagedAddress,
+        reason: &ManagedBuffer,
+    );
+}
@@ -0,0 +1,146 @@
+use multiversx_sc::derive_imports::*;
+use multiversx_sc::imports::*;
+use wine_common::errors::*;
+
+use crate::pagination::{Page, MAX_AUCTION_RESULTS_PAGE_SIZE};
+
+#[derive(TopEncode, TopDecode, NestedEncode, NestedDecode, TypeAbi, PartialEq, Debug, Clone, Copy)]
+pub enum AuctionKind {
+    English,
+    Candle,
+}
+
+// Archived outcome of an auction that sold, as stored
+#[derive(TopEncode, TopDecode, NestedEncode, NestedDecode, TypeAbi, PartialEq, Debug)]
+pub struct AuctionResult<M: ManagedTypeApi> {
+    pub kind: AuctionKind,
+    pub auction_id: u32,
+    pub wine_nft_id: u32,
+    pub seller: ManagedAddress<M>,
+    pub winner: ManagedAddress<M>,
+    pub payment_token: EgldOrEsdtTokenIdentifier<M>,
+    pub final_price: BigUint<M>,
+    pub timestamp: u64,
+}
+
+// Archived outcome as returned by the views. For winners who asked for privacy `winner`
+// holds a salted hash of their address instead, the same one for all of their wins.
+#[derive(TopEncode, TopDecode, NestedEncode, NestedDecode, TypeAbi, PartialEq, Debug)]
+pub struct AuctionResultView<M: ManagedTypeApi> {
+    pub kind: AuctionKind,
+    pub auction_id: u32,
+    pub wine_nft_id: u32,
+    pub seller: ManagedAddress<M>,
+    pub winner: ManagedAddress<M>,
+    pub winner_private: bool,
+    pub payment_token: EgldOrEsdtTokenIdentifier<M>,
+    pub final_price: BigUint<M>,
+    pub timestamp: u64,
+}
+
+// Post-sale record of auction results. Privacy only affects these views: the raw storage
+// and the settlement events keep the full winner address.
+#[multiversx_sc::module]
+pub trait AuctionArchiveModule: crate::market_summary::MarketSummaryModule {
+    // Hide (or show again) the caller's address in the archive views, for past and future wins
+    #[endpoint(setAuctionWinnerPrivacy)]
+    fn set_auction_winner_privacy(&self, private: bool) {
+        let caller = self.blockchain().get_caller();
+        self.private_auction_winner(&caller).set(private);
+        self.auction_winner_privacy_set_event(&caller, private);
+    }
+
+    // Internal helpers
+    fn init_archive_salt(&self) {
+        if self.archive_salt().is_empty() {
+            let seed = self.blockchain().get_block_random_seed();
+            self.archive_salt().set(seed.as_managed_buffer());
+        }
+    }
+
+    fn archive_auction_result(&self, result: AuctionResult<Self::Api>) {
+        self.record_wine_sale(result.wine_nft_id, &result.payment_token, &result.final_price);
+        let result_id = self.auction_results().push(&result);
+        self.auction_result_archived_event(result_id, result.auction_id, &result.winner);
+    }
+
+    fn auction_result_view(&self, result: AuctionResult<Self::Api>) -> AuctionResultView<Self::Api> {
+        let winner_private = self.private_auction_winner(&result.winner).get();
+        let winner = if winner_private {
+            let mut preimage = self.archive_salt().get();
+            preimage.append(result.winner.as_managed_buffer());
+            ManagedAddress::from(self.crypto().keccak256(&preimage))
+        } else {
+            result.winner
+        };
+
+        AuctionResultView {
+            kind: result.kind,
+            auction_id: result.auction_id,
+            wine_nft_id: result.wine_nft_id,
+            seller: result.seller,
+            winner,
+            winner_private,
+            payment_token: result.payment_token,
+            final_price: result.final_price,
+            timestamp: result.timestamp,
+        }
+    }
+
+    // View functions
+    #[view(getAuctionResult)]
+    fn get_auction_result(&self, result_id: usize) -> AuctionResultView<Self::Api> {
+        let results = self.auction_results();
+        require!(result_id >= 1 && result_id <= results.len(), ERR_AUCTION_RESULT_DOES_NOT_EXIST);
+        self.auction_result_view(results.get(result_id))
+    }
+
+    // Paginated: returns the next cursor (0 when done) followed by at most `page_size` results,
+    // oldest first
+    #[view(getAuctionResults)]
+    fn get_auction_results(
+        &self,
+        cursor: u64,
+        page_size: u32,
+    ) -> MultiValue2<u64, MultiValueEncoded<AuctionResultView<Self::Api>>> {
+        let results = self.auction_results();
+        let page = Page::new(cursor, page_size, MAX_AUCTION_RESULTS_PAGE_SIZE, results.len());
+
+        let mut result = MultiValueEncoded::new();
+        for index in page.start..page.end {
+            result.push(self.auction_result_view(results.get(index + 1)));
+        }
+        (page.next_cursor, result).into()
+    }
+
+    #[view(isAuctionWinnerPrivate)]
+    fn is_auction_winner_private(&self, winner: &ManagedAddress) -> bool {
+        self.private_auction_winner(winner).get()
+    }
+
+    // Storage mappers
+    #[storage_mapper("auctionResults")]
+    fn auction_results(&self) -> VecMapper<AuctionResult<Self::Api>>;
+
+    #[storage_mapper("privateAuctionWinner")]
+    fn private_auction_winner(&self, winner: &ManagedAddress) -> SingleValueMapper<bool>;
+
+    #[storage_mapper("archiveSalt")]
+    fn archive_salt(&self) -> SingleValueMapper<ManagedBuffer>;
+
+    // Events
+    #[event("auctionResultArchived")]
+    fn auction_result_archived_event(
+        &self,
+        #[indexed] result_id: usize,
+        #[indexed] auction_id: u32,
+        winner: &ManagedAddress,
+    );
+
+    #[event("auctionWinnerPrivacySet")]
+    fn auction_winner_privacy_set_event(
+        &self,
+        #[indexed] winner: &ManagedAddress,
+        private: bool,
+    );
+}
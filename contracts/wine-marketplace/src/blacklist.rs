@@ -0,0 +1,79 @@
+use multiversx_sc::imports::*;
+use wine_common::errors::*;
+use wine_roles::Role;
+
+use crate::pagination::{Page, MAX_ADDRESSES_PAGE_SIZE};
+
+// Addresses on the blacklist cannot list, buy, bid or make/accept offers
+#[multiversx_sc::module]
+pub trait BlacklistModule: wine_roles::RolesModule {
+    #[endpoint(addToBlacklist)]
+    fn add_to_blacklist(&self, addresses: MultiValueEncoded<ManagedAddress>) {
+        self.require_role(Role::Admin);
+        for address in addresses {
+            if self.blacklist().insert(address.clone()) {
+                self.address_blacklisted_event(&address);
+            }
+        }
+    }
+
+    #[endpoint(removeFromBlacklist)]
+    fn remove_from_blacklist(&self, addresses: MultiValueEncoded<ManagedAddress>) {
+        self.require_role(Role::Admin);
+        for address in addresses {
+            if self.blacklist().swap_remove(&address) {
+                self.address_unblacklisted_event(&address);
+            }
+        }
+    }
+
+    // Internal helpers
+    fn require_not_blacklisted(&self, address: &ManagedAddress) {
+        require!(!self.blacklist().contains(address), ERR_ADDRESS_IS_BLACKLISTED);
+    }
+
+    // View functions
+    #[view(isBlacklisted)]
+    fn is_blacklisted(&self, address: &ManagedAddress) -> bool {
+        self.blacklist().contains(address)
+    }
+
+    #[view(getBlacklistCount)]
+    fn get_blacklist_count(&self) -> usize {
+        self.blacklist().len()
+    }
+
+    // Paginated: returns the next cursor (0 when done) followed by at most `page_size` addresses
+    #[view(getBlacklist)]
+    fn get_blacklist(
+        &self,
+        cursor: u64,
+        page_size: u32,
+    ) -> MultiValue2<u64, MultiValueEncoded<ManagedAddress>> {
+        let blacklist = self.blacklist();
+        let page = Page::new(cursor, page_size, MAX_ADDRESSES_PAGE_SIZE, blacklist.len());
+
+        let mut result = MultiValueEncoded::new();
+        for index in page.start..page.end {
+            result.push(blacklist.get_by_index(index + 1));
+        }
+        (page.next_cursor, result).into()
+    }
+
+    // Storage mappers
+    #[storage_mapper("blacklist")]
+    fn blacklist(&self) -> UnorderedSetMapper<ManagedAddress>;
+
+    // Events
+    #[event("addressBlacklisted")]
+    fn address_blacklisted_event(
+        &self,
+        #[indexed] address: &ManagedAddress,
+    );
+
+    #[event("addressUnblacklisted")]
+    fn address_unblacklisted_event(
+        &self,
+        #[indexed] address: &ManagedAddress,
+    );
+}
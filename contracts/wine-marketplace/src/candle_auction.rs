@@ -0,0 +1,374 @@
+use multiversx_sc::derive_imports::*;
+use multiversx_sc::imports::*;
+use wine_common::errors::*;
+
+use crate::auction_archive::{AuctionKind, AuctionResult};
+use crate::pagination::{Page, MAX_CANDLE_BIDS_PAGE_SIZE, MAX_IDS_PAGE_SIZE};
+use crate::treasury::SaleSettlement;
+
+// Candle auction: bidding runs until `end_timestamp`, but the effective end is drawn
+// retroactively from [closing_window_start, end_timestamp) when the auction is settled
+#[derive(TopEncode, TopDecode, TypeAbi, PartialEq, Debug)]
+pub struct CandleAuction<M: ManagedTypeApi> {
+    pub wine_nft_id: u32,
+    pub nft_token_id: TokenIdentifier<M>,
+    pub nft_nonce: u64,
+    pub seller: ManagedAddress<M>,
+    pub starting_price: BigUint<M>,
+    pub min_bid_increment: BigUint<M>,
+    pub payment_token: EgldOrEsdtTokenIdentifier<M>,
+    pub start_timestamp: u64,
+    pub closing_window_start: u64,
+    pub end_timestamp: u64,
+    pub effective_end_timestamp: u64, // 0 until settled
+    pub highest_bid: BigUint<M>,
+    pub highest_bidder: ManagedAddress<M>,
+    pub bid_count: u32,
+    pub active: bool,
+}
+
+#[derive(TopEncode, TopDecode, NestedEncode, NestedDecode, TypeAbi, PartialEq, Debug)]
+pub struct CandleBid<M: ManagedTypeApi> {
+    pub bidder: ManagedAddress<M>,
+    pub amount: BigUint<M>,
+    pub timestamp: u64,
+}
+
+#[multiversx_sc::module]
+pub trait CandleAuctionModule:
+    wine_roles::RolesModule
+    + crate::admin::AdminModule
+    + crate::collector_tiers::CollectorTiersModule
+    + crate::fee_discounts::FeeDiscountsModule
+    + crate::stats_snapshots::StatsSnapshotsModule
+    + crate::treasury::TreasuryModule
+    + crate::blacklist::BlacklistModule
+    + crate::seller_limits::SellerLimitsModule
+    + crate::registry_cache::RegistryCacheModule
+    + crate::market_summary::MarketSummaryModule
+    + crate::auction_archive::AuctionArchiveModule
+    + wine_randomness::RandomnessModule
+{
+    // Create candle auction: the closing window is the last `closing_window_seconds` of the auction
+    #[payable("*")]
+    #[endpoint(createCandleAuction)]
+    fn create_candle_auction(
+        &self,
+        wine_nft_id: u32,
+        starting_price: BigUint,
+        payment_token: EgldOrEsdtTokenIdentifier,
+        duration_seconds: u64,
+        closing_window_seconds: u64,
+        min_bid_increment: BigUint,
+    ) -> u32 {
+        self.require_not_paused();
+        self.require_supported_payment_token(&payment_token);
+        require!(!starting_price.is_zero(), ERR_STARTING_PRICE_MUST_BE_GREATER_THAN_ZERO);
+        require!(duration_seconds >= 3600, ERR_MINIMUM_AUCTION_DURATION_IS_1_HOUR);
+        require!(duration_seconds <= 604800, ERR_MAXIMUM_AUCTION_DURATION_IS_7_DAYS);
+        require!(closing_window_seconds >= 600, ERR_MINIMUM_CLOSING_WINDOW_IS_10_MINUTES);
+        require!(
+            closing_window_seconds <= duration_seconds,
+            ERR_CLOSING_WINDOW_CANNOT_EXCEED_AUCTION_DURATION
+        );
+        require!(!min_bid_increment.is_zero(), ERR_MIN_BID_INCREMENT_MUST_BE_GREATER_THAN_ZERO);
+
+        // Receive NFT in escrow; registry gates below apply to the wine it is a bottle of
+        let payment = self.call_value().single_esdt();
+        require!(payment.amount == BigUint::from(1u32), ERR_MUST_SEND_EXACTLY_1_NFT);
+        self.require_escrowed_wine(wine_nft_id, &payment.token_identifier, payment.token_nonce);
+
+        let caller = self.blockchain().get_caller();
+        self.require_not_blacklisted(&caller);
+        self.require_wine_listable(wine_nft_id, &caller);
+        self.require_certified_for_auction(wine_nft_id, &payment.token_identifier);
+        let start_timestamp = self.blockchain().get_block_timestamp();
+        let end_timestamp = start_timestamp + duration_seconds;
+
+        let auction_id = self.candle_auction_counter().get();
+        let auction = CandleAuction {
+            wine_nft_id,
+            nft_token_id: payment.token_identifier.clone(),
+            nft_nonce: payment.token_nonce,
+            seller: caller.clone(),
+            starting_price,
+            min_bid_increment,
+            payment_token,
+            start_timestamp,
+            closing_window_start: end_timestamp - closing_window_seconds,
+            end_timestamp,
+            effective_end_timestamp: 0u64,
+            highest_bid: BigUint::zero(),
+            highest_bidder: caller.clone(),
+            bid_count: 0u32,
+            active: true,
+        };
+
+        self.candle_auctions(auction_id).set(&auction);
+        self.candle_auction_counter().set(auction_id + 1);
+        self.seller_candle_auctions(&caller).push(&auction_id);
+
+        self.open_seller_item(&caller);
+        self.lock_wine_in_registry(wine_nft_id, &caller);
+        self.update_marketplace_stats(|stats| {
+            stats.active_auctions += 1;
+        });
+
+        self.candle_auction_created_event(
+            auction_id,
+            wine_nft_id,
+            &caller,
+            &auction.starting_price,
+            auction.closing_window_start,
+            end_timestamp,
+        );
+
+        auction_id
+    }
+
+    // Place candle bid: every bid stays escrowed until settlement, since any bid in the
+    // closing window may turn out to be the winner
+    #[payable("*")]
+    #[endpoint(placeCandleBid)]
+    fn place_candle_bid(&self, auction_id: u32) {
+        self.require_not_paused();
+        let mut auction = self.candle_auctions(auction_id).get();
+        require!(auction.active, ERR_AUCTION_IS_NOT_ACTIVE);
+
+        let current_timestamp = self.blockchain().get_block_timestamp();
+        require!(current_timestamp < auction.end_timestamp, ERR_AUCTION_HAS_ENDED);
+
+        let payment_amount = self.require_fungible_payment(&auction.payment_token);
+
+        let bidder = self.blockchain().get_caller();
+        self.require_not_blacklisted(&bidder);
+        require!(bidder != auction.seller, ERR_CANNOT_BID_ON_YOUR_OWN_AUCTION);
+
+        let min_bid = if auction.bid_count == 0 {
+            auction.starting_price.clone()
+        } else {
+            &auction.highest_bid + &auction.min_bid_increment
+        };
+        require!(payment_amount >= min_bid, ERR_BID_TOO_LOW);
+
+        let bid = CandleBid {
+            bidder: bidder.clone(),
+            amount: payment_amount.clone(),
+            timestamp: current_timestamp,
+        };
+        self.candle_bids(auction_id).push(&bid);
+        self.candle_escrow(auction_id, &bidder).update(|escrow| *escrow += &payment_amount);
+
+        auction.highest_bid = payment_amount.clone();
+        auction.highest_bidder = bidder.clone();
+        auction.bid_count += 1;
+        self.candle_auctions(auction_id).set(&auction);
+
+        self.candle_bid_placed_event(
+            auction_id,
+            &bidder,
+            &auction.payment_token,
+            &payment_amount,
+            current_timestamp,
+        );
+    }
+
+    // Settle candle auction: anyone can trigger it once bidding has closed
+    #[endpoint(settleCandleAuction)]
+    fn settle_candle_auction(&self, auction_id: u32) {
+        let mut auction = self.candle_auctions(auction_id).get();
+        require!(auction.active, ERR_AUCTION_IS_NOT_ACTIVE);
+        require!(
+            self.blockchain().get_block_timestamp() >= auction.end_timestamp,
+            ERR_AUCTION_HAS_NOT_ENDED_YET
+        );
+
+        let domain = ManagedBuffer::from(wine_randomness::CANDLE_AUCTION_DOMAIN);
+        let offset = self.draw_random_u64_in_range(
+            &domain,
+            auction_id as u64,
+            0u64,
+            auction.end_timestamp - auction.closing_window_start,
+        );
+        let effective_end = auction.closing_window_start + offset;
+
+        auction.active = false;
+        auction.effective_end_timestamp = effective_end;
+
+        // Bids are strictly increasing, so the last bid placed before the cutoff is the winner
+        let bids = self.candle_bids(auction_id);
+        let mut winning_bid: Option<CandleBid<Self::Api>> = None;
+        for index in (1..=bids.len()).rev() {
+            let bid = bids.get(index);
+            if bid.timestamp <= effective_end {
+                winning_bid = Some(bid);
+                break;
+            }
+        }
+
+        self.close_seller_item(&auction.seller);
+        self.unlock_wine_in_registry(auction.wine_nft_id, &auction.seller);
+        self.update_marketplace_stats(|stats| {
+            stats.active_auctions -= 1;
+        });
+
+        match winning_bid {
+            Some(bid) => {
+                auction.highest_bid = bid.amount.clone();
+                auction.highest_bidder = bid.bidder.clone();
+                self.candle_auctions(auction_id).set(&auction);
+
+                // The winning amount leaves the winner's escrow; everything else stays withdrawable
+                self.candle_escrow(auction_id, &bid.bidder).update(|escrow| *escrow -= &bid.amount);
+
+                self.send_nft(&bid.bidder, &auction.nft_token_id, auction.nft_nonce);
+                self.record_verified_buyer_in_registry(auction.wine_nft_id, &bid.bidder);
+                let settlement = self.pay_out_sale(
+                    &auction.seller,
+                    &bid.bidder,
+                    &auction.nft_token_id,
+                    auction.nft_nonce,
+                    &auction.payment_token,
+                    &bid.amount,
+                );
+
+                self.candle_auction_settled_event(auction_id, &bid.bidder, effective_end, &settlement);
+                self.archive_auction_result(AuctionResult {
+                    kind: AuctionKind::Candle,
+                    auction_id,
+                    wine_nft_id: auction.wine_nft_id,
+                    seller: auction.seller,
+                    winner: bid.bidder,
+                    payment_token: auction.payment_token,
+                    final_price: bid.amount,
+                    timestamp: effective_end,
+                });
+            },
+            None => {
+                self.candle_auctions(auction_id).set(&auction);
+
+                self.send_nft(&auction.seller, &auction.nft_token_id, auction.nft_nonce);
+
+                let settlement = self.unsettled_sale(&auction.payment_token, &BigUint::zero());
+                self.candle_auction_settled_event(auction_id, &auction.seller, effective_end, &settlement);
+            },
+        }
+    }
+
+    // Withdraw escrowed candle bids that did not win
+    #[endpoint(withdrawCandleFunds)]
+    fn withdraw_candle_funds(&self, auction_id: u32) {
+        let auction = self.candle_auctions(auction_id).get();
+        require!(!auction.active, ERR_AUCTION_IS_NOT_SETTLED_YET);
+
+        let caller = self.blockchain().get_caller();
+        let amount = self.candle_escrow(auction_id, &caller).take();
+        require!(!amount.is_zero(), ERR_NOTHING_TO_WITHDRAW);
+
+        self.send_payment(&caller, &auction.payment_token, &amount);
+
+        self.candle_funds_withdrawn_event(auction_id, &caller, &amount);
+    }
+
+    // View functions
+    #[view(getCandleAuction)]
+    fn get_candle_auction(&self, auction_id: u32) -> CandleAuction<Self::Api> {
+        self.candle_auctions(auction_id).get()
+    }
+
+    // Paginated: returns the next cursor (0 when done) followed by at most `page_size` bids
+    #[view(getCandleBids)]
+    fn get_candle_bids(
+        &self,
+        auction_id: u32,
+        cursor: u64,
+        page_size: u32,
+    ) -> MultiValue2<u64, MultiValueEncoded<CandleBid<Self::Api>>> {
+        let bids = self.candle_bids(auction_id);
+        let page = Page::new(cursor, page_size, MAX_CANDLE_BIDS_PAGE_SIZE, bids.len());
+
+        let mut result = MultiValueEncoded::new();
+        for index in page.start..page.end {
+            result.push(bids.get(index + 1));
+        }
+        (page.next_cursor, result).into()
+    }
+
+    #[view(getCandleEscrow)]
+    fn get_candle_escrow(&self, auction_id: u32, bidder: &ManagedAddress) -> BigUint {
+        self.candle_escrow(auction_id, bidder).get()
+    }
+
+    // Paginated: returns the next cursor (0 when done) followed by at most `page_size` candle auction ids
+    #[view(getSellerCandleAuctions)]
+    fn get_seller_candle_auctions(
+        &self,
+        seller: &ManagedAddress,
+        cursor: u64,
+        page_size: u32,
+    ) -> MultiValue2<u64, MultiValueEncoded<u32>> {
+        let ids = self.seller_candle_auctions(seller);
+        let page = Page::new(cursor, page_size, MAX_IDS_PAGE_SIZE, ids.len());
+
+        let mut result = MultiValueEncoded::new();
+        for index in page.start..page.end {
+            result.push(ids.get(index + 1));
+        }
+        (page.next_cursor, result).into()
+    }
+
+    // Storage mappers
+    #[storage_mapper("candleAuctions")]
+    fn candle_auctions(&self, auction_id: u32) -> SingleValueMapper<CandleAuction<Self::Api>>;
+
+    #[storage_mapper("candleBids")]
+    fn candle_bids(&self, auction_id: u32) -> VecMapper<CandleBid<Self::Api>>;
+
+    #[storage_mapper("candleEscrow")]
+    fn candle_escrow(&self, auction_id: u32, bidder: &ManagedAddress) -> SingleValueMapper<BigUint>;
+
+    #[storage_mapper("sellerCandleAuctions")]
+    fn seller_candle_auctions(&self, seller: &ManagedAddress) -> VecMapper<u32>;
+
+    #[storage_mapper("candleAuctionCounter")]
+    fn candle_auction_counter(&self) -> SingleValueMapper<u32>;
+
+    // Events
+    #[event("candleAuctionCreated")]
+    fn candle_auction_created_event(
+        &self,
+        #[indexed] auction_id: u32,
+        #[indexed] wine_nft_id: u32,
+        #[indexed] seller: &ManagedAddress,
+        starting_price: &BigUint,
+        closing_window_start: u64,
+        end_timestamp: u64,
+    );
+
+    #[event("candleBidPlaced")]
+    fn candle_bid_placed_event(
+        &self,
+        #[indexed] auction_id: u32,
+        #[indexed] bidder: &ManagedAddress,
+        #[indexed] payment_token: &EgldOrEsdtTokenIdentifier,
+        bid_amount: &BigUint,
+        timestamp: u64,
+    );
+
+    #[event("candleAuctionSettled")]
+    fn candle_auction_settled_event(
+        &self,
+        #[indexed] auction_id: u32,
+        #[indexed] winner: &ManagedAddress,
+        #[indexed] effective_end_timestamp: u64,
+        settlement: &SaleSettlement<Self::Api>,
+    );
+
+    #[event("candleFundsWithdrawn")]
+    fn candle_funds_withdrawn_event(
+        &self,
+        #[indexed] auction_id: u32,
+        #[indexed] bidder: &ManagedAddress,
+        amount: &BigUint,
+    );
+}
@@ -0,0 +1,136 @@
+use multiversx_sc::imports::*;
+use wine_common::errors::*;
+use wine_roles::Role;
+
+use crate::migration::STORAGE_VERSION;
+use crate::protected_sale::ProtectedSaleStatus;
+
+// Entries anyone may clean up once this long past their deadline or end; admins need not wait
+pub const CLEANUP_RETENTION: u64 = 90 * 24 * 60 * 60;
+// Ids per cleanupListings / cleanupAuctions call
+pub const MAX_CLEANUP_BATCH: usize = 50;
+
+// Clears the storage of settled listings and auctions to keep contract state small. Only the
+// cleanup event remains, plus the payment token of a cleared auction so outbid bidders can
+// still withdraw. Auction results stay in the auction archive.
+#[multiversx_sc::module]
+pub trait CleanupModule:
+    wine_roles::RolesModule
+    + crate::admin::AdminModule
+    + crate::collector_tiers::CollectorTiersModule
+    + crate::loyalty::LoyaltyModule
+    + crate::fee_discounts::FeeDiscountsModule
+    + crate::stats_snapshots::StatsSnapshotsModule
+    + crate::treasury::TreasuryModule
+    + crate::blacklist::BlacklistModule
+    + crate::seller_limits::SellerLimitsModule
+    + crate::registry_cache::RegistryCacheModule
+    + crate::market_summary::MarketSummaryModule
+    + crate::protected_sale::ProtectedSaleModule
+    + crate::exchange_rates::ExchangeRatesModule
+    + crate::listing::ListingModule
+    + crate::installments::InstallmentsModule
+    + crate::migration::MigrationModule
+    + crate::auction_archive::AuctionArchiveModule
+    + crate::auction::AuctionModule
+{
+    // Ids that are still in use, already cleared or not old enough are skipped. Returns how
+    // many listings were cleared.
+    #[endpoint(cleanupListings)]
+    fn cleanup_listings(&self, listing_ids: MultiValueEncoded<u32>) -> u32 {
+        require!(listing_ids.len() <= MAX_CLEANUP_BATCH, ERR_CLEANUP_BATCH_TOO_LARGE);
+        // Listings not yet migrated cannot be decoded
+        require!(
+            self.get_storage_version() >= STORAGE_VERSION,
+            ERR_STORAGE_MIGRATION_PENDING
+        );
+        let retention = self.cleanup_retention_for_caller();
+        let current_timestamp = self.blockchain().get_block_timestamp();
+
+        let mut cleaned = 0u32;
+        for listing_id in listing_ids {
+            let listing_mapper = self.listings(listing_id);
+            if listing_mapper.is_empty() {
+                continue;
+            }
+            let listing = listing_mapper.get();
+            if listing.active
+                || current_timestamp < listing.deadline + retention
+                || !self.installment_plans(listing_id).is_empty()
+                || self.is_protected_sale_open(listing_id)
+            {
+                continue;
+            }
+
+            listing_mapper.clear();
+            self.listing_payment_tokens(listing_id).clear();
+            self.auto_relist_limit(listing_id).clear();
+            self.mixed_settlement(listing_id).clear();
+            self.relist_count(listing_id).clear();
+            self.protected_sales(listing_id).clear();
+
+            self.listing_cleaned_up_event(listing_id, &listing.seller);
+            cleaned += 1;
+        }
+
+        cleaned
+    }
+
+    // Same rules as cleanupListings, for English auctions
+    #[endpoint(cleanupAuctions)]
+    fn cleanup_auctions(&self, auction_ids: MultiValueEncoded<u32>) -> u32 {
+        require!(auction_ids.len() <= MAX_CLEANUP_BATCH, ERR_CLEANUP_BATCH_TOO_LARGE);
+        let retention = self.cleanup_retention_for_caller();
+        let current_timestamp = self.blockchain().get_block_timestamp();
+
+        let mut cleaned = 0u32;
+        for auction_id in auction_ids {
+            let auction_mapper = self.auctions(auction_id);
+            if auction_mapper.is_empty() {
+                continue;
+            }
+            let auction = auction_mapper.get();
+            if auction.active || current_timestamp < auction.end_timestamp + retention {
+                continue;
+            }
+
+            auction_mapper.clear();
+            self.auction_recipient(auction_id).clear();
+            self.auction_anti_snipe_config(auction_id).clear();
+            self.auction_extension_count(auction_id).clear();
+            self.auction_cancellation_reason(auction_id).clear();
+            self.cleaned_auction_payment_token(auction_id).set(&auction.payment_token);
+
+            self.auction_cleaned_up_event(auction_id, &auction.seller);
+            cleaned += 1;
+        }
+
+        cleaned
+    }
+
+    // Internal helpers
+    fn cleanup_retention_for_caller(&self) -> u64 {
+        let caller = self.blockchain().get_caller();
+        if self.has_role(Role::Admin, &caller) {
+            0
+        } else {
+            CLEANUP_RETENTION
+        }
+    }
+
+    fn is_protected_sale_open(&self, listing_id: u32) -> bool {
+        let sale_mapper = self.protected_sales(listing_id);
+        if sale_mapper.is_empty() {
+            return false;
+        }
+        let status = sale_mapper.get().status;
+        status == ProtectedSaleStatus::Escrowed || status == ProtectedSaleStatus::Disputed
+    }
+
+    // Events
+    #[event("listingCleanedUp")]
+    fn listing_cleaned_up_event(&self, #[indexed] listing_id: u32, #[indexed] seller: &ManagedAddress);
+
+    #[event("auctionCleanedUp")]
+    fn auction_cleaned_up_event(&self, #[indexed] auction_id: u32, #[indexed] seller: &ManagedAddress);
+}
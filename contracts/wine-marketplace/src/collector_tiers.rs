@@ -0,0 +1,142 @@
+use multiversx_sc::derive_imports::*;
+use multiversx_sc::imports::*;
+use wine_common::errors::*;
+use wine_roles::Role;
+
+#[derive(
+    TopEncode, TopDecode, NestedEncode, NestedDecode, TypeAbi, PartialEq, Eq, PartialOrd, Ord, Debug, Clone, Copy,
+)]
+pub enum CollectorTier {
+    None,
+    Bronze,
+    Silver,
+    Gold,
+}
+
+// Counters updated on every settled marketplace sale. Volume only counts EGLD purchases;
+// holdings are bottles bought here minus bottles sold here.
+#[derive(TopEncode, TopDecode, TypeAbi, PartialEq, Debug)]
+pub struct CollectorStats<M: ManagedTypeApi> {
+    pub purchase_count: u32,
+    pub purchase_volume: BigUint<M>,
+    pub holdings: u32,
+}
+
+// A tier is reached when both the volume and the holdings threshold are met. Any verified
+// purchase makes a collector Bronze.
+#[derive(TopEncode, TopDecode, TypeAbi, PartialEq, Debug)]
+pub struct CollectorTierThresholds<M: ManagedTypeApi> {
+    pub silver_volume: BigUint<M>,
+    pub silver_holdings: u32,
+    pub gold_volume: BigUint<M>,
+    pub gold_holdings: u32,
+}
+
+#[multiversx_sc::module]
+pub trait CollectorTiersModule: wine_roles::RolesModule {
+    #[endpoint(setCollectorTierThresholds)]
+    fn set_collector_tier_thresholds(
+        &self,
+        silver_volume: BigUint,
+        silver_holdings: u32,
+        gold_volume: BigUint,
+        gold_holdings: u32,
+    ) {
+        self.require_role(Role::Admin);
+        require!(
+            gold_volume >= silver_volume && gold_holdings >= silver_holdings,
+            ERR_GOLD_THRESHOLDS_MUST_NOT_BE_BELOW_SILVER
+        );
+
+        self.collector_tier_thresholds().set(&CollectorTierThresholds {
+            silver_volume,
+            silver_holdings,
+            gold_volume,
+            gold_holdings,
+        });
+    }
+
+    // Internal helpers
+    fn record_collector_purchase(
+        &self,
+        buyer: &ManagedAddress,
+        payment_token: &EgldOrEsdtTokenIdentifier,
+        amount: &BigUint,
+    ) {
+        let mut stats = self.get_collector_stats(buyer);
+        stats.purchase_count += 1;
+        stats.holdings += 1;
+        if payment_token.is_egld() {
+            stats.purchase_volume += amount;
+        }
+        self.collector_stats(buyer).set(&stats);
+    }
+
+    fn record_collector_sale(&self, seller: &ManagedAddress) {
+        if self.collector_stats(seller).is_empty() {
+            return;
+        }
+        self.collector_stats(seller).update(|stats| {
+            stats.holdings = stats.holdings.saturating_sub(1);
+        });
+    }
+
+    // For modules gating on collector status (allocations, fee tiers, club access)
+    fn collector_tier(&self, collector: &ManagedAddress) -> CollectorTier {
+        let stats = self.get_collector_stats(collector);
+        if stats.purchase_count == 0 {
+            return CollectorTier::None;
+        }
+        if self.collector_tier_thresholds().is_empty() {
+            return CollectorTier::Bronze;
+        }
+
+        let thresholds = self.collector_tier_thresholds().get();
+        if stats.purchase_volume >= thresholds.gold_volume
+            && stats.holdings >= thresholds.gold_holdings
+        {
+            CollectorTier::Gold
+        } else if stats.purchase_volume >= thresholds.silver_volume
+            && stats.holdings >= thresholds.silver_holdings
+        {
+            CollectorTier::Silver
+        } else {
+            CollectorTier::Bronze
+        }
+    }
+
+    // View functions
+    #[view(getCollectorTier)]
+    fn get_collector_tier(&self, collector: &ManagedAddress) -> CollectorTier {
+        self.collector_tier(collector)
+    }
+
+    #[view(getCollectorStats)]
+    fn get_collector_stats(&self, collector: &ManagedAddress) -> CollectorStats<Self::Api> {
+        if self.collector_stats(collector).is_empty() {
+            CollectorStats {
+                purchase_count: 0,
+                purchase_volume: BigUint::zero(),
+                holdings: 0,
+            }
+        } else {
+            self.collector_stats(collector).get()
+        }
+    }
+
+    #[view(getCollectorTierThresholds)]
+    fn get_collector_tier_thresholds(&self) -> OptionalValue<CollectorTierThresholds<Self::Api>> {
+        if self.collector_tier_thresholds().is_empty() {
+            OptionalValue::None
+        } else {
+            OptionalValue::Some(self.collector_tier_thresholds().get())
+        }
+    }
+
+    // Storage mappers
+    #[storage_mapper("collectorStats")]
+    fn collector_stats(&self, collector: &ManagedAddress) -> SingleValueMapper<CollectorStats<Self::Api>>;
+
+    #[storage_mapper("collectorTierThresholds")]
+    fn collector_tier_thresholds(&self) -> SingleValueMapper<CollectorTierThresholds<Self::Api>>;
+}
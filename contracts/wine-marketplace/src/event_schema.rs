@@ -0,0 +1,116 @@
+use multiversx_sc::imports::*;
+use wine_common::errors::*;
+use wine_roles::Role;
+
+use crate::auction::Auction;
+use crate::listing::Listing;
+use crate::migration::STORAGE_VERSION;
+
+// Layout version of the emitted events. Bump it whenever an event gains, loses or reorders
+// topics or data fields, so indexers can switch decoders at the eventSchema marker.
+//   1: layouts as of the first versioned release
+pub const EVENT_SCHEMA_VERSION: u32 = 1;
+// Ids per syncListingStates / syncAuctionStates call
+pub const MAX_SYNC_BATCH: usize = 50;
+
+// Lets indexers that start after deployment pick up the current state without replaying the
+// log from genesis. Every deploy and upgrade emits eventSchema with the version all following
+// events are laid out in; the state sync events re-publish stored listings and auctions as a
+// whole and carry that version as their first topic.
+#[multiversx_sc::module]
+pub trait EventSchemaModule:
+    wine_roles::RolesModule
+    + crate::admin::AdminModule
+    + crate::collector_tiers::CollectorTiersModule
+    + crate::loyalty::LoyaltyModule
+    + crate::fee_discounts::FeeDiscountsModule
+    + crate::stats_snapshots::StatsSnapshotsModule
+    + crate::treasury::TreasuryModule
+    + crate::blacklist::BlacklistModule
+    + crate::seller_limits::SellerLimitsModule
+    + crate::registry_cache::RegistryCacheModule
+    + crate::market_summary::MarketSummaryModule
+    + crate::protected_sale::ProtectedSaleModule
+    + crate::exchange_rates::ExchangeRatesModule
+    + crate::listing::ListingModule
+    + crate::installments::InstallmentsModule
+    + crate::migration::MigrationModule
+    + crate::auction_archive::AuctionArchiveModule
+    + crate::auction::AuctionModule
+{
+    // Emits listingStateSync for each stored listing; unknown and cleaned up ids are skipped.
+    // Returns how many listings were synced.
+    #[endpoint(syncListingStates)]
+    fn sync_listing_states(&self, listing_ids: MultiValueEncoded<u32>) -> u32 {
+        self.require_role(Role::Admin);
+        require!(listing_ids.len() <= MAX_SYNC_BATCH, ERR_SYNC_BATCH_TOO_LARGE);
+        // Listings not yet migrated cannot be decoded
+        require!(
+            self.get_storage_version() >= STORAGE_VERSION,
+            ERR_STORAGE_MIGRATION_PENDING
+        );
+
+        let mut synced = 0u32;
+        for listing_id in listing_ids {
+            let listing_mapper = self.listings(listing_id);
+            if listing_mapper.is_empty() {
+                continue;
+            }
+            self.listing_state_sync_event(EVENT_SCHEMA_VERSION, listing_id, &listing_mapper.get());
+            synced += 1;
+        }
+
+        synced
+    }
+
+    // Same as syncListingStates, for English auctions
+    #[endpoint(syncAuctionStates)]
+    fn sync_auction_states(&self, auction_ids: MultiValueEncoded<u32>) -> u32 {
+        self.require_role(Role::Admin);
+        require!(auction_ids.len() <= MAX_SYNC_BATCH, ERR_SYNC_BATCH_TOO_LARGE);
+
+        let mut synced = 0u32;
+        for auction_id in auction_ids {
+            let auction_mapper = self.auctions(auction_id);
+            if auction_mapper.is_empty() {
+                continue;
+            }
+            self.auction_state_sync_event(EVENT_SCHEMA_VERSION, auction_id, &auction_mapper.get());
+            synced += 1;
+        }
+
+        synced
+    }
+
+    // Internal helpers
+    // Called on deploy and upgrade
+    fn announce_event_schema(&self) {
+        self.event_schema_event(EVENT_SCHEMA_VERSION);
+    }
+
+    // View functions
+    #[view(getEventSchemaVersion)]
+    fn get_event_schema_version(&self) -> u32 {
+        EVENT_SCHEMA_VERSION
+    }
+
+    // Events
+    #[event("eventSchema")]
+    fn event_schema_event(&self, #[indexed] version: u32);
+
+    #[event("listingStateSync")]
+    fn listing_state_sync_event(
+        &self,
+        #[indexed] schema_version: u32,
+        #[indexed] listing_id: u32,
+        listing: &Listing<Self::Api>,
+    );
+
+    #[event("auctionStateSync")]
+    fn auction_state_sync_event(
+        &self,
+        #[indexed] schema_version: u32,
+        #[indexed] auction_id: u32,
+        auction: &Auction<Self::Api>,
+    );
+}
@@ -0,0 +1,172 @@
+use multiversx_sc::derive_imports::*;
+use multiversx_sc::imports::*;
+use wine_common::errors::*;
+use wine_roles::Role;
+
+// Rates are fixed point with 18 decimals: 1 unit of the base token = rate / 10^18 quote units
+pub const RATE_PRECISION: u64 = 1_000_000_000_000_000_000;
+
+#[derive(TopEncode, TopDecode, TypeAbi, PartialEq, Debug)]
+pub struct ExchangeRate<M: ManagedTypeApi> {
+    pub rate: BigUint<M>,
+    pub updated_timestamp: u64,
+}
+
+// Exchange rates between supported payment tokens, maintained by the owner or a rate
+// oracle, such as the wine-oracle contract pushing its medians. Listings priced in one token
+// use them to accept payment in others.
+#[multiversx_sc::module]
+pub trait ExchangeRatesModule: wine_roles::RolesModule + crate::admin::AdminModule {
+    #[endpoint(setRateOracle)]
+    fn set_rate_oracle(&self, oracle: ManagedAddress) {
+        self.require_role(Role::Admin);
+        self.rate_oracle().set(&oracle);
+    }
+
+    // Rates older than `max_rate_age` seconds are rejected at settlement (0 = never stale)
+    #[endpoint(setMaxRateAge)]
+    fn set_max_rate_age(&self, max_rate_age: u64) {
+        self.require_role(Role::Admin);
+        self.max_rate_age().set(max_rate_age);
+    }
+
+    #[endpoint(setExchangeRate)]
+    fn set_exchange_rate(
+        &self,
+        base_token: EgldOrEsdtTokenIdentifier,
+        quote_token: EgldOrEsdtTokenIdentifier,
+        rate: BigUint,
+    ) {
+        self.require_admin_or_rate_oracle();
+        require!(base_token != quote_token, ERR_TOKENS_MUST_DIFFER);
+        require!(
+            self.supported_payment_tokens(&base_token).get() && self.supported_payment_tokens(&quote_token).get(),
+            ERR_PAYMENT_TOKEN_NOT_SUPPORTED
+        );
+        require!(!rate.is_zero(), ERR_RATE_MUST_BE_GREATER_THAN_ZERO);
+
+        let updated_timestamp = self.blockchain().get_block_timestamp();
+        self.exchange_rates(&base_token, &quote_token).set(&ExchangeRate {
+            rate: rate.clone(),
+            updated_timestamp,
+        });
+
+        self.exchange_rate_updated_event(&base_token, &quote_token, &rate);
+    }
+
+    #[endpoint(removeExchangeRate)]
+    fn remove_exchange_rate(&self, base_token: EgldOrEsdtTokenIdentifier, quote_token: EgldOrEsdtTokenIdentifier) {
+        self.require_admin_or_rate_oracle();
+        self.exchange_rates(&base_token, &quote_token).clear();
+    }
+
+    // Internal helpers
+    fn require_admin_or_rate_oracle(&self) {
+        let caller = self.blockchain().get_caller();
+        let is_oracle = !self.rate_oracle().is_empty() && caller == self.rate_oracle().get();
+        require!(
+            self.has_role(Role::Admin, &caller) || is_oracle,
+            ERR_ONLY_ADMIN_OR_RATE_ORACLE_CAN_UPDATE_RATES
+        );
+    }
+
+    // Convert `amount` of `base_token` into `quote_token`, rounding up so the seller is never
+    // paid less than the base price is worth
+    fn convert_amount(
+        &self,
+        base_token: &EgldOrEsdtTokenIdentifier,
+        quote_token: &EgldOrEsdtTokenIdentifier,
+        amount: &BigUint,
+    ) -> BigUint {
+        if base_token == quote_token {
+            return amount.clone();
+        }
+
+        let rate = self.current_exchange_rate(base_token, quote_token);
+        let precision = BigUint::from(RATE_PRECISION);
+        (amount * &rate + &precision - 1u32) / precision
+    }
+
+    // Inverse of `convert_amount`: what `amount` of `quote_token` is worth in `base_token`,
+    // rounded down so a partial payment is never credited above its value
+    fn convert_amount_back(
+        &self,
+        base_token: &EgldOrEsdtTokenIdentifier,
+        quote_token: &EgldOrEsdtTokenIdentifier,
+        amount: &BigUint,
+    ) -> BigUint {
+        if base_token == quote_token {
+            return amount.clone();
+        }
+
+        let rate = self.current_exchange_rate(base_token, quote_token);
+        amount * RATE_PRECISION / rate
+    }
+
+    fn current_exchange_rate(
+        &self,
+        base_token: &EgldOrEsdtTokenIdentifier,
+        quote_token: &EgldOrEsdtTokenIdentifier,
+    ) -> BigUint {
+        let rate_mapper = self.exchange_rates(base_token, quote_token);
+        require!(!rate_mapper.is_empty(), ERR_NO_EXCHANGE_RATE_FOR_PAYMENT_TOKEN);
+        let exchange_rate = rate_mapper.get();
+
+        let max_rate_age = self.max_rate_age().get();
+        require!(
+            max_rate_age == 0
+                || self.blockchain().get_block_timestamp() <= exchange_rate.updated_timestamp + max_rate_age,
+            ERR_EXCHANGE_RATE_IS_STALE
+        );
+
+        exchange_rate.rate
+    }
+
+    // View functions
+    #[view(getExchangeRate)]
+    fn get_exchange_rate(
+        &self,
+        base_token: EgldOrEsdtTokenIdentifier,
+        quote_token: EgldOrEsdtTokenIdentifier,
+    ) -> OptionalValue<ExchangeRate<Self::Api>> {
+        let rate_mapper = self.exchange_rates(&base_token, &quote_token);
+        if rate_mapper.is_empty() {
+            OptionalValue::None
+        } else {
+            OptionalValue::Some(rate_mapper.get())
+        }
+    }
+
+    #[view(getRateOracle)]
+    fn get_rate_oracle(&self) -> ManagedAddress {
+        self.rate_oracle().get()
+    }
+
+    #[view(getMaxRateAge)]
+    fn get_max_rate_age(&self) -> u64 {
+        self.max_rate_age().get()
+    }
+
+    // Storage mappers
+    #[storage_mapper("exchangeRates")]
+    fn exchange_rates(
+        &self,
+        base_token: &EgldOrEsdtTokenIdentifier,
+        quote_token: &EgldOrEsdtTokenIdentifier,
+    ) -> SingleValueMapper<ExchangeRate<Self::Api>>;
+
+    #[storage_mapper("rateOracle")]
+    fn rate_oracle(&self) -> SingleValueMapper<ManagedAddress>;
+
+    #[storage_mapper("maxRateAge")]
+    fn max_rate_age(&self) -> SingleValueMapper<u64>;
+
+    // Events
+    #[event("exchangeRateUpdated")]
+    fn exchange_rate_updated_event(
+        &self,
+        #[indexed] base_token: &EgldOrEsdtTokenIdentifier,
+        #[indexed] quote_token: &EgldOrEsdtTokenIdentifier,
+        rate: &BigUint,
+    );
+}
@@ -0,0 +1,124 @@
+use multiversx_sc::derive_imports::*;
+use multiversx_sc::imports::*;
+use wine_common::errors::*;
+use wine_roles::Role;
+pub use wine_common::MAX_FEE_DISCOUNT_BPS;
+
+// A one-off reduction of the marketplace fee on the account's next sale as seller
+#[derive(TopEncode, TopDecode, TypeAbi, PartialEq, Debug)]
+pub struct FeeDiscount {
+    pub discount_bps: u64,
+    pub expiry_timestamp: u64,
+}
+
+// Fee discounts granted by trusted contracts, e.g. wine-tastings for checked-in attendees.
+// An account holds at most one discount; a new grant replaces it.
+#[multiversx_sc::module]
+pub trait FeeDiscountsModule: wine_roles::RolesModule {
+    #[endpoint(addFeeDiscountIssuer)]
+    fn add_fee_discount_issuer(&self, issuer: ManagedAddress) {
+        self.require_role(Role::Admin);
+        require!(self.fee_discount_issuers().insert(issuer), ERR_ALREADY_A_FEE_DISCOUNT_ISSUER);
+    }
+
+    #[endpoint(removeFeeDiscountIssuer)]
+    fn remove_fee_discount_issuer(&self, issuer: ManagedAddress) {
+        self.require_role(Role::Admin);
+        require!(self.fee_discount_issuers().swap_remove(&issuer), ERR_NOT_A_FEE_DISCOUNT_ISSUER);
+    }
+
+    #[endpoint(grantFeeDiscount)]
+    fn grant_fee_discount(&self, account: ManagedAddress, discount_bps: u64, expiry_timestamp: u64) {
+        let issuer = self.blockchain().get_caller();
+        require!(self.fee_discount_issuers().contains(&issuer), ERR_NOT_A_FEE_DISCOUNT_ISSUER);
+        require!(
+            discount_bps > 0 && discount_bps <= MAX_FEE_DISCOUNT_BPS,
+            ERR_INVALID_FEE_DISCOUNT
+        );
+        require!(
+            expiry_timestamp > self.blockchain().get_block_timestamp(),
+            ERR_EXPIRY_MUST_BE_IN_THE_FUTURE
+        );
+        self.fee_discounts(&account).set(&FeeDiscount {
+            discount_bps,
+            expiry_timestamp,
+        });
+
+        self.fee_discount_granted_event(&account, &issuer, discount_bps, expiry_timestamp);
+    }
+
+    // Internal helpers
+    // Applies and uses up the seller's discount, if it has not expired
+    fn apply_fee_discount(&self, seller: &ManagedAddress, fee: BigUint) -> BigUint {
+        if fee == 0 {
+            return fee;
+        }
+        let discount_bps = self.take_fee_discount_bps(seller);
+        &fee * (10_000 - discount_bps) / 10_000u64
+    }
+
+    // Uses up the seller's discount, 0 if there is none or it has expired. For sales paid in
+    // several tokens, where one discount covers the fee of every part.
+    fn take_fee_discount_bps(&self, seller: &ManagedAddress) -> u64 {
+        let discount_mapper = self.fee_discounts(seller);
+        if discount_mapper.is_empty() {
+            return 0;
+        }
+        let discount = discount_mapper.take();
+        if self.blockchain().get_block_timestamp() > discount.expiry_timestamp {
+            return 0;
+        }
+
+        self.fee_discount_used_event(seller, discount.discount_bps);
+        discount.discount_bps
+    }
+
+    // Discount the seller's next sale would get, without using it up
+    fn pending_fee_discount_bps(&self, seller: &ManagedAddress) -> u64 {
+        let discount_mapper = self.fee_discounts(seller);
+        if discount_mapper.is_empty() {
+            return 0;
+        }
+        let discount = discount_mapper.get();
+        if self.blockchain().get_block_timestamp() > discount.expiry_timestamp {
+            return 0;
+        }
+
+        discount.discount_bps
+    }
+
+    // View functions
+    #[view(getFeeDiscount)]
+    fn get_fee_discount(&self, account: ManagedAddress) -> OptionalValue<FeeDiscount> {
+        if self.fee_discounts(&account).is_empty() {
+            OptionalValue::None
+        } else {
+            OptionalValue::Some(self.fee_discounts(&account).get())
+        }
+    }
+
+    #[view(getFeeDiscountIssuers)]
+    fn get_fee_discount_issuers(&self) -> MultiValueEncoded<ManagedAddress> {
+        self.fee_discount_issuers().iter().collect()
+    }
+
+    // Storage mappers
+    #[storage_mapper("feeDiscountIssuers")]
+    fn fee_discount_issuers(&self) -> UnorderedSetMapper<ManagedAddress>;
+
+    #[storage_mapper("feeDiscounts")]
+    fn fee_discounts(&self, account: &ManagedAddress) -> SingleValueMapper<FeeDiscount>;
+
+    // Events
+    #[event("feeDiscountGranted")]
+    fn fee_discount_granted_event(
+        &self,
+        #[indexed] account: &ManagedAddress,
+        #[indexed] issuer: &ManagedAddress,
+        discount_bps: u64,
+        expiry_timestamp: u64,
+    );
+
+    #[event("feeDiscountUsed")]
+    fn fee_discount_used_event(&self, #[indexed] account: &ManagedAddress, discount_bps: u64);
+}
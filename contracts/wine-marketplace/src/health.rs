@@ -0,0 +1,140 @@
+use multiversx_sc::derive_imports::*;
+use multiversx_sc::imports::*;
+
+use crate::migration::STORAGE_VERSION;
+use crate::protected_sale::ProtectedSaleStatus;
+
+#[derive(TopEncode, TopDecode, TypeAbi, PartialEq, Debug)]
+pub struct MarketplaceHealth<M: ManagedTypeApi> {
+    pub contract_version: ManagedBuffer<M>,
+    pub storage_version: u32,
+    pub migration_pending: bool,
+    pub paused: bool,
+    pub migration_cursor: u32,
+    // Items a keeper (or anyone) can process now
+    pub expired_listings: u32,
+    pub unsettled_auctions: u32,
+    pub unsettled_candle_auctions: u32,
+    pub releasable_protected_sales: u32,
+    pub defaulted_installments: u32,
+    // Configured addresses; fees are paid to the owner
+    pub wine_registry: ManagedAddress<M>,
+    // Fee router when one is set, the fee recipient otherwise
+    pub treasury: ManagedAddress<M>,
+    pub rate_oracle: Option<ManagedAddress<M>>,
+    pub arbiter: Option<ManagedAddress<M>>,
+}
+
+#[multiversx_sc::module]
+pub trait HealthModule:
+    wine_roles::RolesModule
+    + crate::admin::AdminModule
+    + crate::collector_tiers::CollectorTiersModule
+    + crate::loyalty::LoyaltyModule
+    + crate::fee_discounts::FeeDiscountsModule
+    + crate::stats_snapshots::StatsSnapshotsModule
+    + crate::treasury::TreasuryModule
+    + crate::blacklist::BlacklistModule
+    + crate::seller_limits::SellerLimitsModule
+    + crate::registry_cache::RegistryCacheModule
+    + crate::market_summary::MarketSummaryModule
+    + crate::protected_sale::ProtectedSaleModule
+    + crate::exchange_rates::ExchangeRatesModule
+    + crate::listing::ListingModule
+    + crate::installments::InstallmentsModule
+    + crate::migration::MigrationModule
+    + crate::auction_archive::AuctionArchiveModule
+    + crate::auction::AuctionModule
+    + crate::candle_auction::CandleAuctionModule
+    + wine_randomness::RandomnessModule
+{
+    // Status snapshot for monitoring. Scans every listing and auction, so it is meant to be
+    // queried off-chain rather than called from other contracts.
+    #[view(getHealth)]
+    fn get_health(&self) -> MarketplaceHealth<Self::Api> {
+        let current_timestamp = self.blockchain().get_block_timestamp();
+        let storage_version = self.get_storage_version();
+        let migration_pending = storage_version < STORAGE_VERSION;
+
+        let mut expired_listings = 0u32;
+        let mut releasable_protected_sales = 0u32;
+        let mut defaulted_installments = 0u32;
+        // Listings not yet migrated cannot be decoded, so they are only counted afterwards
+        if !migration_pending {
+            for listing_id in 1..self.listing_counter().get() {
+                // Cleaned up entries have nothing pending
+                if self.listings(listing_id).is_empty() {
+                    continue;
+                }
+                let listing = self.listings(listing_id).get();
+                if listing.active && current_timestamp > listing.deadline {
+                    expired_listings += 1;
+                }
+
+                let sale_mapper = self.protected_sales(listing_id);
+                if !sale_mapper.is_empty() {
+                    let sale = sale_mapper.get();
+                    if sale.status == ProtectedSaleStatus::Escrowed
+                        && current_timestamp >= sale.release_timestamp
+                    {
+                        releasable_protected_sales += 1;
+                    }
+                }
+
+                let plan_mapper = self.installment_plans(listing_id);
+                if !plan_mapper.is_empty() && current_timestamp > plan_mapper.get().deadline {
+                    defaulted_installments += 1;
+                }
+            }
+        }
+
+        let mut unsettled_auctions = 0u32;
+        for auction_id in 1..self.auction_counter().get() {
+            if self.auctions(auction_id).is_empty() {
+                continue;
+            }
+            let auction = self.auctions(auction_id).get();
+            if auction.active && current_timestamp >= auction.end_timestamp {
+                unsettled_auctions += 1;
+            }
+        }
+
+        let mut unsettled_candle_auctions = 0u32;
+        for auction_id in 1..self.candle_auction_counter().get() {
+            let auction = self.candle_auctions(auction_id).get();
+            if auction.active && current_timestamp >= auction.end_timestamp {
+                unsettled_candle_auctions += 1;
+            }
+        }
+
+        MarketplaceHealth {
+            contract_version: ManagedBuffer::from(env!("CARGO_PKG_VERSION").as_bytes()),
+            storage_version,
+            migration_pending,
+            paused: self.is_paused(),
+            migration_cursor: self.migration_cursor().get(),
+            expired_listings,
+            unsettled_auctions,
+            unsettled_candle_auctions,
+            releasable_protected_sales,
+            defaulted_installments,
+            wine_registry: self.wine_registry_address().get(),
+            treasury: if self.fee_router().is_empty() {
+                self.fee_recipient_address()
+            } else {
+                self.fee_router().get()
+            },
+            rate_oracle: self.optional_address(&self.rate_oracle()),
+            arbiter: self.optional_address(&self.arbiter()),
+        }
+    }
+
+    // Internal helpers
+    fn optional_address(&self, mapper: &SingleValueMapper<ManagedAddress>) -> Option<ManagedAddress> {
+        if mapper.is_empty() {
+            None
+        } else {
+            Some(mapper.get())
+        }
+    }
+}
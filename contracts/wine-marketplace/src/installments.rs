@@ -0,0 +1,266 @@
+use multiversx_sc::derive_imports::*;
+use multiversx_sc::imports::*;
+use wine_common::errors::*;
+use wine_roles::Role;
+
+// Upper bound for any payment plan: 90 days
+pub const MAX_INSTALLMENT_DURATION: u64 = 7776000;
+
+#[derive(TopEncode, TopDecode, TypeAbi, PartialEq, Debug)]
+pub struct InstallmentConfig {
+    pub min_deposit_percent: u64, // basis points of the listing price
+    pub penalty_percent: u64,     // basis points of the listing price, forfeited on default
+    pub max_duration: u64,        // seconds between the deposit and the payment deadline
+}
+
+// Lay-away purchase of a listing. The listing is reserved while the plan runs; the NFT
+// stays in escrow until the full price is paid.
+#[derive(TopEncode, TopDecode, TypeAbi, PartialEq, Debug)]
+pub struct InstallmentPlan<M: ManagedTypeApi> {
+    pub buyer: ManagedAddress<M>,
+    pub payment_token: EgldOrEsdtTokenIdentifier<M>,
+    pub total_price: BigUint<M>,
+    pub amount_paid: BigUint<M>,
+    pub created_timestamp: u64,
+    pub deadline: u64,
+}
+
+#[multiversx_sc::module]
+pub trait InstallmentsModule:
+    wine_roles::RolesModule
+    + crate::admin::AdminModule
+    + crate::collector_tiers::CollectorTiersModule
+    + crate::loyalty::LoyaltyModule
+    + crate::fee_discounts::FeeDiscountsModule
+    + crate::stats_snapshots::StatsSnapshotsModule
+    + crate::treasury::TreasuryModule
+    + crate::blacklist::BlacklistModule
+    + crate::seller_limits::SellerLimitsModule
+    + crate::registry_cache::RegistryCacheModule
+    + crate::market_summary::MarketSummaryModule
+    + crate::protected_sale::ProtectedSaleModule
+    + crate::exchange_rates::ExchangeRatesModule
+    + crate::listing::ListingModule
+{
+    #[endpoint(setInstallmentConfig)]
+    fn set_installment_config(&self, min_deposit_percent: u64, penalty_percent: u64, max_duration: u64) {
+        self.require_role(Role::Admin);
+        require!(min_deposit_percent > 0 && min_deposit_percent < 10000, ERR_INVALID_MINIMUM_DEPOSIT);
+        // The deposit always covers the penalty, so a default never needs further funds
+        require!(penalty_percent <= min_deposit_percent, ERR_PENALTY_CANNOT_EXCEED_MINIMUM_DEPOSIT);
+        require!(
+            max_duration > 0 && max_duration <= MAX_INSTALLMENT_DURATION,
+            ERR_INVALID_INSTALLMENT_DURATION
+        );
+
+        self.installment_config().set(&InstallmentConfig {
+            min_deposit_percent,
+            penalty_percent,
+            max_duration,
+        });
+    }
+
+    // Reserve a listing by paying a deposit; the rest is due within `duration_seconds`
+    #[payable("*")]
+    #[endpoint(startInstallmentPurchase)]
+    fn start_installment_purchase(&self, listing_id: u32, duration_seconds: u64) {
+        self.require_not_paused();
+        let mut listing = self.listings(listing_id).get();
+        require!(listing.active, ERR_LISTING_IS_NOT_ACTIVE);
+        require!(!listing.protected, ERR_INSTALLMENTS_ARE_NOT_AVAILABLE_FOR_PROTECTED_LISTINGS);
+
+        let current_timestamp = self.blockchain().get_block_timestamp();
+        require!(current_timestamp <= listing.deadline, ERR_LISTING_HAS_EXPIRED);
+
+        let config = self.installment_config().get();
+        require!(
+            duration_seconds > 0 && duration_seconds <= config.max_duration,
+            ERR_INVALID_INSTALLMENT_DURATION
+        );
+
+        let payment_amount = self.require_fungible_payment(&listing.payment_token);
+        let min_deposit = &listing.price * config.min_deposit_percent / 10000u64;
+        require!(payment_amount >= min_deposit, ERR_DEPOSIT_TOO_LOW);
+        require!(payment_amount < listing.price, ERR_DEPOSIT_COVERS_THE_FULL_PRICE_USE_BUYWINE);
+
+        let buyer = self.blockchain().get_caller();
+        self.require_not_blacklisted(&buyer);
+        require!(buyer != listing.seller, ERR_CANNOT_BUY_YOUR_OWN_LISTING);
+
+        let plan = InstallmentPlan {
+            buyer: buyer.clone(),
+            payment_token: listing.payment_token.clone(),
+            total_price: listing.price.clone(),
+            amount_paid: payment_amount.clone(),
+            created_timestamp: current_timestamp,
+            deadline: current_timestamp + duration_seconds,
+        };
+        self.installment_plans(listing_id).set(&plan);
+
+        // Reserved listings cannot be bought or cancelled
+        listing.active = false;
+        self.listings(listing_id).set(&listing);
+        self.active_wine_listings(listing.wine_nft_id).swap_remove(&listing_id);
+        self.untrack_ask(listing.wine_nft_id, &listing.payment_token, listing_id);
+        self.update_marketplace_stats(|stats| {
+            stats.active_listings -= 1;
+        });
+
+        self.installment_purchase_started_event(listing_id, &buyer, plan.deadline, &payment_amount);
+    }
+
+    // Pay towards an open plan; the final payment completes the purchase
+    #[payable("*")]
+    #[endpoint(payInstallment)]
+    fn pay_installment(&self, listing_id: u32) {
+        let mut plan = self.require_installment_plan(listing_id);
+
+        let buyer = self.blockchain().get_caller();
+        require!(buyer == plan.buyer, ERR_ONLY_THE_PLAN_BUYER_CAN_PAY_INSTALLMENTS);
+        self.require_not_blacklisted(&buyer);
+        require!(
+            self.blockchain().get_block_timestamp() <= plan.deadline,
+            ERR_INSTALLMENT_DEADLINE_HAS_PASSED
+        );
+
+        let payment_amount = self.require_fungible_payment(&plan.payment_token);
+        require!(payment_amount > 0u32, ERR_PAYMENT_MUST_BE_GREATER_THAN_ZERO);
+
+        plan.amount_paid += &payment_amount;
+        self.installment_paid_event(listing_id, &buyer, &payment_amount, &plan.amount_paid);
+
+        if plan.amount_paid < plan.total_price {
+            self.installment_plans(listing_id).set(&plan);
+            return;
+        }
+
+        // Fully paid: release the NFT, settle with the seller and return any surplus. The
+        // listing kept its seller slot while reserved.
+        let listing = self.listings(listing_id).get();
+        self.installment_plans(listing_id).clear();
+        self.close_seller_item(&listing.seller);
+        self.unlock_wine_in_registry(listing.wine_nft_id, &listing.seller);
+        self.record_verified_buyer_in_registry(listing.wine_nft_id, &buyer);
+
+        self.send_nft(&buyer, &listing.nft_token_id, listing.nft_nonce);
+        let mut settlement = self.pay_out_sale(
+            &listing.seller,
+            &buyer,
+            &listing.nft_token_id,
+            listing.nft_nonce,
+            &plan.payment_token,
+            &plan.total_price,
+        );
+
+        settlement.surplus_refunded = &plan.amount_paid - &plan.total_price;
+        self.send_payment(&buyer, &plan.payment_token, &settlement.surplus_refunded);
+        self.record_wine_sale(listing.wine_nft_id, &plan.payment_token, &plan.total_price);
+
+        self.installment_purchase_completed_event(listing_id, &buyer);
+        self.wine_sold_event(
+            listing_id,
+            listing.wine_nft_id,
+            &listing.seller,
+            &buyer,
+            &buyer,
+            &settlement
+        );
+    }
+
+    // Close a plan whose deadline passed unpaid: the seller keeps the penalty, the buyer
+    // gets the rest back and the listing is reopened. Callable by anyone.
+    #[endpoint(defaultInstallment)]
+    fn default_installment(&self, listing_id: u32) {
+        let plan = self.require_installment_plan(listing_id);
+        require!(
+            self.blockchain().get_block_timestamp() > plan.deadline,
+            ERR_INSTALLMENT_DEADLINE_HAS_NOT_PASSED
+        );
+
+        let mut listing = self.listings(listing_id).get();
+        self.installment_plans(listing_id).clear();
+
+        let config = self.installment_config().get();
+        let mut penalty = &plan.total_price * config.penalty_percent / 10000u64;
+        if penalty > plan.amount_paid {
+            penalty = plan.amount_paid.clone();
+        }
+        let refund = &plan.amount_paid - &penalty;
+
+        self.send_payment(&listing.seller, &plan.payment_token, &penalty);
+        self.send_payment(&plan.buyer, &plan.payment_token, &refund);
+
+        // NFT is still in escrow, so the listing simply becomes available again
+        listing.active = true;
+        self.listings(listing_id).set(&listing);
+        self.active_wine_listings(listing.wine_nft_id).insert(listing_id);
+        self.track_listing_ask(listing_id, &listing);
+        self.update_marketplace_stats(|stats| {
+            stats.active_listings += 1;
+        });
+
+        self.installment_defaulted_event(listing_id, &plan.buyer, &penalty);
+    }
+
+    // Internal helpers
+    fn require_installment_plan(&self, listing_id: u32) -> InstallmentPlan<Self::Api> {
+        require!(!self.installment_plans(listing_id).is_empty(), ERR_NO_INSTALLMENT_PLAN_FOR_LISTING);
+        self.installment_plans(listing_id).get()
+    }
+
+    // View functions
+    #[view(getInstallmentPlan)]
+    fn get_installment_plan(&self, listing_id: u32) -> OptionalValue<InstallmentPlan<Self::Api>> {
+        if self.installment_plans(listing_id).is_empty() {
+            OptionalValue::None
+        } else {
+            OptionalValue::Some(self.installment_plans(listing_id).get())
+        }
+    }
+
+    #[view(getInstallmentConfig)]
+    fn get_installment_config(&self) -> InstallmentConfig {
+        self.installment_config().get()
+    }
+
+    // Storage mappers
+    #[storage_mapper("installmentPlans")]
+    fn installment_plans(&self, listing_id: u32) -> SingleValueMapper<InstallmentPlan<Self::Api>>;
+
+    #[storage_mapper("installmentConfig")]
+    fn installment_config(&self) -> SingleValueMapper<InstallmentConfig>;
+
+    // Events
+    #[event("installmentPurchaseStarted")]
+    fn installment_purchase_started_event(
+        &self,
+        #[indexed] listing_id: u32,
+        #[indexed] buyer: &ManagedAddress,
+        #[indexed] deadline: u64,
+        deposit: &BigUint,
+    );
+
+    #[event("installmentPaid")]
+    fn installment_paid_event(
+        &self,
+        #[indexed] listing_id: u32,
+        #[indexed] buyer: &ManagedAddress,
+        #[indexed] amount: &BigUint,
+        total_paid: &BigUint,
+    );
+
+    #[event("installmentPurchaseCompleted")]
+    fn installment_purchase_completed_event(
+        &self,
+        #[indexed] listing_id: u32,
+        #[indexed] buyer: &ManagedAddress,
+    );
+
+    #[event("installmentDefaulted")]
+    fn installment_defaulted_event(
+        &self,
+        #[indexed] listing_id: u32,
+        #[indexed] buyer: &ManagedAddress,
+        penalty: &BigUint,
+    );
+}
@@ -0,0 +1,742 @@
+use multiversx_sc::derive_imports::*;
+use multiversx_sc::imports::*;
+use wine_common::errors::*;
+pub use wine_common::Listing;
+
+use crate::market_summary::MarketQuote;
+use crate::pagination::{Page, MAX_IDS_PAGE_SIZE};
+use crate::protected_sale::{ProtectedSale, ProtectedSaleStatus};
+use crate::treasury::SaleSettlement;
+
+// Upper bound on how many times a single listing can be reopened
+pub const MAX_RELIST_COUNT: u32 = 12;
+// Tokens a listing accepts besides the one it is priced in
+pub const MAX_LISTING_PAYMENT_TOKENS: usize = 5;
+// Listings withdrawn per cancelRecalledListings call
+pub const MAX_RECALL_CANCELLATIONS: usize = 20;
+// How EGLD shows up among the ESDTs of a multi-transfer that carries both
+pub const EGLD_000000_TOKEN_IDENTIFIER: &[u8] = b"EGLD-000000";
+
+// Whether a listing takes EGLD and an ESDT together in one purchase, and how the seller
+// receives the proceeds of a purchase paid in several tokens
+#[derive(TopEncode, TopDecode, NestedEncode, NestedDecode, TypeAbi, PartialEq, Eq, Debug, Clone, Copy)]
+pub enum MixedSettlement {
+    Disabled,
+    // Each token is sent on to the seller as it is paid
+    DirectProceeds,
+    // Proceeds are held for the seller until claimSaleProceeds
+    ClaimableProceeds,
+}
+
+#[multiversx_sc::module]
+pub trait ListingModule:
+    wine_roles::RolesModule
+    + crate::admin::AdminModule
+    + crate::collector_tiers::CollectorTiersModule
+    + crate::loyalty::LoyaltyModule
+    + crate::fee_discounts::FeeDiscountsModule
+    + crate::stats_snapshots::StatsSnapshotsModule
+    + crate::treasury::TreasuryModule
+    + crate::blacklist::BlacklistModule
+    + crate::seller_limits::SellerLimitsModule
+    + crate::registry_cache::RegistryCacheModule
+    + crate::market_summary::MarketSummaryModule
+    + crate::protected_sale::ProtectedSaleModule
+    + crate::exchange_rates::ExchangeRatesModule
+{
+    // Create NFT listing with escrow, optionally as a protected sale
+    #[payable("*")]
+    #[endpoint(createListing)]
+    fn create_listing(
+        &self,
+        wine_nft_id: u32,
+        price: BigUint,
+        payment_token: EgldOrEsdtTokenIdentifier,
+        duration_seconds: u64,
+        opt_protected: OptionalValue<bool>,
+    ) -> u32 {
+        self.require_not_paused();
+        // Validate payment token
+        self.require_supported_payment_token(&payment_token);
+        require!(!price.is_zero(), ERR_PRICE_MUST_BE_GREATER_THAN_ZERO);
+        require!(duration_seconds >= 3600, ERR_MINIMUM_LISTING_DURATION_IS_1_HOUR); // 1 hour
+        require!(duration_seconds <= 2592000, ERR_MAXIMUM_LISTING_DURATION_IS_30_DAYS); // 30 days
+        let protected = opt_protected.into_option().unwrap_or(false);
+        if protected {
+            self.require_arbiter_configured();
+        }
+
+        // Receive NFT in escrow; registry gates below apply to the wine it is a bottle of
+        let payment = self.call_value().single_esdt();
+        let nft_token_id = payment.token_identifier.clone();
+        let nft_nonce = payment.token_nonce;
+
+        require!(payment.amount == BigUint::from(1u32), ERR_MUST_SEND_EXACTLY_1_NFT);
+        self.require_escrowed_wine(wine_nft_id, &nft_token_id, nft_nonce);
+
+        let caller = self.blockchain().get_caller();
+        self.require_not_blacklisted(&caller);
+        self.require_wine_listable(wine_nft_id, &caller);
+        self.require_price_floor_met(wine_nft_id, &price, &payment_token);
+        let current_timestamp = self.blockchain().get_block_timestamp();
+        let deadline = current_timestamp + duration_seconds;
+
+        let listing_id = self.listing_counter().get();
+        let listing = Listing {
+            wine_nft_id,
+            nft_token_id: nft_token_id.clone(),
+            nft_nonce,
+            seller: caller.clone(),
+            price,
+            payment_token: payment_token.clone(),
+            deadline,
+            active: true,
+            created_timestamp: current_timestamp,
+            protected,
+        };
+
+        self.listings(listing_id).set(&listing);
+        self.listing_counter().set(listing_id + 1);
+        self.seller_listings(&caller).push(&listing_id);
+        self.active_wine_listings(wine_nft_id).insert(listing_id);
+        self.track_listing_ask(listing_id, &listing);
+
+        // Update stats
+        self.open_seller_item(&caller);
+        self.lock_wine_in_registry(wine_nft_id, &caller);
+        self.update_marketplace_stats(|stats| {
+            stats.total_listings += 1;
+            stats.active_listings += 1;
+        });
+
+        self.wine_listed_event(
+            listing_id,
+            wine_nft_id,
+            &caller,
+            &listing.price,
+            &payment_token
+        );
+
+        listing_id
+    }
+
+    // Buy wine from listing, optionally delivering the NFT to a gift recipient
+    #[payable("*")]
+    #[endpoint(buyWine)]
+    fn buy_wine(&self, listing_id: u32, opt_recipient: OptionalValue<ManagedAddress>) {
+        self.require_not_paused();
+        let listing = self.require_open_listing(listing_id);
+
+        // Several tokens can be combined, e.g. loyalty tokens topped up with a stablecoin, or
+        // EGLD with an ESDT where the listing allows mixed settlement
+        if self.call_value().all_esdt_transfers().len() > 1 {
+            self.buy_wine_with_tokens(listing_id, listing, opt_recipient);
+            return;
+        }
+
+        let payment = self.call_value().egld_or_single_esdt();
+        let buyer = self.blockchain().get_caller();
+        self.buy_listing(listing_id, listing, &buyer, payment, opt_recipient);
+    }
+
+    // Cancel listing, or take back the NFT of an expired one (only seller)
+    #[endpoint(cancelListing)]
+    fn cancel_listing(&self, listing_id: u32) {
+        let mut listing = self.listings(listing_id).get();
+        require!(listing.active, ERR_LISTING_IS_NOT_ACTIVE);
+
+        let caller = self.blockchain().get_caller();
+        require!(caller == listing.seller, ERR_ONLY_SELLER_CAN_CANCEL_LISTING);
+
+        self.withdraw_listing(listing_id, &mut listing);
+
+        // Taking back an expired listing is reported separately from a cancellation
+        if self.blockchain().get_block_timestamp() > listing.deadline {
+            self.listing_expired_event(listing_id, &listing.seller, &listing.nft_token_id, listing.nft_nonce);
+        } else {
+            self.listing_cancelled_event(listing_id);
+        }
+    }
+
+    // Withdraw active listings of a recalled wine, returning the NFTs to their sellers.
+    // Callable by anyone (the registry triggers it on recall); returns how many remain.
+    #[endpoint(cancelRecalledListings)]
+    fn cancel_recalled_listings(&self, wine_id: u32) -> usize {
+        require!(self.is_wine_recalled(wine_id), ERR_WINE_HAS_NOT_BEEN_RECALLED);
+
+        let mut cancelled = 0;
+        while cancelled < MAX_RECALL_CANCELLATIONS && !self.active_wine_listings(wine_id).is_empty() {
+            let listing_id = self.active_wine_listings(wine_id).get_by_index(1);
+            let mut listing = self.listings(listing_id).get();
+            self.withdraw_listing(listing_id, &mut listing);
+            self.recalled_listing_cancelled_event(listing_id, wine_id, &listing.seller);
+            cancelled += 1;
+        }
+
+        self.active_wine_listings(wine_id).len()
+    }
+
+    // Accept payment in further supported tokens, converted from the listing price at the
+    // current exchange rate
+    #[endpoint(addListingPaymentTokens)]
+    fn add_listing_payment_tokens(&self, listing_id: u32, tokens: MultiValueEncoded<EgldOrEsdtTokenIdentifier>) {
+        let listing = self.listings(listing_id).get();
+        require!(listing.active, ERR_LISTING_IS_NOT_ACTIVE);
+
+        let caller = self.blockchain().get_caller();
+        require!(caller == listing.seller, ERR_ONLY_SELLER_CAN_CHANGE_PAYMENT_TOKENS);
+
+        let mut accepted_tokens = self.listing_payment_tokens(listing_id);
+        for token in tokens {
+            self.require_supported_payment_token(&token);
+            require!(token != listing.payment_token, ERR_LISTING_IS_ALREADY_PRICED_IN_THIS_TOKEN);
+            require!(
+                !self.exchange_rates(&listing.payment_token, &token).is_empty(),
+                ERR_NO_EXCHANGE_RATE_FOR_PAYMENT_TOKEN
+            );
+            accepted_tokens.insert(token);
+        }
+        require!(
+            accepted_tokens.len() <= MAX_LISTING_PAYMENT_TOKENS,
+            ERR_TOO_MANY_PAYMENT_TOKENS
+        );
+
+        self.listing_payment_tokens_updated_event(listing_id, accepted_tokens.len());
+    }
+
+    #[endpoint(removeListingPaymentTokens)]
+    fn remove_listing_payment_tokens(&self, listing_id: u32, tokens: MultiValueEncoded<EgldOrEsdtTokenIdentifier>) {
+        let listing = self.listings(listing_id).get();
+        let caller = self.blockchain().get_caller();
+        require!(caller == listing.seller, ERR_ONLY_SELLER_CAN_CHANGE_PAYMENT_TOKENS);
+
+        let mut accepted_tokens = self.listing_payment_tokens(listing_id);
+        for token in tokens {
+            accepted_tokens.swap_remove(&token);
+        }
+
+        self.listing_payment_tokens_updated_event(listing_id, accepted_tokens.len());
+    }
+
+    // Let buyers pay this listing in EGLD and an ESDT at once, at the current exchange
+    // rates. Both tokens still have to be accepted by the listing.
+    #[endpoint(setMixedSettlement)]
+    fn set_mixed_settlement(&self, listing_id: u32, mixed_settlement: MixedSettlement) {
+        let listing = self.listings(listing_id).get();
+        require!(listing.active, ERR_LISTING_IS_NOT_ACTIVE);
+
+        let caller = self.blockchain().get_caller();
+        require!(caller == listing.seller, ERR_ONLY_SELLER_CAN_SET_MIXED_SETTLEMENT);
+
+        self.mixed_settlement(listing_id).set(mixed_settlement);
+        self.mixed_settlement_set_event(listing_id, mixed_settlement);
+    }
+
+    // Internal helpers
+    fn require_open_listing(&self, listing_id: u32) -> Listing<Self::Api> {
+        let listing = self.listings(listing_id).get();
+        require!(listing.active, ERR_LISTING_IS_NOT_ACTIVE);
+        require!(
+            self.blockchain().get_block_timestamp() <= listing.deadline,
+            ERR_LISTING_HAS_EXPIRED
+        );
+        listing
+    }
+
+    // Buy with a single payment, made by `buyer` directly or on their behalf by a relayer
+    fn buy_listing(
+        &self,
+        listing_id: u32,
+        mut listing: Listing<Self::Api>,
+        buyer: &ManagedAddress,
+        payment: EgldOrEsdtTokenPayment,
+        opt_recipient: OptionalValue<ManagedAddress>,
+    ) {
+        // The price converts into any other token the listing accepts
+        let price = self.listing_price_in(listing_id, &listing, &payment.token_identifier);
+        self.require_fungible(&payment);
+        require!(payment.amount >= price, ERR_INSUFFICIENT_PAYMENT);
+
+        self.require_not_blacklisted(buyer);
+        require!(*buyer != listing.seller, ERR_CANNOT_BUY_YOUR_OWN_LISTING);
+        let recipient = self.resolve_recipient(buyer, opt_recipient);
+
+        let mut settlement;
+        if listing.protected {
+            // Keep NFT and payment in escrow until the dispute window passes
+            let current_timestamp = self.blockchain().get_block_timestamp();
+            let sale = ProtectedSale {
+                wine_nft_id: listing.wine_nft_id,
+                nft_token_id: listing.nft_token_id.clone(),
+                nft_nonce: listing.nft_nonce,
+                seller: listing.seller.clone(),
+                buyer: buyer.clone(),
+                recipient: recipient.clone(),
+                payment_token: payment.token_identifier.clone(),
+                amount: price.clone(),
+                purchase_timestamp: current_timestamp,
+                release_timestamp: current_timestamp + self.dispute_window().get(),
+                status: ProtectedSaleStatus::Escrowed,
+            };
+            self.hold_protected_sale(listing_id, sale);
+            settlement = self.unsettled_sale(&payment.token_identifier, &price);
+        } else {
+            // Transfer NFT to buyer (or gift recipient)
+            self.send_nft(&recipient, &listing.nft_token_id, listing.nft_nonce);
+
+            // Pay seller and collect marketplace fee
+            settlement = self.pay_out_sale(
+                &listing.seller,
+                buyer,
+                &listing.nft_token_id,
+                listing.nft_nonce,
+                &payment.token_identifier,
+                &price,
+            );
+            self.accrue_loyalty_rewards(buyer, &payment.token_identifier, &price);
+        }
+
+        // Return surplus if any
+        settlement.surplus_refunded = &payment.amount - &price;
+        self.send_payment(buyer, &payment.token_identifier, &settlement.surplus_refunded);
+
+        self.close_sold_listing(listing_id, &mut listing, buyer, &recipient, &settlement);
+    }
+
+    // Floors are EGLD prices; a listing in another token must be worth at least the floor
+    // at the current exchange rate. While enforced, a wine the registry has not pushed cannot
+    // be shown to be above its floor.
+    fn require_price_floor_met(
+        &self,
+        wine_id: u32,
+        price: &BigUint,
+        payment_token: &EgldOrEsdtTokenIdentifier,
+    ) {
+        if !self.enforce_price_floors().get() {
+            return;
+        }
+        let read_model = self.require_synced_read_model(wine_id);
+        let floor = self.read_model_price_floor(&read_model);
+        if floor == 0u32 {
+            return;
+        }
+
+        let egld = EgldOrEsdtTokenIdentifier::egld();
+        let min_price = self.convert_amount(&egld, payment_token, &floor);
+        require!(*price >= min_price, ERR_PRICE_BELOW_GRADE_FLOOR);
+    }
+
+    fn listing_price_in(
+        &self,
+        listing_id: u32,
+        listing: &Listing<Self::Api>,
+        token: &EgldOrEsdtTokenIdentifier,
+    ) -> BigUint {
+        self.listing_amount_in(listing_id, listing, token, &listing.price)
+    }
+
+    // `amount` of the listing's own token, converted into another token it accepts
+    fn listing_amount_in(
+        &self,
+        listing_id: u32,
+        listing: &Listing<Self::Api>,
+        token: &EgldOrEsdtTokenIdentifier,
+        amount: &BigUint,
+    ) -> BigUint {
+        if *token == listing.payment_token {
+            return amount.clone();
+        }
+        require!(
+            self.listing_payment_tokens(listing_id).contains(token),
+            ERR_INVALID_PAYMENT_TOKEN
+        );
+        self.convert_amount(&listing.payment_token, token, amount)
+    }
+
+    // Payment in several accepted tokens, applied in the order sent: each part pays as much
+    // of the remaining price as it covers and the rest of it is returned in the same token.
+    // One fee rate and seller discount apply to all parts, the sale counts once in the stats.
+    fn buy_wine_with_tokens(
+        &self,
+        listing_id: u32,
+        mut listing: Listing<Self::Api>,
+        opt_recipient: OptionalValue<ManagedAddress>,
+    ) {
+        require!(!listing.protected, ERR_PROTECTED_LISTINGS_TAKE_A_SINGLE_PAYMENT_TOKEN);
+        let payments = self.call_value().all_esdt_transfers().clone_value();
+        let mixed_settlement = self.mixed_settlement(listing_id).get();
+        require!(
+            mixed_settlement != MixedSettlement::Disabled
+                || payments.iter().all(|payment| !self.is_egld_transfer(&payment)),
+            ERR_MIXED_SETTLEMENT_NOT_ALLOWED
+        );
+
+        let buyer = self.blockchain().get_caller();
+        self.require_not_blacklisted(&buyer);
+        require!(buyer != listing.seller, ERR_CANNOT_BUY_YOUR_OWN_LISTING);
+        let recipient = self.resolve_recipient(&buyer, opt_recipient);
+
+        self.send_nft(&recipient, &listing.nft_token_id, listing.nft_nonce);
+
+        let fee_percent = self.take_sale_fee_percent(&listing.nft_token_id);
+        let discount_bps = if fee_percent > 0 {
+            self.take_fee_discount_bps(&listing.seller)
+        } else {
+            0
+        };
+        let hold_proceeds = mixed_settlement == MixedSettlement::ClaimableProceeds;
+
+        // Left to pay, in the listing's own token
+        let mut remaining = listing.price.clone();
+        for (index, payment) in payments.iter().enumerate() {
+            require!(
+                payments
+                    .iter()
+                    .take(index)
+                    .all(|earlier| earlier.token_identifier != payment.token_identifier),
+                ERR_DUPLICATE_PAYMENT_TOKEN
+            );
+            let payment = if self.is_egld_transfer(&payment) {
+                EgldOrEsdtTokenPayment::new(EgldOrEsdtTokenIdentifier::egld(), 0, payment.amount)
+            } else {
+                payment.into()
+            };
+            self.pay_listing_part(
+                listing_id,
+                &listing,
+                &buyer,
+                payment,
+                &mut remaining,
+                fee_percent,
+                discount_bps,
+                hold_proceeds,
+            );
+        }
+        require!(remaining == 0, ERR_INSUFFICIENT_PAYMENT);
+
+        // Collector stats count the purchase at the listing price
+        self.record_sale(&listing.seller, &buyer, &listing.payment_token, &listing.price);
+        let settlement = self.unsettled_sale(&listing.payment_token, &listing.price);
+        self.close_sold_listing(listing_id, &mut listing, &buyer, &recipient, &settlement);
+    }
+
+    fn is_egld_transfer(&self, payment: &EsdtTokenPayment) -> bool {
+        payment.token_identifier.as_managed_buffer() == EGLD_000000_TOKEN_IDENTIFIER
+    }
+
+    // One part of a purchase paid in several tokens, taking what it covers off `remaining`
+    fn pay_listing_part(
+        &self,
+        listing_id: u32,
+        listing: &Listing<Self::Api>,
+        buyer: &ManagedAddress,
+        payment: EgldOrEsdtTokenPayment,
+        remaining: &mut BigUint,
+        fee_percent: u64,
+        discount_bps: u64,
+        hold_proceeds: bool,
+    ) {
+        self.require_fungible(&payment);
+        let token = payment.token_identifier;
+
+        let due = self.listing_amount_in(listing_id, listing, &token, remaining);
+        let used = if payment.amount >= due {
+            *remaining = BigUint::zero();
+            due
+        } else {
+            *remaining -= self.convert_amount_back(&listing.payment_token, &token, &payment.amount);
+            payment.amount.clone()
+        };
+
+        let marketplace_fee = self.discounted_fee(&used, fee_percent, discount_bps);
+        let mut settlement = self.pay_out_sale_part(
+            &listing.seller,
+            &listing.nft_token_id,
+            listing.nft_nonce,
+            &token,
+            &used,
+            marketplace_fee,
+            None,
+            hold_proceeds,
+        );
+        self.accrue_loyalty_rewards(buyer, &token, &used);
+
+        settlement.surplus_refunded = &payment.amount - &used;
+        self.send_payment(buyer, &token, &settlement.surplus_refunded);
+        self.sale_payment_part_event(listing_id, &settlement);
+    }
+
+    fn close_sold_listing(
+        &self,
+        listing_id: u32,
+        listing: &mut Listing<Self::Api>,
+        buyer: &ManagedAddress,
+        recipient: &ManagedAddress,
+        settlement: &SaleSettlement<Self::Api>,
+    ) {
+        // Deactivate listing
+        listing.active = false;
+        self.listings(listing_id).set(&*listing);
+        self.active_wine_listings(listing.wine_nft_id).swap_remove(&listing_id);
+        self.untrack_ask(listing.wine_nft_id, &listing.payment_token, listing_id);
+        // Sales are recorded at the listing price, in the token the ask was quoted in
+        self.record_wine_sale(listing.wine_nft_id, &listing.payment_token, &listing.price);
+
+        // Update stats
+        self.close_seller_item(&listing.seller);
+        self.unlock_wine_in_registry(listing.wine_nft_id, &listing.seller);
+        self.record_verified_buyer_in_registry(listing.wine_nft_id, buyer);
+        self.update_marketplace_stats(|stats| {
+            stats.active_listings -= 1;
+        });
+
+        self.wine_sold_event(
+            listing_id,
+            listing.wine_nft_id,
+            &listing.seller,
+            buyer,
+            recipient,
+            settlement
+        );
+    }
+
+    // Let anyone reopen this listing after it expires, up to `max_relists` times (0 disables)
+    #[endpoint(setAutoRelist)]
+    fn set_auto_relist(&self, listing_id: u32, max_relists: u32) {
+        let listing = self.listings(listing_id).get();
+        require!(listing.active, ERR_LISTING_IS_NOT_ACTIVE);
+
+        let caller = self.blockchain().get_caller();
+        require!(caller == listing.seller, ERR_ONLY_SELLER_CAN_SET_AUTO_RELIST);
+        require!(max_relists <= MAX_RELIST_COUNT, ERR_RELIST_LIMIT_TOO_HIGH);
+
+        self.auto_relist_limit(listing_id).set(max_relists);
+        self.auto_relist_set_event(listing_id, max_relists);
+    }
+
+    // Reopen an expired listing for another period of the same length, keeping the NFT in
+    // escrow. The seller may always relist; anyone else only while auto relist allows it.
+    #[endpoint(relist)]
+    fn relist(&self, listing_id: u32) {
+        self.require_not_paused();
+        let mut listing = self.listings(listing_id).get();
+        require!(listing.active, ERR_LISTING_IS_NOT_ACTIVE);
+
+        let current_timestamp = self.blockchain().get_block_timestamp();
+        require!(current_timestamp > listing.deadline, ERR_LISTING_HAS_NOT_EXPIRED);
+
+        let relist_count = self.relist_count(listing_id).get();
+        require!(relist_count < MAX_RELIST_COUNT, ERR_RELIST_LIMIT_REACHED);
+        let caller = self.blockchain().get_caller();
+        if caller != listing.seller {
+            require!(
+                relist_count < self.auto_relist_limit(listing_id).get(),
+                ERR_AUTO_RELIST_NOT_AVAILABLE
+            );
+            self.require_not_blacklisted(&caller);
+        }
+
+        // A relisted wine passes the same gates as a new listing
+        self.require_not_blacklisted(&listing.seller);
+        self.require_wine_listable(listing.wine_nft_id, &listing.seller);
+        self.require_price_floor_met(listing.wine_nft_id, &listing.price, &listing.payment_token);
+        self.require_seller_within_item_limit(&listing.seller);
+
+        // The listing period restarts from now with its original length
+        let duration = listing.deadline - listing.created_timestamp;
+        listing.created_timestamp = current_timestamp;
+        listing.deadline = current_timestamp + duration;
+        self.listings(listing_id).set(&listing);
+        self.relist_count(listing_id).set(relist_count + 1);
+        self.track_listing_ask(listing_id, &listing);
+
+        self.listing_relisted_event(listing_id, &caller, relist_count + 1, listing.deadline);
+    }
+
+    // Return the escrowed NFT to the seller and close the listing
+    fn withdraw_listing(&self, listing_id: u32, listing: &mut Listing<Self::Api>) {
+        self.send_nft(&listing.seller, &listing.nft_token_id, listing.nft_nonce);
+
+        listing.active = false;
+        self.listings(listing_id).set(&*listing);
+        self.active_wine_listings(listing.wine_nft_id).swap_remove(&listing_id);
+        self.untrack_ask(listing.wine_nft_id, &listing.payment_token, listing_id);
+
+        // Update stats
+        self.close_seller_item(&listing.seller);
+        self.unlock_wine_in_registry(listing.wine_nft_id, &listing.seller);
+        self.update_marketplace_stats(|stats| {
+            stats.active_listings -= 1;
+        });
+    }
+
+    fn track_listing_ask(&self, listing_id: u32, listing: &Listing<Self::Api>) {
+        let quote = MarketQuote {
+            id: listing_id,
+            price: listing.price.clone(),
+            deadline: listing.deadline,
+        };
+        self.track_ask(listing.wine_nft_id, &listing.payment_token, quote);
+    }
+
+    // View functions
+    #[view(getListing)]
+    fn get_listing(&self, listing_id: u32) -> Listing<Self::Api> {
+        self.listings(listing_id).get()
+    }
+
+    #[view(getListingPaymentTokens)]
+    fn get_listing_payment_tokens(&self, listing_id: u32) -> MultiValueEncoded<EgldOrEsdtTokenIdentifier> {
+        self.listing_payment_tokens(listing_id).iter().collect()
+    }
+
+    // Price of a listing when paid in `token`, at the current exchange rate
+    #[view(getListingPrice)]
+    fn get_listing_price(&self, listing_id: u32, token: EgldOrEsdtTokenIdentifier) -> BigUint {
+        let listing = self.listings(listing_id).get();
+        self.listing_price_in(listing_id, &listing, &token)
+    }
+
+    #[view(getMixedSettlement)]
+    fn get_mixed_settlement(&self, listing_id: u32) -> MixedSettlement {
+        self.mixed_settlement(listing_id).get()
+    }
+
+    #[view(getAutoRelistLimit)]
+    fn get_auto_relist_limit(&self, listing_id: u32) -> u32 {
+        self.auto_relist_limit(listing_id).get()
+    }
+
+    #[view(getRelistCount)]
+    fn get_relist_count(&self, listing_id: u32) -> u32 {
+        self.relist_count(listing_id).get()
+    }
+
+    // Paginated: returns the next cursor (0 when done) followed by at most `page_size` listing ids
+    #[view(getSellerListings)]
+    fn get_seller_listings(
+        &self,
+        seller: &ManagedAddress,
+        cursor: u64,
+        page_size: u32,
+    ) -> MultiValue2<u64, MultiValueEncoded<u32>> {
+        let ids = self.seller_listings(seller);
+        let page = Page::new(cursor, page_size, MAX_IDS_PAGE_SIZE, ids.len());
+
+        let mut result = MultiValueEncoded::new();
+        for index in page.start..page.end {
+            result.push(ids.get(index + 1));
+        }
+        (page.next_cursor, result).into()
+    }
+
+    // Storage mappers
+    #[storage_mapper("listings")]
+    fn listings(&self, listing_id: u32) -> SingleValueMapper<Listing<Self::Api>>;
+
+    #[storage_mapper("sellerListings")]
+    fn seller_listings(&self, seller: &ManagedAddress) -> VecMapper<u32>;
+
+    #[storage_mapper("listingCounter")]
+    fn listing_counter(&self) -> SingleValueMapper<u32>;
+
+    #[storage_mapper("listingPaymentTokens")]
+    fn listing_payment_tokens(&self, listing_id: u32) -> UnorderedSetMapper<EgldOrEsdtTokenIdentifier>;
+
+    // Active listings per wine, so a recall can find them
+    #[storage_mapper("activeWineListings")]
+    fn active_wine_listings(&self, wine_id: u32) -> UnorderedSetMapper<u32>;
+
+    #[storage_mapper("mixedSettlement")]
+    fn mixed_settlement(&self, listing_id: u32) -> SingleValueMapper<MixedSettlement>;
+
+    #[storage_mapper("autoRelistLimit")]
+    fn auto_relist_limit(&self, listing_id: u32) -> SingleValueMapper<u32>;
+
+    #[storage_mapper("relistCount")]
+    fn relist_count(&self, listing_id: u32) -> SingleValueMapper<u32>;
+
+    // Events
+    #[event("wineListed")]
+    fn wine_listed_event(
+        &self,
+        #[indexed] listing_id: u32,
+        #[indexed] wine_nft_id: u32,
+        #[indexed] seller: &ManagedAddress,
+        price: &BigUint,
+        payment_token: &EgldOrEsdtTokenIdentifier,
+    );
+
+    #[event("wineSold")]
+    fn wine_sold_event(
+        &self,
+        #[indexed] listing_id: u32,
+        #[indexed] wine_nft_id: u32,
+        #[indexed] seller: &ManagedAddress,
+        #[indexed] buyer: &ManagedAddress,
+        #[indexed] recipient: &ManagedAddress,
+        settlement: &SaleSettlement<Self::Api>,
+    );
+
+    // One per token of a purchase paid in several tokens; wineSold then carries the price in
+    // the listing's own token only
+    #[event("salePaymentPart")]
+    fn sale_payment_part_event(
+        &self,
+        #[indexed] listing_id: u32,
+        settlement: &SaleSettlement<Self::Api>,
+    );
+
+    #[event("listingCancelled")]
+    fn listing_cancelled_event(
+        &self,
+        #[indexed] listing_id: u32,
+    );
+
+    #[event("recalledListingCancelled")]
+    fn recalled_listing_cancelled_event(
+        &self,
+        #[indexed] listing_id: u32,
+        #[indexed] wine_id: u32,
+        #[indexed] seller: &ManagedAddress,
+    );
+
+    #[event("listingExpired")]
+    fn listing_expired_event(
+        &self,
+        #[indexed] listing_id: u32,
+        #[indexed] seller: &ManagedAddress,
+        #[indexed] nft_token_id: &TokenIdentifier,
+        nft_nonce: u64,
+    );
+
+    #[event("listingPaymentTokensUpdated")]
+    fn listing_payment_tokens_updated_event(
+        &self,
+        #[indexed] listing_id: u32,
+        token_count: usize,
+    );
+
+    #[event("mixedSettlementSet")]
+    fn mixed_settlement_set_event(
+        &self,
+        #[indexed] listing_id: u32,
+        mixed_settlement: MixedSettlement,
+    );
+
+    #[event("autoRelistSet")]
+    fn auto_relist_set_event(
+        &self,
+        #[indexed] listing_id: u32,
+        max_relists: u32,
+    );
+
+    #[event("listingRelisted")]
+    fn listing_relisted_event(
+        &self,
+        #[indexed] listing_id: u32,
+        #[indexed] caller: &ManagedAddress,
+        #[indexed] relist_count: u32,
+        deadline: u64,
+    );
+}
@@ -0,0 +1,185 @@
+use multiversx_sc::imports::*;
+use wine_common::errors::*;
+use wine_roles::Role;
+
+// Scale of loyalty emission rates
+pub const LOYALTY_RATE_PRECISION: u64 = 1_000_000_000_000_000_000;
+
+// Loyalty rewards for buyers. Settled buyWine and finalizeAuction purchases accrue reward
+// tokens in proportion to the price, at a rate set per payment token and within a per-epoch
+// cap. Accruals are drawn from a funded pool, so every claimable reward is backed.
+#[multiversx_sc::module]
+pub trait LoyaltyModule: wine_roles::RolesModule {
+    // The reward token can only be chosen once
+    #[endpoint(setLoyaltyToken)]
+    fn set_loyalty_token(&self, token: TokenIdentifier) {
+        self.require_role(Role::Admin);
+        require!(self.loyalty_token().is_empty(), ERR_LOYALTY_TOKEN_ALREADY_SET);
+        require!(token.is_valid_esdt_identifier(), ERR_INVALID_LOYALTY_TOKEN);
+        self.loyalty_token().set(&token);
+    }
+
+    // Reward tokens per unit of `payment_token` spent, scaled by LOYALTY_RATE_PRECISION.
+    // 0 stops emission for purchases in that token.
+    #[endpoint(setLoyaltyEmissionRate)]
+    fn set_loyalty_emission_rate(&self, payment_token: EgldOrEsdtTokenIdentifier, rate: BigUint) {
+        self.require_role(Role::Admin);
+        if rate == 0 {
+            self.loyalty_emission_rates().remove(&payment_token);
+        } else {
+            self.loyalty_emission_rates().insert(payment_token.clone(), rate.clone());
+        }
+
+        self.loyalty_emission_rate_set_event(&payment_token, &rate);
+    }
+
+    // Maximum reward accrued per epoch over all buyers (0 = no cap)
+    #[endpoint(setLoyaltyEpochCap)]
+    fn set_loyalty_epoch_cap(&self, cap: BigUint) {
+        self.require_role(Role::Admin);
+        self.loyalty_epoch_cap().set(&cap);
+    }
+
+    #[payable("*")]
+    #[endpoint(fundLoyaltyPool)]
+    fn fund_loyalty_pool(&self) {
+        require!(!self.loyalty_token().is_empty(), ERR_LOYALTY_TOKEN_NOT_SET);
+        let payment = self.call_value().single_esdt();
+        require!(payment.token_identifier == self.loyalty_token().get(), ERR_INVALID_LOYALTY_TOKEN);
+        require!(payment.amount > 0, ERR_AMOUNT_MUST_BE_GREATER_THAN_0);
+        self.loyalty_pool().update(|pool| *pool += &payment.amount);
+
+        self.loyalty_pool_funded_event(&self.blockchain().get_caller(), &payment.amount);
+    }
+
+    #[endpoint(claimLoyaltyRewards)]
+    fn claim_loyalty_rewards(&self) -> BigUint {
+        let caller = self.blockchain().get_caller();
+        let amount = self.loyalty_rewards(&caller).take();
+        require!(amount > 0, ERR_NO_LOYALTY_REWARDS_TO_CLAIM);
+
+        self.send().direct_esdt(&caller, &self.loyalty_token().get(), 0, &amount);
+
+        self.loyalty_rewards_claimed_event(&caller, &amount);
+
+        amount
+    }
+
+    // Internal helpers
+    // Credit the buyer of a settled purchase, limited by the epoch cap and the pool
+    fn accrue_loyalty_rewards(
+        &self,
+        buyer: &ManagedAddress,
+        payment_token: &EgldOrEsdtTokenIdentifier,
+        amount: &BigUint,
+    ) {
+        let rate = match self.loyalty_emission_rates().get(payment_token) {
+            Some(rate) => rate,
+            None => return,
+        };
+        let mut reward = amount * &rate / LOYALTY_RATE_PRECISION;
+
+        let epoch = self.blockchain().get_block_epoch();
+        let cap = self.loyalty_epoch_cap().get();
+        let emitted = self.loyalty_emitted(epoch).get();
+        if cap > 0 {
+            let remaining = if emitted < cap { &cap - &emitted } else { BigUint::zero() };
+            reward = core::cmp::min(reward, remaining);
+        }
+        let pool = self.loyalty_pool().get();
+        reward = core::cmp::min(reward, pool.clone());
+        if reward == 0 {
+            return;
+        }
+
+        self.loyalty_pool().set(&pool - &reward);
+        self.loyalty_emitted(epoch).set(&emitted + &reward);
+        self.loyalty_rewards(buyer).update(|rewards| *rewards += &reward);
+
+        self.loyalty_rewards_accrued_event(buyer, epoch, &reward);
+    }
+
+    // View functions
+    #[view(getLoyaltyToken)]
+    fn get_loyalty_token(&self) -> OptionalValue<TokenIdentifier> {
+        if self.loyalty_token().is_empty() {
+            OptionalValue::None
+        } else {
+            OptionalValue::Some(self.loyalty_token().get())
+        }
+    }
+
+    // Rates are curated by admins, so they are returned whole
+    #[view(getLoyaltyEmissionRates)]
+    fn get_loyalty_emission_rates(
+        &self,
+    ) -> MultiValueEncoded<MultiValue2<EgldOrEsdtTokenIdentifier, BigUint>> {
+        let mut result = MultiValueEncoded::new();
+        for (payment_token, rate) in self.loyalty_emission_rates().iter() {
+            result.push((payment_token, rate).into());
+        }
+        result
+    }
+
+    #[view(getLoyaltyEpochCap)]
+    fn get_loyalty_epoch_cap(&self) -> BigUint {
+        self.loyalty_epoch_cap().get()
+    }
+
+    #[view(getLoyaltyEmitted)]
+    fn get_loyalty_emitted(&self, epoch: u64) -> BigUint {
+        self.loyalty_emitted(epoch).get()
+    }
+
+    // Funded and not yet accrued to anyone
+    #[view(getLoyaltyPool)]
+    fn get_loyalty_pool(&self) -> BigUint {
+        self.loyalty_pool().get()
+    }
+
+    #[view(getLoyaltyRewards)]
+    fn get_loyalty_rewards(&self, address: ManagedAddress) -> BigUint {
+        self.loyalty_rewards(&address).get()
+    }
+
+    // Storage mappers
+    #[storage_mapper("loyaltyToken")]
+    fn loyalty_token(&self) -> SingleValueMapper<TokenIdentifier>;
+
+    #[storage_mapper("loyaltyEmissionRates")]
+    fn loyalty_emission_rates(&self) -> MapMapper<EgldOrEsdtTokenIdentifier, BigUint>;
+
+    #[storage_mapper("loyaltyEpochCap")]
+    fn loyalty_epoch_cap(&self) -> SingleValueMapper<BigUint>;
+
+    #[storage_mapper("loyaltyEmitted")]
+    fn loyalty_emitted(&self, epoch: u64) -> SingleValueMapper<BigUint>;
+
+    #[storage_mapper("loyaltyPool")]
+    fn loyalty_pool(&self) -> SingleValueMapper<BigUint>;
+
+    #[storage_mapper("loyaltyRewards")]
+    fn loyalty_rewards(&self, address: &ManagedAddress) -> SingleValueMapper<BigUint>;
+
+    // Events
+    #[event("loyaltyEmissionRateSet")]
+    fn loyalty_emission_rate_set_event(
+        &self,
+        #[indexed] payment_token: &EgldOrEsdtTokenIdentifier,
+        rate: &BigUint,
+    );
+
+    #[event("loyaltyPoolFunded")]
+    fn loyalty_pool_funded_event(&self, #[indexed] funder: &ManagedAddress, amount: &BigUint);
+
+    #[event("loyaltyRewardsAccrued")]
+    fn loyalty_rewards_accrued_event(
+        &self,
+        #[indexed] buyer: &ManagedAddress,
+        #[indexed] epoch: u64,
+        amount: &BigUint,
+    );
+
+    #[event("loyaltyRewardsClaimed")]
+    fn loyalty_rewards_claimed_event(&self, #[indexed] address: &ManagedAddress, amount: &BigUint);
+}
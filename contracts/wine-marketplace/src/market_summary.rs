@@ -0,0 +1,229 @@
+use multiversx_sc::derive_imports::*;
+use multiversx_sc::imports::*;
+
+pub const SECONDS_PER_DAY: u64 = 86_400;
+// Days of sales counted in `MarketSummary::volume_30d`, today included
+pub const MARKET_VOLUME_WINDOW_DAYS: u64 = 30;
+// Quotes kept per wine, payment token and side of the book
+pub const MARKET_BOOK_DEPTH: usize = 10;
+
+// Price of an open listing (ask) or offer (bid), in the token it is tracked under
+#[derive(TopEncode, TopDecode, NestedEncode, NestedDecode, ManagedVecItem, TypeAbi, PartialEq, Debug, Clone)]
+pub struct MarketQuote<M: ManagedTypeApi> {
+    pub id: u32,
+    pub price: BigUint<M>,
+    pub deadline: u64,
+}
+
+#[derive(TopEncode, TopDecode, NestedEncode, NestedDecode, TypeAbi, PartialEq, Debug)]
+pub struct WineSale<M: ManagedTypeApi> {
+    pub price: BigUint<M>,
+    pub timestamp: u64,
+}
+
+// Market for one wine in one payment token. Ids and prices are 0 when there is no open
+// listing or offer, or no sale yet.
+#[derive(TopEncode, TopDecode, NestedEncode, NestedDecode, TypeAbi, PartialEq, Debug)]
+pub struct MarketSummary<M: ManagedTypeApi> {
+    pub payment_token: EgldOrEsdtTokenIdentifier<M>,
+    pub best_bid: BigUint<M>,
+    pub best_bid_offer_id: u32,
+    pub best_ask: BigUint<M>,
+    pub best_ask_listing_id: u32,
+    pub last_sale_price: BigUint<M>,
+    pub last_sale_timestamp: u64,
+    pub volume_30d: BigUint<M>,
+}
+
+// Lowest asks and highest bids per wine and payment token, kept up to date as listings and
+// offers open and close, plus the wine's recent sales. Prices in different tokens are not
+// converted, so each token has its own book. Expired entries that were not withdrawn yet
+// do not count. Each side of a book holds only its MARKET_BOOK_DEPTH best quotes, best
+// first, so keeping it up to date costs the same however many listings or offers a wine
+// has; a quote crowded out by better ones is not shown again once those close.
+#[multiversx_sc::module]
+pub trait MarketSummaryModule {
+    // Internal helpers
+    // Also called again when an open listing changes, e.g. on relist
+    fn track_ask(
+        &self,
+        wine_id: u32,
+        payment_token: &EgldOrEsdtTokenIdentifier,
+        quote: MarketQuote<Self::Api>,
+    ) {
+        self.wine_market_tokens(wine_id).insert(payment_token.clone());
+        self.insert_book_quote(&self.ask_book(wine_id, payment_token), quote, true);
+    }
+
+    fn untrack_ask(&self, wine_id: u32, payment_token: &EgldOrEsdtTokenIdentifier, listing_id: u32) {
+        self.remove_book_quote(&self.ask_book(wine_id, payment_token), listing_id);
+    }
+
+    fn track_bid(
+        &self,
+        wine_id: u32,
+        payment_token: &EgldOrEsdtTokenIdentifier,
+        quote: MarketQuote<Self::Api>,
+    ) {
+        self.wine_market_tokens(wine_id).insert(payment_token.clone());
+        self.insert_book_quote(&self.bid_book(wine_id, payment_token), quote, false);
+    }
+
+    fn untrack_bid(&self, wine_id: u32, payment_token: &EgldOrEsdtTokenIdentifier, offer_id: u32) {
+        self.remove_book_quote(&self.bid_book(wine_id, payment_token), offer_id);
+    }
+
+    fn record_wine_sale(
+        &self,
+        wine_id: u32,
+        payment_token: &EgldOrEsdtTokenIdentifier,
+        price: &BigUint,
+    ) {
+        let timestamp = self.blockchain().get_block_timestamp();
+        self.wine_market_tokens(wine_id).insert(payment_token.clone());
+        self.last_wine_sale(wine_id, payment_token).set(WineSale {
+            price: price.clone(),
+            timestamp,
+        });
+        self.wine_daily_volume(wine_id, payment_token, timestamp / SECONDS_PER_DAY)
+            .update(|volume| *volume += price);
+    }
+
+    // Replaces an earlier entry of the same quote, and drops expired ones on the way
+    fn insert_book_quote(
+        &self,
+        book_mapper: &SingleValueMapper<ManagedVec<MarketQuote<Self::Api>>>,
+        quote: MarketQuote<Self::Api>,
+        lowest: bool,
+    ) {
+        let id = quote.id;
+        let mut pending = Some(quote);
+        let mut book = ManagedVec::new();
+        for entry in book_mapper.get().iter() {
+            if entry.id == id || self.is_quote_expired(&entry) {
+                continue;
+            }
+            let goes_before = match &pending {
+                Some(quote) if lowest => quote.price < entry.price,
+                Some(quote) => quote.price > entry.price,
+                None => false,
+            };
+            if goes_before {
+                if let Some(quote) = pending.take() {
+                    book.push(quote);
+                }
+            }
+            book.push(entry);
+        }
+        if let Some(quote) = pending {
+            book.push(quote);
+        }
+        // The worst quote falls out of a full book, possibly the new one itself
+        if book.len() > MARKET_BOOK_DEPTH {
+            book.remove(MARKET_BOOK_DEPTH);
+        }
+        book_mapper.set(&book);
+    }
+
+    fn remove_book_quote(
+        &self,
+        book_mapper: &SingleValueMapper<ManagedVec<MarketQuote<Self::Api>>>,
+        id: u32,
+    ) {
+        let mut book = book_mapper.get();
+        if let Some(index) = book.iter().position(|entry| entry.id == id) {
+            book.remove(index);
+            book_mapper.set(&book);
+        }
+    }
+
+    // Books are sorted best first, so the first unexpired entry is the best quote
+    fn best_book_quote(
+        &self,
+        book_mapper: &SingleValueMapper<ManagedVec<MarketQuote<Self::Api>>>,
+    ) -> Option<MarketQuote<Self::Api>> {
+        book_mapper.get().iter().find(|quote| !self.is_quote_expired(quote))
+    }
+
+    fn is_quote_expired(&self, quote: &MarketQuote<Self::Api>) -> bool {
+        self.blockchain().get_block_timestamp() > quote.deadline
+    }
+
+    // View functions
+    // One entry per payment token the wine has been listed, offered or sold in
+    #[view(getMarketSummary)]
+    fn get_market_summary(&self, wine_nft_id: u32) -> MultiValueEncoded<MarketSummary<Self::Api>> {
+        let today = self.blockchain().get_block_timestamp() / SECONDS_PER_DAY;
+        let first_day = (today + 1).saturating_sub(MARKET_VOLUME_WINDOW_DAYS);
+
+        let mut result = MultiValueEncoded::new();
+        for payment_token in self.wine_market_tokens(wine_nft_id).iter() {
+            let best_bid = self.best_book_quote(&self.bid_book(wine_nft_id, &payment_token));
+            let best_ask = self.best_book_quote(&self.ask_book(wine_nft_id, &payment_token));
+            let last_sale_mapper = self.last_wine_sale(wine_nft_id, &payment_token);
+            let (last_sale_price, last_sale_timestamp) = if last_sale_mapper.is_empty() {
+                (BigUint::zero(), 0)
+            } else {
+                let last_sale = last_sale_mapper.get();
+                (last_sale.price, last_sale.timestamp)
+            };
+            let mut volume_30d = BigUint::zero();
+            for day in first_day..=today {
+                volume_30d += self.wine_daily_volume(wine_nft_id, &payment_token, day).get();
+            }
+
+            let (best_bid, best_bid_offer_id) = match best_bid {
+                Some(quote) => (quote.price, quote.id),
+                None => (BigUint::zero(), 0),
+            };
+            let (best_ask, best_ask_listing_id) = match best_ask {
+                Some(quote) => (quote.price, quote.id),
+                None => (BigUint::zero(), 0),
+            };
+            result.push(MarketSummary {
+                payment_token,
+                best_bid,
+                best_bid_offer_id,
+                best_ask,
+                best_ask_listing_id,
+                last_sale_price,
+                last_sale_timestamp,
+                volume_30d,
+            });
+        }
+        result
+    }
+
+    // Storage mappers
+    #[storage_mapper("askBook")]
+    fn ask_book(
+        &self,
+        wine_id: u32,
+        payment_token: &EgldOrEsdtTokenIdentifier,
+    ) -> SingleValueMapper<ManagedVec<MarketQuote<Self::Api>>>;
+
+    #[storage_mapper("bidBook")]
+    fn bid_book(
+        &self,
+        wine_id: u32,
+        payment_token: &EgldOrEsdtTokenIdentifier,
+    ) -> SingleValueMapper<ManagedVec<MarketQuote<Self::Api>>>;
+
+    #[storage_mapper("wineMarketTokens")]
+    fn wine_market_tokens(&self, wine_id: u32) -> UnorderedSetMapper<EgldOrEsdtTokenIdentifier>;
+
+    #[storage_mapper("lastWineSale")]
+    fn last_wine_sale(
+        &self,
+        wine_id: u32,
+        payment_token: &EgldOrEsdtTokenIdentifier,
+    ) -> SingleValueMapper<WineSale<Self::Api>>;
+
+    #[storage_mapper("wineDailyVolume")]
+    fn wine_daily_volume(
+        &self,
+        wine_id: u32,
+        payment_token: &EgldOrEsdtTokenIdentifier,
+        day: u64,
+    ) -> SingleValueMapper<BigUint>;
+}
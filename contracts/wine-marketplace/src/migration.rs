@@ -0,0 +1,146 @@
+use multiversx_sc::derive_imports::*;
+use multiversx_sc::imports::*;
+use wine_common::errors::*;
+use wine_roles::Role;
+
+use crate::listing::Listing;
+
+// Layout version of the stored Listing/Auction entries. Contracts deployed before versioning
+// have no version stored and are on version 1.
+//   1: initial devnet layout
+//   2: Listing gains `protected`
+pub const STORAGE_VERSION: u32 = 2;
+
+// Listing as stored by storage version 1
+#[derive(TopEncode, TopDecode, TypeAbi, PartialEq, Debug)]
+pub struct ListingV1<M: ManagedTypeApi> {
+    pub wine_nft_id: u32,
+    pub nft_token_id: TokenIdentifier<M>,
+    pub nft_nonce: u64,
+    pub seller: ManagedAddress<M>,
+    pub price: BigUint<M>,
+    pub payment_token: EgldOrEsdtTokenIdentifier<M>,
+    pub deadline: u64,
+    pub active: bool,
+    pub created_timestamp: u64,
+}
+
+// Entries are rewritten in batches after an upgrade, one version step at a time. Entries
+// not yet rewritten cannot be decoded, so `migrateStorage` should run until it reports
+// completion right after upgrading.
+#[multiversx_sc::module]
+pub trait MigrationModule:
+    wine_roles::RolesModule
+    + crate::admin::AdminModule
+    + crate::collector_tiers::CollectorTiersModule
+    + crate::loyalty::LoyaltyModule
+    + crate::fee_discounts::FeeDiscountsModule
+    + crate::stats_snapshots::StatsSnapshotsModule
+    + crate::treasury::TreasuryModule
+    + crate::blacklist::BlacklistModule
+    + crate::seller_limits::SellerLimitsModule
+    + crate::registry_cache::RegistryCacheModule
+    + crate::market_summary::MarketSummaryModule
+    + crate::protected_sale::ProtectedSaleModule
+    + crate::exchange_rates::ExchangeRatesModule
+    + crate::listing::ListingModule
+{
+    // Rewrite up to `batch_size` entries of the current version step. Returns true once
+    // storage is at STORAGE_VERSION.
+    #[endpoint(migrateStorage)]
+    fn migrate_storage(&self, batch_size: u32) -> bool {
+        self.require_role(Role::Admin);
+        require!(batch_size > 0, ERR_BATCH_SIZE_MUST_BE_GREATER_THAN_ZERO);
+
+        let version = self.get_storage_version();
+        if version >= STORAGE_VERSION {
+            return true;
+        }
+
+        let step_done = match version {
+            1 => self.migrate_listings_v1_to_v2(batch_size),
+            _ => sc_panic!(ERR_UNKNOWN_STORAGE_VERSION),
+        };
+        if step_done {
+            self.storage_version().set(version + 1);
+            self.migration_cursor().set(1u32);
+            self.storage_migrated_event(version + 1);
+        }
+
+        version + 1 >= STORAGE_VERSION && step_done
+    }
+
+    // Internal helpers
+    fn init_storage_version(&self) {
+        self.storage_version().set(STORAGE_VERSION);
+    }
+
+    fn begin_storage_migration(&self) {
+        if self.storage_version().is_empty() {
+            self.storage_version().set(1u32);
+        }
+        if self.migration_cursor().is_empty() {
+            self.migration_cursor().set(1u32);
+        }
+    }
+
+    fn migrate_listings_v1_to_v2(&self, batch_size: u32) -> bool {
+        let start = self.migration_cursor().get();
+        let end = core::cmp::min(start + batch_size, self.listing_counter().get());
+
+        for listing_id in start..end {
+            if self.legacy_listings_v1(listing_id).is_empty() {
+                continue;
+            }
+            let legacy = self.legacy_listings_v1(listing_id).get();
+            self.listings(listing_id).set(&Listing {
+                wine_nft_id: legacy.wine_nft_id,
+                nft_token_id: legacy.nft_token_id,
+                nft_nonce: legacy.nft_nonce,
+                seller: legacy.seller,
+                price: legacy.price,
+                payment_token: legacy.payment_token,
+                deadline: legacy.deadline,
+                active: legacy.active,
+                created_timestamp: legacy.created_timestamp,
+                protected: false,
+            });
+        }
+        self.migration_cursor().set(end);
+
+        end >= self.listing_counter().get()
+    }
+
+    // View functions
+    #[view(getStorageVersion)]
+    fn get_storage_version(&self) -> u32 {
+        if self.storage_version().is_empty() {
+            1
+        } else {
+            self.storage_version().get()
+        }
+    }
+
+    #[view(getMigrationCursor)]
+    fn get_migration_cursor(&self) -> u32 {
+        self.migration_cursor().get()
+    }
+
+    // Storage mappers
+    #[storage_mapper("storageVersion")]
+    fn storage_version(&self) -> SingleValueMapper<u32>;
+
+    #[storage_mapper("migrationCursor")]
+    fn migration_cursor(&self) -> SingleValueMapper<u32>;
+
+    // Same key as `listings`, read with the version 1 layout
+    #[storage_mapper("listings")]
+    fn legacy_listings_v1(&self, listing_id: u32) -> SingleValueMapper<ListingV1<Self::Api>>;
+
+    // Events
+    #[event("storageMigrated")]
+    fn storage_migrated_event(
+        &self,
+        #[indexed] storage_version: u32,
+    );
+}
@@ -0,0 +1,222 @@
+use multiversx_sc::derive_imports::*;
+use multiversx_sc::imports::*;
+use wine_common::errors::*;
+
+use crate::market_summary::MarketQuote;
+use crate::pagination::{Page, MAX_IDS_PAGE_SIZE};
+use crate::treasury::SaleSettlement;
+
+// Standing offer on a specific wine NFT; the offered amount stays in escrow
+// until the holder accepts or the buyer cancels
+#[derive(TopEncode, TopDecode, TypeAbi, PartialEq, Debug)]
+pub struct Offer<M: ManagedTypeApi> {
+    pub wine_nft_id: u32,
+    pub nft_token_id: TokenIdentifier<M>,
+    pub nft_nonce: u64,
+    pub buyer: ManagedAddress<M>,
+    pub amount: BigUint<M>,
+    pub payment_token: EgldOrEsdtTokenIdentifier<M>,
+    pub deadline: u64,
+    pub active: bool,
+}
+
+#[multiversx_sc::module]
+pub trait OffersModule:
+    wine_roles::RolesModule
+    + crate::admin::AdminModule
+    + crate::collector_tiers::CollectorTiersModule
+    + crate::fee_discounts::FeeDiscountsModule
+    + crate::stats_snapshots::StatsSnapshotsModule
+    + crate::treasury::TreasuryModule
+    + crate::blacklist::BlacklistModule
+    + crate::registry_cache::RegistryCacheModule
+    + crate::market_summary::MarketSummaryModule
+{
+    // Make an offer on a wine NFT, escrowing the offered amount
+    #[payable("*")]
+    #[endpoint(makeOffer)]
+    fn make_offer(
+        &self,
+        wine_nft_id: u32,
+        nft_token_id: TokenIdentifier,
+        nft_nonce: u64,
+        duration_seconds: u64,
+    ) -> u32 {
+        self.require_not_paused();
+        let payment_token = self.call_value().egld_or_single_esdt().token_identifier;
+        self.require_supported_payment_token(&payment_token);
+        let payment_amount = self.require_fungible_payment(&payment_token);
+        require!(payment_amount > 0u32, ERR_OFFER_AMOUNT_MUST_BE_GREATER_THAN_ZERO);
+        require!(duration_seconds >= 3600, ERR_MINIMUM_OFFER_DURATION_IS_1_HOUR);
+        require!(duration_seconds <= 2592000, ERR_MAXIMUM_OFFER_DURATION_IS_30_DAYS);
+
+        let caller = self.blockchain().get_caller();
+        self.require_not_blacklisted(&caller);
+        let deadline = self.blockchain().get_block_timestamp() + duration_seconds;
+
+        let offer_id = self.offer_counter().get();
+        let offer = Offer {
+            wine_nft_id,
+            nft_token_id,
+            nft_nonce,
+            buyer: caller.clone(),
+            amount: payment_amount,
+            payment_token,
+            deadline,
+            active: true,
+        };
+
+        self.offers(offer_id).set(&offer);
+        self.offer_counter().set(offer_id + 1);
+        self.buyer_offers(&caller).push(&offer_id);
+        let quote = MarketQuote {
+            id: offer_id,
+            price: offer.amount.clone(),
+            deadline,
+        };
+        self.track_bid(wine_nft_id, &offer.payment_token, quote);
+
+        self.offer_made_event(offer_id, wine_nft_id, &caller, &offer.amount, &offer.payment_token);
+
+        offer_id
+    }
+
+    // Accept an offer by sending the matching NFT
+    #[payable("*")]
+    #[endpoint(acceptOffer)]
+    fn accept_offer(&self, offer_id: u32) {
+        self.require_not_paused();
+        let mut offer = self.offers(offer_id).get();
+        require!(offer.active, ERR_OFFER_IS_NOT_ACTIVE);
+        require!(
+            self.blockchain().get_block_timestamp() <= offer.deadline,
+            ERR_OFFER_HAS_EXPIRED
+        );
+
+        let payment = self.call_value().single_esdt();
+        require!(
+            payment.token_identifier == offer.nft_token_id && payment.token_nonce == offer.nft_nonce,
+            ERR_NFT_DOES_NOT_MATCH_OFFER
+        );
+        require!(payment.amount == BigUint::from(1u32), ERR_MUST_SEND_EXACTLY_1_NFT);
+        // The buyer names the wine when making the offer; it must be the one the NFT is of
+        self.require_escrowed_wine(offer.wine_nft_id, &payment.token_identifier, payment.token_nonce);
+
+        let seller = self.blockchain().get_caller();
+        self.require_not_blacklisted(&seller);
+        require!(seller != offer.buyer, ERR_CANNOT_ACCEPT_YOUR_OWN_OFFER);
+        // Accepting an offer on their own wine is a primary sale for the producer
+        let read_model = self.require_synced_read_model(offer.wine_nft_id);
+        self.require_primary_sale_allowed(&read_model, &seller);
+
+        offer.active = false;
+        self.offers(offer_id).set(&offer);
+        self.untrack_bid(offer.wine_nft_id, &offer.payment_token, offer_id);
+        self.record_wine_sale(offer.wine_nft_id, &offer.payment_token, &offer.amount);
+        self.record_verified_buyer_in_registry(offer.wine_nft_id, &offer.buyer);
+
+        self.send_nft(&offer.buyer, &offer.nft_token_id, offer.nft_nonce);
+        let settlement = self.pay_out_sale(
+            &seller,
+            &offer.buyer,
+            &offer.nft_token_id,
+            offer.nft_nonce,
+            &offer.payment_token,
+            &offer.amount,
+        );
+
+        self.offer_accepted_event(offer_id, offer.wine_nft_id, &seller, &offer.buyer, &settlement);
+    }
+
+    // Cancel offer and reclaim the escrowed amount (only buyer, also after expiry)
+    #[endpoint(cancelOffer)]
+    fn cancel_offer(&self, offer_id: u32) {
+        let mut offer = self.offers(offer_id).get();
+        require!(offer.active, ERR_OFFER_IS_NOT_ACTIVE);
+
+        let caller = self.blockchain().get_caller();
+        require!(caller == offer.buyer, ERR_ONLY_BUYER_CAN_CANCEL_OFFER);
+
+        offer.active = false;
+        self.offers(offer_id).set(&offer);
+        self.untrack_bid(offer.wine_nft_id, &offer.payment_token, offer_id);
+
+        self.send_payment(&offer.buyer, &offer.payment_token, &offer.amount);
+
+        if self.blockchain().get_block_timestamp() > offer.deadline {
+            self.offer_expired_event(offer_id, &offer.buyer, &offer.payment_token, &offer.amount);
+        } else {
+            self.offer_cancelled_event(offer_id);
+        }
+    }
+
+    // View functions
+    #[view(getOffer)]
+    fn get_offer(&self, offer_id: u32) -> Offer<Self::Api> {
+        self.offers(offer_id).get()
+    }
+
+    // Paginated: returns the next cursor (0 when done) followed by at most `page_size` offer ids
+    #[view(getBuyerOffers)]
+    fn get_buyer_offers(
+        &self,
+        buyer: &ManagedAddress,
+        cursor: u64,
+        page_size: u32,
+    ) -> MultiValue2<u64, MultiValueEncoded<u32>> {
+        let ids = self.buyer_offers(buyer);
+        let page = Page::new(cursor, page_size, MAX_IDS_PAGE_SIZE, ids.len());
+
+        let mut result = MultiValueEncoded::new();
+        for index in page.start..page.end {
+            result.push(ids.get(index + 1));
+        }
+        (page.next_cursor, result).into()
+    }
+
+    // Storage mappers
+    #[storage_mapper("offers")]
+    fn offers(&self, offer_id: u32) -> SingleValueMapper<Offer<Self::Api>>;
+
+    #[storage_mapper("buyerOffers")]
+    fn buyer_offers(&self, buyer: &ManagedAddress) -> VecMapper<u32>;
+
+    #[storage_mapper("offerCounter")]
+    fn offer_counter(&self) -> SingleValueMapper<u32>;
+
+    // Events
+    #[event("offerMade")]
+    fn offer_made_event(
+        &self,
+        #[indexed] offer_id: u32,
+        #[indexed] wine_nft_id: u32,
+        #[indexed] buyer: &ManagedAddress,
+        amount: &BigUint,
+        payment_token: &EgldOrEsdtTokenIdentifier,
+    );
+
+    #[event("offerAccepted")]
+    fn offer_accepted_event(
+        &self,
+        #[indexed] offer_id: u32,
+        #[indexed] wine_nft_id: u32,
+        #[indexed] seller: &ManagedAddress,
+        #[indexed] buyer: &ManagedAddress,
+        settlement: &SaleSettlement<Self::Api>,
+    );
+
+    #[event("offerCancelled")]
+    fn offer_cancelled_event(
+        &self,
+        #[indexed] offer_id: u32,
+    );
+
+    #[event("offerExpired")]
+    fn offer_expired_event(
+        &self,
+        #[indexed] offer_id: u32,
+        #[indexed] buyer: &ManagedAddress,
+        #[indexed] payment_token: &EgldOrEsdtTokenIdentifier,
+        amount: &BigUint,
+    );
+}
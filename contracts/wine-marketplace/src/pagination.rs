@@ -0,0 +1,60 @@
+use multiversx_sc::imports::*;
+pub use wine_common::MAX_VIEW_RETURN_BYTES;
+
+// Each cap below is sized from the worst-case encoded item, so a full page fits in
+// MAX_VIEW_RETURN_BYTES.
+
+// listing / auction / offer id: 4 bytes
+pub const MAX_IDS_PAGE_SIZE: u32 = 1_000;
+// bidder 32 + amount (4 + 32, i.e. anything below 2^256) + timestamp 8 = 76 bytes
+pub const MAX_CANDLE_BIDS_PAGE_SIZE: u32 = 200;
+// address: 32 bytes
+pub const MAX_ADDRESSES_PAGE_SIZE: u32 = 500;
+// kind 1 + ids 8 + seller/winner 64 + token (4 + 17) + bool 1 + price 36 + timestamp 8 = 139 bytes
+pub const MAX_AUCTION_RESULTS_PAGE_SIZE: u32 = 200;
+
+// Zero-based range [start, end) of a list with `total` items. `next_cursor` is the
+// position to pass back for the following page, or 0 once the list is exhausted.
+pub struct Page {
+    pub start: usize,
+    pub end: usize,
+    pub next_cursor: u64,
+}
+
+impl Page {
+    // A page size of 0, or above `max_page_size`, yields a page of `max_page_size` items
+    pub fn new(cursor: u64, page_size: u32, max_page_size: u32, total: usize) -> Self {
+        let size = if page_size == 0 || page_size > max_page_size {
+            max_page_size
+        } else {
+            page_size
+        };
+        let start = core::cmp::min(usize::try_from(cursor).unwrap_or(usize::MAX), total);
+        let end = core::cmp::min(start.saturating_add(size as usize), total);
+        let next_cursor = if end < total { end as u64 } else { 0 };
+
+        Page {
+            start,
+            end,
+            next_cursor,
+        }
+    }
+}
+
+#[multiversx_sc::module]
+pub trait PaginationModule {
+    // Maximum page size per paginated view, so clients can size their requests
+    #[view(getPageSizeLimits)]
+    fn get_page_size_limits(&self) -> MultiValueEncoded<MultiValue2<ManagedBuffer, u32>> {
+        let mut result = MultiValueEncoded::new();
+        result.push((ManagedBuffer::from("getSellerListings"), MAX_IDS_PAGE_SIZE).into());
+        result.push((ManagedBuffer::from("getSellerAuctions"), MAX_IDS_PAGE_SIZE).into());
+        result.push((ManagedBuffer::from("getSellerCandleAuctions"), MAX_IDS_PAGE_SIZE).into());
+        result.push((ManagedBuffer::from("getFinalizableAuctions"), MAX_IDS_PAGE_SIZE).into());
+        result.push((ManagedBuffer::from("getBuyerOffers"), MAX_IDS_PAGE_SIZE).into());
+        result.push((ManagedBuffer::from("getCandleBids"), MAX_CANDLE_BIDS_PAGE_SIZE).into());
+        result.push((ManagedBuffer::from("getBlacklist"), MAX_ADDRESSES_PAGE_SIZE).into());
+        result.push((ManagedBuffer::from("getAuctionResults"), MAX_AUCTION_RESULTS_PAGE_SIZE).into());
+        result
+    }
+}
@@ -0,0 +1,252 @@
+use multiversx_sc::derive_imports::*;
+use multiversx_sc::imports::*;
+use wine_common::errors::*;
+use wine_roles::Role;
+
+use crate::proxies::wine_arbitration_proxy::WineArbitrationProxy;
+use crate::treasury::SaleSettlement;
+
+// Default time a protected sale stays in escrow before it can be released: 7 days
+pub const DEFAULT_DISPUTE_WINDOW: u64 = 604800;
+// Dispute window bounds: 1 day to 60 days
+pub const MIN_DISPUTE_WINDOW: u64 = 86400;
+pub const MAX_DISPUTE_WINDOW: u64 = 5184000;
+// Gas for opening a case with an arbitration contract
+pub const ARBITRATION_CASE_GAS: u64 = 15_000_000;
+
+#[derive(TopEncode, TopDecode, NestedEncode, NestedDecode, TypeAbi, PartialEq, Eq, Debug, Clone, Copy)]
+pub enum ProtectedSaleStatus {
+    Escrowed,
+    Disputed,
+    Released,
+    Refunded,
+}
+
+// Purchase of a protected listing. Both the NFT and the payment stay in the marketplace
+// until the dispute window passes, the buyer confirms delivery or the arbiter rules.
+#[derive(TopEncode, TopDecode, TypeAbi, PartialEq, Debug)]
+pub struct ProtectedSale<M: ManagedTypeApi> {
+    pub wine_nft_id: u32,
+    pub nft_token_id: TokenIdentifier<M>,
+    pub nft_nonce: u64,
+    pub seller: ManagedAddress<M>,
+    pub buyer: ManagedAddress<M>,
+    pub recipient: ManagedAddress<M>,
+    pub payment_token: EgldOrEsdtTokenIdentifier<M>,
+    pub amount: BigUint<M>,
+    pub purchase_timestamp: u64,
+    pub release_timestamp: u64,
+    pub status: ProtectedSaleStatus,
+}
+
+#[multiversx_sc::module]
+pub trait ProtectedSaleModule:
+    wine_roles::RolesModule
+    + crate::admin::AdminModule
+    + crate::collector_tiers::CollectorTiersModule
+    + crate::loyalty::LoyaltyModule
+    + crate::fee_discounts::FeeDiscountsModule
+    + crate::stats_snapshots::StatsSnapshotsModule
+    + crate::treasury::TreasuryModule
+{
+    // An account, or an arbitration contract such as wine-arbitration: disputes then open a
+    // case there through `openCase(listing_id)`, and its jurors' ruling comes back through
+    // `resolveDispute`.
+    #[endpoint(setArbiter)]
+    fn set_arbiter(&self, arbiter: ManagedAddress) {
+        self.require_role(Role::Admin);
+        require!(!arbiter.is_zero(), ERR_INVALID_ARBITER_ADDRESS);
+        self.arbiter().set(&arbiter);
+        self.arbiter_set_event(&arbiter);
+    }
+
+    #[endpoint(setDisputeWindow)]
+    fn set_dispute_window(&self, dispute_window: u64) {
+        self.require_role(Role::Admin);
+        require!(
+            (MIN_DISPUTE_WINDOW..=MAX_DISPUTE_WINDOW).contains(&dispute_window),
+            ERR_DISPUTE_WINDOW_MUST_BE_BETWEEN_1_AND_60_DAYS
+        );
+        self.dispute_window().set(dispute_window);
+    }
+
+    // Buyer flags a problem with delivery; only possible while the sale is still in escrow
+    #[endpoint(openDispute)]
+    fn open_dispute(&self, listing_id: u32, reason: ManagedBuffer) {
+        let mut sale = self.require_protected_sale(listing_id);
+        require!(sale.status == ProtectedSaleStatus::Escrowed, ERR_SALE_IS_NOT_IN_ESCROW);
+
+        let caller = self.blockchain().get_caller();
+        require!(caller == sale.buyer, ERR_ONLY_BUYER_CAN_OPEN_A_DISPUTE);
+        require!(
+            self.blockchain().get_block_timestamp() < sale.release_timestamp,
+            ERR_DISPUTE_WINDOW_HAS_PASSED
+        );
+
+        sale.status = ProtectedSaleStatus::Disputed;
+        self.protected_sales(listing_id).set(&sale);
+
+        // Protected listings need an arbiter, and it can be replaced but not removed
+        let arbiter = self.arbiter().get();
+        if self.blockchain().is_smart_contract(&arbiter) {
+            self.tx()
+                .to(&arbiter)
+                .typed(WineArbitrationProxy)
+                .open_case(listing_id as u64)
+                .gas(ARBITRATION_CASE_GAS)
+                .transfer_execute();
+        }
+
+        self.dispute_opened_event(listing_id, &caller, &reason);
+    }
+
+    // Release funds to the seller and the NFT to the recipient. The buyer may release early
+    // to confirm delivery; anyone may release once an undisputed window has passed.
+    #[endpoint(releaseProtectedSale)]
+    fn release_protected_sale(&self, listing_id: u32) {
+        let sale = self.require_protected_sale(listing_id);
+        require!(sale.status == ProtectedSaleStatus::Escrowed, ERR_SALE_IS_NOT_IN_ESCROW);
+
+        let caller = self.blockchain().get_caller();
+        require!(
+            caller == sale.buyer || self.blockchain().get_block_timestamp() >= sale.release_timestamp,
+            ERR_DISPUTE_WINDOW_HAS_NOT_PASSED
+        );
+
+        self.settle_protected_sale(listing_id, sale, false);
+    }
+
+    // Arbiter ruling on a disputed sale: refund returns the payment to the buyer and the
+    // NFT to the seller, otherwise the sale completes as if released
+    #[endpoint(resolveDispute)]
+    fn resolve_dispute(&self, listing_id: u32, refund: bool) {
+        let sale = self.require_protected_sale(listing_id);
+        require!(sale.status == ProtectedSaleStatus::Disputed, ERR_SALE_IS_NOT_DISPUTED);
+
+        let caller = self.blockchain().get_caller();
+        require!(
+            !self.arbiter().is_empty() && caller == self.arbiter().get(),
+            ERR_ONLY_ARBITER_CAN_RESOLVE_DISPUTES
+        );
+
+        self.settle_protected_sale(listing_id, sale, refund);
+        self.dispute_resolved_event(listing_id, &caller, refund);
+    }
+
+    // Internal helpers
+    // Called by the buy flow instead of paying out, with the NFT and payment already held
+    fn hold_protected_sale(&self, listing_id: u32, sale: ProtectedSale<Self::Api>) {
+        self.protected_sales(listing_id).set(&sale);
+        self.protected_sale_held_event(listing_id, &sale.buyer, sale.release_timestamp);
+    }
+
+    fn require_protected_sale(&self, listing_id: u32) -> ProtectedSale<Self::Api> {
+        require!(!self.protected_sales(listing_id).is_empty(), ERR_PROTECTED_SALE_DOES_NOT_EXIST);
+        self.protected_sales(listing_id).get()
+    }
+
+    // Protected listings can only be created once there is someone to resolve disputes
+    fn require_arbiter_configured(&self) {
+        require!(!self.arbiter().is_empty(), ERR_NO_ARBITER_CONFIGURED);
+    }
+
+    fn settle_protected_sale(&self, listing_id: u32, mut sale: ProtectedSale<Self::Api>, refund: bool) {
+        let settlement;
+        if refund {
+            self.send_nft(&sale.seller, &sale.nft_token_id, sale.nft_nonce);
+            self.send_payment(&sale.buyer, &sale.payment_token, &sale.amount);
+            sale.status = ProtectedSaleStatus::Refunded;
+            settlement = self.unsettled_sale(&sale.payment_token, &sale.amount);
+        } else {
+            self.send_nft(&sale.recipient, &sale.nft_token_id, sale.nft_nonce);
+            settlement = self.pay_out_sale(
+                &sale.seller,
+                &sale.buyer,
+                &sale.nft_token_id,
+                sale.nft_nonce,
+                &sale.payment_token,
+                &sale.amount,
+            );
+            self.accrue_loyalty_rewards(&sale.buyer, &sale.payment_token, &sale.amount);
+            sale.status = ProtectedSaleStatus::Released;
+        }
+        self.protected_sales(listing_id).set(&sale);
+
+        self.protected_sale_settled_event(listing_id, &sale.seller, &sale.buyer, refund, &settlement);
+    }
+
+    // View functions
+    #[view(getProtectedSale)]
+    fn get_protected_sale(&self, listing_id: u32) -> OptionalValue<ProtectedSale<Self::Api>> {
+        if self.protected_sales(listing_id).is_empty() {
+            OptionalValue::None
+        } else {
+            OptionalValue::Some(self.protected_sales(listing_id).get())
+        }
+    }
+
+    #[view(getArbiter)]
+    fn get_arbiter(&self) -> OptionalValue<ManagedAddress> {
+        if self.arbiter().is_empty() {
+            OptionalValue::None
+        } else {
+            OptionalValue::Some(self.arbiter().get())
+        }
+    }
+
+    #[view(getDisputeWindow)]
+    fn get_dispute_window(&self) -> u64 {
+        self.dispute_window().get()
+    }
+
+    // Storage mappers
+    #[storage_mapper("protectedSales")]
+    fn protected_sales(&self, listing_id: u32) -> SingleValueMapper<ProtectedSale<Self::Api>>;
+
+    #[storage_mapper("arbiter")]
+    fn arbiter(&self) -> SingleValueMapper<ManagedAddress>;
+
+    #[storage_mapper("disputeWindow")]
+    fn dispute_window(&self) -> SingleValueMapper<u64>;
+
+    // Events
+    #[event("arbiterSet")]
+    fn arbiter_set_event(
+        &self,
+        #[indexed] arbiter: &ManagedAddress,
+    );
+
+    #[event("protectedSaleHeld")]
+    fn protected_sale_held_event(
+        &self,
+        #[indexed] listing_id: u32,
+        #[indexed] buyer: &ManagedAddress,
+        release_timestamp: u64,
+    );
+
+    #[event("disputeOpened")]
+    fn dispute_opened_event(
+        &self,
+        #[indexed] listing_id: u32,
+        #[indexed] buyer: &ManagedAddress,
+        reason: &ManagedBuffer,
+    );
+
+    #[event("disputeResolved")]
+    fn dispute_resolved_event(
+        &self,
+        #[indexed] listing_id: u32,
+        #[indexed] arbiter: &ManagedAddress,
+        refund: bool,
+    );
+
+    #[event("protectedSaleSettled")]
+    fn protected_sale_settled_event(
+        &self,
+        #[indexed] listing_id: u32,
+        #[indexed] seller: &ManagedAddress,
+        #[indexed] buyer: &ManagedAddress,
+        #[indexed] refunded: bool,
+        settlement: &SaleSettlement<Self::Api>,
+    );
+}
@@ -0,0 +1,4 @@
+pub mod wine_arbitration_proxy;
+pub mod wine_consignment_proxy;
+pub mod wine_fee_router_proxy;
+pub mod wine_registry_proxy;
@@ -0,0 +1,564 @@
+// Code generated by the multiversx-sc proxy generator. DO NOT EDIT.
+
+////////////////////////////////////////////////////
+////////////////// AUTO-GENERATED //////////////////
+////////////////////////////////////////////////////
+
+#![allow(dead_code)]
+#![allow(clippy::all)]
+
+use multiversx_sc::proxy_imports::*;
+
+pub struct WineArbitrationProxy;
+
+impl<Env, From, To, Gas> TxProxyTrait<Env, From, To, Gas> for WineArbitrationProxy
+where
+    Env: TxEnv,
+    From: TxFrom<Env>,
+    To: TxTo<Env>,
+    Gas: TxGas<Env>,
+{
+    type TxProxyMethods = WineArbitrationProxyMethods<Env, From, To, Gas>;
+
+    fn proxy_methods(self, tx: Tx<Env, From, To, (), Gas, (), ()>) -> Self::TxProxyMethods {
+        WineArbitrationProxyMethods { wrapped_tx: tx }
+    }
+}
+
+pub struct WineArbitrationProxyMethods<Env, From, To, Gas>
+where
+    Env: TxEnv,
+    From: TxFrom<Env>,
+    To: TxTo<Env>,
+    Gas: TxGas<Env>,
+{
+    wrapped_tx: Tx<Env, From, To, (), Gas, (), ()>,
+}
+
+#[rustfmt::skip]
+impl<Env, From, Gas> WineArbitrationProxyMethods<Env, From, (), Gas>
+where
+    Env: TxEnv,
+    Env::Api: VMApi,
+    From: TxFrom<Env>,
+    Gas: TxGas<Env>,
+{
+    pub fn init<
+        Arg0: ProxyArg<TokenIdentifier<Env::Api>>,
+        Arg1: ProxyArg<BigUint<Env::Api>>,
+        Arg2: ProxyArg<u32>,
+        Arg3: ProxyArg<u64>,
+        Arg4: ProxyArg<u64>,
+        Arg5: ProxyArg<u64>,
+    >(
+        self,
+        staking_token: Arg0,
+        min_juror_stake: Arg1,
+        jurors_per_case: Arg2,
+        draw_delay: Arg3,
+        voting_period: Arg4,
+        slash_bps: Arg5,
+    ) -> TxTypedDeploy<Env, From, NotPayable, Gas, ()> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_deploy()
+            .argument(&staking_token)
+            .argument(&min_juror_stake)
+            .argument(&jurors_per_case)
+            .argument(&draw_delay)
+            .argument(&voting_period)
+            .argument(&slash_bps)
+            .original_result()
+    }
+}
+
+#[rustfmt::skip]
+impl<Env, From, To, Gas> WineArbitrationProxyMethods<Env, From, To, Gas>
+where
+    Env: TxEnv,
+    Env::Api: VMApi,
+    From: TxFrom<Env>,
+    To: TxTo<Env>,
+    Gas: TxGas<Env>,
+{
+    pub fn upgrade(
+        self,
+    ) -> TxTypedUpgrade<Env, From, To, NotPayable, Gas, ()> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_upgrade()
+            .original_result()
+    }
+}
+
+#[rustfmt::skip]
+impl<Env, From, To, Gas> WineArbitrationProxyMethods<Env, From, To, Gas>
+where
+    Env: TxEnv,
+    Env::Api: VMApi,
+    From: TxFrom<Env>,
+    To: TxTo<Env>,
+    Gas: TxGas<Env>,
+{
+    pub fn set_arbitration_config<
+        Arg0: ProxyArg<u32>,
+        Arg1: ProxyArg<u64>,
+        Arg2: ProxyArg<u64>,
+        Arg3: ProxyArg<u64>,
+    >(
+        self,
+        jurors_per_case: Arg0,
+        draw_delay: Arg1,
+        voting_period: Arg2,
+        slash_bps: Arg3,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, ()> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("setArbitrationConfig")
+            .argument(&jurors_per_case)
+            .argument(&draw_delay)
+            .argument(&voting_period)
+            .argument(&slash_bps)
+            .original_result()
+    }
+
+    pub fn add_dispute_source<
+        Arg0: ProxyArg<ManagedAddress<Env::Api>>,
+        Arg1: ProxyArg<DisputeSourceKind>,
+    >(
+        self,
+        source: Arg0,
+        kind: Arg1,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, ()> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("addDisputeSource")
+            .argument(&source)
+            .argument(&kind)
+            .original_result()
+    }
+
+    pub fn remove_dispute_source<
+        Arg0: ProxyArg<ManagedAddress<Env::Api>>,
+    >(
+        self,
+        source: Arg0,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, ()> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("removeDisputeSource")
+            .argument(&source)
+            .original_result()
+    }
+
+    pub fn open_case<
+        Arg0: ProxyArg<u64>,
+    >(
+        self,
+        source_id: Arg0,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, u64> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("openCase")
+            .argument(&source_id)
+            .original_result()
+    }
+
+    pub fn draw_jurors<
+        Arg0: ProxyArg<u64>,
+    >(
+        self,
+        case_id: Arg0,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, ()> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("drawJurors")
+            .argument(&case_id)
+            .original_result()
+    }
+
+    pub fn vote<
+        Arg0: ProxyArg<u64>,
+        Arg1: ProxyArg<bool>,
+    >(
+        self,
+        case_id: Arg0,
+        claimant_wins: Arg1,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, ()> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("vote")
+            .argument(&case_id)
+            .argument(&claimant_wins)
+            .original_result()
+    }
+
+    pub fn execute_ruling<
+        Arg0: ProxyArg<u64>,
+    >(
+        self,
+        case_id: Arg0,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, ()> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("executeRuling")
+            .argument(&case_id)
+            .original_result()
+    }
+
+    pub fn get_case<
+        Arg0: ProxyArg<u64>,
+    >(
+        self,
+        case_id: Arg0,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, Case<Env::Api>> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("getCase")
+            .argument(&case_id)
+            .original_result()
+    }
+
+    pub fn get_case_count(
+        self,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, u64> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("getCaseCount")
+            .original_result()
+    }
+
+    pub fn get_case_by_source<
+        Arg0: ProxyArg<ManagedAddress<Env::Api>>,
+        Arg1: ProxyArg<u64>,
+    >(
+        self,
+        source: Arg0,
+        source_id: Arg1,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, OptionalValue<u64>> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("getCaseBySource")
+            .argument(&source)
+            .argument(&source_id)
+            .original_result()
+    }
+
+    pub fn get_arbitration_config(
+        self,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, ArbitrationConfig> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("getArbitrationConfig")
+            .original_result()
+    }
+
+    pub fn get_dispute_sources(
+        self,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, MultiValueEncoded<Env::Api, MultiValue2<ManagedAddress<Env::Api>, DisputeSourceKind>>> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("getDisputeSources")
+            .original_result()
+    }
+
+    pub fn set_min_juror_stake<
+        Arg0: ProxyArg<BigUint<Env::Api>>,
+    >(
+        self,
+        min_juror_stake: Arg0,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, ()> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("setMinJurorStake")
+            .argument(&min_juror_stake)
+            .original_result()
+    }
+
+    pub fn stake_as_juror(
+        self,
+    ) -> TxTypedCall<Env, From, To, (), Gas, ()> {
+        self.wrapped_tx
+            .raw_call("stakeAsJuror")
+            .original_result()
+    }
+
+    pub fn unstake_juror<
+        Arg0: ProxyArg<BigUint<Env::Api>>,
+    >(
+        self,
+        amount: Arg0,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, ()> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("unstakeJuror")
+            .argument(&amount)
+            .original_result()
+    }
+
+    pub fn get_jurors(
+        self,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, MultiValueEncoded<Env::Api, ManagedAddress<Env::Api>>> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("getJurors")
+            .original_result()
+    }
+
+    pub fn get_juror_stake<
+        Arg0: ProxyArg<ManagedAddress<Env::Api>>,
+    >(
+        self,
+        juror: Arg0,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, BigUint<Env::Api>> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("getJurorStake")
+            .argument(&juror)
+            .original_result()
+    }
+
+    pub fn get_juror_active_cases<
+        Arg0: ProxyArg<ManagedAddress<Env::Api>>,
+    >(
+        self,
+        juror: Arg0,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, u32> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("getJurorActiveCases")
+            .argument(&juror)
+            .original_result()
+    }
+
+    pub fn staking_token(
+        self,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, SingleValueMapper<TokenIdentifier<Env::Api>>> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("getStakingToken")
+            .original_result()
+    }
+
+    pub fn min_juror_stake(
+        self,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, SingleValueMapper<BigUint<Env::Api>>> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("getMinJurorStake")
+            .original_result()
+    }
+
+    pub fn commit_entropy<
+        Arg0: ProxyArg<ManagedBuffer<Env::Api>>,
+        Arg1: ProxyArg<u64>,
+        Arg2: ProxyArg<ManagedByteArray<Env::Api, 32>>,
+    >(
+        self,
+        domain: Arg0,
+        context_id: Arg1,
+        commitment: Arg2,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, ()> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("commitEntropy")
+            .argument(&domain)
+            .argument(&context_id)
+            .argument(&commitment)
+            .original_result()
+    }
+
+    pub fn reveal_entropy<
+        Arg0: ProxyArg<ManagedBuffer<Env::Api>>,
+        Arg1: ProxyArg<u64>,
+        Arg2: ProxyArg<ManagedBuffer<Env::Api>>,
+    >(
+        self,
+        domain: Arg0,
+        context_id: Arg1,
+        preimage: Arg2,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, ()> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("revealEntropy")
+            .argument(&domain)
+            .argument(&context_id)
+            .argument(&preimage)
+            .original_result()
+    }
+
+    pub fn get_entropy_contribution_count<
+        Arg0: ProxyArg<ManagedBuffer<Env::Api>>,
+        Arg1: ProxyArg<u64>,
+    >(
+        self,
+        domain: Arg0,
+        context_id: Arg1,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, u32> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("getEntropyContributionCount")
+            .argument(&domain)
+            .argument(&context_id)
+            .original_result()
+    }
+
+    pub fn is_entropy_sealed<
+        Arg0: ProxyArg<ManagedBuffer<Env::Api>>,
+        Arg1: ProxyArg<u64>,
+    >(
+        self,
+        domain: Arg0,
+        context_id: Arg1,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, bool> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("isEntropySealed")
+            .argument(&domain)
+            .argument(&context_id)
+            .original_result()
+    }
+
+    pub fn get_random_draw_nonce<
+        Arg0: ProxyArg<ManagedBuffer<Env::Api>>,
+    >(
+        self,
+        domain: Arg0,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, u64> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("getRandomDrawNonce")
+            .argument(&domain)
+            .original_result()
+    }
+
+    pub fn grant_role<
+        Arg0: ProxyArg<wine_roles::Role>,
+        Arg1: ProxyArg<ManagedAddress<Env::Api>>,
+    >(
+        self,
+        role: Arg0,
+        address: Arg1,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, ()> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("grantRole")
+            .argument(&role)
+            .argument(&address)
+            .original_result()
+    }
+
+    pub fn revoke_role<
+        Arg0: ProxyArg<wine_roles::Role>,
+        Arg1: ProxyArg<ManagedAddress<Env::Api>>,
+    >(
+        self,
+        role: Arg0,
+        address: Arg1,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, ()> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("revokeRole")
+            .argument(&role)
+            .argument(&address)
+            .original_result()
+    }
+
+    pub fn pause(
+        self,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, ()> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("pause")
+            .original_result()
+    }
+
+    pub fn unpause(
+        self,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, ()> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("unpause")
+            .original_result()
+    }
+
+    pub fn has_role<
+        Arg0: ProxyArg<wine_roles::Role>,
+        Arg1: ProxyArg<ManagedAddress<Env::Api>>,
+    >(
+        self,
+        role: Arg0,
+        address: Arg1,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, bool> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("hasRole")
+            .argument(&role)
+            .argument(&address)
+            .original_result()
+    }
+
+    pub fn get_role_members<
+        Arg0: ProxyArg<wine_roles::Role>,
+    >(
+        self,
+        role: Arg0,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, MultiValueEncoded<Env::Api, ManagedAddress<Env::Api>>> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("getRoleMembers")
+            .argument(&role)
+            .original_result()
+    }
+
+    pub fn is_paused(
+        self,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, bool> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("isPaused")
+            .original_result()
+    }
+}
+
+#[type_abi]
+#[derive(TopEncode, TopDecode, NestedEncode, NestedDecode, PartialEq, Debug, Clone, Copy)]
+pub enum DisputeSourceKind {
+    ProtectedSale,
+    Redemption,
+}
+
+#[type_abi]
+#[derive(TopEncode, TopDecode, PartialEq, Debug)]
+pub struct Case<Api>
+where
+    Api: ManagedTypeApi,
+{
+    pub source: ManagedAddress<Api>,
+    pub source_kind: DisputeSourceKind,
+    pub source_id: u64,
+    pub opened_timestamp: u64,
+    pub jurors: ManagedVec<Api, ManagedAddress<Api>>,
+    pub voting_deadline: u64,
+    pub votes_for_claimant: u32,
+    pub votes_against_claimant: u32,
+    pub status: CaseStatus,
+    pub claimant_wins: bool,
+}
+
+#[type_abi]
+#[derive(TopEncode, TopDecode, NestedEncode, NestedDecode, PartialEq, Debug, Clone, Copy)]
+pub enum CaseStatus {
+    AwaitingJurors,
+    Voting,
+    Ruled,
+}
+
+#[type_abi]
+#[derive(TopEncode, TopDecode, PartialEq, Debug)]
+pub struct ArbitrationConfig {
+    pub jurors_per_case: u32,
+    pub draw_delay: u64,
+    pub voting_period: u64,
+    pub slash_bps: u64,
+}
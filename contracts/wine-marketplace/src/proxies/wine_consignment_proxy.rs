@@ -0,0 +1,372 @@
+// Code generated by the multiversx-sc proxy generator. DO NOT EDIT.
+
+////////////////////////////////////////////////////
+////////////////// AUTO-GENERATED //////////////////
+////////////////////////////////////////////////////
+
+#![allow(dead_code)]
+#![allow(clippy::all)]
+
+use multiversx_sc::proxy_imports::*;
+
+pub struct WineConsignmentProxy;
+
+impl<Env, From, To, Gas> TxProxyTrait<Env, From, To, Gas> for WineConsignmentProxy
+where
+    Env: TxEnv,
+    From: TxFrom<Env>,
+    To: TxTo<Env>,
+    Gas: TxGas<Env>,
+{
+    type TxProxyMethods = WineConsignmentProxyMethods<Env, From, To, Gas>;
+
+    fn proxy_methods(self, tx: Tx<Env, From, To, (), Gas, (), ()>) -> Self::TxProxyMethods {
+        WineConsignmentProxyMethods { wrapped_tx: tx }
+    }
+}
+
+pub struct WineConsignmentProxyMethods<Env, From, To, Gas>
+where
+    Env: TxEnv,
+    From: TxFrom<Env>,
+    To: TxTo<Env>,
+    Gas: TxGas<Env>,
+{
+    wrapped_tx: Tx<Env, From, To, (), Gas, (), ()>,
+}
+
+#[rustfmt::skip]
+impl<Env, From, Gas> WineConsignmentProxyMethods<Env, From, (), Gas>
+where
+    Env: TxEnv,
+    Env::Api: VMApi,
+    From: TxFrom<Env>,
+    Gas: TxGas<Env>,
+{
+    pub fn init<
+        Arg0: ProxyArg<ManagedAddress<Env::Api>>,
+        Arg1: ProxyArg<TokenIdentifier<Env::Api>>,
+    >(
+        self,
+        marketplace: Arg0,
+        wine_collection: Arg1,
+    ) -> TxTypedDeploy<Env, From, NotPayable, Gas, ()> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_deploy()
+            .argument(&marketplace)
+            .argument(&wine_collection)
+            .original_result()
+    }
+}
+
+#[rustfmt::skip]
+impl<Env, From, To, Gas> WineConsignmentProxyMethods<Env, From, To, Gas>
+where
+    Env: TxEnv,
+    Env::Api: VMApi,
+    From: TxFrom<Env>,
+    To: TxTo<Env>,
+    Gas: TxGas<Env>,
+{
+    pub fn upgrade(
+        self,
+    ) -> TxTypedUpgrade<Env, From, To, NotPayable, Gas, ()> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_upgrade()
+            .original_result()
+    }
+}
+
+#[rustfmt::skip]
+impl<Env, From, To, Gas> WineConsignmentProxyMethods<Env, From, To, Gas>
+where
+    Env: TxEnv,
+    Env::Api: VMApi,
+    From: TxFrom<Env>,
+    To: TxTo<Env>,
+    Gas: TxGas<Env>,
+{
+    pub fn set_marketplace<
+        Arg0: ProxyArg<ManagedAddress<Env::Api>>,
+    >(
+        self,
+        marketplace: Arg0,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, ()> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("setMarketplace")
+            .argument(&marketplace)
+            .original_result()
+    }
+
+    pub fn consign_wine<
+        Arg0: ProxyArg<ManagedAddress<Env::Api>>,
+        Arg1: ProxyArg<EgldOrEsdtTokenIdentifier<Env::Api>>,
+        Arg2: ProxyArg<BigUint<Env::Api>>,
+        Arg3: ProxyArg<u64>,
+        Arg4: ProxyArg<u64>,
+    >(
+        self,
+        merchant: Arg0,
+        payment_token: Arg1,
+        min_price: Arg2,
+        commission_bps: Arg3,
+        duration_seconds: Arg4,
+    ) -> TxTypedCall<Env, From, To, (), Gas, u32> {
+        self.wrapped_tx
+            .raw_call("consignWine")
+            .argument(&merchant)
+            .argument(&payment_token)
+            .argument(&min_price)
+            .argument(&commission_bps)
+            .argument(&duration_seconds)
+            .original_result()
+    }
+
+    pub fn list_consigned_wine<
+        Arg0: ProxyArg<u32>,
+        Arg1: ProxyArg<BigUint<Env::Api>>,
+        Arg2: ProxyArg<u64>,
+    >(
+        self,
+        consignment_id: Arg0,
+        price: Arg1,
+        duration_seconds: Arg2,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, ()> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("listConsignedWine")
+            .argument(&consignment_id)
+            .argument(&price)
+            .argument(&duration_seconds)
+            .original_result()
+    }
+
+    pub fn delist_consigned_wine<
+        Arg0: ProxyArg<u32>,
+        Arg1: ProxyArg<u32>,
+    >(
+        self,
+        consignment_id: Arg0,
+        listing_id: Arg1,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, ()> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("delistConsignedWine")
+            .argument(&consignment_id)
+            .argument(&listing_id)
+            .original_result()
+    }
+
+    pub fn consignment_sale_proceeds<
+        Arg0: ProxyArg<TokenIdentifier<Env::Api>>,
+        Arg1: ProxyArg<u64>,
+    >(
+        self,
+        collection: Arg0,
+        nonce: Arg1,
+    ) -> TxTypedCall<Env, From, To, (), Gas, ()> {
+        self.wrapped_tx
+            .raw_call("consignmentSaleProceeds")
+            .argument(&collection)
+            .argument(&nonce)
+            .original_result()
+    }
+
+    pub fn close_consignment<
+        Arg0: ProxyArg<u32>,
+    >(
+        self,
+        consignment_id: Arg0,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, ()> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("closeConsignment")
+            .argument(&consignment_id)
+            .original_result()
+    }
+
+    pub fn get_marketplace(
+        self,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, ManagedAddress<Env::Api>> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("getMarketplace")
+            .original_result()
+    }
+
+    pub fn get_wine_collection(
+        self,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, TokenIdentifier<Env::Api>> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("getWineCollection")
+            .original_result()
+    }
+
+    pub fn get_consignment<
+        Arg0: ProxyArg<u32>,
+    >(
+        self,
+        consignment_id: Arg0,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, Consignment<Env::Api>> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("getConsignment")
+            .argument(&consignment_id)
+            .original_result()
+    }
+
+    pub fn get_consignment_count(
+        self,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, u32> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("getConsignmentCount")
+            .original_result()
+    }
+
+    pub fn get_open_consignment<
+        Arg0: ProxyArg<u64>,
+    >(
+        self,
+        wine_nonce: Arg0,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, OptionalValue<u32>> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("getOpenConsignment")
+            .argument(&wine_nonce)
+            .original_result()
+    }
+
+    pub fn get_escrowed_bottles<
+        Arg0: ProxyArg<u32>,
+    >(
+        self,
+        consignment_id: Arg0,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, u64> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("getEscrowedBottles")
+            .argument(&consignment_id)
+            .original_result()
+    }
+
+    pub fn grant_role<
+        Arg0: ProxyArg<wine_roles::Role>,
+        Arg1: ProxyArg<ManagedAddress<Env::Api>>,
+    >(
+        self,
+        role: Arg0,
+        address: Arg1,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, ()> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("grantRole")
+            .argument(&role)
+            .argument(&address)
+            .original_result()
+    }
+
+    pub fn revoke_role<
+        Arg0: ProxyArg<wine_roles::Role>,
+        Arg1: ProxyArg<ManagedAddress<Env::Api>>,
+    >(
+        self,
+        role: Arg0,
+        address: Arg1,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, ()> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("revokeRole")
+            .argument(&role)
+            .argument(&address)
+            .original_result()
+    }
+
+    pub fn pause(
+        self,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, ()> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("pause")
+            .original_result()
+    }
+
+    pub fn unpause(
+        self,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, ()> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("unpause")
+            .original_result()
+    }
+
+    pub fn has_role<
+        Arg0: ProxyArg<wine_roles::Role>,
+        Arg1: ProxyArg<ManagedAddress<Env::Api>>,
+    >(
+        self,
+        role: Arg0,
+        address: Arg1,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, bool> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("hasRole")
+            .argument(&role)
+            .argument(&address)
+            .original_result()
+    }
+
+    pub fn get_role_members<
+        Arg0: ProxyArg<wine_roles::Role>,
+    >(
+        self,
+        role: Arg0,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, MultiValueEncoded<Env::Api, ManagedAddress<Env::Api>>> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("getRoleMembers")
+            .argument(&role)
+            .original_result()
+    }
+
+    pub fn is_paused(
+        self,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, bool> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("isPaused")
+            .original_result()
+    }
+}
+
+#[type_abi]
+#[derive(TopEncode, TopDecode, PartialEq, Debug)]
+pub struct Consignment<Api>
+where
+    Api: ManagedTypeApi,
+{
+    pub producer: ManagedAddress<Api>,
+    pub merchant: ManagedAddress<Api>,
+    pub wine_id: u32,
+    pub wine_nonce: u64,
+    pub bottles: u32,
+    pub sold: u32,
+    pub payment_token: EgldOrEsdtTokenIdentifier<Api>,
+    pub min_price: BigUint<Api>,
+    pub commission_bps: u64,
+    pub end_timestamp: u64,
+    pub producer_proceeds: BigUint<Api>,
+    pub merchant_commission: BigUint<Api>,
+    pub status: ConsignmentStatus,
+}
+
+#[type_abi]
+#[derive(TopEncode, TopDecode, NestedEncode, NestedDecode, PartialEq, Debug, Clone, Copy)]
+pub enum ConsignmentStatus {
+    Open,
+    Closed,
+}
@@ -0,0 +1,109 @@
+use multiversx_sc::derive_imports::*;
+use multiversx_sc::imports::*;
+use wine_common::errors::*;
+
+// What a purchase or bid costs and how the sale would be split, at the fee rate, promotion
+// and seller discount in force when queried. Either can run out before the transaction is
+// processed, in which case the settlement follows the rates of that moment.
+#[derive(TopEncode, TopDecode, TypeAbi, PartialEq, Debug)]
+pub struct SaleQuote<M: ManagedTypeApi> {
+    pub payment_token: EgldOrEsdtTokenIdentifier<M>,
+    // Listing price in the payment token, or the minimum next bid
+    pub amount_due: BigUint<M>,
+    pub fee_percent: u64, // basis points, the promotional rate if one is active
+    pub fee_discount_bps: u64,
+    pub marketplace_fee: BigUint<M>,
+    pub royalty: BigUint<M>,
+    pub seller_amount: BigUint<M>,
+}
+
+// Read-only simulations of buyWine and placeBid, so wallets can show totals without
+// re-implementing fee logic off-chain
+#[multiversx_sc::module]
+pub trait QuotesModule:
+    wine_roles::RolesModule
+    + crate::admin::AdminModule
+    + crate::collector_tiers::CollectorTiersModule
+    + crate::loyalty::LoyaltyModule
+    + crate::fee_discounts::FeeDiscountsModule
+    + crate::stats_snapshots::StatsSnapshotsModule
+    + crate::treasury::TreasuryModule
+    + crate::blacklist::BlacklistModule
+    + crate::seller_limits::SellerLimitsModule
+    + crate::registry_cache::RegistryCacheModule
+    + crate::market_summary::MarketSummaryModule
+    + crate::protected_sale::ProtectedSaleModule
+    + crate::exchange_rates::ExchangeRatesModule
+    + crate::listing::ListingModule
+    + crate::auction_archive::AuctionArchiveModule
+    + crate::auction::AuctionModule
+{
+    // Fails where buyWine would for `payer`. Without a token the quote is in the one the
+    // listing is priced in.
+    #[view(quoteBuy)]
+    fn quote_buy(
+        &self,
+        listing_id: u32,
+        payer: ManagedAddress,
+        opt_payment_token: OptionalValue<EgldOrEsdtTokenIdentifier>,
+    ) -> SaleQuote<Self::Api> {
+        self.require_not_paused();
+        let listing = self.listings(listing_id).get();
+        require!(listing.active, ERR_LISTING_IS_NOT_ACTIVE);
+        require!(
+            self.blockchain().get_block_timestamp() <= listing.deadline,
+            ERR_LISTING_HAS_EXPIRED
+        );
+        self.require_not_blacklisted(&payer);
+        require!(payer != listing.seller, ERR_CANNOT_BUY_YOUR_OWN_LISTING);
+
+        let payment_token = match opt_payment_token {
+            OptionalValue::Some(token) => token,
+            OptionalValue::None => listing.payment_token.clone(),
+        };
+        let price = self.listing_price_in(listing_id, &listing, &payment_token);
+        self.quote_sale(&listing.seller, &listing.nft_token_id, payment_token, price)
+    }
+
+    // Minimum next bid on an English auction, split as if the auction settled at it
+    #[view(quoteNextBid)]
+    fn quote_next_bid(&self, auction_id: u32) -> SaleQuote<Self::Api> {
+        let auction = self.auctions(auction_id).get();
+        require!(auction.active, ERR_AUCTION_IS_NOT_ACTIVE);
+        require!(
+            self.blockchain().get_block_timestamp() < auction.end_timestamp,
+            ERR_AUCTION_HAS_ENDED
+        );
+
+        let min_bid = self.min_next_bid(&auction);
+        self.quote_sale(&auction.seller, &auction.nft_token_id, auction.payment_token, min_bid)
+    }
+
+    // Internal helpers
+    fn quote_sale(
+        &self,
+        seller: &ManagedAddress,
+        collection: &TokenIdentifier,
+        payment_token: EgldOrEsdtTokenIdentifier,
+        amount_due: BigUint,
+    ) -> SaleQuote<Self::Api> {
+        let fee_percent = self.sale_fee_percent(collection);
+        let fee_discount_bps = if fee_percent > 0 {
+            self.pending_fee_discount_bps(seller)
+        } else {
+            0
+        };
+        let marketplace_fee = self.discounted_fee(&amount_due, fee_percent, fee_discount_bps);
+        let seller_amount = &amount_due - &marketplace_fee;
+
+        SaleQuote {
+            payment_token,
+            amount_due,
+            fee_percent,
+            fee_discount_bps,
+            marketplace_fee,
+            royalty: BigUint::zero(),
+            seller_amount,
+        }
+    }
+}
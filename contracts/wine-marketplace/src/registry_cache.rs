@@ -0,0 +1,328 @@
+use multiversx_sc::imports::*;
+use wine_common::errors::*;
+use wine_roles::Role;
+
+use crate::proxies::wine_registry_proxy::WineRegistryProxy;
+pub use wine_common::{WineNftAttributes, WineReadModel, WineStatus};
+
+// Gas forwarded to the registry's lockWine, unlockWine and recordVerifiedBuyer
+pub const REGISTRY_LOCK_GAS: u64 = 5_000_000;
+
+// Local copy of registry data, kept current by the registry so sale paths can read it
+// without a cross-shard call
+#[multiversx_sc::module]
+pub trait RegistryCacheModule: wine_roles::RolesModule + crate::admin::AdminModule {
+    #[endpoint(syncWineReadModel)]
+    fn sync_wine_read_model(&self, wine_id: u32, read_model: WineReadModel<Self::Api>) {
+        let caller = self.blockchain().get_caller();
+        require!(
+            caller == self.wine_registry_address().get(),
+            ERR_ONLY_THE_WINE_REGISTRY_CAN_SYNC_WINE_DATA
+        );
+
+        self.wine_read_models(wine_id).set(&read_model);
+        self.wine_read_model_synced_event(wine_id, &read_model);
+    }
+
+    // Pushed by the registry whenever a certification authority verifies or revokes a producer
+    #[endpoint(syncProducerVerification)]
+    fn sync_producer_verification(&self, producer: ManagedAddress, verified: bool) {
+        let caller = self.blockchain().get_caller();
+        require!(
+            caller == self.wine_registry_address().get(),
+            ERR_ONLY_THE_WINE_REGISTRY_CAN_SYNC_WINE_DATA
+        );
+
+        if verified {
+            self.verified_producers().insert(producer.clone());
+        } else {
+            self.verified_producers().swap_remove(&producer);
+        }
+        self.producer_verification_synced_event(&producer, verified);
+    }
+
+    // Pushed by the registry whenever the recommended minimum price of a grade changes
+    #[endpoint(syncGradePriceFloor)]
+    fn sync_grade_price_floor(&self, quality_grade: u8, min_price: BigUint) {
+        let caller = self.blockchain().get_caller();
+        require!(
+            caller == self.wine_registry_address().get(),
+            ERR_ONLY_THE_WINE_REGISTRY_CAN_SYNC_WINE_DATA
+        );
+
+        self.grade_price_floors(quality_grade).set(&min_price);
+        self.grade_price_floor_synced_event(quality_grade, &min_price);
+    }
+
+    // When enabled, certified wines cannot be listed below the registry's floor for their grade
+    #[endpoint(setEnforcePriceFloors)]
+    fn set_enforce_price_floors(&self, enforced: bool) {
+        self.require_role(Role::Admin);
+        self.enforce_price_floors().set(enforced);
+    }
+
+    // When enabled, producers must be verified to list or auction their own wines (primary
+    // sales). Resales by other holders are not affected.
+    #[endpoint(setRequireVerifiedProducers)]
+    fn set_require_verified_producers(&self, required: bool) {
+        self.require_role(Role::Admin);
+        self.require_verified_producers().set(required);
+    }
+
+    // When enabled, English and candle auctions only accept wines with a valid certification
+    // in the registry, for all collections or only for the given one
+    #[endpoint(setCertifiedOnlyAuctions)]
+    fn set_certified_only_auctions(
+        &self,
+        enabled: bool,
+        opt_collection: OptionalValue<TokenIdentifier>,
+    ) {
+        self.require_role(Role::Admin);
+        match opt_collection {
+            OptionalValue::Some(collection) => {
+                if enabled {
+                    self.certified_only_auction_collections().insert(collection);
+                } else {
+                    self.certified_only_auction_collections().swap_remove(&collection);
+                }
+            },
+            OptionalValue::None => self.certified_only_auctions().set(enabled),
+        }
+    }
+
+    // The registry's wine SFT collection. Only its tokens can be listed or auctioned, as the
+    // wine id of an item is read from the attributes the registry minted it with.
+    #[endpoint(setWineCollection)]
+    fn set_wine_collection(&self, collection: TokenIdentifier) {
+        self.require_role(Role::Admin);
+        require!(collection.is_valid_esdt_identifier(), ERR_INVALID_TOKEN_IDENTIFIER);
+        self.wine_collection().set(&collection);
+    }
+
+    // Internal helpers
+    // Fails unless the item held here at `(token_id, nonce)` is a bottle of `wine_id`, so
+    // registry gates and locks apply to the wine actually escrowed
+    fn require_escrowed_wine(&self, wine_id: u32, token_id: &TokenIdentifier, nonce: u64) {
+        let wine_collection = self.wine_collection();
+        require!(!wine_collection.is_empty(), ERR_WINE_COLLECTION_NOT_SET);
+        require!(*token_id == wine_collection.get(), ERR_NOT_A_WINE_NFT);
+
+        let token_data =
+            self.blockchain().get_esdt_token_data(&self.blockchain().get_sc_address(), token_id, nonce);
+        let attributes: WineNftAttributes<Self::Api> = token_data.decode_attributes();
+        require!(attributes.wine_id == wine_id, ERR_WINE_ID_DOES_NOT_MATCH_NFT);
+    }
+
+    // While its owner has one of its items in escrow here, the registry rejects owner
+    // transfers and availability edits of the wine. A synchronous call, so the registry is
+    // deployed on this marketplace's shard: when it rejects the lock, e.g. because co-owners
+    // have not approved handing the wine over, the listing or auction escrowing it reverts.
+    fn lock_wine_in_registry(&self, wine_id: u32, holder: &ManagedAddress) {
+        self.tx()
+            .to(&self.wine_registry_address().get())
+            .typed(WineRegistryProxy)
+            .lock_wine(wine_id, holder)
+            .gas(REGISTRY_LOCK_GAS)
+            .sync_call();
+    }
+
+    // Fire-and-forget, so a registry that has revoked this marketplace does not keep an
+    // item from being sold or returned to its seller
+    fn unlock_wine_in_registry(&self, wine_id: u32, holder: &ManagedAddress) {
+        self.tx()
+            .to(&self.wine_registry_address().get())
+            .typed(WineRegistryProxy)
+            .unlock_wine(wine_id, holder)
+            .gas(REGISTRY_LOCK_GAS)
+            .transfer_execute();
+    }
+
+    // Lets the buyer's rating of the wine count as a verified purchase. Fire-and-forget like
+    // the unlocks; the registry only records buyers for marketplaces allowed to record sales.
+    fn record_verified_buyer_in_registry(&self, wine_id: u32, buyer: &ManagedAddress) {
+        self.tx()
+            .to(&self.wine_registry_address().get())
+            .typed(WineRegistryProxy)
+            .record_verified_buyer(wine_id, buyer)
+            .gas(REGISTRY_LOCK_GAS)
+            .transfer_execute();
+    }
+
+    fn cached_wine_read_model(&self, wine_id: u32) -> Option<WineReadModel<Self::Api>> {
+        let read_model_mapper = self.wine_read_models(wine_id);
+        if read_model_mapper.is_empty() {
+            None
+        } else {
+            Some(read_model_mapper.get())
+        }
+    }
+
+    // Wines the registry has not pushed (yet) count as not recalled
+    fn is_wine_recalled(&self, wine_id: u32) -> bool {
+        match self.cached_wine_read_model(wine_id) {
+            Some(read_model) => read_model.status == WineStatus::Recalled,
+            None => false,
+        }
+    }
+
+    // Wines the registry has not pushed (yet) cannot be shown to pass any registry gate
+    fn require_synced_read_model(&self, wine_id: u32) -> WineReadModel<Self::Api> {
+        let read_model_mapper = self.wine_read_models(wine_id);
+        require!(!read_model_mapper.is_empty(), ERR_WINE_NOT_SYNCED_FROM_REGISTRY);
+        read_model_mapper.get()
+    }
+
+    // Checked before a wine is listed or auctioned
+    fn require_wine_listable(&self, wine_id: u32, seller: &ManagedAddress) {
+        let read_model = self.require_synced_read_model(wine_id);
+        require!(read_model.status != WineStatus::Recalled, ERR_WINE_HAS_BEEN_RECALLED);
+        require!(read_model.status != WineStatus::Retired, ERR_WINE_HAS_BEEN_RETIRED);
+        require!(!read_model.counterfeit, ERR_WINE_HAS_BEEN_FLAGGED_AS_COUNTERFEIT);
+        self.require_primary_sale_allowed(&read_model, seller);
+    }
+
+    // Checked on every path a producer can sell their own wine through
+    fn require_primary_sale_allowed(
+        &self,
+        read_model: &WineReadModel<Self::Api>,
+        seller: &ManagedAddress,
+    ) {
+        if self.require_verified_producers().get() {
+            require!(
+                read_model.producer != *seller || self.verified_producers().contains(seller),
+                ERR_PRODUCER_IS_NOT_VERIFIED
+            );
+        }
+    }
+
+    // Minimum EGLD price of a wine under the registry's grade floors; zero when none applies.
+    // Only certified wines have a floor, as the grade of the others is self-declared.
+    fn wine_price_floor(&self, wine_id: u32) -> BigUint {
+        match self.cached_wine_read_model(wine_id) {
+            Some(read_model) => self.read_model_price_floor(&read_model),
+            None => BigUint::zero(),
+        }
+    }
+
+    fn read_model_price_floor(&self, read_model: &WineReadModel<Self::Api>) -> BigUint {
+        if self.is_certified_now(read_model) {
+            self.grade_price_floors(read_model.quality_grade).get()
+        } else {
+            BigUint::zero()
+        }
+    }
+
+    // The read model stays certified while one certification is not revoked, until the
+    // latest expiry of the attestations backing them
+    fn is_certified_now(&self, read_model: &WineReadModel<Self::Api>) -> bool {
+        read_model.certified
+            && (read_model.certified_until == 0
+                || self.blockchain().get_block_timestamp() <= read_model.certified_until)
+    }
+
+    // Wines the registry has not pushed (yet) cannot be shown to be certified
+    fn require_certified_for_auction(&self, wine_id: u32, collection: &TokenIdentifier) {
+        if !self.certified_only_auctions().get()
+            && !self.certified_only_auction_collections().contains(collection)
+        {
+            return;
+        }
+
+        let certified = match self.cached_wine_read_model(wine_id) {
+            Some(read_model) => self.is_certified_now(&read_model),
+            None => false,
+        };
+        require!(certified, ERR_ONLY_CERTIFIED_WINES_CAN_BE_AUCTIONED);
+    }
+
+    // View functions
+    #[view(getWineCollection)]
+    fn get_wine_collection(&self) -> OptionalValue<TokenIdentifier> {
+        if self.wine_collection().is_empty() {
+            OptionalValue::None
+        } else {
+            OptionalValue::Some(self.wine_collection().get())
+        }
+    }
+
+    #[view(getCachedWineReadModel)]
+    fn get_cached_wine_read_model(&self, wine_id: u32) -> OptionalValue<WineReadModel<Self::Api>> {
+        self.cached_wine_read_model(wine_id).into()
+    }
+
+    #[view(isProducerVerified)]
+    fn is_producer_verified(&self, producer: &ManagedAddress) -> bool {
+        self.verified_producers().contains(producer)
+    }
+
+    #[view(getRequireVerifiedProducers)]
+    fn get_require_verified_producers(&self) -> bool {
+        self.require_verified_producers().get()
+    }
+
+    #[view(getWinePriceFloor)]
+    fn get_wine_price_floor(&self, wine_id: u32) -> BigUint {
+        self.wine_price_floor(wine_id)
+    }
+
+    #[view(getGradePriceFloor)]
+    fn get_grade_price_floor(&self, quality_grade: u8) -> BigUint {
+        self.grade_price_floors(quality_grade).get()
+    }
+
+    #[view(getEnforcePriceFloors)]
+    fn get_enforce_price_floors(&self) -> bool {
+        self.enforce_price_floors().get()
+    }
+
+    // Whether certified-only auctions apply to all collections, and the collections they apply
+    // to otherwise
+    #[view(getCertifiedOnlyAuctions)]
+    fn get_certified_only_auctions(&self) -> MultiValue2<bool, MultiValueEncoded<TokenIdentifier>> {
+        let collections = self.certified_only_auction_collections().iter().collect();
+        (self.certified_only_auctions().get(), collections).into()
+    }
+
+    // Storage mappers
+    #[storage_mapper("wineCollection")]
+    fn wine_collection(&self) -> SingleValueMapper<TokenIdentifier>;
+
+    #[storage_mapper("verifiedProducers")]
+    fn verified_producers(&self) -> UnorderedSetMapper<ManagedAddress>;
+
+    #[storage_mapper("requireVerifiedProducers")]
+    fn require_verified_producers(&self) -> SingleValueMapper<bool>;
+
+    #[storage_mapper("certifiedOnlyAuctions")]
+    fn certified_only_auctions(&self) -> SingleValueMapper<bool>;
+
+    #[storage_mapper("certifiedOnlyAuctionCollections")]
+    fn certified_only_auction_collections(&self) -> UnorderedSetMapper<TokenIdentifier>;
+
+    #[storage_mapper("gradePriceFloors")]
+    fn grade_price_floors(&self, quality_grade: u8) -> SingleValueMapper<BigUint>;
+
+    #[storage_mapper("enforcePriceFloors")]
+    fn enforce_price_floors(&self) -> SingleValueMapper<bool>;
+
+    #[storage_mapper("wineReadModels")]
+    fn wine_read_models(&self, wine_id: u32) -> SingleValueMapper<WineReadModel<Self::Api>>;
+
+    // Events
+    #[event("wineReadModelSynced")]
+    fn wine_read_model_synced_event(
+        &self,
+        #[indexed] wine_id: u32,
+        read_model: &WineReadModel<Self::Api>,
+    );
+
+    #[event("producerVerificationSynced")]
+    fn producer_verification_synced_event(
+        &self,
+        #[indexed] producer: &ManagedAddress,
+        verified: bool,
+    );
+
+    #[event("gradePriceFloorSynced")]
+    fn grade_price_floor_synced_event(&self, #[indexed] quality_grade: u8, min_price: &BigUint);
+}
@@ -0,0 +1,286 @@
+use multiversx_sc::derive_imports::*;
+use multiversx_sc::imports::*;
+use wine_common::errors::*;
+use wine_roles::Role;
+
+// A buyer's signed request to buy a listing at no more than `max_price`, in the listing's
+// payment token. `nonce` must be the buyer's next relay nonce.
+#[derive(TopEncode, TopDecode, NestedEncode, NestedDecode, TypeAbi, PartialEq, Debug, Clone)]
+pub struct PurchaseIntent<M: ManagedTypeApi> {
+    pub buyer: ManagedAddress<M>,
+    pub listing_id: u32,
+    pub max_price: BigUint<M>,
+    pub nonce: u64,
+    pub deadline: u64,
+}
+
+// A bidder's signed request to bid exactly `amount` on an auction, in its payment token
+#[derive(TopEncode, TopDecode, NestedEncode, NestedDecode, TypeAbi, PartialEq, Debug, Clone)]
+pub struct BidIntent<M: ManagedTypeApi> {
+    pub bidder: ManagedAddress<M>,
+    pub auction_id: u32,
+    pub amount: BigUint<M>,
+    pub nonce: u64,
+    pub deadline: u64,
+}
+
+// Gasless purchases and bids. Users without EGLD for gas hold a relay balance here, funded
+// by themselves through a native relayed transaction or by anyone on their behalf, e.g. a
+// stablecoin on-ramp. They sign intents off-chain with their account key, and whitelisted
+// relayers submit them and pay the gas. The NFT goes to the signer; intents name no gift
+// recipient, so a relayer cannot redirect it.
+#[multiversx_sc::module]
+pub trait RelayedPurchasesModule:
+    wine_roles::RolesModule
+    + crate::admin::AdminModule
+    + crate::collector_tiers::CollectorTiersModule
+    + crate::loyalty::LoyaltyModule
+    + crate::fee_discounts::FeeDiscountsModule
+    + crate::stats_snapshots::StatsSnapshotsModule
+    + crate::treasury::TreasuryModule
+    + crate::blacklist::BlacklistModule
+    + crate::seller_limits::SellerLimitsModule
+    + crate::registry_cache::RegistryCacheModule
+    + crate::market_summary::MarketSummaryModule
+    + crate::protected_sale::ProtectedSaleModule
+    + crate::exchange_rates::ExchangeRatesModule
+    + crate::listing::ListingModule
+    + crate::auction_archive::AuctionArchiveModule
+    + crate::auction::AuctionModule
+{
+    #[endpoint(addRelayer)]
+    fn add_relayer(&self, relayer: ManagedAddress) {
+        self.require_role(Role::Admin);
+        require!(self.relayers().insert(relayer.clone()), ERR_ALREADY_A_RELAYER);
+        self.relayer_added_event(&relayer);
+    }
+
+    #[endpoint(removeRelayer)]
+    fn remove_relayer(&self, relayer: ManagedAddress) {
+        self.require_role(Role::Admin);
+        require!(self.relayers().swap_remove(&relayer), ERR_NOT_A_RELAYER);
+        self.relayer_removed_event(&relayer);
+    }
+
+    // Credit the payment to the relay balance of `beneficiary`, the caller by default
+    #[payable("*")]
+    #[endpoint(depositRelayFunds)]
+    fn deposit_relay_funds(&self, opt_beneficiary: OptionalValue<ManagedAddress>) {
+        self.require_not_paused();
+        let payment = self.call_value().egld_or_single_esdt();
+        self.require_supported_payment_token(&payment.token_identifier);
+        self.require_fungible(&payment);
+        require!(payment.amount > 0u32, ERR_PAYMENT_MUST_BE_GREATER_THAN_ZERO);
+
+        let caller = self.blockchain().get_caller();
+        let beneficiary = self.resolve_recipient(&caller, opt_beneficiary);
+        self.relay_balance(&beneficiary, &payment.token_identifier)
+            .update(|balance| *balance += &payment.amount);
+
+        self.relay_funds_deposited_event(
+            &beneficiary,
+            &caller,
+            &payment.token_identifier,
+            &payment.amount,
+        );
+    }
+
+    // Withdraw the caller's whole relay balance in `token`
+    #[endpoint(withdrawRelayFunds)]
+    fn withdraw_relay_funds(&self, token: EgldOrEsdtTokenIdentifier) {
+        let caller = self.blockchain().get_caller();
+        let amount = self.relay_balance(&caller, &token).take();
+        require!(amount > 0u32, ERR_NOTHING_TO_WITHDRAW);
+
+        self.send_payment(&caller, &token, &amount);
+        self.relay_funds_withdrawn_event(&caller, &token, &amount);
+    }
+
+    // Buy the listing for the intent's signer, paying the current price out of their relay
+    // balance
+    #[endpoint(executePurchaseIntent)]
+    fn execute_purchase_intent(&self, intent: PurchaseIntent<Self::Api>, signature: ManagedBuffer) {
+        self.require_not_paused();
+        let relayer = self.require_relayer();
+        let message = self.purchase_intent_message(&intent);
+        self.consume_intent(&intent.buyer, intent.nonce, intent.deadline, &message, &signature);
+
+        let listing = self.require_open_listing(intent.listing_id);
+        require!(listing.price <= intent.max_price, ERR_PRICE_EXCEEDS_INTENT_MAXIMUM);
+        let payment_token = listing.payment_token.clone();
+        let price = listing.price.clone();
+        self.debit_relay_balance(&intent.buyer, &payment_token, &price);
+
+        let payment = EgldOrEsdtTokenPayment::new(payment_token, 0, price);
+        self.buy_listing(intent.listing_id, listing, &intent.buyer, payment, OptionalValue::None);
+
+        self.purchase_intent_executed_event(intent.listing_id, &intent.buyer, &relayer, intent.nonce);
+    }
+
+    // Place the intent's bid for its signer out of their relay balance. Outbid funds are
+    // handled as for any other bid.
+    #[endpoint(executeBidIntent)]
+    fn execute_bid_intent(&self, intent: BidIntent<Self::Api>, signature: ManagedBuffer) {
+        self.require_not_paused();
+        let relayer = self.require_relayer();
+        let message = self.bid_intent_message(&intent);
+        self.consume_intent(&intent.bidder, intent.nonce, intent.deadline, &message, &signature);
+
+        let auction = self.require_open_auction(intent.auction_id);
+        self.debit_relay_balance(&intent.bidder, &auction.payment_token, &intent.amount);
+        self.record_bid(
+            intent.auction_id,
+            auction,
+            intent.bidder.clone(),
+            intent.amount,
+            OptionalValue::None,
+        );
+
+        self.bid_intent_executed_event(intent.auction_id, &intent.bidder, &relayer, intent.nonce);
+    }
+
+    // Internal helpers
+    fn require_relayer(&self) -> ManagedAddress {
+        let caller = self.blockchain().get_caller();
+        require!(self.relayers().contains(&caller), ERR_CALLER_IS_NOT_A_RELAYER);
+        caller
+    }
+
+    // Checks deadline, nonce and signature, then uses up the nonce. An account's public key
+    // is its address, so the signer needs no separate key registration.
+    fn consume_intent(
+        &self,
+        signer: &ManagedAddress,
+        nonce: u64,
+        deadline: u64,
+        message: &ManagedBuffer,
+        signature: &ManagedBuffer,
+    ) {
+        require!(self.blockchain().get_block_timestamp() <= deadline, ERR_INTENT_HAS_EXPIRED);
+        let nonce_mapper = self.relay_nonce(signer);
+        require!(nonce == nonce_mapper.get(), ERR_INVALID_INTENT_NONCE);
+        require!(signature.len() == 64, ERR_INVALID_INTENT_SIGNATURE);
+        self.crypto().verify_ed25519(signer.as_managed_buffer(), message, signature);
+
+        nonce_mapper.set(nonce + 1);
+    }
+
+    fn debit_relay_balance(
+        &self,
+        user: &ManagedAddress,
+        token: &EgldOrEsdtTokenIdentifier,
+        amount: &BigUint,
+    ) {
+        let balance_mapper = self.relay_balance(user, token);
+        let balance = balance_mapper.get();
+        require!(balance >= *amount, ERR_INSUFFICIENT_RELAY_BALANCE);
+        balance_mapper.set(balance - amount);
+    }
+
+    // The endpoint name keeps a purchase signature from being replayed as a bid, and the
+    // contract address from being replayed on another marketplace
+    fn purchase_intent_message(&self, intent: &PurchaseIntent<Self::Api>) -> ManagedBuffer {
+        self.serializer().top_encode_to_managed_buffer(&(
+            self.blockchain().get_sc_address(),
+            ManagedBuffer::from(b"executePurchaseIntent"),
+            intent.clone(),
+        ))
+    }
+
+    fn bid_intent_message(&self, intent: &BidIntent<Self::Api>) -> ManagedBuffer {
+        self.serializer().top_encode_to_managed_buffer(&(
+            self.blockchain().get_sc_address(),
+            ManagedBuffer::from(b"executeBidIntent"),
+            intent.clone(),
+        ))
+    }
+
+    // View functions
+    // The bytes a buyer signs for `intent`
+    #[view(getPurchaseIntentMessage)]
+    fn get_purchase_intent_message(&self, intent: PurchaseIntent<Self::Api>) -> ManagedBuffer {
+        self.purchase_intent_message(&intent)
+    }
+
+    #[view(getBidIntentMessage)]
+    fn get_bid_intent_message(&self, intent: BidIntent<Self::Api>) -> ManagedBuffer {
+        self.bid_intent_message(&intent)
+    }
+
+    #[view(getRelayers)]
+    fn get_relayers(&self) -> MultiValueEncoded<ManagedAddress> {
+        self.relayers().iter().collect()
+    }
+
+    #[view(isRelayer)]
+    fn is_relayer(&self, address: ManagedAddress) -> bool {
+        self.relayers().contains(&address)
+    }
+
+    #[view(getRelayBalance)]
+    fn get_relay_balance(&self, user: ManagedAddress, token: EgldOrEsdtTokenIdentifier) -> BigUint {
+        self.relay_balance(&user, &token).get()
+    }
+
+    // Nonce the user's next intent must carry
+    #[view(getRelayNonce)]
+    fn get_relay_nonce(&self, user: ManagedAddress) -> u64 {
+        self.relay_nonce(&user).get()
+    }
+
+    // Storage mappers
+    #[storage_mapper("relayers")]
+    fn relayers(&self) -> UnorderedSetMapper<ManagedAddress>;
+
+    #[storage_mapper("relayBalance")]
+    fn relay_balance(
+        &self,
+        user: &ManagedAddress,
+        token: &EgldOrEsdtTokenIdentifier,
+    ) -> SingleValueMapper<BigUint>;
+
+    #[storage_mapper("relayNonce")]
+    fn relay_nonce(&self, user: &ManagedAddress) -> SingleValueMapper<u64>;
+
+    // Events
+    #[event("relayerAdded")]
+    fn relayer_added_event(&self, #[indexed] relayer: &ManagedAddress);
+
+    #[event("relayerRemoved")]
+    fn relayer_removed_event(&self, #[indexed] relayer: &ManagedAddress);
+
+    #[event("relayFundsDeposited")]
+    fn relay_funds_deposited_event(
+        &self,
+        #[indexed] beneficiary: &ManagedAddress,
+        #[indexed] depositor: &ManagedAddress,
+        #[indexed] token: &EgldOrEsdtTokenIdentifier,
+        amount: &BigUint,
+    );
+
+    #[event("relayFundsWithdrawn")]
+    fn relay_funds_withdrawn_event(
+        &self,
+        #[indexed] user: &ManagedAddress,
+        #[indexed] token: &EgldOrEsdtTokenIdentifier,
+        amount: &BigUint,
+    );
+
+    #[event("purchaseIntentExecuted")]
+    fn purchase_intent_executed_event(
+        &self,
+        #[indexed] listing_id: u32,
+        #[indexed] buyer: &ManagedAddress,
+        #[indexed] relayer: &ManagedAddress,
+        nonce: u64,
+    );
+
+    #[event("bidIntentExecuted")]
+    fn bid_intent_executed_event(
+        &self,
+        #[indexed] auction_id: u32,
+        #[indexed] bidder: &ManagedAddress,
+        #[indexed] relayer: &ManagedAddress,
+        nonce: u64,
+    );
+}
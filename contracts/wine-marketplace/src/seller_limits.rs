@@ -0,0 +1,108 @@
+use multiversx_sc::imports::*;
+use wine_common::errors::*;
+use wine_roles::Role;
+
+// Cap on how many listings and auctions (English and candle combined) a seller can have
+// open at once. The limit is checked when an item is created or relisted; items only stop
+// counting once they are sold, cancelled or settled.
+#[multiversx_sc::module]
+pub trait SellerLimitsModule: wine_roles::RolesModule {
+    // 0 disables the respective limit
+    #[endpoint(setMaxActiveItems)]
+    fn set_max_active_items(&self, default_limit: u32, verified_limit: u32) {
+        self.require_role(Role::Admin);
+        require!(
+            verified_limit == 0 || (default_limit != 0 && verified_limit >= default_limit),
+            ERR_VERIFIED_LIMIT_MUST_NOT_BE_BELOW_THE_DEFAULT_LIMIT
+        );
+        self.max_active_items().set(default_limit);
+        self.max_active_items_verified().set(verified_limit);
+        self.max_active_items_updated_event(default_limit, verified_limit);
+    }
+
+    #[endpoint(setVerifiedReseller)]
+    fn set_verified_reseller(&self, reseller: ManagedAddress, verified: bool) {
+        self.require_role(Role::Admin);
+        if verified {
+            self.verified_resellers().insert(reseller.clone());
+        } else {
+            self.verified_resellers().swap_remove(&reseller);
+        }
+        self.verified_reseller_set_event(&reseller, verified);
+    }
+
+    // Internal helpers
+    fn seller_item_limit(&self, seller: &ManagedAddress) -> u32 {
+        if self.verified_resellers().contains(seller) {
+            self.max_active_items_verified().get()
+        } else {
+            self.max_active_items().get()
+        }
+    }
+
+    // Called when a seller creates a listing or auction
+    fn open_seller_item(&self, seller: &ManagedAddress) {
+        let limit = self.seller_item_limit(seller);
+        let active_items = self.active_item_count(seller).get();
+        require!(limit == 0 || active_items < limit, ERR_TOO_MANY_ACTIVE_ITEMS_FOR_THIS_SELLER);
+        self.active_item_count(seller).set(active_items + 1);
+    }
+
+    // A relisted item is already counted, so it only has to fit under the current limit
+    fn require_seller_within_item_limit(&self, seller: &ManagedAddress) {
+        let limit = self.seller_item_limit(seller);
+        require!(
+            limit == 0 || self.active_item_count(seller).get() <= limit,
+            ERR_TOO_MANY_ACTIVE_ITEMS_FOR_THIS_SELLER
+        );
+    }
+
+    // Items opened before the limit existed were never counted, hence the saturating update
+    fn close_seller_item(&self, seller: &ManagedAddress) {
+        self.active_item_count(seller).update(|count| *count = count.saturating_sub(1));
+    }
+
+    // View functions
+    #[view(getMaxActiveItems)]
+    fn get_max_active_items(&self) -> MultiValue2<u32, u32> {
+        (self.max_active_items().get(), self.max_active_items_verified().get()).into()
+    }
+
+    #[view(getActiveItemCount)]
+    fn get_active_item_count(&self, seller: &ManagedAddress) -> u32 {
+        self.active_item_count(seller).get()
+    }
+
+    #[view(isVerifiedReseller)]
+    fn is_verified_reseller(&self, reseller: &ManagedAddress) -> bool {
+        self.verified_resellers().contains(reseller)
+    }
+
+    // Storage mappers
+    #[storage_mapper("maxActiveItems")]
+    fn max_active_items(&self) -> SingleValueMapper<u32>;
+
+    #[storage_mapper("maxActiveItemsVerified")]
+    fn max_active_items_verified(&self) -> SingleValueMapper<u32>;
+
+    #[storage_mapper("verifiedResellers")]
+    fn verified_resellers(&self) -> UnorderedSetMapper<ManagedAddress>;
+
+    #[storage_mapper("activeItemCount")]
+    fn active_item_count(&self, seller: &ManagedAddress) -> SingleValueMapper<u32>;
+
+    // Events
+    #[event("maxActiveItemsUpdated")]
+    fn max_active_items_updated_event(
+        &self,
+        #[indexed] default_limit: u32,
+        verified_limit: u32,
+    );
+
+    #[event("verifiedResellerSet")]
+    fn verified_reseller_set_event(
+        &self,
+        #[indexed] reseller: &ManagedAddress,
+        verified: bool,
+    );
+}
@@ -0,0 +1,162 @@
+use multiversx_sc::derive_imports::*;
+use multiversx_sc::imports::*;
+
+use crate::treasury::MarketplaceStats;
+
+// Collections itemised per epoch; volume of further collections only counts in the totals
+pub const MAX_SNAPSHOT_COLLECTIONS: usize = 100;
+
+// Marketplace figures as they stood at the end of an epoch
+#[derive(TopEncode, TopDecode, TypeAbi, PartialEq, Debug)]
+pub struct StatsSnapshot<M: ManagedTypeApi> {
+    pub stats: MarketplaceStats<M>,
+    // Collections with sales during the epoch, published in collectionVolumeSnapshot events
+    pub collection_count: u32,
+    pub root: ManagedByteArray<M, 32>,
+}
+
+// Freezes the marketplace stats, and the sales volume of each collection during the epoch,
+// at epoch boundaries. The snapshot is taken by the first stats change of a new epoch (or
+// snapshotStats), before that change applies, so it holds the figures the epoch ended with;
+// epochs without any change have no snapshot of their own.
+//
+// The root commits to the snapshot as a Merkle tree, built like the airdrop campaign trees:
+// the first leaf is keccak256 of the top-encoded stats, followed by one leaf per collection,
+// keccak256(collection | volume as big-endian bytes), in event order. Parent nodes hash their
+// two children in ascending order and an odd node out is carried up unchanged.
+#[multiversx_sc::module]
+pub trait StatsSnapshotsModule {
+    // Internal helpers
+    // Called with the current stats before every change to them
+    fn roll_stats_epoch(&self, stats: &MarketplaceStats<Self::Api>) {
+        let current_epoch = self.blockchain().get_block_epoch();
+        let open_epoch_mapper = self.open_stats_epoch();
+        // Epoch 0 encodes as empty, so it is never snapshotted; it only exists on test chains
+        if open_epoch_mapper.is_empty() {
+            open_epoch_mapper.set(current_epoch);
+            return;
+        }
+        let open_epoch = open_epoch_mapper.get();
+        if open_epoch == current_epoch {
+            return;
+        }
+
+        let stats_leaf = self.serializer().top_encode_to_managed_buffer(stats);
+        let mut leaves = ManagedVec::new();
+        leaves.push(self.crypto().keccak256(&stats_leaf));
+
+        let mut volumes = self.epoch_collection_volumes();
+        for (collection, volume) in volumes.iter() {
+            let mut leaf = collection.as_managed_buffer().clone();
+            leaf.append(&volume.to_bytes_be_buffer());
+            leaves.push(self.crypto().keccak256(&leaf));
+            self.collection_volume_snapshot_event(open_epoch, &collection, &volume);
+        }
+        let collection_count = volumes.len() as u32;
+        volumes.clear();
+
+        let root = self.merkle_root(leaves);
+        self.stats_snapshot_event(open_epoch, &root);
+        self.stats_snapshots(open_epoch).set(StatsSnapshot {
+            stats: stats.clone(),
+            collection_count,
+            root,
+        });
+        open_epoch_mapper.set(current_epoch);
+    }
+
+    // Call after `roll_stats_epoch`, so the volume lands in the open epoch
+    fn record_collection_volume(&self, collection: &TokenIdentifier, amount: &BigUint) {
+        let mut volumes = self.epoch_collection_volumes();
+        match volumes.get(collection) {
+            Some(volume) => {
+                volumes.insert(collection.clone(), volume + amount);
+            },
+            None => {
+                if volumes.len() < MAX_SNAPSHOT_COLLECTIONS {
+                    volumes.insert(collection.clone(), amount.clone());
+                }
+            },
+        }
+    }
+
+    fn merkle_root(
+        &self,
+        leaves: ManagedVec<ManagedByteArray<Self::Api, 32>>,
+    ) -> ManagedByteArray<Self::Api, 32> {
+        let mut level = leaves;
+        while level.len() > 1 {
+            let mut next_level = ManagedVec::new();
+            let mut index = 0;
+            while index < level.len() {
+                if index + 1 == level.len() {
+                    next_level.push(level.get(index).clone_value());
+                } else {
+                    next_level.push(self.hash_pair(&level.get(index), &level.get(index + 1)));
+                }
+                index += 2;
+            }
+            level = next_level;
+        }
+        level.get(0).clone_value()
+    }
+
+    fn hash_pair(
+        &self,
+        left: &ManagedByteArray<Self::Api, 32>,
+        right: &ManagedByteArray<Self::Api, 32>,
+    ) -> ManagedByteArray<Self::Api, 32> {
+        let mut pair = ManagedBuffer::new();
+        if left.to_byte_array() <= right.to_byte_array() {
+            pair.append(left.as_managed_buffer());
+            pair.append(right.as_managed_buffer());
+        } else {
+            pair.append(right.as_managed_buffer());
+            pair.append(left.as_managed_buffer());
+        }
+        self.crypto().keccak256(&pair)
+    }
+
+    // View functions
+    #[view(getStatsSnapshot)]
+    fn get_stats_snapshot(&self, epoch: u64) -> OptionalValue<StatsSnapshot<Self::Api>> {
+        if self.stats_snapshots(epoch).is_empty() {
+            OptionalValue::None
+        } else {
+            OptionalValue::Some(self.stats_snapshots(epoch).get())
+        }
+    }
+
+    // Epoch the live stats belong to, i.e. the one the next snapshot will be taken for
+    #[view(getOpenStatsEpoch)]
+    fn get_open_stats_epoch(&self) -> u64 {
+        self.open_stats_epoch().get()
+    }
+
+    #[view(getEpochCollectionVolumes)]
+    fn get_epoch_collection_volumes(&self) -> MultiValueEncoded<MultiValue2<TokenIdentifier, BigUint>> {
+        self.epoch_collection_volumes().iter().map(|entry| entry.into()).collect()
+    }
+
+    // Storage mappers
+    #[storage_mapper("statsSnapshots")]
+    fn stats_snapshots(&self, epoch: u64) -> SingleValueMapper<StatsSnapshot<Self::Api>>;
+
+    #[storage_mapper("openStatsEpoch")]
+    fn open_stats_epoch(&self) -> SingleValueMapper<u64>;
+
+    #[storage_mapper("epochCollectionVolumes")]
+    fn epoch_collection_volumes(&self) -> MapMapper<TokenIdentifier, BigUint>;
+
+    // Events
+    #[event("statsSnapshot")]
+    fn stats_snapshot_event(&self, #[indexed] epoch: u64, root: &ManagedByteArray<Self::Api, 32>);
+
+    #[event("collectionVolumeSnapshot")]
+    fn collection_volume_snapshot_event(
+        &self,
+        #[indexed] epoch: u64,
+        #[indexed] collection: &TokenIdentifier,
+        volume: &BigUint,
+    );
+}
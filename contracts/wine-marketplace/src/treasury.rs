@@ -0,0 +1,642 @@
+use multiversx_sc::derive_imports::*;
+use multiversx_sc::imports::*;
+use wine_common::errors::*;
+use wine_roles::Role;
+
+use crate::proxies::wine_consignment_proxy::WineConsignmentProxy;
+use crate::proxies::wine_fee_router_proxy::WineFeeRouterProxy;
+
+// Reduced-fee launch window for a collection. It ends after `end_timestamp` or once
+// `max_sales` sales have settled, whichever comes first (0 disables either limit).
+#[derive(TopEncode, TopDecode, NestedEncode, NestedDecode, TypeAbi, PartialEq, Debug, Clone)]
+pub struct FeePromotion {
+    pub fee_percent: u64, // basis points, 0 = fee-free
+    pub start_timestamp: u64,
+    pub end_timestamp: u64,
+    pub max_sales: u32,
+    pub sales_count: u32,
+}
+
+// Part of every marketplace fee forwarded to another treasury, e.g. the main marketplace
+// treasury for storefronts deployed by the marketplace factory
+#[derive(TopEncode, TopDecode, NestedEncode, NestedDecode, TypeAbi, PartialEq, Debug)]
+pub struct FeeShare<M: ManagedTypeApi> {
+    pub recipient: ManagedAddress<M>,
+    pub share_percent: u64, // basis points of the fee
+}
+
+// Keeps getActiveFeePromotions bounded
+pub const MAX_FEE_PROMOTIONS: usize = 100;
+// Gas for handing sale proceeds to a consignment contract
+pub const CONSIGNMENT_PROCEEDS_GAS: u64 = 10_000_000;
+// Gas for depositing fees with the fee router
+pub const FEE_ROUTER_GAS: u64 = 10_000_000;
+// Keeper bounty cap, in basis points of the marketplace fee (20%)
+pub const MAX_KEEPER_BOUNTY_PERCENT: u64 = 2_000;
+
+// Where the money of a sale went, emitted with sale events so indexers don't have to
+// re-derive fees. `royalty` is part of the layout already but no royalties are paid yet.
+// Payments are always fungible, so there is no payment nonce to report.
+#[derive(TopEncode, TopDecode, TypeAbi, PartialEq, Debug)]
+pub struct SaleSettlement<M: ManagedTypeApi> {
+    pub payment_token: EgldOrEsdtTokenIdentifier<M>,
+    pub price: BigUint<M>,
+    pub marketplace_fee: BigUint<M>,
+    pub royalty: BigUint<M>,
+    pub seller_amount: BigUint<M>,
+    pub surplus_refunded: BigUint<M>,
+}
+
+#[derive(TopEncode, TopDecode, NestedEncode, NestedDecode, TypeAbi, PartialEq, Debug, Clone)]
+pub struct MarketplaceStats<M: ManagedTypeApi> {
+    pub total_listings: u32,
+    pub total_sales: u32,
+    pub total_volume: BigUint<M>,
+    pub total_fees_collected: BigUint<M>,
+    pub active_listings: u32,
+    pub active_auctions: u32,
+}
+
+// Fees, payouts and transfers out of the marketplace. Sale modules settle through
+// `pay_out_sale` so fee handling and sales stats stay in one place.
+#[multiversx_sc::module]
+pub trait TreasuryModule:
+    wine_roles::RolesModule
+    + crate::collector_tiers::CollectorTiersModule
+    + crate::fee_discounts::FeeDiscountsModule
+    + crate::stats_snapshots::StatsSnapshotsModule
+{
+    // Basis points of the sale price (250 = 2.5%)
+    #[endpoint(setMarketplaceFeePercent)]
+    fn set_marketplace_fee_percent(&self, marketplace_fee_percent: u64) {
+        self.require_role(Role::FeeManager);
+        require!(marketplace_fee_percent <= 1000, ERR_FEE_CANNOT_EXCEED_10_PERCENT); // Max 10%
+        self.marketplace_fee_percent().set(marketplace_fee_percent);
+
+        self.marketplace_fee_updated_event(marketplace_fee_percent);
+    }
+
+    // Launch window for a new collection: sales settle at `fee_percent` for `duration_seconds`
+    // and/or the first `max_sales` sales
+    #[endpoint(setFeePromotion)]
+    fn set_fee_promotion(
+        &self,
+        collection: TokenIdentifier,
+        fee_percent: u64,
+        duration_seconds: u64,
+        max_sales: u32,
+    ) {
+        self.require_role(Role::FeeManager);
+        require!(collection.is_valid_esdt_identifier(), ERR_INVALID_TOKEN_IDENTIFIER);
+        require!(
+            fee_percent < self.marketplace_fee_percent().get(),
+            ERR_PROMOTIONAL_FEE_MUST_BE_BELOW_THE_MARKETPLACE_FEE
+        );
+        require!(duration_seconds > 0 || max_sales > 0, ERR_PROMOTION_NEEDS_A_DURATION_OR_A_SALES_LIMIT);
+
+        let start_timestamp = self.blockchain().get_block_timestamp();
+        let end_timestamp = if duration_seconds > 0 {
+            start_timestamp + duration_seconds
+        } else {
+            0
+        };
+        let promotion = FeePromotion {
+            fee_percent,
+            start_timestamp,
+            end_timestamp,
+            max_sales,
+            sales_count: 0u32,
+        };
+        self.fee_promotions(&collection).set(&promotion);
+        self.fee_promotion_collections().insert(collection.clone());
+        require!(
+            self.fee_promotion_collections().len() <= MAX_FEE_PROMOTIONS,
+            ERR_TOO_MANY_FEE_PROMOTIONS
+        );
+
+        self.fee_promotion_set_event(&collection, &promotion);
+    }
+
+    #[endpoint(removeFeePromotion)]
+    fn remove_fee_promotion(&self, collection: TokenIdentifier) {
+        self.require_role(Role::FeeManager);
+        self.fee_promotions(&collection).clear();
+        self.fee_promotion_collections().swap_remove(&collection);
+    }
+
+    // Sellers that hold wine on behalf of others. They get their proceeds through a
+    // `consignmentSaleProceeds(collection, nonce)` call, so they can tell which wine sold.
+    #[endpoint(addConsignmentContract)]
+    fn add_consignment_contract(&self, address: ManagedAddress) {
+        self.require_role(Role::Admin);
+        require!(
+            self.blockchain().is_smart_contract(&address),
+            ERR_CONSIGNMENT_CONTRACT_MUST_BE_A_SMART_CONTRACT
+        );
+        require!(self.consignment_contracts().insert(address), ERR_ALREADY_A_CONSIGNMENT_CONTRACT);
+    }
+
+    #[endpoint(removeConsignmentContract)]
+    fn remove_consignment_contract(&self, address: ManagedAddress) {
+        self.require_role(Role::Admin);
+        require!(self.consignment_contracts().swap_remove(&address), ERR_NOT_A_CONSIGNMENT_CONTRACT);
+    }
+
+    // Sends the caller all sale proceeds held for them, one transfer per token
+    #[endpoint(claimSaleProceeds)]
+    fn claim_sale_proceeds(&self) {
+        let caller = self.blockchain().get_caller();
+        let mut tokens = self.sale_proceeds_tokens(&caller);
+        require!(!tokens.is_empty(), ERR_NOTHING_TO_WITHDRAW);
+
+        for token in tokens.iter() {
+            let amount = self.sale_proceeds(&caller, &token).take();
+            self.send_payment(&caller, &token, &amount);
+            self.sale_proceeds_claimed_event(&caller, &token, &amount);
+        }
+        tokens.clear();
+    }
+
+    // Takes the pending stats snapshot without waiting for the next change to the stats
+    #[endpoint(snapshotStats)]
+    fn snapshot_stats(&self) {
+        self.roll_stats_epoch(&self.marketplace_stats().get());
+    }
+
+    // Fees go to the owner unless redirected. Owner-only, so that admins of a storefront
+    // deployed by the factory cannot move its fees or fee share.
+    #[endpoint(setFeeRecipient)]
+    fn set_fee_recipient(&self, recipient: ManagedAddress) {
+        require!(
+            self.blockchain().get_caller() == self.blockchain().get_owner_address(),
+            ERR_ONLY_OWNER_CAN_SET_THE_FEE_RECIPIENT
+        );
+        require!(!recipient.is_zero(), ERR_INVALID_FEE_RECIPIENT);
+        self.fee_recipient().set(&recipient);
+
+        self.fee_recipient_updated_event(&recipient);
+    }
+
+    // Fee router contract the retained fees are deposited with, through `depositFees`, instead
+    // of going to the fee recipient. Without an address the router is removed.
+    #[endpoint(setFeeRouter)]
+    fn set_fee_router(&self, opt_router: OptionalValue<ManagedAddress>) {
+        require!(
+            self.blockchain().get_caller() == self.blockchain().get_owner_address(),
+            ERR_ONLY_OWNER_CAN_SET_THE_FEE_ROUTER
+        );
+        match opt_router {
+            OptionalValue::Some(router) => {
+                require!(
+                    self.blockchain().is_smart_contract(&router),
+                    ERR_FEE_ROUTER_MUST_BE_A_SMART_CONTRACT
+                );
+                self.fee_router().set(&router);
+                self.fee_router_updated_event(&router);
+            },
+            OptionalValue::None => {
+                self.fee_router().clear();
+                self.fee_router_updated_event(&ManagedAddress::zero());
+            },
+        }
+    }
+
+    // A share of 0 stops forwarding
+    #[endpoint(setFeeShare)]
+    fn set_fee_share(&self, recipient: ManagedAddress, share_percent: u64) {
+        require!(
+            self.blockchain().get_caller() == self.blockchain().get_owner_address(),
+            ERR_ONLY_OWNER_CAN_SET_THE_FEE_SHARE
+        );
+        require!(share_percent <= 10000, ERR_FEE_SHARE_CANNOT_EXCEED_100_PERCENT);
+        if share_percent == 0 {
+            self.fee_share().clear();
+        } else {
+            require!(!recipient.is_zero(), ERR_INVALID_FEE_SHARE_RECIPIENT);
+            self.fee_share().set(&FeeShare {
+                recipient: recipient.clone(),
+                share_percent,
+            });
+        }
+
+        self.fee_share_updated_event(&recipient, share_percent);
+    }
+
+    // Basis points of the marketplace fee paid to whoever finalizes an ended auction for
+    // someone else; 0 turns the bounty off
+    #[endpoint(setKeeperBountyPercent)]
+    fn set_keeper_bounty_percent(&self, bounty_percent: u64) {
+        self.require_role(Role::FeeManager);
+        require!(bounty_percent <= MAX_KEEPER_BOUNTY_PERCENT, ERR_KEEPER_BOUNTY_TOO_HIGH);
+        self.keeper_bounty_percent().set(bounty_percent);
+
+        self.keeper_bounty_updated_event(bounty_percent);
+    }
+
+    fn is_fee_promotion_active(&self, promotion: &FeePromotion) -> bool {
+        (promotion.end_timestamp == 0 || self.blockchain().get_block_timestamp() <= promotion.end_timestamp)
+            && (promotion.max_sales == 0 || promotion.sales_count < promotion.max_sales)
+    }
+
+    // Fee for a sale in `collection`, counting the sale towards an active promotion
+    fn take_sale_fee(&self, collection: &TokenIdentifier, amount: &BigUint) -> BigUint {
+        amount * self.take_sale_fee_percent(collection) / 10000u64
+    }
+
+    // Fee rate for a sale in `collection`, counting the sale towards an active promotion
+    fn take_sale_fee_percent(&self, collection: &TokenIdentifier) -> u64 {
+        match self.active_fee_promotion(collection) {
+            Some(mut promotion) => {
+                promotion.sales_count += 1;
+                self.fee_promotions(collection).set(&promotion);
+                promotion.fee_percent
+            },
+            None => self.marketplace_fee_percent().get(),
+        }
+    }
+
+    // Fee rate a sale in `collection` would settle at now, without counting it
+    fn sale_fee_percent(&self, collection: &TokenIdentifier) -> u64 {
+        match self.active_fee_promotion(collection) {
+            Some(promotion) => promotion.fee_percent,
+            None => self.marketplace_fee_percent().get(),
+        }
+    }
+
+    fn active_fee_promotion(&self, collection: &TokenIdentifier) -> Option<FeePromotion> {
+        let promotion_mapper = self.fee_promotions(collection);
+        if promotion_mapper.is_empty() {
+            return None;
+        }
+        let promotion = promotion_mapper.get();
+        if self.is_fee_promotion_active(&promotion) {
+            Some(promotion)
+        } else {
+            None
+        }
+    }
+
+    // Fee at `fee_percent` with a seller discount of `discount_bps` taken off, rounded like
+    // `apply_fee_discount`
+    fn discounted_fee(&self, amount: &BigUint, fee_percent: u64, discount_bps: u64) -> BigUint {
+        amount * fee_percent / 10000u64 * (10_000 - discount_bps) / 10_000u64
+    }
+
+    // Split a sale between seller and marketplace treasury and record it in the marketplace
+    // and collector stats.
+    // Returns the breakdown; callers refunding a surplus fill in `surplus_refunded`.
+    fn pay_out_sale(
+        &self,
+        seller: &ManagedAddress,
+        buyer: &ManagedAddress,
+        collection: &TokenIdentifier,
+        nft_nonce: u64,
+        payment_token: &EgldOrEsdtTokenIdentifier,
+        amount: &BigUint,
+    ) -> SaleSettlement<Self::Api> {
+        self.pay_out_sale_with_keeper(seller, buyer, collection, nft_nonce, payment_token, amount, None)
+    }
+
+    // `pay_out_sale` for sales settled by a third party, who gets the keeper bounty out of
+    // the marketplace fee
+    fn pay_out_sale_with_keeper(
+        &self,
+        seller: &ManagedAddress,
+        buyer: &ManagedAddress,
+        collection: &TokenIdentifier,
+        nft_nonce: u64,
+        payment_token: &EgldOrEsdtTokenIdentifier,
+        amount: &BigUint,
+        keeper: Option<&ManagedAddress>,
+    ) -> SaleSettlement<Self::Api> {
+        let marketplace_fee = self.apply_fee_discount(seller, self.take_sale_fee(collection, amount));
+        let settlement = self.pay_out_sale_part(
+            seller,
+            collection,
+            nft_nonce,
+            payment_token,
+            amount,
+            marketplace_fee,
+            keeper,
+            false,
+        );
+        self.record_sale(seller, buyer, payment_token, amount);
+
+        settlement
+    }
+
+    // Pays out one token's share of a sale, with its fee already worked out, and adds it to
+    // the volume and fee stats. A sale paid in several tokens has one part per token.
+    // With `hold_proceeds` the seller's amount is credited for claimSaleProceeds instead.
+    fn pay_out_sale_part(
+        &self,
+        seller: &ManagedAddress,
+        collection: &TokenIdentifier,
+        nft_nonce: u64,
+        payment_token: &EgldOrEsdtTokenIdentifier,
+        amount: &BigUint,
+        marketplace_fee: BigUint,
+        keeper: Option<&ManagedAddress>,
+        hold_proceeds: bool,
+    ) -> SaleSettlement<Self::Api> {
+        let seller_amount = amount - &marketplace_fee;
+
+        if hold_proceeds {
+            self.credit_sale_proceeds(seller, payment_token, &seller_amount);
+        } else if self.consignment_contracts().contains(seller) && seller_amount > 0 {
+            self.tx()
+                .to(seller)
+                .typed(WineConsignmentProxy)
+                .consignment_sale_proceeds(collection, nft_nonce)
+                .egld_or_single_esdt(payment_token, 0, &seller_amount)
+                .gas(CONSIGNMENT_PROCEEDS_GAS)
+                .transfer_execute();
+        } else {
+            self.send_payment(seller, payment_token, &seller_amount);
+        }
+
+        // Collect marketplace fee, paying the keeper bounty and forwarding the fee share first
+        let mut retained_fee = marketplace_fee.clone();
+        if let Some(keeper) = keeper {
+            let bounty = &marketplace_fee * self.keeper_bounty_percent().get() / 10000u64;
+            if bounty > 0 {
+                retained_fee -= &bounty;
+                self.send_payment(keeper, payment_token, &bounty);
+                self.keeper_bounty_paid_event(keeper, payment_token, &bounty);
+            }
+        }
+        let shareable_fee = retained_fee.clone();
+        if !self.fee_share().is_empty() {
+            let fee_share = self.fee_share().get();
+            let shared_fee = &shareable_fee * fee_share.share_percent / 10000u64;
+            retained_fee -= &shared_fee;
+            self.send_payment(&fee_share.recipient, payment_token, &shared_fee);
+        }
+        if !self.fee_router().is_empty() && retained_fee > 0 {
+            self.tx()
+                .to(&self.fee_router().get())
+                .typed(WineFeeRouterProxy)
+                .deposit_fees()
+                .egld_or_single_esdt(payment_token, 0, &retained_fee)
+                .gas(FEE_ROUTER_GAS)
+                .transfer_execute();
+        } else {
+            self.send_payment(&self.fee_recipient_address(), payment_token, &retained_fee);
+        }
+
+        self.update_marketplace_stats(|stats| {
+            stats.total_volume += amount;
+            stats.total_fees_collected += &marketplace_fee;
+        });
+        self.record_collection_volume(collection, amount);
+
+        SaleSettlement {
+            payment_token: payment_token.clone(),
+            price: amount.clone(),
+            marketplace_fee,
+            royalty: BigUint::zero(),
+            seller_amount,
+            surplus_refunded: BigUint::zero(),
+        }
+    }
+
+    fn credit_sale_proceeds(
+        &self,
+        seller: &ManagedAddress,
+        payment_token: &EgldOrEsdtTokenIdentifier,
+        amount: &BigUint,
+    ) {
+        if amount.is_zero() {
+            return;
+        }
+        self.sale_proceeds(seller, payment_token).update(|balance| *balance += amount);
+        self.sale_proceeds_tokens(seller).insert(payment_token.clone());
+    }
+
+    // Counts a settled sale once, however many tokens it was paid in
+    fn record_sale(
+        &self,
+        seller: &ManagedAddress,
+        buyer: &ManagedAddress,
+        payment_token: &EgldOrEsdtTokenIdentifier,
+        amount: &BigUint,
+    ) {
+        self.update_marketplace_stats(|stats| stats.total_sales += 1);
+        self.record_collector_purchase(buyer, payment_token, amount);
+        self.record_collector_sale(seller);
+    }
+
+    // Breakdown for a sale that is not paid out (yet), e.g. held in escrow: nothing has
+    // been taken from the price
+    fn unsettled_sale(
+        &self,
+        payment_token: &EgldOrEsdtTokenIdentifier,
+        amount: &BigUint,
+    ) -> SaleSettlement<Self::Api> {
+        SaleSettlement {
+            payment_token: payment_token.clone(),
+            price: amount.clone(),
+            marketplace_fee: BigUint::zero(),
+            royalty: BigUint::zero(),
+            seller_amount: BigUint::zero(),
+            surplus_refunded: BigUint::zero(),
+        }
+    }
+
+    // Every change to the marketplace stats goes through here, so they can be frozen at
+    // epoch boundaries
+    fn update_marketplace_stats<R, F: FnOnce(&mut MarketplaceStats<Self::Api>) -> R>(&self, f: F) -> R {
+        let mut stats = self.marketplace_stats().get();
+        self.roll_stats_epoch(&stats);
+        let result = f(&mut stats);
+        self.marketplace_stats().set(&stats);
+        result
+    }
+
+    fn fee_recipient_address(&self) -> ManagedAddress {
+        if self.fee_recipient().is_empty() {
+            self.blockchain().get_owner_address()
+        } else {
+            self.fee_recipient().get()
+        }
+    }
+
+    // Fungible payment transfer, skipped for zero amounts
+    fn send_payment(&self, to: &ManagedAddress, token_id: &EgldOrEsdtTokenIdentifier, amount: &BigUint) {
+        if amount.is_zero() {
+            return;
+        }
+        self.send().direct(to, token_id, 0, amount);
+    }
+
+    // Release an escrowed wine NFT
+    fn send_nft(&self, to: &ManagedAddress, nft_token_id: &TokenIdentifier, nft_nonce: u64) {
+        self.send().direct_esdt(to, nft_token_id, nft_nonce, &BigUint::from(1u32));
+    }
+
+    // Gift recipient helper: the NFT goes to the caller unless a recipient is named
+    fn resolve_recipient(
+        &self,
+        caller: &ManagedAddress,
+        opt_recipient: OptionalValue<ManagedAddress>,
+    ) -> ManagedAddress {
+        match opt_recipient {
+            OptionalValue::Some(recipient) => {
+                require!(!recipient.is_zero(), ERR_INVALID_RECIPIENT_ADDRESS);
+                recipient
+            },
+            OptionalValue::None => caller.clone(),
+        }
+    }
+
+    // View functions
+    #[view(getMarketplaceStats)]
+    fn get_marketplace_stats(&self) -> MarketplaceStats<Self::Api> {
+        self.marketplace_stats().get()
+    }
+
+    #[view(getMarketplaceFeePercent)]
+    fn get_marketplace_fee_percent(&self) -> u64 {
+        self.marketplace_fee_percent().get()
+    }
+
+    #[view(getActiveFeePromotions)]
+    fn get_active_fee_promotions(&self) -> MultiValueEncoded<MultiValue2<TokenIdentifier, FeePromotion>> {
+        let mut result = MultiValueEncoded::new();
+        for collection in self.fee_promotion_collections().iter() {
+            let promotion = self.fee_promotions(&collection).get();
+            if self.is_fee_promotion_active(&promotion) {
+                result.push((collection, promotion).into());
+            }
+        }
+        result
+    }
+
+    #[view(getFeeRecipient)]
+    fn get_fee_recipient(&self) -> ManagedAddress {
+        self.fee_recipient_address()
+    }
+
+    #[view(getFeeShare)]
+    fn get_fee_share(&self) -> OptionalValue<FeeShare<Self::Api>> {
+        if self.fee_share().is_empty() {
+            OptionalValue::None
+        } else {
+            OptionalValue::Some(self.fee_share().get())
+        }
+    }
+
+    #[view(getFeeRouter)]
+    fn get_fee_router(&self) -> OptionalValue<ManagedAddress> {
+        if self.fee_router().is_empty() {
+            OptionalValue::None
+        } else {
+            OptionalValue::Some(self.fee_router().get())
+        }
+    }
+
+    #[view(getKeeperBountyPercent)]
+    fn get_keeper_bounty_percent(&self) -> u64 {
+        self.keeper_bounty_percent().get()
+    }
+
+    // Proceeds held for `seller` until claimed, per token
+    #[view(getSaleProceeds)]
+    fn get_sale_proceeds(
+        &self,
+        seller: ManagedAddress,
+    ) -> MultiValueEncoded<MultiValue2<EgldOrEsdtTokenIdentifier, BigUint>> {
+        let mut result = MultiValueEncoded::new();
+        for token in self.sale_proceeds_tokens(&seller).iter() {
+            let amount = self.sale_proceeds(&seller, &token).get();
+            result.push((token, amount).into());
+        }
+        result
+    }
+
+    #[view(getConsignmentContracts)]
+    fn get_consignment_contracts(&self) -> MultiValueEncoded<ManagedAddress> {
+        self.consignment_contracts().iter().collect()
+    }
+
+    // Storage mappers
+    #[storage_mapper("marketplaceStats")]
+    fn marketplace_stats(&self) -> SingleValueMapper<MarketplaceStats<Self::Api>>;
+
+    #[storage_mapper("marketplaceFeePercent")]
+    fn marketplace_fee_percent(&self) -> SingleValueMapper<u64>;
+
+    #[storage_mapper("feePromotions")]
+    fn fee_promotions(&self, collection: &TokenIdentifier) -> SingleValueMapper<FeePromotion>;
+
+    #[storage_mapper("feePromotionCollections")]
+    fn fee_promotion_collections(&self) -> UnorderedSetMapper<TokenIdentifier>;
+
+    #[storage_mapper("consignmentContracts")]
+    fn consignment_contracts(&self) -> UnorderedSetMapper<ManagedAddress>;
+
+    #[storage_mapper("feeRecipient")]
+    fn fee_recipient(&self) -> SingleValueMapper<ManagedAddress>;
+
+    #[storage_mapper("feeRouter")]
+    fn fee_router(&self) -> SingleValueMapper<ManagedAddress>;
+
+    #[storage_mapper("feeShare")]
+    fn fee_share(&self) -> SingleValueMapper<FeeShare<Self::Api>>;
+
+    #[storage_mapper("saleProceeds")]
+    fn sale_proceeds(
+        &self,
+        seller: &ManagedAddress,
+        token: &EgldOrEsdtTokenIdentifier,
+    ) -> SingleValueMapper<BigUint>;
+
+    #[storage_mapper("saleProceedsTokens")]
+    fn sale_proceeds_tokens(
+        &self,
+        seller: &ManagedAddress,
+    ) -> UnorderedSetMapper<EgldOrEsdtTokenIdentifier>;
+
+    #[storage_mapper("keeperBountyPercent")]
+    fn keeper_bounty_percent(&self) -> SingleValueMapper<u64>;
+
+    // Events
+    #[event("marketplaceFeeUpdated")]
+    fn marketplace_fee_updated_event(&self, marketplace_fee_percent: u64);
+
+    #[event("feePromotionSet")]
+    fn fee_promotion_set_event(
+        &self,
+        #[indexed] collection: &TokenIdentifier,
+        promotion: &FeePromotion,
+    );
+
+    #[event("feeRecipientUpdated")]
+    fn fee_recipient_updated_event(&self, #[indexed] recipient: &ManagedAddress);
+
+    // Zero address when the router is removed
+    #[event("feeRouterUpdated")]
+    fn fee_router_updated_event(&self, #[indexed] router: &ManagedAddress);
+
+    #[event("feeShareUpdated")]
+    fn fee_share_updated_event(&self, #[indexed] recipient: &ManagedAddress, share_percent: u64);
+
+    #[event("keeperBountyUpdated")]
+    fn keeper_bounty_updated_event(&self, bounty_percent: u64);
+
+    #[event("saleProceedsClaimed")]
+    fn sale_proceeds_claimed_event(
+        &self,
+        #[indexed] seller: &ManagedAddress,
+        #[indexed] payment_token: &EgldOrEsdtTokenIdentifier,
+        amount: &BigUint,
+    );
+
+    #[event("keeperBountyPaid")]
+    fn keeper_bounty_paid_event(
+        &self,
+        #[indexed] keeper: &ManagedAddress,
+        #[indexed] payment_token: &EgldOrEsdtTokenIdentifier,
+        amount: &BigUint,
+    );
+}
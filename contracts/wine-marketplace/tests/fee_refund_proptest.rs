@@ -0,0 +1,379 @@
+use multiversx_sc::codec::top_encode_to_vec_u8_or_panic;
+use multiversx_sc_scenario::api::StaticApi;
+use multiversx_sc_scenario::imports::*;
+use proptest::prelude::*;
+use wine_common::errors::*;
+use wine_marketplace::auction::ProxyTrait as _;
+use wine_marketplace::listing::ProxyTrait as _;
+use wine_marketplace::registry_cache::ProxyTrait as _;
+use wine_marketplace::treasury::ProxyTrait as _;
+use wine_marketplace::ProxyTrait as _;
+use wine_marketplace::{MarketplaceStats, WineNftAttributes, WineReadModel, WineStatus};
+
+const MARKETPLACE_PATH_EXPR: &str = "mxsc:output/wine-marketplace.mxsc.json";
+const MARKETPLACE_ADDRESS_EXPR: &str = "sc:wine-marketplace";
+const OWNER_ADDRESS_EXPR: &str = "address:owner";
+const REGISTRY_ADDRESS_EXPR: &str = "address:registry";
+const SELLER_ADDRESS_EXPR: &str = "address:seller";
+const BUYER_ADDRESS_EXPR: &str = "address:buyer";
+const BIDDER_ADDRESS_EXPRS: [&str; 3] = ["address:bidder-1", "address:bidder-2", "address:bidder-3"];
+
+const WINE_NFT_TOKEN_ID_EXPR: &str = "str:WINE-abcdef";
+
+const START_TIMESTAMP: u64 = 1_000;
+const DURATION: u64 = 3600;
+const BALANCE: u64 = 1_000_000_000_000;
+// One wine NFT per listing an operation sequence can create
+const MAX_OPS: usize = 24;
+
+type MarketplaceContract = ContractInfo<wine_marketplace::Proxy<StaticApi>>;
+
+fn user_error(message: &str) -> TxExpect {
+    TxExpect::user_error(format!("str:{message}"))
+}
+
+// Registry-minted attributes of a bottle of `wine_id`; each test wine has its nonce as id
+fn encoded_wine_attributes(wine_id: u32) -> Option<Vec<u8>> {
+    let attributes = WineNftAttributes::<StaticApi> {
+        wine_id,
+        vineyard: ManagedBuffer::from("Chateau Test"),
+        variety: ManagedBuffer::from("Merlot"),
+        vintage_year: 2020,
+        region: ManagedBuffer::from("Bordeaux"),
+        quality_grade: 8,
+        ipfs_hash: ManagedBuffer::new(),
+    };
+    Some(top_encode_to_vec_u8_or_panic(&attributes))
+}
+
+// Runs each generated case against a fresh marketplace in the scenario VM
+struct Harness {
+    world: ScenarioWorld,
+    marketplace: MarketplaceContract,
+    timestamp: u64,
+}
+
+impl Harness {
+    fn new(fee_bps: u64) -> Self {
+        let mut world = ScenarioWorld::new();
+        world.register_contract(MARKETPLACE_PATH_EXPR, wine_marketplace::ContractBuilder);
+
+        let mut seller = Account::new().nonce(1);
+        for nonce in 1..=MAX_OPS as u64 {
+            seller = seller.esdt_nft_balance(WINE_NFT_TOKEN_ID_EXPR, nonce, "1", encoded_wine_attributes(nonce as u32));
+        }
+        let mut set_state = SetStateStep::new()
+            .block_timestamp(START_TIMESTAMP)
+            .put_account(OWNER_ADDRESS_EXPR, Account::new().nonce(1))
+            .put_account(REGISTRY_ADDRESS_EXPR, Account::new().nonce(1))
+            .put_account(SELLER_ADDRESS_EXPR, seller)
+            .put_account(BUYER_ADDRESS_EXPR, Account::new().nonce(1).balance(BALANCE))
+            .new_address(OWNER_ADDRESS_EXPR, 1, MARKETPLACE_ADDRESS_EXPR);
+        for bidder in BIDDER_ADDRESS_EXPRS {
+            set_state = set_state.put_account(bidder, Account::new().nonce(1).balance(BALANCE));
+        }
+        world.set_state_step(set_state);
+
+        let mut marketplace = MarketplaceContract::new(MARKETPLACE_ADDRESS_EXPR);
+        let marketplace_code = world.code_expression(MARKETPLACE_PATH_EXPR);
+        let registry = AddressValue::from(REGISTRY_ADDRESS_EXPR).to_address();
+        world.sc_deploy(
+            ScDeployStep::new()
+                .from(OWNER_ADDRESS_EXPR)
+                .code(marketplace_code)
+                .call(marketplace.init(fee_bps, managed_address!(&registry))),
+        );
+        world.sc_call(
+            ScCallStep::new()
+                .from(OWNER_ADDRESS_EXPR)
+                .call(marketplace.set_wine_collection(TokenIdentifier::from("WINE-abcdef"))),
+        );
+        // Every wine is pushed by the registry as released, uncertified and not the seller's own
+        let producer = AddressValue::from(OWNER_ADDRESS_EXPR).to_address();
+        for wine_id in 1..=MAX_OPS as u32 {
+            let read_model = WineReadModel::<StaticApi> {
+                price_per_bottle: BigUint::from(1u32),
+                quality_grade: 8,
+                certified: false,
+                royalty_bps: 0,
+                status: WineStatus::Released,
+                producer: managed_address!(&producer),
+                counterfeit: false,
+                certified_until: 0,
+            };
+            world.sc_call(
+                ScCallStep::new()
+                    .from(REGISTRY_ADDRESS_EXPR)
+                    .call(marketplace.sync_wine_read_model(wine_id, read_model)),
+            );
+        }
+
+        Harness {
+            world,
+            marketplace,
+            timestamp: START_TIMESTAMP,
+        }
+    }
+
+    fn advance_time(&mut self, seconds: u64) {
+        self.timestamp += seconds;
+        self.world.set_state_step(SetStateStep::new().block_timestamp(self.timestamp));
+    }
+
+    fn create_listing(&mut self, nft_nonce: u64, price: u64) {
+        self.world.sc_call(
+            ScCallStep::new()
+                .from(SELLER_ADDRESS_EXPR)
+                .esdt_transfer(WINE_NFT_TOKEN_ID_EXPR, nft_nonce, "1")
+                .call(self.marketplace.create_listing(
+                    nft_nonce as u32,
+                    price,
+                    EgldOrEsdtTokenIdentifier::egld(),
+                    DURATION,
+                    OptionalValue::<bool>::None,
+                )),
+        );
+    }
+
+    fn buy_wine(&mut self, listing_id: u32, payment: u64, expect: TxExpect) {
+        self.world.sc_call(
+            ScCallStep::new()
+                .from(BUYER_ADDRESS_EXPR)
+                .egld_value(payment)
+                .call(self.marketplace.buy_wine(listing_id, OptionalValue::<ManagedAddress<StaticApi>>::None))
+                .expect(expect),
+        );
+    }
+
+    fn cancel_listing(&mut self, listing_id: u32, expect: TxExpect) {
+        self.world.sc_call(
+            ScCallStep::new()
+                .from(SELLER_ADDRESS_EXPR)
+                .call(self.marketplace.cancel_listing(listing_id))
+                .expect(expect),
+        );
+    }
+
+    fn stats(&mut self) -> MarketplaceStats<StaticApi> {
+        self.world.quick_query(self.marketplace.get_marketplace_stats())
+    }
+
+    fn check_balance(&mut self, address_expr: &str, balance: u64) {
+        self.world.check_state_step(
+            CheckStateStep::new().put_account(address_expr, CheckAccount::new().balance(balance.to_string().as_str())),
+        );
+    }
+}
+
+// Fee the marketplace is expected to take, rounded down
+fn expected_fee(price: u64, fee_bps: u64) -> u64 {
+    price * fee_bps / 10_000
+}
+
+// Buying at `price` with `surplus` on top: the seller, the fee and the refund add up to exactly
+// what the buyer paid, and nothing stays in the contract
+fn check_sale_split(price: u64, fee_bps: u64, surplus: u64) {
+    let mut harness = Harness::new(fee_bps);
+    harness.create_listing(1, price);
+    let paid = price + surplus;
+    harness.buy_wine(1, paid, TxExpect::ok());
+
+    let fee = harness.stats().total_fees_collected.to_u64().unwrap();
+    assert_eq!(fee, expected_fee(price, fee_bps));
+    let seller_amount = price - fee;
+    let refunded = BALANCE - paid + surplus;
+    assert_eq!(seller_amount + fee + surplus, paid);
+    harness.check_balance(SELLER_ADDRESS_EXPR, seller_amount);
+    harness.check_balance(OWNER_ADDRESS_EXPR, fee);
+    harness.check_balance(BUYER_ADDRESS_EXPR, refunded);
+    harness.check_balance(MARKETPLACE_ADDRESS_EXPR, 0);
+}
+
+// Each bid tops the previous one by its increment (at least the minimum). Every outbid bidder
+// gets the full bid back, pushed or withdrawn, and only the winning bid is split.
+fn check_bid_sequence(starting_price: u64, fee_bps: u64, bids: &[(usize, u64)], direct_refunds: bool) {
+    let mut harness = Harness::new(fee_bps);
+    if direct_refunds {
+        harness.world.sc_call(
+            ScCallStep::new()
+                .from(OWNER_ADDRESS_EXPR)
+                .call(harness.marketplace.set_direct_outbid_refunds(true)),
+        );
+    }
+    harness.world.sc_call(
+        ScCallStep::new()
+            .from(SELLER_ADDRESS_EXPR)
+            .esdt_transfer(WINE_NFT_TOKEN_ID_EXPR, 1, "1")
+            .call(harness.marketplace.create_auction(
+                1u32,
+                starting_price,
+                EgldOrEsdtTokenIdentifier::egld(),
+                DURATION,
+                1u64,
+            )),
+    );
+
+    let mut current_bid = starting_price;
+    let mut escrowed = [0u64; 3];
+    let mut highest: Option<usize> = None;
+    for &(bidder, increment) in bids {
+        let amount = current_bid + increment;
+        harness.world.sc_call(
+            ScCallStep::new()
+                .from(BIDDER_ADDRESS_EXPRS[bidder])
+                .egld_value(amount)
+                .call(harness.marketplace.place_bid(1u32, OptionalValue::<ManagedAddress<StaticApi>>::None)),
+        );
+        if let Some(previous) = highest {
+            if !direct_refunds {
+                escrowed[previous] += current_bid;
+            }
+        }
+        current_bid = amount;
+        highest = Some(bidder);
+    }
+
+    let winner = highest.unwrap();
+    harness.advance_time(DURATION);
+    harness.world.sc_call(
+        ScCallStep::new()
+            .from(SELLER_ADDRESS_EXPR)
+            .call(harness.marketplace.finalize_auction(1u32)),
+    );
+    for (bidder, amount) in escrowed.iter().enumerate() {
+        let address = AddressValue::from(BIDDER_ADDRESS_EXPRS[bidder]).to_address();
+        let outbid: RustBigUint = harness
+            .world
+            .quick_query(harness.marketplace.get_outbid_funds(1u32, managed_address!(&address)));
+        assert_eq!(outbid, RustBigUint::from(*amount));
+        if *amount > 0 {
+            harness.world.sc_call(
+                ScCallStep::new()
+                    .from(BIDDER_ADDRESS_EXPRS[bidder])
+                    .call(harness.marketplace.withdraw_outbid_funds(1u32)),
+            );
+        }
+    }
+
+    let fee = expected_fee(current_bid, fee_bps);
+    for (bidder, address_expr) in BIDDER_ADDRESS_EXPRS.iter().enumerate() {
+        let spent = if bidder == winner { current_bid } else { 0 };
+        harness.check_balance(address_expr, BALANCE - spent);
+    }
+    harness.check_balance(SELLER_ADDRESS_EXPR, current_bid - fee);
+    harness.check_balance(OWNER_ADDRESS_EXPR, fee);
+    harness.check_balance(MARKETPLACE_ADDRESS_EXPR, 0);
+}
+
+#[derive(Debug, Clone)]
+enum ListingOp {
+    Create(u64),
+    // Index into the listings created so far
+    Buy(usize, u64),
+    Cancel(usize),
+    // Lets every open listing expire
+    Expire,
+}
+
+fn listing_op() -> impl Strategy<Value = ListingOp> {
+    prop_oneof![
+        (1u64..1_000_000).prop_map(ListingOp::Create),
+        (any::<usize>(), 0u64..1_000).prop_map(|(index, surplus)| ListingOp::Buy(index, surplus)),
+        any::<usize>().prop_map(ListingOp::Cancel),
+        Just(ListingOp::Expire),
+    ]
+}
+
+struct ModelListing {
+    price: u64,
+    deadline: u64,
+    active: bool,
+}
+
+// Applies the operations while mirroring them in a model. Failed buys and cancels must leave
+// the stats untouched, the totals only ever grow and `active_listings` never underflows.
+fn check_listing_ops(fee_bps: u64, ops: &[ListingOp]) {
+    let mut harness = Harness::new(fee_bps);
+    let mut listings: Vec<ModelListing> = Vec::new();
+    let mut previous = harness.stats();
+
+    for op in ops {
+        match *op {
+            ListingOp::Create(price) => {
+                harness.create_listing(listings.len() as u64 + 1, price);
+                listings.push(ModelListing {
+                    price,
+                    deadline: harness.timestamp + DURATION,
+                    active: true,
+                });
+            },
+            ListingOp::Buy(index, surplus) if !listings.is_empty() => {
+                let index = index % listings.len();
+                let listing = &mut listings[index];
+                let expect = if !listing.active {
+                    user_error(ERR_LISTING_IS_NOT_ACTIVE)
+                } else if harness.timestamp > listing.deadline {
+                    user_error(ERR_LISTING_HAS_EXPIRED)
+                } else {
+                    listing.active = false;
+                    TxExpect::ok()
+                };
+                let payment = listing.price + surplus;
+                harness.buy_wine(index as u32 + 1, payment, expect);
+            },
+            ListingOp::Cancel(index) if !listings.is_empty() => {
+                let index = index % listings.len();
+                let listing = &mut listings[index];
+                let expect = if listing.active {
+                    listing.active = false;
+                    TxExpect::ok()
+                } else {
+                    user_error(ERR_LISTING_IS_NOT_ACTIVE)
+                };
+                harness.cancel_listing(index as u32 + 1, expect);
+            },
+            ListingOp::Expire => harness.advance_time(DURATION + 1),
+            _ => {},
+        }
+
+        let stats = harness.stats();
+        assert!(stats.total_listings >= previous.total_listings);
+        assert!(stats.total_sales >= previous.total_sales);
+        assert!(stats.total_volume >= previous.total_volume);
+        assert!(stats.total_fees_collected >= previous.total_fees_collected);
+        let active = listings.iter().filter(|listing| listing.active).count();
+        assert_eq!(stats.active_listings as usize, active);
+        assert_eq!(stats.total_listings as usize, listings.len());
+        assert!(stats.total_sales <= stats.total_listings - stats.active_listings);
+        previous = stats;
+    }
+}
+
+proptest! {
+    // Every case deploys a fresh marketplace, so keep the case count modest
+    #![proptest_config(ProptestConfig::with_cases(64))]
+
+    #[test]
+    fn sale_splits_payment_exactly(
+        price in 1u64..1_000_000_000,
+        fee_bps in 0u64..=1000,
+        surplus in 0u64..1_000_000_000,
+    ) {
+        check_sale_split(price, fee_bps, surplus);
+    }
+
+    #[test]
+    fn outbid_bidders_are_refunded(
+        starting_price in 1u64..1_000_000,
+        fee_bps in 0u64..=1000,
+        bids in prop::collection::vec((0usize..3, 1u64..1_000_000), 1..10),
+        direct_refunds in any::<bool>(),
+    ) {
+        check_bid_sequence(starting_price, fee_bps, &bids, direct_refunds);
+    }
+
+    #[test]
+    fn listing_stats_stay_consistent(fee_bps in 0u64..=1000, ops in prop::collection::vec(listing_op(), 1..MAX_OPS)) {
+        check_listing_ops(fee_bps, &ops);
+    }
+}
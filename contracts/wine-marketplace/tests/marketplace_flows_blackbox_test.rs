@@ -0,0 +1,1705 @@
+use ed25519_dalek::{Signer, SigningKey};
+use multiversx_sc::codec::top_encode_to_vec_u8_or_panic;
+use multiversx_sc_scenario::api::StaticApi;
+use multiversx_sc_scenario::imports::*;
+use wine_common::errors::*;
+use wine_marketplace::admin::ProxyTrait as _;
+use wine_marketplace::auction::ProxyTrait as _;
+use wine_marketplace::blacklist::ProxyTrait as _;
+use wine_marketplace::cleanup::ProxyTrait as _;
+use wine_marketplace::cleanup::CLEANUP_RETENTION;
+use wine_marketplace::event_schema::ProxyTrait as _;
+use wine_marketplace::event_schema::EVENT_SCHEMA_VERSION;
+use wine_marketplace::exchange_rates::ProxyTrait as _;
+use wine_marketplace::listing::ProxyTrait as _;
+use wine_marketplace::market_summary::ProxyTrait as _;
+use wine_marketplace::market_summary::{MARKET_BOOK_DEPTH, SECONDS_PER_DAY};
+use wine_marketplace::offers::ProxyTrait as _;
+use wine_marketplace::quotes::ProxyTrait as _;
+use wine_marketplace::registry_cache::ProxyTrait as _;
+use wine_marketplace::relayed::ProxyTrait as _;
+use wine_marketplace::seller_limits::ProxyTrait as _;
+use wine_marketplace::stats_snapshots::ProxyTrait as _;
+use wine_marketplace::treasury::ProxyTrait as _;
+use wine_marketplace::ProxyTrait as _;
+use wine_marketplace::{
+    AuctionView, BidIntent, Listing, MarketSummary, MarketplaceStats, MixedSettlement, PurchaseIntent, SaleQuote,
+    StatsSnapshot, WineNftAttributes, WineReadModel, WineStatus,
+};
+
+const MARKETPLACE_PATH_EXPR: &str = "mxsc:output/wine-marketplace.mxsc.json";
+const MARKETPLACE_ADDRESS_EXPR: &str = "sc:wine-marketplace";
+const OWNER_ADDRESS_EXPR: &str = "address:owner";
+const REGISTRY_ADDRESS_EXPR: &str = "address:registry";
+const SELLER_ADDRESS_EXPR: &str = "address:seller";
+const BUYER_ADDRESS_EXPR: &str = "address:buyer";
+const BIDDER_ADDRESS_EXPR: &str = "address:bidder";
+
+const WINE_NFT_TOKEN_ID_EXPR: &str = "str:WINE-abcdef";
+const USDC_TOKEN_ID_EXPR: &str = "str:USDC-abcdef";
+const EGLD_000000_TOKEN_ID_EXPR: &str = "str:EGLD-000000";
+const LOYALTY_TOKEN_ID_EXPR: &str = "str:LOYAL-abcdef";
+
+const START_TIMESTAMP: u64 = 1_000;
+const DURATION: u64 = 3600;
+const PRICE: u64 = 1_000;
+const MIN_BID_INCREMENT: u64 = 10;
+
+type MarketplaceContract = ContractInfo<wine_marketplace::Proxy<StaticApi>>;
+
+fn user_error(message: &str) -> TxExpect {
+    TxExpect::user_error(format!("str:{message}"))
+}
+
+// Attributes the registry mints bottles of `wine_id` with; the test wines use their nonce as id
+fn wine_attributes(wine_id: u32) -> WineNftAttributes<StaticApi> {
+    WineNftAttributes {
+        wine_id,
+        vineyard: ManagedBuffer::from("Chateau Test"),
+        variety: ManagedBuffer::from("Merlot"),
+        vintage_year: 2020,
+        region: ManagedBuffer::from("Bordeaux"),
+        quality_grade: 8,
+        ipfs_hash: ManagedBuffer::new(),
+    }
+}
+
+fn encoded_wine_attributes(wine_id: u32) -> Option<Vec<u8>> {
+    Some(top_encode_to_vec_u8_or_panic(&wine_attributes(wine_id)))
+}
+
+// A released grade 8 wine produced by the seller, with certifications that do not expire
+fn wine_read_model(certified: bool) -> WineReadModel<StaticApi> {
+    let producer = AddressValue::from(SELLER_ADDRESS_EXPR).to_address();
+    WineReadModel {
+        price_per_bottle: BigUint::from(PRICE),
+        quality_grade: 8,
+        certified,
+        royalty_bps: 0,
+        status: WineStatus::Released,
+        producer: managed_address!(&producer),
+        counterfeit: false,
+        certified_until: 0,
+    }
+}
+
+struct MarketplaceTestState {
+    world: ScenarioWorld,
+    marketplace: MarketplaceContract,
+}
+
+impl MarketplaceTestState {
+    // Deploys the marketplace at a 2.5% fee; the seller holds wine NFTs 1 and 2, both pushed
+    // by the registry, and NFT 3 of a wine the registry has not pushed
+    fn new() -> Self {
+        let mut world = ScenarioWorld::new();
+        world.register_contract(MARKETPLACE_PATH_EXPR, wine_marketplace::ContractBuilder);
+
+        world.set_state_step(
+            SetStateStep::new()
+                .block_timestamp(START_TIMESTAMP)
+                .put_account(OWNER_ADDRESS_EXPR, Account::new().nonce(1))
+                // Receives the lock/unlock calls made as items enter and leave escrow
+                .put_account(REGISTRY_ADDRESS_EXPR, Account::new().nonce(1))
+                .put_account(
+                    SELLER_ADDRESS_EXPR,
+                    Account::new()
+                        .nonce(1)
+                        .esdt_nft_balance(WINE_NFT_TOKEN_ID_EXPR, 1, "1", encoded_wine_attributes(1))
+                        .esdt_nft_balance(WINE_NFT_TOKEN_ID_EXPR, 2, "1", encoded_wine_attributes(2))
+                        .esdt_nft_balance(WINE_NFT_TOKEN_ID_EXPR, 3, "1", encoded_wine_attributes(3)),
+                )
+                .put_account(BUYER_ADDRESS_EXPR, Account::new().nonce(1).balance("10000"))
+                .put_account(BIDDER_ADDRESS_EXPR, Account::new().nonce(1).balance("10000"))
+                .new_address(OWNER_ADDRESS_EXPR, 1, MARKETPLACE_ADDRESS_EXPR),
+        );
+
+        let mut marketplace = MarketplaceContract::new(MARKETPLACE_ADDRESS_EXPR);
+        let marketplace_code = world.code_expression(MARKETPLACE_PATH_EXPR);
+        let registry = AddressValue::from(REGISTRY_ADDRESS_EXPR).to_address();
+        world.sc_deploy(
+            ScDeployStep::new()
+                .from(OWNER_ADDRESS_EXPR)
+                .code(marketplace_code)
+                .call(marketplace.init(250u64, managed_address!(&registry))),
+        );
+        world.sc_call(
+            ScCallStep::new()
+                .from(OWNER_ADDRESS_EXPR)
+                .call(marketplace.set_wine_collection(TokenIdentifier::from("WINE-abcdef"))),
+        );
+
+        let mut state = MarketplaceTestState { world, marketplace };
+        state.sync_wine_read_model(1, false);
+        state.sync_wine_read_model(2, false);
+        state
+    }
+
+    fn set_block_timestamp(&mut self, timestamp: u64) {
+        self.world.set_state_step(SetStateStep::new().block_timestamp(timestamp));
+    }
+
+    fn create_listing(&mut self, nft_nonce: u64, price: u64) {
+        self.world.sc_call(
+            ScCallStep::new()
+                .from(SELLER_ADDRESS_EXPR)
+                .esdt_transfer(WINE_NFT_TOKEN_ID_EXPR, nft_nonce, "1")
+                .call(self.marketplace.create_listing(
+                    nft_nonce as u32,
+                    price,
+                    EgldOrEsdtTokenIdentifier::egld(),
+                    DURATION,
+                    OptionalValue::<bool>::None,
+                )),
+        );
+    }
+
+    fn buy_wine(&mut self, listing_id: u32, payment: u64) {
+        self.world.sc_call(
+            ScCallStep::new()
+                .from(BUYER_ADDRESS_EXPR)
+                .egld_value(payment)
+                .call(self.marketplace.buy_wine(listing_id, OptionalValue::<ManagedAddress<StaticApi>>::None)),
+        );
+    }
+
+    // Lists wine NFT 1 at PRICE in USDC, also accepting the loyalty token at 2 per USDC.
+    // The buyer holds 1000 of each token.
+    fn create_usdc_listing_accepting_loyalty(&mut self) {
+        let usdc = EgldOrEsdtTokenIdentifier::esdt(TokenIdentifier::from("USDC-abcdef"));
+        let loyalty = EgldOrEsdtTokenIdentifier::esdt(TokenIdentifier::from("LOYAL-abcdef"));
+        self.world.set_state_step(
+            SetStateStep::new().put_account(
+                BUYER_ADDRESS_EXPR,
+                Account::new()
+                    .nonce(1)
+                    .esdt_balance(USDC_TOKEN_ID_EXPR, "1000")
+                    .esdt_balance(LOYALTY_TOKEN_ID_EXPR, "1000"),
+            ),
+        );
+        for token in [&usdc, &loyalty] {
+            self.world.sc_call(
+                ScCallStep::new()
+                    .from(OWNER_ADDRESS_EXPR)
+                    .call(self.marketplace.add_supported_token(token.clone())),
+            );
+        }
+        self.world.sc_call(
+            ScCallStep::new()
+                .from(OWNER_ADDRESS_EXPR)
+                .call(self.marketplace.set_exchange_rate(usdc.clone(), loyalty.clone(), 2_000_000_000_000_000_000u64)),
+        );
+
+        self.world.sc_call(
+            ScCallStep::new()
+                .from(SELLER_ADDRESS_EXPR)
+                .esdt_transfer(WINE_NFT_TOKEN_ID_EXPR, 1, "1")
+                .call(self.marketplace.create_listing(1u32, PRICE, usdc, DURATION, OptionalValue::<bool>::None)),
+        );
+        self.world.sc_call(
+            ScCallStep::new()
+                .from(SELLER_ADDRESS_EXPR)
+                .call(self.marketplace.add_listing_payment_tokens(1u32, MultiValueVec::from(vec![loyalty]))),
+        );
+    }
+
+    fn create_auction(&mut self) {
+        self.world.sc_call(
+            ScCallStep::new()
+                .from(SELLER_ADDRESS_EXPR)
+                .esdt_transfer(WINE_NFT_TOKEN_ID_EXPR, 1, "1")
+                .call(self.marketplace.create_auction(
+                    1u32,
+                    PRICE,
+                    EgldOrEsdtTokenIdentifier::egld(),
+                    DURATION,
+                    MIN_BID_INCREMENT,
+                )),
+        );
+    }
+
+    fn place_bid(&mut self, bidder: &str, auction_id: u32, amount: u64) {
+        self.world.sc_call(
+            ScCallStep::new()
+                .from(bidder)
+                .egld_value(amount)
+                .call(self.marketplace.place_bid(auction_id, OptionalValue::<ManagedAddress<StaticApi>>::None)),
+        );
+    }
+
+    // Pushes a released grade 8 wine produced by the seller, as the registry would
+    fn sync_wine_read_model(&mut self, wine_id: u32, certified: bool) {
+        self.sync_wine_status(wine_id, certified, WineStatus::Released);
+    }
+
+    fn sync_wine_status(&mut self, wine_id: u32, certified: bool, status: WineStatus) {
+        let mut read_model = wine_read_model(certified);
+        read_model.status = status;
+        self.push_wine_read_model(wine_id, read_model);
+    }
+
+    fn push_wine_read_model(&mut self, wine_id: u32, read_model: WineReadModel<StaticApi>) {
+        self.world.sc_call(
+            ScCallStep::new()
+                .from(REGISTRY_ADDRESS_EXPR)
+                .call(self.marketplace.sync_wine_read_model(wine_id, read_model)),
+        );
+    }
+
+    fn listing(&mut self, listing_id: u32) -> Listing<StaticApi> {
+        self.world.quick_query(self.marketplace.get_listing(listing_id))
+    }
+
+    fn stats(&mut self) -> MarketplaceStats<StaticApi> {
+        self.world.quick_query(self.marketplace.get_marketplace_stats())
+    }
+
+    fn market_summary(&mut self, wine_nft_id: u32) -> Vec<MarketSummary<StaticApi>> {
+        let summaries: MultiValueEncoded<StaticApi, MarketSummary<StaticApi>> =
+            self.world.quick_query(self.marketplace.get_market_summary(wine_nft_id));
+        summaries.into_iter().collect()
+    }
+
+    fn auction_end(&mut self, auction_id: u32) -> u64 {
+        let auction: wine_marketplace::auction::Auction<StaticApi> =
+            self.world.quick_query(self.marketplace.get_auction(auction_id));
+        auction.end_timestamp
+    }
+}
+
+#[test]
+fn listing_lifecycle_create_buy() {
+    let mut state = MarketplaceTestState::new();
+
+    state.create_listing(1, PRICE);
+    let listing = state.listing(1);
+    assert!(listing.active);
+    assert_eq!(listing.deadline, START_TIMESTAMP + DURATION);
+    let stats = state.stats();
+    assert_eq!(stats.total_listings, 1);
+    assert_eq!(stats.active_listings, 1);
+    state.world.check_state_step(
+        CheckStateStep::new().put_account(
+            MARKETPLACE_ADDRESS_EXPR,
+            CheckAccount::new().esdt_nft_balance_and_attributes(
+                WINE_NFT_TOKEN_ID_EXPR,
+                1,
+                "1",
+                Some(wine_attributes(1)),
+            ),
+        ),
+    );
+
+    state.buy_wine(1, PRICE);
+
+    assert!(!state.listing(1).active);
+    let stats = state.stats();
+    assert_eq!(stats.total_listings, 1);
+    assert_eq!(stats.active_listings, 0);
+    assert_eq!(stats.total_sales, 1);
+    assert_eq!(stats.total_volume, PRICE);
+    state.world.check_state_step(
+        CheckStateStep::new().put_account(
+            BUYER_ADDRESS_EXPR,
+            CheckAccount::new().balance("9000").esdt_nft_balance_and_attributes(
+                WINE_NFT_TOKEN_ID_EXPR,
+                1,
+                "1",
+                Some(wine_attributes(1)),
+            ),
+        ),
+    );
+
+    // A sold listing can be neither bought again nor cancelled
+    state.world.sc_call(
+        ScCallStep::new()
+            .from(BUYER_ADDRESS_EXPR)
+            .egld_value(PRICE)
+            .call(state.marketplace.buy_wine(1u32, OptionalValue::<ManagedAddress<StaticApi>>::None))
+            .expect(user_error(ERR_LISTING_IS_NOT_ACTIVE)),
+    );
+    state.world.sc_call(
+        ScCallStep::new()
+            .from(SELLER_ADDRESS_EXPR)
+            .call(state.marketplace.cancel_listing(1u32))
+            .expect(user_error(ERR_LISTING_IS_NOT_ACTIVE)),
+    );
+}
+
+#[test]
+fn listing_lifecycle_cancel() {
+    let mut state = MarketplaceTestState::new();
+    state.create_listing(1, PRICE);
+
+    state.world.sc_call(
+        ScCallStep::new()
+            .from(BUYER_ADDRESS_EXPR)
+            .call(state.marketplace.cancel_listing(1u32))
+            .expect(user_error(ERR_ONLY_SELLER_CAN_CANCEL_LISTING)),
+    );
+    state.world.sc_call(
+        ScCallStep::new()
+            .from(SELLER_ADDRESS_EXPR)
+            .call(state.marketplace.cancel_listing(1u32)),
+    );
+
+    assert!(!state.listing(1).active);
+    let stats = state.stats();
+    assert_eq!(stats.active_listings, 0);
+    assert_eq!(stats.total_sales, 0);
+    state.world.check_state_step(
+        CheckStateStep::new().put_account(
+            SELLER_ADDRESS_EXPR,
+            CheckAccount::new().esdt_nft_balance_and_attributes(
+                WINE_NFT_TOKEN_ID_EXPR,
+                1,
+                "1",
+                Some(wine_attributes(1)),
+            ),
+        ),
+    );
+}
+
+#[test]
+fn listed_wine_id_must_match_the_escrowed_nft() {
+    let mut state = MarketplaceTestState::new();
+    state.world.set_state_step(
+        SetStateStep::new().put_account(
+            SELLER_ADDRESS_EXPR,
+            Account::new()
+                .nonce(1)
+                .esdt_nft_balance(WINE_NFT_TOKEN_ID_EXPR, 1, "1", encoded_wine_attributes(1))
+                .esdt_nft_balance("str:OTHER-abcdef", 1, "1", encoded_wine_attributes(1)),
+        ),
+    );
+
+    // A bottle of wine 1 cannot be passed off as wine 2, e.g. to lock wine 2 in the registry
+    state.world.sc_call(
+        ScCallStep::new()
+            .from(SELLER_ADDRESS_EXPR)
+            .esdt_transfer(WINE_NFT_TOKEN_ID_EXPR, 1, "1")
+            .call(state.marketplace.create_listing(
+                2u32,
+                PRICE,
+                EgldOrEsdtTokenIdentifier::egld(),
+                DURATION,
+                OptionalValue::<bool>::None,
+            ))
+            .expect(user_error(ERR_WINE_ID_DOES_NOT_MATCH_NFT)),
+    );
+    // Attributes only tell the wine for tokens the registry minted
+    state.world.sc_call(
+        ScCallStep::new()
+            .from(SELLER_ADDRESS_EXPR)
+            .esdt_transfer("str:OTHER-abcdef", 1, "1")
+            .call(state.marketplace.create_auction(
+                1u32,
+                PRICE,
+                EgldOrEsdtTokenIdentifier::egld(),
+                DURATION,
+                MIN_BID_INCREMENT,
+            ))
+            .expect(user_error(ERR_NOT_A_WINE_NFT)),
+    );
+
+    state.create_listing(1, PRICE);
+    assert_eq!(state.listing(1).wine_nft_id, 1);
+}
+
+#[test]
+fn recalled_wine_cannot_be_listed_under_another_id() {
+    let mut state = MarketplaceTestState::new();
+    state.sync_wine_status(1, false, WineStatus::Recalled);
+
+    // The recall gate applies to the wine the item is a bottle of, not the declared one
+    for (wine_id, error) in [(2u32, ERR_WINE_ID_DOES_NOT_MATCH_NFT), (1u32, ERR_WINE_HAS_BEEN_RECALLED)] {
+        state.world.sc_call(
+            ScCallStep::new()
+                .from(SELLER_ADDRESS_EXPR)
+                .esdt_transfer(WINE_NFT_TOKEN_ID_EXPR, 1, "1")
+                .call(state.marketplace.create_listing(
+                    wine_id,
+                    PRICE,
+                    EgldOrEsdtTokenIdentifier::egld(),
+                    DURATION,
+                    OptionalValue::<bool>::None,
+                ))
+                .expect(user_error(error)),
+        );
+    }
+    assert_eq!(state.stats().active_listings, 0);
+}
+
+#[test]
+fn counterfeit_or_unsynced_wines_cannot_be_listed() {
+    let mut state = MarketplaceTestState::new();
+    let mut read_model = wine_read_model(false);
+    read_model.counterfeit = true;
+    state.push_wine_read_model(1, read_model);
+
+    for (nft_nonce, error) in [
+        (1u64, ERR_WINE_HAS_BEEN_FLAGGED_AS_COUNTERFEIT),
+        (3u64, ERR_WINE_NOT_SYNCED_FROM_REGISTRY),
+    ] {
+        state.world.sc_call(
+            ScCallStep::new()
+                .from(SELLER_ADDRESS_EXPR)
+                .esdt_transfer(WINE_NFT_TOKEN_ID_EXPR, nft_nonce, "1")
+                .call(state.marketplace.create_listing(
+                    nft_nonce as u32,
+                    PRICE,
+                    EgldOrEsdtTokenIdentifier::egld(),
+                    DURATION,
+                    OptionalValue::<bool>::None,
+                ))
+                .expect(user_error(error)),
+        );
+    }
+    assert_eq!(state.stats().active_listings, 0);
+}
+
+#[test]
+fn overpayment_surplus_is_refunded() {
+    let mut state = MarketplaceTestState::new();
+    state.create_listing(1, PRICE);
+
+    state.world.sc_call(
+        ScCallStep::new()
+            .from(BUYER_ADDRESS_EXPR)
+            .egld_value(PRICE - 1)
+            .call(state.marketplace.buy_wine(1u32, OptionalValue::<ManagedAddress<StaticApi>>::None))
+            .expect(user_error(ERR_INSUFFICIENT_PAYMENT)),
+    );
+
+    state.buy_wine(1, PRICE + 500);
+
+    // The buyer is only charged the price; fee and proceeds are taken from the price alone
+    state.world.check_state_step(
+        CheckStateStep::new()
+            .put_account(BUYER_ADDRESS_EXPR, CheckAccount::new().balance("9000"))
+            .put_account(SELLER_ADDRESS_EXPR, CheckAccount::new().balance("975"))
+            .put_account(OWNER_ADDRESS_EXPR, CheckAccount::new().balance("25"))
+            .put_account(MARKETPLACE_ADDRESS_EXPR, CheckAccount::new().balance("0")),
+    );
+    let stats = state.stats();
+    assert_eq!(stats.total_volume, PRICE);
+    assert_eq!(stats.total_fees_collected, 25u64);
+}
+
+#[test]
+fn buy_quote_matches_settlement() {
+    let mut state = MarketplaceTestState::new();
+    state.create_listing(1, PRICE);
+
+    let buyer = AddressValue::from(BUYER_ADDRESS_EXPR).to_address();
+    let quote: SaleQuote<StaticApi> = state.world.quick_query(state.marketplace.quote_buy(
+        1u32,
+        managed_address!(&buyer),
+        OptionalValue::<EgldOrEsdtTokenIdentifier<StaticApi>>::None,
+    ));
+    assert_eq!(quote.payment_token, EgldOrEsdtTokenIdentifier::egld());
+    assert_eq!(quote.amount_due, PRICE);
+    assert_eq!(quote.fee_percent, 250);
+    assert_eq!(quote.fee_discount_bps, 0);
+    assert_eq!(quote.marketplace_fee, 25u64);
+    assert_eq!(quote.seller_amount, 975u64);
+
+    // The seller cannot buy their own listing, so there is nothing to quote
+    let seller = AddressValue::from(SELLER_ADDRESS_EXPR).to_address();
+    state.world.sc_query(
+        ScQueryStep::new()
+            .call(state.marketplace.quote_buy(
+                1u32,
+                managed_address!(&seller),
+                OptionalValue::<EgldOrEsdtTokenIdentifier<StaticApi>>::None,
+            ))
+            .expect(user_error(ERR_CANNOT_BUY_YOUR_OWN_LISTING)),
+    );
+
+    state.buy_wine(1, PRICE);
+    state.world.check_state_step(
+        CheckStateStep::new()
+            .put_account(SELLER_ADDRESS_EXPR, CheckAccount::new().balance("975"))
+            .put_account(OWNER_ADDRESS_EXPR, CheckAccount::new().balance("25")),
+    );
+}
+
+#[test]
+fn fee_math_rounds_down_and_follows_fee_percent() {
+    let mut state = MarketplaceTestState::new();
+
+    // 2.5% of 999 is 24.975, rounded down in the seller's favour
+    state.create_listing(1, 999);
+    state.buy_wine(1, 999);
+    state.world.check_state_step(
+        CheckStateStep::new()
+            .put_account(SELLER_ADDRESS_EXPR, CheckAccount::new().balance("975"))
+            .put_account(OWNER_ADDRESS_EXPR, CheckAccount::new().balance("24")),
+    );
+
+    state.world.sc_call(
+        ScCallStep::new()
+            .from(OWNER_ADDRESS_EXPR)
+            .call(state.marketplace.set_marketplace_fee_percent(1000u64)),
+    );
+    state.create_listing(2, 2_000);
+    state.buy_wine(2, 2_000);
+    state.world.check_state_step(
+        CheckStateStep::new()
+            .put_account(SELLER_ADDRESS_EXPR, CheckAccount::new().balance("2775"))
+            .put_account(OWNER_ADDRESS_EXPR, CheckAccount::new().balance("224")),
+    );
+
+    let stats = state.stats();
+    assert_eq!(stats.total_sales, 2);
+    assert_eq!(stats.total_volume, 2_999u64);
+    assert_eq!(stats.total_fees_collected, 224u64);
+}
+
+#[test]
+fn expired_listing_cannot_be_bought_and_returns_to_seller() {
+    let mut state = MarketplaceTestState::new();
+    state.create_listing(1, PRICE);
+
+    // Buying is allowed up to and including the deadline
+    state.set_block_timestamp(START_TIMESTAMP + DURATION + 1);
+    state.world.sc_call(
+        ScCallStep::new()
+            .from(BUYER_ADDRESS_EXPR)
+            .egld_value(PRICE)
+            .call(state.marketplace.buy_wine(1u32, OptionalValue::<ManagedAddress<StaticApi>>::None))
+            .expect(user_error(ERR_LISTING_HAS_EXPIRED)),
+    );
+
+    state.world.sc_call(
+        ScCallStep::new()
+            .from(SELLER_ADDRESS_EXPR)
+            .call(state.marketplace.cancel_listing(1u32)),
+    );
+
+    assert!(!state.listing(1).active);
+    assert_eq!(state.stats().active_listings, 0);
+    state.world.check_state_step(
+        CheckStateStep::new()
+            .put_account(
+                SELLER_ADDRESS_EXPR,
+                CheckAccount::new().balance("0").esdt_nft_balance_and_attributes(
+                    WINE_NFT_TOKEN_ID_EXPR,
+                    1,
+                    "1",
+                    Some(wine_attributes(1)),
+                ),
+            )
+            .put_account(BUYER_ADDRESS_EXPR, CheckAccount::new().balance("10000")),
+    );
+}
+
+#[test]
+fn relisting_runs_the_listing_gates() {
+    let mut state = MarketplaceTestState::new();
+    state.create_listing(1, PRICE);
+    state.create_listing(2, PRICE);
+    state.set_block_timestamp(START_TIMESTAMP + DURATION + 1);
+    state.sync_wine_status(1, false, WineStatus::Recalled);
+
+    let seller = AddressValue::from(SELLER_ADDRESS_EXPR).to_address();
+    state.world.sc_call(
+        ScCallStep::new()
+            .from(OWNER_ADDRESS_EXPR)
+            .call(state.marketplace.add_to_blacklist(MultiValueVec::from(vec![managed_address!(&seller)]))),
+    );
+    state.world.sc_call(
+        ScCallStep::new()
+            .from(SELLER_ADDRESS_EXPR)
+            .call(state.marketplace.relist(2u32))
+            .expect(user_error(ERR_ADDRESS_IS_BLACKLISTED)),
+    );
+    state.world.sc_call(
+        ScCallStep::new()
+            .from(OWNER_ADDRESS_EXPR)
+            .call(state.marketplace.remove_from_blacklist(MultiValueVec::from(vec![managed_address!(&seller)]))),
+    );
+
+    // Lowering the limit below the items already open blocks relisting them
+    state.world.sc_call(
+        ScCallStep::new()
+            .from(OWNER_ADDRESS_EXPR)
+            .call(state.marketplace.set_max_active_items(1u32, 0u32)),
+    );
+    state.world.sc_call(
+        ScCallStep::new()
+            .from(SELLER_ADDRESS_EXPR)
+            .call(state.marketplace.relist(2u32))
+            .expect(user_error(ERR_TOO_MANY_ACTIVE_ITEMS_FOR_THIS_SELLER)),
+    );
+    state.world.sc_call(
+        ScCallStep::new()
+            .from(OWNER_ADDRESS_EXPR)
+            .call(state.marketplace.set_max_active_items(0u32, 0u32)),
+    );
+
+    state.world.sc_call(
+        ScCallStep::new()
+            .from(SELLER_ADDRESS_EXPR)
+            .call(state.marketplace.relist(1u32))
+            .expect(user_error(ERR_WINE_HAS_BEEN_RECALLED)),
+    );
+    state.world.sc_call(
+        ScCallStep::new()
+            .from(SELLER_ADDRESS_EXPR)
+            .call(state.marketplace.relist(2u32)),
+    );
+    assert_eq!(state.listing(2).deadline, START_TIMESTAMP + 2 * DURATION + 1);
+}
+
+#[test]
+fn payment_in_two_tokens_returns_change_per_token() {
+    let mut state = MarketplaceTestState::new();
+    state.create_usdc_listing_accepting_loyalty();
+
+    // 600 LOYAL cover 300 USDC of the price, so 700 of the 800 USDC are used
+    state.world.sc_call(
+        ScCallStep::new()
+            .from(BUYER_ADDRESS_EXPR)
+            .esdt_transfer(LOYALTY_TOKEN_ID_EXPR, 0, "600")
+            .esdt_transfer(USDC_TOKEN_ID_EXPR, 0, "800")
+            .call(state.marketplace.buy_wine(1u32, OptionalValue::<ManagedAddress<StaticApi>>::None)),
+    );
+
+    assert!(!state.listing(1).active);
+    let stats = state.stats();
+    assert_eq!(stats.total_sales, 1);
+    assert_eq!(stats.active_listings, 0);
+    // Each part pays the 2.5% fee in its own token, rounded down
+    state.world.check_state_step(
+        CheckStateStep::new()
+            .put_account(
+                BUYER_ADDRESS_EXPR,
+                CheckAccount::new()
+                    .esdt_balance(LOYALTY_TOKEN_ID_EXPR, "400")
+                    .esdt_balance(USDC_TOKEN_ID_EXPR, "300")
+                    .esdt_nft_balance_and_attributes(WINE_NFT_TOKEN_ID_EXPR, 1, "1", Some(wine_attributes(1))),
+            )
+            .put_account(
+                SELLER_ADDRESS_EXPR,
+                CheckAccount::new()
+                    .esdt_balance(LOYALTY_TOKEN_ID_EXPR, "585")
+                    .esdt_balance(USDC_TOKEN_ID_EXPR, "683"),
+            )
+            .put_account(
+                OWNER_ADDRESS_EXPR,
+                CheckAccount::new()
+                    .esdt_balance(LOYALTY_TOKEN_ID_EXPR, "15")
+                    .esdt_balance(USDC_TOKEN_ID_EXPR, "17"),
+            ),
+    );
+}
+
+#[test]
+fn payment_in_several_tokens_is_validated() {
+    let mut state = MarketplaceTestState::new();
+    state.create_usdc_listing_accepting_loyalty();
+
+    // 600 LOYAL and 600 USDC are worth 900 USDC
+    state.world.sc_call(
+        ScCallStep::new()
+            .from(BUYER_ADDRESS_EXPR)
+            .esdt_transfer(LOYALTY_TOKEN_ID_EXPR, 0, "600")
+            .esdt_transfer(USDC_TOKEN_ID_EXPR, 0, "600")
+            .call(state.marketplace.buy_wine(1u32, OptionalValue::<ManagedAddress<StaticApi>>::None))
+            .expect(user_error(ERR_INSUFFICIENT_PAYMENT)),
+    );
+    state.world.sc_call(
+        ScCallStep::new()
+            .from(BUYER_ADDRESS_EXPR)
+            .esdt_transfer(USDC_TOKEN_ID_EXPR, 0, "500")
+            .esdt_transfer(USDC_TOKEN_ID_EXPR, 0, "500")
+            .call(state.marketplace.buy_wine(1u32, OptionalValue::<ManagedAddress<StaticApi>>::None))
+            .expect(user_error(ERR_DUPLICATE_PAYMENT_TOKEN)),
+    );
+    assert!(state.listing(1).active);
+}
+
+#[test]
+fn outbid_funds_are_escrowed_until_withdrawn() {
+    let mut state = MarketplaceTestState::new();
+    state.create_auction();
+
+    state.world.sc_call(
+        ScCallStep::new()
+            .from(BIDDER_ADDRESS_EXPR)
+            .egld_value(PRICE + MIN_BID_INCREMENT - 1)
+            .call(state.marketplace.place_bid(1u32, OptionalValue::<ManagedAddress<StaticApi>>::None))
+            .expect(user_error(ERR_BID_TOO_LOW)),
+    );
+    state.place_bid(BIDDER_ADDRESS_EXPR, 1, 1_010);
+    state.place_bid(BUYER_ADDRESS_EXPR, 1, 1_100);
+
+    let bidder = AddressValue::from(BIDDER_ADDRESS_EXPR).to_address();
+    let outbid: RustBigUint = state
+        .world
+        .quick_query(state.marketplace.get_outbid_funds(1u32, managed_address!(&bidder)));
+    assert_eq!(outbid, RustBigUint::from(1_010u64));
+    state.world.check_state_step(
+        CheckStateStep::new()
+            .put_account(BIDDER_ADDRESS_EXPR, CheckAccount::new().balance("8990"))
+            .put_account(MARKETPLACE_ADDRESS_EXPR, CheckAccount::new().balance("2110")),
+    );
+
+    state.world.sc_call(
+        ScCallStep::new()
+            .from(BIDDER_ADDRESS_EXPR)
+            .call(state.marketplace.withdraw_outbid_funds(1u32)),
+    );
+    state.world.sc_call(
+        ScCallStep::new()
+            .from(BIDDER_ADDRESS_EXPR)
+            .call(state.marketplace.withdraw_outbid_funds(1u32))
+            .expect(user_error(ERR_NOTHING_TO_WITHDRAW)),
+    );
+    state.world.check_state_step(
+        CheckStateStep::new()
+            .put_account(BIDDER_ADDRESS_EXPR, CheckAccount::new().balance("10000"))
+            .put_account(MARKETPLACE_ADDRESS_EXPR, CheckAccount::new().balance("1100")),
+    );
+}
+
+#[test]
+fn outbid_funds_are_pushed_back_with_direct_refunds() {
+    let mut state = MarketplaceTestState::new();
+    state.world.sc_call(
+        ScCallStep::new()
+            .from(OWNER_ADDRESS_EXPR)
+            .call(state.marketplace.set_direct_outbid_refunds(true)),
+    );
+    state.create_auction();
+
+    state.place_bid(BIDDER_ADDRESS_EXPR, 1, 1_010);
+    state.place_bid(BUYER_ADDRESS_EXPR, 1, 1_100);
+
+    state.world.check_state_step(
+        CheckStateStep::new()
+            .put_account(BIDDER_ADDRESS_EXPR, CheckAccount::new().balance("10000"))
+            .put_account(BUYER_ADDRESS_EXPR, CheckAccount::new().balance("8900"))
+            .put_account(MARKETPLACE_ADDRESS_EXPR, CheckAccount::new().balance("1100")),
+    );
+}
+
+#[test]
+fn finalized_auction_pays_seller_net_of_fee() {
+    let mut state = MarketplaceTestState::new();
+    state.create_auction();
+    state.place_bid(BUYER_ADDRESS_EXPR, 1, 1_100);
+
+    state.world.sc_call(
+        ScCallStep::new()
+            .from(SELLER_ADDRESS_EXPR)
+            .call(state.marketplace.finalize_auction(1u32))
+            .expect(user_error(ERR_AUCTION_HAS_NOT_ENDED_YET)),
+    );
+
+    state.set_block_timestamp(START_TIMESTAMP + DURATION);
+    state.world.sc_call(
+        ScCallStep::new()
+            .from(SELLER_ADDRESS_EXPR)
+            .call(state.marketplace.finalize_auction(1u32)),
+    );
+
+    // 2.5% of 1100 is 27.5, rounded down
+    state.world.check_state_step(
+        CheckStateStep::new()
+            .put_account(SELLER_ADDRESS_EXPR, CheckAccount::new().balance("1073"))
+            .put_account(OWNER_ADDRESS_EXPR, CheckAccount::new().balance("27"))
+            .put_account(
+                BUYER_ADDRESS_EXPR,
+                CheckAccount::new().balance("8900").esdt_nft_balance_and_attributes(
+                    WINE_NFT_TOKEN_ID_EXPR,
+                    1,
+                    "1",
+                    Some(wine_attributes(1)),
+                ),
+            ),
+    );
+    let stats = state.stats();
+    assert_eq!(stats.active_auctions, 0);
+    assert_eq!(stats.total_sales, 1);
+}
+
+#[test]
+fn next_bid_quote_follows_bids() {
+    let mut state = MarketplaceTestState::new();
+    state.create_auction();
+
+    // The first bid has to beat the starting price by the increment
+    let quote: SaleQuote<StaticApi> = state.world.quick_query(state.marketplace.quote_next_bid(1u32));
+    assert_eq!(quote.amount_due, PRICE + MIN_BID_INCREMENT);
+    state.world.sc_call(
+        ScCallStep::new()
+            .from(BIDDER_ADDRESS_EXPR)
+            .egld_value(PRICE + MIN_BID_INCREMENT - 1)
+            .call(state.marketplace.place_bid(1u32, OptionalValue::<ManagedAddress<StaticApi>>::None))
+            .expect(user_error(ERR_BID_TOO_LOW)),
+    );
+    state.place_bid(BIDDER_ADDRESS_EXPR, 1, PRICE + MIN_BID_INCREMENT);
+
+    // 2.5% of 1020 is 25.5, rounded down
+    let quote: SaleQuote<StaticApi> = state.world.quick_query(state.marketplace.quote_next_bid(1u32));
+    assert_eq!(quote.amount_due, 1_020u64);
+    assert_eq!(quote.marketplace_fee, 25u64);
+    assert_eq!(quote.seller_amount, 995u64);
+
+    state.set_block_timestamp(START_TIMESTAMP + DURATION);
+    state.world.sc_query(
+        ScQueryStep::new()
+            .call(state.marketplace.quote_next_bid(1u32))
+            .expect(user_error(ERR_AUCTION_HAS_ENDED)),
+    );
+}
+
+#[test]
+fn late_bid_extends_auction_end() {
+    let mut state = MarketplaceTestState::new();
+    state.create_auction();
+    let end = START_TIMESTAMP + DURATION;
+
+    // Outside the default 10 minute window: no extension
+    state.set_block_timestamp(end - 600);
+    state.place_bid(BIDDER_ADDRESS_EXPR, 1, 1_010);
+    assert_eq!(state.auction_end(1), end);
+    let extensions: u32 = state.world.quick_query(state.marketplace.get_auction_extension_count(1u32));
+    assert_eq!(extensions, 0);
+
+    // Inside the window: pushed back by the default 10 minutes
+    state.set_block_timestamp(end - 1);
+    state.place_bid(BUYER_ADDRESS_EXPR, 1, 1_100);
+    assert_eq!(state.auction_end(1), end + 600);
+    let extensions: u32 = state.world.quick_query(state.marketplace.get_auction_extension_count(1u32));
+    assert_eq!(extensions, 1);
+
+    // Bids are still accepted at the original end, which is a full window before the new one
+    state.set_block_timestamp(end);
+    state.place_bid(BIDDER_ADDRESS_EXPR, 1, 1_200);
+    assert_eq!(state.auction_end(1), end + 600);
+}
+
+#[test]
+fn auction_view_tracks_time_left_across_extensions() {
+    let mut state = MarketplaceTestState::new();
+    state.create_auction();
+    let end = START_TIMESTAMP + DURATION;
+
+    let view: AuctionView<StaticApi> = state.world.quick_query(state.marketplace.get_auction_view(1u32));
+    assert_eq!(view.time_left, DURATION);
+    assert_eq!(view.min_next_bid, PRICE + MIN_BID_INCREMENT);
+    assert!(!view.anti_snipe_active);
+
+    // Inside the default 600 second window a bid would extend the auction
+    state.set_block_timestamp(end - 100);
+    let view: AuctionView<StaticApi> = state.world.quick_query(state.marketplace.get_auction_view(1u32));
+    assert_eq!(view.time_left, 100);
+    assert!(view.anti_snipe_active);
+
+    state.place_bid(BIDDER_ADDRESS_EXPR, 1, 1_010);
+    let view: AuctionView<StaticApi> = state.world.quick_query(state.marketplace.get_auction_view(1u32));
+    assert_eq!(view.end_timestamp, end + 600);
+    assert_eq!(view.time_left, 700);
+    assert_eq!(view.extension_count, 1);
+    assert!(!view.anti_snipe_active);
+    let time_left: u64 = state.world.quick_query(state.marketplace.get_time_left(1u32));
+    assert_eq!(time_left, 700);
+    let min_next_bid: RustBigUint = state.world.quick_query(state.marketplace.get_min_next_bid(1u32));
+    assert_eq!(min_next_bid, RustBigUint::from(1_020u64));
+
+    // Bidding has closed, although the auction is not finalized yet
+    state.set_block_timestamp(end + 600);
+    let view: AuctionView<StaticApi> = state.world.quick_query(state.marketplace.get_auction_view(1u32));
+    assert!(view.active);
+    assert_eq!(view.time_left, 0);
+    assert!(!view.anti_snipe_active);
+}
+
+#[test]
+fn anti_snipe_extensions_are_capped() {
+    let mut state = MarketplaceTestState::new();
+    state.create_auction();
+    state.world.sc_call(
+        ScCallStep::new()
+            .from(OWNER_ADDRESS_EXPR)
+            .call(state.marketplace.set_auction_anti_snipe_config(1u32, 300u64, 120u64, 1u32)),
+    );
+    let end = START_TIMESTAMP + DURATION;
+
+    state.set_block_timestamp(end - 10);
+    state.place_bid(BIDDER_ADDRESS_EXPR, 1, 1_010);
+    assert_eq!(state.auction_end(1), end + 120);
+
+    // The single allowed extension is used up
+    state.set_block_timestamp(end + 100);
+    state.place_bid(BUYER_ADDRESS_EXPR, 1, 1_100);
+    assert_eq!(state.auction_end(1), end + 120);
+
+    state.set_block_timestamp(end + 120);
+    state.world.sc_call(
+        ScCallStep::new()
+            .from(BIDDER_ADDRESS_EXPR)
+            .egld_value(1_200u64)
+            .call(state.marketplace.place_bid(1u32, OptionalValue::<ManagedAddress<StaticApi>>::None))
+            .expect(user_error(ERR_AUCTION_HAS_ENDED)),
+    );
+}
+
+#[test]
+fn sold_listings_are_cleaned_up_after_retention() {
+    let mut state = MarketplaceTestState::new();
+    state.create_listing(1, PRICE);
+    state.create_listing(2, PRICE);
+    state.buy_wine(1, PRICE);
+
+    // Too early for anyone but an admin
+    state.world.sc_call(
+        ScCallStep::new()
+            .from(BUYER_ADDRESS_EXPR)
+            .call(state.marketplace.cleanup_listings(MultiValueVec::from(vec![1u32, 2u32])))
+            .expect_value(0u32),
+    );
+
+    // The open listing is kept however old it is
+    state.set_block_timestamp(START_TIMESTAMP + DURATION + CLEANUP_RETENTION + 1);
+    state.world.sc_call(
+        ScCallStep::new()
+            .from(BUYER_ADDRESS_EXPR)
+            .call(state.marketplace.cleanup_listings(MultiValueVec::from(vec![1u32, 2u32])))
+            .expect_value(1u32),
+    );
+    state.world.sc_call(
+        ScCallStep::new()
+            .from(BUYER_ADDRESS_EXPR)
+            .call(state.marketplace.cleanup_listings(MultiValueVec::from(vec![1u32])))
+            .expect_value(0u32),
+    );
+    assert!(state.listing(2).active);
+
+    state.world.sc_call(
+        ScCallStep::new()
+            .from(SELLER_ADDRESS_EXPR)
+            .call(state.marketplace.cancel_listing(2u32)),
+    );
+    state.world.sc_call(
+        ScCallStep::new()
+            .from(OWNER_ADDRESS_EXPR)
+            .call(state.marketplace.cleanup_listings(MultiValueVec::from(vec![2u32])))
+            .expect_value(1u32),
+    );
+    let stats = state.stats();
+    assert_eq!(stats.total_listings, 2);
+    assert_eq!(stats.total_sales, 1);
+}
+
+#[test]
+fn outbid_funds_stay_withdrawable_after_auction_cleanup() {
+    let mut state = MarketplaceTestState::new();
+    state.create_auction();
+    state.place_bid(BIDDER_ADDRESS_EXPR, 1, 1_010);
+    state.place_bid(BUYER_ADDRESS_EXPR, 1, 1_100);
+
+    // A running auction is never cleaned up
+    state.world.sc_call(
+        ScCallStep::new()
+            .from(OWNER_ADDRESS_EXPR)
+            .call(state.marketplace.cleanup_auctions(MultiValueVec::from(vec![1u32])))
+            .expect_value(0u32),
+    );
+
+    state.set_block_timestamp(START_TIMESTAMP + DURATION);
+    state.world.sc_call(
+        ScCallStep::new()
+            .from(SELLER_ADDRESS_EXPR)
+            .call(state.marketplace.finalize_auction(1u32)),
+    );
+    state.world.sc_call(
+        ScCallStep::new()
+            .from(OWNER_ADDRESS_EXPR)
+            .call(state.marketplace.cleanup_auctions(MultiValueVec::from(vec![1u32])))
+            .expect_value(1u32),
+    );
+
+    state.world.sc_call(
+        ScCallStep::new()
+            .from(BIDDER_ADDRESS_EXPR)
+            .call(state.marketplace.withdraw_outbid_funds(1u32)),
+    );
+    state.world.check_state_step(
+        CheckStateStep::new().put_account(BIDDER_ADDRESS_EXPR, CheckAccount::new().balance("10000")),
+    );
+}
+
+#[test]
+fn stats_are_snapshotted_at_epoch_boundaries() {
+    let mut state = MarketplaceTestState::new();
+    state.world.set_state_step(SetStateStep::new().block_epoch(10));
+    state.create_listing(1, PRICE);
+    state.create_listing(2, PRICE);
+    state.buy_wine(1, PRICE);
+    let open_epoch: u64 = state.world.quick_query(state.marketplace.get_open_stats_epoch());
+    assert_eq!(open_epoch, 10);
+
+    // The sale in epoch 11 first freezes the epoch 10 figures
+    state.world.set_state_step(SetStateStep::new().block_epoch(11));
+    state.buy_wine(2, PRICE);
+    let snapshot: OptionalValue<StatsSnapshot<StaticApi>> =
+        state.world.quick_query(state.marketplace.get_stats_snapshot(10u64));
+    let snapshot = snapshot.into_option().unwrap();
+    assert_eq!(snapshot.stats.total_sales, 1);
+    assert_eq!(snapshot.stats.total_volume, PRICE);
+    assert_eq!(snapshot.collection_count, 1);
+    assert_eq!(state.stats().total_sales, 2);
+
+    // Anyone may close an epoch without a stats change
+    state.world.set_state_step(SetStateStep::new().block_epoch(12));
+    state.world.sc_call(
+        ScCallStep::new()
+            .from(BUYER_ADDRESS_EXPR)
+            .call(state.marketplace.snapshot_stats()),
+    );
+    let snapshot: OptionalValue<StatsSnapshot<StaticApi>> =
+        state.world.quick_query(state.marketplace.get_stats_snapshot(11u64));
+    let snapshot = snapshot.into_option().unwrap();
+    assert_eq!(snapshot.stats.total_sales, 2);
+    assert_eq!(snapshot.collection_count, 1);
+    let open_epoch: u64 = state.world.quick_query(state.marketplace.get_open_stats_epoch());
+    assert_eq!(open_epoch, 12);
+}
+
+#[test]
+fn admin_resyncs_stored_listings_and_auctions() {
+    let mut state = MarketplaceTestState::new();
+    let schema_version: u32 = state.world.quick_query(state.marketplace.get_event_schema_version());
+    assert_eq!(schema_version, EVENT_SCHEMA_VERSION);
+
+    state.create_listing(2, PRICE);
+    state.create_auction();
+
+    // Unknown ids are skipped
+    state.world.sc_call(
+        ScCallStep::new()
+            .from(OWNER_ADDRESS_EXPR)
+            .call(state.marketplace.sync_listing_states(MultiValueVec::from(vec![1u32, 2u32])))
+            .expect_value(1u32),
+    );
+    state.world.sc_call(
+        ScCallStep::new()
+            .from(OWNER_ADDRESS_EXPR)
+            .call(state.marketplace.sync_auction_states(MultiValueVec::from(vec![1u32])))
+            .expect_value(1u32),
+    );
+
+    state.world.sc_call(
+        ScCallStep::new()
+            .from(BUYER_ADDRESS_EXPR)
+            .call(state.marketplace.sync_listing_states(MultiValueVec::from(vec![1u32])))
+            .expect(user_error(ERR_CALLER_DOES_NOT_HAVE_THE_REQUIRED_ROLE)),
+    );
+    state.world.sc_call(
+        ScCallStep::new()
+            .from(OWNER_ADDRESS_EXPR)
+            .call(state.marketplace.sync_auction_states(MultiValueVec::from(vec![1u32; 51])))
+            .expect(user_error(ERR_SYNC_BATCH_TOO_LARGE)),
+    );
+}
+
+#[test]
+fn certified_wines_cannot_be_listed_below_grade_floor() {
+    let mut state = MarketplaceTestState::new();
+    state.sync_wine_read_model(1, true);
+    state.sync_wine_read_model(2, false);
+    state.world.sc_call(
+        ScCallStep::new()
+            .from(REGISTRY_ADDRESS_EXPR)
+            .call(state.marketplace.sync_grade_price_floor(8u8, 2 * PRICE)),
+    );
+
+    // Floors are only published until the marketplace enforces them
+    let floor: RustBigUint = state.world.quick_query(state.marketplace.get_wine_price_floor(1u32));
+    assert_eq!(floor, RustBigUint::from(2 * PRICE));
+    state.world.sc_call(
+        ScCallStep::new()
+            .from(OWNER_ADDRESS_EXPR)
+            .call(state.marketplace.set_enforce_price_floors(true)),
+    );
+
+    state.world.sc_call(
+        ScCallStep::new()
+            .from(SELLER_ADDRESS_EXPR)
+            .esdt_transfer(WINE_NFT_TOKEN_ID_EXPR, 1, "1")
+            .call(state.marketplace.create_listing(
+                1u32,
+                2 * PRICE - 1,
+                EgldOrEsdtTokenIdentifier::egld(),
+                DURATION,
+                OptionalValue::<bool>::None,
+            ))
+            .expect(user_error(ERR_PRICE_BELOW_GRADE_FLOOR)),
+    );
+    state.create_listing(1, 2 * PRICE);
+    // The grade of an uncertified wine is self-declared, so no floor applies
+    state.create_listing(2, PRICE);
+    assert_eq!(state.stats().active_listings, 2);
+
+    // A wine the registry has not pushed has no known floor to meet
+    let floor: RustBigUint = state.world.quick_query(state.marketplace.get_wine_price_floor(3u32));
+    assert_eq!(floor, RustBigUint::from(0u32));
+    state.world.sc_call(
+        ScCallStep::new()
+            .from(SELLER_ADDRESS_EXPR)
+            .esdt_transfer(WINE_NFT_TOKEN_ID_EXPR, 3, "1")
+            .call(state.marketplace.create_listing(
+                3u32,
+                2 * PRICE,
+                EgldOrEsdtTokenIdentifier::egld(),
+                DURATION,
+                OptionalValue::<bool>::None,
+            ))
+            .expect(user_error(ERR_WINE_NOT_SYNCED_FROM_REGISTRY)),
+    );
+}
+
+#[test]
+fn unverified_producer_cannot_accept_offer_on_own_wine() {
+    let mut state = MarketplaceTestState::new();
+    let producer = AddressValue::from(SELLER_ADDRESS_EXPR).to_address();
+    state.sync_wine_read_model(1, false);
+    state.world.sc_call(
+        ScCallStep::new()
+            .from(OWNER_ADDRESS_EXPR)
+            .call(state.marketplace.set_require_verified_producers(true)),
+    );
+    state.world.sc_call(
+        ScCallStep::new()
+            .from(BUYER_ADDRESS_EXPR)
+            .egld_value(PRICE)
+            .call(state.marketplace.make_offer(1u32, TokenIdentifier::from("WINE-abcdef"), 1u64, DURATION)),
+    );
+
+    state.world.sc_call(
+        ScCallStep::new()
+            .from(SELLER_ADDRESS_EXPR)
+            .esdt_transfer(WINE_NFT_TOKEN_ID_EXPR, 1, "1")
+            .call(state.marketplace.accept_offer(1u32))
+            .expect(user_error(ERR_PRODUCER_IS_NOT_VERIFIED)),
+    );
+
+    state.world.sc_call(
+        ScCallStep::new()
+            .from(REGISTRY_ADDRESS_EXPR)
+            .call(state.marketplace.sync_producer_verification(managed_address!(&producer), true)),
+    );
+    state.world.sc_call(
+        ScCallStep::new()
+            .from(SELLER_ADDRESS_EXPR)
+            .esdt_transfer(WINE_NFT_TOKEN_ID_EXPR, 1, "1")
+            .call(state.marketplace.accept_offer(1u32)),
+    );
+    assert_eq!(state.stats().total_sales, 1);
+}
+
+#[test]
+fn offers_are_bound_to_the_wine_of_the_accepted_nft() {
+    let mut state = MarketplaceTestState::new();
+    // An offer naming wine 2 for NFT 1, and one for NFT 3 of a wine the registry has not pushed
+    for (wine_id, nft_nonce) in [(2u32, 1u64), (3u32, 3u64)] {
+        state.world.sc_call(
+            ScCallStep::new()
+                .from(BUYER_ADDRESS_EXPR)
+                .egld_value(PRICE)
+                .call(state.marketplace.make_offer(wine_id, TokenIdentifier::from("WINE-abcdef"), nft_nonce, DURATION)),
+        );
+    }
+
+    for (offer_id, nft_nonce, error) in [
+        (1u32, 1u64, ERR_WINE_ID_DOES_NOT_MATCH_NFT),
+        (2u32, 3u64, ERR_WINE_NOT_SYNCED_FROM_REGISTRY),
+    ] {
+        state.world.sc_call(
+            ScCallStep::new()
+                .from(SELLER_ADDRESS_EXPR)
+                .esdt_transfer(WINE_NFT_TOKEN_ID_EXPR, nft_nonce, "1")
+                .call(state.marketplace.accept_offer(offer_id))
+                .expect(user_error(error)),
+        );
+    }
+    assert_eq!(state.stats().total_sales, 0);
+}
+
+#[test]
+fn certified_only_auctions_reject_uncertified_wines() {
+    let mut state = MarketplaceTestState::new();
+    state.world.sc_call(
+        ScCallStep::new()
+            .from(OWNER_ADDRESS_EXPR)
+            .call(state.marketplace.set_certified_only_auctions(true, OptionalValue::Some(
+                TokenIdentifier::from("WINE-abcdef"),
+            ))),
+    );
+
+    // Neither a wine the registry has not pushed nor an uncertified one is accepted
+    for (nft_nonce, error) in [
+        (3u64, ERR_WINE_NOT_SYNCED_FROM_REGISTRY),
+        (1u64, ERR_ONLY_CERTIFIED_WINES_CAN_BE_AUCTIONED),
+    ] {
+        state.world.sc_call(
+            ScCallStep::new()
+                .from(SELLER_ADDRESS_EXPR)
+                .esdt_transfer(WINE_NFT_TOKEN_ID_EXPR, nft_nonce, "1")
+                .call(state.marketplace.create_auction(
+                    nft_nonce as u32,
+                    PRICE,
+                    EgldOrEsdtTokenIdentifier::egld(),
+                    DURATION,
+                    MIN_BID_INCREMENT,
+                ))
+                .expect(user_error(error)),
+        );
+    }
+
+    // A certification backed by an expired attestation no longer counts
+    let mut read_model = wine_read_model(true);
+    read_model.certified_until = START_TIMESTAMP - 1;
+    state.push_wine_read_model(1, read_model);
+    state.world.sc_call(
+        ScCallStep::new()
+            .from(SELLER_ADDRESS_EXPR)
+            .esdt_transfer(WINE_NFT_TOKEN_ID_EXPR, 1, "1")
+            .call(state.marketplace.create_auction(
+                1u32,
+                PRICE,
+                EgldOrEsdtTokenIdentifier::egld(),
+                DURATION,
+                MIN_BID_INCREMENT,
+            ))
+            .expect(user_error(ERR_ONLY_CERTIFIED_WINES_CAN_BE_AUCTIONED)),
+    );
+
+    let mut read_model = wine_read_model(true);
+    read_model.certified_until = START_TIMESTAMP;
+    state.push_wine_read_model(1, read_model);
+    state.create_auction();
+    assert_eq!(state.auction_end(1), START_TIMESTAMP + DURATION);
+}
+
+#[test]
+fn keeper_finalizing_an_ended_auction_earns_a_bounty_from_the_fee() {
+    let mut state = MarketplaceTestState::new();
+    state.world.sc_call(
+        ScCallStep::new()
+            .from(OWNER_ADDRESS_EXPR)
+            .call(state.marketplace.set_keeper_bounty_percent(2_001u64))
+            .expect(user_error(ERR_KEEPER_BOUNTY_TOO_HIGH)),
+    );
+    state.world.sc_call(
+        ScCallStep::new()
+            .from(OWNER_ADDRESS_EXPR)
+            .call(state.marketplace.set_keeper_bounty_percent(2_000u64)),
+    );
+    state.create_auction();
+    state.place_bid(BUYER_ADDRESS_EXPR, 1, 1_100);
+
+    let finalizable: MultiValue2<u64, MultiValueEncoded<StaticApi, u32>> =
+        state.world.quick_query(state.marketplace.get_finalizable_auctions(0u64, 0u32));
+    let (next_cursor, ids) = finalizable.into_tuple();
+    assert_eq!(next_cursor, 0);
+    assert!(ids.is_empty());
+
+    state.set_block_timestamp(START_TIMESTAMP + DURATION);
+    let finalizable: MultiValue2<u64, MultiValueEncoded<StaticApi, u32>> =
+        state.world.quick_query(state.marketplace.get_finalizable_auctions(0u64, 0u32));
+    let ids: Vec<u32> = finalizable.into_tuple().1.into_iter().collect();
+    assert_eq!(ids, vec![1]);
+
+    // Neither seller nor winner: the bidder account acts as keeper
+    state.world.sc_call(
+        ScCallStep::new()
+            .from(BIDDER_ADDRESS_EXPR)
+            .call(state.marketplace.finalize_auction(1u32)),
+    );
+
+    // The 27 fee splits into a 5 bounty (20%, rounded down) and 22 for the fee recipient
+    state.world.check_state_step(
+        CheckStateStep::new()
+            .put_account(SELLER_ADDRESS_EXPR, CheckAccount::new().balance("1073"))
+            .put_account(OWNER_ADDRESS_EXPR, CheckAccount::new().balance("22"))
+            .put_account(BIDDER_ADDRESS_EXPR, CheckAccount::new().balance("10005"))
+            .put_account(
+                BUYER_ADDRESS_EXPR,
+                CheckAccount::new().balance("8900").esdt_nft_balance_and_attributes(
+                    WINE_NFT_TOKEN_ID_EXPR,
+                    1,
+                    "1",
+                    Some(wine_attributes(1)),
+                ),
+            ),
+    );
+    assert_eq!(state.stats().total_fees_collected, BigUint::from(27u64));
+
+    let finalizable: MultiValue2<u64, MultiValueEncoded<StaticApi, u32>> =
+        state.world.quick_query(state.marketplace.get_finalizable_auctions(0u64, 0u32));
+    assert!(finalizable.into_tuple().1.is_empty());
+}
+
+#[test]
+fn mixed_egld_and_esdt_payment_settles_into_claimable_proceeds() {
+    let mut state = MarketplaceTestState::new();
+    let usdc = EgldOrEsdtTokenIdentifier::esdt(TokenIdentifier::from("USDC-abcdef"));
+    let egld = EgldOrEsdtTokenIdentifier::egld();
+    // EGLD sent along with an ESDT arrives as EGLD-000000 in the multi-transfer
+    state.world.set_state_step(
+        SetStateStep::new().put_account(
+            BUYER_ADDRESS_EXPR,
+            Account::new()
+                .nonce(1)
+                .esdt_balance(EGLD_000000_TOKEN_ID_EXPR, "1000")
+                .esdt_balance(USDC_TOKEN_ID_EXPR, "1000"),
+        ),
+    );
+    state.world.sc_call(
+        ScCallStep::new()
+            .from(OWNER_ADDRESS_EXPR)
+            .call(state.marketplace.add_supported_token(usdc.clone())),
+    );
+    state.world.sc_call(
+        ScCallStep::new()
+            .from(OWNER_ADDRESS_EXPR)
+            .call(state.marketplace.set_exchange_rate(usdc.clone(), egld.clone(), 2_000_000_000_000_000_000u64)),
+    );
+    state.world.sc_call(
+        ScCallStep::new()
+            .from(SELLER_ADDRESS_EXPR)
+            .esdt_transfer(WINE_NFT_TOKEN_ID_EXPR, 1, "1")
+            .call(state.marketplace.create_listing(1u32, PRICE, usdc.clone(), DURATION, OptionalValue::<bool>::None)),
+    );
+    state.world.sc_call(
+        ScCallStep::new()
+            .from(SELLER_ADDRESS_EXPR)
+            .call(state.marketplace.add_listing_payment_tokens(1u32, MultiValueVec::from(vec![egld.clone()]))),
+    );
+    // The scenario VM moves EGLD-000000 like any ESDT, so the EGLD the marketplace pays out
+    // comes from a bid escrowed on another auction
+    state.world.sc_call(
+        ScCallStep::new()
+            .from(SELLER_ADDRESS_EXPR)
+            .esdt_transfer(WINE_NFT_TOKEN_ID_EXPR, 2, "1")
+            .call(state.marketplace.create_auction(2u32, PRICE, egld.clone(), DURATION, MIN_BID_INCREMENT)),
+    );
+    state.place_bid(BIDDER_ADDRESS_EXPR, 1, 1_100);
+
+    // Accepting EGLD alone does not allow paying in EGLD and USDC at once
+    state.world.sc_call(
+        ScCallStep::new()
+            .from(BUYER_ADDRESS_EXPR)
+            .esdt_transfer(EGLD_000000_TOKEN_ID_EXPR, 0, "600")
+            .esdt_transfer(USDC_TOKEN_ID_EXPR, 0, "800")
+            .call(state.marketplace.buy_wine(1u32, OptionalValue::<ManagedAddress<StaticApi>>::None))
+            .expect(user_error(ERR_MIXED_SETTLEMENT_NOT_ALLOWED)),
+    );
+    state.world.sc_call(
+        ScCallStep::new()
+            .from(BUYER_ADDRESS_EXPR)
+            .call(state.marketplace.set_mixed_settlement(1u32, MixedSettlement::ClaimableProceeds))
+            .expect(user_error(ERR_ONLY_SELLER_CAN_SET_MIXED_SETTLEMENT)),
+    );
+    state.world.sc_call(
+        ScCallStep::new()
+            .from(SELLER_ADDRESS_EXPR)
+            .call(state.marketplace.set_mixed_settlement(1u32, MixedSettlement::ClaimableProceeds)),
+    );
+
+    // 600 EGLD cover 300 USDC of the price, so 700 of the 800 USDC are used
+    state.world.sc_call(
+        ScCallStep::new()
+            .from(BUYER_ADDRESS_EXPR)
+            .esdt_transfer(EGLD_000000_TOKEN_ID_EXPR, 0, "600")
+            .esdt_transfer(USDC_TOKEN_ID_EXPR, 0, "800")
+            .call(state.marketplace.buy_wine(1u32, OptionalValue::<ManagedAddress<StaticApi>>::None)),
+    );
+    assert!(!state.listing(1).active);
+    assert_eq!(state.stats().total_sales, 1);
+
+    // Fees are paid out right away, the seller's share waits to be claimed
+    state.world.check_state_step(
+        CheckStateStep::new()
+            .put_account(
+                BUYER_ADDRESS_EXPR,
+                CheckAccount::new()
+                    .esdt_balance(USDC_TOKEN_ID_EXPR, "300")
+                    .esdt_nft_balance_and_attributes(WINE_NFT_TOKEN_ID_EXPR, 1, "1", Some(wine_attributes(1))),
+            )
+            .put_account(
+                OWNER_ADDRESS_EXPR,
+                CheckAccount::new().balance("15").esdt_balance(USDC_TOKEN_ID_EXPR, "17"),
+            )
+            .put_account(SELLER_ADDRESS_EXPR, CheckAccount::new().balance("0")),
+    );
+    let seller = AddressValue::from(SELLER_ADDRESS_EXPR).to_address();
+    let proceeds: MultiValueEncoded<StaticApi, MultiValue2<EgldOrEsdtTokenIdentifier<StaticApi>, BigUint<StaticApi>>> =
+        state.world.quick_query(state.marketplace.get_sale_proceeds(managed_address!(&seller)));
+    let proceeds: Vec<(EgldOrEsdtTokenIdentifier<StaticApi>, BigUint<StaticApi>)> =
+        proceeds.into_iter().map(|entry| entry.into_tuple()).collect();
+    assert_eq!(proceeds, vec![(egld, BigUint::from(585u64)), (usdc, BigUint::from(683u64))]);
+
+    state.world.sc_call(
+        ScCallStep::new()
+            .from(SELLER_ADDRESS_EXPR)
+            .call(state.marketplace.claim_sale_proceeds()),
+    );
+    state.world.check_state_step(
+        CheckStateStep::new().put_account(
+            SELLER_ADDRESS_EXPR,
+            CheckAccount::new().balance("585").esdt_balance(USDC_TOKEN_ID_EXPR, "683"),
+        ),
+    );
+    state.world.sc_call(
+        ScCallStep::new()
+            .from(SELLER_ADDRESS_EXPR)
+            .call(state.marketplace.claim_sale_proceeds())
+            .expect(user_error(ERR_NOTHING_TO_WITHDRAW)),
+    );
+}
+
+#[test]
+fn market_summary_tracks_best_bid_ask_and_recent_volume() {
+    let mut state = MarketplaceTestState::new();
+    assert!(state.market_summary(1).is_empty());
+
+    // Two bottles of wine 1, asked at different prices
+    state.world.set_state_step(
+        SetStateStep::new().put_account(
+            SELLER_ADDRESS_EXPR,
+            Account::new()
+                .nonce(1)
+                .esdt_nft_balance(WINE_NFT_TOKEN_ID_EXPR, 1, "2", encoded_wine_attributes(1)),
+        ),
+    );
+    for price in [PRICE, 1_200u64] {
+        state.world.sc_call(
+            ScCallStep::new()
+                .from(SELLER_ADDRESS_EXPR)
+                .esdt_transfer(WINE_NFT_TOKEN_ID_EXPR, 1, "1")
+                .call(state.marketplace.create_listing(
+                    1u32,
+                    price,
+                    EgldOrEsdtTokenIdentifier::egld(),
+                    DURATION,
+                    OptionalValue::<bool>::None,
+                )),
+        );
+    }
+    for (bidder, amount) in [(BUYER_ADDRESS_EXPR, 500u64), (BIDDER_ADDRESS_EXPR, 700u64)] {
+        state.world.sc_call(
+            ScCallStep::new()
+                .from(bidder)
+                .egld_value(amount)
+                .call(state.marketplace.make_offer(1u32, TokenIdentifier::from("WINE-abcdef"), 1u64, DURATION)),
+        );
+    }
+
+    let summary = &state.market_summary(1)[0];
+    assert_eq!(summary.payment_token, EgldOrEsdtTokenIdentifier::egld());
+    assert_eq!((summary.best_ask_listing_id, summary.best_ask.clone()), (1, BigUint::from(PRICE)));
+    assert_eq!((summary.best_bid_offer_id, summary.best_bid.clone()), (2, BigUint::from(700u64)));
+    assert_eq!(summary.last_sale_price, 0u64);
+    assert_eq!(summary.volume_30d, 0u64);
+
+    // Removing the best quote on either side falls back to the next one
+    state.world.sc_call(
+        ScCallStep::new()
+            .from(BIDDER_ADDRESS_EXPR)
+            .call(state.marketplace.cancel_offer(2u32)),
+    );
+    state.set_block_timestamp(START_TIMESTAMP + 60);
+    state.buy_wine(1, PRICE);
+
+    let summaries = state.market_summary(1);
+    assert_eq!(summaries.len(), 1);
+    let summary = &summaries[0];
+    assert_eq!((summary.best_ask_listing_id, summary.best_ask.clone()), (2, BigUint::from(1_200u64)));
+    assert_eq!((summary.best_bid_offer_id, summary.best_bid.clone()), (1, BigUint::from(500u64)));
+    assert_eq!(summary.last_sale_price, PRICE);
+    assert_eq!(summary.last_sale_timestamp, START_TIMESTAMP + 60);
+    assert_eq!(summary.volume_30d, PRICE);
+    assert!(state.market_summary(2).is_empty());
+
+    // Expired quotes no longer count, and the sale drops out of the 30-day window
+    state.set_block_timestamp(START_TIMESTAMP + 30 * SECONDS_PER_DAY);
+    let summary = &state.market_summary(1)[0];
+    assert_eq!((summary.best_ask_listing_id, summary.best_ask.clone()), (0, BigUint::zero()));
+    assert_eq!((summary.best_bid_offer_id, summary.best_bid.clone()), (0, BigUint::zero()));
+    assert_eq!(summary.last_sale_price, PRICE);
+    assert_eq!(summary.volume_30d, 0u64);
+}
+
+#[test]
+fn market_book_keeps_only_the_best_quotes() {
+    let mut state = MarketplaceTestState::new();
+    let make_offer = |state: &mut MarketplaceTestState, amount: u64| {
+        state.world.sc_call(
+            ScCallStep::new()
+                .from(BUYER_ADDRESS_EXPR)
+                .egld_value(amount)
+                .call(state.marketplace.make_offer(1u32, TokenIdentifier::from("WINE-abcdef"), 1u64, DURATION)),
+        );
+    };
+
+    // Offers 1..=depth bid 101, 102, ...; a full book turns away worse bids
+    for offer in 1..=MARKET_BOOK_DEPTH as u64 {
+        make_offer(&mut state, 100 + offer);
+    }
+    for _ in 0..5 {
+        make_offer(&mut state, 1);
+    }
+    let best_bid = |state: &mut MarketplaceTestState| {
+        let summary = &state.market_summary(1)[0];
+        (summary.best_bid_offer_id, summary.best_bid.to_u64().unwrap())
+    };
+    let depth = MARKET_BOOK_DEPTH as u32;
+    assert_eq!(best_bid(&mut state), (depth, 100 + depth as u64));
+
+    // A better bid takes the top, and removing it falls back to the next one
+    make_offer(&mut state, 500);
+    let top_offer = depth + 6;
+    assert_eq!(best_bid(&mut state), (top_offer, 500));
+    state.world.sc_call(
+        ScCallStep::new()
+            .from(BUYER_ADDRESS_EXPR)
+            .call(state.marketplace.cancel_offer(top_offer)),
+    );
+    assert_eq!(best_bid(&mut state), (depth, 100 + depth as u64));
+}
+
+#[test]
+fn relayers_execute_signed_purchase_and_bid_intents() {
+    let mut state = MarketplaceTestState::new();
+    state.create_usdc_listing_accepting_loyalty();
+    let usdc = EgldOrEsdtTokenIdentifier::esdt(TokenIdentifier::from("USDC-abcdef"));
+
+    // The signer holds no EGLD; the owner relays, the buyer and bidder fund the relay balance
+    let signing_key = SigningKey::from_bytes(&[7u8; 32]);
+    let signer = Address::from(signing_key.verifying_key().to_bytes());
+    let relayer = AddressValue::from(OWNER_ADDRESS_EXPR).to_address();
+    state.world.set_state_step(SetStateStep::new().put_account(&signer, Account::new().nonce(1)));
+    state.world.sc_call(
+        ScCallStep::new()
+            .from(OWNER_ADDRESS_EXPR)
+            .call(state.marketplace.add_relayer(managed_address!(&relayer))),
+    );
+    state.world.sc_call(
+        ScCallStep::new()
+            .from(BUYER_ADDRESS_EXPR)
+            .esdt_transfer(USDC_TOKEN_ID_EXPR, 0, "1000")
+            .call(state.marketplace.deposit_relay_funds(OptionalValue::Some(managed_address!(&signer)))),
+    );
+    state.world.sc_call(
+        ScCallStep::new()
+            .from(BIDDER_ADDRESS_EXPR)
+            .egld_value(2_000u64)
+            .call(state.marketplace.deposit_relay_funds(OptionalValue::Some(managed_address!(&signer)))),
+    );
+
+    let intent = PurchaseIntent::<StaticApi> {
+        buyer: managed_address!(&signer),
+        listing_id: 1,
+        max_price: BigUint::from(PRICE),
+        nonce: 0,
+        deadline: START_TIMESTAMP + 60,
+    };
+    let message: ManagedBuffer<StaticApi> =
+        state.world.quick_query(state.marketplace.get_purchase_intent_message(intent.clone()));
+    let signature = signing_key.sign(message.to_boxed_bytes().as_slice()).to_bytes();
+    let signature = ManagedBuffer::<StaticApi>::from(&signature[..]);
+
+    state.world.sc_call(
+        ScCallStep::new()
+            .from(BUYER_ADDRESS_EXPR)
+            .call(state.marketplace.execute_purchase_intent(intent.clone(), signature.clone()))
+            .expect(user_error(ERR_CALLER_IS_NOT_A_RELAYER)),
+    );
+    state.world.sc_call(
+        ScCallStep::new()
+            .from(OWNER_ADDRESS_EXPR)
+            .call(state.marketplace.execute_purchase_intent(intent.clone(), signature.clone())),
+    );
+    state.world.check_state_step(
+        CheckStateStep::new()
+            .put_account(
+                &signer,
+                CheckAccount::new().balance("0").esdt_nft_balance_and_attributes(
+                    WINE_NFT_TOKEN_ID_EXPR,
+                    1,
+                    "1",
+                    Some(wine_attributes(1)),
+                ),
+            )
+            .put_account(SELLER_ADDRESS_EXPR, CheckAccount::new().esdt_balance(USDC_TOKEN_ID_EXPR, "975")),
+    );
+    let balance: RustBigUint =
+        state.world.quick_query(state.marketplace.get_relay_balance(managed_address!(&signer), usdc.clone()));
+    assert_eq!(balance, RustBigUint::from(0u64));
+
+    // Each intent runs once
+    state.world.sc_call(
+        ScCallStep::new()
+            .from(OWNER_ADDRESS_EXPR)
+            .call(state.marketplace.execute_purchase_intent(intent, signature))
+            .expect(user_error(ERR_INVALID_INTENT_NONCE)),
+    );
+
+    state.world.sc_call(
+        ScCallStep::new()
+            .from(SELLER_ADDRESS_EXPR)
+            .esdt_transfer(WINE_NFT_TOKEN_ID_EXPR, 2, "1")
+            .call(state.marketplace.create_auction(
+                2u32,
+                PRICE,
+                EgldOrEsdtTokenIdentifier::egld(),
+                DURATION,
+                MIN_BID_INCREMENT,
+            )),
+    );
+    let intent = BidIntent::<StaticApi> {
+        bidder: managed_address!(&signer),
+        auction_id: 1,
+        amount: BigUint::from(1_100u64),
+        nonce: 1,
+        deadline: START_TIMESTAMP + 60,
+    };
+    let message: ManagedBuffer<StaticApi> =
+        state.world.quick_query(state.marketplace.get_bid_intent_message(intent.clone()));
+    let signature = signing_key.sign(message.to_boxed_bytes().as_slice()).to_bytes();
+    let signature = ManagedBuffer::<StaticApi>::from(&signature[..]);
+
+    state.set_block_timestamp(START_TIMESTAMP + 61);
+    state.world.sc_call(
+        ScCallStep::new()
+            .from(OWNER_ADDRESS_EXPR)
+            .call(state.marketplace.execute_bid_intent(intent.clone(), signature.clone()))
+            .expect(user_error(ERR_INTENT_HAS_EXPIRED)),
+    );
+    state.set_block_timestamp(START_TIMESTAMP + 60);
+    state.world.sc_call(
+        ScCallStep::new()
+            .from(OWNER_ADDRESS_EXPR)
+            .call(state.marketplace.execute_bid_intent(intent, signature)),
+    );
+    let auction: wine_marketplace::auction::Auction<StaticApi> =
+        state.world.quick_query(state.marketplace.get_auction(1u32));
+    assert_eq!(auction.highest_bidder, managed_address!(&signer));
+    assert_eq!(auction.current_bid, 1_100u64);
+    let nonce: u64 = state.world.quick_query(state.marketplace.get_relay_nonce(managed_address!(&signer)));
+    assert_eq!(nonce, 2);
+
+    // Whatever is left can be taken out through a relayed transaction of the signer's own
+    state.world.sc_call(
+        ScCallStep::new()
+            .from(&signer)
+            .call(state.marketplace.withdraw_relay_funds(EgldOrEsdtTokenIdentifier::egld())),
+    );
+    state.world.check_state_step(CheckStateStep::new().put_account(&signer, CheckAccount::new().balance("900")));
+}
@@ -0,0 +1,172 @@
+use multiversx_sc::codec::top_encode_to_vec_u8_or_panic;
+use multiversx_sc_scenario::imports::*;
+use wine_marketplace::auction::AuctionModule;
+use wine_marketplace::blacklist::BlacklistModule;
+use wine_marketplace::candle_auction::{CandleAuctionModule, CandleBid};
+use wine_marketplace::listing::ListingModule;
+use wine_marketplace::offers::OffersModule;
+use wine_marketplace::pagination::*;
+use wine_marketplace::*;
+
+const MARKETPLACE_PATH_EXPR: &str = "mxsc:output/wine-marketplace.mxsc.json";
+const MARKETPLACE_ADDRESS_EXPR: &str = "sc:wine-marketplace";
+const OWNER_ADDRESS_EXPR: &str = "address:owner";
+const REGISTRY_ADDRESS_EXPR: &str = "address:registry";
+const SELLER_ADDRESS_EXPR: &str = "address:seller";
+
+// Cursor return value, encoded as u64
+const CURSOR_BYTES: usize = 8;
+
+type MarketplaceWhitebox = WhiteboxContract<wine_marketplace::ContractObj<DebugApi>>;
+
+fn setup() -> (ScenarioWorld, MarketplaceWhitebox) {
+    let mut world = ScenarioWorld::new();
+    world.register_contract(MARKETPLACE_PATH_EXPR, wine_marketplace::ContractBuilder);
+
+    let marketplace_whitebox = WhiteboxContract::new(MARKETPLACE_ADDRESS_EXPR, wine_marketplace::contract_obj);
+    let marketplace_code = world.code_expression(MARKETPLACE_PATH_EXPR);
+
+    world
+        .set_state_step(
+            SetStateStep::new()
+                .put_account(OWNER_ADDRESS_EXPR, Account::new().nonce(1))
+                .put_account(SELLER_ADDRESS_EXPR, Account::new().nonce(1))
+                .new_address(OWNER_ADDRESS_EXPR, 1, MARKETPLACE_ADDRESS_EXPR),
+        )
+        .whitebox_deploy(
+            &marketplace_whitebox,
+            ScDeployStep::new().from(OWNER_ADDRESS_EXPR).code(marketplace_code),
+            |sc| {
+                let registry = AddressValue::from(REGISTRY_ADDRESS_EXPR).to_address();
+                sc.init(250u64, managed_address!(&registry));
+            },
+        );
+
+    (world, marketplace_whitebox)
+}
+
+fn address(expr: &str) -> Address {
+    AddressValue::from(expr).to_address()
+}
+
+fn top_encoded_len<T: TopEncode>(value: &T) -> usize {
+    top_encode_to_vec_u8_or_panic(value).len()
+}
+
+fn raw_address(index: usize) -> Address {
+    let mut bytes = [0xffu8; 32];
+    bytes[..8].copy_from_slice(&(index as u64).to_be_bytes());
+    Address::from(bytes)
+}
+
+// Largest bid amount covered by the candle bid page size
+fn max_bid_amount() -> BigUint<DebugApi> {
+    BigUint::from_bytes_be(&[0xffu8; 32])
+}
+
+#[test]
+fn full_pages_of_maximal_items_fit_return_budget() {
+    let (mut world, whitebox) = setup();
+    let seller = address(SELLER_ADDRESS_EXPR);
+
+    world.whitebox_call(&whitebox, ScCallStep::new().from(OWNER_ADDRESS_EXPR), |sc| {
+        for _ in 0..(MAX_IDS_PAGE_SIZE * 2) {
+            sc.seller_listings(&managed_address!(&seller)).push(&u32::MAX);
+            sc.seller_auctions(&managed_address!(&seller)).push(&u32::MAX);
+            sc.seller_candle_auctions(&managed_address!(&seller)).push(&u32::MAX);
+            sc.buyer_offers(&managed_address!(&seller)).push(&u32::MAX);
+        }
+        for index in 0..(MAX_CANDLE_BIDS_PAGE_SIZE as usize * 2) {
+            sc.candle_bids(1).push(&CandleBid {
+                bidder: managed_address!(&raw_address(index)),
+                amount: max_bid_amount(),
+                timestamp: u64::MAX,
+            });
+        }
+        for index in 0..(MAX_ADDRESSES_PAGE_SIZE as usize * 2) {
+            sc.blacklist().insert(managed_address!(&raw_address(index)));
+        }
+    });
+
+    world.whitebox_query(&whitebox, |sc| {
+        let seller = managed_address!(&seller);
+        let id_pages = [
+            sc.get_seller_listings(&seller, 0, u32::MAX),
+            sc.get_seller_auctions(&seller, 0, u32::MAX),
+            sc.get_seller_candle_auctions(&seller, 0, u32::MAX),
+            sc.get_buyer_offers(&seller, 0, u32::MAX),
+        ];
+        for id_page in id_pages {
+            let (next_cursor, ids) = id_page.into_tuple();
+            assert_eq!(next_cursor, MAX_IDS_PAGE_SIZE as u64);
+            assert_eq!(ids.len(), MAX_IDS_PAGE_SIZE as usize);
+            let ids_bytes: usize = ids.into_iter().map(|id| top_encoded_len(&id)).sum();
+            assert!(CURSOR_BYTES + ids_bytes <= MAX_VIEW_RETURN_BYTES);
+        }
+
+        let (next_cursor, bids) = sc.get_candle_bids(1, 0, u32::MAX).into_tuple();
+        assert_eq!(next_cursor, MAX_CANDLE_BIDS_PAGE_SIZE as u64);
+        assert_eq!(bids.len(), MAX_CANDLE_BIDS_PAGE_SIZE as usize);
+        let bids_bytes: usize = bids.into_iter().map(|bid| top_encoded_len(&bid)).sum();
+        assert!(CURSOR_BYTES + bids_bytes <= MAX_VIEW_RETURN_BYTES);
+
+        let (next_cursor, addresses) = sc.get_blacklist(0, u32::MAX).into_tuple();
+        assert_eq!(next_cursor, MAX_ADDRESSES_PAGE_SIZE as u64);
+        assert_eq!(addresses.len(), MAX_ADDRESSES_PAGE_SIZE as usize);
+        let addresses_bytes: usize = addresses.into_iter().map(|address| top_encoded_len(&address)).sum();
+        assert!(CURSOR_BYTES + addresses_bytes <= MAX_VIEW_RETURN_BYTES);
+    });
+}
+
+#[test]
+fn worst_case_item_sizes_match_page_limits() {
+    let (mut world, whitebox) = setup();
+
+    world.whitebox_query(&whitebox, |_| {
+        assert!(CURSOR_BYTES + MAX_IDS_PAGE_SIZE as usize * top_encoded_len(&u32::MAX) <= MAX_VIEW_RETURN_BYTES);
+
+        let bid = CandleBid::<DebugApi> {
+            bidder: managed_address!(&raw_address(0)),
+            amount: max_bid_amount(),
+            timestamp: u64::MAX,
+        };
+        assert!(CURSOR_BYTES + MAX_CANDLE_BIDS_PAGE_SIZE as usize * top_encoded_len(&bid) <= MAX_VIEW_RETURN_BYTES);
+
+        let address_len = top_encoded_len(&ManagedAddress::<DebugApi>::from(raw_address(0)));
+        assert!(CURSOR_BYTES + MAX_ADDRESSES_PAGE_SIZE as usize * address_len <= MAX_VIEW_RETURN_BYTES);
+    });
+}
+
+#[test]
+fn cursor_walks_every_item_once() {
+    let (mut world, whitebox) = setup();
+    let total = MAX_ADDRESSES_PAGE_SIZE as usize * 2 + 17;
+
+    world.whitebox_call(&whitebox, ScCallStep::new().from(OWNER_ADDRESS_EXPR), |sc| {
+        for index in 0..total {
+            sc.blacklist().insert(managed_address!(&raw_address(index)));
+        }
+    });
+
+    world.whitebox_query(&whitebox, |sc| {
+        let mut collected = Vec::new();
+        let mut cursor = 0u64;
+        let mut pages = 0;
+        loop {
+            let (next_cursor, addresses) = sc.get_blacklist(cursor, 0).into_tuple();
+            collected.extend(addresses.into_iter().map(|address| address.to_address()));
+            pages += 1;
+            if next_cursor == 0 {
+                break;
+            }
+            cursor = next_cursor;
+        }
+        assert_eq!(pages, 3);
+        assert_eq!(collected, (0..total).map(raw_address).collect::<Vec<Address>>());
+
+        // cursors past the end return nothing
+        let (next_cursor, addresses) = sc.get_blacklist(u64::MAX, 25).into_tuple();
+        assert_eq!(next_cursor, 0);
+        assert_eq!(addresses.len(), 0);
+    });
+}
@@ -0,0 +1,380 @@
+use multiversx_sc::codec::top_encode_to_vec_u8_or_panic;
+use multiversx_sc_scenario::api::StaticApi;
+use multiversx_sc_scenario::imports::*;
+use wine_common::errors::*;
+use wine_marketplace::admin::AdminModule;
+use wine_marketplace::auction::AuctionModule;
+use wine_marketplace::candle_auction::CandleAuctionModule;
+use wine_marketplace::installments::InstallmentsModule;
+use wine_marketplace::listing::ListingModule;
+use wine_marketplace::offers::OffersModule;
+use wine_marketplace::registry_cache::RegistryCacheModule;
+use wine_marketplace::*;
+
+const MARKETPLACE_PATH_EXPR: &str = "mxsc:output/wine-marketplace.mxsc.json";
+const MARKETPLACE_ADDRESS_EXPR: &str = "sc:wine-marketplace";
+const OWNER_ADDRESS_EXPR: &str = "address:owner";
+const REGISTRY_ADDRESS_EXPR: &str = "address:registry";
+const SELLER_ADDRESS_EXPR: &str = "address:seller";
+const BUYER_ADDRESS_EXPR: &str = "address:buyer";
+
+const WINE_NFT_TOKEN_ID: &[u8] = b"WINE-abcdef";
+const WINE_NFT_TOKEN_ID_EXPR: &str = "str:WINE-abcdef";
+// Fungible payment token; nonce 7 of the same identifier is an SFT
+const USDC_TOKEN_ID: &[u8] = b"USDC-123456";
+const USDC_TOKEN_ID_EXPR: &str = "str:USDC-123456";
+const USDC_SFT_NONCE: u64 = 7;
+// Transfer-role restricted tokens: the marketplace holds the role for ROLE but not for LOCK
+const ROLE_TOKEN_ID: &[u8] = b"ROLE-123456";
+const ROLE_TOKEN_ID_EXPR: &str = "str:ROLE-123456";
+const LOCK_TOKEN_ID: &[u8] = b"LOCK-123456";
+const LOCK_TOKEN_ID_EXPR: &str = "str:LOCK-123456";
+
+const PRICE: u64 = 1_000;
+
+// Registry-minted attributes of a bottle of `wine_id`; each test wine has its nonce as id
+fn encoded_wine_attributes(wine_id: u32) -> Vec<u8> {
+    let attributes = WineNftAttributes::<StaticApi> {
+        wine_id,
+        vineyard: ManagedBuffer::from("Chateau Test"),
+        variety: ManagedBuffer::from("Merlot"),
+        vintage_year: 2020,
+        region: ManagedBuffer::from("Bordeaux"),
+        quality_grade: 8,
+        ipfs_hash: ManagedBuffer::new(),
+    };
+    top_encode_to_vec_u8_or_panic(&attributes)
+}
+
+type MarketplaceWhitebox = WhiteboxContract<wine_marketplace::ContractObj<DebugApi>>;
+
+fn setup() -> (ScenarioWorld, MarketplaceWhitebox) {
+    let mut world = ScenarioWorld::new();
+    world.register_contract(MARKETPLACE_PATH_EXPR, wine_marketplace::ContractBuilder);
+
+    let marketplace_whitebox = WhiteboxContract::new(MARKETPLACE_ADDRESS_EXPR, wine_marketplace::contract_obj);
+    let marketplace_code = world.code_expression(MARKETPLACE_PATH_EXPR);
+
+    // Set up directly rather than deployed, so the marketplace account can carry ESDT roles
+    world.set_state_step(
+        SetStateStep::new()
+            .put_account(OWNER_ADDRESS_EXPR, Account::new().nonce(1))
+            // Receives the lock/unlock calls made as items enter and leave escrow
+            .put_account(REGISTRY_ADDRESS_EXPR, Account::new().nonce(1))
+            .put_account(
+                SELLER_ADDRESS_EXPR,
+                Account::new()
+                    .nonce(1)
+                    .esdt_nft_balance(WINE_NFT_TOKEN_ID_EXPR, 1, "1", Some(encoded_wine_attributes(1)))
+                    .esdt_nft_balance(WINE_NFT_TOKEN_ID_EXPR, 2, "1", Some(encoded_wine_attributes(2))),
+            )
+            .put_account(
+                BUYER_ADDRESS_EXPR,
+                Account::new()
+                    .nonce(1)
+                    .balance("10000")
+                    .esdt_balance(USDC_TOKEN_ID_EXPR, "10000")
+                    .esdt_nft_balance(USDC_TOKEN_ID_EXPR, USDC_SFT_NONCE, "10000", Option::<&[u8]>::None)
+                    .esdt_balance(ROLE_TOKEN_ID_EXPR, "10000")
+                    .esdt_balance(LOCK_TOKEN_ID_EXPR, "10000"),
+            )
+            .put_account(
+                MARKETPLACE_ADDRESS_EXPR,
+                Account::new()
+                    .code(marketplace_code)
+                    .owner(OWNER_ADDRESS_EXPR)
+                    .esdt_roles(ROLE_TOKEN_ID_EXPR, vec!["ESDTTransferRole".to_string()]),
+            ),
+    );
+
+    world.whitebox_call(&marketplace_whitebox, ScCallStep::new().from(OWNER_ADDRESS_EXPR), |sc| {
+        let registry = AddressValue::from(REGISTRY_ADDRESS_EXPR).to_address();
+        sc.init(250u64, managed_address!(&registry));
+        sc.set_wine_collection(managed_token_id!(WINE_NFT_TOKEN_ID));
+        // Both wines as the registry would push them: released, uncertified, by the owner
+        for wine_id in [1u32, 2u32] {
+            sc.wine_read_models(wine_id).set(WineReadModel {
+                price_per_bottle: BigUint::from(PRICE),
+                quality_grade: 8,
+                certified: false,
+                royalty_bps: 0,
+                status: WineStatus::Released,
+                producer: managed_address!(&AddressValue::from(OWNER_ADDRESS_EXPR).to_address()),
+                counterfeit: false,
+                certified_until: 0,
+            });
+        }
+        for token_id in [USDC_TOKEN_ID, ROLE_TOKEN_ID, LOCK_TOKEN_ID] {
+            sc.add_supported_token(EgldOrEsdtTokenIdentifier::esdt(managed_token_id!(token_id)));
+        }
+        sc.set_transfer_role_restricted(managed_token_id!(ROLE_TOKEN_ID), true);
+    });
+
+    (world, marketplace_whitebox)
+}
+
+fn payment_token(token_id: &[u8]) -> EgldOrEsdtTokenIdentifier<DebugApi> {
+    EgldOrEsdtTokenIdentifier::esdt(managed_token_id!(token_id))
+}
+
+fn create_listing(world: &mut ScenarioWorld, whitebox: &MarketplaceWhitebox, token_id: &[u8]) -> u32 {
+    let mut listing_id = 0;
+    world.whitebox_call(
+        whitebox,
+        ScCallStep::new()
+            .from(SELLER_ADDRESS_EXPR)
+            .esdt_transfer(WINE_NFT_TOKEN_ID_EXPR, 1, "1"),
+        |sc| {
+            listing_id = sc.create_listing(1, BigUint::from(PRICE), payment_token(token_id), 3600, OptionalValue::None);
+        },
+    );
+    listing_id
+}
+
+fn expect_error<F>(world: &mut ScenarioWorld, whitebox: &MarketplaceWhitebox, step: ScCallStep, call: F, message: &str)
+where
+    F: FnOnce(wine_marketplace::ContractObj<DebugApi>),
+{
+    world.whitebox_call_check(whitebox, step.no_expect(), call, |r| {
+        r.assert_user_error(message);
+    });
+}
+
+#[test]
+fn buy_wine_rejects_sft_of_payment_collection() {
+    let (mut world, whitebox) = setup();
+    let listing_id = create_listing(&mut world, &whitebox, USDC_TOKEN_ID);
+
+    expect_error(
+        &mut world,
+        &whitebox,
+        ScCallStep::new()
+            .from(BUYER_ADDRESS_EXPR)
+            .esdt_transfer(USDC_TOKEN_ID_EXPR, USDC_SFT_NONCE, PRICE.to_string().as_str()),
+        |sc| sc.buy_wine(listing_id, OptionalValue::None),
+        ERR_PAYMENT_MUST_BE_A_FUNGIBLE_TOKEN,
+    );
+}
+
+#[test]
+fn buy_wine_rejects_other_token() {
+    let (mut world, whitebox) = setup();
+    let listing_id = create_listing(&mut world, &whitebox, USDC_TOKEN_ID);
+
+    expect_error(
+        &mut world,
+        &whitebox,
+        ScCallStep::new()
+            .from(BUYER_ADDRESS_EXPR)
+            .egld_value(PRICE.to_string().as_str()),
+        |sc| sc.buy_wine(listing_id, OptionalValue::None),
+        ERR_INVALID_PAYMENT_TOKEN,
+    );
+}
+
+#[test]
+fn place_bid_rejects_sft_of_payment_collection() {
+    let (mut world, whitebox) = setup();
+
+    let mut auction_id = 0;
+    world.whitebox_call(
+        &whitebox,
+        ScCallStep::new()
+            .from(SELLER_ADDRESS_EXPR)
+            .esdt_transfer(WINE_NFT_TOKEN_ID_EXPR, 1, "1"),
+        |sc| {
+            auction_id = sc.create_auction(
+                1,
+                BigUint::from(PRICE),
+                payment_token(USDC_TOKEN_ID),
+                3600,
+                BigUint::from(10u64),
+            );
+        },
+    );
+
+    expect_error(
+        &mut world,
+        &whitebox,
+        ScCallStep::new()
+            .from(BUYER_ADDRESS_EXPR)
+            .esdt_transfer(USDC_TOKEN_ID_EXPR, USDC_SFT_NONCE, "2000"),
+        |sc| sc.place_bid(auction_id, OptionalValue::None),
+        ERR_PAYMENT_MUST_BE_A_FUNGIBLE_TOKEN,
+    );
+}
+
+#[test]
+fn place_candle_bid_rejects_sft_of_payment_collection() {
+    let (mut world, whitebox) = setup();
+
+    let mut auction_id = 0;
+    world.whitebox_call(
+        &whitebox,
+        ScCallStep::new()
+            .from(SELLER_ADDRESS_EXPR)
+            .esdt_transfer(WINE_NFT_TOKEN_ID_EXPR, 1, "1"),
+        |sc| {
+            auction_id = sc.create_candle_auction(
+                1,
+                BigUint::from(PRICE),
+                payment_token(USDC_TOKEN_ID),
+                7200,
+                3600,
+                BigUint::from(10u64),
+            );
+        },
+    );
+
+    expect_error(
+        &mut world,
+        &whitebox,
+        ScCallStep::new()
+            .from(BUYER_ADDRESS_EXPR)
+            .esdt_transfer(USDC_TOKEN_ID_EXPR, USDC_SFT_NONCE, "2000"),
+        |sc| sc.place_candle_bid(auction_id),
+        ERR_PAYMENT_MUST_BE_A_FUNGIBLE_TOKEN,
+    );
+}
+
+#[test]
+fn make_offer_rejects_sft_of_payment_collection() {
+    let (mut world, whitebox) = setup();
+
+    expect_error(
+        &mut world,
+        &whitebox,
+        ScCallStep::new()
+            .from(BUYER_ADDRESS_EXPR)
+            .esdt_transfer(USDC_TOKEN_ID_EXPR, USDC_SFT_NONCE, "500"),
+        |sc| {
+            sc.make_offer(1, managed_token_id!(WINE_NFT_TOKEN_ID), 2, 3600);
+        },
+        ERR_PAYMENT_MUST_BE_A_FUNGIBLE_TOKEN,
+    );
+}
+
+#[test]
+fn make_offer_rejects_nft_as_payment() {
+    let (mut world, whitebox) = setup();
+
+    expect_error(
+        &mut world,
+        &whitebox,
+        ScCallStep::new()
+            .from(SELLER_ADDRESS_EXPR)
+            .esdt_transfer(WINE_NFT_TOKEN_ID_EXPR, 1, "1"),
+        |sc| {
+            sc.make_offer(1, managed_token_id!(WINE_NFT_TOKEN_ID), 2, 3600);
+        },
+        ERR_PAYMENT_TOKEN_NOT_SUPPORTED,
+    );
+}
+
+#[test]
+fn installment_payments_reject_sft_of_payment_collection() {
+    let (mut world, whitebox) = setup();
+    let listing_id = create_listing(&mut world, &whitebox, USDC_TOKEN_ID);
+
+    expect_error(
+        &mut world,
+        &whitebox,
+        ScCallStep::new()
+            .from(BUYER_ADDRESS_EXPR)
+            .esdt_transfer(USDC_TOKEN_ID_EXPR, USDC_SFT_NONCE, "300"),
+        |sc| sc.start_installment_purchase(listing_id, 86400),
+        ERR_PAYMENT_MUST_BE_A_FUNGIBLE_TOKEN,
+    );
+
+    world.whitebox_call(
+        &whitebox,
+        ScCallStep::new()
+            .from(BUYER_ADDRESS_EXPR)
+            .esdt_transfer(USDC_TOKEN_ID_EXPR, 0, "300"),
+        |sc| sc.start_installment_purchase(listing_id, 86400),
+    );
+
+    expect_error(
+        &mut world,
+        &whitebox,
+        ScCallStep::new()
+            .from(BUYER_ADDRESS_EXPR)
+            .esdt_transfer(USDC_TOKEN_ID_EXPR, USDC_SFT_NONCE, "700"),
+        |sc| sc.pay_installment(listing_id),
+        ERR_PAYMENT_MUST_BE_A_FUNGIBLE_TOKEN,
+    );
+}
+
+#[test]
+fn restricted_token_without_transfer_role_cannot_be_listed() {
+    let (mut world, whitebox) = setup();
+
+    world.whitebox_call(&whitebox, ScCallStep::new().from(OWNER_ADDRESS_EXPR), |sc| {
+        sc.set_transfer_role_restricted(managed_token_id!(LOCK_TOKEN_ID), true);
+    });
+
+    expect_error(
+        &mut world,
+        &whitebox,
+        ScCallStep::new()
+            .from(SELLER_ADDRESS_EXPR)
+            .esdt_transfer(WINE_NFT_TOKEN_ID_EXPR, 1, "1"),
+        |sc| {
+            sc.create_listing(1, BigUint::from(PRICE), payment_token(LOCK_TOKEN_ID), 3600, OptionalValue::None);
+        },
+        ERR_MARKETPLACE_LACKS_TRANSFER_ROLE_FOR_PAYMENT_TOKEN,
+    );
+}
+
+#[test]
+fn restricted_token_without_transfer_role_cannot_be_paid() {
+    let (mut world, whitebox) = setup();
+    let listing_id = create_listing(&mut world, &whitebox, LOCK_TOKEN_ID);
+
+    // Restriction flagged after the listing was created
+    world.whitebox_call(&whitebox, ScCallStep::new().from(OWNER_ADDRESS_EXPR), |sc| {
+        sc.set_transfer_role_restricted(managed_token_id!(LOCK_TOKEN_ID), true);
+    });
+
+    expect_error(
+        &mut world,
+        &whitebox,
+        ScCallStep::new()
+            .from(BUYER_ADDRESS_EXPR)
+            .esdt_transfer(LOCK_TOKEN_ID_EXPR, 0, PRICE.to_string().as_str()),
+        |sc| sc.buy_wine(listing_id, OptionalValue::None),
+        ERR_MARKETPLACE_LACKS_TRANSFER_ROLE_FOR_PAYMENT_TOKEN,
+    );
+}
+
+#[test]
+fn restricted_token_with_transfer_role_settles_sale() {
+    let (mut world, whitebox) = setup();
+    let listing_id = create_listing(&mut world, &whitebox, ROLE_TOKEN_ID);
+
+    world.whitebox_call(
+        &whitebox,
+        ScCallStep::new()
+            .from(BUYER_ADDRESS_EXPR)
+            .esdt_transfer(ROLE_TOKEN_ID_EXPR, 0, PRICE.to_string().as_str()),
+        |sc| sc.buy_wine(listing_id, OptionalValue::None),
+    );
+
+    world.check_state_step(
+        CheckStateStep::new()
+            .put_account(
+                SELLER_ADDRESS_EXPR,
+                CheckAccount::new().esdt_balance(ROLE_TOKEN_ID_EXPR, "975"),
+            )
+            .put_account(
+                OWNER_ADDRESS_EXPR,
+                CheckAccount::new().esdt_balance(ROLE_TOKEN_ID_EXPR, "25"),
+            )
+            .put_account(
+                BUYER_ADDRESS_EXPR,
+                CheckAccount::new()
+                    .esdt_balance(ROLE_TOKEN_ID_EXPR, "9000")
+                    .esdt_nft_balance_and_attributes(WINE_NFT_TOKEN_ID_EXPR, 1, "1", Some(encoded_wine_attributes(1))),
+            ),
+    );
+}
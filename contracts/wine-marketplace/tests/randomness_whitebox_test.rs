@@ -0,0 +1,228 @@
+use multiversx_sc_scenario::imports::*;
+use wine_common::errors::*;
+use wine_marketplace::*;
+use wine_randomness::{RandomnessModule, CANDLE_AUCTION_DOMAIN, RAFFLE_DOMAIN};
+
+const MARKETPLACE_PATH_EXPR: &str = "mxsc:output/wine-marketplace.mxsc.json";
+const MARKETPLACE_ADDRESS_EXPR: &str = "sc:wine-marketplace";
+const OWNER_ADDRESS_EXPR: &str = "address:owner";
+const REGISTRY_ADDRESS_EXPR: &str = "address:registry";
+const ALICE_ADDRESS_EXPR: &str = "address:alice";
+const BOB_ADDRESS_EXPR: &str = "address:bob";
+
+const SEED_A: &str = "0x111111111111111111111111111111111111111111111111111111111111111111111111111111111111111111111111";
+const SEED_B: &str = "0x222222222222222222222222222222222222222222222222222222222222222222222222222222222222222222222222";
+
+type MarketplaceWhitebox = WhiteboxContract<wine_marketplace::ContractObj<DebugApi>>;
+
+fn world() -> ScenarioWorld {
+    let mut blockchain = ScenarioWorld::new();
+    blockchain.register_contract(MARKETPLACE_PATH_EXPR, wine_marketplace::ContractBuilder);
+    blockchain
+}
+
+fn setup() -> (ScenarioWorld, MarketplaceWhitebox) {
+    let mut world = world();
+    let marketplace_whitebox = WhiteboxContract::new(MARKETPLACE_ADDRESS_EXPR, wine_marketplace::contract_obj);
+    let marketplace_code = world.code_expression(MARKETPLACE_PATH_EXPR);
+
+    world
+        .set_state_step(
+            SetStateStep::new()
+                .put_account(OWNER_ADDRESS_EXPR, Account::new().nonce(1))
+                .put_account(ALICE_ADDRESS_EXPR, Account::new().nonce(1))
+                .put_account(BOB_ADDRESS_EXPR, Account::new().nonce(1))
+                .new_address(OWNER_ADDRESS_EXPR, 1, MARKETPLACE_ADDRESS_EXPR)
+                .block_random_seed(SEED_A),
+        )
+        .whitebox_deploy(
+            &marketplace_whitebox,
+            ScDeployStep::new().from(OWNER_ADDRESS_EXPR).code(marketplace_code),
+            |sc| {
+                let registry = AddressValue::from(REGISTRY_ADDRESS_EXPR).to_address();
+                sc.init(250u64, managed_address!(&registry));
+            },
+        );
+
+    (world, marketplace_whitebox)
+}
+
+fn draw(world: &mut ScenarioWorld, whitebox: &MarketplaceWhitebox, domain: &[u8], context_id: u64) -> [u8; 32] {
+    let mut result = [0u8; 32];
+    world.whitebox_call(whitebox, ScCallStep::new().from(ALICE_ADDRESS_EXPR), |sc| {
+        result = sc.draw_random_hash(&managed_buffer!(domain), context_id).to_byte_array();
+    });
+    result
+}
+
+fn commit(world: &mut ScenarioWorld, whitebox: &MarketplaceWhitebox, from: &str, context_id: u64, preimage: &[u8]) {
+    world.whitebox_call(whitebox, ScCallStep::new().from(from), |sc| {
+        let commitment = sc.crypto().keccak256(&managed_buffer!(preimage));
+        sc.commit_entropy(managed_buffer!(CANDLE_AUCTION_DOMAIN), context_id, commitment);
+    });
+}
+
+fn reveal(world: &mut ScenarioWorld, whitebox: &MarketplaceWhitebox, from: &str, context_id: u64, preimage: &[u8]) {
+    world.whitebox_call(whitebox, ScCallStep::new().from(from), |sc| {
+        sc.reveal_entropy(managed_buffer!(CANDLE_AUCTION_DOMAIN), context_id, managed_buffer!(preimage));
+    });
+}
+
+#[test]
+fn consecutive_draws_in_same_block_differ() {
+    let (mut world, whitebox) = setup();
+
+    let first = draw(&mut world, &whitebox, CANDLE_AUCTION_DOMAIN, 1);
+    let second = draw(&mut world, &whitebox, CANDLE_AUCTION_DOMAIN, 1);
+    assert_ne!(first, second);
+
+    world.whitebox_query(&whitebox, |sc| {
+        assert_eq!(sc.get_random_draw_nonce(managed_buffer!(CANDLE_AUCTION_DOMAIN)), 2u64);
+    });
+}
+
+#[test]
+fn domains_and_contexts_are_separated() {
+    let (mut world, candle_whitebox) = setup();
+    let candle_output = draw(&mut world, &candle_whitebox, CANDLE_AUCTION_DOMAIN, 1);
+
+    let (mut world, raffle_whitebox) = setup();
+    let raffle_output = draw(&mut world, &raffle_whitebox, RAFFLE_DOMAIN, 1);
+
+    let (mut world, other_context_whitebox) = setup();
+    let other_context_output = draw(&mut world, &other_context_whitebox, CANDLE_AUCTION_DOMAIN, 2);
+
+    assert_ne!(candle_output, raffle_output);
+    assert_ne!(candle_output, other_context_output);
+}
+
+#[test]
+fn output_depends_on_block_seed() {
+    let (mut world, whitebox) = setup();
+    let with_seed_a = draw(&mut world, &whitebox, CANDLE_AUCTION_DOMAIN, 1);
+
+    let (mut world, whitebox) = setup();
+    world.set_state_step(SetStateStep::new().block_random_seed(SEED_B));
+    let with_seed_b = draw(&mut world, &whitebox, CANDLE_AUCTION_DOMAIN, 1);
+
+    assert_ne!(with_seed_a, with_seed_b);
+}
+
+#[test]
+fn same_inputs_replay_to_same_output() {
+    // determinism across fresh deployments is what makes a settlement auditable
+    let (mut world, whitebox) = setup();
+    let first_run = draw(&mut world, &whitebox, CANDLE_AUCTION_DOMAIN, 7);
+
+    let (mut world, whitebox) = setup();
+    let second_run = draw(&mut world, &whitebox, CANDLE_AUCTION_DOMAIN, 7);
+
+    assert_eq!(first_run, second_run);
+}
+
+#[test]
+fn revealed_entropy_changes_output() {
+    let (mut world, whitebox) = setup();
+    let without_entropy = draw(&mut world, &whitebox, CANDLE_AUCTION_DOMAIN, 1);
+
+    let (mut world, whitebox) = setup();
+    commit(&mut world, &whitebox, ALICE_ADDRESS_EXPR, 1, b"alice-secret");
+    reveal(&mut world, &whitebox, ALICE_ADDRESS_EXPR, 1, b"alice-secret");
+    let with_entropy = draw(&mut world, &whitebox, CANDLE_AUCTION_DOMAIN, 1);
+
+    assert_ne!(without_entropy, with_entropy);
+    world.whitebox_query(&whitebox, |sc| {
+        assert_eq!(sc.get_entropy_contribution_count(managed_buffer!(CANDLE_AUCTION_DOMAIN), 1), 1u32);
+    });
+}
+
+#[test]
+fn copied_preimage_yields_different_contribution() {
+    let (mut world, whitebox) = setup();
+    commit(&mut world, &whitebox, ALICE_ADDRESS_EXPR, 1, b"shared-secret");
+    reveal(&mut world, &whitebox, ALICE_ADDRESS_EXPR, 1, b"shared-secret");
+    let alice_output = draw(&mut world, &whitebox, CANDLE_AUCTION_DOMAIN, 1);
+
+    let (mut world, whitebox) = setup();
+    commit(&mut world, &whitebox, BOB_ADDRESS_EXPR, 1, b"shared-secret");
+    reveal(&mut world, &whitebox, BOB_ADDRESS_EXPR, 1, b"shared-secret");
+    let bob_output = draw(&mut world, &whitebox, CANDLE_AUCTION_DOMAIN, 1);
+
+    assert_ne!(alice_output, bob_output);
+}
+
+#[test]
+fn reveal_rejects_wrong_preimage() {
+    let (mut world, whitebox) = setup();
+    commit(&mut world, &whitebox, ALICE_ADDRESS_EXPR, 1, b"alice-secret");
+
+    world.whitebox_call_check(
+        &whitebox,
+        ScCallStep::new().from(ALICE_ADDRESS_EXPR).no_expect(),
+        |sc| {
+            sc.reveal_entropy(managed_buffer!(CANDLE_AUCTION_DOMAIN), 1, managed_buffer!(b"guessed"));
+        },
+        |r| {
+            r.assert_user_error(ERR_PREIMAGE_DOES_NOT_MATCH_COMMITMENT);
+        },
+    );
+}
+
+#[test]
+fn reveal_cannot_be_replayed() {
+    let (mut world, whitebox) = setup();
+    commit(&mut world, &whitebox, ALICE_ADDRESS_EXPR, 1, b"alice-secret");
+    reveal(&mut world, &whitebox, ALICE_ADDRESS_EXPR, 1, b"alice-secret");
+
+    world.whitebox_call_check(
+        &whitebox,
+        ScCallStep::new().from(ALICE_ADDRESS_EXPR).no_expect(),
+        |sc| {
+            sc.reveal_entropy(managed_buffer!(CANDLE_AUCTION_DOMAIN), 1, managed_buffer!(b"alice-secret"));
+        },
+        |r| {
+            r.assert_user_error(ERR_ENTROPY_ALREADY_REVEALED);
+        },
+    );
+}
+
+#[test]
+fn context_is_sealed_after_first_draw() {
+    let (mut world, whitebox) = setup();
+    commit(&mut world, &whitebox, ALICE_ADDRESS_EXPR, 1, b"alice-secret");
+    draw(&mut world, &whitebox, CANDLE_AUCTION_DOMAIN, 1);
+
+    world.whitebox_call_check(
+        &whitebox,
+        ScCallStep::new().from(ALICE_ADDRESS_EXPR).no_expect(),
+        |sc| {
+            sc.reveal_entropy(managed_buffer!(CANDLE_AUCTION_DOMAIN), 1, managed_buffer!(b"alice-secret"));
+        },
+        |r| {
+            r.assert_user_error(ERR_ENTROPY_IS_SEALED_FOR_THIS_CONTEXT);
+        },
+    );
+    world.whitebox_call_check(
+        &whitebox,
+        ScCallStep::new().from(BOB_ADDRESS_EXPR).no_expect(),
+        |sc| {
+            let commitment = sc.crypto().keccak256(&managed_buffer!(b"late"));
+            sc.commit_entropy(managed_buffer!(CANDLE_AUCTION_DOMAIN), 1, commitment);
+        },
+        |r| {
+            r.assert_user_error(ERR_ENTROPY_IS_SEALED_FOR_THIS_CONTEXT);
+        },
+    );
+}
+
+#[test]
+fn range_draws_stay_in_bounds() {
+    let (mut world, whitebox) = setup();
+
+    for _ in 0..20 {
+        world.whitebox_call(&whitebox, ScCallStep::new().from(ALICE_ADDRESS_EXPR), |sc| {
+            let value = sc.draw_random_u64_in_range(&managed_buffer!(RAFFLE_DOMAIN), 1, 100, 110);
+            assert!((100..110).contains(&value));
+        });
+    }
+}
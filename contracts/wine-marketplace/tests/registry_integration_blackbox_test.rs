@@ -0,0 +1,276 @@
+use multiversx_sc::codec::top_encode_to_vec_u8_or_panic;
+use multiversx_sc_scenario::api::StaticApi;
+use multiversx_sc_scenario::imports::*;
+use wine_marketplace::listing::ProxyTrait as _;
+use wine_marketplace::registry_cache::ProxyTrait as _;
+use wine_marketplace::ProxyTrait as _;
+use wine_common::errors::*;
+use wine_marketplace::WineNftAttributes;
+use wine_registry::admin::ProxyTrait as _;
+use wine_registry::provenance::ProxyTrait as _;
+use wine_registry::provenance::{SensitiveAction, WineAction};
+use wine_registry::ratings::ProxyTrait as _;
+use wine_registry::read_model::ProxyTrait as _;
+use wine_registry::registration::ProxyTrait as _;
+use wine_registry::ProxyTrait as _;
+
+const MARKETPLACE_PATH_EXPR: &str = "mxsc:output/wine-marketplace.mxsc.json";
+const REGISTRY_PATH_EXPR: &str = "mxsc:../wine-registry/output/wine-registry.mxsc.json";
+const MARKETPLACE_ADDRESS_EXPR: &str = "sc:wine-marketplace";
+const REGISTRY_ADDRESS_EXPR: &str = "sc:wine-registry";
+const OWNER_ADDRESS_EXPR: &str = "address:owner";
+const PRODUCER_ADDRESS_EXPR: &str = "address:producer";
+const BUYER_ADDRESS_EXPR: &str = "address:buyer";
+const COLLECTOR_ADDRESS_EXPR: &str = "address:collector";
+
+const WINE_NFT_TOKEN_ID_EXPR: &str = "str:WINE-abcdef";
+
+const REGISTRATION_FEE: u64 = 1_000;
+const PRICE: u64 = 1_000;
+const DURATION: u64 = 3600;
+
+type MarketplaceContract = ContractInfo<wine_marketplace::Proxy<StaticApi>>;
+type RegistryContract = ContractInfo<wine_registry::Proxy<StaticApi>>;
+
+fn user_error(message: &str) -> TxExpect {
+    TxExpect::user_error(format!("str:{message}"))
+}
+
+// Both contracts deployed side by side: the registry pushes wine 1 to the marketplace on
+// registration, and the marketplace may record sales in the registry
+struct IntegrationTestState {
+    world: ScenarioWorld,
+    marketplace: MarketplaceContract,
+    registry: RegistryContract,
+}
+
+impl IntegrationTestState {
+    fn new() -> Self {
+        let mut world = ScenarioWorld::new();
+        world.register_contract(MARKETPLACE_PATH_EXPR, wine_marketplace::ContractBuilder);
+        world.register_contract(REGISTRY_PATH_EXPR, wine_registry::ContractBuilder);
+
+        let attributes = WineNftAttributes::<StaticApi> {
+            wine_id: 1,
+            vineyard: ManagedBuffer::from("Domaine de Test"),
+            variety: ManagedBuffer::from("Pinot Noir"),
+            vintage_year: 2020,
+            region: ManagedBuffer::from("Burgundy"),
+            quality_grade: 8,
+            ipfs_hash: ManagedBuffer::new(),
+        };
+        world.set_state_step(
+            SetStateStep::new()
+                .put_account(OWNER_ADDRESS_EXPR, Account::new().nonce(1))
+                .put_account(
+                    PRODUCER_ADDRESS_EXPR,
+                    Account::new().nonce(1).balance(REGISTRATION_FEE).esdt_nft_balance(
+                        WINE_NFT_TOKEN_ID_EXPR,
+                        1,
+                        "1",
+                        Some(top_encode_to_vec_u8_or_panic(&attributes)),
+                    ),
+                )
+                .put_account(BUYER_ADDRESS_EXPR, Account::new().nonce(1).balance(PRICE))
+                .put_account(COLLECTOR_ADDRESS_EXPR, Account::new().nonce(1))
+                .new_address(OWNER_ADDRESS_EXPR, 1, REGISTRY_ADDRESS_EXPR)
+                .new_address(OWNER_ADDRESS_EXPR, 2, MARKETPLACE_ADDRESS_EXPR),
+        );
+
+        let mut registry = RegistryContract::new(REGISTRY_ADDRESS_EXPR);
+        let registry_code = world.code_expression(REGISTRY_PATH_EXPR);
+        world.sc_deploy(
+            ScDeployStep::new()
+                .from(OWNER_ADDRESS_EXPR)
+                .code(registry_code)
+                .call(registry.init(REGISTRATION_FEE)),
+        );
+        let mut marketplace = MarketplaceContract::new(MARKETPLACE_ADDRESS_EXPR);
+        let marketplace_code = world.code_expression(MARKETPLACE_PATH_EXPR);
+        let registry_address = AddressValue::from(REGISTRY_ADDRESS_EXPR).to_address();
+        world.sc_deploy(
+            ScDeployStep::new()
+                .from(OWNER_ADDRESS_EXPR)
+                .code(marketplace_code)
+                .call(marketplace.init(250u64, managed_address!(&registry_address))),
+        );
+
+        let marketplace_address = AddressValue::from(MARKETPLACE_ADDRESS_EXPR).to_address();
+        world
+            .sc_call(
+                ScCallStep::new()
+                    .from(OWNER_ADDRESS_EXPR)
+                    .call(marketplace.set_wine_collection(TokenIdentifier::from("WINE-abcdef"))),
+            )
+            .sc_call(
+                ScCallStep::new()
+                    .from(OWNER_ADDRESS_EXPR)
+                    .call(registry.add_read_model_subscriber(managed_address!(&marketplace_address))),
+            )
+            .sc_call(
+                ScCallStep::new()
+                    .from(OWNER_ADDRESS_EXPR)
+                    .call(registry.set_integration_permissions(
+                        managed_address!(&marketplace_address),
+                        false,
+                        true,
+                        false,
+                    )),
+            );
+
+        world.sc_call(
+            ScCallStep::new()
+                .from(PRODUCER_ADDRESS_EXPR)
+                .egld_value(REGISTRATION_FEE)
+                .call(registry.register_wine(
+                    "Domaine de Test",
+                    "Pinot Noir",
+                    2020u32,
+                    8u8,
+                    1300u16,
+                    "Burgundy",
+                    "AOC",
+                    "producer-signature",
+                    "QmYwAPJzv5CZsnA625s3Xf2nemtYgPpHdWEz79ojWnPbdG",
+                    100u32,
+                    50_000u64,
+                    false,
+                    1_600_000_000u64,
+                    "18 months in French oak",
+                    "Cherry, forest floor",
+                ))
+                .expect_value(1u32),
+        );
+
+        IntegrationTestState { world, marketplace, registry }
+    }
+
+    fn create_listing_step(&mut self) -> TypedScCall<u32> {
+        ScCallStep::new()
+            .from(PRODUCER_ADDRESS_EXPR)
+            .esdt_transfer(WINE_NFT_TOKEN_ID_EXPR, 1, "1")
+            .call(self.marketplace.create_listing(
+                1u32,
+                PRICE,
+                EgldOrEsdtTokenIdentifier::egld(),
+                DURATION,
+                OptionalValue::<bool>::None,
+            ))
+    }
+}
+
+#[test]
+fn marketplace_sale_makes_the_buyer_rating_verified() {
+    let mut state = IntegrationTestState::new();
+
+    state.world.sc_call(
+        ScCallStep::new()
+            .from(PRODUCER_ADDRESS_EXPR)
+            .esdt_transfer(WINE_NFT_TOKEN_ID_EXPR, 1, "1")
+            .call(state.marketplace.create_listing(
+                1u32,
+                PRICE,
+                EgldOrEsdtTokenIdentifier::egld(),
+                DURATION,
+                OptionalValue::<bool>::None,
+            )),
+    );
+    let locked: bool = state.world.quick_query(state.registry.is_wine_locked(1u32));
+    assert!(locked);
+
+    state.world.sc_call(
+        ScCallStep::new()
+            .from(BUYER_ADDRESS_EXPR)
+            .egld_value(PRICE)
+            .call(state.marketplace.buy_wine(1u32, OptionalValue::<ManagedAddress<StaticApi>>::None)),
+    );
+    let locked: bool = state.world.quick_query(state.registry.is_wine_locked(1u32));
+    assert!(!locked);
+
+    // Only the buyer's rating counts as a verified purchase
+    for rater in [BUYER_ADDRESS_EXPR, OWNER_ADDRESS_EXPR] {
+        state.world.sc_call(
+            ScCallStep::new()
+                .from(rater)
+                .call(state.registry.rate_wine(1u32, 8u8, "Lovely structure")),
+        );
+    }
+    let buyer = AddressValue::from(BUYER_ADDRESS_EXPR).to_address();
+    let owner = AddressValue::from(OWNER_ADDRESS_EXPR).to_address();
+    let verified: bool =
+        state.world.quick_query(state.registry.is_verified_rating(1u32, managed_address!(&buyer)));
+    assert!(verified);
+    let verified: bool =
+        state.world.quick_query(state.registry.is_verified_rating(1u32, managed_address!(&owner)));
+    assert!(!verified);
+}
+
+#[test]
+fn listing_reverts_when_the_registry_rejects_the_lock() {
+    let mut state = IntegrationTestState::new();
+    let collector = AddressValue::from(COLLECTOR_ADDRESS_EXPR).to_address();
+    state
+        .world
+        .sc_call(
+            ScCallStep::new()
+                .from(PRODUCER_ADDRESS_EXPR)
+                .call(state.registry.add_co_owner(1u32, managed_address!(&collector), 4_000u64)),
+        )
+        .sc_call(
+            ScCallStep::new()
+                .from(PRODUCER_ADDRESS_EXPR)
+                .call(state.registry.set_approval_threshold(1u32, SensitiveAction::Transfer, 10_000u64)),
+        );
+
+    // Escrowing the owner's bottle needs the co-owner's approval, so the listing is undone
+    // and the bottle stays with the producer until the co-owner approves
+    let step = state.create_listing_step();
+    state.world.sc_call(step.expect(user_error(ERR_CO_OWNER_APPROVALS_BELOW_THRESHOLD)));
+    let locked: bool = state.world.quick_query(state.registry.is_wine_locked(1u32));
+    assert!(!locked);
+
+    let marketplace_address = AddressValue::from(MARKETPLACE_ADDRESS_EXPR).to_address();
+    state.world.sc_call(
+        ScCallStep::new()
+            .from(COLLECTOR_ADDRESS_EXPR)
+            .call(state.registry.approve_wine_action(
+                1u32,
+                WineAction::<StaticApi>::Transfer(managed_address!(&marketplace_address)),
+            )),
+    );
+    let step = state.create_listing_step();
+    state.world.sc_call(step);
+    let locked: bool = state.world.quick_query(state.registry.is_wine_locked(1u32));
+    assert!(locked);
+}
+
+#[test]
+fn listing_needs_the_permission_to_record_sales_in_the_registry() {
+    let mut state = IntegrationTestState::new();
+    let marketplace_address = AddressValue::from(MARKETPLACE_ADDRESS_EXPR).to_address();
+    state.world.sc_call(
+        ScCallStep::new()
+            .from(OWNER_ADDRESS_EXPR)
+            .call(state.registry.revoke_integration_permissions(managed_address!(&marketplace_address))),
+    );
+
+    let step = state.create_listing_step();
+    state.world.sc_call(step.expect(user_error(ERR_NOT_AUTHORIZED_TO_RECORD_SALES)));
+    let locked: bool = state.world.quick_query(state.registry.is_wine_locked(1u32));
+    assert!(!locked);
+
+    state.world.sc_call(
+        ScCallStep::new()
+            .from(OWNER_ADDRESS_EXPR)
+            .call(state.registry.set_integration_permissions(
+                managed_address!(&marketplace_address),
+                false,
+                true,
+                false,
+            )),
+    );
+    let step = state.create_listing_step();
+    state.world.sc_call(step.expect_value(1u32));
+    let locked: bool = state.world.quick_query(state.registry.is_wine_locked(1u32));
+    assert!(locked);
+}
@@ -0,0 +1,170 @@
+use multiversx_sc::codec::top_encode_to_vec_u8_or_panic;
+use multiversx_sc_scenario::api::StaticApi;
+use multiversx_sc_scenario::imports::*;
+use wine_marketplace::admin::AdminModule;
+use wine_marketplace::auction::AuctionModule;
+use wine_marketplace::candle_auction::CandleAuctionModule;
+use wine_marketplace::installments::InstallmentsModule;
+use wine_marketplace::listing::ListingModule;
+use wine_marketplace::offers::OffersModule;
+use wine_marketplace::registry_cache::RegistryCacheModule;
+use wine_marketplace::treasury::TreasuryModule;
+use wine_marketplace::*;
+
+const MARKETPLACE_PATH_EXPR: &str = "mxsc:output/wine-marketplace.mxsc.json";
+const MARKETPLACE_ADDRESS_EXPR: &str = "sc:wine-marketplace";
+const OWNER_ADDRESS_EXPR: &str = "address:owner";
+const REGISTRY_ADDRESS_EXPR: &str = "address:registry";
+const SELLER_ADDRESS_EXPR: &str = "address:seller";
+const BUYER_ADDRESS_EXPR: &str = "address:buyer";
+
+const WINE_NFT_TOKEN_ID: &[u8] = b"WINE-abcdef";
+const WINE_NFT_TOKEN_ID_EXPR: &str = "str:WINE-abcdef";
+
+const PRICE: u64 = 1_000;
+
+// Registry-minted attributes of a bottle of `wine_id`; each test wine has its nonce as id
+fn encoded_wine_attributes(wine_id: u32) -> Vec<u8> {
+    let attributes = WineNftAttributes::<StaticApi> {
+        wine_id,
+        vineyard: ManagedBuffer::from("Chateau Test"),
+        variety: ManagedBuffer::from("Merlot"),
+        vintage_year: 2020,
+        region: ManagedBuffer::from("Bordeaux"),
+        quality_grade: 8,
+        ipfs_hash: ManagedBuffer::new(),
+    };
+    top_encode_to_vec_u8_or_panic(&attributes)
+}
+
+type MarketplaceWhitebox = WhiteboxContract<wine_marketplace::ContractObj<DebugApi>>;
+
+// A marketplace holding only what the first devnet deployment's init stored, then upgraded
+fn setup_upgraded() -> (ScenarioWorld, MarketplaceWhitebox) {
+    let mut world = ScenarioWorld::new();
+    world.register_contract(MARKETPLACE_PATH_EXPR, wine_marketplace::ContractBuilder);
+
+    let marketplace_whitebox = WhiteboxContract::new(MARKETPLACE_ADDRESS_EXPR, wine_marketplace::contract_obj);
+    let marketplace_code = world.code_expression(MARKETPLACE_PATH_EXPR);
+
+    world.set_state_step(
+        SetStateStep::new()
+            .put_account(OWNER_ADDRESS_EXPR, Account::new().nonce(1))
+            .put_account(REGISTRY_ADDRESS_EXPR, Account::new().nonce(1))
+            .put_account(
+                SELLER_ADDRESS_EXPR,
+                Account::new()
+                    .nonce(1)
+                    .esdt_nft_balance(WINE_NFT_TOKEN_ID_EXPR, 1, "1", Some(encoded_wine_attributes(1)))
+                    .esdt_nft_balance(WINE_NFT_TOKEN_ID_EXPR, 2, "1", Some(encoded_wine_attributes(2))),
+            )
+            .put_account(BUYER_ADDRESS_EXPR, Account::new().nonce(1).balance("10000"))
+            .put_account(
+                MARKETPLACE_ADDRESS_EXPR,
+                Account::new().code(marketplace_code).owner(OWNER_ADDRESS_EXPR),
+            ),
+    );
+
+    world.whitebox_call(&marketplace_whitebox, ScCallStep::new().from(OWNER_ADDRESS_EXPR), |sc| {
+        let registry = AddressValue::from(REGISTRY_ADDRESS_EXPR).to_address();
+        sc.marketplace_fee_percent().set(250u64);
+        sc.wine_registry_address().set(managed_address!(&registry));
+        sc.listing_counter().set(1u32);
+        sc.auction_counter().set(1u32);
+        sc.supported_payment_tokens(&EgldOrEsdtTokenIdentifier::egld()).set(true);
+        sc.marketplace_stats().set(MarketplaceStats {
+            total_listings: 0u32,
+            total_sales: 0u32,
+            total_volume: BigUint::zero(),
+            total_fees_collected: BigUint::zero(),
+            active_listings: 0u32,
+            active_auctions: 0u32,
+        });
+    });
+    world.whitebox_call(&marketplace_whitebox, ScCallStep::new().from(OWNER_ADDRESS_EXPR), |sc| {
+        sc.upgrade();
+        sc.set_wine_collection(managed_token_id!(WINE_NFT_TOKEN_ID));
+        for wine_id in [1u32, 2u32] {
+            sc.wine_read_models(wine_id).set(WineReadModel {
+                price_per_bottle: BigUint::from(PRICE),
+                quality_grade: 8,
+                certified: false,
+                royalty_bps: 0,
+                status: WineStatus::Released,
+                producer: managed_address!(&AddressValue::from(OWNER_ADDRESS_EXPR).to_address()),
+                counterfeit: false,
+                certified_until: 0,
+            });
+        }
+    });
+
+    (world, marketplace_whitebox)
+}
+
+#[test]
+fn upgrade_seeds_settings_added_after_the_first_deployment() {
+    let (mut world, whitebox) = setup_upgraded();
+
+    world.whitebox_query(&whitebox, |sc| {
+        assert_eq!(sc.candle_auction_counter().get(), 1);
+        assert_eq!(sc.offer_counter().get(), 1);
+    });
+
+    // Bids read the anti-snipe settings
+    let mut auction_id = 0;
+    world.whitebox_call(
+        &whitebox,
+        ScCallStep::new()
+            .from(SELLER_ADDRESS_EXPR)
+            .esdt_transfer(WINE_NFT_TOKEN_ID_EXPR, 1, "1"),
+        |sc| {
+            auction_id = sc.create_auction(
+                1,
+                BigUint::from(PRICE),
+                EgldOrEsdtTokenIdentifier::egld(),
+                3600,
+                BigUint::from(10u64),
+            );
+        },
+    );
+    world.whitebox_call(
+        &whitebox,
+        ScCallStep::new().from(BUYER_ADDRESS_EXPR).egld_value("2000"),
+        |sc| sc.place_bid(auction_id, OptionalValue::None),
+    );
+
+    // Payment plans read the installment settings
+    let mut listing_id = 0;
+    world.whitebox_call(
+        &whitebox,
+        ScCallStep::new()
+            .from(SELLER_ADDRESS_EXPR)
+            .esdt_transfer(WINE_NFT_TOKEN_ID_EXPR, 2, "1"),
+        |sc| {
+            listing_id = sc.create_listing(
+                2,
+                BigUint::from(PRICE),
+                EgldOrEsdtTokenIdentifier::egld(),
+                3600,
+                OptionalValue::None,
+            );
+        },
+    );
+    world.whitebox_call(
+        &whitebox,
+        ScCallStep::new().from(BUYER_ADDRESS_EXPR).egld_value("200"),
+        |sc| sc.start_installment_purchase(listing_id, 86400),
+    );
+
+    world.whitebox_call(
+        &whitebox,
+        ScCallStep::new().from(BUYER_ADDRESS_EXPR).egld_value("500"),
+        |sc| {
+            let offer_id = sc.make_offer(1, managed_token_id!(WINE_NFT_TOKEN_ID), 1, 3600);
+            assert_eq!(offer_id, 1);
+        },
+    );
+    world.whitebox_query(&whitebox, |sc| {
+        assert!(!sc.installment_plans(listing_id).is_empty());
+    });
+}
@@ -0,0 +1,107 @@
+use multiversx_sc::imports::*;
+use wine_common::errors::*;
+use wine_roles::Role;
+
+// Every submission for a pair is scanned on aggregation, so the feeder set stays small
+pub const MAX_PRICE_FEEDERS: usize = 20;
+// Subscribers are pushed to one by one, so keep the list short enough for a single tx
+pub const MAX_PRICE_SUBSCRIBERS: usize = 10;
+
+// Who feeds prices, who receives the aggregates, and how fresh submissions must be
+#[multiversx_sc::module]
+pub trait FeedersModule: wine_roles::RolesModule {
+    #[endpoint(addPriceFeeder)]
+    fn add_price_feeder(&self, feeder: ManagedAddress) {
+        self.require_role(Role::Admin);
+        require!(self.price_feeders().insert(feeder.clone()), ERR_ALREADY_A_PRICE_FEEDER);
+        require!(self.price_feeders().len() <= MAX_PRICE_FEEDERS, ERR_TOO_MANY_PRICE_FEEDERS);
+
+        self.price_feeder_added_event(&feeder);
+    }
+
+    // Submissions already made stay until they go stale
+    #[endpoint(removePriceFeeder)]
+    fn remove_price_feeder(&self, feeder: ManagedAddress) {
+        self.require_role(Role::Admin);
+        require!(self.price_feeders().swap_remove(&feeder), ERR_NOT_A_PRICE_FEEDER);
+
+        self.price_feeder_removed_event(&feeder);
+    }
+
+    // Contracts that receive every new aggregate through `setExchangeRate`, e.g. the
+    // marketplace with this contract set as its rate oracle
+    #[endpoint(addPriceSubscriber)]
+    fn add_price_subscriber(&self, subscriber: ManagedAddress) {
+        self.require_role(Role::Admin);
+        require!(
+            self.blockchain().is_smart_contract(&subscriber),
+            ERR_SUBSCRIBER_MUST_BE_A_SMART_CONTRACT
+        );
+        self.price_subscribers().insert(subscriber);
+        require!(
+            self.price_subscribers().len() <= MAX_PRICE_SUBSCRIBERS,
+            ERR_TOO_MANY_PRICE_SUBSCRIBERS
+        );
+    }
+
+    #[endpoint(removePriceSubscriber)]
+    fn remove_price_subscriber(&self, subscriber: ManagedAddress) {
+        self.require_role(Role::Admin);
+        self.price_subscribers().swap_remove(&subscriber);
+    }
+
+    // Submissions older than `max_price_age` seconds are left out of the median, and the
+    // median needs at least `min_submissions` fresh ones
+    #[endpoint(setOracleConfig)]
+    fn set_oracle_config(&self, max_price_age: u64, min_submissions: u32) {
+        self.require_role(Role::Admin);
+        self.store_oracle_config(max_price_age, min_submissions);
+    }
+
+    // Internal helpers
+    fn store_oracle_config(&self, max_price_age: u64, min_submissions: u32) {
+        require!(max_price_age > 0, ERR_MAXIMUM_PRICE_AGE_MUST_BE_GREATER_THAN_0);
+        require!(
+            min_submissions > 0 && min_submissions as usize <= MAX_PRICE_FEEDERS,
+            ERR_INVALID_MINIMUM_SUBMISSIONS
+        );
+        self.max_price_age().set(max_price_age);
+        self.min_submissions().set(min_submissions);
+    }
+
+    // View functions
+    #[view(getPriceFeeders)]
+    fn get_price_feeders(&self) -> MultiValueEncoded<ManagedAddress> {
+        self.price_feeders().iter().collect()
+    }
+
+    #[view(getPriceSubscribers)]
+    fn get_price_subscribers(&self) -> MultiValueEncoded<ManagedAddress> {
+        self.price_subscribers().iter().collect()
+    }
+
+    #[view(getOracleConfig)]
+    fn get_oracle_config(&self) -> MultiValue2<u64, u32> {
+        (self.max_price_age().get(), self.min_submissions().get()).into()
+    }
+
+    // Storage mappers
+    #[storage_mapper("priceFeeders")]
+    fn price_feeders(&self) -> UnorderedSetMapper<ManagedAddress>;
+
+    #[storage_mapper("priceSubscribers")]
+    fn price_subscribers(&self) -> UnorderedSetMapper<ManagedAddress>;
+
+    #[storage_mapper("maxPriceAge")]
+    fn max_price_age(&self) -> SingleValueMapper<u64>;
+
+    #[storage_mapper("minSubmissions")]
+    fn min_submissions(&self) -> SingleValueMapper<u32>;
+
+    // Events
+    #[event("priceFeederAdded")]
+    fn price_feeder_added_event(&self, #[indexed] feeder: &ManagedAddress);
+
+    #[event("priceFeederRemoved")]
+    fn price_feeder_removed_event(&self, #[indexed] feeder: &ManagedAddress);
+}
@@ -0,0 +1,22 @@
+#![no_std]
+
+use multiversx_sc::imports::*;
+
+pub mod feeders;
+pub mod prices;
+
+pub use prices::{AggregatedPrice, PriceSubmission};
+
+// Price oracle for EGLD, stablecoins and the WINE token. Whitelisted feeders submit prices,
+// the median of the fresh submissions becomes the pair's price, and each new median is
+// pushed to subscribed contracts such as the marketplace's exchange rates.
+#[multiversx_sc::contract]
+pub trait WineOracle: feeders::FeedersModule + prices::PricesModule + wine_roles::RolesModule {
+    #[init]
+    fn init(&self, max_price_age: u64, min_submissions: u32) {
+        self.store_oracle_config(max_price_age, min_submissions);
+    }
+
+    #[upgrade]
+    fn upgrade(&self) {}
+}
@@ -0,0 +1,209 @@
+use multiversx_sc::derive_imports::*;
+use multiversx_sc::imports::*;
+use wine_common::errors::*;
+
+pub const PRICE_PUSH_GAS: u64 = 5_000_000;
+
+#[derive(TopEncode, TopDecode, NestedEncode, NestedDecode, TypeAbi, PartialEq, Debug)]
+pub struct PriceSubmission<M: ManagedTypeApi> {
+    pub price: BigUint<M>,
+    pub timestamp: u64,
+}
+
+// Median of the fresh submissions for a pair at `timestamp`
+#[derive(TopEncode, TopDecode, TypeAbi, PartialEq, Debug)]
+pub struct AggregatedPrice<M: ManagedTypeApi> {
+    pub price: BigUint<M>,
+    pub submissions: u32,
+    pub timestamp: u64,
+}
+
+// Prices are quoted like the marketplace's exchange rates: 1 unit of the base token is worth
+// price / 10^18 units of the quote token. Each feeder keeps one submission per pair; every
+// submission recomputes the median and, once enough feeders agree, pushes it to subscribers.
+#[multiversx_sc::module]
+pub trait PricesModule: wine_roles::RolesModule + crate::feeders::FeedersModule {
+    #[endpoint(submitPrice)]
+    fn submit_price(
+        &self,
+        base_token: EgldOrEsdtTokenIdentifier,
+        quote_token: EgldOrEsdtTokenIdentifier,
+        price: BigUint,
+    ) {
+        self.require_not_paused();
+        let feeder = self.blockchain().get_caller();
+        require!(self.price_feeders().contains(&feeder), ERR_NOT_A_PRICE_FEEDER);
+        require!(base_token != quote_token, ERR_TOKENS_MUST_DIFFER);
+        require!(price > 0, ERR_PRICE_MUST_BE_GREATER_THAN_0);
+
+        let timestamp = self.blockchain().get_block_timestamp();
+        self.price_submissions(&base_token, &quote_token).insert(
+            feeder.clone(),
+            PriceSubmission {
+                price: price.clone(),
+                timestamp,
+            },
+        );
+        self.price_submitted_event(&base_token, &quote_token, &feeder, &price);
+
+        let fresh_prices = self.fresh_prices(&base_token, &quote_token);
+        if fresh_prices.len() < self.min_submissions().get() as usize {
+            return;
+        }
+        let median = self.median(&fresh_prices);
+        self.aggregated_prices(&base_token, &quote_token).set(&AggregatedPrice {
+            price: median.clone(),
+            submissions: fresh_prices.len() as u32,
+            timestamp,
+        });
+        self.push_price(&base_token, &quote_token, &median);
+    }
+
+    // Internal helpers
+    // Submissions of current feeders within the maximum price age
+    fn fresh_prices(
+        &self,
+        base_token: &EgldOrEsdtTokenIdentifier,
+        quote_token: &EgldOrEsdtTokenIdentifier,
+    ) -> ManagedVec<BigUint> {
+        let now = self.blockchain().get_block_timestamp();
+        let max_price_age = self.max_price_age().get();
+        let mut prices = ManagedVec::new();
+        for (feeder, submission) in self.price_submissions(base_token, quote_token).iter() {
+            if self.price_feeders().contains(&feeder) && now <= submission.timestamp + max_price_age {
+                prices.push(submission.price);
+            }
+        }
+        prices
+    }
+
+    // Middle value, or the mean of the two middle values for an even count
+    fn median(&self, prices: &ManagedVec<BigUint>) -> BigUint {
+        let len = prices.len();
+        let upper = self.nth_smallest(prices, len / 2);
+        if len % 2 == 1 {
+            return upper;
+        }
+        (self.nth_smallest(prices, len / 2 - 1) + upper) / 2u32
+    }
+
+    // The few feeders make a quadratic rank count cheaper than sorting in storage
+    fn nth_smallest(&self, prices: &ManagedVec<BigUint>, n: usize) -> BigUint {
+        for candidate in prices.iter() {
+            let mut below = 0;
+            let mut at_most = 0;
+            for price in prices.iter() {
+                if *price < *candidate {
+                    below += 1;
+                }
+                if *price <= *candidate {
+                    at_most += 1;
+                }
+            }
+            if below <= n && n < at_most {
+                return candidate.clone_value();
+            }
+        }
+        sc_panic!(ERR_RANK_OUT_OF_RANGE)
+    }
+
+    // Fire-and-forget calls: a failing subscriber does not revert the submission
+    fn push_price(
+        &self,
+        base_token: &EgldOrEsdtTokenIdentifier,
+        quote_token: &EgldOrEsdtTokenIdentifier,
+        price: &BigUint,
+    ) {
+        for subscriber in self.price_subscribers().iter() {
+            self.tx()
+                .to(&subscriber)
+                .gas(PRICE_PUSH_GAS)
+                .raw_call("setExchangeRate")
+                .argument(base_token)
+                .argument(quote_token)
+                .argument(price)
+                .transfer_execute();
+        }
+
+        self.price_aggregated_event(base_token, quote_token, price);
+    }
+
+    // View functions
+    // Latest median, rejected once older than the maximum price age
+    #[view(getPrice)]
+    fn get_price(
+        &self,
+        base_token: EgldOrEsdtTokenIdentifier,
+        quote_token: EgldOrEsdtTokenIdentifier,
+    ) -> BigUint {
+        let aggregated_mapper = self.aggregated_prices(&base_token, &quote_token);
+        require!(!aggregated_mapper.is_empty(), ERR_NO_PRICE_FOR_PAIR);
+        let aggregated = aggregated_mapper.get();
+        require!(
+            self.blockchain().get_block_timestamp() <= aggregated.timestamp + self.max_price_age().get(),
+            ERR_PRICE_IS_STALE
+        );
+        aggregated.price
+    }
+
+    #[view(getAggregatedPrice)]
+    fn get_aggregated_price(
+        &self,
+        base_token: EgldOrEsdtTokenIdentifier,
+        quote_token: EgldOrEsdtTokenIdentifier,
+    ) -> OptionalValue<AggregatedPrice<Self::Api>> {
+        let aggregated_mapper = self.aggregated_prices(&base_token, &quote_token);
+        if aggregated_mapper.is_empty() {
+            OptionalValue::None
+        } else {
+            OptionalValue::Some(aggregated_mapper.get())
+        }
+    }
+
+    // Latest submission of every feeder that has priced the pair, stale ones included
+    #[view(getPriceSubmissions)]
+    fn get_price_submissions(
+        &self,
+        base_token: EgldOrEsdtTokenIdentifier,
+        quote_token: EgldOrEsdtTokenIdentifier,
+    ) -> MultiValueEncoded<MultiValue2<ManagedAddress, PriceSubmission<Self::Api>>> {
+        let mut result = MultiValueEncoded::new();
+        for (feeder, submission) in self.price_submissions(&base_token, &quote_token).iter() {
+            result.push((feeder, submission).into());
+        }
+        result
+    }
+
+    // Storage mappers
+    #[storage_mapper("priceSubmissions")]
+    fn price_submissions(
+        &self,
+        base_token: &EgldOrEsdtTokenIdentifier,
+        quote_token: &EgldOrEsdtTokenIdentifier,
+    ) -> MapMapper<ManagedAddress, PriceSubmission<Self::Api>>;
+
+    #[storage_mapper("aggregatedPrices")]
+    fn aggregated_prices(
+        &self,
+        base_token: &EgldOrEsdtTokenIdentifier,
+        quote_token: &EgldOrEsdtTokenIdentifier,
+    ) -> SingleValueMapper<AggregatedPrice<Self::Api>>;
+
+    // Events
+    #[event("priceSubmitted")]
+    fn price_submitted_event(
+        &self,
+        #[indexed] base_token: &EgldOrEsdtTokenIdentifier,
+        #[indexed] quote_token: &EgldOrEsdtTokenIdentifier,
+        #[indexed] feeder: &ManagedAddress,
+        price: &BigUint,
+    );
+
+    #[event("priceAggregated")]
+    fn price_aggregated_event(
+        &self,
+        #[indexed] base_token: &EgldOrEsdtTokenIdentifier,
+        #[indexed] quote_token: &EgldOrEsdtTokenIdentifier,
+        price: &BigUint,
+    );
+}
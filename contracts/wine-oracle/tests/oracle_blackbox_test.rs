@@ -0,0 +1,213 @@
+use multiversx_sc_scenario::api::StaticApi;
+use multiversx_sc_scenario::imports::*;
+use wine_common::errors::*;
+use wine_marketplace::admin::ProxyTrait as _;
+use wine_marketplace::exchange_rates::{ExchangeRate, ProxyTrait as _};
+use wine_marketplace::ProxyTrait as _;
+use wine_oracle::feeders::ProxyTrait as _;
+use wine_oracle::prices::ProxyTrait as _;
+use wine_oracle::ProxyTrait as _;
+use wine_oracle::AggregatedPrice;
+
+const ORACLE_PATH_EXPR: &str = "mxsc:output/wine-oracle.mxsc.json";
+const MARKETPLACE_PATH_EXPR: &str = "mxsc:../wine-marketplace/output/wine-marketplace.mxsc.json";
+const ORACLE_ADDRESS_EXPR: &str = "sc:wine-oracle";
+const MARKETPLACE_ADDRESS_EXPR: &str = "sc:wine-marketplace";
+const OWNER_ADDRESS_EXPR: &str = "address:owner";
+const FEEDER_A_ADDRESS_EXPR: &str = "address:feeder-a";
+const FEEDER_B_ADDRESS_EXPR: &str = "address:feeder-b";
+const FEEDER_C_ADDRESS_EXPR: &str = "address:feeder-c";
+
+const START_TIMESTAMP: u64 = 1_000;
+const MAX_PRICE_AGE: u64 = 600;
+const MIN_SUBMISSIONS: u32 = 2;
+
+type OracleContract = ContractInfo<wine_oracle::Proxy<StaticApi>>;
+type MarketplaceContract = ContractInfo<wine_marketplace::Proxy<StaticApi>>;
+
+fn user_error(message: &str) -> TxExpect {
+    TxExpect::user_error(format!("str:{message}"))
+}
+
+fn egld() -> EgldOrEsdtTokenIdentifier<StaticApi> {
+    EgldOrEsdtTokenIdentifier::egld()
+}
+
+fn usdc() -> EgldOrEsdtTokenIdentifier<StaticApi> {
+    EgldOrEsdtTokenIdentifier::esdt("USDC-abcdef")
+}
+
+struct OracleTestState {
+    world: ScenarioWorld,
+    oracle: OracleContract,
+    marketplace: MarketplaceContract,
+}
+
+impl OracleTestState {
+    // Three feeders, of which two fresh submissions make a price, next to a marketplace that
+    // takes this contract as its rate oracle
+    fn new() -> Self {
+        let mut world = ScenarioWorld::new();
+        world.register_contract(ORACLE_PATH_EXPR, wine_oracle::ContractBuilder);
+        world.register_contract(MARKETPLACE_PATH_EXPR, wine_marketplace::ContractBuilder);
+
+        world.set_state_step(
+            SetStateStep::new()
+                .block_timestamp(START_TIMESTAMP)
+                .put_account(OWNER_ADDRESS_EXPR, Account::new().nonce(1))
+                .put_account(FEEDER_A_ADDRESS_EXPR, Account::new().nonce(1))
+                .put_account(FEEDER_B_ADDRESS_EXPR, Account::new().nonce(1))
+                .put_account(FEEDER_C_ADDRESS_EXPR, Account::new().nonce(1))
+                .new_address(OWNER_ADDRESS_EXPR, 1, ORACLE_ADDRESS_EXPR)
+                .new_address(OWNER_ADDRESS_EXPR, 2, MARKETPLACE_ADDRESS_EXPR),
+        );
+
+        let mut oracle = OracleContract::new(ORACLE_ADDRESS_EXPR);
+        let oracle_code = world.code_expression(ORACLE_PATH_EXPR);
+        world.sc_deploy(
+            ScDeployStep::new()
+                .from(OWNER_ADDRESS_EXPR)
+                .code(oracle_code)
+                .call(oracle.init(MAX_PRICE_AGE, MIN_SUBMISSIONS)),
+        );
+        let mut marketplace = MarketplaceContract::new(MARKETPLACE_ADDRESS_EXPR);
+        let marketplace_code = world.code_expression(MARKETPLACE_PATH_EXPR);
+        let owner = AddressValue::from(OWNER_ADDRESS_EXPR).to_address();
+        let oracle_address = AddressValue::from(ORACLE_ADDRESS_EXPR).to_address();
+        world.sc_deploy(
+            ScDeployStep::new()
+                .from(OWNER_ADDRESS_EXPR)
+                .code(marketplace_code)
+                .call(marketplace.init(250u64, managed_address!(&owner))),
+        );
+        world
+            .sc_call(ScCallStep::new().from(OWNER_ADDRESS_EXPR).call(marketplace.add_supported_token(usdc())))
+            .sc_call(
+                ScCallStep::new()
+                    .from(OWNER_ADDRESS_EXPR)
+                    .call(marketplace.set_rate_oracle(managed_address!(&oracle_address))),
+            );
+
+        // Feeders are whitelisted by an admin
+        let feeder_a = AddressValue::from(FEEDER_A_ADDRESS_EXPR).to_address();
+        world.sc_call(
+            ScCallStep::new()
+                .from(FEEDER_A_ADDRESS_EXPR)
+                .call(oracle.add_price_feeder(managed_address!(&feeder_a)))
+                .expect(user_error(ERR_CALLER_DOES_NOT_HAVE_THE_REQUIRED_ROLE)),
+        );
+        for feeder in [FEEDER_A_ADDRESS_EXPR, FEEDER_B_ADDRESS_EXPR, FEEDER_C_ADDRESS_EXPR] {
+            let feeder = AddressValue::from(feeder).to_address();
+            world.sc_call(
+                ScCallStep::new()
+                    .from(OWNER_ADDRESS_EXPR)
+                    .call(oracle.add_price_feeder(managed_address!(&feeder))),
+            );
+        }
+
+        OracleTestState { world, oracle, marketplace }
+    }
+
+    fn set_block_timestamp(&mut self, timestamp: u64) {
+        self.world.set_state_step(SetStateStep::new().block_timestamp(timestamp));
+    }
+
+    fn submit_price(&mut self, feeder: &str, price: u64) {
+        self.world.sc_call(
+            ScCallStep::new()
+                .from(feeder)
+                .call(self.oracle.submit_price(egld(), usdc(), price)),
+        );
+    }
+
+    fn price(&mut self) -> BigUint<StaticApi> {
+        self.world.quick_query(self.oracle.get_price(egld(), usdc()))
+    }
+}
+
+#[test]
+fn median_of_the_fresh_submissions_becomes_the_price() {
+    let mut state = OracleTestState::new();
+
+    state.world.sc_call(
+        ScCallStep::new()
+            .from(OWNER_ADDRESS_EXPR)
+            .call(state.oracle.submit_price(egld(), usdc(), 100u64))
+            .expect(user_error(ERR_NOT_A_PRICE_FEEDER)),
+    );
+    state.submit_price(FEEDER_A_ADDRESS_EXPR, 100);
+    state.world.sc_query(
+        ScQueryStep::new()
+            .call(state.oracle.get_price(egld(), usdc()))
+            .expect(user_error(ERR_NO_PRICE_FOR_PAIR)),
+    );
+
+    // Two submissions average their middle values, three take the middle one
+    state.submit_price(FEEDER_B_ADDRESS_EXPR, 300);
+    assert_eq!(state.price(), 200u64);
+    state.submit_price(FEEDER_C_ADDRESS_EXPR, 250);
+    assert_eq!(state.price(), 250u64);
+
+    // A feeder's new submission replaces the old one
+    state.submit_price(FEEDER_A_ADDRESS_EXPR, 280);
+    let aggregated: OptionalValue<AggregatedPrice<StaticApi>> =
+        state.world.quick_query(state.oracle.get_aggregated_price(egld(), usdc()));
+    let aggregated = aggregated.into_option().unwrap();
+    assert_eq!(aggregated.price, 280u64);
+    assert_eq!(aggregated.submissions, 3);
+}
+
+#[test]
+fn stale_and_removed_submissions_are_left_out() {
+    let mut state = OracleTestState::new();
+    state.submit_price(FEEDER_A_ADDRESS_EXPR, 100);
+    state.submit_price(FEEDER_B_ADDRESS_EXPR, 300);
+
+    // Only C's submission is fresh, which is not enough for a new price
+    state.set_block_timestamp(START_TIMESTAMP + MAX_PRICE_AGE + 1);
+    state.submit_price(FEEDER_C_ADDRESS_EXPR, 500);
+    state.world.sc_query(
+        ScQueryStep::new()
+            .call(state.oracle.get_price(egld(), usdc()))
+            .expect(user_error(ERR_PRICE_IS_STALE)),
+    );
+    state.submit_price(FEEDER_B_ADDRESS_EXPR, 400);
+    assert_eq!(state.price(), 450u64);
+
+    let feeder_c = AddressValue::from(FEEDER_C_ADDRESS_EXPR).to_address();
+    state.world.sc_call(
+        ScCallStep::new()
+            .from(OWNER_ADDRESS_EXPR)
+            .call(state.oracle.remove_price_feeder(managed_address!(&feeder_c))),
+    );
+    state.submit_price(FEEDER_A_ADDRESS_EXPR, 420);
+    assert_eq!(state.price(), 410u64);
+}
+
+#[test]
+fn new_prices_are_pushed_to_the_marketplace() {
+    let mut state = OracleTestState::new();
+
+    let owner = AddressValue::from(OWNER_ADDRESS_EXPR).to_address();
+    let marketplace_address = AddressValue::from(MARKETPLACE_ADDRESS_EXPR).to_address();
+    state.world.sc_call(
+        ScCallStep::new()
+            .from(OWNER_ADDRESS_EXPR)
+            .call(state.oracle.add_price_subscriber(managed_address!(&owner)))
+            .expect(user_error(ERR_SUBSCRIBER_MUST_BE_A_SMART_CONTRACT)),
+    );
+    state.world.sc_call(
+        ScCallStep::new()
+            .from(OWNER_ADDRESS_EXPR)
+            .call(state.oracle.add_price_subscriber(managed_address!(&marketplace_address))),
+    );
+
+    state.submit_price(FEEDER_A_ADDRESS_EXPR, 100);
+    state.submit_price(FEEDER_B_ADDRESS_EXPR, 300);
+
+    let rate: OptionalValue<ExchangeRate<StaticApi>> =
+        state.world.quick_query(state.marketplace.get_exchange_rate(egld(), usdc()));
+    let rate = rate.into_option().unwrap();
+    assert_eq!(rate.rate, 200u64);
+    assert_eq!(rate.updated_timestamp, START_TIMESTAMP);
+}
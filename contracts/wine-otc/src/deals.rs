@@ -0,0 +1,203 @@
+use multiversx_sc::derive_imports::*;
+use multiversx_sc::imports::*;
+use wine_common::errors::*;
+
+pub const MAX_DEAL_TOKENS: usize = 20;
+
+#[derive(TopEncode, TopDecode, NestedEncode, NestedDecode, TypeAbi, PartialEq, Debug, Clone, Copy)]
+pub enum DealStatus {
+    // Awaiting funding or settlement
+    Open,
+    // Both legs swapped
+    Settled,
+    // Called off before the payment was funded; the tokens are refunded
+    Cancelled,
+}
+
+#[derive(TopEncode, TopDecode, TypeAbi, PartialEq, Debug)]
+pub struct OtcDeal<M: ManagedTypeApi> {
+    // Seller, whose leg is the tokens
+    pub party_a: ManagedAddress<M>,
+    // Buyer, whose leg is the payment
+    pub party_b: ManagedAddress<M>,
+    // Escrowed when the deal is proposed
+    pub tokens: ManagedVec<M, EsdtTokenPayment<M>>,
+    pub payment_token: EgldOrEsdtTokenIdentifier<M>,
+    pub payment_amount: BigUint<M>,
+    pub payment_funded: bool,
+    pub status: DealStatus,
+}
+
+// Escrow for deals negotiated off the marketplace. Party A proposes the exact terms and
+// escrows the wine NFTs; party B escrows the agreed payment. Until the payment is in, either
+// party can call the deal off and party A gets the NFTs back; once it is, either party
+// settles and the legs swap in one transaction.
+#[multiversx_sc::module]
+pub trait DealsModule: wine_roles::RolesModule {
+    // Propose a deal to `party_b`. The tokens sent along are party A's leg, funded at once.
+    #[payable("*")]
+    #[endpoint(proposeDeal)]
+    fn propose_deal(
+        &self,
+        party_b: ManagedAddress,
+        payment_token: EgldOrEsdtTokenIdentifier,
+        payment_amount: BigUint,
+    ) -> u32 {
+        self.require_not_paused();
+        let party_a = self.blockchain().get_caller();
+        require!(party_a != party_b, ERR_CANNOT_DEAL_WITH_YOURSELF);
+        require!(!party_b.is_zero(), ERR_INVALID_COUNTERPARTY);
+        require!(payment_token.is_valid(), ERR_INVALID_PAYMENT_TOKEN);
+        require!(payment_amount > 0, ERR_PAYMENT_AMOUNT_MUST_BE_GREATER_THAN_0);
+        let tokens = self.call_value().all_esdt_transfers().clone_value();
+        require!(!tokens.is_empty(), ERR_DEAL_MUST_INCLUDE_TOKENS);
+        require!(tokens.len() <= MAX_DEAL_TOKENS, ERR_TOO_MANY_TOKENS_IN_ONE_DEAL);
+
+        let deal_id = self.deal_count().get() + 1;
+        self.deal_count().set(deal_id);
+        self.deals(deal_id).set(&OtcDeal {
+            party_a: party_a.clone(),
+            party_b: party_b.clone(),
+            tokens,
+            payment_token: payment_token.clone(),
+            payment_amount: payment_amount.clone(),
+            payment_funded: false,
+            status: DealStatus::Open,
+        });
+        self.open_deals(&party_a).insert(deal_id);
+        self.open_deals(&party_b).insert(deal_id);
+
+        self.deal_proposed_event(deal_id, &party_a, &party_b, &payment_token, &payment_amount);
+
+        deal_id
+    }
+
+    // Party B escrows exactly the agreed payment
+    #[payable("*")]
+    #[endpoint(fundDeal)]
+    fn fund_deal(&self, deal_id: u32) {
+        self.require_not_paused();
+        let mut deal = self.require_open_deal(deal_id);
+        require!(
+            self.blockchain().get_caller() == deal.party_b,
+            ERR_ONLY_THE_COUNTERPARTY_CAN_FUND_THE_PAYMENT
+        );
+        require!(!deal.payment_funded, ERR_PAYMENT_ALREADY_FUNDED);
+        let payment = self.call_value().egld_or_single_esdt();
+        require!(payment.token_identifier == deal.payment_token, ERR_INVALID_PAYMENT_TOKEN);
+        require!(payment.amount == deal.payment_amount, ERR_INCORRECT_PAYMENT_AMOUNT);
+
+        deal.payment_funded = true;
+        self.deals(deal_id).set(&deal);
+
+        self.deal_funded_event(deal_id, &deal.party_b);
+    }
+
+    // Either party can call the deal off until the payment is funded
+    #[endpoint(cancelDeal)]
+    fn cancel_deal(&self, deal_id: u32) {
+        let mut deal = self.require_open_deal(deal_id);
+        let caller = self.blockchain().get_caller();
+        require!(
+            caller == deal.party_a || caller == deal.party_b,
+            ERR_ONLY_A_PARTY_TO_THE_DEAL_CAN_CANCEL_IT
+        );
+        require!(!deal.payment_funded, ERR_BOTH_LEGS_ARE_FUNDED_THE_DEAL_CAN_ONLY_BE_SETTLED);
+
+        self.send().direct_multi(&deal.party_a, &deal.tokens);
+        deal.status = DealStatus::Cancelled;
+        self.close_deal(deal_id, &deal);
+
+        self.deal_cancelled_event(deal_id, &caller);
+    }
+
+    // Swap the legs: the tokens go to party B and the payment to party A
+    #[endpoint(settleDeal)]
+    fn settle_deal(&self, deal_id: u32) {
+        self.require_not_paused();
+        let mut deal = self.require_open_deal(deal_id);
+        let caller = self.blockchain().get_caller();
+        require!(
+            caller == deal.party_a || caller == deal.party_b,
+            ERR_ONLY_A_PARTY_TO_THE_DEAL_CAN_SETTLE_IT
+        );
+        require!(deal.payment_funded, ERR_DEAL_IS_NOT_FULLY_FUNDED);
+
+        self.send().direct_multi(&deal.party_b, &deal.tokens);
+        self.send().direct(&deal.party_a, &deal.payment_token, 0, &deal.payment_amount);
+        deal.status = DealStatus::Settled;
+        self.close_deal(deal_id, &deal);
+
+        self.deal_settled_event(deal_id, &deal.party_a, &deal.party_b);
+    }
+
+    // Internal helpers
+    fn require_deal(&self, deal_id: u32) -> OtcDeal<Self::Api> {
+        require!(!self.deals(deal_id).is_empty(), ERR_DEAL_DOES_NOT_EXIST);
+        self.deals(deal_id).get()
+    }
+
+    fn require_open_deal(&self, deal_id: u32) -> OtcDeal<Self::Api> {
+        let deal = self.require_deal(deal_id);
+        require!(deal.status == DealStatus::Open, ERR_DEAL_IS_NOT_OPEN);
+        deal
+    }
+
+    fn close_deal(&self, deal_id: u32, deal: &OtcDeal<Self::Api>) {
+        self.deals(deal_id).set(deal);
+        self.open_deals(&deal.party_a).swap_remove(&deal_id);
+        self.open_deals(&deal.party_b).swap_remove(&deal_id);
+    }
+
+    // View functions
+    #[view(getDeal)]
+    fn get_deal(&self, deal_id: u32) -> OtcDeal<Self::Api> {
+        self.require_deal(deal_id)
+    }
+
+    #[view(getDealCount)]
+    fn get_deal_count(&self) -> u32 {
+        self.deal_count().get()
+    }
+
+    // Open deals in which the address is either party
+    #[view(getOpenDeals)]
+    fn get_open_deals(&self, address: ManagedAddress) -> MultiValueEncoded<u32> {
+        self.open_deals(&address).iter().collect()
+    }
+
+    // Storage mappers
+    #[storage_mapper("dealCount")]
+    fn deal_count(&self) -> SingleValueMapper<u32>;
+
+    #[storage_mapper("deals")]
+    fn deals(&self, deal_id: u32) -> SingleValueMapper<OtcDeal<Self::Api>>;
+
+    #[storage_mapper("openDeals")]
+    fn open_deals(&self, address: &ManagedAddress) -> UnorderedSetMapper<u32>;
+
+    // Events
+    #[event("dealProposed")]
+    fn deal_proposed_event(
+        &self,
+        #[indexed] deal_id: u32,
+        #[indexed] party_a: &ManagedAddress,
+        #[indexed] party_b: &ManagedAddress,
+        #[indexed] payment_token: &EgldOrEsdtTokenIdentifier,
+        payment_amount: &BigUint,
+    );
+
+    #[event("dealFunded")]
+    fn deal_funded_event(&self, #[indexed] deal_id: u32, #[indexed] party_b: &ManagedAddress);
+
+    #[event("dealCancelled")]
+    fn deal_cancelled_event(&self, #[indexed] deal_id: u32, #[indexed] cancelled_by: &ManagedAddress);
+
+    #[event("dealSettled")]
+    fn deal_settled_event(
+        &self,
+        #[indexed] deal_id: u32,
+        #[indexed] party_a: &ManagedAddress,
+        #[indexed] party_b: &ManagedAddress,
+    );
+}
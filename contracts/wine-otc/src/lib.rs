@@ -0,0 +1,18 @@
+#![no_std]
+
+use multiversx_sc::imports::*;
+
+pub mod deals;
+
+pub use deals::{DealStatus, OtcDeal};
+
+// Trustless settlement for wine deals negotiated privately. Each deal escrows the seller's
+// NFTs and the buyer's payment and swaps them atomically once both are in.
+#[multiversx_sc::contract]
+pub trait WineOtc: deals::DealsModule + wine_roles::RolesModule {
+    #[init]
+    fn init(&self) {}
+
+    #[upgrade]
+    fn upgrade(&self) {}
+}
@@ -0,0 +1,197 @@
+use multiversx_sc_scenario::api::StaticApi;
+use multiversx_sc_scenario::imports::*;
+use wine_common::errors::*;
+use wine_otc::deals::ProxyTrait as _;
+use wine_otc::ProxyTrait as _;
+use wine_otc::{DealStatus, OtcDeal};
+use wine_roles::ProxyTrait as _;
+
+const OTC_PATH_EXPR: &str = "mxsc:output/wine-otc.mxsc.json";
+const OTC_ADDRESS_EXPR: &str = "sc:wine-otc";
+const OWNER_ADDRESS_EXPR: &str = "address:owner";
+const SELLER_ADDRESS_EXPR: &str = "address:seller";
+const BUYER_ADDRESS_EXPR: &str = "address:buyer";
+const STRANGER_ADDRESS_EXPR: &str = "address:stranger";
+
+const WINE_NFT_TOKEN_ID_EXPR: &str = "str:WINE-abcdef";
+
+const PAYMENT_AMOUNT: u64 = 500;
+
+type OtcContract = ContractInfo<wine_otc::Proxy<StaticApi>>;
+
+fn user_error(message: &str) -> TxExpect {
+    TxExpect::user_error(format!("str:{message}"))
+}
+
+struct OtcTestState {
+    world: ScenarioWorld,
+    otc: OtcContract,
+}
+
+impl OtcTestState {
+    // The seller holds two bottles of wine NFT 1 and one of NFT 2, the buyer 1000 EGLD
+    fn new() -> Self {
+        let mut world = ScenarioWorld::new();
+        world.register_contract(OTC_PATH_EXPR, wine_otc::ContractBuilder);
+
+        world.set_state_step(
+            SetStateStep::new()
+                .put_account(OWNER_ADDRESS_EXPR, Account::new().nonce(1))
+                .put_account(
+                    SELLER_ADDRESS_EXPR,
+                    Account::new()
+                        .nonce(1)
+                        .esdt_nft_balance(WINE_NFT_TOKEN_ID_EXPR, 1, "2", Option::<&str>::None)
+                        .esdt_nft_balance(WINE_NFT_TOKEN_ID_EXPR, 2, "1", Option::<&str>::None),
+                )
+                .put_account(BUYER_ADDRESS_EXPR, Account::new().nonce(1).balance("1000"))
+                .put_account(STRANGER_ADDRESS_EXPR, Account::new().nonce(1).balance("1000"))
+                .new_address(OWNER_ADDRESS_EXPR, 1, OTC_ADDRESS_EXPR),
+        );
+
+        let mut otc = OtcContract::new(OTC_ADDRESS_EXPR);
+        let otc_code = world.code_expression(OTC_PATH_EXPR);
+        world.sc_deploy(
+            ScDeployStep::new()
+                .from(OWNER_ADDRESS_EXPR)
+                .code(otc_code)
+                .call(otc.init()),
+        );
+
+        OtcTestState { world, otc }
+    }
+
+    // Both of the seller's bottles for 500 EGLD from the buyer
+    fn propose_deal(&mut self) -> TypedScCall<u32> {
+        let buyer = AddressValue::from(BUYER_ADDRESS_EXPR).to_address();
+        ScCallStep::new()
+            .from(SELLER_ADDRESS_EXPR)
+            .esdt_transfer(WINE_NFT_TOKEN_ID_EXPR, 1, "2")
+            .esdt_transfer(WINE_NFT_TOKEN_ID_EXPR, 2, "1")
+            .call(self.otc.propose_deal(
+                managed_address!(&buyer),
+                EgldOrEsdtTokenIdentifier::egld(),
+                PAYMENT_AMOUNT,
+            ))
+    }
+
+    fn fund_deal(&mut self, funder: &str, amount: u64) -> TypedScCall<()> {
+        ScCallStep::new()
+            .from(funder)
+            .egld_value(amount)
+            .call(self.otc.fund_deal(1u32))
+    }
+
+    fn deal(&mut self) -> OtcDeal<StaticApi> {
+        self.world.quick_query(self.otc.get_deal(1u32))
+    }
+}
+
+#[test]
+fn funded_deal_swaps_the_bottles_for_the_payment() {
+    let mut state = OtcTestState::new();
+    let step = state.propose_deal();
+    state.world.sc_call(step.expect_value(1u32));
+
+    let step = state.fund_deal(STRANGER_ADDRESS_EXPR, PAYMENT_AMOUNT);
+    state.world.sc_call(step.expect(user_error(ERR_ONLY_THE_COUNTERPARTY_CAN_FUND_THE_PAYMENT)));
+    let step = state.fund_deal(BUYER_ADDRESS_EXPR, PAYMENT_AMOUNT - 1);
+    state.world.sc_call(step.expect(user_error(ERR_INCORRECT_PAYMENT_AMOUNT)));
+    state.world.sc_call(
+        ScCallStep::new()
+            .from(SELLER_ADDRESS_EXPR)
+            .call(state.otc.settle_deal(1u32))
+            .expect(user_error(ERR_DEAL_IS_NOT_FULLY_FUNDED)),
+    );
+    let step = state.fund_deal(BUYER_ADDRESS_EXPR, PAYMENT_AMOUNT);
+    state.world.sc_call(step);
+
+    // Once both legs are in, the deal can only go through
+    state.world.sc_call(
+        ScCallStep::new()
+            .from(SELLER_ADDRESS_EXPR)
+            .call(state.otc.cancel_deal(1u32))
+            .expect(user_error(ERR_BOTH_LEGS_ARE_FUNDED_THE_DEAL_CAN_ONLY_BE_SETTLED)),
+    );
+    state.world.sc_call(
+        ScCallStep::new()
+            .from(STRANGER_ADDRESS_EXPR)
+            .call(state.otc.settle_deal(1u32))
+            .expect(user_error(ERR_ONLY_A_PARTY_TO_THE_DEAL_CAN_SETTLE_IT)),
+    );
+    state.world.sc_call(
+        ScCallStep::new()
+            .from(BUYER_ADDRESS_EXPR)
+            .call(state.otc.settle_deal(1u32)),
+    );
+
+    assert_eq!(state.deal().status, DealStatus::Settled);
+    let seller = AddressValue::from(SELLER_ADDRESS_EXPR).to_address();
+    let open_deals: MultiValueVec<u32> = state.world.quick_query(state.otc.get_open_deals(managed_address!(&seller)));
+    assert!(open_deals.into_vec().is_empty());
+    state.world.check_state_step(
+        CheckStateStep::new()
+            .put_account(SELLER_ADDRESS_EXPR, CheckAccount::new().balance("500"))
+            .put_account(
+                BUYER_ADDRESS_EXPR,
+                CheckAccount::new()
+                    .balance("500")
+                    .esdt_nft_balance_and_attributes(WINE_NFT_TOKEN_ID_EXPR, 1, "2", Some(""))
+                    .esdt_nft_balance_and_attributes(WINE_NFT_TOKEN_ID_EXPR, 2, "1", Some("")),
+            )
+            .put_account(OTC_ADDRESS_EXPR, CheckAccount::new().balance("0")),
+    );
+}
+
+#[test]
+fn unfunded_deal_can_be_called_off_by_either_party() {
+    let mut state = OtcTestState::new();
+    let step = state.propose_deal();
+    state.world.sc_call(step);
+
+    let buyer = AddressValue::from(BUYER_ADDRESS_EXPR).to_address();
+    let open_deals: MultiValueVec<u32> = state.world.quick_query(state.otc.get_open_deals(managed_address!(&buyer)));
+    assert_eq!(open_deals.into_vec(), vec![1]);
+    state.world.sc_call(
+        ScCallStep::new()
+            .from(STRANGER_ADDRESS_EXPR)
+            .call(state.otc.cancel_deal(1u32))
+            .expect(user_error(ERR_ONLY_A_PARTY_TO_THE_DEAL_CAN_CANCEL_IT)),
+    );
+    state.world.sc_call(
+        ScCallStep::new()
+            .from(BUYER_ADDRESS_EXPR)
+            .call(state.otc.cancel_deal(1u32)),
+    );
+
+    assert_eq!(state.deal().status, DealStatus::Cancelled);
+    let step = state.fund_deal(BUYER_ADDRESS_EXPR, PAYMENT_AMOUNT);
+    state.world.sc_call(step.expect(user_error(ERR_DEAL_IS_NOT_OPEN)));
+    state.world.check_state_step(
+        CheckStateStep::new().put_account(
+            SELLER_ADDRESS_EXPR,
+            CheckAccount::new()
+                .esdt_nft_balance_and_attributes(WINE_NFT_TOKEN_ID_EXPR, 1, "2", Some(""))
+                .esdt_nft_balance_and_attributes(WINE_NFT_TOKEN_ID_EXPR, 2, "1", Some("")),
+        ),
+    );
+}
+
+#[test]
+fn no_deals_are_proposed_while_paused() {
+    let mut state = OtcTestState::new();
+
+    state.world.sc_call(
+        ScCallStep::new()
+            .from(SELLER_ADDRESS_EXPR)
+            .call(state.otc.pause())
+            .expect(user_error(ERR_CALLER_DOES_NOT_HAVE_THE_REQUIRED_ROLE)),
+    );
+    state.world.sc_call(ScCallStep::new().from(OWNER_ADDRESS_EXPR).call(state.otc.pause()));
+    let step = state.propose_deal();
+    state.world.sc_call(step.expect(user_error(ERR_CONTRACT_IS_PAUSED)));
+
+    state.world.sc_call(ScCallStep::new().from(OWNER_ADDRESS_EXPR).call(state.otc.unpause()));
+    let step = state.propose_deal();
+    state.world.sc_call(step.expect_value(1u32));
+}
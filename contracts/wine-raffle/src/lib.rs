@@ -0,0 +1,26 @@
+#![no_std]
+
+use multiversx_sc::imports::*;
+use wine_common::errors::*;
+
+pub mod raffles;
+
+pub use raffles::{Raffle, RaffleStatus, TicketPurchase};
+
+// Raffles for rare bottles. Sellers escrow a wine NFT and sell a capped number of tickets;
+// a sold-out raffle draws its winner from block randomness mixed with revealed entropy,
+// and raffles that do not sell out refund their tickets.
+#[multiversx_sc::contract]
+pub trait WineRaffle:
+    raffles::RafflesModule + wine_randomness::RandomnessModule + wine_roles::RolesModule
+{
+    #[init]
+    fn init(&self, wine_collection: TokenIdentifier, raffle_fee_percent: u64) {
+        require!(wine_collection.is_valid_esdt_identifier(), ERR_INVALID_WINE_COLLECTION);
+        self.wine_collection().set(&wine_collection);
+        self.store_raffle_fee_percent(raffle_fee_percent);
+    }
+
+    #[upgrade]
+    fn upgrade(&self) {}
+}
@@ -0,0 +1,352 @@
+use multiversx_sc::derive_imports::*;
+use multiversx_sc::imports::*;
+use wine_common::errors::*;
+use wine_roles::Role;
+
+// Keeps a raffle's ticket purchases searchable within a single draw
+pub const MAX_TICKETS_PER_RAFFLE: u32 = 10_000;
+pub const MIN_RAFFLE_DURATION: u64 = 3_600; // 1 hour
+pub const MAX_RAFFLE_DURATION: u64 = 2_592_000; // 30 days
+
+#[derive(TopEncode, TopDecode, NestedEncode, NestedDecode, TypeAbi, PartialEq, Debug, Clone, Copy)]
+pub enum RaffleStatus {
+    Open,
+    // Sold out and drawn; the winner got the NFT
+    Drawn,
+    // Not sold out by the deadline, or withdrawn before any sale; tickets are refundable
+    Cancelled,
+}
+
+#[derive(TopEncode, TopDecode, TypeAbi, PartialEq, Debug)]
+pub struct Raffle<M: ManagedTypeApi> {
+    pub seller: ManagedAddress<M>,
+    pub nft_nonce: u64,
+    pub payment_token: EgldOrEsdtTokenIdentifier<M>,
+    pub ticket_price: BigUint<M>,
+    pub max_tickets: u32,
+    pub tickets_sold: u32,
+    pub deadline: u64,
+    pub status: RaffleStatus,
+    pub winner: Option<ManagedAddress<M>>,
+}
+
+// Tickets [first_ticket, first_ticket + count) bought in one call
+#[derive(TopEncode, TopDecode, NestedEncode, NestedDecode, TypeAbi, PartialEq, Debug)]
+pub struct TicketPurchase<M: ManagedTypeApi> {
+    pub buyer: ManagedAddress<M>,
+    pub first_ticket: u32,
+    pub count: u32,
+}
+
+// A seller escrows one wine NFT and sells up to `max_tickets` tickets until the deadline.
+// A sold-out raffle is drawn after the deadline: the winning ticket gets the NFT and the
+// seller the proceeds minus the raffle fee. Otherwise the NFT goes back to the seller and
+// ticket holders claim refunds. Ticket holders can commit and reveal entropy for the draw
+// through the randomness endpoints, in the raffle domain with the raffle id as context.
+#[multiversx_sc::module]
+pub trait RafflesModule: wine_roles::RolesModule + wine_randomness::RandomnessModule {
+    #[endpoint(addRafflePaymentToken)]
+    fn add_raffle_payment_token(&self, token: EgldOrEsdtTokenIdentifier) {
+        self.require_role(Role::Admin);
+        require!(token.is_valid(), ERR_INVALID_PAYMENT_TOKEN);
+        require!(self.payment_tokens().insert(token), ERR_PAYMENT_TOKEN_ALREADY_SUPPORTED);
+    }
+
+    #[endpoint(removeRafflePaymentToken)]
+    fn remove_raffle_payment_token(&self, token: EgldOrEsdtTokenIdentifier) {
+        self.require_role(Role::Admin);
+        require!(self.payment_tokens().swap_remove(&token), ERR_PAYMENT_TOKEN_NOT_SUPPORTED);
+    }
+
+    // Basis points of the proceeds kept on a drawn raffle
+    #[endpoint(setRaffleFeePercent)]
+    fn set_raffle_fee_percent(&self, raffle_fee_percent: u64) {
+        self.require_role(Role::FeeManager);
+        self.store_raffle_fee_percent(raffle_fee_percent);
+    }
+
+    #[payable("*")]
+    #[endpoint(createRaffle)]
+    fn create_raffle(
+        &self,
+        payment_token: EgldOrEsdtTokenIdentifier,
+        ticket_price: BigUint,
+        max_tickets: u32,
+        duration_seconds: u64,
+    ) -> u32 {
+        self.require_not_paused();
+        require!(self.payment_tokens().contains(&payment_token), ERR_PAYMENT_TOKEN_NOT_SUPPORTED);
+        require!(ticket_price > 0, ERR_TICKET_PRICE_MUST_BE_GREATER_THAN_0);
+        require!(
+            max_tickets > 1 && max_tickets <= MAX_TICKETS_PER_RAFFLE,
+            ERR_INVALID_TICKET_CAP
+        );
+        require!(
+            (MIN_RAFFLE_DURATION..=MAX_RAFFLE_DURATION).contains(&duration_seconds),
+            ERR_INVALID_RAFFLE_DURATION
+        );
+        let payment = self.call_value().single_esdt();
+        require!(payment.token_identifier == self.wine_collection().get(), ERR_INVALID_WINE_TOKEN);
+        require!(payment.amount == 1u32, ERR_MUST_SEND_EXACTLY_1_NFT);
+
+        let seller = self.blockchain().get_caller();
+        let raffle_id = self.raffle_count().get() + 1;
+        self.raffle_count().set(raffle_id);
+        let deadline = self.blockchain().get_block_timestamp() + duration_seconds;
+        self.raffles(raffle_id).set(&Raffle {
+            seller: seller.clone(),
+            nft_nonce: payment.token_nonce,
+            payment_token,
+            ticket_price,
+            max_tickets,
+            tickets_sold: 0,
+            deadline,
+            status: RaffleStatus::Open,
+            winner: None,
+        });
+
+        self.raffle_created_event(raffle_id, &seller, payment.token_nonce, max_tickets, deadline);
+
+        raffle_id
+    }
+
+    // Pay exactly `count` times the ticket price
+    #[payable("*")]
+    #[endpoint(buyTickets)]
+    fn buy_tickets(&self, raffle_id: u32, count: u32) {
+        self.require_not_paused();
+        let mut raffle = self.require_raffle(raffle_id);
+        require!(raffle.status == RaffleStatus::Open, ERR_RAFFLE_IS_NOT_OPEN);
+        require!(
+            self.blockchain().get_block_timestamp() <= raffle.deadline,
+            ERR_RAFFLE_HAS_ENDED
+        );
+        require!(count > 0, ERR_COUNT_MUST_BE_GREATER_THAN_0);
+        require!(
+            count <= raffle.max_tickets - raffle.tickets_sold,
+            ERR_NOT_ENOUGH_TICKETS_LEFT
+        );
+        let buyer = self.blockchain().get_caller();
+        require!(buyer != raffle.seller, ERR_CANNOT_ENTER_YOUR_OWN_RAFFLE);
+
+        let payment = self.call_value().egld_or_single_esdt();
+        require!(payment.token_identifier == raffle.payment_token, ERR_INVALID_PAYMENT_TOKEN);
+        require!(
+            payment.amount == &raffle.ticket_price * count,
+            ERR_PAYMENT_MUST_MATCH_THE_TICKET_PRICE
+        );
+
+        self.ticket_purchases(raffle_id).push(&TicketPurchase {
+            buyer: buyer.clone(),
+            first_ticket: raffle.tickets_sold,
+            count,
+        });
+        self.tickets(raffle_id, &buyer).update(|tickets| *tickets += count);
+        raffle.tickets_sold += count;
+        self.raffles(raffle_id).set(&raffle);
+
+        self.tickets_bought_event(raffle_id, &buyer, count);
+    }
+
+    // Withdraw a raffle before any ticket is sold
+    #[endpoint(cancelRaffle)]
+    fn cancel_raffle(&self, raffle_id: u32) {
+        let mut raffle = self.require_raffle(raffle_id);
+        require!(self.blockchain().get_caller() == raffle.seller, ERR_ONLY_SELLER_CAN_CANCEL);
+        require!(raffle.status == RaffleStatus::Open, ERR_RAFFLE_IS_NOT_OPEN);
+        require!(raffle.tickets_sold == 0, ERR_TICKETS_HAVE_BEEN_SOLD);
+
+        self.cancel(raffle_id, &mut raffle);
+    }
+
+    // Anyone can settle a raffle once its deadline has passed
+    #[endpoint(drawRaffle)]
+    fn draw_raffle(&self, raffle_id: u32) {
+        let mut raffle = self.require_raffle(raffle_id);
+        require!(raffle.status == RaffleStatus::Open, ERR_RAFFLE_IS_NOT_OPEN);
+        require!(
+            self.blockchain().get_block_timestamp() > raffle.deadline,
+            ERR_RAFFLE_HAS_NOT_ENDED_YET
+        );
+
+        if raffle.tickets_sold < raffle.max_tickets {
+            self.cancel(raffle_id, &mut raffle);
+            return;
+        }
+
+        let domain = ManagedBuffer::from(wine_randomness::RAFFLE_DOMAIN);
+        let winning_ticket =
+            self.draw_random_u64_in_range(&domain, raffle_id as u64, 0, raffle.tickets_sold as u64);
+        let winner = self.ticket_owner(raffle_id, winning_ticket as u32);
+        raffle.status = RaffleStatus::Drawn;
+        raffle.winner = Some(winner.clone());
+        self.raffles(raffle_id).set(&raffle);
+
+        self.send().direct_esdt(
+            &winner,
+            &self.wine_collection().get(),
+            raffle.nft_nonce,
+            &BigUint::from(1u32),
+        );
+        let proceeds = &raffle.ticket_price * raffle.tickets_sold;
+        let fee = &proceeds * self.raffle_fee_percent().get() / 10_000u64;
+        let seller_amount = &proceeds - &fee;
+        self.send().direct(&raffle.seller, &raffle.payment_token, 0, &seller_amount);
+        if fee > 0 {
+            let owner = self.blockchain().get_owner_address();
+            self.send().direct(&owner, &raffle.payment_token, 0, &fee);
+        }
+
+        self.raffle_drawn_event(raffle_id, &winner, winning_ticket as u32, &seller_amount, &fee);
+    }
+
+    #[endpoint(claimRaffleRefund)]
+    fn claim_raffle_refund(&self, raffle_id: u32) -> BigUint {
+        let raffle = self.require_raffle(raffle_id);
+        require!(raffle.status == RaffleStatus::Cancelled, ERR_RAFFLE_WAS_NOT_CANCELLED);
+        let buyer = self.blockchain().get_caller();
+        let tickets = self.tickets(raffle_id, &buyer).take();
+        require!(tickets > 0, ERR_NO_TICKETS_TO_REFUND);
+
+        let refund = &raffle.ticket_price * tickets;
+        self.send().direct(&buyer, &raffle.payment_token, 0, &refund);
+
+        self.raffle_refund_claimed_event(raffle_id, &buyer, &refund);
+
+        refund
+    }
+
+    // Internal helpers
+    fn require_raffle(&self, raffle_id: u32) -> Raffle<Self::Api> {
+        require!(!self.raffles(raffle_id).is_empty(), ERR_RAFFLE_DOES_NOT_EXIST);
+        self.raffles(raffle_id).get()
+    }
+
+    fn store_raffle_fee_percent(&self, raffle_fee_percent: u64) {
+        require!(raffle_fee_percent <= 1000, ERR_FEE_CANNOT_EXCEED_10_PERCENT);
+        self.raffle_fee_percent().set(raffle_fee_percent);
+    }
+
+    fn cancel(&self, raffle_id: u32, raffle: &mut Raffle<Self::Api>) {
+        raffle.status = RaffleStatus::Cancelled;
+        self.raffles(raffle_id).set(&*raffle);
+        self.send().direct_esdt(
+            &raffle.seller,
+            &self.wine_collection().get(),
+            raffle.nft_nonce,
+            &BigUint::from(1u32),
+        );
+
+        self.raffle_cancelled_event(raffle_id, raffle.tickets_sold);
+    }
+
+    // Binary search for the purchase holding `ticket`
+    fn ticket_owner(&self, raffle_id: u32, ticket: u32) -> ManagedAddress {
+        let purchases = self.ticket_purchases(raffle_id);
+        let mut low = 1;
+        let mut high = purchases.len();
+        while low < high {
+            let middle = (low + high + 1) / 2;
+            if purchases.get(middle).first_ticket <= ticket {
+                low = middle;
+            } else {
+                high = middle - 1;
+            }
+        }
+        purchases.get(low).buyer
+    }
+
+    // View functions
+    #[view(getWineCollection)]
+    fn get_wine_collection(&self) -> TokenIdentifier {
+        self.wine_collection().get()
+    }
+
+    #[view(getRafflePaymentTokens)]
+    fn get_raffle_payment_tokens(&self) -> MultiValueEncoded<EgldOrEsdtTokenIdentifier> {
+        self.payment_tokens().iter().collect()
+    }
+
+    #[view(getRaffleFeePercent)]
+    fn get_raffle_fee_percent(&self) -> u64 {
+        self.raffle_fee_percent().get()
+    }
+
+    #[view(getRaffle)]
+    fn get_raffle(&self, raffle_id: u32) -> Raffle<Self::Api> {
+        self.require_raffle(raffle_id)
+    }
+
+    #[view(getRaffleCount)]
+    fn get_raffle_count(&self) -> u32 {
+        self.raffle_count().get()
+    }
+
+    // Tickets held, 0 once refunded
+    #[view(getTickets)]
+    fn get_tickets(&self, raffle_id: u32, buyer: ManagedAddress) -> u32 {
+        self.tickets(raffle_id, &buyer).get()
+    }
+
+    // Storage mappers
+    #[storage_mapper("wineCollection")]
+    fn wine_collection(&self) -> SingleValueMapper<TokenIdentifier>;
+
+    #[storage_mapper("paymentTokens")]
+    fn payment_tokens(&self) -> UnorderedSetMapper<EgldOrEsdtTokenIdentifier>;
+
+    #[storage_mapper("raffleFeePercent")]
+    fn raffle_fee_percent(&self) -> SingleValueMapper<u64>;
+
+    #[storage_mapper("raffleCount")]
+    fn raffle_count(&self) -> SingleValueMapper<u32>;
+
+    #[storage_mapper("raffles")]
+    fn raffles(&self, raffle_id: u32) -> SingleValueMapper<Raffle<Self::Api>>;
+
+    #[storage_mapper("ticketPurchases")]
+    fn ticket_purchases(&self, raffle_id: u32) -> VecMapper<TicketPurchase<Self::Api>>;
+
+    #[storage_mapper("tickets")]
+    fn tickets(&self, raffle_id: u32, buyer: &ManagedAddress) -> SingleValueMapper<u32>;
+
+    // Events
+    #[event("raffleCreated")]
+    fn raffle_created_event(
+        &self,
+        #[indexed] raffle_id: u32,
+        #[indexed] seller: &ManagedAddress,
+        #[indexed] nft_nonce: u64,
+        max_tickets: u32,
+        deadline: u64,
+    );
+
+    #[event("ticketsBought")]
+    fn tickets_bought_event(
+        &self,
+        #[indexed] raffle_id: u32,
+        #[indexed] buyer: &ManagedAddress,
+        count: u32,
+    );
+
+    #[event("raffleDrawn")]
+    fn raffle_drawn_event(
+        &self,
+        #[indexed] raffle_id: u32,
+        #[indexed] winner: &ManagedAddress,
+        #[indexed] winning_ticket: u32,
+        seller_amount: &BigUint,
+        fee: &BigUint,
+    );
+
+    #[event("raffleCancelled")]
+    fn raffle_cancelled_event(&self, #[indexed] raffle_id: u32, tickets_sold: u32);
+
+    #[event("raffleRefundClaimed")]
+    fn raffle_refund_claimed_event(
+        &self,
+        #[indexed] raffle_id: u32,
+        #[indexed] buyer: &ManagedAddress,
+        amount: &BigUint,
+    );
+}
@@ -0,0 +1,229 @@
+use multiversx_sc_scenario::api::StaticApi;
+use multiversx_sc_scenario::imports::*;
+use wine_common::errors::*;
+use wine_raffle::raffles::ProxyTrait as _;
+use wine_raffle::ProxyTrait as _;
+use wine_raffle::{Raffle, RaffleStatus};
+
+const RAFFLE_PATH_EXPR: &str = "mxsc:output/wine-raffle.mxsc.json";
+const RAFFLE_ADDRESS_EXPR: &str = "sc:wine-raffle";
+const OWNER_ADDRESS_EXPR: &str = "address:owner";
+const SELLER_ADDRESS_EXPR: &str = "address:seller";
+const ALICE_ADDRESS_EXPR: &str = "address:alice";
+const BOB_ADDRESS_EXPR: &str = "address:bob";
+
+const WINE_NFT_TOKEN_ID_EXPR: &str = "str:WINE-abcdef";
+
+const START_TIMESTAMP: u64 = 1_000;
+const RAFFLE_DURATION: u64 = 3_600;
+const RAFFLE_FEE_PERCENT: u64 = 500;
+const TICKET_PRICE: u64 = 100;
+const MAX_TICKETS: u32 = 3;
+
+type RaffleContract = ContractInfo<wine_raffle::Proxy<StaticApi>>;
+
+fn user_error(message: &str) -> TxExpect {
+    TxExpect::user_error(format!("str:{message}"))
+}
+
+struct RaffleTestState {
+    world: ScenarioWorld,
+    raffle: RaffleContract,
+}
+
+impl RaffleTestState {
+    // Raffle 1 sells three EGLD tickets at 100 for the seller's bottle, with a 5% fee
+    fn new() -> Self {
+        let mut world = ScenarioWorld::new();
+        world.register_contract(RAFFLE_PATH_EXPR, wine_raffle::ContractBuilder);
+
+        world.set_state_step(
+            SetStateStep::new()
+                .block_timestamp(START_TIMESTAMP)
+                .put_account(OWNER_ADDRESS_EXPR, Account::new().nonce(1))
+                .put_account(
+                    SELLER_ADDRESS_EXPR,
+                    Account::new()
+                        .nonce(1)
+                        .balance("1000")
+                        .esdt_nft_balance(WINE_NFT_TOKEN_ID_EXPR, 1, "1", Option::<&str>::None),
+                )
+                .put_account(ALICE_ADDRESS_EXPR, Account::new().nonce(1).balance("1000"))
+                .put_account(BOB_ADDRESS_EXPR, Account::new().nonce(1).balance("1000"))
+                .new_address(OWNER_ADDRESS_EXPR, 1, RAFFLE_ADDRESS_EXPR),
+        );
+
+        let mut raffle = RaffleContract::new(RAFFLE_ADDRESS_EXPR);
+        let raffle_code = world.code_expression(RAFFLE_PATH_EXPR);
+        world.sc_deploy(
+            ScDeployStep::new()
+                .from(OWNER_ADDRESS_EXPR)
+                .code(raffle_code)
+                .call(raffle.init(TokenIdentifier::from("WINE-abcdef"), RAFFLE_FEE_PERCENT)),
+        );
+
+        // Only admins choose the ticket currencies
+        world.sc_call(
+            ScCallStep::new()
+                .from(SELLER_ADDRESS_EXPR)
+                .call(raffle.add_raffle_payment_token(EgldOrEsdtTokenIdentifier::egld()))
+                .expect(user_error(ERR_CALLER_DOES_NOT_HAVE_THE_REQUIRED_ROLE)),
+        );
+        world.sc_call(
+            ScCallStep::new()
+                .from(OWNER_ADDRESS_EXPR)
+                .call(raffle.add_raffle_payment_token(EgldOrEsdtTokenIdentifier::egld())),
+        );
+        world.sc_call(
+            ScCallStep::new()
+                .from(SELLER_ADDRESS_EXPR)
+                .esdt_transfer(WINE_NFT_TOKEN_ID_EXPR, 1, "1")
+                .call(raffle.create_raffle(
+                    EgldOrEsdtTokenIdentifier::egld(),
+                    TICKET_PRICE,
+                    MAX_TICKETS,
+                    RAFFLE_DURATION,
+                ))
+                .expect_value(1u32),
+        );
+
+        RaffleTestState { world, raffle }
+    }
+
+    fn set_block_timestamp(&mut self, timestamp: u64) {
+        self.world.set_state_step(SetStateStep::new().block_timestamp(timestamp));
+    }
+
+    fn buy_tickets(&mut self, buyer: &str, count: u32) -> TypedScCall<()> {
+        ScCallStep::new()
+            .from(buyer)
+            .egld_value(TICKET_PRICE * count as u64)
+            .call(self.raffle.buy_tickets(1u32, count))
+    }
+
+    fn draw_raffle(&mut self) {
+        self.world.sc_call(
+            ScCallStep::new()
+                .from(OWNER_ADDRESS_EXPR)
+                .call(self.raffle.draw_raffle(1u32)),
+        );
+    }
+
+    fn raffle(&mut self) -> Raffle<StaticApi> {
+        self.world.quick_query(self.raffle.get_raffle(1u32))
+    }
+}
+
+#[test]
+fn sold_out_raffle_sends_the_bottle_to_a_ticket_holder() {
+    let mut state = RaffleTestState::new();
+
+    let step = state.buy_tickets(SELLER_ADDRESS_EXPR, 1);
+    state.world.sc_call(step.expect(user_error(ERR_CANNOT_ENTER_YOUR_OWN_RAFFLE)));
+    let step = state.buy_tickets(ALICE_ADDRESS_EXPR, 1);
+    state.world.sc_call(step);
+    let step = state.buy_tickets(BOB_ADDRESS_EXPR, 3);
+    state.world.sc_call(step.expect(user_error(ERR_NOT_ENOUGH_TICKETS_LEFT)));
+    let step = state.buy_tickets(BOB_ADDRESS_EXPR, 2);
+    state.world.sc_call(step);
+
+    state.world.sc_call(
+        ScCallStep::new()
+            .from(OWNER_ADDRESS_EXPR)
+            .call(state.raffle.draw_raffle(1u32))
+            .expect(user_error(ERR_RAFFLE_HAS_NOT_ENDED_YET)),
+    );
+    state.set_block_timestamp(START_TIMESTAMP + RAFFLE_DURATION + 1);
+    state.draw_raffle();
+
+    let raffle = state.raffle();
+    assert_eq!(raffle.status, RaffleStatus::Drawn);
+    let alice = AddressValue::from(ALICE_ADDRESS_EXPR).to_address();
+    let winner = if raffle.winner.unwrap().to_address() == alice {
+        ALICE_ADDRESS_EXPR
+    } else {
+        BOB_ADDRESS_EXPR
+    };
+
+    // The seller gets the 300 in ticket sales minus the 5% fee
+    state.world.check_state_step(
+        CheckStateStep::new()
+            .put_account(
+                winner,
+                CheckAccount::new().esdt_nft_balance_and_attributes(WINE_NFT_TOKEN_ID_EXPR, 1, "1", Some("")),
+            )
+            .put_account(SELLER_ADDRESS_EXPR, CheckAccount::new().balance("1285"))
+            .put_account(OWNER_ADDRESS_EXPR, CheckAccount::new().balance("15"))
+            .put_account(RAFFLE_ADDRESS_EXPR, CheckAccount::new().balance("0")),
+    );
+    state.world.sc_call(
+        ScCallStep::new()
+            .from(ALICE_ADDRESS_EXPR)
+            .call(state.raffle.claim_raffle_refund(1u32))
+            .expect(user_error(ERR_RAFFLE_WAS_NOT_CANCELLED)),
+    );
+}
+
+#[test]
+fn unsold_raffle_returns_the_bottle_and_refunds_the_tickets() {
+    let mut state = RaffleTestState::new();
+    let step = state.buy_tickets(ALICE_ADDRESS_EXPR, 2);
+    state.world.sc_call(step);
+
+    state.set_block_timestamp(START_TIMESTAMP + RAFFLE_DURATION + 1);
+    let step = state.buy_tickets(BOB_ADDRESS_EXPR, 1);
+    state.world.sc_call(step.expect(user_error(ERR_RAFFLE_HAS_ENDED)));
+    state.draw_raffle();
+
+    assert_eq!(state.raffle().status, RaffleStatus::Cancelled);
+    state.world.sc_call(
+        ScCallStep::new()
+            .from(ALICE_ADDRESS_EXPR)
+            .call(state.raffle.claim_raffle_refund(1u32))
+            .expect_value(BigUint::<StaticApi>::from(200u64)),
+    );
+    state.world.sc_call(
+        ScCallStep::new()
+            .from(ALICE_ADDRESS_EXPR)
+            .call(state.raffle.claim_raffle_refund(1u32))
+            .expect(user_error(ERR_NO_TICKETS_TO_REFUND)),
+    );
+    state.world.check_state_step(
+        CheckStateStep::new()
+            .put_account(
+                SELLER_ADDRESS_EXPR,
+                CheckAccount::new()
+                    .balance("1000")
+                    .esdt_nft_balance_and_attributes(WINE_NFT_TOKEN_ID_EXPR, 1, "1", Some("")),
+            )
+            .put_account(ALICE_ADDRESS_EXPR, CheckAccount::new().balance("1000"))
+            .put_account(RAFFLE_ADDRESS_EXPR, CheckAccount::new().balance("0")),
+    );
+}
+
+#[test]
+fn only_the_seller_can_withdraw_a_raffle_before_any_sale() {
+    let mut state = RaffleTestState::new();
+
+    state.world.sc_call(
+        ScCallStep::new()
+            .from(ALICE_ADDRESS_EXPR)
+            .call(state.raffle.cancel_raffle(1u32))
+            .expect(user_error(ERR_ONLY_SELLER_CAN_CANCEL)),
+    );
+    state.world.sc_call(
+        ScCallStep::new()
+            .from(SELLER_ADDRESS_EXPR)
+            .call(state.raffle.cancel_raffle(1u32)),
+    );
+
+    assert_eq!(state.raffle().status, RaffleStatus::Cancelled);
+    state.world.check_state_step(
+        CheckStateStep::new().put_account(
+            SELLER_ADDRESS_EXPR,
+            CheckAccount::new().esdt_nft_balance_and_attributes(WINE_NFT_TOKEN_ID_EXPR, 1, "1", Some("")),
+        ),
+    );
+    let step = state.buy_tickets(ALICE_ADDRESS_EXPR, 1);
+    state.world.sc_call(step.expect(user_error(ERR_RAFFLE_IS_NOT_OPEN)));
+}
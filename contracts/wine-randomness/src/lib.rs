@@ -0,0 +1,165 @@
+#![no_std]
+
+use multiversx_sc::imports::*;
+use wine_common::errors::*;
+
+// Domain tags, one per feature consuming randomness
+pub const CANDLE_AUCTION_DOMAIN: &[u8] = b"wine.candle_auction";
+pub const RAFFLE_DOMAIN: &[u8] = b"wine.raffle";
+pub const ARBITRATION_DOMAIN: &[u8] = b"wine.arbitration";
+
+// Every draw hashes (domain hash | context id | draw nonce | block random seed | entropy accumulator).
+// All parts are fixed width, so no two distinct inputs share an encoding.
+//
+// - Domain separation: the same block seed yields unrelated values for different features/contexts.
+// - Replay resistance: the per-domain draw nonce changes the output of every draw, even in the same block.
+// - User entropy: participants commit keccak256(preimage) and later reveal the preimage; revealed
+//   values are folded into the context's accumulator, so a single validator controlling the block seed
+//   cannot predict the result alone. Reveals close once the context is sealed by its first draw.
+#[multiversx_sc::module]
+pub trait RandomnessModule {
+    #[endpoint(commitEntropy)]
+    fn commit_entropy(&self, domain: ManagedBuffer, context_id: u64, commitment: ManagedByteArray<32>) {
+        require!(!domain.is_empty(), ERR_DOMAIN_CANNOT_BE_EMPTY);
+        require!(!self.entropy_sealed(&domain, context_id).get(), ERR_ENTROPY_IS_SEALED_FOR_THIS_CONTEXT);
+
+        let caller = self.blockchain().get_caller();
+        let commitment_mapper = self.entropy_commitment(&domain, context_id, &caller);
+        require!(commitment_mapper.is_empty(), ERR_ENTROPY_ALREADY_COMMITTED);
+
+        commitment_mapper.set(&commitment);
+
+        self.entropy_committed_event(&domain, context_id, &caller);
+    }
+
+    #[endpoint(revealEntropy)]
+    fn reveal_entropy(&self, domain: ManagedBuffer, context_id: u64, preimage: ManagedBuffer) {
+        require!(!self.entropy_sealed(&domain, context_id).get(), ERR_ENTROPY_IS_SEALED_FOR_THIS_CONTEXT);
+
+        let caller = self.blockchain().get_caller();
+        let commitment_mapper = self.entropy_commitment(&domain, context_id, &caller);
+        require!(!commitment_mapper.is_empty(), ERR_NO_ENTROPY_COMMITMENT_FOUND);
+        require!(
+            !self.entropy_revealed(&domain, context_id, &caller).get(),
+            ERR_ENTROPY_ALREADY_REVEALED
+        );
+        require!(
+            self.crypto().keccak256(&preimage) == commitment_mapper.get(),
+            ERR_PREIMAGE_DOES_NOT_MATCH_COMMITMENT
+        );
+
+        // Bind the contribution to the revealer, so copying someone else's preimage adds nothing
+        let mut contribution = caller.as_managed_buffer().clone();
+        contribution.append(&preimage);
+        let contribution_hash = self.crypto().keccak256(&contribution);
+        self.mix_entropy(&domain, context_id, &contribution_hash);
+
+        self.entropy_revealed(&domain, context_id, &caller).set(true);
+        self.entropy_contribution_count(&domain, context_id).update(|count| *count += 1);
+
+        self.entropy_revealed_event(&domain, context_id, &caller);
+    }
+
+    // Internal helpers
+    fn mix_entropy(&self, domain: &ManagedBuffer, context_id: u64, contribution_hash: &ManagedByteArray<32>) {
+        let accumulator_mapper = self.entropy_accumulator(domain, context_id);
+        let mut material = ManagedBuffer::new();
+        if !accumulator_mapper.is_empty() {
+            material.append(accumulator_mapper.get().as_managed_buffer());
+        }
+        material.append(contribution_hash.as_managed_buffer());
+
+        accumulator_mapper.set(&self.crypto().keccak256(&material));
+    }
+
+    fn draw_random_hash(&self, domain: &ManagedBuffer, context_id: u64) -> ManagedByteArray<32> {
+        let draw_nonce = self.random_draw_nonce(domain).update(|nonce| {
+            *nonce += 1;
+            *nonce
+        });
+        self.entropy_sealed(domain, context_id).set(true);
+
+        let mut material = ManagedBuffer::new();
+        material.append(self.crypto().keccak256(domain).as_managed_buffer());
+        material.append_bytes(&context_id.to_be_bytes());
+        material.append_bytes(&draw_nonce.to_be_bytes());
+        material.append(self.blockchain().get_block_random_seed().as_managed_buffer());
+        let accumulator_mapper = self.entropy_accumulator(domain, context_id);
+        if accumulator_mapper.is_empty() {
+            material.append_bytes(&[0u8; 32]);
+        } else {
+            material.append(accumulator_mapper.get().as_managed_buffer());
+        }
+
+        self.crypto().keccak256(&material)
+    }
+
+    // Returns a value in [min, max). Modulo bias is negligible for ranges far below 2^64.
+    fn draw_random_u64_in_range(&self, domain: &ManagedBuffer, context_id: u64, min: u64, max: u64) -> u64 {
+        require!(min < max, ERR_INVALID_RANDOM_RANGE);
+
+        let hash_bytes = self.draw_random_hash(domain, context_id).to_byte_array();
+        let mut value_bytes = [0u8; 8];
+        value_bytes.copy_from_slice(&hash_bytes[..8]);
+        let value = u64::from_be_bytes(value_bytes);
+
+        min + value % (max - min)
+    }
+
+    // Views
+    #[view(getEntropyContributionCount)]
+    fn get_entropy_contribution_count(&self, domain: ManagedBuffer, context_id: u64) -> u32 {
+        self.entropy_contribution_count(&domain, context_id).get()
+    }
+
+    #[view(isEntropySealed)]
+    fn is_entropy_sealed(&self, domain: ManagedBuffer, context_id: u64) -> bool {
+        self.entropy_sealed(&domain, context_id).get()
+    }
+
+    #[view(getRandomDrawNonce)]
+    fn get_random_draw_nonce(&self, domain: ManagedBuffer) -> u64 {
+        self.random_draw_nonce(&domain).get()
+    }
+
+    // Storage mappers
+    #[storage_mapper("entropyCommitment")]
+    fn entropy_commitment(
+        &self,
+        domain: &ManagedBuffer,
+        context_id: u64,
+        user: &ManagedAddress,
+    ) -> SingleValueMapper<ManagedByteArray<32>>;
+
+    #[storage_mapper("entropyRevealed")]
+    fn entropy_revealed(&self, domain: &ManagedBuffer, context_id: u64, user: &ManagedAddress) -> SingleValueMapper<bool>;
+
+    #[storage_mapper("entropyAccumulator")]
+    fn entropy_accumulator(&self, domain: &ManagedBuffer, context_id: u64) -> SingleValueMapper<ManagedByteArray<32>>;
+
+    #[storage_mapper("entropyContributionCount")]
+    fn entropy_contribution_count(&self, domain: &ManagedBuffer, context_id: u64) -> SingleValueMapper<u32>;
+
+    #[storage_mapper("entropySealed")]
+    fn entropy_sealed(&self, domain: &ManagedBuffer, context_id: u64) -> SingleValueMapper<bool>;
+
+    #[storage_mapper("randomDrawNonce")]
+    fn random_draw_nonce(&self, domain: &ManagedBuffer) -> SingleValueMapper<u64>;
+
+    // Events
+    #[event("entropyCommitted")]
+    fn entropy_committed_event(
+        &self,
+        #[indexed] domain: &ManagedBuffer,
+        #[indexed] context_id: u64,
+        #[indexed] user: &ManagedAddress,
+    );
+
+    #[event("entropyRevealed")]
+    fn entropy_revealed_event(
+        &self,
+        #[indexed] domain: &ManagedBuffer,
+        #[indexed] context_id: u64,
+        #[indexed] user: &ManagedAddress,
+    );
+}
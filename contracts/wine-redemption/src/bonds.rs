@@ -0,0 +1,163 @@
+use multiversx_sc::derive_imports::*;
+use multiversx_sc::imports::*;
+use wine_common::errors::*;
+use wine_roles::Role;
+
+#[derive(TopEncode, TopDecode, TypeAbi, PartialEq, Debug)]
+pub struct RedemptionConfig<M: ManagedTypeApi> {
+    // Producer bond locked per bottle under redemption
+    pub bond_per_bottle: BigUint<M>,
+    // Seconds the producer has to ship after a request
+    pub shipping_timeout: u64,
+    // Seconds the holder has to confirm receipt or dispute after shipment
+    pub confirmation_timeout: u64,
+}
+
+// Producers ship the bottles of the wines assigned to them by admins, and back every open
+// order with a bond from their deposit. The bond compensates the holder if the producer
+// fails to ship, and returns to the producer once delivery is confirmed.
+#[multiversx_sc::module]
+pub trait BondsModule: wine_roles::RolesModule {
+    // Only wines of this collection can be redeemed
+    #[endpoint(setWineCollection)]
+    fn set_wine_collection(&self, wine_collection: TokenIdentifier) {
+        self.require_role(Role::Admin);
+        require!(wine_collection.is_valid_esdt_identifier(), ERR_INVALID_WINE_COLLECTION);
+        self.wine_collection().set(&wine_collection);
+    }
+
+    #[endpoint(setRedemptionConfig)]
+    fn set_redemption_config(
+        &self,
+        bond_per_bottle: BigUint,
+        shipping_timeout: u64,
+        confirmation_timeout: u64,
+    ) {
+        self.require_role(Role::Admin);
+        self.store_redemption_config(bond_per_bottle, shipping_timeout, confirmation_timeout);
+    }
+
+    // Assign the producer who ships a wine's bottles, as recorded in the registry
+    #[endpoint(setWineProducer)]
+    fn set_wine_producer(&self, wine_id: u32, producer: ManagedAddress) {
+        self.require_role(Role::Admin);
+        self.wine_producer(wine_id).set(&producer);
+
+        self.wine_producer_set_event(wine_id, &producer);
+    }
+
+    #[endpoint(removeWineProducer)]
+    fn remove_wine_producer(&self, wine_id: u32) {
+        self.require_role(Role::Admin);
+        require!(!self.wine_producer(wine_id).is_empty(), ERR_WINE_HAS_NO_PRODUCER);
+        self.wine_producer(wine_id).clear();
+    }
+
+    #[payable("*")]
+    #[endpoint(depositBond)]
+    fn deposit_bond(&self) {
+        let payment = self.call_value().egld_or_single_esdt();
+        require!(payment.token_identifier == self.bond_token().get(), ERR_INVALID_BOND_TOKEN);
+        require!(payment.amount > 0, ERR_AMOUNT_MUST_BE_GREATER_THAN_0);
+        let producer = self.blockchain().get_caller();
+        self.producer_bond(&producer).update(|bond| *bond += &payment.amount);
+
+        self.bond_deposited_event(&producer, &payment.amount);
+    }
+
+    // Only the part not locked by open orders can be withdrawn
+    #[endpoint(withdrawBond)]
+    fn withdraw_bond(&self, amount: BigUint) {
+        require!(amount > 0, ERR_AMOUNT_MUST_BE_GREATER_THAN_0);
+        let producer = self.blockchain().get_caller();
+        let bond = self.producer_bond(&producer).get();
+        require!(amount <= bond, ERR_NOT_ENOUGH_FREE_BOND);
+        self.producer_bond(&producer).set(&bond - &amount);
+        self.send().direct(&producer, &self.bond_token().get(), 0, &amount);
+
+        self.bond_withdrawn_event(&producer, &amount);
+    }
+
+    // Internal helpers
+    fn store_redemption_config(
+        &self,
+        bond_per_bottle: BigUint,
+        shipping_timeout: u64,
+        confirmation_timeout: u64,
+    ) {
+        require!(shipping_timeout > 0, ERR_SHIPPING_TIMEOUT_MUST_BE_GREATER_THAN_0);
+        require!(confirmation_timeout > 0, ERR_CONFIRMATION_TIMEOUT_MUST_BE_GREATER_THAN_0);
+        self.redemption_config().set(&RedemptionConfig {
+            bond_per_bottle,
+            shipping_timeout,
+            confirmation_timeout,
+        });
+    }
+
+    fn lock_bond(&self, producer: &ManagedAddress, amount: &BigUint) {
+        let bond = self.producer_bond(producer).get();
+        require!(amount <= &bond, ERR_PRODUCER_BOND_TOO_LOW);
+        self.producer_bond(producer).set(&bond - amount);
+    }
+
+    fn release_bond(&self, producer: &ManagedAddress, amount: &BigUint) {
+        self.producer_bond(producer).update(|bond| *bond += amount);
+    }
+
+    // View functions
+    #[view(getWineCollection)]
+    fn get_wine_collection(&self) -> TokenIdentifier {
+        self.wine_collection().get()
+    }
+
+    #[view(getBondToken)]
+    fn get_bond_token(&self) -> EgldOrEsdtTokenIdentifier {
+        self.bond_token().get()
+    }
+
+    #[view(getRedemptionConfig)]
+    fn get_redemption_config(&self) -> RedemptionConfig<Self::Api> {
+        self.redemption_config().get()
+    }
+
+    #[view(getWineProducer)]
+    fn get_wine_producer(&self, wine_id: u32) -> OptionalValue<ManagedAddress> {
+        if self.wine_producer(wine_id).is_empty() {
+            OptionalValue::None
+        } else {
+            OptionalValue::Some(self.wine_producer(wine_id).get())
+        }
+    }
+
+    // Free bond, not locked by open orders
+    #[view(getProducerBond)]
+    fn get_producer_bond(&self, producer: ManagedAddress) -> BigUint {
+        self.producer_bond(&producer).get()
+    }
+
+    // Storage mappers
+    #[storage_mapper("wineCollection")]
+    fn wine_collection(&self) -> SingleValueMapper<TokenIdentifier>;
+
+    #[storage_mapper("bondToken")]
+    fn bond_token(&self) -> SingleValueMapper<EgldOrEsdtTokenIdentifier>;
+
+    #[storage_mapper("redemptionConfig")]
+    fn redemption_config(&self) -> SingleValueMapper<RedemptionConfig<Self::Api>>;
+
+    #[storage_mapper("wineProducer")]
+    fn wine_producer(&self, wine_id: u32) -> SingleValueMapper<ManagedAddress>;
+
+    #[storage_mapper("producerBond")]
+    fn producer_bond(&self, producer: &ManagedAddress) -> SingleValueMapper<BigUint>;
+
+    // Events
+    #[event("wineProducerSet")]
+    fn wine_producer_set_event(&self, #[indexed] wine_id: u32, #[indexed] producer: &ManagedAddress);
+
+    #[event("bondDeposited")]
+    fn bond_deposited_event(&self, #[indexed] producer: &ManagedAddress, amount: &BigUint);
+
+    #[event("bondWithdrawn")]
+    fn bond_withdrawn_event(&self, #[indexed] producer: &ManagedAddress, amount: &BigUint);
+}
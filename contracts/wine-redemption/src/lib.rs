@@ -0,0 +1,37 @@
+#![no_std]
+
+use multiversx_sc::imports::*;
+use wine_common::errors::*;
+
+pub mod bonds;
+pub mod orders;
+
+pub use bonds::RedemptionConfig;
+pub use orders::{RedemptionOrder, RedemptionStatus, WineNftAttributes};
+
+// Physical redemption of wine NFTs. A holder escrows bottles with a hash of the shipping
+// details, the producer ships against a bond, and the holder's confirmation of receipt
+// releases the bond. Timeouts close orders the other side abandons; admins resolve disputes.
+#[multiversx_sc::contract]
+pub trait WineRedemption:
+    bonds::BondsModule + orders::OrdersModule + wine_roles::RolesModule
+{
+    #[init]
+    fn init(
+        &self,
+        wine_collection: TokenIdentifier,
+        bond_token: EgldOrEsdtTokenIdentifier,
+        bond_per_bottle: BigUint,
+        shipping_timeout: u64,
+        confirmation_timeout: u64,
+    ) {
+        require!(wine_collection.is_valid_esdt_identifier(), ERR_INVALID_WINE_COLLECTION);
+        require!(bond_token.is_valid(), ERR_INVALID_BOND_TOKEN);
+        self.wine_collection().set(&wine_collection);
+        self.bond_token().set(&bond_token);
+        self.store_redemption_config(bond_per_bottle, shipping_timeout, confirmation_timeout);
+    }
+
+    #[upgrade]
+    fn upgrade(&self) {}
+}
@@ -0,0 +1,300 @@
+use multiversx_sc::derive_imports::*;
+use multiversx_sc::imports::*;
+use wine_roles::Role;
+pub use wine_common::WineNftAttributes;
+use wine_common::errors::*;
+
+#[derive(TopEncode, TopDecode, NestedEncode, NestedDecode, TypeAbi, PartialEq, Debug, Clone, Copy)]
+pub enum RedemptionStatus {
+    // Awaiting shipment by the producer
+    Requested,
+    // Shipped, awaiting the holder's confirmation
+    Shipped,
+    // The holder got the bottles; the NFTs stay locked
+    Delivered,
+    // Receipt contested by the holder, awaiting an admin
+    Disputed,
+    // Called off; the NFTs went back to the holder
+    Cancelled,
+}
+
+#[derive(TopEncode, TopDecode, TypeAbi, PartialEq, Debug)]
+pub struct RedemptionOrder<M: ManagedTypeApi> {
+    pub holder: ManagedAddress<M>,
+    pub producer: ManagedAddress<M>,
+    pub wine_id: u32,
+    pub wine_nonce: u64,
+    pub bottles: u32,
+    // Hash of the shipping details, shared with the producer off-chain
+    pub shipping_hash: ManagedByteArray<M, 32>,
+    // Hash of the carrier's tracking details, set on shipment
+    pub tracking_hash: ManagedByteArray<M, 32>,
+    pub bond: BigUint<M>,
+    pub status: RedemptionStatus,
+    pub requested_timestamp: u64,
+    pub shipped_timestamp: u64,
+}
+
+// Holders escrow wine NFTs here to get the physical bottles. Delivered NFTs stay locked in
+// this contract for good, taking the redeemed bottles out of circulation. Either side can
+// close an order once the other misses its timeout, and admins settle disputes.
+#[multiversx_sc::module]
+pub trait OrdersModule: wine_roles::RolesModule + crate::bonds::BondsModule {
+    #[payable("*")]
+    #[endpoint(requestRedemption)]
+    fn request_redemption(&self, shipping_hash: ManagedByteArray<Self::Api, 32>) -> u32 {
+        self.require_not_paused();
+        let payment = self.call_value().single_esdt();
+        require!(payment.token_identifier == self.wine_collection().get(), ERR_INVALID_WINE_TOKEN);
+        let bottles = payment.amount.to_u64().unwrap_or(u64::MAX);
+        require!(bottles > 0 && bottles <= u32::MAX as u64, ERR_INVALID_BOTTLE_AMOUNT);
+
+        let token_data = self.blockchain().get_esdt_token_data(
+            &self.blockchain().get_sc_address(),
+            &payment.token_identifier,
+            payment.token_nonce,
+        );
+        let attributes: WineNftAttributes<Self::Api> = token_data.decode_attributes();
+        require!(
+            !self.wine_producer(attributes.wine_id).is_empty(),
+            ERR_WINE_IS_NOT_REDEEMABLE
+        );
+        let producer = self.wine_producer(attributes.wine_id).get();
+        let bond = self.redemption_config().get().bond_per_bottle * bottles;
+        self.lock_bond(&producer, &bond);
+
+        let holder = self.blockchain().get_caller();
+        let order_id = self.order_count().get() + 1;
+        self.order_count().set(order_id);
+        self.orders(order_id).set(&RedemptionOrder {
+            holder: holder.clone(),
+            producer: producer.clone(),
+            wine_id: attributes.wine_id,
+            wine_nonce: payment.token_nonce,
+            bottles: bottles as u32,
+            shipping_hash,
+            tracking_hash: ManagedByteArray::default(),
+            bond,
+            status: RedemptionStatus::Requested,
+            requested_timestamp: self.blockchain().get_block_timestamp(),
+            shipped_timestamp: 0,
+        });
+        self.producer_orders(&producer).insert(order_id);
+
+        self.redemption_requested_event(
+            order_id,
+            &holder,
+            &producer,
+            attributes.wine_id,
+            bottles as u32,
+        );
+
+        order_id
+    }
+
+    // Withdraw a request the producer has not shipped yet
+    #[endpoint(cancelRedemption)]
+    fn cancel_redemption(&self, order_id: u32) {
+        let mut order = self.require_order(order_id);
+        require!(self.blockchain().get_caller() == order.holder, ERR_ONLY_HOLDER_CAN_CANCEL);
+        require!(order.status == RedemptionStatus::Requested, ERR_ORDER_IS_NOT_PENDING_SHIPMENT);
+
+        self.release_bond(&order.producer, &order.bond);
+        self.return_wine(&order);
+        order.status = RedemptionStatus::Cancelled;
+        self.close_order(order_id, &order);
+    }
+
+    #[endpoint(confirmShipment)]
+    fn confirm_shipment(&self, order_id: u32, tracking_hash: ManagedByteArray<Self::Api, 32>) {
+        let mut order = self.require_order(order_id);
+        require!(
+            self.blockchain().get_caller() == order.producer,
+            ERR_ONLY_PRODUCER_CAN_CONFIRM_SHIPMENT
+        );
+        require!(order.status == RedemptionStatus::Requested, ERR_ORDER_IS_NOT_PENDING_SHIPMENT);
+        require!(!self.is_shipping_overdue(&order), ERR_SHIPPING_TIMEOUT_HAS_PASSED);
+
+        order.tracking_hash = tracking_hash;
+        order.status = RedemptionStatus::Shipped;
+        order.shipped_timestamp = self.blockchain().get_block_timestamp();
+        self.orders(order_id).set(&order);
+
+        self.redemption_shipped_event(order_id, &order.producer, &order.tracking_hash);
+    }
+
+    #[endpoint(confirmReceipt)]
+    fn confirm_receipt(&self, order_id: u32) {
+        let mut order = self.require_order(order_id);
+        require!(
+            self.blockchain().get_caller() == order.holder,
+            ERR_ONLY_HOLDER_CAN_CONFIRM_RECEIPT
+        );
+        require!(order.status == RedemptionStatus::Shipped, ERR_ORDER_IS_NOT_SHIPPED);
+
+        self.complete_delivery(order_id, &mut order);
+    }
+
+    // The producer missed the shipping timeout: the holder gets the NFTs back and the bond
+    #[endpoint(claimShippingTimeout)]
+    fn claim_shipping_timeout(&self, order_id: u32) {
+        let mut order = self.require_order(order_id);
+        require!(self.blockchain().get_caller() == order.holder, ERR_ONLY_HOLDER_CAN_CLAIM);
+        require!(order.status == RedemptionStatus::Requested, ERR_ORDER_IS_NOT_PENDING_SHIPMENT);
+        require!(self.is_shipping_overdue(&order), ERR_SHIPPING_TIMEOUT_HAS_NOT_PASSED);
+
+        self.return_wine(&order);
+        self.send().direct(&order.holder, &self.bond_token().get(), 0, &order.bond);
+        order.status = RedemptionStatus::Cancelled;
+        self.close_order(order_id, &order);
+    }
+
+    // The holder neither confirmed nor disputed in time, so the delivery stands
+    #[endpoint(claimConfirmationTimeout)]
+    fn claim_confirmation_timeout(&self, order_id: u32) {
+        let mut order = self.require_order(order_id);
+        require!(self.blockchain().get_caller() == order.producer, ERR_ONLY_PRODUCER_CAN_CLAIM);
+        require!(order.status == RedemptionStatus::Shipped, ERR_ORDER_IS_NOT_SHIPPED);
+        require!(
+            self.is_confirmation_overdue(&order),
+            ERR_CONFIRMATION_TIMEOUT_HAS_NOT_PASSED
+        );
+
+        self.complete_delivery(order_id, &mut order);
+    }
+
+    // Contest a shipment that did not arrive or arrived damaged, within the confirmation
+    // timeout
+    #[endpoint(openDispute)]
+    fn open_dispute(&self, order_id: u32) {
+        let mut order = self.require_order(order_id);
+        require!(self.blockchain().get_caller() == order.holder, ERR_ONLY_HOLDER_CAN_DISPUTE);
+        require!(order.status == RedemptionStatus::Shipped, ERR_ORDER_IS_NOT_SHIPPED);
+        require!(
+            !self.is_confirmation_overdue(&order),
+            ERR_CONFIRMATION_TIMEOUT_HAS_PASSED
+        );
+
+        order.status = RedemptionStatus::Disputed;
+        self.orders(order_id).set(&order);
+
+        self.redemption_disputed_event(order_id, &order.holder);
+    }
+
+    // Settle a dispute: either the delivery stands, or the holder gets the NFTs back and the
+    // producer's bond
+    #[endpoint(resolveDispute)]
+    fn resolve_dispute(&self, order_id: u32, delivered: bool) {
+        self.require_role(Role::Admin);
+        let mut order = self.require_order(order_id);
+        require!(order.status == RedemptionStatus::Disputed, ERR_ORDER_IS_NOT_DISPUTED);
+
+        if delivered {
+            self.complete_delivery(order_id, &mut order);
+        } else {
+            self.return_wine(&order);
+            self.send().direct(&order.holder, &self.bond_token().get(), 0, &order.bond);
+            order.status = RedemptionStatus::Cancelled;
+            self.close_order(order_id, &order);
+        }
+    }
+
+    // Internal helpers
+    fn require_order(&self, order_id: u32) -> RedemptionOrder<Self::Api> {
+        require!(!self.orders(order_id).is_empty(), ERR_ORDER_DOES_NOT_EXIST);
+        self.orders(order_id).get()
+    }
+
+    fn is_shipping_overdue(&self, order: &RedemptionOrder<Self::Api>) -> bool {
+        let shipping_timeout = self.redemption_config().get().shipping_timeout;
+        self.blockchain().get_block_timestamp() > order.requested_timestamp + shipping_timeout
+    }
+
+    fn is_confirmation_overdue(&self, order: &RedemptionOrder<Self::Api>) -> bool {
+        let confirmation_timeout = self.redemption_config().get().confirmation_timeout;
+        self.blockchain().get_block_timestamp() > order.shipped_timestamp + confirmation_timeout
+    }
+
+    fn complete_delivery(&self, order_id: u32, order: &mut RedemptionOrder<Self::Api>) {
+        self.release_bond(&order.producer, &order.bond);
+        self.redeemed_bottles(order.wine_id).update(|redeemed| *redeemed += order.bottles as u64);
+        order.status = RedemptionStatus::Delivered;
+        self.close_order(order_id, order);
+    }
+
+    fn return_wine(&self, order: &RedemptionOrder<Self::Api>) {
+        self.send().direct_esdt(
+            &order.holder,
+            &self.wine_collection().get(),
+            order.wine_nonce,
+            &BigUint::from(order.bottles),
+        );
+    }
+
+    fn close_order(&self, order_id: u32, order: &RedemptionOrder<Self::Api>) {
+        self.orders(order_id).set(order);
+        self.producer_orders(&order.producer).swap_remove(&order_id);
+
+        self.redemption_closed_event(order_id, order.status);
+    }
+
+    // View functions
+    #[view(getOrder)]
+    fn get_order(&self, order_id: u32) -> RedemptionOrder<Self::Api> {
+        self.require_order(order_id)
+    }
+
+    #[view(getOrderCount)]
+    fn get_order_count(&self) -> u32 {
+        self.order_count().get()
+    }
+
+    // Orders a producer still has to ship or see confirmed
+    #[view(getProducerOrders)]
+    fn get_producer_orders(&self, producer: ManagedAddress) -> MultiValueEncoded<u32> {
+        self.producer_orders(&producer).iter().collect()
+    }
+
+    #[view(getRedeemedBottles)]
+    fn get_redeemed_bottles(&self, wine_id: u32) -> u64 {
+        self.redeemed_bottles(wine_id).get()
+    }
+
+    // Storage mappers
+    #[storage_mapper("orderCount")]
+    fn order_count(&self) -> SingleValueMapper<u32>;
+
+    #[storage_mapper("orders")]
+    fn orders(&self, order_id: u32) -> SingleValueMapper<RedemptionOrder<Self::Api>>;
+
+    #[storage_mapper("producerOrders")]
+    fn producer_orders(&self, producer: &ManagedAddress) -> UnorderedSetMapper<u32>;
+
+    #[storage_mapper("redeemedBottles")]
+    fn redeemed_bottles(&self, wine_id: u32) -> SingleValueMapper<u64>;
+
+    // Events
+    #[event("redemptionRequested")]
+    fn redemption_requested_event(
+        &self,
+        #[indexed] order_id: u32,
+        #[indexed] holder: &ManagedAddress,
+        #[indexed] producer: &ManagedAddress,
+        #[indexed] wine_id: u32,
+        bottles: u32,
+    );
+
+    #[event("redemptionShipped")]
+    fn redemption_shipped_event(
+        &self,
+        #[indexed] order_id: u32,
+        #[indexed] producer: &ManagedAddress,
+        tracking_hash: &ManagedByteArray<Self::Api, 32>,
+    );
+
+    #[event("redemptionDisputed")]
+    fn redemption_disputed_event(&self, #[indexed] order_id: u32, #[indexed] holder: &ManagedAddress);
+
+    #[event("redemptionClosed")]
+    fn redemption_closed_event(&self, #[indexed] order_id: u32, #[indexed] status: RedemptionStatus);
+}
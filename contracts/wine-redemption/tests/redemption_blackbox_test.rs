@@ -0,0 +1,351 @@
+use multiversx_sc::codec::top_encode_to_vec_u8_or_panic;
+use multiversx_sc_scenario::api::StaticApi;
+use multiversx_sc_scenario::imports::*;
+use wine_common::errors::*;
+use wine_redemption::bonds::ProxyTrait as _;
+use wine_redemption::orders::ProxyTrait as _;
+use wine_redemption::ProxyTrait as _;
+use wine_redemption::{RedemptionOrder, RedemptionStatus, WineNftAttributes};
+
+const REDEMPTION_PATH_EXPR: &str = "mxsc:output/wine-redemption.mxsc.json";
+const REDEMPTION_ADDRESS_EXPR: &str = "sc:wine-redemption";
+const OWNER_ADDRESS_EXPR: &str = "address:owner";
+const PRODUCER_ADDRESS_EXPR: &str = "address:producer";
+const HOLDER_ADDRESS_EXPR: &str = "address:holder";
+
+const WINE_NFT_TOKEN_ID_EXPR: &str = "str:WINE-abcdef";
+
+const START_TIMESTAMP: u64 = 1_000;
+const BOND_PER_BOTTLE: u64 = 50;
+const SHIPPING_TIMEOUT: u64 = 7 * 86_400;
+const CONFIRMATION_TIMEOUT: u64 = 3 * 86_400;
+const WINE_ID: u32 = 7;
+
+type RedemptionContract = ContractInfo<wine_redemption::Proxy<StaticApi>>;
+
+fn user_error(message: &str) -> TxExpect {
+    TxExpect::user_error(format!("str:{message}"))
+}
+
+fn encoded_wine_attributes(wine_id: u32) -> Option<Vec<u8>> {
+    Some(top_encode_to_vec_u8_or_panic(&WineNftAttributes::<StaticApi> {
+        wine_id,
+        vineyard: ManagedBuffer::from("Chateau Test"),
+        variety: ManagedBuffer::from("Merlot"),
+        vintage_year: 2020,
+        region: ManagedBuffer::from("Bordeaux"),
+        quality_grade: 8,
+        ipfs_hash: ManagedBuffer::new(),
+    }))
+}
+
+fn hash(byte: u8) -> ManagedByteArray<StaticApi, 32> {
+    ManagedByteArray::from(&[byte; 32])
+}
+
+struct RedemptionTestState {
+    world: ScenarioWorld,
+    redemption: RedemptionContract,
+}
+
+impl RedemptionTestState {
+    // The producer ships wine 7 and bonded 100 EGLD, enough for two bottles. The holder owns
+    // 3 bottles of wine 7 (NFT 1) and one of wine 8 (NFT 2), which nobody ships.
+    fn new() -> Self {
+        let mut world = ScenarioWorld::new();
+        world.register_contract(REDEMPTION_PATH_EXPR, wine_redemption::ContractBuilder);
+
+        world.set_state_step(
+            SetStateStep::new()
+                .block_timestamp(START_TIMESTAMP)
+                .put_account(OWNER_ADDRESS_EXPR, Account::new().nonce(1))
+                .put_account(PRODUCER_ADDRESS_EXPR, Account::new().nonce(1).balance("1000"))
+                .put_account(
+                    HOLDER_ADDRESS_EXPR,
+                    Account::new()
+                        .nonce(1)
+                        .esdt_nft_balance(WINE_NFT_TOKEN_ID_EXPR, 1, "3", encoded_wine_attributes(WINE_ID))
+                        .esdt_nft_balance(WINE_NFT_TOKEN_ID_EXPR, 2, "1", encoded_wine_attributes(8)),
+                )
+                .new_address(OWNER_ADDRESS_EXPR, 1, REDEMPTION_ADDRESS_EXPR),
+        );
+
+        let mut redemption = RedemptionContract::new(REDEMPTION_ADDRESS_EXPR);
+        let redemption_code = world.code_expression(REDEMPTION_PATH_EXPR);
+        world.sc_deploy(
+            ScDeployStep::new()
+                .from(OWNER_ADDRESS_EXPR)
+                .code(redemption_code)
+                .call(redemption.init(
+                    TokenIdentifier::from("WINE-abcdef"),
+                    EgldOrEsdtTokenIdentifier::egld(),
+                    BOND_PER_BOTTLE,
+                    SHIPPING_TIMEOUT,
+                    CONFIRMATION_TIMEOUT,
+                )),
+        );
+
+        // Producers are assigned by an admin
+        let producer = AddressValue::from(PRODUCER_ADDRESS_EXPR).to_address();
+        world.sc_call(
+            ScCallStep::new()
+                .from(PRODUCER_ADDRESS_EXPR)
+                .call(redemption.set_wine_producer(WINE_ID, managed_address!(&producer)))
+                .expect(user_error(ERR_CALLER_DOES_NOT_HAVE_THE_REQUIRED_ROLE)),
+        );
+        world.sc_call(
+            ScCallStep::new()
+                .from(OWNER_ADDRESS_EXPR)
+                .call(redemption.set_wine_producer(WINE_ID, managed_address!(&producer))),
+        );
+        world.sc_call(
+            ScCallStep::new()
+                .from(PRODUCER_ADDRESS_EXPR)
+                .egld_value(2 * BOND_PER_BOTTLE)
+                .call(redemption.deposit_bond()),
+        );
+
+        RedemptionTestState { world, redemption }
+    }
+
+    fn set_block_timestamp(&mut self, timestamp: u64) {
+        self.world.set_state_step(SetStateStep::new().block_timestamp(timestamp));
+    }
+
+    fn request_redemption(&mut self, bottles: &str) {
+        self.world.sc_call(
+            ScCallStep::new()
+                .from(HOLDER_ADDRESS_EXPR)
+                .esdt_transfer(WINE_NFT_TOKEN_ID_EXPR, 1, bottles)
+                .call(self.redemption.request_redemption(hash(1))),
+        );
+    }
+
+    fn confirm_shipment(&mut self) {
+        self.world.sc_call(
+            ScCallStep::new()
+                .from(PRODUCER_ADDRESS_EXPR)
+                .call(self.redemption.confirm_shipment(1u32, hash(2))),
+        );
+    }
+
+    fn order_status(&mut self) -> RedemptionStatus {
+        let order: RedemptionOrder<StaticApi> = self.world.quick_query(self.redemption.get_order(1u32));
+        order.status
+    }
+
+    fn producer_bond(&mut self) -> BigUint<StaticApi> {
+        let producer = AddressValue::from(PRODUCER_ADDRESS_EXPR).to_address();
+        self.world.quick_query(self.redemption.get_producer_bond(managed_address!(&producer)))
+    }
+}
+
+#[test]
+fn confirmed_delivery_keeps_the_bottles_and_releases_the_bond() {
+    let mut state = RedemptionTestState::new();
+
+    state.world.sc_call(
+        ScCallStep::new()
+            .from(HOLDER_ADDRESS_EXPR)
+            .esdt_transfer(WINE_NFT_TOKEN_ID_EXPR, 2, "1")
+            .call(state.redemption.request_redemption(hash(1)))
+            .expect(user_error(ERR_WINE_IS_NOT_REDEEMABLE)),
+    );
+    state.world.sc_call(
+        ScCallStep::new()
+            .from(HOLDER_ADDRESS_EXPR)
+            .esdt_transfer(WINE_NFT_TOKEN_ID_EXPR, 1, "3")
+            .call(state.redemption.request_redemption(hash(1)))
+            .expect(user_error(ERR_PRODUCER_BOND_TOO_LOW)),
+    );
+    state.request_redemption("2");
+    assert_eq!(state.producer_bond(), 0u64);
+
+    state.world.sc_call(
+        ScCallStep::new()
+            .from(HOLDER_ADDRESS_EXPR)
+            .call(state.redemption.confirm_shipment(1u32, hash(2)))
+            .expect(user_error(ERR_ONLY_PRODUCER_CAN_CONFIRM_SHIPMENT)),
+    );
+    state.confirm_shipment();
+    state.world.sc_call(
+        ScCallStep::new()
+            .from(PRODUCER_ADDRESS_EXPR)
+            .call(state.redemption.confirm_receipt(1u32))
+            .expect(user_error(ERR_ONLY_HOLDER_CAN_CONFIRM_RECEIPT)),
+    );
+    state.world.sc_call(
+        ScCallStep::new()
+            .from(HOLDER_ADDRESS_EXPR)
+            .call(state.redemption.confirm_receipt(1u32)),
+    );
+
+    assert_eq!(state.order_status(), RedemptionStatus::Delivered);
+    let redeemed: u64 = state.world.quick_query(state.redemption.get_redeemed_bottles(WINE_ID));
+    assert_eq!(redeemed, 2);
+
+    state.world.sc_call(
+        ScCallStep::new()
+            .from(PRODUCER_ADDRESS_EXPR)
+            .call(state.redemption.withdraw_bond(101u64))
+            .expect(user_error(ERR_NOT_ENOUGH_FREE_BOND)),
+    );
+    state.world.sc_call(
+        ScCallStep::new()
+            .from(PRODUCER_ADDRESS_EXPR)
+            .call(state.redemption.withdraw_bond(100u64)),
+    );
+    state.world.check_state_step(
+        CheckStateStep::new()
+            .put_account(PRODUCER_ADDRESS_EXPR, CheckAccount::new().balance("1000"))
+            .put_account(
+                REDEMPTION_ADDRESS_EXPR,
+                CheckAccount::new().balance("0").esdt_nft_balance_and_attributes(
+                    WINE_NFT_TOKEN_ID_EXPR,
+                    1,
+                    "2",
+                    encoded_wine_attributes(WINE_ID),
+                ),
+            ),
+    );
+}
+
+#[test]
+fn missed_shipping_timeout_returns_the_bottles_and_the_bond() {
+    let mut state = RedemptionTestState::new();
+    state.request_redemption("2");
+
+    state.world.sc_call(
+        ScCallStep::new()
+            .from(HOLDER_ADDRESS_EXPR)
+            .call(state.redemption.claim_shipping_timeout(1u32))
+            .expect(user_error(ERR_SHIPPING_TIMEOUT_HAS_NOT_PASSED)),
+    );
+    state.set_block_timestamp(START_TIMESTAMP + SHIPPING_TIMEOUT + 1);
+    state.world.sc_call(
+        ScCallStep::new()
+            .from(PRODUCER_ADDRESS_EXPR)
+            .call(state.redemption.confirm_shipment(1u32, hash(2)))
+            .expect(user_error(ERR_SHIPPING_TIMEOUT_HAS_PASSED)),
+    );
+    state.world.sc_call(
+        ScCallStep::new()
+            .from(HOLDER_ADDRESS_EXPR)
+            .call(state.redemption.claim_shipping_timeout(1u32)),
+    );
+
+    assert_eq!(state.order_status(), RedemptionStatus::Cancelled);
+    assert_eq!(state.producer_bond(), 0u64);
+    state.world.check_state_step(
+        CheckStateStep::new().put_account(
+            HOLDER_ADDRESS_EXPR,
+            CheckAccount::new().balance("100").esdt_nft_balance_and_attributes(
+                WINE_NFT_TOKEN_ID_EXPR,
+                1,
+                "3",
+                encoded_wine_attributes(WINE_ID),
+            ),
+        ),
+    );
+}
+
+#[test]
+fn unshipped_request_is_cancelled_by_the_holder() {
+    let mut state = RedemptionTestState::new();
+    state.request_redemption("2");
+
+    state.world.sc_call(
+        ScCallStep::new()
+            .from(PRODUCER_ADDRESS_EXPR)
+            .call(state.redemption.cancel_redemption(1u32))
+            .expect(user_error(ERR_ONLY_HOLDER_CAN_CANCEL)),
+    );
+    state.world.sc_call(
+        ScCallStep::new()
+            .from(HOLDER_ADDRESS_EXPR)
+            .call(state.redemption.cancel_redemption(1u32)),
+    );
+
+    assert_eq!(state.order_status(), RedemptionStatus::Cancelled);
+    assert_eq!(state.producer_bond(), 2 * BOND_PER_BOTTLE);
+    state.world.check_state_step(
+        CheckStateStep::new().put_account(
+            HOLDER_ADDRESS_EXPR,
+            CheckAccount::new().esdt_nft_balance_and_attributes(
+                WINE_NFT_TOKEN_ID_EXPR,
+                1,
+                "3",
+                encoded_wine_attributes(WINE_ID),
+            ),
+        ),
+    );
+}
+
+#[test]
+fn unconfirmed_shipment_counts_as_delivered_after_the_confirmation_timeout() {
+    let mut state = RedemptionTestState::new();
+    state.request_redemption("2");
+    state.confirm_shipment();
+
+    state.world.sc_call(
+        ScCallStep::new()
+            .from(PRODUCER_ADDRESS_EXPR)
+            .call(state.redemption.claim_confirmation_timeout(1u32))
+            .expect(user_error(ERR_CONFIRMATION_TIMEOUT_HAS_NOT_PASSED)),
+    );
+    state.set_block_timestamp(START_TIMESTAMP + CONFIRMATION_TIMEOUT + 1);
+    state.world.sc_call(
+        ScCallStep::new()
+            .from(HOLDER_ADDRESS_EXPR)
+            .call(state.redemption.open_dispute(1u32))
+            .expect(user_error(ERR_CONFIRMATION_TIMEOUT_HAS_PASSED)),
+    );
+    state.world.sc_call(
+        ScCallStep::new()
+            .from(PRODUCER_ADDRESS_EXPR)
+            .call(state.redemption.claim_confirmation_timeout(1u32)),
+    );
+
+    assert_eq!(state.order_status(), RedemptionStatus::Delivered);
+    assert_eq!(state.producer_bond(), 2 * BOND_PER_BOTTLE);
+}
+
+#[test]
+fn admin_settles_a_disputed_shipment() {
+    let mut state = RedemptionTestState::new();
+    state.request_redemption("2");
+    state.confirm_shipment();
+    state.world.sc_call(
+        ScCallStep::new()
+            .from(HOLDER_ADDRESS_EXPR)
+            .call(state.redemption.open_dispute(1u32)),
+    );
+    assert_eq!(state.order_status(), RedemptionStatus::Disputed);
+
+    state.world.sc_call(
+        ScCallStep::new()
+            .from(HOLDER_ADDRESS_EXPR)
+            .call(state.redemption.resolve_dispute(1u32, false))
+            .expect(user_error(ERR_CALLER_DOES_NOT_HAVE_THE_REQUIRED_ROLE)),
+    );
+    state.world.sc_call(
+        ScCallStep::new()
+            .from(OWNER_ADDRESS_EXPR)
+            .call(state.redemption.resolve_dispute(1u32, false)),
+    );
+
+    // Not delivered: the holder gets the bottles back along with the producer's bond
+    assert_eq!(state.order_status(), RedemptionStatus::Cancelled);
+    assert_eq!(state.producer_bond(), 0u64);
+    state.world.check_state_step(
+        CheckStateStep::new().put_account(
+            HOLDER_ADDRESS_EXPR,
+            CheckAccount::new().balance("100").esdt_nft_balance_and_attributes(
+                WINE_NFT_TOKEN_ID_EXPR,
+                1,
+                "3",
+                encoded_wine_attributes(WINE_ID),
+            ),
+        ),
+    );
+}
@@ -0,0 +1,101 @@
+use multiversx_sc::derive_imports::*;
+use multiversx_sc::imports::*;
+use wine_common::errors::*;
+use wine_roles::Role;
+
+use crate::pagination::{Page, MAX_INTEGRATIONS_PAGE_SIZE};
+
+// Scoped permissions granted to external contracts (marketplaces, indexers, oracles)
+#[derive(TopEncode, TopDecode, NestedEncode, NestedDecode, TypeAbi, PartialEq, Debug, Default)]
+pub struct IntegrationPermissions {
+    pub can_decrement_availability: bool,
+    pub can_record_sale: bool,
+    pub can_flag: bool,
+}
+
+#[multiversx_sc::module]
+pub trait AdminModule: wine_roles::RolesModule {
+    // Integration permission management
+    #[endpoint(setIntegrationPermissions)]
+    fn set_integration_permissions(
+        &self,
+        integration: ManagedAddress,
+        can_decrement_availability: bool,
+        can_record_sale: bool,
+        can_flag: bool,
+    ) {
+        self.require_role(Role::Admin);
+        let permissions = IntegrationPermissions {
+            can_decrement_availability,
+            can_record_sale,
+            can_flag,
+        };
+        if permissions == IntegrationPermissions::default() {
+            self.integration_permissions(&integration).clear();
+            self.integrations().swap_remove(&integration);
+        } else {
+            self.integration_permissions(&integration).set(&permissions);
+            self.integrations().insert(integration.clone());
+        }
+
+        self.integration_permissions_updated_event(
+            &integration,
+            can_decrement_availability,
+            can_record_sale,
+            can_flag,
+        );
+    }
+
+    #[endpoint(revokeIntegrationPermissions)]
+    fn revoke_integration_permissions(&self, integration: ManagedAddress) {
+        self.require_role(Role::Admin);
+        require!(self.integrations().contains(&integration), ERR_INTEGRATION_NOT_REGISTERED);
+        self.set_integration_permissions(integration, false, false, false);
+    }
+
+    #[view(getIntegrationPermissions)]
+    fn get_integration_permissions(&self, integration: &ManagedAddress) -> IntegrationPermissions {
+        let permissions_mapper = self.integration_permissions(integration);
+        if permissions_mapper.is_empty() {
+            IntegrationPermissions::default()
+        } else {
+            permissions_mapper.get()
+        }
+    }
+
+    // Paginated: returns the next cursor (0 when done) followed by at most `page_size` integrations
+    #[view(getIntegrations)]
+    fn get_integrations(
+        &self,
+        cursor: u64,
+        page_size: u32,
+    ) -> MultiValue2<u64, MultiValueEncoded<MultiValue2<ManagedAddress, IntegrationPermissions>>> {
+        let integrations = self.integrations();
+        let page = Page::new(cursor, page_size, MAX_INTEGRATIONS_PAGE_SIZE, integrations.len());
+
+        let mut result = MultiValueEncoded::new();
+        for index in page.start..page.end {
+            let integration = integrations.get_by_index(index + 1);
+            let permissions = self.integration_permissions(&integration).get();
+            result.push((integration, permissions).into());
+        }
+        (page.next_cursor, result).into()
+    }
+
+    // Storage mappers
+    #[storage_mapper("integrationPermissions")]
+    fn integration_permissions(&self, integration: &ManagedAddress) -> SingleValueMapper<IntegrationPermissions>;
+
+    #[storage_mapper("integrations")]
+    fn integrations(&self) -> UnorderedSetMapper<ManagedAddress>;
+
+    // Events
+    #[event("integrationPermissionsUpdated")]
+    fn integration_permissions_updated_event(
+        &self,
+        #[indexed] integration: &ManagedAddress,
+        #[indexed] can_decrement_availability: bool,
+        #[indexed] can_record_sale: bool,
+        #[indexed] can_flag: bool,
+    );
+}
@@ -0,0 +1,95 @@
+use multiversx_sc::derive_imports::*;
+use multiversx_sc::imports::*;
+use wine_common::errors::*;
+
+use crate::registration::WineRegistration;
+
+pub const MAX_BLEND_COMPONENTS: usize = 10;
+// Shares are in basis points and must add up to 100%
+pub const TOTAL_BLEND_SHARE_BPS: u32 = 10_000;
+
+#[derive(TopEncode, TopDecode, NestedEncode, NestedDecode, TypeAbi, PartialEq, Debug)]
+pub struct BlendComponent {
+    pub wine_id: u32,
+    pub share_bps: u32,
+}
+
+#[multiversx_sc::module]
+pub trait BlendsModule:
+    wine_roles::RolesModule
+    + crate::admin::AdminModule
+    + crate::provenance::ProvenanceModule
+    + crate::read_model::ReadModelModule
+    + crate::registration::RegistrationModule
+{
+    // Register a wine blended from other registered lots, given as (source wine id, share in
+    // basis points) pairs. The caller must own every source lot. Costs one registration fee.
+    #[payable("*")]
+    #[endpoint(registerBlend)]
+    fn register_blend(
+        &self,
+        registration: WineRegistration<Self::Api>,
+        components: MultiValueEncoded<MultiValue2<u32, u32>>,
+    ) -> u32 {
+        self.require_not_paused();
+        require!(components.len() >= 2, ERR_A_BLEND_NEEDS_AT_LEAST_TWO_SOURCE_WINES);
+        require!(components.len() <= MAX_BLEND_COMPONENTS, ERR_TOO_MANY_SOURCE_WINES);
+
+        self.collect_registration_fee(1);
+        if let Some(error) = self.wine_registration_error(&registration) {
+            sc_panic!(error);
+        }
+
+        let caller = self.blockchain().get_caller();
+        let mut blend_components = ManagedVec::<Self::Api, u32>::new();
+        let mut total_share_bps = 0u32;
+        for component in components.clone().into_iter() {
+            let (source_wine_id, share_bps) = component.into_tuple();
+            self.require_wine_exists(source_wine_id);
+            require!(
+                caller == self.wine_owner(source_wine_id).get(),
+                ERR_ONLY_THE_OWNER_OF_A_SOURCE_WINE_CAN_BLEND_IT
+            );
+            require!(!blend_components.contains(&source_wine_id), ERR_DUPLICATE_SOURCE_WINE);
+            require!(share_bps > 0, ERR_SHARE_MUST_BE_GREATER_THAN_0);
+
+            blend_components.push(source_wine_id);
+            total_share_bps += share_bps;
+        }
+        require!(total_share_bps == TOTAL_BLEND_SHARE_BPS, ERR_SHARES_MUST_ADD_UP_TO_100_PERCENT);
+
+        self.verify_producer_signature(&caller, &registration);
+        let wine_id = self.store_wine(&caller, registration);
+        for component in components.into_iter() {
+            let (source_wine_id, share_bps) = component.into_tuple();
+            self.blend_composition(wine_id).push(&BlendComponent {
+                wine_id: source_wine_id,
+                share_bps,
+            });
+        }
+
+        self.blend_registered_event(wine_id, &caller, blend_components.len());
+
+        wine_id
+    }
+
+    // View functions
+    // Empty for wines that are not blends
+    #[view(getBlendComposition)]
+    fn get_blend_composition(&self, wine_id: u32) -> MultiValueEncoded<BlendComponent> {
+        self.blend_composition(wine_id).iter().collect()
+    }
+
+    // Storage mappers
+    #[storage_mapper("blendComposition")]
+    fn blend_composition(&self, wine_id: u32) -> VecMapper<BlendComponent>;
+
+    // Events
+    #[event("blendRegistered")]
+    fn blend_registered_event(
+        &self,
+        #[indexed] wine_id: u32,
+        #[indexed] producer: &ManagedAddress,
+        component_count: usize,
+    );
+}
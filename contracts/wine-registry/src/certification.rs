@@ -0,0 +1,551 @@
+use multiversx_sc::derive_imports::*;
+use multiversx_sc::imports::*;
+use wine_common::errors::*;
+use wine_common::ipfs::is_valid_ipfs_cid;
+use wine_roles::Role;
+
+use crate::pagination::{Page, MAX_CERTIFICATION_INDEXES_PAGE_SIZE};
+
+// What a certification attests. Authorities can only issue the classes they were granted.
+#[derive(
+    TopEncode, TopDecode, NestedEncode, NestedDecode, TypeAbi, PartialEq, Eq, Debug, Clone, Copy,
+)]
+pub enum CertificationType {
+    Organic,
+    Appellation,
+    Quality,
+    Export,
+}
+
+#[derive(TopEncode, TopDecode, TypeAbi, PartialEq, Debug)]
+pub struct CertificationAuthority<M: ManagedTypeApi> {
+    pub name: ManagedBuffer<M>,
+    pub authority_address: ManagedAddress<M>,
+    pub is_active: bool,
+    pub certification_count: u32,
+}
+
+#[derive(TopEncode, TopDecode, TypeAbi, PartialEq, Debug)]
+pub struct CertificationRevocation<M: ManagedTypeApi> {
+    pub revoked_by: ManagedAddress<M>,
+    pub reason: ManagedBuffer<M>,
+    pub timestamp: u64,
+}
+
+// A certification backed by an attestation published on the attestations contract, which
+// third-party tooling can verify against the issuer's signature
+#[derive(TopEncode, TopDecode, TypeAbi, PartialEq, Debug)]
+pub struct AttestationReference<M: ManagedTypeApi> {
+    pub attestation_contract: ManagedAddress<M>,
+    pub attestation_id: u64,
+    // Expiry of the attestation, after which the certification no longer counts as valid
+    // for marketplaces; 0 for attestations that do not expire
+    pub expiry_timestamp: u64,
+}
+
+#[multiversx_sc::module]
+pub trait CertificationModule:
+    wine_roles::RolesModule
+    + crate::admin::AdminModule
+    + crate::provenance::ProvenanceModule
+    + crate::read_model::ReadModelModule
+    + crate::registration::RegistrationModule
+{
+    // Certification authority management
+    #[endpoint(addCertificationAuthority)]
+    fn add_certification_authority(
+        &self,
+        name: ManagedBuffer,
+        authority_address: ManagedAddress,
+    ) -> u32 {
+        self.require_role(Role::Admin);
+        let authority_id = self.authority_counter().get();
+        
+        let authority = CertificationAuthority {
+            name,
+            authority_address,
+            is_active: true,
+            certification_count: 0u32,
+        };
+        
+        self.certification_authorities(authority_id).set(&authority);
+        self.authority_counter().set(authority_id + 1);
+        
+        self.authority_added_event(authority_id, &authority.authority_address);
+        
+        authority_id
+    }
+
+    // A deactivated authority cannot certify until reactivated; its past certifications stay
+    #[endpoint(deactivateAuthority)]
+    fn deactivate_authority(&self, authority_id: u32) {
+        self.require_role(Role::Admin);
+        self.require_authority_exists(authority_id);
+        let mut authority = self.certification_authorities(authority_id).get();
+        require!(authority.is_active, ERR_AUTHORITY_IS_ALREADY_INACTIVE);
+
+        authority.is_active = false;
+        self.certification_authorities(authority_id).set(&authority);
+
+        self.authority_deactivated_event(authority_id, &authority.authority_address);
+    }
+
+    #[endpoint(reactivateAuthority)]
+    fn reactivate_authority(&self, authority_id: u32) {
+        self.require_role(Role::Admin);
+        self.require_authority_exists(authority_id);
+        let mut authority = self.certification_authorities(authority_id).get();
+        require!(!authority.is_active, ERR_AUTHORITY_IS_ALREADY_ACTIVE);
+
+        authority.is_active = true;
+        self.certification_authorities(authority_id).set(&authority);
+
+        self.authority_reactivated_event(authority_id, &authority.authority_address);
+    }
+
+    // Removal is permanent: the id is not reused and the address would have to be added again
+    #[endpoint(removeAuthority)]
+    fn remove_authority(&self, authority_id: u32) {
+        self.require_role(Role::Admin);
+        self.require_authority_exists(authority_id);
+        let authority = self.certification_authorities(authority_id).take();
+        self.authority_classes(authority_id).clear();
+
+        self.authority_removed_event(authority_id, &authority.authority_address);
+    }
+
+    #[endpoint(grantAuthorityClass)]
+    fn grant_authority_class(&self, authority_id: u32, class: CertificationType) {
+        self.require_role(Role::Admin);
+        self.require_authority_exists(authority_id);
+        require!(self.authority_classes(authority_id).insert(class), ERR_CLASS_ALREADY_GRANTED);
+        self.authority_class_granted_event(authority_id, class);
+    }
+
+    #[endpoint(revokeAuthorityClass)]
+    fn revoke_authority_class(&self, authority_id: u32, class: CertificationType) {
+        self.require_role(Role::Admin);
+        self.require_authority_exists(authority_id);
+        require!(self.authority_classes(authority_id).swap_remove(&class), ERR_CLASS_NOT_GRANTED);
+        self.authority_class_revoked_event(authority_id, class);
+    }
+
+    // Attestations contract that certifications can reference
+    #[endpoint(setAttestationContract)]
+    fn set_attestation_contract(&self, attestation_contract: ManagedAddress) {
+        self.require_role(Role::Admin);
+        require!(
+            self.blockchain().is_smart_contract(&attestation_contract),
+            ERR_ATTESTATION_CONTRACT_MUST_BE_A_SMART_CONTRACT
+        );
+        self.attestation_contract().set(&attestation_contract);
+    }
+
+    // Certify wine by authority, for a class the authority holds. An attestation refers to
+    // the configured attestations contract by id, with the expiry it was published with, and
+    // can stand in for the certification hash.
+    #[endpoint(certifyWine)]
+    fn certify_wine(
+        &self,
+        wine_id: u32,
+        certification_type: CertificationType,
+        certification_hash: ManagedBuffer,
+        opt_attestation: OptionalValue<MultiValue2<u64, u64>>,
+    ) {
+        self.require_not_paused();
+        self.require_wine_exists(wine_id);
+        
+        let caller = self.blockchain().get_caller();
+        require!(self.is_certification_authority(&caller), ERR_NOT_A_CERTIFICATION_AUTHORITY);
+        let authority_id = self.get_authority_id(&caller);
+        require!(
+            self.authority_classes(authority_id).contains(&certification_type),
+            ERR_AUTHORITY_NOT_ALLOWED_TO_ISSUE_THIS_CERTIFICATION_TYPE
+        );
+        let opt_attestation =
+            opt_attestation.into_option().map(|attestation| attestation.into_tuple());
+        let opt_attestation_id = opt_attestation.map(|(attestation_id, _)| attestation_id);
+        // The hash may only be left out when an attestation stands in for it
+        require!(
+            is_valid_ipfs_cid(&certification_hash)
+                || (certification_hash.is_empty() && opt_attestation.is_some()),
+            ERR_INVALID_IPFS_CID
+        );
+        
+        let index = self.wine_certifications(wine_id).push(&certification_hash);
+        self.certification_type(wine_id, index).set(certification_type);
+        self.certifications_by_type(wine_id, certification_type).push(&index);
+        let details_version = self.get_wine_details_version(wine_id);
+        self.certification_details_version(wine_id, index).set(details_version);
+        self.certification_issuer(wine_id, index).set(&caller);
+        self.wine_certified_by(wine_id, &caller).set(&true);
+        if let Some((attestation_id, expiry_timestamp)) = opt_attestation {
+            require!(!self.attestation_contract().is_empty(), ERR_ATTESTATION_CONTRACT_NOT_SET);
+            require!(
+                expiry_timestamp == 0 || expiry_timestamp > self.blockchain().get_block_timestamp(),
+                ERR_ATTESTATION_HAS_EXPIRED
+            );
+            self.certification_attestation(wine_id, index).set(&AttestationReference {
+                attestation_contract: self.attestation_contract().get(),
+                attestation_id,
+                expiry_timestamp,
+            });
+        }
+        
+        // Update authority certification count
+        self.certification_authorities(authority_id).update(|authority| {
+            authority.certification_count += 1;
+        });
+        
+        let certified_until = self.certified_until(wine_id);
+        self.update_wine_read_model(wine_id, |read_model| {
+            read_model.certified = true;
+            read_model.certified_until = certified_until;
+        });
+        self.update_producer_stats(wine_id, |stats| stats.certifications += 1);
+
+        self.wine_certified_event(wine_id, &caller, certification_type, &certification_hash);
+        if let Some(attestation_id) = opt_attestation_id {
+            self.certification_attested_event(wine_id, index, attestation_id);
+        }
+    }
+
+    // Mark a certification (1-based index) as invalid; it stays in the wine's history. The
+    // issuing authority or the owner can revoke. The wine stays certified in the read model
+    // as long as another certification is still valid.
+    #[endpoint(revokeCertification)]
+    fn revoke_certification(&self, wine_id: u32, certification_index: usize, reason: ManagedBuffer) {
+        require!(
+            certification_index >= 1 && certification_index <= self.wine_certifications(wine_id).len(),
+            ERR_CERTIFICATION_DOES_NOT_EXIST
+        );
+        require!(
+            self.certification_revocation(wine_id, certification_index).is_empty(),
+            ERR_CERTIFICATION_ALREADY_REVOKED
+        );
+
+        // Certifications issued before issuers were recorded can only be revoked by admins
+        let caller = self.blockchain().get_caller();
+        let issuer_mapper = self.certification_issuer(wine_id, certification_index);
+        let is_issuer = !issuer_mapper.is_empty() && issuer_mapper.get() == caller;
+        require!(
+            is_issuer || self.has_role(Role::Admin, &caller),
+            ERR_ONLY_ISSUING_AUTHORITY_OR_ADMIN_CAN_REVOKE
+        );
+
+        self.certification_revocation(wine_id, certification_index).set(&CertificationRevocation {
+            revoked_by: caller.clone(),
+            reason: reason.clone(),
+            timestamp: self.blockchain().get_block_timestamp(),
+        });
+        self.revoked_certification_count(wine_id).update(|count| *count += 1);
+
+        let issuer = if issuer_mapper.is_empty() {
+            ManagedAddress::zero()
+        } else {
+            issuer_mapper.get()
+        };
+        let authority_id = self.get_authority_id(&issuer);
+        if authority_id != 0 {
+            self.certification_authorities(authority_id).update(|authority| {
+                authority.certification_count = authority.certification_count.saturating_sub(1);
+            });
+        }
+
+        self.update_producer_stats(wine_id, |stats| stats.certifications -= 1);
+        let certified = self.get_valid_certification_count(wine_id) > 0;
+        let certified_until = if certified { self.certified_until(wine_id) } else { 0 };
+        self.update_wine_read_model(wine_id, |read_model| {
+            read_model.certified = certified;
+            read_model.certified_until = certified_until;
+        });
+
+        self.certification_revoked_event(wine_id, certification_index, &issuer, &caller, &reason);
+    }
+
+    // Internal helpers
+    // Latest expiry among the wine's unrevoked certifications, or 0 when one of them does not
+    // expire: only attested certifications carry an expiry
+    fn certified_until(&self, wine_id: u32) -> u64 {
+        let mut certified_until = 0;
+        for index in 1..=self.wine_certifications(wine_id).len() {
+            if !self.certification_revocation(wine_id, index).is_empty() {
+                continue;
+            }
+            let attestation_mapper = self.certification_attestation(wine_id, index);
+            if attestation_mapper.is_empty() {
+                return 0;
+            }
+            let expiry_timestamp = attestation_mapper.get().expiry_timestamp;
+            if expiry_timestamp == 0 {
+                return 0;
+            }
+            certified_until = certified_until.max(expiry_timestamp);
+        }
+        certified_until
+    }
+
+    fn require_authority_exists(&self, authority_id: u32) {
+        require!(
+            !self.certification_authorities(authority_id).is_empty(),
+            ERR_AUTHORITY_DOES_NOT_EXIST
+        );
+    }
+
+    // View functions
+    #[view(getCertificationAuthority)]
+    fn get_certification_authority(&self, authority_id: u32) -> CertificationAuthority<Self::Api> {
+        self.require_authority_exists(authority_id);
+        self.certification_authorities(authority_id).get()
+    }
+
+    // Details version the certification at `index` (1-based) was issued against
+    #[view(getCertificationDetailsVersion)]
+    fn get_certification_details_version(&self, wine_id: u32, index: usize) -> u32 {
+        require!(
+            index >= 1 && index <= self.wine_certifications(wine_id).len(),
+            ERR_CERTIFICATION_DOES_NOT_EXIST
+        );
+        // Certifications issued before versioning existed refer to the registered version
+        let version_mapper = self.certification_details_version(wine_id, index);
+        if version_mapper.is_empty() {
+            1
+        } else {
+            version_mapper.get()
+        }
+    }
+
+    // None for certifications issued before types were recorded
+    #[view(getCertificationType)]
+    fn get_certification_type(&self, wine_id: u32, index: usize) -> OptionalValue<CertificationType> {
+        let type_mapper = self.certification_type(wine_id, index);
+        if type_mapper.is_empty() {
+            OptionalValue::None
+        } else {
+            OptionalValue::Some(type_mapper.get())
+        }
+    }
+
+    // Paginated: returns the next cursor (0 when done) followed by at most `page_size`
+    // certification indexes (1-based) of the given type, revoked ones included
+    #[view(getCertificationsByType)]
+    fn get_certifications_by_type(
+        &self,
+        wine_id: u32,
+        certification_type: CertificationType,
+        cursor: u64,
+        page_size: u32,
+    ) -> MultiValue2<u64, MultiValueEncoded<usize>> {
+        let indexes = self.certifications_by_type(wine_id, certification_type);
+        let page = Page::new(cursor, page_size, MAX_CERTIFICATION_INDEXES_PAGE_SIZE, indexes.len());
+
+        let mut result = MultiValueEncoded::new();
+        for index in page.start..page.end {
+            result.push(indexes.get(index + 1));
+        }
+        (page.next_cursor, result).into()
+    }
+
+    #[view(getAuthorityClasses)]
+    fn get_authority_classes(&self, authority_id: u32) -> MultiValueEncoded<CertificationType> {
+        self.authority_classes(authority_id).iter().collect()
+    }
+
+    // Zero address for certifications issued before issuers were recorded
+    #[view(getCertificationIssuer)]
+    fn get_certification_issuer(&self, wine_id: u32, index: usize) -> ManagedAddress {
+        let issuer_mapper = self.certification_issuer(wine_id, index);
+        if issuer_mapper.is_empty() {
+            ManagedAddress::zero()
+        } else {
+            issuer_mapper.get()
+        }
+    }
+
+    #[view(getCertificationAttestation)]
+    fn get_certification_attestation(
+        &self,
+        wine_id: u32,
+        index: usize,
+    ) -> OptionalValue<AttestationReference<Self::Api>> {
+        let attestation_mapper = self.certification_attestation(wine_id, index);
+        if attestation_mapper.is_empty() {
+            OptionalValue::None
+        } else {
+            OptionalValue::Some(attestation_mapper.get())
+        }
+    }
+
+    #[view(getCertificationRevocation)]
+    fn get_certification_revocation(
+        &self,
+        wine_id: u32,
+        index: usize,
+    ) -> OptionalValue<CertificationRevocation<Self::Api>> {
+        let revocation_mapper = self.certification_revocation(wine_id, index);
+        if revocation_mapper.is_empty() {
+            OptionalValue::None
+        } else {
+            OptionalValue::Some(revocation_mapper.get())
+        }
+    }
+
+    #[view(getValidCertificationCount)]
+    fn get_valid_certification_count(&self, wine_id: u32) -> usize {
+        self.wine_certifications(wine_id).len() - self.revoked_certification_count(wine_id).get()
+    }
+
+    // Removed authorities are skipped
+    #[view(isCertificationAuthority)]
+    fn is_certification_authority(&self, address: &ManagedAddress) -> bool {
+        let authority_count = self.authority_counter().get();
+        for i in 1..authority_count {
+            if self.certification_authorities(i).is_empty() {
+                continue;
+            }
+            let authority = self.certification_authorities(i).get();
+            if authority.authority_address == *address && authority.is_active {
+                return true;
+            }
+        }
+        false
+    }
+
+    #[view(getAuthorityId)]
+    fn get_authority_id(&self, address: &ManagedAddress) -> u32 {
+        let authority_count = self.authority_counter().get();
+        for i in 1..authority_count {
+            if self.certification_authorities(i).is_empty() {
+                continue;
+            }
+            let authority = self.certification_authorities(i).get();
+            if authority.authority_address == *address {
+                return i;
+            }
+        }
+        0
+    }
+
+    // Storage mappers
+    #[storage_mapper("wineCertifications")]
+    fn wine_certifications(&self, wine_id: u32) -> VecMapper<ManagedBuffer>;
+
+    #[storage_mapper("certificationDetailsVersion")]
+    fn certification_details_version(&self, wine_id: u32, index: usize) -> SingleValueMapper<u32>;
+
+    #[storage_mapper("certificationType")]
+    fn certification_type(&self, wine_id: u32, index: usize) -> SingleValueMapper<CertificationType>;
+
+    #[storage_mapper("certificationsByType")]
+    fn certifications_by_type(
+        &self,
+        wine_id: u32,
+        certification_type: CertificationType,
+    ) -> VecMapper<usize>;
+
+    #[storage_mapper("authorityClasses")]
+    fn authority_classes(&self, authority_id: u32) -> UnorderedSetMapper<CertificationType>;
+
+    #[storage_mapper("certificationIssuer")]
+    fn certification_issuer(&self, wine_id: u32, index: usize) -> SingleValueMapper<ManagedAddress>;
+
+    #[storage_mapper("certificationRevocation")]
+    fn certification_revocation(
+        &self,
+        wine_id: u32,
+        index: usize,
+    ) -> SingleValueMapper<CertificationRevocation<Self::Api>>;
+
+    #[storage_mapper("revokedCertificationCount")]
+    fn revoked_certification_count(&self, wine_id: u32) -> SingleValueMapper<usize>;
+
+    #[storage_mapper("wineCertifiedBy")]
+    fn wine_certified_by(&self, wine_id: u32, authority: &ManagedAddress) -> SingleValueMapper<bool>;
+
+    #[storage_mapper("certificationAuthorities")]
+    fn certification_authorities(&self, authority_id: u32) -> SingleValueMapper<CertificationAuthority<Self::Api>>;
+
+    #[storage_mapper("authorityCounter")]
+    fn authority_counter(&self) -> SingleValueMapper<u32>;
+
+    #[view(getAttestationContract)]
+    #[storage_mapper("attestationContract")]
+    fn attestation_contract(&self) -> SingleValueMapper<ManagedAddress>;
+
+    #[storage_mapper("certificationAttestation")]
+    fn certification_attestation(
+        &self,
+        wine_id: u32,
+        index: usize,
+    ) -> SingleValueMapper<AttestationReference<Self::Api>>;
+
+    // Events
+    #[event("wineCertified")]
+    fn wine_certified_event(
+        &self,
+        #[indexed] wine_id: u32,
+        #[indexed] authority: &ManagedAddress,
+        #[indexed] certification_type: CertificationType,
+        certification_hash: &ManagedBuffer,
+    );
+
+    #[event("certificationAttested")]
+    fn certification_attested_event(
+        &self,
+        #[indexed] wine_id: u32,
+        #[indexed] certification_index: usize,
+        #[indexed] attestation_id: u64,
+    );
+
+    #[event("certificationRevoked")]
+    fn certification_revoked_event(
+        &self,
+        #[indexed] wine_id: u32,
+        #[indexed] certification_index: usize,
+        #[indexed] authority: &ManagedAddress,
+        #[indexed] revoked_by: &ManagedAddress,
+        reason: &ManagedBuffer,
+    );
+
+    #[event("authorityAdded")]
+    fn authority_added_event(
+        &self,
+        #[indexed] authority_id: u32,
+        #[indexed] authority_address: &ManagedAddress,
+    );
+
+    #[event("authorityDeactivated")]
+    fn authority_deactivated_event(
+        &self,
+        #[indexed] authority_id: u32,
+        #[indexed] authority_address: &ManagedAddress,
+    );
+
+    #[event("authorityReactivated")]
+    fn authority_reactivated_event(
+        &self,
+        #[indexed] authority_id: u32,
+        #[indexed] authority_address: &ManagedAddress,
+    );
+
+    #[event("authorityClassGranted")]
+    fn authority_class_granted_event(
+        &self,
+        #[indexed] authority_id: u32,
+        #[indexed] class: CertificationType,
+    );
+
+    #[event("authorityClassRevoked")]
+    fn authority_class_revoked_event(
+        &self,
+        #[indexed] authority_id: u32,
+        #[indexed] class: CertificationType,
+    );
+
+    #[event("authorityRemoved")]
+    fn authority_removed_event(
+        &self,
+        #[indexed] authority_id: u32,
+        #[indexed] authority_address: &ManagedAddress,
+    );
+}
@@ -0,0 +1,188 @@
+use multiversx_sc::derive_imports::*;
+use multiversx_sc::imports::*;
+use wine_common::errors::*;
+use wine_common::ipfs::is_valid_ipfs_cid;
+use wine_roles::Role;
+
+use crate::pagination::{Page, MAX_IPFS_HASH_LENGTH, MAX_REPORT_IDS_PAGE_SIZE};
+
+#[derive(TopEncode, TopDecode, NestedEncode, NestedDecode, TypeAbi, PartialEq, Debug, Clone, Copy)]
+pub enum CounterfeitReportStatus {
+    Open,
+    Dismissed,
+    Confirmed,
+}
+
+#[derive(TopEncode, TopDecode, TypeAbi, PartialEq, Debug)]
+pub struct CounterfeitReport<M: ManagedTypeApi> {
+    pub wine_id: u32,
+    pub reporter: ManagedAddress<M>,
+    pub evidence_ipfs_hash: ManagedBuffer<M>,
+    pub timestamp: u64,
+    pub status: CounterfeitReportStatus,
+    // Zero address and 0 while the report is open
+    pub resolved_by: ManagedAddress<M>,
+    pub resolved_timestamp: u64,
+}
+
+// Anyone can report a suspected counterfeit; certification authorities investigate and
+// resolve. A confirmed report flags the wine in the read model, so subscribed marketplaces
+// stop accepting new listings of it.
+#[multiversx_sc::module]
+pub trait CounterfeitModule:
+    wine_roles::RolesModule
+    + crate::admin::AdminModule
+    + crate::provenance::ProvenanceModule
+    + crate::read_model::ReadModelModule
+    + crate::registration::RegistrationModule
+    + crate::certification::CertificationModule
+{
+    #[endpoint(reportCounterfeit)]
+    fn report_counterfeit(&self, wine_id: u32, evidence_ipfs_hash: ManagedBuffer) -> usize {
+        self.require_wine_exists(wine_id);
+        require!(!evidence_ipfs_hash.is_empty(), ERR_EVIDENCE_IS_REQUIRED);
+        require!(evidence_ipfs_hash.len() <= MAX_IPFS_HASH_LENGTH, ERR_EVIDENCE_HASH_IS_TOO_LONG);
+        require!(is_valid_ipfs_cid(&evidence_ipfs_hash), ERR_INVALID_IPFS_CID);
+
+        let reporter = self.blockchain().get_caller();
+        let report_id = self.counterfeit_reports().push(&CounterfeitReport {
+            wine_id,
+            reporter: reporter.clone(),
+            evidence_ipfs_hash,
+            timestamp: self.blockchain().get_block_timestamp(),
+            status: CounterfeitReportStatus::Open,
+            resolved_by: ManagedAddress::zero(),
+            resolved_timestamp: 0,
+        });
+        self.open_counterfeit_reports().insert(report_id);
+        self.wine_counterfeit_reports(wine_id).push(&report_id);
+
+        self.counterfeit_reported_event(report_id, wine_id, &reporter);
+
+        report_id
+    }
+
+    #[endpoint(dismissCounterfeitReport)]
+    fn dismiss_counterfeit_report(&self, report_id: usize) {
+        self.resolve_counterfeit_report(report_id, CounterfeitReportStatus::Dismissed);
+    }
+
+    #[endpoint(confirmCounterfeitReport)]
+    fn confirm_counterfeit_report(&self, report_id: usize) {
+        let wine_id = self.resolve_counterfeit_report(report_id, CounterfeitReportStatus::Confirmed);
+        if !self.require_wine_read_model(wine_id).counterfeit {
+            self.update_wine_read_model(wine_id, |read_model| read_model.counterfeit = true);
+            self.wine_flagged_counterfeit_event(wine_id, report_id);
+        }
+    }
+
+    // Lift the flag, e.g. after a confirmation turns out to be wrong
+    #[endpoint(clearCounterfeitFlag)]
+    fn clear_counterfeit_flag(&self, wine_id: u32) {
+        self.require_role(Role::Admin);
+        require!(self.require_wine_read_model(wine_id).counterfeit, ERR_WINE_IS_NOT_FLAGGED);
+        self.update_wine_read_model(wine_id, |read_model| read_model.counterfeit = false);
+        self.counterfeit_flag_cleared_event(wine_id);
+    }
+
+    // Internal helpers
+    // Returns the reported wine
+    fn resolve_counterfeit_report(&self, report_id: usize, status: CounterfeitReportStatus) -> u32 {
+        let caller = self.blockchain().get_caller();
+        require!(self.is_certification_authority(&caller), ERR_NOT_A_CERTIFICATION_AUTHORITY);
+        require!(self.open_counterfeit_reports().swap_remove(&report_id), ERR_REPORT_IS_NOT_OPEN);
+
+        let mut report = self.counterfeit_reports().get(report_id);
+        report.status = status;
+        report.resolved_by = caller.clone();
+        report.resolved_timestamp = self.blockchain().get_block_timestamp();
+        self.counterfeit_reports().set(report_id, &report);
+
+        self.counterfeit_report_resolved_event(report_id, report.wine_id, status, &caller);
+
+        report.wine_id
+    }
+
+    // View functions
+    #[view(getCounterfeitReport)]
+    fn get_counterfeit_report(&self, report_id: usize) -> CounterfeitReport<Self::Api> {
+        let reports = self.counterfeit_reports();
+        require!(report_id >= 1 && report_id <= reports.len(), ERR_REPORT_DOES_NOT_EXIST);
+        reports.get(report_id)
+    }
+
+    // Paginated: returns the next cursor (0 when done) followed by at most `page_size` report
+    // ids. Order is not stable across resolutions.
+    #[view(getOpenCounterfeitReports)]
+    fn get_open_counterfeit_reports(
+        &self,
+        cursor: u64,
+        page_size: u32,
+    ) -> MultiValue2<u64, MultiValueEncoded<usize>> {
+        let open_reports = self.open_counterfeit_reports();
+        let page = Page::new(cursor, page_size, MAX_REPORT_IDS_PAGE_SIZE, open_reports.len());
+
+        let mut result = MultiValueEncoded::new();
+        for index in page.start..page.end {
+            result.push(open_reports.get_by_index(index + 1));
+        }
+        (page.next_cursor, result).into()
+    }
+
+    // Paginated: returns the next cursor (0 when done) followed by at most `page_size` report
+    // ids, oldest first
+    #[view(getWineCounterfeitReports)]
+    fn get_wine_counterfeit_reports(
+        &self,
+        wine_id: u32,
+        cursor: u64,
+        page_size: u32,
+    ) -> MultiValue2<u64, MultiValueEncoded<usize>> {
+        let wine_reports = self.wine_counterfeit_reports(wine_id);
+        let page = Page::new(cursor, page_size, MAX_REPORT_IDS_PAGE_SIZE, wine_reports.len());
+
+        let mut result = MultiValueEncoded::new();
+        for index in page.start..page.end {
+            result.push(wine_reports.get(index + 1));
+        }
+        (page.next_cursor, result).into()
+    }
+
+    // Storage mappers
+    #[storage_mapper("counterfeitReports")]
+    fn counterfeit_reports(&self) -> VecMapper<CounterfeitReport<Self::Api>>;
+
+    #[storage_mapper("openCounterfeitReports")]
+    fn open_counterfeit_reports(&self) -> UnorderedSetMapper<usize>;
+
+    #[storage_mapper("wineCounterfeitReports")]
+    fn wine_counterfeit_reports(&self, wine_id: u32) -> VecMapper<usize>;
+
+    // Events
+    #[event("counterfeitReported")]
+    fn counterfeit_reported_event(
+        &self,
+        #[indexed] report_id: usize,
+        #[indexed] wine_id: u32,
+        reporter: &ManagedAddress,
+    );
+
+    #[event("counterfeitReportResolved")]
+    fn counterfeit_report_resolved_event(
+        &self,
+        #[indexed] report_id: usize,
+        #[indexed] wine_id: u32,
+        #[indexed] status: CounterfeitReportStatus,
+        resolved_by: &ManagedAddress,
+    );
+
+    #[event("wineFlaggedCounterfeit")]
+    fn wine_flagged_counterfeit_event(
+        &self,
+        #[indexed] wine_id: u32,
+        report_id: usize,
+    );
+
+    #[event("counterfeitFlagCleared")]
+    fn counterfeit_flag_cleared_event(&self, #[indexed] wine_id: u32);
+}
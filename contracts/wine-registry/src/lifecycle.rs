@@ -0,0 +1,199 @@
+use multiversx_sc::derive_imports::*;
+use multiversx_sc::imports::*;
+use wine_common::errors::*;
+pub use wine_common::WineStatus;
+
+use crate::provenance::WineAction;
+use crate::proxies::wine_marketplace_proxy::WineMarketplaceProxy;
+
+// Recall-triggered listing cancellation runs as its own call, so it gets its own budget
+pub const RECALL_CANCEL_GAS: u64 = 30_000_000;
+
+#[derive(TopEncode, TopDecode, NestedEncode, NestedDecode, TypeAbi, PartialEq, Debug)]
+pub struct WineStatusChange<M: ManagedTypeApi> {
+    pub status: WineStatus,
+    pub timestamp: u64,
+    pub changed_by: ManagedAddress<M>,
+}
+
+// Wines start out Harvested when registered. The status lives in the read model, so every
+// change reaches subscribed marketplaces.
+#[multiversx_sc::module]
+pub trait LifecycleModule:
+    wine_roles::RolesModule
+    + crate::admin::AdminModule
+    + crate::provenance::ProvenanceModule
+    + crate::read_model::ReadModelModule
+    + crate::registration::RegistrationModule
+    + crate::certification::CertificationModule
+    + crate::minting::MintingModule
+    + crate::rewards::RewardsModule
+    + crate::ratings::RatingsModule
+{
+    // Move a wine forward through its production stages (skipping is allowed), or into
+    // Recalled/Consumed once bottled. Producer or certification authorities only.
+    #[endpoint(setWineStatus)]
+    fn set_wine_status(&self, wine_id: u32, status: WineStatus) {
+        self.change_wine_status(wine_id, status);
+    }
+
+    // Recall a bottled or released wine: availability is frozen and subscribed marketplaces
+    // stop accepting new listings. If `opt_cancel_listings` is set, they are also asked to
+    // cancel the wine's active listings.
+    #[endpoint(recallWine)]
+    fn recall_wine(
+        &self,
+        wine_id: u32,
+        reason: ManagedBuffer,
+        opt_cancel_listings: OptionalValue<bool>,
+    ) {
+        self.change_wine_status(wine_id, WineStatus::Recalled);
+        self.wine_recall_reason(wine_id).set(&reason);
+
+        if opt_cancel_listings.into_option().unwrap_or(false) {
+            for marketplace in self.read_model_subscribers().iter() {
+                self.tx()
+                    .to(&marketplace)
+                    .typed(WineMarketplaceProxy)
+                    .cancel_recalled_listings(wine_id)
+                    .gas(RECALL_CANCEL_GAS)
+                    .transfer_execute();
+            }
+        }
+
+        self.wine_recalled_event(wine_id, &self.blockchain().get_caller(), &reason);
+    }
+
+    // Withdraw a wine that never had bottles minted. Its details, ratings and search index
+    // entries are cleared; the id, owner, provenance and status history remain, and the wine
+    // can no longer be rated, certified, minted or listed.
+    #[endpoint(retireWine)]
+    fn retire_wine(&self, wine_id: u32) {
+        self.require_wine_exists(wine_id);
+        let caller = self.blockchain().get_caller();
+        require!(caller == self.wine_producer(wine_id), ERR_ONLY_PRODUCER_CAN_RETIRE_THE_WINE);
+        require!(self.minted_bottles(wine_id).get() == 0, ERR_WINE_HAS_MINTED_BOTTLES);
+
+        // Its ratings are removed, so they stop counting towards the producer's stats
+        let community = self.rating_totals(wine_id);
+        let professional = self.professional_rating_totals(wine_id);
+        self.update_producer_stats(wine_id, |stats| {
+            stats.remove_ratings(community.sum, community.count);
+            stats.remove_ratings(professional.sum, professional.count);
+        });
+
+        let wine = self.load_wine_details(wine_id);
+        self.clear_wine_details(wine_id);
+        self.unindex_wine(wine_id, &wine);
+        self.wine_details_history(wine_id).clear();
+        self.wine_price_history(wine_id).clear();
+        self.wine_ratings(wine_id).clear();
+        self.wine_rating_totals(wine_id).clear();
+        self.wine_average_rating(wine_id).clear();
+        self.wine_weighted_average_rating(wine_id).clear();
+        self.wine_professional_rating_totals(wine_id).clear();
+        self.wine_professional_average_rating(wine_id).clear();
+
+        self.update_wine_read_model(wine_id, |read_model| read_model.status = WineStatus::Retired);
+        self.wine_status_history(wine_id).push(&WineStatusChange {
+            status: WineStatus::Retired,
+            timestamp: self.blockchain().get_block_timestamp(),
+            changed_by: caller.clone(),
+        });
+
+        self.wine_status_changed_event(wine_id, &caller, WineStatus::Retired);
+        self.wine_retired_event(wine_id, &caller);
+    }
+
+    // Internal helpers
+    fn change_wine_status(&self, wine_id: u32, status: WineStatus) {
+        self.require_wine_exists(wine_id);
+        let caller = self.blockchain().get_caller();
+        require!(
+            caller == self.wine_owner(wine_id).get() || self.is_certification_authority(&caller),
+            ERR_ONLY_PRODUCER_OR_CERTIFICATION_AUTHORITY_CAN_CHANGE_STATUS
+        );
+
+        let current_status = self.require_wine_read_model(wine_id).status;
+        require!(
+            self.is_valid_status_transition(current_status, status),
+            ERR_INVALID_STATUS_TRANSITION
+        );
+        // Authorities recall on their own; an owner may need co-owner approval
+        if status == WineStatus::Recalled && !self.is_certification_authority(&caller) {
+            self.require_co_owner_approval(wine_id, &caller, WineAction::Recall);
+        }
+
+        self.update_wine_read_model(wine_id, |read_model| read_model.status = status);
+        self.wine_status_history(wine_id).push(&WineStatusChange {
+            status,
+            timestamp: self.blockchain().get_block_timestamp(),
+            changed_by: caller.clone(),
+        });
+
+        self.wine_status_changed_event(wine_id, &caller, status);
+    }
+
+    fn is_valid_status_transition(&self, from: WineStatus, to: WineStatus) -> bool {
+        match to {
+            WineStatus::Retired => false,
+            WineStatus::Recalled | WineStatus::Consumed => {
+                from == WineStatus::Bottled || from == WineStatus::Released
+            },
+            _ => from < to && from < WineStatus::Recalled,
+        }
+    }
+
+    // View functions
+    #[view(getWineStatus)]
+    fn get_wine_status(&self, wine_id: u32) -> WineStatus {
+        self.require_wine_read_model(wine_id).status
+    }
+
+    // Status changes after registration, oldest first. Bounded by the number of statuses,
+    // since a wine only moves forward.
+    #[view(getWineStatusHistory)]
+    fn get_wine_status_history(&self, wine_id: u32) -> MultiValueEncoded<WineStatusChange<Self::Api>> {
+        self.wine_status_history(wine_id).iter().collect()
+    }
+
+    #[view(getWineRecallReason)]
+    fn get_wine_recall_reason(&self, wine_id: u32) -> OptionalValue<ManagedBuffer> {
+        if self.wine_recall_reason(wine_id).is_empty() {
+            OptionalValue::None
+        } else {
+            OptionalValue::Some(self.wine_recall_reason(wine_id).get())
+        }
+    }
+
+    // Storage mappers
+    #[storage_mapper("wineRecallReason")]
+    fn wine_recall_reason(&self, wine_id: u32) -> SingleValueMapper<ManagedBuffer>;
+
+    #[storage_mapper("wineStatusHistory")]
+    fn wine_status_history(&self, wine_id: u32) -> VecMapper<WineStatusChange<Self::Api>>;
+
+    // Events
+    #[event("wineStatusChanged")]
+    fn wine_status_changed_event(
+        &self,
+        #[indexed] wine_id: u32,
+        #[indexed] changed_by: &ManagedAddress,
+        #[indexed] status: WineStatus,
+    );
+
+    #[event("wineRetired")]
+    fn wine_retired_event(
+        &self,
+        #[indexed] wine_id: u32,
+        #[indexed] retired_by: &ManagedAddress,
+    );
+
+    #[event("wineRecalled")]
+    fn wine_recalled_event(
+        &self,
+        #[indexed] wine_id: u32,
+        #[indexed] recalled_by: &ManagedAddress,
+        reason: &ManagedBuffer,
+    );
+}
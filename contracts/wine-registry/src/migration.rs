@@ -0,0 +1,171 @@
+use multiversx_sc::derive_imports::*;
+use multiversx_sc::imports::*;
+use wine_common::errors::*;
+use wine_roles::Role;
+
+// Layout version of the stored WineDetails entries. Contracts deployed before versioning have
+// no version stored and are on version 1.
+//   1: initial devnet layout
+//   2: WineDetails gains `producer`
+//   3: producer stats are kept
+pub const STORAGE_VERSION: u32 = 3;
+// Storage version that introduced the current WineDetails layout
+pub const WINE_DETAILS_LAYOUT: u32 = 2;
+
+// WineDetails as stored by storage version 1
+#[derive(TopEncode, TopDecode, TypeAbi, PartialEq, Debug)]
+pub struct WineDetailsV1<M: ManagedTypeApi> {
+    pub vineyard: ManagedBuffer<M>,
+    pub variety: ManagedBuffer<M>,
+    pub vintage_year: u32,
+    pub production_date: u64,
+    pub quality_grade: u8,
+    pub alcohol_content: u16,
+    pub region: ManagedBuffer<M>,
+    pub certification: ManagedBuffer<M>,
+    pub producer_signature: ManagedBuffer<M>,
+    pub ipfs_hash: ManagedBuffer<M>,
+    pub total_bottles: u32,
+    pub available_bottles: u32,
+    pub price_per_bottle: BigUint<M>,
+    pub is_organic: bool,
+    pub harvest_date: u64,
+    pub aging_process: ManagedBuffer<M>,
+    pub tasting_notes: ManagedBuffer<M>,
+}
+
+// Each wine records the layout it was written with, so older entries stay readable right
+// after an upgrade: they are converted on read and rewritten on their next update. Running
+// `migrateStorage` until it reports completion rewrites the rest in batches.
+#[multiversx_sc::module]
+pub trait MigrationModule:
+    wine_roles::RolesModule
+    + crate::admin::AdminModule
+    + crate::provenance::ProvenanceModule
+    + crate::read_model::ReadModelModule
+    + crate::registration::RegistrationModule
+    + crate::certification::CertificationModule
+    + crate::minting::MintingModule
+    + crate::rewards::RewardsModule
+    + crate::ratings::RatingsModule
+{
+    // Rewrite up to `batch_size` entries of the current version step. Returns true once
+    // storage is at STORAGE_VERSION.
+    #[endpoint(migrateStorage)]
+    fn migrate_storage(&self, batch_size: u32) -> bool {
+        self.require_role(Role::Admin);
+        require!(batch_size > 0, ERR_BATCH_SIZE_MUST_BE_GREATER_THAN_ZERO);
+
+        let version = self.get_storage_version();
+        if version >= STORAGE_VERSION {
+            return true;
+        }
+
+        let step_done = match version {
+            1 => self.migrate_wine_details_v1_to_v2(batch_size),
+            2 => self.migrate_producer_stats_v2_to_v3(batch_size),
+            _ => sc_panic!(ERR_UNKNOWN_STORAGE_VERSION),
+        };
+        if step_done {
+            self.storage_version().set(version + 1);
+            self.migration_cursor().set(1u32);
+            self.storage_migrated_event(version + 1);
+        }
+
+        version + 1 >= STORAGE_VERSION && step_done
+    }
+
+    // Internal helpers
+    fn init_storage_version(&self) {
+        self.storage_version().set(STORAGE_VERSION);
+    }
+
+    fn begin_storage_migration(&self) {
+        if self.storage_version().is_empty() {
+            self.storage_version().set(1u32);
+        }
+        if self.migration_cursor().is_empty() {
+            self.migration_cursor().set(1u32);
+        }
+    }
+
+    fn migrate_wine_details_v1_to_v2(&self, batch_size: u32) -> bool {
+        let start = self.migration_cursor().get();
+        let end = core::cmp::min(start + batch_size, self.wine_counter().get());
+
+        for wine_id in start..end {
+            // Retired wines have no details left, and updated wines are already rewritten
+            let layout = self.wine_details_layout(wine_id).get();
+            if self.wine_details(wine_id).is_empty() || layout >= WINE_DETAILS_LAYOUT {
+                continue;
+            }
+            let wine = self.load_wine_details(wine_id);
+            self.store_wine_details(wine_id, &wine);
+        }
+        self.migration_cursor().set(end);
+
+        end >= self.wine_counter().get()
+    }
+
+    // Adds every wine registered before stats were kept to its producer's stats
+    fn migrate_producer_stats_v2_to_v3(&self, batch_size: u32) -> bool {
+        let start = self.migration_cursor().get();
+        let end = core::cmp::min(start + batch_size, self.wine_counter().get());
+
+        for wine_id in start..end {
+            if self.wine_owner(wine_id).is_empty() || self.producer_stats_counted(wine_id).get() {
+                continue;
+            }
+
+            let producer = self.wine_producer(wine_id);
+            let community = self.rating_totals(wine_id);
+            let professional = self.professional_rating_totals(wine_id);
+            let mut stats = self.load_producer_stats(&producer);
+            stats.wines_registered += 1;
+            stats.bottles_minted += self.minted_bottles(wine_id).get() as u64;
+            // Retired wines have no details left
+            if !self.wine_details(wine_id).is_empty() {
+                let wine = self.load_wine_details(wine_id);
+                stats.bottles_sold += (wine.total_bottles - wine.available_bottles) as u64;
+            }
+            stats.add_ratings(community.sum, community.count);
+            stats.add_ratings(professional.sum, professional.count);
+            stats.certifications += self.get_valid_certification_count(wine_id) as u32;
+
+            self.producer_stats(&producer).set(&stats);
+            self.producer_stats_counted(wine_id).set(true);
+        }
+        self.migration_cursor().set(end);
+
+        end >= self.wine_counter().get()
+    }
+
+    // View functions
+    #[view(getStorageVersion)]
+    fn get_storage_version(&self) -> u32 {
+        if self.storage_version().is_empty() {
+            1
+        } else {
+            self.storage_version().get()
+        }
+    }
+
+    #[view(getMigrationCursor)]
+    fn get_migration_cursor(&self) -> u32 {
+        self.migration_cursor().get()
+    }
+
+    // Storage mappers
+    #[storage_mapper("storageVersion")]
+    fn storage_version(&self) -> SingleValueMapper<u32>;
+
+    #[storage_mapper("migrationCursor")]
+    fn migration_cursor(&self) -> SingleValueMapper<u32>;
+
+    // Events
+    #[event("storageMigrated")]
+    fn storage_migrated_event(
+        &self,
+        #[indexed] storage_version: u32,
+    );
+}
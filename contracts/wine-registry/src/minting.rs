@@ -0,0 +1,291 @@
+use multiversx_sc::derive_imports::*;
+use multiversx_sc::imports::*;
+use wine_common::errors::*;
+use wine_roles::Role;
+pub use wine_common::WineNftAttributes;
+
+use crate::pagination::{Page, MAX_BOTTLES_PAGE_SIZE, MAX_BOTTLE_SCANS_PAGE_SIZE};
+
+// Every minted bottle gets its own record, so a single mint is bounded to stay within gas
+pub const MAX_MINT_BATCH: u32 = 500;
+
+// Bottles are minted Sealed and become Redeemed once claimed for physical delivery
+#[derive(TopEncode, TopDecode, NestedEncode, NestedDecode, TypeAbi, PartialEq, Debug, Clone, Copy)]
+pub enum BottleStatus {
+    Sealed,
+    Redeemed,
+}
+
+// One physical bottle. Serials are sequential per wine, starting at 1 in minting order.
+#[derive(TopEncode, TopDecode, NestedEncode, NestedDecode, TypeAbi, PartialEq, Debug)]
+pub struct Bottle {
+    pub nonce: u64,
+    pub serial: u32,
+    pub status: BottleStatus,
+    pub minted_timestamp: u64,
+}
+
+// One authentication attempt against a bottle's QR secret
+#[derive(TopEncode, TopDecode, NestedEncode, NestedDecode, TypeAbi, PartialEq, Debug)]
+pub struct BottleScan<M: ManagedTypeApi> {
+    pub scanner: ManagedAddress<M>,
+    pub timestamp: u64,
+    pub authentic: bool,
+}
+
+// Each wine is one SFT nonce in the wine collection, one unit per bottle. The first mint
+// creates the nonce, later mints add quantity to it, up to the wine's `total_bottles`.
+#[multiversx_sc::module]
+pub trait MintingModule:
+    wine_roles::RolesModule
+    + crate::admin::AdminModule
+    + crate::provenance::ProvenanceModule
+    + crate::read_model::ReadModelModule
+    + crate::registration::RegistrationModule
+{
+    // Issue the SFT collection with all roles assigned to the registry. Requires the issue
+    // cost in EGLD.
+    #[payable("EGLD")]
+    #[endpoint(issueWineCollection)]
+    fn issue_wine_collection(&self, token_display_name: ManagedBuffer, token_ticker: ManagedBuffer) {
+        self.require_role(Role::Admin);
+        let issue_cost = self.call_value().egld_value().clone_value();
+        self.wine_collection().issue_and_set_all_roles(
+            EsdtTokenType::SemiFungible,
+            issue_cost,
+            token_display_name,
+            token_ticker,
+            0,
+            Some(self.callbacks().wine_collection_issued()),
+        );
+    }
+
+    // Mint `quantity` bottles of a wine to its owner. Optionally pass one keccak256 hash per
+    // bottle, in serial order, of the secret printed in the bottle's QR code.
+    #[endpoint(mintWineNft)]
+    fn mint_wine_nft(
+        &self,
+        wine_id: u32,
+        quantity: u32,
+        secret_hashes: MultiValueEncoded<ManagedByteArray<Self::Api, 32>>,
+    ) -> u64 {
+        self.require_wine_exists(wine_id);
+        let caller = self.blockchain().get_caller();
+        require!(caller == self.wine_owner(wine_id).get(), ERR_ONLY_WINE_OWNER_CAN_MINT);
+        require!(quantity > 0, ERR_QUANTITY_MUST_BE_GREATER_THAN_0);
+        require!(quantity <= MAX_MINT_BATCH, ERR_MINT_BATCH_TOO_LARGE);
+        require!(
+            secret_hashes.is_empty() || secret_hashes.len() == quantity as usize,
+            ERR_EXPECTED_ONE_SECRET_HASH_PER_BOTTLE
+        );
+        require!(!self.wine_collection().is_empty(), ERR_WINE_COLLECTION_NOT_ISSUED);
+
+        let wine = self.load_wine_details(wine_id);
+        let minted = self.minted_bottles(wine_id).get();
+        require!(minted + quantity <= wine.total_bottles, ERR_CANNOT_MINT_MORE_THAN_TOTAL_BOTTLES);
+
+        let token_id = self.wine_collection().get_token_id();
+        let amount = BigUint::from(quantity);
+        let nonce = if self.wine_nft_nonce(wine_id).is_empty() {
+            let attributes = WineNftAttributes {
+                wine_id,
+                vineyard: wine.vineyard.clone(),
+                variety: wine.variety,
+                vintage_year: wine.vintage_year,
+                region: wine.region,
+                quality_grade: wine.quality_grade,
+                ipfs_hash: wine.ipfs_hash.clone(),
+            };
+            let royalties = BigUint::from(self.require_wine_read_model(wine_id).royalty_bps);
+            let mut uris = ManagedVec::new();
+            uris.push(sc_format!("ipfs://{}", wine.ipfs_hash));
+
+            let nonce = self.send().esdt_nft_create(
+                &token_id,
+                &amount,
+                &wine.vineyard,
+                &royalties,
+                &ManagedBuffer::new(),
+                &attributes,
+                &uris,
+            );
+            self.wine_nft_nonce(wine_id).set(nonce);
+            self.wine_by_nonce(nonce).set(wine_id);
+            nonce
+        } else {
+            let nonce = self.wine_nft_nonce(wine_id).get();
+            self.send().esdt_local_mint(&token_id, nonce, &amount);
+            nonce
+        };
+
+        self.minted_bottles(wine_id).set(minted + quantity);
+        self.update_producer_stats(wine_id, |stats| stats.bottles_minted += quantity as u64);
+        let minted_timestamp = self.blockchain().get_block_timestamp();
+        for serial in minted + 1..=minted + quantity {
+            self.bottles(wine_id).push(&Bottle {
+                nonce,
+                serial,
+                status: BottleStatus::Sealed,
+                minted_timestamp,
+            });
+        }
+        for (offset, secret_hash) in secret_hashes.into_iter().enumerate() {
+            self.bottle_secret_hash(wine_id, minted + 1 + offset as u32).set(&secret_hash);
+        }
+        self.send().direct_esdt(&caller, &token_id, nonce, &amount);
+
+        self.wine_nft_minted_event(wine_id, nonce, minted + 1, quantity);
+
+        nonce
+    }
+
+    // Check a scanned QR secret against the bottle's hash and record the scan. Once a secret
+    // has been checked it is public, so later scans are judged by the scan history.
+    #[endpoint(authenticateBottle)]
+    fn authenticate_bottle(&self, wine_id: u32, serial: u32, preimage: ManagedBuffer) -> bool {
+        let secret_hash_mapper = self.bottle_secret_hash(wine_id, serial);
+        require!(!secret_hash_mapper.is_empty(), ERR_BOTTLE_HAS_NO_AUTHENTICATION_SECRET);
+
+        let authentic = self.crypto().keccak256(&preimage) == secret_hash_mapper.get();
+        let scanner = self.blockchain().get_caller();
+        self.bottle_scans(wine_id, serial).push(&BottleScan {
+            scanner: scanner.clone(),
+            timestamp: self.blockchain().get_block_timestamp(),
+            authentic,
+        });
+
+        self.bottle_authenticated_event(wine_id, serial, &scanner, authentic);
+
+        authentic
+    }
+
+    #[callback]
+    fn wine_collection_issued(&self, #[call_result] result: ManagedAsyncCallResult<TokenIdentifier>) {
+        match result {
+            ManagedAsyncCallResult::Ok(token_id) => {
+                self.wine_collection().set_token_id(token_id);
+            },
+            ManagedAsyncCallResult::Err(_) => {
+                self.wine_collection().clear();
+            },
+        }
+    }
+
+    // View functions
+    #[view(getWineCollection)]
+    fn get_wine_collection(&self) -> OptionalValue<TokenIdentifier> {
+        if self.wine_collection().is_empty() {
+            OptionalValue::None
+        } else {
+            OptionalValue::Some(self.wine_collection().get_token_id())
+        }
+    }
+
+    #[view(getMintedBottles)]
+    fn get_minted_bottles(&self, wine_id: u32) -> u32 {
+        self.minted_bottles(wine_id).get()
+    }
+
+    // 0 until the first bottles are minted
+    #[view(getWineNftNonce)]
+    fn get_wine_nft_nonce(&self, wine_id: u32) -> u64 {
+        self.wine_nft_nonce(wine_id).get()
+    }
+
+    #[view(getWineByNonce)]
+    fn get_wine_by_nonce(&self, nonce: u64) -> u32 {
+        self.wine_by_nonce(nonce).get()
+    }
+
+    #[view(getBottle)]
+    fn get_bottle(&self, wine_id: u32, serial: u32) -> Bottle {
+        let bottles = self.bottles(wine_id);
+        require!(serial >= 1 && serial as usize <= bottles.len(), ERR_BOTTLE_DOES_NOT_EXIST);
+        bottles.get(serial as usize)
+    }
+
+    // Paginated: returns the next cursor (0 when done) followed by at most `page_size` bottles,
+    // in serial order
+    #[view(getBottlesForWine)]
+    fn get_bottles_for_wine(
+        &self,
+        wine_id: u32,
+        cursor: u64,
+        page_size: u32,
+    ) -> MultiValue2<u64, MultiValueEncoded<Bottle>> {
+        let bottles = self.bottles(wine_id);
+        let page = Page::new(cursor, page_size, MAX_BOTTLES_PAGE_SIZE, bottles.len());
+
+        let mut result = MultiValueEncoded::new();
+        for index in page.start..page.end {
+            result.push(bottles.get(index + 1));
+        }
+        (page.next_cursor, result).into()
+    }
+
+    // Paginated: returns the next cursor (0 when done) followed by at most `page_size` scans,
+    // oldest first
+    #[view(getBottleScans)]
+    fn get_bottle_scans(
+        &self,
+        wine_id: u32,
+        serial: u32,
+        cursor: u64,
+        page_size: u32,
+    ) -> MultiValue2<u64, MultiValueEncoded<BottleScan<Self::Api>>> {
+        let scans = self.bottle_scans(wine_id, serial);
+        let page = Page::new(cursor, page_size, MAX_BOTTLE_SCANS_PAGE_SIZE, scans.len());
+
+        let mut result = MultiValueEncoded::new();
+        for index in page.start..page.end {
+            result.push(scans.get(index + 1));
+        }
+        (page.next_cursor, result).into()
+    }
+
+    // Storage mappers
+    #[storage_mapper("wineCollection")]
+    fn wine_collection(&self) -> NonFungibleTokenMapper;
+
+    #[storage_mapper("mintedBottles")]
+    fn minted_bottles(&self, wine_id: u32) -> SingleValueMapper<u32>;
+
+    #[storage_mapper("wineNftNonce")]
+    fn wine_nft_nonce(&self, wine_id: u32) -> SingleValueMapper<u64>;
+
+    #[storage_mapper("wineByNonce")]
+    fn wine_by_nonce(&self, nonce: u64) -> SingleValueMapper<u32>;
+
+    // Indexed by serial
+    #[storage_mapper("bottles")]
+    fn bottles(&self, wine_id: u32) -> VecMapper<Bottle>;
+
+    #[storage_mapper("bottleSecretHash")]
+    fn bottle_secret_hash(
+        &self,
+        wine_id: u32,
+        serial: u32,
+    ) -> SingleValueMapper<ManagedByteArray<Self::Api, 32>>;
+
+    #[storage_mapper("bottleScans")]
+    fn bottle_scans(&self, wine_id: u32, serial: u32) -> VecMapper<BottleScan<Self::Api>>;
+
+    // Events
+    #[event("wineNftMinted")]
+    fn wine_nft_minted_event(
+        &self,
+        #[indexed] wine_id: u32,
+        #[indexed] nonce: u64,
+        #[indexed] first_serial: u32,
+        quantity: u32,
+    );
+
+    #[event("bottleAuthenticated")]
+    fn bottle_authenticated_event(
+        &self,
+        #[indexed] wine_id: u32,
+        #[indexed] serial: u32,
+        #[indexed] scanner: &ManagedAddress,
+        authentic: bool,
+    );
+}
@@ -0,0 +1,116 @@
+use multiversx_sc::imports::*;
+pub use wine_common::MAX_VIEW_RETURN_BYTES;
+
+// Page size caps below are derived from the worst-case encoded size of one item so that a
+// full page always fits in MAX_VIEW_RETURN_BYTES.
+
+// Reviews are capped so that a rating has a bounded encoded size
+pub const MAX_REVIEW_LENGTH: usize = 500;
+
+// wine id: 4 bytes
+pub const MAX_WINE_IDS_PAGE_SIZE: u32 = 1_000;
+// Wine details have free-text fields of unbounded length; this assumes up to ~1.2 KB per wine
+pub const MAX_WINES_PAGE_SIZE: u32 = 25;
+// rater 32 + rating 1 + review (4 + 500) + timestamp 8 = 545 bytes
+pub const MAX_RATINGS_PAGE_SIZE: u32 = 50;
+// address 32 + permissions 3 = 35 bytes
+pub const MAX_INTEGRATIONS_PAGE_SIZE: u32 = 500;
+// owner 32 + timestamp 8 + reason 1 = 41 bytes
+pub const MAX_PROVENANCE_PAGE_SIZE: u32 = 500;
+// nonce 8 + serial 4 + status 1 + timestamp 8 = 21 bytes
+pub const MAX_BOTTLES_PAGE_SIZE: u32 = 1_000;
+
+// IPFS references kept in lists (counterfeit evidence, vintage reports) are capped like
+// reviews to bound storage
+pub const MAX_IPFS_HASH_LENGTH: usize = 128;
+
+// Region names are capped so that a region has a bounded encoded size
+pub const MAX_REGION_NAME_LENGTH: usize = 64;
+
+// Tags are capped like region names
+pub const MAX_TAG_LENGTH: usize = 32;
+
+// region (4 + 64) = 68 bytes
+pub const MAX_REGIONS_PAGE_SIZE: u32 = 400;
+// report id: 4 bytes
+pub const MAX_REPORT_IDS_PAGE_SIZE: u32 = 1_000;
+// token (4 + 17) + amount (4 + 32) + timestamp 8 = 65 bytes
+pub const MAX_FEE_CHANGES_PAGE_SIZE: u32 = 400;
+// scanner 32 + timestamp 8 + authentic 1 = 41 bytes
+pub const MAX_BOTTLE_SCANS_PAGE_SIZE: u32 = 500;
+// author 32 + weather hash 32 + yield 4 + sugar 4 + report (4 + 128) + timestamp 8 = 212 bytes
+pub const MAX_VINTAGE_REPORTS_PAGE_SIZE: u32 = 150;
+// certification index: 4 bytes
+pub const MAX_CERTIFICATION_INDEXES_PAGE_SIZE: u32 = 1_000;
+// version 4 + hash 32 + timestamp 8 = 44 bytes
+pub const MAX_DETAILS_VERSIONS_PAGE_SIZE: u32 = 500;
+// price (4 + 32) + timestamp 8 = 44 bytes
+pub const MAX_PRICE_CHANGES_PAGE_SIZE: u32 = 500;
+// address: 32 bytes
+pub const MAX_SOMMELIERS_PAGE_SIZE: u32 = 1_000;
+// logger 32 + temperature 4 + humidity 4 + location hash 32 + timestamp 8 = 80 bytes
+pub const MAX_STORAGE_CONDITIONS_PAGE_SIZE: u32 = 100;
+
+// Zero-based range [start, end) of a list with `total` items. `next_cursor` is the
+// position to pass back for the following page, or 0 once the list is exhausted.
+pub struct Page {
+    pub start: usize,
+    pub end: usize,
+    pub next_cursor: u64,
+}
+
+impl Page {
+    // A page size of 0, or above `max_page_size`, yields a page of `max_page_size` items
+    pub fn new(cursor: u64, page_size: u32, max_page_size: u32, total: usize) -> Self {
+        let size = if page_size == 0 || page_size > max_page_size {
+            max_page_size
+        } else {
+            page_size
+        };
+        let start = core::cmp::min(usize::try_from(cursor).unwrap_or(usize::MAX), total);
+        let end = core::cmp::min(start.saturating_add(size as usize), total);
+        let next_cursor = if end < total { end as u64 } else { 0 };
+
+        Page {
+            start,
+            end,
+            next_cursor,
+        }
+    }
+}
+
+#[multiversx_sc::module]
+pub trait PaginationModule {
+    // Maximum page size per paginated view, so clients can size their requests
+    #[view(getPageSizeLimits)]
+    fn get_page_size_limits(&self) -> MultiValueEncoded<MultiValue2<ManagedBuffer, u32>> {
+        let mut result = MultiValueEncoded::new();
+        result.push((ManagedBuffer::from("getProducerWines"), MAX_WINE_IDS_PAGE_SIZE).into());
+        result.push((ManagedBuffer::from("getVineyardWines"), MAX_WINE_IDS_PAGE_SIZE).into());
+        result.push((ManagedBuffer::from("getAllWines"), MAX_WINES_PAGE_SIZE).into());
+        result.push((ManagedBuffer::from("getWinesByRegion"), MAX_WINE_IDS_PAGE_SIZE).into());
+        result.push((ManagedBuffer::from("getWinesByVintage"), MAX_WINE_IDS_PAGE_SIZE).into());
+        result.push((ManagedBuffer::from("getWinesByVariety"), MAX_WINE_IDS_PAGE_SIZE).into());
+        result.push((ManagedBuffer::from("getWinesByTag"), MAX_WINE_IDS_PAGE_SIZE).into());
+        result.push((ManagedBuffer::from("getWineRatings"), MAX_RATINGS_PAGE_SIZE).into());
+        result.push((ManagedBuffer::from("getIntegrations"), MAX_INTEGRATIONS_PAGE_SIZE).into());
+        result.push((ManagedBuffer::from("getBottlesForWine"), MAX_BOTTLES_PAGE_SIZE).into());
+        result.push((ManagedBuffer::from("getBottleScans"), MAX_BOTTLE_SCANS_PAGE_SIZE).into());
+        result.push((ManagedBuffer::from("getProvenance"), MAX_PROVENANCE_PAGE_SIZE).into());
+        result.push(
+            (ManagedBuffer::from("getCertificationsByType"), MAX_CERTIFICATION_INDEXES_PAGE_SIZE).into(),
+        );
+        result.push((ManagedBuffer::from("getOpenCounterfeitReports"), MAX_REPORT_IDS_PAGE_SIZE).into());
+        result.push((ManagedBuffer::from("getWineCounterfeitReports"), MAX_REPORT_IDS_PAGE_SIZE).into());
+        result.push((ManagedBuffer::from("getRegions"), MAX_REGIONS_PAGE_SIZE).into());
+        result.push((ManagedBuffer::from("getRegistrationFeeHistory"), MAX_FEE_CHANGES_PAGE_SIZE).into());
+        result.push((ManagedBuffer::from("getVintageReports"), MAX_VINTAGE_REPORTS_PAGE_SIZE).into());
+        result.push((ManagedBuffer::from("getWineDetailsHistory"), MAX_DETAILS_VERSIONS_PAGE_SIZE).into());
+        result.push((ManagedBuffer::from("getPriceHistory"), MAX_PRICE_CHANGES_PAGE_SIZE).into());
+        result.push((ManagedBuffer::from("getSommeliers"), MAX_SOMMELIERS_PAGE_SIZE).into());
+        result.push(
+            (ManagedBuffer::from("getLatestStorageConditions"), MAX_STORAGE_CONDITIONS_PAGE_SIZE).into(),
+        );
+        result
+    }
+}
@@ -0,0 +1,67 @@
+use multiversx_sc::imports::*;
+use wine_common::errors::*;
+use wine_roles::Role;
+
+// Recommended minimum EGLD price per bottle for each quality grade, published to subscribed
+// marketplaces so they can keep certified wines from being dumped below their appellation's
+// floor. Whether a floor is enforced is up to each marketplace.
+#[multiversx_sc::module]
+pub trait PriceFloorsModule:
+    wine_roles::RolesModule
+    + crate::admin::AdminModule
+    + crate::provenance::ProvenanceModule
+    + crate::read_model::ReadModelModule
+    + crate::registration::RegistrationModule
+    + crate::certification::CertificationModule
+{
+    // A zero price removes the grade's floor
+    #[endpoint(setGradePriceFloor)]
+    fn set_grade_price_floor(&self, quality_grade: u8, min_price: BigUint) {
+        let caller = self.blockchain().get_caller();
+        require!(
+            self.has_role(Role::Admin, &caller) || self.is_certification_authority(&caller),
+            ERR_ONLY_ADMIN_OR_CERTIFICATION_AUTHORITY_CAN_SET_PRICE_FLOORS
+        );
+        require!(
+            (1..=10).contains(&quality_grade),
+            ERR_QUALITY_GRADE_MUST_BE_BETWEEN_1_10
+        );
+
+        self.grade_price_floor(quality_grade).set(&min_price);
+        self.push_grade_price_floor(quality_grade, &min_price);
+
+        self.grade_price_floor_set_event(quality_grade, &caller, &min_price);
+    }
+
+    // View functions
+    #[view(getGradePriceFloor)]
+    fn get_grade_price_floor(&self, quality_grade: u8) -> BigUint {
+        self.grade_price_floor(quality_grade).get()
+    }
+
+    // Grades with a floor set, as (grade, min price) pairs
+    #[view(getGradePriceFloors)]
+    fn get_grade_price_floors(&self) -> MultiValueEncoded<MultiValue2<u8, BigUint>> {
+        let mut floors = MultiValueEncoded::new();
+        for quality_grade in 1..=10u8 {
+            let min_price = self.grade_price_floor(quality_grade).get();
+            if min_price > 0u32 {
+                floors.push((quality_grade, min_price).into());
+            }
+        }
+        floors
+    }
+
+    // Storage mappers
+    #[storage_mapper("gradePriceFloor")]
+    fn grade_price_floor(&self, quality_grade: u8) -> SingleValueMapper<BigUint>;
+
+    // Events
+    #[event("gradePriceFloorSet")]
+    fn grade_price_floor_set_event(
+        &self,
+        #[indexed] quality_grade: u8,
+        #[indexed] caller: &ManagedAddress,
+        min_price: &BigUint,
+    );
+}
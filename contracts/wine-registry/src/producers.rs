@@ -0,0 +1,66 @@
+use multiversx_sc::imports::*;
+use wine_common::errors::*;
+
+use crate::registration::ProducerStats;
+
+// Producers vetted by a certification authority. Subscribed marketplaces are kept in sync,
+// so they can restrict primary sales to verified producers.
+#[multiversx_sc::module]
+pub trait ProducersModule:
+    wine_roles::RolesModule
+    + crate::admin::AdminModule
+    + crate::provenance::ProvenanceModule
+    + crate::read_model::ReadModelModule
+    + crate::registration::RegistrationModule
+    + crate::certification::CertificationModule
+{
+    #[endpoint(verifyProducer)]
+    fn verify_producer(&self, producer: ManagedAddress) {
+        let caller = self.blockchain().get_caller();
+        require!(self.is_certification_authority(&caller), ERR_NOT_A_CERTIFICATION_AUTHORITY);
+        require!(!self.verified_producer(&producer).get(), ERR_PRODUCER_ALREADY_VERIFIED);
+
+        self.verified_producer(&producer).set(true);
+        self.push_producer_verification(&producer, true);
+
+        self.producer_verified_event(&producer, &caller);
+    }
+
+    #[endpoint(revokeProducerVerification)]
+    fn revoke_producer_verification(&self, producer: ManagedAddress) {
+        let caller = self.blockchain().get_caller();
+        require!(self.is_certification_authority(&caller), ERR_NOT_A_CERTIFICATION_AUTHORITY);
+        require!(self.verified_producer(&producer).get(), ERR_PRODUCER_IS_NOT_VERIFIED);
+
+        self.verified_producer(&producer).clear();
+        self.push_producer_verification(&producer, false);
+
+        self.producer_verification_revoked_event(&producer, &caller);
+    }
+
+    // View functions
+    #[view(getProducerStats)]
+    fn get_producer_stats(&self, producer: &ManagedAddress) -> ProducerStats {
+        self.load_producer_stats(producer)
+    }
+
+    #[view(isVerifiedProducer)]
+    fn is_verified_producer(&self, producer: &ManagedAddress) -> bool {
+        self.verified_producer(producer).get()
+    }
+
+    // Events
+    #[event("producerVerified")]
+    fn producer_verified_event(
+        &self,
+        #[indexed] producer: &ManagedAddress,
+        verified_by: &ManagedAddress,
+    );
+
+    #[event("producerVerificationRevoked")]
+    fn producer_verification_revoked_event(
+        &self,
+        #[indexed] producer: &ManagedAddress,
+        revoked_by: &ManagedAddress,
+    );
+}
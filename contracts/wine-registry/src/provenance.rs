@@ -0,0 +1,514 @@
+use multiversx_sc::derive_imports::*;
+use multiversx_sc::imports::*;
+use wine_common::errors::*;
+
+use crate::pagination::{Page, MAX_PROVENANCE_PAGE_SIZE, MAX_WINE_IDS_PAGE_SIZE};
+
+// Ownership shares are in basis points of the whole wine
+pub const FULL_SHARE_BPS: u64 = 10_000;
+pub const MAX_CO_OWNERS: usize = 20;
+
+#[derive(TopEncode, TopDecode, NestedEncode, NestedDecode, TypeAbi, PartialEq, Debug, Clone, Copy)]
+pub enum OwnershipChangeReason {
+    Registration,
+    Sale,
+    Gift,
+    Marketplace,
+}
+
+// One entry of a wine's ownership history
+#[derive(TopEncode, TopDecode, NestedEncode, NestedDecode, TypeAbi, PartialEq, Debug)]
+pub struct ProvenanceRecord<M: ManagedTypeApi> {
+    pub owner: ManagedAddress<M>,
+    pub timestamp: u64,
+    pub reason: OwnershipChangeReason,
+}
+
+// Owner actions that can require co-owner approval
+#[derive(TopEncode, TopDecode, NestedEncode, NestedDecode, TypeAbi, PartialEq, Debug, Clone, Copy)]
+pub enum SensitiveAction {
+    Recall,
+    Transfer,
+    PriceUpdate,
+}
+
+// A sensitive action together with what co-owners approve: the new owner of a transfer, the
+// new price of a price update
+#[derive(TopEncode, TopDecode, NestedEncode, NestedDecode, TypeAbi, PartialEq, Debug, Clone)]
+pub enum WineAction<M: ManagedTypeApi> {
+    Recall,
+    Transfer(ManagedAddress<M>),
+    PriceUpdate(BigUint<M>),
+}
+
+impl<M: ManagedTypeApi> WineAction<M> {
+    pub fn sensitive_action(&self) -> SensitiveAction {
+        match self {
+            WineAction::Recall => SensitiveAction::Recall,
+            WineAction::Transfer(_) => SensitiveAction::Transfer,
+            WineAction::PriceUpdate(_) => SensitiveAction::PriceUpdate,
+        }
+    }
+}
+
+// Co-owners hold shares of a wine next to its owner, who keeps whatever is not given out and
+// still acts for the wine. Per action, the owner can require approvals of at least a
+// threshold of all shares; whoever performs the action counts as approving it. Thresholds
+// can only be lowered while the wine has no co-owners, and only co-owners can give up their
+// share. All shares return to the owner when the wine changes hands.
+#[multiversx_sc::module]
+pub trait ProvenanceModule: wine_roles::RolesModule + crate::admin::AdminModule {
+    // Hand a wine over to a new owner. The owner records sales and gifts; integrations
+    // allowed to record sales can move it on behalf of a marketplace sale, of an item the
+    // owner escrowed with them or with co-owner approval of the new owner. Co-owners do not
+    // carry over to the new owner.
+    #[endpoint(transferWineOwnership)]
+    fn transfer_wine_ownership(
+        &self,
+        wine_id: u32,
+        new_owner: ManagedAddress,
+        reason: OwnershipChangeReason,
+    ) {
+        require!(!self.wine_owner(wine_id).is_empty(), ERR_WINE_DOES_NOT_EXIST);
+        let current_owner = self.wine_owner(wine_id).get();
+        require!(!new_owner.is_zero(), ERR_INVALID_NEW_OWNER);
+        require!(new_owner != current_owner, ERR_WINE_ALREADY_BELONGS_TO_THIS_ADDRESS);
+
+        let caller = self.blockchain().get_caller();
+        match reason {
+            OwnershipChangeReason::Sale | OwnershipChangeReason::Gift => {
+                require!(caller == current_owner, ERR_ONLY_WINE_OWNER_CAN_TRANSFER_OWNERSHIP);
+                require!(!self.is_wine_locked(wine_id), ERR_WINE_IS_LOCKED_BY_A_MARKETPLACE);
+                let transfer = WineAction::Transfer(new_owner.clone());
+                self.require_co_owner_approval(wine_id, &caller, transfer);
+            },
+            OwnershipChangeReason::Marketplace => {
+                require!(
+                    self.get_integration_permissions(&caller).can_record_sale,
+                    ERR_NOT_AUTHORIZED_TO_RECORD_SALES
+                );
+                // Co-owners approved handing the wine to the marketplace when it was locked
+                if self.wine_locks(wine_id, &caller, &current_owner).get() == 0 {
+                    let transfer = WineAction::Transfer(new_owner.clone());
+                    self.require_co_owner_approval(wine_id, &current_owner, transfer);
+                }
+            },
+            OwnershipChangeReason::Registration => sc_panic!(ERR_INVALID_TRANSFER_REASON),
+        }
+
+        self.record_wine_owner(wine_id, &new_owner, reason);
+        self.clear_co_owners(wine_id);
+        self.wine_ownership_transferred_event(
+            wine_id,
+            &caller,
+            &current_owner,
+            &new_owner,
+            reason,
+        );
+    }
+
+    // Marketplaces (integrations allowed to record sales) lock a wine while one of its items
+    // is in escrow there, and unlock it on sale or cancellation. Locks are counted per
+    // marketplace and per `holder` who escrowed the item. Only items escrowed by the wine's
+    // owner keep the wine locked; bottles resold by collectors do not. The owner escrowing
+    // an item is a transfer to the marketplace, which co-owners have to approve.
+    #[endpoint(lockWine)]
+    fn lock_wine(&self, wine_id: u32, holder: ManagedAddress) {
+        require!(!self.wine_owner(wine_id).is_empty(), ERR_WINE_DOES_NOT_EXIST);
+        let caller = self.blockchain().get_caller();
+        require!(
+            self.get_integration_permissions(&caller).can_record_sale,
+            ERR_NOT_AUTHORIZED_TO_RECORD_SALES
+        );
+
+        if holder == self.wine_owner(wine_id).get() {
+            self.require_co_owner_approval(wine_id, &holder, WineAction::Transfer(caller.clone()));
+        }
+
+        self.wine_locks(wine_id, &caller, &holder).update(|locks| *locks += 1);
+        self.wine_holder_lock_count(wine_id, &holder).update(|locks| *locks += 1);
+        self.wine_lock_count(wine_id).update(|locks| *locks += 1);
+
+        self.wine_locked_event(wine_id, &caller, &holder);
+    }
+
+    #[endpoint(unlockWine)]
+    fn unlock_wine(&self, wine_id: u32, holder: ManagedAddress) {
+        let caller = self.blockchain().get_caller();
+        let locks_mapper = self.wine_locks(wine_id, &caller, &holder);
+        require!(locks_mapper.get() > 0, ERR_WINE_IS_NOT_LOCKED_BY_CALLER);
+
+        locks_mapper.update(|locks| *locks -= 1);
+        self.wine_holder_lock_count(wine_id, &holder).update(|locks| *locks -= 1);
+        self.wine_lock_count(wine_id).update(|locks| *locks -= 1);
+
+        self.wine_unlocked_event(wine_id, &caller, &holder);
+    }
+
+    // Give `co_owner` part of the owner's own share, who has to keep some of it
+    #[endpoint(addCoOwner)]
+    fn add_co_owner(&self, wine_id: u32, co_owner: ManagedAddress, share_bps: u64) {
+        require!(!self.wine_owner(wine_id).is_empty(), ERR_WINE_DOES_NOT_EXIST);
+        let owner = self.wine_owner(wine_id).get();
+        require!(self.blockchain().get_caller() == owner, ERR_ONLY_WINE_OWNER_CAN_ADD_CO_OWNERS);
+        require!(!co_owner.is_zero() && co_owner != owner, ERR_INVALID_CO_OWNER);
+        require!(share_bps > 0, ERR_CO_OWNER_SHARE_MUST_BE_GREATER_THAN_0);
+        require!(share_bps < self.owner_share(wine_id), ERR_CO_OWNER_SHARE_EXCEEDS_OWNER_SHARE);
+
+        let mut co_owners = self.co_owners(wine_id);
+        require!(co_owners.len() < MAX_CO_OWNERS, ERR_TOO_MANY_CO_OWNERS);
+        require!(co_owners.insert(co_owner.clone()), ERR_ALREADY_A_CO_OWNER);
+        self.co_owner_share(wine_id, &co_owner).set(share_bps);
+        self.co_owned_share(wine_id).update(|share| *share += share_bps);
+
+        self.co_owner_added_event(wine_id, &co_owner, share_bps);
+    }
+
+    // A co-owner gives up their share, which goes back to the owner
+    #[endpoint(removeCoOwner)]
+    fn remove_co_owner(&self, wine_id: u32, co_owner: ManagedAddress) {
+        require!(self.co_owners(wine_id).contains(&co_owner), ERR_NOT_A_CO_OWNER);
+        require!(
+            self.blockchain().get_caller() == co_owner,
+            ERR_ONLY_THE_CO_OWNER_CAN_GIVE_UP_THEIR_SHARE
+        );
+
+        let share_bps = self.remove_co_owner_share(wine_id, &co_owner);
+        self.co_owner_removed_event(wine_id, &co_owner, share_bps);
+    }
+
+    // Shares needed to approve `action`, in basis points; 0 lets the owner act alone
+    #[endpoint(setApprovalThreshold)]
+    fn set_approval_threshold(&self, wine_id: u32, action: SensitiveAction, threshold_bps: u64) {
+        require!(!self.wine_owner(wine_id).is_empty(), ERR_WINE_DOES_NOT_EXIST);
+        require!(
+            self.blockchain().get_caller() == self.wine_owner(wine_id).get(),
+            ERR_ONLY_WINE_OWNER_CAN_SET_APPROVAL_THRESHOLDS
+        );
+        require!(threshold_bps <= FULL_SHARE_BPS, ERR_APPROVAL_THRESHOLD_CANNOT_EXCEED_100_PERCENT);
+        let threshold = self.approval_threshold(wine_id, action);
+        require!(
+            threshold_bps >= threshold.get() || self.co_owners(wine_id).is_empty(),
+            ERR_APPROVAL_THRESHOLD_CANNOT_BE_LOWERED_WITH_CO_OWNERS
+        );
+
+        threshold.set(threshold_bps);
+        self.approval_threshold_set_event(wine_id, action, threshold_bps);
+    }
+
+    // Approvals stay until the action is performed or the approval is revoked. They count
+    // with the approver's share at that time.
+    #[endpoint(approveWineAction)]
+    fn approve_wine_action(&self, wine_id: u32, action: WineAction<Self::Api>) {
+        let caller = self.blockchain().get_caller();
+        require!(self.ownership_share(wine_id, &caller) > 0, ERR_CALLER_HOLDS_NO_SHARE_OF_THE_WINE);
+
+        let action_hash = self.wine_action_hash(&action);
+        require!(
+            self.wine_action_approvals(wine_id, &action_hash).insert(caller.clone()),
+            ERR_WINE_ACTION_ALREADY_APPROVED
+        );
+        self.wine_action_approved_event(wine_id, &caller, &action);
+    }
+
+    #[endpoint(revokeWineActionApproval)]
+    fn revoke_wine_action_approval(&self, wine_id: u32, action: WineAction<Self::Api>) {
+        let caller = self.blockchain().get_caller();
+        let action_hash = self.wine_action_hash(&action);
+        require!(
+            self.wine_action_approvals(wine_id, &action_hash).swap_remove(&caller),
+            ERR_WINE_ACTION_NOT_APPROVED
+        );
+        self.wine_action_approval_revoked_event(wine_id, &caller, &action);
+    }
+
+    // Internal helpers
+    fn record_wine_owner(&self, wine_id: u32, owner: &ManagedAddress, reason: OwnershipChangeReason) {
+        self.wine_owner(wine_id).set(owner);
+        self.provenance(wine_id).push(&ProvenanceRecord {
+            owner: owner.clone(),
+            timestamp: self.blockchain().get_block_timestamp(),
+            reason,
+        });
+    }
+
+    // Fails unless `caller` and the approvers of `action` together hold the threshold, then
+    // uses up the approvals
+    fn require_co_owner_approval(
+        &self,
+        wine_id: u32,
+        caller: &ManagedAddress,
+        action: WineAction<Self::Api>,
+    ) {
+        let threshold = self.approval_threshold(wine_id, action.sensitive_action()).get();
+        if threshold == 0 {
+            return;
+        }
+
+        let action_hash = self.wine_action_hash(&action);
+        let mut approvals = self.wine_action_approvals(wine_id, &action_hash);
+        let mut approved_share = self.approved_share(wine_id, &approvals);
+        if !approvals.contains(caller) {
+            approved_share += self.ownership_share(wine_id, caller);
+        }
+        require!(approved_share >= threshold, ERR_CO_OWNER_APPROVALS_BELOW_THRESHOLD);
+        approvals.clear();
+    }
+
+    fn approved_share(&self, wine_id: u32, approvals: &UnorderedSetMapper<ManagedAddress>) -> u64 {
+        approvals
+            .iter()
+            .map(|approver| self.ownership_share(wine_id, &approver))
+            .sum()
+    }
+
+    fn ownership_share(&self, wine_id: u32, address: &ManagedAddress) -> u64 {
+        if self.wine_owner(wine_id).get() == *address {
+            self.owner_share(wine_id)
+        } else {
+            self.co_owner_share(wine_id, address).get()
+        }
+    }
+
+    fn owner_share(&self, wine_id: u32) -> u64 {
+        FULL_SHARE_BPS - self.co_owned_share(wine_id).get()
+    }
+
+    fn remove_co_owner_share(&self, wine_id: u32, co_owner: &ManagedAddress) -> u64 {
+        self.co_owners(wine_id).swap_remove(co_owner);
+        let share_bps = self.co_owner_share(wine_id, co_owner).take();
+        self.co_owned_share(wine_id).update(|share| *share -= share_bps);
+        share_bps
+    }
+
+    // Folds every co-owner's share back into the owner's; at most MAX_CO_OWNERS
+    fn clear_co_owners(&self, wine_id: u32) {
+        let co_owners: ManagedVec<ManagedAddress> = self.co_owners(wine_id).iter().collect();
+        for co_owner in co_owners.iter() {
+            let share_bps = self.remove_co_owner_share(wine_id, &co_owner);
+            self.co_owner_removed_event(wine_id, &co_owner, share_bps);
+        }
+    }
+
+    fn wine_action_hash(&self, action: &WineAction<Self::Api>) -> ManagedByteArray<Self::Api, 32> {
+        let encoded = self.serializer().top_encode_to_managed_buffer(action);
+        self.crypto().keccak256(&encoded)
+    }
+
+    // The registering producer, i.e. the first recorded owner. Wines registered before
+    // provenance was tracked fall back to their current owner.
+    fn wine_producer(&self, wine_id: u32) -> ManagedAddress {
+        let provenance = self.provenance(wine_id);
+        if provenance.is_empty() {
+            self.wine_owner(wine_id).get()
+        } else {
+            provenance.get(1).owner
+        }
+    }
+
+    // View functions
+    #[view(getWineOwner)]
+    fn get_wine_owner(&self, wine_id: u32) -> ManagedAddress {
+        self.wine_owner(wine_id).get()
+    }
+
+    // Whether the owner has an item of the wine in escrow on a marketplace
+    #[view(isWineLocked)]
+    fn is_wine_locked(&self, wine_id: u32) -> bool {
+        let owner = self.wine_owner(wine_id).get();
+        self.wine_holder_lock_count(wine_id, &owner).get() > 0
+    }
+
+    // Escrowed items across all marketplaces
+    #[view(getWineLockCount)]
+    fn get_wine_lock_count(&self, wine_id: u32) -> u32 {
+        self.wine_lock_count(wine_id).get()
+    }
+
+    // The owner and their share first, then each co-owner and theirs
+    #[view(getOwnershipTable)]
+    fn get_ownership_table(&self, wine_id: u32) -> MultiValueEncoded<MultiValue2<ManagedAddress, u64>> {
+        let mut result = MultiValueEncoded::new();
+        if self.wine_owner(wine_id).is_empty() {
+            return result;
+        }
+        result.push((self.wine_owner(wine_id).get(), self.owner_share(wine_id)).into());
+        for co_owner in self.co_owners(wine_id).iter() {
+            let share_bps = self.co_owner_share(wine_id, &co_owner).get();
+            result.push((co_owner, share_bps).into());
+        }
+        result
+    }
+
+    #[view(getApprovalThreshold)]
+    fn get_approval_threshold(&self, wine_id: u32, action: SensitiveAction) -> u64 {
+        self.approval_threshold(wine_id, action).get()
+    }
+
+    #[view(getWineActionApprovals)]
+    fn get_wine_action_approvals(
+        &self,
+        wine_id: u32,
+        action: WineAction<Self::Api>,
+    ) -> MultiValueEncoded<ManagedAddress> {
+        let action_hash = self.wine_action_hash(&action);
+        self.wine_action_approvals(wine_id, &action_hash).iter().collect()
+    }
+
+    // Shares currently approving `action`, not counting whoever would perform it
+    #[view(getApprovedShare)]
+    fn get_approved_share(&self, wine_id: u32, action: WineAction<Self::Api>) -> u64 {
+        let action_hash = self.wine_action_hash(&action);
+        self.approved_share(wine_id, &self.wine_action_approvals(wine_id, &action_hash))
+    }
+
+    // Paginated: returns the next cursor (0 when done) followed by at most `page_size` wine ids
+    #[view(getProducerWines)]
+    fn get_producer_wines(
+        &self,
+        producer: &ManagedAddress,
+        cursor: u64,
+        page_size: u32,
+    ) -> MultiValue2<u64, MultiValueEncoded<u32>> {
+        let producer_wines = self.producer_wines(producer);
+        let page = Page::new(cursor, page_size, MAX_WINE_IDS_PAGE_SIZE, producer_wines.len());
+
+        let mut result = MultiValueEncoded::new();
+        for index in page.start..page.end {
+            result.push(producer_wines.get(index + 1));
+        }
+        (page.next_cursor, result).into()
+    }
+
+    // Paginated: returns the next cursor (0 when done) followed by at most `page_size` owner
+    // changes, oldest first
+    #[view(getProvenance)]
+    fn get_provenance(
+        &self,
+        wine_id: u32,
+        cursor: u64,
+        page_size: u32,
+    ) -> MultiValue2<u64, MultiValueEncoded<ProvenanceRecord<Self::Api>>> {
+        let provenance = self.provenance(wine_id);
+        let page = Page::new(cursor, page_size, MAX_PROVENANCE_PAGE_SIZE, provenance.len());
+
+        let mut result = MultiValueEncoded::new();
+        for index in page.start..page.end {
+            result.push(provenance.get(index + 1));
+        }
+        (page.next_cursor, result).into()
+    }
+
+    // Storage mappers
+    #[storage_mapper("wineOwner")]
+    fn wine_owner(&self, wine_id: u32) -> SingleValueMapper<ManagedAddress>;
+
+    #[storage_mapper("producerWines")]
+    fn producer_wines(&self, producer: &ManagedAddress) -> VecMapper<u32>;
+
+    #[storage_mapper("provenance")]
+    fn provenance(&self, wine_id: u32) -> VecMapper<ProvenanceRecord<Self::Api>>;
+
+    #[storage_mapper("wineLocks")]
+    fn wine_locks(
+        &self,
+        wine_id: u32,
+        marketplace: &ManagedAddress,
+        holder: &ManagedAddress,
+    ) -> SingleValueMapper<u32>;
+
+    // Escrowed items per holder, across all marketplaces
+    #[storage_mapper("wineHolderLockCount")]
+    fn wine_holder_lock_count(&self, wine_id: u32, holder: &ManagedAddress) -> SingleValueMapper<u32>;
+
+    #[storage_mapper("wineLockCount")]
+    fn wine_lock_count(&self, wine_id: u32) -> SingleValueMapper<u32>;
+
+    #[storage_mapper("coOwners")]
+    fn co_owners(&self, wine_id: u32) -> UnorderedSetMapper<ManagedAddress>;
+
+    #[storage_mapper("coOwnerShare")]
+    fn co_owner_share(&self, wine_id: u32, co_owner: &ManagedAddress) -> SingleValueMapper<u64>;
+
+    // Sum of all co-owner shares
+    #[storage_mapper("coOwnedShare")]
+    fn co_owned_share(&self, wine_id: u32) -> SingleValueMapper<u64>;
+
+    #[storage_mapper("approvalThreshold")]
+    fn approval_threshold(&self, wine_id: u32, action: SensitiveAction) -> SingleValueMapper<u64>;
+
+    // Approvers per action and arguments, keyed by the keccak256 of the encoded `WineAction`
+    #[storage_mapper("wineActionApprovals")]
+    fn wine_action_approvals(
+        &self,
+        wine_id: u32,
+        action_hash: &ManagedByteArray<Self::Api, 32>,
+    ) -> UnorderedSetMapper<ManagedAddress>;
+
+    // Events
+    #[event("wineOwnershipTransferred")]
+    fn wine_ownership_transferred_event(
+        &self,
+        #[indexed] wine_id: u32,
+        #[indexed] recorded_by: &ManagedAddress,
+        #[indexed] previous_owner: &ManagedAddress,
+        #[indexed] new_owner: &ManagedAddress,
+        reason: OwnershipChangeReason,
+    );
+
+    #[event("wineLocked")]
+    fn wine_locked_event(
+        &self,
+        #[indexed] wine_id: u32,
+        #[indexed] marketplace: &ManagedAddress,
+        #[indexed] holder: &ManagedAddress,
+    );
+
+    #[event("wineUnlocked")]
+    fn wine_unlocked_event(
+        &self,
+        #[indexed] wine_id: u32,
+        #[indexed] marketplace: &ManagedAddress,
+        #[indexed] holder: &ManagedAddress,
+    );
+
+    #[event("coOwnerAdded")]
+    fn co_owner_added_event(
+        &self,
+        #[indexed] wine_id: u32,
+        #[indexed] co_owner: &ManagedAddress,
+        share_bps: u64,
+    );
+
+    #[event("coOwnerRemoved")]
+    fn co_owner_removed_event(
+        &self,
+        #[indexed] wine_id: u32,
+        #[indexed] co_owner: &ManagedAddress,
+        share_bps: u64,
+    );
+
+    #[event("approvalThresholdSet")]
+    fn approval_threshold_set_event(
+        &self,
+        #[indexed] wine_id: u32,
+        #[indexed] action: SensitiveAction,
+        threshold_bps: u64,
+    );
+
+    #[event("wineActionApproved")]
+    fn wine_action_approved_event(
+        &self,
+        #[indexed] wine_id: u32,
+        #[indexed] approver: &ManagedAddress,
+        action: &WineAction<Self::Api>,
+    );
+
+    #[event("wineActionApprovalRevoked")]
+    fn wine_action_approval_revoked_event(
+        &self,
+        #[indexed] wine_id: u32,
+        #[indexed] approver: &ManagedAddress,
+        action: &WineAction<Self::Api>,
+    );
+}
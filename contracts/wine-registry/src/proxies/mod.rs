@@ -0,0 +1 @@
+pub mod wine_marketplace_proxy;
@@ -0,0 +1,432 @@
+use multiversx_sc::derive_imports::*;
+use multiversx_sc::imports::*;
+use wine_common::errors::*;
+pub use wine_common::WineRating;
+
+use crate::pagination::{Page, MAX_RATINGS_PAGE_SIZE, MAX_REVIEW_LENGTH};
+
+// A verified buyer's rating counts this many times in the weighted average
+pub const VERIFIED_RATING_WEIGHT: u32 = 3;
+// Share of the professional average in the combined score, in basis points
+pub const PROFESSIONAL_SCORE_WEIGHT_BPS: u64 = 5_000;
+// Raters can edit or delete their rating this long after submitting it
+pub const RATING_EDIT_GRACE_PERIOD: u64 = 7 * 24 * 60 * 60;
+
+// Running totals behind the averages, so a rating change does not loop over all ratings.
+// Community and professional (sommelier) ratings are totalled separately.
+#[derive(TopEncode, TopDecode, TypeAbi, PartialEq, Debug)]
+pub struct RatingTotals {
+    pub sum: u64,
+    pub count: u32,
+    pub weighted_sum: u64,
+    pub total_weight: u32,
+}
+
+#[multiversx_sc::module]
+pub trait RatingsModule:
+    wine_roles::RolesModule
+    + crate::admin::AdminModule
+    + crate::provenance::ProvenanceModule
+    + crate::read_model::ReadModelModule
+    + crate::registration::RegistrationModule
+    + crate::rewards::RewardsModule
+{
+    // Marketplaces (integrations allowed to record sales) report buyers of a wine, so their
+    // ratings are flagged as verified purchases
+    #[endpoint(recordVerifiedBuyer)]
+    fn record_verified_buyer(&self, wine_id: u32, buyer: ManagedAddress) {
+        self.require_wine_exists(wine_id);
+        let caller = self.blockchain().get_caller();
+        require!(
+            self.get_integration_permissions(&caller).can_record_sale,
+            ERR_NOT_AUTHORIZED_TO_RECORD_SALES
+        );
+
+        if !self.verified_buyer(wine_id, &buyer).get() {
+            self.verified_buyer(wine_id, &buyer).set(true);
+            self.verified_buyer_recorded_event(wine_id, &buyer, &caller);
+        }
+    }
+
+    // Add wine rating system
+    #[endpoint(rateWine)]
+    fn rate_wine(&self, wine_id: u32, rating: u8, review: ManagedBuffer) {
+        self.require_not_paused();
+        self.require_wine_exists(wine_id);
+        require!(rating >= 1 && rating <= 10, ERR_RATING_MUST_BE_BETWEEN_1_10);
+        require!(review.len() <= MAX_REVIEW_LENGTH, ERR_REVIEW_IS_TOO_LONG);
+        
+        let caller = self.blockchain().get_caller();
+        let current_timestamp = self.blockchain().get_block_timestamp();
+        
+        // Check if user already rated this wine
+        require!(
+            self.wine_user_rating(wine_id, &caller).is_empty(),
+            ERR_YOU_HAVE_ALREADY_RATED_THIS_WINE
+        );
+        
+        let wine_rating = WineRating {
+            rater: caller.clone(),
+            rating,
+            review,
+            timestamp: current_timestamp,
+        };
+        
+        // Verification and the professional track are fixed at rating time
+        let verified = self.verified_buyer(wine_id, &caller).get();
+        if verified {
+            self.verified_rating(wine_id, &caller).set(true);
+        }
+        if self.sommeliers().contains(&caller) {
+            self.professional_rating(wine_id, &caller).set(true);
+        }
+
+        // Seed the totals before the new rating is stored
+        let mut totals = self.rating_totals_for(wine_id, &caller);
+        let index = self.wine_ratings(wine_id).push(&wine_rating);
+        self.wine_user_rating(wine_id, &caller).set(&rating);
+        self.wine_user_rating_index(wine_id, &caller).set(index);
+        
+        // Update average rating
+        let weight = self.rating_weight(wine_id, &caller);
+        totals.sum += rating as u64;
+        totals.count += 1;
+        totals.weighted_sum += rating as u64 * weight as u64;
+        totals.total_weight += weight;
+        self.store_rating_totals_for(wine_id, &caller, &totals);
+        self.credit_rating_points(wine_id, &caller, verified);
+        self.update_producer_stats(wine_id, |stats| stats.add_ratings(rating as u64, 1));
+        
+        self.wine_rated_event(wine_id, &caller, verified, rating);
+    }
+
+    // Change your own rating within RATING_EDIT_GRACE_PERIOD of submitting it. The original
+    // timestamp is kept.
+    #[endpoint(updateRating)]
+    fn update_rating(&self, wine_id: u32, rating: u8, review: ManagedBuffer) {
+        require!(rating >= 1 && rating <= 10, ERR_RATING_MUST_BE_BETWEEN_1_10);
+        require!(review.len() <= MAX_REVIEW_LENGTH, ERR_REVIEW_IS_TOO_LONG);
+
+        let caller = self.blockchain().get_caller();
+        let index = self.require_editable_rating(wine_id, &caller);
+        let mut totals = self.rating_totals_for(wine_id, &caller);
+        let mut wine_rating = self.wine_ratings(wine_id).get(index);
+        let previous_rating = wine_rating.rating;
+
+        wine_rating.rating = rating;
+        wine_rating.review = review;
+        self.wine_ratings(wine_id).set(index, &wine_rating);
+        self.wine_user_rating(wine_id, &caller).set(&rating);
+
+        let weight = self.rating_weight(wine_id, &caller) as u64;
+        totals.sum = totals.sum - previous_rating as u64 + rating as u64;
+        totals.weighted_sum =
+            totals.weighted_sum - previous_rating as u64 * weight + rating as u64 * weight;
+        self.store_rating_totals_for(wine_id, &caller, &totals);
+        self.update_producer_stats(wine_id, |stats| {
+            stats.remove_ratings(previous_rating as u64, 1);
+            stats.add_ratings(rating as u64, 1);
+        });
+
+        self.wine_rating_updated_event(wine_id, &caller, previous_rating, rating);
+    }
+
+    // Remove your own rating within RATING_EDIT_GRACE_PERIOD of submitting it. The wine can
+    // be rated again afterwards.
+    #[endpoint(deleteRating)]
+    fn delete_rating(&self, wine_id: u32) {
+        let caller = self.blockchain().get_caller();
+        let index = self.require_editable_rating(wine_id, &caller);
+        let mut totals = self.rating_totals_for(wine_id, &caller);
+        let wine_rating = self.wine_ratings(wine_id).get(index);
+
+        let weight = self.rating_weight(wine_id, &caller);
+        totals.sum -= wine_rating.rating as u64;
+        totals.count -= 1;
+        totals.weighted_sum -= wine_rating.rating as u64 * weight as u64;
+        totals.total_weight -= weight;
+        self.store_rating_totals_for(wine_id, &caller, &totals);
+        self.update_producer_stats(wine_id, |stats| stats.remove_ratings(wine_rating.rating as u64, 1));
+
+        // The last rating takes the removed one's place
+        let mut ratings = self.wine_ratings(wine_id);
+        ratings.swap_remove(index);
+        if index <= ratings.len() {
+            let moved_rater = ratings.get(index).rater;
+            self.wine_user_rating_index(wine_id, &moved_rater).set(index);
+        }
+        self.wine_user_rating(wine_id, &caller).clear();
+        self.wine_user_rating_index(wine_id, &caller).clear();
+        self.verified_rating(wine_id, &caller).clear();
+        self.professional_rating(wine_id, &caller).clear();
+        self.revoke_rating_points(wine_id, &caller);
+
+        self.wine_rating_deleted_event(wine_id, &caller, wine_rating.rating);
+    }
+
+    // Internal helper functions
+    // Returns the index of the caller's rating
+    fn require_editable_rating(&self, wine_id: u32, rater: &ManagedAddress) -> usize {
+        self.require_wine_exists(wine_id);
+        require!(!self.wine_user_rating(wine_id, rater).is_empty(), ERR_YOU_HAVE_NOT_RATED_THIS_WINE);
+        let index = self.user_rating_index(wine_id, rater);
+        let submitted = self.wine_ratings(wine_id).get(index).timestamp;
+        require!(
+            self.blockchain().get_block_timestamp() <= submitted + RATING_EDIT_GRACE_PERIOD,
+            ERR_RATING_CAN_NO_LONGER_BE_CHANGED
+        );
+        index
+    }
+
+    // Ratings submitted before indexes were stored are looked up once
+    fn user_rating_index(&self, wine_id: u32, rater: &ManagedAddress) -> usize {
+        let index_mapper = self.wine_user_rating_index(wine_id, rater);
+        if !index_mapper.is_empty() {
+            return index_mapper.get();
+        }
+
+        let ratings = self.wine_ratings(wine_id);
+        for index in 1..=ratings.len() {
+            if ratings.get(index).rater == *rater {
+                index_mapper.set(index);
+                return index;
+            }
+        }
+        sc_panic!(ERR_RATING_NOT_FOUND);
+    }
+
+    // Verified purchases only carry extra weight in the community track
+    fn rating_weight(&self, wine_id: u32, rater: &ManagedAddress) -> u32 {
+        let professional = self.professional_rating(wine_id, rater).get();
+        if self.verified_rating(wine_id, rater).get() && !professional {
+            VERIFIED_RATING_WEIGHT
+        } else {
+            1
+        }
+    }
+
+    // Totals of the track the rater's rating belongs to
+    fn rating_totals_for(&self, wine_id: u32, rater: &ManagedAddress) -> RatingTotals {
+        if self.professional_rating(wine_id, rater).get() {
+            self.professional_rating_totals(wine_id)
+        } else {
+            self.rating_totals(wine_id)
+        }
+    }
+
+    fn professional_rating_totals(&self, wine_id: u32) -> RatingTotals {
+        let totals_mapper = self.wine_professional_rating_totals(wine_id);
+        if totals_mapper.is_empty() {
+            self.empty_rating_totals()
+        } else {
+            totals_mapper.get()
+        }
+    }
+
+    fn store_rating_totals_for(&self, wine_id: u32, rater: &ManagedAddress, totals: &RatingTotals) {
+        if !self.professional_rating(wine_id, rater).get() {
+            self.store_rating_totals(wine_id, totals);
+            return;
+        }
+
+        self.wine_professional_rating_totals(wine_id).set(totals);
+        if totals.count == 0 {
+            self.wine_professional_average_rating(wine_id).clear();
+        } else {
+            let average = totals.sum * 100 / totals.count as u64;
+            self.wine_professional_average_rating(wine_id).set(average as u16);
+        }
+    }
+
+    fn empty_rating_totals(&self) -> RatingTotals {
+        RatingTotals {
+            sum: 0,
+            count: 0,
+            weighted_sum: 0,
+            total_weight: 0,
+        }
+    }
+
+    // Community totals. Wines rated before totals were kept get them computed once from the
+    // stored ratings.
+    fn rating_totals(&self, wine_id: u32) -> RatingTotals {
+        let totals_mapper = self.wine_rating_totals(wine_id);
+        if !totals_mapper.is_empty() {
+            return totals_mapper.get();
+        }
+
+        let mut totals = self.empty_rating_totals();
+        for rating in self.wine_ratings(wine_id).iter() {
+            if self.professional_rating(wine_id, &rating.rater).get() {
+                continue;
+            }
+            let weight = self.rating_weight(wine_id, &rating.rater);
+            totals.sum += rating.rating as u64;
+            totals.count += 1;
+            totals.weighted_sum += rating.rating as u64 * weight as u64;
+            totals.total_weight += weight;
+        }
+        totals
+    }
+
+    // Community averages are x100 for precision, and 0 once the last rating is removed
+    fn store_rating_totals(&self, wine_id: u32, totals: &RatingTotals) {
+        self.wine_rating_totals(wine_id).set(totals);
+        if totals.count == 0 {
+            self.wine_average_rating(wine_id).clear();
+            self.wine_weighted_average_rating(wine_id).clear();
+        } else {
+            let average = totals.sum * 100 / totals.count as u64;
+            self.wine_average_rating(wine_id).set(average as u16);
+            let weighted_average = totals.weighted_sum * 100 / totals.total_weight as u64;
+            self.wine_weighted_average_rating(wine_id).set(weighted_average as u16);
+        }
+    }
+
+    // View functions
+    // Paginated: returns the next cursor (0 when done) followed by at most `page_size` ratings
+    #[view(getWineRatings)]
+    fn get_wine_ratings(
+        &self,
+        wine_id: u32,
+        cursor: u64,
+        page_size: u32,
+    ) -> MultiValue2<u64, MultiValueEncoded<WineRating<Self::Api>>> {
+        let ratings = self.wine_ratings(wine_id);
+        let page = Page::new(cursor, page_size, MAX_RATINGS_PAGE_SIZE, ratings.len());
+
+        let mut result = MultiValueEncoded::new();
+        for index in page.start..page.end {
+            result.push(ratings.get(index + 1));
+        }
+        (page.next_cursor, result).into()
+    }
+
+    // Total for sizing getWineRatings requests
+    #[view(getWineRatingCount)]
+    fn get_wine_rating_count(&self, wine_id: u32) -> usize {
+        self.wine_ratings(wine_id).len()
+    }
+
+    #[view(getWineAverageRating)]
+    fn get_wine_average_rating(&self, wine_id: u32) -> u16 {
+        self.wine_average_rating(wine_id).get()
+    }
+
+    // Average with verified-purchase ratings weighted by VERIFIED_RATING_WEIGHT, x100. Only
+    // maintained for wines whose ratings changed since weighting was introduced.
+    #[view(getWineWeightedAverageRating)]
+    fn get_wine_weighted_average_rating(&self, wine_id: u32) -> u16 {
+        self.wine_weighted_average_rating(wine_id).get()
+    }
+
+    // Average of sommelier ratings, x100
+    #[view(getWineProfessionalAverageRating)]
+    fn get_wine_professional_average_rating(&self, wine_id: u32) -> u16 {
+        self.wine_professional_average_rating(wine_id).get()
+    }
+
+    // Weighted community average and professional average blended by
+    // PROFESSIONAL_SCORE_WEIGHT_BPS, x100. Falls back to whichever track has ratings.
+    #[view(getWineCombinedScore)]
+    fn get_wine_combined_score(&self, wine_id: u32) -> u16 {
+        let community = self.wine_weighted_average_rating(wine_id).get() as u64;
+        let professional = self.wine_professional_average_rating(wine_id).get() as u64;
+        if professional == 0 {
+            return community as u16;
+        }
+        if community == 0 {
+            return professional as u16;
+        }
+
+        let combined = (community * (10_000 - PROFESSIONAL_SCORE_WEIGHT_BPS)
+            + professional * PROFESSIONAL_SCORE_WEIGHT_BPS)
+            / 10_000;
+        combined as u16
+    }
+
+    #[view(isVerifiedBuyer)]
+    fn is_verified_buyer(&self, wine_id: u32, buyer: &ManagedAddress) -> bool {
+        self.verified_buyer(wine_id, buyer).get()
+    }
+
+    // Whether the rater's rating is flagged as a verified purchase
+    #[view(isVerifiedRating)]
+    fn is_verified_rating(&self, wine_id: u32, rater: &ManagedAddress) -> bool {
+        self.verified_rating(wine_id, rater).get()
+    }
+
+    // Storage mappers
+    #[storage_mapper("wineRatings")]
+    fn wine_ratings(&self, wine_id: u32) -> VecMapper<WineRating<Self::Api>>;
+
+    #[storage_mapper("wineUserRating")]
+    fn wine_user_rating(&self, wine_id: u32, user: &ManagedAddress) -> SingleValueMapper<u8>;
+
+    #[storage_mapper("wineAverageRating")]
+    fn wine_average_rating(&self, wine_id: u32) -> SingleValueMapper<u16>;
+
+    #[storage_mapper("wineUserRatingIndex")]
+    fn wine_user_rating_index(&self, wine_id: u32, user: &ManagedAddress) -> SingleValueMapper<usize>;
+
+    #[storage_mapper("wineRatingTotals")]
+    fn wine_rating_totals(&self, wine_id: u32) -> SingleValueMapper<RatingTotals>;
+
+    #[storage_mapper("wineWeightedAverageRating")]
+    fn wine_weighted_average_rating(&self, wine_id: u32) -> SingleValueMapper<u16>;
+
+    #[storage_mapper("verifiedBuyer")]
+    fn verified_buyer(&self, wine_id: u32, buyer: &ManagedAddress) -> SingleValueMapper<bool>;
+
+    #[storage_mapper("wineProfessionalRatingTotals")]
+    fn wine_professional_rating_totals(&self, wine_id: u32) -> SingleValueMapper<RatingTotals>;
+
+    #[storage_mapper("wineProfessionalAverageRating")]
+    fn wine_professional_average_rating(&self, wine_id: u32) -> SingleValueMapper<u16>;
+
+    // Managed through the sommeliers module
+    #[storage_mapper("sommeliers")]
+    fn sommeliers(&self) -> UnorderedSetMapper<ManagedAddress>;
+
+    // Set for ratings submitted by a registered sommelier
+    #[storage_mapper("professionalRating")]
+    fn professional_rating(&self, wine_id: u32, rater: &ManagedAddress) -> SingleValueMapper<bool>;
+
+    #[storage_mapper("verifiedRating")]
+    fn verified_rating(&self, wine_id: u32, rater: &ManagedAddress) -> SingleValueMapper<bool>;
+
+    // Events
+    #[event("wineRated")]
+    fn wine_rated_event(
+        &self,
+        #[indexed] wine_id: u32,
+        #[indexed] rater: &ManagedAddress,
+        #[indexed] verified: bool,
+        rating: u8,
+    );
+
+    #[event("wineRatingUpdated")]
+    fn wine_rating_updated_event(
+        &self,
+        #[indexed] wine_id: u32,
+        #[indexed] rater: &ManagedAddress,
+        #[indexed] previous_rating: u8,
+        rating: u8,
+    );
+
+    #[event("wineRatingDeleted")]
+    fn wine_rating_deleted_event(
+        &self,
+        #[indexed] wine_id: u32,
+        #[indexed] rater: &ManagedAddress,
+        rating: u8,
+    );
+
+    #[event("verifiedBuyerRecorded")]
+    fn verified_buyer_recorded_event(
+        &self,
+        #[indexed] wine_id: u32,
+        #[indexed] buyer: &ManagedAddress,
+        recorded_by: &ManagedAddress,
+    );
+}
@@ -0,0 +1,136 @@
+use multiversx_sc::imports::*;
+use wine_common::errors::*;
+use wine_roles::Role;
+
+use crate::proxies::wine_marketplace_proxy::WineMarketplaceProxy;
+pub use wine_common::WineReadModel;
+
+// Subscribers are pushed to one by one, so keep the list short enough for a single tx
+pub const MAX_READ_MODEL_SUBSCRIBERS: usize = 10;
+pub const READ_MODEL_PUSH_GAS: u64 = 5_000_000;
+// Royalty cap: 10%
+pub const MAX_ROYALTY_BPS: u32 = 1000;
+
+#[multiversx_sc::module]
+pub trait ReadModelModule:
+    wine_roles::RolesModule + crate::admin::AdminModule + crate::provenance::ProvenanceModule
+{
+    #[endpoint(addReadModelSubscriber)]
+    fn add_read_model_subscriber(&self, marketplace: ManagedAddress) {
+        self.require_role(Role::Admin);
+        require!(
+            self.blockchain().is_smart_contract(&marketplace),
+            ERR_SUBSCRIBER_MUST_BE_A_SMART_CONTRACT
+        );
+        self.read_model_subscribers().insert(marketplace);
+        require!(
+            self.read_model_subscribers().len() <= MAX_READ_MODEL_SUBSCRIBERS,
+            ERR_TOO_MANY_READ_MODEL_SUBSCRIBERS
+        );
+    }
+
+    #[endpoint(removeReadModelSubscriber)]
+    fn remove_read_model_subscriber(&self, marketplace: ManagedAddress) {
+        self.require_role(Role::Admin);
+        self.read_model_subscribers().swap_remove(&marketplace);
+    }
+
+    // Royalty paid to the producer on secondary sales, in basis points
+    #[endpoint(setWineRoyalty)]
+    fn set_wine_royalty(&self, wine_id: u32, royalty_bps: u32) {
+        let caller = self.blockchain().get_caller();
+        require!(caller == self.wine_owner(wine_id).get(), ERR_ONLY_WINE_OWNER_CAN_SET_ROYALTY);
+        require!(royalty_bps <= MAX_ROYALTY_BPS, ERR_ROYALTY_CANNOT_EXCEED_10_PERCENT);
+
+        self.update_wine_read_model(wine_id, |read_model| read_model.royalty_bps = royalty_bps);
+    }
+
+    // Push the current record again, e.g. to a marketplace that subscribed later
+    #[endpoint(resyncWineReadModel)]
+    fn resync_wine_read_model(&self, wine_id: u32) {
+        let caller = self.blockchain().get_caller();
+        require!(
+            self.has_role(Role::Admin, &caller) || caller == self.wine_owner(wine_id).get(),
+            ERR_ONLY_ADMIN_OR_WINE_OWNER_CAN_RESYNC
+        );
+
+        let read_model = self.require_wine_read_model(wine_id);
+        self.push_wine_read_model(wine_id, &read_model);
+    }
+
+    // Internal helpers
+    fn require_wine_read_model(&self, wine_id: u32) -> WineReadModel<Self::Api> {
+        require!(!self.wine_read_model(wine_id).is_empty(), ERR_WINE_DOES_NOT_EXIST);
+        self.wine_read_model(wine_id).get()
+    }
+
+    // Apply a change to a wine's record and push the result to all subscribers
+    fn update_wine_read_model<F: FnOnce(&mut WineReadModel<Self::Api>)>(&self, wine_id: u32, f: F) {
+        let mut read_model = self.require_wine_read_model(wine_id);
+        f(&mut read_model);
+        self.wine_read_model(wine_id).set(&read_model);
+        self.push_wine_read_model(wine_id, &read_model);
+    }
+
+    // Fire-and-forget calls: a failing subscriber does not revert the registry update
+    fn push_wine_read_model(&self, wine_id: u32, read_model: &WineReadModel<Self::Api>) {
+        for marketplace in self.read_model_subscribers().iter() {
+            self.tx()
+                .to(&marketplace)
+                .typed(WineMarketplaceProxy)
+                .sync_wine_read_model(wine_id, read_model)
+                .gas(READ_MODEL_PUSH_GAS)
+                .transfer_execute();
+        }
+
+        self.wine_read_model_pushed_event(wine_id, read_model);
+    }
+
+    fn push_producer_verification(&self, producer: &ManagedAddress, verified: bool) {
+        for marketplace in self.read_model_subscribers().iter() {
+            self.tx()
+                .to(&marketplace)
+                .typed(WineMarketplaceProxy)
+                .sync_producer_verification(producer, verified)
+                .gas(READ_MODEL_PUSH_GAS)
+                .transfer_execute();
+        }
+    }
+
+    fn push_grade_price_floor(&self, quality_grade: u8, min_price: &BigUint) {
+        for marketplace in self.read_model_subscribers().iter() {
+            self.tx()
+                .to(&marketplace)
+                .typed(WineMarketplaceProxy)
+                .sync_grade_price_floor(quality_grade, min_price)
+                .gas(READ_MODEL_PUSH_GAS)
+                .transfer_execute();
+        }
+    }
+
+    // View functions
+    #[view(getWineReadModel)]
+    fn get_wine_read_model(&self, wine_id: u32) -> WineReadModel<Self::Api> {
+        self.require_wine_read_model(wine_id)
+    }
+
+    #[view(getReadModelSubscribers)]
+    fn get_read_model_subscribers(&self) -> MultiValueEncoded<ManagedAddress> {
+        self.read_model_subscribers().iter().collect()
+    }
+
+    // Storage mappers
+    #[storage_mapper("wineReadModel")]
+    fn wine_read_model(&self, wine_id: u32) -> SingleValueMapper<WineReadModel<Self::Api>>;
+
+    #[storage_mapper("readModelSubscribers")]
+    fn read_model_subscribers(&self) -> UnorderedSetMapper<ManagedAddress>;
+
+    // Events
+    #[event("wineReadModelPushed")]
+    fn wine_read_model_pushed_event(
+        &self,
+        #[indexed] wine_id: u32,
+        read_model: &WineReadModel<Self::Api>,
+    );
+}
@@ -0,0 +1,93 @@
+use multiversx_sc::imports::*;
+use wine_common::errors::*;
+use wine_roles::Role;
+
+use crate::pagination::{Page, MAX_REGION_NAME_LENGTH, MAX_REGIONS_PAGE_SIZE};
+use crate::registration::OTHER_REGION;
+
+// Curated list of appellations and regions that registrations are checked against. While the
+// list is empty any region is accepted; after that, unlisted wines register as "Other".
+#[multiversx_sc::module]
+pub trait RegionsModule:
+    wine_roles::RolesModule
+    + crate::admin::AdminModule
+    + crate::provenance::ProvenanceModule
+    + crate::read_model::ReadModelModule
+    + crate::registration::RegistrationModule
+    + crate::certification::CertificationModule
+{
+    #[endpoint(addRegion)]
+    fn add_region(&self, region: ManagedBuffer) {
+        self.require_region_curator();
+        require!(!region.is_empty(), ERR_REGION_NAME_IS_REQUIRED);
+        require!(region.len() <= MAX_REGION_NAME_LENGTH, ERR_REGION_NAME_IS_TOO_LONG);
+        require!(region != OTHER_REGION, ERR_OTHER_IS_ALWAYS_ACCEPTED);
+        require!(self.valid_regions().insert(region.clone()), ERR_REGION_ALREADY_LISTED);
+
+        self.region_added_event(&region, &self.blockchain().get_caller());
+    }
+
+    // Wines already registered with the region keep it
+    #[endpoint(removeRegion)]
+    fn remove_region(&self, region: ManagedBuffer) {
+        self.require_region_curator();
+        require!(self.valid_regions().swap_remove(&region), ERR_REGION_NOT_LISTED);
+
+        self.region_removed_event(&region, &self.blockchain().get_caller());
+    }
+
+    // Internal helpers
+    fn require_region_curator(&self) {
+        let caller = self.blockchain().get_caller();
+        require!(
+            self.has_role(Role::Admin, &caller) || self.is_certification_authority(&caller),
+            ERR_ONLY_ADMIN_OR_CERTIFICATION_AUTHORITY_CAN_CURATE_REGIONS
+        );
+    }
+
+    // View functions
+    #[view(isValidRegion)]
+    fn is_valid_region(&self, region: &ManagedBuffer) -> bool {
+        self.valid_regions().is_empty()
+            || *region == OTHER_REGION
+            || self.valid_regions().contains(region)
+    }
+
+    // Paginated: returns the next cursor (0 when done) followed by at most `page_size` regions.
+    // Order is not stable across removals.
+    #[view(getRegions)]
+    fn get_regions(
+        &self,
+        cursor: u64,
+        page_size: u32,
+    ) -> MultiValue2<u64, MultiValueEncoded<ManagedBuffer>> {
+        let regions = self.valid_regions();
+        let page = Page::new(cursor, page_size, MAX_REGIONS_PAGE_SIZE, regions.len());
+
+        let mut result = MultiValueEncoded::new();
+        for index in page.start..page.end {
+            result.push(regions.get_by_index(index + 1));
+        }
+        (page.next_cursor, result).into()
+    }
+
+    #[view(getRegionCount)]
+    fn get_region_count(&self) -> usize {
+        self.valid_regions().len()
+    }
+
+    // Events
+    #[event("regionAdded")]
+    fn region_added_event(
+        &self,
+        #[indexed] region: &ManagedBuffer,
+        added_by: &ManagedAddress,
+    );
+
+    #[event("regionRemoved")]
+    fn region_removed_event(
+        &self,
+        #[indexed] region: &ManagedBuffer,
+        removed_by: &ManagedAddress,
+    );
+}
@@ -0,0 +1,991 @@
+use multiversx_sc::derive_imports::*;
+use multiversx_sc::imports::*;
+use wine_common::errors::*;
+use wine_common::ipfs::is_valid_ipfs_cid;
+use wine_roles::Role;
+pub use wine_common::WineDetails;
+
+use crate::lifecycle::WineStatus;
+use crate::migration::{WineDetailsV1, WINE_DETAILS_LAYOUT};
+use crate::pagination::{
+    Page, MAX_DETAILS_VERSIONS_PAGE_SIZE, MAX_FEE_CHANGES_PAGE_SIZE, MAX_PRICE_CHANGES_PAGE_SIZE,
+};
+use crate::provenance::{OwnershipChangeReason, WineAction};
+use crate::read_model::WineReadModel;
+
+// Producer-supplied fields of a registration, as accepted by `registerWines`
+#[derive(TopEncode, TopDecode, NestedEncode, NestedDecode, TypeAbi, PartialEq, Debug, Clone)]
+pub struct WineRegistration<M: ManagedTypeApi> {
+    pub vineyard: ManagedBuffer<M>,
+    pub variety: ManagedBuffer<M>,
+    pub vintage_year: u32,
+    pub quality_grade: u8,
+    pub alcohol_content: u16,
+    pub region: ManagedBuffer<M>,
+    pub certification: ManagedBuffer<M>,
+    pub producer_signature: ManagedBuffer<M>,
+    pub ipfs_hash: ManagedBuffer<M>,
+    pub total_bottles: u32,
+    pub price_per_bottle: BigUint<M>,
+    pub is_organic: bool,
+    pub harvest_date: u64,
+    pub aging_process: ManagedBuffer<M>,
+    pub tasting_notes: ManagedBuffer<M>,
+}
+
+// Aggregates over all wines of a producer, updated as the underlying data changes
+#[derive(TopEncode, TopDecode, TypeAbi, PartialEq, Debug, Default)]
+pub struct ProducerStats {
+    pub wines_registered: u32,
+    pub bottles_minted: u64,
+    pub bottles_sold: u64,
+    // Community and professional ratings alike
+    pub rating_count: u32,
+    pub rating_sum: u64,
+    pub average_rating: u16, // x100 for precision
+    // Valid certifications, i.e. not revoked
+    pub certifications: u32,
+}
+
+impl ProducerStats {
+    pub fn add_ratings(&mut self, sum: u64, count: u32) {
+        self.rating_sum += sum;
+        self.rating_count += count;
+        self.update_average_rating();
+    }
+
+    pub fn remove_ratings(&mut self, sum: u64, count: u32) {
+        self.rating_sum -= sum;
+        self.rating_count -= count;
+        self.update_average_rating();
+    }
+
+    fn update_average_rating(&mut self) {
+        self.average_rating = if self.rating_count == 0 {
+            0
+        } else {
+            (self.rating_sum * 100 / self.rating_count as u64) as u16
+        };
+    }
+}
+
+// One entry of a wine's details changelog. Versions start at 1 (as registered).
+#[derive(TopEncode, TopDecode, NestedEncode, NestedDecode, TypeAbi, PartialEq, Debug)]
+pub struct WineDetailsVersion<M: ManagedTypeApi> {
+    pub version: u32,
+    // keccak256 of the top-encoded WineDetails of this version
+    pub details_hash: ManagedByteArray<M, 32>,
+    // When this version took effect
+    pub timestamp: u64,
+}
+
+// One entry of a wine's suggested retail price history
+#[derive(TopEncode, TopDecode, NestedEncode, NestedDecode, TypeAbi, PartialEq, Debug)]
+pub struct WinePriceChange<M: ManagedTypeApi> {
+    pub price_per_bottle: BigUint<M>,
+    // When this price took effect
+    pub timestamp: u64,
+}
+
+// One registration fee change; the fee stays in force until the next change of that token
+#[derive(TopEncode, TopDecode, NestedEncode, NestedDecode, TypeAbi, PartialEq, Debug)]
+pub struct RegistrationFeeChange<M: ManagedTypeApi> {
+    pub token: EgldOrEsdtTokenIdentifier<M>,
+    pub amount: BigUint<M>,
+    pub timestamp: u64,
+}
+
+// Accepted for any region once the region list is in use
+pub const OTHER_REGION: &[u8; 5] = b"Other";
+
+// Bounds the gas of a single registerWines call
+pub const MAX_BATCH_REGISTRATIONS: usize = 20;
+
+#[multiversx_sc::module]
+pub trait RegistrationModule:
+    wine_roles::RolesModule
+    + crate::admin::AdminModule
+    + crate::provenance::ProvenanceModule
+    + crate::read_model::ReadModelModule
+{
+    // Configure the registration fee for a payment token; EGLD is always accepted. An amount
+    // of 0 stops accepting an ESDT, or waives the fee in EGLD.
+    #[endpoint(setRegistrationFee)]
+    fn set_registration_fee(&self, token: EgldOrEsdtTokenIdentifier, amount: BigUint) {
+        self.require_role(Role::FeeManager);
+        if token.is_egld() {
+            self.registration_fee().set(&amount);
+        } else {
+            require!(token.is_valid(), ERR_INVALID_FEE_TOKEN);
+            let esdt_token = token.clone().unwrap_esdt();
+            if amount == 0 {
+                self.esdt_registration_fees().remove(&esdt_token);
+            } else {
+                self.esdt_registration_fees().insert(esdt_token, amount.clone());
+            }
+        }
+
+        self.record_registration_fee_change(token, amount);
+    }
+
+    // Ed25519 key that `producer_signature` is checked against for the caller's registrations
+    #[endpoint(setProducerPublicKey)]
+    fn set_producer_public_key(&self, public_key: ManagedByteArray<Self::Api, 32>) {
+        let producer = self.blockchain().get_caller();
+        self.producer_public_key(&producer).set(&public_key);
+        self.producer_public_key_set_event(&producer, &public_key);
+    }
+
+    // When enabled, producers must have a public key registered before registering wines.
+    // Producers with a key always have their signatures checked.
+    #[endpoint(setRequireProducerSignatures)]
+    fn set_require_producer_signatures(&self, required: bool) {
+        self.require_role(Role::Admin);
+        self.require_producer_signatures().set(required);
+    }
+
+    // Anti-spam throttle: registrations each producer may make per epoch, verified producers
+    // exempt. 0 lifts the limit.
+    #[endpoint(setRegistrationRateLimit)]
+    fn set_registration_rate_limit(&self, max_per_epoch: u32) {
+        self.require_role(Role::Admin);
+        self.registration_rate_limit().set(max_per_epoch);
+    }
+
+    // Address besides the owner allowed to withdraw collected fees
+    #[endpoint(setFeeTreasury)]
+    fn set_fee_treasury(&self, treasury: ManagedAddress) {
+        self.require_role(Role::FeeManager);
+        self.fee_treasury().set(&treasury);
+    }
+
+    #[endpoint(withdrawCollectedFees)]
+    fn withdraw_collected_fees(
+        &self,
+        token: EgldOrEsdtTokenIdentifier,
+        amount: BigUint,
+        to: ManagedAddress,
+    ) {
+        let caller = self.blockchain().get_caller();
+        require!(
+            self.has_role(Role::FeeManager, &caller)
+                || (!self.fee_treasury().is_empty() && caller == self.fee_treasury().get()),
+            ERR_ONLY_FEE_MANAGERS_OR_TREASURY_CAN_WITHDRAW_FEES
+        );
+        require!(amount > 0, ERR_AMOUNT_MUST_BE_GREATER_THAN_0);
+        // Funds set aside for rating rewards cannot be withdrawn as fees
+        let reserved = if token.is_esdt() {
+            self.reward_pool(&token.clone().unwrap_esdt()).get()
+        } else {
+            BigUint::zero()
+        };
+        require!(
+            &amount + &reserved <= self.blockchain().get_sc_balance(&token, 0),
+            ERR_INSUFFICIENT_BALANCE
+        );
+
+        self.withdrawn_fees(&token).update(|withdrawn| *withdrawn += &amount);
+        self.send().direct(&to, &token, 0, &amount);
+
+        self.fees_withdrawn_event(&token, &to, &amount);
+    }
+
+    // Enhanced wine registration with comprehensive data, paid for in EGLD or any configured
+    // fee token
+    #[payable("*")]
+    #[endpoint(registerWine)]
+    fn register_wine(
+        &self,
+        vineyard: ManagedBuffer,
+        variety: ManagedBuffer,
+        vintage_year: u32,
+        quality_grade: u8,
+        alcohol_content: u16,
+        region: ManagedBuffer,
+        certification: ManagedBuffer,
+        producer_signature: ManagedBuffer,
+        ipfs_hash: ManagedBuffer,
+        total_bottles: u32,
+        price_per_bottle: BigUint,
+        is_organic: bool,
+        harvest_date: u64,
+        aging_process: ManagedBuffer,
+        tasting_notes: ManagedBuffer,
+    ) -> u32 {
+        self.require_not_paused();
+        let caller = self.blockchain().get_caller();
+        self.consume_registration_quota(&caller, 1);
+        self.collect_registration_fee(1);
+
+        let registration = WineRegistration {
+            vineyard,
+            variety,
+            vintage_year,
+            quality_grade,
+            alcohol_content,
+            region,
+            certification,
+            producer_signature,
+            ipfs_hash,
+            total_bottles,
+            price_per_bottle,
+            is_organic,
+            harvest_date,
+            aging_process,
+            tasting_notes,
+        };
+        if let Some(error) = self.wine_registration_error(&registration) {
+            sc_panic!(error);
+        }
+
+        self.verify_producer_signature(&caller, &registration);
+        self.store_wine(&caller, registration)
+    }
+
+    // Register several wines for one aggregated fee (registration fee x entries). Returns the
+    // assigned wine ids in entry order; a failing entry is reported by its zero-based index.
+    #[payable("*")]
+    #[endpoint(registerWines)]
+    fn register_wines(
+        &self,
+        registrations: MultiValueEncoded<WineRegistration<Self::Api>>,
+    ) -> MultiValueEncoded<u32> {
+        self.require_not_paused();
+        let count = registrations.len();
+        require!(count > 0, ERR_NO_WINES_TO_REGISTER);
+        require!(count <= MAX_BATCH_REGISTRATIONS, ERR_TOO_MANY_WINES_IN_ONE_BATCH);
+
+        let caller = self.blockchain().get_caller();
+        self.consume_registration_quota(&caller, count as u32);
+        self.collect_registration_fee(count as u64);
+
+        let mut wine_ids = MultiValueEncoded::new();
+        for (index, registration) in registrations.into_iter().enumerate() {
+            if let Some(error) = self.wine_registration_error(&registration) {
+                sc_panic!("Entry {}: {}", index, ManagedBuffer::from(error));
+            }
+            self.verify_producer_signature(&caller, &registration);
+            wine_ids.push(self.store_wine(&caller, registration));
+        }
+
+        wine_ids
+    }
+
+    // Correct the descriptive details of a wine. Producer only. Every version is recorded in
+    // the changelog so certifications can be checked against the version they certified.
+    #[endpoint(updateWineDetails)]
+    fn update_wine_details(
+        &self,
+        wine_id: u32,
+        ipfs_hash: ManagedBuffer,
+        aging_process: ManagedBuffer,
+        tasting_notes: ManagedBuffer,
+    ) -> u32 {
+        self.require_wine_exists(wine_id);
+        let caller = self.blockchain().get_caller();
+        require!(caller == self.wine_producer(wine_id), ERR_ONLY_PRODUCER_CAN_UPDATE_WINE_DETAILS);
+        require!(!ipfs_hash.is_empty(), ERR_IPFS_HASH_IS_REQUIRED);
+        require!(is_valid_ipfs_cid(&ipfs_hash), ERR_INVALID_IPFS_CID);
+
+        let mut wine = self.load_wine_details(wine_id);
+        let mut history = self.wine_details_history(wine_id);
+        // The registered version is only recorded once the wine is first updated
+        if history.is_empty() {
+            history.push(&WineDetailsVersion {
+                version: 1,
+                details_hash: self.wine_details_hash(&wine),
+                timestamp: wine.production_date,
+            });
+        }
+
+        wine.ipfs_hash = ipfs_hash;
+        wine.aging_process = aging_process;
+        wine.tasting_notes = tasting_notes;
+        self.store_wine_details(wine_id, &wine);
+
+        let version = history.len() as u32 + 1;
+        let details_hash = self.wine_details_hash(&wine);
+        history.push(&WineDetailsVersion {
+            version,
+            details_hash: details_hash.clone(),
+            timestamp: self.blockchain().get_block_timestamp(),
+        });
+
+        self.wine_details_updated_event(wine_id, &caller, version, &details_hash);
+
+        version
+    }
+
+    // Change the suggested retail price per bottle, also pushed to the read model
+    #[endpoint(updateWinePrice)]
+    fn update_wine_price(&self, wine_id: u32, new_price: BigUint) {
+        self.require_wine_exists(wine_id);
+        let caller = self.blockchain().get_caller();
+        require!(caller == self.wine_producer(wine_id), ERR_ONLY_PRODUCER_CAN_UPDATE_THE_PRICE);
+        require!(new_price > 0, ERR_PRICE_PER_BOTTLE_MUST_BE_GREATER_THAN_0);
+        let price_update = WineAction::PriceUpdate(new_price.clone());
+        self.require_co_owner_approval(wine_id, &caller, price_update);
+
+        let mut wine = self.load_wine_details(wine_id);
+        require!(new_price != wine.price_per_bottle, ERR_PRICE_IS_UNCHANGED);
+        let mut history = self.wine_price_history(wine_id);
+        // The registered price is only recorded once the price is first changed
+        if history.is_empty() {
+            history.push(&WinePriceChange {
+                price_per_bottle: wine.price_per_bottle.clone(),
+                timestamp: wine.production_date,
+            });
+        }
+
+        wine.price_per_bottle = new_price.clone();
+        self.store_wine_details(wine_id, &wine);
+        self.update_wine_read_model(wine_id, |read_model| {
+            read_model.price_per_bottle = new_price.clone()
+        });
+        history.push(&WinePriceChange {
+            price_per_bottle: new_price.clone(),
+            timestamp: self.blockchain().get_block_timestamp(),
+        });
+
+        self.wine_price_updated_event(wine_id, &caller, &new_price);
+    }
+
+    // Release a further tranche of bottles: both the total and the available count grow
+    #[endpoint(restockWine)]
+    fn restock_wine(&self, wine_id: u32, additional_bottles: u32) {
+        self.require_wine_exists(wine_id);
+        let caller = self.blockchain().get_caller();
+        require!(caller == self.wine_producer(wine_id), ERR_ONLY_PRODUCER_CAN_RESTOCK_THE_WINE);
+        require!(additional_bottles > 0, ERR_ADDITIONAL_BOTTLES_MUST_BE_GREATER_THAN_0);
+        require!(
+            self.require_wine_read_model(wine_id).status != WineStatus::Recalled,
+            ERR_WINE_HAS_BEEN_RECALLED
+        );
+
+        let mut wine = self.load_wine_details(wine_id);
+        require!(
+            additional_bottles <= u32::MAX - wine.total_bottles,
+            ERR_TOTAL_BOTTLES_WOULD_OVERFLOW
+        );
+        wine.total_bottles += additional_bottles;
+        wine.available_bottles += additional_bottles;
+        self.store_wine_details(wine_id, &wine);
+
+        self.wine_restocked_event(
+            wine_id,
+            &caller,
+            additional_bottles,
+            wine.total_bottles,
+            wine.available_bottles,
+        );
+    }
+
+    // Update wine availability (for marketplace integration)
+    #[endpoint(updateWineAvailability)]
+    fn update_wine_availability(&self, wine_id: u32, bottles_sold: u32) {
+        let caller = self.blockchain().get_caller();
+        let is_integration = self.get_integration_permissions(&caller).can_decrement_availability;
+        require!(
+            caller == self.wine_owner(wine_id).get() || is_integration,
+            ERR_NOT_AUTHORIZED_TO_UPDATE_AVAILABILITY
+        );
+        // While listed, only marketplaces can change availability
+        require!(is_integration || !self.is_wine_locked(wine_id), ERR_WINE_IS_LOCKED_BY_A_MARKETPLACE);
+        self.require_wine_exists(wine_id);
+        require!(
+            self.require_wine_read_model(wine_id).status != WineStatus::Recalled,
+            ERR_WINE_HAS_BEEN_RECALLED
+        );
+
+        let mut wine = self.load_wine_details(wine_id);
+        require!(wine.available_bottles >= bottles_sold, ERR_INSUFFICIENT_BOTTLES_AVAILABLE);
+        wine.available_bottles -= bottles_sold;
+        self.store_wine_details(wine_id, &wine);
+        self.update_producer_stats(wine_id, |stats| stats.bottles_sold += bottles_sold as u64);
+
+        self.wine_availability_updated_event(wine_id, &caller, bottles_sold, wine.available_bottles);
+    }
+
+    // Internal helpers
+    // Count `count` registrations against the producer's allowance for the current epoch
+    fn consume_registration_quota(&self, producer: &ManagedAddress, count: u32) {
+        let remaining = match self.remaining_registrations(producer) {
+            Some(remaining) => remaining,
+            None => return,
+        };
+        require!(count <= remaining, ERR_REGISTRATION_RATE_LIMIT_EXCEEDED);
+
+        let used = self.registration_rate_limit().get() - remaining;
+        let current_epoch = self.blockchain().get_block_epoch();
+        self.producer_epoch_registrations(producer).set((current_epoch, used + count));
+    }
+
+    // None when the producer is not throttled
+    fn remaining_registrations(&self, producer: &ManagedAddress) -> Option<u32> {
+        let max_per_epoch = self.registration_rate_limit().get();
+        if max_per_epoch == 0 || self.verified_producer(producer).get() {
+            return None;
+        }
+
+        let registrations_mapper = self.producer_epoch_registrations(producer);
+        if registrations_mapper.is_empty() {
+            return Some(max_per_epoch);
+        }
+        let (epoch, used) = registrations_mapper.get();
+        if epoch != self.blockchain().get_block_epoch() {
+            return Some(max_per_epoch);
+        }
+        Some(max_per_epoch.saturating_sub(used))
+    }
+
+    // Check the payment covers `count` registrations in its token and refund the excess
+    fn collect_registration_fee(&self, count: u64) {
+        let payment = self.call_value().egld_or_single_esdt();
+        require!(payment.token_nonce == 0, ERR_INVALID_FEE_TOKEN);
+        let total_fee = self.registration_fee_for(&payment.token_identifier) * count;
+        require!(payment.amount >= total_fee, ERR_INSUFFICIENT_REGISTRATION_FEE);
+        self.collected_fees(&payment.token_identifier).update(|collected| *collected += &total_fee);
+
+        let excess = &payment.amount - &total_fee;
+        if excess > 0 {
+            let caller = self.blockchain().get_caller();
+            self.send().direct(&caller, &payment.token_identifier, 0, &excess);
+        }
+    }
+
+    fn record_registration_fee_change(&self, token: EgldOrEsdtTokenIdentifier, amount: BigUint) {
+        self.registration_fee_updated_event(&token, &amount);
+        self.registration_fee_history().push(&RegistrationFeeChange {
+            token,
+            amount,
+            timestamp: self.blockchain().get_block_timestamp(),
+        });
+    }
+
+    // Signs the registry address, the producer and every registration field except the
+    // signature itself, so a signature cannot be replayed elsewhere
+    fn registration_signing_message(
+        &self,
+        producer: &ManagedAddress,
+        registration: &WineRegistration<Self::Api>,
+    ) -> ManagedBuffer {
+        let mut unsigned_registration = registration.clone();
+        unsigned_registration.producer_signature = ManagedBuffer::new();
+        self.serializer().top_encode_to_managed_buffer(&(
+            self.blockchain().get_sc_address(),
+            producer.clone(),
+            unsigned_registration,
+        ))
+    }
+
+    // Fails the transaction on an invalid signature
+    fn verify_producer_signature(
+        &self,
+        producer: &ManagedAddress,
+        registration: &WineRegistration<Self::Api>,
+    ) {
+        let public_key_mapper = self.producer_public_key(producer);
+        if public_key_mapper.is_empty() {
+            require!(!self.require_producer_signatures().get(), ERR_PRODUCER_PUBLIC_KEY_NOT_REGISTERED);
+            return;
+        }
+
+        require!(registration.producer_signature.len() == 64, ERR_INVALID_PRODUCER_SIGNATURE);
+        let message = self.registration_signing_message(producer, registration);
+        self.crypto().verify_ed25519(
+            public_key_mapper.get().as_managed_buffer(),
+            &message,
+            &registration.producer_signature,
+        );
+    }
+
+    fn registration_fee_for(&self, token: &EgldOrEsdtTokenIdentifier) -> BigUint {
+        if token.is_egld() {
+            return self.registration_fee().get();
+        }
+
+        match self.esdt_registration_fees().get(&token.clone().unwrap_esdt()) {
+            Some(fee) => fee,
+            None => sc_panic!(ERR_TOKEN_NOT_ACCEPTED_FOR_REGISTRATION_FEES),
+        }
+    }
+
+    fn wine_registration_error(&self, registration: &WineRegistration<Self::Api>) -> Option<&'static str> {
+        if registration.quality_grade < 1 || registration.quality_grade > 10 {
+            return Some(ERR_QUALITY_GRADE_MUST_BE_BETWEEN_1_10);
+        }
+        if registration.alcohol_content > 2000 {
+            return Some(ERR_ALCOHOL_CONTENT_CANNOT_EXCEED_20_PERCENT); // 2000 = 20.00%
+        }
+        if registration.vintage_year < 1800 || registration.vintage_year > 2030 {
+            return Some(ERR_INVALID_VINTAGE_YEAR);
+        }
+        if registration.total_bottles == 0 {
+            return Some(ERR_TOTAL_BOTTLES_MUST_BE_GREATER_THAN_0);
+        }
+        if registration.price_per_bottle.is_zero() {
+            return Some(ERR_PRICE_PER_BOTTLE_MUST_BE_GREATER_THAN_0);
+        }
+        if registration.ipfs_hash.is_empty() {
+            return Some(ERR_IPFS_HASH_IS_REQUIRED);
+        }
+        if !is_valid_ipfs_cid(&registration.ipfs_hash) {
+            return Some(ERR_INVALID_IPFS_CID);
+        }
+        // Regions are only validated once the curated list has entries
+        let valid_regions = self.valid_regions();
+        if !valid_regions.is_empty()
+            && !valid_regions.contains(&registration.region)
+            && registration.region != OTHER_REGION
+        {
+            return Some(ERR_UNKNOWN_REGION);
+        }
+        None
+    }
+
+    // Store a validated registration for `producer` and publish it. Returns the new wine id.
+    fn store_wine(&self, producer: &ManagedAddress, registration: WineRegistration<Self::Api>) -> u32 {
+        let wine_id = self.wine_counter().get();
+        let current_timestamp = self.blockchain().get_block_timestamp();
+
+        let wine_details = WineDetails {
+            vineyard: registration.vineyard,
+            variety: registration.variety,
+            vintage_year: registration.vintage_year,
+            production_date: current_timestamp,
+            quality_grade: registration.quality_grade,
+            alcohol_content: registration.alcohol_content,
+            region: registration.region,
+            certification: registration.certification,
+            producer_signature: registration.producer_signature,
+            ipfs_hash: registration.ipfs_hash,
+            total_bottles: registration.total_bottles,
+            available_bottles: registration.total_bottles,
+            price_per_bottle: registration.price_per_bottle,
+            is_organic: registration.is_organic,
+            harvest_date: registration.harvest_date,
+            aging_process: registration.aging_process,
+            tasting_notes: registration.tasting_notes,
+            producer: producer.clone(),
+        };
+
+        self.store_wine_details(wine_id, &wine_details);
+        self.record_wine_owner(wine_id, producer, OwnershipChangeReason::Registration);
+        self.wine_counter().set(wine_id + 1);
+        self.total_wines_registered().update(|count| *count += 1);
+
+        // Add to producer's wine list
+        self.producer_wines(producer).push(&wine_id);
+        self.producer_stats_counted(wine_id).set(true);
+        self.update_producer_stats(wine_id, |stats| stats.wines_registered += 1);
+        self.index_wine(wine_id, &wine_details);
+
+        // Publish the read model to subscribed marketplaces
+        let read_model = WineReadModel {
+            price_per_bottle: wine_details.price_per_bottle.clone(),
+            quality_grade: wine_details.quality_grade,
+            certified: false,
+            royalty_bps: 0u32,
+            status: WineStatus::Harvested,
+            producer: producer.clone(),
+            counterfeit: false,
+            certified_until: 0,
+        };
+        self.wine_read_model(wine_id).set(&read_model);
+        self.push_wine_read_model(wine_id, &read_model);
+
+        // Emit comprehensive event
+        self.wine_registered_event(
+            wine_id,
+            producer,
+            &wine_details.vineyard,
+            &wine_details.variety,
+            wine_details.vintage_year,
+            wine_details.total_bottles
+        );
+
+        wine_id
+    }
+
+    fn wine_details_hash(&self, wine: &WineDetails<Self::Api>) -> ManagedByteArray<Self::Api, 32> {
+        let encoded = self.serializer().top_encode_to_managed_buffer(wine);
+        self.crypto().keccak256(&encoded)
+    }
+
+    // Search indexes, see the search module
+    fn index_wine(&self, wine_id: u32, wine: &WineDetails<Self::Api>) {
+        self.wines_by_region(&self.crypto().keccak256(&wine.region)).insert(wine_id);
+        self.wines_by_vintage(wine.vintage_year).insert(wine_id);
+        self.wines_by_variety(&self.crypto().keccak256(&wine.variety)).insert(wine_id);
+    }
+
+    fn unindex_wine(&self, wine_id: u32, wine: &WineDetails<Self::Api>) {
+        self.wines_by_region(&self.crypto().keccak256(&wine.region)).swap_remove(&wine_id);
+        self.wines_by_vintage(wine.vintage_year).swap_remove(&wine_id);
+        self.wines_by_variety(&self.crypto().keccak256(&wine.variety)).swap_remove(&wine_id);
+    }
+
+    // Entries written with an older layout are converted on read. They are rewritten on their
+    // next update, or by the migration module.
+    fn load_wine_details(&self, wine_id: u32) -> WineDetails<Self::Api> {
+        if self.wine_details_layout(wine_id).get() >= WINE_DETAILS_LAYOUT {
+            return self.wine_details(wine_id).get();
+        }
+
+        let legacy = self.legacy_wine_details_v1(wine_id).get();
+        WineDetails {
+            vineyard: legacy.vineyard,
+            variety: legacy.variety,
+            vintage_year: legacy.vintage_year,
+            production_date: legacy.production_date,
+            quality_grade: legacy.quality_grade,
+            alcohol_content: legacy.alcohol_content,
+            region: legacy.region,
+            certification: legacy.certification,
+            producer_signature: legacy.producer_signature,
+            ipfs_hash: legacy.ipfs_hash,
+            total_bottles: legacy.total_bottles,
+            available_bottles: legacy.available_bottles,
+            price_per_bottle: legacy.price_per_bottle,
+            is_organic: legacy.is_organic,
+            harvest_date: legacy.harvest_date,
+            aging_process: legacy.aging_process,
+            tasting_notes: legacy.tasting_notes,
+            producer: self.wine_producer(wine_id),
+        }
+    }
+
+    fn store_wine_details(&self, wine_id: u32, wine: &WineDetails<Self::Api>) {
+        self.wine_details(wine_id).set(wine);
+        self.wine_details_layout(wine_id).set(WINE_DETAILS_LAYOUT);
+    }
+
+    fn clear_wine_details(&self, wine_id: u32) {
+        self.wine_details(wine_id).clear();
+        self.wine_details_layout(wine_id).clear();
+    }
+
+    fn load_producer_stats(&self, producer: &ManagedAddress) -> ProducerStats {
+        let stats_mapper = self.producer_stats(producer);
+        if stats_mapper.is_empty() {
+            ProducerStats::default()
+        } else {
+            stats_mapper.get()
+        }
+    }
+
+    // Wines registered before stats were kept are left out until the migration module has
+    // added them
+    fn update_producer_stats<F: FnOnce(&mut ProducerStats)>(&self, wine_id: u32, f: F) {
+        if !self.producer_stats_counted(wine_id).get() {
+            return;
+        }
+
+        let producer = self.wine_producer(wine_id);
+        let mut stats = self.load_producer_stats(&producer);
+        f(&mut stats);
+        self.producer_stats(&producer).set(&stats);
+    }
+
+    // Retired wines count as removed
+    fn require_wine_exists(&self, wine_id: u32) {
+        require!(!self.wine_details(wine_id).is_empty(), ERR_WINE_DOES_NOT_EXIST);
+    }
+
+    // View functions
+    #[view(getWineDetails)]
+    fn get_wine_details(&self, wine_id: u32) -> WineDetails<Self::Api> {
+        self.require_wine_exists(wine_id);
+        self.load_wine_details(wine_id)
+    }
+
+    // 1 until the details are first updated
+    #[view(getWineDetailsVersion)]
+    fn get_wine_details_version(&self, wine_id: u32) -> u32 {
+        self.require_wine_exists(wine_id);
+        core::cmp::max(self.wine_details_history(wine_id).len() as u32, 1)
+    }
+
+    // Paginated: returns the next cursor (0 when done) followed by at most `page_size`
+    // versions, oldest first. Empty until the details are first updated.
+    #[view(getWineDetailsHistory)]
+    fn get_wine_details_history(
+        &self,
+        wine_id: u32,
+        cursor: u64,
+        page_size: u32,
+    ) -> MultiValue2<u64, MultiValueEncoded<WineDetailsVersion<Self::Api>>> {
+        let history = self.wine_details_history(wine_id);
+        let page = Page::new(cursor, page_size, MAX_DETAILS_VERSIONS_PAGE_SIZE, history.len());
+
+        let mut result = MultiValueEncoded::new();
+        for index in page.start..page.end {
+            result.push(history.get(index + 1));
+        }
+        (page.next_cursor, result).into()
+    }
+
+    // Paginated: returns the next cursor (0 when done) followed by at most `page_size` price
+    // changes, oldest first. Empty until the price is first changed.
+    #[view(getPriceHistory)]
+    fn get_price_history(
+        &self,
+        wine_id: u32,
+        cursor: u64,
+        page_size: u32,
+    ) -> MultiValue2<u64, MultiValueEncoded<WinePriceChange<Self::Api>>> {
+        let history = self.wine_price_history(wine_id);
+        let page = Page::new(cursor, page_size, MAX_PRICE_CHANGES_PAGE_SIZE, history.len());
+
+        let mut result = MultiValueEncoded::new();
+        for index in page.start..page.end {
+            result.push(history.get(index + 1));
+        }
+        (page.next_cursor, result).into()
+    }
+
+    // Paginated: returns the next cursor (0 when done) followed by at most `page_size` fee
+    // changes, oldest first
+    #[view(getRegistrationFeeHistory)]
+    fn get_registration_fee_history(
+        &self,
+        cursor: u64,
+        page_size: u32,
+    ) -> MultiValue2<u64, MultiValueEncoded<RegistrationFeeChange<Self::Api>>> {
+        let history = self.registration_fee_history();
+        let page = Page::new(cursor, page_size, MAX_FEE_CHANGES_PAGE_SIZE, history.len());
+
+        let mut result = MultiValueEncoded::new();
+        for index in page.start..page.end {
+            result.push(history.get(index + 1));
+        }
+        (page.next_cursor, result).into()
+    }
+
+    // Fees collected and withdrawn so far in `token`. Fees paid before tracking started are
+    // not counted but can still be withdrawn.
+    #[view(getCollectedFees)]
+    fn get_collected_fees(&self, token: EgldOrEsdtTokenIdentifier) -> MultiValue2<BigUint, BigUint> {
+        (self.collected_fees(&token).get(), self.withdrawn_fees(&token).get()).into()
+    }
+
+    #[view(getTotalWinesRegistered)]
+    fn get_total_wines_registered(&self) -> u32 {
+        self.total_wines_registered().get()
+    }
+
+    // Message `producer_signature` must sign for this registration by `producer`; the
+    // signature field of `registration` is ignored
+    #[view(getRegistrationSigningMessage)]
+    fn get_registration_signing_message(
+        &self,
+        producer: ManagedAddress,
+        registration: WineRegistration<Self::Api>,
+    ) -> ManagedBuffer {
+        self.registration_signing_message(&producer, &registration)
+    }
+
+    #[view(getProducerPublicKey)]
+    fn get_producer_public_key(
+        &self,
+        producer: &ManagedAddress,
+    ) -> OptionalValue<ManagedByteArray<Self::Api, 32>> {
+        let public_key_mapper = self.producer_public_key(producer);
+        if public_key_mapper.is_empty() {
+            OptionalValue::None
+        } else {
+            OptionalValue::Some(public_key_mapper.get())
+        }
+    }
+
+    #[view(getRegistrationRateLimit)]
+    fn get_registration_rate_limit(&self) -> u32 {
+        self.registration_rate_limit().get()
+    }
+
+    // Registrations the producer can still make this epoch; none when not throttled
+    #[view(getRemainingRegistrations)]
+    fn get_remaining_registrations(&self, producer: ManagedAddress) -> OptionalValue<u32> {
+        self.remaining_registrations(&producer).into()
+    }
+
+    // Fee in EGLD
+    #[view(getRegistrationFee)]
+    fn get_registration_fee(&self) -> BigUint {
+        self.registration_fee().get()
+    }
+
+    // Every accepted fee token with its fee, EGLD first
+    #[view(getRegistrationFees)]
+    fn get_registration_fees(
+        &self,
+    ) -> MultiValueEncoded<MultiValue2<EgldOrEsdtTokenIdentifier, BigUint>> {
+        let mut result = MultiValueEncoded::new();
+        result.push((EgldOrEsdtTokenIdentifier::egld(), self.registration_fee().get()).into());
+        for (token, fee) in self.esdt_registration_fees().iter() {
+            result.push((EgldOrEsdtTokenIdentifier::esdt(token), fee).into());
+        }
+        result
+    }
+
+    // Storage mappers
+    #[storage_mapper("wineDetails")]
+    fn wine_details(&self, wine_id: u32) -> SingleValueMapper<WineDetails<Self::Api>>;
+
+    #[storage_mapper("producerStats")]
+    fn producer_stats(&self, producer: &ManagedAddress) -> SingleValueMapper<ProducerStats>;
+
+    // Set once the wine is included in its producer's stats
+    #[storage_mapper("producerStatsCounted")]
+    fn producer_stats_counted(&self, wine_id: u32) -> SingleValueMapper<bool>;
+
+    // Storage version the wine's details were written with; empty for version 1
+    #[storage_mapper("wineDetailsLayout")]
+    fn wine_details_layout(&self, wine_id: u32) -> SingleValueMapper<u32>;
+
+    // Same key as `wine_details`, read with the version 1 layout
+    #[storage_mapper("wineDetails")]
+    fn legacy_wine_details_v1(&self, wine_id: u32) -> SingleValueMapper<WineDetailsV1<Self::Api>>;
+
+    #[storage_mapper("wineDetailsHistory")]
+    fn wine_details_history(&self, wine_id: u32) -> VecMapper<WineDetailsVersion<Self::Api>>;
+
+    #[storage_mapper("winePriceHistory")]
+    fn wine_price_history(&self, wine_id: u32) -> VecMapper<WinePriceChange<Self::Api>>;
+
+    #[storage_mapper("wineCounter")]
+    fn wine_counter(&self) -> SingleValueMapper<u32>;
+
+    #[storage_mapper("totalWinesRegistered")]
+    fn total_wines_registered(&self) -> SingleValueMapper<u32>;
+
+    #[storage_mapper("registrationFee")]
+    fn registration_fee(&self) -> SingleValueMapper<BigUint>;
+
+    #[storage_mapper("collectedFees")]
+    fn collected_fees(&self, token: &EgldOrEsdtTokenIdentifier) -> SingleValueMapper<BigUint>;
+
+    #[storage_mapper("withdrawnFees")]
+    fn withdrawn_fees(&self, token: &EgldOrEsdtTokenIdentifier) -> SingleValueMapper<BigUint>;
+
+    // Funded and paid out through the rewards module
+    #[storage_mapper("rewardPool")]
+    fn reward_pool(&self, token: &TokenIdentifier) -> SingleValueMapper<BigUint>;
+
+    #[storage_mapper("producerPublicKey")]
+    fn producer_public_key(
+        &self,
+        producer: &ManagedAddress,
+    ) -> SingleValueMapper<ManagedByteArray<Self::Api, 32>>;
+
+    #[storage_mapper("requireProducerSignatures")]
+    fn require_producer_signatures(&self) -> SingleValueMapper<bool>;
+
+    // Set by certification authorities through the producers module
+    #[storage_mapper("verifiedProducer")]
+    fn verified_producer(&self, producer: &ManagedAddress) -> SingleValueMapper<bool>;
+
+    #[storage_mapper("registrationRateLimit")]
+    fn registration_rate_limit(&self) -> SingleValueMapper<u32>;
+
+    // (epoch, registrations made in it) of the producer's latest throttled registration
+    #[storage_mapper("producerEpochRegistrations")]
+    fn producer_epoch_registrations(&self, producer: &ManagedAddress) -> SingleValueMapper<(u64, u32)>;
+
+    #[storage_mapper("feeTreasury")]
+    fn fee_treasury(&self) -> SingleValueMapper<ManagedAddress>;
+
+    #[storage_mapper("registrationFeeHistory")]
+    fn registration_fee_history(&self) -> VecMapper<RegistrationFeeChange<Self::Api>>;
+
+    // Keyed by keccak256 of the region name
+    #[storage_mapper("winesByRegion")]
+    fn wines_by_region(&self, region_hash: &ManagedByteArray<Self::Api, 32>) -> UnorderedSetMapper<u32>;
+
+    #[storage_mapper("winesByVintage")]
+    fn wines_by_vintage(&self, vintage_year: u32) -> UnorderedSetMapper<u32>;
+
+    // Keyed by keccak256 of the variety name
+    #[storage_mapper("winesByVariety")]
+    fn wines_by_variety(
+        &self,
+        variety_hash: &ManagedByteArray<Self::Api, 32>,
+    ) -> UnorderedSetMapper<u32>;
+
+    // Curated through the regions module
+    #[storage_mapper("validRegions")]
+    fn valid_regions(&self) -> UnorderedSetMapper<ManagedBuffer>;
+
+    #[storage_mapper("esdtRegistrationFees")]
+    fn esdt_registration_fees(&self) -> MapMapper<TokenIdentifier, BigUint>;
+
+    // Events
+    #[event("wineRegistered")]
+    fn wine_registered_event(
+        &self,
+        #[indexed] wine_id: u32,
+        #[indexed] owner: &ManagedAddress,
+        #[indexed] vineyard: &ManagedBuffer,
+        variety: &ManagedBuffer,
+        vintage_year: u32,
+        total_bottles: u32,
+    );
+
+    #[event("wineDetailsUpdated")]
+    fn wine_details_updated_event(
+        &self,
+        #[indexed] wine_id: u32,
+        #[indexed] updated_by: &ManagedAddress,
+        #[indexed] version: u32,
+        details_hash: &ManagedByteArray<Self::Api, 32>,
+    );
+
+    #[event("wineRestocked")]
+    fn wine_restocked_event(
+        &self,
+        #[indexed] wine_id: u32,
+        #[indexed] producer: &ManagedAddress,
+        additional_bottles: u32,
+        total_bottles: u32,
+        available_bottles: u32,
+    );
+
+    #[event("winePriceUpdated")]
+    fn wine_price_updated_event(
+        &self,
+        #[indexed] wine_id: u32,
+        #[indexed] updated_by: &ManagedAddress,
+        price_per_bottle: &BigUint,
+    );
+
+    #[event("registrationFeeUpdated")]
+    fn registration_fee_updated_event(
+        &self,
+        #[indexed] token: &EgldOrEsdtTokenIdentifier,
+        amount: &BigUint,
+    );
+
+    #[event("producerPublicKeySet")]
+    fn producer_public_key_set_event(
+        &self,
+        #[indexed] producer: &ManagedAddress,
+        public_key: &ManagedByteArray<Self::Api, 32>,
+    );
+
+    #[event("feesWithdrawn")]
+    fn fees_withdrawn_event(
+        &self,
+        #[indexed] token: &EgldOrEsdtTokenIdentifier,
+        #[indexed] to: &ManagedAddress,
+        amount: &BigUint,
+    );
+
+    #[event("wineAvailabilityUpdated")]
+    fn wine_availability_updated_event(
+        &self,
+        #[indexed] wine_id: u32,
+        #[indexed] updated_by: &ManagedAddress,
+        bottles_sold: u32,
+        available_bottles: u32,
+    );
+}
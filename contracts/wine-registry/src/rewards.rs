@@ -0,0 +1,148 @@
+use multiversx_sc::imports::*;
+use wine_common::errors::*;
+use wine_roles::Role;
+
+// Points credited for each accepted rating, plus a bonus for verified purchases
+pub const RATING_REWARD_POINTS: u64 = 10;
+pub const VERIFIED_RATING_BONUS_POINTS: u64 = 10;
+// Holders settled per settleRewardPoints call
+pub const MAX_SETTLEMENT_BATCH: usize = 100;
+
+// Points ledger for raters. Points accrue on-chain and are settled later into an ESDT reward
+// token, paid from a pool the owner funds up front.
+#[multiversx_sc::module]
+pub trait RewardsModule:
+    wine_roles::RolesModule
+    + crate::admin::AdminModule
+    + crate::provenance::ProvenanceModule
+    + crate::read_model::ReadModelModule
+    + crate::registration::RegistrationModule
+{
+    #[payable("*")]
+    #[endpoint(fundRewardPool)]
+    fn fund_reward_pool(&self) {
+        self.require_role(Role::FeeManager);
+        let payment = self.call_value().single_esdt();
+        require!(payment.token_nonce == 0, ERR_REWARD_TOKEN_MUST_BE_FUNGIBLE);
+        require!(payment.amount > 0, ERR_AMOUNT_MUST_BE_GREATER_THAN_0);
+
+        self.reward_pool(&payment.token_identifier).update(|pool| *pool += &payment.amount);
+
+        self.reward_pool_funded_event(&payment.token_identifier, &payment.amount);
+    }
+
+    // Pays `amount_per_point` of `token` per point to each holder and resets their balance
+    #[endpoint(settleRewardPoints)]
+    fn settle_reward_points(
+        &self,
+        token: TokenIdentifier,
+        amount_per_point: BigUint,
+        holders: MultiValueEncoded<ManagedAddress>,
+    ) {
+        self.require_role(Role::FeeManager);
+        require!(amount_per_point > 0, ERR_AMOUNT_PER_POINT_MUST_BE_GREATER_THAN_0);
+        require!(holders.len() <= MAX_SETTLEMENT_BATCH, ERR_TOO_MANY_HOLDERS);
+
+        for holder in holders.into_iter() {
+            let points = self.reward_points(&holder).take();
+            if points == 0 {
+                continue;
+            }
+
+            let amount = &amount_per_point * points;
+            let pool = self.reward_pool(&token).get();
+            require!(amount <= pool, ERR_INSUFFICIENT_REWARD_POOL);
+            self.reward_pool(&token).set(&(pool - &amount));
+            self.total_reward_points().update(|total| *total -= points);
+            self.send().direct_esdt(&holder, &token, 0, &amount);
+
+            self.reward_points_settled_event(&holder, &token, points, &amount);
+        }
+    }
+
+    // Internal helpers
+    fn credit_rating_points(&self, wine_id: u32, rater: &ManagedAddress, verified: bool) {
+        let points = if verified {
+            RATING_REWARD_POINTS + VERIFIED_RATING_BONUS_POINTS
+        } else {
+            RATING_REWARD_POINTS
+        };
+        self.rating_reward_points(wine_id, rater).set(points);
+        self.reward_points(rater).update(|balance| *balance += points);
+        self.total_reward_points().update(|total| *total += points);
+
+        self.reward_points_credited_event(rater, wine_id, points);
+    }
+
+    // A deleted rating gives back its points, as far as they have not been settled yet
+    fn revoke_rating_points(&self, wine_id: u32, rater: &ManagedAddress) {
+        let awarded = self.rating_reward_points(wine_id, rater).take();
+        let balance = self.reward_points(rater).get();
+        let revoked = core::cmp::min(awarded, balance);
+        if revoked == 0 {
+            return;
+        }
+
+        self.reward_points(rater).set(balance - revoked);
+        self.total_reward_points().update(|total| *total -= revoked);
+
+        self.reward_points_revoked_event(rater, wine_id, revoked);
+    }
+
+    // View functions
+    #[view(getRewardPoints)]
+    fn get_reward_points(&self, address: &ManagedAddress) -> u64 {
+        self.reward_points(address).get()
+    }
+
+    // Unsettled points across all holders
+    #[view(getTotalRewardPoints)]
+    fn get_total_reward_points(&self) -> u64 {
+        self.total_reward_points().get()
+    }
+
+    #[view(getRewardPool)]
+    fn get_reward_pool(&self, token: &TokenIdentifier) -> BigUint {
+        self.reward_pool(token).get()
+    }
+
+    // Storage mappers
+    #[storage_mapper("rewardPoints")]
+    fn reward_points(&self, address: &ManagedAddress) -> SingleValueMapper<u64>;
+
+    #[storage_mapper("totalRewardPoints")]
+    fn total_reward_points(&self) -> SingleValueMapper<u64>;
+
+    // Points credited for a rater's rating of a wine
+    #[storage_mapper("ratingRewardPoints")]
+    fn rating_reward_points(&self, wine_id: u32, rater: &ManagedAddress) -> SingleValueMapper<u64>;
+
+    // Events
+    #[event("rewardPointsCredited")]
+    fn reward_points_credited_event(
+        &self,
+        #[indexed] holder: &ManagedAddress,
+        #[indexed] wine_id: u32,
+        points: u64,
+    );
+
+    #[event("rewardPointsRevoked")]
+    fn reward_points_revoked_event(
+        &self,
+        #[indexed] holder: &ManagedAddress,
+        #[indexed] wine_id: u32,
+        points: u64,
+    );
+
+    #[event("rewardPoolFunded")]
+    fn reward_pool_funded_event(&self, #[indexed] token: &TokenIdentifier, amount: &BigUint);
+
+    #[event("rewardPointsSettled")]
+    fn reward_points_settled_event(
+        &self,
+        #[indexed] holder: &ManagedAddress,
+        #[indexed] token: &TokenIdentifier,
+        points: u64,
+        amount: &BigUint,
+    );
+}
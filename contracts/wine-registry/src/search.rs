@@ -0,0 +1,108 @@
+use multiversx_sc::imports::*;
+use wine_common::errors::*;
+use wine_roles::Role;
+
+use crate::pagination::{Page, MAX_WINES_PAGE_SIZE, MAX_WINE_IDS_PAGE_SIZE};
+use crate::registration::WineDetails;
+
+// Bounds the gas of a single reindexWines call
+pub const MAX_REINDEX_BATCH: u32 = 100;
+
+// Paginated lookups over the secondary indexes kept at registration. Names must match the
+// registered spelling exactly, since the indexes are keyed by their hash.
+#[multiversx_sc::module]
+pub trait SearchModule:
+    wine_roles::RolesModule
+    + crate::admin::AdminModule
+    + crate::provenance::ProvenanceModule
+    + crate::read_model::ReadModelModule
+    + crate::registration::RegistrationModule
+{
+    // Add wines registered before the indexes existed. Already indexed wines are skipped, so
+    // ranges can overlap.
+    #[endpoint(reindexWines)]
+    fn reindex_wines(&self, first_wine_id: u32, count: u32) {
+        self.require_role(Role::Admin);
+        require!(count <= MAX_REINDEX_BATCH, ERR_REINDEX_BATCH_TOO_LARGE);
+        let end = core::cmp::min(first_wine_id.saturating_add(count), self.wine_counter().get());
+        for wine_id in first_wine_id..end {
+            if !self.wine_details(wine_id).is_empty() {
+                self.index_wine(wine_id, &self.load_wine_details(wine_id));
+            }
+        }
+    }
+
+    // Paginated: returns the next cursor (0 when done) followed by at most `page_size` wines
+    // with their ids, in id order
+    #[view(getAllWines)]
+    fn get_all_wines(
+        &self,
+        cursor: u64,
+        page_size: u32,
+    ) -> MultiValue2<u64, MultiValueEncoded<MultiValue2<u32, WineDetails<Self::Api>>>> {
+        // Wine ids start at 1
+        let total = self.wine_counter().get().saturating_sub(1) as usize;
+        let page = Page::new(cursor, page_size, MAX_WINES_PAGE_SIZE, total);
+
+        let mut result = MultiValueEncoded::new();
+        for index in page.start..page.end {
+            let wine_id = index as u32 + 1;
+            if !self.wine_details(wine_id).is_empty() {
+                result.push((wine_id, self.load_wine_details(wine_id)).into());
+            }
+        }
+        (page.next_cursor, result).into()
+    }
+
+    // Paginated: returns the next cursor (0 when done) followed by at most `page_size` wine ids.
+    // Order is not stable across index changes.
+    #[view(getWinesByRegion)]
+    fn get_wines_by_region(
+        &self,
+        region: ManagedBuffer,
+        cursor: u64,
+        page_size: u32,
+    ) -> MultiValue2<u64, MultiValueEncoded<u32>> {
+        let region_hash = self.crypto().keccak256(&region);
+        self.wine_ids_page(&self.wines_by_region(&region_hash), cursor, page_size)
+    }
+
+    // Paginated like getWinesByRegion
+    #[view(getWinesByVintage)]
+    fn get_wines_by_vintage(
+        &self,
+        vintage_year: u32,
+        cursor: u64,
+        page_size: u32,
+    ) -> MultiValue2<u64, MultiValueEncoded<u32>> {
+        self.wine_ids_page(&self.wines_by_vintage(vintage_year), cursor, page_size)
+    }
+
+    // Paginated like getWinesByRegion
+    #[view(getWinesByVariety)]
+    fn get_wines_by_variety(
+        &self,
+        variety: ManagedBuffer,
+        cursor: u64,
+        page_size: u32,
+    ) -> MultiValue2<u64, MultiValueEncoded<u32>> {
+        let variety_hash = self.crypto().keccak256(&variety);
+        self.wine_ids_page(&self.wines_by_variety(&variety_hash), cursor, page_size)
+    }
+
+    // Internal helpers
+    fn wine_ids_page(
+        &self,
+        wine_ids: &UnorderedSetMapper<u32>,
+        cursor: u64,
+        page_size: u32,
+    ) -> MultiValue2<u64, MultiValueEncoded<u32>> {
+        let page = Page::new(cursor, page_size, MAX_WINE_IDS_PAGE_SIZE, wine_ids.len());
+
+        let mut result = MultiValueEncoded::new();
+        for index in page.start..page.end {
+            result.push(wine_ids.get_by_index(index + 1));
+        }
+        (page.next_cursor, result).into()
+    }
+}
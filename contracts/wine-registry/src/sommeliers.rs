@@ -0,0 +1,88 @@
+use multiversx_sc::imports::*;
+use wine_common::errors::*;
+use wine_roles::Role;
+
+use crate::pagination::{Page, MAX_SOMMELIERS_PAGE_SIZE};
+
+// Registered sommeliers rate on a separate professional track. A rating stays on the track
+// it was submitted on, even if the sommelier is later removed.
+#[multiversx_sc::module]
+pub trait SommeliersModule:
+    wine_roles::RolesModule
+    + crate::admin::AdminModule
+    + crate::provenance::ProvenanceModule
+    + crate::read_model::ReadModelModule
+    + crate::registration::RegistrationModule
+    + crate::certification::CertificationModule
+    + crate::rewards::RewardsModule
+    + crate::ratings::RatingsModule
+{
+    #[endpoint(registerSommelier)]
+    fn register_sommelier(&self, sommelier: ManagedAddress) {
+        self.require_sommelier_curator();
+        require!(self.sommeliers().insert(sommelier.clone()), ERR_ALREADY_A_SOMMELIER);
+
+        self.sommelier_registered_event(&sommelier, &self.blockchain().get_caller());
+    }
+
+    #[endpoint(removeSommelier)]
+    fn remove_sommelier(&self, sommelier: ManagedAddress) {
+        self.require_sommelier_curator();
+        require!(self.sommeliers().swap_remove(&sommelier), ERR_NOT_A_SOMMELIER);
+
+        self.sommelier_removed_event(&sommelier, &self.blockchain().get_caller());
+    }
+
+    // Internal helpers
+    fn require_sommelier_curator(&self) {
+        let caller = self.blockchain().get_caller();
+        require!(
+            self.has_role(Role::Admin, &caller) || self.is_certification_authority(&caller),
+            ERR_ONLY_ADMIN_OR_CERTIFICATION_AUTHORITY_CAN_MANAGE_SOMMELIERS
+        );
+    }
+
+    // View functions
+    #[view(isSommelier)]
+    fn is_sommelier(&self, address: &ManagedAddress) -> bool {
+        self.sommeliers().contains(address)
+    }
+
+    // Paginated: returns the next cursor (0 when done) followed by at most `page_size` addresses.
+    // Order is not stable across removals.
+    #[view(getSommeliers)]
+    fn get_sommeliers(
+        &self,
+        cursor: u64,
+        page_size: u32,
+    ) -> MultiValue2<u64, MultiValueEncoded<ManagedAddress>> {
+        let sommeliers = self.sommeliers();
+        let page = Page::new(cursor, page_size, MAX_SOMMELIERS_PAGE_SIZE, sommeliers.len());
+
+        let mut result = MultiValueEncoded::new();
+        for index in page.start..page.end {
+            result.push(sommeliers.get_by_index(index + 1));
+        }
+        (page.next_cursor, result).into()
+    }
+
+    #[view(isProfessionalRating)]
+    fn is_professional_rating(&self, wine_id: u32, rater: &ManagedAddress) -> bool {
+        self.professional_rating(wine_id, rater).get()
+    }
+
+    // Events
+    #[event("sommelierRegistered")]
+    fn sommelier_registered_event(
+        &self,
+        #[indexed] sommelier: &ManagedAddress,
+        registered_by: &ManagedAddress,
+    );
+
+    #[event("sommelierRemoved")]
+    fn sommelier_removed_event(
+        &self,
+        #[indexed] sommelier: &ManagedAddress,
+        removed_by: &ManagedAddress,
+    );
+}
@@ -0,0 +1,143 @@
+use multiversx_sc::derive_imports::*;
+use multiversx_sc::imports::*;
+use wine_common::errors::*;
+use wine_roles::Role;
+pub use wine_common::MAX_HUMIDITY_X100;
+
+use crate::pagination::MAX_STORAGE_CONDITIONS_PAGE_SIZE;
+
+// Readings kept per wine; older ones are overwritten
+pub const STORAGE_CONDITION_CAPACITY: u64 = 100;
+
+// Cold-chain reading reported by an authorized logger (cellar sensor, logistics oracle)
+#[derive(TopEncode, TopDecode, NestedEncode, NestedDecode, TypeAbi, PartialEq, Debug)]
+pub struct StorageCondition<M: ManagedTypeApi> {
+    pub logger: ManagedAddress<M>,
+    pub temperature_x100: i32, // x100 for precision (e.g., 1250 = 12.50 °C)
+    pub humidity_x100: u32, // x100 for precision (e.g., 7000 = 70.00%)
+    // Hash of the storage location; the location itself stays off-chain
+    pub location_hash: ManagedByteArray<M, 32>,
+    pub timestamp: u64,
+}
+
+#[multiversx_sc::module]
+pub trait StorageConditionsModule:
+    wine_roles::RolesModule
+    + crate::admin::AdminModule
+    + crate::provenance::ProvenanceModule
+    + crate::read_model::ReadModelModule
+    + crate::registration::RegistrationModule
+{
+    #[endpoint(addStorageLogger)]
+    fn add_storage_logger(&self, logger: ManagedAddress) {
+        self.require_role(Role::Admin);
+        require!(self.storage_loggers().insert(logger.clone()), ERR_ALREADY_A_STORAGE_LOGGER);
+
+        self.storage_logger_added_event(&logger);
+    }
+
+    #[endpoint(removeStorageLogger)]
+    fn remove_storage_logger(&self, logger: ManagedAddress) {
+        self.require_role(Role::Admin);
+        require!(self.storage_loggers().swap_remove(&logger), ERR_NOT_A_STORAGE_LOGGER);
+
+        self.storage_logger_removed_event(&logger);
+    }
+
+    // Authorized loggers only. Returns the sequence number of the reading.
+    #[endpoint(logStorageCondition)]
+    fn log_storage_condition(
+        &self,
+        wine_id: u32,
+        temperature_x100: i32,
+        humidity_x100: u32,
+        location_hash: ManagedByteArray<Self::Api, 32>,
+    ) -> u64 {
+        self.require_wine_exists(wine_id);
+        let caller = self.blockchain().get_caller();
+        require!(self.storage_loggers().contains(&caller), ERR_NOT_A_STORAGE_LOGGER);
+        require!(humidity_x100 <= MAX_HUMIDITY_X100, ERR_HUMIDITY_CANNOT_EXCEED_100_PERCENT);
+
+        let sequence = self.storage_condition_count(wine_id).get();
+        let reading = StorageCondition {
+            logger: caller,
+            temperature_x100,
+            humidity_x100,
+            location_hash,
+            timestamp: self.blockchain().get_block_timestamp(),
+        };
+        self.storage_conditions(wine_id, sequence % STORAGE_CONDITION_CAPACITY).set(&reading);
+        self.storage_condition_count(wine_id).set(sequence + 1);
+
+        self.storage_condition_logged_event(
+            wine_id,
+            &reading.logger,
+            temperature_x100,
+            humidity_x100,
+        );
+
+        sequence
+    }
+
+    // View functions
+    #[view(isStorageLogger)]
+    fn is_storage_logger(&self, address: &ManagedAddress) -> bool {
+        self.storage_loggers().contains(address)
+    }
+
+    // Total readings ever logged for the wine, including overwritten ones
+    #[view(getStorageConditionCount)]
+    fn get_storage_condition_count(&self, wine_id: u32) -> u64 {
+        self.storage_condition_count(wine_id).get()
+    }
+
+    // Up to `count` most recent readings, newest first
+    #[view(getLatestStorageConditions)]
+    fn get_latest_storage_conditions(
+        &self,
+        wine_id: u32,
+        count: u32,
+    ) -> MultiValueEncoded<StorageCondition<Self::Api>> {
+        let total = self.storage_condition_count(wine_id).get();
+        let retained = core::cmp::min(total, STORAGE_CONDITION_CAPACITY);
+        let count = core::cmp::min(count, MAX_STORAGE_CONDITIONS_PAGE_SIZE) as u64;
+
+        let mut result = MultiValueEncoded::new();
+        for offset in 1..=core::cmp::min(count, retained) {
+            let sequence = total - offset;
+            result.push(self.storage_conditions(wine_id, sequence % STORAGE_CONDITION_CAPACITY).get());
+        }
+        result
+    }
+
+    // Storage mappers
+    #[storage_mapper("storageLoggers")]
+    fn storage_loggers(&self) -> UnorderedSetMapper<ManagedAddress>;
+
+    // Ring buffer of STORAGE_CONDITION_CAPACITY slots per wine
+    #[storage_mapper("storageConditions")]
+    fn storage_conditions(
+        &self,
+        wine_id: u32,
+        slot: u64,
+    ) -> SingleValueMapper<StorageCondition<Self::Api>>;
+
+    #[storage_mapper("storageConditionCount")]
+    fn storage_condition_count(&self, wine_id: u32) -> SingleValueMapper<u64>;
+
+    // Events
+    #[event("storageLoggerAdded")]
+    fn storage_logger_added_event(&self, #[indexed] logger: &ManagedAddress);
+
+    #[event("storageLoggerRemoved")]
+    fn storage_logger_removed_event(&self, #[indexed] logger: &ManagedAddress);
+
+    #[event("storageConditionLogged")]
+    fn storage_condition_logged_event(
+        &self,
+        #[indexed] wine_id: u32,
+        #[indexed] logger: &ManagedAddress,
+        #[indexed] temperature_x100: i32,
+        humidity_x100: u32,
+    );
+}
@@ -0,0 +1,179 @@
+use multiversx_sc::derive_imports::*;
+use multiversx_sc::imports::*;
+use wine_common::errors::*;
+use wine_roles::Role;
+
+use crate::pagination::MAX_TAG_LENGTH;
+
+// Keeps getTagOptions unpaginated
+pub const MAX_TAG_OPTIONS_PER_CATEGORY: usize = 100;
+// Tags a wine can carry in one category
+pub const MAX_WINE_TAGS_PER_CATEGORY: usize = 10;
+
+#[derive(
+    TopEncode, TopDecode, NestedEncode, NestedDecode, TypeAbi, PartialEq, Eq, Debug, Clone, Copy,
+)]
+pub enum TagCategory {
+    FoodPairing,
+    Style,
+    Sweetness,
+    Body,
+}
+
+// Controlled vocabulary for discovery: admins curate the tags allowed in each category and
+// producers pick from them for their wines. Tags are indexed for getWinesByTag.
+#[multiversx_sc::module]
+pub trait TagsModule:
+    wine_roles::RolesModule
+    + crate::admin::AdminModule
+    + crate::provenance::ProvenanceModule
+    + crate::read_model::ReadModelModule
+    + crate::registration::RegistrationModule
+    + crate::search::SearchModule
+{
+    #[endpoint(addTagOption)]
+    fn add_tag_option(&self, category: TagCategory, tag: ManagedBuffer) {
+        self.require_role(Role::Admin);
+        require!(!tag.is_empty(), ERR_TAG_IS_REQUIRED);
+        require!(tag.len() <= MAX_TAG_LENGTH, ERR_TAG_IS_TOO_LONG);
+        require!(self.tag_options(category).insert(tag.clone()), ERR_TAG_ALREADY_LISTED);
+        require!(
+            self.tag_options(category).len() <= MAX_TAG_OPTIONS_PER_CATEGORY,
+            ERR_TOO_MANY_TAG_OPTIONS
+        );
+
+        self.tag_option_added_event(category, &tag);
+    }
+
+    // Wines already tagged keep the tag until their producer removes it
+    #[endpoint(removeTagOption)]
+    fn remove_tag_option(&self, category: TagCategory, tag: ManagedBuffer) {
+        self.require_role(Role::Admin);
+        require!(self.tag_options(category).swap_remove(&tag), ERR_TAG_NOT_LISTED);
+
+        self.tag_option_removed_event(category, &tag);
+    }
+
+    #[endpoint(addWineTags)]
+    fn add_wine_tags(
+        &self,
+        wine_id: u32,
+        category: TagCategory,
+        tags: MultiValueEncoded<ManagedBuffer>,
+    ) {
+        self.require_wine_tagger(wine_id);
+
+        let mut wine_tags = self.wine_tags(wine_id, category);
+        for tag in tags {
+            require!(self.tag_options(category).contains(&tag), ERR_TAG_NOT_LISTED);
+            if wine_tags.insert(tag.clone()) {
+                let tag_hash = self.crypto().keccak256(&tag);
+                self.wines_by_tag(category, &tag_hash).insert(wine_id);
+                self.wine_tag_added_event(wine_id, category, &tag);
+            }
+        }
+        require!(wine_tags.len() <= MAX_WINE_TAGS_PER_CATEGORY, ERR_TOO_MANY_WINE_TAGS);
+    }
+
+    // Tags the wine does not carry are ignored
+    #[endpoint(removeWineTags)]
+    fn remove_wine_tags(
+        &self,
+        wine_id: u32,
+        category: TagCategory,
+        tags: MultiValueEncoded<ManagedBuffer>,
+    ) {
+        self.require_wine_tagger(wine_id);
+
+        let mut wine_tags = self.wine_tags(wine_id, category);
+        for tag in tags {
+            if wine_tags.swap_remove(&tag) {
+                let tag_hash = self.crypto().keccak256(&tag);
+                self.wines_by_tag(category, &tag_hash).swap_remove(&wine_id);
+                self.wine_tag_removed_event(wine_id, category, &tag);
+            }
+        }
+    }
+
+    // Internal helpers
+    fn require_wine_tagger(&self, wine_id: u32) {
+        require!(!self.wine_owner(wine_id).is_empty(), ERR_WINE_DOES_NOT_EXIST);
+        require!(
+            self.blockchain().get_caller() == self.wine_producer(wine_id),
+            ERR_ONLY_PRODUCER_CAN_TAG_THE_WINE
+        );
+    }
+
+    // View functions
+    #[view(getTagOptions)]
+    fn get_tag_options(&self, category: TagCategory) -> MultiValueEncoded<ManagedBuffer> {
+        self.tag_options(category).iter().collect()
+    }
+
+    // Every tag of the wine, as (category, tag) pairs
+    #[view(getWineTags)]
+    fn get_wine_tags(&self, wine_id: u32) -> MultiValueEncoded<MultiValue2<TagCategory, ManagedBuffer>> {
+        let mut result = MultiValueEncoded::new();
+        for category in [
+            TagCategory::FoodPairing,
+            TagCategory::Style,
+            TagCategory::Sweetness,
+            TagCategory::Body,
+        ] {
+            for tag in self.wine_tags(wine_id, category).iter() {
+                result.push((category, tag).into());
+            }
+        }
+        result
+    }
+
+    // Paginated like getWinesByRegion
+    #[view(getWinesByTag)]
+    fn get_wines_by_tag(
+        &self,
+        category: TagCategory,
+        tag: ManagedBuffer,
+        cursor: u64,
+        page_size: u32,
+    ) -> MultiValue2<u64, MultiValueEncoded<u32>> {
+        let tag_hash = self.crypto().keccak256(&tag);
+        self.wine_ids_page(&self.wines_by_tag(category, &tag_hash), cursor, page_size)
+    }
+
+    // Storage mappers
+    #[storage_mapper("tagOptions")]
+    fn tag_options(&self, category: TagCategory) -> UnorderedSetMapper<ManagedBuffer>;
+
+    #[storage_mapper("wineTags")]
+    fn wine_tags(&self, wine_id: u32, category: TagCategory) -> UnorderedSetMapper<ManagedBuffer>;
+
+    #[storage_mapper("winesByTag")]
+    fn wines_by_tag(
+        &self,
+        category: TagCategory,
+        tag_hash: &ManagedByteArray<Self::Api, 32>,
+    ) -> UnorderedSetMapper<u32>;
+
+    // Events
+    #[event("tagOptionAdded")]
+    fn tag_option_added_event(&self, #[indexed] category: TagCategory, #[indexed] tag: &ManagedBuffer);
+
+    #[event("tagOptionRemoved")]
+    fn tag_option_removed_event(&self, #[indexed] category: TagCategory, #[indexed] tag: &ManagedBuffer);
+
+    #[event("wineTagAdded")]
+    fn wine_tag_added_event(
+        &self,
+        #[indexed] wine_id: u32,
+        #[indexed] category: TagCategory,
+        #[indexed] tag: &ManagedBuffer,
+    );
+
+    #[event("wineTagRemoved")]
+    fn wine_tag_removed_event(
+        &self,
+        #[indexed] wine_id: u32,
+        #[indexed] category: TagCategory,
+        #[indexed] tag: &ManagedBuffer,
+    );
+}
@@ -0,0 +1,150 @@
+use multiversx_sc::derive_imports::*;
+use multiversx_sc::imports::*;
+use wine_common::errors::*;
+
+use crate::pagination::{Page, MAX_WINE_IDS_PAGE_SIZE};
+
+#[derive(TopEncode, TopDecode, TypeAbi, PartialEq, Debug)]
+pub struct Vineyard<M: ManagedTypeApi> {
+    pub name: ManagedBuffer<M>,
+    pub region: ManagedBuffer<M>,
+    // Hash of the plot coordinates; the coordinates themselves stay off-chain
+    pub coordinates_hash: ManagedByteArray<M, 32>,
+    pub size_hectares: u32, // x100 for precision (e.g., 1250 = 12.50 ha)
+    pub owner: ManagedAddress<M>,
+    pub registered_timestamp: u64,
+}
+
+// Vineyards are registered by their owner. A wine is linked to a vineyard once, by its
+// producer, who must own the vineyard.
+#[multiversx_sc::module]
+pub trait VineyardsModule:
+    wine_roles::RolesModule
+    + crate::admin::AdminModule
+    + crate::provenance::ProvenanceModule
+    + crate::read_model::ReadModelModule
+    + crate::registration::RegistrationModule
+{
+    #[endpoint(registerVineyard)]
+    fn register_vineyard(
+        &self,
+        name: ManagedBuffer,
+        region: ManagedBuffer,
+        coordinates_hash: ManagedByteArray<Self::Api, 32>,
+        size_hectares: u32,
+    ) -> u32 {
+        require!(!name.is_empty(), ERR_VINEYARD_NAME_IS_REQUIRED);
+        require!(!region.is_empty(), ERR_VINEYARD_REGION_IS_REQUIRED);
+        require!(size_hectares > 0, ERR_VINEYARD_SIZE_MUST_BE_GREATER_THAN_0);
+
+        let vineyard_id = self.vineyard_counter().get() + 1;
+        let owner = self.blockchain().get_caller();
+        let vineyard = Vineyard {
+            name,
+            region,
+            coordinates_hash,
+            size_hectares,
+            owner: owner.clone(),
+            registered_timestamp: self.blockchain().get_block_timestamp(),
+        };
+        self.vineyards(vineyard_id).set(&vineyard);
+        self.vineyard_counter().set(vineyard_id);
+
+        self.vineyard_registered_event(vineyard_id, &owner, &vineyard.name);
+
+        vineyard_id
+    }
+
+    #[endpoint(setWineVineyard)]
+    fn set_wine_vineyard(&self, wine_id: u32, vineyard_id: u32) {
+        self.require_wine_exists(wine_id);
+        self.require_vineyard_exists(vineyard_id);
+        let caller = self.blockchain().get_caller();
+        require!(caller == self.wine_producer(wine_id), ERR_ONLY_PRODUCER_CAN_SET_THE_VINEYARD);
+        require!(
+            caller == self.vineyards(vineyard_id).get().owner,
+            ERR_ONLY_VINEYARD_OWNER_CAN_LINK_WINES
+        );
+        require!(self.wine_vineyard(wine_id).is_empty(), ERR_WINE_ALREADY_LINKED_TO_A_VINEYARD);
+
+        self.wine_vineyard(wine_id).set(vineyard_id);
+        self.vineyard_wines(vineyard_id).push(&wine_id);
+
+        self.wine_vineyard_set_event(wine_id, vineyard_id);
+    }
+
+    // Internal helpers
+    fn require_vineyard_exists(&self, vineyard_id: u32) {
+        require!(!self.vineyards(vineyard_id).is_empty(), ERR_VINEYARD_DOES_NOT_EXIST);
+    }
+
+    // View functions
+    #[view(getVineyard)]
+    fn get_vineyard(&self, vineyard_id: u32) -> Vineyard<Self::Api> {
+        self.require_vineyard_exists(vineyard_id);
+        self.vineyards(vineyard_id).get()
+    }
+
+    // 0 if the wine is not linked to a vineyard
+    #[view(getWineVineyard)]
+    fn get_wine_vineyard(&self, wine_id: u32) -> u32 {
+        self.wine_vineyard(wine_id).get()
+    }
+
+    // Paginated: returns the next cursor (0 when done) followed by at most `page_size` wine ids
+    #[view(getVineyardWines)]
+    fn get_vineyard_wines(
+        &self,
+        vineyard_id: u32,
+        cursor: u64,
+        page_size: u32,
+    ) -> MultiValue2<u64, MultiValueEncoded<u32>> {
+        let vineyard_wines = self.vineyard_wines(vineyard_id);
+        let page = Page::new(cursor, page_size, MAX_WINE_IDS_PAGE_SIZE, vineyard_wines.len());
+
+        let mut result = MultiValueEncoded::new();
+        for index in page.start..page.end {
+            result.push(vineyard_wines.get(index + 1));
+        }
+        (page.next_cursor, result).into()
+    }
+
+    // Whether the region claimed at registration matches the linked vineyard's region
+    #[view(isWineRegionConsistent)]
+    fn is_wine_region_consistent(&self, wine_id: u32) -> bool {
+        self.require_wine_exists(wine_id);
+        let vineyard_id = self.wine_vineyard(wine_id).get();
+        require!(vineyard_id != 0, ERR_WINE_IS_NOT_LINKED_TO_A_VINEYARD);
+
+        self.load_wine_details(wine_id).region == self.vineyards(vineyard_id).get().region
+    }
+
+    // Storage mappers
+    #[storage_mapper("vineyards")]
+    fn vineyards(&self, vineyard_id: u32) -> SingleValueMapper<Vineyard<Self::Api>>;
+
+    #[storage_mapper("vineyardCounter")]
+    fn vineyard_counter(&self) -> SingleValueMapper<u32>;
+
+    #[storage_mapper("wineVineyard")]
+    fn wine_vineyard(&self, wine_id: u32) -> SingleValueMapper<u32>;
+
+    #[storage_mapper("vineyardWines")]
+    fn vineyard_wines(&self, vineyard_id: u32) -> VecMapper<u32>;
+
+    // Events
+    #[event("vineyardRegistered")]
+    fn vineyard_registered_event(
+        &self,
+        #[indexed] vineyard_id: u32,
+        #[indexed] owner: &ManagedAddress,
+        name: &ManagedBuffer,
+    );
+
+    #[event("wineVineyardSet")]
+    fn wine_vineyard_set_event(
+        &self,
+        #[indexed] wine_id: u32,
+        #[indexed] vineyard_id: u32,
+    );
+}
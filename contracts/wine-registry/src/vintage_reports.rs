@@ -0,0 +1,94 @@
+use multiversx_sc::derive_imports::*;
+use multiversx_sc::imports::*;
+use wine_common::errors::*;
+use wine_common::ipfs::is_valid_ipfs_cid;
+
+use crate::pagination::{Page, MAX_IPFS_HASH_LENGTH, MAX_VINTAGE_REPORTS_PAGE_SIZE};
+
+// Harvest conditions of a wine's vintage; the full report lives behind the IPFS hash
+#[derive(TopEncode, TopDecode, NestedEncode, NestedDecode, TypeAbi, PartialEq, Debug)]
+pub struct VintageReport<M: ManagedTypeApi> {
+    pub author: ManagedAddress<M>,
+    pub weather_summary_hash: ManagedByteArray<M, 32>,
+    pub yield_kg_per_hectare: u32,
+    pub sugar_level_brix: u32, // x100 for precision (e.g., 2350 = 23.50 °Bx)
+    pub report_ipfs_hash: ManagedBuffer<M>,
+    pub timestamp: u64,
+}
+
+#[multiversx_sc::module]
+pub trait VintageReportsModule:
+    wine_roles::RolesModule
+    + crate::admin::AdminModule
+    + crate::provenance::ProvenanceModule
+    + crate::read_model::ReadModelModule
+    + crate::registration::RegistrationModule
+    + crate::certification::CertificationModule
+{
+    // Producer or certification authorities only
+    #[endpoint(addVintageReport)]
+    fn add_vintage_report(
+        &self,
+        wine_id: u32,
+        weather_summary_hash: ManagedByteArray<Self::Api, 32>,
+        yield_kg_per_hectare: u32,
+        sugar_level_brix: u32,
+        report_ipfs_hash: ManagedBuffer,
+    ) -> usize {
+        self.require_wine_exists(wine_id);
+        let caller = self.blockchain().get_caller();
+        require!(
+            caller == self.wine_producer(wine_id) || self.is_certification_authority(&caller),
+            ERR_ONLY_PRODUCER_OR_CERTIFICATION_AUTHORITY_CAN_ADD_VINTAGE_REPORTS
+        );
+        require!(!report_ipfs_hash.is_empty(), ERR_IPFS_HASH_IS_REQUIRED);
+        require!(report_ipfs_hash.len() <= MAX_IPFS_HASH_LENGTH, ERR_IPFS_HASH_IS_TOO_LONG);
+        require!(is_valid_ipfs_cid(&report_ipfs_hash), ERR_INVALID_IPFS_CID);
+
+        let report_index = self.vintage_reports(wine_id).push(&VintageReport {
+            author: caller.clone(),
+            weather_summary_hash,
+            yield_kg_per_hectare,
+            sugar_level_brix,
+            report_ipfs_hash,
+            timestamp: self.blockchain().get_block_timestamp(),
+        });
+
+        self.vintage_report_added_event(wine_id, report_index, &caller);
+
+        report_index
+    }
+
+    // View functions
+    // Paginated: returns the next cursor (0 when done) followed by at most `page_size` reports,
+    // oldest first
+    #[view(getVintageReports)]
+    fn get_vintage_reports(
+        &self,
+        wine_id: u32,
+        cursor: u64,
+        page_size: u32,
+    ) -> MultiValue2<u64, MultiValueEncoded<VintageReport<Self::Api>>> {
+        let reports = self.vintage_reports(wine_id);
+        let page = Page::new(cursor, page_size, MAX_VINTAGE_REPORTS_PAGE_SIZE, reports.len());
+
+        let mut result = MultiValueEncoded::new();
+        for index in page.start..page.end {
+            result.push(reports.get(index + 1));
+        }
+        (page.next_cursor, result).into()
+    }
+
+    // Storage mappers
+    #[storage_mapper("vintageReports")]
+    fn vintage_reports(&self, wine_id: u32) -> VecMapper<VintageReport<Self::Api>>;
+
+    // Events
+    #[event("vintageReportAdded")]
+    fn vintage_report_added_event(
+        &self,
+        #[indexed] wine_id: u32,
+        #[indexed] report_index: usize,
+        author: &ManagedAddress,
+    );
+}
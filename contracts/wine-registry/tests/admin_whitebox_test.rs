@@ -0,0 +1,35 @@
+mod registry_setup;
+
+use multiversx_sc_scenario::imports::*;
+use registry_setup::*;
+use wine_registry::admin::AdminModule;
+
+#[test]
+fn integration_permissions_round_trip() {
+    let (mut world, whitebox) = setup();
+
+    world.whitebox_call(&whitebox, ScCallStep::new().from(OWNER_ADDRESS_EXPR), |sc| {
+        sc.set_integration_permissions(managed_address!(&address(MARKETPLACE_ADDRESS_EXPR)), true, true, false);
+    });
+
+    world.whitebox_query(&whitebox, |sc| {
+        let permissions = sc.get_integration_permissions(&managed_address!(&address(MARKETPLACE_ADDRESS_EXPR)));
+        assert!(permissions.can_decrement_availability);
+        assert!(permissions.can_record_sale);
+        assert!(!permissions.can_flag);
+        let (next_cursor, integrations) = sc.get_integrations(0, 0).into_tuple();
+        assert_eq!(next_cursor, 0);
+        assert_eq!(integrations.len(), 1);
+    });
+
+    world.whitebox_call(&whitebox, ScCallStep::new().from(OWNER_ADDRESS_EXPR), |sc| {
+        sc.revoke_integration_permissions(managed_address!(&address(MARKETPLACE_ADDRESS_EXPR)));
+    });
+
+    world.whitebox_query(&whitebox, |sc| {
+        let permissions = sc.get_integration_permissions(&managed_address!(&address(MARKETPLACE_ADDRESS_EXPR)));
+        assert!(!permissions.can_decrement_availability);
+        let (_, integrations) = sc.get_integrations(0, 0).into_tuple();
+        assert_eq!(integrations.len(), 0);
+    });
+}
@@ -0,0 +1,226 @@
+mod registry_setup;
+
+use multiversx_sc_scenario::imports::*;
+use registry_setup::*;
+use wine_common::errors::*;
+use wine_registry::certification::{CertificationModule, CertificationType};
+use wine_registry::price_floors::PriceFloorsModule;
+use wine_registry::read_model::ReadModelModule;
+
+#[test]
+fn owner_is_initial_authority() {
+    let (mut world, whitebox) = setup();
+
+    world.whitebox_query(&whitebox, |sc| {
+        assert!(sc.is_certification_authority(&managed_address!(&address(OWNER_ADDRESS_EXPR))));
+        assert!(!sc.is_certification_authority(&managed_address!(&address(AUTHORITY_ADDRESS_EXPR))));
+        assert_eq!(sc.get_authority_id(&managed_address!(&address(OWNER_ADDRESS_EXPR))), 1u32);
+    });
+}
+
+#[test]
+fn added_authority_can_certify() {
+    let (mut world, whitebox) = setup();
+    let wine_id = register_wine(&mut world, &whitebox, PRODUCER_ADDRESS_EXPR, 100);
+
+    world.whitebox_call(&whitebox, ScCallStep::new().from(OWNER_ADDRESS_EXPR), |sc| {
+        let authority_id = sc.add_certification_authority(
+            managed_buffer!(b"INAO"),
+            managed_address!(&address(AUTHORITY_ADDRESS_EXPR)),
+        );
+        assert_eq!(authority_id, 2u32);
+        sc.grant_authority_class(authority_id, CertificationType::Appellation);
+    });
+
+    world.whitebox_call(&whitebox, ScCallStep::new().from(AUTHORITY_ADDRESS_EXPR), |sc| {
+        sc.certify_wine(
+            wine_id,
+            CertificationType::Appellation,
+            managed_buffer!(b"bafybeigdyrzt5sfp7udm7hu76uh7y26nf3efuylqabf3oclgtqy55fbzdi"),
+            OptionalValue::None,
+        );
+    });
+
+    world.whitebox_query(&whitebox, |sc| {
+        let authority = sc.certification_authorities(2).get();
+        assert_eq!(authority.certification_count, 1u32);
+        assert_eq!(sc.wine_certifications(wine_id).len(), 1);
+        assert!(sc
+            .wine_certified_by(wine_id, &managed_address!(&address(AUTHORITY_ADDRESS_EXPR)))
+            .get());
+    });
+}
+
+#[test]
+fn authority_lookup_follows_deactivation_and_removal() {
+    let (mut world, whitebox) = setup();
+    let authority = address(AUTHORITY_ADDRESS_EXPR);
+
+    world.whitebox_call(&whitebox, ScCallStep::new().from(OWNER_ADDRESS_EXPR), |sc| {
+        sc.add_certification_authority(managed_buffer!(b"INAO"), managed_address!(&authority));
+    });
+    world.whitebox_query(&whitebox, |sc| {
+        assert!(sc.is_certification_authority(&managed_address!(&authority)));
+        assert_eq!(sc.get_authority_id(&managed_address!(&authority)), 2u32);
+        assert_eq!(sc.get_certification_authority(2).name, managed_buffer!(b"INAO"));
+    });
+
+    // A deactivated authority keeps its id but no longer counts as an authority
+    world.whitebox_call(&whitebox, ScCallStep::new().from(OWNER_ADDRESS_EXPR), |sc| {
+        sc.deactivate_authority(2);
+    });
+    world.whitebox_query(&whitebox, |sc| {
+        assert!(!sc.is_certification_authority(&managed_address!(&authority)));
+        assert_eq!(sc.get_authority_id(&managed_address!(&authority)), 2u32);
+    });
+
+    world.whitebox_call(&whitebox, ScCallStep::new().from(OWNER_ADDRESS_EXPR), |sc| {
+        sc.remove_authority(2);
+    });
+    world.whitebox_query(&whitebox, |sc| {
+        assert!(!sc.is_certification_authority(&managed_address!(&authority)));
+        assert_eq!(sc.get_authority_id(&managed_address!(&authority)), 0u32);
+    });
+}
+
+#[test]
+fn only_admin_can_add_authority() {
+    let (mut world, whitebox) = setup();
+
+    world.whitebox_call_check(
+        &whitebox,
+        ScCallStep::new().from(PRODUCER_ADDRESS_EXPR).no_expect(),
+        |sc| {
+            sc.add_certification_authority(
+                managed_buffer!(b"Self-appointed"),
+                managed_address!(&address(PRODUCER_ADDRESS_EXPR)),
+            );
+        },
+        |r| {
+            r.assert_user_error(ERR_CALLER_DOES_NOT_HAVE_THE_REQUIRED_ROLE);
+        },
+    );
+}
+
+#[test]
+fn attested_certifications_expire_in_the_read_model() {
+    let (mut world, whitebox) = setup();
+    let wine_id = register_wine(&mut world, &whitebox, PRODUCER_ADDRESS_EXPR, 100);
+    world.set_state_step(SetStateStep::new().block_timestamp(1_000));
+
+    world.whitebox_call(&whitebox, ScCallStep::new().from(OWNER_ADDRESS_EXPR), |sc| {
+        sc.grant_authority_class(1, CertificationType::Quality);
+        sc.set_attestation_contract(managed_address!(&address(REGISTRY_ADDRESS_EXPR)));
+    });
+    world.whitebox_call_check(
+        &whitebox,
+        ScCallStep::new().from(OWNER_ADDRESS_EXPR).no_expect(),
+        |sc| {
+            sc.certify_wine(
+                wine_id,
+                CertificationType::Quality,
+                ManagedBuffer::new(),
+                OptionalValue::Some((1u64, 1_000u64).into()),
+            );
+        },
+        |r| {
+            r.assert_user_error(ERR_ATTESTATION_HAS_EXPIRED);
+        },
+    );
+
+    // The read model carries the latest expiry of the attestations backing the wine
+    world.whitebox_call(&whitebox, ScCallStep::new().from(OWNER_ADDRESS_EXPR), |sc| {
+        sc.certify_wine(
+            wine_id,
+            CertificationType::Quality,
+            ManagedBuffer::new(),
+            OptionalValue::Some((1u64, 2_000u64).into()),
+        );
+        sc.certify_wine(
+            wine_id,
+            CertificationType::Quality,
+            ManagedBuffer::new(),
+            OptionalValue::Some((2u64, 3_000u64).into()),
+        );
+    });
+    world.whitebox_query(&whitebox, |sc| {
+        let read_model = sc.get_wine_read_model(wine_id);
+        assert!(read_model.certified);
+        assert_eq!(read_model.certified_until, 3_000);
+        let attestation = sc.get_certification_attestation(wine_id, 2).into_option().unwrap();
+        assert_eq!(attestation.expiry_timestamp, 3_000);
+    });
+
+    world.whitebox_call(&whitebox, ScCallStep::new().from(OWNER_ADDRESS_EXPR), |sc| {
+        sc.revoke_certification(wine_id, 2, managed_buffer!(b"superseded"));
+    });
+    world.whitebox_query(&whitebox, |sc| {
+        assert_eq!(sc.get_wine_read_model(wine_id).certified_until, 2_000);
+    });
+
+    // A certification that is not backed by an attestation does not expire
+    world.whitebox_call(&whitebox, ScCallStep::new().from(OWNER_ADDRESS_EXPR), |sc| {
+        sc.certify_wine(
+            wine_id,
+            CertificationType::Quality,
+            managed_buffer!(b"bafybeigdyrzt5sfp7udm7hu76uh7y26nf3efuylqabf3oclgtqy55fbzdi"),
+            OptionalValue::None,
+        );
+    });
+    world.whitebox_query(&whitebox, |sc| {
+        assert_eq!(sc.get_wine_read_model(wine_id).certified_until, 0);
+    });
+}
+
+#[test]
+fn non_authority_cannot_certify() {
+    let (mut world, whitebox) = setup();
+    let wine_id = register_wine(&mut world, &whitebox, PRODUCER_ADDRESS_EXPR, 100);
+
+    world.whitebox_call_check(
+        &whitebox,
+        ScCallStep::new().from(PRODUCER_ADDRESS_EXPR).no_expect(),
+        |sc| {
+            sc.certify_wine(
+                wine_id,
+                CertificationType::Quality,
+                managed_buffer!(b"self-certified"),
+                OptionalValue::None,
+            );
+        },
+        |r| {
+            r.assert_user_error(ERR_NOT_A_CERTIFICATION_AUTHORITY);
+        },
+    );
+}
+
+#[test]
+fn authorities_publish_grade_price_floors() {
+    let (mut world, whitebox) = setup();
+
+    world.whitebox_call(&whitebox, ScCallStep::new().from(OWNER_ADDRESS_EXPR), |sc| {
+        sc.set_grade_price_floor(8, managed_biguint!(500));
+        sc.set_grade_price_floor(9, managed_biguint!(800));
+        sc.set_grade_price_floor(9, managed_biguint!(0));
+    });
+    world.whitebox_query(&whitebox, |sc| {
+        assert_eq!(sc.get_grade_price_floor(8), managed_biguint!(500));
+        assert_eq!(sc.get_grade_price_floor(9), managed_biguint!(0));
+        let floors: Vec<(u8, BigUint<DebugApi>)> =
+            sc.get_grade_price_floors().into_iter().map(|floor| floor.into_tuple()).collect();
+        assert_eq!(floors, vec![(8u8, managed_biguint!(500))]);
+    });
+
+    world.whitebox_call_check(
+        &whitebox,
+        ScCallStep::new().from(OWNER_ADDRESS_EXPR).no_expect(),
+        |sc| sc.set_grade_price_floor(11, managed_biguint!(500)),
+        |r| r.assert_user_error(ERR_QUALITY_GRADE_MUST_BE_BETWEEN_1_10),
+    );
+    world.whitebox_call_check(
+        &whitebox,
+        ScCallStep::new().from(PRODUCER_ADDRESS_EXPR).no_expect(),
+        |sc| sc.set_grade_price_floor(8, managed_biguint!(1)),
+        |r| r.assert_user_error(ERR_ONLY_ADMIN_OR_CERTIFICATION_AUTHORITY_CAN_SET_PRICE_FLOORS),
+    );
+}
@@ -0,0 +1,163 @@
+mod registry_setup;
+
+use multiversx_sc::codec::top_encode_to_vec_u8_or_panic;
+use multiversx_sc_scenario::imports::*;
+use registry_setup::*;
+use wine_common::errors::*;
+use wine_registry::admin::AdminModule;
+use wine_registry::pagination::*;
+use wine_registry::provenance::ProvenanceModule;
+use wine_registry::ratings::RatingsModule;
+use wine_registry::{IntegrationPermissions, WineRating};
+
+// Cursor return value, encoded as u64
+const CURSOR_BYTES: usize = 8;
+
+fn top_encoded_len<T: TopEncode>(value: &T) -> usize {
+    top_encode_to_vec_u8_or_panic(value).len()
+}
+
+fn raw_address(index: usize) -> Address {
+    let mut bytes = [0xffu8; 32];
+    bytes[..8].copy_from_slice(&(index as u64).to_be_bytes());
+    Address::from(bytes)
+}
+
+#[test]
+fn full_pages_of_maximal_items_fit_return_budget() {
+    let (mut world, whitebox) = setup();
+    let producer = address(PRODUCER_ADDRESS_EXPR);
+
+    world.whitebox_call(&whitebox, ScCallStep::new().from(OWNER_ADDRESS_EXPR), |sc| {
+        for index in 0..(MAX_RATINGS_PAGE_SIZE as usize * 2) {
+            sc.wine_ratings(1).push(&WineRating {
+                rater: managed_address!(&raw_address(index)),
+                rating: 10,
+                review: ManagedBuffer::from(&[0xffu8; MAX_REVIEW_LENGTH][..]),
+                timestamp: u64::MAX,
+            });
+        }
+        for _ in 0..(MAX_WINE_IDS_PAGE_SIZE * 2) {
+            sc.producer_wines(&managed_address!(&producer)).push(&u32::MAX);
+        }
+        for index in 0..(MAX_INTEGRATIONS_PAGE_SIZE as usize * 2) {
+            sc.set_integration_permissions(managed_address!(&raw_address(index)), true, true, true);
+        }
+    });
+
+    world.whitebox_query(&whitebox, |sc| {
+        let (next_cursor, ratings) = sc.get_wine_ratings(1, 0, u32::MAX).into_tuple();
+        assert_eq!(next_cursor, MAX_RATINGS_PAGE_SIZE as u64);
+        assert_eq!(ratings.len(), MAX_RATINGS_PAGE_SIZE as usize);
+        let ratings_bytes: usize = ratings.into_iter().map(|rating| top_encoded_len(&rating)).sum();
+        assert!(CURSOR_BYTES + ratings_bytes <= MAX_VIEW_RETURN_BYTES);
+
+        let (next_cursor, wine_ids) = sc
+            .get_producer_wines(&managed_address!(&producer), 0, u32::MAX)
+            .into_tuple();
+        assert_eq!(next_cursor, MAX_WINE_IDS_PAGE_SIZE as u64);
+        assert_eq!(wine_ids.len(), MAX_WINE_IDS_PAGE_SIZE as usize);
+        let wine_ids_bytes: usize = wine_ids.into_iter().map(|wine_id| top_encoded_len(&wine_id)).sum();
+        assert!(CURSOR_BYTES + wine_ids_bytes <= MAX_VIEW_RETURN_BYTES);
+
+        let (next_cursor, integrations) = sc.get_integrations(0, u32::MAX).into_tuple();
+        assert_eq!(next_cursor, MAX_INTEGRATIONS_PAGE_SIZE as u64);
+        assert_eq!(integrations.len(), MAX_INTEGRATIONS_PAGE_SIZE as usize);
+        let integrations_bytes: usize = integrations
+            .into_iter()
+            .map(|entry| {
+                let (integration, permissions) = entry.into_tuple();
+                top_encoded_len(&integration) + top_encoded_len(&permissions)
+            })
+            .sum();
+        assert!(CURSOR_BYTES + integrations_bytes <= MAX_VIEW_RETURN_BYTES);
+    });
+}
+
+#[test]
+fn worst_case_item_sizes_match_page_limits() {
+    let (mut world, whitebox) = setup();
+
+    world.whitebox_query(&whitebox, |_| {
+        let rating = WineRating::<DebugApi> {
+            rater: managed_address!(&raw_address(0)),
+            rating: 10,
+            review: ManagedBuffer::from(&[0xffu8; MAX_REVIEW_LENGTH][..]),
+            timestamp: u64::MAX,
+        };
+        assert!(CURSOR_BYTES + MAX_RATINGS_PAGE_SIZE as usize * top_encoded_len(&rating) <= MAX_VIEW_RETURN_BYTES);
+
+        assert!(CURSOR_BYTES + MAX_WINE_IDS_PAGE_SIZE as usize * top_encoded_len(&u32::MAX) <= MAX_VIEW_RETURN_BYTES);
+
+        let permissions = IntegrationPermissions {
+            can_decrement_availability: true,
+            can_record_sale: true,
+            can_flag: true,
+        };
+        let integration_len =
+            top_encoded_len(&ManagedAddress::<DebugApi>::from(raw_address(0))) + top_encoded_len(&permissions);
+        assert!(CURSOR_BYTES + MAX_INTEGRATIONS_PAGE_SIZE as usize * integration_len <= MAX_VIEW_RETURN_BYTES);
+    });
+}
+
+#[test]
+fn cursor_walks_every_item_once() {
+    let (mut world, whitebox) = setup();
+    let producer = address(PRODUCER_ADDRESS_EXPR);
+    let total = MAX_WINE_IDS_PAGE_SIZE * 2 + 345;
+
+    world.whitebox_call(&whitebox, ScCallStep::new().from(OWNER_ADDRESS_EXPR), |sc| {
+        for wine_id in 1..=total {
+            sc.producer_wines(&managed_address!(&producer)).push(&wine_id);
+        }
+    });
+
+    world.whitebox_query(&whitebox, |sc| {
+        let mut collected = Vec::new();
+        let mut cursor = 0u64;
+        let mut pages = 0;
+        loop {
+            let (next_cursor, wine_ids) = sc
+                .get_producer_wines(&managed_address!(&producer), cursor, 0)
+                .into_tuple();
+            collected.extend(wine_ids.into_iter());
+            pages += 1;
+            if next_cursor == 0 {
+                break;
+            }
+            cursor = next_cursor;
+        }
+        assert_eq!(pages, 3);
+        assert_eq!(collected, (1..=total).collect::<Vec<u32>>());
+
+        // smaller requested pages are honoured, cursors past the end return nothing
+        let (next_cursor, wine_ids) = sc
+            .get_producer_wines(&managed_address!(&producer), 10, 25)
+            .into_tuple();
+        assert_eq!(next_cursor, 35);
+        assert_eq!(wine_ids.to_vec().get(0), 11u32);
+
+        let (next_cursor, wine_ids) = sc
+            .get_producer_wines(&managed_address!(&producer), u64::MAX, 25)
+            .into_tuple();
+        assert_eq!(next_cursor, 0);
+        assert_eq!(wine_ids.len(), 0);
+    });
+}
+
+#[test]
+fn overlong_review_is_rejected() {
+    let (mut world, whitebox) = setup();
+    let wine_id = register_wine(&mut world, &whitebox, PRODUCER_ADDRESS_EXPR, 100);
+
+    world.whitebox_call_check(
+        &whitebox,
+        ScCallStep::new().from(COLLECTOR_ADDRESS_EXPR).no_expect(),
+        |sc| {
+            sc.rate_wine(wine_id, 7, ManagedBuffer::from(&[b'a'; MAX_REVIEW_LENGTH + 1][..]));
+        },
+        |r| {
+            r.assert_user_error(ERR_REVIEW_IS_TOO_LONG);
+        },
+    );
+}
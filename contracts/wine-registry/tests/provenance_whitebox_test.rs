@@ -0,0 +1,228 @@
+mod registry_setup;
+
+use multiversx_sc_scenario::imports::*;
+use registry_setup::*;
+use wine_common::errors::*;
+use wine_registry::admin::AdminModule;
+use wine_registry::provenance::{OwnershipChangeReason, ProvenanceModule, SensitiveAction, WineAction};
+use wine_registry::registration::RegistrationModule;
+
+#[test]
+fn registration_records_owner_and_producer_index() {
+    let (mut world, whitebox) = setup();
+    let first_id = register_wine(&mut world, &whitebox, PRODUCER_ADDRESS_EXPR, 100);
+    let second_id = register_wine(&mut world, &whitebox, COLLECTOR_ADDRESS_EXPR, 10);
+    let third_id = register_wine(&mut world, &whitebox, PRODUCER_ADDRESS_EXPR, 50);
+
+    world.whitebox_query(&whitebox, |sc| {
+        let producer = managed_address!(&address(PRODUCER_ADDRESS_EXPR));
+        assert_eq!(sc.get_wine_owner(first_id), producer);
+        assert_eq!(sc.get_wine_owner(second_id), managed_address!(&address(COLLECTOR_ADDRESS_EXPR)));
+
+        let (next_cursor, producer_wines) = sc.get_producer_wines(&producer, 0, 0).into_tuple();
+        let producer_wines = producer_wines.to_vec();
+        assert_eq!(next_cursor, 0);
+        assert_eq!(producer_wines.len(), 2);
+        assert_eq!(producer_wines.get(0), first_id);
+        assert_eq!(producer_wines.get(1), third_id);
+    });
+}
+
+#[test]
+fn only_items_escrowed_by_the_owner_lock_the_wine() {
+    let (mut world, whitebox) = setup();
+    let wine_id = register_wine(&mut world, &whitebox, PRODUCER_ADDRESS_EXPR, 10);
+    world.whitebox_call(&whitebox, ScCallStep::new().from(OWNER_ADDRESS_EXPR), |sc| {
+        sc.set_integration_permissions(managed_address!(&address(MARKETPLACE_ADDRESS_EXPR)), false, true, false);
+    });
+
+    // A collector reselling a bottle does not freeze the producer's wine
+    world.whitebox_call(&whitebox, ScCallStep::new().from(MARKETPLACE_ADDRESS_EXPR), |sc| {
+        sc.lock_wine(wine_id, managed_address!(&address(COLLECTOR_ADDRESS_EXPR)));
+    });
+    world.whitebox_query(&whitebox, |sc| {
+        assert!(!sc.is_wine_locked(wine_id));
+        assert_eq!(sc.get_wine_lock_count(wine_id), 1);
+    });
+
+    world.whitebox_call(&whitebox, ScCallStep::new().from(MARKETPLACE_ADDRESS_EXPR), |sc| {
+        sc.lock_wine(wine_id, managed_address!(&address(PRODUCER_ADDRESS_EXPR)));
+    });
+    world.whitebox_call_check(
+        &whitebox,
+        ScCallStep::new().from(PRODUCER_ADDRESS_EXPR).no_expect(),
+        |sc| {
+            let collector = managed_address!(&address(COLLECTOR_ADDRESS_EXPR));
+            sc.transfer_wine_ownership(wine_id, collector, OwnershipChangeReason::Gift)
+        },
+        |r| r.assert_user_error(ERR_WINE_IS_LOCKED_BY_A_MARKETPLACE),
+    );
+    // Locks are released by the holder they were taken for
+    world.whitebox_call_check(
+        &whitebox,
+        ScCallStep::new().from(MARKETPLACE_ADDRESS_EXPR).no_expect(),
+        |sc| sc.unlock_wine(wine_id, managed_address!(&address(AUTHORITY_ADDRESS_EXPR))),
+        |r| r.assert_user_error(ERR_WINE_IS_NOT_LOCKED_BY_CALLER),
+    );
+    world.whitebox_call(&whitebox, ScCallStep::new().from(MARKETPLACE_ADDRESS_EXPR), |sc| {
+        sc.unlock_wine(wine_id, managed_address!(&address(PRODUCER_ADDRESS_EXPR)));
+    });
+    world.whitebox_call(&whitebox, ScCallStep::new().from(PRODUCER_ADDRESS_EXPR), |sc| {
+        let collector = managed_address!(&address(COLLECTOR_ADDRESS_EXPR));
+        sc.transfer_wine_ownership(wine_id, collector, OwnershipChangeReason::Gift);
+    });
+
+    // The collector's own escrowed bottle locks the wine now that they own it
+    world.whitebox_query(&whitebox, |sc| {
+        assert!(sc.is_wine_locked(wine_id));
+    });
+}
+
+#[test]
+fn co_owners_share_a_wine_and_approve_sensitive_actions() {
+    let (mut world, whitebox) = setup();
+    let wine_id = register_wine(&mut world, &whitebox, PRODUCER_ADDRESS_EXPR, 10);
+
+    world.whitebox_call_check(
+        &whitebox,
+        ScCallStep::new().from(COLLECTOR_ADDRESS_EXPR).no_expect(),
+        |sc| sc.add_co_owner(wine_id, managed_address!(&address(COLLECTOR_ADDRESS_EXPR)), 3_000),
+        |r| r.assert_user_error(ERR_ONLY_WINE_OWNER_CAN_ADD_CO_OWNERS),
+    );
+    world.whitebox_call(&whitebox, ScCallStep::new().from(PRODUCER_ADDRESS_EXPR), |sc| {
+        sc.add_co_owner(wine_id, managed_address!(&address(COLLECTOR_ADDRESS_EXPR)), 3_000);
+        sc.add_co_owner(wine_id, managed_address!(&address(AUTHORITY_ADDRESS_EXPR)), 2_000);
+        sc.set_approval_threshold(wine_id, SensitiveAction::PriceUpdate, 6_000);
+        sc.set_approval_threshold(wine_id, SensitiveAction::Transfer, 10_000);
+    });
+    // The owner has to keep part of the wine
+    world.whitebox_call_check(
+        &whitebox,
+        ScCallStep::new().from(PRODUCER_ADDRESS_EXPR).no_expect(),
+        |sc| sc.add_co_owner(wine_id, managed_address!(&address(MARKETPLACE_ADDRESS_EXPR)), 5_000),
+        |r| r.assert_user_error(ERR_CO_OWNER_SHARE_EXCEEDS_OWNER_SHARE),
+    );
+    world.whitebox_call_check(
+        &whitebox,
+        ScCallStep::new().from(PRODUCER_ADDRESS_EXPR).no_expect(),
+        |sc| sc.set_approval_threshold(wine_id, SensitiveAction::PriceUpdate, 0),
+        |r| r.assert_user_error(ERR_APPROVAL_THRESHOLD_CANNOT_BE_LOWERED_WITH_CO_OWNERS),
+    );
+
+    world.whitebox_query(&whitebox, |sc| {
+        let table: Vec<_> = sc.get_ownership_table(wine_id).into_iter().map(|entry| entry.into_tuple()).collect();
+        assert_eq!(
+            table,
+            vec![
+                (managed_address!(&address(PRODUCER_ADDRESS_EXPR)), 5_000),
+                (managed_address!(&address(COLLECTOR_ADDRESS_EXPR)), 3_000),
+                (managed_address!(&address(AUTHORITY_ADDRESS_EXPR)), 2_000),
+            ]
+        );
+    });
+
+    // The owner's half is not enough to change the price alone
+    world.whitebox_call_check(
+        &whitebox,
+        ScCallStep::new().from(PRODUCER_ADDRESS_EXPR).no_expect(),
+        |sc| sc.update_wine_price(wine_id, BigUint::from(2_000u64)),
+        |r| r.assert_user_error(ERR_CO_OWNER_APPROVALS_BELOW_THRESHOLD),
+    );
+    // Approvals are for one price only
+    world.whitebox_call(&whitebox, ScCallStep::new().from(COLLECTOR_ADDRESS_EXPR), |sc| {
+        sc.approve_wine_action(wine_id, WineAction::PriceUpdate(BigUint::from(2_000u64)));
+    });
+    world.whitebox_call_check(
+        &whitebox,
+        ScCallStep::new().from(PRODUCER_ADDRESS_EXPR).no_expect(),
+        |sc| sc.update_wine_price(wine_id, BigUint::from(3_000u64)),
+        |r| r.assert_user_error(ERR_CO_OWNER_APPROVALS_BELOW_THRESHOLD),
+    );
+    world.whitebox_call(&whitebox, ScCallStep::new().from(PRODUCER_ADDRESS_EXPR), |sc| {
+        sc.update_wine_price(wine_id, BigUint::from(2_000u64));
+    });
+    world.whitebox_query(&whitebox, |sc| {
+        assert_eq!(sc.get_approved_share(wine_id, WineAction::PriceUpdate(BigUint::from(2_000u64))), 0);
+    });
+
+    // Only co-owners give up their own share
+    world.whitebox_call_check(
+        &whitebox,
+        ScCallStep::new().from(PRODUCER_ADDRESS_EXPR).no_expect(),
+        |sc| sc.remove_co_owner(wine_id, managed_address!(&address(AUTHORITY_ADDRESS_EXPR))),
+        |r| r.assert_user_error(ERR_ONLY_THE_CO_OWNER_CAN_GIVE_UP_THEIR_SHARE),
+    );
+    world.whitebox_call(&whitebox, ScCallStep::new().from(AUTHORITY_ADDRESS_EXPR), |sc| {
+        sc.remove_co_owner(wine_id, managed_address!(&address(AUTHORITY_ADDRESS_EXPR)));
+    });
+    world.whitebox_query(&whitebox, |sc| {
+        let table: Vec<_> = sc.get_ownership_table(wine_id).into_iter().map(|entry| entry.into_tuple()).collect();
+        assert_eq!(
+            table,
+            vec![
+                (managed_address!(&address(PRODUCER_ADDRESS_EXPR)), 7_000),
+                (managed_address!(&address(COLLECTOR_ADDRESS_EXPR)), 3_000),
+            ]
+        );
+    });
+
+    // Co-owners do not carry over: the new owner holds the whole wine
+    world.whitebox_call(&whitebox, ScCallStep::new().from(COLLECTOR_ADDRESS_EXPR), |sc| {
+        let authority = managed_address!(&address(AUTHORITY_ADDRESS_EXPR));
+        sc.approve_wine_action(wine_id, WineAction::Transfer(authority));
+    });
+    world.whitebox_call(&whitebox, ScCallStep::new().from(PRODUCER_ADDRESS_EXPR), |sc| {
+        let authority = managed_address!(&address(AUTHORITY_ADDRESS_EXPR));
+        sc.transfer_wine_ownership(wine_id, authority, OwnershipChangeReason::Gift);
+    });
+    world.whitebox_query(&whitebox, |sc| {
+        let table: Vec<_> = sc.get_ownership_table(wine_id).into_iter().map(|entry| entry.into_tuple()).collect();
+        assert_eq!(table, vec![(managed_address!(&address(AUTHORITY_ADDRESS_EXPR)), 10_000)]);
+        assert!(sc.co_owners(wine_id).is_empty());
+    });
+}
+
+#[test]
+fn co_owners_approve_handing_the_wine_to_a_marketplace() {
+    let (mut world, whitebox) = setup();
+    let wine_id = register_wine(&mut world, &whitebox, PRODUCER_ADDRESS_EXPR, 10);
+    world.whitebox_call(&whitebox, ScCallStep::new().from(OWNER_ADDRESS_EXPR), |sc| {
+        sc.set_integration_permissions(managed_address!(&address(MARKETPLACE_ADDRESS_EXPR)), false, true, false);
+    });
+    world.whitebox_call(&whitebox, ScCallStep::new().from(PRODUCER_ADDRESS_EXPR), |sc| {
+        sc.add_co_owner(wine_id, managed_address!(&address(COLLECTOR_ADDRESS_EXPR)), 4_000);
+        sc.set_approval_threshold(wine_id, SensitiveAction::Transfer, 10_000);
+    });
+
+    // Neither escrowing the owner's item nor a marketplace transfer bypasses the co-owners
+    world.whitebox_call_check(
+        &whitebox,
+        ScCallStep::new().from(MARKETPLACE_ADDRESS_EXPR).no_expect(),
+        |sc| sc.lock_wine(wine_id, managed_address!(&address(PRODUCER_ADDRESS_EXPR))),
+        |r| r.assert_user_error(ERR_CO_OWNER_APPROVALS_BELOW_THRESHOLD),
+    );
+    world.whitebox_call_check(
+        &whitebox,
+        ScCallStep::new().from(MARKETPLACE_ADDRESS_EXPR).no_expect(),
+        |sc| {
+            let authority = managed_address!(&address(AUTHORITY_ADDRESS_EXPR));
+            sc.transfer_wine_ownership(wine_id, authority, OwnershipChangeReason::Marketplace)
+        },
+        |r| r.assert_user_error(ERR_CO_OWNER_APPROVALS_BELOW_THRESHOLD),
+    );
+
+    // Once co-owners approve the marketplace, its sale of the escrowed item goes through
+    world.whitebox_call(&whitebox, ScCallStep::new().from(COLLECTOR_ADDRESS_EXPR), |sc| {
+        let marketplace = managed_address!(&address(MARKETPLACE_ADDRESS_EXPR));
+        sc.approve_wine_action(wine_id, WineAction::Transfer(marketplace));
+    });
+    world.whitebox_call(&whitebox, ScCallStep::new().from(MARKETPLACE_ADDRESS_EXPR), |sc| {
+        sc.lock_wine(wine_id, managed_address!(&address(PRODUCER_ADDRESS_EXPR)));
+        let authority = managed_address!(&address(AUTHORITY_ADDRESS_EXPR));
+        sc.transfer_wine_ownership(wine_id, authority, OwnershipChangeReason::Marketplace);
+    });
+    world.whitebox_query(&whitebox, |sc| {
+        let table: Vec<_> = sc.get_ownership_table(wine_id).into_iter().map(|entry| entry.into_tuple()).collect();
+        assert_eq!(table, vec![(managed_address!(&address(AUTHORITY_ADDRESS_EXPR)), 10_000)]);
+    });
+}
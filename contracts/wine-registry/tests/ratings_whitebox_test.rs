@@ -0,0 +1,96 @@
+mod registry_setup;
+
+use multiversx_sc_scenario::imports::*;
+use registry_setup::*;
+use wine_common::errors::*;
+use wine_registry::ratings::RatingsModule;
+
+fn rate(world: &mut ScenarioWorld, whitebox: &RegistryWhitebox, from: &str, wine_id: u32, rating: u8) {
+    world.whitebox_call(whitebox, ScCallStep::new().from(from), |sc| {
+        sc.rate_wine(wine_id, rating, managed_buffer!(b"Lovely structure"));
+    });
+}
+
+#[test]
+fn average_rating_is_recomputed_on_each_rating() {
+    let (mut world, whitebox) = setup();
+    let wine_id = register_wine(&mut world, &whitebox, PRODUCER_ADDRESS_EXPR, 100);
+
+    rate(&mut world, &whitebox, COLLECTOR_ADDRESS_EXPR, wine_id, 8);
+    world.whitebox_query(&whitebox, |sc| {
+        assert_eq!(sc.get_wine_average_rating(wine_id), 800u16);
+    });
+
+    rate(&mut world, &whitebox, PRODUCER_ADDRESS_EXPR, wine_id, 5);
+    world.whitebox_query(&whitebox, |sc| {
+        assert_eq!(sc.get_wine_average_rating(wine_id), 650u16);
+        let (_, ratings) = sc.get_wine_ratings(wine_id, 0, 0).into_tuple();
+        assert_eq!(ratings.len(), 2);
+    });
+}
+
+#[test]
+fn average_rating_rounds_down() {
+    let (mut world, whitebox) = setup();
+    let wine_id = register_wine(&mut world, &whitebox, PRODUCER_ADDRESS_EXPR, 100);
+
+    rate(&mut world, &whitebox, COLLECTOR_ADDRESS_EXPR, wine_id, 8);
+    rate(&mut world, &whitebox, PRODUCER_ADDRESS_EXPR, wine_id, 5);
+    rate(&mut world, &whitebox, OWNER_ADDRESS_EXPR, wine_id, 6);
+
+    // 19 / 3 = 6.333...
+    world.whitebox_query(&whitebox, |sc| {
+        assert_eq!(sc.get_wine_average_rating(wine_id), 633u16);
+    });
+}
+
+#[test]
+fn duplicate_rating_is_rejected() {
+    let (mut world, whitebox) = setup();
+    let wine_id = register_wine(&mut world, &whitebox, PRODUCER_ADDRESS_EXPR, 100);
+    rate(&mut world, &whitebox, COLLECTOR_ADDRESS_EXPR, wine_id, 8);
+
+    world.whitebox_call_check(
+        &whitebox,
+        ScCallStep::new().from(COLLECTOR_ADDRESS_EXPR).no_expect(),
+        |sc| {
+            sc.rate_wine(wine_id, 9, managed_buffer!(b"Changed my mind"));
+        },
+        |r| {
+            r.assert_user_error(ERR_YOU_HAVE_ALREADY_RATED_THIS_WINE);
+        },
+    );
+
+    world.whitebox_query(&whitebox, |sc| {
+        assert_eq!(sc.get_wine_average_rating(wine_id), 800u16);
+        let (_, ratings) = sc.get_wine_ratings(wine_id, 0, 0).into_tuple();
+        assert_eq!(ratings.len(), 1);
+    });
+}
+
+#[test]
+fn rating_validation() {
+    let (mut world, whitebox) = setup();
+    let wine_id = register_wine(&mut world, &whitebox, PRODUCER_ADDRESS_EXPR, 100);
+
+    world.whitebox_call_check(
+        &whitebox,
+        ScCallStep::new().from(COLLECTOR_ADDRESS_EXPR).no_expect(),
+        |sc| {
+            sc.rate_wine(wine_id, 11, managed_buffer!(b""));
+        },
+        |r| {
+            r.assert_user_error(ERR_RATING_MUST_BE_BETWEEN_1_10);
+        },
+    );
+    world.whitebox_call_check(
+        &whitebox,
+        ScCallStep::new().from(COLLECTOR_ADDRESS_EXPR).no_expect(),
+        |sc| {
+            sc.rate_wine(wine_id + 1, 5, managed_buffer!(b""));
+        },
+        |r| {
+            r.assert_user_error(ERR_WINE_DOES_NOT_EXIST);
+        },
+    );
+}
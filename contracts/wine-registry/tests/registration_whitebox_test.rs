@@ -0,0 +1,259 @@
+mod registry_setup;
+
+use multiversx_sc_scenario::imports::*;
+use registry_setup::*;
+use wine_common::errors::*;
+use wine_registry::admin::AdminModule;
+use wine_registry::producers::ProducersModule;
+use wine_registry::registration::RegistrationModule;
+
+#[test]
+fn register_wine_assigns_sequential_ids() {
+    let (mut world, whitebox) = setup();
+
+    let first_id = register_wine(&mut world, &whitebox, PRODUCER_ADDRESS_EXPR, 120);
+    let second_id = register_wine(&mut world, &whitebox, PRODUCER_ADDRESS_EXPR, 60);
+    assert_eq!(first_id, 1);
+    assert_eq!(second_id, 2);
+
+    world.whitebox_query(&whitebox, |sc| {
+        assert_eq!(sc.get_total_wines_registered(), 2u32);
+        let wine = sc.get_wine_details(1);
+        assert_eq!(wine.total_bottles, 120u32);
+        assert_eq!(wine.available_bottles, 120u32);
+        assert_eq!(wine.production_date, 1_700_000_000u64);
+    });
+}
+
+#[test]
+fn register_wine_refunds_overpayment() {
+    let (mut world, whitebox) = setup();
+
+    world.whitebox_call(
+        &whitebox,
+        ScCallStep::new().from(PRODUCER_ADDRESS_EXPR).egld_value(REGISTRATION_FEE * 3),
+        |sc| {
+            sc.register_wine(
+                managed_buffer!(b"Domaine de Test"),
+                managed_buffer!(b"Syrah"),
+                2019u32,
+                7u8,
+                1400u16,
+                managed_buffer!(b"Rhone"),
+                managed_buffer!(b""),
+                managed_buffer!(b""),
+                managed_buffer!(b"QmYwAPJzv5CZsnA625s3Xf2nemtYgPpHdWEz79ojWnPbdG"),
+                10u32,
+                managed_biguint!(1_000),
+                true,
+                0u64,
+                managed_buffer!(b""),
+                managed_buffer!(b""),
+            );
+        },
+    );
+
+    world.check_state_step(
+        CheckStateStep::new()
+            .put_account(PRODUCER_ADDRESS_EXPR, CheckAccount::new().balance((INITIAL_BALANCE - REGISTRATION_FEE).to_string().as_str()))
+            .put_account(REGISTRY_ADDRESS_EXPR, CheckAccount::new().balance(REGISTRATION_FEE.to_string().as_str())),
+    );
+}
+
+#[test]
+fn register_wine_rejects_insufficient_fee() {
+    let (mut world, whitebox) = setup();
+
+    world.whitebox_call_check(
+        &whitebox,
+        ScCallStep::new().from(PRODUCER_ADDRESS_EXPR).egld_value(REGISTRATION_FEE - 1).no_expect(),
+        |sc| {
+            sc.register_wine(
+                managed_buffer!(b"Domaine de Test"),
+                managed_buffer!(b"Syrah"),
+                2019u32,
+                7u8,
+                1400u16,
+                managed_buffer!(b"Rhone"),
+                managed_buffer!(b""),
+                managed_buffer!(b""),
+                managed_buffer!(b"QmYwAPJzv5CZsnA625s3Xf2nemtYgPpHdWEz79ojWnPbdG"),
+                10u32,
+                managed_biguint!(1_000),
+                true,
+                0u64,
+                managed_buffer!(b""),
+                managed_buffer!(b""),
+            );
+        },
+        |r| {
+            r.assert_user_error(ERR_INSUFFICIENT_REGISTRATION_FEE);
+        },
+    );
+}
+
+#[test]
+fn availability_updates_require_owner_or_integration() {
+    let (mut world, whitebox) = setup();
+    let wine_id = register_wine(&mut world, &whitebox, PRODUCER_ADDRESS_EXPR, 100);
+
+    world.whitebox_call(&whitebox, ScCallStep::new().from(PRODUCER_ADDRESS_EXPR), |sc| {
+        sc.update_wine_availability(wine_id, 10);
+    });
+
+    world.whitebox_call_check(
+        &whitebox,
+        ScCallStep::new().from(MARKETPLACE_ADDRESS_EXPR).no_expect(),
+        |sc| {
+            sc.update_wine_availability(wine_id, 10);
+        },
+        |r| {
+            r.assert_user_error(ERR_NOT_AUTHORIZED_TO_UPDATE_AVAILABILITY);
+        },
+    );
+
+    world.whitebox_call(&whitebox, ScCallStep::new().from(OWNER_ADDRESS_EXPR), |sc| {
+        sc.set_integration_permissions(managed_address!(&address(MARKETPLACE_ADDRESS_EXPR)), true, false, false);
+    });
+    world.whitebox_call(&whitebox, ScCallStep::new().from(MARKETPLACE_ADDRESS_EXPR), |sc| {
+        sc.update_wine_availability(wine_id, 15);
+    });
+
+    world.whitebox_query(&whitebox, |sc| {
+        assert_eq!(sc.get_wine_details(wine_id).available_bottles, 75u32);
+    });
+}
+
+#[test]
+fn availability_cannot_go_below_zero() {
+    let (mut world, whitebox) = setup();
+    let wine_id = register_wine(&mut world, &whitebox, PRODUCER_ADDRESS_EXPR, 5);
+
+    world.whitebox_call_check(
+        &whitebox,
+        ScCallStep::new().from(PRODUCER_ADDRESS_EXPR).no_expect(),
+        |sc| {
+            sc.update_wine_availability(wine_id, 6);
+        },
+        |r| {
+            r.assert_user_error(ERR_INSUFFICIENT_BOTTLES_AVAILABLE);
+        },
+    );
+}
+
+#[test]
+fn registrations_are_throttled_per_epoch_except_for_verified_producers() {
+    let (mut world, whitebox) = setup();
+    let producer = address(PRODUCER_ADDRESS_EXPR);
+    world.whitebox_call(&whitebox, ScCallStep::new().from(OWNER_ADDRESS_EXPR), |sc| {
+        sc.set_registration_rate_limit(2);
+    });
+
+    register_wine(&mut world, &whitebox, PRODUCER_ADDRESS_EXPR, 10);
+    register_wine(&mut world, &whitebox, PRODUCER_ADDRESS_EXPR, 10);
+    world.whitebox_query(&whitebox, |sc| {
+        let remaining = sc.get_remaining_registrations(managed_address!(&producer));
+        assert_eq!(remaining.into_option(), Some(0u32));
+    });
+    world.whitebox_call_check(
+        &whitebox,
+        ScCallStep::new().from(PRODUCER_ADDRESS_EXPR).egld_value(REGISTRATION_FEE).no_expect(),
+        |sc| {
+            sc.register_wine(
+                managed_buffer!(b"Domaine de Test"),
+                managed_buffer!(b"Syrah"),
+                2019u32,
+                7u8,
+                1400u16,
+                managed_buffer!(b"Rhone"),
+                managed_buffer!(b""),
+                managed_buffer!(b""),
+                managed_buffer!(b"QmYwAPJzv5CZsnA625s3Xf2nemtYgPpHdWEz79ojWnPbdG"),
+                10u32,
+                managed_biguint!(1_000),
+                true,
+                0u64,
+                managed_buffer!(b""),
+                managed_buffer!(b""),
+            );
+        },
+        |r| {
+            r.assert_user_error(ERR_REGISTRATION_RATE_LIMIT_EXCEEDED);
+        },
+    );
+
+    // The allowance resets with the epoch
+    world.set_state_step(SetStateStep::new().block_epoch(1));
+    register_wine(&mut world, &whitebox, PRODUCER_ADDRESS_EXPR, 10);
+    world.whitebox_query(&whitebox, |sc| {
+        let remaining = sc.get_remaining_registrations(managed_address!(&producer));
+        assert_eq!(remaining.into_option(), Some(1u32));
+    });
+
+    // Verified producers are not throttled
+    world.whitebox_call(&whitebox, ScCallStep::new().from(OWNER_ADDRESS_EXPR), |sc| {
+        sc.verify_producer(managed_address!(&producer));
+    });
+    register_wine(&mut world, &whitebox, PRODUCER_ADDRESS_EXPR, 10);
+    register_wine(&mut world, &whitebox, PRODUCER_ADDRESS_EXPR, 10);
+    world.whitebox_query(&whitebox, |sc| {
+        assert!(sc.get_remaining_registrations(managed_address!(&producer)).into_option().is_none());
+        assert_eq!(sc.get_total_wines_registered(), 5u32);
+    });
+}
+
+#[test]
+fn register_wine_accepts_only_structurally_valid_cids() {
+    let (mut world, whitebox) = setup();
+    let valid_cids: [&[u8]; 4] = [
+        b"QmYwAPJzv5CZsnA625s3Xf2nemtYgPpHdWEz79ojWnPbdG",
+        b"bafybeigdyrzt5sfp7udm7hu76uh7y26nf3efuylqabf3oclgtqy55fbzdi",
+        b"BAFYBEIGDYRZT5SFP7UDM7HU76UH7Y26NF3EFUYLQABF3OCLGTQY55FBZDI",
+        b"zdj7WWeQ43G6JJvLWQWZpyHuAMq6uYWRjkBXFad11vE2LHhQ7",
+    ];
+    let invalid_cids: [&[u8]; 7] = [
+        b"ipfs://QmYwAPJzv5CZsnA625s3Xf2nemtYgPpHdWEz79ojWnPbdG",
+        // '0' is not base58
+        b"QmYwAPJzv5CZsnA625s3Xf2nemtYgPpHdWEz79ojWnPbd0",
+        b"QmYwAPJzv5CZsnA625s3Xf2nemtYgPpHdWEz79ojWnPb",
+        b"bafy",
+        // Not version 1, and '1' is not base32
+        b"bbfybeigdyrzt5sfp7udm7hu76uh7y26nf3efuylqabf3oclgtqy55fbzdi",
+        b"bafybeigdyrzt5sfp7udm7hu76uh7y26nf3efuylqabf3oclgtqy55fbzd1",
+        b"metadata.json",
+    ];
+
+    for (cid, expected_error) in valid_cids
+        .iter()
+        .map(|cid| (*cid, None))
+        .chain(invalid_cids.iter().map(|cid| (*cid, Some(ERR_INVALID_IPFS_CID))))
+    {
+        world.whitebox_call_check(
+            &whitebox,
+            ScCallStep::new().from(PRODUCER_ADDRESS_EXPR).egld_value(REGISTRATION_FEE).no_expect(),
+            |sc| {
+                sc.register_wine(
+                    managed_buffer!(b"Domaine de Test"),
+                    managed_buffer!(b"Syrah"),
+                    2019u32,
+                    7u8,
+                    1400u16,
+                    managed_buffer!(b"Rhone"),
+                    managed_buffer!(b""),
+                    managed_buffer!(b""),
+                    managed_buffer!(cid),
+                    10u32,
+                    managed_biguint!(1_000),
+                    true,
+                    0u64,
+                    managed_buffer!(b""),
+                    managed_buffer!(b""),
+                );
+            },
+            |r| match expected_error {
+                Some(error) => r.assert_user_error(error),
+                None => r.assert_ok(),
+            },
+        );
+    }
+}
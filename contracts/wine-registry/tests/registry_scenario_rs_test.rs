@@ -0,0 +1,6 @@
+mod registry_setup;
+
+#[test]
+fn registry_flow_rs() {
+    registry_setup::world().run("scenarios/registry_flow.scen.json");
+}
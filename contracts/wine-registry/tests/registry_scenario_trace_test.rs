@@ -0,0 +1,144 @@
+mod registry_setup;
+
+use multiversx_sc_scenario::api::StaticApi;
+use multiversx_sc_scenario::imports::*;
+use registry_setup::*;
+use wine_common::errors::*;
+use wine_registry::ratings::ProxyTrait as _;
+use wine_registry::registration::ProxyTrait as _;
+use wine_registry::ProxyTrait as _;
+use wine_registry::WineDetails;
+
+const SCENARIO_PATH: &str = "scenarios/registry_flow.scen.json";
+// Code path as seen from the scenarios directory
+const SCENARIO_CODE_PATH_EXPR: &str = "mxsc:../output/wine-registry.mxsc.json";
+const REGISTRATION_TIMESTAMP: u64 = 1_700_000_000;
+
+type RegistryContract = ContractInfo<wine_registry::Proxy<StaticApi>>;
+
+fn user_error(message: &str) -> TxExpect {
+    TxExpect::user_error(format!("str:{message}"))
+}
+
+// Records a registration, two ratings and an availability update, with the expected view
+// results, as the scenario replayed by registry_scenario_rs_test. Regenerate it with
+// `cargo test -- --ignored` only after an intended change to storage or encoding.
+#[test]
+#[ignore = "regenerates scenarios/registry_flow.scen.json"]
+fn generate_registry_flow_scenario() {
+    let mut world = ScenarioWorld::new();
+    world.register_contract(SCENARIO_CODE_PATH_EXPR, wine_registry::ContractBuilder);
+    world.start_trace();
+    let mut registry = RegistryContract::new(REGISTRY_ADDRESS_EXPR);
+    let registry_code = world.code_expression(SCENARIO_CODE_PATH_EXPR);
+
+    world.set_state_step(
+        SetStateStep::new()
+            .put_account(OWNER_ADDRESS_EXPR, Account::new().nonce(1))
+            .put_account(PRODUCER_ADDRESS_EXPR, Account::new().nonce(1).balance(INITIAL_BALANCE))
+            .put_account(COLLECTOR_ADDRESS_EXPR, Account::new().nonce(1).balance(INITIAL_BALANCE))
+            .new_address(OWNER_ADDRESS_EXPR, 1, REGISTRY_ADDRESS_EXPR)
+            .block_timestamp(REGISTRATION_TIMESTAMP),
+    );
+    world.sc_deploy(
+        ScDeployStep::new()
+            .from(OWNER_ADDRESS_EXPR)
+            .code(registry_code)
+            .call(registry.init(REGISTRATION_FEE)),
+    );
+
+    // Overpaying the fee is refunded
+    world.sc_call(
+        ScCallStep::new()
+            .from(PRODUCER_ADDRESS_EXPR)
+            .egld_value(REGISTRATION_FEE * 2)
+            .call(registry.register_wine(
+                "Domaine de Test",
+                "Pinot Noir",
+                2020u32,
+                8u8,
+                1300u16,
+                "Burgundy",
+                "AOC",
+                "producer-signature",
+                "QmYwAPJzv5CZsnA625s3Xf2nemtYgPpHdWEz79ojWnPbdG",
+                100u32,
+                50_000u64,
+                false,
+                1_600_000_000u64,
+                "18 months in French oak",
+                "Cherry, forest floor",
+            ))
+            .expect_value(1u32),
+    );
+    world.check_state_step(
+        CheckStateStep::new()
+            .put_account(
+                PRODUCER_ADDRESS_EXPR,
+                CheckAccount::new().balance((INITIAL_BALANCE - REGISTRATION_FEE).to_string().as_str()),
+            )
+            .put_account(REGISTRY_ADDRESS_EXPR, CheckAccount::new().balance(REGISTRATION_FEE.to_string().as_str())),
+    );
+
+    world.sc_call(
+        ScCallStep::new()
+            .from(PRODUCER_ADDRESS_EXPR)
+            .call(registry.update_wine_availability(1u32, 10u32)),
+    );
+    world.sc_call(
+        ScCallStep::new()
+            .from(COLLECTOR_ADDRESS_EXPR)
+            .call(registry.update_wine_availability(1u32, 10u32))
+            .expect(user_error(ERR_NOT_AUTHORIZED_TO_UPDATE_AVAILABILITY)),
+    );
+
+    world.sc_call(
+        ScCallStep::new()
+            .from(COLLECTOR_ADDRESS_EXPR)
+            .call(registry.rate_wine(1u32, 8u8, "Lovely structure")),
+    );
+    world.sc_call(
+        ScCallStep::new()
+            .from(OWNER_ADDRESS_EXPR)
+            .call(registry.rate_wine(1u32, 5u8, "Short finish")),
+    );
+    world.sc_call(
+        ScCallStep::new()
+            .from(COLLECTOR_ADDRESS_EXPR)
+            .call(registry.rate_wine(1u32, 9u8, "Changed my mind"))
+            .expect(user_error(ERR_YOU_HAVE_ALREADY_RATED_THIS_WINE)),
+    );
+
+    let producer = address(PRODUCER_ADDRESS_EXPR);
+    world.sc_query(
+        ScQueryStep::new()
+            .call(registry.get_wine_details(1u32))
+            .expect_value(WineDetails::<StaticApi> {
+                vineyard: managed_buffer!(b"Domaine de Test"),
+                variety: managed_buffer!(b"Pinot Noir"),
+                vintage_year: 2020,
+                production_date: REGISTRATION_TIMESTAMP,
+                quality_grade: 8,
+                alcohol_content: 1300,
+                region: managed_buffer!(b"Burgundy"),
+                certification: managed_buffer!(b"AOC"),
+                producer_signature: managed_buffer!(b"producer-signature"),
+                ipfs_hash: managed_buffer!(b"QmYwAPJzv5CZsnA625s3Xf2nemtYgPpHdWEz79ojWnPbdG"),
+                total_bottles: 100,
+                available_bottles: 90,
+                price_per_bottle: managed_biguint!(50_000),
+                is_organic: false,
+                harvest_date: 1_600_000_000,
+                aging_process: managed_buffer!(b"18 months in French oak"),
+                tasting_notes: managed_buffer!(b"Cherry, forest floor"),
+                producer: managed_address!(&producer),
+            }),
+    );
+    world.sc_query(
+        ScQueryStep::new()
+            .call(registry.get_wine_average_rating(1u32))
+            .expect_value(650u16),
+    );
+
+    world.write_scenario_trace(SCENARIO_PATH);
+}
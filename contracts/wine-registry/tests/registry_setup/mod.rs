@@ -0,0 +1,83 @@
+#![allow(dead_code)]
+
+use multiversx_sc_scenario::imports::*;
+use wine_registry::registration::RegistrationModule;
+use wine_registry::WineRegistry;
+
+pub const REGISTRY_PATH_EXPR: &str = "mxsc:output/wine-registry.mxsc.json";
+pub const REGISTRY_ADDRESS_EXPR: &str = "sc:wine-registry";
+pub const OWNER_ADDRESS_EXPR: &str = "address:owner";
+pub const PRODUCER_ADDRESS_EXPR: &str = "address:producer";
+pub const COLLECTOR_ADDRESS_EXPR: &str = "address:collector";
+pub const AUTHORITY_ADDRESS_EXPR: &str = "address:authority";
+pub const MARKETPLACE_ADDRESS_EXPR: &str = "address:marketplace";
+
+pub const REGISTRATION_FEE: u64 = 1_000;
+pub const INITIAL_BALANCE: u64 = 1_000_000;
+
+pub type RegistryWhitebox = WhiteboxContract<wine_registry::ContractObj<DebugApi>>;
+
+pub fn world() -> ScenarioWorld {
+    let mut blockchain = ScenarioWorld::new();
+    blockchain.register_contract(REGISTRY_PATH_EXPR, wine_registry::ContractBuilder);
+    blockchain
+}
+
+pub fn setup() -> (ScenarioWorld, RegistryWhitebox) {
+    let mut world = world();
+    let registry_whitebox = WhiteboxContract::new(REGISTRY_ADDRESS_EXPR, wine_registry::contract_obj);
+    let registry_code = world.code_expression(REGISTRY_PATH_EXPR);
+
+    world
+        .set_state_step(
+            SetStateStep::new()
+                .put_account(OWNER_ADDRESS_EXPR, Account::new().nonce(1))
+                .put_account(PRODUCER_ADDRESS_EXPR, Account::new().nonce(1).balance(INITIAL_BALANCE))
+                .put_account(COLLECTOR_ADDRESS_EXPR, Account::new().nonce(1).balance(INITIAL_BALANCE))
+                .put_account(AUTHORITY_ADDRESS_EXPR, Account::new().nonce(1))
+                .put_account(MARKETPLACE_ADDRESS_EXPR, Account::new().nonce(1))
+                .new_address(OWNER_ADDRESS_EXPR, 1, REGISTRY_ADDRESS_EXPR)
+                .block_timestamp(1_700_000_000u64),
+        )
+        .whitebox_deploy(
+            &registry_whitebox,
+            ScDeployStep::new().from(OWNER_ADDRESS_EXPR).code(registry_code),
+            |sc| {
+                sc.init(REGISTRATION_FEE.into());
+            },
+        );
+
+    (world, registry_whitebox)
+}
+
+pub fn address(address_expr: &str) -> Address {
+    AddressValue::from(address_expr).to_address()
+}
+
+pub fn register_wine(world: &mut ScenarioWorld, whitebox: &RegistryWhitebox, from: &str, total_bottles: u32) -> u32 {
+    let mut wine_id = 0u32;
+    world.whitebox_call(
+        whitebox,
+        ScCallStep::new().from(from).egld_value(REGISTRATION_FEE),
+        |sc| {
+            wine_id = sc.register_wine(
+                managed_buffer!(b"Domaine de Test"),
+                managed_buffer!(b"Pinot Noir"),
+                2020u32,
+                8u8,
+                1300u16,
+                managed_buffer!(b"Burgundy"),
+                managed_buffer!(b"AOC"),
+                managed_buffer!(b"producer-signature"),
+                managed_buffer!(b"QmYwAPJzv5CZsnA625s3Xf2nemtYgPpHdWEz79ojWnPbdG"),
+                total_bottles,
+                managed_biguint!(50_000),
+                false,
+                1_600_000_000u64,
+                managed_buffer!(b"18 months in French oak"),
+                managed_buffer!(b"Cherry, forest floor"),
+            );
+        },
+    );
+    wine_id
+}
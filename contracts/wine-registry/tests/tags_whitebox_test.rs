@@ -0,0 +1,85 @@
+mod registry_setup;
+
+use multiversx_sc_scenario::imports::*;
+use registry_setup::*;
+use wine_common::errors::*;
+use wine_registry::tags::{TagCategory, TagsModule};
+
+fn tags(names: &[&[u8]]) -> MultiValueEncoded<DebugApi, ManagedBuffer<DebugApi>> {
+    let mut result = MultiValueEncoded::new();
+    for name in names {
+        result.push(ManagedBuffer::new_from_bytes(name));
+    }
+    result
+}
+
+#[test]
+fn producers_tag_wines_from_the_curated_lists() {
+    let (mut world, whitebox) = setup();
+    let first_id = register_wine(&mut world, &whitebox, PRODUCER_ADDRESS_EXPR, 10);
+    let second_id = register_wine(&mut world, &whitebox, PRODUCER_ADDRESS_EXPR, 10);
+
+    world.whitebox_call(&whitebox, ScCallStep::new().from(OWNER_ADDRESS_EXPR), |sc| {
+        sc.add_tag_option(TagCategory::FoodPairing, managed_buffer!(b"lamb"));
+        sc.add_tag_option(TagCategory::FoodPairing, managed_buffer!(b"cheese"));
+        sc.add_tag_option(TagCategory::Body, managed_buffer!(b"full"));
+    });
+    world.whitebox_call_check(
+        &whitebox,
+        ScCallStep::new().from(PRODUCER_ADDRESS_EXPR).no_expect(),
+        |sc| sc.add_tag_option(TagCategory::Style, managed_buffer!(b"natural")),
+        |r| r.assert_user_error(ERR_CALLER_DOES_NOT_HAVE_THE_REQUIRED_ROLE),
+    );
+
+    // Tags are checked against the list of their own category
+    world.whitebox_call_check(
+        &whitebox,
+        ScCallStep::new().from(PRODUCER_ADDRESS_EXPR).no_expect(),
+        |sc| sc.add_wine_tags(first_id, TagCategory::Body, tags(&[b"lamb"])),
+        |r| r.assert_user_error(ERR_TAG_NOT_LISTED),
+    );
+    world.whitebox_call_check(
+        &whitebox,
+        ScCallStep::new().from(COLLECTOR_ADDRESS_EXPR).no_expect(),
+        |sc| sc.add_wine_tags(first_id, TagCategory::FoodPairing, tags(&[b"lamb"])),
+        |r| r.assert_user_error(ERR_ONLY_PRODUCER_CAN_TAG_THE_WINE),
+    );
+    world.whitebox_call(&whitebox, ScCallStep::new().from(PRODUCER_ADDRESS_EXPR), |sc| {
+        sc.add_wine_tags(first_id, TagCategory::FoodPairing, tags(&[b"lamb", b"cheese"]));
+        sc.add_wine_tags(first_id, TagCategory::Body, tags(&[b"full"]));
+        sc.add_wine_tags(second_id, TagCategory::FoodPairing, tags(&[b"lamb"]));
+    });
+
+    world.whitebox_query(&whitebox, |sc| {
+        let (next_cursor, wine_ids) =
+            sc.get_wines_by_tag(TagCategory::FoodPairing, managed_buffer!(b"lamb"), 0, 0).into_tuple();
+        assert_eq!(next_cursor, 0);
+        assert_eq!(wine_ids.to_vec().into_vec(), vec![first_id, second_id]);
+        let (_, wine_ids) = sc.get_wines_by_tag(TagCategory::Body, managed_buffer!(b"lamb"), 0, 0).into_tuple();
+        assert!(wine_ids.is_empty());
+        assert_eq!(sc.get_wine_tags(first_id).len(), 3);
+    });
+
+    // Removing a tag option leaves it on the wines, which drop it themselves
+    world.whitebox_call(&whitebox, ScCallStep::new().from(OWNER_ADDRESS_EXPR), |sc| {
+        sc.remove_tag_option(TagCategory::FoodPairing, managed_buffer!(b"lamb"));
+    });
+    world.whitebox_call(&whitebox, ScCallStep::new().from(PRODUCER_ADDRESS_EXPR), |sc| {
+        sc.remove_wine_tags(first_id, TagCategory::FoodPairing, tags(&[b"lamb", b"unknown"]));
+    });
+    world.whitebox_query(&whitebox, |sc| {
+        let (_, wine_ids) =
+            sc.get_wines_by_tag(TagCategory::FoodPairing, managed_buffer!(b"lamb"), 0, 0).into_tuple();
+        assert_eq!(wine_ids.to_vec().into_vec(), vec![second_id]);
+        let first_tags: Vec<(TagCategory, ManagedBuffer<DebugApi>)> =
+            sc.get_wine_tags(first_id).into_iter().map(|tag| tag.into_tuple()).collect();
+        assert_eq!(
+            first_tags,
+            vec![
+                (TagCategory::FoodPairing, managed_buffer!(b"cheese")),
+                (TagCategory::Body, managed_buffer!(b"full")),
+            ]
+        );
+        assert_eq!(sc.get_tag_options(TagCategory::FoodPairing).len(), 1);
+    });
+}
@@ -0,0 +1,118 @@
+#![no_std]
+
+use multiversx_sc::derive_imports::*;
+use multiversx_sc::imports::*;
+use wine_common::errors::*;
+
+#[derive(TopEncode, TopDecode, NestedEncode, NestedDecode, TypeAbi, Clone, Copy, PartialEq, Debug)]
+pub enum Role {
+    Admin,
+    FeeManager,
+    Pauser,
+}
+
+// Roles replace single-owner control. The owner implicitly holds every role and is the only
+// one who can grant or revoke Admin; admins manage the other roles. While paused, each
+// contract rejects the endpoints it guards with `require_not_paused`.
+#[multiversx_sc::module]
+pub trait RolesModule {
+    #[endpoint(grantRole)]
+    fn grant_role(&self, role: Role, address: ManagedAddress) {
+        self.require_role_manager(role);
+        require!(self.role_members(role).insert(address.clone()), ERR_ADDRESS_ALREADY_HAS_THE_ROLE);
+
+        self.role_granted_event(role, &address, &self.blockchain().get_caller());
+    }
+
+    #[endpoint(revokeRole)]
+    fn revoke_role(&self, role: Role, address: ManagedAddress) {
+        self.require_role_manager(role);
+        require!(self.role_members(role).swap_remove(&address), ERR_ADDRESS_DOES_NOT_HAVE_THE_ROLE);
+
+        self.role_revoked_event(role, &address, &self.blockchain().get_caller());
+    }
+
+    #[endpoint(pause)]
+    fn pause(&self) {
+        self.require_role(Role::Pauser);
+        require!(!self.paused().get(), ERR_CONTRACT_IS_ALREADY_PAUSED);
+        self.paused().set(true);
+
+        self.contract_paused_event(&self.blockchain().get_caller());
+    }
+
+    #[endpoint(unpause)]
+    fn unpause(&self) {
+        self.require_role(Role::Pauser);
+        require!(self.paused().get(), ERR_CONTRACT_IS_NOT_PAUSED);
+        self.paused().clear();
+
+        self.contract_unpaused_event(&self.blockchain().get_caller());
+    }
+
+    // Internal helpers
+    fn require_role(&self, role: Role) {
+        let caller = self.blockchain().get_caller();
+        require!(self.has_role(role, &caller), ERR_CALLER_DOES_NOT_HAVE_THE_REQUIRED_ROLE);
+    }
+
+    fn require_role_manager(&self, role: Role) {
+        let caller = self.blockchain().get_caller();
+        if role == Role::Admin {
+            require!(caller == self.blockchain().get_owner_address(), ERR_ONLY_OWNER_CAN_MANAGE_ADMINS);
+        } else {
+            require!(self.has_role(Role::Admin, &caller), ERR_ONLY_ADMINS_CAN_MANAGE_ROLES);
+        }
+    }
+
+    fn require_not_paused(&self) {
+        require!(!self.paused().get(), ERR_CONTRACT_IS_PAUSED);
+    }
+
+    // View functions
+    #[view(hasRole)]
+    fn has_role(&self, role: Role, address: &ManagedAddress) -> bool {
+        *address == self.blockchain().get_owner_address() || self.role_members(role).contains(address)
+    }
+
+    // Role sets are curated by the owner and admins, so they are returned whole
+    #[view(getRoleMembers)]
+    fn get_role_members(&self, role: Role) -> MultiValueEncoded<ManagedAddress> {
+        self.role_members(role).iter().collect()
+    }
+
+    #[view(isPaused)]
+    fn is_paused(&self) -> bool {
+        self.paused().get()
+    }
+
+    // Storage mappers
+    #[storage_mapper("roleMembers")]
+    fn role_members(&self, role: Role) -> UnorderedSetMapper<ManagedAddress>;
+
+    #[storage_mapper("paused")]
+    fn paused(&self) -> SingleValueMapper<bool>;
+
+    // Events
+    #[event("roleGranted")]
+    fn role_granted_event(
+        &self,
+        #[indexed] role: Role,
+        #[indexed] address: &ManagedAddress,
+        granted_by: &ManagedAddress,
+    );
+
+    #[event("roleRevoked")]
+    fn role_revoked_event(
+        &self,
+        #[indexed] role: Role,
+        #[indexed] address: &ManagedAddress,
+        revoked_by: &ManagedAddress,
+    );
+
+    #[event("contractPaused")]
+    fn contract_paused_event(&self, #[indexed] paused_by: &ManagedAddress);
+
+    #[event("contractUnpaused")]
+    fn contract_unpaused_event(&self, #[indexed] unpaused_by: &ManagedAddress);
+}
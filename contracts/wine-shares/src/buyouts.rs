@@ -0,0 +1,352 @@
+use multiversx_sc::derive_imports::*;
+use multiversx_sc::imports::*;
+
+use crate::vaults::VaultStatus;
+use wine_common::errors::*;
+
+// Below the reserve price, a buyout needs votes from holders of more than half the supply
+pub const BUYOUT_APPROVAL_BPS: u64 = 5_000;
+pub const BUYOUT_VOTING_PERIOD: u64 = 7 * 24 * 60 * 60;
+
+// An offer for a vault's wine, escrowed until it executes or the bidder withdraws it
+#[derive(TopEncode, TopDecode, NestedEncode, NestedDecode, TypeAbi, PartialEq, Debug)]
+pub struct Buyout<M: ManagedTypeApi> {
+    // Sequential per vault, starting at 1
+    pub id: u32,
+    pub bidder: ManagedAddress<M>,
+    pub amount: BigUint<M>,
+    // End of the voting period
+    pub deadline: u64,
+    // Shares locked in favour of this buyout
+    pub votes: BigUint<M>,
+}
+
+// Shares a holder has locked to vote. They count for `buyout_id` only, and stay locked until
+// withdrawn or, once the vault is bought out, claimed against the proceeds.
+#[derive(TopEncode, TopDecode, NestedEncode, NestedDecode, TypeAbi, PartialEq, Debug)]
+pub struct VoteLock<M: ManagedTypeApi> {
+    pub buyout_id: u32,
+    pub shares: BigUint<M>,
+}
+
+// Ways out of a vault. A buyout at or above the reserve price executes at once; lower offers
+// run a vote among shareholders. After a buyout, burning shares pays out the proceeds
+// pro-rata. Holding the whole supply redeems the wine directly.
+#[multiversx_sc::module]
+pub trait BuyoutsModule: wine_roles::RolesModule + crate::vaults::VaultsModule {
+    // Offer to buy the vault's wine, paying the offer in the vault's payment token. Only one
+    // buyout is open per vault; a higher offer replaces it and refunds its bidder, and votes
+    // locked for it move over when their holders vote again.
+    #[payable("*")]
+    #[endpoint(proposeBuyout)]
+    fn propose_buyout(&self, vault_id: u32) {
+        self.require_not_paused();
+        let vault = self.require_vault(vault_id);
+        require!(vault.status == VaultStatus::Active, ERR_VAULT_IS_NOT_ACTIVE);
+
+        let payment = self.call_value().egld_or_single_esdt();
+        require!(payment.token_identifier == vault.payment_token, ERR_INVALID_PAYMENT_TOKEN);
+        require!(payment.amount > 0, ERR_BUYOUT_AMOUNT_MUST_BE_GREATER_THAN_0);
+        if !self.active_buyout(vault_id).is_empty() {
+            let open_buyout = self.active_buyout(vault_id).get();
+            require!(payment.amount > open_buyout.amount, ERR_OFFER_MUST_EXCEED_THE_OPEN_BUYOUT);
+            self.refund_open_buyout(vault_id, &vault.payment_token);
+        }
+
+        let bidder = self.blockchain().get_caller();
+        let buyout_id = self.buyout_count(vault_id).get() + 1;
+        self.buyout_count(vault_id).set(buyout_id);
+        let buyout = Buyout {
+            id: buyout_id,
+            bidder: bidder.clone(),
+            amount: payment.amount,
+            deadline: self.blockchain().get_block_timestamp() + BUYOUT_VOTING_PERIOD,
+            votes: BigUint::zero(),
+        };
+
+        self.buyout_proposed_event(vault_id, buyout_id, &bidder, &buyout.amount);
+
+        if buyout.amount >= vault.reserve_price {
+            self.execute_buyout(vault_id, &buyout);
+        } else {
+            self.active_buyout(vault_id).set(&buyout);
+        }
+    }
+
+    // Lock shares in favour of the open buyout, paying any additional shares to the call.
+    // Shares already locked for an earlier buyout are moved to this one. The buyout executes
+    // as soon as the votes pass the approval threshold.
+    #[payable("*")]
+    #[endpoint(voteBuyout)]
+    fn vote_buyout(&self, vault_id: u32) {
+        let vault = self.require_vault(vault_id);
+        require!(!self.active_buyout(vault_id).is_empty(), ERR_NO_OPEN_BUYOUT);
+        let mut buyout = self.active_buyout(vault_id).get();
+        require!(
+            self.blockchain().get_block_timestamp() <= buyout.deadline,
+            ERR_VOTING_PERIOD_HAS_ENDED
+        );
+
+        let voter = self.blockchain().get_caller();
+        let added_shares = self.collect_share_payments(vault_id);
+        let mut lock = self.vote_lock_or_empty(vault_id, &voter);
+        let new_votes = if lock.buyout_id == buyout.id {
+            added_shares.clone()
+        } else {
+            &lock.shares + &added_shares
+        };
+        require!(new_votes > 0, ERR_NO_SHARES_TO_VOTE_WITH);
+
+        lock.buyout_id = buyout.id;
+        lock.shares += added_shares;
+        self.vote_locks(vault_id, &voter).set(&lock);
+        buyout.votes += &new_votes;
+
+        self.buyout_voted_event(vault_id, buyout.id, &voter, &new_votes);
+
+        if buyout.votes.clone() * 10_000u64 > vault.share_supply * BUYOUT_APPROVAL_BPS {
+            self.execute_buyout(vault_id, &buyout);
+        } else {
+            self.active_buyout(vault_id).set(&buyout);
+        }
+    }
+
+    // Take back locked shares, removing their votes from the open buyout
+    #[endpoint(withdrawVote)]
+    fn withdraw_vote(&self, vault_id: u32) {
+        let vault = self.require_vault(vault_id);
+        require!(vault.status != VaultStatus::BoughtOut, ERR_VAULT_BOUGHT_OUT_CLAIM_THE_PROCEEDS);
+        let voter = self.blockchain().get_caller();
+        require!(!self.vote_locks(vault_id, &voter).is_empty(), ERR_NO_LOCKED_SHARES);
+        let lock = self.vote_locks(vault_id, &voter).take();
+
+        if !self.active_buyout(vault_id).is_empty() {
+            self.active_buyout(vault_id).update(|buyout| {
+                if buyout.id == lock.buyout_id {
+                    buyout.votes -= &lock.shares;
+                }
+            });
+        }
+        let share_token = self.share_token(vault_id).get_token_id();
+        self.send().direct_esdt(&voter, &share_token, 0, &lock.shares);
+
+        self.vote_withdrawn_event(vault_id, lock.buyout_id, &voter, &lock.shares);
+    }
+
+    // Refund a buyout that did not pass before the end of its voting period
+    #[endpoint(withdrawBuyout)]
+    fn withdraw_buyout(&self, vault_id: u32) {
+        let vault = self.require_vault(vault_id);
+        require!(!self.active_buyout(vault_id).is_empty(), ERR_NO_OPEN_BUYOUT);
+        let buyout = self.active_buyout(vault_id).get();
+        require!(self.blockchain().get_caller() == buyout.bidder, ERR_ONLY_BIDDER_CAN_WITHDRAW);
+        require!(
+            self.blockchain().get_block_timestamp() > buyout.deadline,
+            ERR_VOTING_PERIOD_HAS_NOT_ENDED
+        );
+
+        self.active_buyout(vault_id).clear();
+        self.send().direct(&buyout.bidder, &vault.payment_token, 0, &buyout.amount);
+
+        self.buyout_withdrawn_event(vault_id, buyout.id, &buyout.bidder);
+    }
+
+    // Burn shares, paid to the call and/or locked as votes, for their part of the proceeds
+    #[payable("*")]
+    #[endpoint(claimProceeds)]
+    fn claim_proceeds(&self, vault_id: u32) -> BigUint {
+        let vault = self.require_vault(vault_id);
+        require!(vault.status == VaultStatus::BoughtOut, ERR_VAULT_HAS_NOT_BEEN_BOUGHT_OUT);
+
+        let holder = self.blockchain().get_caller();
+        let mut shares = self.collect_share_payments(vault_id);
+        if !self.vote_locks(vault_id, &holder).is_empty() {
+            shares += self.vote_locks(vault_id, &holder).take().shares;
+        }
+        require!(shares > 0, ERR_NO_SHARES_TO_CLAIM_WITH);
+
+        // Paid out of what is left, so rounding dust goes to the last claimer
+        let unclaimed_shares = self.unclaimed_shares(vault_id).get();
+        let unclaimed_proceeds = self.unclaimed_proceeds(vault_id).get();
+        let payout = &unclaimed_proceeds * &shares / &unclaimed_shares;
+        self.unclaimed_shares(vault_id).set(&unclaimed_shares - &shares);
+        self.unclaimed_proceeds(vault_id).set(&unclaimed_proceeds - &payout);
+
+        self.share_token(vault_id).burn(&shares);
+        if payout > 0 {
+            self.send().direct(&holder, &vault.payment_token, 0, &payout);
+        }
+
+        self.proceeds_claimed_event(vault_id, &holder, &shares, &payout);
+
+        payout
+    }
+
+    // Burn the whole share supply to take the wine out of the vault, refunding any open buyout
+    #[payable("*")]
+    #[endpoint(redeemWine)]
+    fn redeem_wine(&self, vault_id: u32) {
+        let mut vault = self.require_vault(vault_id);
+        require!(vault.status == VaultStatus::Active, ERR_VAULT_IS_NOT_ACTIVE);
+        let shares = self.collect_share_payments(vault_id);
+        require!(shares == vault.share_supply, ERR_THE_WHOLE_SHARE_SUPPLY_IS_REQUIRED);
+
+        // Nobody is left to vote on an open buyout
+        self.refund_open_buyout(vault_id, &vault.payment_token);
+        vault.status = VaultStatus::Redeemed;
+        self.vaults(vault_id).set(&vault);
+        self.share_token(vault_id).burn(&shares);
+        let holder = self.blockchain().get_caller();
+        self.send_wine(&holder, &vault);
+
+        self.wine_redeemed_event(vault_id, &holder);
+    }
+
+    // Internal helpers
+    // Hand the wine to the bidder and open the proceeds to shareholders
+    fn execute_buyout(&self, vault_id: u32, buyout: &Buyout<Self::Api>) {
+        let mut vault = self.vaults(vault_id).get();
+        vault.status = VaultStatus::BoughtOut;
+        self.vaults(vault_id).set(&vault);
+        self.active_buyout(vault_id).clear();
+        self.unclaimed_shares(vault_id).set(&vault.share_supply);
+        self.unclaimed_proceeds(vault_id).set(&buyout.amount);
+        self.send_wine(&buyout.bidder, &vault);
+
+        self.buyout_executed_event(vault_id, buyout.id, &buyout.bidder, &buyout.amount);
+    }
+
+    // Close the open buyout, if any, and return the offer to its bidder
+    fn refund_open_buyout(&self, vault_id: u32, payment_token: &EgldOrEsdtTokenIdentifier) {
+        if self.active_buyout(vault_id).is_empty() {
+            return;
+        }
+        let buyout = self.active_buyout(vault_id).take();
+        self.send().direct(&buyout.bidder, payment_token, 0, &buyout.amount);
+
+        self.buyout_refunded_event(vault_id, buyout.id, &buyout.bidder, &buyout.amount);
+    }
+
+    fn vote_lock_or_empty(&self, vault_id: u32, voter: &ManagedAddress) -> VoteLock<Self::Api> {
+        let mapper = self.vote_locks(vault_id, voter);
+        if mapper.is_empty() {
+            VoteLock {
+                buyout_id: 0,
+                shares: BigUint::zero(),
+            }
+        } else {
+            mapper.get()
+        }
+    }
+
+    // View functions
+    #[view(getActiveBuyout)]
+    fn get_active_buyout(&self, vault_id: u32) -> OptionalValue<Buyout<Self::Api>> {
+        if self.active_buyout(vault_id).is_empty() {
+            OptionalValue::None
+        } else {
+            OptionalValue::Some(self.active_buyout(vault_id).get())
+        }
+    }
+
+    #[view(getLockedShares)]
+    fn get_locked_shares(&self, vault_id: u32, voter: ManagedAddress) -> BigUint {
+        self.vote_lock_or_empty(vault_id, &voter).shares
+    }
+
+    // Proceeds that burning `shares` would pay out right now; 0 unless bought out
+    #[view(getClaimableProceeds)]
+    fn get_claimable_proceeds(&self, vault_id: u32, shares: BigUint) -> BigUint {
+        let unclaimed_shares = self.unclaimed_shares(vault_id).get();
+        if unclaimed_shares == 0 || shares > unclaimed_shares {
+            return BigUint::zero();
+        }
+        self.unclaimed_proceeds(vault_id).get() * shares / unclaimed_shares
+    }
+
+    // Storage mappers
+    #[storage_mapper("activeBuyout")]
+    fn active_buyout(&self, vault_id: u32) -> SingleValueMapper<Buyout<Self::Api>>;
+
+    #[storage_mapper("buyoutCount")]
+    fn buyout_count(&self, vault_id: u32) -> SingleValueMapper<u32>;
+
+    #[storage_mapper("voteLocks")]
+    fn vote_locks(
+        &self,
+        vault_id: u32,
+        voter: &ManagedAddress,
+    ) -> SingleValueMapper<VoteLock<Self::Api>>;
+
+    #[storage_mapper("unclaimedShares")]
+    fn unclaimed_shares(&self, vault_id: u32) -> SingleValueMapper<BigUint>;
+
+    #[storage_mapper("unclaimedProceeds")]
+    fn unclaimed_proceeds(&self, vault_id: u32) -> SingleValueMapper<BigUint>;
+
+    // Events
+    #[event("buyoutProposed")]
+    fn buyout_proposed_event(
+        &self,
+        #[indexed] vault_id: u32,
+        #[indexed] buyout_id: u32,
+        #[indexed] bidder: &ManagedAddress,
+        amount: &BigUint,
+    );
+
+    #[event("buyoutVoted")]
+    fn buyout_voted_event(
+        &self,
+        #[indexed] vault_id: u32,
+        #[indexed] buyout_id: u32,
+        #[indexed] voter: &ManagedAddress,
+        shares: &BigUint,
+    );
+
+    #[event("voteWithdrawn")]
+    fn vote_withdrawn_event(
+        &self,
+        #[indexed] vault_id: u32,
+        #[indexed] buyout_id: u32,
+        #[indexed] voter: &ManagedAddress,
+        shares: &BigUint,
+    );
+
+    #[event("buyoutWithdrawn")]
+    fn buyout_withdrawn_event(
+        &self,
+        #[indexed] vault_id: u32,
+        #[indexed] buyout_id: u32,
+        #[indexed] bidder: &ManagedAddress,
+    );
+
+    #[event("buyoutRefunded")]
+    fn buyout_refunded_event(
+        &self,
+        #[indexed] vault_id: u32,
+        #[indexed] buyout_id: u32,
+        #[indexed] bidder: &ManagedAddress,
+        amount: &BigUint,
+    );
+
+    #[event("buyoutExecuted")]
+    fn buyout_executed_event(
+        &self,
+        #[indexed] vault_id: u32,
+        #[indexed] buyout_id: u32,
+        #[indexed] bidder: &ManagedAddress,
+        amount: &BigUint,
+    );
+
+    #[event("proceedsClaimed")]
+    fn proceeds_claimed_event(
+        &self,
+        #[indexed] vault_id: u32,
+        #[indexed] holder: &ManagedAddress,
+        shares: &BigUint,
+        amount: &BigUint,
+    );
+
+    #[event("wineRedeemed")]
+    fn wine_redeemed_event(&self, #[indexed] vault_id: u32, #[indexed] holder: &ManagedAddress);
+}
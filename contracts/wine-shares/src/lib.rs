@@ -0,0 +1,28 @@
+#![no_std]
+
+use multiversx_sc::imports::*;
+use wine_common::errors::*;
+
+pub mod buyouts;
+pub mod vaults;
+
+pub use buyouts::{Buyout, VoteLock};
+pub use vaults::{Vault, VaultStatus};
+
+// Fractional ownership of wine NFTs. A vault locks a wine from the registry's collection and
+// issues a fixed supply of fungible shares against it; the wine leaves the vault through a
+// buyout, with proceeds shared pro-rata, or when one holder redeems the whole supply.
+#[multiversx_sc::contract]
+pub trait WineShares:
+    vaults::VaultsModule + buyouts::BuyoutsModule + wine_roles::RolesModule
+{
+    #[init]
+    fn init(&self, wine_collection: TokenIdentifier) {
+        require!(wine_collection.is_valid_esdt_identifier(), ERR_INVALID_WINE_COLLECTION);
+        self.wine_collection().set(&wine_collection);
+        self.vault_counter().set(1u32);
+    }
+
+    #[upgrade]
+    fn upgrade(&self) {}
+}
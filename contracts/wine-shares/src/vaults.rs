@@ -0,0 +1,216 @@
+use multiversx_sc::derive_imports::*;
+use multiversx_sc::imports::*;
+use wine_common::errors::*;
+use wine_roles::Role;
+
+// Shares are whole units
+pub const SHARE_DECIMALS: usize = 0;
+
+#[derive(TopEncode, TopDecode, NestedEncode, NestedDecode, TypeAbi, PartialEq, Debug, Clone, Copy)]
+pub enum VaultStatus {
+    // Waiting for the share token and the wine deposit
+    Pending,
+    // Wine locked, shares in circulation
+    Active,
+    // Sold through a buyout; shares are redeemed for the sale proceeds
+    BoughtOut,
+    // The whole share supply was burned and the wine handed out
+    Redeemed,
+}
+
+#[derive(TopEncode, TopDecode, TypeAbi, PartialEq, Debug)]
+pub struct Vault<M: ManagedTypeApi> {
+    pub curator: ManagedAddress<M>,
+    pub wine_token: TokenIdentifier<M>,
+    // 0 until the wine is deposited
+    pub wine_nonce: u64,
+    pub wine_amount: BigUint<M>,
+    pub share_supply: BigUint<M>,
+    // Token buyouts are paid in
+    pub payment_token: EgldOrEsdtTokenIdentifier<M>,
+    // Buyouts offering at least this much go through without a vote
+    pub reserve_price: BigUint<M>,
+    pub status: VaultStatus,
+    pub created_timestamp: u64,
+}
+
+// A vault is opened in two steps: `createVault` issues its share token, then `depositWine`
+// locks the wine and mints the whole share supply to the curator.
+#[multiversx_sc::module]
+pub trait VaultsModule: wine_roles::RolesModule {
+    // Only wines of this collection can be fractionalized
+    #[endpoint(setWineCollection)]
+    fn set_wine_collection(&self, wine_collection: TokenIdentifier) {
+        self.require_role(Role::Admin);
+        require!(wine_collection.is_valid_esdt_identifier(), ERR_INVALID_WINE_COLLECTION);
+        self.wine_collection().set(&wine_collection);
+    }
+
+    // Open a vault and issue its share token. Requires the issue cost in EGLD.
+    #[payable("EGLD")]
+    #[endpoint(createVault)]
+    fn create_vault(
+        &self,
+        share_supply: BigUint,
+        payment_token: EgldOrEsdtTokenIdentifier,
+        reserve_price: BigUint,
+        share_display_name: ManagedBuffer,
+        share_ticker: ManagedBuffer,
+    ) {
+        self.require_not_paused();
+        require!(share_supply > 0, ERR_SHARE_SUPPLY_MUST_BE_GREATER_THAN_0);
+        require!(payment_token.is_valid(), ERR_INVALID_PAYMENT_TOKEN);
+        require!(reserve_price > 0, ERR_RESERVE_PRICE_MUST_BE_GREATER_THAN_0);
+
+        let curator = self.blockchain().get_caller();
+        let vault_id = self.vault_counter().get();
+        self.vault_counter().set(vault_id + 1);
+        self.vaults(vault_id).set(&Vault {
+            curator: curator.clone(),
+            wine_token: self.wine_collection().get(),
+            wine_nonce: 0,
+            wine_amount: BigUint::zero(),
+            share_supply: share_supply.clone(),
+            payment_token,
+            reserve_price,
+            status: VaultStatus::Pending,
+            created_timestamp: self.blockchain().get_block_timestamp(),
+        });
+
+        self.vault_created_event(vault_id, &curator, &share_supply);
+
+        let issue_cost = self.call_value().egld_value().clone_value();
+        self.share_token(vault_id).issue_and_set_all_roles(
+            issue_cost,
+            share_display_name,
+            share_ticker,
+            SHARE_DECIMALS,
+            Some(self.callbacks().share_token_issued(vault_id)),
+        );
+    }
+
+    // Lock the wine in a vault whose share token is issued and mint the shares to the curator
+    #[payable("*")]
+    #[endpoint(depositWine)]
+    fn deposit_wine(&self, vault_id: u32) {
+        self.require_not_paused();
+        let mut vault = self.require_vault(vault_id);
+        let caller = self.blockchain().get_caller();
+        require!(caller == vault.curator, ERR_ONLY_CURATOR_CAN_DEPOSIT_THE_WINE);
+        require!(vault.status == VaultStatus::Pending, ERR_VAULT_IS_NOT_PENDING);
+        require!(
+            self.share_token(vault_id).get_token_state().is_set(),
+            ERR_SHARE_TOKEN_NOT_ISSUED
+        );
+
+        let payment = self.call_value().single_esdt();
+        require!(payment.token_identifier == vault.wine_token, ERR_INVALID_WINE_TOKEN);
+        require!(payment.token_nonce > 0 && payment.amount > 0, ERR_INVALID_WINE_PAYMENT);
+
+        vault.wine_nonce = payment.token_nonce;
+        vault.wine_amount = payment.amount.clone();
+        vault.status = VaultStatus::Active;
+        self.vaults(vault_id).set(&vault);
+        self.share_token(vault_id).mint_and_send(&caller, vault.share_supply.clone());
+
+        self.wine_deposited_event(vault_id, vault.wine_nonce, &vault.wine_amount);
+    }
+
+    // Internal helpers
+    fn require_vault(&self, vault_id: u32) -> Vault<Self::Api> {
+        require!(!self.vaults(vault_id).is_empty(), ERR_VAULT_DOES_NOT_EXIST);
+        self.vaults(vault_id).get()
+    }
+
+    // Sum of the share tokens paid to the current call; any other token is rejected
+    fn collect_share_payments(&self, vault_id: u32) -> BigUint {
+        let share_token = self.share_token(vault_id);
+        let mut shares = BigUint::zero();
+        for payment in self.call_value().all_esdt_transfers().iter() {
+            share_token.require_same_token(&payment.token_identifier);
+            shares += &payment.amount;
+        }
+        shares
+    }
+
+    fn send_wine(&self, to: &ManagedAddress, vault: &Vault<Self::Api>) {
+        self.send().direct_esdt(to, &vault.wine_token, vault.wine_nonce, &vault.wine_amount);
+    }
+
+    // On failure the issue cost is returned to the curator; the vault stays pending
+    #[callback]
+    fn share_token_issued(
+        &self,
+        vault_id: u32,
+        #[call_result] result: ManagedAsyncCallResult<TokenIdentifier>,
+    ) {
+        match result {
+            ManagedAsyncCallResult::Ok(token_id) => {
+                self.share_token(vault_id).set_token_id(token_id);
+            },
+            ManagedAsyncCallResult::Err(_) => {
+                self.share_token(vault_id).clear();
+                let refund = self.call_value().egld_value().clone_value();
+                if refund > 0 {
+                    self.send().direct_egld(&self.vaults(vault_id).get().curator, &refund);
+                }
+            },
+        }
+    }
+
+    // View functions
+    #[view(getWineCollection)]
+    fn get_wine_collection(&self) -> TokenIdentifier {
+        self.wine_collection().get()
+    }
+
+    #[view(getVault)]
+    fn get_vault(&self, vault_id: u32) -> Vault<Self::Api> {
+        self.require_vault(vault_id)
+    }
+
+    #[view(getVaultCount)]
+    fn get_vault_count(&self) -> u32 {
+        self.vault_counter().get() - 1
+    }
+
+    // Empty until the share token is issued
+    #[view(getShareToken)]
+    fn get_share_token(&self, vault_id: u32) -> OptionalValue<TokenIdentifier> {
+        if self.share_token(vault_id).get_token_state().is_set() {
+            OptionalValue::Some(self.share_token(vault_id).get_token_id())
+        } else {
+            OptionalValue::None
+        }
+    }
+
+    // Storage mappers
+    #[storage_mapper("wineCollection")]
+    fn wine_collection(&self) -> SingleValueMapper<TokenIdentifier>;
+
+    #[storage_mapper("vaultCounter")]
+    fn vault_counter(&self) -> SingleValueMapper<u32>;
+
+    #[storage_mapper("vaults")]
+    fn vaults(&self, vault_id: u32) -> SingleValueMapper<Vault<Self::Api>>;
+
+    #[storage_mapper("shareToken")]
+    fn share_token(&self, vault_id: u32) -> FungibleTokenMapper;
+
+    // Events
+    #[event("vaultCreated")]
+    fn vault_created_event(
+        &self,
+        #[indexed] vault_id: u32,
+        #[indexed] curator: &ManagedAddress,
+        share_supply: &BigUint,
+    );
+
+    #[event("wineDeposited")]
+    fn wine_deposited_event(
+        &self,
+        #[indexed] vault_id: u32,
+        #[indexed] wine_nonce: u64,
+        amount: &BigUint,
+    );
+}
@@ -0,0 +1,374 @@
+use multiversx_sc_scenario::api::StaticApi;
+use multiversx_sc_scenario::imports::*;
+use wine_common::errors::*;
+use wine_shares::buyouts::ProxyTrait as _;
+use wine_shares::vaults::ProxyTrait as _;
+use wine_shares::ProxyTrait as _;
+use wine_shares::{Buyout, VaultStatus};
+
+const SHARES_PATH_EXPR: &str = "mxsc:output/wine-shares.mxsc.json";
+const SHARES_ADDRESS_EXPR: &str = "sc:wine-shares";
+const OWNER_ADDRESS_EXPR: &str = "address:owner";
+const CURATOR_ADDRESS_EXPR: &str = "address:curator";
+const HOLDER_ADDRESS_EXPR: &str = "address:holder";
+const BIDDER_ADDRESS_EXPR: &str = "address:bidder";
+const GRIEFER_ADDRESS_EXPR: &str = "address:griefer";
+// Issues the share tokens. The VM runs its own mock of it, the account only has to exist.
+const ESDT_SYSTEM_SC_ADDRESS_EXPR: &str = "0x000000000000000000010000000000000000000000000000000000000002ffff";
+
+const WINE_NFT_TOKEN_ID_EXPR: &str = "str:WINE-abcdef";
+const SHARE_TOKEN_ID: &str = "SHARE-abcdef";
+const SHARE_TOKEN_ID_EXPR: &str = "str:SHARE-abcdef";
+
+const START_TIMESTAMP: u64 = 1_000;
+const ISSUE_COST: u64 = 50_000;
+const SHARE_SUPPLY: u64 = 100;
+const RESERVE_PRICE: u64 = 5_000;
+const VOTING_PERIOD: u64 = 7 * 24 * 60 * 60;
+
+type SharesContract = ContractInfo<wine_shares::Proxy<StaticApi>>;
+
+fn user_error(message: &str) -> TxExpect {
+    TxExpect::user_error(format!("str:{message}"))
+}
+
+struct SharesTestState {
+    world: ScenarioWorld,
+    shares: SharesContract,
+}
+
+impl SharesTestState {
+    // Vault 1 holds the curator's wine NFT 1 against 100 shares, of which the holder got 40
+    fn new() -> Self {
+        let mut world = ScenarioWorld::new();
+        world.register_contract(SHARES_PATH_EXPR, wine_shares::ContractBuilder);
+
+        let shares_code = world.code_expression(SHARES_PATH_EXPR);
+        world.set_state_step(
+            SetStateStep::new()
+                .block_timestamp(START_TIMESTAMP)
+                .put_account(OWNER_ADDRESS_EXPR, Account::new().nonce(1))
+                .put_account(
+                    CURATOR_ADDRESS_EXPR,
+                    Account::new()
+                        .nonce(1)
+                        .balance(ISSUE_COST)
+                        .esdt_nft_balance(WINE_NFT_TOKEN_ID_EXPR, 1, "1", Option::<Vec<u8>>::None),
+                )
+                .put_account(
+                    HOLDER_ADDRESS_EXPR,
+                    Account::new()
+                        .nonce(1)
+                        .esdt_nft_balance(WINE_NFT_TOKEN_ID_EXPR, 2, "1", Option::<Vec<u8>>::None),
+                )
+                .put_account(BIDDER_ADDRESS_EXPR, Account::new().nonce(1).balance("10000"))
+                .put_account(GRIEFER_ADDRESS_EXPR, Account::new().nonce(1).balance("10"))
+                .put_account(ESDT_SYSTEM_SC_ADDRESS_EXPR, Account::new().code(shares_code.clone()))
+                .new_address(OWNER_ADDRESS_EXPR, 1, SHARES_ADDRESS_EXPR)
+                .new_token_identifier(SHARE_TOKEN_ID),
+        );
+
+        let mut shares = SharesContract::new(SHARES_ADDRESS_EXPR);
+        world.sc_deploy(
+            ScDeployStep::new()
+                .from(OWNER_ADDRESS_EXPR)
+                .code(shares_code)
+                .call(shares.init(TokenIdentifier::from("WINE-abcdef"))),
+        );
+
+        world.sc_call(
+            ScCallStep::new()
+                .from(CURATOR_ADDRESS_EXPR)
+                .egld_value(ISSUE_COST)
+                .call(shares.create_vault(
+                    SHARE_SUPPLY,
+                    EgldOrEsdtTokenIdentifier::egld(),
+                    RESERVE_PRICE,
+                    ManagedBuffer::from("WineShares"),
+                    ManagedBuffer::from("SHARE"),
+                )),
+        );
+
+        // Only the curator locks the wine
+        world.sc_call(
+            ScCallStep::new()
+                .from(HOLDER_ADDRESS_EXPR)
+                .esdt_transfer(WINE_NFT_TOKEN_ID_EXPR, 2, "1")
+                .call(shares.deposit_wine(1u32))
+                .expect(user_error(ERR_ONLY_CURATOR_CAN_DEPOSIT_THE_WINE)),
+        );
+        world.sc_call(
+            ScCallStep::new()
+                .from(CURATOR_ADDRESS_EXPR)
+                .esdt_transfer(WINE_NFT_TOKEN_ID_EXPR, 1, "1")
+                .call(shares.deposit_wine(1u32)),
+        );
+        world.transfer_step(
+            TransferStep::new()
+                .from(CURATOR_ADDRESS_EXPR)
+                .to(HOLDER_ADDRESS_EXPR)
+                .esdt_transfer(SHARE_TOKEN_ID_EXPR, 0, "40"),
+        );
+
+        SharesTestState { world, shares }
+    }
+
+    fn set_block_timestamp(&mut self, timestamp: u64) {
+        self.world.set_state_step(SetStateStep::new().block_timestamp(timestamp));
+    }
+
+    fn propose_buyout(&mut self, amount: u64) {
+        self.world.sc_call(
+            ScCallStep::new()
+                .from(BIDDER_ADDRESS_EXPR)
+                .egld_value(amount)
+                .call(self.shares.propose_buyout(1u32)),
+        );
+    }
+
+    fn propose_dust_buyout(&mut self) {
+        self.world.sc_call(
+            ScCallStep::new()
+                .from(GRIEFER_ADDRESS_EXPR)
+                .egld_value(1u64)
+                .call(self.shares.propose_buyout(1u32)),
+        );
+    }
+
+    fn vote_buyout(&mut self, voter: &str, shares: &str) {
+        self.world.sc_call(
+            ScCallStep::new()
+                .from(voter)
+                .esdt_transfer(SHARE_TOKEN_ID_EXPR, 0, shares)
+                .call(self.shares.vote_buyout(1u32)),
+        );
+    }
+
+    fn claim_proceeds(&mut self, holder: &str, shares: &str) {
+        self.world.sc_call(
+            ScCallStep::new()
+                .from(holder)
+                .esdt_transfer(SHARE_TOKEN_ID_EXPR, 0, shares)
+                .call(self.shares.claim_proceeds(1u32)),
+        );
+    }
+
+    fn vault_status(&mut self) -> VaultStatus {
+        let vault: wine_shares::Vault<StaticApi> = self.world.quick_query(self.shares.get_vault(1u32));
+        vault.status
+    }
+}
+
+#[test]
+fn depositing_the_wine_mints_the_share_supply_to_the_curator() {
+    let mut state = SharesTestState::new();
+
+    assert_eq!(state.vault_status(), VaultStatus::Active);
+    state.world.check_state_step(
+        CheckStateStep::new()
+            .put_account(
+                SHARES_ADDRESS_EXPR,
+                CheckAccount::new().esdt_nft_balance_and_attributes(WINE_NFT_TOKEN_ID_EXPR, 1, "1", Option::<Vec<u8>>::None),
+            )
+            .put_account(CURATOR_ADDRESS_EXPR, CheckAccount::new().esdt_balance(SHARE_TOKEN_ID_EXPR, "60"))
+            .put_account(HOLDER_ADDRESS_EXPR, CheckAccount::new().esdt_balance(SHARE_TOKEN_ID_EXPR, "40")),
+    );
+
+    // The wine collection is an admin setting
+    state.world.sc_call(
+        ScCallStep::new()
+            .from(CURATOR_ADDRESS_EXPR)
+            .call(state.shares.set_wine_collection(TokenIdentifier::from("OTHER-abcdef")))
+            .expect(user_error(ERR_CALLER_DOES_NOT_HAVE_THE_REQUIRED_ROLE)),
+    );
+}
+
+#[test]
+fn buyout_at_the_reserve_price_pays_shareholders_pro_rata() {
+    let mut state = SharesTestState::new();
+    state.propose_buyout(RESERVE_PRICE);
+
+    assert_eq!(state.vault_status(), VaultStatus::BoughtOut);
+    state.world.check_state_step(
+        CheckStateStep::new().put_account(
+            BIDDER_ADDRESS_EXPR,
+            CheckAccount::new()
+                .balance("5000")
+                .esdt_nft_balance_and_attributes(WINE_NFT_TOKEN_ID_EXPR, 1, "1", Option::<Vec<u8>>::None),
+        ),
+    );
+
+    state.claim_proceeds(HOLDER_ADDRESS_EXPR, "40");
+    state.claim_proceeds(CURATOR_ADDRESS_EXPR, "60");
+    state.world.check_state_step(
+        CheckStateStep::new()
+            .put_account(
+                HOLDER_ADDRESS_EXPR,
+                CheckAccount::new().balance("2000").esdt_balance(SHARE_TOKEN_ID_EXPR, "0"),
+            )
+            .put_account(
+                CURATOR_ADDRESS_EXPR,
+                CheckAccount::new().balance("3000").esdt_balance(SHARE_TOKEN_ID_EXPR, "0"),
+            )
+            .put_account(SHARES_ADDRESS_EXPR, CheckAccount::new().balance("0")),
+    );
+
+    // Nothing is left to claim without shares, and the vault takes no further buyouts
+    state.world.sc_call(
+        ScCallStep::new()
+            .from(HOLDER_ADDRESS_EXPR)
+            .call(state.shares.claim_proceeds(1u32))
+            .expect(user_error(ERR_NO_SHARES_TO_CLAIM_WITH)),
+    );
+    state.world.sc_call(
+        ScCallStep::new()
+            .from(BIDDER_ADDRESS_EXPR)
+            .egld_value(RESERVE_PRICE)
+            .call(state.shares.propose_buyout(1u32))
+            .expect(user_error(ERR_VAULT_IS_NOT_ACTIVE)),
+    );
+}
+
+#[test]
+fn buyout_below_the_reserve_price_executes_on_a_majority_vote() {
+    let mut state = SharesTestState::new();
+    state.propose_buyout(1_000);
+    assert_eq!(state.vault_status(), VaultStatus::Active);
+
+    // 40 of 100 shares is not a majority yet
+    state.vote_buyout(HOLDER_ADDRESS_EXPR, "40");
+    let buyout: OptionalValue<Buyout<StaticApi>> = state.world.quick_query(state.shares.get_active_buyout(1u32));
+    assert_eq!(buyout.into_option().unwrap().votes, 40u64);
+    state.world.sc_call(
+        ScCallStep::new()
+            .from(HOLDER_ADDRESS_EXPR)
+            .call(state.shares.withdraw_buyout(1u32))
+            .expect(user_error(ERR_ONLY_BIDDER_CAN_WITHDRAW)),
+    );
+    state.world.sc_call(
+        ScCallStep::new()
+            .from(BIDDER_ADDRESS_EXPR)
+            .call(state.shares.withdraw_buyout(1u32))
+            .expect(user_error(ERR_VOTING_PERIOD_HAS_NOT_ENDED)),
+    );
+
+    state.vote_buyout(CURATOR_ADDRESS_EXPR, "60");
+    assert_eq!(state.vault_status(), VaultStatus::BoughtOut);
+
+    // Shares locked as votes are claimed against the proceeds without being sent again
+    state.world.sc_call(
+        ScCallStep::new()
+            .from(HOLDER_ADDRESS_EXPR)
+            .call(state.shares.withdraw_vote(1u32))
+            .expect(user_error(ERR_VAULT_BOUGHT_OUT_CLAIM_THE_PROCEEDS)),
+    );
+    state.world.sc_call(
+        ScCallStep::new()
+            .from(HOLDER_ADDRESS_EXPR)
+            .call(state.shares.claim_proceeds(1u32)),
+    );
+    state.world.check_state_step(
+        CheckStateStep::new().put_account(HOLDER_ADDRESS_EXPR, CheckAccount::new().balance("400")),
+    );
+}
+
+#[test]
+fn failed_buyout_is_refunded_and_votes_are_withdrawn() {
+    let mut state = SharesTestState::new();
+    state.propose_buyout(1_000);
+    state.vote_buyout(HOLDER_ADDRESS_EXPR, "40");
+
+    state.set_block_timestamp(START_TIMESTAMP + VOTING_PERIOD + 1);
+    state.world.sc_call(
+        ScCallStep::new()
+            .from(CURATOR_ADDRESS_EXPR)
+            .esdt_transfer(SHARE_TOKEN_ID_EXPR, 0, "60")
+            .call(state.shares.vote_buyout(1u32))
+            .expect(user_error(ERR_VOTING_PERIOD_HAS_ENDED)),
+    );
+    state.world.sc_call(
+        ScCallStep::new()
+            .from(BIDDER_ADDRESS_EXPR)
+            .call(state.shares.withdraw_buyout(1u32)),
+    );
+    state.world.sc_call(
+        ScCallStep::new()
+            .from(HOLDER_ADDRESS_EXPR)
+            .call(state.shares.withdraw_vote(1u32)),
+    );
+
+    assert_eq!(state.vault_status(), VaultStatus::Active);
+    state.world.check_state_step(
+        CheckStateStep::new()
+            .put_account(BIDDER_ADDRESS_EXPR, CheckAccount::new().balance("10000"))
+            .put_account(HOLDER_ADDRESS_EXPR, CheckAccount::new().esdt_balance(SHARE_TOKEN_ID_EXPR, "40")),
+    );
+}
+
+#[test]
+fn a_higher_offer_replaces_a_dust_buyout() {
+    let mut state = SharesTestState::new();
+    state.propose_dust_buyout();
+
+    state.world.sc_call(
+        ScCallStep::new()
+            .from(BIDDER_ADDRESS_EXPR)
+            .egld_value(1u64)
+            .call(state.shares.propose_buyout(1u32))
+            .expect(user_error(ERR_OFFER_MUST_EXCEED_THE_OPEN_BUYOUT)),
+    );
+    state.propose_buyout(1_000);
+
+    let buyout: OptionalValue<Buyout<StaticApi>> = state.world.quick_query(state.shares.get_active_buyout(1u32));
+    let buyout = buyout.into_option().unwrap();
+    assert_eq!(buyout.id, 2);
+    assert_eq!(buyout.amount, 1_000u64);
+    state.world.check_state_step(
+        CheckStateStep::new()
+            .put_account(GRIEFER_ADDRESS_EXPR, CheckAccount::new().balance("10"))
+            .put_account(BIDDER_ADDRESS_EXPR, CheckAccount::new().balance("9000")),
+    );
+
+    // The real offer passes its vote without waiting out the dust one
+    state.vote_buyout(CURATOR_ADDRESS_EXPR, "60");
+    assert_eq!(state.vault_status(), VaultStatus::BoughtOut);
+}
+
+#[test]
+fn the_whole_share_supply_redeems_the_wine() {
+    let mut state = SharesTestState::new();
+    state.propose_dust_buyout();
+
+    state.world.sc_call(
+        ScCallStep::new()
+            .from(CURATOR_ADDRESS_EXPR)
+            .esdt_transfer(SHARE_TOKEN_ID_EXPR, 0, "60")
+            .call(state.shares.redeem_wine(1u32))
+            .expect(user_error(ERR_THE_WHOLE_SHARE_SUPPLY_IS_REQUIRED)),
+    );
+
+    state.world.transfer_step(
+        TransferStep::new()
+            .from(HOLDER_ADDRESS_EXPR)
+            .to(CURATOR_ADDRESS_EXPR)
+            .esdt_transfer(SHARE_TOKEN_ID_EXPR, 0, "40"),
+    );
+    state.world.sc_call(
+        ScCallStep::new()
+            .from(CURATOR_ADDRESS_EXPR)
+            .esdt_transfer(SHARE_TOKEN_ID_EXPR, 0, "100")
+            .call(state.shares.redeem_wine(1u32)),
+    );
+
+    assert_eq!(state.vault_status(), VaultStatus::Redeemed);
+    state.world.check_state_step(
+        CheckStateStep::new()
+            .put_account(
+                CURATOR_ADDRESS_EXPR,
+                CheckAccount::new()
+                    .esdt_balance(SHARE_TOKEN_ID_EXPR, "0")
+                    .esdt_nft_balance_and_attributes(WINE_NFT_TOKEN_ID_EXPR, 1, "1", Option::<Vec<u8>>::None),
+            )
+            .put_account(GRIEFER_ADDRESS_EXPR, CheckAccount::new().balance("10")),
+    );
+}
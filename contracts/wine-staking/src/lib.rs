@@ -0,0 +1,34 @@
+#![no_std]
+
+use multiversx_sc::imports::*;
+use wine_common::errors::*;
+
+pub mod rewards;
+pub mod staking;
+
+pub use staking::{StakePosition, UnbondingEntry};
+
+// Staking of the WINE utility token for a share of marketplace fees. Fees are fed in by the
+// marketplace or its fee managers and split over the stake active at the time; new stake
+// starts earning from the next epoch, and unstaked tokens wait out an unbonding period.
+#[multiversx_sc::contract]
+pub trait WineStaking:
+    staking::StakingModule + rewards::RewardsModule + wine_roles::RolesModule
+{
+    #[init]
+    fn init(
+        &self,
+        staking_token: TokenIdentifier,
+        reward_token: EgldOrEsdtTokenIdentifier,
+        unbonding_epochs: u64,
+    ) {
+        require!(staking_token.is_valid_esdt_identifier(), ERR_INVALID_STAKING_TOKEN);
+        require!(reward_token.is_valid(), ERR_INVALID_REWARD_TOKEN);
+        self.staking_token().set(&staking_token);
+        self.reward_token().set(&reward_token);
+        self.unbonding_epochs().set(unbonding_epochs);
+    }
+
+    #[upgrade]
+    fn upgrade(&self) {}
+}
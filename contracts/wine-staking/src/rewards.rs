@@ -0,0 +1,116 @@
+use multiversx_sc::imports::*;
+use wine_roles::Role;
+
+use crate::staking::REWARD_PRECISION;
+use wine_common::errors::*;
+
+// Fee feeds and reward claims. Feeds arriving while nothing is actively staked are held back
+// and distributed with the next feed.
+#[multiversx_sc::module]
+pub trait RewardsModule: wine_roles::RolesModule + crate::staking::StakingModule {
+    // Marketplaces allowed to feed rewards, next to fee managers
+    #[endpoint(addRewardFeeder)]
+    fn add_reward_feeder(&self, feeder: ManagedAddress) {
+        self.require_role(Role::Admin);
+        require!(self.reward_feeders().insert(feeder), ERR_ALREADY_A_REWARD_FEEDER);
+    }
+
+    #[endpoint(removeRewardFeeder)]
+    fn remove_reward_feeder(&self, feeder: ManagedAddress) {
+        self.require_role(Role::Admin);
+        require!(self.reward_feeders().swap_remove(&feeder), ERR_NOT_A_REWARD_FEEDER);
+    }
+
+    // Feed marketplace fees for this epoch, split over the currently active stake
+    #[payable("*")]
+    #[endpoint(depositRewards)]
+    fn deposit_rewards(&self) {
+        let caller = self.blockchain().get_caller();
+        require!(
+            self.reward_feeders().contains(&caller) || self.has_role(Role::FeeManager, &caller),
+            ERR_NOT_AUTHORIZED_TO_FEED_REWARDS
+        );
+        let payment = self.call_value().egld_or_single_esdt();
+        require!(payment.token_identifier == self.reward_token().get(), ERR_INVALID_REWARD_TOKEN);
+        require!(payment.amount > 0, ERR_AMOUNT_MUST_BE_GREATER_THAN_0);
+
+        self.activate_pending_stake();
+        let epoch = self.blockchain().get_block_epoch();
+        self.epoch_rewards(epoch).update(|rewards| *rewards += &payment.amount);
+
+        let to_distribute = payment.amount + self.undistributed_rewards().take();
+        let total_active = self.total_active_stake().get();
+        if total_active == 0 {
+            self.undistributed_rewards().set(&to_distribute);
+        } else {
+            self.reward_per_share().update(|reward_per_share| {
+                *reward_per_share += &to_distribute * REWARD_PRECISION / &total_active
+            });
+        }
+
+        self.rewards_deposited_event(&caller, epoch, &to_distribute);
+    }
+
+    #[endpoint(claimRewards)]
+    fn claim_rewards(&self) -> BigUint {
+        let staker = self.blockchain().get_caller();
+        self.activate_pending_stake();
+        let mut position = self.current_position(&staker);
+        let rewards = core::mem::replace(&mut position.rewards, BigUint::zero());
+        require!(rewards > 0, ERR_NO_REWARDS_TO_CLAIM);
+        self.stake_positions(&staker).set(&position);
+
+        self.send().direct(&staker, &self.reward_token().get(), 0, &rewards);
+
+        self.rewards_claimed_event(&staker, &rewards);
+
+        rewards
+    }
+
+    // View functions
+    #[view(getRewardToken)]
+    fn get_reward_token(&self) -> EgldOrEsdtTokenIdentifier {
+        self.reward_token().get()
+    }
+
+    #[view(getRewardFeeders)]
+    fn get_reward_feeders(&self) -> MultiValueEncoded<ManagedAddress> {
+        self.reward_feeders().iter().collect()
+    }
+
+    // Total fed during `epoch`
+    #[view(getEpochRewards)]
+    fn get_epoch_rewards(&self, epoch: u64) -> BigUint {
+        self.epoch_rewards(epoch).get()
+    }
+
+    #[view(getUndistributedRewards)]
+    fn get_undistributed_rewards(&self) -> BigUint {
+        self.undistributed_rewards().get()
+    }
+
+    // Storage mappers
+    #[storage_mapper("rewardToken")]
+    fn reward_token(&self) -> SingleValueMapper<EgldOrEsdtTokenIdentifier>;
+
+    #[storage_mapper("rewardFeeders")]
+    fn reward_feeders(&self) -> UnorderedSetMapper<ManagedAddress>;
+
+    #[storage_mapper("epochRewards")]
+    fn epoch_rewards(&self, epoch: u64) -> SingleValueMapper<BigUint>;
+
+    #[storage_mapper("undistributedRewards")]
+    fn undistributed_rewards(&self) -> SingleValueMapper<BigUint>;
+
+    // Events
+    #[event("rewardsDeposited")]
+    fn rewards_deposited_event(
+        &self,
+        #[indexed] feeder: &ManagedAddress,
+        #[indexed] epoch: u64,
+        amount: &BigUint,
+    );
+
+    #[event("rewardsClaimed")]
+    fn rewards_claimed_event(&self, #[indexed] staker: &ManagedAddress, amount: &BigUint);
+}
@@ -0,0 +1,233 @@
+use multiversx_sc::derive_imports::*;
+use multiversx_sc::imports::*;
+use wine_common::errors::*;
+use wine_roles::Role;
+
+// Scale of the reward-per-share accumulator
+pub const REWARD_PRECISION: u64 = 1_000_000_000_000_000_000;
+// Bounds the unbonding list walked by withdrawUnbonded
+pub const MAX_UNBONDING_ENTRIES: usize = 10;
+
+// A staker's position. Stake added in `pending_epoch` only starts earning in the next epoch.
+#[derive(TopEncode, TopDecode, TypeAbi, PartialEq, Debug)]
+pub struct StakePosition<M: ManagedTypeApi> {
+    pub active: BigUint<M>,
+    pub pending: BigUint<M>,
+    pub pending_epoch: u64,
+    // Accumulator value up to which `rewards` has been credited
+    pub reward_per_share_paid: BigUint<M>,
+    // Credited and not yet claimed
+    pub rewards: BigUint<M>,
+}
+
+#[derive(TopEncode, TopDecode, NestedEncode, NestedDecode, TypeAbi, PartialEq, Debug)]
+pub struct UnbondingEntry<M: ManagedTypeApi> {
+    pub amount: BigUint<M>,
+    pub unlock_epoch: u64,
+}
+
+// Stake accounting. Rewards accrue through a reward-per-share accumulator. Pending stake of
+// all stakers shares one epoch, since any call in a later epoch activates it first; the
+// accumulator value at that moment is kept so each position can activate lazily.
+#[multiversx_sc::module]
+pub trait StakingModule: wine_roles::RolesModule {
+    #[endpoint(setUnbondingEpochs)]
+    fn set_unbonding_epochs(&self, unbonding_epochs: u64) {
+        self.require_role(Role::Admin);
+        self.unbonding_epochs().set(unbonding_epochs);
+    }
+
+    // Stake the paid staking tokens; they earn from the next epoch on
+    #[payable("*")]
+    #[endpoint(stake)]
+    fn stake(&self) {
+        self.require_not_paused();
+        let payment = self.call_value().single_esdt();
+        require!(payment.token_identifier == self.staking_token().get(), ERR_INVALID_STAKING_TOKEN);
+        require!(payment.amount > 0, ERR_AMOUNT_MUST_BE_GREATER_THAN_0);
+
+        let staker = self.blockchain().get_caller();
+        let epoch = self.blockchain().get_block_epoch();
+        self.activate_pending_stake();
+        let mut position = self.current_position(&staker);
+        position.pending += &payment.amount;
+        position.pending_epoch = epoch;
+        self.stake_positions(&staker).set(&position);
+        self.pending_stake().update(|pending| *pending += &payment.amount);
+        self.pending_stake_epoch().set(epoch);
+
+        self.staked_event(&staker, &payment.amount);
+    }
+
+    // Start unbonding `amount`, taken from pending stake first. Earned rewards stay claimable.
+    #[endpoint(unstake)]
+    fn unstake(&self, amount: BigUint) {
+        require!(amount > 0, ERR_AMOUNT_MUST_BE_GREATER_THAN_0);
+        let staker = self.blockchain().get_caller();
+        self.activate_pending_stake();
+        let mut position = self.current_position(&staker);
+        require!(amount <= &position.active + &position.pending, ERR_NOT_ENOUGH_STAKE);
+        let mut unbonding = self.unbonding(&staker);
+        require!(unbonding.len() < MAX_UNBONDING_ENTRIES, ERR_TOO_MANY_UNBONDING_ENTRIES);
+
+        let from_pending = core::cmp::min(amount.clone(), position.pending.clone());
+        let from_active = &amount - &from_pending;
+        position.pending -= &from_pending;
+        position.active -= &from_active;
+        self.stake_positions(&staker).set(&position);
+        self.pending_stake().update(|pending| *pending -= &from_pending);
+        self.total_active_stake().update(|total| *total -= &from_active);
+
+        let unlock_epoch = self.blockchain().get_block_epoch() + self.unbonding_epochs().get();
+        unbonding.push(&UnbondingEntry {
+            amount: amount.clone(),
+            unlock_epoch,
+        });
+
+        self.unstaked_event(&staker, &amount, unlock_epoch);
+    }
+
+    // Pay out every unbonding entry whose unlock epoch has been reached
+    #[endpoint(withdrawUnbonded)]
+    fn withdraw_unbonded(&self) -> BigUint {
+        let staker = self.blockchain().get_caller();
+        let epoch = self.blockchain().get_block_epoch();
+        let mut unbonding = self.unbonding(&staker);
+        let mut amount = BigUint::zero();
+        let mut index = 1;
+        while index <= unbonding.len() {
+            let entry = unbonding.get(index);
+            if entry.unlock_epoch <= epoch {
+                amount += entry.amount;
+                unbonding.swap_remove(index);
+            } else {
+                index += 1;
+            }
+        }
+        require!(amount > 0, ERR_NOTHING_TO_WITHDRAW);
+
+        self.send().direct_esdt(&staker, &self.staking_token().get(), 0, &amount);
+
+        self.unbonded_withdrawn_event(&staker, &amount);
+
+        amount
+    }
+
+    // Internal helpers
+    // Move the pending stake of an earlier epoch into the active stake
+    fn activate_pending_stake(&self) {
+        let pending = self.pending_stake().get();
+        let pending_epoch = self.pending_stake_epoch().get();
+        if pending > 0 && self.blockchain().get_block_epoch() > pending_epoch {
+            self.activation_reward_per_share(pending_epoch).set(self.reward_per_share().get());
+            self.total_active_stake().update(|total| *total += &pending);
+            self.pending_stake().clear();
+        }
+    }
+
+    // The stored position with its pending stake activated and rewards credited up to now
+    fn current_position(&self, staker: &ManagedAddress) -> StakePosition<Self::Api> {
+        let mut position = if self.stake_positions(staker).is_empty() {
+            StakePosition {
+                active: BigUint::zero(),
+                pending: BigUint::zero(),
+                pending_epoch: 0,
+                reward_per_share_paid: BigUint::zero(),
+                rewards: BigUint::zero(),
+            }
+        } else {
+            self.stake_positions(staker).get()
+        };
+        let reward_per_share = self.reward_per_share().get();
+
+        if position.pending > 0 && self.blockchain().get_block_epoch() > position.pending_epoch {
+            // Not activated yet if nothing happened since the pending epoch ended. The stored
+            // value cannot tell, since an accumulator of zero leaves the mapper empty.
+            let not_activated = self.pending_stake().get() > 0
+                && self.pending_stake_epoch().get() == position.pending_epoch;
+            let activated_at = if not_activated {
+                reward_per_share.clone()
+            } else {
+                self.activation_reward_per_share(position.pending_epoch).get()
+            };
+            position.rewards +=
+                &position.active * &(&activated_at - &position.reward_per_share_paid) / REWARD_PRECISION;
+            position.reward_per_share_paid = activated_at;
+            position.active += &position.pending;
+            position.pending = BigUint::zero();
+        }
+        position.rewards +=
+            &position.active * &(&reward_per_share - &position.reward_per_share_paid) / REWARD_PRECISION;
+        position.reward_per_share_paid = reward_per_share;
+
+        position
+    }
+
+    // View functions
+    #[view(getStakingToken)]
+    fn get_staking_token(&self) -> TokenIdentifier {
+        self.staking_token().get()
+    }
+
+    #[view(getUnbondingEpochs)]
+    fn get_unbonding_epochs(&self) -> u64 {
+        self.unbonding_epochs().get()
+    }
+
+    // Rewards are credited up to now
+    #[view(getStakePosition)]
+    fn get_stake_position(&self, staker: ManagedAddress) -> StakePosition<Self::Api> {
+        self.current_position(&staker)
+    }
+
+    #[view(getUnbonding)]
+    fn get_unbonding(&self, staker: ManagedAddress) -> MultiValueEncoded<UnbondingEntry<Self::Api>> {
+        self.unbonding(&staker).iter().collect()
+    }
+
+    // Active and pending stake
+    #[view(getTotalStaked)]
+    fn get_total_staked(&self) -> BigUint {
+        self.total_active_stake().get() + self.pending_stake().get()
+    }
+
+    // Storage mappers
+    #[storage_mapper("stakingToken")]
+    fn staking_token(&self) -> SingleValueMapper<TokenIdentifier>;
+
+    #[storage_mapper("unbondingEpochs")]
+    fn unbonding_epochs(&self) -> SingleValueMapper<u64>;
+
+    #[storage_mapper("stakePositions")]
+    fn stake_positions(&self, staker: &ManagedAddress) -> SingleValueMapper<StakePosition<Self::Api>>;
+
+    #[storage_mapper("unbonding")]
+    fn unbonding(&self, staker: &ManagedAddress) -> VecMapper<UnbondingEntry<Self::Api>>;
+
+    #[storage_mapper("totalActiveStake")]
+    fn total_active_stake(&self) -> SingleValueMapper<BigUint>;
+
+    #[storage_mapper("pendingStake")]
+    fn pending_stake(&self) -> SingleValueMapper<BigUint>;
+
+    #[storage_mapper("pendingStakeEpoch")]
+    fn pending_stake_epoch(&self) -> SingleValueMapper<u64>;
+
+    // Rewards per unit of active stake since deployment, scaled by REWARD_PRECISION
+    #[storage_mapper("rewardPerShare")]
+    fn reward_per_share(&self) -> SingleValueMapper<BigUint>;
+
+    // Accumulator value when the stake pending in `epoch` became active
+    #[storage_mapper("activationRewardPerShare")]
+    fn activation_reward_per_share(&self, epoch: u64) -> SingleValueMapper<BigUint>;
+
+    // Events
+    #[event("staked")]
+    fn staked_event(&self, #[indexed] staker: &ManagedAddress, amount: &BigUint);
+
+    #[event("unstaked")]
+    fn unstaked_event(&self, #[indexed] staker: &ManagedAddress, amount: &BigUint, unlock_epoch: u64);
+
+    #[event("unbondedWithdrawn")]
+    fn unbonded_withdrawn_event(&self, #[indexed] staker: &ManagedAddress, amount: &BigUint);
+}
@@ -0,0 +1,255 @@
+use multiversx_sc_scenario::api::StaticApi;
+use multiversx_sc_scenario::imports::*;
+use wine_common::errors::*;
+use wine_roles::ProxyTrait as _;
+use wine_roles::Role;
+use wine_staking::rewards::ProxyTrait as _;
+use wine_staking::staking::ProxyTrait as _;
+use wine_staking::ProxyTrait as _;
+use wine_staking::{StakePosition, UnbondingEntry};
+
+const STAKING_PATH_EXPR: &str = "mxsc:output/wine-staking.mxsc.json";
+const STAKING_ADDRESS_EXPR: &str = "sc:wine-staking";
+const OWNER_ADDRESS_EXPR: &str = "address:owner";
+const FEEDER_ADDRESS_EXPR: &str = "address:feeder";
+const ALICE_ADDRESS_EXPR: &str = "address:alice";
+const BOB_ADDRESS_EXPR: &str = "address:bob";
+
+const STAKE_TOKEN_ID_EXPR: &str = "str:STAKE-abcdef";
+
+const UNBONDING_EPOCHS: u64 = 2;
+
+type StakingContract = ContractInfo<wine_staking::Proxy<StaticApi>>;
+
+fn user_error(message: &str) -> TxExpect {
+    TxExpect::user_error(format!("str:{message}"))
+}
+
+struct StakingTestState {
+    world: ScenarioWorld,
+    staking: StakingContract,
+}
+
+impl StakingTestState {
+    // Staking of STAKE for EGLD fees, starting in epoch 1 with the feeder allowed to feed
+    fn new() -> Self {
+        let mut world = ScenarioWorld::new();
+        world.register_contract(STAKING_PATH_EXPR, wine_staking::ContractBuilder);
+
+        world.set_state_step(
+            SetStateStep::new()
+                .block_epoch(1)
+                .put_account(OWNER_ADDRESS_EXPR, Account::new().nonce(1).balance("1000"))
+                .put_account(FEEDER_ADDRESS_EXPR, Account::new().nonce(1).balance("2000"))
+                .put_account(ALICE_ADDRESS_EXPR, Account::new().nonce(1).esdt_balance(STAKE_TOKEN_ID_EXPR, "100"))
+                .put_account(BOB_ADDRESS_EXPR, Account::new().nonce(1).esdt_balance(STAKE_TOKEN_ID_EXPR, "300"))
+                .new_address(OWNER_ADDRESS_EXPR, 1, STAKING_ADDRESS_EXPR),
+        );
+
+        let mut staking = StakingContract::new(STAKING_ADDRESS_EXPR);
+        let staking_code = world.code_expression(STAKING_PATH_EXPR);
+        world.sc_deploy(
+            ScDeployStep::new()
+                .from(OWNER_ADDRESS_EXPR)
+                .code(staking_code)
+                .call(staking.init(
+                    TokenIdentifier::from("STAKE-abcdef"),
+                    EgldOrEsdtTokenIdentifier::egld(),
+                    UNBONDING_EPOCHS,
+                )),
+        );
+
+        let feeder = AddressValue::from(FEEDER_ADDRESS_EXPR).to_address();
+        world.sc_call(
+            ScCallStep::new()
+                .from(ALICE_ADDRESS_EXPR)
+                .call(staking.add_reward_feeder(managed_address!(&feeder)))
+                .expect(user_error(ERR_CALLER_DOES_NOT_HAVE_THE_REQUIRED_ROLE)),
+        );
+        world.sc_call(
+            ScCallStep::new()
+                .from(OWNER_ADDRESS_EXPR)
+                .call(staking.add_reward_feeder(managed_address!(&feeder))),
+        );
+
+        StakingTestState { world, staking }
+    }
+
+    fn set_block_epoch(&mut self, epoch: u64) {
+        self.world.set_state_step(SetStateStep::new().block_epoch(epoch));
+    }
+
+    fn stake(&mut self, staker: &str, amount: &str) {
+        self.world.sc_call(
+            ScCallStep::new()
+                .from(staker)
+                .esdt_transfer(STAKE_TOKEN_ID_EXPR, 0, amount)
+                .call(self.staking.stake()),
+        );
+    }
+
+    fn deposit_rewards(&mut self, amount: u64) {
+        self.world.sc_call(
+            ScCallStep::new()
+                .from(FEEDER_ADDRESS_EXPR)
+                .egld_value(amount)
+                .call(self.staking.deposit_rewards()),
+        );
+    }
+
+    fn stake_position(&mut self, staker: &str) -> StakePosition<StaticApi> {
+        let staker = AddressValue::from(staker).to_address();
+        self.world.quick_query(self.staking.get_stake_position(managed_address!(&staker)))
+    }
+}
+
+#[test]
+fn rewards_are_split_over_the_stake_active_when_they_are_fed() {
+    let mut state = StakingTestState::new();
+    state.stake(ALICE_ADDRESS_EXPR, "100");
+
+    // Nothing is active yet, so the first feed waits for the next one
+    state.deposit_rewards(1_000);
+    let undistributed: BigUint<StaticApi> = state.world.quick_query(state.staking.get_undistributed_rewards());
+    assert_eq!(undistributed, 1_000u64);
+
+    state.set_block_epoch(2);
+    state.stake(BOB_ADDRESS_EXPR, "300");
+    state.deposit_rewards(400);
+    state.set_block_epoch(3);
+    state.deposit_rewards(400);
+
+    assert_eq!(state.stake_position(ALICE_ADDRESS_EXPR).rewards, 1_500u64);
+    assert_eq!(state.stake_position(BOB_ADDRESS_EXPR).rewards, 300u64);
+    let total_staked: BigUint<StaticApi> = state.world.quick_query(state.staking.get_total_staked());
+    assert_eq!(total_staked, 400u64);
+
+    for staker in [ALICE_ADDRESS_EXPR, BOB_ADDRESS_EXPR] {
+        state.world.sc_call(ScCallStep::new().from(staker).call(state.staking.claim_rewards()));
+    }
+    state.world.check_state_step(
+        CheckStateStep::new()
+            .put_account(ALICE_ADDRESS_EXPR, CheckAccount::new().balance("1500"))
+            .put_account(BOB_ADDRESS_EXPR, CheckAccount::new().balance("300"))
+            .put_account(
+                STAKING_ADDRESS_EXPR,
+                CheckAccount::new().balance("0").esdt_balance(STAKE_TOKEN_ID_EXPR, "400"),
+            ),
+    );
+    state.world.sc_call(
+        ScCallStep::new()
+            .from(ALICE_ADDRESS_EXPR)
+            .call(state.staking.claim_rewards())
+            .expect(user_error(ERR_NO_REWARDS_TO_CLAIM)),
+    );
+}
+
+#[test]
+fn only_feeders_and_fee_managers_feed_rewards() {
+    let mut state = StakingTestState::new();
+    state.stake(ALICE_ADDRESS_EXPR, "100");
+    state.set_block_epoch(2);
+
+    state.world.sc_call(
+        ScCallStep::new()
+            .from(BOB_ADDRESS_EXPR)
+            .egld_value(0u64)
+            .call(state.staking.deposit_rewards())
+            .expect(user_error(ERR_NOT_AUTHORIZED_TO_FEED_REWARDS)),
+    );
+
+    let bob = AddressValue::from(BOB_ADDRESS_EXPR).to_address();
+    state.world.sc_call(
+        ScCallStep::new()
+            .from(OWNER_ADDRESS_EXPR)
+            .call(state.staking.grant_role(Role::FeeManager, managed_address!(&bob))),
+    );
+    state.world.transfer_step(
+        TransferStep::new().from(OWNER_ADDRESS_EXPR).to(BOB_ADDRESS_EXPR).egld_value("1000"),
+    );
+    state.world.sc_call(
+        ScCallStep::new()
+            .from(BOB_ADDRESS_EXPR)
+            .egld_value(1_000u64)
+            .call(state.staking.deposit_rewards()),
+    );
+    assert_eq!(state.stake_position(ALICE_ADDRESS_EXPR).rewards, 1_000u64);
+
+    // A removed feeder is turned away
+    let feeder = AddressValue::from(FEEDER_ADDRESS_EXPR).to_address();
+    state.world.sc_call(
+        ScCallStep::new()
+            .from(OWNER_ADDRESS_EXPR)
+            .call(state.staking.remove_reward_feeder(managed_address!(&feeder))),
+    );
+    state.world.sc_call(
+        ScCallStep::new()
+            .from(FEEDER_ADDRESS_EXPR)
+            .egld_value(1_000u64)
+            .call(state.staking.deposit_rewards())
+            .expect(user_error(ERR_NOT_AUTHORIZED_TO_FEED_REWARDS)),
+    );
+}
+
+#[test]
+fn unstaked_tokens_are_withdrawn_after_the_unbonding_period() {
+    let mut state = StakingTestState::new();
+    state.stake(ALICE_ADDRESS_EXPR, "100");
+    state.set_block_epoch(2);
+
+    state.world.sc_call(
+        ScCallStep::new()
+            .from(ALICE_ADDRESS_EXPR)
+            .call(state.staking.unstake(101u64))
+            .expect(user_error(ERR_NOT_ENOUGH_STAKE)),
+    );
+    state.world.sc_call(ScCallStep::new().from(ALICE_ADDRESS_EXPR).call(state.staking.unstake(40u64)));
+
+    let alice = AddressValue::from(ALICE_ADDRESS_EXPR).to_address();
+    let unbonding: MultiValueVec<UnbondingEntry<StaticApi>> =
+        state.world.quick_query(state.staking.get_unbonding(managed_address!(&alice)));
+    let unbonding = unbonding.into_vec();
+    assert_eq!(unbonding.len(), 1);
+    assert_eq!(unbonding[0].unlock_epoch, 2 + UNBONDING_EPOCHS);
+
+    state.set_block_epoch(3);
+    state.world.sc_call(
+        ScCallStep::new()
+            .from(ALICE_ADDRESS_EXPR)
+            .call(state.staking.withdraw_unbonded())
+            .expect(user_error(ERR_NOTHING_TO_WITHDRAW)),
+    );
+
+    state.set_block_epoch(2 + UNBONDING_EPOCHS);
+    state.world.sc_call(ScCallStep::new().from(ALICE_ADDRESS_EXPR).call(state.staking.withdraw_unbonded()));
+    assert_eq!(state.stake_position(ALICE_ADDRESS_EXPR).active, 60u64);
+    state.world.check_state_step(
+        CheckStateStep::new()
+            .put_account(ALICE_ADDRESS_EXPR, CheckAccount::new().esdt_balance(STAKE_TOKEN_ID_EXPR, "40"))
+            .put_account(STAKING_ADDRESS_EXPR, CheckAccount::new().esdt_balance(STAKE_TOKEN_ID_EXPR, "60")),
+    );
+}
+
+#[test]
+fn staking_is_rejected_while_paused() {
+    let mut state = StakingTestState::new();
+
+    state.world.sc_call(
+        ScCallStep::new()
+            .from(ALICE_ADDRESS_EXPR)
+            .call(state.staking.pause())
+            .expect(user_error(ERR_CALLER_DOES_NOT_HAVE_THE_REQUIRED_ROLE)),
+    );
+    state.world.sc_call(ScCallStep::new().from(OWNER_ADDRESS_EXPR).call(state.staking.pause()));
+    state.world.sc_call(
+        ScCallStep::new()
+            .from(ALICE_ADDRESS_EXPR)
+            .esdt_transfer(STAKE_TOKEN_ID_EXPR, 0, "100")
+            .call(state.staking.stake())
+            .expect(user_error(ERR_CONTRACT_IS_PAUSED)),
+    );
+
+    state.world.sc_call(ScCallStep::new().from(OWNER_ADDRESS_EXPR).call(state.staking.unpause()));
+    state.stake(ALICE_ADDRESS_EXPR, "100");
+    assert_eq!(state.stake_position(ALICE_ADDRESS_EXPR).pending, 100u64);
+}
@@ -0,0 +1,137 @@
+use multiversx_sc::imports::*;
+use wine_common::MAX_FEE_DISCOUNT_BPS;
+use wine_roles::Role;
+
+use crate::events::CheckInMode;
+use wine_common::errors::*;
+
+pub const GRANT_FEE_DISCOUNT_GAS: u64 = 10_000_000;
+
+// Check-in either flags the ticket (organizer-driven) or burns it when the attendee hands it
+// in. Checked-in attendees can be granted a one-off marketplace fee discount.
+#[multiversx_sc::module]
+pub trait CheckInModule: crate::events::EventsModule + wine_roles::RolesModule {
+    // The marketplace must list this contract as a fee discount issuer
+    #[endpoint(setMarketplace)]
+    fn set_marketplace(&self, marketplace: ManagedAddress) {
+        self.require_role(Role::Admin);
+        require!(
+            self.blockchain().is_smart_contract(&marketplace),
+            ERR_INVALID_MARKETPLACE_ADDRESS
+        );
+        self.marketplace().set(&marketplace);
+    }
+
+    // A discount of 0 stops granting discounts on check-in
+    #[endpoint(setEventDiscount)]
+    fn set_event_discount(&self, event_id: u32, discount_bps: u64, validity_seconds: u64) {
+        let mut event = self.require_organizer(event_id);
+        require!(discount_bps <= MAX_FEE_DISCOUNT_BPS, ERR_DISCOUNT_TOO_HIGH);
+        require!(discount_bps == 0 || validity_seconds > 0, ERR_VALIDITY_MUST_BE_GREATER_THAN_0);
+        require!(discount_bps == 0 || !self.marketplace().is_empty(), ERR_MARKETPLACE_NOT_SET);
+        event.discount_bps = discount_bps;
+        event.discount_validity_seconds = validity_seconds;
+        self.events(event_id).set(&event);
+
+        self.event_discount_set_event(event_id, discount_bps, validity_seconds);
+    }
+
+    // Burn-mode events only accept tickets while check-in is open
+    #[endpoint(setCheckInOpen)]
+    fn set_check_in_open(&self, event_id: u32, open: bool) {
+        let mut event = self.require_organizer(event_id);
+        require!(event.check_in_mode == CheckInMode::Burn, ERR_EVENT_DOES_NOT_USE_TICKET_BURN);
+        event.check_in_open = open;
+        self.events(event_id).set(&event);
+    }
+
+    // Flag mode: the organizer checks the ticket at the door and names its holder, who gets
+    // the discount
+    #[endpoint(checkInTicket)]
+    fn check_in_ticket(&self, ticket_nonce: u64, attendee: ManagedAddress) {
+        let event_id = self.require_ticket(ticket_nonce);
+        let event = self.require_organizer(event_id);
+        require!(event.check_in_mode == CheckInMode::Flag, ERR_EVENT_DOES_NOT_USE_TICKET_FLAGS);
+        require!(!self.checked_in(ticket_nonce).get(), ERR_TICKET_ALREADY_CHECKED_IN);
+
+        self.checked_in(ticket_nonce).set(true);
+        self.complete_check_in(event_id, ticket_nonce, &attendee);
+    }
+
+    // Burn mode: the attendee hands in the ticket
+    #[payable("*")]
+    #[endpoint(redeemTicket)]
+    fn redeem_ticket(&self) {
+        let payment = self.call_value().single_esdt();
+        require!(
+            payment.token_identifier == self.ticket_collection().get_token_id(),
+            ERR_INVALID_TICKET
+        );
+        let event_id = self.require_ticket(payment.token_nonce);
+        let event = self.require_event(event_id);
+        require!(event.check_in_mode == CheckInMode::Burn, ERR_EVENT_DOES_NOT_USE_TICKET_BURN);
+        require!(event.check_in_open, ERR_CHECK_IN_IS_NOT_OPEN);
+
+        self.send().esdt_local_burn(&payment.token_identifier, payment.token_nonce, &payment.amount);
+        self.checked_in(payment.token_nonce).set(true);
+        let attendee = self.blockchain().get_caller();
+        self.complete_check_in(event_id, payment.token_nonce, &attendee);
+    }
+
+    // Internal helpers
+    fn require_ticket(&self, ticket_nonce: u64) -> u32 {
+        require!(!self.ticket_event(ticket_nonce).is_empty(), ERR_TICKET_DOES_NOT_EXIST);
+        self.ticket_event(ticket_nonce).get()
+    }
+
+    fn complete_check_in(&self, event_id: u32, ticket_nonce: u64, attendee: &ManagedAddress) {
+        let mut event = self.events(event_id).get();
+        event.checked_in += 1;
+        self.events(event_id).set(&event);
+
+        self.ticket_checked_in_event(event_id, ticket_nonce, attendee);
+
+        if event.discount_bps > 0 && !self.marketplace().is_empty() {
+            let expiry = self.blockchain().get_block_timestamp() + event.discount_validity_seconds;
+            self.tx()
+                .to(&self.marketplace().get())
+                .gas(GRANT_FEE_DISCOUNT_GAS)
+                .raw_call("grantFeeDiscount")
+                .argument(attendee)
+                .argument(&event.discount_bps)
+                .argument(&expiry)
+                .transfer_execute();
+        }
+    }
+
+    // View functions
+    #[view(isTicketCheckedIn)]
+    fn is_ticket_checked_in(&self, ticket_nonce: u64) -> bool {
+        self.checked_in(ticket_nonce).get()
+    }
+
+    // Storage mappers
+    #[view(getMarketplace)]
+    #[storage_mapper("marketplace")]
+    fn marketplace(&self) -> SingleValueMapper<ManagedAddress>;
+
+    #[storage_mapper("checkedIn")]
+    fn checked_in(&self, ticket_nonce: u64) -> SingleValueMapper<bool>;
+
+    // Events
+    #[event("eventDiscountSet")]
+    fn event_discount_set_event(
+        &self,
+        #[indexed] event_id: u32,
+        discount_bps: u64,
+        validity_seconds: u64,
+    );
+
+    #[event("ticketCheckedIn")]
+    fn ticket_checked_in_event(
+        &self,
+        #[indexed] event_id: u32,
+        #[indexed] ticket_nonce: u64,
+        #[indexed] attendee: &ManagedAddress,
+    );
+}
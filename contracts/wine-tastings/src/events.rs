@@ -0,0 +1,307 @@
+use multiversx_sc::derive_imports::*;
+use multiversx_sc::imports::*;
+use wine_common::errors::*;
+use wine_roles::Role;
+
+// Tickets are minted one NFT each, so purchases stay small
+pub const MAX_TICKETS_PER_PURCHASE: u32 = 10;
+pub const MAX_PRICE_TOKENS: usize = 5;
+
+#[derive(TopEncode, TopDecode, NestedEncode, NestedDecode, TypeAbi, PartialEq, Debug, Clone, Copy)]
+pub enum CheckInMode {
+    // The organizer flags the ticket; it stays with the attendee as a keepsake
+    Flag,
+    // The attendee hands the ticket in while check-in is open and it is burned
+    Burn,
+}
+
+#[derive(TopEncode, TopDecode, TypeAbi, PartialEq, Debug)]
+pub struct TastingEvent<M: ManagedTypeApi> {
+    pub organizer: ManagedAddress<M>,
+    pub name: ManagedBuffer<M>,
+    pub event_timestamp: u64,
+    pub max_tickets: u32,
+    pub sold_tickets: u32,
+    pub max_tickets_per_wallet: u32,
+    pub check_in_mode: CheckInMode,
+    pub check_in_open: bool,
+    pub checked_in: u32,
+    // Marketplace fee discount granted on check-in, 0 for none
+    pub discount_bps: u64,
+    pub discount_validity_seconds: u64,
+}
+
+// On-chain attributes of a ticket NFT
+#[derive(TopEncode, TopDecode, TypeAbi, PartialEq, Debug)]
+pub struct TicketAttributes {
+    pub event_id: u32,
+    pub ticket_number: u32,
+}
+
+// Producers organize tastings and sell tickets in any of the tokens they price them in, with
+// a cap per wallet. Payments go straight to the organizer.
+#[multiversx_sc::module]
+pub trait EventsModule: wine_roles::RolesModule {
+    // Issue the ticket NFT collection with all roles assigned to this contract. Requires the
+    // issue cost in EGLD.
+    #[payable("EGLD")]
+    #[endpoint(issueTicketCollection)]
+    fn issue_ticket_collection(&self, token_display_name: ManagedBuffer, token_ticker: ManagedBuffer) {
+        self.require_role(Role::Admin);
+        let issue_cost = self.call_value().egld_value().clone_value();
+        self.ticket_collection().issue_and_set_all_roles(
+            EsdtTokenType::NonFungible,
+            issue_cost,
+            token_display_name,
+            token_ticker,
+            0,
+            Some(self.callbacks().ticket_collection_issued()),
+        );
+    }
+
+    #[callback]
+    fn ticket_collection_issued(&self, #[call_result] result: ManagedAsyncCallResult<TokenIdentifier>) {
+        match result {
+            ManagedAsyncCallResult::Ok(token_id) => {
+                self.ticket_collection().set_token_id(token_id);
+            },
+            ManagedAsyncCallResult::Err(_) => {
+                self.ticket_collection().clear();
+            },
+        }
+    }
+
+    #[endpoint(addEventOrganizer)]
+    fn add_event_organizer(&self, organizer: ManagedAddress) {
+        self.require_role(Role::Admin);
+        require!(self.event_organizers().insert(organizer), ERR_ALREADY_AN_EVENT_ORGANIZER);
+    }
+
+    #[endpoint(removeEventOrganizer)]
+    fn remove_event_organizer(&self, organizer: ManagedAddress) {
+        self.require_role(Role::Admin);
+        require!(self.event_organizers().swap_remove(&organizer), ERR_NOT_AN_EVENT_ORGANIZER);
+    }
+
+    #[endpoint(createTastingEvent)]
+    fn create_tasting_event(
+        &self,
+        name: ManagedBuffer,
+        event_timestamp: u64,
+        max_tickets: u32,
+        max_tickets_per_wallet: u32,
+        check_in_mode: CheckInMode,
+    ) -> u32 {
+        self.require_not_paused();
+        let organizer = self.blockchain().get_caller();
+        require!(self.event_organizers().contains(&organizer), ERR_NOT_AN_EVENT_ORGANIZER);
+        require!(!name.is_empty(), ERR_EVENT_NAME_CANNOT_BE_EMPTY);
+        require!(
+            event_timestamp > self.blockchain().get_block_timestamp(),
+            ERR_EVENT_MUST_BE_IN_THE_FUTURE
+        );
+        require!(max_tickets > 0, ERR_MAX_TICKETS_MUST_BE_GREATER_THAN_0);
+        require!(max_tickets_per_wallet > 0, ERR_PER_WALLET_CAP_MUST_BE_GREATER_THAN_0);
+
+        let event_id = self.event_count().get() + 1;
+        self.event_count().set(event_id);
+        self.events(event_id).set(&TastingEvent {
+            organizer: organizer.clone(),
+            name,
+            event_timestamp,
+            max_tickets,
+            sold_tickets: 0,
+            max_tickets_per_wallet,
+            check_in_mode,
+            check_in_open: false,
+            checked_in: 0,
+            discount_bps: 0,
+            discount_validity_seconds: 0,
+        });
+
+        self.tasting_event_created_event(event_id, &organizer, event_timestamp, max_tickets);
+
+        event_id
+    }
+
+    // A price of 0 stops sales in that token
+    #[endpoint(setTicketPrice)]
+    fn set_ticket_price(&self, event_id: u32, token: EgldOrEsdtTokenIdentifier, price: BigUint) {
+        self.require_organizer(event_id);
+        require!(token.is_valid(), ERR_INVALID_PAYMENT_TOKEN);
+        if price == 0 {
+            self.ticket_prices(event_id).remove(&token);
+        } else {
+            self.ticket_prices(event_id).insert(token.clone(), price.clone());
+            require!(
+                self.ticket_prices(event_id).len() <= MAX_PRICE_TOKENS,
+                ERR_TOO_MANY_PAYMENT_TOKENS
+            );
+        }
+
+        self.ticket_price_set_event(event_id, &token, &price);
+    }
+
+    #[payable("*")]
+    #[endpoint(buyTickets)]
+    fn buy_tickets(&self, event_id: u32, quantity: u32) {
+        self.require_not_paused();
+        let mut event = self.require_event(event_id);
+        require!(
+            self.blockchain().get_block_timestamp() < event.event_timestamp,
+            ERR_TICKET_SALES_HAVE_ENDED
+        );
+        require!(
+            quantity > 0 && quantity <= MAX_TICKETS_PER_PURCHASE,
+            ERR_INVALID_TICKET_QUANTITY
+        );
+        require!(quantity <= event.max_tickets - event.sold_tickets, ERR_NOT_ENOUGH_TICKETS_LEFT);
+        let buyer = self.blockchain().get_caller();
+        let bought = self.tickets_bought(event_id, &buyer).get() + quantity;
+        require!(bought <= event.max_tickets_per_wallet, ERR_PER_WALLET_TICKET_CAP_REACHED);
+        require!(
+            self.ticket_collection().get_token_state().is_set(),
+            ERR_TICKET_COLLECTION_NOT_ISSUED
+        );
+
+        let payment = self.call_value().egld_or_single_esdt();
+        let price = self
+            .ticket_prices(event_id)
+            .get(&payment.token_identifier)
+            .unwrap_or_else(|| sc_panic!(ERR_TICKETS_ARE_NOT_SOLD_IN_THIS_TOKEN));
+        require!(payment.amount == price * quantity, ERR_INCORRECT_PAYMENT_AMOUNT);
+
+        let token_id = self.ticket_collection().get_token_id();
+        let mut tickets = ManagedVec::new();
+        for _ in 0..quantity {
+            event.sold_tickets += 1;
+            let attributes = TicketAttributes {
+                event_id,
+                ticket_number: event.sold_tickets,
+            };
+            let nonce = self.send().esdt_nft_create(
+                &token_id,
+                &BigUint::from(1u32),
+                &sc_format!("{} #{}", event.name, event.sold_tickets),
+                &BigUint::zero(),
+                &ManagedBuffer::new(),
+                &attributes,
+                &ManagedVec::new(),
+            );
+            self.ticket_event(nonce).set(event_id);
+            tickets.push(EsdtTokenPayment::new(token_id.clone(), nonce, BigUint::from(1u32)));
+        }
+        self.events(event_id).set(&event);
+        self.tickets_bought(event_id, &buyer).set(bought);
+        self.send().direct_multi(&buyer, &tickets);
+        self.send().direct(&event.organizer, &payment.token_identifier, 0, &payment.amount);
+
+        self.tickets_bought_event(
+            event_id,
+            &buyer,
+            quantity,
+            &payment.token_identifier,
+            &payment.amount,
+        );
+    }
+
+    // Internal helpers
+    fn require_event(&self, event_id: u32) -> TastingEvent<Self::Api> {
+        require!(!self.events(event_id).is_empty(), ERR_EVENT_DOES_NOT_EXIST);
+        self.events(event_id).get()
+    }
+
+    fn require_organizer(&self, event_id: u32) -> TastingEvent<Self::Api> {
+        let event = self.require_event(event_id);
+        require!(
+            self.blockchain().get_caller() == event.organizer,
+            ERR_ONLY_THE_ORGANIZER_CAN_MANAGE_THE_EVENT
+        );
+        event
+    }
+
+    // View functions
+    #[view(getTastingEvent)]
+    fn get_tasting_event(&self, event_id: u32) -> TastingEvent<Self::Api> {
+        self.require_event(event_id)
+    }
+
+    #[view(getTastingEventCount)]
+    fn get_tasting_event_count(&self) -> u32 {
+        self.event_count().get()
+    }
+
+    #[view(getTicketPrices)]
+    fn get_ticket_prices(
+        &self,
+        event_id: u32,
+    ) -> MultiValueEncoded<MultiValue2<EgldOrEsdtTokenIdentifier, BigUint>> {
+        let mut result = MultiValueEncoded::new();
+        for (token, price) in self.ticket_prices(event_id).iter() {
+            result.push((token, price).into());
+        }
+        result
+    }
+
+    #[view(getTicketCollection)]
+    fn get_ticket_collection(&self) -> OptionalValue<TokenIdentifier> {
+        if self.ticket_collection().get_token_state().is_set() {
+            OptionalValue::Some(self.ticket_collection().get_token_id())
+        } else {
+            OptionalValue::None
+        }
+    }
+
+    // Storage mappers
+    #[storage_mapper("ticketCollection")]
+    fn ticket_collection(&self) -> NonFungibleTokenMapper;
+
+    #[storage_mapper("eventOrganizers")]
+    fn event_organizers(&self) -> UnorderedSetMapper<ManagedAddress>;
+
+    #[storage_mapper("eventCount")]
+    fn event_count(&self) -> SingleValueMapper<u32>;
+
+    #[storage_mapper("events")]
+    fn events(&self, event_id: u32) -> SingleValueMapper<TastingEvent<Self::Api>>;
+
+    #[storage_mapper("ticketPrices")]
+    fn ticket_prices(&self, event_id: u32) -> MapMapper<EgldOrEsdtTokenIdentifier, BigUint>;
+
+    #[view(getTicketsBought)]
+    #[storage_mapper("ticketsBought")]
+    fn tickets_bought(&self, event_id: u32, buyer: &ManagedAddress) -> SingleValueMapper<u32>;
+
+    // Event a ticket nonce belongs to
+    #[view(getTicketEvent)]
+    #[storage_mapper("ticketEvent")]
+    fn ticket_event(&self, ticket_nonce: u64) -> SingleValueMapper<u32>;
+
+    // Events
+    #[event("tastingEventCreated")]
+    fn tasting_event_created_event(
+        &self,
+        #[indexed] event_id: u32,
+        #[indexed] organizer: &ManagedAddress,
+        event_timestamp: u64,
+        max_tickets: u32,
+    );
+
+    #[event("ticketPriceSet")]
+    fn ticket_price_set_event(
+        &self,
+        #[indexed] event_id: u32,
+        #[indexed] token: &EgldOrEsdtTokenIdentifier,
+        price: &BigUint,
+    );
+
+    #[event("ticketsBought")]
+    fn tickets_bought_event(
+        &self,
+        #[indexed] event_id: u32,
+        #[indexed] buyer: &ManagedAddress,
+        #[indexed] quantity: u32,
+        #[indexed] payment_token: &EgldOrEsdtTokenIdentifier,
+        amount: &BigUint,
+    );
+}
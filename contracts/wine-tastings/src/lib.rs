@@ -0,0 +1,21 @@
+#![no_std]
+
+use multiversx_sc::imports::*;
+
+pub mod check_in;
+pub mod events;
+
+pub use events::{CheckInMode, TastingEvent, TicketAttributes};
+
+// Tickets for producer tastings, minted as NFTs. Attendees who check in can be granted a
+// one-off discount on their next marketplace fee.
+#[multiversx_sc::contract]
+pub trait WineTastings:
+    events::EventsModule + check_in::CheckInModule + wine_roles::RolesModule
+{
+    #[init]
+    fn init(&self) {}
+
+    #[upgrade]
+    fn upgrade(&self) {}
+}
@@ -0,0 +1,389 @@
+use multiversx_sc::codec::top_encode_to_vec_u8_or_panic;
+use multiversx_sc_scenario::api::StaticApi;
+use multiversx_sc_scenario::imports::*;
+use wine_common::errors::*;
+use wine_marketplace::fee_discounts::ProxyTrait as _;
+use wine_marketplace::FeeDiscount;
+use wine_marketplace::ProxyTrait as _;
+use wine_roles::ProxyTrait as _;
+use wine_tastings::check_in::ProxyTrait as _;
+use wine_tastings::events::ProxyTrait as _;
+use wine_tastings::ProxyTrait as _;
+use wine_tastings::{CheckInMode, TastingEvent, TicketAttributes};
+
+const TASTINGS_PATH_EXPR: &str = "mxsc:output/wine-tastings.mxsc.json";
+const MARKETPLACE_PATH_EXPR: &str = "mxsc:../wine-marketplace/output/wine-marketplace.mxsc.json";
+const TASTINGS_ADDRESS_EXPR: &str = "sc:wine-tastings";
+const MARKETPLACE_ADDRESS_EXPR: &str = "sc:wine-marketplace";
+const ESDT_SYSTEM_SC_ADDRESS_EXPR: &str = "0x000000000000000000010000000000000000000000000000000000000002ffff";
+const OWNER_ADDRESS_EXPR: &str = "address:owner";
+const ORGANIZER_ADDRESS_EXPR: &str = "address:organizer";
+const ALICE_ADDRESS_EXPR: &str = "address:alice";
+const BOB_ADDRESS_EXPR: &str = "address:bob";
+
+const TICKET_TOKEN_ID: &str = "TICKET-abcdef";
+const TICKET_TOKEN_ID_EXPR: &str = "str:TICKET-abcdef";
+const USDC_TOKEN_ID: &str = "USDC-abcdef";
+const USDC_TOKEN_ID_EXPR: &str = "str:USDC-abcdef";
+
+const START_TIMESTAMP: u64 = 1_000;
+const EVENT_TIMESTAMP: u64 = 10_000;
+
+type TastingsContract = ContractInfo<wine_tastings::Proxy<StaticApi>>;
+type MarketplaceContract = ContractInfo<wine_marketplace::Proxy<StaticApi>>;
+
+fn user_error(message: &str) -> TxExpect {
+    TxExpect::user_error(format!("str:{message}"))
+}
+
+fn encoded_ticket_attributes(ticket_number: u32) -> Option<Vec<u8>> {
+    Some(top_encode_to_vec_u8_or_panic(&TicketAttributes {
+        event_id: 1,
+        ticket_number,
+    }))
+}
+
+struct TastingsTestState {
+    world: ScenarioWorld,
+    tastings: TastingsContract,
+    marketplace: MarketplaceContract,
+}
+
+impl TastingsTestState {
+    // The ticket collection is issued and an approved organizer has created event 1, selling
+    // tickets at 100 EGLD or 50 USDC with a cap of three per wallet. Alice holds 1000 EGLD,
+    // Bob 500 USDC.
+    fn new(check_in_mode: CheckInMode) -> Self {
+        let mut world = ScenarioWorld::new();
+        world.register_contract(TASTINGS_PATH_EXPR, wine_tastings::ContractBuilder);
+        world.register_contract(MARKETPLACE_PATH_EXPR, wine_marketplace::ContractBuilder);
+
+        let tastings_code = world.code_expression(TASTINGS_PATH_EXPR);
+        world.set_state_step(
+            SetStateStep::new()
+                .block_timestamp(START_TIMESTAMP)
+                .put_account(OWNER_ADDRESS_EXPR, Account::new().nonce(1).balance("50000"))
+                .put_account(ORGANIZER_ADDRESS_EXPR, Account::new().nonce(1))
+                .put_account(ALICE_ADDRESS_EXPR, Account::new().nonce(1).balance("1000"))
+                .put_account(BOB_ADDRESS_EXPR, Account::new().nonce(1).esdt_balance(USDC_TOKEN_ID_EXPR, "500"))
+                .put_account(ESDT_SYSTEM_SC_ADDRESS_EXPR, Account::new().code(tastings_code.clone()))
+                .new_address(OWNER_ADDRESS_EXPR, 1, TASTINGS_ADDRESS_EXPR)
+                .new_address(OWNER_ADDRESS_EXPR, 2, MARKETPLACE_ADDRESS_EXPR)
+                .new_token_identifier(TICKET_TOKEN_ID),
+        );
+
+        let mut tastings = TastingsContract::new(TASTINGS_ADDRESS_EXPR);
+        world.sc_deploy(
+            ScDeployStep::new()
+                .from(OWNER_ADDRESS_EXPR)
+                .code(tastings_code)
+                .call(tastings.init()),
+        );
+        let mut marketplace = MarketplaceContract::new(MARKETPLACE_ADDRESS_EXPR);
+        let marketplace_code = world.code_expression(MARKETPLACE_PATH_EXPR);
+        let owner = AddressValue::from(OWNER_ADDRESS_EXPR).to_address();
+        world.sc_deploy(
+            ScDeployStep::new()
+                .from(OWNER_ADDRESS_EXPR)
+                .code(marketplace_code)
+                .call(marketplace.init(250u64, managed_address!(&owner))),
+        );
+
+        // The collection and the organizers are managed by admins
+        world.sc_call(
+            ScCallStep::new()
+                .from(ALICE_ADDRESS_EXPR)
+                .egld_value("1000")
+                .call(tastings.issue_ticket_collection(ManagedBuffer::from("Tastings"), ManagedBuffer::from("TICKET")))
+                .expect(user_error(ERR_CALLER_DOES_NOT_HAVE_THE_REQUIRED_ROLE)),
+        );
+        world.sc_call(
+            ScCallStep::new()
+                .from(OWNER_ADDRESS_EXPR)
+                .egld_value("50000")
+                .call(tastings.issue_ticket_collection(ManagedBuffer::from("Tastings"), ManagedBuffer::from("TICKET"))),
+        );
+        let organizer = AddressValue::from(ORGANIZER_ADDRESS_EXPR).to_address();
+        world.sc_call(
+            ScCallStep::new()
+                .from(ORGANIZER_ADDRESS_EXPR)
+                .call(tastings.add_event_organizer(managed_address!(&organizer)))
+                .expect(user_error(ERR_CALLER_DOES_NOT_HAVE_THE_REQUIRED_ROLE)),
+        );
+        world.sc_call(
+            ScCallStep::new()
+                .from(ALICE_ADDRESS_EXPR)
+                .call(tastings.create_tasting_event(
+                    ManagedBuffer::from("Bordeaux night"),
+                    EVENT_TIMESTAMP,
+                    5u32,
+                    3u32,
+                    check_in_mode,
+                ))
+                .expect(user_error(ERR_NOT_AN_EVENT_ORGANIZER)),
+        );
+        world.sc_call(
+            ScCallStep::new()
+                .from(OWNER_ADDRESS_EXPR)
+                .call(tastings.add_event_organizer(managed_address!(&organizer))),
+        );
+        world.sc_call(
+            ScCallStep::new()
+                .from(ORGANIZER_ADDRESS_EXPR)
+                .call(tastings.create_tasting_event(
+                    ManagedBuffer::from("Bordeaux night"),
+                    EVENT_TIMESTAMP,
+                    5u32,
+                    3u32,
+                    check_in_mode,
+                ))
+                .expect_value(1u32),
+        );
+
+        let mut state = TastingsTestState {
+            world,
+            tastings,
+            marketplace,
+        };
+        let step = state.set_ticket_price(ALICE_ADDRESS_EXPR, EgldOrEsdtTokenIdentifier::egld(), 100);
+        state.world.sc_call(step.expect(user_error(ERR_ONLY_THE_ORGANIZER_CAN_MANAGE_THE_EVENT)));
+        let step = state.set_ticket_price(ORGANIZER_ADDRESS_EXPR, EgldOrEsdtTokenIdentifier::egld(), 100);
+        state.world.sc_call(step);
+        let step = state.set_ticket_price(ORGANIZER_ADDRESS_EXPR, EgldOrEsdtTokenIdentifier::esdt(USDC_TOKEN_ID), 50);
+        state.world.sc_call(step);
+        state
+    }
+
+    fn set_block_timestamp(&mut self, timestamp: u64) {
+        self.world.set_state_step(SetStateStep::new().block_timestamp(timestamp));
+    }
+
+    fn set_ticket_price(
+        &mut self,
+        caller: &str,
+        token: EgldOrEsdtTokenIdentifier<StaticApi>,
+        price: u64,
+    ) -> TypedScCall<()> {
+        ScCallStep::new()
+            .from(caller)
+            .call(self.tastings.set_ticket_price(1u32, token, price))
+    }
+
+    fn buy_tickets_with_egld(&mut self, quantity: u32, amount: u64) -> TypedScCall<()> {
+        ScCallStep::new()
+            .from(ALICE_ADDRESS_EXPR)
+            .egld_value(amount)
+            .call(self.tastings.buy_tickets(1u32, quantity))
+    }
+
+    fn event(&mut self) -> TastingEvent<StaticApi> {
+        self.world.quick_query(self.tastings.get_tasting_event(1u32))
+    }
+
+    // Lets the tastings contract grant marketplace fee discounts, 10% for a day on check-in
+    fn enable_discounts(&mut self) {
+        let tastings_address = AddressValue::from(TASTINGS_ADDRESS_EXPR).to_address();
+        let marketplace_address = AddressValue::from(MARKETPLACE_ADDRESS_EXPR).to_address();
+        self.world.sc_call(
+            ScCallStep::new()
+                .from(OWNER_ADDRESS_EXPR)
+                .call(self.marketplace.add_fee_discount_issuer(managed_address!(&tastings_address))),
+        );
+        self.world.sc_call(
+            ScCallStep::new()
+                .from(ORGANIZER_ADDRESS_EXPR)
+                .call(self.tastings.set_event_discount(1u32, 1_000u64, 86_400u64))
+                .expect(user_error(ERR_MARKETPLACE_NOT_SET)),
+        );
+        self.world.sc_call(
+            ScCallStep::new()
+                .from(ORGANIZER_ADDRESS_EXPR)
+                .call(self.tastings.set_marketplace(managed_address!(&marketplace_address)))
+                .expect(user_error(ERR_CALLER_DOES_NOT_HAVE_THE_REQUIRED_ROLE)),
+        );
+        self.world.sc_call(
+            ScCallStep::new()
+                .from(OWNER_ADDRESS_EXPR)
+                .call(self.tastings.set_marketplace(managed_address!(&marketplace_address))),
+        );
+        self.world.sc_call(
+            ScCallStep::new()
+                .from(ORGANIZER_ADDRESS_EXPR)
+                .call(self.tastings.set_event_discount(1u32, 1_000u64, 86_400u64)),
+        );
+    }
+
+    fn fee_discount(&mut self, account: &str) -> Option<FeeDiscount> {
+        let account = AddressValue::from(account).to_address();
+        let discount: OptionalValue<FeeDiscount> =
+            self.world.quick_query(self.marketplace.get_fee_discount(managed_address!(&account)));
+        discount.into_option()
+    }
+}
+
+#[test]
+fn tickets_are_sold_in_any_priced_token_and_paid_to_the_organizer() {
+    let mut state = TastingsTestState::new(CheckInMode::Flag);
+
+    let step = state.buy_tickets_with_egld(2, 150);
+    state.world.sc_call(step.expect(user_error(ERR_INCORRECT_PAYMENT_AMOUNT)));
+    let step = state.buy_tickets_with_egld(4, 400);
+    state.world.sc_call(step.expect(user_error(ERR_PER_WALLET_TICKET_CAP_REACHED)));
+    let step = state.buy_tickets_with_egld(2, 200);
+    state.world.sc_call(step);
+    let step = state.buy_tickets_with_egld(2, 200);
+    state.world.sc_call(step.expect(user_error(ERR_PER_WALLET_TICKET_CAP_REACHED)));
+
+    state.world.sc_call(
+        ScCallStep::new()
+            .from(BOB_ADDRESS_EXPR)
+            .esdt_transfer(USDC_TOKEN_ID_EXPR, 0, "200")
+            .call(state.tastings.buy_tickets(1u32, 4u32))
+            .expect(user_error(ERR_NOT_ENOUGH_TICKETS_LEFT)),
+    );
+    state.world.sc_call(
+        ScCallStep::new()
+            .from(BOB_ADDRESS_EXPR)
+            .esdt_transfer(USDC_TOKEN_ID_EXPR, 0, "150")
+            .call(state.tastings.buy_tickets(1u32, 3u32)),
+    );
+    assert_eq!(state.event().sold_tickets, 5);
+
+    // Dropping a price stops sales in that token
+    let step = state.set_ticket_price(ORGANIZER_ADDRESS_EXPR, EgldOrEsdtTokenIdentifier::egld(), 0);
+    state.world.sc_call(step);
+    let prices: MultiValueVec<MultiValue2<EgldOrEsdtTokenIdentifier<StaticApi>, BigUint<StaticApi>>> =
+        state.world.quick_query(state.tastings.get_ticket_prices(1u32));
+    assert_eq!(prices.into_vec().len(), 1);
+
+    let collection: OptionalValue<TokenIdentifier<StaticApi>> =
+        state.world.quick_query(state.tastings.get_ticket_collection());
+    assert_eq!(collection.into_option(), Some(TokenIdentifier::from(TICKET_TOKEN_ID)));
+    state.world.check_state_step(
+        CheckStateStep::new()
+            .put_account(
+                ORGANIZER_ADDRESS_EXPR,
+                CheckAccount::new().balance("200").esdt_balance(USDC_TOKEN_ID_EXPR, "150"),
+            )
+            .put_account(
+                ALICE_ADDRESS_EXPR,
+                CheckAccount::new()
+                    .balance("800")
+                    .esdt_nft_balance_and_attributes(TICKET_TOKEN_ID_EXPR, 1, "1", encoded_ticket_attributes(1))
+                    .esdt_nft_balance_and_attributes(TICKET_TOKEN_ID_EXPR, 2, "1", encoded_ticket_attributes(2)),
+            )
+            .put_account(
+                BOB_ADDRESS_EXPR,
+                CheckAccount::new()
+                    .esdt_balance(USDC_TOKEN_ID_EXPR, "350")
+                    .esdt_nft_balance_and_attributes(TICKET_TOKEN_ID_EXPR, 5, "1", encoded_ticket_attributes(5)),
+            ),
+    );
+
+    state.set_block_timestamp(EVENT_TIMESTAMP);
+    let step = state.buy_tickets_with_egld(1, 100);
+    state.world.sc_call(step.expect(user_error(ERR_TICKET_SALES_HAVE_ENDED)));
+}
+
+#[test]
+fn flagged_check_in_grants_a_marketplace_fee_discount() {
+    let mut state = TastingsTestState::new(CheckInMode::Flag);
+    state.enable_discounts();
+    let step = state.buy_tickets_with_egld(1, 100);
+    state.world.sc_call(step);
+
+    let alice = AddressValue::from(ALICE_ADDRESS_EXPR).to_address();
+    state.world.sc_call(
+        ScCallStep::new()
+            .from(ALICE_ADDRESS_EXPR)
+            .call(state.tastings.check_in_ticket(1u64, managed_address!(&alice)))
+            .expect(user_error(ERR_ONLY_THE_ORGANIZER_CAN_MANAGE_THE_EVENT)),
+    );
+    state.world.sc_call(
+        ScCallStep::new()
+            .from(ORGANIZER_ADDRESS_EXPR)
+            .call(state.tastings.check_in_ticket(1u64, managed_address!(&alice))),
+    );
+    state.world.sc_call(
+        ScCallStep::new()
+            .from(ORGANIZER_ADDRESS_EXPR)
+            .call(state.tastings.check_in_ticket(1u64, managed_address!(&alice)))
+            .expect(user_error(ERR_TICKET_ALREADY_CHECKED_IN)),
+    );
+
+    let checked_in: bool = state.world.quick_query(state.tastings.is_ticket_checked_in(1u64));
+    assert!(checked_in);
+    assert_eq!(state.event().checked_in, 1);
+    assert_eq!(
+        state.fee_discount(ALICE_ADDRESS_EXPR),
+        Some(FeeDiscount {
+            discount_bps: 1_000,
+            expiry_timestamp: START_TIMESTAMP + 86_400,
+        })
+    );
+
+    // The ticket stays with the attendee as a keepsake
+    state.world.check_state_step(CheckStateStep::new().put_account(
+        ALICE_ADDRESS_EXPR,
+        CheckAccount::new().esdt_nft_balance_and_attributes(TICKET_TOKEN_ID_EXPR, 1, "1", encoded_ticket_attributes(1)),
+    ));
+}
+
+#[test]
+fn burned_tickets_are_only_accepted_while_check_in_is_open() {
+    let mut state = TastingsTestState::new(CheckInMode::Burn);
+    let step = state.buy_tickets_with_egld(1, 100);
+    state.world.sc_call(step);
+
+    state.world.sc_call(
+        ScCallStep::new()
+            .from(ALICE_ADDRESS_EXPR)
+            .esdt_transfer(TICKET_TOKEN_ID_EXPR, 1, "1")
+            .call(state.tastings.redeem_ticket())
+            .expect(user_error(ERR_CHECK_IN_IS_NOT_OPEN)),
+    );
+    state.world.sc_call(
+        ScCallStep::new()
+            .from(ALICE_ADDRESS_EXPR)
+            .call(state.tastings.set_check_in_open(1u32, true))
+            .expect(user_error(ERR_ONLY_THE_ORGANIZER_CAN_MANAGE_THE_EVENT)),
+    );
+    state.world.sc_call(
+        ScCallStep::new()
+            .from(ORGANIZER_ADDRESS_EXPR)
+            .call(state.tastings.set_check_in_open(1u32, true)),
+    );
+    state.world.sc_call(
+        ScCallStep::new()
+            .from(ALICE_ADDRESS_EXPR)
+            .esdt_transfer(TICKET_TOKEN_ID_EXPR, 1, "1")
+            .call(state.tastings.redeem_ticket()),
+    );
+
+    let checked_in: bool = state.world.quick_query(state.tastings.is_ticket_checked_in(1u64));
+    assert!(checked_in);
+    assert_eq!(state.event().checked_in, 1);
+    assert_eq!(state.fee_discount(ALICE_ADDRESS_EXPR), None);
+}
+
+#[test]
+fn no_tickets_are_sold_while_paused() {
+    let mut state = TastingsTestState::new(CheckInMode::Flag);
+
+    state.world.sc_call(
+        ScCallStep::new()
+            .from(ORGANIZER_ADDRESS_EXPR)
+            .call(state.tastings.pause())
+            .expect(user_error(ERR_CALLER_DOES_NOT_HAVE_THE_REQUIRED_ROLE)),
+    );
+    state.world.sc_call(ScCallStep::new().from(OWNER_ADDRESS_EXPR).call(state.tastings.pause()));
+    let step = state.buy_tickets_with_egld(1, 100);
+    state.world.sc_call(step.expect(user_error(ERR_CONTRACT_IS_PAUSED)));
+
+    state.world.sc_call(ScCallStep::new().from(OWNER_ADDRESS_EXPR).call(state.tastings.unpause()));
+    let step = state.buy_tickets_with_egld(1, 100);
+    state.world.sc_call(step);
+    state.world.check_state_step(
+        CheckStateStep::new().put_account(ORGANIZER_ADDRESS_EXPR, CheckAccount::new().balance("100")),
+    );
+}
@@ -0,0 +1,21 @@
+#![no_std]
+
+use multiversx_sc::imports::*;
+use wine_common::errors::*;
+
+pub mod schedules;
+
+pub use schedules::VestingSchedule;
+
+// Vesting of WINE token allocations for the team, investors and partners
+#[multiversx_sc::contract]
+pub trait WineVesting: schedules::SchedulesModule + wine_roles::RolesModule {
+    #[init]
+    fn init(&self, vesting_token: TokenIdentifier) {
+        require!(vesting_token.is_valid_esdt_identifier(), ERR_INVALID_VESTING_TOKEN);
+        self.vesting_token().set(&vesting_token);
+    }
+
+    #[upgrade]
+    fn upgrade(&self) {}
+}
@@ -0,0 +1,212 @@
+use multiversx_sc::derive_imports::*;
+use multiversx_sc::imports::*;
+use wine_common::errors::*;
+use wine_roles::Role;
+
+// Bounds the claim loop and the per-beneficiary views
+pub const MAX_SCHEDULES_PER_BENEFICIARY: usize = 20;
+
+#[derive(TopEncode, TopDecode, TypeAbi, PartialEq, Debug)]
+pub struct VestingSchedule<M: ManagedTypeApi> {
+    pub beneficiary: ManagedAddress<M>,
+    // Reduced to the amount vested at revocation when a schedule is revoked
+    pub total_amount: BigUint<M>,
+    pub claimed_amount: BigUint<M>,
+    pub start_timestamp: u64,
+    // Nothing vests before start + cliff; then everything since the start vests at once
+    pub cliff_seconds: u64,
+    pub duration_seconds: u64,
+    // Team grants are revocable; investor and partner allocations usually are not
+    pub revocable: bool,
+    pub revoked: bool,
+}
+
+// WINE allocations released on a cliff followed by linear vesting. Admins fund a schedule
+// when creating it; beneficiaries claim whatever has vested across all their schedules.
+// Revoking a schedule leaves the vested part claimable and returns the rest to the admin.
+#[multiversx_sc::module]
+pub trait SchedulesModule: wine_roles::RolesModule {
+    // Funded with the WINE sent along
+    #[payable("*")]
+    #[endpoint(createVestingSchedule)]
+    fn create_vesting_schedule(
+        &self,
+        beneficiary: ManagedAddress,
+        start_timestamp: u64,
+        cliff_seconds: u64,
+        duration_seconds: u64,
+        revocable: bool,
+    ) -> u32 {
+        self.require_role(Role::Admin);
+        require!(!beneficiary.is_zero(), ERR_INVALID_BENEFICIARY);
+        require!(duration_seconds > 0, ERR_DURATION_MUST_BE_GREATER_THAN_0);
+        require!(cliff_seconds <= duration_seconds, ERR_CLIFF_CANNOT_EXCEED_THE_DURATION);
+        require!(
+            self.beneficiary_schedules(&beneficiary).len() < MAX_SCHEDULES_PER_BENEFICIARY,
+            ERR_TOO_MANY_SCHEDULES_FOR_BENEFICIARY
+        );
+        let payment = self.call_value().single_esdt();
+        require!(payment.token_identifier == self.vesting_token().get(), ERR_INVALID_VESTING_TOKEN);
+        require!(payment.amount > 0, ERR_AMOUNT_MUST_BE_GREATER_THAN_0);
+
+        let schedule_id = self.schedule_count().get() + 1;
+        self.schedule_count().set(schedule_id);
+        self.schedules(schedule_id).set(&VestingSchedule {
+            beneficiary: beneficiary.clone(),
+            total_amount: payment.amount.clone(),
+            claimed_amount: BigUint::zero(),
+            start_timestamp,
+            cliff_seconds,
+            duration_seconds,
+            revocable,
+            revoked: false,
+        });
+        self.beneficiary_schedules(&beneficiary).insert(schedule_id);
+
+        self.vesting_schedule_created_event(schedule_id, &beneficiary, &payment.amount);
+
+        schedule_id
+    }
+
+    // Claim everything vested so far across the caller's schedules
+    #[endpoint(claimVested)]
+    fn claim_vested(&self) -> BigUint {
+        self.require_not_paused();
+        let beneficiary = self.blockchain().get_caller();
+        let mut claimed = BigUint::zero();
+        for schedule_id in self.beneficiary_schedules(&beneficiary).iter() {
+            let mut schedule = self.schedules(schedule_id).get();
+            let releasable = self.releasable_amount(&schedule);
+            if releasable > 0 {
+                schedule.claimed_amount += &releasable;
+                self.schedules(schedule_id).set(&schedule);
+                claimed += releasable;
+            }
+        }
+        require!(claimed > 0, ERR_NOTHING_TO_CLAIM);
+        self.send().direct_esdt(&beneficiary, &self.vesting_token().get(), 0, &claimed);
+
+        self.vested_claimed_event(&beneficiary, &claimed);
+
+        claimed
+    }
+
+    // Stop a revocable schedule. What has vested stays claimable; the unvested rest goes to
+    // the calling admin.
+    #[endpoint(revokeVestingSchedule)]
+    fn revoke_vesting_schedule(&self, schedule_id: u32) -> BigUint {
+        self.require_role(Role::Admin);
+        let mut schedule = self.require_schedule(schedule_id);
+        require!(schedule.revocable, ERR_SCHEDULE_IS_NOT_REVOCABLE);
+        require!(!schedule.revoked, ERR_SCHEDULE_ALREADY_REVOKED);
+
+        let vested = self.vested_amount(&schedule);
+        let unvested = &schedule.total_amount - &vested;
+        schedule.total_amount = vested;
+        schedule.revoked = true;
+        self.schedules(schedule_id).set(&schedule);
+        let caller = self.blockchain().get_caller();
+        if unvested > 0 {
+            self.send().direct_esdt(&caller, &self.vesting_token().get(), 0, &unvested);
+        }
+
+        self.vesting_schedule_revoked_event(schedule_id, &schedule.beneficiary, &unvested);
+
+        unvested
+    }
+
+    // Internal helpers
+    fn require_schedule(&self, schedule_id: u32) -> VestingSchedule<Self::Api> {
+        require!(!self.schedules(schedule_id).is_empty(), ERR_SCHEDULE_DOES_NOT_EXIST);
+        self.schedules(schedule_id).get()
+    }
+
+    fn vested_amount(&self, schedule: &VestingSchedule<Self::Api>) -> BigUint {
+        if schedule.revoked {
+            return schedule.total_amount.clone();
+        }
+        let now = self.blockchain().get_block_timestamp();
+        if now < schedule.start_timestamp + schedule.cliff_seconds {
+            return BigUint::zero();
+        }
+        let elapsed = now - schedule.start_timestamp;
+        if elapsed >= schedule.duration_seconds {
+            return schedule.total_amount.clone();
+        }
+        &schedule.total_amount * elapsed / schedule.duration_seconds
+    }
+
+    fn releasable_amount(&self, schedule: &VestingSchedule<Self::Api>) -> BigUint {
+        self.vested_amount(schedule) - &schedule.claimed_amount
+    }
+
+    // View functions
+    #[view(getVestingSchedule)]
+    fn get_vesting_schedule(&self, schedule_id: u32) -> VestingSchedule<Self::Api> {
+        self.require_schedule(schedule_id)
+    }
+
+    #[view(getVestingScheduleCount)]
+    fn get_vesting_schedule_count(&self) -> u32 {
+        self.schedule_count().get()
+    }
+
+    #[view(getBeneficiarySchedules)]
+    fn get_beneficiary_schedules(&self, beneficiary: ManagedAddress) -> MultiValueEncoded<u32> {
+        self.beneficiary_schedules(&beneficiary).iter().collect()
+    }
+
+    // Vested so far across the beneficiary's schedules, claimed or not
+    #[view(getVestedAmount)]
+    fn get_vested_amount(&self, beneficiary: ManagedAddress) -> BigUint {
+        let mut vested = BigUint::zero();
+        for schedule_id in self.beneficiary_schedules(&beneficiary).iter() {
+            vested += self.vested_amount(&self.schedules(schedule_id).get());
+        }
+        vested
+    }
+
+    // Vested and not yet claimed; what `claimVested` would pay out now
+    #[view(getReleasableAmount)]
+    fn get_releasable_amount(&self, beneficiary: ManagedAddress) -> BigUint {
+        let mut releasable = BigUint::zero();
+        for schedule_id in self.beneficiary_schedules(&beneficiary).iter() {
+            releasable += self.releasable_amount(&self.schedules(schedule_id).get());
+        }
+        releasable
+    }
+
+    // Storage mappers
+    #[view(getVestingToken)]
+    #[storage_mapper("vestingToken")]
+    fn vesting_token(&self) -> SingleValueMapper<TokenIdentifier>;
+
+    #[storage_mapper("scheduleCount")]
+    fn schedule_count(&self) -> SingleValueMapper<u32>;
+
+    #[storage_mapper("schedules")]
+    fn schedules(&self, schedule_id: u32) -> SingleValueMapper<VestingSchedule<Self::Api>>;
+
+    #[storage_mapper("beneficiarySchedules")]
+    fn beneficiary_schedules(&self, beneficiary: &ManagedAddress) -> UnorderedSetMapper<u32>;
+
+    // Events
+    #[event("vestingScheduleCreated")]
+    fn vesting_schedule_created_event(
+        &self,
+        #[indexed] schedule_id: u32,
+        #[indexed] beneficiary: &ManagedAddress,
+        total_amount: &BigUint,
+    );
+
+    #[event("vestedClaimed")]
+    fn vested_claimed_event(&self, #[indexed] beneficiary: &ManagedAddress, amount: &BigUint);
+
+    #[event("vestingScheduleRevoked")]
+    fn vesting_schedule_revoked_event(
+        &self,
+        #[indexed] schedule_id: u32,
+        #[indexed] beneficiary: &ManagedAddress,
+        unvested_amount: &BigUint,
+    );
+}
@@ -0,0 +1,175 @@
+use multiversx_sc_scenario::api::StaticApi;
+use multiversx_sc_scenario::imports::*;
+use wine_common::errors::*;
+use wine_vesting::schedules::ProxyTrait as _;
+use wine_vesting::ProxyTrait as _;
+use wine_vesting::VestingSchedule;
+
+const VESTING_PATH_EXPR: &str = "mxsc:output/wine-vesting.mxsc.json";
+const VESTING_ADDRESS_EXPR: &str = "sc:wine-vesting";
+const OWNER_ADDRESS_EXPR: &str = "address:owner";
+const ALICE_ADDRESS_EXPR: &str = "address:alice";
+
+const WINE_TOKEN_ID_EXPR: &str = "str:WINE-123456";
+
+const START_TIMESTAMP: u64 = 1_000;
+const TEAM_CLIFF: u64 = 100;
+const TEAM_DURATION: u64 = 400;
+const INVESTOR_DURATION: u64 = 800;
+
+type VestingContract = ContractInfo<wine_vesting::Proxy<StaticApi>>;
+
+fn user_error(message: &str) -> TxExpect {
+    TxExpect::user_error(format!("str:{message}"))
+}
+
+struct VestingTestState {
+    world: ScenarioWorld,
+    vesting: VestingContract,
+}
+
+impl VestingTestState {
+    // Alice has two schedules starting at timestamp 1000: a revocable team grant of 1200 WINE
+    // with a 100 second cliff over 400 seconds, and an investor allocation of 800 WINE over
+    // 800 seconds without a cliff
+    fn new() -> Self {
+        let mut world = ScenarioWorld::new();
+        world.register_contract(VESTING_PATH_EXPR, wine_vesting::ContractBuilder);
+
+        world.set_state_step(
+            SetStateStep::new()
+                .block_timestamp(START_TIMESTAMP)
+                .put_account(
+                    OWNER_ADDRESS_EXPR,
+                    Account::new().nonce(1).esdt_balance(WINE_TOKEN_ID_EXPR, "2000"),
+                )
+                .put_account(
+                    ALICE_ADDRESS_EXPR,
+                    Account::new().nonce(1).esdt_balance(WINE_TOKEN_ID_EXPR, "100"),
+                )
+                .new_address(OWNER_ADDRESS_EXPR, 1, VESTING_ADDRESS_EXPR),
+        );
+
+        let mut vesting = VestingContract::new(VESTING_ADDRESS_EXPR);
+        let vesting_code = world.code_expression(VESTING_PATH_EXPR);
+        world.sc_deploy(
+            ScDeployStep::new()
+                .from(OWNER_ADDRESS_EXPR)
+                .code(vesting_code)
+                .call(vesting.init(TokenIdentifier::from("WINE-123456"))),
+        );
+
+        // Only admins fund schedules
+        let alice = AddressValue::from(ALICE_ADDRESS_EXPR).to_address();
+        world.sc_call(
+            ScCallStep::new()
+                .from(ALICE_ADDRESS_EXPR)
+                .esdt_transfer(WINE_TOKEN_ID_EXPR, 0, "100")
+                .call(vesting.create_vesting_schedule(managed_address!(&alice), START_TIMESTAMP, 0u64, 1u64, false))
+                .expect(user_error(ERR_CALLER_DOES_NOT_HAVE_THE_REQUIRED_ROLE)),
+        );
+        for (amount, cliff, duration, revocable, schedule_id) in [
+            ("1200", TEAM_CLIFF, TEAM_DURATION, true, 1u32),
+            ("800", 0, INVESTOR_DURATION, false, 2),
+        ] {
+            world.sc_call(
+                ScCallStep::new()
+                    .from(OWNER_ADDRESS_EXPR)
+                    .esdt_transfer(WINE_TOKEN_ID_EXPR, 0, amount)
+                    .call(vesting.create_vesting_schedule(
+                        managed_address!(&alice),
+                        START_TIMESTAMP,
+                        cliff,
+                        duration,
+                        revocable,
+                    ))
+                    .expect_value(schedule_id),
+            );
+        }
+
+        VestingTestState { world, vesting }
+    }
+
+    fn set_block_timestamp(&mut self, timestamp: u64) {
+        self.world.set_state_step(SetStateStep::new().block_timestamp(timestamp));
+    }
+
+    fn claim_vested(&mut self) -> TypedScCall<BigUint<StaticApi>> {
+        ScCallStep::new()
+            .from(ALICE_ADDRESS_EXPR)
+            .call(self.vesting.claim_vested())
+    }
+
+    fn revoke_team_grant(&mut self, caller: &str) -> TypedScCall<BigUint<StaticApi>> {
+        ScCallStep::new()
+            .from(caller)
+            .call(self.vesting.revoke_vesting_schedule(1u32))
+    }
+}
+
+#[test]
+fn allocations_vest_from_the_cliff_on_linearly() {
+    let mut state = VestingTestState::new();
+
+    // Before the cliff, only the investor allocation has started to vest
+    state.set_block_timestamp(START_TIMESTAMP + TEAM_CLIFF - 1);
+    let alice = AddressValue::from(ALICE_ADDRESS_EXPR).to_address();
+    let releasable: BigUint<StaticApi> =
+        state.world.quick_query(state.vesting.get_releasable_amount(managed_address!(&alice)));
+    assert_eq!(releasable, 99u64);
+
+    // At the cliff, everything since the start of the team grant vests at once
+    state.set_block_timestamp(START_TIMESTAMP + TEAM_CLIFF);
+    let step = state.claim_vested();
+    state.world.sc_call(step.expect_value(BigUint::from(400u64)));
+    let step = state.claim_vested();
+    state.world.sc_call(step.expect(user_error(ERR_NOTHING_TO_CLAIM)));
+
+    state.set_block_timestamp(START_TIMESTAMP + TEAM_DURATION);
+    let step = state.claim_vested();
+    state.world.sc_call(step.expect_value(BigUint::from(1_200u64)));
+    state.set_block_timestamp(START_TIMESTAMP + INVESTOR_DURATION + 1);
+    let step = state.claim_vested();
+    state.world.sc_call(step.expect_value(BigUint::from(400u64)));
+
+    state.world.check_state_step(
+        CheckStateStep::new()
+            .put_account(ALICE_ADDRESS_EXPR, CheckAccount::new().esdt_balance(WINE_TOKEN_ID_EXPR, "2100"))
+            .put_account(VESTING_ADDRESS_EXPR, CheckAccount::new().esdt_balance(WINE_TOKEN_ID_EXPR, "0")),
+    );
+}
+
+#[test]
+fn revoking_returns_the_unvested_rest_to_the_admin() {
+    let mut state = VestingTestState::new();
+    state.set_block_timestamp(START_TIMESTAMP + 200);
+
+    let step = state.revoke_team_grant(ALICE_ADDRESS_EXPR);
+    state.world.sc_call(step.expect(user_error(ERR_CALLER_DOES_NOT_HAVE_THE_REQUIRED_ROLE)));
+    state.world.sc_call(
+        ScCallStep::new()
+            .from(OWNER_ADDRESS_EXPR)
+            .call(state.vesting.revoke_vesting_schedule(2u32))
+            .expect(user_error(ERR_SCHEDULE_IS_NOT_REVOCABLE)),
+    );
+
+    // Half of the team grant has vested
+    let step = state.revoke_team_grant(OWNER_ADDRESS_EXPR);
+    state.world.sc_call(step.expect_value(BigUint::from(600u64)));
+    let step = state.revoke_team_grant(OWNER_ADDRESS_EXPR);
+    state.world.sc_call(step.expect(user_error(ERR_SCHEDULE_ALREADY_REVOKED)));
+    let schedule: VestingSchedule<StaticApi> = state.world.quick_query(state.vesting.get_vesting_schedule(1u32));
+    assert!(schedule.revoked);
+    assert_eq!(schedule.total_amount, 600u64);
+
+    // The vested part stays claimable next to the investor allocation
+    state.set_block_timestamp(START_TIMESTAMP + INVESTOR_DURATION);
+    let step = state.claim_vested();
+    state.world.sc_call(step.expect_value(BigUint::from(1_400u64)));
+    state.world.check_state_step(
+        CheckStateStep::new()
+            .put_account(OWNER_ADDRESS_EXPR, CheckAccount::new().esdt_balance(WINE_TOKEN_ID_EXPR, "600"))
+            .put_account(ALICE_ADDRESS_EXPR, CheckAccount::new().esdt_balance(WINE_TOKEN_ID_EXPR, "1500"))
+            .put_account(VESTING_ADDRESS_EXPR, CheckAccount::new().esdt_balance(WINE_TOKEN_ID_EXPR, "0")),
+    );
+}
@@ -0,0 +1,109 @@
+use std::time::Duration;
+
+use clap::Parser;
+use wine_interactor::config::NetworkArgs;
+use wine_interactor::demo::plan::{MILLI_EGLD, ONE_EGLD};
+use wine_interactor::demo::{DemoParams, DemoPlan, DemoRunner};
+
+// Populates devnet with producers, wines, listings, auctions and ratings.
+// The same seed always produces the same accounts and catalog.
+#[derive(Parser, Debug)]
+#[command(name = "demo-generator", about = "Generate seeded demo data for the Wine dApp on devnet")]
+struct Cli {
+    #[command(flatten)]
+    network: NetworkArgs,
+
+    /// PEM wallet that funds the demo accounts
+    #[arg(long, env = "DEMO_FUNDER_PEM")]
+    funder_pem: String,
+
+    #[arg(long, default_value_t = 42)]
+    seed: u64,
+
+    #[arg(long, default_value_t = 24)]
+    producers: usize,
+
+    #[arg(long, default_value_t = 48)]
+    collectors: usize,
+
+    #[arg(long, default_value_t = 240)]
+    wines: usize,
+
+    /// Share of wines put up as fixed-price listings
+    #[arg(long, default_value_t = 45)]
+    listing_percent: u32,
+
+    /// Share of wines put up for auction
+    #[arg(long, default_value_t = 25)]
+    auction_percent: u32,
+
+    /// Share of listings bought right away
+    #[arg(long, default_value_t = 30)]
+    buy_percent: u32,
+
+    #[arg(long, default_value_t = 6)]
+    bid_rounds: u32,
+
+    /// Pause between bid rounds, in seconds
+    #[arg(long, default_value_t = 300)]
+    bid_interval_secs: u64,
+
+    #[arg(long, default_value_t = 400)]
+    ratings: usize,
+
+    /// EGLD sent to every demo account, in milli-EGLD
+    #[arg(long, default_value_t = 2_000)]
+    fund_milli_egld: u64,
+
+    /// Print the plan summary without sending transactions
+    #[arg(long)]
+    dry_run: bool,
+}
+
+#[tokio::main]
+async fn main() {
+    let cli = Cli::parse();
+    assert!(
+        cli.listing_percent + cli.auction_percent <= 100,
+        "listing and auction shares cannot exceed 100%"
+    );
+
+    let params = DemoParams {
+        seed: cli.seed,
+        producers: cli.producers,
+        collectors: cli.collectors,
+        wines: cli.wines,
+        listing_percent: cli.listing_percent,
+        auction_percent: cli.auction_percent,
+        buy_percent: cli.buy_percent,
+        bid_rounds: cli.bid_rounds,
+        ratings: cli.ratings,
+    };
+    let plan = DemoPlan::generate(&params);
+
+    if cli.dry_run {
+        println!(
+            "seed {}: {} wines, {} sales, {} ratings",
+            cli.seed,
+            plan.wines.len(),
+            plan.sales.len(),
+            plan.ratings.len()
+        );
+        return;
+    }
+
+    let fund_amount = cli.fund_milli_egld * MILLI_EGLD;
+    assert!(fund_amount <= 10 * ONE_EGLD, "refusing to send more than 10 EGLD per account");
+
+    let mut runner = DemoRunner::new(
+        &cli.network.gateway_url,
+        cli.network.registry(),
+        cli.network.marketplace(),
+        &cli.funder_pem,
+        &plan,
+    )
+    .await;
+    runner
+        .run(&plan, fund_amount, Duration::from_secs(cli.bid_interval_secs))
+        .await;
+}
@@ -0,0 +1,125 @@
+use std::path::PathBuf;
+
+use clap::{Parser, Subcommand};
+use wine_interactor::config::Network;
+use wine_interactor::interact::{SaleArgs, WineArgs, WineInteract};
+use wine_interactor::state::DEFAULT_STATE_FILE;
+
+// Deploys, upgrades and calls the registry and marketplace on devnet or testnet.
+// Contract addresses are kept in the state file between runs.
+#[derive(Parser, Debug)]
+#[command(name = "wine-interactor", about = "Deploy and operate the Wine dApp contracts")]
+struct Cli {
+    #[arg(long, value_enum, default_value_t = Network::Devnet)]
+    network: Network,
+
+    /// Overrides the gateway of --network
+    #[arg(long, env = "GATEWAY_URL")]
+    gateway_url: Option<String>,
+
+    /// PEM wallet that signs every transaction
+    #[arg(long, env = "WALLET_PEM")]
+    pem: String,
+
+    #[arg(long, env = "WINE_INTERACTOR_STATE", default_value = DEFAULT_STATE_FILE)]
+    state_file: PathBuf,
+
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(Subcommand, Debug)]
+enum Command {
+    DeployRegistry {
+        /// In the smallest unit of EGLD
+        #[arg(long, default_value_t = 0)]
+        registration_fee: u128,
+    },
+    /// Deploys the marketplace wired to the registry in the state file and lets it record sales there
+    DeployMarketplace {
+        /// Marketplace fee in basis points, at most 1000
+        #[arg(long, default_value_t = 250)]
+        fee_bps: u64,
+    },
+    UpgradeRegistry,
+    UpgradeMarketplace,
+    /// Issues the registry's wine collection, paying the issue cost
+    IssueWineCollection {
+        #[arg(long, default_value = "WineBottles")]
+        name: String,
+        #[arg(long, default_value = "WINE")]
+        ticker: String,
+    },
+    /// Subscribes the marketplace to the registry and sets its wine collection
+    ConnectMarketplace,
+    RegisterWine(WineArgs),
+    MintWine {
+        #[arg(long)]
+        wine_id: u32,
+        #[arg(long, default_value_t = 1)]
+        quantity: u32,
+    },
+    /// Pushes a wine's record to the subscribed marketplaces again
+    ResyncWine {
+        wine_id: u32,
+    },
+    CreateListing {
+        #[command(flatten)]
+        sale: SaleArgs,
+        #[arg(long)]
+        price: u128,
+    },
+    CreateAuction {
+        #[command(flatten)]
+        sale: SaleArgs,
+        #[arg(long)]
+        starting_price: u128,
+        #[arg(long)]
+        min_bid_increment: u128,
+    },
+    RegistrationFee,
+    Wine {
+        wine_id: u32,
+    },
+    Listing {
+        listing_id: u32,
+    },
+    Auction {
+        auction_id: u32,
+    },
+}
+
+#[tokio::main]
+async fn main() {
+    let cli = Cli::parse();
+    let gateway_url = cli
+        .gateway_url
+        .unwrap_or_else(|| cli.network.gateway_url().to_string());
+    let mut interact = WineInteract::new(&gateway_url, &cli.pem, &cli.state_file).await;
+
+    match cli.command {
+        Command::DeployRegistry { registration_fee } => interact.deploy_registry(registration_fee).await,
+        Command::DeployMarketplace { fee_bps } => interact.deploy_marketplace(fee_bps).await,
+        Command::UpgradeRegistry => interact.upgrade_registry().await,
+        Command::UpgradeMarketplace => interact.upgrade_marketplace().await,
+        Command::IssueWineCollection { name, ticker } => interact.issue_wine_collection(&name, &ticker).await,
+        Command::ConnectMarketplace => interact.connect_marketplace().await,
+        Command::RegisterWine(wine) => interact.register_wine(&wine).await,
+        Command::MintWine { wine_id, quantity } => interact.mint_wine(wine_id, quantity).await,
+        Command::ResyncWine { wine_id } => interact.resync_wine(wine_id).await,
+        Command::CreateListing { sale, price } => interact.create_listing(&sale, price).await,
+        Command::CreateAuction {
+            sale,
+            starting_price,
+            min_bid_increment,
+        } => {
+            interact
+                .create_auction(&sale, starting_price, min_bid_increment)
+                .await
+        },
+        Command::RegistrationFee => interact.print_registration_fee().await,
+        Command::Wine { wine_id } => interact.print_wine(wine_id).await,
+        Command::Listing { listing_id } => interact.print_listing(listing_id).await,
+        Command::Auction { auction_id } => interact.print_auction(auction_id).await,
+    }
+}
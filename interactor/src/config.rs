@@ -0,0 +1,43 @@
+use clap::{Args, ValueEnum};
+use multiversx_sc_snippets::imports::*;
+
+pub const DEVNET_GATEWAY: &str = "https://devnet-gateway.multiversx.com";
+pub const TESTNET_GATEWAY: &str = "https://testnet-gateway.multiversx.com";
+
+#[derive(ValueEnum, Debug, Clone, Copy)]
+pub enum Network {
+    Devnet,
+    Testnet,
+}
+
+impl Network {
+    pub fn gateway_url(self) -> &'static str {
+        match self {
+            Network::Devnet => DEVNET_GATEWAY,
+            Network::Testnet => TESTNET_GATEWAY,
+        }
+    }
+}
+
+// Network settings shared by the interactor tools, read from the same variables as `.env.example`
+#[derive(Args, Debug, Clone)]
+pub struct NetworkArgs {
+    #[arg(long, env = "GATEWAY_URL", default_value = DEVNET_GATEWAY)]
+    pub gateway_url: String,
+
+    #[arg(long, env = "WINE_REGISTRY_ADDRESS")]
+    pub registry_address: String,
+
+    #[arg(long, env = "WINE_MARKETPLACE_ADDRESS")]
+    pub marketplace_address: String,
+}
+
+impl NetworkArgs {
+    pub fn registry(&self) -> Bech32Address {
+        Bech32Address::from_bech32_string(self.registry_address.clone())
+    }
+
+    pub fn marketplace(&self) -> Bech32Address {
+        Bech32Address::from_bech32_string(self.marketplace_address.clone())
+    }
+}
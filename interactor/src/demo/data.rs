@@ -0,0 +1,76 @@
+pub const VINEYARDS: &[&str] = &[
+    "Domaine des Collines",
+    "Château Belle Rive",
+    "Cramele Recaș",
+    "Villa Vinifera",
+    "Bodega Alta Sierra",
+    "Weingut Sonnenhang",
+    "Tenuta San Lorenzo",
+    "Quinta do Vale",
+    "Davino",
+    "Liliac",
+    "Domaine du Vieux Pont",
+    "Clos de la Source",
+    "Podgoria Dealu Mare",
+    "Casa Nova Estate",
+    "Oak Ridge Cellars",
+    "Stone Terrace Vineyards",
+];
+
+pub const VARIETIES: &[&str] = &[
+    "Fetească Neagră",
+    "Fetească Albă",
+    "Tămâioasă Românească",
+    "Cabernet Sauvignon",
+    "Merlot",
+    "Pinot Noir",
+    "Syrah",
+    "Chardonnay",
+    "Sauvignon Blanc",
+    "Riesling",
+    "Tempranillo",
+    "Nebbiolo",
+];
+
+pub const REGIONS: &[&str] = &[
+    "Dealu Mare",
+    "Murfatlar",
+    "Cotnari",
+    "Târnave",
+    "Bordeaux",
+    "Burgundy",
+    "Rhône",
+    "Rioja",
+    "Piedmont",
+    "Douro",
+    "Mosel",
+    "Napa Valley",
+];
+
+pub const CERTIFICATIONS: &[&str] = &["DOC", "DOCG", "AOC", "IGP", "DOC-CMD", ""];
+
+pub const AGING_PROCESSES: &[&str] = &[
+    "12 months in French oak",
+    "18 months in Romanian oak",
+    "Stainless steel, no oak",
+    "24 months in barrique",
+    "6 months on lees",
+];
+
+pub const TASTING_NOTES: &[&str] = &[
+    "Black cherry, plum and a hint of smoke",
+    "Citrus zest, white flowers, crisp finish",
+    "Ripe blackberry, vanilla, firm tannins",
+    "Rose petals, honey and apricot",
+    "Red berries, forest floor, silky texture",
+    "Green apple, minerality, long finish",
+];
+
+pub const REVIEWS: &[&str] = &[
+    "Excellent value for the vintage",
+    "Needs a few more years in the cellar",
+    "Perfect with grilled lamb",
+    "Balanced and elegant",
+    "A bit too oaky for my taste",
+    "Outstanding, buying a second case",
+];
@@ -0,0 +1,8 @@
+// Seeded demo data for devnet: `plan` decides everything up front from the seed,
+// `runner` replays the plan as transactions, so the same seed always yields the same data set.
+pub mod data;
+pub mod plan;
+pub mod runner;
+
+pub use plan::{DemoParams, DemoPlan};
+pub use runner::DemoRunner;
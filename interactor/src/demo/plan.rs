@@ -0,0 +1,217 @@
+use std::collections::HashSet;
+
+use rand::seq::SliceRandom;
+use rand::{Rng, RngCore, SeedableRng};
+use rand_chacha::ChaCha20Rng;
+
+use super::data::*;
+
+pub const ONE_EGLD: u64 = 1_000_000_000_000_000_000;
+pub const MILLI_EGLD: u64 = ONE_EGLD / 1_000;
+
+// Separate RNG streams, so changing e.g. the wine count does not reshuffle the wallets
+const WALLET_STREAM: u64 = 1;
+const CATALOG_STREAM: u64 = 2;
+const SALES_STREAM: u64 = 3;
+
+const BASE58_ALPHABET: &[u8] = b"123456789ABCDEFGHJKLMNPQRSTUVWXYZabcdefghijkmnopqrstuvwxyz";
+
+#[derive(Debug, Clone)]
+pub struct DemoParams {
+    pub seed: u64,
+    pub producers: usize,
+    pub collectors: usize,
+    pub wines: usize,
+    pub listing_percent: u32,
+    pub auction_percent: u32,
+    pub buy_percent: u32,
+    pub bid_rounds: u32,
+    pub ratings: usize,
+}
+
+#[derive(Debug, Clone)]
+pub struct WinePlan {
+    pub producer: usize,
+    pub vineyard: String,
+    pub variety: String,
+    pub vintage_year: u32,
+    pub quality_grade: u8,
+    pub alcohol_content: u16,
+    pub region: String,
+    pub certification: String,
+    pub ipfs_hash: String,
+    pub total_bottles: u32,
+    pub price_per_bottle: u64,
+    pub is_organic: bool,
+    pub harvest_date: u64,
+    pub aging_process: String,
+    pub tasting_notes: String,
+}
+
+#[derive(Debug, Clone)]
+pub struct BidPlan {
+    pub round: u32,
+    pub collector: usize,
+}
+
+#[derive(Debug, Clone)]
+pub enum SalePlan {
+    Listing {
+        wine: usize,
+        price: u64,
+        duration_seconds: u64,
+        buyer: Option<usize>,
+    },
+    Auction {
+        wine: usize,
+        starting_price: u64,
+        min_bid_increment: u64,
+        duration_seconds: u64,
+        bids: Vec<BidPlan>,
+    },
+}
+
+#[derive(Debug, Clone)]
+pub struct RatingPlan {
+    pub wine: usize,
+    pub collector: usize,
+    pub rating: u8,
+    pub review: String,
+}
+
+#[derive(Debug, Clone)]
+pub struct DemoPlan {
+    pub producer_keys: Vec<[u8; 32]>,
+    pub collector_keys: Vec<[u8; 32]>,
+    pub wines: Vec<WinePlan>,
+    pub sales: Vec<SalePlan>,
+    pub ratings: Vec<RatingPlan>,
+}
+
+impl DemoPlan {
+    pub fn generate(params: &DemoParams) -> Self {
+        assert!(params.producers > 0, "at least one producer is required");
+        assert!(params.collectors > 0, "at least one collector is required");
+
+        let mut wallet_rng = stream_rng(params.seed, WALLET_STREAM);
+        let producer_keys = (0..params.producers).map(|_| random_key(&mut wallet_rng)).collect();
+        let collector_keys = (0..params.collectors).map(|_| random_key(&mut wallet_rng)).collect();
+
+        let mut catalog_rng = stream_rng(params.seed, CATALOG_STREAM);
+        let wines: Vec<WinePlan> = (0..params.wines)
+            .map(|index| random_wine(&mut catalog_rng, index % params.producers))
+            .collect();
+
+        let mut sales_rng = stream_rng(params.seed, SALES_STREAM);
+        let mut sales = Vec::new();
+        for (index, wine) in wines.iter().enumerate() {
+            let roll = sales_rng.gen_range(0..100);
+            if roll < params.listing_percent {
+                let price = wine.price_per_bottle * sales_rng.gen_range(6..=12);
+                let buyer = (sales_rng.gen_range(0..100) < params.buy_percent)
+                    .then(|| sales_rng.gen_range(0..params.collectors));
+                sales.push(SalePlan::Listing {
+                    wine: index,
+                    price,
+                    duration_seconds: sales_rng.gen_range(1..=30) * 86_400,
+                    buyer,
+                });
+            } else if roll < params.listing_percent + params.auction_percent {
+                let starting_price = wine.price_per_bottle * sales_rng.gen_range(3..=6);
+                let mut bids = Vec::new();
+                for round in 0..params.bid_rounds {
+                    if sales_rng.gen_bool(0.6) {
+                        bids.push(BidPlan {
+                            round,
+                            collector: sales_rng.gen_range(0..params.collectors),
+                        });
+                    }
+                }
+                sales.push(SalePlan::Auction {
+                    wine: index,
+                    starting_price,
+                    min_bid_increment: starting_price / 20 + MILLI_EGLD,
+                    duration_seconds: sales_rng.gen_range(1..=24) * 3_600,
+                    bids,
+                });
+            }
+        }
+
+        // The registry accepts one rating per user and wine, so duplicate pairs are skipped
+        let mut ratings = Vec::new();
+        let mut rated = HashSet::new();
+        if !wines.is_empty() {
+            for _ in 0..params.ratings {
+                let wine = sales_rng.gen_range(0..wines.len());
+                let collector = sales_rng.gen_range(0..params.collectors);
+                let rating = sales_rng.gen_range(4..=10);
+                let review = pick(&mut sales_rng, REVIEWS);
+                if rated.insert((wine, collector)) {
+                    ratings.push(RatingPlan {
+                        wine,
+                        collector,
+                        rating,
+                        review,
+                    });
+                }
+            }
+        }
+
+        DemoPlan {
+            producer_keys,
+            collector_keys,
+            wines,
+            sales,
+            ratings,
+        }
+    }
+}
+
+fn stream_rng(seed: u64, stream: u64) -> ChaCha20Rng {
+    let mut rng = ChaCha20Rng::seed_from_u64(seed);
+    rng.set_stream(stream);
+    rng
+}
+
+fn random_key(rng: &mut ChaCha20Rng) -> [u8; 32] {
+    let mut key = [0u8; 32];
+    rng.fill_bytes(&mut key);
+    key
+}
+
+fn pick(rng: &mut ChaCha20Rng, values: &[&str]) -> String {
+    values.choose(rng).unwrap().to_string()
+}
+
+// Structurally a CIDv0, pointing nowhere; good enough for indexers and UI placeholders
+fn random_ipfs_hash(rng: &mut ChaCha20Rng) -> String {
+    let mut hash = String::from("Qm");
+    for _ in 0..44 {
+        hash.push(*BASE58_ALPHABET.choose(rng).unwrap() as char);
+    }
+    hash
+}
+
+fn random_wine(rng: &mut ChaCha20Rng, producer: usize) -> WinePlan {
+    let vintage_year = rng.gen_range(1995..=2023);
+    // harvest in September/October of the vintage year
+    let harvest_date = (vintage_year as u64 - 1970) * 31_556_952 + rng.gen_range(243..=300) * 86_400;
+
+    WinePlan {
+        producer,
+        vineyard: pick(rng, VINEYARDS),
+        variety: pick(rng, VARIETIES),
+        vintage_year,
+        quality_grade: rng.gen_range(5..=10),
+        alcohol_content: rng.gen_range(1150..=1500),
+        region: pick(rng, REGIONS),
+        certification: pick(rng, CERTIFICATIONS),
+        ipfs_hash: random_ipfs_hash(rng),
+        total_bottles: rng.gen_range(1..=20) * 60,
+        price_per_bottle: rng.gen_range(5..=250) * MILLI_EGLD,
+        is_organic: rng.gen_bool(0.3),
+        harvest_date,
+        aging_process: pick(rng, AGING_PROCESSES),
+        tasting_notes: pick(rng, TASTING_NOTES),
+    }
+}
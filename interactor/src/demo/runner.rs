@@ -0,0 +1,359 @@
+use std::time::Duration;
+
+use multiversx_sc_snippets::imports::*;
+use wine_common::WineReadModel;
+
+use super::plan::{DemoPlan, SalePlan, WinePlan};
+
+const TRANSFER_GAS: u64 = 100_000;
+const REGISTER_WINE_GAS: u64 = 30_000_000;
+const MINT_WINE_GAS: u64 = 20_000_000;
+const MARKETPLACE_GAS: u64 = 20_000_000;
+const RATE_WINE_GAS: u64 = 15_000_000;
+
+// Read models reach the marketplace in a cross-shard call after registration
+const READ_MODEL_SYNC_ATTEMPTS: u32 = 10;
+const READ_MODEL_SYNC_INTERVAL: Duration = Duration::from_secs(6);
+
+// Replays a `DemoPlan` against a live network. The funder wallet pays for the demo accounts.
+// The registry must have issued its wine collection and the marketplace must be connected to
+// it (`wine-interactor connect-marketplace`); each producer mints one bottle of their wine
+// through the registry.
+pub struct DemoRunner {
+    interactor: Interactor,
+    registry: Bech32Address,
+    marketplace: Bech32Address,
+    wine_collection: TokenIdentifier<StaticApi>,
+    funder: Address,
+    producers: Vec<Address>,
+    collectors: Vec<Address>,
+}
+
+struct RegisteredWine {
+    wine_id: u32,
+    nft_nonce: u64,
+}
+
+impl DemoRunner {
+    pub async fn new(
+        gateway_url: &str,
+        registry: Bech32Address,
+        marketplace: Bech32Address,
+        funder_pem: &str,
+        plan: &DemoPlan,
+    ) -> Self {
+        let mut interactor = Interactor::new(gateway_url).await;
+        let funder = interactor.register_wallet(Wallet::from_pem_file(funder_pem).expect("invalid funder PEM file"));
+        let producers = register_wallets(&mut interactor, &plan.producer_keys);
+        let collectors = register_wallets(&mut interactor, &plan.collector_keys);
+        let wine_collection = connected_wine_collection(&mut interactor, &registry, &marketplace).await;
+
+        DemoRunner {
+            interactor,
+            registry,
+            marketplace,
+            wine_collection,
+            funder,
+            producers,
+            collectors,
+        }
+    }
+
+    pub async fn run(&mut self, plan: &DemoPlan, fund_amount: u64, bid_interval: Duration) {
+        println!(
+            "demo: {} producers, {} collectors, {} wines, {} sales, {} ratings",
+            self.producers.len(),
+            self.collectors.len(),
+            plan.wines.len(),
+            plan.sales.len(),
+            plan.ratings.len()
+        );
+
+        self.fund_accounts(fund_amount).await;
+        let wines = self.register_wines(&plan.wines).await;
+        self.run_sales(plan, &wines, bid_interval).await;
+        self.rate_wines(plan, &wines).await;
+
+        println!("demo: done");
+    }
+
+    async fn fund_accounts(&mut self, fund_amount: u64) {
+        let accounts: Vec<Address> = self.producers.iter().chain(self.collectors.iter()).cloned().collect();
+        for account in accounts {
+            self.interactor
+                .tx()
+                .from(&self.funder)
+                .to(&account)
+                .gas(TRANSFER_GAS)
+                .egld(BigUint::from(fund_amount))
+                .prepare_async()
+                .run()
+                .await;
+        }
+        println!("demo: funded demo accounts");
+    }
+
+    async fn register_wines(&mut self, wines: &[WinePlan]) -> Vec<RegisteredWine> {
+        let registration_fee = self
+            .interactor
+            .query()
+            .to(&self.registry)
+            .raw_call("getRegistrationFee")
+            .original_result::<BigUint<StaticApi>>()
+            .returns(ReturnsResult)
+            .prepare_async()
+            .run()
+            .await;
+
+        let mut registered = Vec::with_capacity(wines.len());
+        for wine in wines {
+            let producer = self.producers[wine.producer].clone();
+            let signature = format!("demo-producer-{}", wine.producer);
+            let wine_id = self
+                .interactor
+                .tx()
+                .from(&producer)
+                .to(&self.registry)
+                .gas(REGISTER_WINE_GAS)
+                .egld(registration_fee.clone())
+                .raw_call("registerWine")
+                .argument(&ManagedBuffer::<StaticApi>::from(wine.vineyard.as_str()))
+                .argument(&ManagedBuffer::<StaticApi>::from(wine.variety.as_str()))
+                .argument(&wine.vintage_year)
+                .argument(&wine.quality_grade)
+                .argument(&wine.alcohol_content)
+                .argument(&ManagedBuffer::<StaticApi>::from(wine.region.as_str()))
+                .argument(&ManagedBuffer::<StaticApi>::from(wine.certification.as_str()))
+                .argument(&ManagedBuffer::<StaticApi>::from(signature.as_str()))
+                .argument(&ManagedBuffer::<StaticApi>::from(wine.ipfs_hash.as_str()))
+                .argument(&wine.total_bottles)
+                .argument(&BigUint::<StaticApi>::from(wine.price_per_bottle))
+                .argument(&wine.is_organic)
+                .argument(&wine.harvest_date)
+                .argument(&ManagedBuffer::<StaticApi>::from(wine.aging_process.as_str()))
+                .argument(&ManagedBuffer::<StaticApi>::from(wine.tasting_notes.as_str()))
+                .original_result::<u32>()
+                .returns(ReturnsResultUnmanaged)
+                .prepare_async()
+                .run()
+                .await;
+
+            let nft_nonce = self.mint_bottle(&producer, wine_id).await;
+            registered.push(RegisteredWine { wine_id, nft_nonce });
+        }
+        println!("demo: registered {} wines", registered.len());
+
+        registered
+    }
+
+    // The registry mints the bottle to the producer with the wine's attributes, which the
+    // marketplace reads the wine id of escrowed items from
+    async fn mint_bottle(&mut self, producer: &Address, wine_id: u32) -> u64 {
+        self.interactor
+            .tx()
+            .from(producer)
+            .to(&self.registry)
+            .gas(MINT_WINE_GAS)
+            .raw_call("mintWineNft")
+            .argument(&wine_id)
+            .argument(&1u32)
+            .original_result::<u64>()
+            .returns(ReturnsResultUnmanaged)
+            .prepare_async()
+            .run()
+            .await
+    }
+
+    // Listings and auctions of wines the marketplace has no read model of are rejected
+    async fn wait_for_read_model(&mut self, wine_id: u32) {
+        for _ in 0..READ_MODEL_SYNC_ATTEMPTS {
+            let read_model = self
+                .interactor
+                .query()
+                .to(&self.marketplace)
+                .raw_call("getCachedWineReadModel")
+                .argument(&wine_id)
+                .original_result::<OptionalValue<WineReadModel<StaticApi>>>()
+                .returns(ReturnsResult)
+                .prepare_async()
+                .run()
+                .await;
+            if read_model.is_some() {
+                return;
+            }
+            tokio::time::sleep(READ_MODEL_SYNC_INTERVAL).await;
+        }
+        panic!("wine {wine_id} was not synced to the marketplace, check the registry's read model subscribers");
+    }
+
+    async fn run_sales(&mut self, plan: &DemoPlan, wines: &[RegisteredWine], bid_interval: Duration) {
+        // (auction id, current bid, min increment, planned bids)
+        let mut auctions = Vec::new();
+
+        for sale in &plan.sales {
+            match sale {
+                SalePlan::Listing {
+                    wine,
+                    price,
+                    duration_seconds,
+                    buyer,
+                } => {
+                    let seller = self.producers[plan.wines[*wine].producer].clone();
+                    self.wait_for_read_model(wines[*wine].wine_id).await;
+                    let listing_id = self
+                        .interactor
+                        .tx()
+                        .from(&seller)
+                        .to(&self.marketplace)
+                        .gas(MARKETPLACE_GAS)
+                        .single_esdt(&self.wine_collection, wines[*wine].nft_nonce, &BigUint::from(1u64))
+                        .raw_call("createListing")
+                        .argument(&wines[*wine].wine_id)
+                        .argument(&BigUint::<StaticApi>::from(*price))
+                        .argument(&EgldOrEsdtTokenIdentifier::<StaticApi>::egld())
+                        .argument(duration_seconds)
+                        .original_result::<u32>()
+                        .returns(ReturnsResultUnmanaged)
+                        .prepare_async()
+                        .run()
+                        .await;
+
+                    if let Some(buyer) = buyer {
+                        let buyer = self.collectors[*buyer].clone();
+                        self.interactor
+                            .tx()
+                            .from(&buyer)
+                            .to(&self.marketplace)
+                            .gas(MARKETPLACE_GAS)
+                            .egld(BigUint::from(*price))
+                            .raw_call("buyWine")
+                            .argument(&listing_id)
+                            .prepare_async()
+                            .run()
+                            .await;
+                    }
+                },
+                SalePlan::Auction {
+                    wine,
+                    starting_price,
+                    min_bid_increment,
+                    duration_seconds,
+                    bids,
+                } => {
+                    let seller = self.producers[plan.wines[*wine].producer].clone();
+                    self.wait_for_read_model(wines[*wine].wine_id).await;
+                    let auction_id = self
+                        .interactor
+                        .tx()
+                        .from(&seller)
+                        .to(&self.marketplace)
+                        .gas(MARKETPLACE_GAS)
+                        .single_esdt(&self.wine_collection, wines[*wine].nft_nonce, &BigUint::from(1u64))
+                        .raw_call("createAuction")
+                        .argument(&wines[*wine].wine_id)
+                        .argument(&BigUint::<StaticApi>::from(*starting_price))
+                        .argument(&EgldOrEsdtTokenIdentifier::<StaticApi>::egld())
+                        .argument(duration_seconds)
+                        .argument(&BigUint::<StaticApi>::from(*min_bid_increment))
+                        .original_result::<u32>()
+                        .returns(ReturnsResultUnmanaged)
+                        .prepare_async()
+                        .run()
+                        .await;
+
+                    auctions.push((auction_id, *starting_price, *min_bid_increment, bids.clone()));
+                },
+            }
+        }
+        println!("demo: created listings and auctions");
+
+        // Bidding happens in rounds spaced by `bid_interval`, so auctions show activity over time
+        let rounds = auctions
+            .iter()
+            .flat_map(|(_, _, _, bids)| bids.iter().map(|bid| bid.round + 1))
+            .max()
+            .unwrap_or(0);
+        for round in 0..rounds {
+            for (auction_id, current_bid, min_bid_increment, bids) in auctions.iter_mut() {
+                for bid in bids.iter().filter(|bid| bid.round == round) {
+                    let amount = *current_bid + *min_bid_increment;
+                    let bidder = self.collectors[bid.collector].clone();
+                    self.interactor
+                        .tx()
+                        .from(&bidder)
+                        .to(&self.marketplace)
+                        .gas(MARKETPLACE_GAS)
+                        .egld(BigUint::from(amount))
+                        .raw_call("placeBid")
+                        .argument(auction_id)
+                        .prepare_async()
+                        .run()
+                        .await;
+                    *current_bid = amount;
+                }
+            }
+            println!("demo: bid round {}/{} done", round + 1, rounds);
+            if round + 1 < rounds {
+                tokio::time::sleep(bid_interval).await;
+            }
+        }
+    }
+
+    async fn rate_wines(&mut self, plan: &DemoPlan, wines: &[RegisteredWine]) {
+        for rating in &plan.ratings {
+            let collector = self.collectors[rating.collector].clone();
+            self.interactor
+                .tx()
+                .from(&collector)
+                .to(&self.registry)
+                .gas(RATE_WINE_GAS)
+                .raw_call("rateWine")
+                .argument(&wines[rating.wine].wine_id)
+                .argument(&rating.rating)
+                .argument(&ManagedBuffer::<StaticApi>::from(rating.review.as_str()))
+                .prepare_async()
+                .run()
+                .await;
+        }
+        println!("demo: submitted {} ratings", plan.ratings.len());
+    }
+}
+
+// The registry's wine collection, once the marketplace only accepts its bottles
+async fn connected_wine_collection(
+    interactor: &mut Interactor,
+    registry: &Bech32Address,
+    marketplace: &Bech32Address,
+) -> TokenIdentifier<StaticApi> {
+    let mut collections = Vec::with_capacity(2);
+    for contract in [registry, marketplace] {
+        let collection = interactor
+            .query()
+            .to(contract)
+            .raw_call("getWineCollection")
+            .original_result::<OptionalValue<TokenIdentifier<StaticApi>>>()
+            .returns(ReturnsResult)
+            .prepare_async()
+            .run()
+            .await;
+        collections.push(collection.into_option());
+    }
+
+    let registry_collection = collections[0]
+        .clone()
+        .expect("the registry has no wine collection, run wine-interactor issue-wine-collection first");
+    assert!(
+        collections[1].as_ref() == Some(&registry_collection),
+        "the marketplace does not sell the registry's wine collection, run wine-interactor connect-marketplace first"
+    );
+    registry_collection
+}
+
+fn register_wallets(interactor: &mut Interactor, keys: &[[u8; 32]]) -> Vec<Address> {
+    keys.iter()
+        .map(|key| {
+            let wallet = Wallet::from_private_key(&hex::encode(key)).expect("invalid derived key");
+            interactor.register_wallet(wallet)
+        })
+        .collect()
+}
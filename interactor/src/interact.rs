@@ -0,0 +1,496 @@
+use std::path::Path;
+
+use clap::Args;
+use multiversx_sc_snippets::imports::*;
+
+use crate::proxies::wine_marketplace_proxy::WineMarketplaceProxy;
+use crate::proxies::wine_registry_proxy::WineRegistryProxy;
+use crate::state::State;
+
+// Built by `sc-meta all build` in each contract; paths are relative to the interactor crate
+const REGISTRY_CODE: MxscPath = MxscPath::new("../contracts/wine-registry/output/wine-registry.mxsc.json");
+const MARKETPLACE_CODE: MxscPath =
+    MxscPath::new("../contracts/wine-marketplace/output/wine-marketplace.mxsc.json");
+
+const DEPLOY_GAS: u64 = 600_000_000;
+const ISSUE_GAS: u64 = 100_000_000;
+const ADMIN_GAS: u64 = 10_000_000;
+const REGISTER_WINE_GAS: u64 = 30_000_000;
+const MINT_WINE_GAS: u64 = 20_000_000;
+const MARKETPLACE_GAS: u64 = 20_000_000;
+
+// EGLD burnt by the protocol to issue an ESDT collection: 0.05 EGLD
+const ISSUE_COST: u64 = 50_000_000_000_000_000;
+
+// Producer-supplied fields of `registerWine`
+#[derive(Args, Debug, Clone)]
+pub struct WineArgs {
+    #[arg(long)]
+    pub vineyard: String,
+    #[arg(long)]
+    pub variety: String,
+    #[arg(long)]
+    pub vintage_year: u32,
+    /// 1-10
+    #[arg(long)]
+    pub quality_grade: u8,
+    /// x100, e.g. 1350 = 13.50%
+    #[arg(long)]
+    pub alcohol_content: u16,
+    #[arg(long)]
+    pub region: String,
+    #[arg(long, default_value = "")]
+    pub certification: String,
+    #[arg(long)]
+    pub producer_signature: String,
+    #[arg(long)]
+    pub ipfs_hash: String,
+    #[arg(long)]
+    pub total_bottles: u32,
+    /// In the smallest unit of EGLD
+    #[arg(long)]
+    pub price_per_bottle: u128,
+    #[arg(long)]
+    pub is_organic: bool,
+    #[arg(long)]
+    pub harvest_date: u64,
+    #[arg(long, default_value = "")]
+    pub aging_process: String,
+    #[arg(long, default_value = "")]
+    pub tasting_notes: String,
+}
+
+// Wine whose minted bottle a new listing or auction escrows, and the terms it is offered on.
+// The bottle is taken from the registry's wine collection, the only one the marketplace accepts.
+#[derive(Args, Debug, Clone)]
+pub struct SaleArgs {
+    #[arg(long)]
+    pub wine_id: u32,
+    /// EGLD or an ESDT identifier
+    #[arg(long, default_value = "EGLD")]
+    pub payment_token: String,
+    #[arg(long, default_value_t = 7 * 24 * 60 * 60)]
+    pub duration_seconds: u64,
+}
+
+// Deploys and operates the registry and marketplace with a single wallet. Deployed addresses
+// are written to the state file, which every later command reads.
+pub struct WineInteract {
+    interactor: Interactor,
+    wallet: Address,
+    state: State,
+}
+
+impl WineInteract {
+    pub async fn new(gateway_url: &str, wallet_pem: &str, state_file: &Path) -> Self {
+        let mut interactor = Interactor::new(gateway_url).await;
+        let wallet = interactor.register_wallet(Wallet::from_pem_file(wallet_pem).expect("invalid wallet PEM file"));
+
+        WineInteract {
+            interactor,
+            wallet,
+            state: State::load(state_file),
+        }
+    }
+
+    pub async fn deploy_registry(&mut self, registration_fee: u128) {
+        let new_address = self
+            .interactor
+            .tx()
+            .from(&self.wallet)
+            .gas(DEPLOY_GAS)
+            .typed(WineRegistryProxy)
+            .init(BigUint::<StaticApi>::from(registration_fee))
+            .code(REGISTRY_CODE)
+            .code_metadata(CodeMetadata::UPGRADEABLE)
+            .returns(ReturnsNewAddress)
+            .prepare_async()
+            .run()
+            .await;
+
+        let new_address = Bech32Address::from(new_address);
+        self.state.set_registry(&new_address);
+        println!("wine-registry deployed at {new_address}");
+    }
+
+    // The marketplace is wired to the registry from the state file and may lock and record
+    // sales of its wines. It locks them with a synchronous call, so it has to be on the
+    // registry's shard: deploy both from the same wallet.
+    pub async fn deploy_marketplace(&mut self, fee_bps: u64) {
+        let registry = self.state.registry();
+        let new_address = self
+            .interactor
+            .tx()
+            .from(&self.wallet)
+            .gas(DEPLOY_GAS)
+            .typed(WineMarketplaceProxy)
+            .init(fee_bps, ManagedAddress::<StaticApi>::from(registry.to_address()))
+            .code(MARKETPLACE_CODE)
+            .code_metadata(CodeMetadata::UPGRADEABLE)
+            .returns(ReturnsNewAddress)
+            .prepare_async()
+            .run()
+            .await;
+
+        let marketplace_address = ManagedAddress::<StaticApi>::from(&new_address);
+        self.interactor
+            .tx()
+            .from(&self.wallet)
+            .to(&registry)
+            .gas(ADMIN_GAS)
+            .typed(WineRegistryProxy)
+            .set_integration_permissions(&marketplace_address, false, true, false)
+            .prepare_async()
+            .run()
+            .await;
+
+        let new_address = Bech32Address::from(new_address);
+        self.state.set_marketplace(&new_address);
+        println!("wine-marketplace deployed at {new_address}");
+    }
+
+    pub async fn upgrade_registry(&mut self) {
+        self.interactor
+            .tx()
+            .from(&self.wallet)
+            .to(&self.state.registry())
+            .gas(DEPLOY_GAS)
+            .typed(WineRegistryProxy)
+            .upgrade()
+            .code(REGISTRY_CODE)
+            .code_metadata(CodeMetadata::UPGRADEABLE)
+            .prepare_async()
+            .run()
+            .await;
+        println!("wine-registry upgraded");
+    }
+
+    pub async fn upgrade_marketplace(&mut self) {
+        self.interactor
+            .tx()
+            .from(&self.wallet)
+            .to(&self.state.marketplace())
+            .gas(DEPLOY_GAS)
+            .typed(WineMarketplaceProxy)
+            .upgrade()
+            .code(MARKETPLACE_CODE)
+            .code_metadata(CodeMetadata::UPGRADEABLE)
+            .prepare_async()
+            .run()
+            .await;
+        println!("wine-marketplace upgraded");
+    }
+
+    // The registry mints every bottle in its own collection; issuing it needs the issue cost
+    pub async fn issue_wine_collection(&mut self, name: &str, ticker: &str) {
+        self.interactor
+            .tx()
+            .from(&self.wallet)
+            .to(&self.state.registry())
+            .gas(ISSUE_GAS)
+            .typed(WineRegistryProxy)
+            .issue_wine_collection(buffer(name), buffer(ticker))
+            .egld(BigUint::<StaticApi>::from(ISSUE_COST))
+            .prepare_async()
+            .run()
+            .await;
+
+        let collection = self.wine_collection().await;
+        println!("wine collection issued: {collection}");
+    }
+
+    // Lets the marketplace sell the registry's wines: it receives their read models and only
+    // accepts bottles of the registry's collection. Run it before registering wines; wines
+    // registered earlier need `resync-wine`.
+    pub async fn connect_marketplace(&mut self) {
+        let registry = self.state.registry();
+        let marketplace = self.state.marketplace();
+        let collection = self.wine_collection().await;
+        let marketplace_address = ManagedAddress::<StaticApi>::from(marketplace.to_address());
+
+        self.interactor
+            .tx()
+            .from(&self.wallet)
+            .to(&registry)
+            .gas(ADMIN_GAS)
+            .typed(WineRegistryProxy)
+            .add_read_model_subscriber(&marketplace_address)
+            .prepare_async()
+            .run()
+            .await;
+        self.interactor
+            .tx()
+            .from(&self.wallet)
+            .to(&marketplace)
+            .gas(ADMIN_GAS)
+            .typed(WineMarketplaceProxy)
+            .set_wine_collection(TokenIdentifier::from(collection.as_str()))
+            .prepare_async()
+            .run()
+            .await;
+        println!("wine-marketplace connected to wine-registry, selling {collection}");
+    }
+
+    // Pushes the registry's current record of a wine to the subscribed marketplaces
+    pub async fn resync_wine(&mut self, wine_id: u32) {
+        self.interactor
+            .tx()
+            .from(&self.wallet)
+            .to(&self.state.registry())
+            .gas(ADMIN_GAS)
+            .typed(WineRegistryProxy)
+            .resync_wine_read_model(wine_id)
+            .prepare_async()
+            .run()
+            .await;
+        println!("wine {wine_id} resynced");
+    }
+
+    // Pays the current EGLD registration fee
+    pub async fn register_wine(&mut self, wine: &WineArgs) {
+        let registration_fee = self.registration_fee().await;
+        let wine_id = self
+            .interactor
+            .tx()
+            .from(&self.wallet)
+            .to(&self.state.registry())
+            .gas(REGISTER_WINE_GAS)
+            .typed(WineRegistryProxy)
+            .register_wine(
+                buffer(&wine.vineyard),
+                buffer(&wine.variety),
+                wine.vintage_year,
+                wine.quality_grade,
+                wine.alcohol_content,
+                buffer(&wine.region),
+                buffer(&wine.certification),
+                buffer(&wine.producer_signature),
+                buffer(&wine.ipfs_hash),
+                wine.total_bottles,
+                BigUint::<StaticApi>::from(wine.price_per_bottle),
+                wine.is_organic,
+                wine.harvest_date,
+                buffer(&wine.aging_process),
+                buffer(&wine.tasting_notes),
+            )
+            .egld(registration_fee)
+            .returns(ReturnsResult)
+            .prepare_async()
+            .run()
+            .await;
+        println!("registered wine {wine_id}");
+    }
+
+    // Mints to the wine's owner, who has to be the wallet
+    pub async fn mint_wine(&mut self, wine_id: u32, quantity: u32) {
+        let nonce = self
+            .interactor
+            .tx()
+            .from(&self.wallet)
+            .to(&self.state.registry())
+            .gas(MINT_WINE_GAS)
+            .typed(WineRegistryProxy)
+            .mint_wine_nft(wine_id, quantity, MultiValueEncoded::new())
+            .returns(ReturnsResult)
+            .prepare_async()
+            .run()
+            .await;
+        println!("minted {quantity} bottles of wine {wine_id} at nonce {nonce:x}");
+    }
+
+    pub async fn create_listing(&mut self, sale: &SaleArgs, price: u128) {
+        let (collection, nonce) = self.wine_bottle(sale.wine_id).await;
+        let listing_id = self
+            .interactor
+            .tx()
+            .from(&self.wallet)
+            .to(&self.state.marketplace())
+            .gas(MARKETPLACE_GAS)
+            .typed(WineMarketplaceProxy)
+            .create_listing(
+                sale.wine_id,
+                BigUint::<StaticApi>::from(price),
+                payment_token(&sale.payment_token),
+                sale.duration_seconds,
+                OptionalValue::<bool>::None,
+            )
+            .single_esdt(&collection, nonce, &BigUint::from(1u64))
+            .returns(ReturnsResult)
+            .prepare_async()
+            .run()
+            .await;
+        println!("created listing {listing_id}");
+    }
+
+    pub async fn create_auction(&mut self, sale: &SaleArgs, starting_price: u128, min_bid_increment: u128) {
+        let (collection, nonce) = self.wine_bottle(sale.wine_id).await;
+        let auction_id = self
+            .interactor
+            .tx()
+            .from(&self.wallet)
+            .to(&self.state.marketplace())
+            .gas(MARKETPLACE_GAS)
+            .typed(WineMarketplaceProxy)
+            .create_auction(
+                sale.wine_id,
+                BigUint::<StaticApi>::from(starting_price),
+                payment_token(&sale.payment_token),
+                sale.duration_seconds,
+                BigUint::<StaticApi>::from(min_bid_increment),
+            )
+            .single_esdt(&collection, nonce, &BigUint::from(1u64))
+            .returns(ReturnsResult)
+            .prepare_async()
+            .run()
+            .await;
+        println!("created auction {auction_id}");
+    }
+
+    pub async fn wine_collection(&mut self) -> String {
+        let collection = self
+            .interactor
+            .query()
+            .to(&self.state.registry())
+            .typed(WineRegistryProxy)
+            .get_wine_collection()
+            .returns(ReturnsResult)
+            .prepare_async()
+            .run()
+            .await
+            .into_option()
+            .expect("the wine collection is not issued, run issue-wine-collection first");
+        text(collection.as_managed_buffer())
+    }
+
+    // The registry's collection and the nonce its bottles of `wine_id` were minted at
+    async fn wine_bottle(&mut self, wine_id: u32) -> (TokenIdentifier<StaticApi>, u64) {
+        let collection = self.wine_collection().await;
+        let nonce = self
+            .interactor
+            .query()
+            .to(&self.state.registry())
+            .typed(WineRegistryProxy)
+            .get_wine_nft_nonce(wine_id)
+            .returns(ReturnsResult)
+            .prepare_async()
+            .run()
+            .await;
+        assert!(nonce != 0, "no bottles of wine {wine_id} are minted, run mint-wine first");
+        (TokenIdentifier::from(collection.as_str()), nonce)
+    }
+
+    pub async fn registration_fee(&mut self) -> BigUint<StaticApi> {
+        self.interactor
+            .query()
+            .to(&self.state.registry())
+            .typed(WineRegistryProxy)
+            .get_registration_fee()
+            .returns(ReturnsResult)
+            .prepare_async()
+            .run()
+            .await
+    }
+
+    pub async fn print_registration_fee(&mut self) {
+        let registration_fee = self.registration_fee().await;
+        println!("registration fee: {}", amount(&registration_fee));
+    }
+
+    pub async fn print_wine(&mut self, wine_id: u32) {
+        let wine = self
+            .interactor
+            .query()
+            .to(&self.state.registry())
+            .typed(WineRegistryProxy)
+            .get_wine_details(wine_id)
+            .returns(ReturnsResult)
+            .prepare_async()
+            .run()
+            .await;
+
+        println!("wine {wine_id}");
+        println!("  vineyard:  {}", text(&wine.vineyard));
+        println!("  variety:   {}", text(&wine.variety));
+        println!("  vintage:   {}", wine.vintage_year);
+        println!("  region:    {}", text(&wine.region));
+        println!("  grade:     {}", wine.quality_grade);
+        println!("  bottles:   {}/{}", wine.available_bottles, wine.total_bottles);
+        println!("  price:     {}", amount(&wine.price_per_bottle));
+        println!("  producer:  {}", address(&wine.producer));
+    }
+
+    pub async fn print_listing(&mut self, listing_id: u32) {
+        let listing = self
+            .interactor
+            .query()
+            .to(&self.state.marketplace())
+            .typed(WineMarketplaceProxy)
+            .get_listing(listing_id)
+            .returns(ReturnsResult)
+            .prepare_async()
+            .run()
+            .await;
+
+        println!("listing {listing_id}");
+        println!("  wine:      {}", listing.wine_nft_id);
+        println!("  nft:       {}-{:x}", text(listing.nft_token_id.as_managed_buffer()), listing.nft_nonce);
+        println!("  seller:    {}", address(&listing.seller));
+        println!("  price:     {} {}", amount(&listing.price), token(&listing.payment_token));
+        println!("  deadline:  {}", listing.deadline);
+        println!("  active:    {}", listing.active);
+    }
+
+    pub async fn print_auction(&mut self, auction_id: u32) {
+        let auction = self
+            .interactor
+            .query()
+            .to(&self.state.marketplace())
+            .typed(WineMarketplaceProxy)
+            .get_auction(auction_id)
+            .returns(ReturnsResult)
+            .prepare_async()
+            .run()
+            .await;
+
+        println!("auction {auction_id}");
+        println!("  wine:        {}", auction.wine_nft_id);
+        println!("  nft:         {}-{:x}", text(auction.nft_token_id.as_managed_buffer()), auction.nft_nonce);
+        println!("  seller:      {}", address(&auction.seller));
+        println!("  current bid: {} {}", amount(&auction.current_bid), token(&auction.payment_token));
+        println!("  bids:        {}", auction.bid_count);
+        println!("  ends:        {}", auction.end_timestamp);
+        println!("  active:      {}", auction.active);
+    }
+}
+
+fn buffer(value: &str) -> ManagedBuffer<StaticApi> {
+    ManagedBuffer::from(value)
+}
+
+fn payment_token(value: &str) -> EgldOrEsdtTokenIdentifier<StaticApi> {
+    if value == "EGLD" {
+        EgldOrEsdtTokenIdentifier::egld()
+    } else {
+        EgldOrEsdtTokenIdentifier::esdt(TokenIdentifier::from(value))
+    }
+}
+
+fn text(value: &ManagedBuffer<StaticApi>) -> String {
+    String::from_utf8_lossy(&value.to_vec()).into_owned()
+}
+
+fn amount(value: &BigUint<StaticApi>) -> String {
+    text(&value.to_display())
+}
+
+fn address(value: &ManagedAddress<StaticApi>) -> Bech32Address {
+    Bech32Address::from(value.to_address())
+}
+
+fn token(value: &EgldOrEsdtTokenIdentifier<StaticApi>) -> String {
+    if value.is_egld() {
+        "EGLD".to_string()
+    } else {
+        text(value.clone().unwrap_esdt().as_managed_buffer())
+    }
+}
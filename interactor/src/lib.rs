@@ -0,0 +1,5 @@
+pub mod config;
+pub mod demo;
+pub mod interact;
+pub mod proxies;
+pub mod state;
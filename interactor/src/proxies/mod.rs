@@ -0,0 +1,2 @@
+pub mod wine_marketplace_proxy;
+pub mod wine_registry_proxy;
@@ -0,0 +1,2892 @@
+// Code generated by the multiversx-sc proxy generator. DO NOT EDIT.
+
+////////////////////////////////////////////////////
+////////////////// AUTO-GENERATED //////////////////
+////////////////////////////////////////////////////
+
+#![allow(dead_code)]
+#![allow(clippy::all)]
+
+use multiversx_sc::proxy_imports::*;
+
+pub struct WineMarketplaceProxy;
+
+impl<Env, From, To, Gas> TxProxyTrait<Env, From, To, Gas> for WineMarketplaceProxy
+where
+    Env: TxEnv,
+    From: TxFrom<Env>,
+    To: TxTo<Env>,
+    Gas: TxGas<Env>,
+{
+    type TxProxyMethods = WineMarketplaceProxyMethods<Env, From, To, Gas>;
+
+    fn proxy_methods(self, tx: Tx<Env, From, To, (), Gas, (), ()>) -> Self::TxProxyMethods {
+        WineMarketplaceProxyMethods { wrapped_tx: tx }
+    }
+}
+
+pub struct WineMarketplaceProxyMethods<Env, From, To, Gas>
+where
+    Env: TxEnv,
+    From: TxFrom<Env>,
+    To: TxTo<Env>,
+    Gas: TxGas<Env>,
+{
+    wrapped_tx: Tx<Env, From, To, (), Gas, (), ()>,
+}
+
+#[rustfmt::skip]
+impl<Env, From, Gas> WineMarketplaceProxyMethods<Env, From, (), Gas>
+where
+    Env: TxEnv,
+    Env::Api: VMApi,
+    From: TxFrom<Env>,
+    Gas: TxGas<Env>,
+{
+    pub fn init<
+        Arg0: ProxyArg<u64>,
+        Arg1: ProxyArg<ManagedAddress<Env::Api>>,
+    >(
+        self,
+        marketplace_fee_percent: Arg0,
+        wine_registry_address: Arg1,
+    ) -> TxTypedDeploy<Env, From, NotPayable, Gas, ()> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_deploy()
+            .argument(&marketplace_fee_percent)
+            .argument(&wine_registry_address)
+            .original_result()
+    }
+}
+
+#[rustfmt::skip]
+impl<Env, From, To, Gas> WineMarketplaceProxyMethods<Env, From, To, Gas>
+where
+    Env: TxEnv,
+    Env::Api: VMApi,
+    From: TxFrom<Env>,
+    To: TxTo<Env>,
+    Gas: TxGas<Env>,
+{
+    pub fn upgrade(
+        self,
+    ) -> TxTypedUpgrade<Env, From, To, NotPayable, Gas, ()> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_upgrade()
+            .original_result()
+    }
+}
+
+#[rustfmt::skip]
+impl<Env, From, To, Gas> WineMarketplaceProxyMethods<Env, From, To, Gas>
+where
+    Env: TxEnv,
+    Env::Api: VMApi,
+    From: TxFrom<Env>,
+    To: TxTo<Env>,
+    Gas: TxGas<Env>,
+{
+    pub fn add_supported_token<
+        Arg0: ProxyArg<EgldOrEsdtTokenIdentifier<Env::Api>>,
+    >(
+        self,
+        token_id: Arg0,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, ()> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("addSupportedToken")
+            .argument(&token_id)
+            .original_result()
+    }
+
+    pub fn set_transfer_role_restricted<
+        Arg0: ProxyArg<TokenIdentifier<Env::Api>>,
+        Arg1: ProxyArg<bool>,
+    >(
+        self,
+        token_id: Arg0,
+        restricted: Arg1,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, ()> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("setTransferRoleRestricted")
+            .argument(&token_id)
+            .argument(&restricted)
+            .original_result()
+    }
+
+    pub fn is_supported_payment_token<
+        Arg0: ProxyArg<EgldOrEsdtTokenIdentifier<Env::Api>>,
+    >(
+        self,
+        token_id: Arg0,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, bool> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("isSupportedPaymentToken")
+            .argument(&token_id)
+            .original_result()
+    }
+
+    pub fn is_transfer_role_restricted<
+        Arg0: ProxyArg<TokenIdentifier<Env::Api>>,
+    >(
+        self,
+        token_id: Arg0,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, bool> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("isTransferRoleRestricted")
+            .argument(&token_id)
+            .original_result()
+    }
+
+    pub fn set_collector_tier_thresholds<
+        Arg0: ProxyArg<BigUint<Env::Api>>,
+        Arg1: ProxyArg<u32>,
+        Arg2: ProxyArg<BigUint<Env::Api>>,
+        Arg3: ProxyArg<u32>,
+    >(
+        self,
+        silver_volume: Arg0,
+        silver_holdings: Arg1,
+        gold_volume: Arg2,
+        gold_holdings: Arg3,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, ()> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("setCollectorTierThresholds")
+            .argument(&silver_volume)
+            .argument(&silver_holdings)
+            .argument(&gold_volume)
+            .argument(&gold_holdings)
+            .original_result()
+    }
+
+    pub fn get_collector_tier<
+        Arg0: ProxyArg<ManagedAddress<Env::Api>>,
+    >(
+        self,
+        collector: Arg0,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, CollectorTier> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("getCollectorTier")
+            .argument(&collector)
+            .original_result()
+    }
+
+    pub fn get_collector_stats<
+        Arg0: ProxyArg<ManagedAddress<Env::Api>>,
+    >(
+        self,
+        collector: Arg0,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, CollectorStats<Env::Api>> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("getCollectorStats")
+            .argument(&collector)
+            .original_result()
+    }
+
+    pub fn get_collector_tier_thresholds(
+        self,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, OptionalValue<CollectorTierThresholds<Env::Api>>> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("getCollectorTierThresholds")
+            .original_result()
+    }
+
+    pub fn set_loyalty_token<
+        Arg0: ProxyArg<TokenIdentifier<Env::Api>>,
+    >(
+        self,
+        token: Arg0,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, ()> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("setLoyaltyToken")
+            .argument(&token)
+            .original_result()
+    }
+
+    pub fn set_loyalty_emission_rate<
+        Arg0: ProxyArg<EgldOrEsdtTokenIdentifier<Env::Api>>,
+        Arg1: ProxyArg<BigUint<Env::Api>>,
+    >(
+        self,
+        payment_token: Arg0,
+        rate: Arg1,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, ()> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("setLoyaltyEmissionRate")
+            .argument(&payment_token)
+            .argument(&rate)
+            .original_result()
+    }
+
+    pub fn set_loyalty_epoch_cap<
+        Arg0: ProxyArg<BigUint<Env::Api>>,
+    >(
+        self,
+        cap: Arg0,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, ()> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("setLoyaltyEpochCap")
+            .argument(&cap)
+            .original_result()
+    }
+
+    pub fn fund_loyalty_pool(
+        self,
+    ) -> TxTypedCall<Env, From, To, (), Gas, ()> {
+        self.wrapped_tx
+            .raw_call("fundLoyaltyPool")
+            .original_result()
+    }
+
+    pub fn claim_loyalty_rewards(
+        self,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, BigUint<Env::Api>> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("claimLoyaltyRewards")
+            .original_result()
+    }
+
+    pub fn get_loyalty_token(
+        self,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, OptionalValue<TokenIdentifier<Env::Api>>> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("getLoyaltyToken")
+            .original_result()
+    }
+
+    pub fn get_loyalty_emission_rates(
+        self,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, MultiValueEncoded<Env::Api, MultiValue2<EgldOrEsdtTokenIdentifier<Env::Api>, BigUint<Env::Api>>>> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("getLoyaltyEmissionRates")
+            .original_result()
+    }
+
+    pub fn get_loyalty_epoch_cap(
+        self,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, BigUint<Env::Api>> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("getLoyaltyEpochCap")
+            .original_result()
+    }
+
+    pub fn get_loyalty_emitted<
+        Arg0: ProxyArg<u64>,
+    >(
+        self,
+        epoch: Arg0,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, BigUint<Env::Api>> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("getLoyaltyEmitted")
+            .argument(&epoch)
+            .original_result()
+    }
+
+    pub fn get_loyalty_pool(
+        self,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, BigUint<Env::Api>> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("getLoyaltyPool")
+            .original_result()
+    }
+
+    pub fn get_loyalty_rewards<
+        Arg0: ProxyArg<ManagedAddress<Env::Api>>,
+    >(
+        self,
+        address: Arg0,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, BigUint<Env::Api>> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("getLoyaltyRewards")
+            .argument(&address)
+            .original_result()
+    }
+
+    pub fn add_fee_discount_issuer<
+        Arg0: ProxyArg<ManagedAddress<Env::Api>>,
+    >(
+        self,
+        issuer: Arg0,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, ()> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("addFeeDiscountIssuer")
+            .argument(&issuer)
+            .original_result()
+    }
+
+    pub fn remove_fee_discount_issuer<
+        Arg0: ProxyArg<ManagedAddress<Env::Api>>,
+    >(
+        self,
+        issuer: Arg0,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, ()> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("removeFeeDiscountIssuer")
+            .argument(&issuer)
+            .original_result()
+    }
+
+    pub fn grant_fee_discount<
+        Arg0: ProxyArg<ManagedAddress<Env::Api>>,
+        Arg1: ProxyArg<u64>,
+        Arg2: ProxyArg<u64>,
+    >(
+        self,
+        account: Arg0,
+        discount_bps: Arg1,
+        expiry_timestamp: Arg2,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, ()> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("grantFeeDiscount")
+            .argument(&account)
+            .argument(&discount_bps)
+            .argument(&expiry_timestamp)
+            .original_result()
+    }
+
+    pub fn get_fee_discount<
+        Arg0: ProxyArg<ManagedAddress<Env::Api>>,
+    >(
+        self,
+        account: Arg0,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, OptionalValue<FeeDiscount>> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("getFeeDiscount")
+            .argument(&account)
+            .original_result()
+    }
+
+    pub fn get_fee_discount_issuers(
+        self,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, MultiValueEncoded<Env::Api, ManagedAddress<Env::Api>>> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("getFeeDiscountIssuers")
+            .original_result()
+    }
+
+    pub fn get_stats_snapshot<
+        Arg0: ProxyArg<u64>,
+    >(
+        self,
+        epoch: Arg0,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, OptionalValue<StatsSnapshot<Env::Api>>> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("getStatsSnapshot")
+            .argument(&epoch)
+            .original_result()
+    }
+
+    pub fn get_open_stats_epoch(
+        self,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, u64> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("getOpenStatsEpoch")
+            .original_result()
+    }
+
+    pub fn get_epoch_collection_volumes(
+        self,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, MultiValueEncoded<Env::Api, MultiValue2<TokenIdentifier<Env::Api>, BigUint<Env::Api>>>> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("getEpochCollectionVolumes")
+            .original_result()
+    }
+
+    pub fn set_marketplace_fee_percent<
+        Arg0: ProxyArg<u64>,
+    >(
+        self,
+        marketplace_fee_percent: Arg0,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, ()> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("setMarketplaceFeePercent")
+            .argument(&marketplace_fee_percent)
+            .original_result()
+    }
+
+    pub fn set_fee_promotion<
+        Arg0: ProxyArg<TokenIdentifier<Env::Api>>,
+        Arg1: ProxyArg<u64>,
+        Arg2: ProxyArg<u64>,
+        Arg3: ProxyArg<u32>,
+    >(
+        self,
+        collection: Arg0,
+        fee_percent: Arg1,
+        duration_seconds: Arg2,
+        max_sales: Arg3,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, ()> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("setFeePromotion")
+            .argument(&collection)
+            .argument(&fee_percent)
+            .argument(&duration_seconds)
+            .argument(&max_sales)
+            .original_result()
+    }
+
+    pub fn remove_fee_promotion<
+        Arg0: ProxyArg<TokenIdentifier<Env::Api>>,
+    >(
+        self,
+        collection: Arg0,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, ()> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("removeFeePromotion")
+            .argument(&collection)
+            .original_result()
+    }
+
+    pub fn add_consignment_contract<
+        Arg0: ProxyArg<ManagedAddress<Env::Api>>,
+    >(
+        self,
+        address: Arg0,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, ()> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("addConsignmentContract")
+            .argument(&address)
+            .original_result()
+    }
+
+    pub fn remove_consignment_contract<
+        Arg0: ProxyArg<ManagedAddress<Env::Api>>,
+    >(
+        self,
+        address: Arg0,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, ()> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("removeConsignmentContract")
+            .argument(&address)
+            .original_result()
+    }
+
+    pub fn claim_sale_proceeds(
+        self,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, ()> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("claimSaleProceeds")
+            .original_result()
+    }
+
+    pub fn snapshot_stats(
+        self,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, ()> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("snapshotStats")
+            .original_result()
+    }
+
+    pub fn set_fee_recipient<
+        Arg0: ProxyArg<ManagedAddress<Env::Api>>,
+    >(
+        self,
+        recipient: Arg0,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, ()> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("setFeeRecipient")
+            .argument(&recipient)
+            .original_result()
+    }
+
+    pub fn set_fee_router<
+        Arg0: ProxyArg<OptionalValue<ManagedAddress<Env::Api>>>,
+    >(
+        self,
+        opt_router: Arg0,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, ()> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("setFeeRouter")
+            .argument(&opt_router)
+            .original_result()
+    }
+
+    pub fn set_fee_share<
+        Arg0: ProxyArg<ManagedAddress<Env::Api>>,
+        Arg1: ProxyArg<u64>,
+    >(
+        self,
+        recipient: Arg0,
+        share_percent: Arg1,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, ()> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("setFeeShare")
+            .argument(&recipient)
+            .argument(&share_percent)
+            .original_result()
+    }
+
+    pub fn set_keeper_bounty_percent<
+        Arg0: ProxyArg<u64>,
+    >(
+        self,
+        bounty_percent: Arg0,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, ()> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("setKeeperBountyPercent")
+            .argument(&bounty_percent)
+            .original_result()
+    }
+
+    pub fn get_marketplace_stats(
+        self,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, MarketplaceStats<Env::Api>> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("getMarketplaceStats")
+            .original_result()
+    }
+
+    pub fn get_marketplace_fee_percent(
+        self,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, u64> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("getMarketplaceFeePercent")
+            .original_result()
+    }
+
+    pub fn get_active_fee_promotions(
+        self,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, MultiValueEncoded<Env::Api, MultiValue2<TokenIdentifier<Env::Api>, FeePromotion>>> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("getActiveFeePromotions")
+            .original_result()
+    }
+
+    pub fn get_fee_recipient(
+        self,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, ManagedAddress<Env::Api>> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("getFeeRecipient")
+            .original_result()
+    }
+
+    pub fn get_fee_share(
+        self,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, OptionalValue<FeeShare<Env::Api>>> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("getFeeShare")
+            .original_result()
+    }
+
+    pub fn get_fee_router(
+        self,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, OptionalValue<ManagedAddress<Env::Api>>> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("getFeeRouter")
+            .original_result()
+    }
+
+    pub fn get_keeper_bounty_percent(
+        self,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, u64> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("getKeeperBountyPercent")
+            .original_result()
+    }
+
+    pub fn get_sale_proceeds<
+        Arg0: ProxyArg<ManagedAddress<Env::Api>>,
+    >(
+        self,
+        seller: Arg0,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, MultiValueEncoded<Env::Api, MultiValue2<EgldOrEsdtTokenIdentifier<Env::Api>, BigUint<Env::Api>>>> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("getSaleProceeds")
+            .argument(&seller)
+            .original_result()
+    }
+
+    pub fn get_consignment_contracts(
+        self,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, MultiValueEncoded<Env::Api, ManagedAddress<Env::Api>>> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("getConsignmentContracts")
+            .original_result()
+    }
+
+    pub fn add_to_blacklist<
+        Arg0: ProxyArg<MultiValueEncoded<Env::Api, ManagedAddress<Env::Api>>>,
+    >(
+        self,
+        addresses: Arg0,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, ()> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("addToBlacklist")
+            .argument(&addresses)
+            .original_result()
+    }
+
+    pub fn remove_from_blacklist<
+        Arg0: ProxyArg<MultiValueEncoded<Env::Api, ManagedAddress<Env::Api>>>,
+    >(
+        self,
+        addresses: Arg0,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, ()> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("removeFromBlacklist")
+            .argument(&addresses)
+            .original_result()
+    }
+
+    pub fn is_blacklisted<
+        Arg0: ProxyArg<ManagedAddress<Env::Api>>,
+    >(
+        self,
+        address: Arg0,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, bool> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("isBlacklisted")
+            .argument(&address)
+            .original_result()
+    }
+
+    pub fn get_blacklist_count(
+        self,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, usize> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("getBlacklistCount")
+            .original_result()
+    }
+
+    pub fn get_blacklist<
+        Arg0: ProxyArg<u64>,
+        Arg1: ProxyArg<u32>,
+    >(
+        self,
+        cursor: Arg0,
+        page_size: Arg1,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, MultiValue2<u64, MultiValueEncoded<Env::Api, ManagedAddress<Env::Api>>>> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("getBlacklist")
+            .argument(&cursor)
+            .argument(&page_size)
+            .original_result()
+    }
+
+    pub fn set_max_active_items<
+        Arg0: ProxyArg<u32>,
+        Arg1: ProxyArg<u32>,
+    >(
+        self,
+        default_limit: Arg0,
+        verified_limit: Arg1,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, ()> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("setMaxActiveItems")
+            .argument(&default_limit)
+            .argument(&verified_limit)
+            .original_result()
+    }
+
+    pub fn set_verified_reseller<
+        Arg0: ProxyArg<ManagedAddress<Env::Api>>,
+        Arg1: ProxyArg<bool>,
+    >(
+        self,
+        reseller: Arg0,
+        verified: Arg1,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, ()> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("setVerifiedReseller")
+            .argument(&reseller)
+            .argument(&verified)
+            .original_result()
+    }
+
+    pub fn get_max_active_items(
+        self,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, MultiValue2<u32, u32>> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("getMaxActiveItems")
+            .original_result()
+    }
+
+    pub fn get_active_item_count<
+        Arg0: ProxyArg<ManagedAddress<Env::Api>>,
+    >(
+        self,
+        seller: Arg0,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, u32> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("getActiveItemCount")
+            .argument(&seller)
+            .original_result()
+    }
+
+    pub fn is_verified_reseller<
+        Arg0: ProxyArg<ManagedAddress<Env::Api>>,
+    >(
+        self,
+        reseller: Arg0,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, bool> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("isVerifiedReseller")
+            .argument(&reseller)
+            .original_result()
+    }
+
+    pub fn set_arbiter<
+        Arg0: ProxyArg<ManagedAddress<Env::Api>>,
+    >(
+        self,
+        arbiter: Arg0,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, ()> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("setArbiter")
+            .argument(&arbiter)
+            .original_result()
+    }
+
+    pub fn set_dispute_window<
+        Arg0: ProxyArg<u64>,
+    >(
+        self,
+        dispute_window: Arg0,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, ()> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("setDisputeWindow")
+            .argument(&dispute_window)
+            .original_result()
+    }
+
+    pub fn open_dispute<
+        Arg0: ProxyArg<u32>,
+        Arg1: ProxyArg<ManagedBuffer<Env::Api>>,
+    >(
+        self,
+        listing_id: Arg0,
+        reason: Arg1,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, ()> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("openDispute")
+            .argument(&listing_id)
+            .argument(&reason)
+            .original_result()
+    }
+
+    pub fn release_protected_sale<
+        Arg0: ProxyArg<u32>,
+    >(
+        self,
+        listing_id: Arg0,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, ()> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("releaseProtectedSale")
+            .argument(&listing_id)
+            .original_result()
+    }
+
+    pub fn resolve_dispute<
+        Arg0: ProxyArg<u32>,
+        Arg1: ProxyArg<bool>,
+    >(
+        self,
+        listing_id: Arg0,
+        refund: Arg1,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, ()> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("resolveDispute")
+            .argument(&listing_id)
+            .argument(&refund)
+            .original_result()
+    }
+
+    pub fn get_protected_sale<
+        Arg0: ProxyArg<u32>,
+    >(
+        self,
+        listing_id: Arg0,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, OptionalValue<ProtectedSale<Env::Api>>> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("getProtectedSale")
+            .argument(&listing_id)
+            .original_result()
+    }
+
+    pub fn get_arbiter(
+        self,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, OptionalValue<ManagedAddress<Env::Api>>> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("getArbiter")
+            .original_result()
+    }
+
+    pub fn get_dispute_window(
+        self,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, u64> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("getDisputeWindow")
+            .original_result()
+    }
+
+    pub fn set_rate_oracle<
+        Arg0: ProxyArg<ManagedAddress<Env::Api>>,
+    >(
+        self,
+        oracle: Arg0,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, ()> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("setRateOracle")
+            .argument(&oracle)
+            .original_result()
+    }
+
+    pub fn set_max_rate_age<
+        Arg0: ProxyArg<u64>,
+    >(
+        self,
+        max_rate_age: Arg0,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, ()> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("setMaxRateAge")
+            .argument(&max_rate_age)
+            .original_result()
+    }
+
+    pub fn set_exchange_rate<
+        Arg0: ProxyArg<EgldOrEsdtTokenIdentifier<Env::Api>>,
+        Arg1: ProxyArg<EgldOrEsdtTokenIdentifier<Env::Api>>,
+        Arg2: ProxyArg<BigUint<Env::Api>>,
+    >(
+        self,
+        base_token: Arg0,
+        quote_token: Arg1,
+        rate: Arg2,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, ()> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("setExchangeRate")
+            .argument(&base_token)
+            .argument(&quote_token)
+            .argument(&rate)
+            .original_result()
+    }
+
+    pub fn remove_exchange_rate<
+        Arg0: ProxyArg<EgldOrEsdtTokenIdentifier<Env::Api>>,
+        Arg1: ProxyArg<EgldOrEsdtTokenIdentifier<Env::Api>>,
+    >(
+        self,
+        base_token: Arg0,
+        quote_token: Arg1,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, ()> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("removeExchangeRate")
+            .argument(&base_token)
+            .argument(&quote_token)
+            .original_result()
+    }
+
+    pub fn get_exchange_rate<
+        Arg0: ProxyArg<EgldOrEsdtTokenIdentifier<Env::Api>>,
+        Arg1: ProxyArg<EgldOrEsdtTokenIdentifier<Env::Api>>,
+    >(
+        self,
+        base_token: Arg0,
+        quote_token: Arg1,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, OptionalValue<ExchangeRate<Env::Api>>> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("getExchangeRate")
+            .argument(&base_token)
+            .argument(&quote_token)
+            .original_result()
+    }
+
+    pub fn get_rate_oracle(
+        self,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, ManagedAddress<Env::Api>> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("getRateOracle")
+            .original_result()
+    }
+
+    pub fn get_max_rate_age(
+        self,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, u64> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("getMaxRateAge")
+            .original_result()
+    }
+
+    pub fn get_market_summary<
+        Arg0: ProxyArg<u32>,
+    >(
+        self,
+        wine_nft_id: Arg0,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, MultiValueEncoded<Env::Api, MarketSummary<Env::Api>>> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("getMarketSummary")
+            .argument(&wine_nft_id)
+            .original_result()
+    }
+
+    pub fn create_listing<
+        Arg0: ProxyArg<u32>,
+        Arg1: ProxyArg<BigUint<Env::Api>>,
+        Arg2: ProxyArg<EgldOrEsdtTokenIdentifier<Env::Api>>,
+        Arg3: ProxyArg<u64>,
+        Arg4: ProxyArg<OptionalValue<bool>>,
+    >(
+        self,
+        wine_nft_id: Arg0,
+        price: Arg1,
+        payment_token: Arg2,
+        duration_seconds: Arg3,
+        opt_protected: Arg4,
+    ) -> TxTypedCall<Env, From, To, (), Gas, u32> {
+        self.wrapped_tx
+            .raw_call("createListing")
+            .argument(&wine_nft_id)
+            .argument(&price)
+            .argument(&payment_token)
+            .argument(&duration_seconds)
+            .argument(&opt_protected)
+            .original_result()
+    }
+
+    pub fn buy_wine<
+        Arg0: ProxyArg<u32>,
+        Arg1: ProxyArg<OptionalValue<ManagedAddress<Env::Api>>>,
+    >(
+        self,
+        listing_id: Arg0,
+        opt_recipient: Arg1,
+    ) -> TxTypedCall<Env, From, To, (), Gas, ()> {
+        self.wrapped_tx
+            .raw_call("buyWine")
+            .argument(&listing_id)
+            .argument(&opt_recipient)
+            .original_result()
+    }
+
+    pub fn cancel_listing<
+        Arg0: ProxyArg<u32>,
+    >(
+        self,
+        listing_id: Arg0,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, ()> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("cancelListing")
+            .argument(&listing_id)
+            .original_result()
+    }
+
+    pub fn cancel_recalled_listings<
+        Arg0: ProxyArg<u32>,
+    >(
+        self,
+        wine_id: Arg0,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, usize> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("cancelRecalledListings")
+            .argument(&wine_id)
+            .original_result()
+    }
+
+    pub fn add_listing_payment_tokens<
+        Arg0: ProxyArg<u32>,
+        Arg1: ProxyArg<MultiValueEncoded<Env::Api, EgldOrEsdtTokenIdentifier<Env::Api>>>,
+    >(
+        self,
+        listing_id: Arg0,
+        tokens: Arg1,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, ()> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("addListingPaymentTokens")
+            .argument(&listing_id)
+            .argument(&tokens)
+            .original_result()
+    }
+
+    pub fn remove_listing_payment_tokens<
+        Arg0: ProxyArg<u32>,
+        Arg1: ProxyArg<MultiValueEncoded<Env::Api, EgldOrEsdtTokenIdentifier<Env::Api>>>,
+    >(
+        self,
+        listing_id: Arg0,
+        tokens: Arg1,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, ()> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("removeListingPaymentTokens")
+            .argument(&listing_id)
+            .argument(&tokens)
+            .original_result()
+    }
+
+    pub fn set_mixed_settlement<
+        Arg0: ProxyArg<u32>,
+        Arg1: ProxyArg<MixedSettlement>,
+    >(
+        self,
+        listing_id: Arg0,
+        mixed_settlement: Arg1,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, ()> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("setMixedSettlement")
+            .argument(&listing_id)
+            .argument(&mixed_settlement)
+            .original_result()
+    }
+
+    pub fn set_auto_relist<
+        Arg0: ProxyArg<u32>,
+        Arg1: ProxyArg<u32>,
+    >(
+        self,
+        listing_id: Arg0,
+        max_relists: Arg1,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, ()> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("setAutoRelist")
+            .argument(&listing_id)
+            .argument(&max_relists)
+            .original_result()
+    }
+
+    pub fn relist<
+        Arg0: ProxyArg<u32>,
+    >(
+        self,
+        listing_id: Arg0,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, ()> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("relist")
+            .argument(&listing_id)
+            .original_result()
+    }
+
+    pub fn get_listing<
+        Arg0: ProxyArg<u32>,
+    >(
+        self,
+        listing_id: Arg0,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, wine_common::marketplace::Listing<Env::Api>> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("getListing")
+            .argument(&listing_id)
+            .original_result()
+    }
+
+    pub fn get_listing_payment_tokens<
+        Arg0: ProxyArg<u32>,
+    >(
+        self,
+        listing_id: Arg0,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, MultiValueEncoded<Env::Api, EgldOrEsdtTokenIdentifier<Env::Api>>> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("getListingPaymentTokens")
+            .argument(&listing_id)
+            .original_result()
+    }
+
+    pub fn get_listing_price<
+        Arg0: ProxyArg<u32>,
+        Arg1: ProxyArg<EgldOrEsdtTokenIdentifier<Env::Api>>,
+    >(
+        self,
+        listing_id: Arg0,
+        token: Arg1,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, BigUint<Env::Api>> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("getListingPrice")
+            .argument(&listing_id)
+            .argument(&token)
+            .original_result()
+    }
+
+    pub fn get_mixed_settlement<
+        Arg0: ProxyArg<u32>,
+    >(
+        self,
+        listing_id: Arg0,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, MixedSettlement> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("getMixedSettlement")
+            .argument(&listing_id)
+            .original_result()
+    }
+
+    pub fn get_auto_relist_limit<
+        Arg0: ProxyArg<u32>,
+    >(
+        self,
+        listing_id: Arg0,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, u32> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("getAutoRelistLimit")
+            .argument(&listing_id)
+            .original_result()
+    }
+
+    pub fn get_relist_count<
+        Arg0: ProxyArg<u32>,
+    >(
+        self,
+        listing_id: Arg0,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, u32> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("getRelistCount")
+            .argument(&listing_id)
+            .original_result()
+    }
+
+    pub fn get_seller_listings<
+        Arg0: ProxyArg<ManagedAddress<Env::Api>>,
+        Arg1: ProxyArg<u64>,
+        Arg2: ProxyArg<u32>,
+    >(
+        self,
+        seller: Arg0,
+        cursor: Arg1,
+        page_size: Arg2,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, MultiValue2<u64, MultiValueEncoded<Env::Api, u32>>> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("getSellerListings")
+            .argument(&seller)
+            .argument(&cursor)
+            .argument(&page_size)
+            .original_result()
+    }
+
+    pub fn set_installment_config<
+        Arg0: ProxyArg<u64>,
+        Arg1: ProxyArg<u64>,
+        Arg2: ProxyArg<u64>,
+    >(
+        self,
+        min_deposit_percent: Arg0,
+        penalty_percent: Arg1,
+        max_duration: Arg2,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, ()> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("setInstallmentConfig")
+            .argument(&min_deposit_percent)
+            .argument(&penalty_percent)
+            .argument(&max_duration)
+            .original_result()
+    }
+
+    pub fn start_installment_purchase<
+        Arg0: ProxyArg<u32>,
+        Arg1: ProxyArg<u64>,
+    >(
+        self,
+        listing_id: Arg0,
+        duration_seconds: Arg1,
+    ) -> TxTypedCall<Env, From, To, (), Gas, ()> {
+        self.wrapped_tx
+            .raw_call("startInstallmentPurchase")
+            .argument(&listing_id)
+            .argument(&duration_seconds)
+            .original_result()
+    }
+
+    pub fn pay_installment<
+        Arg0: ProxyArg<u32>,
+    >(
+        self,
+        listing_id: Arg0,
+    ) -> TxTypedCall<Env, From, To, (), Gas, ()> {
+        self.wrapped_tx
+            .raw_call("payInstallment")
+            .argument(&listing_id)
+            .original_result()
+    }
+
+    pub fn default_installment<
+        Arg0: ProxyArg<u32>,
+    >(
+        self,
+        listing_id: Arg0,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, ()> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("defaultInstallment")
+            .argument(&listing_id)
+            .original_result()
+    }
+
+    pub fn get_installment_plan<
+        Arg0: ProxyArg<u32>,
+    >(
+        self,
+        listing_id: Arg0,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, OptionalValue<InstallmentPlan<Env::Api>>> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("getInstallmentPlan")
+            .argument(&listing_id)
+            .original_result()
+    }
+
+    pub fn get_installment_config(
+        self,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, InstallmentConfig> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("getInstallmentConfig")
+            .original_result()
+    }
+
+    pub fn migrate_storage<
+        Arg0: ProxyArg<u32>,
+    >(
+        self,
+        batch_size: Arg0,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, bool> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("migrateStorage")
+            .argument(&batch_size)
+            .original_result()
+    }
+
+    pub fn get_storage_version(
+        self,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, u32> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("getStorageVersion")
+            .original_result()
+    }
+
+    pub fn get_migration_cursor(
+        self,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, u32> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("getMigrationCursor")
+            .original_result()
+    }
+
+    pub fn set_auction_winner_privacy<
+        Arg0: ProxyArg<bool>,
+    >(
+        self,
+        private: Arg0,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, ()> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("setAuctionWinnerPrivacy")
+            .argument(&private)
+            .original_result()
+    }
+
+    pub fn get_auction_result<
+        Arg0: ProxyArg<usize>,
+    >(
+        self,
+        result_id: Arg0,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, AuctionResultView<Env::Api>> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("getAuctionResult")
+            .argument(&result_id)
+            .original_result()
+    }
+
+    pub fn get_auction_results<
+        Arg0: ProxyArg<u64>,
+        Arg1: ProxyArg<u32>,
+    >(
+        self,
+        cursor: Arg0,
+        page_size: Arg1,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, MultiValue2<u64, MultiValueEncoded<Env::Api, AuctionResultView<Env::Api>>>> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("getAuctionResults")
+            .argument(&cursor)
+            .argument(&page_size)
+            .original_result()
+    }
+
+    pub fn is_auction_winner_private<
+        Arg0: ProxyArg<ManagedAddress<Env::Api>>,
+    >(
+        self,
+        winner: Arg0,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, bool> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("isAuctionWinnerPrivate")
+            .argument(&winner)
+            .original_result()
+    }
+
+    pub fn set_direct_outbid_refunds<
+        Arg0: ProxyArg<bool>,
+    >(
+        self,
+        enabled: Arg0,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, ()> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("setDirectOutbidRefunds")
+            .argument(&enabled)
+            .original_result()
+    }
+
+    pub fn set_anti_snipe_config<
+        Arg0: ProxyArg<u64>,
+        Arg1: ProxyArg<u64>,
+        Arg2: ProxyArg<u32>,
+    >(
+        self,
+        extension_window: Arg0,
+        extension_duration: Arg1,
+        max_extensions: Arg2,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, ()> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("setAntiSnipeConfig")
+            .argument(&extension_window)
+            .argument(&extension_duration)
+            .argument(&max_extensions)
+            .original_result()
+    }
+
+    pub fn set_auction_anti_snipe_config<
+        Arg0: ProxyArg<u32>,
+        Arg1: ProxyArg<u64>,
+        Arg2: ProxyArg<u64>,
+        Arg3: ProxyArg<u32>,
+    >(
+        self,
+        auction_id: Arg0,
+        extension_window: Arg1,
+        extension_duration: Arg2,
+        max_extensions: Arg3,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, ()> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("setAuctionAntiSnipeConfig")
+            .argument(&auction_id)
+            .argument(&extension_window)
+            .argument(&extension_duration)
+            .argument(&max_extensions)
+            .original_result()
+    }
+
+    pub fn clear_auction_anti_snipe_config<
+        Arg0: ProxyArg<u32>,
+    >(
+        self,
+        auction_id: Arg0,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, ()> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("clearAuctionAntiSnipeConfig")
+            .argument(&auction_id)
+            .original_result()
+    }
+
+    pub fn create_auction<
+        Arg0: ProxyArg<u32>,
+        Arg1: ProxyArg<BigUint<Env::Api>>,
+        Arg2: ProxyArg<EgldOrEsdtTokenIdentifier<Env::Api>>,
+        Arg3: ProxyArg<u64>,
+        Arg4: ProxyArg<BigUint<Env::Api>>,
+    >(
+        self,
+        wine_nft_id: Arg0,
+        starting_price: Arg1,
+        payment_token: Arg2,
+        duration_seconds: Arg3,
+        min_bid_increment: Arg4,
+    ) -> TxTypedCall<Env, From, To, (), Gas, u32> {
+        self.wrapped_tx
+            .raw_call("createAuction")
+            .argument(&wine_nft_id)
+            .argument(&starting_price)
+            .argument(&payment_token)
+            .argument(&duration_seconds)
+            .argument(&min_bid_increment)
+            .original_result()
+    }
+
+    pub fn place_bid<
+        Arg0: ProxyArg<u32>,
+        Arg1: ProxyArg<OptionalValue<ManagedAddress<Env::Api>>>,
+    >(
+        self,
+        auction_id: Arg0,
+        opt_recipient: Arg1,
+    ) -> TxTypedCall<Env, From, To, (), Gas, ()> {
+        self.wrapped_tx
+            .raw_call("placeBid")
+            .argument(&auction_id)
+            .argument(&opt_recipient)
+            .original_result()
+    }
+
+    pub fn finalize_auction<
+        Arg0: ProxyArg<u32>,
+    >(
+        self,
+        auction_id: Arg0,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, ()> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("finalizeAuction")
+            .argument(&auction_id)
+            .original_result()
+    }
+
+    pub fn emergency_cancel_auction<
+        Arg0: ProxyArg<u32>,
+        Arg1: ProxyArg<ManagedBuffer<Env::Api>>,
+    >(
+        self,
+        auction_id: Arg0,
+        reason: Arg1,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, ()> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("emergencyCancelAuction")
+            .argument(&auction_id)
+            .argument(&reason)
+            .original_result()
+    }
+
+    pub fn withdraw_outbid_funds<
+        Arg0: ProxyArg<u32>,
+    >(
+        self,
+        auction_id: Arg0,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, ()> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("withdrawOutbidFunds")
+            .argument(&auction_id)
+            .original_result()
+    }
+
+    pub fn get_auction<
+        Arg0: ProxyArg<u32>,
+    >(
+        self,
+        auction_id: Arg0,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, wine_common::marketplace::Auction<Env::Api>> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("getAuction")
+            .argument(&auction_id)
+            .original_result()
+    }
+
+    pub fn get_min_next_bid<
+        Arg0: ProxyArg<u32>,
+    >(
+        self,
+        auction_id: Arg0,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, BigUint<Env::Api>> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("getMinNextBid")
+            .argument(&auction_id)
+            .original_result()
+    }
+
+    pub fn get_time_left<
+        Arg0: ProxyArg<u32>,
+    >(
+        self,
+        auction_id: Arg0,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, u64> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("getTimeLeft")
+            .argument(&auction_id)
+            .original_result()
+    }
+
+    pub fn get_auction_view<
+        Arg0: ProxyArg<u32>,
+    >(
+        self,
+        auction_id: Arg0,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, AuctionView<Env::Api>> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("getAuctionView")
+            .argument(&auction_id)
+            .original_result()
+    }
+
+    pub fn get_auction_recipient_view<
+        Arg0: ProxyArg<u32>,
+    >(
+        self,
+        auction_id: Arg0,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, ManagedAddress<Env::Api>> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("getAuctionRecipient")
+            .argument(&auction_id)
+            .original_result()
+    }
+
+    pub fn get_auction_cancellation_reason<
+        Arg0: ProxyArg<u32>,
+    >(
+        self,
+        auction_id: Arg0,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, ManagedBuffer<Env::Api>> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("getAuctionCancellationReason")
+            .argument(&auction_id)
+            .original_result()
+    }
+
+    pub fn get_outbid_funds<
+        Arg0: ProxyArg<u32>,
+        Arg1: ProxyArg<ManagedAddress<Env::Api>>,
+    >(
+        self,
+        auction_id: Arg0,
+        bidder: Arg1,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, BigUint<Env::Api>> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("getOutbidFunds")
+            .argument(&auction_id)
+            .argument(&bidder)
+            .original_result()
+    }
+
+    pub fn is_direct_outbid_refunds_enabled(
+        self,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, bool> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("isDirectOutbidRefundsEnabled")
+            .original_result()
+    }
+
+    pub fn get_anti_snipe_config(
+        self,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, AntiSnipeConfig> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("getAntiSnipeConfig")
+            .original_result()
+    }
+
+    pub fn get_auction_anti_snipe_config<
+        Arg0: ProxyArg<u32>,
+    >(
+        self,
+        auction_id: Arg0,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, AntiSnipeConfig> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("getAuctionAntiSnipeConfig")
+            .argument(&auction_id)
+            .original_result()
+    }
+
+    pub fn get_auction_extension_count<
+        Arg0: ProxyArg<u32>,
+    >(
+        self,
+        auction_id: Arg0,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, u32> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("getAuctionExtensionCount")
+            .argument(&auction_id)
+            .original_result()
+    }
+
+    pub fn get_seller_auctions<
+        Arg0: ProxyArg<ManagedAddress<Env::Api>>,
+        Arg1: ProxyArg<u64>,
+        Arg2: ProxyArg<u32>,
+    >(
+        self,
+        seller: Arg0,
+        cursor: Arg1,
+        page_size: Arg2,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, MultiValue2<u64, MultiValueEncoded<Env::Api, u32>>> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("getSellerAuctions")
+            .argument(&seller)
+            .argument(&cursor)
+            .argument(&page_size)
+            .original_result()
+    }
+
+    pub fn get_finalizable_auctions<
+        Arg0: ProxyArg<u64>,
+        Arg1: ProxyArg<u32>,
+    >(
+        self,
+        cursor: Arg0,
+        page_size: Arg1,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, MultiValue2<u64, MultiValueEncoded<Env::Api, u32>>> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("getFinalizableAuctions")
+            .argument(&cursor)
+            .argument(&page_size)
+            .original_result()
+    }
+
+    pub fn create_candle_auction<
+        Arg0: ProxyArg<u32>,
+        Arg1: ProxyArg<BigUint<Env::Api>>,
+        Arg2: ProxyArg<EgldOrEsdtTokenIdentifier<Env::Api>>,
+        Arg3: ProxyArg<u64>,
+        Arg4: ProxyArg<u64>,
+        Arg5: ProxyArg<BigUint<Env::Api>>,
+    >(
+        self,
+        wine_nft_id: Arg0,
+        starting_price: Arg1,
+        payment_token: Arg2,
+        duration_seconds: Arg3,
+        closing_window_seconds: Arg4,
+        min_bid_increment: Arg5,
+    ) -> TxTypedCall<Env, From, To, (), Gas, u32> {
+        self.wrapped_tx
+            .raw_call("createCandleAuction")
+            .argument(&wine_nft_id)
+            .argument(&starting_price)
+            .argument(&payment_token)
+            .argument(&duration_seconds)
+            .argument(&closing_window_seconds)
+            .argument(&min_bid_increment)
+            .original_result()
+    }
+
+    pub fn place_candle_bid<
+        Arg0: ProxyArg<u32>,
+    >(
+        self,
+        auction_id: Arg0,
+    ) -> TxTypedCall<Env, From, To, (), Gas, ()> {
+        self.wrapped_tx
+            .raw_call("placeCandleBid")
+            .argument(&auction_id)
+            .original_result()
+    }
+
+    pub fn settle_candle_auction<
+        Arg0: ProxyArg<u32>,
+    >(
+        self,
+        auction_id: Arg0,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, ()> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("settleCandleAuction")
+            .argument(&auction_id)
+            .original_result()
+    }
+
+    pub fn withdraw_candle_funds<
+        Arg0: ProxyArg<u32>,
+    >(
+        self,
+        auction_id: Arg0,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, ()> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("withdrawCandleFunds")
+            .argument(&auction_id)
+            .original_result()
+    }
+
+    pub fn get_candle_auction<
+        Arg0: ProxyArg<u32>,
+    >(
+        self,
+        auction_id: Arg0,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, CandleAuction<Env::Api>> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("getCandleAuction")
+            .argument(&auction_id)
+            .original_result()
+    }
+
+    pub fn get_candle_bids<
+        Arg0: ProxyArg<u32>,
+        Arg1: ProxyArg<u64>,
+        Arg2: ProxyArg<u32>,
+    >(
+        self,
+        auction_id: Arg0,
+        cursor: Arg1,
+        page_size: Arg2,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, MultiValue2<u64, MultiValueEncoded<Env::Api, CandleBid<Env::Api>>>> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("getCandleBids")
+            .argument(&auction_id)
+            .argument(&cursor)
+            .argument(&page_size)
+            .original_result()
+    }
+
+    pub fn get_candle_escrow<
+        Arg0: ProxyArg<u32>,
+        Arg1: ProxyArg<ManagedAddress<Env::Api>>,
+    >(
+        self,
+        auction_id: Arg0,
+        bidder: Arg1,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, BigUint<Env::Api>> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("getCandleEscrow")
+            .argument(&auction_id)
+            .argument(&bidder)
+            .original_result()
+    }
+
+    pub fn get_seller_candle_auctions<
+        Arg0: ProxyArg<ManagedAddress<Env::Api>>,
+        Arg1: ProxyArg<u64>,
+        Arg2: ProxyArg<u32>,
+    >(
+        self,
+        seller: Arg0,
+        cursor: Arg1,
+        page_size: Arg2,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, MultiValue2<u64, MultiValueEncoded<Env::Api, u32>>> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("getSellerCandleAuctions")
+            .argument(&seller)
+            .argument(&cursor)
+            .argument(&page_size)
+            .original_result()
+    }
+
+    pub fn cleanup_listings<
+        Arg0: ProxyArg<MultiValueEncoded<Env::Api, u32>>,
+    >(
+        self,
+        listing_ids: Arg0,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, u32> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("cleanupListings")
+            .argument(&listing_ids)
+            .original_result()
+    }
+
+    pub fn cleanup_auctions<
+        Arg0: ProxyArg<MultiValueEncoded<Env::Api, u32>>,
+    >(
+        self,
+        auction_ids: Arg0,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, u32> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("cleanupAuctions")
+            .argument(&auction_ids)
+            .original_result()
+    }
+
+    pub fn sync_listing_states<
+        Arg0: ProxyArg<MultiValueEncoded<Env::Api, u32>>,
+    >(
+        self,
+        listing_ids: Arg0,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, u32> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("syncListingStates")
+            .argument(&listing_ids)
+            .original_result()
+    }
+
+    pub fn sync_auction_states<
+        Arg0: ProxyArg<MultiValueEncoded<Env::Api, u32>>,
+    >(
+        self,
+        auction_ids: Arg0,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, u32> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("syncAuctionStates")
+            .argument(&auction_ids)
+            .original_result()
+    }
+
+    pub fn get_event_schema_version(
+        self,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, u32> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("getEventSchemaVersion")
+            .original_result()
+    }
+
+    pub fn make_offer<
+        Arg0: ProxyArg<u32>,
+        Arg1: ProxyArg<TokenIdentifier<Env::Api>>,
+        Arg2: ProxyArg<u64>,
+        Arg3: ProxyArg<u64>,
+    >(
+        self,
+        wine_nft_id: Arg0,
+        nft_token_id: Arg1,
+        nft_nonce: Arg2,
+        duration_seconds: Arg3,
+    ) -> TxTypedCall<Env, From, To, (), Gas, u32> {
+        self.wrapped_tx
+            .raw_call("makeOffer")
+            .argument(&wine_nft_id)
+            .argument(&nft_token_id)
+            .argument(&nft_nonce)
+            .argument(&duration_seconds)
+            .original_result()
+    }
+
+    pub fn accept_offer<
+        Arg0: ProxyArg<u32>,
+    >(
+        self,
+        offer_id: Arg0,
+    ) -> TxTypedCall<Env, From, To, (), Gas, ()> {
+        self.wrapped_tx
+            .raw_call("acceptOffer")
+            .argument(&offer_id)
+            .original_result()
+    }
+
+    pub fn cancel_offer<
+        Arg0: ProxyArg<u32>,
+    >(
+        self,
+        offer_id: Arg0,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, ()> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("cancelOffer")
+            .argument(&offer_id)
+            .original_result()
+    }
+
+    pub fn get_offer<
+        Arg0: ProxyArg<u32>,
+    >(
+        self,
+        offer_id: Arg0,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, Offer<Env::Api>> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("getOffer")
+            .argument(&offer_id)
+            .original_result()
+    }
+
+    pub fn get_buyer_offers<
+        Arg0: ProxyArg<ManagedAddress<Env::Api>>,
+        Arg1: ProxyArg<u64>,
+        Arg2: ProxyArg<u32>,
+    >(
+        self,
+        buyer: Arg0,
+        cursor: Arg1,
+        page_size: Arg2,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, MultiValue2<u64, MultiValueEncoded<Env::Api, u32>>> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("getBuyerOffers")
+            .argument(&buyer)
+            .argument(&cursor)
+            .argument(&page_size)
+            .original_result()
+    }
+
+    pub fn quote_buy<
+        Arg0: ProxyArg<u32>,
+        Arg1: ProxyArg<ManagedAddress<Env::Api>>,
+        Arg2: ProxyArg<OptionalValue<EgldOrEsdtTokenIdentifier<Env::Api>>>,
+    >(
+        self,
+        listing_id: Arg0,
+        payer: Arg1,
+        opt_payment_token: Arg2,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, SaleQuote<Env::Api>> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("quoteBuy")
+            .argument(&listing_id)
+            .argument(&payer)
+            .argument(&opt_payment_token)
+            .original_result()
+    }
+
+    pub fn quote_next_bid<
+        Arg0: ProxyArg<u32>,
+    >(
+        self,
+        auction_id: Arg0,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, SaleQuote<Env::Api>> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("quoteNextBid")
+            .argument(&auction_id)
+            .original_result()
+    }
+
+    pub fn add_relayer<
+        Arg0: ProxyArg<ManagedAddress<Env::Api>>,
+    >(
+        self,
+        relayer: Arg0,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, ()> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("addRelayer")
+            .argument(&relayer)
+            .original_result()
+    }
+
+    pub fn remove_relayer<
+        Arg0: ProxyArg<ManagedAddress<Env::Api>>,
+    >(
+        self,
+        relayer: Arg0,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, ()> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("removeRelayer")
+            .argument(&relayer)
+            .original_result()
+    }
+
+    pub fn deposit_relay_funds<
+        Arg0: ProxyArg<OptionalValue<ManagedAddress<Env::Api>>>,
+    >(
+        self,
+        opt_beneficiary: Arg0,
+    ) -> TxTypedCall<Env, From, To, (), Gas, ()> {
+        self.wrapped_tx
+            .raw_call("depositRelayFunds")
+            .argument(&opt_beneficiary)
+            .original_result()
+    }
+
+    pub fn withdraw_relay_funds<
+        Arg0: ProxyArg<EgldOrEsdtTokenIdentifier<Env::Api>>,
+    >(
+        self,
+        token: Arg0,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, ()> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("withdrawRelayFunds")
+            .argument(&token)
+            .original_result()
+    }
+
+    pub fn execute_purchase_intent<
+        Arg0: ProxyArg<PurchaseIntent<Env::Api>>,
+        Arg1: ProxyArg<ManagedBuffer<Env::Api>>,
+    >(
+        self,
+        intent: Arg0,
+        signature: Arg1,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, ()> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("executePurchaseIntent")
+            .argument(&intent)
+            .argument(&signature)
+            .original_result()
+    }
+
+    pub fn execute_bid_intent<
+        Arg0: ProxyArg<BidIntent<Env::Api>>,
+        Arg1: ProxyArg<ManagedBuffer<Env::Api>>,
+    >(
+        self,
+        intent: Arg0,
+        signature: Arg1,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, ()> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("executeBidIntent")
+            .argument(&intent)
+            .argument(&signature)
+            .original_result()
+    }
+
+    pub fn get_purchase_intent_message<
+        Arg0: ProxyArg<PurchaseIntent<Env::Api>>,
+    >(
+        self,
+        intent: Arg0,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, ManagedBuffer<Env::Api>> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("getPurchaseIntentMessage")
+            .argument(&intent)
+            .original_result()
+    }
+
+    pub fn get_bid_intent_message<
+        Arg0: ProxyArg<BidIntent<Env::Api>>,
+    >(
+        self,
+        intent: Arg0,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, ManagedBuffer<Env::Api>> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("getBidIntentMessage")
+            .argument(&intent)
+            .original_result()
+    }
+
+    pub fn get_relayers(
+        self,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, MultiValueEncoded<Env::Api, ManagedAddress<Env::Api>>> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("getRelayers")
+            .original_result()
+    }
+
+    pub fn is_relayer<
+        Arg0: ProxyArg<ManagedAddress<Env::Api>>,
+    >(
+        self,
+        address: Arg0,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, bool> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("isRelayer")
+            .argument(&address)
+            .original_result()
+    }
+
+    pub fn get_relay_balance<
+        Arg0: ProxyArg<ManagedAddress<Env::Api>>,
+        Arg1: ProxyArg<EgldOrEsdtTokenIdentifier<Env::Api>>,
+    >(
+        self,
+        user: Arg0,
+        token: Arg1,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, BigUint<Env::Api>> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("getRelayBalance")
+            .argument(&user)
+            .argument(&token)
+            .original_result()
+    }
+
+    pub fn get_relay_nonce<
+        Arg0: ProxyArg<ManagedAddress<Env::Api>>,
+    >(
+        self,
+        user: Arg0,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, u64> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("getRelayNonce")
+            .argument(&user)
+            .original_result()
+    }
+
+    pub fn get_health(
+        self,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, MarketplaceHealth<Env::Api>> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("getHealth")
+            .original_result()
+    }
+
+    pub fn get_page_size_limits(
+        self,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, MultiValueEncoded<Env::Api, MultiValue2<ManagedBuffer<Env::Api>, u32>>> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("getPageSizeLimits")
+            .original_result()
+    }
+
+    pub fn sync_wine_read_model<
+        Arg0: ProxyArg<u32>,
+        Arg1: ProxyArg<wine_common::registry::WineReadModel<Env::Api>>,
+    >(
+        self,
+        wine_id: Arg0,
+        read_model: Arg1,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, ()> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("syncWineReadModel")
+            .argument(&wine_id)
+            .argument(&read_model)
+            .original_result()
+    }
+
+    pub fn sync_producer_verification<
+        Arg0: ProxyArg<ManagedAddress<Env::Api>>,
+        Arg1: ProxyArg<bool>,
+    >(
+        self,
+        producer: Arg0,
+        verified: Arg1,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, ()> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("syncProducerVerification")
+            .argument(&producer)
+            .argument(&verified)
+            .original_result()
+    }
+
+    pub fn sync_grade_price_floor<
+        Arg0: ProxyArg<u8>,
+        Arg1: ProxyArg<BigUint<Env::Api>>,
+    >(
+        self,
+        quality_grade: Arg0,
+        min_price: Arg1,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, ()> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("syncGradePriceFloor")
+            .argument(&quality_grade)
+            .argument(&min_price)
+            .original_result()
+    }
+
+    pub fn set_enforce_price_floors<
+        Arg0: ProxyArg<bool>,
+    >(
+        self,
+        enforced: Arg0,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, ()> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("setEnforcePriceFloors")
+            .argument(&enforced)
+            .original_result()
+    }
+
+    pub fn set_require_verified_producers<
+        Arg0: ProxyArg<bool>,
+    >(
+        self,
+        required: Arg0,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, ()> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("setRequireVerifiedProducers")
+            .argument(&required)
+            .original_result()
+    }
+
+    pub fn set_certified_only_auctions<
+        Arg0: ProxyArg<bool>,
+        Arg1: ProxyArg<OptionalValue<TokenIdentifier<Env::Api>>>,
+    >(
+        self,
+        enabled: Arg0,
+        opt_collection: Arg1,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, ()> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("setCertifiedOnlyAuctions")
+            .argument(&enabled)
+            .argument(&opt_collection)
+            .original_result()
+    }
+
+    pub fn set_wine_collection<
+        Arg0: ProxyArg<TokenIdentifier<Env::Api>>,
+    >(
+        self,
+        collection: Arg0,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, ()> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("setWineCollection")
+            .argument(&collection)
+            .original_result()
+    }
+
+    pub fn get_wine_collection(
+        self,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, OptionalValue<TokenIdentifier<Env::Api>>> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("getWineCollection")
+            .original_result()
+    }
+
+    pub fn get_cached_wine_read_model<
+        Arg0: ProxyArg<u32>,
+    >(
+        self,
+        wine_id: Arg0,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, OptionalValue<wine_common::registry::WineReadModel<Env::Api>>> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("getCachedWineReadModel")
+            .argument(&wine_id)
+            .original_result()
+    }
+
+    pub fn is_producer_verified<
+        Arg0: ProxyArg<ManagedAddress<Env::Api>>,
+    >(
+        self,
+        producer: Arg0,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, bool> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("isProducerVerified")
+            .argument(&producer)
+            .original_result()
+    }
+
+    pub fn get_require_verified_producers(
+        self,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, bool> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("getRequireVerifiedProducers")
+            .original_result()
+    }
+
+    pub fn get_wine_price_floor<
+        Arg0: ProxyArg<u32>,
+    >(
+        self,
+        wine_id: Arg0,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, BigUint<Env::Api>> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("getWinePriceFloor")
+            .argument(&wine_id)
+            .original_result()
+    }
+
+    pub fn get_grade_price_floor<
+        Arg0: ProxyArg<u8>,
+    >(
+        self,
+        quality_grade: Arg0,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, BigUint<Env::Api>> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("getGradePriceFloor")
+            .argument(&quality_grade)
+            .original_result()
+    }
+
+    pub fn get_enforce_price_floors(
+        self,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, bool> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("getEnforcePriceFloors")
+            .original_result()
+    }
+
+    pub fn get_certified_only_auctions(
+        self,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, MultiValue2<bool, MultiValueEncoded<Env::Api, TokenIdentifier<Env::Api>>>> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("getCertifiedOnlyAuctions")
+            .original_result()
+    }
+
+    pub fn commit_entropy<
+        Arg0: ProxyArg<ManagedBuffer<Env::Api>>,
+        Arg1: ProxyArg<u64>,
+        Arg2: ProxyArg<ManagedByteArray<Env::Api, 32>>,
+    >(
+        self,
+        domain: Arg0,
+        context_id: Arg1,
+        commitment: Arg2,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, ()> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("commitEntropy")
+            .argument(&domain)
+            .argument(&context_id)
+            .argument(&commitment)
+            .original_result()
+    }
+
+    pub fn reveal_entropy<
+        Arg0: ProxyArg<ManagedBuffer<Env::Api>>,
+        Arg1: ProxyArg<u64>,
+        Arg2: ProxyArg<ManagedBuffer<Env::Api>>,
+    >(
+        self,
+        domain: Arg0,
+        context_id: Arg1,
+        preimage: Arg2,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, ()> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("revealEntropy")
+            .argument(&domain)
+            .argument(&context_id)
+            .argument(&preimage)
+            .original_result()
+    }
+
+    pub fn get_entropy_contribution_count<
+        Arg0: ProxyArg<ManagedBuffer<Env::Api>>,
+        Arg1: ProxyArg<u64>,
+    >(
+        self,
+        domain: Arg0,
+        context_id: Arg1,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, u32> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("getEntropyContributionCount")
+            .argument(&domain)
+            .argument(&context_id)
+            .original_result()
+    }
+
+    pub fn is_entropy_sealed<
+        Arg0: ProxyArg<ManagedBuffer<Env::Api>>,
+        Arg1: ProxyArg<u64>,
+    >(
+        self,
+        domain: Arg0,
+        context_id: Arg1,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, bool> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("isEntropySealed")
+            .argument(&domain)
+            .argument(&context_id)
+            .original_result()
+    }
+
+    pub fn get_random_draw_nonce<
+        Arg0: ProxyArg<ManagedBuffer<Env::Api>>,
+    >(
+        self,
+        domain: Arg0,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, u64> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("getRandomDrawNonce")
+            .argument(&domain)
+            .original_result()
+    }
+
+    pub fn grant_role<
+        Arg0: ProxyArg<wine_roles::Role>,
+        Arg1: ProxyArg<ManagedAddress<Env::Api>>,
+    >(
+        self,
+        role: Arg0,
+        address: Arg1,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, ()> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("grantRole")
+            .argument(&role)
+            .argument(&address)
+            .original_result()
+    }
+
+    pub fn revoke_role<
+        Arg0: ProxyArg<wine_roles::Role>,
+        Arg1: ProxyArg<ManagedAddress<Env::Api>>,
+    >(
+        self,
+        role: Arg0,
+        address: Arg1,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, ()> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("revokeRole")
+            .argument(&role)
+            .argument(&address)
+            .original_result()
+    }
+
+    pub fn pause(
+        self,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, ()> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("pause")
+            .original_result()
+    }
+
+    pub fn unpause(
+        self,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, ()> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("unpause")
+            .original_result()
+    }
+
+    pub fn has_role<
+        Arg0: ProxyArg<wine_roles::Role>,
+        Arg1: ProxyArg<ManagedAddress<Env::Api>>,
+    >(
+        self,
+        role: Arg0,
+        address: Arg1,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, bool> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("hasRole")
+            .argument(&role)
+            .argument(&address)
+            .original_result()
+    }
+
+    pub fn get_role_members<
+        Arg0: ProxyArg<wine_roles::Role>,
+    >(
+        self,
+        role: Arg0,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, MultiValueEncoded<Env::Api, ManagedAddress<Env::Api>>> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("getRoleMembers")
+            .argument(&role)
+            .original_result()
+    }
+
+    pub fn is_paused(
+        self,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, bool> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("isPaused")
+            .original_result()
+    }
+}
+
+#[type_abi]
+#[derive(TopEncode, TopDecode, PartialEq, Debug)]
+pub struct CollectorStats<Api>
+where
+    Api: ManagedTypeApi,
+{
+    pub purchase_count: u32,
+    pub purchase_volume: BigUint<Api>,
+    pub holdings: u32,
+}
+
+#[type_abi]
+#[derive(TopEncode, TopDecode, PartialEq, Debug)]
+pub struct CollectorTierThresholds<Api>
+where
+    Api: ManagedTypeApi,
+{
+    pub silver_volume: BigUint<Api>,
+    pub silver_holdings: u32,
+    pub gold_volume: BigUint<Api>,
+    pub gold_holdings: u32,
+}
+
+#[type_abi]
+#[derive(TopEncode, TopDecode, PartialEq, Debug)]
+pub struct FeeDiscount {
+    pub discount_bps: u64,
+    pub expiry_timestamp: u64,
+}
+
+#[type_abi]
+#[derive(TopEncode, TopDecode, PartialEq, Debug)]
+pub struct StatsSnapshot<Api>
+where
+    Api: ManagedTypeApi,
+{
+    pub stats: MarketplaceStats<Api>,
+    pub collection_count: u32,
+    pub root: ManagedByteArray<Api, 32usize>,
+}
+
+#[type_abi]
+#[derive(TopEncode, TopDecode, NestedEncode, NestedDecode, PartialEq, Debug, Clone)]
+pub struct MarketplaceStats<Api>
+where
+    Api: ManagedTypeApi,
+{
+    pub total_listings: u32,
+    pub total_sales: u32,
+    pub total_volume: BigUint<Api>,
+    pub total_fees_collected: BigUint<Api>,
+    pub active_listings: u32,
+    pub active_auctions: u32,
+}
+
+#[type_abi]
+#[derive(TopEncode, TopDecode, NestedEncode, NestedDecode, PartialEq, Debug, Clone)]
+pub struct FeePromotion {
+    pub fee_percent: u64,
+    pub start_timestamp: u64,
+    pub end_timestamp: u64,
+    pub max_sales: u32,
+    pub sales_count: u32,
+}
+
+#[type_abi]
+#[derive(TopEncode, TopDecode, NestedEncode, NestedDecode, PartialEq, Debug)]
+pub struct FeeShare<Api>
+where
+    Api: ManagedTypeApi,
+{
+    pub recipient: ManagedAddress<Api>,
+    pub share_percent: u64,
+}
+
+#[type_abi]
+#[derive(TopEncode, TopDecode, PartialEq, Debug)]
+pub struct ProtectedSale<Api>
+where
+    Api: ManagedTypeApi,
+{
+    pub wine_nft_id: u32,
+    pub nft_token_id: TokenIdentifier<Api>,
+    pub nft_nonce: u64,
+    pub seller: ManagedAddress<Api>,
+    pub buyer: ManagedAddress<Api>,
+    pub recipient: ManagedAddress<Api>,
+    pub payment_token: EgldOrEsdtTokenIdentifier<Api>,
+    pub amount: BigUint<Api>,
+    pub purchase_timestamp: u64,
+    pub release_timestamp: u64,
+    pub status: ProtectedSaleStatus,
+}
+
+#[type_abi]
+#[derive(TopEncode, TopDecode, NestedEncode, NestedDecode, PartialEq, Eq, Debug, Clone, Copy)]
+pub enum ProtectedSaleStatus {
+    Escrowed,
+    Disputed,
+    Released,
+    Refunded,
+}
+
+#[type_abi]
+#[derive(TopEncode, TopDecode, PartialEq, Debug)]
+pub struct ExchangeRate<Api>
+where
+    Api: ManagedTypeApi,
+{
+    pub rate: BigUint<Api>,
+    pub updated_timestamp: u64,
+}
+
+#[type_abi]
+#[derive(TopEncode, TopDecode, NestedEncode, NestedDecode, PartialEq, Debug)]
+pub struct MarketSummary<Api>
+where
+    Api: ManagedTypeApi,
+{
+    pub payment_token: EgldOrEsdtTokenIdentifier<Api>,
+    pub best_bid: BigUint<Api>,
+    pub best_bid_offer_id: u32,
+    pub best_ask: BigUint<Api>,
+    pub best_ask_listing_id: u32,
+    pub last_sale_price: BigUint<Api>,
+    pub last_sale_timestamp: u64,
+    pub volume_30d: BigUint<Api>,
+}
+
+#[type_abi]
+#[derive(TopEncode, TopDecode, NestedEncode, NestedDecode, PartialEq, Eq, Debug, Clone, Copy)]
+pub enum MixedSettlement {
+    Disabled,
+    DirectProceeds,
+    ClaimableProceeds,
+}
+
+#[type_abi]
+#[derive(TopEncode, TopDecode, PartialEq, Debug)]
+pub struct InstallmentPlan<Api>
+where
+    Api: ManagedTypeApi,
+{
+    pub buyer: ManagedAddress<Api>,
+    pub payment_token: EgldOrEsdtTokenIdentifier<Api>,
+    pub total_price: BigUint<Api>,
+    pub amount_paid: BigUint<Api>,
+    pub created_timestamp: u64,
+    pub deadline: u64,
+}
+
+#[type_abi]
+#[derive(TopEncode, TopDecode, PartialEq, Debug)]
+pub struct InstallmentConfig {
+    pub min_deposit_percent: u64,
+    pub penalty_percent: u64,
+    pub max_duration: u64,
+}
+
+#[type_abi]
+#[derive(TopEncode, TopDecode, NestedEncode, NestedDecode, PartialEq, Debug)]
+pub struct AuctionResultView<Api>
+where
+    Api: ManagedTypeApi,
+{
+    pub kind: AuctionKind,
+    pub auction_id: u32,
+    pub wine_nft_id: u32,
+    pub seller: ManagedAddress<Api>,
+    pub winner: ManagedAddress<Api>,
+    pub winner_private: bool,
+    pub payment_token: EgldOrEsdtTokenIdentifier<Api>,
+    pub final_price: BigUint<Api>,
+    pub timestamp: u64,
+}
+
+#[type_abi]
+#[derive(TopEncode, TopDecode, NestedEncode, NestedDecode, PartialEq, Debug, Clone, Copy)]
+pub enum AuctionKind {
+    English,
+    Candle,
+}
+
+#[type_abi]
+#[derive(TopEncode, TopDecode, PartialEq, Debug)]
+pub struct AuctionView<Api>
+where
+    Api: ManagedTypeApi,
+{
+    pub auction_id: u32,
+    pub wine_nft_id: u32,
+    pub seller: ManagedAddress<Api>,
+    pub payment_token: EgldOrEsdtTokenIdentifier<Api>,
+    pub current_bid: BigUint<Api>,
+    pub highest_bidder: ManagedAddress<Api>,
+    pub bid_count: u32,
+    pub min_next_bid: BigUint<Api>,
+    pub end_timestamp: u64,
+    pub time_left: u64,
+    pub extension_count: u32,
+    pub anti_snipe_active: bool,
+    pub active: bool,
+}
+
+#[type_abi]
+#[derive(TopEncode, TopDecode, PartialEq, Debug, Clone)]
+pub struct AntiSnipeConfig {
+    pub extension_window: u64,
+    pub extension_duration: u64,
+    pub max_extensions: u32,
+}
+
+#[type_abi]
+#[derive(TopEncode, TopDecode, PartialEq, Debug)]
+pub struct CandleAuction<Api>
+where
+    Api: ManagedTypeApi,
+{
+    pub wine_nft_id: u32,
+    pub nft_token_id: TokenIdentifier<Api>,
+    pub nft_nonce: u64,
+    pub seller: ManagedAddress<Api>,
+    pub starting_price: BigUint<Api>,
+    pub min_bid_increment: BigUint<Api>,
+    pub payment_token: EgldOrEsdtTokenIdentifier<Api>,
+    pub start_timestamp: u64,
+    pub closing_window_start: u64,
+    pub end_timestamp: u64,
+    pub effective_end_timestamp: u64,
+    pub highest_bid: BigUint<Api>,
+    pub highest_bidder: ManagedAddress<Api>,
+    pub bid_count: u32,
+    pub active: bool,
+}
+
+#[type_abi]
+#[derive(TopEncode, TopDecode, NestedEncode, NestedDecode, PartialEq, Debug)]
+pub struct CandleBid<Api>
+where
+    Api: ManagedTypeApi,
+{
+    pub bidder: ManagedAddress<Api>,
+    pub amount: BigUint<Api>,
+    pub timestamp: u64,
+}
+
+#[type_abi]
+#[derive(TopEncode, TopDecode, PartialEq, Debug)]
+pub struct Offer<Api>
+where
+    Api: ManagedTypeApi,
+{
+    pub wine_nft_id: u32,
+    pub nft_token_id: TokenIdentifier<Api>,
+    pub nft_nonce: u64,
+    pub buyer: ManagedAddress<Api>,
+    pub amount: BigUint<Api>,
+    pub payment_token: EgldOrEsdtTokenIdentifier<Api>,
+    pub deadline: u64,
+    pub active: bool,
+}
+
+#[type_abi]
+#[derive(TopEncode, TopDecode, PartialEq, Debug)]
+pub struct SaleQuote<Api>
+where
+    Api: ManagedTypeApi,
+{
+    pub payment_token: EgldOrEsdtTokenIdentifier<Api>,
+    pub amount_due: BigUint<Api>,
+    pub fee_percent: u64,
+    pub fee_discount_bps: u64,
+    pub marketplace_fee: BigUint<Api>,
+    pub royalty: BigUint<Api>,
+    pub seller_amount: BigUint<Api>,
+}
+
+#[type_abi]
+#[derive(TopEncode, TopDecode, NestedEncode, NestedDecode, PartialEq, Debug, Clone)]
+pub struct PurchaseIntent<Api>
+where
+    Api: ManagedTypeApi,
+{
+    pub buyer: ManagedAddress<Api>,
+    pub listing_id: u32,
+    pub max_price: BigUint<Api>,
+    pub nonce: u64,
+    pub deadline: u64,
+}
+
+#[type_abi]
+#[derive(TopEncode, TopDecode, NestedEncode, NestedDecode, PartialEq, Debug, Clone)]
+pub struct BidIntent<Api>
+where
+    Api: ManagedTypeApi,
+{
+    pub bidder: ManagedAddress<Api>,
+    pub auction_id: u32,
+    pub amount: BigUint<Api>,
+    pub nonce: u64,
+    pub deadline: u64,
+}
+
+#[type_abi]
+#[derive(TopEncode, TopDecode, PartialEq, Debug)]
+pub struct MarketplaceHealth<Api>
+where
+    Api: ManagedTypeApi,
+{
+    pub contract_version: ManagedBuffer<Api>,
+    pub storage_version: u32,
+    pub migration_pending: bool,
+    pub paused: bool,
+    pub migration_cursor: u32,
+    pub expired_listings: u32,
+    pub unsettled_auctions: u32,
+    pub unsettled_candle_auctions: u32,
+    pub releasable_protected_sales: u32,
+    pub defaulted_installments: u32,
+    pub wine_registry: ManagedAddress<Api>,
+    pub treasury: ManagedAddress<Api>,
+    pub rate_oracle: Option<ManagedAddress<Api>>,
+    pub arbiter: Option<ManagedAddress<Api>>,
+}
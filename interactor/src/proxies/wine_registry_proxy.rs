@@ -0,0 +1,2882 @@
+// Code generated by the multiversx-sc proxy generator. DO NOT EDIT.
+
+////////////////////////////////////////////////////
+////////////////// AUTO-GENERATED //////////////////
+////////////////////////////////////////////////////
+
+#![allow(dead_code)]
+#![allow(clippy::all)]
+
+use multiversx_sc::proxy_imports::*;
+
+pub struct WineRegistryProxy;
+
+impl<Env, From, To, Gas> TxProxyTrait<Env, From, To, Gas> for WineRegistryProxy
+where
+    Env: TxEnv,
+    From: TxFrom<Env>,
+    To: TxTo<Env>,
+    Gas: TxGas<Env>,
+{
+    type TxProxyMethods = WineRegistryProxyMethods<Env, From, To, Gas>;
+
+    fn proxy_methods(self, tx: Tx<Env, From, To, (), Gas, (), ()>) -> Self::TxProxyMethods {
+        WineRegistryProxyMethods { wrapped_tx: tx }
+    }
+}
+
+pub struct WineRegistryProxyMethods<Env, From, To, Gas>
+where
+    Env: TxEnv,
+    From: TxFrom<Env>,
+    To: TxTo<Env>,
+    Gas: TxGas<Env>,
+{
+    wrapped_tx: Tx<Env, From, To, (), Gas, (), ()>,
+}
+
+#[rustfmt::skip]
+impl<Env, From, Gas> WineRegistryProxyMethods<Env, From, (), Gas>
+where
+    Env: TxEnv,
+    Env::Api: VMApi,
+    From: TxFrom<Env>,
+    Gas: TxGas<Env>,
+{
+    pub fn init<
+        Arg0: ProxyArg<BigUint<Env::Api>>,
+    >(
+        self,
+        registration_fee: Arg0,
+    ) -> TxTypedDeploy<Env, From, NotPayable, Gas, ()> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_deploy()
+            .argument(&registration_fee)
+            .original_result()
+    }
+}
+
+#[rustfmt::skip]
+impl<Env, From, To, Gas> WineRegistryProxyMethods<Env, From, To, Gas>
+where
+    Env: TxEnv,
+    Env::Api: VMApi,
+    From: TxFrom<Env>,
+    To: TxTo<Env>,
+    Gas: TxGas<Env>,
+{
+    pub fn upgrade(
+        self,
+    ) -> TxTypedUpgrade<Env, From, To, NotPayable, Gas, ()> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_upgrade()
+            .original_result()
+    }
+}
+
+#[rustfmt::skip]
+impl<Env, From, To, Gas> WineRegistryProxyMethods<Env, From, To, Gas>
+where
+    Env: TxEnv,
+    Env::Api: VMApi,
+    From: TxFrom<Env>,
+    To: TxTo<Env>,
+    Gas: TxGas<Env>,
+{
+    pub fn set_integration_permissions<
+        Arg0: ProxyArg<ManagedAddress<Env::Api>>,
+        Arg1: ProxyArg<bool>,
+        Arg2: ProxyArg<bool>,
+        Arg3: ProxyArg<bool>,
+    >(
+        self,
+        integration: Arg0,
+        can_decrement_availability: Arg1,
+        can_record_sale: Arg2,
+        can_flag: Arg3,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, ()> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("setIntegrationPermissions")
+            .argument(&integration)
+            .argument(&can_decrement_availability)
+            .argument(&can_record_sale)
+            .argument(&can_flag)
+            .original_result()
+    }
+
+    pub fn revoke_integration_permissions<
+        Arg0: ProxyArg<ManagedAddress<Env::Api>>,
+    >(
+        self,
+        integration: Arg0,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, ()> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("revokeIntegrationPermissions")
+            .argument(&integration)
+            .original_result()
+    }
+
+    pub fn get_integration_permissions<
+        Arg0: ProxyArg<ManagedAddress<Env::Api>>,
+    >(
+        self,
+        integration: Arg0,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, IntegrationPermissions> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("getIntegrationPermissions")
+            .argument(&integration)
+            .original_result()
+    }
+
+    pub fn get_integrations<
+        Arg0: ProxyArg<u64>,
+        Arg1: ProxyArg<u32>,
+    >(
+        self,
+        cursor: Arg0,
+        page_size: Arg1,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, MultiValue2<u64, MultiValueEncoded<Env::Api, MultiValue2<ManagedAddress<Env::Api>, IntegrationPermissions>>>> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("getIntegrations")
+            .argument(&cursor)
+            .argument(&page_size)
+            .original_result()
+    }
+
+    pub fn transfer_wine_ownership<
+        Arg0: ProxyArg<u32>,
+        Arg1: ProxyArg<ManagedAddress<Env::Api>>,
+        Arg2: ProxyArg<OwnershipChangeReason>,
+    >(
+        self,
+        wine_id: Arg0,
+        new_owner: Arg1,
+        reason: Arg2,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, ()> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("transferWineOwnership")
+            .argument(&wine_id)
+            .argument(&new_owner)
+            .argument(&reason)
+            .original_result()
+    }
+
+    pub fn lock_wine<
+        Arg0: ProxyArg<u32>,
+        Arg1: ProxyArg<ManagedAddress<Env::Api>>,
+    >(
+        self,
+        wine_id: Arg0,
+        holder: Arg1,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, ()> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("lockWine")
+            .argument(&wine_id)
+            .argument(&holder)
+            .original_result()
+    }
+
+    pub fn unlock_wine<
+        Arg0: ProxyArg<u32>,
+        Arg1: ProxyArg<ManagedAddress<Env::Api>>,
+    >(
+        self,
+        wine_id: Arg0,
+        holder: Arg1,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, ()> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("unlockWine")
+            .argument(&wine_id)
+            .argument(&holder)
+            .original_result()
+    }
+
+    pub fn add_co_owner<
+        Arg0: ProxyArg<u32>,
+        Arg1: ProxyArg<ManagedAddress<Env::Api>>,
+        Arg2: ProxyArg<u64>,
+    >(
+        self,
+        wine_id: Arg0,
+        co_owner: Arg1,
+        share_bps: Arg2,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, ()> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("addCoOwner")
+            .argument(&wine_id)
+            .argument(&co_owner)
+            .argument(&share_bps)
+            .original_result()
+    }
+
+    pub fn remove_co_owner<
+        Arg0: ProxyArg<u32>,
+        Arg1: ProxyArg<ManagedAddress<Env::Api>>,
+    >(
+        self,
+        wine_id: Arg0,
+        co_owner: Arg1,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, ()> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("removeCoOwner")
+            .argument(&wine_id)
+            .argument(&co_owner)
+            .original_result()
+    }
+
+    pub fn set_approval_threshold<
+        Arg0: ProxyArg<u32>,
+        Arg1: ProxyArg<SensitiveAction>,
+        Arg2: ProxyArg<u64>,
+    >(
+        self,
+        wine_id: Arg0,
+        action: Arg1,
+        threshold_bps: Arg2,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, ()> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("setApprovalThreshold")
+            .argument(&wine_id)
+            .argument(&action)
+            .argument(&threshold_bps)
+            .original_result()
+    }
+
+    pub fn approve_wine_action<
+        Arg0: ProxyArg<u32>,
+        Arg1: ProxyArg<WineAction<Env::Api>>,
+    >(
+        self,
+        wine_id: Arg0,
+        action: Arg1,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, ()> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("approveWineAction")
+            .argument(&wine_id)
+            .argument(&action)
+            .original_result()
+    }
+
+    pub fn revoke_wine_action_approval<
+        Arg0: ProxyArg<u32>,
+        Arg1: ProxyArg<WineAction<Env::Api>>,
+    >(
+        self,
+        wine_id: Arg0,
+        action: Arg1,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, ()> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("revokeWineActionApproval")
+            .argument(&wine_id)
+            .argument(&action)
+            .original_result()
+    }
+
+    pub fn get_wine_owner<
+        Arg0: ProxyArg<u32>,
+    >(
+        self,
+        wine_id: Arg0,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, ManagedAddress<Env::Api>> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("getWineOwner")
+            .argument(&wine_id)
+            .original_result()
+    }
+
+    pub fn is_wine_locked<
+        Arg0: ProxyArg<u32>,
+    >(
+        self,
+        wine_id: Arg0,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, bool> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("isWineLocked")
+            .argument(&wine_id)
+            .original_result()
+    }
+
+    pub fn get_wine_lock_count<
+        Arg0: ProxyArg<u32>,
+    >(
+        self,
+        wine_id: Arg0,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, u32> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("getWineLockCount")
+            .argument(&wine_id)
+            .original_result()
+    }
+
+    pub fn get_ownership_table<
+        Arg0: ProxyArg<u32>,
+    >(
+        self,
+        wine_id: Arg0,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, MultiValueEncoded<Env::Api, MultiValue2<ManagedAddress<Env::Api>, u64>>> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("getOwnershipTable")
+            .argument(&wine_id)
+            .original_result()
+    }
+
+    pub fn get_approval_threshold<
+        Arg0: ProxyArg<u32>,
+        Arg1: ProxyArg<SensitiveAction>,
+    >(
+        self,
+        wine_id: Arg0,
+        action: Arg1,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, u64> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("getApprovalThreshold")
+            .argument(&wine_id)
+            .argument(&action)
+            .original_result()
+    }
+
+    pub fn get_wine_action_approvals<
+        Arg0: ProxyArg<u32>,
+        Arg1: ProxyArg<WineAction<Env::Api>>,
+    >(
+        self,
+        wine_id: Arg0,
+        action: Arg1,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, MultiValueEncoded<Env::Api, ManagedAddress<Env::Api>>> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("getWineActionApprovals")
+            .argument(&wine_id)
+            .argument(&action)
+            .original_result()
+    }
+
+    pub fn get_approved_share<
+        Arg0: ProxyArg<u32>,
+        Arg1: ProxyArg<WineAction<Env::Api>>,
+    >(
+        self,
+        wine_id: Arg0,
+        action: Arg1,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, u64> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("getApprovedShare")
+            .argument(&wine_id)
+            .argument(&action)
+            .original_result()
+    }
+
+    pub fn get_producer_wines<
+        Arg0: ProxyArg<ManagedAddress<Env::Api>>,
+        Arg1: ProxyArg<u64>,
+        Arg2: ProxyArg<u32>,
+    >(
+        self,
+        producer: Arg0,
+        cursor: Arg1,
+        page_size: Arg2,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, MultiValue2<u64, MultiValueEncoded<Env::Api, u32>>> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("getProducerWines")
+            .argument(&producer)
+            .argument(&cursor)
+            .argument(&page_size)
+            .original_result()
+    }
+
+    pub fn get_provenance<
+        Arg0: ProxyArg<u32>,
+        Arg1: ProxyArg<u64>,
+        Arg2: ProxyArg<u32>,
+    >(
+        self,
+        wine_id: Arg0,
+        cursor: Arg1,
+        page_size: Arg2,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, MultiValue2<u64, MultiValueEncoded<Env::Api, ProvenanceRecord<Env::Api>>>> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("getProvenance")
+            .argument(&wine_id)
+            .argument(&cursor)
+            .argument(&page_size)
+            .original_result()
+    }
+
+    pub fn add_read_model_subscriber<
+        Arg0: ProxyArg<ManagedAddress<Env::Api>>,
+    >(
+        self,
+        marketplace: Arg0,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, ()> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("addReadModelSubscriber")
+            .argument(&marketplace)
+            .original_result()
+    }
+
+    pub fn remove_read_model_subscriber<
+        Arg0: ProxyArg<ManagedAddress<Env::Api>>,
+    >(
+        self,
+        marketplace: Arg0,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, ()> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("removeReadModelSubscriber")
+            .argument(&marketplace)
+            .original_result()
+    }
+
+    pub fn set_wine_royalty<
+        Arg0: ProxyArg<u32>,
+        Arg1: ProxyArg<u32>,
+    >(
+        self,
+        wine_id: Arg0,
+        royalty_bps: Arg1,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, ()> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("setWineRoyalty")
+            .argument(&wine_id)
+            .argument(&royalty_bps)
+            .original_result()
+    }
+
+    pub fn resync_wine_read_model<
+        Arg0: ProxyArg<u32>,
+    >(
+        self,
+        wine_id: Arg0,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, ()> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("resyncWineReadModel")
+            .argument(&wine_id)
+            .original_result()
+    }
+
+    pub fn get_wine_read_model<
+        Arg0: ProxyArg<u32>,
+    >(
+        self,
+        wine_id: Arg0,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, wine_common::registry::WineReadModel<Env::Api>> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("getWineReadModel")
+            .argument(&wine_id)
+            .original_result()
+    }
+
+    pub fn get_read_model_subscribers(
+        self,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, MultiValueEncoded<Env::Api, ManagedAddress<Env::Api>>> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("getReadModelSubscribers")
+            .original_result()
+    }
+
+    pub fn set_registration_fee<
+        Arg0: ProxyArg<EgldOrEsdtTokenIdentifier<Env::Api>>,
+        Arg1: ProxyArg<BigUint<Env::Api>>,
+    >(
+        self,
+        token: Arg0,
+        amount: Arg1,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, ()> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("setRegistrationFee")
+            .argument(&token)
+            .argument(&amount)
+            .original_result()
+    }
+
+    pub fn set_producer_public_key<
+        Arg0: ProxyArg<ManagedByteArray<Env::Api, 32>>,
+    >(
+        self,
+        public_key: Arg0,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, ()> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("setProducerPublicKey")
+            .argument(&public_key)
+            .original_result()
+    }
+
+    pub fn set_require_producer_signatures<
+        Arg0: ProxyArg<bool>,
+    >(
+        self,
+        required: Arg0,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, ()> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("setRequireProducerSignatures")
+            .argument(&required)
+            .original_result()
+    }
+
+    pub fn set_registration_rate_limit<
+        Arg0: ProxyArg<u32>,
+    >(
+        self,
+        max_per_epoch: Arg0,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, ()> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("setRegistrationRateLimit")
+            .argument(&max_per_epoch)
+            .original_result()
+    }
+
+    pub fn set_fee_treasury<
+        Arg0: ProxyArg<ManagedAddress<Env::Api>>,
+    >(
+        self,
+        treasury: Arg0,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, ()> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("setFeeTreasury")
+            .argument(&treasury)
+            .original_result()
+    }
+
+    pub fn withdraw_collected_fees<
+        Arg0: ProxyArg<EgldOrEsdtTokenIdentifier<Env::Api>>,
+        Arg1: ProxyArg<BigUint<Env::Api>>,
+        Arg2: ProxyArg<ManagedAddress<Env::Api>>,
+    >(
+        self,
+        token: Arg0,
+        amount: Arg1,
+        to: Arg2,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, ()> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("withdrawCollectedFees")
+            .argument(&token)
+            .argument(&amount)
+            .argument(&to)
+            .original_result()
+    }
+
+    pub fn register_wine<
+        Arg0: ProxyArg<ManagedBuffer<Env::Api>>,
+        Arg1: ProxyArg<ManagedBuffer<Env::Api>>,
+        Arg2: ProxyArg<u32>,
+        Arg3: ProxyArg<u8>,
+        Arg4: ProxyArg<u16>,
+        Arg5: ProxyArg<ManagedBuffer<Env::Api>>,
+        Arg6: ProxyArg<ManagedBuffer<Env::Api>>,
+        Arg7: ProxyArg<ManagedBuffer<Env::Api>>,
+        Arg8: ProxyArg<ManagedBuffer<Env::Api>>,
+        Arg9: ProxyArg<u32>,
+        Arg10: ProxyArg<BigUint<Env::Api>>,
+        Arg11: ProxyArg<bool>,
+        Arg12: ProxyArg<u64>,
+        Arg13: ProxyArg<ManagedBuffer<Env::Api>>,
+        Arg14: ProxyArg<ManagedBuffer<Env::Api>>,
+    >(
+        self,
+        vineyard: Arg0,
+        variety: Arg1,
+        vintage_year: Arg2,
+        quality_grade: Arg3,
+        alcohol_content: Arg4,
+        region: Arg5,
+        certification: Arg6,
+        producer_signature: Arg7,
+        ipfs_hash: Arg8,
+        total_bottles: Arg9,
+        price_per_bottle: Arg10,
+        is_organic: Arg11,
+        harvest_date: Arg12,
+        aging_process: Arg13,
+        tasting_notes: Arg14,
+    ) -> TxTypedCall<Env, From, To, (), Gas, u32> {
+        self.wrapped_tx
+            .raw_call("registerWine")
+            .argument(&vineyard)
+            .argument(&variety)
+            .argument(&vintage_year)
+            .argument(&quality_grade)
+            .argument(&alcohol_content)
+            .argument(&region)
+            .argument(&certification)
+            .argument(&producer_signature)
+            .argument(&ipfs_hash)
+            .argument(&total_bottles)
+            .argument(&price_per_bottle)
+            .argument(&is_organic)
+            .argument(&harvest_date)
+            .argument(&aging_process)
+            .argument(&tasting_notes)
+            .original_result()
+    }
+
+    pub fn register_wines<
+        Arg0: ProxyArg<MultiValueEncoded<Env::Api, WineRegistration<Env::Api>>>,
+    >(
+        self,
+        registrations: Arg0,
+    ) -> TxTypedCall<Env, From, To, (), Gas, MultiValueEncoded<Env::Api, u32>> {
+        self.wrapped_tx
+            .raw_call("registerWines")
+            .argument(&registrations)
+            .original_result()
+    }
+
+    pub fn update_wine_details<
+        Arg0: ProxyArg<u32>,
+        Arg1: ProxyArg<ManagedBuffer<Env::Api>>,
+        Arg2: ProxyArg<ManagedBuffer<Env::Api>>,
+        Arg3: ProxyArg<ManagedBuffer<Env::Api>>,
+    >(
+        self,
+        wine_id: Arg0,
+        ipfs_hash: Arg1,
+        aging_process: Arg2,
+        tasting_notes: Arg3,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, u32> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("updateWineDetails")
+            .argument(&wine_id)
+            .argument(&ipfs_hash)
+            .argument(&aging_process)
+            .argument(&tasting_notes)
+            .original_result()
+    }
+
+    pub fn update_wine_price<
+        Arg0: ProxyArg<u32>,
+        Arg1: ProxyArg<BigUint<Env::Api>>,
+    >(
+        self,
+        wine_id: Arg0,
+        new_price: Arg1,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, ()> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("updateWinePrice")
+            .argument(&wine_id)
+            .argument(&new_price)
+            .original_result()
+    }
+
+    pub fn restock_wine<
+        Arg0: ProxyArg<u32>,
+        Arg1: ProxyArg<u32>,
+    >(
+        self,
+        wine_id: Arg0,
+        additional_bottles: Arg1,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, ()> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("restockWine")
+            .argument(&wine_id)
+            .argument(&additional_bottles)
+            .original_result()
+    }
+
+    pub fn update_wine_availability<
+        Arg0: ProxyArg<u32>,
+        Arg1: ProxyArg<u32>,
+    >(
+        self,
+        wine_id: Arg0,
+        bottles_sold: Arg1,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, ()> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("updateWineAvailability")
+            .argument(&wine_id)
+            .argument(&bottles_sold)
+            .original_result()
+    }
+
+    pub fn get_wine_details<
+        Arg0: ProxyArg<u32>,
+    >(
+        self,
+        wine_id: Arg0,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, wine_common::registry::WineDetails<Env::Api>> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("getWineDetails")
+            .argument(&wine_id)
+            .original_result()
+    }
+
+    pub fn get_wine_details_version<
+        Arg0: ProxyArg<u32>,
+    >(
+        self,
+        wine_id: Arg0,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, u32> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("getWineDetailsVersion")
+            .argument(&wine_id)
+            .original_result()
+    }
+
+    pub fn get_wine_details_history<
+        Arg0: ProxyArg<u32>,
+        Arg1: ProxyArg<u64>,
+        Arg2: ProxyArg<u32>,
+    >(
+        self,
+        wine_id: Arg0,
+        cursor: Arg1,
+        page_size: Arg2,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, MultiValue2<u64, MultiValueEncoded<Env::Api, WineDetailsVersion<Env::Api>>>> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("getWineDetailsHistory")
+            .argument(&wine_id)
+            .argument(&cursor)
+            .argument(&page_size)
+            .original_result()
+    }
+
+    pub fn get_price_history<
+        Arg0: ProxyArg<u32>,
+        Arg1: ProxyArg<u64>,
+        Arg2: ProxyArg<u32>,
+    >(
+        self,
+        wine_id: Arg0,
+        cursor: Arg1,
+        page_size: Arg2,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, MultiValue2<u64, MultiValueEncoded<Env::Api, WinePriceChange<Env::Api>>>> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("getPriceHistory")
+            .argument(&wine_id)
+            .argument(&cursor)
+            .argument(&page_size)
+            .original_result()
+    }
+
+    pub fn get_registration_fee_history<
+        Arg0: ProxyArg<u64>,
+        Arg1: ProxyArg<u32>,
+    >(
+        self,
+        cursor: Arg0,
+        page_size: Arg1,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, MultiValue2<u64, MultiValueEncoded<Env::Api, RegistrationFeeChange<Env::Api>>>> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("getRegistrationFeeHistory")
+            .argument(&cursor)
+            .argument(&page_size)
+            .original_result()
+    }
+
+    pub fn get_collected_fees<
+        Arg0: ProxyArg<EgldOrEsdtTokenIdentifier<Env::Api>>,
+    >(
+        self,
+        token: Arg0,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, MultiValue2<BigUint<Env::Api>, BigUint<Env::Api>>> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("getCollectedFees")
+            .argument(&token)
+            .original_result()
+    }
+
+    pub fn get_total_wines_registered(
+        self,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, u32> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("getTotalWinesRegistered")
+            .original_result()
+    }
+
+    pub fn get_registration_signing_message<
+        Arg0: ProxyArg<ManagedAddress<Env::Api>>,
+        Arg1: ProxyArg<WineRegistration<Env::Api>>,
+    >(
+        self,
+        producer: Arg0,
+        registration: Arg1,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, ManagedBuffer<Env::Api>> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("getRegistrationSigningMessage")
+            .argument(&producer)
+            .argument(&registration)
+            .original_result()
+    }
+
+    pub fn get_producer_public_key<
+        Arg0: ProxyArg<ManagedAddress<Env::Api>>,
+    >(
+        self,
+        producer: Arg0,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, OptionalValue<ManagedByteArray<Env::Api, 32>>> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("getProducerPublicKey")
+            .argument(&producer)
+            .original_result()
+    }
+
+    pub fn get_registration_rate_limit(
+        self,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, u32> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("getRegistrationRateLimit")
+            .original_result()
+    }
+
+    pub fn get_remaining_registrations<
+        Arg0: ProxyArg<ManagedAddress<Env::Api>>,
+    >(
+        self,
+        producer: Arg0,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, OptionalValue<u32>> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("getRemainingRegistrations")
+            .argument(&producer)
+            .original_result()
+    }
+
+    pub fn get_registration_fee(
+        self,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, BigUint<Env::Api>> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("getRegistrationFee")
+            .original_result()
+    }
+
+    pub fn get_registration_fees(
+        self,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, MultiValueEncoded<Env::Api, MultiValue2<EgldOrEsdtTokenIdentifier<Env::Api>, BigUint<Env::Api>>>> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("getRegistrationFees")
+            .original_result()
+    }
+
+    pub fn issue_wine_collection<
+        Arg0: ProxyArg<ManagedBuffer<Env::Api>>,
+        Arg1: ProxyArg<ManagedBuffer<Env::Api>>,
+    >(
+        self,
+        token_display_name: Arg0,
+        token_ticker: Arg1,
+    ) -> TxTypedCall<Env, From, To, (), Gas, ()> {
+        self.wrapped_tx
+            .raw_call("issueWineCollection")
+            .argument(&token_display_name)
+            .argument(&token_ticker)
+            .original_result()
+    }
+
+    pub fn mint_wine_nft<
+        Arg0: ProxyArg<u32>,
+        Arg1: ProxyArg<u32>,
+        Arg2: ProxyArg<MultiValueEncoded<Env::Api, ManagedByteArray<Env::Api, 32>>>,
+    >(
+        self,
+        wine_id: Arg0,
+        quantity: Arg1,
+        secret_hashes: Arg2,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, u64> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("mintWineNft")
+            .argument(&wine_id)
+            .argument(&quantity)
+            .argument(&secret_hashes)
+            .original_result()
+    }
+
+    pub fn authenticate_bottle<
+        Arg0: ProxyArg<u32>,
+        Arg1: ProxyArg<u32>,
+        Arg2: ProxyArg<ManagedBuffer<Env::Api>>,
+    >(
+        self,
+        wine_id: Arg0,
+        serial: Arg1,
+        preimage: Arg2,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, bool> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("authenticateBottle")
+            .argument(&wine_id)
+            .argument(&serial)
+            .argument(&preimage)
+            .original_result()
+    }
+
+    pub fn get_wine_collection(
+        self,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, OptionalValue<TokenIdentifier<Env::Api>>> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("getWineCollection")
+            .original_result()
+    }
+
+    pub fn get_minted_bottles<
+        Arg0: ProxyArg<u32>,
+    >(
+        self,
+        wine_id: Arg0,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, u32> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("getMintedBottles")
+            .argument(&wine_id)
+            .original_result()
+    }
+
+    pub fn get_wine_nft_nonce<
+        Arg0: ProxyArg<u32>,
+    >(
+        self,
+        wine_id: Arg0,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, u64> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("getWineNftNonce")
+            .argument(&wine_id)
+            .original_result()
+    }
+
+    pub fn get_wine_by_nonce<
+        Arg0: ProxyArg<u64>,
+    >(
+        self,
+        nonce: Arg0,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, u32> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("getWineByNonce")
+            .argument(&nonce)
+            .original_result()
+    }
+
+    pub fn get_bottle<
+        Arg0: ProxyArg<u32>,
+        Arg1: ProxyArg<u32>,
+    >(
+        self,
+        wine_id: Arg0,
+        serial: Arg1,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, Bottle> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("getBottle")
+            .argument(&wine_id)
+            .argument(&serial)
+            .original_result()
+    }
+
+    pub fn get_bottles_for_wine<
+        Arg0: ProxyArg<u32>,
+        Arg1: ProxyArg<u64>,
+        Arg2: ProxyArg<u32>,
+    >(
+        self,
+        wine_id: Arg0,
+        cursor: Arg1,
+        page_size: Arg2,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, MultiValue2<u64, MultiValueEncoded<Env::Api, Bottle>>> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("getBottlesForWine")
+            .argument(&wine_id)
+            .argument(&cursor)
+            .argument(&page_size)
+            .original_result()
+    }
+
+    pub fn get_bottle_scans<
+        Arg0: ProxyArg<u32>,
+        Arg1: ProxyArg<u32>,
+        Arg2: ProxyArg<u64>,
+        Arg3: ProxyArg<u32>,
+    >(
+        self,
+        wine_id: Arg0,
+        serial: Arg1,
+        cursor: Arg2,
+        page_size: Arg3,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, MultiValue2<u64, MultiValueEncoded<Env::Api, BottleScan<Env::Api>>>> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("getBottleScans")
+            .argument(&wine_id)
+            .argument(&serial)
+            .argument(&cursor)
+            .argument(&page_size)
+            .original_result()
+    }
+
+    pub fn fund_reward_pool(
+        self,
+    ) -> TxTypedCall<Env, From, To, (), Gas, ()> {
+        self.wrapped_tx
+            .raw_call("fundRewardPool")
+            .original_result()
+    }
+
+    pub fn settle_reward_points<
+        Arg0: ProxyArg<TokenIdentifier<Env::Api>>,
+        Arg1: ProxyArg<BigUint<Env::Api>>,
+        Arg2: ProxyArg<MultiValueEncoded<Env::Api, ManagedAddress<Env::Api>>>,
+    >(
+        self,
+        token: Arg0,
+        amount_per_point: Arg1,
+        holders: Arg2,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, ()> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("settleRewardPoints")
+            .argument(&token)
+            .argument(&amount_per_point)
+            .argument(&holders)
+            .original_result()
+    }
+
+    pub fn get_reward_points<
+        Arg0: ProxyArg<ManagedAddress<Env::Api>>,
+    >(
+        self,
+        address: Arg0,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, u64> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("getRewardPoints")
+            .argument(&address)
+            .original_result()
+    }
+
+    pub fn get_total_reward_points(
+        self,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, u64> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("getTotalRewardPoints")
+            .original_result()
+    }
+
+    pub fn get_reward_pool<
+        Arg0: ProxyArg<TokenIdentifier<Env::Api>>,
+    >(
+        self,
+        token: Arg0,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, BigUint<Env::Api>> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("getRewardPool")
+            .argument(&token)
+            .original_result()
+    }
+
+    pub fn record_verified_buyer<
+        Arg0: ProxyArg<u32>,
+        Arg1: ProxyArg<ManagedAddress<Env::Api>>,
+    >(
+        self,
+        wine_id: Arg0,
+        buyer: Arg1,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, ()> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("recordVerifiedBuyer")
+            .argument(&wine_id)
+            .argument(&buyer)
+            .original_result()
+    }
+
+    pub fn rate_wine<
+        Arg0: ProxyArg<u32>,
+        Arg1: ProxyArg<u8>,
+        Arg2: ProxyArg<ManagedBuffer<Env::Api>>,
+    >(
+        self,
+        wine_id: Arg0,
+        rating: Arg1,
+        review: Arg2,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, ()> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("rateWine")
+            .argument(&wine_id)
+            .argument(&rating)
+            .argument(&review)
+            .original_result()
+    }
+
+    pub fn update_rating<
+        Arg0: ProxyArg<u32>,
+        Arg1: ProxyArg<u8>,
+        Arg2: ProxyArg<ManagedBuffer<Env::Api>>,
+    >(
+        self,
+        wine_id: Arg0,
+        rating: Arg1,
+        review: Arg2,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, ()> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("updateRating")
+            .argument(&wine_id)
+            .argument(&rating)
+            .argument(&review)
+            .original_result()
+    }
+
+    pub fn delete_rating<
+        Arg0: ProxyArg<u32>,
+    >(
+        self,
+        wine_id: Arg0,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, ()> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("deleteRating")
+            .argument(&wine_id)
+            .original_result()
+    }
+
+    pub fn get_wine_ratings<
+        Arg0: ProxyArg<u32>,
+        Arg1: ProxyArg<u64>,
+        Arg2: ProxyArg<u32>,
+    >(
+        self,
+        wine_id: Arg0,
+        cursor: Arg1,
+        page_size: Arg2,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, MultiValue2<u64, MultiValueEncoded<Env::Api, wine_common::registry::WineRating<Env::Api>>>> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("getWineRatings")
+            .argument(&wine_id)
+            .argument(&cursor)
+            .argument(&page_size)
+            .original_result()
+    }
+
+    pub fn get_wine_rating_count<
+        Arg0: ProxyArg<u32>,
+    >(
+        self,
+        wine_id: Arg0,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, usize> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("getWineRatingCount")
+            .argument(&wine_id)
+            .original_result()
+    }
+
+    pub fn get_wine_average_rating<
+        Arg0: ProxyArg<u32>,
+    >(
+        self,
+        wine_id: Arg0,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, u16> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("getWineAverageRating")
+            .argument(&wine_id)
+            .original_result()
+    }
+
+    pub fn get_wine_weighted_average_rating<
+        Arg0: ProxyArg<u32>,
+    >(
+        self,
+        wine_id: Arg0,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, u16> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("getWineWeightedAverageRating")
+            .argument(&wine_id)
+            .original_result()
+    }
+
+    pub fn get_wine_professional_average_rating<
+        Arg0: ProxyArg<u32>,
+    >(
+        self,
+        wine_id: Arg0,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, u16> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("getWineProfessionalAverageRating")
+            .argument(&wine_id)
+            .original_result()
+    }
+
+    pub fn get_wine_combined_score<
+        Arg0: ProxyArg<u32>,
+    >(
+        self,
+        wine_id: Arg0,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, u16> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("getWineCombinedScore")
+            .argument(&wine_id)
+            .original_result()
+    }
+
+    pub fn is_verified_buyer<
+        Arg0: ProxyArg<u32>,
+        Arg1: ProxyArg<ManagedAddress<Env::Api>>,
+    >(
+        self,
+        wine_id: Arg0,
+        buyer: Arg1,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, bool> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("isVerifiedBuyer")
+            .argument(&wine_id)
+            .argument(&buyer)
+            .original_result()
+    }
+
+    pub fn is_verified_rating<
+        Arg0: ProxyArg<u32>,
+        Arg1: ProxyArg<ManagedAddress<Env::Api>>,
+    >(
+        self,
+        wine_id: Arg0,
+        rater: Arg1,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, bool> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("isVerifiedRating")
+            .argument(&wine_id)
+            .argument(&rater)
+            .original_result()
+    }
+
+    pub fn add_certification_authority<
+        Arg0: ProxyArg<ManagedBuffer<Env::Api>>,
+        Arg1: ProxyArg<ManagedAddress<Env::Api>>,
+    >(
+        self,
+        name: Arg0,
+        authority_address: Arg1,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, u32> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("addCertificationAuthority")
+            .argument(&name)
+            .argument(&authority_address)
+            .original_result()
+    }
+
+    pub fn deactivate_authority<
+        Arg0: ProxyArg<u32>,
+    >(
+        self,
+        authority_id: Arg0,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, ()> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("deactivateAuthority")
+            .argument(&authority_id)
+            .original_result()
+    }
+
+    pub fn reactivate_authority<
+        Arg0: ProxyArg<u32>,
+    >(
+        self,
+        authority_id: Arg0,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, ()> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("reactivateAuthority")
+            .argument(&authority_id)
+            .original_result()
+    }
+
+    pub fn remove_authority<
+        Arg0: ProxyArg<u32>,
+    >(
+        self,
+        authority_id: Arg0,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, ()> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("removeAuthority")
+            .argument(&authority_id)
+            .original_result()
+    }
+
+    pub fn grant_authority_class<
+        Arg0: ProxyArg<u32>,
+        Arg1: ProxyArg<CertificationType>,
+    >(
+        self,
+        authority_id: Arg0,
+        class: Arg1,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, ()> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("grantAuthorityClass")
+            .argument(&authority_id)
+            .argument(&class)
+            .original_result()
+    }
+
+    pub fn revoke_authority_class<
+        Arg0: ProxyArg<u32>,
+        Arg1: ProxyArg<CertificationType>,
+    >(
+        self,
+        authority_id: Arg0,
+        class: Arg1,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, ()> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("revokeAuthorityClass")
+            .argument(&authority_id)
+            .argument(&class)
+            .original_result()
+    }
+
+    pub fn set_attestation_contract<
+        Arg0: ProxyArg<ManagedAddress<Env::Api>>,
+    >(
+        self,
+        attestation_contract: Arg0,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, ()> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("setAttestationContract")
+            .argument(&attestation_contract)
+            .original_result()
+    }
+
+    pub fn certify_wine<
+        Arg0: ProxyArg<u32>,
+        Arg1: ProxyArg<CertificationType>,
+        Arg2: ProxyArg<ManagedBuffer<Env::Api>>,
+        Arg3: ProxyArg<OptionalValue<MultiValue2<u64, u64>>>,
+    >(
+        self,
+        wine_id: Arg0,
+        certification_type: Arg1,
+        certification_hash: Arg2,
+        opt_attestation: Arg3,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, ()> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("certifyWine")
+            .argument(&wine_id)
+            .argument(&certification_type)
+            .argument(&certification_hash)
+            .argument(&opt_attestation)
+            .original_result()
+    }
+
+    pub fn revoke_certification<
+        Arg0: ProxyArg<u32>,
+        Arg1: ProxyArg<usize>,
+        Arg2: ProxyArg<ManagedBuffer<Env::Api>>,
+    >(
+        self,
+        wine_id: Arg0,
+        certification_index: Arg1,
+        reason: Arg2,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, ()> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("revokeCertification")
+            .argument(&wine_id)
+            .argument(&certification_index)
+            .argument(&reason)
+            .original_result()
+    }
+
+    pub fn get_certification_authority<
+        Arg0: ProxyArg<u32>,
+    >(
+        self,
+        authority_id: Arg0,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, CertificationAuthority<Env::Api>> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("getCertificationAuthority")
+            .argument(&authority_id)
+            .original_result()
+    }
+
+    pub fn get_certification_details_version<
+        Arg0: ProxyArg<u32>,
+        Arg1: ProxyArg<usize>,
+    >(
+        self,
+        wine_id: Arg0,
+        index: Arg1,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, u32> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("getCertificationDetailsVersion")
+            .argument(&wine_id)
+            .argument(&index)
+            .original_result()
+    }
+
+    pub fn get_certification_type<
+        Arg0: ProxyArg<u32>,
+        Arg1: ProxyArg<usize>,
+    >(
+        self,
+        wine_id: Arg0,
+        index: Arg1,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, OptionalValue<CertificationType>> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("getCertificationType")
+            .argument(&wine_id)
+            .argument(&index)
+            .original_result()
+    }
+
+    pub fn get_certifications_by_type<
+        Arg0: ProxyArg<u32>,
+        Arg1: ProxyArg<CertificationType>,
+        Arg2: ProxyArg<u64>,
+        Arg3: ProxyArg<u32>,
+    >(
+        self,
+        wine_id: Arg0,
+        certification_type: Arg1,
+        cursor: Arg2,
+        page_size: Arg3,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, MultiValue2<u64, MultiValueEncoded<Env::Api, usize>>> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("getCertificationsByType")
+            .argument(&wine_id)
+            .argument(&certification_type)
+            .argument(&cursor)
+            .argument(&page_size)
+            .original_result()
+    }
+
+    pub fn get_authority_classes<
+        Arg0: ProxyArg<u32>,
+    >(
+        self,
+        authority_id: Arg0,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, MultiValueEncoded<Env::Api, CertificationType>> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("getAuthorityClasses")
+            .argument(&authority_id)
+            .original_result()
+    }
+
+    pub fn get_certification_issuer<
+        Arg0: ProxyArg<u32>,
+        Arg1: ProxyArg<usize>,
+    >(
+        self,
+        wine_id: Arg0,
+        index: Arg1,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, ManagedAddress<Env::Api>> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("getCertificationIssuer")
+            .argument(&wine_id)
+            .argument(&index)
+            .original_result()
+    }
+
+    pub fn get_certification_attestation<
+        Arg0: ProxyArg<u32>,
+        Arg1: ProxyArg<usize>,
+    >(
+        self,
+        wine_id: Arg0,
+        index: Arg1,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, OptionalValue<AttestationReference<Env::Api>>> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("getCertificationAttestation")
+            .argument(&wine_id)
+            .argument(&index)
+            .original_result()
+    }
+
+    pub fn get_certification_revocation<
+        Arg0: ProxyArg<u32>,
+        Arg1: ProxyArg<usize>,
+    >(
+        self,
+        wine_id: Arg0,
+        index: Arg1,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, OptionalValue<CertificationRevocation<Env::Api>>> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("getCertificationRevocation")
+            .argument(&wine_id)
+            .argument(&index)
+            .original_result()
+    }
+
+    pub fn get_valid_certification_count<
+        Arg0: ProxyArg<u32>,
+    >(
+        self,
+        wine_id: Arg0,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, usize> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("getValidCertificationCount")
+            .argument(&wine_id)
+            .original_result()
+    }
+
+    pub fn is_certification_authority<
+        Arg0: ProxyArg<ManagedAddress<Env::Api>>,
+    >(
+        self,
+        address: Arg0,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, bool> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("isCertificationAuthority")
+            .argument(&address)
+            .original_result()
+    }
+
+    pub fn get_authority_id<
+        Arg0: ProxyArg<ManagedAddress<Env::Api>>,
+    >(
+        self,
+        address: Arg0,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, u32> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("getAuthorityId")
+            .argument(&address)
+            .original_result()
+    }
+
+    pub fn attestation_contract(
+        self,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, SingleValueMapper<ManagedAddress<Env::Api>>> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("getAttestationContract")
+            .original_result()
+    }
+
+    pub fn set_wine_status<
+        Arg0: ProxyArg<u32>,
+        Arg1: ProxyArg<WineStatus>,
+    >(
+        self,
+        wine_id: Arg0,
+        status: Arg1,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, ()> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("setWineStatus")
+            .argument(&wine_id)
+            .argument(&status)
+            .original_result()
+    }
+
+    pub fn recall_wine<
+        Arg0: ProxyArg<u32>,
+        Arg1: ProxyArg<ManagedBuffer<Env::Api>>,
+        Arg2: ProxyArg<OptionalValue<bool>>,
+    >(
+        self,
+        wine_id: Arg0,
+        reason: Arg1,
+        opt_cancel_listings: Arg2,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, ()> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("recallWine")
+            .argument(&wine_id)
+            .argument(&reason)
+            .argument(&opt_cancel_listings)
+            .original_result()
+    }
+
+    pub fn retire_wine<
+        Arg0: ProxyArg<u32>,
+    >(
+        self,
+        wine_id: Arg0,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, ()> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("retireWine")
+            .argument(&wine_id)
+            .original_result()
+    }
+
+    pub fn get_wine_status<
+        Arg0: ProxyArg<u32>,
+    >(
+        self,
+        wine_id: Arg0,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, WineStatus> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("getWineStatus")
+            .argument(&wine_id)
+            .original_result()
+    }
+
+    pub fn get_wine_status_history<
+        Arg0: ProxyArg<u32>,
+    >(
+        self,
+        wine_id: Arg0,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, MultiValueEncoded<Env::Api, WineStatusChange<Env::Api>>> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("getWineStatusHistory")
+            .argument(&wine_id)
+            .original_result()
+    }
+
+    pub fn get_wine_recall_reason<
+        Arg0: ProxyArg<u32>,
+    >(
+        self,
+        wine_id: Arg0,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, OptionalValue<ManagedBuffer<Env::Api>>> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("getWineRecallReason")
+            .argument(&wine_id)
+            .original_result()
+    }
+
+    pub fn register_vineyard<
+        Arg0: ProxyArg<ManagedBuffer<Env::Api>>,
+        Arg1: ProxyArg<ManagedBuffer<Env::Api>>,
+        Arg2: ProxyArg<ManagedByteArray<Env::Api, 32>>,
+        Arg3: ProxyArg<u32>,
+    >(
+        self,
+        name: Arg0,
+        region: Arg1,
+        coordinates_hash: Arg2,
+        size_hectares: Arg3,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, u32> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("registerVineyard")
+            .argument(&name)
+            .argument(&region)
+            .argument(&coordinates_hash)
+            .argument(&size_hectares)
+            .original_result()
+    }
+
+    pub fn set_wine_vineyard<
+        Arg0: ProxyArg<u32>,
+        Arg1: ProxyArg<u32>,
+    >(
+        self,
+        wine_id: Arg0,
+        vineyard_id: Arg1,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, ()> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("setWineVineyard")
+            .argument(&wine_id)
+            .argument(&vineyard_id)
+            .original_result()
+    }
+
+    pub fn get_vineyard<
+        Arg0: ProxyArg<u32>,
+    >(
+        self,
+        vineyard_id: Arg0,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, Vineyard<Env::Api>> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("getVineyard")
+            .argument(&vineyard_id)
+            .original_result()
+    }
+
+    pub fn get_wine_vineyard<
+        Arg0: ProxyArg<u32>,
+    >(
+        self,
+        wine_id: Arg0,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, u32> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("getWineVineyard")
+            .argument(&wine_id)
+            .original_result()
+    }
+
+    pub fn get_vineyard_wines<
+        Arg0: ProxyArg<u32>,
+        Arg1: ProxyArg<u64>,
+        Arg2: ProxyArg<u32>,
+    >(
+        self,
+        vineyard_id: Arg0,
+        cursor: Arg1,
+        page_size: Arg2,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, MultiValue2<u64, MultiValueEncoded<Env::Api, u32>>> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("getVineyardWines")
+            .argument(&vineyard_id)
+            .argument(&cursor)
+            .argument(&page_size)
+            .original_result()
+    }
+
+    pub fn is_wine_region_consistent<
+        Arg0: ProxyArg<u32>,
+    >(
+        self,
+        wine_id: Arg0,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, bool> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("isWineRegionConsistent")
+            .argument(&wine_id)
+            .original_result()
+    }
+
+    pub fn add_region<
+        Arg0: ProxyArg<ManagedBuffer<Env::Api>>,
+    >(
+        self,
+        region: Arg0,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, ()> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("addRegion")
+            .argument(&region)
+            .original_result()
+    }
+
+    pub fn remove_region<
+        Arg0: ProxyArg<ManagedBuffer<Env::Api>>,
+    >(
+        self,
+        region: Arg0,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, ()> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("removeRegion")
+            .argument(&region)
+            .original_result()
+    }
+
+    pub fn is_valid_region<
+        Arg0: ProxyArg<ManagedBuffer<Env::Api>>,
+    >(
+        self,
+        region: Arg0,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, bool> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("isValidRegion")
+            .argument(&region)
+            .original_result()
+    }
+
+    pub fn get_regions<
+        Arg0: ProxyArg<u64>,
+        Arg1: ProxyArg<u32>,
+    >(
+        self,
+        cursor: Arg0,
+        page_size: Arg1,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, MultiValue2<u64, MultiValueEncoded<Env::Api, ManagedBuffer<Env::Api>>>> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("getRegions")
+            .argument(&cursor)
+            .argument(&page_size)
+            .original_result()
+    }
+
+    pub fn get_region_count(
+        self,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, usize> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("getRegionCount")
+            .original_result()
+    }
+
+    pub fn verify_producer<
+        Arg0: ProxyArg<ManagedAddress<Env::Api>>,
+    >(
+        self,
+        producer: Arg0,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, ()> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("verifyProducer")
+            .argument(&producer)
+            .original_result()
+    }
+
+    pub fn revoke_producer_verification<
+        Arg0: ProxyArg<ManagedAddress<Env::Api>>,
+    >(
+        self,
+        producer: Arg0,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, ()> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("revokeProducerVerification")
+            .argument(&producer)
+            .original_result()
+    }
+
+    pub fn get_producer_stats<
+        Arg0: ProxyArg<ManagedAddress<Env::Api>>,
+    >(
+        self,
+        producer: Arg0,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, ProducerStats> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("getProducerStats")
+            .argument(&producer)
+            .original_result()
+    }
+
+    pub fn is_verified_producer<
+        Arg0: ProxyArg<ManagedAddress<Env::Api>>,
+    >(
+        self,
+        producer: Arg0,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, bool> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("isVerifiedProducer")
+            .argument(&producer)
+            .original_result()
+    }
+
+    pub fn set_grade_price_floor<
+        Arg0: ProxyArg<u8>,
+        Arg1: ProxyArg<BigUint<Env::Api>>,
+    >(
+        self,
+        quality_grade: Arg0,
+        min_price: Arg1,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, ()> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("setGradePriceFloor")
+            .argument(&quality_grade)
+            .argument(&min_price)
+            .original_result()
+    }
+
+    pub fn get_grade_price_floor<
+        Arg0: ProxyArg<u8>,
+    >(
+        self,
+        quality_grade: Arg0,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, BigUint<Env::Api>> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("getGradePriceFloor")
+            .argument(&quality_grade)
+            .original_result()
+    }
+
+    pub fn get_grade_price_floors(
+        self,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, MultiValueEncoded<Env::Api, MultiValue2<u8, BigUint<Env::Api>>>> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("getGradePriceFloors")
+            .original_result()
+    }
+
+    pub fn report_counterfeit<
+        Arg0: ProxyArg<u32>,
+        Arg1: ProxyArg<ManagedBuffer<Env::Api>>,
+    >(
+        self,
+        wine_id: Arg0,
+        evidence_ipfs_hash: Arg1,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, usize> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("reportCounterfeit")
+            .argument(&wine_id)
+            .argument(&evidence_ipfs_hash)
+            .original_result()
+    }
+
+    pub fn dismiss_counterfeit_report<
+        Arg0: ProxyArg<usize>,
+    >(
+        self,
+        report_id: Arg0,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, ()> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("dismissCounterfeitReport")
+            .argument(&report_id)
+            .original_result()
+    }
+
+    pub fn confirm_counterfeit_report<
+        Arg0: ProxyArg<usize>,
+    >(
+        self,
+        report_id: Arg0,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, ()> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("confirmCounterfeitReport")
+            .argument(&report_id)
+            .original_result()
+    }
+
+    pub fn clear_counterfeit_flag<
+        Arg0: ProxyArg<u32>,
+    >(
+        self,
+        wine_id: Arg0,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, ()> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("clearCounterfeitFlag")
+            .argument(&wine_id)
+            .original_result()
+    }
+
+    pub fn get_counterfeit_report<
+        Arg0: ProxyArg<usize>,
+    >(
+        self,
+        report_id: Arg0,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, CounterfeitReport<Env::Api>> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("getCounterfeitReport")
+            .argument(&report_id)
+            .original_result()
+    }
+
+    pub fn get_open_counterfeit_reports<
+        Arg0: ProxyArg<u64>,
+        Arg1: ProxyArg<u32>,
+    >(
+        self,
+        cursor: Arg0,
+        page_size: Arg1,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, MultiValue2<u64, MultiValueEncoded<Env::Api, usize>>> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("getOpenCounterfeitReports")
+            .argument(&cursor)
+            .argument(&page_size)
+            .original_result()
+    }
+
+    pub fn get_wine_counterfeit_reports<
+        Arg0: ProxyArg<u32>,
+        Arg1: ProxyArg<u64>,
+        Arg2: ProxyArg<u32>,
+    >(
+        self,
+        wine_id: Arg0,
+        cursor: Arg1,
+        page_size: Arg2,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, MultiValue2<u64, MultiValueEncoded<Env::Api, usize>>> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("getWineCounterfeitReports")
+            .argument(&wine_id)
+            .argument(&cursor)
+            .argument(&page_size)
+            .original_result()
+    }
+
+    pub fn register_sommelier<
+        Arg0: ProxyArg<ManagedAddress<Env::Api>>,
+    >(
+        self,
+        sommelier: Arg0,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, ()> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("registerSommelier")
+            .argument(&sommelier)
+            .original_result()
+    }
+
+    pub fn remove_sommelier<
+        Arg0: ProxyArg<ManagedAddress<Env::Api>>,
+    >(
+        self,
+        sommelier: Arg0,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, ()> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("removeSommelier")
+            .argument(&sommelier)
+            .original_result()
+    }
+
+    pub fn is_sommelier<
+        Arg0: ProxyArg<ManagedAddress<Env::Api>>,
+    >(
+        self,
+        address: Arg0,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, bool> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("isSommelier")
+            .argument(&address)
+            .original_result()
+    }
+
+    pub fn get_sommeliers<
+        Arg0: ProxyArg<u64>,
+        Arg1: ProxyArg<u32>,
+    >(
+        self,
+        cursor: Arg0,
+        page_size: Arg1,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, MultiValue2<u64, MultiValueEncoded<Env::Api, ManagedAddress<Env::Api>>>> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("getSommeliers")
+            .argument(&cursor)
+            .argument(&page_size)
+            .original_result()
+    }
+
+    pub fn is_professional_rating<
+        Arg0: ProxyArg<u32>,
+        Arg1: ProxyArg<ManagedAddress<Env::Api>>,
+    >(
+        self,
+        wine_id: Arg0,
+        rater: Arg1,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, bool> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("isProfessionalRating")
+            .argument(&wine_id)
+            .argument(&rater)
+            .original_result()
+    }
+
+    pub fn reindex_wines<
+        Arg0: ProxyArg<u32>,
+        Arg1: ProxyArg<u32>,
+    >(
+        self,
+        first_wine_id: Arg0,
+        count: Arg1,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, ()> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("reindexWines")
+            .argument(&first_wine_id)
+            .argument(&count)
+            .original_result()
+    }
+
+    pub fn get_all_wines<
+        Arg0: ProxyArg<u64>,
+        Arg1: ProxyArg<u32>,
+    >(
+        self,
+        cursor: Arg0,
+        page_size: Arg1,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, MultiValue2<u64, MultiValueEncoded<Env::Api, MultiValue2<u32, wine_common::registry::WineDetails<Env::Api>>>>> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("getAllWines")
+            .argument(&cursor)
+            .argument(&page_size)
+            .original_result()
+    }
+
+    pub fn get_wines_by_region<
+        Arg0: ProxyArg<ManagedBuffer<Env::Api>>,
+        Arg1: ProxyArg<u64>,
+        Arg2: ProxyArg<u32>,
+    >(
+        self,
+        region: Arg0,
+        cursor: Arg1,
+        page_size: Arg2,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, MultiValue2<u64, MultiValueEncoded<Env::Api, u32>>> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("getWinesByRegion")
+            .argument(&region)
+            .argument(&cursor)
+            .argument(&page_size)
+            .original_result()
+    }
+
+    pub fn get_wines_by_vintage<
+        Arg0: ProxyArg<u32>,
+        Arg1: ProxyArg<u64>,
+        Arg2: ProxyArg<u32>,
+    >(
+        self,
+        vintage_year: Arg0,
+        cursor: Arg1,
+        page_size: Arg2,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, MultiValue2<u64, MultiValueEncoded<Env::Api, u32>>> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("getWinesByVintage")
+            .argument(&vintage_year)
+            .argument(&cursor)
+            .argument(&page_size)
+            .original_result()
+    }
+
+    pub fn get_wines_by_variety<
+        Arg0: ProxyArg<ManagedBuffer<Env::Api>>,
+        Arg1: ProxyArg<u64>,
+        Arg2: ProxyArg<u32>,
+    >(
+        self,
+        variety: Arg0,
+        cursor: Arg1,
+        page_size: Arg2,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, MultiValue2<u64, MultiValueEncoded<Env::Api, u32>>> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("getWinesByVariety")
+            .argument(&variety)
+            .argument(&cursor)
+            .argument(&page_size)
+            .original_result()
+    }
+
+    pub fn add_tag_option<
+        Arg0: ProxyArg<TagCategory>,
+        Arg1: ProxyArg<ManagedBuffer<Env::Api>>,
+    >(
+        self,
+        category: Arg0,
+        tag: Arg1,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, ()> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("addTagOption")
+            .argument(&category)
+            .argument(&tag)
+            .original_result()
+    }
+
+    pub fn remove_tag_option<
+        Arg0: ProxyArg<TagCategory>,
+        Arg1: ProxyArg<ManagedBuffer<Env::Api>>,
+    >(
+        self,
+        category: Arg0,
+        tag: Arg1,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, ()> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("removeTagOption")
+            .argument(&category)
+            .argument(&tag)
+            .original_result()
+    }
+
+    pub fn add_wine_tags<
+        Arg0: ProxyArg<u32>,
+        Arg1: ProxyArg<TagCategory>,
+        Arg2: ProxyArg<MultiValueEncoded<Env::Api, ManagedBuffer<Env::Api>>>,
+    >(
+        self,
+        wine_id: Arg0,
+        category: Arg1,
+        tags: Arg2,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, ()> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("addWineTags")
+            .argument(&wine_id)
+            .argument(&category)
+            .argument(&tags)
+            .original_result()
+    }
+
+    pub fn remove_wine_tags<
+        Arg0: ProxyArg<u32>,
+        Arg1: ProxyArg<TagCategory>,
+        Arg2: ProxyArg<MultiValueEncoded<Env::Api, ManagedBuffer<Env::Api>>>,
+    >(
+        self,
+        wine_id: Arg0,
+        category: Arg1,
+        tags: Arg2,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, ()> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("removeWineTags")
+            .argument(&wine_id)
+            .argument(&category)
+            .argument(&tags)
+            .original_result()
+    }
+
+    pub fn get_tag_options<
+        Arg0: ProxyArg<TagCategory>,
+    >(
+        self,
+        category: Arg0,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, MultiValueEncoded<Env::Api, ManagedBuffer<Env::Api>>> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("getTagOptions")
+            .argument(&category)
+            .original_result()
+    }
+
+    pub fn get_wine_tags<
+        Arg0: ProxyArg<u32>,
+    >(
+        self,
+        wine_id: Arg0,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, MultiValueEncoded<Env::Api, MultiValue2<TagCategory, ManagedBuffer<Env::Api>>>> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("getWineTags")
+            .argument(&wine_id)
+            .original_result()
+    }
+
+    pub fn get_wines_by_tag<
+        Arg0: ProxyArg<TagCategory>,
+        Arg1: ProxyArg<ManagedBuffer<Env::Api>>,
+        Arg2: ProxyArg<u64>,
+        Arg3: ProxyArg<u32>,
+    >(
+        self,
+        category: Arg0,
+        tag: Arg1,
+        cursor: Arg2,
+        page_size: Arg3,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, MultiValue2<u64, MultiValueEncoded<Env::Api, u32>>> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("getWinesByTag")
+            .argument(&category)
+            .argument(&tag)
+            .argument(&cursor)
+            .argument(&page_size)
+            .original_result()
+    }
+
+    pub fn register_blend<
+        Arg0: ProxyArg<WineRegistration<Env::Api>>,
+        Arg1: ProxyArg<MultiValueEncoded<Env::Api, MultiValue2<u32, u32>>>,
+    >(
+        self,
+        registration: Arg0,
+        components: Arg1,
+    ) -> TxTypedCall<Env, From, To, (), Gas, u32> {
+        self.wrapped_tx
+            .raw_call("registerBlend")
+            .argument(&registration)
+            .argument(&components)
+            .original_result()
+    }
+
+    pub fn get_blend_composition<
+        Arg0: ProxyArg<u32>,
+    >(
+        self,
+        wine_id: Arg0,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, MultiValueEncoded<Env::Api, BlendComponent>> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("getBlendComposition")
+            .argument(&wine_id)
+            .original_result()
+    }
+
+    pub fn add_vintage_report<
+        Arg0: ProxyArg<u32>,
+        Arg1: ProxyArg<ManagedByteArray<Env::Api, 32>>,
+        Arg2: ProxyArg<u32>,
+        Arg3: ProxyArg<u32>,
+        Arg4: ProxyArg<ManagedBuffer<Env::Api>>,
+    >(
+        self,
+        wine_id: Arg0,
+        weather_summary_hash: Arg1,
+        yield_kg_per_hectare: Arg2,
+        sugar_level_brix: Arg3,
+        report_ipfs_hash: Arg4,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, usize> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("addVintageReport")
+            .argument(&wine_id)
+            .argument(&weather_summary_hash)
+            .argument(&yield_kg_per_hectare)
+            .argument(&sugar_level_brix)
+            .argument(&report_ipfs_hash)
+            .original_result()
+    }
+
+    pub fn get_vintage_reports<
+        Arg0: ProxyArg<u32>,
+        Arg1: ProxyArg<u64>,
+        Arg2: ProxyArg<u32>,
+    >(
+        self,
+        wine_id: Arg0,
+        cursor: Arg1,
+        page_size: Arg2,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, MultiValue2<u64, MultiValueEncoded<Env::Api, VintageReport<Env::Api>>>> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("getVintageReports")
+            .argument(&wine_id)
+            .argument(&cursor)
+            .argument(&page_size)
+            .original_result()
+    }
+
+    pub fn migrate_storage<
+        Arg0: ProxyArg<u32>,
+    >(
+        self,
+        batch_size: Arg0,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, bool> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("migrateStorage")
+            .argument(&batch_size)
+            .original_result()
+    }
+
+    pub fn get_storage_version(
+        self,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, u32> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("getStorageVersion")
+            .original_result()
+    }
+
+    pub fn get_migration_cursor(
+        self,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, u32> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("getMigrationCursor")
+            .original_result()
+    }
+
+    pub fn add_storage_logger<
+        Arg0: ProxyArg<ManagedAddress<Env::Api>>,
+    >(
+        self,
+        logger: Arg0,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, ()> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("addStorageLogger")
+            .argument(&logger)
+            .original_result()
+    }
+
+    pub fn remove_storage_logger<
+        Arg0: ProxyArg<ManagedAddress<Env::Api>>,
+    >(
+        self,
+        logger: Arg0,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, ()> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("removeStorageLogger")
+            .argument(&logger)
+            .original_result()
+    }
+
+    pub fn log_storage_condition<
+        Arg0: ProxyArg<u32>,
+        Arg1: ProxyArg<i32>,
+        Arg2: ProxyArg<u32>,
+        Arg3: ProxyArg<ManagedByteArray<Env::Api, 32>>,
+    >(
+        self,
+        wine_id: Arg0,
+        temperature_x100: Arg1,
+        humidity_x100: Arg2,
+        location_hash: Arg3,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, u64> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("logStorageCondition")
+            .argument(&wine_id)
+            .argument(&temperature_x100)
+            .argument(&humidity_x100)
+            .argument(&location_hash)
+            .original_result()
+    }
+
+    pub fn is_storage_logger<
+        Arg0: ProxyArg<ManagedAddress<Env::Api>>,
+    >(
+        self,
+        address: Arg0,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, bool> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("isStorageLogger")
+            .argument(&address)
+            .original_result()
+    }
+
+    pub fn get_storage_condition_count<
+        Arg0: ProxyArg<u32>,
+    >(
+        self,
+        wine_id: Arg0,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, u64> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("getStorageConditionCount")
+            .argument(&wine_id)
+            .original_result()
+    }
+
+    pub fn get_latest_storage_conditions<
+        Arg0: ProxyArg<u32>,
+        Arg1: ProxyArg<u32>,
+    >(
+        self,
+        wine_id: Arg0,
+        count: Arg1,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, MultiValueEncoded<Env::Api, StorageCondition<Env::Api>>> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("getLatestStorageConditions")
+            .argument(&wine_id)
+            .argument(&count)
+            .original_result()
+    }
+
+    pub fn get_page_size_limits(
+        self,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, MultiValueEncoded<Env::Api, MultiValue2<ManagedBuffer<Env::Api>, u32>>> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("getPageSizeLimits")
+            .original_result()
+    }
+
+    pub fn grant_role<
+        Arg0: ProxyArg<wine_roles::Role>,
+        Arg1: ProxyArg<ManagedAddress<Env::Api>>,
+    >(
+        self,
+        role: Arg0,
+        address: Arg1,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, ()> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("grantRole")
+            .argument(&role)
+            .argument(&address)
+            .original_result()
+    }
+
+    pub fn revoke_role<
+        Arg0: ProxyArg<wine_roles::Role>,
+        Arg1: ProxyArg<ManagedAddress<Env::Api>>,
+    >(
+        self,
+        role: Arg0,
+        address: Arg1,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, ()> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("revokeRole")
+            .argument(&role)
+            .argument(&address)
+            .original_result()
+    }
+
+    pub fn pause(
+        self,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, ()> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("pause")
+            .original_result()
+    }
+
+    pub fn unpause(
+        self,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, ()> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("unpause")
+            .original_result()
+    }
+
+    pub fn has_role<
+        Arg0: ProxyArg<wine_roles::Role>,
+        Arg1: ProxyArg<ManagedAddress<Env::Api>>,
+    >(
+        self,
+        role: Arg0,
+        address: Arg1,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, bool> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("hasRole")
+            .argument(&role)
+            .argument(&address)
+            .original_result()
+    }
+
+    pub fn get_role_members<
+        Arg0: ProxyArg<wine_roles::Role>,
+    >(
+        self,
+        role: Arg0,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, MultiValueEncoded<Env::Api, ManagedAddress<Env::Api>>> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("getRoleMembers")
+            .argument(&role)
+            .original_result()
+    }
+
+    pub fn is_paused(
+        self,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, bool> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("isPaused")
+            .original_result()
+    }
+}
+
+#[type_abi]
+#[derive(TopEncode, TopDecode, NestedEncode, NestedDecode, PartialEq, Debug, Default)]
+pub struct IntegrationPermissions {
+    pub can_decrement_availability: bool,
+    pub can_record_sale: bool,
+    pub can_flag: bool,
+}
+
+#[type_abi]
+#[derive(TopEncode, TopDecode, NestedEncode, NestedDecode, PartialEq, Debug, Clone, Copy)]
+pub enum OwnershipChangeReason {
+    Registration,
+    Sale,
+    Gift,
+    Marketplace,
+}
+
+#[type_abi]
+#[derive(TopEncode, TopDecode, NestedEncode, NestedDecode, PartialEq, Debug, Clone, Copy)]
+pub enum SensitiveAction {
+    Recall,
+    Transfer,
+    PriceUpdate,
+}
+
+#[type_abi]
+#[derive(TopEncode, TopDecode, NestedEncode, NestedDecode, PartialEq, Debug, Clone)]
+pub enum WineAction<Api>
+where
+    Api: ManagedTypeApi,
+{
+    Recall,
+    Transfer(ManagedAddress<Api>),
+    PriceUpdate(BigUint<Api>),
+}
+
+#[type_abi]
+#[derive(TopEncode, TopDecode, NestedEncode, NestedDecode, PartialEq, Debug)]
+pub struct ProvenanceRecord<Api>
+where
+    Api: ManagedTypeApi,
+{
+    pub owner: ManagedAddress<Api>,
+    pub timestamp: u64,
+    pub reason: OwnershipChangeReason,
+}
+
+#[type_abi]
+#[derive(TopEncode, TopDecode, NestedEncode, NestedDecode, PartialEq, Debug, Clone)]
+pub struct WineRegistration<Api>
+where
+    Api: ManagedTypeApi,
+{
+    pub vineyard: ManagedBuffer<Api>,
+    pub variety: ManagedBuffer<Api>,
+    pub vintage_year: u32,
+    pub quality_grade: u8,
+    pub alcohol_content: u16,
+    pub region: ManagedBuffer<Api>,
+    pub certification: ManagedBuffer<Api>,
+    pub producer_signature: ManagedBuffer<Api>,
+    pub ipfs_hash: ManagedBuffer<Api>,
+    pub total_bottles: u32,
+    pub price_per_bottle: BigUint<Api>,
+    pub is_organic: bool,
+    pub harvest_date: u64,
+    pub aging_process: ManagedBuffer<Api>,
+    pub tasting_notes: ManagedBuffer<Api>,
+}
+
+#[type_abi]
+#[derive(TopEncode, TopDecode, NestedEncode, NestedDecode, PartialEq, Debug)]
+pub struct WineDetailsVersion<Api>
+where
+    Api: ManagedTypeApi,
+{
+    pub version: u32,
+    pub details_hash: ManagedByteArray<Api, 32usize>,
+    pub timestamp: u64,
+}
+
+#[type_abi]
+#[derive(TopEncode, TopDecode, NestedEncode, NestedDecode, PartialEq, Debug)]
+pub struct WinePriceChange<Api>
+where
+    Api: ManagedTypeApi,
+{
+    pub price_per_bottle: BigUint<Api>,
+    pub timestamp: u64,
+}
+
+#[type_abi]
+#[derive(TopEncode, TopDecode, NestedEncode, NestedDecode, PartialEq, Debug)]
+pub struct RegistrationFeeChange<Api>
+where
+    Api: ManagedTypeApi,
+{
+    pub token: EgldOrEsdtTokenIdentifier<Api>,
+    pub amount: BigUint<Api>,
+    pub timestamp: u64,
+}
+
+#[type_abi]
+#[derive(TopEncode, TopDecode, NestedEncode, NestedDecode, PartialEq, Debug)]
+pub struct Bottle {
+    pub nonce: u64,
+    pub serial: u32,
+    pub status: BottleStatus,
+    pub minted_timestamp: u64,
+}
+
+#[type_abi]
+#[derive(TopEncode, TopDecode, NestedEncode, NestedDecode, PartialEq, Debug, Clone, Copy)]
+pub enum BottleStatus {
+    Sealed,
+    Redeemed,
+}
+
+#[type_abi]
+#[derive(TopEncode, TopDecode, NestedEncode, NestedDecode, PartialEq, Debug)]
+pub struct BottleScan<Api>
+where
+    Api: ManagedTypeApi,
+{
+    pub scanner: ManagedAddress<Api>,
+    pub timestamp: u64,
+    pub authentic: bool,
+}
+
+#[type_abi]
+#[derive(TopEncode, TopDecode, PartialEq, Debug)]
+pub struct CertificationAuthority<Api>
+where
+    Api: ManagedTypeApi,
+{
+    pub name: ManagedBuffer<Api>,
+    pub authority_address: ManagedAddress<Api>,
+    pub is_active: bool,
+    pub certification_count: u32,
+}
+
+#[type_abi]
+#[derive(TopEncode, TopDecode, PartialEq, Debug)]
+pub struct AttestationReference<Api>
+where
+    Api: ManagedTypeApi,
+{
+    pub attestation_contract: ManagedAddress<Api>,
+    pub attestation_id: u64,
+    pub expiry_timestamp: u64,
+}
+
+#[type_abi]
+#[derive(TopEncode, TopDecode, PartialEq, Debug)]
+pub struct CertificationRevocation<Api>
+where
+    Api: ManagedTypeApi,
+{
+    pub revoked_by: ManagedAddress<Api>,
+    pub reason: ManagedBuffer<Api>,
+    pub timestamp: u64,
+}
+
+#[type_abi]
+#[derive(TopEncode, TopDecode, NestedEncode, NestedDecode, PartialEq, Debug)]
+pub struct WineStatusChange<Api>
+where
+    Api: ManagedTypeApi,
+{
+    pub status: WineStatus,
+    pub timestamp: u64,
+    pub changed_by: ManagedAddress<Api>,
+}
+
+#[type_abi]
+#[derive(TopEncode, TopDecode, PartialEq, Debug)]
+pub struct Vineyard<Api>
+where
+    Api: ManagedTypeApi,
+{
+    pub name: ManagedBuffer<Api>,
+    pub region: ManagedBuffer<Api>,
+    pub coordinates_hash: ManagedByteArray<Api, 32usize>,
+    pub size_hectares: u32,
+    pub owner: ManagedAddress<Api>,
+    pub registered_timestamp: u64,
+}
+
+#[type_abi]
+#[derive(TopEncode, TopDecode, PartialEq, Debug, Default)]
+pub struct ProducerStats {
+    pub wines_registered: u32,
+    pub bottles_minted: u64,
+    pub bottles_sold: u64,
+    pub rating_count: u32,
+    pub rating_sum: u64,
+    pub average_rating: u16,
+    pub certifications: u32,
+}
+
+#[type_abi]
+#[derive(TopEncode, TopDecode, PartialEq, Debug)]
+pub struct CounterfeitReport<Api>
+where
+    Api: ManagedTypeApi,
+{
+    pub wine_id: u32,
+    pub reporter: ManagedAddress<Api>,
+    pub evidence_ipfs_hash: ManagedBuffer<Api>,
+    pub timestamp: u64,
+    pub status: CounterfeitReportStatus,
+    pub resolved_by: ManagedAddress<Api>,
+    pub resolved_timestamp: u64,
+}
+
+#[type_abi]
+#[derive(TopEncode, TopDecode, NestedEncode, NestedDecode, PartialEq, Debug, Clone, Copy)]
+pub enum CounterfeitReportStatus {
+    Open,
+    Dismissed,
+    Confirmed,
+}
+
+#[type_abi]
+#[derive(TopEncode, TopDecode, NestedEncode, NestedDecode, PartialEq, Debug)]
+pub struct BlendComponent {
+    pub wine_id: u32,
+    pub share_bps: u32,
+}
+
+#[type_abi]
+#[derive(TopEncode, TopDecode, NestedEncode, NestedDecode, PartialEq, Debug)]
+pub struct VintageReport<Api>
+where
+    Api: ManagedTypeApi,
+{
+    pub author: ManagedAddress<Api>,
+    pub weather_summary_hash: ManagedByteArray<Api, 32usize>,
+    pub yield_kg_per_hectare: u32,
+    pub sugar_level_brix: u32,
+    pub report_ipfs_hash: ManagedBuffer<Api>,
+    pub timestamp: u64,
+}
+
+#[type_abi]
+#[derive(TopEncode, TopDecode, NestedEncode, NestedDecode, PartialEq, Debug)]
+pub struct StorageCondition<Api>
+where
+    Api: ManagedTypeApi,
+{
+    pub logger: ManagedAddress<Api>,
+    pub temperature_x100: i32,
+    pub humidity_x100: u32,
+    pub location_hash: ManagedByteArray<Api, 32usize>,
+    pub timestamp: u64,
+}
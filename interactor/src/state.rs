@@ -0,0 +1,60 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use multiversx_sc_snippets::imports::*;
+use serde::{Deserialize, Serialize};
+
+pub const DEFAULT_STATE_FILE: &str = "state.toml";
+
+// Addresses of the contracts deployed by the interactor, kept between runs so later commands
+// know which contracts to call
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct State {
+    #[serde(skip)]
+    path: PathBuf,
+    registry_address: Option<String>,
+    marketplace_address: Option<String>,
+}
+
+impl State {
+    // A missing file is an empty state; nothing has been deployed yet
+    pub fn load(path: &Path) -> Self {
+        let mut state: State = match fs::read_to_string(path) {
+            Ok(content) => toml::from_str(&content).expect("invalid state file"),
+            Err(_) => State::default(),
+        };
+        state.path = path.to_path_buf();
+        state
+    }
+
+    pub fn registry(&self) -> Bech32Address {
+        let address = self
+            .registry_address
+            .clone()
+            .expect("wine-registry is not deployed, run deploy-registry first");
+        Bech32Address::from_bech32_string(address)
+    }
+
+    pub fn marketplace(&self) -> Bech32Address {
+        let address = self
+            .marketplace_address
+            .clone()
+            .expect("wine-marketplace is not deployed, run deploy-marketplace first");
+        Bech32Address::from_bech32_string(address)
+    }
+
+    pub fn set_registry(&mut self, address: &Bech32Address) {
+        self.registry_address = Some(address.to_bech32_string());
+        self.save();
+    }
+
+    pub fn set_marketplace(&mut self, address: &Bech32Address) {
+        self.marketplace_address = Some(address.to_bech32_string());
+        self.save();
+    }
+
+    fn save(&self) {
+        let content = toml::to_string(self).expect("failed to encode state");
+        fs::write(&self.path, content).expect("failed to write state file");
+    }
+}